@@ -2084,20 +2084,43 @@ impl AcpThread {
         output_byte_limit: Option<u64>,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
-        let env = match &cwd {
+        self.create_task_terminal(
+            task::SpawnInTerminal {
+                command: Some(command),
+                args,
+                cwd,
+                env: extra_env
+                    .into_iter()
+                    .map(|var| (var.name, var.value))
+                    .collect(),
+                ..Default::default()
+            },
+            output_byte_limit,
+            cx,
+        )
+    }
+
+    /// Creates a terminal that runs `spawn_in_terminal` with its environment and shell, such as
+    /// a resolved task.
+    pub fn create_task_terminal(
+        &self,
+        spawn_in_terminal: task::SpawnInTerminal,
+        output_byte_limit: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Terminal>>> {
+        let env = match &spawn_in_terminal.cwd {
             Some(dir) => self.project.update(cx, |project, cx| {
                 let shell = TerminalSettings::get_global(cx).shell.clone();
                 project.directory_environment(&shell, dir.as_path().into(), cx)
             }),
             None => Task::ready(None).shared(),
         };
+        let task_env = spawn_in_terminal.env.clone();
         let env = cx.spawn(async move |_, _| {
             let mut env = env.await.unwrap_or_default();
             // Disables paging for `git` and hopefully other commands
             env.insert("PAGER".into(), "".into());
-            for var in extra_env {
-                env.insert(var.name, var.value);
-            }
+            env.extend(task_env);
             env
         });
 
@@ -2109,14 +2132,22 @@ impl AcpThread {
             let terminal_id = terminal_id.clone();
             async move |_this, cx| {
                 let env = env.await;
-                let shell = project
-                    .update(cx, |project, cx| {
+                let shell = match spawn_in_terminal.shell.clone() {
+                    Shell::System => Shell::Program(
                         project
-                            .remote_client()
-                            .and_then(|r| r.read(cx).default_system_shell())
-                    })?
-                    .unwrap_or_else(|| get_default_system_shell());
-                let (task_command, task_args) = ShellBuilder::new(&Shell::Program(shell))
+                            .update(cx, |project, cx| {
+                                project
+                                    .remote_client()
+                                    .and_then(|r| r.read(cx).default_system_shell())
+                            })?
+                            .unwrap_or_else(|| get_default_system_shell()),
+                    ),
+                    shell => shell,
+                };
+                let command = spawn_in_terminal.command.clone().unwrap_or_default();
+                let args = spawn_in_terminal.args.clone();
+                let cwd = spawn_in_terminal.cwd.clone();
+                let (task_command, task_args) = ShellBuilder::new(&shell)
                     .redirect_stdin_to_dev_null()
                     .build(Some(command.clone()), &args);
                 let terminal = project
@@ -2125,9 +2156,8 @@ impl AcpThread {
                             task::SpawnInTerminal {
                                 command: Some(task_command),
                                 args: task_args,
-                                cwd: cwd.clone(),
                                 env,
-                                ..Default::default()
+                                ..spawn_in_terminal
                             },
                             cx,
                         )
@@ -198,6 +198,13 @@ pub trait AgentModelSelector: 'static {
     fn watch(&self, _cx: &mut App) -> Option<watch::Receiver<()>> {
         None
     }
+
+    /// Describes why the given model may not be able to continue the session,
+    /// such as a context window that's smaller than the conversation so far.
+    /// Optional for agents that can't tell ahead of time.
+    fn compatibility_warnings(&self, _model_id: &acp::ModelId, _cx: &App) -> Vec<SharedString> {
+        Vec::new()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -921,6 +921,21 @@ impl acp_thread::AgentModelSelector for NativeAgentModelSelector {
     fn watch(&self, cx: &mut App) -> Option<watch::Receiver<()>> {
         Some(self.connection.0.read(cx).models.watch())
     }
+
+    fn compatibility_warnings(&self, model_id: &acp::ModelId, cx: &App) -> Vec<SharedString> {
+        let connection = self.connection.0.read(cx);
+        let Some(thread) = connection
+            .sessions
+            .get(&self.session_id)
+            .map(|session| session.thread.read(cx))
+        else {
+            return Vec::new();
+        };
+        let Some(model) = connection.models.model_from_id(model_id) else {
+            return Vec::new();
+        };
+        thread.model_compatibility_warnings(model.as_ref(), cx)
+    }
 }
 
 impl acp_thread::AgentConnection for NativeAgentConnection {
@@ -1152,7 +1167,28 @@ impl ThreadEnvironment for AcpThreadEnvironment {
         let task = self.acp_thread.update(cx, |thread, cx| {
             thread.create_terminal(command, vec![], vec![], cwd, output_byte_limit, cx)
         });
+        self.terminal_handle(task, cx)
+    }
+
+    fn create_task_terminal(
+        &self,
+        spawn_in_terminal: task::SpawnInTerminal,
+        output_byte_limit: Option<u64>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<Rc<dyn TerminalHandle>>> {
+        let task = self.acp_thread.update(cx, |thread, cx| {
+            thread.create_task_terminal(spawn_in_terminal, output_byte_limit, cx)
+        });
+        self.terminal_handle(task, cx)
+    }
+}
 
+impl AcpThreadEnvironment {
+    fn terminal_handle(
+        &self,
+        task: Result<Task<Result<Entity<acp_thread::Terminal>>>>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<Rc<dyn TerminalHandle>>> {
         let acp_thread = self.acp_thread.clone();
         cx.spawn(async move |cx| {
             let terminal = task?.await?;
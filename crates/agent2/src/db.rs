@@ -16,8 +16,9 @@ use sqlez::{
     connection::Connection,
     statement::Statement,
 };
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use ui::{App, SharedString};
+use util::ResultExt as _;
 use zed_env_vars::ZED_STATELESS;
 
 pub type DbMessage = crate::Message;
@@ -30,6 +31,11 @@ pub struct DbThreadMetadata {
     #[serde(alias = "summary")]
     pub title: SharedString,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub tags: Vec<SharedString>,
+    /// The worktrees of the project the thread was started in.
+    #[serde(default)]
+    pub worktree_paths: Vec<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -295,11 +301,28 @@ impl ThreadsDatabase {
         "})?()
         .map_err(|e| anyhow!("Failed to create threads table: {}", e))?;
 
+        connection
+            .migrate(
+                "agent_threads",
+                &[indoc! {"
+                    ALTER TABLE threads ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';
+                    ALTER TABLE threads ADD COLUMN worktree_paths TEXT NOT NULL DEFAULT '[]';
+                    ALTER TABLE threads ADD COLUMN search_text TEXT NOT NULL DEFAULT '';
+                "}],
+                |_, _, _| false,
+            )
+            .map_err(|e| anyhow!("Failed to migrate threads table: {}", e))?;
+
         let db = Self {
             executor,
             connection: Arc::new(Mutex::new(connection)),
         };
 
+        let connection = db.connection.clone();
+        db.executor
+            .spawn(async move { Self::backfill_index_columns(&connection).log_err() })
+            .detach();
+
         Ok(db)
     }
 
@@ -319,6 +342,7 @@ impl ThreadsDatabase {
 
         let title = thread.title.to_string();
         let updated_at = thread.updated_at.to_rfc3339();
+        let (worktree_paths, search_text) = Self::index_columns(&thread)?;
         let json_data = serde_json::to_string(&SerializedThread {
             thread,
             version: DbThread::VERSION,
@@ -330,11 +354,27 @@ impl ThreadsDatabase {
         let data_type = DataType::Zstd;
         let data = compressed;
 
-        let mut insert = connection.exec_bound::<(Arc<str>, String, String, DataType, Vec<u8>)>(indoc! {"
-            INSERT OR REPLACE INTO threads (id, summary, updated_at, data_type, data) VALUES (?, ?, ?, ?, ?)
+        // Tags are only ever changed through `set_thread_tags`, so they're kept when the thread is saved.
+        let mut insert = connection.exec_bound::<(Arc<str>, String, String, DataType, Vec<u8>, String, String)>(indoc! {"
+            INSERT INTO threads (id, summary, updated_at, data_type, data, worktree_paths, search_text) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                summary = excluded.summary,
+                updated_at = excluded.updated_at,
+                data_type = excluded.data_type,
+                data = excluded.data,
+                worktree_paths = excluded.worktree_paths,
+                search_text = excluded.search_text
         "})?;
 
-        insert((id.0, title, updated_at, data_type, data))?;
+        insert((
+            id.0,
+            title,
+            updated_at,
+            data_type,
+            data,
+            worktree_paths,
+            search_text,
+        ))?;
 
         Ok(())
     }
@@ -345,19 +385,21 @@ impl ThreadsDatabase {
         self.executor.spawn(async move {
             let connection = connection.lock();
 
-            let mut select =
-                connection.select_bound::<(), (Arc<str>, String, String)>(indoc! {"
-                SELECT id, summary, updated_at FROM threads ORDER BY updated_at DESC
+            let mut select = connection
+                .select_bound::<(), (Arc<str>, String, String, String, String)>(indoc! {"
+                SELECT id, summary, updated_at, tags, worktree_paths FROM threads ORDER BY updated_at DESC
             "})?;
 
             let rows = select(())?;
             let mut threads = Vec::new();
 
-            for (id, summary, updated_at) in rows {
+            for (id, summary, updated_at, tags, worktree_paths) in rows {
                 threads.push(DbThreadMetadata {
                     id: acp::SessionId(id),
                     title: summary.into(),
                     updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+                    tags: serde_json::from_str(&tags).unwrap_or_default(),
+                    worktree_paths: serde_json::from_str(&worktree_paths).unwrap_or_default(),
                 });
             }
 
@@ -376,21 +418,82 @@ impl ThreadsDatabase {
 
             let rows = select(id.0)?;
             if let Some((data_type, data)) = rows.into_iter().next() {
-                let json_data = match data_type {
-                    DataType::Zstd => {
-                        let decompressed = zstd::decode_all(&data[..])?;
-                        String::from_utf8(decompressed)?
-                    }
-                    DataType::Json => String::from_utf8(data)?,
-                };
-                let thread = DbThread::from_json(json_data.as_bytes())?;
-                Ok(Some(thread))
+                Ok(Some(Self::decode_thread(data_type, data)?))
             } else {
                 Ok(None)
             }
         })
     }
 
+    fn decode_thread(data_type: DataType, data: Vec<u8>) -> Result<DbThread> {
+        let json_data = match data_type {
+            DataType::Zstd => {
+                let decompressed = zstd::decode_all(&data[..])?;
+                String::from_utf8(decompressed)?
+            }
+            DataType::Json => String::from_utf8(data)?,
+        };
+        DbThread::from_json(json_data.as_bytes())
+    }
+
+    /// Returns the values of the `worktree_paths` and `search_text` columns for a thread.
+    fn index_columns(thread: &DbThread) -> Result<(String, String)> {
+        let worktree_paths = serde_json::to_string(
+            &thread
+                .initial_project_snapshot
+                .iter()
+                .flat_map(|snapshot| &snapshot.worktree_snapshots)
+                .filter(|worktree| !worktree.worktree_path.is_empty())
+                .map(|worktree| PathBuf::from(&worktree.worktree_path))
+                .collect::<Vec<_>>(),
+        )?;
+        let search_text = thread
+            .messages
+            .iter()
+            .map(|message| message.to_markdown())
+            .collect::<String>();
+        Ok((worktree_paths, search_text))
+    }
+
+    /// Fills in the `worktree_paths` and `search_text` columns of threads saved before these
+    /// columns were added, so that they can be searched and filtered too.
+    ///
+    /// Threads are indexed one at a time, so that the database stays available meanwhile.
+    fn backfill_index_columns(connection: &Arc<Mutex<Connection>>) -> Result<()> {
+        let ids = connection.lock().select::<Arc<str>>(indoc! {"
+            SELECT id FROM threads WHERE search_text = ''
+        "})?()?;
+
+        for id in ids {
+            let connection = connection.lock();
+            let mut select =
+                connection.select_bound::<Arc<str>, (String, DataType, Vec<u8>)>(indoc! {"
+                    SELECT updated_at, data_type, data FROM threads WHERE id = ? LIMIT 1
+                "})?;
+            let Some((updated_at, data_type, data)) = select(id.clone())?.into_iter().next() else {
+                continue;
+            };
+            let thread = match Self::decode_thread(data_type, data) {
+                Ok(thread) => thread,
+                Err(error) => {
+                    log::error!("failed to index thread {id}: {error:#}");
+                    continue;
+                }
+            };
+            let (worktree_paths, search_text) = Self::index_columns(&thread)?;
+
+            // Skip threads that were saved, and therefore indexed, in the meantime.
+            let mut update =
+                connection.exec_bound::<(String, String, Arc<str>, String)>(indoc! {"
+                UPDATE threads SET worktree_paths = ?, search_text = ?
+                WHERE id = ? AND updated_at = ? AND search_text = ''
+            "})?;
+            update((worktree_paths, search_text, id, updated_at))?;
+        }
+
+        Ok(())
+    }
+
     pub fn save_thread(&self, id: acp::SessionId, thread: DbThread) -> Task<Result<()>> {
         let connection = self.connection.clone();
 
@@ -398,6 +501,42 @@ impl ThreadsDatabase {
             .spawn(async move { Self::save_thread_sync(&connection, id, thread) })
     }
 
+    /// Returns the threads whose title or messages contain `query`, ignoring ASCII case.
+    pub fn search_threads(&self, query: String) -> Task<Result<Vec<acp::SessionId>>> {
+        let connection = self.connection.clone();
+
+        self.executor.spawn(async move {
+            let connection = connection.lock();
+            let pattern = format!("%{}%", escape_like_pattern(&query));
+            let mut select = connection.select_bound::<(&str, &str), Arc<str>>(indoc! {"
+                SELECT id FROM threads
+                WHERE summary LIKE ? ESCAPE '\\' OR search_text LIKE ? ESCAPE '\\'
+                ORDER BY updated_at DESC
+            "})?;
+
+            Ok(select((&pattern, &pattern))?
+                .into_iter()
+                .map(acp::SessionId)
+                .collect())
+        })
+    }
+
+    pub fn set_thread_tags(&self, id: acp::SessionId, tags: Vec<SharedString>) -> Task<Result<()>> {
+        let connection = self.connection.clone();
+
+        self.executor.spawn(async move {
+            let tags = serde_json::to_string(&tags)?;
+            let connection = connection.lock();
+            let mut update = connection.exec_bound::<(String, Arc<str>)>(indoc! {"
+                UPDATE threads SET tags = ? WHERE id = ?
+            "})?;
+
+            update((tags, id.0))?;
+
+            Ok(())
+        })
+    }
+
     pub fn delete_thread(&self, id: acp::SessionId) -> Task<Result<()>> {
         let connection = self.connection.clone();
 
@@ -415,6 +554,17 @@ impl ThreadsDatabase {
     }
 }
 
+fn escape_like_pattern(query: &str) -> String {
+    let mut escaped = String::with_capacity(query.len());
+    for char in query.chars() {
+        if matches!(char, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -494,4 +644,92 @@ mod tests {
             "## Assistant\n\nHow're you doing?\n"
         );
     }
+
+    #[gpui::test]
+    async fn test_search_and_tag_threads(cx: &mut TestAppContext) {
+        let db = ThreadsDatabase::new(cx.executor()).unwrap();
+        let thread = |title: &str, text: &str| DbThread {
+            title: title.to_string().into(),
+            messages: vec![crate::Message::User(UserMessage {
+                id: UserMessageId::new(),
+                content: vec![UserMessageContent::Text(text.into())],
+            })],
+            updated_at: Utc::now(),
+            detailed_summary: None,
+            initial_project_snapshot: None,
+            cumulative_token_usage: Default::default(),
+            request_token_usage: Default::default(),
+            model: None,
+            completion_mode: None,
+            profile: None,
+        };
+
+        let first_id = acp::SessionId("first".into());
+        let second_id = acp::SessionId("second".into());
+        db.save_thread(first_id.clone(), thread("Parser", "Fix the 100% CPU usage"))
+            .await
+            .unwrap();
+        db.save_thread(second_id.clone(), thread("Docs", "Update the README"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            db.search_threads("readme".into()).await.unwrap(),
+            vec![second_id.clone()]
+        );
+        assert_eq!(
+            db.search_threads("parser".into()).await.unwrap(),
+            vec![first_id.clone()]
+        );
+        assert_eq!(
+            db.search_threads("100%".into()).await.unwrap(),
+            vec![first_id.clone()]
+        );
+        assert!(db.search_threads("0_C".into()).await.unwrap().is_empty());
+
+        db.set_thread_tags(first_id.clone(), vec!["bugs".into()])
+            .await
+            .unwrap();
+        // Saving the thread again keeps its tags.
+        db.save_thread(first_id.clone(), thread("Parser", "Fix the CPU usage"))
+            .await
+            .unwrap();
+        let threads = db.list_threads().await.unwrap();
+        let first = threads.iter().find(|thread| thread.id == first_id).unwrap();
+        assert_eq!(first.tags, vec![SharedString::from("bugs")]);
+    }
+
+    #[gpui::test]
+    async fn test_backfill_index_columns(cx: &mut TestAppContext) {
+        let db = ThreadsDatabase::new(cx.executor()).unwrap();
+        cx.run_until_parked();
+        let id = acp::SessionId("legacy".into());
+        let thread = DbThread {
+            title: "Legacy".into(),
+            messages: vec![crate::Message::User(UserMessage {
+                id: UserMessageId::new(),
+                content: vec![UserMessageContent::Text("Rename the parser module".into())],
+            })],
+            updated_at: Utc::now(),
+            detailed_summary: None,
+            initial_project_snapshot: None,
+            cumulative_token_usage: Default::default(),
+            request_token_usage: Default::default(),
+            model: None,
+            completion_mode: None,
+            profile: None,
+        };
+        db.save_thread(id.clone(), thread).await.unwrap();
+
+        // Simulate a thread saved before the index columns were added.
+        db.connection
+            .lock()
+            .exec("UPDATE threads SET search_text = ''")
+            .unwrap()()
+        .unwrap();
+        assert!(db.search_threads("parser".into()).await.unwrap().is_empty());
+
+        ThreadsDatabase::backfill_index_columns(&db.connection).unwrap();
+        assert_eq!(db.search_threads("parser".into()).await.unwrap(), vec![id]);
+    }
 }
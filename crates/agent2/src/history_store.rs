@@ -9,7 +9,12 @@ use gpui::{App, AsyncApp, Entity, SharedString, Task, prelude::*};
 use itertools::Itertools;
 use paths::contexts_dir;
 use serde::{Deserialize, Serialize};
-use std::{collections::VecDeque, path::Path, sync::Arc, time::Duration};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 use ui::ElementId;
 use util::ResultExt as _;
 
@@ -53,6 +58,24 @@ impl HistoryEntry {
         }
     }
 
+    pub fn tags(&self) -> &[SharedString] {
+        match self {
+            HistoryEntry::AcpThread(thread) => &thread.tags,
+            HistoryEntry::TextThread(_) => &[],
+        }
+    }
+
+    /// Whether the entry was started in a project containing any of the given worktrees.
+    pub fn belongs_to_worktrees(&self, worktree_paths: &[PathBuf]) -> bool {
+        match self {
+            HistoryEntry::AcpThread(thread) => thread
+                .worktree_paths
+                .iter()
+                .any(|path| worktree_paths.contains(path)),
+            HistoryEntry::TextThread(_) => false,
+        }
+    }
+
     pub fn title(&self) -> &SharedString {
         match self {
             HistoryEntry::AcpThread(thread) if thread.title.is_empty() => DEFAULT_TITLE,
@@ -140,6 +163,68 @@ impl HistoryStore {
         })
     }
 
+    /// Returns the threads whose title or messages contain `query`.
+    pub fn search_threads(
+        &self,
+        query: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<acp::SessionId>>> {
+        let database_future = ThreadsDatabase::connect(cx);
+        cx.background_spawn(async move {
+            let database = database_future.await.map_err(|err| anyhow!(err))?;
+            database.search_threads(query).await
+        })
+    }
+
+    pub fn set_thread_tags(
+        &mut self,
+        id: acp::SessionId,
+        tags: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let database_future = ThreadsDatabase::connect(cx);
+        cx.spawn(async move |this, cx| {
+            let database = database_future.await.map_err(|err| anyhow!(err))?;
+            database.set_thread_tags(id, tags).await?;
+            this.update(cx, |this, cx| this.reload(cx))
+        })
+    }
+
+    /// Copies a thread, including its messages and project snapshot, into a new
+    /// thread that can be continued independently.
+    pub fn branch_thread(
+        &mut self,
+        id: acp::SessionId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DbThreadMetadata>> {
+        let database_future = ThreadsDatabase::connect(cx);
+        let (tags, worktree_paths) = self
+            .thread_from_session_id(&id)
+            .map(|thread| (thread.tags.clone(), thread.worktree_paths.clone()))
+            .unwrap_or_default();
+        cx.spawn(async move |this, cx| {
+            let database = database_future.await.map_err(|err| anyhow!(err))?;
+            let mut thread = database
+                .load_thread(id)
+                .await?
+                .context("thread not found")?;
+            let branch_id = acp::SessionId(uuid::Uuid::new_v4().to_string().into());
+            thread.title = format!("{} (Branch)", thread.title).into();
+            thread.updated_at = Utc::now();
+            let metadata = DbThreadMetadata {
+                id: branch_id.clone(),
+                title: thread.title.clone(),
+                updated_at: thread.updated_at,
+                tags: tags.clone(),
+                worktree_paths,
+            };
+            database.save_thread(branch_id.clone(), thread).await?;
+            database.set_thread_tags(branch_id, tags).await?;
+            this.update(cx, |this, cx| this.reload(cx))?;
+            Ok(metadata)
+        })
+    }
+
     pub fn delete_text_thread(
         &mut self,
         path: Arc<Path>,
@@ -351,6 +436,16 @@ impl HistoryStore {
         self.save_recently_opened_entries(cx);
     }
 
+    /// All the tags used by threads, sorted alphabetically.
+    pub fn tags(&self) -> Vec<SharedString> {
+        self.threads
+            .iter()
+            .flat_map(|thread| thread.tags.iter().cloned())
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
     pub fn entries(&self) -> impl Iterator<Item = HistoryEntry> {
         self.entries.iter().cloned()
     }
@@ -1,7 +1,7 @@
 use super::*;
 use acp_thread::{AgentConnection, AgentModelGroupName, AgentModelList, UserMessageId};
 use agent_client_protocol::{self as acp};
-use agent_settings::AgentProfileId;
+use agent_settings::{AgentProfileId, AgentSettings};
 use anyhow::Result;
 use client::{Client, UserStore};
 use cloud_llm_client::CompletionIntent;
@@ -16,7 +16,8 @@ use futures::{
     },
 };
 use gpui::{
-    App, AppContext, Entity, Task, TestAppContext, UpdateGlobal, http_client::FakeHttpClient,
+    App, AppContext, Entity, SharedString, Task, TestAppContext, UpdateGlobal,
+    http_client::FakeHttpClient,
 };
 use indoc::indoc;
 use language_model::{
@@ -34,7 +35,7 @@ use reqwest_client::ReqwestClient;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use settings::{Settings, SettingsStore};
+use settings::{Settings, SettingsStore, ToolPermissionKind};
 use std::{path::Path, rc::Rc, sync::Arc, time::Duration};
 use util::path;
 
@@ -443,14 +444,14 @@ async fn test_tool_authorization(cx: &mut TestAppContext) {
     // Approve the first
     tool_call_auth_1
         .response
-        .send(tool_call_auth_1.options[1].id.clone())
+        .send(tool_call_auth_1.options[2].id.clone())
         .unwrap();
     cx.run_until_parked();
 
     // Reject the second
     tool_call_auth_2
         .response
-        .send(tool_call_auth_1.options[2].id.clone())
+        .send(tool_call_auth_1.options[3].id.clone())
         .unwrap();
     cx.run_until_parked();
 
@@ -538,6 +539,74 @@ async fn test_tool_authorization(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_tool_authorization_by_kind(cx: &mut TestAppContext) {
+    let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
+    let fake_model = model.as_fake();
+
+    let mut events = thread
+        .update(cx, |thread, cx| {
+            thread.add_tool(ToolRequiringPermission);
+            thread.send(UserMessageId::new(), ["abc"], cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: "tool_id_1".into(),
+            name: ToolRequiringPermission::name().into(),
+            raw_input: "{}".into(),
+            input: json!({}),
+            is_input_complete: true,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+
+    // Respond by always allowing tools of the same kind.
+    let tool_call_auth = next_tool_call_authorization(&mut events).await;
+    assert_eq!(tool_call_auth.options[1].name, "Always Allow Other Tools");
+    tool_call_auth
+        .response
+        .send(tool_call_auth.options[1].id.clone())
+        .unwrap();
+    cx.run_until_parked();
+    cx.update(|cx| {
+        let settings = AgentSettings::get_global(cx);
+        assert!(!settings.always_allow_tool_actions);
+        assert_eq!(
+            settings.always_allow_tool_kinds,
+            vec![ToolPermissionKind::Other]
+        );
+    });
+
+    // Tools of that kind no longer require authorization.
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::ToolUse(
+        LanguageModelToolUse {
+            id: "tool_id_2".into(),
+            name: ToolRequiringPermission::name().into(),
+            raw_input: "{}".into(),
+            input: json!({}),
+            is_input_complete: true,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+    let completion = fake_model.pending_completions().pop().unwrap();
+    let message = completion.messages.last().unwrap();
+    assert_eq!(
+        message.content,
+        vec![language_model::MessageContent::ToolResult(
+            LanguageModelToolResult {
+                tool_use_id: "tool_id_2".into(),
+                tool_name: ToolRequiringPermission::name().into(),
+                is_error: false,
+                content: "Allowed".into(),
+                output: Some("Allowed".into())
+            }
+        )]
+    );
+}
+
 #[gpui::test]
 async fn test_tool_hallucination(cx: &mut TestAppContext) {
     let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
@@ -1686,6 +1755,66 @@ async fn test_truncate_second_message(cx: &mut TestAppContext) {
     assert_first_message_state(cx);
 }
 
+#[gpui::test]
+async fn test_model_compatibility_warnings(cx: &mut TestAppContext) {
+    let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
+    let fake_model = model.as_fake();
+
+    thread.read_with(cx, |thread, cx| {
+        assert!(
+            thread
+                .model_compatibility_warnings(fake_model, cx)
+                .is_empty()
+        );
+    });
+
+    thread.update(cx, |thread, _| thread.add_tool(EchoTool));
+    thread.read_with(cx, |thread, cx| {
+        assert_eq!(
+            thread.model_compatibility_warnings(fake_model, cx),
+            vec![SharedString::from(
+                "Fake doesn't support tool use, so the agent won't be able to use its tools."
+            )]
+        );
+    });
+
+    thread.update(cx, |thread, _| thread.remove_tool(&EchoTool::name()));
+    thread
+        .update(cx, |thread, cx| {
+            thread.send(
+                UserMessageId::new(),
+                [UserMessageContent::Image(
+                    language_model::LanguageModelImage::empty(),
+                )],
+                cx,
+            )
+        })
+        .unwrap();
+    cx.run_until_parked();
+    fake_model.send_last_completion_stream_event(LanguageModelCompletionEvent::UsageUpdate(
+        language_model::TokenUsage {
+            input_tokens: 1_500_000,
+            output_tokens: 0,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: 0,
+        },
+    ));
+    fake_model.end_last_completion_stream();
+    cx.run_until_parked();
+
+    thread.read_with(cx, |thread, cx| {
+        assert_eq!(
+            thread.model_compatibility_warnings(fake_model, cx),
+            vec![
+                SharedString::from(
+                    "This thread uses 1500000 tokens, more than Fake's context window of 1000000 tokens."
+                ),
+                SharedString::from("Fake doesn't support images, which this thread contains."),
+            ]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_title_generation(cx: &mut TestAppContext) {
     let ThreadTest { model, thread, .. } = setup(cx, TestModel::Fake).await;
@@ -1,8 +1,8 @@
 use crate::{
     ContextServerRegistry, CopyPathTool, CreateDirectoryTool, DbLanguageModel, DbThread,
     DeletePathTool, DiagnosticsTool, EditFileTool, FetchTool, FindPathTool, GrepTool,
-    ListDirectoryTool, MovePathTool, NowTool, OpenTool, ReadFileTool, SystemPromptTemplate,
-    Template, Templates, TerminalTool, ThinkingTool, WebSearchTool,
+    ListDirectoryTool, MovePathTool, NowTool, OpenTool, ReadFileTool, RunTaskTool,
+    SystemPromptTemplate, Template, Templates, TerminalTool, ThinkingTool, WebSearchTool,
 };
 use acp_thread::{MentionUri, UserMessageId};
 use action_log::ActionLog;
@@ -34,7 +34,7 @@ use language_model::{
     LanguageModelImage, LanguageModelProviderId, LanguageModelRegistry, LanguageModelRequest,
     LanguageModelRequestMessage, LanguageModelRequestTool, LanguageModelToolResult,
     LanguageModelToolResultContent, LanguageModelToolSchemaFormat, LanguageModelToolUse,
-    LanguageModelToolUseId, Role, SelectedModel, StopReason, TokenUsage,
+    LanguageModelToolUseId, LanguageModelUsageTracker, Role, SelectedModel, StopReason, TokenUsage,
 };
 use project::{
     Project,
@@ -43,7 +43,7 @@ use project::{
 use prompt_store::ProjectContext;
 use schemars::{JsonSchema, Schema};
 use serde::{Deserialize, Serialize};
-use settings::{Settings, update_settings_file};
+use settings::{Settings, ToolPermissionKind, update_settings_file};
 use smol::stream::StreamExt;
 use std::{
     collections::BTreeMap,
@@ -538,6 +538,14 @@ pub trait ThreadEnvironment {
         output_byte_limit: Option<u64>,
         cx: &mut AsyncApp,
     ) -> Task<Result<Rc<dyn TerminalHandle>>>;
+
+    /// Creates a terminal that runs a resolved task, with the task's environment and shell.
+    fn create_task_terminal(
+        &self,
+        spawn_in_terminal: task::SpawnInTerminal,
+        output_byte_limit: Option<u64>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<Rc<dyn TerminalHandle>>>;
 }
 
 #[derive(Debug)]
@@ -594,8 +602,9 @@ pub struct Thread {
     tools: BTreeMap<SharedString, Arc<dyn AnyAgentTool>>,
     tool_use_limit_reached: bool,
     request_token_usage: HashMap<UserMessageId, language_model::TokenUsage>,
-    #[allow(unused)]
     cumulative_token_usage: TokenUsage,
+    /// The usage reported so far for the request that is currently streaming.
+    current_request_usage: Option<TokenUsage>,
     #[allow(unused)]
     initial_project_snapshot: Shared<Task<Option<Arc<ProjectSnapshot>>>>,
     context_server_registry: Entity<ContextServerRegistry>,
@@ -648,6 +657,7 @@ impl Thread {
             tool_use_limit_reached: false,
             request_token_usage: HashMap::default(),
             cumulative_token_usage: TokenUsage::default(),
+            current_request_usage: None,
             initial_project_snapshot: {
                 let project_snapshot = Self::project_snapshot(project.clone(), cx);
                 cx.foreground_executor()
@@ -753,6 +763,7 @@ impl Thread {
         if let Some(output) = output.clone() {
             let tool_event_stream = ToolCallEventStream::new(
                 tool_use.id.clone(),
+                kind,
                 stream.clone(),
                 Some(self.project.read(cx).fs().clone()),
             );
@@ -827,6 +838,7 @@ impl Thread {
             tool_use_limit_reached: false,
             request_token_usage: db_thread.request_token_usage.clone(),
             cumulative_token_usage: db_thread.cumulative_token_usage,
+            current_request_usage: None,
             initial_project_snapshot: Task::ready(db_thread.initial_project_snapshot).shared(),
             context_server_registry,
             profile_id,
@@ -987,6 +999,56 @@ impl Thread {
         self.model.as_ref()
     }
 
+    /// Describes the ways `model` can't handle this thread as it currently stands,
+    /// so that the user can be warned before switching to it.
+    pub fn model_compatibility_warnings(
+        &self,
+        model: &dyn LanguageModel,
+        cx: &App,
+    ) -> Vec<SharedString> {
+        let mut warnings = Vec::new();
+        let name = model.name().0;
+
+        if let Some(usage) = self.latest_token_usage() {
+            let max_tokens = model.max_token_count_for_mode(self.completion_mode.into());
+            if usage.used_tokens > max_tokens {
+                warnings.push(
+                    format!(
+                        "This thread uses {} tokens, more than {name}'s context window of {max_tokens} tokens.",
+                        usage.used_tokens
+                    )
+                    .into(),
+                );
+            }
+        }
+
+        let uses_tools = AgentSettings::get_global(cx)
+            .profiles
+            .get(&self.profile_id)
+            .is_some_and(|profile| self.tools.keys().any(|tool| profile.is_tool_enabled(tool)));
+        if uses_tools && !model.supports_tools() {
+            warnings.push(
+                format!(
+                    "{name} doesn't support tool use, so the agent won't be able to use its tools."
+                )
+                .into(),
+            );
+        }
+
+        let has_images = self.messages.iter().any(|message| {
+            matches!(message, Message::User(message) if message
+                .content
+                .iter()
+                .any(|content| matches!(content, UserMessageContent::Image(_))))
+        });
+        if has_images && !model.supports_images() {
+            warnings
+                .push(format!("{name} doesn't support images, which this thread contains.").into());
+        }
+
+        warnings
+    }
+
     pub fn set_model(&mut self, model: Arc<dyn LanguageModel>, cx: &mut Context<Self>) {
         let old_usage = self.latest_token_usage();
         self.model = Some(model);
@@ -1064,6 +1126,7 @@ impl Thread {
             self.project.clone(),
             self.action_log.clone(),
         ));
+        self.add_tool(RunTaskTool::new(self.project.clone(), environment.clone()));
         self.add_tool(TerminalTool::new(self.project.clone(), environment));
         self.add_tool(ThinkingTool);
         self.add_tool(WebSearchTool);
@@ -1093,6 +1156,13 @@ impl Thread {
     }
 
     fn update_token_usage(&mut self, update: language_model::TokenUsage, cx: &mut Context<Self>) {
+        let previous = self.current_request_usage.replace(update);
+        let delta = update.saturating_sub(previous.unwrap_or_default());
+        self.cumulative_token_usage = self.cumulative_token_usage + delta;
+        if let Some(model) = self.model.clone() {
+            LanguageModelUsageTracker::record(model.as_ref(), delta, previous.is_none(), cx);
+        }
+
         let Some(last_user_message) = self.last_user_message() else {
             return;
         };
@@ -1124,6 +1194,11 @@ impl Thread {
         Ok(())
     }
 
+    /// The tokens used by every request in this thread, including tool calls and retries.
+    pub fn cumulative_token_usage(&self) -> TokenUsage {
+        self.cumulative_token_usage
+    }
+
     pub fn latest_token_usage(&self) -> Option<acp_thread::TokenUsage> {
         let last_user_message = self.last_user_message()?;
         let tokens = self.request_token_usage.get(&last_user_message.id)?;
@@ -1236,8 +1311,10 @@ impl Thread {
         let mut attempt = 0;
         let mut intent = CompletionIntent::UserPrompt;
         loop {
-            let request =
-                this.update(cx, |this, cx| this.build_completion_request(intent, cx))??;
+            let request = this.update(cx, |this, cx| {
+                this.current_request_usage = None;
+                this.build_completion_request(intent, cx)
+            })??;
 
             telemetry::event!(
                 "Agent Thread Completion",
@@ -1556,8 +1633,12 @@ impl Thread {
         };
 
         let fs = self.project.read(cx).fs().clone();
-        let tool_event_stream =
-            ToolCallEventStream::new(tool_use.id.clone(), event_stream.clone(), Some(fs));
+        let tool_event_stream = ToolCallEventStream::new(
+            tool_use.id.clone(),
+            tool.kind(),
+            event_stream.clone(),
+            Some(fs),
+        );
         tool_event_stream.update_fields(acp::ToolCallUpdateFields {
             status: Some(acp::ToolCallStatus::InProgress),
             ..Default::default()
@@ -1656,11 +1737,18 @@ impl Thread {
         });
         cx.spawn(async move |this, cx| {
             let mut summary = String::new();
+            let mut usage_reporter = LanguageModelUsageTracker::reporter(model.as_ref(), cx);
             let mut messages = model.stream_completion(request, cx).await?;
             while let Some(event) = messages.next().await {
                 let event = event?;
                 let text = match event {
                     LanguageModelCompletionEvent::Text(text) => text,
+                    LanguageModelCompletionEvent::UsageUpdate(usage) => {
+                        if let Some(usage_reporter) = usage_reporter.as_mut() {
+                            usage_reporter.report(usage);
+                        }
+                        continue;
+                    }
                     LanguageModelCompletionEvent::StatusUpdate(
                         CompletionRequestStatus::UsageUpdated { amount, limit },
                     ) => {
@@ -1716,11 +1804,18 @@ impl Thread {
             let mut title = String::new();
 
             let generate = async {
+                let mut usage_reporter = LanguageModelUsageTracker::reporter(model.as_ref(), cx);
                 let mut messages = model.stream_completion(request, cx).await?;
                 while let Some(event) = messages.next().await {
                     let event = event?;
                     let text = match event {
                         LanguageModelCompletionEvent::Text(text) => text,
+                        LanguageModelCompletionEvent::UsageUpdate(usage) => {
+                            if let Some(usage_reporter) = usage_reporter.as_mut() {
+                                usage_reporter.report(usage);
+                            }
+                            continue;
+                        }
                         LanguageModelCompletionEvent::StatusUpdate(
                             CompletionRequestStatus::UsageUpdated { amount, limit },
                         ) => {
@@ -2370,6 +2465,7 @@ impl ThreadEventStream {
 #[derive(Clone)]
 pub struct ToolCallEventStream {
     tool_use_id: LanguageModelToolUseId,
+    kind: acp::ToolKind,
     stream: ThreadEventStream,
     fs: Option<Arc<dyn Fs>>,
 }
@@ -2379,18 +2475,25 @@ impl ToolCallEventStream {
     pub fn test() -> (Self, ToolCallEventStreamReceiver) {
         let (events_tx, events_rx) = mpsc::unbounded::<Result<ThreadEvent>>();
 
-        let stream = ToolCallEventStream::new("test_id".into(), ThreadEventStream(events_tx), None);
+        let stream = ToolCallEventStream::new(
+            "test_id".into(),
+            acp::ToolKind::Other,
+            ThreadEventStream(events_tx),
+            None,
+        );
 
         (stream, ToolCallEventStreamReceiver(events_rx))
     }
 
     fn new(
         tool_use_id: LanguageModelToolUseId,
+        kind: acp::ToolKind,
         stream: ThreadEventStream,
         fs: Option<Arc<dyn Fs>>,
     ) -> Self {
         Self {
             tool_use_id,
+            kind,
             stream,
             fs,
         }
@@ -2415,7 +2518,11 @@ impl ToolCallEventStream {
     }
 
     pub fn authorize(&self, title: impl Into<String>, cx: &mut App) -> Task<Result<()>> {
-        if agent_settings::AgentSettings::get_global(cx).always_allow_tool_actions {
+        let settings = agent_settings::AgentSettings::get_global(cx);
+        let permission_kind = tool_permission_kind(self.kind);
+        if settings.always_allow_tool_actions
+            || settings.always_allow_tool_kinds.contains(&permission_kind)
+        {
             return Task::ready(Ok(()));
         }
 
@@ -2439,6 +2546,15 @@ impl ToolCallEventStream {
                             kind: acp::PermissionOptionKind::AllowAlways,
                             meta: None,
                         },
+                        acp::PermissionOption {
+                            id: acp::PermissionOptionId("always_allow_kind".into()),
+                            name: format!(
+                                "Always Allow {}",
+                                permission_kind_label(permission_kind)
+                            ),
+                            kind: acp::PermissionOptionKind::AllowAlways,
+                            meta: None,
+                        },
                         acp::PermissionOption {
                             id: acp::PermissionOptionId("allow".into()),
                             name: "Allow".into(),
@@ -2472,12 +2588,54 @@ impl ToolCallEventStream {
 
                 Ok(())
             }
+            "always_allow_kind" => {
+                if let Some(fs) = fs.clone() {
+                    cx.update(|cx| {
+                        update_settings_file(fs, cx, move |settings, _| {
+                            settings
+                                .agent
+                                .get_or_insert_default()
+                                .allow_tool_kind(permission_kind);
+                        });
+                    })?;
+                }
+
+                Ok(())
+            }
             "allow" => Ok(()),
             _ => Err(anyhow!("Permission to run tool denied by user")),
         })
     }
 }
 
+/// The class of actions a tool of the given kind performs, for permissions
+/// that apply to every tool in the class.
+fn tool_permission_kind(kind: acp::ToolKind) -> ToolPermissionKind {
+    match kind {
+        acp::ToolKind::Read => ToolPermissionKind::Read,
+        acp::ToolKind::Edit => ToolPermissionKind::Edit,
+        acp::ToolKind::Delete => ToolPermissionKind::Delete,
+        acp::ToolKind::Move => ToolPermissionKind::Move,
+        acp::ToolKind::Search => ToolPermissionKind::Search,
+        acp::ToolKind::Execute => ToolPermissionKind::Execute,
+        acp::ToolKind::Fetch => ToolPermissionKind::Fetch,
+        _ => ToolPermissionKind::Other,
+    }
+}
+
+fn permission_kind_label(kind: ToolPermissionKind) -> &'static str {
+    match kind {
+        ToolPermissionKind::Read => "Reading",
+        ToolPermissionKind::Edit => "Editing",
+        ToolPermissionKind::Delete => "Deleting",
+        ToolPermissionKind::Move => "Moving",
+        ToolPermissionKind::Search => "Searching",
+        ToolPermissionKind::Execute => "Running Commands",
+        ToolPermissionKind::Fetch => "Fetching",
+        ToolPermissionKind::Other => "Other Tools",
+    }
+}
+
 #[cfg(test)]
 pub struct ToolCallEventStreamReceiver(mpsc::UnboundedReceiver<Result<ThreadEvent>>);
 
@@ -12,6 +12,7 @@ mod move_path_tool;
 mod now_tool;
 mod open_tool;
 mod read_file_tool;
+mod run_task_tool;
 mod terminal_tool;
 mod thinking_tool;
 mod web_search_tool;
@@ -32,6 +33,7 @@ pub fn default_tool_names() -> impl Iterator<Item = &'static str> {
         NowTool::name(),
         OpenTool::name(),
         ReadFileTool::name(),
+        RunTaskTool::name(),
         TerminalTool::name(),
         ThinkingTool::name(),
         WebSearchTool::name(),
@@ -53,6 +55,7 @@ pub use move_path_tool::*;
 pub use now_tool::*;
 pub use open_tool::*;
 pub use read_file_tool::*;
+pub use run_task_tool::*;
 pub use terminal_tool::*;
 pub use thinking_tool::*;
 pub use web_search_tool::*;
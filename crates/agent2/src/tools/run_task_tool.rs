@@ -0,0 +1,168 @@
+use agent_client_protocol as acp;
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{App, Entity, SharedString, Task};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{iter, rc::Rc, sync::Arc};
+use task::{TaskContext, TaskVariables, VariableName};
+use util::markdown::MarkdownInlineCode;
+
+use crate::{AgentTool, ThreadEnvironment, ToolCallEventStream, process_content};
+
+const TASK_OUTPUT_LIMIT: u64 = 16 * 1024;
+
+/// Runs one of the tasks the user has defined for the project, such as a build or test task, and returns its combined output.
+///
+/// Prefer this tool over the `terminal` tool when a task already exists for what you want to do, since it runs the command exactly as the user configured it.
+///
+/// Do not use this tool for tasks that run indefinitely, such as servers or file watchers.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RunTaskToolInput {
+    /// The label of the task to run, exactly as the user defined it.
+    label: String,
+    /// The root directory of the project to run the task in.
+    /// Only needed when the project has multiple root directories.
+    #[serde(default)]
+    root: Option<String>,
+}
+
+pub struct RunTaskTool {
+    project: Entity<Project>,
+    environment: Rc<dyn ThreadEnvironment>,
+}
+
+impl RunTaskTool {
+    pub fn new(project: Entity<Project>, environment: Rc<dyn ThreadEnvironment>) -> Self {
+        Self {
+            project,
+            environment,
+        }
+    }
+}
+
+impl AgentTool for RunTaskTool {
+    type Input = RunTaskToolInput;
+    type Output = String;
+
+    fn name() -> &'static str {
+        "run_task"
+    }
+
+    fn kind() -> acp::ToolKind {
+        acp::ToolKind::Execute
+    }
+
+    fn initial_title(
+        &self,
+        input: Result<Self::Input, serde_json::Value>,
+        _cx: &mut App,
+    ) -> SharedString {
+        match input {
+            Ok(input) => format!("Run task {}", MarkdownInlineCode(&input.label)).into(),
+            Err(_) => "Run task".into(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: Self::Input,
+        event_stream: ToolCallEventStream,
+        cx: &mut App,
+    ) -> Task<Result<Self::Output>> {
+        let project = self.project.read(cx);
+        let Some(inventory) = project.task_store().read(cx).task_inventory().cloned() else {
+            return Task::ready(Err(anyhow!("Tasks aren't available in this project")));
+        };
+
+        let mut worktrees = project.visible_worktrees(cx);
+        let worktree = match &input.root {
+            Some(root) => worktrees.find(|worktree| {
+                worktree.read(cx).root_name().as_unix_str() == root.trim_end_matches('/')
+            }),
+            None => {
+                let worktree = worktrees.next();
+                if worktrees.next().is_some() {
+                    return Task::ready(Err(anyhow!(
+                        "The project has multiple root directories. Specify which one to run the task in."
+                    )));
+                }
+                worktree
+            }
+        };
+        let (worktree_id, worktree_root) = match (&input.root, worktree) {
+            (_, Some(worktree)) => {
+                let worktree = worktree.read(cx);
+                (Some(worktree.id()), Some(worktree.abs_path()))
+            }
+            (Some(root), None) => {
+                return Task::ready(Err(anyhow!(
+                    "{root} is not a root directory of the project"
+                )));
+            }
+            (None, None) => (None, None),
+        };
+
+        let template =
+            inventory
+                .read(cx)
+                .task_template_by_label(None, worktree_id, &input.label, cx);
+        cx.spawn(async move |cx| {
+            let template = template
+                .await
+                .with_context(|| format!("No task is labeled {:?}", input.label))?;
+
+            let mut task_variables = TaskVariables::default();
+            if let Some(root) = &worktree_root {
+                task_variables.insert(VariableName::WorktreeRoot, root.to_string_lossy().into());
+            }
+            let context = TaskContext {
+                cwd: worktree_root.as_ref().map(|root| root.to_path_buf()),
+                task_variables,
+                project_env: Default::default(),
+            };
+            let resolved = template
+                .resolve_task("agent", &context)
+                .with_context(|| format!("Failed to resolve task {:?}", input.label))?
+                .resolved;
+
+            let command = iter::once(resolved.command.clone().unwrap_or_default())
+                .chain(resolved.args.iter().cloned())
+                .map(|arg| {
+                    shlex::try_quote(&arg)
+                        .map(|arg| arg.into_owned())
+                        .map_err(|_| anyhow!("Task argument contains a null byte: {arg:?}"))
+                })
+                .collect::<Result<Vec<_>>>()?
+                .join(" ");
+
+            cx.update(|cx| {
+                event_stream.authorize(
+                    format!(
+                        "Run task {}: {}",
+                        MarkdownInlineCode(&input.label),
+                        MarkdownInlineCode(&command)
+                    ),
+                    cx,
+                )
+            })?
+            .await?;
+
+            let terminal = self
+                .environment
+                .create_task_terminal(resolved, Some(TASK_OUTPUT_LIMIT), cx)
+                .await?;
+
+            let terminal_id = terminal.id(cx)?;
+            event_stream.update_fields(acp::ToolCallUpdateFields {
+                content: Some(vec![acp::ToolCallContent::Terminal { terminal_id }]),
+                ..Default::default()
+            });
+
+            let exit_status = terminal.wait_for_exit(cx)?.await;
+            let output = terminal.current_output(cx)?;
+
+            Ok(process_content(output, &command, exit_status))
+        })
+    }
+}
@@ -125,7 +125,7 @@ impl AgentTool for TerminalTool {
     }
 }
 
-fn process_content(
+pub(crate) fn process_content(
     output: acp::TerminalOutputResponse,
     command: &str,
     exit_status: acp::TerminalExitStatus,
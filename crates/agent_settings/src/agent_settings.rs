@@ -4,13 +4,13 @@ use std::sync::Arc;
 
 use collections::IndexMap;
 use gpui::{App, Pixels, px};
-use language_model::LanguageModel;
+use language_model::{LanguageModel, TokenUsage};
 use project::DisableAiSettings;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{
-    DefaultAgentView, DockPosition, LanguageModelParameters, LanguageModelSelection,
-    NotifyWhenAgentWaiting, Settings, SettingsContent,
+    DefaultAgentView, DockPosition, LanguageModelParameters, LanguageModelPrice,
+    LanguageModelSelection, NotifyWhenAgentWaiting, Settings, SettingsContent, ToolPermissionKind,
 };
 
 pub use crate::agent_profile::*;
@@ -40,6 +40,7 @@ pub struct AgentSettings {
     pub default_view: DefaultAgentView,
     pub profiles: IndexMap<AgentProfileId, AgentProfileSettings>,
     pub always_allow_tool_actions: bool,
+    pub always_allow_tool_kinds: Vec<ToolPermissionKind>,
     pub notify_when_agent_waiting: NotifyWhenAgentWaiting,
     pub play_sound_when_agent_done: bool,
     pub stream_edits: bool,
@@ -51,6 +52,54 @@ pub struct AgentSettings {
     pub expand_terminal_card: bool,
     pub use_modifier_to_send: bool,
     pub message_editor_min_lines: usize,
+    pub token_usage: TokenUsageSettings,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TokenUsageSettings {
+    pub warn_above_tokens: Option<u64>,
+    pub warn_above_cost: Option<f64>,
+    pub model_prices: Vec<LanguageModelPrice>,
+}
+
+impl TokenUsageSettings {
+    /// Returns the configured price for the given model, if any.
+    pub fn price_for(&self, provider_id: &str, model_id: &str) -> Option<&LanguageModelPrice> {
+        self.model_prices.iter().rev().find(|price| {
+            price
+                .provider
+                .as_ref()
+                .is_none_or(|provider| provider.0 == provider_id)
+                && price
+                    .model
+                    .as_ref()
+                    .is_none_or(|model| model.as_str() == model_id)
+        })
+    }
+
+    /// Estimates the cost of `usage` with the given model, if it has a configured price.
+    pub fn estimated_cost(
+        &self,
+        provider_id: &str,
+        model_id: &str,
+        usage: &TokenUsage,
+    ) -> Option<f64> {
+        let price = self.price_for(provider_id, model_id)?;
+        let per_token =
+            |price_per_million: f64, tokens: u64| price_per_million * tokens as f64 / 1_000_000.;
+        Some(
+            per_token(price.input, usage.input_tokens)
+                + per_token(price.output, usage.output_tokens)
+                + per_token(
+                    price.cache_read.unwrap_or(price.input),
+                    usage.cache_read_input_tokens,
+                )
+                + per_token(
+                    price.cache_write.unwrap_or(price.input),
+                    usage.cache_creation_input_tokens,
+                ),
+        )
+    }
 }
 
 impl AgentSettings {
@@ -173,6 +222,7 @@ impl Settings for AgentSettings {
                 .map(|(key, val)| (AgentProfileId(key), val.into()))
                 .collect(),
             always_allow_tool_actions: agent.always_allow_tool_actions.unwrap(),
+            always_allow_tool_kinds: agent.always_allow_tool_kinds.unwrap(),
             notify_when_agent_waiting: agent.notify_when_agent_waiting.unwrap(),
             play_sound_when_agent_done: agent.play_sound_when_agent_done.unwrap(),
             stream_edits: agent.stream_edits.unwrap(),
@@ -184,6 +234,14 @@ impl Settings for AgentSettings {
             expand_terminal_card: agent.expand_terminal_card.unwrap(),
             use_modifier_to_send: agent.use_modifier_to_send.unwrap(),
             message_editor_min_lines: agent.message_editor_min_lines.unwrap(),
+            token_usage: agent
+                .token_usage
+                .map(|token_usage| TokenUsageSettings {
+                    warn_above_tokens: token_usage.warn_above_tokens,
+                    warn_above_cost: token_usage.warn_above_cost,
+                    model_prices: token_usage.model_prices,
+                })
+                .unwrap_or_default(),
         }
     }
 
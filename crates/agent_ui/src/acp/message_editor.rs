@@ -47,13 +47,17 @@ use std::{
 };
 use text::OffsetRangeExt;
 use theme::ThemeSettings;
-use ui::{ButtonLike, TintColor, Toggleable, prelude::*};
+use ui::{ButtonLike, TintColor, Toggleable, Tooltip, prelude::*};
 use util::{ResultExt, debug_panic, rel_path::RelPath};
 use workspace::{Workspace, notifications::NotifyResultExt as _};
 use zed_actions::agent::Chat;
 
 pub struct MessageEditor {
     mention_set: MentionSet,
+    pinned_mentions: HashSet<MentionUri>,
+    /// Estimated token counts of the mentions whose content has finished loading,
+    /// in the order they appear in the message.
+    mention_token_estimates: Vec<(MentionUri, usize)>,
     editor: Entity<Editor>,
     project: Entity<Project>,
     workspace: WeakEntity<Workspace>,
@@ -78,6 +82,9 @@ impl EventEmitter<MessageEditorEvent> for MessageEditor {}
 
 const COMMAND_HINT_INLAY_ID: u32 = 0;
 
+/// Rough estimate used to show how much of the context window a mention will use.
+const BYTES_PER_TOKEN_ESTIMATE: usize = 4;
+
 impl MessageEditor {
     pub fn new(
         workspace: WeakEntity<Workspace>,
@@ -162,6 +169,7 @@ impl MessageEditor {
                         editor.snapshot(window, cx)
                     });
                     this.mention_set.remove_invalid(snapshot);
+                    this.update_mention_token_estimates(window, cx);
 
                     cx.notify();
                 }
@@ -172,6 +180,8 @@ impl MessageEditor {
             editor,
             project,
             mention_set,
+            pinned_mentions: HashSet::default(),
+            mention_token_estimates: Vec::new(),
             workspace,
             history_store,
             prompt_store,
@@ -401,10 +411,158 @@ impl MessageEditor {
                     this.mention_set.mentions.remove(&crease_id);
                 })
                 .ok();
+            } else {
+                // The mention's token estimate is only known once its content has loaded.
+                this.update_in(cx, |this, window, cx| {
+                    this.update_mention_token_estimates(window, cx);
+                    cx.notify();
+                })
+                .ok();
             }
         })
     }
 
+    pub fn is_mention_pinned(&self, uri: &MentionUri) -> bool {
+        self.pinned_mentions.contains(uri)
+    }
+
+    /// Pinned mentions are re-attached to the next message after this one is sent.
+    pub fn toggle_pinned_mention(&mut self, uri: MentionUri, cx: &mut Context<Self>) {
+        if !self.pinned_mentions.remove(&uri) {
+            self.pinned_mentions.insert(uri);
+        }
+        cx.notify();
+    }
+
+    /// Clears the editor, keeping any pinned mentions that were part of the message.
+    pub fn clear_unpinned(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let mentions = self.mentions();
+        self.pinned_mentions.retain(|uri| mentions.contains(uri));
+        let mut pinned = self.pinned_mentions.iter().cloned().collect::<Vec<_>>();
+        pinned.sort_by_key(|uri| uri.name());
+
+        self.clear(window, cx);
+        for uri in pinned {
+            self.insert_mention(uri, window, cx).detach();
+        }
+    }
+
+    fn insert_mention(
+        &mut self,
+        uri: MentionUri,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let buffer = self.editor.read(cx).buffer().clone();
+        let Some(buffer) = buffer.read(cx).as_singleton() else {
+            return Task::ready(());
+        };
+        let new_text = format!("{} ", uri.as_link());
+        let content_len = new_text.len() - 1;
+        let anchor = buffer.update(cx, |buffer, _cx| buffer.anchor_before(buffer.len()));
+        self.editor.update(cx, |editor, cx| {
+            editor.edit(
+                [(
+                    multi_buffer::Anchor::max()..multi_buffer::Anchor::max(),
+                    new_text,
+                )],
+                cx,
+            );
+        });
+        self.confirm_mention_completion(uri.name().into(), anchor, content_len, uri, window, cx)
+    }
+
+    /// Recomputes the token estimates of the mentions, whenever they're added,
+    /// removed or finish loading, so that rendering doesn't have to.
+    fn update_mention_token_estimates(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let snapshot = self
+            .editor
+            .update(cx, |editor, cx| editor.snapshot(window, cx));
+        let mut estimates = Vec::new();
+        for (crease_id, _) in snapshot.crease_snapshot.creases() {
+            let Some((uri, task)) = self.mention_set.mentions.get(&crease_id) else {
+                continue;
+            };
+            if let Some(Ok(Mention::Text { content, .. })) = task.peek() {
+                estimates.push((uri.clone(), content.len() / BYTES_PER_TOKEN_ESTIMATE));
+            }
+        }
+        self.mention_token_estimates = estimates;
+    }
+
+    /// Rough estimate of the tokens the message will add to the conversation,
+    /// including the content of its mentions.
+    pub fn estimated_token_count(&self, cx: &App) -> u64 {
+        let text_tokens = self.text(cx).len() / BYTES_PER_TOKEN_ESTIMATE;
+        let mention_tokens = self
+            .mention_token_estimates
+            .iter()
+            .map(|(_, tokens)| tokens)
+            .sum::<usize>();
+        (text_tokens + mention_tokens) as u64
+    }
+
+    fn render_mention_summary(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let estimates = &self.mention_token_estimates;
+        if estimates.is_empty() {
+            return None;
+        }
+        let total = estimates.iter().map(|(_, tokens)| tokens).sum::<usize>();
+
+        Some(
+            h_flex()
+                .flex_wrap()
+                .gap_1()
+                .pt_1()
+                .children(
+                    estimates
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .map(|(ix, (uri, tokens))| {
+                            let pinned = self.is_mention_pinned(&uri);
+                            let can_pin = !matches!(
+                                uri,
+                                MentionUri::Selection { .. } | MentionUri::PastedImage
+                            );
+                            h_flex()
+                                .gap_0p5()
+                                .child(
+                                    Label::new(format!(
+                                        "{} ~{}",
+                                        uri.name(),
+                                        format_tokens(tokens)
+                                    ))
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted),
+                                )
+                                .when(can_pin, |this| {
+                                    this.child(
+                                        IconButton::new(("pin-mention", ix), IconName::Pin)
+                                            .icon_size(IconSize::XSmall)
+                                            .toggle_state(pinned)
+                                            .selected_icon_color(Color::Accent)
+                                            .tooltip(Tooltip::text(if pinned {
+                                                "Unpin from Future Messages"
+                                            } else {
+                                                "Keep in Future Messages"
+                                            }))
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.toggle_pinned_mention(uri.clone(), cx)
+                                            })),
+                                    )
+                                })
+                        }),
+                )
+                .child(
+                    Label::new(format!("Total ~{}", format_tokens(total)))
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+        )
+    }
+
     fn confirm_mention_for_file(
         &mut self,
         abs_path: PathBuf,
@@ -598,6 +756,7 @@ impl MessageEditor {
                 ),
             );
         }
+        self.update_mention_token_estimates(window, cx);
     }
 
     fn confirm_mention_for_thread(
@@ -1157,6 +1316,7 @@ impl MessageEditor {
                 (mention_uri.clone(), Task::ready(Ok(mention)).shared()),
             );
         }
+        self.update_mention_token_estimates(window, cx);
         cx.notify();
     }
 
@@ -1172,6 +1332,47 @@ impl MessageEditor {
     }
 }
 
+/// Re-reads a file or symbol mention from its buffer, so that edits made after
+/// the mention was added are included when the message is sent.
+fn refresh_mention(uri: &MentionUri, mention: Mention, cx: &mut App) -> Task<Result<Mention>> {
+    let Mention::Text {
+        tracked_buffers, ..
+    } = &mention
+    else {
+        return Task::ready(Ok(mention));
+    };
+    let [buffer] = tracked_buffers.as_slice() else {
+        return Task::ready(Ok(mention));
+    };
+    let buffer = buffer.clone();
+
+    match uri {
+        MentionUri::File { abs_path } => {
+            let abs_path = abs_path.to_string_lossy().into_owned();
+            cx.spawn(async move |cx| {
+                let buffer_content =
+                    outline::get_buffer_content_or_outline(buffer.clone(), Some(&abs_path), &cx)
+                        .await?;
+                Ok(Mention::Text {
+                    content: buffer_content.text,
+                    tracked_buffers: vec![buffer],
+                })
+            })
+        }
+        MentionUri::Symbol { line_range, .. } => {
+            let buffer_ref = buffer.read(cx);
+            let start = Point::new(*line_range.start(), 0).min(buffer_ref.max_point());
+            let end = Point::new(*line_range.end() + 1, 0).min(buffer_ref.max_point());
+            let content = buffer_ref.text_for_range(start..end).collect();
+            Task::ready(Ok(Mention::Text {
+                content,
+                tracked_buffers: vec![buffer],
+            }))
+        }
+        _ => Task::ready(Ok(mention)),
+    }
+}
+
 fn full_mention_for_directory(
     project: &Entity<Project>,
     abs_path: &Path,
@@ -1284,8 +1485,9 @@ impl Focusable for MessageEditor {
 }
 
 impl Render for MessageEditor {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        div()
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mention_summary = self.render_mention_summary(cx);
+        v_flex()
             .key_context("MessageEditor")
             .on_action(cx.listener(Self::send))
             .on_action(cx.listener(Self::cancel))
@@ -1316,6 +1518,15 @@ impl Render for MessageEditor {
                     },
                 )
             })
+            .children(mention_summary)
+    }
+}
+
+fn format_tokens(tokens: usize) -> String {
+    if tokens >= 1000 {
+        format!("{:.1}k tokens", tokens as f64 / 1000.0)
+    } else {
+        format!("{tokens} tokens")
     }
 }
 
@@ -1539,7 +1750,13 @@ impl MentionSet {
                     cx.update(|cx| full_mention_for_directory(&project, abs_path, cx))?
                         .await?
                 } else {
-                    task.await.map_err(|e| anyhow!("{e}"))?
+                    let mention = task.await.map_err(|e| anyhow!("{e}"))?;
+                    if full_mention_content {
+                        cx.update(|cx| refresh_mention(&mention_uri, mention, cx))?
+                            .await?
+                    } else {
+                        mention
+                    }
                 };
 
                 contents.insert(crease_id, (mention_uri, content));
@@ -2747,4 +2964,71 @@ mod tests {
             _ => panic!("Expected Text mention for small file"),
         }
     }
+
+    #[gpui::test]
+    async fn test_file_mention_is_refreshed_on_send(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/project", json!({"file.rs": "fn one() {}\n"}))
+            .await;
+        let project = Project::test(fs, [Path::new(path!("/project"))], cx).await;
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let context_store = cx.new(|cx| ContextStore::fake(project.clone(), cx));
+        let history_store = cx.new(|cx| HistoryStore::new(context_store, cx));
+
+        let message_editor = cx.update(|window, cx| {
+            cx.new(|cx| {
+                MessageEditor::new(
+                    workspace.downgrade(),
+                    project.clone(),
+                    history_store.clone(),
+                    None,
+                    Default::default(),
+                    Default::default(),
+                    "Test Agent".into(),
+                    "Test",
+                    EditorMode::AutoHeight {
+                        min_lines: 1,
+                        max_lines: None,
+                    },
+                    window,
+                    cx,
+                )
+            })
+        });
+
+        let abs_path = project.read_with(cx, |project, cx| {
+            let worktree = project.worktrees(cx).next().unwrap();
+            worktree.read(cx).abs_path().join("file.rs")
+        });
+        let mention = message_editor
+            .update(cx, |editor, cx| {
+                editor.confirm_mention_for_file(abs_path.clone(), cx)
+            })
+            .await
+            .unwrap();
+        let Mention::Text {
+            tracked_buffers, ..
+        } = &mention
+        else {
+            panic!("expected text mention");
+        };
+        tracked_buffers[0].update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "fn zero() {}\n")], None, cx)
+        });
+
+        let uri = MentionUri::File { abs_path };
+        let refreshed = cx
+            .update(|_, cx| super::refresh_mention(&uri, mention, cx))
+            .await
+            .unwrap();
+        let Mention::Text { content, .. } = refreshed else {
+            panic!("expected text mention");
+        };
+        assert_eq!(content, "fn zero() {}\nfn one() {}\n");
+    }
 }
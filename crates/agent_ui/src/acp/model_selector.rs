@@ -1,16 +1,20 @@
 use std::{cmp::Reverse, rc::Rc, sync::Arc};
 
 use acp_thread::{AgentModelInfo, AgentModelList, AgentModelSelector};
+use agent_client_protocol as acp;
 use anyhow::Result;
-use collections::IndexMap;
+use collections::{HashMap, IndexMap};
 use futures::FutureExt;
 use fuzzy::{StringMatchCandidate, match_strings};
-use gpui::{Action, AsyncWindowContext, BackgroundExecutor, DismissEvent, Task, WeakEntity};
+use gpui::{
+    Action, AsyncWindowContext, BackgroundExecutor, DismissEvent, PromptLevel, Task, WeakEntity,
+};
+use itertools::{Either, Itertools as _};
 use ordered_float::OrderedFloat;
 use picker::{Picker, PickerDelegate};
 use ui::{
     AnyElement, App, Context, DocumentationAside, DocumentationEdge, DocumentationSide,
-    IntoElement, ListItem, ListItemSpacing, SharedString, Window, prelude::*, rems,
+    IntoElement, ListItem, ListItemSpacing, SharedString, Tooltip, Window, prelude::*, rems,
 };
 use util::ResultExt;
 
@@ -40,10 +44,41 @@ pub struct AcpModelPickerDelegate {
     selected_index: usize,
     selected_description: Option<(usize, SharedString)>,
     selected_model: Option<AgentModelInfo>,
+    compatibility_warnings: HashMap<acp::ModelId, Vec<SharedString>>,
     _refresh_models_task: Task<()>,
 }
 
 impl AcpModelPickerDelegate {
+    fn select_model(
+        &mut self,
+        model_info: AgentModelInfo,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selector
+            .select_model(model_info.id.clone(), cx)
+            .detach_and_log_err(cx);
+        self.selected_model = Some(model_info);
+        let current_index = self.selected_index;
+        self.set_selected_index(current_index, window, cx);
+
+        cx.emit(DismissEvent);
+    }
+
+    fn update_compatibility_warnings(&mut self, cx: &App) {
+        let models = match &self.models {
+            Some(AgentModelList::Flat(models)) => Either::Left(models.iter()),
+            Some(AgentModelList::Grouped(groups)) => Either::Right(groups.values().flatten()),
+            None => Either::Left([].iter()),
+        };
+        self.compatibility_warnings = models
+            .filter_map(|model| {
+                let warnings = self.selector.compatibility_warnings(&model.id, cx);
+                (!warnings.is_empty()).then(|| (model.id.clone(), warnings))
+            })
+            .collect();
+    }
+
     fn new(
         selector: Rc<dyn AgentModelSelector>,
         window: &mut Window,
@@ -70,6 +105,7 @@ impl AcpModelPickerDelegate {
                         this.update_in(cx, |this, window, cx| {
                             this.delegate.models = models.ok();
                             this.delegate.selected_model = selected_model.ok();
+                            this.delegate.update_compatibility_warnings(cx);
                             this.refresh(window, cx)
                         })
                     }
@@ -91,6 +127,7 @@ impl AcpModelPickerDelegate {
             selected_model: None,
             selected_index: 0,
             selected_description: None,
+            compatibility_warnings: HashMap::default(),
             _refresh_models_task: refresh_models_task,
         }
     }
@@ -181,14 +218,29 @@ impl PickerDelegate for AcpModelPickerDelegate {
         if let Some(AcpModelPickerEntry::Model(model_info)) =
             self.filtered_entries.get(self.selected_index)
         {
-            self.selector
-                .select_model(model_info.id.clone(), cx)
-                .detach_and_log_err(cx);
-            self.selected_model = Some(model_info.clone());
-            let current_index = self.selected_index;
-            self.set_selected_index(current_index, window, cx);
-
-            cx.emit(DismissEvent);
+            let model_info = model_info.clone();
+            let Some(warnings) = self.compatibility_warnings.get(&model_info.id) else {
+                self.select_model(model_info, window, cx);
+                return;
+            };
+
+            let detail = warnings.iter().join("\n");
+            let answer = window.prompt(
+                PromptLevel::Warning,
+                &format!("Switch to {}?", model_info.name),
+                Some(&detail),
+                &["Switch Anyway", "Cancel"],
+                cx,
+            );
+            cx.spawn_in(window, async move |this, cx| {
+                if answer.await? == 0 {
+                    this.update_in(cx, |this, window, cx| {
+                        this.delegate.select_model(model_info, window, cx)
+                    })?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
         }
     }
 
@@ -231,6 +283,11 @@ impl PickerDelegate for AcpModelPickerDelegate {
                 } else {
                     Color::Muted
                 };
+                let warnings = if is_selected {
+                    None
+                } else {
+                    self.compatibility_warnings.get(&model_info.id)
+                };
 
                 Some(
                     div()
@@ -264,13 +321,27 @@ impl PickerDelegate for AcpModelPickerDelegate {
                                         .w(px(240.))
                                         .child(Label::new(model_info.name.clone()).truncate()),
                                 )
-                                .end_slot(div().pr_3().when(is_selected, |this| {
-                                    this.child(
-                                        Icon::new(IconName::Check)
-                                            .color(Color::Accent)
-                                            .size(IconSize::Small),
-                                    )
-                                })),
+                                .end_slot(
+                                    div()
+                                        .id(("model-status", ix))
+                                        .pr_3()
+                                        .when(is_selected, |this| {
+                                            this.child(
+                                                Icon::new(IconName::Check)
+                                                    .color(Color::Accent)
+                                                    .size(IconSize::Small),
+                                            )
+                                        })
+                                        .when_some(warnings, |this, warnings| {
+                                            let warnings = warnings.iter().join("\n");
+                                            this.child(
+                                                    Icon::new(IconName::Warning)
+                                                        .color(Color::Warning)
+                                                        .size(IconSize::Small),
+                                                )
+                                                .tooltip(Tooltip::text(warnings))
+                                        }),
+                                ),
                         )
                         .into_any_element()
                 )
@@ -329,10 +400,10 @@ fn info_list_to_picker_entries(
 ) -> impl Iterator<Item = AcpModelPickerEntry> {
     match model_list {
         AgentModelList::Flat(list) => {
-            itertools::Either::Left(list.into_iter().map(AcpModelPickerEntry::Model))
+            Either::Left(list.into_iter().map(AcpModelPickerEntry::Model))
         }
         AgentModelList::Grouped(index_map) => {
-            itertools::Either::Right(index_map.into_iter().flat_map(|(group_name, models)| {
+            Either::Right(index_map.into_iter().flat_map(|(group_name, models)| {
                 std::iter::once(AcpModelPickerEntry::Separator(group_name.0))
                     .chain(models.into_iter().map(AcpModelPickerEntry::Model))
             }))
@@ -401,7 +472,6 @@ async fn fuzzy_search(
 
 #[cfg(test)]
 mod tests {
-    use agent_client_protocol as acp;
     use gpui::TestAppContext;
 
     use super::*;
@@ -1,5 +1,6 @@
 use crate::acp::AcpThreadView;
 use crate::{AgentPanel, RemoveSelectedThread};
+use agent_client_protocol as acp;
 use agent2::{HistoryEntry, HistoryStore};
 use chrono::{Datelike as _, Local, NaiveDate, TimeDelta};
 use editor::{Editor, EditorEvent};
@@ -8,16 +9,22 @@ use gpui::{
     App, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Task,
     UniformListScrollHandle, WeakEntity, Window, uniform_list,
 };
-use std::{fmt::Display, ops::Range};
+use itertools::Itertools as _;
+use project::Project;
+use std::{fmt::Display, ops::Range, path::PathBuf};
 use text::Bias;
 use time::{OffsetDateTime, UtcOffset};
 use ui::{
     HighlightedLabel, IconButtonShape, ListItem, ListItemSpacing, Tooltip, WithScrollbar,
     prelude::*,
 };
+use util::ResultExt as _;
 
 pub struct AcpThreadHistory {
     pub(crate) history_store: Entity<HistoryStore>,
+    project: WeakEntity<Project>,
+    show_project_threads_only: bool,
+    tags_editor: Option<(acp::SessionId, Entity<Editor>)>,
     scroll_handle: UniformListScrollHandle,
     selected_index: usize,
     hovered_index: Option<usize>,
@@ -63,12 +70,13 @@ impl EventEmitter<ThreadHistoryEvent> for AcpThreadHistory {}
 impl AcpThreadHistory {
     pub(crate) fn new(
         history_store: Entity<agent2::HistoryStore>,
+        project: WeakEntity<Project>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
         let search_editor = cx.new(|cx| {
             let mut editor = Editor::single_line(window, cx);
-            editor.set_placeholder_text("Search threads...", window, cx);
+            editor.set_placeholder_text("Search threads, or filter by #tag...", window, cx);
             editor
         });
 
@@ -91,6 +99,9 @@ impl AcpThreadHistory {
 
         let mut this = Self {
             history_store,
+            project,
+            show_project_threads_only: false,
+            tags_editor: None,
             scroll_handle,
             selected_index: 0,
             hovered_index: None,
@@ -109,13 +120,25 @@ impl AcpThreadHistory {
     }
 
     fn update_visible_items(&mut self, preserve_selected_item: bool, cx: &mut Context<Self>) {
+        let query = SearchQuery::parse(&self.search_query);
+        let project_worktrees = self
+            .show_project_threads_only
+            .then(|| self.project_worktree_paths(cx));
         let entries = self
             .history_store
-            .update(cx, |store, _| store.entries().collect());
-        let new_list_items = if self.search_query.is_empty() {
+            .read(cx)
+            .entries()
+            .filter(|entry| query.matches_tags(entry.tags()))
+            .filter(|entry| {
+                project_worktrees
+                    .as_ref()
+                    .is_none_or(|worktrees| entry.belongs_to_worktrees(worktrees))
+            })
+            .collect();
+        let new_list_items = if query.text.is_empty() {
             self.add_list_separators(entries, cx)
         } else {
-            self.filter_search_results(entries, cx)
+            self.filter_search_results(entries, query.text, cx)
         };
         let selected_history_entry = if preserve_selected_item {
             self.selected_history_entry().cloned()
@@ -176,12 +199,28 @@ impl AcpThreadHistory {
         })
     }
 
+    fn project_worktree_paths(&self, cx: &App) -> Vec<PathBuf> {
+        self.project
+            .upgrade()
+            .map(|project| {
+                project
+                    .read(cx)
+                    .visible_worktrees(cx)
+                    .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     fn filter_search_results(
         &self,
         entries: Vec<HistoryEntry>,
-        cx: &App,
+        query: String,
+        cx: &mut Context<Self>,
     ) -> Task<Vec<ListItemType>> {
-        let query = self.search_query.clone();
+        let content_matches = self
+            .history_store
+            .update(cx, |store, cx| store.search_threads(query.clone(), cx));
         cx.background_spawn({
             let executor = cx.background_executor().clone();
             async move {
@@ -204,17 +243,103 @@ impl AcpThreadHistory {
                 )
                 .await;
 
-                matches
+                let mut items = matches
                     .into_iter()
                     .map(|search_match| ListItemType::SearchResult {
                         entry: entries[search_match.candidate_id].clone(),
                         positions: search_match.positions,
                     })
-                    .collect()
+                    .collect::<Vec<_>>();
+
+                // Threads whose messages match the query are listed after the title matches.
+                let content_matches = content_matches.await.log_err().unwrap_or_default();
+                for session_id in content_matches {
+                    if items.len() >= MAX_MATCHES {
+                        break;
+                    }
+                    let already_matched = items.iter().any(|item| {
+                        matches!(
+                            item.history_entry(),
+                            Some(HistoryEntry::AcpThread(thread)) if thread.id == session_id
+                        )
+                    });
+                    if already_matched {
+                        continue;
+                    }
+                    if let Some(entry) = entries.iter().find(|entry| {
+                        matches!(entry, HistoryEntry::AcpThread(thread) if thread.id == session_id)
+                    }) {
+                        items.push(ListItemType::SearchResult {
+                            entry: entry.clone(),
+                            positions: Vec::new(),
+                        });
+                    }
+                }
+
+                items
             }
         })
     }
 
+    fn toggle_project_threads_only(&mut self, cx: &mut Context<Self>) {
+        self.show_project_threads_only = !self.show_project_threads_only;
+        self.update_visible_items(false, cx);
+    }
+
+    fn branch_thread(&mut self, visible_item_ix: usize, cx: &mut Context<Self>) {
+        let Some(HistoryEntry::AcpThread(thread)) = self.get_history_entry(visible_item_ix) else {
+            return;
+        };
+        let task = self
+            .history_store
+            .update(cx, |store, cx| store.branch_thread(thread.id.clone(), cx));
+        cx.spawn(async move |this, cx| {
+            let branch = task.await?;
+            this.update(cx, |_, cx| {
+                cx.emit(ThreadHistoryEvent::Open(HistoryEntry::AcpThread(branch)))
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn edit_tags(&mut self, visible_item_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(HistoryEntry::AcpThread(thread)) = self.get_history_entry(visible_item_ix) else {
+            return;
+        };
+        let session_id = thread.id.clone();
+        let tags = thread.tags.iter().join(", ");
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Comma-separated tags", window, cx);
+            editor.set_text(tags, window, cx);
+            editor
+        });
+        window.focus(&editor.focus_handle(cx));
+        self.tags_editor = Some((session_id, editor));
+        cx.notify();
+    }
+
+    fn confirm_tags(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((session_id, editor)) = self.tags_editor.take() else {
+            return;
+        };
+        let tags = parse_tags(&editor.read(cx).text(cx));
+        self.history_store
+            .update(cx, |store, cx| store.set_thread_tags(session_id, tags, cx))
+            .detach_and_log_err(cx);
+        window.focus(&self.search_editor.focus_handle(cx));
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.tags_editor.take().is_some() {
+            window.focus(&self.search_editor.focus_handle(cx));
+            cx.notify();
+        } else {
+            cx.propagate();
+        }
+    }
+
     fn search_produced_no_matches(&self) -> bool {
         self.visible_items.is_empty() && !self.search_query.is_empty()
     }
@@ -298,8 +423,12 @@ impl AcpThreadHistory {
         self.set_selected_index(self.visible_items.len() - 1, Bias::Left, cx);
     }
 
-    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
-        self.confirm_entry(self.selected_index, cx);
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.tags_editor.is_some() {
+            self.confirm_tags(window, cx);
+        } else {
+            self.confirm_entry(self.selected_index, cx);
+        }
     }
 
     fn confirm_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
@@ -350,6 +479,24 @@ impl AcpThreadHistory {
     }
 
     fn render_list_item(&self, item: &ListItemType, ix: usize, cx: &Context<Self>) -> AnyElement {
+        if let Some((session_id, editor)) = &self.tags_editor
+            && let Some(HistoryEntry::AcpThread(thread)) = item.history_entry()
+            && &thread.id == session_id
+        {
+            return h_flex()
+                .w_full()
+                .pb_1()
+                .px_2()
+                .gap_2()
+                .child(
+                    Icon::new(IconName::Hash)
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(editor.clone())
+                .into_any_element();
+        }
+
         match item {
             ListItemType::Entry { entry, format } => self
                 .render_history_entry(entry, *format, ix, Vec::default(), cx)
@@ -386,6 +533,7 @@ impl AcpThreadHistory {
         let hovered = Some(ix) == self.hovered_index;
         let timestamp = entry.updated_at().timestamp();
         let thread_timestamp = format.format_timestamp(timestamp, self.local_timezone);
+        let is_agent_thread = matches!(entry, HistoryEntry::AcpThread(_));
 
         h_flex()
             .w_full()
@@ -401,9 +549,19 @@ impl AcpThreadHistory {
                             .gap_2()
                             .justify_between()
                             .child(
-                                HighlightedLabel::new(entry.title(), highlight_positions)
-                                    .size(LabelSize::Small)
-                                    .truncate(),
+                                h_flex()
+                                    .min_w_0()
+                                    .gap_1()
+                                    .child(
+                                        HighlightedLabel::new(entry.title(), highlight_positions)
+                                            .size(LabelSize::Small)
+                                            .truncate(),
+                                    )
+                                    .children(entry.tags().iter().map(|tag| {
+                                        Label::new(format!("#{tag}"))
+                                            .size(LabelSize::XSmall)
+                                            .color(Color::Muted)
+                                    })),
                             )
                             .child(
                                 Label::new(thread_timestamp)
@@ -420,17 +578,48 @@ impl AcpThreadHistory {
 
                         cx.notify();
                     }))
-                    .end_slot::<IconButton>(if hovered {
+                    .end_slot::<Div>(if hovered {
                         Some(
-                            IconButton::new("delete", IconName::Trash)
-                                .shape(IconButtonShape::Square)
-                                .icon_size(IconSize::XSmall)
-                                .icon_color(Color::Muted)
-                                .tooltip(move |window, cx| {
-                                    Tooltip::for_action("Delete", &RemoveSelectedThread, window, cx)
+                            h_flex()
+                                .gap_0p5()
+                                .when(is_agent_thread, |this| {
+                                    this.child(
+                                        IconButton::new("branch", IconName::GitBranch)
+                                            .shape(IconButtonShape::Square)
+                                            .icon_size(IconSize::XSmall)
+                                            .icon_color(Color::Muted)
+                                            .tooltip(Tooltip::text("Branch into New Thread"))
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.branch_thread(ix, cx)
+                                            })),
+                                    )
+                                    .child(
+                                        IconButton::new("edit-tags", IconName::Hash)
+                                            .shape(IconButtonShape::Square)
+                                            .icon_size(IconSize::XSmall)
+                                            .icon_color(Color::Muted)
+                                            .tooltip(Tooltip::text("Edit Tags"))
+                                            .on_click(cx.listener(move |this, _, window, cx| {
+                                                this.edit_tags(ix, window, cx)
+                                            })),
+                                    )
                                 })
-                                .on_click(
-                                    cx.listener(move |this, _, _, cx| this.remove_thread(ix, cx)),
+                                .child(
+                                    IconButton::new("delete", IconName::Trash)
+                                        .shape(IconButtonShape::Square)
+                                        .icon_size(IconSize::XSmall)
+                                        .icon_color(Color::Muted)
+                                        .tooltip(move |window, cx| {
+                                            Tooltip::for_action(
+                                                "Delete",
+                                                &RemoveSelectedThread,
+                                                window,
+                                                cx,
+                                            )
+                                        })
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.remove_thread(ix, cx)
+                                        })),
                                 ),
                         )
                     } else {
@@ -459,6 +648,7 @@ impl Render for AcpThreadHistory {
             .on_action(cx.listener(Self::select_last))
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::remove_selected_thread))
+            .on_action(cx.listener(Self::cancel))
             .when(!self.history_store.read(cx).is_empty(cx), |parent| {
                 parent.child(
                     h_flex()
@@ -475,7 +665,18 @@ impl Render for AcpThreadHistory {
                                 .color(Color::Muted)
                                 .size(IconSize::Small),
                         )
-                        .child(self.search_editor.clone()),
+                        .child(self.search_editor.clone())
+                        .child(
+                            IconButton::new("project-threads-only", IconName::Folder)
+                                .shape(IconButtonShape::Square)
+                                .icon_size(IconSize::Small)
+                                .toggle_state(self.show_project_threads_only)
+                                .selected_icon_color(Color::Accent)
+                                .tooltip(Tooltip::text("Only Show Threads From This Project"))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.toggle_project_threads_only(cx)
+                                })),
+                        ),
                 )
             })
             .child({
@@ -523,6 +724,44 @@ impl Render for AcpThreadHistory {
     }
 }
 
+/// A search query, split into `#tag` filters and the text to search for.
+#[derive(Debug, PartialEq)]
+struct SearchQuery {
+    text: String,
+    tags: Vec<String>,
+}
+
+impl SearchQuery {
+    fn parse(query: &str) -> Self {
+        let (tags, words): (Vec<_>, Vec<_>) = query
+            .split_whitespace()
+            .partition(|word| word.len() > 1 && word.starts_with('#'));
+        Self {
+            text: words.join(" "),
+            tags: tags
+                .into_iter()
+                .map(|tag| tag.trim_start_matches('#').to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn matches_tags(&self, tags: &[SharedString]) -> bool {
+        self.tags.iter().all(|filter| {
+            tags.iter()
+                .any(|tag| tag.to_lowercase().starts_with(filter.as_str()))
+        })
+    }
+}
+
+fn parse_tags(text: &str) -> Vec<SharedString> {
+    text.split(',')
+        .map(|tag| tag.trim().trim_start_matches('#').trim())
+        .filter(|tag| !tag.is_empty())
+        .unique()
+        .map(|tag| SharedString::from(tag.to_string()))
+        .collect()
+}
+
 #[derive(IntoElement)]
 pub struct AcpHistoryEntryElement {
     entry: HistoryEntry,
@@ -744,6 +983,24 @@ mod tests {
     use super::*;
     use chrono::NaiveDate;
 
+    #[test]
+    fn test_search_query() {
+        let query = SearchQuery::parse("#Work parser  #bugs error");
+        assert_eq!(query.text, "parser error");
+        assert_eq!(query.tags, vec!["work".to_string(), "bugs".to_string()]);
+        assert!(query.matches_tags(&["bugs".into(), "work/client".into()]));
+        assert!(!query.matches_tags(&["bugs".into()]));
+
+        let query = SearchQuery::parse("# issue");
+        assert_eq!(query.text, "# issue");
+        assert!(query.tags.is_empty());
+
+        assert_eq!(
+            parse_tags(" #work, bugs,,work "),
+            vec![SharedString::from("work"), SharedString::from("bugs")]
+        );
+    }
+
     #[test]
     fn test_time_bucket_from_dates() {
         let today = NaiveDate::from_ymd_opt(2023, 1, 15).unwrap();
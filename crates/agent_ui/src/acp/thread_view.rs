@@ -24,9 +24,10 @@ use futures::FutureExt as _;
 use gpui::{
     Action, Animation, AnimationExt, AnyView, App, BorderStyle, ClickEvent, ClipboardItem,
     CursorStyle, EdgesRefinement, ElementId, Empty, Entity, FocusHandle, Focusable, Hsla, Length,
-    ListOffset, ListState, PlatformDisplay, SharedString, StyleRefinement, Subscription, Task,
-    TextStyle, TextStyleRefinement, UnderlineStyle, WeakEntity, Window, WindowHandle, div,
-    ease_in_out, linear_color_stop, linear_gradient, list, point, prelude::*, pulsating_between,
+    ListOffset, ListState, PlatformDisplay, PromptLevel, SharedString, Stateful, StyleRefinement,
+    Subscription, Task, TextStyle, TextStyleRefinement, UnderlineStyle, WeakEntity, Window,
+    WindowHandle, div, ease_in_out, linear_color_stop, linear_gradient, list, point, prelude::*,
+    pulsating_between,
 };
 use language::Buffer;
 
@@ -35,6 +36,7 @@ use markdown::{HeadingLevelStyles, Markdown, MarkdownElement, MarkdownStyle};
 use project::{Project, ProjectEntryId};
 use prompt_store::{PromptId, PromptStore};
 use rope::Point;
+use semantic_index::{SearchResult, SemanticDb};
 use settings::{NotifyWhenAgentWaiting, Settings as _, SettingsStore};
 use std::cell::RefCell;
 use std::path::Path;
@@ -1074,9 +1076,81 @@ impl AcpThreadView {
             return;
         }
 
+        if let Some(warning) = self.token_limit_warning(cx) {
+            let answer = window.prompt(
+                PromptLevel::Warning,
+                &warning,
+                Some("You can change this limit in the `agent.token_usage` settings."),
+                &["Send", "Cancel"],
+                cx,
+            );
+            cx.spawn_in(window, async move |this, cx| {
+                if answer.await.ok() == Some(0) {
+                    this.update_in(cx, |this, window, cx| {
+                        this.send_impl(this.message_editor.clone(), window, cx)
+                    })
+                    .ok();
+                }
+            })
+            .detach();
+            return;
+        }
+
         self.send_impl(self.message_editor.clone(), window, cx)
     }
 
+    /// Returns a warning when sending the current message is estimated to exceed
+    /// the token or cost limits configured in `agent.token_usage`.
+    fn token_limit_warning(&self, cx: &App) -> Option<String> {
+        let settings = AgentSettings::get_global(cx).token_usage.clone();
+        if settings.warn_above_tokens.is_none() && settings.warn_above_cost.is_none() {
+            return None;
+        }
+
+        let thread = self.as_native_thread(cx)?;
+        let (model, context_tokens) = {
+            let thread = thread.read(cx);
+            let context_tokens = thread
+                .latest_token_usage()
+                .map_or(0, |usage| usage.used_tokens);
+            (thread.model()?.clone(), context_tokens)
+        };
+        let message_tokens = self.message_editor.read(cx).estimated_token_count(cx);
+        let input_tokens = context_tokens + message_tokens;
+        let cost = settings.estimated_cost(
+            &model.provider_id().0,
+            &model.id().0,
+            &language_model::TokenUsage {
+                input_tokens,
+                ..Default::default()
+            },
+        );
+
+        let exceeds_tokens = settings
+            .warn_above_tokens
+            .is_some_and(|limit| input_tokens > limit);
+        let exceeds_cost = settings
+            .warn_above_cost
+            .zip(cost)
+            .is_some_and(|(limit, cost)| cost > limit);
+        if !exceeds_tokens && !exceeds_cost {
+            return None;
+        }
+
+        let mut warning = format!(
+            "This request is estimated to use about {} input tokens",
+            crate::text_thread_editor::humanize_token_count(input_tokens)
+        );
+        if let Some(cost) = cost {
+            warning.push_str(&format!(
+                " (at least {})",
+                crate::text_thread_editor::humanize_cost(cost)
+            ));
+        }
+        warning.push_str(". Send anyway?");
+        Some(warning)
+    }
+
     fn stop_current_and_send_new_message(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(thread) = self.thread().cloned() else {
             return;
@@ -1115,6 +1189,11 @@ impl AcpThreadView {
         });
 
         let agent_telemetry_id = self.agent.telemetry_id();
+        let project_index = if self.as_native_thread(cx).is_some() {
+            SemanticDb::project_index(&self.project, cx)
+        } else {
+            None
+        };
 
         self.thread_error.take();
         self.editing_message.take();
@@ -1141,17 +1220,26 @@ impl AcpThreadView {
         .detach();
 
         let task = cx.spawn_in(window, async move |this, cx| {
-            let (contents, tracked_buffers) = contents.await?;
+            let (mut contents, tracked_buffers) = contents.await?;
 
             if contents.is_empty() {
                 return Ok(());
             }
 
+            if let Some(project_index) = project_index {
+                let search = project_index
+                    .read_with(cx, |index, cx| index.search(message_text(&contents), cx))?;
+                match search.await {
+                    Ok(results) => contents.extend(results.into_iter().map(retrieved_snippet)),
+                    Err(error) => log::error!("failed to search semantic index: {error:#}"),
+                }
+            }
+
             this.update_in(cx, |this, window, cx| {
                 this.set_editor_is_expanded(false, cx);
                 this.scroll_to_bottom(cx);
                 this.message_editor.update(cx, |message_editor, cx| {
-                    message_editor.clear(window, cx);
+                    message_editor.clear_unpinned(window, cx);
                 });
             })?;
             let send = thread.update(cx, |thread, cx| {
@@ -3996,16 +4084,29 @@ impl AcpThreadView {
             .is_some_and(|model| model.provider_id() == language_model::ZED_CLOUD_PROVIDER_ID)
     }
 
-    fn render_token_usage(&self, cx: &mut Context<Self>) -> Option<Div> {
+    fn render_token_usage(&self, cx: &mut Context<Self>) -> Option<Stateful<Div>> {
         let thread = self.thread()?.read(cx);
         let usage = thread.token_usage()?;
         let is_generating = thread.status() != ThreadStatus::Idle;
 
         let used = crate::text_thread_editor::humanize_token_count(usage.used_tokens);
         let max = crate::text_thread_editor::humanize_token_count(usage.max_tokens);
+        let cumulative_usage = self.cumulative_usage_summary(cx);
 
         Some(
             h_flex()
+                .id("token-usage")
+                .when_some(cumulative_usage, |this, summary| {
+                    this.tooltip(move |window, cx| {
+                        Tooltip::with_meta(
+                            "Context Window",
+                            None,
+                            format!("Used by this thread so far: {summary}"),
+                            window,
+                            cx,
+                        )
+                    })
+                })
                 .flex_shrink_0()
                 .gap_0p5()
                 .mr_1p5()
@@ -4038,6 +4139,39 @@ impl AcpThreadView {
         )
     }
 
+    /// Summarizes the tokens used by every request in the thread, with the
+    /// estimated cost when the model has a configured price.
+    fn cumulative_usage_summary(&self, cx: &App) -> Option<String> {
+        let thread = self.as_native_thread(cx)?;
+        let thread = thread.read(cx);
+        let usage = thread.cumulative_token_usage();
+        if usage.total_tokens() == 0 {
+            return None;
+        }
+
+        let mut summary = format!(
+            "{} in, {} out",
+            crate::text_thread_editor::humanize_token_count(
+                usage.input_tokens
+                    + usage.cache_read_input_tokens
+                    + usage.cache_creation_input_tokens
+            ),
+            crate::text_thread_editor::humanize_token_count(usage.output_tokens),
+        );
+        let model = thread.model()?;
+        if let Some(cost) = AgentSettings::get_global(cx).token_usage.estimated_cost(
+            &model.provider_id().0,
+            &model.id().0,
+            &usage,
+        ) {
+            summary.push_str(&format!(
+                " (~{})",
+                crate::text_thread_editor::humanize_cost(cost)
+            ));
+        }
+        Some(summary)
+    }
+
     fn toggle_burn_mode(
         &mut self,
         _: &ToggleBurnMode,
@@ -4361,6 +4495,8 @@ impl AcpThreadView {
                                     id,
                                     title: name.into(),
                                     updated_at: Default::default(),
+                                    tags: Vec::new(),
+                                    worktree_paths: Vec::new(),
                                 },
                                 window,
                                 cx,
@@ -5370,6 +5506,37 @@ impl AcpThreadView {
     }
 }
 
+/// The text the user typed, without the contents of any mentions.
+fn message_text(contents: &[acp::ContentBlock]) -> String {
+    contents
+        .iter()
+        .filter_map(|block| match block {
+            acp::ContentBlock::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Includes a snippet retrieved from the semantic index as a selection mention,
+/// so that it's shown in the message alongside the user's own mentions.
+fn retrieved_snippet(result: SearchResult) -> acp::ContentBlock {
+    let uri = MentionUri::Selection {
+        abs_path: Some(result.abs_path),
+        line_range: result.rows.start..=result.rows.end.saturating_sub(1),
+    };
+    acp::ContentBlock::Resource(acp::EmbeddedResource {
+        annotations: None,
+        resource: acp::EmbeddedResourceResource::TextResourceContents(acp::TextResourceContents {
+            mime_type: None,
+            text: result.text,
+            uri: uri.to_uri().to_string(),
+            meta: None,
+        }),
+        meta: None,
+    })
+}
+
 fn loading_contents_spinner(size: IconSize) -> AnyElement {
     Icon::new(IconName::LoadCircle)
         .size(size)
@@ -23,7 +23,8 @@ use gpui::{
 };
 use language::LanguageRegistry;
 use language_model::{
-    LanguageModelProvider, LanguageModelProviderId, LanguageModelRegistry, ZED_CLOUD_PROVIDER_ID,
+    LanguageModelProvider, LanguageModelProviderId, LanguageModelRegistry,
+    LanguageModelUsageTracker, ModelTokenUsage, ZED_CLOUD_PROVIDER_ID,
 };
 use notifications::status_toast::{StatusToast, ToastIcon};
 use project::{
@@ -46,6 +47,7 @@ pub(crate) use manage_profiles_modal::ManageProfilesModal;
 use crate::{
     AddContextServer,
     agent_configuration::add_llm_provider_modal::{AddLlmProviderModal, LlmCompatibleProvider},
+    text_thread_editor::{humanize_cost, humanize_token_count},
 };
 
 pub struct AgentConfiguration {
@@ -96,6 +98,9 @@ impl AgentConfiguration {
 
         cx.subscribe(&context_server_store, |_, _, _, cx| cx.notify())
             .detach();
+        if let Some(usage_tracker) = LanguageModelUsageTracker::global(cx) {
+            cx.observe(&usage_tracker, |_, _, cx| cx.notify()).detach();
+        }
 
         let mut this = Self {
             fs,
@@ -498,6 +503,117 @@ impl AgentConfiguration {
             .child(self.render_modifier_to_send(cx))
     }
 
+    fn render_token_usage_section(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        let models = LanguageModelUsageTracker::global(cx)
+            .map(|tracker| tracker.read(cx).models().to_vec())
+            .unwrap_or_default();
+        let token_usage_settings = &AgentSettings::get_global(cx).token_usage;
+        let cost_for = |entry: &ModelTokenUsage| {
+            token_usage_settings.estimated_cost(
+                &entry.provider_id.0,
+                &entry.model_id.0,
+                &entry.usage,
+            )
+        };
+        let total_cost = models
+            .iter()
+            .filter_map(cost_for)
+            .reduce(|total, cost| total + cost);
+
+        let rows = models
+            .iter()
+            .map(|entry| {
+                let mut details = format!(
+                    "{} in · {} out · {} {}",
+                    humanize_token_count(
+                        entry.usage.input_tokens
+                            + entry.usage.cache_read_input_tokens
+                            + entry.usage.cache_creation_input_tokens
+                    ),
+                    humanize_token_count(entry.usage.output_tokens),
+                    entry.request_count,
+                    if entry.request_count == 1 {
+                        "request"
+                    } else {
+                        "requests"
+                    }
+                );
+                if let Some(cost) = cost_for(entry) {
+                    details.push_str(&format!(" · ~{}", humanize_cost(cost)));
+                }
+
+                h_flex()
+                    .w_full()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        Label::new(format!(
+                            "{} / {}",
+                            entry.provider_name.0, entry.model_name.0
+                        ))
+                        .truncate(),
+                    )
+                    .child(
+                        Label::new(details)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .p(DynamicSpacing::Base16.rems(cx))
+            .pr(DynamicSpacing::Base20.rems(cx))
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .w_full()
+                    .justify_between()
+                    .gap_2()
+                    .child(
+                        v_flex()
+                            .gap_0p5()
+                            .child(Headline::new("Token Usage"))
+                            .child(
+                                Label::new(
+                                    "Tokens used since Zed was started. Costs are estimated from the prices in your `agent.token_usage` settings.",
+                                )
+                                .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        Button::new("reset-token-usage", "Reset")
+                            .style(ButtonStyle::Filled)
+                            .layer(ElevationIndex::ModalSurface)
+                            .label_size(LabelSize::Small)
+                            .disabled(models.is_empty())
+                            .on_click(|_, _, cx| {
+                                if let Some(tracker) = LanguageModelUsageTracker::global(cx) {
+                                    tracker.update(cx, |tracker, cx| tracker.reset(cx));
+                                }
+                            }),
+                    ),
+            )
+            .map(|this| {
+                if rows.is_empty() {
+                    this.child(Label::new("No requests yet.").color(Color::Muted))
+                } else {
+                    this.children(rows).when_some(total_cost, |this, total_cost| {
+                        this.child(Divider::horizontal().color(DividerColor::BorderFaded))
+                            .child(
+                                h_flex()
+                                    .w_full()
+                                    .justify_between()
+                                    .child(Label::new("Estimated Total"))
+                                    .child(Label::new(humanize_cost(total_cost))),
+                            )
+                    })
+                }
+            })
+    }
+
     fn render_zed_plan_info(&self, plan: Option<Plan>, cx: &mut Context<Self>) -> impl IntoElement {
         if let Some(plan) = plan {
             let free_chip_bg = cx
@@ -1146,6 +1262,7 @@ impl Render for AgentConfiguration {
                             .size_full()
                             .overflow_y_scroll()
                             .child(self.render_general_settings_section(cx))
+                            .child(self.render_token_usage_section(cx))
                             .child(self.render_agent_servers_section(cx))
                             .child(self.render_context_servers_section(window, cx))
                             .child(self.render_provider_configuration_section(cx)),
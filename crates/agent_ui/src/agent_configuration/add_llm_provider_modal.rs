@@ -246,6 +246,7 @@ fn save_provider_to_settings(
                         OpenAiCompatibleSettingsContent {
                             api_url,
                             available_models: models,
+                            discover_models: None,
                         },
                     );
             });
@@ -563,7 +563,9 @@ impl AgentPanel {
         let history_store = cx.new(|cx| HistoryStore::new(context_store.clone(), [], cx));
 
         let acp_history_store = cx.new(|cx| agent2::HistoryStore::new(context_store.clone(), cx));
-        let acp_history = cx.new(|cx| AcpThreadHistory::new(acp_history_store.clone(), window, cx));
+        let acp_history = cx.new(|cx| {
+            AcpThreadHistory::new(acp_history_store.clone(), project.downgrade(), window, cx)
+        });
         cx.subscribe_in(
             &acp_history,
             window,
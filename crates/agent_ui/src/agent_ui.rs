@@ -26,7 +26,7 @@ use agent::ThreadId;
 use agent_settings::{AgentProfileId, AgentSettings};
 use assistant_slash_command::SlashCommandRegistry;
 use client::Client;
-use command_palette_hooks::CommandPaletteFilter;
+use command_palette_hooks::{CommandPaletteCommand, CommandPaletteCommands, CommandPaletteFilter};
 use feature_flags::FeatureFlagAppExt as _;
 use fs::Fs;
 use gpui::{Action, App, Entity, SharedString, actions};
@@ -36,7 +36,7 @@ use language_model::{
 };
 use project::DisableAiSettings;
 use project::agent_server_store::AgentServerCommand;
-use prompt_store::PromptBuilder;
+use prompt_store::{PromptBuilder, PromptStore, PromptsUpdatedEvent};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{LanguageModelSelection, Settings as _, SettingsStore};
@@ -273,6 +273,7 @@ pub fn init(
     TextThreadEditor::init(cx);
 
     register_slash_commands(cx);
+    register_rule_commands(cx);
     inline_assistant::init(
         fs.clone(),
         prompt_builder.clone(),
@@ -297,6 +298,47 @@ pub fn init(
     .detach();
 }
 
+/// Adds a command palette entry for every rule in the library, which runs the rule with the
+/// inline assistant.
+fn register_rule_commands(cx: &mut App) {
+    let prompt_store = PromptStore::global(cx);
+    cx.spawn(async move |cx| {
+        let prompt_store = prompt_store.await?;
+        cx.update(|cx| {
+            sync_rule_commands(&prompt_store, cx);
+            cx.subscribe(
+                &prompt_store,
+                |prompt_store, _: &PromptsUpdatedEvent, cx| {
+                    sync_rule_commands(&prompt_store, cx);
+                },
+            )
+            .detach();
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+fn sync_rule_commands(prompt_store: &Entity<PromptStore>, cx: &mut App) {
+    let commands = prompt_store
+        .read(cx)
+        .all_prompt_metadata()
+        .into_iter()
+        .filter(|metadata| !metadata.id.is_built_in())
+        .filter_map(|metadata| {
+            let title = metadata.title?;
+            Some(CommandPaletteCommand {
+                name: format!("run rule: {title}"),
+                action: Box::new(zed_actions::assistant::RunRule {
+                    title: title.to_string(),
+                }),
+            })
+        })
+        .collect();
+    CommandPaletteCommands::update_global(cx, |palette_commands, _| {
+        palette_commands.set("rules", commands);
+    });
+}
+
 fn update_command_palette_filter(cx: &mut App) {
     let disable_ai = DisableAiSettings::get_global(cx).disable_ai;
     CommandPaletteFilter::update_global(cx, |filter, _| {
@@ -420,6 +462,7 @@ fn register_slash_commands(cx: &mut App) {
     slash_command_registry
         .register_command(assistant_slash_commands::DiagnosticsSlashCommand, true);
     slash_command_registry.register_command(assistant_slash_commands::FetchSlashCommand, true);
+    assistant_slash_commands::register_prompt_library_commands(cx);
 
     cx.observe_flag::<assistant_slash_commands::StreamingExampleSlashCommandFeatureFlag, _>({
         move |is_enabled, _cx| {
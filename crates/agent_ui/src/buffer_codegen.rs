@@ -27,7 +27,7 @@ use smol::future::FutureExt;
 use std::{
     cmp,
     future::Future,
-    iter,
+    iter, mem,
     ops::{Range, RangeInclusive},
     pin::Pin,
     sync::Arc,
@@ -230,6 +230,16 @@ impl BufferCodegen {
     pub fn last_equal_ranges<'a>(&self, cx: &'a App) -> &'a [Range<Anchor>] {
         self.active_alternative().read(cx).last_equal_ranges()
     }
+
+    pub fn accept_hunk(&mut self, hunk_ix: usize, cx: &mut Context<Self>) {
+        self.active_alternative()
+            .update(cx, |codegen, cx| codegen.accept_hunk(hunk_ix, cx));
+    }
+
+    pub fn reject_hunk(&mut self, hunk_ix: usize, cx: &mut Context<Self>) {
+        self.active_alternative()
+            .update(cx, |codegen, cx| codegen.reject_hunk(hunk_ix, cx));
+    }
 }
 
 impl EventEmitter<CodegenEvent> for BufferCodegen {}
@@ -773,6 +783,40 @@ impl CodegenAlternative {
         });
     }
 
+    /// Keeps the changes of a single hunk, so that it's no longer shown for review.
+    pub fn accept_hunk(&mut self, hunk_ix: usize, cx: &mut Context<Self>) {
+        if hunk_ix >= self.diff.hunks.len() {
+            return;
+        }
+        let mut hunks = mem::take(&mut self.diff.hunks);
+        hunks.remove(hunk_ix);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.diff.set_hunks(hunks, &snapshot);
+        cx.notify();
+    }
+
+    /// Restores the original text of a single hunk. The restoration is part of the
+    /// transformation's transaction, so undoing the assist also undoes it.
+    pub fn reject_hunk(&mut self, hunk_ix: usize, cx: &mut Context<Self>) {
+        if hunk_ix >= self.diff.hunks.len() {
+            return;
+        }
+        let mut hunks = mem::take(&mut self.diff.hunks);
+        let hunk = hunks.remove(hunk_ix);
+        let old_text = self
+            .snapshot
+            .text_for_range(
+                Point::new(hunk.old_rows.start, 0)
+                    ..row_start_or_end(&self.snapshot, hunk.old_rows.end),
+            )
+            .collect::<String>();
+        self.apply_edits([(hunk.new_range, old_text)], cx);
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.diff.set_hunks(hunks, &snapshot);
+        cx.notify();
+    }
+
     fn apply_edits(
         &mut self,
         edits: impl IntoIterator<Item = (Range<Anchor>, String)>,
@@ -815,6 +859,7 @@ impl CodegenAlternative {
 
         self.diff.deleted_row_ranges.clear();
         self.diff.inserted_row_ranges.clear();
+        self.diff.hunks.clear();
         for operation in line_operations {
             match operation {
                 LineOperation::Keep { lines } => {
@@ -866,7 +911,7 @@ impl CodegenAlternative {
         let new_range = self.range.to_point(&new_snapshot);
 
         cx.spawn(async move |codegen, cx| {
-            let (deleted_row_ranges, inserted_row_ranges) = cx
+            let hunks = cx
                 .background_spawn(async move {
                     let old_text = old_snapshot
                         .text_for_range(
@@ -889,35 +934,29 @@ impl CodegenAlternative {
 
                     let old_start_row = old_range.start.row;
                     let new_start_row = new_range.start.row;
-                    let mut deleted_row_ranges: Vec<(Anchor, RangeInclusive<u32>)> = Vec::new();
-                    let mut inserted_row_ranges = Vec::new();
-                    for (old_rows, new_rows) in line_diff(&old_text, &new_text) {
-                        let old_rows = old_start_row + old_rows.start..old_start_row + old_rows.end;
-                        let new_rows = new_start_row + new_rows.start..new_start_row + new_rows.end;
-                        if !old_rows.is_empty() {
-                            deleted_row_ranges.push((
-                                new_snapshot.anchor_before(Point::new(new_rows.start, 0)),
-                                old_rows.start..=old_rows.end - 1,
-                            ));
-                        }
-                        if !new_rows.is_empty() {
-                            let start = new_snapshot.anchor_before(Point::new(new_rows.start, 0));
-                            let new_end_row = new_rows.end - 1;
-                            let end = new_snapshot.anchor_before(Point::new(
-                                new_end_row,
-                                new_snapshot.line_len(MultiBufferRow(new_end_row)),
-                            ));
-                            inserted_row_ranges.push(start..end);
-                        }
-                    }
-                    (deleted_row_ranges, inserted_row_ranges)
+                    line_diff(&old_text, &new_text)
+                        .into_iter()
+                        .map(|(old_rows, new_rows)| {
+                            let new_rows =
+                                new_start_row + new_rows.start..new_start_row + new_rows.end;
+                            DiffHunk {
+                                old_rows: old_start_row + old_rows.start
+                                    ..old_start_row + old_rows.end,
+                                new_range: new_snapshot.anchor_before(Point::new(new_rows.start, 0))
+                                    ..new_snapshot.anchor_before(row_start_or_end(
+                                        &new_snapshot,
+                                        new_rows.end,
+                                    )),
+                            }
+                        })
+                        .collect::<Vec<_>>()
                 })
                 .await;
 
             codegen
                 .update(cx, |codegen, cx| {
-                    codegen.diff.deleted_row_ranges = deleted_row_ranges;
-                    codegen.diff.inserted_row_ranges = inserted_row_ranges;
+                    let snapshot = codegen.buffer.read(cx).snapshot(cx);
+                    codegen.diff.set_hunks(hunks, &snapshot);
                     cx.notify();
                 })
                 .ok();
@@ -925,6 +964,15 @@ impl CodegenAlternative {
     }
 }
 
+/// The start of `row`, or the end of the buffer if `row` is past its last line.
+fn row_start_or_end(snapshot: &MultiBufferSnapshot, row: u32) -> Point {
+    if row > snapshot.max_point().row {
+        snapshot.max_point()
+    } else {
+        Point::new(row, 0)
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum CodegenEvent {
     Finished,
@@ -1067,12 +1115,49 @@ fn prefixes(text: &str) -> impl Iterator<Item = &str> {
 pub struct Diff {
     pub deleted_row_ranges: Vec<(Anchor, RangeInclusive<u32>)>,
     pub inserted_row_ranges: Vec<Range<Anchor>>,
+    /// The changes that can be accepted or rejected individually, available
+    /// once the transformation has finished streaming.
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiffHunk {
+    /// The rows of the original text that this hunk replaced.
+    pub old_rows: Range<u32>,
+    /// The whole lines that replaced them in the buffer.
+    pub new_range: Range<Anchor>,
 }
 
 impl Diff {
     fn is_empty(&self) -> bool {
         self.deleted_row_ranges.is_empty() && self.inserted_row_ranges.is_empty()
     }
+
+    fn set_hunks(&mut self, hunks: Vec<DiffHunk>, snapshot: &MultiBufferSnapshot) {
+        self.deleted_row_ranges.clear();
+        self.inserted_row_ranges.clear();
+        for hunk in &hunks {
+            if !hunk.old_rows.is_empty() {
+                self.deleted_row_ranges.push((
+                    hunk.new_range.start,
+                    hunk.old_rows.start..=hunk.old_rows.end - 1,
+                ));
+            }
+
+            let new_range = hunk.new_range.to_point(snapshot);
+            if new_range.end > new_range.start {
+                let end = if new_range.end.column == 0 {
+                    let row = new_range.end.row - 1;
+                    Point::new(row, snapshot.line_len(MultiBufferRow(row)))
+                } else {
+                    new_range.end
+                };
+                self.inserted_row_ranges
+                    .push(hunk.new_range.start..snapshot.anchor_before(end));
+            }
+        }
+        self.hunks = hunks;
+    }
 }
 
 #[cfg(test)]
@@ -1161,6 +1246,76 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_accept_and_reject_hunks(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let text = indoc! {"
+            one
+            two
+            three
+            four
+            five
+        "};
+        let buffer = cx.new(|cx| Buffer::local(text, cx));
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let range = buffer.read_with(cx, |buffer, cx| {
+            let snapshot = buffer.snapshot(cx);
+            snapshot.anchor_before(Point::new(0, 0))..snapshot.anchor_after(Point::new(4, 4))
+        });
+        let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, vec![], cx).await;
+        let codegen = cx.new(|cx| {
+            CodegenAlternative::new(
+                buffer.clone(),
+                range.clone(),
+                true,
+                None,
+                project.downgrade(),
+                None,
+                None,
+                prompt_builder,
+                cx,
+            )
+        });
+
+        let chunks_tx = simulate_response_stream(&codegen, cx);
+        chunks_tx
+            .unbounded_send("ONE\ntwo\nthree\nFOUR\nfive".to_string())
+            .unwrap();
+        drop(chunks_tx);
+        cx.background_executor.run_until_parked();
+        assert_eq!(
+            codegen.read_with(cx, |codegen, _| codegen.diff.hunks.len()),
+            2
+        );
+
+        codegen.update(cx, |codegen, cx| codegen.reject_hunk(0, cx));
+        assert_eq!(
+            buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx).text()),
+            "one\ntwo\nthree\nFOUR\nfive\n"
+        );
+        assert_eq!(
+            codegen.read_with(cx, |codegen, _| codegen.diff.hunks.len()),
+            1
+        );
+
+        codegen.update(cx, |codegen, cx| codegen.accept_hunk(0, cx));
+        assert_eq!(
+            buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx).text()),
+            "one\ntwo\nthree\nFOUR\nfive\n"
+        );
+        assert!(codegen.read_with(cx, |codegen, _| codegen.diff.is_empty()));
+
+        // Rejections are part of the transformation, so undoing it restores the original text.
+        codegen.update(cx, |codegen, cx| codegen.undo(cx));
+        assert_eq!(
+            buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx).text()),
+            text
+        );
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_autoindent_when_generating_past_indentation(
         cx: &mut TestAppContext,
@@ -358,6 +358,60 @@ impl InlineAssistant {
         }
     }
 
+    pub fn run_rule(
+        workspace: &mut Workspace,
+        action: &zed_actions::assistant::RunRule,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let prompt_store = PromptStore::global(cx);
+        let title = action.title.clone();
+        cx.spawn_in(window, async move |workspace, cx| {
+            let prompt_store = prompt_store.await?;
+            let body = prompt_store
+                .read_with(cx, |prompt_store, cx| {
+                    let id = prompt_store
+                        .id_for_title(&title)
+                        .with_context(|| format!("no rule titled {title:?}"))?;
+                    anyhow::Ok(prompt_store.load(id, cx))
+                })??
+                .await?;
+            let variables =
+                cx.update(|_, cx| assistant_slash_commands::prompt_variables(&workspace, cx))?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let action = zed_actions::assistant::InlineAssist {
+                    prompt: Some(variables.expand(&body)),
+                };
+                Self::inline_assist(workspace, &action, window, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn explain_terminal_command(
+        workspace: &mut Workspace,
+        _: &zed_actions::assistant::ExplainTerminalCommand,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if !AgentSettings::get_global(cx).enabled(cx) {
+            return;
+        }
+
+        let Some(InlineAssistTarget::Terminal(terminal_view)) = Self::resolve_inline_assist_target(
+            workspace,
+            workspace.panel::<AgentPanel>(cx),
+            window,
+            cx,
+        ) else {
+            return;
+        };
+
+        TerminalInlineAssistant::update_global(cx, |assistant, cx| {
+            assistant.explain(&terminal_view, cx.entity().downgrade(), window, cx)
+        });
+    }
+
     pub fn assist(
         &mut self,
         editor: &Entity<Editor>,
@@ -1417,67 +1471,106 @@ impl InlineAssistant {
         let codegen = assist.codegen.read(cx);
         let old_snapshot = codegen.snapshot(cx);
         let old_buffer = codegen.old_buffer(cx);
-        let deleted_row_ranges = codegen.diff(cx).deleted_row_ranges.clone();
+        let diff = codegen.diff(cx);
+        // Once the transformation is done, every hunk gets its own block with
+        // controls to accept or reject it, even if it only inserted lines.
+        let blocks = if diff.hunks.is_empty() {
+            diff.deleted_row_ranges
+                .iter()
+                .map(|(new_row, old_row_range)| (*new_row, Some(old_row_range.clone()), None))
+                .collect::<Vec<_>>()
+        } else {
+            diff.hunks
+                .iter()
+                .enumerate()
+                .map(|(ix, hunk)| {
+                    let old_row_range = (!hunk.old_rows.is_empty())
+                        .then(|| hunk.old_rows.start..=hunk.old_rows.end - 1);
+                    (hunk.new_range.start, old_row_range, Some(ix))
+                })
+                .collect()
+        };
 
         editor.update(cx, |editor, cx| {
             let old_blocks = mem::take(&mut decorations.removed_line_block_ids);
             editor.remove_blocks(old_blocks, None, cx);
 
             let mut new_blocks = Vec::new();
-            for (new_row, old_row_range) in deleted_row_ranges {
-                let (_, buffer_start) = old_snapshot
-                    .point_to_buffer_offset(Point::new(*old_row_range.start(), 0))
-                    .unwrap();
-                let (_, buffer_end) = old_snapshot
-                    .point_to_buffer_offset(Point::new(
-                        *old_row_range.end(),
-                        old_snapshot.line_len(MultiBufferRow(*old_row_range.end())),
-                    ))
-                    .unwrap();
-
-                let deleted_lines_editor = cx.new(|cx| {
-                    let multi_buffer =
-                        cx.new(|_| MultiBuffer::without_headers(language::Capability::ReadOnly));
-                    multi_buffer.update(cx, |multi_buffer, cx| {
-                        multi_buffer.push_excerpts(
-                            old_buffer.clone(),
-                            Some(ExcerptRange::new(buffer_start..buffer_end)),
+            for (new_row, old_row_range, hunk_ix) in blocks {
+                let deleted_lines_editor = old_row_range.map(|old_row_range| {
+                    let (_, buffer_start) = old_snapshot
+                        .point_to_buffer_offset(Point::new(*old_row_range.start(), 0))
+                        .unwrap();
+                    let (_, buffer_end) = old_snapshot
+                        .point_to_buffer_offset(Point::new(
+                            *old_row_range.end(),
+                            old_snapshot.line_len(MultiBufferRow(*old_row_range.end())),
+                        ))
+                        .unwrap();
+
+                    cx.new(|cx| {
+                        let multi_buffer = cx
+                            .new(|_| MultiBuffer::without_headers(language::Capability::ReadOnly));
+                        multi_buffer.update(cx, |multi_buffer, cx| {
+                            multi_buffer.push_excerpts(
+                                old_buffer.clone(),
+                                Some(ExcerptRange::new(buffer_start..buffer_end)),
+                                cx,
+                            );
+                        });
+
+                        enum DeletedLines {}
+                        let mut editor = Editor::for_multibuffer(multi_buffer, None, window, cx);
+                        editor.disable_scrollbars_and_minimap(window, cx);
+                        editor.set_soft_wrap_mode(language::language_settings::SoftWrap::None, cx);
+                        editor.set_show_wrap_guides(false, cx);
+                        editor.set_show_gutter(false, cx);
+                        editor.scroll_manager.set_forbid_vertical_scroll(true);
+                        editor.set_read_only(true);
+                        editor.set_show_edit_predictions(Some(false), window, cx);
+                        editor.highlight_rows::<DeletedLines>(
+                            Anchor::min()..Anchor::max(),
+                            cx.theme().status().deleted_background,
+                            Default::default(),
                             cx,
                         );
-                    });
-
-                    enum DeletedLines {}
-                    let mut editor = Editor::for_multibuffer(multi_buffer, None, window, cx);
-                    editor.disable_scrollbars_and_minimap(window, cx);
-                    editor.set_soft_wrap_mode(language::language_settings::SoftWrap::None, cx);
-                    editor.set_show_wrap_guides(false, cx);
-                    editor.set_show_gutter(false, cx);
-                    editor.scroll_manager.set_forbid_vertical_scroll(true);
-                    editor.set_read_only(true);
-                    editor.set_show_edit_predictions(Some(false), window, cx);
-                    editor.highlight_rows::<DeletedLines>(
-                        Anchor::min()..Anchor::max(),
-                        cx.theme().status().deleted_background,
-                        Default::default(),
-                        cx,
-                    );
-                    editor
+                        editor
+                    })
                 });
 
-                let height =
-                    deleted_lines_editor.update(cx, |editor, cx| editor.max_point(cx).row().0 + 1);
+                let deleted_height = deleted_lines_editor.as_ref().map_or(0, |editor| {
+                    editor.update(cx, |editor, cx| editor.max_point(cx).row().0 + 1)
+                });
+                let height = deleted_height + u32::from(hunk_ix.is_some());
                 new_blocks.push(BlockProperties {
                     placement: BlockPlacement::Above(new_row),
                     height: Some(height),
                     style: BlockStyle::Flex,
                     render: Arc::new(move |cx| {
-                        div()
+                        v_flex()
                             .block_mouse_except_scroll()
-                            .bg(cx.theme().status().deleted_background)
                             .size_full()
                             .h(height as f32 * cx.window.line_height())
-                            .pl(cx.margins.gutter.full_width())
-                            .child(deleted_lines_editor.clone())
+                            .when_some(hunk_ix, |this, hunk_ix| {
+                                this.child(render_hunk_controls(
+                                    assist_id,
+                                    hunk_ix,
+                                    cx.window.line_height(),
+                                    cx.margins.gutter.full_width(),
+                                ))
+                            })
+                            .when_some(
+                                deleted_lines_editor.clone(),
+                                |this, deleted_lines_editor| {
+                                    this.child(
+                                        div()
+                                            .flex_1()
+                                            .bg(cx.theme().status().deleted_background)
+                                            .pl(cx.margins.gutter.full_width())
+                                            .child(deleted_lines_editor),
+                                    )
+                                },
+                            )
                             .into_any_element()
                     }),
                     priority: 0,
@@ -1491,6 +1584,51 @@ impl InlineAssistant {
         })
     }
 
+    fn accept_hunk(
+        &mut self,
+        assist_id: InlineAssistId,
+        hunk_ix: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if let Some(assist) = self.assists.get(&assist_id) {
+            assist
+                .codegen
+                .update(cx, |codegen, cx| codegen.accept_hunk(hunk_ix, cx));
+            self.finish_assist_if_reviewed(assist_id, window, cx);
+        }
+    }
+
+    fn reject_hunk(
+        &mut self,
+        assist_id: InlineAssistId,
+        hunk_ix: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if let Some(assist) = self.assists.get(&assist_id) {
+            assist
+                .codegen
+                .update(cx, |codegen, cx| codegen.reject_hunk(hunk_ix, cx));
+            self.finish_assist_if_reviewed(assist_id, window, cx);
+        }
+    }
+
+    /// Keeps the result of an assist once every hunk has been accepted or rejected.
+    fn finish_assist_if_reviewed(
+        &mut self,
+        assist_id: InlineAssistId,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(assist) = self.assists.get(&assist_id) else {
+            return;
+        };
+        if assist.codegen.read(cx).diff(cx).hunks.is_empty() {
+            self.finish_assist(assist_id, false, window, cx);
+        }
+    }
+
     fn resolve_inline_assist_target(
         workspace: &mut Workspace,
         agent_panel: Option<Entity<AgentPanel>>,
@@ -1630,6 +1768,44 @@ impl InlineAssistGroup {
     }
 }
 
+fn render_hunk_controls(
+    assist_id: InlineAssistId,
+    hunk_ix: usize,
+    line_height: Pixels,
+    gutter_width: Pixels,
+) -> impl IntoElement {
+    h_flex()
+        .h(line_height)
+        .pl(gutter_width)
+        .pr_2()
+        .gap_1()
+        .justify_end()
+        .child(
+            Button::new(("reject-hunk", hunk_ix), "Reject")
+                .icon(IconName::Close)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::XSmall)
+                .label_size(LabelSize::Small)
+                .on_click(move |_, window, cx| {
+                    InlineAssistant::update_global(cx, |this, cx| {
+                        this.reject_hunk(assist_id, hunk_ix, window, cx)
+                    })
+                }),
+        )
+        .child(
+            Button::new(("accept-hunk", hunk_ix), "Accept")
+                .icon(IconName::Check)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::XSmall)
+                .label_size(LabelSize::Small)
+                .on_click(move |_, window, cx| {
+                    InlineAssistant::update_global(cx, |this, cx| {
+                        this.accept_hunk(assist_id, hunk_ix, window, cx)
+                    })
+                }),
+        )
+}
+
 fn build_assist_editor_renderer(editor: &Entity<PromptEditor<BufferCodegen>>) -> RenderBlock {
     let editor = editor.clone();
 
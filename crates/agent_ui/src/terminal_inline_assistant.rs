@@ -14,18 +14,19 @@ use cloud_llm_client::CompletionIntent;
 use collections::{HashMap, VecDeque};
 use editor::{MultiBuffer, actions::SelectAll};
 use fs::Fs;
+use futures::StreamExt as _;
 use gpui::{App, Entity, Focusable, Global, Subscription, Task, UpdateGlobal, WeakEntity};
 use language::Buffer;
 use language_model::{
-    ConfiguredModel, LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage,
-    Role, report_assistant_event,
+    ConfiguredModel, LanguageModel, LanguageModelRegistry, LanguageModelRequest,
+    LanguageModelRequestMessage, Role, report_assistant_event,
 };
 use project::Project;
 use prompt_store::{PromptBuilder, PromptStore};
 use std::sync::Arc;
 use telemetry_events::{AssistantEventData, AssistantKind, AssistantPhase};
 use terminal_view::TerminalView;
-use ui::prelude::*;
+use ui::{Tooltip, prelude::*};
 use util::ResultExt;
 use workspace::{Toast, Workspace, notifications::NotificationId};
 
@@ -40,6 +41,7 @@ pub fn init(
 
 const DEFAULT_CONTEXT_LINES: usize = 50;
 const PROMPT_HISTORY_MAX_LEN: usize = 20;
+const EXPLANATION_HEIGHT_IN_LINES: u8 = 10;
 
 pub struct TerminalInlineAssistant {
     next_assist_id: TerminalInlineAssistId,
@@ -131,6 +133,99 @@ impl TerminalInlineAssistant {
         self.focus_assist(assist_id, window, cx);
     }
 
+    /// Explains the selected terminal text, or the command on the prompt line,
+    /// in a block below the cursor. The command itself is never run.
+    pub fn explain(
+        &mut self,
+        terminal_view: &Entity<TerminalView>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let (command, working_directory) = {
+            let terminal = terminal_view.read(cx).terminal().read(cx);
+            let command = terminal
+                .last_content()
+                .selection_text
+                .clone()
+                .or_else(|| terminal.last_n_non_empty_lines(1).pop())
+                .filter(|command| !command.trim().is_empty());
+            let working_directory = terminal
+                .working_directory()
+                .map(|path| path.to_string_lossy().into_owned());
+            (command, working_directory)
+        };
+        let Some(command) = command else {
+            return;
+        };
+
+        let Some(ConfiguredModel { model, .. }) =
+            LanguageModelRegistry::read_global(cx).inline_assistant_model()
+        else {
+            workspace
+                .update(cx, |workspace, cx| {
+                    struct NoInlineAssistantModel;
+
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<NoInlineAssistantModel>(),
+                            "Configure an inline assistant model to explain terminal commands",
+                        ),
+                        cx,
+                    );
+                })
+                .ok();
+            return;
+        };
+
+        let shell = std::env::var("SHELL").ok();
+        let Some(prompt) = self
+            .prompt_builder
+            .generate_terminal_explain_prompt(
+                command.trim(),
+                shell.as_deref(),
+                working_directory.as_deref(),
+            )
+            .log_err()
+        else {
+            return;
+        };
+
+        let request = LanguageModelRequest {
+            thread_id: None,
+            prompt_id: None,
+            mode: None,
+            intent: Some(CompletionIntent::TerminalInlineAssist),
+            messages: vec![LanguageModelRequestMessage {
+                role: Role::User,
+                content: vec![prompt.into()],
+                cache: false,
+            }],
+            tools: Vec::new(),
+            tool_choice: None,
+            stop: Vec::new(),
+            temperature: AgentSettings::temperature_for_model(&model, cx),
+            thinking_allowed: false,
+        };
+
+        let explanation = cx.new(|cx| {
+            TerminalCommandExplanation::new(
+                command.trim().to_string(),
+                terminal_view.downgrade(),
+                model,
+                request,
+                cx,
+            )
+        });
+        let block = terminal_view::BlockProperties {
+            height: EXPLANATION_HEIGHT_IN_LINES,
+            render: Box::new(move |_| explanation.clone().into_any_element()),
+        };
+        terminal_view.update(cx, |terminal_view, cx| {
+            terminal_view.set_block_below_cursor(block, window, cx);
+        });
+    }
+
     fn focus_assist(
         &mut self,
         assist_id: TerminalInlineAssistId,
@@ -476,3 +571,113 @@ impl TerminalInlineAssist {
         }
     }
 }
+
+/// A streamed explanation of a terminal command, shown below the cursor.
+struct TerminalCommandExplanation {
+    command: String,
+    explanation: String,
+    error: Option<SharedString>,
+    terminal_view: WeakEntity<TerminalView>,
+    _generation: Task<()>,
+}
+
+impl TerminalCommandExplanation {
+    fn new(
+        command: String,
+        terminal_view: WeakEntity<TerminalView>,
+        model: Arc<dyn LanguageModel>,
+        request: LanguageModelRequest,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let generation = cx.spawn(async move |this, cx| {
+            let result = async {
+                let mut chunks = model.stream_completion_text(request, cx).await?.stream;
+                while let Some(chunk) = chunks.next().await {
+                    let chunk = chunk?;
+                    this.update(cx, |this, cx| {
+                        this.explanation.push_str(&chunk);
+                        cx.notify();
+                    })?;
+                }
+                anyhow::Ok(())
+            }
+            .await;
+
+            if let Err(error) = result {
+                this.update(cx, |this, cx| {
+                    this.error = Some(error.to_string().into());
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+
+        Self {
+            command,
+            explanation: String::new(),
+            error: None,
+            terminal_view,
+            _generation: generation,
+        }
+    }
+
+    fn dismiss(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.terminal_view
+            .update(cx, |terminal_view, cx| {
+                terminal_view.clear_block_below_cursor(cx);
+                terminal_view.focus_handle(cx).focus(window);
+            })
+            .log_err();
+    }
+}
+
+impl Render for TerminalCommandExplanation {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let body = if let Some(error) = self.error.clone() {
+            Label::new(error).color(Color::Error)
+        } else if self.explanation.is_empty() {
+            Label::new("Explaining…").color(Color::Muted)
+        } else {
+            Label::new(self.explanation.trim().to_string())
+        };
+
+        v_flex()
+            .size_full()
+            .py_1()
+            .pl(px(40.))
+            .pr(px(24.))
+            .gap_1()
+            .border_y_1()
+            .border_color(cx.theme().status().info_border)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::ZedAssistant)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(self.command.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        IconButton::new("dismiss-explanation", IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Dismiss"))
+                            .on_click(cx.listener(|this, _, window, cx| this.dismiss(window, cx))),
+                    ),
+            )
+            .child(
+                div()
+                    .id("command-explanation")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .child(body),
+            )
+    }
+}
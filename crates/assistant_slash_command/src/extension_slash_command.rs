@@ -1,6 +1,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use extension::{Extension, ExtensionHostProxy, ExtensionSlashCommandProxy, WorktreeDelegate};
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionSlashCommandProxy, SlashCommandOutputEvent,
+    WorktreeDelegate,
+};
+use futures::{StreamExt as _, channel::mpsc, stream, stream::BoxStream};
 use gpui::{App, Task, WeakEntity, Window};
 use language::{BufferSnapshot, LspAdapterDelegate};
 use std::sync::{Arc, atomic::AtomicBool};
@@ -9,8 +13,8 @@ use util::rel_path::RelPath;
 use workspace::Workspace;
 
 use crate::{
-    ArgumentCompletion, SlashCommand, SlashCommandOutput, SlashCommandOutputSection,
-    SlashCommandRegistry, SlashCommandResult,
+    ArgumentCompletion, SlashCommand, SlashCommandContent, SlashCommandEvent, SlashCommandOutput,
+    SlashCommandOutputSection, SlashCommandRegistry, SlashCommandResult,
 };
 
 pub fn init(cx: &mut App) {
@@ -139,33 +143,145 @@ impl SlashCommand for ExtensionSlashCommand {
     ) -> Task<SlashCommandResult> {
         let command = self.command.clone();
         let arguments = arguments.to_owned();
+        let (output_events_tx, output_events_rx) = mpsc::unbounded();
         let output = cx.background_spawn(async move {
             let delegate =
                 delegate.map(|delegate| Arc::new(WorktreeDelegateAdapter(delegate.clone())) as _);
             let output = self
                 .extension
-                .run_slash_command(command, arguments, delegate)
+                .run_slash_command(command, arguments, delegate, output_events_tx)
                 .await?;
 
             anyhow::Ok(output)
         });
-        cx.foreground_executor().spawn(async move {
-            let output = output.await?;
-            Ok(SlashCommandOutput {
-                text: output.text,
-                sections: output
-                    .sections
-                    .into_iter()
-                    .map(|section| SlashCommandOutputSection {
-                        range: section.range,
+
+        Task::ready(Ok(output_event_stream(output_events_rx, output)))
+    }
+}
+
+/// Combines the events an extension streams while running a slash command with the
+/// output it returns once done, which come after them.
+fn output_event_stream(
+    streamed_events: mpsc::UnboundedReceiver<SlashCommandOutputEvent>,
+    output: impl Future<Output = Result<extension::SlashCommandOutput>> + Send + 'static,
+) -> BoxStream<'static, Result<SlashCommandEvent>> {
+    let streamed_events = streamed_events.map(|event| {
+        Ok(match event {
+            SlashCommandOutputEvent::StartSection(label) => SlashCommandEvent::StartSection {
+                icon: IconName::Code,
+                label: label.into(),
+                metadata: None,
+            },
+            SlashCommandOutputEvent::Text(text) => {
+                SlashCommandEvent::Content(SlashCommandContent::Text {
+                    text,
+                    run_commands_in_text: false,
+                })
+            }
+            SlashCommandOutputEvent::EndSection => SlashCommandEvent::EndSection,
+        })
+    });
+    let output_events = stream::once(output).flat_map(|output| match output {
+        Ok(output) => SlashCommandOutput {
+            text: output.text,
+            sections: output
+                .sections
+                .into_iter()
+                .map(|section| SlashCommandOutputSection {
+                    range: section.range,
+                    icon: IconName::Code,
+                    label: section.label.into(),
+                    metadata: None,
+                })
+                .collect(),
+            run_commands_in_text: false,
+        }
+        .into_event_stream(),
+        Err(error) => stream::once(async move { Err(error) }).boxed(),
+    });
+    streamed_events.chain(output_events).boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_streamed_output_comes_before_returned_output() {
+        let (output_events_tx, output_events_rx) = mpsc::unbounded();
+        output_events_tx
+            .unbounded_send(SlashCommandOutputEvent::StartSection(
+                "Progress".to_string(),
+            ))
+            .unwrap();
+        output_events_tx
+            .unbounded_send(SlashCommandOutputEvent::Text("Fetching\n".to_string()))
+            .unwrap();
+        output_events_tx
+            .unbounded_send(SlashCommandOutputEvent::EndSection)
+            .unwrap();
+        drop(output_events_tx);
+
+        let output = async {
+            Ok(extension::SlashCommandOutput {
+                text: "Done".to_string(),
+                sections: vec![extension::SlashCommandOutputSection {
+                    range: 0..4,
+                    label: "Result".to_string(),
+                }],
+            })
+        };
+        let output =
+            SlashCommandOutput::from_event_stream(output_event_stream(output_events_rx, output))
+                .await
+                .unwrap();
+
+        assert_eq!(
+            output,
+            SlashCommandOutput {
+                text: "Fetching\nDone".to_string(),
+                sections: vec![
+                    SlashCommandOutputSection {
+                        range: 0..9,
                         icon: IconName::Code,
-                        label: section.label.into(),
+                        label: "Progress".into(),
                         metadata: None,
-                    })
-                    .collect(),
+                    },
+                    SlashCommandOutputSection {
+                        range: 9..13,
+                        icon: IconName::Code,
+                        label: "Result".into(),
+                        metadata: None,
+                    },
+                ],
                 run_commands_in_text: false,
             }
-            .into_event_stream())
-        })
+        );
+    }
+
+    #[gpui::test]
+    async fn test_streamed_output_is_kept_when_the_command_fails() {
+        let (output_events_tx, output_events_rx) = mpsc::unbounded();
+        output_events_tx
+            .unbounded_send(SlashCommandOutputEvent::Text("Fetching\n".to_string()))
+            .unwrap();
+        drop(output_events_tx);
+
+        let events = output_event_stream(output_events_rx, async { Err(anyhow!("offline")) })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_ref().unwrap(),
+            &SlashCommandEvent::Content(SlashCommandContent::Text {
+                text: "Fetching\n".to_string(),
+                run_commands_in_text: false,
+            })
+        );
+        assert_eq!(events[1].as_ref().unwrap_err().to_string(), "offline");
     }
 }
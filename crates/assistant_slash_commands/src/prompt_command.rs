@@ -1,13 +1,17 @@
 use anyhow::{Context as _, Result, anyhow};
+use assistant_slash_command::SlashCommandRegistry;
 use assistant_slash_command::{
     ArgumentCompletion, SlashCommand, SlashCommandOutput, SlashCommandOutputSection,
     SlashCommandResult,
 };
-use gpui::{Task, WeakEntity};
+use collections::HashSet;
+use editor::Editor;
+use gpui::{Entity, Task, WeakEntity};
 use language::{BufferSnapshot, LspAdapterDelegate};
-use prompt_store::{PromptMetadata, PromptStore};
+use prompt_store::{PromptId, PromptMetadata, PromptStore, PromptVariables, PromptsUpdatedEvent};
 use std::sync::{Arc, atomic::AtomicBool};
 use ui::prelude::*;
+use util::ResultExt as _;
 use workspace::Workspace;
 
 pub struct PromptSlashCommand;
@@ -69,7 +73,7 @@ impl SlashCommand for PromptSlashCommand {
         arguments: &[String],
         _context_slash_command_output_sections: &[SlashCommandOutputSection<language::Anchor>],
         _context_buffer: BufferSnapshot,
-        _workspace: WeakEntity<Workspace>,
+        workspace: WeakEntity<Workspace>,
         _delegate: Option<Arc<dyn LspAdapterDelegate>>,
         _: &mut Window,
         cx: &mut App,
@@ -96,28 +100,212 @@ impl SlashCommand for PromptSlashCommand {
                 anyhow::Ok(body)
             }
         });
-        cx.foreground_executor().spawn(async move {
-            let mut prompt = prompt.await?;
+        let variables = prompt_variables(&workspace, cx);
+        prompt_output(title, prompt, variables, cx)
+    }
+}
 
-            if prompt.starts_with('/') {
-                // Prevent an edge case where the inserted prompt starts with a slash command (that leads to funky rendering).
-                prompt.insert(0, '\n');
-            }
-            if prompt.is_empty() {
-                prompt.push('\n');
-            }
-            let range = 0..prompt.len();
-            Ok(SlashCommandOutput {
-                text: prompt,
-                sections: vec![SlashCommandOutputSection {
-                    range,
-                    icon: IconName::Library,
-                    label: title,
-                    metadata: None,
-                }],
-                run_commands_in_text: true,
-            }
-            .into_event_stream())
+/// A slash command for a single rule from the library, named after the rule's title,
+/// so that `/review-code` inserts the "Review Code" rule.
+pub struct UserPromptSlashCommand {
+    name: String,
+    title: SharedString,
+    prompt_id: PromptId,
+}
+
+impl SlashCommand for UserPromptSlashCommand {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn description(&self) -> String {
+        format!("Insert \"{}\" from library", self.title)
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::Library
+    }
+
+    fn menu_text(&self) -> String {
+        self.description()
+    }
+
+    fn requires_argument(&self) -> bool {
+        false
+    }
+
+    fn complete_argument(
+        self: Arc<Self>,
+        _arguments: &[String],
+        _cancellation_flag: Arc<AtomicBool>,
+        _workspace: Option<WeakEntity<Workspace>>,
+        _: &mut Window,
+        _cx: &mut App,
+    ) -> Task<Result<Vec<ArgumentCompletion>>> {
+        Task::ready(Ok(Vec::new()))
+    }
+
+    fn run(
+        self: Arc<Self>,
+        _arguments: &[String],
+        _context_slash_command_output_sections: &[SlashCommandOutputSection<language::Anchor>],
+        _context_buffer: BufferSnapshot,
+        workspace: WeakEntity<Workspace>,
+        _delegate: Option<Arc<dyn LspAdapterDelegate>>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<SlashCommandResult> {
+        let store = PromptStore::global(cx);
+        let prompt_id = self.prompt_id;
+        let prompt = cx.spawn(async move |cx| {
+            let store = store.await?;
+            store
+                .read_with(cx, |store, cx| store.load(prompt_id, cx))?
+                .await
+        });
+        let variables = prompt_variables(&workspace, cx);
+        prompt_output(self.title.clone(), prompt, variables, cx)
+    }
+}
+
+/// Registers a slash command for every rule in the library, keeping them in sync
+/// as rules are added, renamed, or deleted.
+pub fn register_prompt_library_commands(cx: &mut App) {
+    let store = PromptStore::global(cx);
+    cx.spawn(async move |cx| {
+        let store = store.await?;
+        cx.update(|cx| {
+            let mut registered = HashSet::default();
+            sync_prompt_library_commands(&store, &mut registered, cx);
+            cx.subscribe(&store, move |store, _: &PromptsUpdatedEvent, cx| {
+                sync_prompt_library_commands(&store, &mut registered, cx);
+            })
+            .detach();
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+fn sync_prompt_library_commands(
+    store: &Entity<PromptStore>,
+    registered: &mut HashSet<String>,
+    cx: &mut App,
+) {
+    let registry = SlashCommandRegistry::global(cx);
+    for name in registered.drain() {
+        registry.unregister_command_by_name(&name);
+    }
+
+    for metadata in store.read(cx).all_prompt_metadata() {
+        if metadata.id.is_built_in() {
+            continue;
+        }
+        let Some(title) = metadata.title else {
+            continue;
+        };
+        let name = prompt_command_name(&title);
+        // Never shadow built-in commands or a rule that claimed the name first.
+        if name.is_empty() || registry.command(&name).is_some() {
+            continue;
+        }
+        registry.register_command(
+            UserPromptSlashCommand {
+                name: name.clone(),
+                title,
+                prompt_id: metadata.id,
+            },
+            false,
+        );
+        registered.insert(name);
+    }
+}
+
+/// Turns a rule title like "Review Code" into a command name like `review-code`.
+fn prompt_command_name(title: &str) -> String {
+    let mut name = String::with_capacity(title.len());
+    for word in title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !name.is_empty() {
+            name.push('-');
+        }
+        name.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    name
+}
+
+/// Collects the values of the prompt variables from the active editor.
+pub fn prompt_variables(workspace: &WeakEntity<Workspace>, cx: &mut App) -> PromptVariables {
+    workspace
+        .update(cx, |workspace, cx| {
+            let editor = workspace
+                .active_item(cx)
+                .and_then(|item| item.act_as::<Editor>(cx))?;
+            editor.update(cx, |editor, cx| {
+                let selection = editor.selections.newest_adjusted(cx);
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let selected_text = snapshot
+                    .text_for_range(selection.range())
+                    .collect::<String>();
+                Some(PromptVariables {
+                    selection: (!selected_text.is_empty()).then_some(selected_text),
+                    filename: snapshot
+                        .file_at(selection.head())
+                        .map(|file| file.file_name(cx).to_string()),
+                    language: snapshot
+                        .language_at(selection.head())
+                        .map(|language| language.name().to_string()),
+                })
+            })
         })
+        .log_err()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn prompt_output(
+    title: SharedString,
+    prompt: Task<Result<String>>,
+    variables: PromptVariables,
+    cx: &mut App,
+) -> Task<SlashCommandResult> {
+    cx.foreground_executor().spawn(async move {
+        let mut prompt = variables.expand(&prompt.await?);
+
+        if prompt.starts_with('/') {
+            // Prevent an edge case where the inserted prompt starts with a slash command (that leads to funky rendering).
+            prompt.insert(0, '\n');
+        }
+        if prompt.is_empty() {
+            prompt.push('\n');
+        }
+        let range = 0..prompt.len();
+        Ok(SlashCommandOutput {
+            text: prompt,
+            sections: vec![SlashCommandOutputSection {
+                range,
+                icon: IconName::Library,
+                label: title,
+                metadata: None,
+            }],
+            run_commands_in_text: true,
+        }
+        .into_event_stream())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_command_name() {
+        assert_eq!(prompt_command_name("Review Code"), "review-code");
+        assert_eq!(
+            prompt_command_name("  Explain: Rust/Go "),
+            "explain-rust-go"
+        );
+        assert_eq!(prompt_command_name("!!!"), "");
     }
 }
@@ -23,9 +23,11 @@ use settings::Settings;
 use std::{io::Cursor, num::NonZero, path::PathBuf, sync::atomic::Ordering, time::Duration};
 use util::ResultExt;
 
+mod audio_cues;
 mod audio_settings;
 mod replays;
 mod rodio_ext;
+pub use audio_cues::{AudioCue, AudioCueSettings, AudioCues};
 pub use audio_settings::AudioSettings;
 pub use rodio_ext::RodioExt;
 
@@ -62,6 +64,10 @@ pub enum Sound {
     StartScreenshare,
     StopScreenshare,
     AgentDone,
+    Diagnostic,
+    TaskSucceeded,
+    TaskFailed,
+    BreakpointHit,
 }
 
 impl Sound {
@@ -75,6 +81,10 @@ impl Sound {
             Self::StartScreenshare => "start_screenshare",
             Self::StopScreenshare => "stop_screenshare",
             Self::AgentDone => "agent_done",
+            Self::Diagnostic => "diagnostic",
+            Self::TaskSucceeded => "task_succeeded",
+            Self::TaskFailed => "task_failed",
+            Self::BreakpointHit => "breakpoint_hit",
         }
     }
 }
@@ -255,6 +265,26 @@ impl Audio {
     }
 
     pub fn play_sound(sound: Sound, cx: &mut App) {
+        Self::play_sound_at_volume(sound, 1., cx);
+    }
+
+    /// Plays the cue's sound at the volume it's configured with, unless it's disabled.
+    pub fn play_cue(cue: AudioCue, cx: &mut App) {
+        Self::play_cue_with_sound(cue, cue.sound(), cx);
+    }
+
+    /// Like [`Audio::play_cue`], but plays a variant of the cue's sound.
+    pub fn play_cue_with_sound(cue: AudioCue, sound: Sound, cx: &mut App) {
+        let Some(settings) = AudioSettings::try_get(cx).map(|settings| settings.cues.get(cue))
+        else {
+            return;
+        };
+        if settings.enabled && settings.volume > 0. {
+            Self::play_sound_at_volume(sound, settings.volume, cx);
+        }
+    }
+
+    fn play_sound_at_volume(sound: Sound, volume: f32, cx: &mut App) {
         cx.update_default_global(|this: &mut Self, cx| {
             let source = this.sound_source(sound, cx).log_err()?;
             let output_mixer = this
@@ -262,7 +292,7 @@ impl Audio {
                 .context("Could not get output mixer")
                 .log_err()?;
 
-            output_mixer.add(source);
+            output_mixer.add(source.amplify(volume));
             Some(())
         });
     }
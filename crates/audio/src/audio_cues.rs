@@ -0,0 +1,89 @@
+use settings::{AudioCueSettingsContent, AudioCuesSettingsContent};
+
+use crate::Sound;
+
+/// Something that happened in Zed that can be announced with a sound, for those who'd rather
+/// hear about it than look for it.
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
+pub enum AudioCue {
+    DiagnosticOnLine,
+    TaskSucceeded,
+    TaskFailed,
+    BreakpointHit,
+    ParticipantJoined,
+    ParticipantLeft,
+}
+
+impl AudioCue {
+    pub(crate) fn sound(self) -> Sound {
+        match self {
+            Self::DiagnosticOnLine => Sound::Diagnostic,
+            Self::TaskSucceeded => Sound::TaskSucceeded,
+            Self::TaskFailed => Sound::TaskFailed,
+            Self::BreakpointHit => Sound::BreakpointHit,
+            Self::ParticipantJoined => Sound::Joined,
+            Self::ParticipantLeft => Sound::Leave,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioCueSettings {
+    pub enabled: bool,
+    /// The volume of the sound, from 0 to 1.
+    pub volume: f32,
+}
+
+impl AudioCueSettings {
+    fn from_content(content: &AudioCueSettingsContent) -> Self {
+        Self {
+            enabled: content.enabled.unwrap(),
+            volume: content.volume.unwrap().clamp(0., 1.),
+        }
+    }
+}
+
+/// The settings of each audio cue.
+#[derive(Clone, Debug)]
+pub struct AudioCues {
+    pub diagnostic_on_line: AudioCueSettings,
+    pub task_succeeded: AudioCueSettings,
+    pub task_failed: AudioCueSettings,
+    pub breakpoint_hit: AudioCueSettings,
+    pub participant_joined: AudioCueSettings,
+    pub participant_left: AudioCueSettings,
+}
+
+impl AudioCues {
+    pub(crate) fn from_content(content: &AudioCuesSettingsContent) -> Self {
+        Self {
+            diagnostic_on_line: AudioCueSettings::from_content(
+                content.diagnostic_on_line.as_ref().unwrap(),
+            ),
+            task_succeeded: AudioCueSettings::from_content(
+                content.task_succeeded.as_ref().unwrap(),
+            ),
+            task_failed: AudioCueSettings::from_content(content.task_failed.as_ref().unwrap()),
+            breakpoint_hit: AudioCueSettings::from_content(
+                content.breakpoint_hit.as_ref().unwrap(),
+            ),
+            participant_joined: AudioCueSettings::from_content(
+                content.participant_joined.as_ref().unwrap(),
+            ),
+            participant_left: AudioCueSettings::from_content(
+                content.participant_left.as_ref().unwrap(),
+            ),
+        }
+    }
+
+    pub fn get(&self, cue: AudioCue) -> AudioCueSettings {
+        match cue {
+            AudioCue::DiagnosticOnLine => self.diagnostic_on_line,
+            AudioCue::TaskSucceeded => self.task_succeeded,
+            AudioCue::TaskFailed => self.task_failed,
+            AudioCue::BreakpointHit => self.breakpoint_hit,
+            AudioCue::ParticipantJoined => self.participant_joined,
+            AudioCue::ParticipantLeft => self.participant_left,
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use gpui::App;
 use settings::{Settings, SettingsStore};
 
+use crate::AudioCues;
+
 #[derive(Clone, Debug)]
 pub struct AudioSettings {
     /// Opt into the new audio system.
@@ -38,6 +40,8 @@ pub struct AudioSettings {
     ///
     /// You need to rejoin a call for this setting to apply
     pub legacy_audio_compatible: bool,
+    /// Sounds played when things happen in Zed, such as a task finishing.
+    pub cues: AudioCues,
 }
 
 /// Configuration of audio in Zed
@@ -50,6 +54,7 @@ impl Settings for AudioSettings {
             auto_speaker_volume: audio.auto_speaker_volume.unwrap(),
             denoise: audio.denoise.unwrap(),
             legacy_audio_compatible: audio.legacy_audio_compatible.unwrap(),
+            cues: AudioCues::from_content(audio.cues.as_ref().unwrap()),
         }
     }
 }
@@ -0,0 +1,96 @@
+//! Plays the audio cues for events that happen outside of calls. The cues themselves, and their
+//! settings, are defined by the `audio` crate.
+
+use audio::{Audio, AudioCue, AudioSettings};
+use editor::{Editor, EditorEvent};
+use gpui::{App, Context};
+use language::{DiagnosticSeverity, Point};
+use multi_buffer::MultiBufferRow;
+use project::debugger::session::{Session, SessionEvent};
+use settings::Settings as _;
+use terminal::{TaskStatus, Terminal};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|editor: &mut Editor, _, cx| play_cue_on_diagnostic_lines(editor, cx))
+        .detach();
+    cx.observe_new(|_: &mut Terminal, _, cx| {
+        cx.subscribe_self(|terminal, event: &terminal::Event, cx| {
+            if let terminal::Event::TaskFinished = event
+                && let Some(cue) = terminal.task().and_then(|task| task_cue(task.status))
+            {
+                Audio::play_cue(cue, cx);
+            }
+        })
+        .detach();
+    })
+    .detach();
+    cx.observe_new(|_: &mut Session, _, cx| {
+        cx.subscribe_self(|_, event: &SessionEvent, cx| {
+            if let SessionEvent::BreakpointHit = event {
+                Audio::play_cue(AudioCue::BreakpointHit, cx);
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+/// Plays a cue when the cursor moves to a line with an error or a warning.
+fn play_cue_on_diagnostic_lines(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() {
+        return;
+    }
+
+    let mut last_row = None;
+    cx.subscribe_self(move |editor, event: &EditorEvent, cx| {
+        if !matches!(event, EditorEvent::SelectionsChanged { local: true })
+            || !AudioSettings::get_global(cx)
+                .cues
+                .diagnostic_on_line
+                .enabled
+        {
+            return;
+        }
+        let row = editor.selections.newest::<Point>(cx).head().row;
+        if last_row.replace(row) == Some(row) {
+            return;
+        }
+
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let line = Point::new(row, 0)..Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+        let has_diagnostic = snapshot.diagnostics_in_range::<Point>(line).any(|entry| {
+            let severity = entry.diagnostic.severity;
+            severity == DiagnosticSeverity::ERROR || severity == DiagnosticSeverity::WARNING
+        });
+        if has_diagnostic {
+            Audio::play_cue(AudioCue::DiagnosticOnLine, cx);
+        }
+    })
+    .detach();
+}
+
+fn task_cue(status: TaskStatus) -> Option<AudioCue> {
+    match status {
+        TaskStatus::Completed { success: true } => Some(AudioCue::TaskSucceeded),
+        TaskStatus::Completed { success: false } => Some(AudioCue::TaskFailed),
+        TaskStatus::Running | TaskStatus::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_cue() {
+        assert_eq!(
+            task_cue(TaskStatus::Completed { success: true }),
+            Some(AudioCue::TaskSucceeded)
+        );
+        assert_eq!(
+            task_cue(TaskStatus::Completed { success: false }),
+            Some(AudioCue::TaskFailed)
+        );
+        assert_eq!(task_cue(TaskStatus::Unknown), None);
+    }
+}
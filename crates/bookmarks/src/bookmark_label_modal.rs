@@ -0,0 +1,103 @@
+use editor::{Editor, EditorEvent};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription};
+use ui::prelude::*;
+use workspace::ModalView;
+
+use crate::{Bookmarks, CursorLocation};
+
+/// Asks for the label of the bookmark on the cursor's line.
+pub struct BookmarkLabelModal {
+    label_editor: Entity<Editor>,
+    bookmarks: Entity<Bookmarks>,
+    location: CursorLocation,
+    _subscription: Subscription,
+}
+
+impl BookmarkLabelModal {
+    pub(crate) fn new(
+        bookmarks: Entity<Bookmarks>,
+        location: CursorLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let label = bookmarks.read(cx).label(&location, cx);
+        let label_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bookmark label", window, cx);
+            if let Some(label) = label {
+                editor.set_text(label, window, cx);
+                editor.select_all(&Default::default(), window, cx);
+            }
+            editor
+        });
+        let subscription = cx.subscribe(&label_editor, |_, _, event: &EditorEvent, cx| {
+            if matches!(event, EditorEvent::Blurred) {
+                cx.emit(DismissEvent);
+            }
+        });
+        Self {
+            label_editor,
+            bookmarks,
+            location,
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let label = self.label_editor.read(cx).text(cx).trim().to_string();
+        let label = (!label.is_empty()).then(|| label.into());
+        let location = self.location.clone();
+        self.bookmarks
+            .update(cx, |bookmarks, cx| bookmarks.set_label(location, label, cx));
+        cx.emit(DismissEvent);
+    }
+}
+
+impl ModalView for BookmarkLabelModal {}
+
+impl EventEmitter<DismissEvent> for BookmarkLabelModal {}
+
+impl Focusable for BookmarkLabelModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.label_editor.focus_handle(cx)
+    }
+}
+
+impl Render for BookmarkLabelModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let file_name = self
+            .location
+            .abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("BookmarkLabelModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.label_editor.clone()),
+            )
+            .child(
+                h_flex().px_2().py_1().child(
+                    Label::new(format!(
+                        "Label the bookmark on line {} of {file_name}",
+                        self.location.row + 1
+                    ))
+                    .color(Color::Muted),
+                ),
+            )
+    }
+}
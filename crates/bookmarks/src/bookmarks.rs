@@ -0,0 +1,574 @@
+mod bookmark_label_modal;
+mod bookmarks_picker;
+mod persistence;
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use editor::{Editor, MultiBufferRow};
+use gpui::{
+    App, AppContext as _, Context, Entity, SharedString, Subscription, Task, WeakEntity, Window,
+    actions,
+};
+use language::{Buffer, BufferEvent, Point};
+use project::buffer_store::BufferStoreEvent;
+use text::ToPoint as _;
+use ui::ActiveTheme as _;
+use util::ResultExt as _;
+use workspace::{
+    OpenOptions, OpenVisible, Workspace, WorkspaceId, notifications::DetachAndPromptErr as _,
+};
+
+use crate::{
+    bookmark_label_modal::BookmarkLabelModal,
+    bookmarks_picker::BookmarksPicker,
+    persistence::{DB, SerializedBookmark},
+};
+
+actions!(
+    bookmarks,
+    [
+        /// Adds a bookmark on the cursor's line, or removes the one that's there.
+        ToggleBookmark,
+        /// Labels the bookmark on the cursor's line, adding one if there's none.
+        LabelBookmark,
+        /// Moves the cursor to the next bookmark in the workspace.
+        NextBookmark,
+        /// Moves the cursor to the previous bookmark in the workspace.
+        PreviousBookmark,
+        /// Opens a picker listing the bookmarks in the workspace.
+        OpenBookmarks,
+        /// Removes all the bookmarks in the workspace.
+        ClearBookmarks
+    ]
+);
+
+/// How long to wait after the bookmarks change before saving them.
+const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        if window.is_none() || !workspace.project().read(cx).is_local() {
+            return;
+        }
+        let bookmarks = cx.new(|cx| Bookmarks::new(workspace, cx));
+
+        // The gutters of editors are updated from the workspace, so that it isn't updated while
+        // the bookmarks are.
+        cx.observe(&bookmarks, |workspace, bookmarks, cx| {
+            for editor in workspace.items_of_type::<Editor>(cx).collect::<Vec<_>>() {
+                show_bookmarks_in_gutter(&editor, bookmarks.read(cx), cx);
+            }
+        })
+        .detach();
+        cx.subscribe_self({
+            let bookmarks = bookmarks.clone();
+            move |_, event, cx| {
+                if let workspace::Event::ItemAdded { item } = event
+                    && let Some(editor) = item.act_as::<Editor>(cx)
+                {
+                    show_bookmarks_in_gutter(&editor, bookmarks.read(cx), cx);
+                }
+            }
+        })
+        .detach();
+
+        workspace.register_action({
+            let bookmarks = bookmarks.clone();
+            move |workspace, _: &ToggleBookmark, _, cx| {
+                if let Some(location) = cursor_location(workspace, cx) {
+                    bookmarks.update(cx, |bookmarks, cx| bookmarks.toggle(location, cx));
+                }
+            }
+        });
+        workspace.register_action({
+            let bookmarks = bookmarks.clone();
+            move |workspace, _: &LabelBookmark, window, cx| {
+                if let Some(location) = cursor_location(workspace, cx) {
+                    let bookmarks = bookmarks.clone();
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        BookmarkLabelModal::new(bookmarks, location, window, cx)
+                    });
+                }
+            }
+        });
+        workspace.register_action({
+            let bookmarks = bookmarks.clone();
+            move |workspace, _: &NextBookmark, window, cx| {
+                go_to_adjacent_bookmark(workspace, &bookmarks, true, window, cx);
+            }
+        });
+        workspace.register_action({
+            let bookmarks = bookmarks.clone();
+            move |workspace, _: &PreviousBookmark, window, cx| {
+                go_to_adjacent_bookmark(workspace, &bookmarks, false, window, cx);
+            }
+        });
+        workspace.register_action({
+            let bookmarks = bookmarks.clone();
+            move |workspace, _: &OpenBookmarks, window, cx| {
+                BookmarksPicker::toggle(bookmarks.clone(), workspace, window, cx);
+            }
+        });
+        workspace.register_action(move |_, _: &ClearBookmarks, _, cx| {
+            bookmarks.update(cx, |bookmarks, cx| bookmarks.clear(cx));
+        });
+    })
+    .detach();
+}
+
+/// A bookmarked line in a file.
+pub struct Bookmark {
+    pub abs_path: PathBuf,
+    pub label: Option<SharedString>,
+    /// The row of the bookmark when its buffer was last open.
+    row: u32,
+    /// While the file is open, an anchor that keeps the bookmark on its line as the buffer is
+    /// edited.
+    anchor: Option<(WeakEntity<Buffer>, text::Anchor)>,
+}
+
+impl Bookmark {
+    /// The zero-based row that the bookmark is on.
+    pub fn row(&self, cx: &App) -> u32 {
+        self.anchor
+            .as_ref()
+            .and_then(|(buffer, anchor)| {
+                let buffer = buffer.upgrade()?;
+                Some(anchor.to_point(&buffer.read(cx).snapshot()).row)
+            })
+            .unwrap_or(self.row)
+    }
+}
+
+/// The line that the cursor is on in a file.
+#[derive(Clone)]
+pub(crate) struct CursorLocation {
+    pub(crate) abs_path: PathBuf,
+    pub(crate) buffer: Entity<Buffer>,
+    pub(crate) row: u32,
+}
+
+/// The bookmarks in a workspace, which are saved in the workspace database.
+pub struct Bookmarks {
+    workspace_id: Option<WorkspaceId>,
+    bookmarks: Vec<Bookmark>,
+    _pending_save: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl Bookmarks {
+    fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        let workspace_id = workspace.database_id();
+        let bookmarks = workspace_id
+            .and_then(|workspace_id| DB.get_bookmarks(workspace_id).log_err())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|bookmark| Bookmark {
+                abs_path: bookmark.abs_path,
+                label: bookmark.label.map(SharedString::from),
+                row: bookmark.row,
+                anchor: None,
+            })
+            .collect();
+
+        let buffer_store = workspace.project().read(cx).buffer_store().clone();
+        let subscriptions = vec![
+            cx.subscribe(&buffer_store, |this, _, event, cx| {
+                if let BufferStoreEvent::BufferAdded(buffer) = event {
+                    this.watch_buffer(buffer, cx);
+                }
+            }),
+            cx.on_app_quit(|this, cx| this.save(cx)),
+        ];
+        let mut this = Self {
+            workspace_id,
+            bookmarks,
+            _pending_save: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        for buffer in buffer_store.read(cx).buffers().collect::<Vec<_>>() {
+            this.watch_buffer(&buffer, cx);
+        }
+        this
+    }
+
+    /// Anchors the bookmarks in a buffer's file, and saves them when the buffer is edited, as
+    /// their rows may have changed.
+    fn watch_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(abs_path) = local_abs_path(buffer.read(cx), cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let max_row = snapshot.max_point().row;
+        for bookmark in &mut self.bookmarks {
+            if bookmark.abs_path == abs_path {
+                let row = bookmark.row.min(max_row);
+                bookmark.anchor = Some((
+                    buffer.downgrade(),
+                    snapshot.anchor_before(Point::new(row, 0)),
+                ));
+            }
+        }
+        cx.subscribe(buffer, |this, _, event, cx| {
+            if matches!(event, BufferEvent::Edited | BufferEvent::Reloaded) {
+                this.schedule_save(cx);
+            }
+        })
+        .detach();
+        // Once the buffer is closed, its bookmarks stay on the rows they were last on.
+        let buffer_id = buffer.entity_id();
+        cx.observe_release(buffer, move |this, buffer, _| {
+            let snapshot = buffer.snapshot();
+            for bookmark in &mut this.bookmarks {
+                if let Some((bookmark_buffer, anchor)) = &bookmark.anchor
+                    && bookmark_buffer.entity_id() == buffer_id
+                {
+                    bookmark.row = anchor.to_point(&snapshot).row;
+                    bookmark.anchor = None;
+                }
+            }
+        })
+        .detach();
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    fn position(&self, abs_path: &Path, row: u32, cx: &App) -> Option<usize> {
+        self.bookmarks
+            .iter()
+            .position(|bookmark| bookmark.abs_path == abs_path && bookmark.row(cx) == row)
+    }
+
+    fn toggle(&mut self, location: CursorLocation, cx: &mut Context<Self>) {
+        match self.position(&location.abs_path, location.row, cx) {
+            Some(ix) => {
+                self.bookmarks.remove(ix);
+            }
+            None => self.add(location, None, cx),
+        }
+        self.changed(cx);
+    }
+
+    pub(crate) fn label(&self, location: &CursorLocation, cx: &App) -> Option<SharedString> {
+        let ix = self.position(&location.abs_path, location.row, cx)?;
+        self.bookmarks[ix].label.clone()
+    }
+
+    pub(crate) fn set_label(
+        &mut self,
+        location: CursorLocation,
+        label: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        match self.position(&location.abs_path, location.row, cx) {
+            Some(ix) => self.bookmarks[ix].label = label,
+            None => self.add(location, label, cx),
+        }
+        self.changed(cx);
+    }
+
+    fn add(&mut self, location: CursorLocation, label: Option<SharedString>, cx: &App) {
+        let anchor = location
+            .buffer
+            .read(cx)
+            .anchor_before(Point::new(location.row, 0));
+        self.bookmarks.push(Bookmark {
+            abs_path: location.abs_path,
+            label,
+            row: location.row,
+            anchor: Some((location.buffer.downgrade(), anchor)),
+        });
+    }
+
+    pub(crate) fn remove(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if ix < self.bookmarks.len() {
+            self.bookmarks.remove(ix);
+            self.changed(cx);
+        }
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.bookmarks.clear();
+        self.changed(cx);
+    }
+
+    fn changed(&mut self, cx: &mut Context<Self>) {
+        self.schedule_save(cx);
+        cx.notify();
+    }
+
+    /// The indices of the bookmarks, ordered by path and row.
+    pub(crate) fn sorted(&self, cx: &App) -> Vec<(usize, u32)> {
+        let mut sorted = self
+            .bookmarks
+            .iter()
+            .enumerate()
+            .map(|(ix, bookmark)| (ix, bookmark.row(cx)))
+            .collect::<Vec<_>>();
+        sorted.sort_by(|(a, a_row), (b, b_row)| {
+            self.bookmarks[*a]
+                .abs_path
+                .cmp(&self.bookmarks[*b].abs_path)
+                .then(a_row.cmp(b_row))
+        });
+        sorted
+    }
+
+    /// Saves the bookmarks shortly, replacing any save that's already pending so that changes in
+    /// quick succession are saved once.
+    fn schedule_save(&mut self, cx: &mut Context<Self>) {
+        self._pending_save = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SAVE_DELAY).await;
+            if let Ok(save) = this.update(cx, |this, cx| this.save(cx)) {
+                save.await;
+            }
+        });
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        self._pending_save = Task::ready(());
+        let Some(workspace_id) = self.workspace_id else {
+            return Task::ready(());
+        };
+        let mut bookmarks = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| SerializedBookmark {
+                abs_path: bookmark.abs_path.clone(),
+                row: bookmark.row(cx),
+                label: bookmark.label.as_ref().map(ToString::to_string),
+            })
+            .collect::<Vec<_>>();
+        // Bookmarks whose lines were joined end up on the same row, and only one is kept.
+        bookmarks.sort_by(|a, b| a.abs_path.cmp(&b.abs_path).then(a.row.cmp(&b.row)));
+        bookmarks.dedup_by(|a, b| a.abs_path == b.abs_path && a.row == b.row);
+        cx.background_spawn(async move {
+            DB.save_bookmarks(workspace_id, bookmarks).await.log_err();
+        })
+    }
+}
+
+/// Returns the path of a buffer's file, if it's on the local disk.
+fn local_abs_path(buffer: &Buffer, cx: &App) -> Option<PathBuf> {
+    Some(buffer.file()?.as_local()?.abs_path(cx))
+}
+
+/// Returns the line of the cursor in the active editor, if it's editing a local file.
+fn cursor_location(workspace: &Workspace, cx: &mut App) -> Option<CursorLocation> {
+    let editor = workspace.active_item_as::<Editor>(cx)?;
+    let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+    let abs_path = local_abs_path(buffer.read(cx), cx)?;
+    let row = editor.update(cx, |editor, cx| {
+        editor.selections.newest::<Point>(cx).head().row
+    });
+    Some(CursorLocation {
+        abs_path,
+        buffer,
+        row,
+    })
+}
+
+/// Returns the index into `positions`, which are sorted, of the first position after `current`,
+/// or before it if `forward` is false, wrapping around at the ends.
+fn adjacent_position(
+    positions: &[(&Path, u32)],
+    current: (&Path, u32),
+    forward: bool,
+) -> Option<usize> {
+    if positions.is_empty() {
+        return None;
+    }
+    if forward {
+        Some(
+            positions
+                .iter()
+                .position(|position| *position > current)
+                .unwrap_or(0),
+        )
+    } else {
+        Some(
+            positions
+                .iter()
+                .rposition(|position| *position < current)
+                .unwrap_or(positions.len() - 1),
+        )
+    }
+}
+
+fn go_to_adjacent_bookmark(
+    workspace: &mut Workspace,
+    bookmarks: &Entity<Bookmarks>,
+    forward: bool,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let current = cursor_location(workspace, cx);
+    let bookmarks = bookmarks.read(cx);
+    let sorted = bookmarks.sorted(cx);
+    let positions = sorted
+        .iter()
+        .map(|(ix, row)| (bookmarks.bookmarks[*ix].abs_path.as_path(), *row))
+        .collect::<Vec<_>>();
+    let current = current.as_ref().map_or((Path::new(""), 0), |location| {
+        (location.abs_path.as_path(), location.row)
+    });
+    let Some(position) = adjacent_position(&positions, current, forward) else {
+        return;
+    };
+    let (abs_path, row) = positions[position];
+    let abs_path = abs_path.to_path_buf();
+    open_bookmark(abs_path, row, workspace, window, cx);
+}
+
+pub(crate) fn open_bookmark(
+    abs_path: PathBuf,
+    row: u32,
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open = workspace.open_abs_path(
+        abs_path,
+        OpenOptions {
+            visible: Some(OpenVisible::None),
+            ..Default::default()
+        },
+        window,
+        cx,
+    );
+    cx.spawn_in(window, async move |_, cx| {
+        let item = open.await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update_in(cx, |editor, window, cx| {
+                editor.go_to_singleton_buffer_point(Point::new(row, 0), window, cx);
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to open bookmark", window, cx, |_, _, _| None);
+}
+
+/// Marks the bookmarked lines of an editor's file in its gutter.
+fn show_bookmarks_in_gutter(editor: &Entity<Editor>, bookmarks: &Bookmarks, cx: &mut App) {
+    enum BookmarkGutterHighlights {}
+
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let Some(abs_path) = local_abs_path(buffer.read(cx), cx) else {
+        return;
+    };
+    let rows = bookmarks
+        .bookmarks
+        .iter()
+        .filter(|bookmark| bookmark.abs_path == abs_path)
+        .map(|bookmark| bookmark.row(cx))
+        .collect::<Vec<_>>();
+    editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let ranges = rows
+            .into_iter()
+            .filter(|row| *row <= snapshot.max_point().row)
+            .map(|row| {
+                let end = Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+                snapshot.anchor_before(Point::new(row, 0))..snapshot.anchor_after(end)
+            })
+            .collect::<Vec<_>>();
+        editor.highlight_gutter::<BookmarkGutterHighlights>(
+            ranges,
+            |cx| cx.theme().status().info,
+            cx,
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use util::path;
+    use workspace::AppState;
+
+    #[test]
+    fn test_adjacent_position() {
+        let a = Path::new("/a.rs");
+        let b = Path::new("/b.rs");
+        let positions = [(a, 2), (a, 8), (b, 1)];
+        assert_eq!(adjacent_position(&positions, (a, 0), true), Some(0));
+        assert_eq!(adjacent_position(&positions, (a, 2), true), Some(1));
+        assert_eq!(adjacent_position(&positions, (a, 9), true), Some(2));
+        assert_eq!(adjacent_position(&positions, (b, 1), true), Some(0));
+        assert_eq!(adjacent_position(&positions, (a, 8), false), Some(0));
+        assert_eq!(adjacent_position(&positions, (a, 2), false), Some(2));
+        assert_eq!(adjacent_position(&[], (a, 2), false), None);
+    }
+
+    #[gpui::test]
+    async fn test_bookmarks_follow_edits(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            AppState::test(cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({ "a.rs": "one\ntwo\nthree\nfour\n" }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    PathBuf::from(path!("/project/a.rs")),
+                    OpenOptions::default(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        let cursor_row = |editor: &Entity<Editor>, cx: &mut VisualTestContext| {
+            editor.update(cx, |editor, cx| {
+                editor.selections.newest::<Point>(cx).head().row
+            })
+        };
+        editor.update_in(cx, |editor, window, cx| {
+            editor.go_to_singleton_buffer_point(Point::new(2, 0), window, cx);
+        });
+        cx.dispatch_action(ToggleBookmark);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.go_to_singleton_buffer_point(Point::new(0, 0), window, cx);
+            // Inserting lines above the bookmark moves it down with its line.
+            editor.insert("zero\n", window, cx);
+        });
+
+        cx.dispatch_action(NextBookmark);
+        cx.run_until_parked();
+        assert_eq!(cursor_row(&editor, cx), 3);
+
+        // Toggling the bookmark again removes it.
+        cx.dispatch_action(ToggleBookmark);
+        cx.dispatch_action(NextBookmark);
+        cx.run_until_parked();
+        assert_eq!(cursor_row(&editor, cx), 3);
+        editor.update(cx, |editor, cx| {
+            assert_eq!(editor.text(cx), "zero\none\ntwo\nthree\nfour\n");
+        });
+    }
+}
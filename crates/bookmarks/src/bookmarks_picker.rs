@@ -0,0 +1,288 @@
+use std::{path::PathBuf, sync::Arc};
+
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{Bookmarks, open_bookmark};
+
+/// A bookmark as listed in the picker.
+struct BookmarkMatch {
+    ix: usize,
+    abs_path: PathBuf,
+    row: u32,
+    label: Option<SharedString>,
+    /// The file name and line number of the bookmark.
+    location: String,
+}
+
+/// Lists the bookmarks in the workspace, so that they can be jumped to or removed.
+pub struct BookmarksPicker {
+    picker: Entity<Picker<BookmarksPickerDelegate>>,
+}
+
+impl BookmarksPicker {
+    pub fn toggle(
+        bookmarks: Entity<Bookmarks>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let workspace_handle = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            let delegate = BookmarksPickerDelegate::new(
+                cx.entity().downgrade(),
+                workspace_handle,
+                bookmarks,
+                cx,
+            );
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            Self { picker }
+        });
+    }
+}
+
+impl Render for BookmarksPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BookmarksPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for BookmarksPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for BookmarksPicker {}
+impl ModalView for BookmarksPicker {}
+
+pub struct BookmarksPickerDelegate {
+    bookmarks_picker: WeakEntity<BookmarksPicker>,
+    workspace: WeakEntity<Workspace>,
+    bookmarks: Entity<Bookmarks>,
+    entries: Vec<BookmarkMatch>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl BookmarksPickerDelegate {
+    fn new(
+        bookmarks_picker: WeakEntity<BookmarksPicker>,
+        workspace: WeakEntity<Workspace>,
+        bookmarks: Entity<Bookmarks>,
+        cx: &App,
+    ) -> Self {
+        let mut this = Self {
+            bookmarks_picker,
+            workspace,
+            bookmarks,
+            entries: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        this.load_entries(cx);
+        this
+    }
+
+    fn load_entries(&mut self, cx: &App) {
+        let bookmarks = self.bookmarks.read(cx);
+        self.entries = bookmarks
+            .sorted(cx)
+            .into_iter()
+            .map(|(ix, row)| {
+                let bookmark = &bookmarks.bookmarks()[ix];
+                let file_name = bookmark
+                    .abs_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                BookmarkMatch {
+                    ix,
+                    abs_path: bookmark.abs_path.clone(),
+                    row,
+                    label: bookmark.label.clone(),
+                    location: format!("{file_name}:{}", row + 1),
+                }
+            })
+            .collect();
+    }
+
+    fn remove_selected(&mut self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(mat) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let ix = self.entries[mat.candidate_id].ix;
+        self.bookmarks
+            .update(cx, |bookmarks, cx| bookmarks.remove(ix, cx));
+        self.load_entries(cx);
+        cx.spawn_in(window, async move |picker, cx| {
+            picker
+                .update_in(cx, |picker, window, cx| picker.refresh(window, cx))
+                .log_err();
+        })
+        .detach();
+    }
+}
+
+impl PickerDelegate for BookmarksPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search bookmarks…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No bookmarks".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if secondary {
+            self.remove_selected(window, cx);
+            return;
+        }
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let entry = &self.entries[mat.candidate_id];
+            let abs_path = entry.abs_path.clone();
+            let row = entry.row;
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    open_bookmark(abs_path, row, workspace, window, cx);
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.bookmarks_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        // Match both the label and the location, so that unlabeled bookmarks can be found too.
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| {
+                let label = entry.label.as_deref().unwrap_or_default();
+                StringMatchCandidate::new(id, &format!("{label} {}", entry.location))
+            })
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let entry = &self.entries[mat.candidate_id];
+        let label = entry.label.as_deref().unwrap_or_default();
+        let location_start = label.len() + 1;
+        let label_positions = mat
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < label.len())
+            .collect::<Vec<_>>();
+        let location_positions = mat
+            .positions
+            .iter()
+            .filter_map(|position| position.checked_sub(location_start))
+            .collect::<Vec<_>>();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::Bookmark).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .when(!label.is_empty(), |this| {
+                            this.child(HighlightedLabel::new(label.to_string(), label_positions))
+                        })
+                        .child(
+                            HighlightedLabel::new(entry.location.clone(), location_positions)
+                                .color(if label.is_empty() {
+                                    Color::Default
+                                } else {
+                                    Color::Muted
+                                }),
+                        ),
+                ),
+        )
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use db::{
+    query,
+    sqlez::{
+        bindable::Column, domain::Domain, statement::Statement,
+        thread_safe_connection::ThreadSafeConnection,
+    },
+    sqlez_macros::sql,
+};
+use std::path::PathBuf;
+use workspace::{WorkspaceDb, WorkspaceId};
+
+/// A bookmark as stored in the workspace database, at the row it was on when it was saved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SerializedBookmark {
+    pub abs_path: PathBuf,
+    pub row: u32,
+    pub label: Option<String>,
+}
+
+impl Column for SerializedBookmark {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (abs_path, next_index): (PathBuf, i32) = Column::column(statement, start_index)?;
+        let (row, next_index): (u32, i32) = Column::column(statement, next_index)?;
+        let (label, next_index): (Option<String>, i32) = Column::column(statement, next_index)?;
+        let bookmark = Self {
+            abs_path,
+            row,
+            label,
+        };
+        Ok((bookmark, next_index))
+    }
+}
+
+pub struct BookmarksDb(ThreadSafeConnection);
+
+impl Domain for BookmarksDb {
+    const NAME: &str = stringify!(BookmarksDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE bookmarks(
+            workspace_id INTEGER NOT NULL,
+            abs_path BLOB NOT NULL,
+            buffer_row INTEGER NOT NULL,
+            label TEXT,
+            PRIMARY KEY(workspace_id, abs_path, buffer_row),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        ) STRICT;
+    )];
+}
+
+db::static_connection!(DB, BookmarksDb, [WorkspaceDb]);
+
+impl BookmarksDb {
+    query! {
+        pub fn get_bookmarks(workspace_id: WorkspaceId) -> Result<Vec<SerializedBookmark>> {
+            SELECT abs_path, buffer_row, label
+            FROM bookmarks
+            WHERE workspace_id = ?
+            ORDER BY abs_path, buffer_row
+        }
+    }
+
+    /// Replaces the workspace's bookmarks with the given ones.
+    pub async fn save_bookmarks(
+        &self,
+        workspace_id: WorkspaceId,
+        bookmarks: Vec<SerializedBookmark>,
+    ) -> Result<()> {
+        log::debug!(
+            "Saving {} bookmarks for workspace {workspace_id:?}",
+            bookmarks.len()
+        );
+        self.write(move |conn| {
+            conn.with_savepoint("save_bookmarks", || {
+                conn.exec_bound(sql!(
+                    DELETE FROM bookmarks WHERE workspace_id = ?;
+                ))?(workspace_id)?;
+                for bookmark in bookmarks {
+                    conn.exec_bound(sql!(
+                        INSERT OR REPLACE INTO bookmarks
+                            (workspace_id, abs_path, buffer_row, label)
+                        VALUES
+                            (?1, ?2, ?3, ?4);
+                    ))?((
+                        workspace_id,
+                        bookmark.abs_path,
+                        bookmark.row,
+                        bookmark.label,
+                    ))?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_get_bookmarks() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        assert_eq!(DB.get_bookmarks(workspace_id).unwrap(), []);
+
+        let bookmarks = vec![
+            SerializedBookmark {
+                abs_path: PathBuf::from("/project/a.rs"),
+                row: 3,
+                label: Some("entry point".to_owned()),
+            },
+            SerializedBookmark {
+                abs_path: PathBuf::from("/project/b.rs"),
+                row: 0,
+                label: None,
+            },
+        ];
+        DB.save_bookmarks(workspace_id, bookmarks.clone())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_bookmarks(workspace_id).unwrap(), bookmarks);
+
+        DB.save_bookmarks(workspace_id, bookmarks[1..].to_vec())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_bookmarks(workspace_id).unwrap(), &bookmarks[1..]);
+    }
+}
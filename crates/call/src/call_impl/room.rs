@@ -3,7 +3,7 @@ use crate::{
     participant::{LocalParticipant, ParticipantLocation, RemoteParticipant},
 };
 use anyhow::{Context as _, Result, anyhow};
-use audio::{Audio, Sound};
+use audio::{Audio, AudioCue, Sound};
 use client::{
     ChannelId, Client, ParticipantIndex, TypedEnvelope, User, UserStore,
     proto::{self, PeerId},
@@ -834,9 +834,13 @@ impl Room {
                             // Dont play extra sounds over that.
                             if this.created.elapsed() > Duration::from_millis(100) {
                                 if let proto::ChannelRole::Guest = role {
-                                    Audio::play_sound(Sound::GuestJoined, cx);
+                                    Audio::play_cue_with_sound(
+                                        AudioCue::ParticipantJoined,
+                                        Sound::GuestJoined,
+                                        cx,
+                                    );
                                 } else {
-                                    Audio::play_sound(Sound::Joined, cx);
+                                    Audio::play_cue(AudioCue::ParticipantJoined, cx);
                                 }
                             }
 
@@ -867,6 +871,7 @@ impl Room {
                         if this.participant_user_ids.contains(user_id) {
                             true
                         } else {
+                            Audio::play_cue(AudioCue::ParticipantLeft, cx);
                             for project in &participant.projects {
                                 cx.emit(Event::RemoteProjectUnshared {
                                     project_id: project.id,
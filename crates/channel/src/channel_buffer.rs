@@ -1,18 +1,22 @@
-use crate::{Channel, ChannelStore};
+use crate::{
+    Channel, ChannelStore,
+    persistence::{DB, OfflineChannelNotes},
+};
 use anyhow::Result;
 use client::{ChannelId, Client, Collaborator, UserStore, ZED_ALWAYS_ACTIVE};
 use collections::HashMap;
-use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Task};
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
 use language::proto::serialize_version;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
     proto::{self, PeerId},
 };
-use std::{sync::Arc, time::Duration};
+use std::{mem, ops::Range, sync::Arc, time::Duration};
 use text::BufferId;
 use util::ResultExt;
 
 pub const ACKNOWLEDGE_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(250);
+pub const SAVE_OFFLINE_EDITS_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(250);
 
 pub(crate) fn init(client: &AnyProtoClient) {
     client.add_entity_message_handler(ChannelBuffer::handle_update_channel_buffer);
@@ -30,6 +34,12 @@ pub struct ChannelBuffer {
     client: Arc<Client>,
     subscription: Option<client::Subscription>,
     acknowledge_task: Option<Task<Result<()>>>,
+    /// The buffer's text and version when the connection was lost, used to persist the
+    /// edits made while offline until they are synchronized with the server.
+    offline_base: Option<(String, clock::Global)>,
+    has_offline_edits: bool,
+    save_offline_edits_task: Option<Task<()>>,
+    _quit_subscription: Subscription,
 }
 
 pub enum ChannelBufferEvent {
@@ -70,8 +80,13 @@ impl ChannelBuffer {
         buffer.update(cx, |buffer, cx| buffer.apply_ops(operations, cx))?;
 
         let subscription = client.subscribe_to_entity(channel.id.0)?;
+        let offline_notes = client.user_id().and_then(|user_id| {
+            DB.get_offline_notes(user_id, channel.id.0)
+                .log_err()
+                .flatten()
+        });
 
-        anyhow::Ok(cx.new(|cx| {
+        let this = cx.new(|cx| {
             cx.subscribe(&buffer, Self::on_buffer_update).detach();
             cx.on_release(Self::release).detach();
             let mut this = Self {
@@ -81,6 +96,10 @@ impl ChannelBuffer {
                 connected: true,
                 collaborators: Default::default(),
                 acknowledge_task: None,
+                offline_base: None,
+                has_offline_edits: false,
+                save_offline_edits_task: None,
+                _quit_subscription: cx.on_app_quit(Self::flush_offline_edits),
                 channel_id: channel.id,
                 subscription: Some(subscription.set_entity(&cx.entity(), &cx.to_async())),
                 user_store,
@@ -88,7 +107,11 @@ impl ChannelBuffer {
             };
             this.replace_collaborators(response.collaborators, cx);
             this
-        })?)
+        })?;
+        if let Some(offline_notes) = offline_notes {
+            this.update(cx, |this, cx| this.restore_offline_edits(offline_notes, cx))?;
+        }
+        anyhow::Ok(this)
     }
 
     fn release(&mut self, _: &mut App) {
@@ -106,6 +129,11 @@ impl ChannelBuffer {
 
     pub fn connected(&mut self, cx: &mut Context<Self>) {
         self.connected = true;
+        self.offline_base = None;
+        self.save_offline_edits_task = None;
+        if mem::take(&mut self.has_offline_edits) {
+            self.delete_offline_edits(cx);
+        }
         if self.subscription.is_none() {
             let Ok(subscription) = self.client.subscribe_to_entity(self.channel_id.0) else {
                 return;
@@ -191,6 +219,25 @@ impl ChannelBuffer {
                 operation,
                 is_local: true,
             } => {
+                // While offline, local operations are kept in the buffer's history and
+                // sent to the server once the buffer is rejoined.
+                if !self.connected {
+                    if matches!(operation, language::Operation::Buffer(_)) {
+                        self.has_offline_edits = true;
+                        self.save_offline_edits_task = Some(cx.spawn(async move |this, cx| {
+                            cx.background_executor()
+                                .timer(SAVE_OFFLINE_EDITS_DEBOUNCE_INTERVAL)
+                                .await;
+                            if let Ok(save) =
+                                this.update(cx, |this, cx| this.save_offline_edits(cx))
+                            {
+                                save.await;
+                            }
+                        }));
+                        cx.notify();
+                    }
+                    return;
+                }
                 if *ZED_ALWAYS_ACTIVE
                     && let language::Operation::UpdateSelections { selections, .. } = operation
                     && selections.is_empty()
@@ -257,6 +304,8 @@ impl ChannelBuffer {
         log::info!("channel buffer {} disconnected", self.channel_id);
         if self.connected {
             self.connected = false;
+            let buffer = self.buffer.read(cx);
+            self.offline_base = Some((buffer.text(), buffer.version()));
             self.subscription.take();
             cx.emit(ChannelBufferEvent::Disconnected);
             cx.notify()
@@ -272,7 +321,207 @@ impl ChannelBuffer {
         self.connected
     }
 
+    /// Whether the notes were edited while disconnected and these edits are
+    /// yet to be synchronized with the server.
+    pub fn has_offline_edits(&self) -> bool {
+        self.has_offline_edits
+    }
+
+    /// Persists the edits made while offline right away, so that they can be synchronized
+    /// with the server once the notes are opened again.
+    pub(crate) fn flush_offline_edits(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        self.save_offline_edits_task = None;
+        self.save_offline_edits(cx)
+    }
+
+    fn save_offline_edits(&self, cx: &mut Context<Self>) -> Task<()> {
+        let (Some(user_id), Some((base_text, base_version)), true) = (
+            self.client.user_id(),
+            self.offline_base.as_ref(),
+            self.has_offline_edits,
+        ) else {
+            return Task::ready(());
+        };
+
+        let buffer = self.buffer.read(cx);
+        let replica_id = buffer.replica_id();
+        let notes = OfflineChannelNotes {
+            epoch: self.buffer_epoch,
+            replica_id,
+            disconnected_at: base_version.get(replica_id),
+            base_text: base_text.clone(),
+            local_text: buffer.text(),
+            operations: Vec::new(),
+        };
+        let operations = buffer.serialize_ops(Some(base_version.clone()), cx);
+        let channel_id = self.channel_id;
+        cx.background_spawn(async move {
+            let mut operations = operations.await;
+            // Selections and diagnostics are only relevant while the replica is connected.
+            operations.retain(|operation| {
+                matches!(
+                    operation.variant,
+                    Some(proto::operation::Variant::Edit(_) | proto::operation::Variant::Undo(_))
+                )
+            });
+            let notes = OfflineChannelNotes {
+                operations,
+                ..notes
+            };
+            DB.save_offline_notes(user_id, channel_id.0, notes)
+                .await
+                .log_err();
+        })
+    }
+
+    fn delete_offline_edits(&self, cx: &mut Context<Self>) {
+        let Some(user_id) = self.client.user_id() else {
+            return;
+        };
+        let channel_id = self.channel_id;
+        cx.background_spawn(async move {
+            DB.delete_offline_notes(user_id, channel_id.0)
+                .await
+                .log_err();
+        })
+        .detach();
+    }
+
+    /// Synchronizes the edits persisted while offline with the server.
+    ///
+    /// As long as the notes' history wasn't compacted into a new epoch, the offline operations
+    /// are replayed through the CRDT. Otherwise, they can't be applied anymore, and the offline
+    /// edits are rebased onto the current text instead.
+    fn restore_offline_edits(&mut self, notes: OfflineChannelNotes, cx: &mut Context<Self>) {
+        let epoch = self.buffer_epoch;
+        let replayed = self.buffer.update(cx, |buffer, cx| {
+            // Operations can't be replayed if the replica id has been assigned to another
+            // collaborator who performed operations with it in the meantime.
+            if notes.epoch != epoch
+                || buffer.version().get(notes.replica_id) > notes.disconnected_at
+            {
+                return false;
+            }
+            let Some(operations) = notes
+                .operations
+                .iter()
+                .cloned()
+                .map(language::proto::deserialize_operation)
+                .collect::<Result<Vec<_>>>()
+                .log_err()
+            else {
+                return false;
+            };
+            buffer.apply_ops(operations, cx);
+            true
+        });
+
+        if replayed {
+            for chunk in language::proto::split_operations(notes.operations) {
+                self.client
+                    .send(proto::UpdateChannelBuffer {
+                        channel_id: self.channel_id.0,
+                        operations: chunk,
+                    })
+                    .log_err();
+            }
+        } else {
+            self.buffer.update(cx, |buffer, cx| {
+                let edits =
+                    rebase_offline_edits(&notes.base_text, &notes.local_text, &buffer.text());
+                buffer.edit(edits, None, cx);
+            });
+        }
+        self.delete_offline_edits(cx);
+        log::info!(
+            "restored offline edits to channel {} notes",
+            self.channel_id
+        );
+    }
+
     pub fn replica_id(&self, cx: &App) -> u16 {
         self.buffer.read(cx).replica_id()
     }
 }
+
+/// Rebases the edits turning `base_text` into `local_text` onto `remote_text`, which
+/// diverged from `base_text` while the local replica was offline.
+///
+/// The returned edits are expressed in `remote_text` coordinates. Local edits that
+/// overlap a remote edit are inserted after it, so that no text written offline is lost.
+pub(crate) fn rebase_offline_edits(
+    base_text: &str,
+    local_text: &str,
+    remote_text: &str,
+) -> Vec<(Range<usize>, Arc<str>)> {
+    let local_edits = language::text_diff(base_text, local_text);
+    let mut remote_edits = language::text_diff(base_text, remote_text)
+        .into_iter()
+        .peekable();
+
+    let mut rebased_edits = Vec::with_capacity(local_edits.len());
+    let mut delta = 0_isize;
+    let mut last_remote_end = None;
+    for (range, new_text) in local_edits {
+        while let Some((remote_range, remote_text)) = remote_edits.peek() {
+            let overlaps = remote_range.start < range.end && range.start < remote_range.end;
+            if remote_range.end > range.start && !overlaps {
+                break;
+            }
+            delta += remote_text.len() as isize - remote_range.len() as isize;
+            last_remote_end = Some((
+                remote_range.end,
+                (remote_range.end as isize + delta) as usize,
+            ));
+            remote_edits.next();
+        }
+
+        match last_remote_end {
+            Some((base_end, rebased_end)) if range.start < base_end => {
+                rebased_edits.push((rebased_end..rebased_end, new_text));
+            }
+            _ => {
+                let start = (range.start as isize + delta) as usize;
+                let end = (range.end as isize + delta) as usize;
+                rebased_edits.push((start..end, new_text));
+            }
+        }
+    }
+    rebased_edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(text: &str, edits: Vec<(Range<usize>, Arc<str>)>) -> String {
+        let mut text = text.to_string();
+        for (range, new_text) in edits.into_iter().rev() {
+            text.replace_range(range, &new_text);
+        }
+        text
+    }
+
+    #[test]
+    fn test_rebase_offline_edits() {
+        // Without remote changes, the local text is restored as is.
+        let base = "one\ntwo\nthree\n";
+        let local = "one\n2\nthree\nfour\n";
+        assert_eq!(apply(base, rebase_offline_edits(base, local, base)), local);
+
+        // Non-overlapping edits are merged.
+        let remote = "zero\none\ntwo\nthree\n";
+        assert_eq!(
+            apply(remote, rebase_offline_edits(base, local, remote)),
+            "zero\none\n2\nthree\nfour\n"
+        );
+
+        // Conflicting local edits are kept after the remote ones.
+        let local = "one\nTWO\nthree\n";
+        let remote = "one\nthree\n";
+        let merged = apply(remote, rebase_offline_edits(base, local, remote));
+        assert!(merged.contains("TWO"), "{merged:?}");
+        assert!(merged.starts_with("one\n"), "{merged:?}");
+        assert!(merged.ends_with("three\n"), "{merged:?}");
+    }
+}
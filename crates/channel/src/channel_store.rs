@@ -1,6 +1,6 @@
 mod channel_index;
 
-use crate::channel_buffer::ChannelBuffer;
+use crate::{channel_buffer::ChannelBuffer, persistence::DB};
 use anyhow::{Context as _, Result, anyhow};
 use channel_index::ChannelIndex;
 use client::{ChannelId, Client, ClientSettings, Subscription, User, UserId, UserStore};
@@ -851,6 +851,7 @@ impl ChannelStore {
         self.channel_index.clear();
         self.outgoing_invites.clear();
         self.disconnect_channel_buffers_task.take();
+        self.sync_persisted_offline_edits(cx);
 
         let mut buffer_versions = Vec::new();
         for buffer in self.opened_buffers.values() {
@@ -879,6 +880,7 @@ impl ChannelStore {
             let mut response = response.await?;
 
             this.update(cx, |this, cx| {
+                let mut offline_edits = Vec::new();
                 this.opened_buffers.retain(|_, buffer| match buffer {
                     OpenEntityHandle::Open(channel_buffer) => {
                         let Some(channel_buffer) = channel_buffer.upgrade() else {
@@ -935,18 +937,59 @@ impl ChannelStore {
                                 }
                             }
 
+                            if channel_buffer.has_offline_edits() {
+                                offline_edits.push((
+                                    channel_buffer.channel_id,
+                                    channel_buffer.flush_offline_edits(cx),
+                                ));
+                            }
                             channel_buffer.disconnect(cx);
                             false
                         })
                     }
                     OpenEntityHandle::Loading(_) => true,
                 });
+
+                // Notes that couldn't be rejoined are opened again, which synchronizes the
+                // edits persisted while offline.
+                for (channel_id, flush_offline_edits) in offline_edits {
+                    cx.spawn(async move |this, cx| {
+                        flush_offline_edits.await;
+                        this.update(cx, |this, cx| this.open_channel_buffer(channel_id, cx))?
+                            .await?;
+                        anyhow::Ok(())
+                    })
+                    .detach_and_log_err(cx);
+                }
             })
             .ok();
             anyhow::Ok(())
         })
     }
 
+    /// Opens the notes of channels that were edited offline and not synchronized before the
+    /// previous session ended, which synchronizes these edits with the server.
+    fn sync_persisted_offline_edits(&mut self, cx: &mut Context<Self>) {
+        let Some(user_id) = self.client.user_id() else {
+            return;
+        };
+        let channel_ids = DB
+            .channels_with_offline_notes(user_id)
+            .log_err()
+            .unwrap_or_default();
+        for channel_id in channel_ids.into_iter().map(ChannelId) {
+            if self.has_open_channel_buffer(channel_id, cx) {
+                continue;
+            }
+            let open_buffer = self.open_channel_buffer(channel_id, cx);
+            cx.spawn(async move |_, _| {
+                open_buffer.await?;
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
     fn handle_disconnect(&mut self, wait_for_reconnect: bool, cx: &mut Context<Self>) {
         cx.notify();
         self.did_subscribe = false;
@@ -0,0 +1,156 @@
+use anyhow::Result;
+use db::{
+    query,
+    sqlez::{
+        bindable::Column, domain::Domain, statement::Statement,
+        thread_safe_connection::ThreadSafeConnection,
+    },
+    sqlez_macros::sql,
+};
+use rpc::proto::{self, Message as _};
+
+/// The edits made to a channel's notes while offline, kept until they are synchronized with
+/// the server so that they survive restarts.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct OfflineChannelNotes {
+    pub epoch: u64,
+    pub replica_id: u16,
+    /// The local replica's clock when the connection was lost.
+    pub disconnected_at: u32,
+    /// The notes' text when the connection was lost.
+    pub base_text: String,
+    /// The notes' text after the offline edits.
+    pub local_text: String,
+    /// The operations performed while offline.
+    pub operations: Vec<proto::Operation>,
+}
+
+impl Column for OfflineChannelNotes {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (epoch, next_index): (u64, i32) = Column::column(statement, start_index)?;
+        let (replica_id, next_index): (u16, i32) = Column::column(statement, next_index)?;
+        let (disconnected_at, next_index): (u32, i32) = Column::column(statement, next_index)?;
+        let (base_text, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (local_text, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (operations, next_index): (Vec<u8>, i32) = Column::column(statement, next_index)?;
+        let notes = Self {
+            epoch,
+            replica_id,
+            disconnected_at,
+            base_text,
+            local_text,
+            operations: proto::UpdateChannelBuffer::decode(operations.as_slice())?.operations,
+        };
+        Ok((notes, next_index))
+    }
+}
+
+pub(crate) struct ChannelNotesDb(ThreadSafeConnection);
+
+impl Domain for ChannelNotesDb {
+    const NAME: &str = stringify!(ChannelNotesDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE offline_channel_notes(
+            user_id INTEGER NOT NULL,
+            channel_id INTEGER NOT NULL,
+            epoch INTEGER NOT NULL,
+            replica_id INTEGER NOT NULL,
+            disconnected_at INTEGER NOT NULL,
+            base_text TEXT NOT NULL,
+            local_text TEXT NOT NULL,
+            operations BLOB NOT NULL,
+            PRIMARY KEY(user_id, channel_id)
+        ) STRICT;
+    )];
+}
+
+db::static_connection!(DB, ChannelNotesDb, []);
+
+impl ChannelNotesDb {
+    query! {
+        pub(crate) fn get_offline_notes(user_id: u64, channel_id: u64) -> Result<Option<OfflineChannelNotes>> {
+            SELECT epoch, replica_id, disconnected_at, base_text, local_text, operations
+            FROM offline_channel_notes
+            WHERE user_id = ? AND channel_id = ?
+        }
+    }
+
+    query! {
+        pub(crate) fn channels_with_offline_notes(user_id: u64) -> Result<Vec<u64>> {
+            SELECT channel_id
+            FROM offline_channel_notes
+            WHERE user_id = ?
+        }
+    }
+
+    query! {
+        pub(crate) async fn delete_offline_notes(user_id: u64, channel_id: u64) -> Result<()> {
+            DELETE FROM offline_channel_notes
+            WHERE user_id = ? AND channel_id = ?
+        }
+    }
+
+    pub(crate) async fn save_offline_notes(
+        &self,
+        user_id: u64,
+        channel_id: u64,
+        notes: OfflineChannelNotes,
+    ) -> Result<()> {
+        let operations = proto::UpdateChannelBuffer {
+            channel_id,
+            operations: notes.operations,
+        }
+        .encode_to_vec();
+        self.write(move |conn| {
+            conn.exec_bound(sql!(
+                INSERT OR REPLACE INTO offline_channel_notes(
+                    user_id,
+                    channel_id,
+                    epoch,
+                    replica_id,
+                    disconnected_at,
+                    base_text,
+                    local_text,
+                    operations
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
+            ))?((
+                user_id,
+                channel_id,
+                notes.epoch,
+                notes.replica_id,
+                notes.disconnected_at,
+                notes.base_text,
+                notes.local_text,
+                operations,
+            ))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_offline_notes_roundtrip() {
+        let db = ChannelNotesDb::open_test_db("test_offline_notes_roundtrip").await;
+        let notes = OfflineChannelNotes {
+            epoch: 2,
+            replica_id: 3,
+            disconnected_at: 5,
+            base_text: "one\n".into(),
+            local_text: "one\ntwo\n".into(),
+            operations: vec![proto::Operation::default()],
+        };
+
+        db.save_offline_notes(1, 10, notes.clone()).await.unwrap();
+        assert_eq!(db.get_offline_notes(1, 10).unwrap(), Some(notes));
+        assert_eq!(db.get_offline_notes(2, 10).unwrap(), None);
+        assert_eq!(db.channels_with_offline_notes(1).unwrap(), vec![10]);
+
+        db.delete_offline_notes(1, 10).await.unwrap();
+        assert_eq!(db.get_offline_notes(1, 10).unwrap(), None);
+    }
+}
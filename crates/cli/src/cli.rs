@@ -16,6 +16,8 @@ pub enum CliRequest {
         diff_paths: Vec<[String; 2]>,
         wsl: Option<String>,
         wait: bool,
+        /// Text piped to the CLI with `zed -`, which is opened in a new, unsaved buffer.
+        stdin: Option<String>,
         open_new_workspace: Option<bool>,
         env: Option<HashMap<String, String>>,
         user_data_dir: Option<String>,
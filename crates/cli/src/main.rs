@@ -12,7 +12,8 @@ use clap::Parser;
 use cli::{CliRequest, CliResponse, IpcHandshake, ipc::IpcOneShotServer};
 use parking_lot::Mutex;
 use std::{
-    env, fs, io,
+    env, fs,
+    io::{self, Read as _},
     path::{Path, PathBuf},
     process::ExitStatus,
     sync::Arc,
@@ -311,7 +312,7 @@ fn main() -> Result<()> {
     let mut paths = vec![];
     let mut urls = vec![];
     let mut diff_paths = vec![];
-    let mut stdin_tmp_file: Option<fs::File> = None;
+    let mut read_stdin = false;
     let mut anonymous_fd_tmp_files = vec![];
 
     for path in args.diff.chunks(2) {
@@ -330,10 +331,7 @@ fn main() -> Result<()> {
         if URL_PREFIX.iter().any(|&prefix| path.starts_with(prefix)) {
             urls.push(path.to_string());
         } else if path == "-" && args.paths_with_position.len() == 1 {
-            let file = NamedTempFile::new()?;
-            paths.push(file.path().to_string_lossy().into_owned());
-            let (file, _) = file.keep()?;
-            stdin_tmp_file = Some(file);
+            read_stdin = true;
         } else if let Some(file) = anonymous_fd(path) {
             let tmp_file = NamedTempFile::new()?;
             paths.push(tmp_file.path().to_string_lossy().into_owned());
@@ -365,12 +363,19 @@ fn main() -> Result<()> {
                 #[cfg(not(target_os = "windows"))]
                 let wsl = None;
 
+                let stdin = if read_stdin {
+                    read_stdin_to_string()?
+                } else {
+                    None
+                };
+
                 tx.send(CliRequest::Open {
                     paths,
                     urls,
                     diff_paths,
                     wsl,
                     wait: args.wait,
+                    stdin,
                     open_new_workspace,
                     env,
                     user_data_dir: user_data_dir_for_thread,
@@ -393,20 +398,6 @@ fn main() -> Result<()> {
         })
         .unwrap();
 
-    let stdin_pipe_handle: Option<JoinHandle<anyhow::Result<()>>> =
-        stdin_tmp_file.map(|mut tmp_file| {
-            thread::Builder::new()
-                .name("CliStdin".to_string())
-                .spawn(move || {
-                    let mut stdin = std::io::stdin().lock();
-                    if !io::IsTerminal::is_terminal(&stdin) {
-                        io::copy(&mut stdin, &mut tmp_file)?;
-                    }
-                    Ok(())
-                })
-                .unwrap()
-        });
-
     let anonymous_fd_pipe_handles: Vec<_> = anonymous_fd_tmp_files
         .into_iter()
         .map(|(mut file, mut tmp_file)| {
@@ -422,9 +413,6 @@ fn main() -> Result<()> {
     } else {
         app.launch(url)?;
         sender.join().unwrap()?;
-        if let Some(handle) = stdin_pipe_handle {
-            handle.join().unwrap()?;
-        }
         for handle in anonymous_fd_pipe_handles {
             handle.join().unwrap()?;
         }
@@ -436,6 +424,19 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Reads the text piped to the CLI, or returns `None` if stdin is a terminal.
+fn read_stdin_to_string() -> anyhow::Result<Option<String>> {
+    let mut stdin = io::stdin().lock();
+    if io::IsTerminal::is_terminal(&stdin) {
+        return Ok(None);
+    }
+    let mut bytes = Vec::new();
+    stdin
+        .read_to_end(&mut bytes)
+        .context("reading from stdin")?;
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
 fn anonymous_fd(path: &str) -> Option<fs::File> {
     #[cfg(target_os = "linux")]
     {
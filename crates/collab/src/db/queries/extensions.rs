@@ -459,6 +459,7 @@ fn metadata_from_extension_and_version(
                 .collect::<Vec<_>>(),
             description: Some(version.description),
             repository: version.repository,
+            changelog: None,
             schema_version: Some(version.schema_version),
             wasm_api_version: version.wasm_api_version,
             provides,
@@ -102,6 +102,7 @@ async fn test_extensions(db: &Arc<Database>) {
                     authors: vec!["max".into(), "marshall".into()],
                     description: Some("a good extension".into()),
                     repository: "ext1/repo".into(),
+                    changelog: None,
                     schema_version: Some(1),
                     wasm_api_version: None,
                     provides: BTreeSet::default(),
@@ -117,6 +118,7 @@ async fn test_extensions(db: &Arc<Database>) {
                     authors: vec!["marshall".into()],
                     description: Some("a great extension".into()),
                     repository: "ext2/repo".into(),
+                    changelog: None,
                     schema_version: Some(0),
                     wasm_api_version: None,
                     provides: BTreeSet::default(),
@@ -139,6 +141,7 @@ async fn test_extensions(db: &Arc<Database>) {
                 authors: vec!["marshall".into()],
                 description: Some("a great extension".into()),
                 repository: "ext2/repo".into(),
+                changelog: None,
                 schema_version: Some(0),
                 wasm_api_version: None,
                 provides: BTreeSet::default(),
@@ -181,6 +184,7 @@ async fn test_extensions(db: &Arc<Database>) {
                     authors: vec!["marshall".into()],
                     description: Some("a great extension".into()),
                     repository: "ext2/repo".into(),
+                    changelog: None,
                     schema_version: Some(0),
                     wasm_api_version: None,
                     provides: BTreeSet::default(),
@@ -196,6 +200,7 @@ async fn test_extensions(db: &Arc<Database>) {
                     authors: vec!["max".into(), "marshall".into()],
                     description: Some("a good extension".into()),
                     repository: "ext1/repo".into(),
+                    changelog: None,
                     schema_version: Some(1),
                     wasm_api_version: None,
                     provides: BTreeSet::default(),
@@ -271,6 +276,7 @@ async fn test_extensions(db: &Arc<Database>) {
                     authors: vec!["marshall".into()],
                     description: Some("a great extension".into()),
                     repository: "ext2/repo".into(),
+                    changelog: None,
                     schema_version: Some(0),
                     wasm_api_version: None,
                     provides: BTreeSet::default(),
@@ -286,6 +292,7 @@ async fn test_extensions(db: &Arc<Database>) {
                     authors: vec!["max".into(), "marshall".into()],
                     description: Some("a real good extension".into()),
                     repository: "ext1/repo".into(),
+                    changelog: None,
                     schema_version: Some(1),
                     wasm_api_version: None,
                     provides: BTreeSet::default(),
@@ -408,6 +415,7 @@ async fn test_extensions_by_id(db: &Arc<Database>) {
                 authors: vec!["max".into()],
                 description: Some("a good extension".into()),
                 repository: "ext1/repo".into(),
+                changelog: None,
                 schema_version: Some(1),
                 wasm_api_version: Some("0.0.4".into()),
                 provides: BTreeSet::from_iter([
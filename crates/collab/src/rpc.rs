@@ -439,6 +439,7 @@ impl Server {
             .add_request_handler(follow)
             .add_message_handler(unfollow)
             .add_message_handler(update_followers)
+            .add_message_handler(update_presenting)
             .add_message_handler(acknowledge_channel_message)
             .add_message_handler(acknowledge_buffer_version)
             .add_request_handler(get_supermaven_api_key)
@@ -2593,6 +2594,35 @@ async fn update_followers(request: proto::UpdateFollowers, session: MessageConte
     Ok(())
 }
 
+/// Notify the other participants that you started or stopped presenting.
+async fn update_presenting(
+    request: proto::UpdatePresenting,
+    session: MessageContext,
+) -> Result<()> {
+    let room_id = RoomId::from_proto(request.room_id);
+    let database = session.db.lock().await;
+
+    let connection_ids = if let Some(project_id) = request.project_id {
+        let project_id = ProjectId::from_proto(project_id);
+        database
+            .project_connection_ids(project_id, session.connection_id, true)
+            .await?
+    } else {
+        database
+            .room_connection_ids(room_id, session.connection_id)
+            .await?
+    };
+
+    for connection_id in connection_ids.iter().cloned() {
+        if connection_id != session.connection_id {
+            session
+                .peer
+                .forward_send(session.connection_id, connection_id, request.clone())?;
+        }
+    }
+    Ok(())
+}
+
 /// Get public data about users.
 async fn get_users(
     request: proto::GetUsers,
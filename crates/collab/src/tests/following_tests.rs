@@ -1440,6 +1440,77 @@ async fn test_peers_simultaneously_following_each_other(
     });
 }
 
+#[gpui::test]
+async fn test_presenting(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    cx_a.update(editor::init);
+    cx_b.update(editor::init);
+
+    client_a.fs().insert_tree("/a", json!({})).await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let (workspace_a, cx_a) = client_a.build_workspace(&project_a, cx_a);
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    let (workspace_b, cx_b) = client_b.build_workspace(&project_b, cx_b);
+
+    executor.run_until_parked();
+    let client_a_id = project_b.update(cx_b, |project, _| {
+        project.collaborators().values().next().unwrap().peer_id
+    });
+
+    // When a starts presenting, b automatically follows a.
+    workspace_a.update_in(cx_a, |workspace, window, cx| {
+        workspace.start_presenting(&workspace::StartPresenting, window, cx)
+    });
+    executor.run_until_parked();
+    workspace_b.update(cx_b, |workspace, _| {
+        assert_eq!(workspace.presenter(), Some(client_a_id));
+        assert!(workspace.is_following_presenter());
+        assert_eq!(
+            workspace.leader_for_pane(workspace.active_pane()),
+            Some(client_a_id.into())
+        );
+    });
+
+    // b can step out of the presentation and rejoin it later.
+    workspace_b.update_in(cx_b, |workspace, window, cx| {
+        workspace.unfollow(client_a_id, window, cx);
+    });
+    workspace_b.update(cx_b, |workspace, _| {
+        assert_eq!(workspace.presenter(), Some(client_a_id));
+        assert!(!workspace.is_following_presenter());
+    });
+    workspace_b.update_in(cx_b, |workspace, window, cx| {
+        workspace.follow_presenter(&workspace::FollowPresenter, window, cx)
+    });
+    executor.run_until_parked();
+    workspace_b.update(cx_b, |workspace, _| {
+        assert!(workspace.is_following_presenter());
+    });
+
+    // When a stops presenting, b stops following a.
+    workspace_a.update_in(cx_a, |workspace, window, cx| {
+        workspace.stop_presenting(&workspace::StopPresenting, window, cx)
+    });
+    executor.run_until_parked();
+    workspace_b.update(cx_b, |workspace, _| {
+        assert_eq!(workspace.presenter(), None);
+        assert_eq!(workspace.leader_for_pane(workspace.active_pane()), None);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_following_across_workspaces(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     // a and b join a channel/call
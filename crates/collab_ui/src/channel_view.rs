@@ -347,12 +347,14 @@ impl ChannelView {
         cx: &mut Context<Self>,
     ) {
         match event {
-            ChannelBufferEvent::Disconnected => self.editor.update(cx, |editor, cx| {
-                editor.set_read_only(true);
+            // Notes remain editable while offline, edits are synchronized on reconnect.
+            ChannelBufferEvent::Disconnected => self.editor.update(cx, |_, cx| {
+                cx.emit(editor::EditorEvent::TitleChanged);
                 cx.notify();
             }),
             ChannelBufferEvent::Connected => self.editor.update(cx, |editor, cx| {
                 editor.set_read_only(false);
+                cx.emit(editor::EditorEvent::TitleChanged);
                 cx.notify();
             }),
             ChannelBufferEvent::ChannelChanged => {
@@ -397,13 +399,15 @@ impl ChannelView {
 
     fn get_channel(&self, cx: &App) -> (SharedString, Option<SharedString>) {
         if let Some(channel) = self.channel(cx) {
+            let channel_buffer = self.channel_buffer.read(cx);
             let status = match (
-                self.channel_buffer.read(cx).buffer().read(cx).read_only(),
-                self.channel_buffer.read(cx).is_connected(),
+                channel_buffer.buffer().read(cx).read_only(),
+                channel_buffer.is_connected(),
             ) {
                 (false, true) => None,
                 (true, true) => Some("read-only"),
-                (_, false) => Some("disconnected"),
+                (_, false) if channel_buffer.has_offline_edits() => Some("offline, unsynced"),
+                (_, false) => Some("offline"),
             };
 
             (channel.name.clone(), status.map(Into::into))
@@ -0,0 +1,302 @@
+use std::ops::Range;
+
+use editor::{
+    Anchor, Editor, EditorEvent, MultiBufferRow,
+    actions::ToggleColorPicker,
+    color_literals::{ColorFormat, find_color_literals, format_color, parse_color_literal},
+};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Hsla, Render, Rgba, Styled,
+    Subscription, div, prelude::*,
+};
+use text::Point;
+use theme::ActiveTheme;
+use ui::prelude::*;
+use workspace::ModalView;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(ColorPicker::register).detach();
+}
+
+/// Edits the color literal under the cursor, writing it back in one of several formats.
+pub struct ColorPicker {
+    color_editor: Entity<Editor>,
+    active_editor: Entity<Editor>,
+    literal_range: Range<Anchor>,
+    selected_format: ColorFormat,
+    _subscription: Subscription,
+}
+
+impl ModalView for ColorPicker {}
+
+impl Focusable for ColorPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.color_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ColorPicker {}
+
+impl ColorPicker {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |_: &ToggleColorPicker, window, cx| {
+                let Some(editor_handle) = handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor_handle.read(cx).workspace() else {
+                    return;
+                };
+                let Some((literal_range, literal_text)) =
+                    editor_handle.update(cx, |editor, cx| color_literal_at_cursor(editor, cx))
+                else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        ColorPicker::new(editor_handle, literal_range, literal_text, window, cx)
+                    });
+                })
+            })
+            .detach();
+    }
+
+    fn new(
+        active_editor: Entity<Editor>,
+        literal_range: Range<Anchor>,
+        literal_text: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let selected_format =
+            parse_color_literal(&literal_text).map_or(ColorFormat::Hex, |(_, format)| format);
+        let color_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Color, such as #ff8000 or rgb(255, 128, 0)", window, cx);
+            editor.set_text(literal_text, window, cx);
+            editor.select_all(&Default::default(), window, cx);
+            editor
+        });
+        let subscription =
+            cx.subscribe(&color_editor, |_, _, event: &EditorEvent, cx| match event {
+                EditorEvent::Blurred => cx.emit(DismissEvent),
+                EditorEvent::BufferEdited => cx.notify(),
+                _ => {}
+            });
+        Self {
+            color_editor,
+            active_editor,
+            literal_range,
+            selected_format,
+            _subscription: subscription,
+        }
+    }
+
+    fn color(&self, cx: &App) -> Option<Rgba> {
+        parse_color_literal(&self.color_editor.read(cx).text(cx)).map(|(color, _)| color)
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_format_at_offset(1, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_format_at_offset(ColorFormat::ALL.len() - 1, cx);
+    }
+
+    fn select_format_at_offset(&mut self, offset: usize, cx: &mut Context<Self>) {
+        let ix = ColorFormat::ALL
+            .iter()
+            .position(|format| *format == self.selected_format)
+            .unwrap_or(0);
+        self.selected_format = ColorFormat::ALL[(ix + offset) % ColorFormat::ALL.len()];
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        self.apply(self.selected_format, cx);
+    }
+
+    fn apply(&mut self, format: ColorFormat, cx: &mut Context<Self>) {
+        let Some(color) = self.color(cx) else {
+            return;
+        };
+        let text = format_color(color, format);
+        let range = self.literal_range.clone();
+        self.active_editor.update(cx, |editor, cx| {
+            editor.edit([(range, text)], cx);
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+/// Finds the color literal that the newest cursor is in or touching.
+fn color_literal_at_cursor(
+    editor: &mut Editor,
+    cx: &mut Context<Editor>,
+) -> Option<(Range<Anchor>, String)> {
+    let cursor = editor.selections.newest::<Point>(cx).head();
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let line_start = Point::new(cursor.row, 0);
+    let line_end = Point::new(cursor.row, snapshot.line_len(MultiBufferRow(cursor.row)));
+    let line = snapshot
+        .text_for_range(line_start..line_end)
+        .collect::<String>();
+    let cursor_column = cursor.column as usize;
+    let literal = find_color_literals(&line).into_iter().find(|literal| {
+        literal.range.start <= cursor_column && cursor_column <= literal.range.end
+    })?;
+    let start = snapshot.anchor_after(Point::new(cursor.row, literal.range.start as u32));
+    let end = snapshot.anchor_before(Point::new(cursor.row, literal.range.end as u32));
+    Some((start..end, line[literal.range].to_string()))
+}
+
+impl Render for ColorPicker {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let color = self.color(cx);
+        let swatch = div()
+            .flex_none()
+            .size_4()
+            .rounded_sm()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .when_some(color, |swatch, color| swatch.bg(Hsla::from(color)));
+
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("ColorPicker")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(swatch)
+                    .child(self.color_editor.clone()),
+            )
+            .child(v_flex().p_1().map(|this| {
+                match color {
+                    Some(color) => this.children(ColorFormat::ALL.into_iter().map(|format| {
+                        let selected = format == self.selected_format;
+                        h_flex()
+                            .id(format.label())
+                            .px_2()
+                            .py_0p5()
+                            .gap_2()
+                            .rounded_sm()
+                            .when(selected, |row| row.bg(cx.theme().colors().element_selected))
+                            .hover(|row| row.bg(cx.theme().colors().element_hover))
+                            .child(
+                                div()
+                                    .w(rems(3.))
+                                    .child(Label::new(format.label()).color(Color::Muted)),
+                            )
+                            .child(Label::new(format_color(color, format)).buffer_font(cx))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.apply(format, cx);
+                            }))
+                    })),
+                    None => this.child(
+                        div()
+                            .px_2()
+                            .py_0p5()
+                            .child(Label::new("Not a valid color").color(Color::Error)),
+                    ),
+                }
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use std::sync::Arc;
+    use util::{path, rel_path::rel_path};
+    use workspace::{AppState, Workspace};
+
+    #[gpui::test]
+    async fn test_rewriting_color_literal(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "a.css": "a { color: #ff0000; }" }))
+            .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("a.css")), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        // Without a color under the cursor, there's nothing to pick.
+        cx.dispatch_action(ToggleColorPicker);
+        assert!(active_color_picker(&workspace, cx).is_none());
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.go_to_singleton_buffer_point(Point::new(0, 13), window, cx);
+        });
+        cx.dispatch_action(ToggleColorPicker);
+        let color_picker = active_color_picker(&workspace, cx).unwrap();
+        color_picker.update(cx, |color_picker, _| {
+            assert_eq!(color_picker.selected_format, ColorFormat::Hex);
+        });
+
+        cx.simulate_input("hsl(120, 100%, 50%)");
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(menu::Confirm);
+        assert!(active_color_picker(&workspace, cx).is_none());
+        editor.update(cx, |editor, cx| {
+            assert_eq!(editor.text(cx), "a { color: rgb(0, 255, 0); }");
+        });
+    }
+
+    fn active_color_picker(
+        workspace: &Entity<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> Option<Entity<ColorPicker>> {
+        workspace.update(cx, |workspace, cx| {
+            workspace.active_modal::<ColorPicker>(cx)
+        })
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            language::init(cx);
+            crate::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+}
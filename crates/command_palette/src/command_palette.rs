@@ -9,7 +9,7 @@ use std::{
 
 use client::parse_zed_link;
 use command_palette_hooks::{
-    CommandInterceptResult, CommandPaletteFilter, CommandPaletteInterceptor,
+    CommandInterceptResult, CommandPaletteCommands, CommandPaletteFilter, CommandPaletteInterceptor,
 };
 
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -94,7 +94,7 @@ impl CommandPalette {
     ) -> Self {
         let filter = CommandPaletteFilter::try_global(cx);
 
-        let commands = window
+        let mut commands = window
             .available_actions(cx)
             .into_iter()
             .filter_map(|action| {
@@ -107,7 +107,20 @@ impl CommandPalette {
                     action,
                 })
             })
-            .collect();
+            .collect::<Vec<_>>();
+
+        let extra_commands = CommandPaletteCommands::try_global(cx)
+            .into_iter()
+            .flat_map(|commands| commands.commands())
+            .map(|command| Command {
+                name: command.name.clone(),
+                action: command.action.boxed_clone(),
+            })
+            .collect::<Vec<_>>();
+        commands.extend(extra_commands.into_iter().filter(|command| {
+            !filter.is_some_and(|filter| filter.is_hidden(&*command.action))
+                && window.is_action_available(&*command.action, cx)
+        }));
 
         let delegate =
             CommandPaletteDelegate::new(cx.entity().downgrade(), commands, previous_focus_handle);
@@ -477,6 +490,7 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
+    use command_palette_hooks::CommandPaletteCommand;
     use editor::Editor;
     use go_to_line::GoToLine;
     use gpui::TestAppContext;
@@ -636,6 +650,61 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_extra_commands(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text("abc", window, cx);
+            editor
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor.update(cx, |editor, cx| window.focus(&editor.focus_handle(cx)))
+        });
+
+        cx.update(|_window, cx| {
+            CommandPaletteCommands::update_global(cx, |commands, _| {
+                commands.set(
+                    "test",
+                    vec![CommandPaletteCommand {
+                        name: "custom: remove last character".into(),
+                        action: Box::new(editor::actions::Backspace),
+                    }],
+                );
+            });
+        });
+
+        cx.simulate_keystrokes("cmd-shift-p");
+        let palette = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<CommandPalette>(cx)
+                .unwrap()
+                .read(cx)
+                .picker
+                .clone()
+        });
+
+        cx.simulate_input("custom: remove");
+        palette.read_with(cx, |palette, _| {
+            assert_eq!(
+                palette.delegate.matches[0].string,
+                "custom: remove last character"
+            );
+        });
+
+        cx.simulate_keystrokes("enter");
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<CommandPalette>(cx).is_none());
+            assert_eq!(editor.read(cx).text(cx), "ab")
+        });
+    }
+
     #[gpui::test]
     async fn test_go_to_line(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
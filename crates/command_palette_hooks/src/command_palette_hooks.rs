@@ -4,7 +4,7 @@
 
 use std::any::TypeId;
 
-use collections::HashSet;
+use collections::{HashMap, HashSet};
 use derive_more::{Deref, DerefMut};
 use gpui::{Action, App, BorrowAppContext, Global};
 
@@ -12,6 +12,7 @@ use gpui::{Action, App, BorrowAppContext, Global};
 pub fn init(cx: &mut App) {
     cx.set_global(GlobalCommandPaletteFilter::default());
     cx.set_global(GlobalCommandPaletteInterceptor::default());
+    cx.set_global(GlobalCommandPaletteCommands::default());
 }
 
 /// A filter for the command palette.
@@ -152,3 +153,55 @@ impl CommandPaletteInterceptor {
         self.0 = Some(handler);
     }
 }
+
+/// A command listed in the command palette in addition to the available actions, for
+/// things that aren't actions of their own, like the rules in the rules library.
+pub struct CommandPaletteCommand {
+    /// The name shown in the command palette.
+    pub name: String,
+    /// The action dispatched when the command is confirmed.
+    pub action: Box<dyn Action>,
+}
+
+/// Additional commands for the command palette, grouped by the source that provides them.
+#[derive(Default)]
+pub struct CommandPaletteCommands {
+    commands_by_source: HashMap<&'static str, Vec<CommandPaletteCommand>>,
+}
+
+#[derive(Deref, DerefMut, Default)]
+struct GlobalCommandPaletteCommands(CommandPaletteCommands);
+
+impl Global for GlobalCommandPaletteCommands {}
+
+impl CommandPaletteCommands {
+    /// Returns the global [`CommandPaletteCommands`], if set.
+    pub fn try_global(cx: &App) -> Option<&CommandPaletteCommands> {
+        cx.try_global::<GlobalCommandPaletteCommands>()
+            .map(|commands| &commands.0)
+    }
+
+    /// Updates the global [`CommandPaletteCommands`] using the given closure.
+    pub fn update_global<F>(cx: &mut App, update: F)
+    where
+        F: FnOnce(&mut Self, &mut App),
+    {
+        if cx.has_global::<GlobalCommandPaletteCommands>() {
+            cx.update_global(|this: &mut GlobalCommandPaletteCommands, cx| update(&mut this.0, cx))
+        }
+    }
+
+    /// Replaces the commands provided by the given source.
+    pub fn set(&mut self, source: &'static str, commands: Vec<CommandPaletteCommand>) {
+        if commands.is_empty() {
+            self.commands_by_source.remove(source);
+        } else {
+            self.commands_by_source.insert(source, commands);
+        }
+    }
+
+    /// Returns the commands of all sources.
+    pub fn commands(&self) -> impl Iterator<Item = &CommandPaletteCommand> {
+        self.commands_by_source.values().flatten()
+    }
+}
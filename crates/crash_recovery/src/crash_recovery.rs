@@ -0,0 +1,339 @@
+mod persistence;
+mod recovered_changes_view;
+
+use std::{path::PathBuf, time::Duration};
+
+use gpui::{App, AppContext as _, Context, Entity, Subscription, Task, WeakEntity};
+use language::{Buffer, BufferEvent, BufferSnapshot};
+use project::{Project, buffer_store::BufferStoreEvent};
+use ui::{IconName, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace, WorkspaceId,
+    notifications::{NotificationId, simple_message_notification::MessageNotification},
+};
+
+pub use crate::persistence::JournalEntry;
+use crate::{persistence::DB, recovered_changes_view::RecoveredChangesView};
+
+/// How long to wait after a buffer changes before writing the journal.
+const JOURNAL_DELAY: Duration = Duration::from_secs(1);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let Some(workspace_id) = workspace.database_id() else {
+            return;
+        };
+        if !workspace.project().read(cx).is_local() {
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let journal = cx.new(|cx| RecoveryJournal::new(workspace_id, project, cx));
+
+        // Entries left in the journal were written by a session that didn't quit cleanly. Those
+        // whose changes the workspace's editors restore by themselves are skipped.
+        let restored_buffers = editor::serialized_unsaved_buffers(workspace_id, cx);
+        cx.spawn_in(window, {
+            let journal = journal.clone();
+            async move |workspace, cx| {
+                let recovered = cx
+                    .background_spawn(async move { DB.get_journal_entries(workspace_id) })
+                    .await
+                    .log_err()
+                    .unwrap_or_default();
+                let restored_buffers = restored_buffers.await.log_err().unwrap_or_default();
+                let recovered = recovered
+                    .into_iter()
+                    .filter(|entry| !is_restored_by_editor(entry, &restored_buffers))
+                    .collect::<Vec<_>>();
+
+                let has_recovered = !recovered.is_empty();
+                journal.update(cx, |journal, cx| journal.set_recovered(recovered, cx))?;
+                if has_recovered {
+                    workspace.update(cx, |workspace, cx| {
+                        show_recovery_notification(workspace, journal, cx)
+                    })?;
+                }
+                anyhow::Ok(())
+            }
+        })
+        .detach_and_log_err(cx);
+
+        // The journal lives as long as the workspace, and is cleared when it's closed.
+        cx.on_release(move |_, cx| {
+            journal.update(cx, |journal, cx| journal.write(false, cx).detach());
+        })
+        .detach();
+    })
+    .detach();
+}
+
+/// Records the unsaved contents of a workspace's buffers, so that they can be restored if Zed
+/// doesn't quit cleanly.
+pub struct RecoveryJournal {
+    workspace_id: WorkspaceId,
+    project: WeakEntity<Project>,
+    /// Whether the entries left by the previous session have been loaded. The journal isn't
+    /// written until then, so that they aren't overwritten.
+    is_loaded: bool,
+    /// The entries recovered from the previous session that haven't been restored or discarded.
+    recovered: Vec<JournalEntry>,
+    _pending_write: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl RecoveryJournal {
+    fn new(workspace_id: WorkspaceId, project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let buffer_store = project.read(cx).buffer_store().clone();
+        for buffer in buffer_store.read(cx).buffers() {
+            Self::watch_buffer(&buffer, cx);
+        }
+        let subscriptions = vec![
+            cx.subscribe(&buffer_store, |_, _, event, cx| {
+                if let BufferStoreEvent::BufferAdded(buffer) = event {
+                    Self::watch_buffer(buffer, cx);
+                }
+            }),
+            // Unsaved changes are either saved or discarded when quitting, so only the recovered
+            // entries that haven't been looked at yet are kept.
+            cx.on_app_quit(|journal, cx| journal.write(false, cx)),
+        ];
+
+        Self {
+            workspace_id,
+            project: project.downgrade(),
+            is_loaded: false,
+            recovered: Vec::new(),
+            _pending_write: Task::ready(()),
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn set_recovered(&mut self, recovered: Vec<JournalEntry>, cx: &mut Context<Self>) {
+        self.is_loaded = true;
+        self.recovered = recovered;
+        self.schedule_write(cx);
+    }
+
+    fn watch_buffer(buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        cx.subscribe(buffer, |journal, _, event, cx| match event {
+            BufferEvent::Edited
+            | BufferEvent::DirtyChanged
+            | BufferEvent::Saved
+            | BufferEvent::Reloaded
+            | BufferEvent::FileHandleChanged => journal.schedule_write(cx),
+            _ => {}
+        })
+        .detach();
+        cx.observe_release(buffer, |journal, _, cx| journal.schedule_write(cx))
+            .detach();
+    }
+
+    /// Writes the journal shortly, replacing any write that's already pending so that changes
+    /// in quick succession are written once.
+    fn schedule_write(&mut self, cx: &mut Context<Self>) {
+        self._pending_write = cx.spawn(async move |journal, cx| {
+            cx.background_executor().timer(JOURNAL_DELAY).await;
+            if let Ok(write) = journal.update(cx, |journal, cx| journal.write(true, cx)) {
+                write.await;
+            }
+        });
+    }
+
+    /// Replaces the journal with the recovered entries that are still unresolved, and, if
+    /// `include_buffers` is true, the unsaved contents of the project's buffers.
+    fn write(&mut self, include_buffers: bool, cx: &mut Context<Self>) -> Task<()> {
+        self._pending_write = Task::ready(());
+        if !self.is_loaded {
+            return Task::ready(());
+        }
+        let buffers = if include_buffers {
+            self.project
+                .upgrade()
+                .map(|project| {
+                    project
+                        .read(cx)
+                        .buffer_store()
+                        .read(cx)
+                        .buffers()
+                        .filter_map(|buffer| unsaved_buffer(buffer.read(cx), cx))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let recovered = self.recovered.clone();
+        let workspace_id = self.workspace_id;
+        cx.background_spawn(async move {
+            let entries = recovered
+                .into_iter()
+                .chain(buffers.into_iter().map(|(abs_path, snapshot)| {
+                    JournalEntry {
+                        abs_path,
+                        language: snapshot
+                            .language()
+                            .map(|language| language.name().to_string()),
+                        contents: snapshot.text(),
+                    }
+                }))
+                .collect();
+            DB.save_journal_entries(workspace_id, entries)
+                .await
+                .log_err();
+        })
+    }
+
+    /// Removes a recovered entry once it has been restored or discarded.
+    pub fn resolve(&mut self, entry: &JournalEntry, cx: &mut Context<Self>) {
+        self.recovered.retain(|recovered| recovered != entry);
+        self.write(true, cx).detach();
+    }
+
+    fn resolve_all(&mut self, cx: &mut Context<Self>) {
+        self.recovered.clear();
+        self.write(true, cx).detach();
+    }
+}
+
+/// Returns the path and a snapshot of a buffer with unsaved changes. Buffers of files that
+/// aren't on the local disk are skipped, as are untitled buffers that are empty.
+fn unsaved_buffer(buffer: &Buffer, cx: &App) -> Option<(Option<PathBuf>, BufferSnapshot)> {
+    if !buffer.is_dirty() {
+        return None;
+    }
+    let abs_path = match buffer.file() {
+        Some(file) => Some(file.as_local()?.abs_path(cx)),
+        None if buffer.is_empty() => return None,
+        None => None,
+    };
+    Some((abs_path, buffer.snapshot()))
+}
+
+/// Returns whether the editors restore the entry's changes by themselves, as the unsaved contents
+/// of the same file, or, for an untitled buffer, as identical contents.
+fn is_restored_by_editor(
+    entry: &JournalEntry,
+    restored_buffers: &[(Option<PathBuf>, String)],
+) -> bool {
+    restored_buffers
+        .iter()
+        .any(|(abs_path, contents)| match (&entry.abs_path, abs_path) {
+            (Some(entry_path), Some(abs_path)) => entry_path == abs_path,
+            (None, None) => entry.contents == *contents,
+            _ => false,
+        })
+}
+
+fn show_recovery_notification(
+    workspace: &mut Workspace,
+    journal: Entity<RecoveryJournal>,
+    cx: &mut Context<Workspace>,
+) {
+    struct CrashRecoveryNotification;
+
+    let count = journal.read(cx).recovered.len();
+    let message = if count == 1 {
+        "Zed didn't quit cleanly, and a file had unsaved changes.".to_string()
+    } else {
+        format!("Zed didn't quit cleanly, and {count} files had unsaved changes.")
+    };
+    let workspace_handle = cx.entity().downgrade();
+    workspace.show_notification(
+        NotificationId::unique::<CrashRecoveryNotification>(),
+        cx,
+        move |cx| {
+            cx.new(move |cx| {
+                MessageNotification::new(message, cx)
+                    .primary_message("Review Changes")
+                    .primary_icon(IconName::Diff)
+                    .primary_icon_color(Color::Accent)
+                    .primary_on_click({
+                        let journal = journal.clone();
+                        let workspace = workspace_handle.clone();
+                        move |window, cx| {
+                            let entries = journal.read(cx).recovered.clone();
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    for entry in entries {
+                                        RecoveredChangesView::open(
+                                            entry,
+                                            journal.clone(),
+                                            workspace,
+                                            window,
+                                            cx,
+                                        )
+                                        .detach_and_log_err(cx);
+                                    }
+                                })
+                                .log_err();
+                        }
+                    })
+                    .secondary_message("Discard All")
+                    .secondary_icon(IconName::Trash)
+                    .secondary_on_click(move |_, cx| {
+                        journal.update(cx, |journal, cx| journal.resolve_all(cx));
+                    })
+            })
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn test_unsaved_buffer(cx: &mut TestAppContext) {
+        let empty = cx.new(|cx| Buffer::local("", cx));
+        let untitled = cx.new(|cx| Buffer::local("", cx));
+        untitled.update(cx, |buffer, cx| buffer.set_text("notes", cx));
+        cx.read(|cx| {
+            assert!(unsaved_buffer(empty.read(cx), cx).is_none());
+            let (abs_path, snapshot) = unsaved_buffer(untitled.read(cx), cx).unwrap();
+            assert_eq!(abs_path, None);
+            assert_eq!(snapshot.text(), "notes");
+        });
+    }
+
+    #[test]
+    fn test_is_restored_by_editor() {
+        let restored_buffers = vec![
+            (
+                Some(PathBuf::from("/project/main.rs")),
+                "fn main() {}\n".to_owned(),
+            ),
+            (None, "notes".to_owned()),
+        ];
+        let entry = |abs_path: Option<&str>, contents: &str| JournalEntry {
+            abs_path: abs_path.map(PathBuf::from),
+            language: None,
+            contents: contents.to_owned(),
+        };
+
+        // Files are matched by path, since the editor may have been serialized at another time.
+        assert!(is_restored_by_editor(
+            &entry(Some("/project/main.rs"), "fn main() { todo!() }\n"),
+            &restored_buffers
+        ));
+        assert!(!is_restored_by_editor(
+            &entry(Some("/project/lib.rs"), "fn main() {}\n"),
+            &restored_buffers
+        ));
+        // Untitled buffers are matched by their contents.
+        assert!(is_restored_by_editor(
+            &entry(None, "notes"),
+            &restored_buffers
+        ));
+        assert!(!is_restored_by_editor(
+            &entry(None, "other notes"),
+            &restored_buffers
+        ));
+        assert!(!is_restored_by_editor(&entry(None, "notes"), &[]));
+    }
+}
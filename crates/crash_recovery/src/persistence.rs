@@ -0,0 +1,141 @@
+use anyhow::Result;
+use db::{
+    query,
+    sqlez::{
+        bindable::Column, domain::Domain, statement::Statement,
+        thread_safe_connection::ThreadSafeConnection,
+    },
+    sqlez_macros::sql,
+};
+use std::path::PathBuf;
+use workspace::{WorkspaceDb, WorkspaceId};
+
+/// The unsaved contents of a buffer, as recorded in the crash recovery journal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JournalEntry {
+    /// The path of the buffer's file, or `None` for an untitled buffer.
+    pub abs_path: Option<PathBuf>,
+    pub language: Option<String>,
+    pub contents: String,
+}
+
+impl Column for JournalEntry {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (abs_path, next_index): (Option<PathBuf>, i32) =
+            Column::column(statement, start_index)?;
+        let (language, next_index): (Option<String>, i32) = Column::column(statement, next_index)?;
+        let (contents, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let entry = Self {
+            abs_path,
+            language,
+            contents,
+        };
+        Ok((entry, next_index))
+    }
+}
+
+pub struct CrashRecoveryDb(ThreadSafeConnection);
+
+impl Domain for CrashRecoveryDb {
+    const NAME: &str = stringify!(CrashRecoveryDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE journal_entries(
+            workspace_id INTEGER NOT NULL,
+            entry_index INTEGER NOT NULL,
+            abs_path BLOB,
+            language TEXT,
+            contents TEXT NOT NULL,
+            PRIMARY KEY(workspace_id, entry_index),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        ) STRICT;
+    )];
+}
+
+db::static_connection!(DB, CrashRecoveryDb, [WorkspaceDb]);
+
+impl CrashRecoveryDb {
+    query! {
+        pub fn get_journal_entries(workspace_id: WorkspaceId) -> Result<Vec<JournalEntry>> {
+            SELECT abs_path, language, contents
+            FROM journal_entries
+            WHERE workspace_id = ?
+            ORDER BY entry_index
+        }
+    }
+
+    /// Replaces the workspace's journal with the given entries.
+    pub async fn save_journal_entries(
+        &self,
+        workspace_id: WorkspaceId,
+        entries: Vec<JournalEntry>,
+    ) -> Result<()> {
+        log::debug!(
+            "Saving {} crash recovery entries for workspace {workspace_id:?}",
+            entries.len()
+        );
+        self.write(move |conn| {
+            conn.with_savepoint("save_journal_entries", || {
+                conn.exec_bound(sql!(
+                    DELETE FROM journal_entries WHERE workspace_id = ?;
+                ))?(workspace_id)?;
+                for (index, entry) in entries.into_iter().enumerate() {
+                    conn.exec_bound(sql!(
+                        INSERT INTO journal_entries
+                            (workspace_id, entry_index, abs_path, language, contents)
+                        VALUES
+                            (?1, ?2, ?3, ?4, ?5);
+                    ))?((
+                        workspace_id,
+                        index,
+                        entry.abs_path,
+                        entry.language,
+                        entry.contents,
+                    ))?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_get_journal_entries() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        assert_eq!(DB.get_journal_entries(workspace_id).unwrap(), []);
+
+        let entries = vec![
+            JournalEntry {
+                abs_path: Some(PathBuf::from("/project/main.rs")),
+                language: Some("Rust".to_owned()),
+                contents: "fn main() {}\n".to_owned(),
+            },
+            JournalEntry {
+                abs_path: None,
+                language: None,
+                contents: "notes".to_owned(),
+            },
+        ];
+        DB.save_journal_entries(workspace_id, entries.clone())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_journal_entries(workspace_id).unwrap(), entries);
+
+        // Saving again replaces the previous entries.
+        DB.save_journal_entries(workspace_id, entries[1..].to_vec())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_journal_entries(workspace_id).unwrap(), &entries[1..]);
+
+        DB.save_journal_entries(workspace_id, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_journal_entries(workspace_id).unwrap(), []);
+    }
+}
@@ -0,0 +1,308 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use buffer_diff::{BufferDiff, BufferDiffSnapshot};
+use editor::{Editor, MultiBuffer};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity,
+};
+use language::Buffer;
+use project::Project;
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr as _,
+};
+
+use crate::{JournalEntry, RecoveryJournal};
+
+/// Shows the unsaved changes recovered from a previous session as a diff against the file on
+/// disk, so that they can be restored or discarded.
+pub struct RecoveredChangesView {
+    entry: JournalEntry,
+    journal: Entity<RecoveryJournal>,
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    editor: Entity<Editor>,
+}
+
+impl RecoveredChangesView {
+    pub fn open(
+        entry: JournalEntry,
+        journal: Entity<RecoveryJournal>,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let fs = workspace.app_state().fs.clone();
+        let languages = workspace.app_state().languages.clone();
+        let workspace = workspace.weak_handle();
+        window.spawn(cx, async move |cx| {
+            // A file that no longer exists is shown as if it were empty.
+            let disk_text = match &entry.abs_path {
+                Some(abs_path) => fs.load(abs_path).await.unwrap_or_default(),
+                None => String::new(),
+            };
+            let language = match &entry.language {
+                Some(name) => languages.language_for_name(name).await.log_err(),
+                None => None,
+            };
+
+            let disk_buffer = cx.new(|cx| Buffer::local(disk_text, cx))?;
+            let recovered_buffer = cx.new(|cx| {
+                let mut buffer = Buffer::local(entry.contents.clone(), cx);
+                buffer.set_language(language, cx);
+                buffer
+            })?;
+            let diff = build_buffer_diff(&disk_buffer, &recovered_buffer, cx).await?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    Self::new(
+                        entry,
+                        journal,
+                        workspace,
+                        recovered_buffer,
+                        diff,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            })
+        })
+    }
+
+    fn new(
+        entry: JournalEntry,
+        journal: Entity<RecoveryJournal>,
+        workspace: &Workspace,
+        recovered_buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let project = workspace.project().clone();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(recovered_buffer, cx);
+            multibuffer.add_diff(diff, cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        });
+        Self {
+            entry,
+            journal,
+            workspace: workspace.weak_handle(),
+            project,
+            editor,
+        }
+    }
+
+    fn file_name(&self) -> SharedString {
+        self.entry
+            .abs_path
+            .as_ref()
+            .and_then(|abs_path| abs_path.file_name())
+            .map_or_else(
+                || "untitled".into(),
+                |name| name.to_string_lossy().to_string().into(),
+            )
+    }
+
+    /// Opens the file with the recovered contents, leaving it unsaved so that it can be reviewed.
+    fn restore(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let entry = self.entry.clone();
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        let language = self
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .and_then(|buffer| buffer.read(cx).language().cloned());
+        cx.spawn_in(window, async move |this, cx| {
+            let buffer = match &entry.abs_path {
+                Some(abs_path) => {
+                    project
+                        .update(cx, |project, cx| project.open_local_buffer(abs_path, cx))?
+                        .await?
+                }
+                None => {
+                    let buffer = project
+                        .update(cx, |project, cx| project.create_buffer(true, cx))?
+                        .await?;
+                    if let Some(language) = language {
+                        project.update(cx, |project, cx| {
+                            project.set_language_for_buffer(&buffer, language, cx);
+                        })?;
+                    }
+                    buffer
+                }
+            };
+            buffer.update(cx, |buffer, cx| {
+                if buffer.text() != entry.contents {
+                    buffer.set_text(entry.contents.as_str(), cx);
+                }
+            })?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let pane = workspace.active_pane().clone();
+                workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+            })?;
+            this.update(cx, |this, cx| this.resolve(cx))
+        })
+        .detach_and_prompt_err(
+            "Failed to restore unsaved changes",
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    /// Removes the recovered changes from the journal and closes the view.
+    fn resolve(&mut self, cx: &mut Context<Self>) {
+        self.journal
+            .update(cx, |journal, cx| journal.resolve(&self.entry, cx));
+        cx.emit(ItemEvent::CloseItem);
+    }
+}
+
+async fn build_buffer_diff(
+    disk_buffer: &Entity<Buffer>,
+    recovered_buffer: &Entity<Buffer>,
+    cx: &mut gpui::AsyncWindowContext,
+) -> Result<Entity<BufferDiff>> {
+    let disk_snapshot = disk_buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+    let recovered_snapshot = recovered_buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+
+    let diff_snapshot = cx
+        .update(|_, cx| {
+            BufferDiffSnapshot::new_with_base_buffer(
+                recovered_snapshot.text.clone(),
+                Some(disk_snapshot.text().into()),
+                disk_snapshot,
+                cx,
+            )
+        })?
+        .await;
+
+    cx.new(|cx| {
+        let mut diff = BufferDiff::new(&recovered_snapshot.text, cx);
+        diff.set_snapshot(diff_snapshot, &recovered_snapshot.text, cx);
+        diff
+    })
+}
+
+impl EventEmitter<ItemEvent> for RecoveredChangesView {}
+
+impl Focusable for RecoveredChangesView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for RecoveredChangesView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("Recovered: {}", self.file_name()).into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        self.entry
+            .abs_path
+            .as_ref()
+            .map(|abs_path| abs_path.to_string_lossy().to_string().into())
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+}
+
+impl Render for RecoveredChangesView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(format!(
+                            "Unsaved changes to {} recovered from a previous session",
+                            self.file_name()
+                        ))
+                        .color(Color::Muted),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("discard-recovered-changes", "Discard")
+                                    .icon(IconName::Trash)
+                                    .icon_position(IconPosition::Start)
+                                    .icon_size(IconSize::Small)
+                                    .on_click(cx.listener(|this, _, _, cx| this.resolve(cx))),
+                            )
+                            .child(
+                                Button::new("restore-recovered-changes", "Restore")
+                                    .style(ButtonStyle::Filled)
+                                    .icon(IconName::Undo)
+                                    .icon_position(IconPosition::Start)
+                                    .icon_size(IconSize::Small)
+                                    .on_click(
+                                        cx.listener(|this, _, window, cx| this.restore(window, cx)),
+                                    ),
+                            ),
+                    ),
+            )
+            .child(self.editor.clone())
+    }
+}
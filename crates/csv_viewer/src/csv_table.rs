@@ -0,0 +1,264 @@
+use std::{cmp::Ordering, ops::Range};
+
+/// The records of a CSV or TSV file, parsed one at a time so that large files can be shown
+/// while they're still being parsed.
+pub struct Records<'a> {
+    text: &'a str,
+    delimiter: char,
+    position: usize,
+}
+
+impl<'a> Records<'a> {
+    pub fn new(text: &'a str, delimiter: char) -> Self {
+        Self {
+            text,
+            delimiter,
+            position: 0,
+        }
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self
+            .text
+            .get(self.position..)
+            .filter(|rest| !rest.is_empty())?;
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = rest.char_indices().peekable();
+        while let Some((_, char)) = chars.next() {
+            if in_quotes {
+                if char != '"' {
+                    field.push(char);
+                } else if chars.next_if(|(_, char)| *char == '"').is_some() {
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else if char == '"' && field.is_empty() {
+                in_quotes = true;
+            } else if char == self.delimiter {
+                record.push(std::mem::take(&mut field));
+            } else if char == '\n' || char == '\r' {
+                if char == '\r' {
+                    chars.next_if(|(_, char)| *char == '\n');
+                }
+                self.position += chars.peek().map_or(rest.len(), |(ix, _)| *ix);
+                record.push(field);
+                return Some(record);
+            } else {
+                field.push(char);
+            }
+        }
+
+        self.position = self.text.len();
+        record.push(field);
+        Some(record)
+    }
+}
+
+/// Returns whether the given record is an empty line.
+pub fn is_empty_record(record: &[String]) -> bool {
+    matches!(record, [field] if field.is_empty())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Compares two cells numerically if they are both numbers, and alphabetically otherwise.
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a
+            .to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b)),
+    }
+}
+
+/// Returns whether any cell of the row contains the given lowercase query.
+pub fn row_matches_filter(row: &[String], lowercase_query: &str) -> bool {
+    lowercase_query.is_empty()
+        || row
+            .iter()
+            .any(|cell| cell.to_lowercase().contains(lowercase_query))
+}
+
+/// Returns the indices of the rows that match the filter, in the given sort order.
+pub fn visible_rows(
+    rows: &[Vec<String>],
+    filter: &str,
+    sort: Option<(usize, SortDirection)>,
+) -> Vec<usize> {
+    let lowercase_filter = filter.to_lowercase();
+    let mut visible_rows = (0..rows.len())
+        .filter(|&ix| row_matches_filter(&rows[ix], &lowercase_filter))
+        .collect::<Vec<_>>();
+    if let Some((column, direction)) = sort {
+        let cell = |ix: usize| rows[ix].get(column).map_or("", String::as_str);
+        visible_rows.sort_by(|&a, &b| {
+            let ordering = compare_cells(cell(a), cell(b));
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+    visible_rows
+}
+
+/// Formats the given rows as CSV, or as TSV if the delimiter is a tab.
+pub fn to_csv<'a>(rows: impl IntoIterator<Item = Vec<&'a str>>, delimiter: char) -> String {
+    let mut csv = String::new();
+    for row in rows {
+        for (ix, cell) in row.into_iter().enumerate() {
+            if ix > 0 {
+                csv.push(delimiter);
+            }
+            if cell.contains([delimiter, '"', '\n', '\r']) {
+                csv.push('"');
+                csv.push_str(&cell.replace('"', "\"\""));
+                csv.push('"');
+            } else {
+                csv.push_str(cell);
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Formats the given rows as a Markdown table with the given header.
+pub fn to_markdown_table<'a>(
+    header: Vec<&'a str>,
+    rows: impl IntoIterator<Item = Vec<&'a str>>,
+) -> String {
+    fn push_row(markdown: &mut String, row: impl IntoIterator<Item = String>) {
+        markdown.push('|');
+        for cell in row {
+            markdown.push(' ');
+            markdown.push_str(&cell);
+            markdown.push_str(" |");
+        }
+        markdown.push('\n');
+    }
+
+    let escape = |cell: &str| cell.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let mut markdown = String::new();
+    let column_count = header.len();
+    push_row(&mut markdown, header.into_iter().map(escape));
+    push_row(&mut markdown, (0..column_count).map(|_| "---".to_string()));
+    for row in rows {
+        push_row(&mut markdown, row.into_iter().map(escape));
+    }
+    markdown
+}
+
+/// A rectangular selection of cells, in terms of visible rows and columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellSelection {
+    pub anchor: (usize, usize),
+    pub head: (usize, usize),
+}
+
+impl CellSelection {
+    pub fn new(cell: (usize, usize)) -> Self {
+        Self {
+            anchor: cell,
+            head: cell,
+        }
+    }
+
+    pub fn rows(&self) -> Range<usize> {
+        self.anchor.0.min(self.head.0)..self.anchor.0.max(self.head.0) + 1
+    }
+
+    pub fn columns(&self) -> Range<usize> {
+        self.anchor.1.min(self.head.1)..self.anchor.1.max(self.head.1) + 1
+    }
+
+    pub fn contains(&self, (row, column): (usize, usize)) -> bool {
+        self.rows().contains(&row) && self.columns().contains(&column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str, delimiter: char) -> Vec<Vec<String>> {
+        Records::new(text, delimiter).collect()
+    }
+
+    #[test]
+    fn test_parse_records() {
+        assert_eq!(
+            parse("name,age\nAlice,30\r\nBob,25\n", ','),
+            vec![vec!["name", "age"], vec!["Alice", "30"], vec!["Bob", "25"]]
+        );
+        assert_eq!(
+            parse("\"Smith, John\",\"said \"\"hi\"\"\"\n\"multi\nline\",", ','),
+            vec![vec!["Smith, John", "said \"hi\""], vec!["multi\nline", ""]]
+        );
+        assert_eq!(
+            parse("a\tb, c\n1\t2", '\t'),
+            vec![vec!["a", "b, c"], vec!["1", "2"]]
+        );
+        assert!(is_empty_record(&parse("\n", ',')[0]));
+    }
+
+    #[test]
+    fn test_visible_rows() {
+        let rows = ["10", "9", "banana", "Apple", "100"]
+            .into_iter()
+            .map(|cell| vec![cell.to_string()])
+            .collect::<Vec<_>>();
+
+        assert_eq!(visible_rows(&rows, "", None), vec![0, 1, 2, 3, 4]);
+        assert_eq!(
+            visible_rows(&rows, "", Some((0, SortDirection::Ascending))),
+            vec![1, 0, 4, 3, 2]
+        );
+        assert_eq!(
+            visible_rows(&rows, "", Some((0, SortDirection::Descending))),
+            vec![2, 3, 4, 0, 1]
+        );
+        assert_eq!(
+            visible_rows(&rows, "A", Some((0, SortDirection::Ascending))),
+            vec![3, 2]
+        );
+    }
+
+    #[test]
+    fn test_copy_formats() {
+        let rows = || vec![vec!["1", "a|b"], vec!["2", "say \"hi\", bye"]];
+
+        assert_eq!(to_csv(rows(), ','), "1,a|b\n2,\"say \"\"hi\"\", bye\"\n");
+        assert_eq!(
+            to_markdown_table(vec!["id", "text"], rows()),
+            "| id | text |\n| --- | --- |\n| 1 | a\\|b |\n| 2 | say \"hi\", bye |\n"
+        );
+    }
+
+    #[test]
+    fn test_cell_selection() {
+        let selection = CellSelection {
+            anchor: (5, 2),
+            head: (3, 0),
+        };
+
+        assert_eq!(selection.rows(), 3..6);
+        assert_eq!(selection.columns(), 0..3);
+        assert!(selection.contains((4, 1)));
+        assert!(!selection.contains((6, 1)));
+    }
+}
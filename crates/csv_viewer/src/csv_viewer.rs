@@ -0,0 +1,678 @@
+mod csv_table;
+
+use std::{ops::Range, time::Duration};
+
+use anyhow::Result;
+use collections::HashSet;
+use editor::{Editor, EditorEvent};
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{
+    AnyElement, App, ClickEvent, ClipboardItem, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, FontWeight, Subscription, Task, UniformListScrollHandle, Window, actions,
+    uniform_list,
+};
+use language::{Buffer, BufferEvent};
+use project::{Project, ProjectEntryId, ProjectPath};
+use ui::prelude::*;
+use workspace::{
+    Pane, WorkspaceId,
+    item::{Item, ItemEvent, ProjectItem, SaveOptions, TabContentParams},
+};
+
+use crate::csv_table::{
+    CellSelection, Records, SortDirection, is_empty_record, row_matches_filter, to_csv,
+    to_markdown_table, visible_rows,
+};
+
+actions!(
+    csv_viewer,
+    [
+        /// Toggles between the table and the raw text of a CSV or TSV file.
+        ToggleRawText,
+        /// Copies the selected cells as CSV.
+        CopyAsCsv,
+        /// Copies the selected cells as a Markdown table.
+        CopyAsMarkdownTable,
+    ]
+);
+
+const REPARSE_DEBOUNCE: Duration = Duration::from_millis(200);
+/// The number of rows that are parsed before they're shown, so that large files show up
+/// while they're still being parsed.
+const PARSE_BATCH_SIZE: usize = 10_000;
+/// The number of rows that are measured to size the columns.
+const COLUMN_WIDTH_SAMPLE_SIZE: usize = 1000;
+const MIN_COLUMN_WIDTH_CHARS: usize = 3;
+const MAX_COLUMN_WIDTH_CHARS: usize = 40;
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<CsvView>(cx);
+}
+
+fn delimiter_for_extension(extension: &str) -> Option<char> {
+    if extension.eq_ignore_ascii_case("csv") {
+        Some(',')
+    } else if extension.eq_ignore_ascii_case("tsv") {
+        Some('\t')
+    } else {
+        None
+    }
+}
+
+/// A CSV or TSV file, along with the buffer containing its text.
+pub struct CsvFile {
+    buffer: Entity<Buffer>,
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    delimiter: char,
+}
+
+impl project::ProjectItem for CsvFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let delimiter = delimiter_for_extension(path.path.extension()?)?;
+        let entry_id = project
+            .read(cx)
+            .entry_for_path(path, cx)
+            .map(|entry| entry.id);
+        let open_buffer = project.update(cx, |project, cx| project.open_buffer(path.clone(), cx));
+        let project_path = path.clone();
+        Some(cx.spawn(async move |cx| {
+            let buffer = open_buffer.await?;
+            cx.new(|_| Self {
+                buffer,
+                project_path,
+                entry_id,
+                delimiter,
+            })
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// Shows a CSV or TSV file as a table that can be sorted and filtered, or as raw text.
+pub struct CsvView {
+    file: Entity<CsvFile>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    header: Vec<SharedString>,
+    rows: Vec<Vec<String>>,
+    /// The width of each column, in characters.
+    column_widths: Vec<usize>,
+    /// The indices of the rows that match the filter, in the order they're shown.
+    visible_rows: Vec<usize>,
+    sort: Option<(usize, SortDirection)>,
+    selection: Option<CellSelection>,
+    filter_editor: Entity<Editor>,
+    raw_text_editor: Option<Entity<Editor>>,
+    show_raw_text: bool,
+    is_parsing: bool,
+    scroll_handle: UniformListScrollHandle,
+    _parse_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CsvView {
+    fn new(
+        file: Entity<CsvFile>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let buffer = file.read(cx).buffer.clone();
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter rows…", window, cx);
+            editor
+        });
+        let _subscriptions = vec![
+            cx.subscribe(&buffer, |this, _, event: &BufferEvent, cx| match event {
+                BufferEvent::Edited => this.parse(true, cx),
+                BufferEvent::Reloaded => this.parse(false, cx),
+                BufferEvent::DirtyChanged | BufferEvent::Saved | BufferEvent::FileHandleChanged => {
+                    cx.emit(ItemEvent::UpdateTab)
+                }
+                _ => {}
+            }),
+            cx.subscribe(&filter_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_visible_rows(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            file,
+            project,
+            focus_handle: cx.focus_handle(),
+            header: Vec::new(),
+            rows: Vec::new(),
+            column_widths: Vec::new(),
+            visible_rows: Vec::new(),
+            sort: None,
+            selection: None,
+            filter_editor,
+            raw_text_editor: None,
+            show_raw_text: false,
+            is_parsing: false,
+            scroll_handle: UniformListScrollHandle::new(),
+            _parse_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.parse(false, cx);
+        this
+    }
+
+    fn parse(&mut self, wait_for_debounce: bool, cx: &mut Context<Self>) {
+        let file = self.file.read(cx);
+        let text = file.buffer.read(cx).text();
+        let delimiter = file.delimiter;
+        self.is_parsing = true;
+        self._parse_task = cx.spawn(async move |this, cx| {
+            if wait_for_debounce {
+                // Wait for the user to stop typing
+                cx.background_executor().timer(REPARSE_DEBOUNCE).await;
+            }
+
+            // The rows are parsed in batches, and parsing stops once this task is dropped.
+            let (batch_tx, mut batch_rx) = mpsc::unbounded();
+            cx.background_spawn(async move {
+                let mut records =
+                    Records::new(&text, delimiter).filter(|record| !is_empty_record(record));
+                loop {
+                    let batch = records.by_ref().take(PARSE_BATCH_SIZE).collect::<Vec<_>>();
+                    if batch.is_empty() || batch_tx.unbounded_send(batch).is_err() {
+                        break;
+                    }
+                }
+            })
+            .detach();
+
+            let mut is_first_batch = true;
+            while let Some(batch) = batch_rx.next().await {
+                let Ok(()) = this.update(cx, |this, cx| {
+                    this.append_rows(batch, is_first_batch, cx);
+                }) else {
+                    return;
+                };
+                is_first_batch = false;
+            }
+            this.update(cx, |this, cx| {
+                if is_first_batch {
+                    this.append_rows(Vec::new(), true, cx);
+                }
+                this.is_parsing = false;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn append_rows(
+        &mut self,
+        batch: Vec<Vec<String>>,
+        is_first_batch: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let mut batch = batch.into_iter();
+        if is_first_batch {
+            self.header = batch
+                .next()
+                .unwrap_or_default()
+                .into_iter()
+                .map(SharedString::from)
+                .collect();
+            self.rows.clear();
+            self.visible_rows.clear();
+        }
+
+        let new_rows = self.rows.len()..self.rows.len() + batch.len();
+        self.rows.extend(batch);
+        if is_first_batch {
+            self.column_widths = self.measure_column_widths();
+            self.selection = None;
+        }
+
+        if self.sort.is_some() {
+            self.update_visible_rows(cx);
+        } else {
+            let lowercase_filter = self.filter_editor.read(cx).text(cx).to_lowercase();
+            self.visible_rows.extend(
+                new_rows.filter(|&ix| row_matches_filter(&self.rows[ix], &lowercase_filter)),
+            );
+            cx.notify();
+        }
+    }
+
+    fn measure_column_widths(&self) -> Vec<usize> {
+        let mut widths = self
+            .header
+            .iter()
+            .map(|header| header.chars().count())
+            .collect::<Vec<_>>();
+        for row in self.rows.iter().take(COLUMN_WIDTH_SAMPLE_SIZE) {
+            if widths.len() < row.len() {
+                widths.resize(row.len(), 0);
+            }
+            for (width, cell) in widths.iter_mut().zip(row) {
+                let cell_width = cell.lines().next().unwrap_or_default().chars().count();
+                *width = (*width).max(cell_width);
+            }
+        }
+        widths
+            .into_iter()
+            .map(|width| width.clamp(MIN_COLUMN_WIDTH_CHARS, MAX_COLUMN_WIDTH_CHARS))
+            .collect()
+    }
+
+    fn update_visible_rows(&mut self, cx: &mut Context<Self>) {
+        let filter = self.filter_editor.read(cx).text(cx);
+        self.visible_rows = visible_rows(&self.rows, &filter, self.sort);
+        self.selection = None;
+        cx.notify();
+    }
+
+    fn toggle_sort(&mut self, column: usize, cx: &mut Context<Self>) {
+        self.sort = match self.sort {
+            Some((sorted_column, SortDirection::Ascending)) if sorted_column == column => {
+                Some((column, SortDirection::Descending))
+            }
+            Some((sorted_column, SortDirection::Descending)) if sorted_column == column => None,
+            _ => Some((column, SortDirection::Ascending)),
+        };
+        self.update_visible_rows(cx);
+    }
+
+    fn select_cell(&mut self, cell: (usize, usize), extend: bool, cx: &mut Context<Self>) {
+        match &mut self.selection {
+            Some(selection) if extend => selection.head = cell,
+            _ => self.selection = Some(CellSelection::new(cell)),
+        }
+        cx.notify();
+    }
+
+    /// Returns the selected columns, and the selected cells of each selected row.
+    fn selected_cells(&self) -> Option<(Range<usize>, Vec<Vec<&str>>)> {
+        let selection = self.selection?;
+        let columns = selection.columns();
+        let rows = selection
+            .rows()
+            .filter_map(|visible_ix| self.visible_rows.get(visible_ix))
+            .map(|&row_ix| {
+                columns
+                    .clone()
+                    .map(|column| self.rows[row_ix].get(column).map_or("", String::as_str))
+                    .collect()
+            })
+            .collect();
+        Some((columns, rows))
+    }
+
+    fn copy_as_csv(&mut self, _: &CopyAsCsv, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some((_, rows)) = self.selected_cells() {
+            let delimiter = self.file.read(cx).delimiter;
+            cx.write_to_clipboard(ClipboardItem::new_string(to_csv(rows, delimiter)));
+        }
+    }
+
+    fn copy_as_markdown_table(
+        &mut self,
+        _: &CopyAsMarkdownTable,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((columns, rows)) = self.selected_cells() {
+            let header = columns
+                .map(|column| self.header.get(column).map_or("", |header| header.as_ref()))
+                .collect();
+            cx.write_to_clipboard(ClipboardItem::new_string(to_markdown_table(header, rows)));
+        }
+    }
+
+    fn toggle_raw_text(&mut self, _: &ToggleRawText, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_raw_text = !self.show_raw_text;
+        if self.show_raw_text {
+            let buffer = self.file.read(cx).buffer.clone();
+            let project = self.project.clone();
+            let editor = self
+                .raw_text_editor
+                .get_or_insert_with(|| {
+                    cx.new(|cx| Editor::for_buffer(buffer, Some(project), window, cx))
+                })
+                .clone();
+            window.focus(&editor.focus_handle(cx));
+        } else {
+            window.focus(&self.focus_handle);
+        }
+        cx.notify();
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let row_count = if self.visible_rows.len() == self.rows.len() {
+            format!("{} rows", self.rows.len())
+        } else {
+            format!("{} of {} rows", self.visible_rows.len(), self.rows.len())
+        };
+
+        h_flex()
+            .w_full()
+            .p_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .when(!self.show_raw_text, |this| {
+                this.child(
+                    div()
+                        .w(rems(16.))
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(self.filter_editor.clone()),
+                )
+                .child(
+                    Label::new(row_count)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .when(self.is_parsing, |this| {
+                    this.child(
+                        Label::new("Loading…")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+            })
+            .child(div().flex_1())
+            .when(!self.show_raw_text, |this| {
+                this.child(
+                    Button::new("copy-as-csv", "Copy as CSV")
+                        .label_size(LabelSize::Small)
+                        .disabled(self.selection.is_none())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.copy_as_csv(&CopyAsCsv, window, cx)
+                        })),
+                )
+                .child(
+                    Button::new("copy-as-markdown-table", "Copy as Markdown")
+                        .label_size(LabelSize::Small)
+                        .disabled(self.selection.is_none())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.copy_as_markdown_table(&CopyAsMarkdownTable, window, cx)
+                        })),
+                )
+            })
+            .child(
+                Button::new(
+                    "toggle-raw-text",
+                    if self.show_raw_text {
+                        "Show Table"
+                    } else {
+                        "Show Raw Text"
+                    },
+                )
+                .label_size(LabelSize::Small)
+                .on_click(cx.listener(|this, _, window, cx| {
+                    this.toggle_raw_text(&ToggleRawText, window, cx)
+                })),
+            )
+    }
+
+    fn render_table(&self, cx: &mut Context<Self>) -> AnyElement {
+        let column_widths = self
+            .column_widths
+            .iter()
+            .map(|&chars| rems(chars as f32 * 0.6 + 1.))
+            .collect::<Vec<_>>();
+        let table_width = column_widths.iter().map(|width| width.0).sum::<f32>();
+
+        // The header is outside of the list, so that it stays visible while the rows scroll.
+        let header = h_flex()
+            .w_full()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().element_background)
+            .children(column_widths.iter().enumerate().map(|(column, width)| {
+                let sort_icon = match self.sort {
+                    Some((sorted_column, direction)) if sorted_column == column => {
+                        Some(match direction {
+                            SortDirection::Ascending => IconName::ArrowUp,
+                            SortDirection::Descending => IconName::ArrowDown,
+                        })
+                    }
+                    _ => None,
+                };
+                h_flex()
+                    .id(("csv-header", column))
+                    .w(*width)
+                    .flex_none()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .overflow_hidden()
+                    .cursor_pointer()
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .child(
+                        Label::new(self.header.get(column).cloned().unwrap_or_default())
+                            .weight(FontWeight::SEMIBOLD)
+                            .truncate(),
+                    )
+                    .children(sort_icon.map(|icon| Icon::new(icon).size(IconSize::XSmall)))
+                    .on_click(cx.listener(move |this, _, _, cx| this.toggle_sort(column, cx)))
+            }));
+
+        let rows = uniform_list(
+            "csv-rows",
+            self.visible_rows.len(),
+            cx.processor(move |this, range: Range<usize>, _, cx| {
+                range
+                    .map(|visible_ix| this.render_row(visible_ix, &column_widths, cx))
+                    .collect::<Vec<_>>()
+            }),
+        )
+        .flex_grow()
+        .track_scroll(self.scroll_handle.clone());
+
+        div()
+            .id("csv-table")
+            .size_full()
+            .overflow_x_scroll()
+            .child(
+                v_flex()
+                    .w(rems(table_width))
+                    .h_full()
+                    .font_buffer(cx)
+                    .child(header)
+                    .child(rows),
+            )
+            .into_any_element()
+    }
+
+    fn render_row(
+        &self,
+        visible_ix: usize,
+        column_widths: &[Rems],
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let row = &self.rows[self.visible_rows[visible_ix]];
+        let column_count = column_widths.len();
+        h_flex()
+            .w_full()
+            .when(visible_ix % 2 == 1, |this| {
+                this.bg(cx.theme().colors().surface_background)
+            })
+            .children(column_widths.iter().enumerate().map(|(column, width)| {
+                let cell = row.get(column).map_or("", String::as_str);
+                let is_selected = self
+                    .selection
+                    .is_some_and(|selection| selection.contains((visible_ix, column)));
+                div()
+                    .id(("csv-cell", visible_ix * column_count + column))
+                    .w(*width)
+                    .flex_none()
+                    .px_2()
+                    .truncate()
+                    .when(is_selected, |this| {
+                        this.bg(cx.theme().colors().element_selected)
+                    })
+                    .child(SharedString::from(
+                        cell.lines().next().unwrap_or_default().to_string(),
+                    ))
+                    .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                        window.focus(&this.focus_handle);
+                        this.select_cell((visible_ix, column), event.modifiers().shift, cx);
+                    }))
+            }))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for CsvView {}
+
+impl Focusable for CsvView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.raw_text_editor {
+            Some(editor) if self.show_raw_text => editor.focus_handle(cx),
+            _ => self.focus_handle.clone(),
+        }
+    }
+}
+
+impl Item for CsvView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        true
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.file
+            .read(cx)
+            .project_path
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string()
+            .into()
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.file.read(cx).buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.file.read(cx).buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        _: SaveOptions,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let buffer = self.file.read(cx).buffer.clone();
+        project.update(cx, |project, cx| project.save_buffer(buffer, cx))
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let buffer = self.file.read(cx).buffer.clone();
+        let reload = project.update(cx, |project, cx| {
+            project.reload_buffers(HashSet::from_iter([buffer]), true, cx)
+        });
+        cx.background_spawn(async move {
+            reload.await?;
+            Ok(())
+        })
+    }
+
+    fn clone_on_split(
+        &self,
+        _: Option<WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        Some(cx.new(|cx| Self::new(self.file.clone(), self.project.clone(), window, cx)))
+    }
+}
+
+impl Render for CsvView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match &self.raw_text_editor {
+            Some(editor) if self.show_raw_text => editor.clone().into_any_element(),
+            _ => self.render_table(cx),
+        };
+
+        v_flex()
+            .key_context("CsvView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::toggle_raw_text))
+            .on_action(cx.listener(Self::copy_as_csv))
+            .on_action(cx.listener(Self::copy_as_markdown_table))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_toolbar(cx))
+            .child(div().flex_1().min_h_0().child(content))
+    }
+}
+
+impl ProjectItem for CsvView {
+    type Item = CsvFile;
+
+    fn for_project_item(
+        project: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, project, window, cx)
+    }
+}
@@ -0,0 +1,541 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use edit_prediction::{Direction, EditPrediction, EditPredictionProvider};
+use extension::{
+    EditPredictionProviderManifestEntry, EditPredictionRequest, Extension,
+    ExtensionEditPredictionProviderProxy, ExtensionHostProxy,
+};
+use futures::{AsyncReadExt as _, FutureExt as _, future::BoxFuture};
+use gpui::{App, Context, Entity, EntityId, Global, Task};
+use http_client::{AsyncBody, HttpClient, Method, Request};
+use language::{
+    Anchor, Buffer, BufferSnapshot, File as _,
+    language_settings::{CustomEditPredictionSettings, all_language_settings},
+};
+use serde::{Deserialize, Serialize};
+use text::{Bias, ToOffset};
+
+pub const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// The number of bytes of text before the cursor to send as the prompt.
+const MAX_PREFIX_LEN: usize = 8 * 1024;
+/// The number of bytes of text after the cursor to send as the suffix.
+const MAX_SUFFIX_LEN: usize = 2 * 1024;
+/// The number of alternative completions requested when cycling through predictions.
+const CYCLING_CANDIDATES: u32 = 3;
+/// The sampling temperature used for alternative completions, so that they differ from the
+/// deterministic one.
+const CYCLING_TEMPERATURE: f32 = 0.8;
+
+pub fn init(cx: &mut App) {
+    ExtensionHostProxy::default_global(cx)
+        .register_edit_prediction_provider_proxy(ExtensionEditPredictionProviderRegistryProxy);
+}
+
+/// The edit prediction providers registered by the installed extensions, by ID.
+#[derive(Default)]
+struct ExtensionEditPredictionProviders(HashMap<Arc<str>, Arc<dyn Extension>>);
+
+impl Global for ExtensionEditPredictionProviders {}
+
+struct ExtensionEditPredictionProviderRegistryProxy;
+
+impl ExtensionEditPredictionProviderProxy for ExtensionEditPredictionProviderRegistryProxy {
+    fn register_edit_prediction_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        _provider: EditPredictionProviderManifestEntry,
+        cx: &mut App,
+    ) {
+        cx.default_global::<ExtensionEditPredictionProviders>()
+            .0
+            .insert(provider_id, extension);
+    }
+
+    fn unregister_edit_prediction_provider(&self, provider_id: Arc<str>, cx: &mut App) {
+        cx.default_global::<ExtensionEditPredictionProviders>()
+            .0
+            .remove(&provider_id);
+    }
+}
+
+/// Requests fill-in-the-middle completions from an OpenAI-compatible
+/// `/completions` endpoint, such as a local llama.cpp server, or from an
+/// edit prediction provider registered by an extension.
+pub struct CustomEditPredictionProvider {
+    http_client: Arc<dyn HttpClient>,
+    pending_refresh: Option<Task<Result<()>>>,
+    completion: Option<CurrentCompletion>,
+}
+
+struct CurrentCompletion {
+    buffer_id: EntityId,
+    position: Anchor,
+    /// The predicted texts, starting with the deterministic one.
+    candidates: Vec<String>,
+    active_candidate: usize,
+    /// Whether alternative candidates were requested.
+    cycled: bool,
+}
+
+#[derive(Serialize)]
+struct CompletionRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    prompt: String,
+    suffix: String,
+    max_tokens: u32,
+    temperature: f32,
+    n: u32,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct CompletionResponse {
+    choices: Vec<CompletionChoice>,
+}
+
+#[derive(Deserialize)]
+struct CompletionChoice {
+    text: String,
+}
+
+impl CustomEditPredictionProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            http_client,
+            pending_refresh: None,
+            completion: None,
+        }
+    }
+}
+
+impl EditPredictionProvider for CustomEditPredictionProvider {
+    fn name() -> &'static str {
+        "custom"
+    }
+
+    fn display_name() -> &'static str {
+        "Custom"
+    }
+
+    fn show_completions_in_menu() -> bool {
+        true
+    }
+
+    fn show_tab_accept_marker() -> bool {
+        true
+    }
+
+    fn supports_jump_to_edit() -> bool {
+        false
+    }
+
+    fn is_enabled(&self, _buffer: &Entity<Buffer>, _cursor_position: Anchor, _cx: &App) -> bool {
+        true
+    }
+
+    fn is_refreshing(&self) -> bool {
+        self.pending_refresh.is_some()
+    }
+
+    fn refresh(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        // Keep showing the current completion while the user types through it.
+        if self.suggestion_text(&buffer, cursor_position, cx).is_some() {
+            return;
+        }
+
+        let snapshot = buffer.read(cx).snapshot();
+        let cursor_offset = cursor_position.to_offset(&snapshot);
+        let position = snapshot.anchor_before(cursor_offset);
+        let buffer_id = buffer.entity_id();
+        let request = self.request_predictions(&snapshot, cursor_offset, 1, 0.0, cx);
+
+        self.pending_refresh = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(DEBOUNCE_TIMEOUT).await;
+            }
+
+            let candidates = request.await;
+            this.update(cx, |this, cx| {
+                this.pending_refresh = None;
+                this.completion = match candidates {
+                    Ok(candidates) => candidates
+                        .into_iter()
+                        .find(|text| !text.trim().is_empty())
+                        .map(|text| CurrentCompletion {
+                            buffer_id,
+                            position,
+                            candidates: vec![text],
+                            active_candidate: 0,
+                            cycled: false,
+                        }),
+                    Err(error) => {
+                        log::error!("failed to fetch custom edit prediction: {error:#}");
+                        None
+                    }
+                };
+                cx.notify();
+            })
+        }));
+    }
+
+    fn cycle(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        direction: Direction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(completion) = self.completion.as_mut() else {
+            return;
+        };
+        if completion.buffer_id != buffer.entity_id() {
+            return;
+        }
+
+        if completion.cycled {
+            let len = completion.candidates.len();
+            completion.active_candidate = match direction {
+                Direction::Prev => (completion.active_candidate + len - 1) % len,
+                Direction::Next => (completion.active_candidate + 1) % len,
+            };
+            cx.notify();
+            return;
+        }
+
+        // Alternatives are requested for the position of the current completion, so that
+        // they all apply to the same text.
+        let snapshot = buffer.read(cx).snapshot();
+        let position = completion.position;
+        let request = self.request_predictions(
+            &snapshot,
+            position.to_offset(&snapshot),
+            CYCLING_CANDIDATES,
+            CYCLING_TEMPERATURE,
+            cx,
+        );
+
+        self.pending_refresh = Some(cx.spawn(async move |this, cx| {
+            let alternatives = request.await;
+            this.update(cx, |this, cx| {
+                this.pending_refresh = None;
+                let Some(completion) = this
+                    .completion
+                    .as_mut()
+                    .filter(|completion| completion.position == position)
+                else {
+                    return;
+                };
+                completion.cycled = true;
+                match alternatives {
+                    Ok(alternatives) => {
+                        for text in alternatives {
+                            if !text.trim().is_empty() && !completion.candidates.contains(&text) {
+                                completion.candidates.push(text);
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        log::error!(
+                            "failed to fetch alternative custom edit predictions: {error:#}"
+                        )
+                    }
+                }
+                this.cycle(buffer, cursor_position, direction, cx);
+            })
+        }));
+    }
+
+    fn accept(&mut self, _cx: &mut Context<Self>) {
+        self.pending_refresh = None;
+        self.completion = None;
+    }
+
+    fn discard(&mut self, _cx: &mut Context<Self>) {
+        self.pending_refresh = None;
+        self.completion = None;
+    }
+
+    fn suggest(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cursor_position: Anchor,
+        cx: &mut Context<Self>,
+    ) -> Option<EditPrediction> {
+        let text = self.suggestion_text(buffer, cursor_position, cx)?;
+        let snapshot = buffer.read(cx).snapshot();
+        let cursor = snapshot.anchor_after(cursor_position.to_offset(&snapshot));
+        Some(EditPrediction::Local {
+            id: None,
+            edits: vec![(cursor..cursor, text)],
+            edit_preview: None,
+        })
+    }
+}
+
+impl CustomEditPredictionProvider {
+    /// Requests `n` predictions at the given offset from the configured extension provider,
+    /// or from the configured endpoint if there's none. Nothing is sent until the returned
+    /// future is polled.
+    fn request_predictions(
+        &self,
+        snapshot: &BufferSnapshot,
+        cursor_offset: usize,
+        n: u32,
+        temperature: f32,
+        cx: &App,
+    ) -> BoxFuture<'static, Result<Vec<String>>> {
+        let settings = all_language_settings(None, cx)
+            .edit_predictions
+            .custom
+            .clone();
+        let (prompt, suffix) = fim_context(snapshot, cursor_offset);
+
+        if let Some(provider_id) = settings.extension_provider {
+            let extension = cx
+                .try_global::<ExtensionEditPredictionProviders>()
+                .and_then(|providers| providers.0.get(provider_id.as_str()).cloned());
+            let request = EditPredictionRequest {
+                path: snapshot
+                    .file()
+                    .map(|file| file.path().as_unix_str().to_string()),
+                language_name: snapshot
+                    .language()
+                    .map(|language| language.name().to_string()),
+                prefix: prompt,
+                suffix,
+                candidates: n,
+            };
+            return async move {
+                let extension = extension.with_context(|| {
+                    format!("no extension provides the edit prediction provider `{provider_id}`")
+                })?;
+                extension.predict_edit(provider_id.into(), request).await
+            }
+            .boxed();
+        }
+
+        let http_client = self.http_client.clone();
+        async move {
+            request_completions(
+                http_client.as_ref(),
+                &settings,
+                prompt,
+                suffix,
+                n,
+                temperature,
+            )
+            .await
+        }
+        .boxed()
+    }
+
+    /// The part of the current completion that hasn't been typed yet, if the
+    /// cursor is still within the text the completion predicted.
+    fn suggestion_text(
+        &self,
+        buffer: &Entity<Buffer>,
+        cursor_position: Anchor,
+        cx: &App,
+    ) -> Option<String> {
+        let completion = self.completion.as_ref()?;
+        if completion.buffer_id != buffer.entity_id() {
+            return None;
+        }
+
+        let snapshot = buffer.read(cx).snapshot();
+        let start = completion.position.to_offset(&snapshot);
+        let end = cursor_position.to_offset(&snapshot);
+        if start > end {
+            return None;
+        }
+        let typed = snapshot.text_for_range(start..end).collect::<String>();
+        let text = completion.candidates.get(completion.active_candidate)?;
+        remaining_completion(text, &typed).map(str::to_string)
+    }
+}
+
+fn remaining_completion<'a>(completion: &'a str, typed: &str) -> Option<&'a str> {
+    let remaining = completion.strip_prefix(typed)?.trim_end();
+    if remaining.trim().is_empty() {
+        None
+    } else {
+        Some(remaining)
+    }
+}
+
+/// Returns the text before and after the cursor, clipped to character boundaries.
+fn fim_context(snapshot: &BufferSnapshot, cursor_offset: usize) -> (String, String) {
+    let prefix_start =
+        snapshot.clip_offset(cursor_offset.saturating_sub(MAX_PREFIX_LEN), Bias::Right);
+    let suffix_end = snapshot.clip_offset(
+        (cursor_offset + MAX_SUFFIX_LEN).min(snapshot.len()),
+        Bias::Left,
+    );
+    let range = |range: Range<usize>| snapshot.text_for_range(range).collect::<String>();
+    (
+        range(prefix_start..cursor_offset),
+        range(cursor_offset..suffix_end),
+    )
+}
+
+async fn request_completions(
+    http_client: &dyn HttpClient,
+    settings: &CustomEditPredictionSettings,
+    prompt: String,
+    suffix: String,
+    n: u32,
+    temperature: f32,
+) -> Result<Vec<String>> {
+    let body = CompletionRequest {
+        model: settings.model.as_deref(),
+        prompt,
+        suffix,
+        max_tokens: settings.max_tokens,
+        temperature,
+        n,
+        stream: false,
+    };
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(format!(
+            "{}/completions",
+            settings.api_url.trim_end_matches('/')
+        ))
+        .header("Content-Type", "application/json");
+    if let Some(api_key) = settings
+        .api_key_env_var
+        .as_ref()
+        .and_then(|name| std::env::var(name).ok())
+        .filter(|key| !key.is_empty())
+    {
+        request = request.header("Authorization", format!("Bearer {api_key}"));
+    }
+    let request = request.body(AsyncBody::from(serde_json::to_string(&body)?))?;
+
+    let mut response = http_client.send(request).await?;
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "request to {} failed with status {}: {}",
+        settings.api_url,
+        response.status(),
+        body
+    );
+
+    let response: CompletionResponse =
+        serde_json::from_str(&body).context("failed to parse completion response")?;
+    Ok(response
+        .choices
+        .into_iter()
+        .map(|choice| choice.text)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::AsyncReadExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use http_client::{FakeHttpClient, Response};
+    use settings::SettingsStore;
+
+    #[test]
+    fn test_remaining_completion() {
+        assert_eq!(remaining_completion("foo(bar);\n", ""), Some("foo(bar);"));
+        assert_eq!(remaining_completion("foo(bar);", "foo("), Some("bar);"));
+        assert_eq!(remaining_completion("foo(bar);", "fob"), None);
+        assert_eq!(remaining_completion("foo(bar);", "foo(bar);"), None);
+    }
+
+    #[gpui::test]
+    fn test_fim_context(cx: &mut TestAppContext) {
+        let buffer = cx.new(|cx| Buffer::local("fn main() {\n    \n}\n", cx));
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let (prefix, suffix) = fim_context(&snapshot, "fn main() {\n    ".len());
+        assert_eq!(prefix, "fn main() {\n    ");
+        assert_eq!(suffix, "\n}\n");
+    }
+
+    #[gpui::test]
+    async fn test_cycle(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            language::init(cx);
+        });
+        let http_client = FakeHttpClient::create(|request| async move {
+            let mut body = String::new();
+            request.into_body().read_to_string(&mut body).await?;
+            let request: serde_json::Value = serde_json::from_str(&body)?;
+            let choices = if request["n"] == 1 {
+                vec!["foo();"]
+            } else {
+                vec!["bar();", "foo();", "baz();"]
+            };
+            let response = serde_json::json!({
+                "choices": choices
+                    .into_iter()
+                    .map(|text| serde_json::json!({ "text": text }))
+                    .collect::<Vec<_>>(),
+            });
+            Ok(Response::builder()
+                .status(200)
+                .body(response.to_string().into())
+                .unwrap())
+        });
+
+        let buffer = cx.new(|cx| Buffer::local("fn main() {\n    \n}\n", cx));
+        let cursor = buffer.read_with(cx, |buffer, _| {
+            buffer.anchor_before("fn main() {\n    ".len())
+        });
+        let provider = cx.new(|_| CustomEditPredictionProvider::new(http_client));
+        let suggestion = |cx: &mut TestAppContext| {
+            provider.update(cx, |provider, cx| {
+                match provider.suggest(&buffer, cursor, cx)? {
+                    EditPrediction::Local { edits, .. } => Some(edits[0].1.clone()),
+                    EditPrediction::Jump { .. } => None,
+                }
+            })
+        };
+
+        provider.update(cx, |provider, cx| {
+            provider.refresh(buffer.clone(), cursor, false, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(suggestion(cx).as_deref(), Some("foo();"));
+
+        // Alternatives are requested when cycling for the first time, without duplicates.
+        provider.update(cx, |provider, cx| {
+            provider.cycle(buffer.clone(), cursor, Direction::Next, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(suggestion(cx).as_deref(), Some("bar();"));
+
+        provider.update(cx, |provider, cx| {
+            provider.cycle(buffer.clone(), cursor, Direction::Next, cx)
+        });
+        assert_eq!(suggestion(cx).as_deref(), Some("baz();"));
+
+        provider.update(cx, |provider, cx| {
+            provider.cycle(buffer.clone(), cursor, Direction::Next, cx)
+        });
+        assert_eq!(suggestion(cx).as_deref(), Some("foo();"));
+
+        provider.update(cx, |provider, cx| {
+            provider.cycle(buffer.clone(), cursor, Direction::Prev, cx)
+        });
+        assert_eq!(suggestion(cx).as_deref(), Some("baz();"));
+    }
+}
@@ -163,6 +163,7 @@ impl DebugTaskDefinition {
             build: None,
             tcp_connection: self.tcp_connection.clone(),
             config: self.config.clone(),
+            start_with: Vec::new(),
         }
     }
 
@@ -445,6 +446,7 @@ impl DebugAdapter for FakeAdapter {
             build: None,
             config,
             tcp_connection: None,
+            start_with: Vec::new(),
         })
     }
 
@@ -31,6 +31,10 @@ pub struct DebuggerSettings {
     ///
     /// Default: Bottom
     pub dock: settings::DockPosition,
+    /// Where to show the values of variables while the debugged program is stopped.
+    ///
+    /// Default: inline
+    pub inline_values_position: settings::InlineValuesPosition,
 }
 
 impl Settings for DebuggerSettings {
@@ -46,6 +50,7 @@ impl Settings for DebuggerSettings {
             log_dap_communications: content.log_dap_communications.unwrap(),
             format_dap_log_messages: content.format_dap_log_messages.unwrap(),
             dock: content.dock.unwrap(),
+            inline_values_position: content.inline_values_position.unwrap(),
         }
     }
 }
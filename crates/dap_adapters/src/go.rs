@@ -400,6 +400,7 @@ impl DebugAdapter for GoDebugAdapter {
             build: None,
             config: args,
             tcp_connection: None,
+            start_with: Vec::new(),
         })
     }
 
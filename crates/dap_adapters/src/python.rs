@@ -368,6 +368,7 @@ impl DebugAdapter for PythonDebugAdapter {
             config: args,
             build: None,
             tcp_connection: None,
+            start_with: Vec::new(),
         })
     }
 
@@ -5,9 +5,10 @@ use crate::session::running::breakpoint_list::BreakpointList;
 
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    FocusConsole, FocusDisassembly, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal,
+    FocusThreads, FocusVariables, NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto,
+    StepOut, StepOver, Stop, ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker,
+    persistence, spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
@@ -172,6 +173,8 @@ impl DebugPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.start_companion_sessions(&scenario, &task_context, worktree_id, window, cx);
+
         let dap_store = self.project.read(cx).dap_store();
         let Some(adapter) = DapRegistry::global(cx).adapter(&scenario.adapter) else {
             return;
@@ -293,6 +296,57 @@ impl DebugPanel {
         });
     }
 
+    /// Starts the scenarios `scenario` should be started with, each in its own session.
+    fn start_companion_sessions(
+        &mut self,
+        scenario: &DebugScenario,
+        task_context: &TaskContext,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if scenario.start_with.is_empty() {
+            return;
+        }
+        let Some(inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return;
+        };
+
+        let configured_scenarios = inventory.read(cx).configured_debug_scenarios(worktree_id);
+        for label in &scenario.start_with {
+            let Some((_, companion)) = configured_scenarios
+                .iter()
+                .find(|(_, companion)| &companion.label == label)
+            else {
+                log::warn!(
+                    "No debug scenario labeled `{label}` to start with `{}`",
+                    scenario.label
+                );
+                continue;
+            };
+            // Companions don't start their own companions, so scenarios starting each other don't loop.
+            let companion = DebugScenario {
+                start_with: Vec::new(),
+                ..companion.clone()
+            };
+            self.start_session(
+                companion,
+                task_context.clone(),
+                None,
+                worktree_id,
+                window,
+                cx,
+            );
+        }
+    }
+
     pub(crate) fn rerun_last_session(
         &mut self,
         workspace: &mut Workspace,
@@ -334,6 +388,33 @@ impl DebugPanel {
         );
     }
 
+    /// Starts a scenario configured in `debug.json`, resolving its variables in the current task context.
+    pub(crate) fn start_configured_scenario(
+        &mut self,
+        scenario: DebugScenario,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let task_contexts = workspace
+                .update_in(cx, |workspace, window, cx| {
+                    tasks_ui::task_contexts(workspace, window, cx)
+                })?
+                .await;
+            let task_context = worktree_id
+                .and_then(|worktree_id| task_contexts.task_context_for_worktree_id(worktree_id))
+                .or_else(|| task_contexts.active_context())
+                .cloned()
+                .unwrap_or_default();
+            this.update_in(cx, |this, window, cx| {
+                this.start_session(scenario, task_context, None, worktree_id, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub(crate) async fn register_session(
         this: WeakEntity<Self>,
         session: Entity<Session>,
@@ -626,6 +707,13 @@ impl DebugPanel {
                 })
         };
 
+        let configuration_menu = self.render_configuration_menu(window, cx);
+        let (side_configuration_menu, configuration_menu) = if is_side {
+            (configuration_menu, None)
+        } else {
+            (None, configuration_menu)
+        };
+
         let edit_debug_json_button = || {
             IconButton::new("debug-edit-debug-json", IconName::Code)
                 .icon_size(IconSize::Small)
@@ -909,6 +997,7 @@ impl DebugPanel {
                         )
                         .when(is_side, |this| {
                             this.child(new_session_button())
+                                .children(side_configuration_menu)
                                 .child(edit_debug_json_button())
                                 .child(documentation_button())
                                 .child(logs_button())
@@ -962,6 +1051,7 @@ impl DebugPanel {
                                 ))
                                 .when(!is_side, |this| {
                                     this.child(new_session_button())
+                                        .children(configuration_menu)
                                         .child(edit_debug_json_button())
                                         .child(documentation_button())
                                         .child(logs_button())
@@ -1615,6 +1705,24 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusThreads, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Threads, window, cx);
+                    })
+                    .ok();
+                }
+            })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &ToggleThreadPicker, window, cx| {
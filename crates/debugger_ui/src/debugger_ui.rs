@@ -74,6 +74,10 @@ actions!(
         FocusLoadedSources,
         /// Focuses on the terminal panel.
         FocusTerminal,
+        /// Focuses on the threads panel.
+        FocusThreads,
+        /// Focuses on the disassembly panel.
+        FocusDisassembly,
         /// Shows the stack trace for the current thread.
         ShowStackTrace,
         /// Toggles the thread picker dropdown.
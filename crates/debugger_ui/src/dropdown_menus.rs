@@ -1,8 +1,11 @@
 use std::rc::Rc;
 
 use collections::HashMap;
-use gpui::{Entity, WeakEntity};
-use project::debugger::session::{ThreadId, ThreadStatus};
+use gpui::{Action as _, Entity, WeakEntity};
+use project::{
+    TaskSourceKind,
+    debugger::session::{ThreadId, ThreadStatus},
+};
 use ui::{CommonAnimationExt, ContextMenu, DropdownMenu, DropdownStyle, Indicator, prelude::*};
 use util::{maybe, truncate_and_trailoff};
 
@@ -270,6 +273,53 @@ impl DebugPanel {
             .into_any_element()
     }
 
+    /// Lists the scenarios configured in `debug.json`, starting the clicked one.
+    pub(crate) fn render_configuration_menu(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<DropdownMenu> {
+        const MAX_LABEL_CHARS: usize = 150;
+
+        let project = self.project.read(cx);
+        let worktree_ids = project
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).id())
+            .collect::<Vec<_>>();
+        let inventory = project.task_store().read(cx).task_inventory()?;
+        let scenarios = inventory.read(cx).configured_debug_scenarios(worktree_ids);
+
+        let weak = cx.weak_entity();
+        let menu = ContextMenu::build(window, cx, move |mut this, _, _| {
+            if scenarios.is_empty() {
+                this = this.label("No Configurations");
+            }
+            for (kind, scenario) in scenarios {
+                let worktree_id = match kind {
+                    TaskSourceKind::Worktree { id, .. } => Some(id),
+                    _ => None,
+                };
+                let label = truncate_and_trailoff(&scenario.label, MAX_LABEL_CHARS);
+                let weak = weak.clone();
+                this = this.entry(label, None, move |window, cx| {
+                    weak.update(cx, |panel, cx| {
+                        panel.start_configured_scenario(scenario.clone(), worktree_id, window, cx);
+                    })
+                    .ok();
+                });
+            }
+            this.separator().action(
+                "Edit debug.json",
+                zed_actions::OpenProjectDebugTasks.boxed_clone(),
+            )
+        });
+
+        Some(
+            DropdownMenu::new("debugger-configuration-list", "Configurations", menu)
+                .style(DropdownStyle::Ghost),
+        )
+    }
+
     pub(crate) fn render_thread_dropdown(
         &self,
         running_state: &Entity<RunningState>,
@@ -1,9 +1,11 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
 pub(crate) mod memory_view;
 pub(crate) mod module_list;
 pub mod stack_frame_list;
+pub(crate) mod thread_list;
 pub mod variable_list;
 use std::{any::Any, ops::ControlFlow, path::PathBuf, sync::Arc, time::Duration};
 
@@ -24,6 +26,7 @@ use dap::{
     client::SessionId,
     debugger_settings::DebuggerSettings,
 };
+use disassembly_view::DisassemblyView;
 use futures::{SinkExt, channel::mpsc};
 use gpui::{
     Action as _, AnyView, AppContext, Axis, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
@@ -45,6 +48,7 @@ use task::{
     ZedDebugConfig, substitute_variables_in_str,
 };
 use terminal_view::TerminalView;
+use thread_list::ThreadList;
 use ui::{
     FluentBuilder, IntoElement, Render, StatefulInteractiveElement, Tab, Tooltip, VisibleOnHover,
     VisualContext, prelude::*,
@@ -69,6 +73,8 @@ pub struct RunningState {
     loaded_sources_list: Entity<LoadedSourceList>,
     pub debug_terminal: Entity<DebugTerminal>,
     module_list: Entity<module_list::ModuleList>,
+    thread_list: Entity<ThreadList>,
+    disassembly_view: Entity<DisassemblyView>,
     console: Entity<Console>,
     breakpoint_list: Entity<BreakpointList>,
     panes: PaneGroup,
@@ -730,6 +736,18 @@ impl RunningState {
 
         let module_list = cx.new(|cx| ModuleList::new(session.clone(), workspace.clone(), cx));
 
+        let thread_list = cx.new(|cx| ThreadList::new(session.clone(), weak_state.clone(), cx));
+
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(
+                session.clone(),
+                stack_frame_list.clone(),
+                weak_state.clone(),
+                workspace.clone(),
+                cx,
+            )
+        });
+
         let loaded_source_list = cx.new(|cx| LoadedSourceList::new(session.clone(), cx));
 
         let console = cx.new(|cx| {
@@ -801,6 +819,9 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
@@ -839,6 +860,8 @@ impl RunningState {
                 &loaded_source_list,
                 &debug_terminal,
                 &memory_view,
+                &thread_list,
+                &disassembly_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -880,6 +903,8 @@ impl RunningState {
             panes,
             active_pane,
             module_list,
+            thread_list,
+            disassembly_view,
             console,
             breakpoint_list,
             loaded_sources_list: loaded_source_list,
@@ -950,6 +975,7 @@ impl RunningState {
                 build,
                 mut config,
                 tcp_connection,
+                start_with: _,
             } = scenario;
             Self::relativize_paths(None, &mut config, &task_context);
             Self::substitute_variables_in_config(&mut config, &task_context);
@@ -1199,6 +1225,8 @@ impl RunningState {
             command_label: title,
             cwd,
             env: envs,
+            env_files: Vec::new(),
+            secrets: HashMap::default(),
             use_new_terminal: true,
             allow_concurrent_runs: true,
             reveal: task::RevealStrategy::NoFocus,
@@ -1208,6 +1236,9 @@ impl RunningState {
             show_summary: false,
             show_command: false,
             show_rerun: false,
+            problem_matchers: Vec::new(),
+            notify: task::NotifyStrategy::Never,
+            watch: None,
         };
 
         let workspace = self.workspace.clone();
@@ -1288,6 +1319,18 @@ impl RunningState {
                 item_kind,
                 cx,
             )),
+            DebuggerPaneItem::Threads => Box::new(SubView::new(
+                self.thread_list.focus_handle(cx),
+                self.thread_list.clone().into(),
+                item_kind,
+                cx,
+            )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                cx,
+            )),
         }
     }
 
@@ -1472,6 +1515,16 @@ impl RunningState {
         &self.module_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn thread_list(&self) -> &Entity<ThreadList> {
+        &self.thread_list
+    }
+
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
     pub(crate) fn activate_item(
         &mut self,
         item: DebuggerPaneItem,
@@ -6,7 +6,10 @@ use alacritty_terminal::vte::ansi;
 use anyhow::Result;
 use collections::HashMap;
 use dap::{CompletionItem, CompletionItemType, OutputEvent};
-use editor::{Bias, CompletionProvider, Editor, EditorElement, EditorStyle, ExcerptId};
+use editor::{
+    Bias, CompletionProvider, Editor, EditorElement, EditorStyle, ExcerptId,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, RenderBlock},
+};
 use fuzzy::StringMatchCandidate;
 use gpui::{
     Action as _, AppContext, Context, Corner, Entity, FocusHandle, Focusable, HighlightStyle, Hsla,
@@ -22,7 +25,7 @@ use project::{
 };
 use settings::Settings;
 use std::fmt::Write;
-use std::{cell::RefCell, ops::Range, rc::Rc, usize};
+use std::{cell::RefCell, ops::Range, rc::Rc, sync::Arc, usize};
 use theme::{Theme, ThemeSettings};
 use ui::{ContextMenu, Divider, PopoverMenu, SplitButton, Tooltip, prelude::*};
 use util::ResultExt;
@@ -148,20 +151,31 @@ impl Console {
     fn add_messages(
         &mut self,
         events: Vec<OutputEvent>,
+        evaluated_expressions: Vec<Option<SharedString>>,
         window: &mut Window,
-        cx: &mut App,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
+        let this = cx.weak_entity();
         self.console.update(cx, |_, cx| {
             cx.spawn_in(window, async move |console, cx| {
                 let mut len = console.update(cx, |this, cx| this.buffer().read(cx).len(cx))?;
-                let (output, spans, background_spans) = cx
+                let (output, spans, background_spans, inspectable_results) = cx
                     .background_spawn(async move {
                         let mut all_spans = Vec::new();
                         let mut all_background_spans = Vec::new();
+                        let mut inspectable_results = Vec::new();
                         let mut to_insert = String::new();
                         let mut scratch = String::new();
 
-                        for event in &events {
+                        for (event, evaluated_expression) in
+                            events.iter().zip(evaluated_expressions)
+                        {
+                            // Evaluation results that are structured objects can be inspected
+                            // in the variables tree, by watching the evaluated expression.
+                            if let Some(expression) = evaluated_expression {
+                                inspectable_results.push((len, expression));
+                            }
+
                             scratch.clear();
                             let mut ansi_handler = ConsoleHandler::default();
                             let mut ansi_processor =
@@ -203,7 +217,12 @@ impl Console {
                             all_spans.extend(spans);
                             all_background_spans.extend(background_spans);
                         }
-                        (to_insert, all_spans, all_background_spans)
+                        (
+                            to_insert,
+                            all_spans,
+                            all_background_spans,
+                            inspectable_results,
+                        )
                     })
                     .await;
                 console.update_in(cx, |console, window, cx| {
@@ -249,6 +268,20 @@ impl Console {
                         );
                     }
 
+                    let blocks = inspectable_results
+                        .into_iter()
+                        .map(|(offset, expression)| BlockProperties {
+                            placement: BlockPlacement::Below(buffer.anchor_after(offset)),
+                            height: Some(1),
+                            style: BlockStyle::Sticky,
+                            render: render_inspect_button(this.clone(), offset, expression),
+                            priority: 0,
+                        })
+                        .collect::<Vec<_>>();
+                    if !blocks.is_empty() {
+                        console.insert_blocks(blocks, None, cx);
+                    }
+
                     cx.notify();
                 })?;
 
@@ -292,6 +325,25 @@ impl Console {
         });
     }
 
+    /// Watches the evaluated `expression`, expanding its fields in the variables tree.
+    fn inspect_result(
+        &mut self,
+        expression: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(stack_frame_id) = self.stack_frame_list.read(cx).opened_stack_frame_id() else {
+            return;
+        };
+        self.variable_list.update(cx, |variable_list, cx| {
+            variable_list.expand_watcher(expression.clone(), cx);
+        });
+        self.session.update(cx, |session, cx| {
+            session.add_watcher(expression, stack_frame_id, cx).detach();
+        });
+        window.dispatch_action(crate::FocusVariables.boxed_clone(), cx);
+    }
+
     fn previous_query(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
         let prev = self.history.previous(&mut self.cursor);
         if let Some(prev) = prev {
@@ -418,6 +470,11 @@ impl Console {
             let Some((last_processed_token, task)) = session
                 .update_in(cx, |session, window, cx| {
                     let (output, last_processed_token) = session.output(token);
+                    let output = output.cloned().collect::<Vec<_>>();
+                    let first_token = last_processed_token.0 + 1 - output.len();
+                    let evaluated_expressions = (first_token..=last_processed_token.0)
+                        .map(|token| session.evaluated_expression(OutputToken(token)))
+                        .collect();
 
                     this.update(cx, |this, cx| {
                         if last_processed_token == this.last_token {
@@ -425,7 +482,7 @@ impl Console {
                         }
                         Some((
                             last_processed_token,
-                            this.add_messages(output.cloned().collect(), window, cx),
+                            this.add_messages(output, evaluated_expressions, window, cx),
                         ))
                     })
                     .ok()
@@ -512,6 +569,35 @@ impl Focusable for Console {
     }
 }
 
+fn render_inspect_button(
+    console: WeakEntity<Console>,
+    offset: usize,
+    expression: SharedString,
+) -> RenderBlock {
+    Arc::new(move |cx: &mut BlockContext| {
+        let console = console.clone();
+        let expression = expression.clone();
+        h_flex()
+            .pl(cx.anchor_x)
+            .child(
+                Button::new(("inspect-result", offset), "Show in Variables")
+                    .icon(IconName::ListTree)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::XSmall)
+                    .icon_color(Color::Muted)
+                    .label_size(LabelSize::Small)
+                    .on_click(move |_, window, cx| {
+                        console
+                            .update(cx, |console, cx| {
+                                console.inspect_result(expression.clone(), window, cx)
+                            })
+                            .ok();
+                    }),
+            )
+            .into_any_element()
+    })
+}
+
 struct ConsoleQueryBarCompletionProvider(WeakEntity<Console>);
 
 impl CompletionProvider for ConsoleQueryBarCompletionProvider {
@@ -0,0 +1,328 @@
+use std::{ops::Range, path::PathBuf, sync::Arc};
+
+use dap::{DisassembledInstruction, SteppingGranularity};
+use editor::Editor;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use language::Point;
+use project::debugger::session::{Session, ThreadStatus};
+use ui::{Tooltip, WithScrollbar, prelude::*};
+use workspace::{OpenOptions, OpenVisible, Workspace};
+
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+
+/// How many instructions before the one execution stopped at are disassembled.
+const INSTRUCTIONS_BEFORE: i64 = 32;
+const INSTRUCTION_COUNT: u64 = 96;
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    running_state: WeakEntity<RunningState>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    instructions: Arc<[DisassembledInstruction]>,
+    /// The instruction execution stopped at in the selected stack frame.
+    current_ix: Option<usize>,
+    _refresh_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        running_state: WeakEntity<RunningState>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_)
+                | StackFrameListEvent::BuiltEntries => this.refresh(cx),
+            }),
+            cx.observe(&session, |_, _, cx| cx.notify()),
+        ];
+
+        Self {
+            session,
+            stack_frame_list,
+            running_state,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            instructions: Arc::default(),
+            current_ix: None,
+            _refresh_task: Task::ready(()),
+            _subscriptions,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn instructions(&self) -> &[DisassembledInstruction] {
+        &self.instructions
+    }
+
+    #[cfg(test)]
+    pub(crate) fn current_instruction(&self) -> Option<&DisassembledInstruction> {
+        self.instructions.get(self.current_ix?)
+    }
+
+    fn selected_stack_frame(&self, cx: &mut Context<Self>) -> Option<dap::StackFrame> {
+        let stack_frame_id = self.stack_frame_list.read(cx).opened_stack_frame_id()?;
+        let thread_id = self
+            .running_state
+            .read_with(cx, |running_state, _| running_state.selected_thread_id())
+            .ok()??;
+        self.session
+            .update(cx, |session, cx| session.stack_frames(thread_id, cx))
+            .ok()?
+            .into_iter()
+            .find(|stack_frame| stack_frame.dap.id == stack_frame_id)
+            .map(|stack_frame| stack_frame.dap)
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let Some(instruction_pointer) = self
+            .selected_stack_frame(cx)
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference)
+        else {
+            self.instructions = Arc::default();
+            self.current_ix = None;
+            cx.notify();
+            return;
+        };
+
+        let instructions = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                instruction_pointer.clone(),
+                -INSTRUCTIONS_BEFORE,
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        });
+        self._refresh_task = cx.spawn(async move |this, cx| {
+            let Some(instructions) = instructions.await else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.current_ix = instructions.iter().position(|instruction| {
+                    same_address(&instruction.address, &instruction_pointer)
+                });
+                this.instructions = instructions;
+                if let Some(ix) = this.current_ix {
+                    this.scroll_handle
+                        .scroll_to_item(ix, ScrollStrategy::Center);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn step(&mut self, step_in: bool, cx: &mut Context<Self>) {
+        let Some(thread_id) = self
+            .running_state
+            .read_with(cx, |running_state, _| running_state.selected_thread_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        self.session.update(cx, |session, cx| {
+            if step_in {
+                session.step_in(thread_id, SteppingGranularity::Instruction, cx);
+            } else {
+                session.step_over(thread_id, SteppingGranularity::Instruction, cx);
+            }
+        });
+    }
+
+    fn go_to_source(&self, path: PathBuf, line: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(open) = self
+            .workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path(
+                    path,
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..OpenOptions::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .ok()
+        else {
+            return;
+        };
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open.await?;
+            if let Some(editor) = item.downcast::<Editor>() {
+                editor.update_in(cx, |editor, window, cx| {
+                    let point = Point::new(line.saturating_sub(1) as u32, 0);
+                    editor.go_to_singleton_buffer_point(point, window, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_entry(&mut self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let instruction = &self.instructions[ix];
+        let is_current = Some(ix) == self.current_ix;
+        let source = instruction
+            .location
+            .as_ref()
+            .and_then(|source| source.path.clone())
+            .zip(instruction.line);
+        let source_label = source.as_ref().map(|(path, line)| {
+            let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+            format!("{file_name}:{line}")
+        });
+
+        h_flex()
+            .id(("disassembly", ix))
+            .w_full()
+            .gap_2()
+            .px_1()
+            .rounded_sm()
+            .font_buffer(cx)
+            .when(is_current, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .child(div().w_4().when(is_current, |this| {
+                this.child(
+                    Icon::new(IconName::ArrowRight)
+                        .size(IconSize::Small)
+                        .color(Color::Accent),
+                )
+            }))
+            .child(
+                Label::new(instruction.address.clone())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .children(
+                instruction
+                    .instruction_bytes
+                    .clone()
+                    .map(|bytes| Label::new(bytes).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(Label::new(instruction.instruction.clone()).size(LabelSize::Small))
+            .children(
+                instruction
+                    .symbol
+                    .clone()
+                    .map(|symbol| Label::new(format!("<{symbol}>")).size(LabelSize::Small)),
+            )
+            .child(div().flex_1())
+            .when_some(source.zip(source_label), |this, ((path, line), label)| {
+                this.child(
+                    Label::new(label)
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .cursor_pointer()
+                .hover(|s| s.bg(cx.theme().colors().element_hover))
+                .tooltip(Tooltip::text("Go to Source"))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.go_to_source(PathBuf::from(&path), line, window, cx);
+                }))
+            })
+            .into_any()
+    }
+
+    fn render_controls(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self
+            .running_state
+            .read_with(cx, |running_state, cx| running_state.thread_status(cx))
+            .ok()
+            .flatten()
+            == Some(ThreadStatus::Stopped);
+
+        h_flex()
+            .gap_1()
+            .p_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Over Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(false, cx))),
+            )
+            .child(
+                IconButton::new("disassembly-step-into", IconName::DebugStepInto)
+                    .icon_size(IconSize::Small)
+                    .disabled(!is_stopped)
+                    .tooltip(Tooltip::text("Step Into Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(true, cx))),
+            )
+    }
+
+    fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly",
+            self.instructions.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(self.scroll_handle.clone())
+        .size_full()
+    }
+}
+
+/// Compares two instruction addresses, which adapters may format differently, e.g. with or without leading zeros.
+fn same_address(a: &str, b: &str) -> bool {
+    fn parse(address: &str) -> Option<u64> {
+        let address = address.trim();
+        let address = address
+            .strip_prefix("0x")
+            .or_else(|| address.strip_prefix("0X"))
+            .unwrap_or(address);
+        u64::from_str_radix(address, 16).ok()
+    }
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_controls(cx))
+            .child(
+                div()
+                    .size_full()
+                    .p_1()
+                    .when(self.instructions.is_empty(), |this| {
+                        this.child(
+                            Label::new("No instructions to show for the selected stack frame")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(self.render_list(window, cx))
+                    .vertical_scrollbar_for(self.scroll_handle.clone(), window, cx),
+            )
+    }
+}
@@ -0,0 +1,268 @@
+use std::ops::Range;
+
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent, ThreadId, ThreadStatus};
+use ui::{Indicator, Tooltip, WithScrollbar, prelude::*};
+
+use super::RunningState;
+
+pub struct ThreadList {
+    scroll_handle: UniformListScrollHandle,
+    selected_ix: Option<usize>,
+    session: Entity<Session>,
+    running_state: WeakEntity<RunningState>,
+    focus_handle: FocusHandle,
+    entries: Vec<dap::Thread>,
+    _rebuild_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ThreadList {
+    pub fn new(
+        session: Entity<Session>,
+        running_state: WeakEntity<RunningState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let _subscriptions = vec![
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) | SessionEvent::Threads => {
+                    if this._rebuild_task.is_some() {
+                        this.schedule_rebuild(cx);
+                    }
+                }
+                _ => {}
+            }),
+            // Thread statuses change on continuing and stepping, without a session event.
+            cx.observe(&session, |_, _, cx| cx.notify()),
+        ];
+
+        Self {
+            scroll_handle: UniformListScrollHandle::new(),
+            selected_ix: None,
+            session,
+            running_state,
+            focus_handle,
+            entries: Vec::new(),
+            _rebuild_task: None,
+            _subscriptions,
+        }
+    }
+
+    fn schedule_rebuild(&mut self, cx: &mut Context<Self>) {
+        self._rebuild_task = Some(cx.spawn(async move |this, cx| {
+            this.update(cx, |this, cx| {
+                let threads = this.session.update(cx, |session, cx| session.threads(cx));
+                this.entries = threads.into_iter().map(|(thread, _)| thread).collect();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    #[cfg(test)]
+    pub(crate) fn threads(&self) -> &[dap::Thread] {
+        &self.entries
+    }
+
+    pub(crate) fn select_thread(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(thread) = self.entries.get(ix) else {
+            return;
+        };
+        let thread_id = ThreadId(thread.id);
+        self.selected_ix = Some(ix);
+        self.running_state
+            .update(cx, |running_state, cx| {
+                running_state.select_thread(thread_id, window, cx);
+                cx.notify();
+            })
+            .ok();
+        cx.notify();
+    }
+
+    fn toggle_thread_paused(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        self.session.update(cx, |session, cx| {
+            if session.thread_status(thread_id) == ThreadStatus::Stopped {
+                session.continue_thread(thread_id, cx);
+            } else {
+                session.pause_thread(thread_id, cx);
+            }
+        });
+    }
+
+    fn render_entry(&mut self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let thread = &self.entries[ix];
+        let thread_id = ThreadId(thread.id);
+        let name = if thread.name.is_empty() {
+            format!("Tid: {}", thread.id)
+        } else {
+            thread.name.clone()
+        };
+        let session = self.session.read(cx);
+        let status = session.thread_status(thread_id);
+        let session_terminated = session.is_terminated();
+        let is_current = self
+            .running_state
+            .read_with(cx, |running_state, _| {
+                running_state.selected_thread_id() == Some(thread_id)
+            })
+            .unwrap_or_default();
+
+        let indicator = match status {
+            ThreadStatus::Stopped => Indicator::dot().color(Color::Conflict),
+            ThreadStatus::Running | ThreadStatus::Stepping => {
+                Indicator::dot().color(Color::Success)
+            }
+            ThreadStatus::Exited | ThreadStatus::Ended => Indicator::dot().color(Color::Muted),
+        };
+        let (toggle_icon, toggle_tooltip) = if status == ThreadStatus::Stopped {
+            (IconName::DebugContinue, "Continue Thread")
+        } else {
+            (IconName::DebugPause, "Pause Thread")
+        };
+
+        h_flex()
+            .id(("thread-list", ix))
+            .w_full()
+            .gap_2()
+            .p_1()
+            .rounded_md()
+            .group("thread-list-entry")
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_thread(ix, window, cx);
+            }))
+            .hover(|s| s.bg(cx.theme().colors().element_hover))
+            .when(Some(ix) == self.selected_ix, |s| {
+                s.bg(cx.theme().colors().element_hover)
+            })
+            .child(indicator)
+            .child(
+                Label::new(name)
+                    .size(LabelSize::Small)
+                    .truncate()
+                    .when(is_current, |label| label.color(Color::Accent)),
+            )
+            .child(
+                Label::new(status.label())
+                    .size(LabelSize::XSmall)
+                    .color(Color::Muted),
+            )
+            .child(div().flex_1())
+            .when(
+                !session_terminated
+                    && matches!(
+                        status,
+                        ThreadStatus::Running | ThreadStatus::Stepping | ThreadStatus::Stopped
+                    ),
+                |this| {
+                    this.child(
+                        IconButton::new(("thread-list-toggle", ix), toggle_icon)
+                            .icon_size(IconSize::XSmall)
+                            .visible_on_hover("thread-list-entry")
+                            .tooltip(Tooltip::text(toggle_tooltip))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_thread_paused(thread_id, cx);
+                            })),
+                    )
+                },
+            )
+            .into_any()
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.select_thread(ix, window, cx);
+        }
+    }
+
+    fn select_ix(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        self.selected_ix = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _window: &mut Window, cx: &mut Context<Self>) {
+        let ix = match self.selected_ix {
+            _ if self.entries.is_empty() => None,
+            None => Some(0),
+            Some(ix) => Some((ix + 1) % self.entries.len()),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = match self.selected_ix {
+            _ if self.entries.is_empty() => None,
+            None | Some(0) => Some(self.entries.len() - 1),
+            Some(ix) => Some(ix - 1),
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_first(
+        &mut self,
+        _: &menu::SelectFirst,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ix = (!self.entries.is_empty()).then_some(0);
+        self.select_ix(ix, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.entries.len().checked_sub(1);
+        self.select_ix(ix, cx);
+    }
+
+    fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "thread-list",
+            self.entries.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(self.scroll_handle.clone())
+        .size_full()
+    }
+}
+
+impl Focusable for ThreadList {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ThreadList {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self._rebuild_task.is_none() {
+            self.schedule_rebuild(cx);
+        }
+        div()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .p_1()
+            .child(self.render_list(window, cx))
+            .vertical_scrollbar_for(self.scroll_handle.clone(), window, cx)
+    }
+}
@@ -423,6 +423,22 @@ impl VariableList {
             .collect()
     }
 
+    /// Expands and selects the watcher of `expression`, once it's added.
+    pub(crate) fn expand_watcher(&mut self, expression: SharedString, cx: &mut Context<Self>) {
+        let path = EntryPath::for_watcher(expression.clone()).with_child(expression);
+        self.entry_states
+            .entry(path.clone())
+            .or_insert(EntryState {
+                depth: path.indices.len(),
+                is_expanded: true,
+                has_children: true,
+                parent_reference: 0,
+            })
+            .is_expanded = true;
+        self.selection = Some(path);
+        self.build_entries(cx);
+    }
+
     pub(crate) fn toggle_entry(&mut self, var_path: &EntryPath, cx: &mut Context<Self>) {
         let Some(entry) = self.entry_states.get_mut(var_path) else {
             log::error!("Could not find variable list entry state to toggle");
@@ -21,6 +21,8 @@ mod dap_logger;
 #[cfg(test)]
 mod debugger_panel;
 #[cfg(test)]
+mod disassembly_view;
+#[cfg(test)]
 mod inline_values;
 #[cfg(test)]
 mod module_list;
@@ -31,6 +33,8 @@ mod persistence;
 #[cfg(test)]
 mod stack_frame_list;
 #[cfg(test)]
+mod thread_list;
+#[cfg(test)]
 mod variable_list;
 
 pub fn init_test(cx: &mut gpui::TestAppContext) {
@@ -2,8 +2,11 @@ use crate::{
     tests::{active_debug_session_panel, start_debug_session},
     *,
 };
-use dap::requests::StackTrace;
-use editor::{DisplayPoint, display_map::DisplayRow};
+use dap::requests::{Evaluate, StackTrace};
+use editor::{
+    DisplayPoint, RowExt as _, ToPoint as _,
+    display_map::{Block, DisplayRow},
+};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{FakeFs, Project};
 use serde_json::json;
@@ -356,6 +359,119 @@ async fn test_escape_code_processing(executor: BackgroundExecutor, cx: &mut Test
         .unwrap();
 }
 
+#[gpui::test]
+async fn test_inspect_evaluated_objects(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "fn main() {}" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<Evaluate, _>(move |_, args| {
+        let (result, variables_reference) = match args.expression.as_str() {
+            "object" => ("{a: 1}", 7),
+            _ => ("2", 0),
+        };
+        Ok(dap::EvaluateResponse {
+            result: result.to_string(),
+            type_: None,
+            presentation_hint: None,
+            variables_reference,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+
+    // Program output that looks like an evaluation shouldn't be inspectable.
+    for output in ["> spoofed", "< {spoofed: 1}"] {
+        client
+            .fake_event(dap::messages::Events::Output(dap::OutputEvent {
+                category: None,
+                output: output.to_string(),
+                data: None,
+                variables_reference: Some(3),
+                source: None,
+                line: None,
+                column: None,
+                group: None,
+                location_reference: None,
+            }))
+            .await;
+    }
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    for expression in ["object", "1 + 1"] {
+        session
+            .update(cx, |session, cx| {
+                session.evaluate(expression.to_string(), None, None, None, cx)
+            })
+            .await;
+    }
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+    running_state.update(cx, |_, cx| {
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    let editor = running_state.update(cx, |running_state, cx| {
+        running_state.console().read(cx).editor().clone()
+    });
+    editor.update_in(cx, |editor, window, cx| {
+        assert_eq!(
+            editor.text(cx),
+            "> spoofed\n< {spoofed: 1}\n> object\n< {a: 1}\n> 1 + 1\n< 2\n"
+        );
+
+        // Only the result of evaluating `object` offers to show it in the variables tree.
+        let snapshot = editor.snapshot(window, cx);
+        let inspectable_rows = snapshot
+            .blocks_in_range(DisplayRow(0)..snapshot.max_point().row().next_row())
+            .filter_map(|(_, block)| match block {
+                Block::Custom(block) => Some(block.start().to_point(&snapshot.buffer_snapshot).row),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(inspectable_rows, vec![3]);
+    });
+}
+
 // #[gpui::test]
 // async fn test_grouped_output(executor: BackgroundExecutor, cx: &mut TestAppContext) {
 //     init_test(cx);
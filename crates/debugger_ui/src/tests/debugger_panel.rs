@@ -20,7 +20,12 @@ use editor::{
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::{
+            Breakpoint, BreakpointEditAction, BreakpointState, BreakpointWithPosition,
+        },
+        session::{ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
@@ -1242,6 +1247,105 @@ async fn test_send_breakpoints_when_editor_has_been_saved(
     );
 }
 
+#[gpui::test]
+async fn test_unsupported_breakpoint_options_are_not_sent(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = workspace
+        .update(cx, |_, _, cx| {
+            project.read(cx).worktrees(cx).next().unwrap().read(cx).id()
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(|_, _| {
+            Ok(dap::Capabilities {
+                supports_log_points: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("main.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
+    client.on_request::<SetBreakpoints, _>({
+        let called_set_breakpoints = called_set_breakpoints.clone();
+        move |_, args| {
+            assert_eq!(
+                vec![SourceBreakpoint {
+                    line: 2,
+                    column: None,
+                    condition: None,
+                    hit_condition: None,
+                    log_message: Some("reached the second line".into()),
+                    mode: None
+                }],
+                args.breakpoints.unwrap(),
+                "Only log points are supported by the adapter"
+            );
+
+            called_set_breakpoints.store(true, Ordering::SeqCst);
+
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+
+    project.update(cx, |project, cx| {
+        let position = buffer.read(cx).anchor_before(text::Point::new(1, 0));
+        project
+            .breakpoint_store()
+            .update(cx, |breakpoint_store, cx| {
+                breakpoint_store.toggle_breakpoint(
+                    buffer.clone(),
+                    BreakpointWithPosition {
+                        position,
+                        bp: Breakpoint {
+                            message: Some("reached the second line".into()),
+                            hit_condition: Some("3".into()),
+                            condition: Some("x > 1".into()),
+                            state: BreakpointState::Enabled,
+                        },
+                    },
+                    BreakpointEditAction::Toggle,
+                    cx,
+                );
+            });
+    });
+
+    cx.run_until_parked();
+
+    assert!(
+        called_set_breakpoints.load(Ordering::SeqCst),
+        "SetBreakpoint request must be called"
+    );
+}
+
 #[gpui::test]
 async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
     executor: BackgroundExecutor,
@@ -6,6 +6,7 @@ use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use language::{Language, LanguageConfig, LanguageMatcher, tree_sitter_python, tree_sitter_rust};
 use project::{FakeFs, Project};
 use serde_json::json;
+use settings::{InlineValuesPosition, SettingsStore};
 use unindent::Unindent as _;
 use util::{path, rel_path::rel_path};
 
@@ -1888,11 +1889,22 @@ async fn test_inline_values_util(
     before: &str,
     after: &str,
     active_debug_line: Option<usize>,
+    values_position: InlineValuesPosition,
     language: Language,
     executor: BackgroundExecutor,
     cx: &mut TestAppContext,
 ) {
     init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings
+                    .debugger
+                    .get_or_insert_default()
+                    .inline_values_position = Some(values_position);
+            });
+        });
+    });
 
     let lines_count = before.lines().count();
     let stop_line =
@@ -2145,6 +2157,7 @@ fn main() {
         &before,
         &after,
         None,
+        InlineValuesPosition::Inline,
         rust_lang(),
         executor,
         cx,
@@ -2190,6 +2203,7 @@ fn main() {
         &before,
         &after,
         None,
+        InlineValuesPosition::Inline,
         rust_lang(),
         executor,
         cx,
@@ -2235,6 +2249,7 @@ func main() {
         &before,
         &after,
         None,
+        InlineValuesPosition::Inline,
         go_lang(),
         executor,
         cx,
@@ -2266,6 +2281,45 @@ fn main() {
         &before,
         &after,
         None,
+        InlineValuesPosition::Inline,
+        rust_lang(),
+        executor,
+        cx,
+    )
+    .await;
+}
+
+#[gpui::test]
+async fn test_inline_values_at_end_of_line(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    let variables = [("x", "10"), ("y", "20"), ("result", "30")];
+
+    let before = r#"
+fn main() {
+    let x = 10;
+    let y = 20;
+    let result = x + y;
+    println!("Result: {}", result);
+}
+"#
+    .unindent();
+
+    let after = r#"
+fn main() {
+    let x = 10;  x: 10
+    let y = 20;  y: 20
+    let result = x + y;  result: 30, x: 10, y: 20
+    println!("Result: {}", result);  result: 30
+}
+"#
+    .unindent();
+
+    test_inline_values_util(
+        &variables,
+        &[],
+        &before,
+        &after,
+        None,
+        InlineValuesPosition::EndOfLine,
         rust_lang(),
         executor,
         cx,
@@ -138,6 +138,7 @@ async fn test_debug_session_substitutes_variables_and_relativizes_paths(
                 "otherField": input_path
             }),
             tcp_connection: None,
+            start_with: Vec::new(),
         };
 
         workspace
@@ -0,0 +1,105 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StoppedEvent,
+    requests::{StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger::session::ThreadId};
+use util::path;
+
+#[gpui::test]
+async fn test_thread_list(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let threads = vec![
+        dap::Thread {
+            id: 1,
+            name: "main".into(),
+        },
+        dap::Thread {
+            id: 2,
+            name: "worker".into(),
+        },
+    ];
+    client.on_request::<Threads, _>({
+        let threads = threads.clone();
+        move |_, _| {
+            Ok(dap::ThreadsResponse {
+                threads: threads.clone(),
+            })
+        }
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::new(),
+            total_frames: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.activate_item(DebuggerPaneItem::Threads, window, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    let thread_list = running_state.update(cx, |state, _| state.thread_list().clone());
+    thread_list.update(cx, |list, _| {
+        assert_eq!(threads, list.threads());
+    });
+    running_state.update(cx, |state, _| {
+        assert_eq!(Some(ThreadId(1)), state.selected_thread_id());
+    });
+
+    thread_list.update_in(cx, |list, window, cx| {
+        list.select_thread(1, window, cx);
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, _| {
+        assert_eq!(
+            Some(ThreadId(2)),
+            state.selected_thread_id(),
+            "Clicking a thread in the list should select it"
+        );
+    });
+}
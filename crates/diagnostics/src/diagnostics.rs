@@ -9,7 +9,7 @@ mod diagnostics_tests;
 
 use anyhow::Result;
 use buffer_diagnostics::BufferDiagnosticsEditor;
-use collections::{BTreeSet, HashMap};
+use collections::{BTreeMap, BTreeSet, HashMap};
 use diagnostic_renderer::DiagnosticBlock;
 use editor::{
     Editor, EditorEvent, ExcerptRange, MultiBuffer, PathKey,
@@ -22,11 +22,12 @@ use gpui::{
     Subscription, Task, WeakEntity, Window, actions, div,
 };
 use language::{
-    Bias, Buffer, BufferRow, BufferSnapshot, DiagnosticEntry, DiagnosticEntryRef, Point,
-    ToTreeSitterPoint,
+    Bias, Buffer, BufferRow, BufferSnapshot, Diagnostic, DiagnosticEntry, DiagnosticEntryRef,
+    Point, ToTreeSitterPoint,
 };
+use lsp::CodeActionKind;
 use project::{
-    DiagnosticSummary, Project, ProjectPath,
+    DiagnosticSummary, LspAction, Project, ProjectPath,
     project_settings::{DiagnosticSeverity, ProjectSettings},
 };
 use settings::Settings;
@@ -41,8 +42,8 @@ use text::{BufferId, OffsetRangeExt};
 use theme::ActiveTheme;
 use toolbar_controls::DiagnosticsToolbarEditor;
 pub use toolbar_controls::ToolbarControls;
-use ui::{Icon, IconName, Label, h_flex, prelude::*};
-use util::ResultExt;
+use ui::{ContextMenu, Icon, IconName, Label, PopoverMenu, Tooltip, h_flex, prelude::*};
+use util::{ResultExt, paths::PathMatcher, rel_path::RelPath};
 use workspace::{
     ItemNavHistory, ToolbarItemLocation, Workspace,
     item::{BreadcrumbText, Item, ItemEvent, ItemHandle, SaveOptions, TabContentParams},
@@ -57,7 +58,9 @@ actions!(
         /// Toggles the display of warning-level diagnostics.
         ToggleWarnings,
         /// Toggles automatic refresh of diagnostics.
-        ToggleDiagnosticsRefresh
+        ToggleDiagnosticsRefresh,
+        /// Applies the preferred quick fix of every diagnostic shown.
+        FixAll
     ]
 );
 
@@ -78,15 +81,99 @@ pub(crate) struct ProjectDiagnosticsEditor {
     editor: Entity<Editor>,
     diagnostics: HashMap<BufferId, Vec<DiagnosticEntry<text::Anchor>>>,
     blocks: HashMap<BufferId, Vec<CustomBlockId>>,
+    path_keys: HashMap<BufferId, Vec<PathKey>>,
     summary: DiagnosticSummary,
     multibuffer: Entity<MultiBuffer>,
     paths_to_update: BTreeSet<ProjectPath>,
     include_warnings: bool,
+    grouping: DiagnosticsGrouping,
+    filter: DiagnosticsFilter,
+    filter_editor: Entity<Editor>,
+    exclude_editor: Entity<Editor>,
     update_excerpts_task: Option<Task<Result<()>>>,
     diagnostic_summary_update: Task<()>,
+    fix_all_task: Option<Task<()>>,
     _subscription: Subscription,
 }
 
+/// How the diagnostics view orders its excerpts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum DiagnosticsGrouping {
+    #[default]
+    File,
+    Severity,
+    Source,
+}
+
+impl DiagnosticsGrouping {
+    const ALL: [Self; 3] = [Self::File, Self::Severity, Self::Source];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::File => "File",
+            Self::Severity => "Severity",
+            Self::Source => "Source",
+        }
+    }
+
+    /// The name of the group the diagnostic belongs to, which prefixes the paths of the
+    /// excerpts so that they're ordered by group.
+    fn group_name(self, diagnostic: &Diagnostic) -> Option<String> {
+        match self {
+            Self::File => None,
+            Self::Severity => Some(
+                match diagnostic.severity {
+                    lsp::DiagnosticSeverity::ERROR => "1-error",
+                    lsp::DiagnosticSeverity::WARNING => "2-warning",
+                    lsp::DiagnosticSeverity::INFORMATION => "3-info",
+                    _ => "4-hint",
+                }
+                .to_string(),
+            ),
+            Self::Source => Some(
+                diagnostic
+                    .source
+                    .as_deref()
+                    .unwrap_or("other")
+                    .chars()
+                    .map(|c| {
+                        if c.is_alphanumeric() || c == '-' || c == '_' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Restricts the diagnostics view to the diagnostics matching a query, outside of excluded
+/// paths.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct DiagnosticsFilter {
+    query: String,
+    excluded_paths: PathMatcher,
+}
+
+impl DiagnosticsFilter {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        self.query.is_empty()
+            || diagnostic.message.to_lowercase().contains(&self.query)
+            || diagnostic
+                .source
+                .as_ref()
+                .is_some_and(|source| source.to_lowercase().contains(&self.query))
+    }
+
+    fn excludes(&self, buffer: &Buffer) -> bool {
+        buffer
+            .file()
+            .is_some_and(|file| self.excluded_paths.is_match(file.path().as_std_path()))
+    }
+}
+
 impl EventEmitter<EditorEvent> for ProjectDiagnosticsEditor {}
 
 const DIAGNOSTICS_UPDATE_DELAY: Duration = Duration::from_millis(50);
@@ -136,7 +223,10 @@ impl Render for ProjectDiagnosticsEditor {
                         )
                     })
             } else {
-                div().size_full().child(self.editor.clone())
+                v_flex()
+                    .size_full()
+                    .child(self.render_filter_bar(cx))
+                    .child(div().flex_1().min_h_0().child(self.editor.clone()))
             };
 
         div()
@@ -145,6 +235,7 @@ impl Render for ProjectDiagnosticsEditor {
             .size_full()
             .on_action(cx.listener(Self::toggle_warnings))
             .on_action(cx.listener(Self::toggle_diagnostics_refresh))
+            .on_action(cx.listener(Self::fix_all))
             .child(child)
     }
 }
@@ -263,13 +354,41 @@ impl ProjectDiagnosticsEditor {
         })
         .detach();
 
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter by message or source…", window, cx);
+            editor
+        });
+        let exclude_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Exclude files, e.g. vendor/**, *.min.js", window, cx);
+            editor
+        });
+        for filter_input in [&filter_editor, &exclude_editor] {
+            cx.subscribe_in(
+                filter_input,
+                window,
+                |this, _, event: &EditorEvent, window, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_filter(window, cx);
+                    }
+                },
+            )
+            .detach();
+        }
+
         let project = project_handle.read(cx);
         let mut this = Self {
             project: project_handle.clone(),
             summary: project.diagnostic_summary(false, cx),
             diagnostics: Default::default(),
             blocks: Default::default(),
+            path_keys: Default::default(),
             include_warnings,
+            grouping: DiagnosticsGrouping::default(),
+            filter: DiagnosticsFilter::default(),
+            filter_editor,
+            exclude_editor,
             workspace,
             multibuffer: excerpts,
             focus_handle,
@@ -277,6 +396,7 @@ impl ProjectDiagnosticsEditor {
             paths_to_update: Default::default(),
             update_excerpts_task: None,
             diagnostic_summary_update: Task::ready(()),
+            fix_all_task: None,
             _subscription: project_event_subscription,
         };
         this.update_all_excerpts(window, cx);
@@ -372,6 +492,246 @@ impl ProjectDiagnosticsEditor {
         cx.notify();
     }
 
+    fn update_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let path_style = self.project.read(cx).path_style(cx);
+        let excluded_globs = self
+            .exclude_editor
+            .read(cx)
+            .text(cx)
+            .split(',')
+            .map(str::trim)
+            .filter(|glob| !glob.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        // Keep excluding the previous globs while a new one is being typed.
+        let Some(excluded_paths) = PathMatcher::new(&excluded_globs, path_style).log_err() else {
+            return;
+        };
+        let filter = DiagnosticsFilter {
+            query: self.filter_editor.read(cx).text(cx).trim().to_lowercase(),
+            excluded_paths,
+        };
+        if filter != self.filter {
+            self.filter = filter;
+            self.diagnostics.clear();
+            self.update_all_excerpts(window, cx);
+        }
+    }
+
+    fn set_grouping(
+        &mut self,
+        grouping: DiagnosticsGrouping,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if grouping != self.grouping {
+            self.grouping = grouping;
+            self.diagnostics.clear();
+            self.update_all_excerpts(window, cx);
+            cx.notify();
+        }
+    }
+
+    /// The primary diagnostics shown in the view, along with their buffers.
+    fn visible_primary_diagnostics(
+        &self,
+        cx: &App,
+    ) -> Vec<(Entity<Buffer>, DiagnosticEntry<text::Anchor>)> {
+        let max_severity = if self.include_warnings {
+            lsp::DiagnosticSeverity::WARNING
+        } else {
+            lsp::DiagnosticSeverity::ERROR
+        };
+        let multibuffer = self.multibuffer.read(cx);
+        self.diagnostics
+            .iter()
+            .filter_map(|(buffer_id, entries)| Some((multibuffer.buffer(*buffer_id)?, entries)))
+            .filter(|(buffer, _)| !self.filter.excludes(buffer.read(cx)))
+            .flat_map(|(buffer, entries)| {
+                entries
+                    .iter()
+                    .filter(|entry| {
+                        entry.diagnostic.is_primary
+                            && entry.diagnostic.severity <= max_severity
+                            && self.filter.matches(&entry.diagnostic)
+                    })
+                    .map(move |entry| (buffer.clone(), entry.clone()))
+            })
+            .collect()
+    }
+
+    fn fix_all(&mut self, _: &FixAll, window: &mut Window, cx: &mut Context<Self>) {
+        self.fix_all_from_source(None, window, cx);
+    }
+
+    /// Applies the preferred quick fix of each shown diagnostic, optionally only of those
+    /// reported by the given source.
+    fn fix_all_from_source(
+        &mut self,
+        source: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let diagnostics = self
+            .visible_primary_diagnostics(cx)
+            .into_iter()
+            .filter(|(_, entry)| source.is_none() || entry.diagnostic.source == source)
+            .collect::<Vec<_>>();
+        if diagnostics.is_empty() {
+            return;
+        }
+
+        let project = self.project.clone();
+        self.fix_all_task = Some(cx.spawn_in(window, async move |this, cx| {
+            // Fixes are applied one at a time, as each one may move the others.
+            for (buffer, entry) in diagnostics {
+                let Ok(actions) = project.update(cx, |project, cx| {
+                    project.code_actions(
+                        &buffer,
+                        entry.range.clone(),
+                        Some(vec![CodeActionKind::QUICKFIX]),
+                        cx,
+                    )
+                }) else {
+                    break;
+                };
+                let Some(actions) = actions.await.log_err().flatten() else {
+                    continue;
+                };
+                let preferred_action = actions.into_iter().find(|action| {
+                    matches!(
+                        &action.lsp_action,
+                        LspAction::Action(action) if action.is_preferred == Some(true)
+                    )
+                });
+                let Some(action) = preferred_action else {
+                    continue;
+                };
+                let Ok(apply) = project.update(cx, |project, cx| {
+                    project.apply_code_action(buffer, action, true, cx)
+                }) else {
+                    break;
+                };
+                apply.await.log_err();
+            }
+            this.update(cx, |this, cx| {
+                this.fix_all_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn render_filter_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let this = cx.entity().downgrade();
+        let grouping = self.grouping;
+        let mut sources = self
+            .visible_primary_diagnostics(cx)
+            .into_iter()
+            .filter_map(|(_, entry)| entry.diagnostic.source)
+            .collect::<Vec<_>>();
+        sources.sort();
+        sources.dedup();
+        let is_fixing = self.fix_all_task.is_some();
+
+        let input = |editor: &Entity<Editor>| {
+            h_flex()
+                .w_64()
+                .px_2()
+                .py_1()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().colors().border)
+                .child(editor.clone())
+        };
+
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(input(&self.filter_editor))
+            .child(input(&self.exclude_editor))
+            .child(div().flex_1())
+            .child(
+                PopoverMenu::new("diagnostics-grouping")
+                    .trigger(
+                        Button::new(
+                            "diagnostics-grouping-trigger",
+                            format!("Group by {}", grouping.label()),
+                        )
+                        .label_size(LabelSize::Small),
+                    )
+                    .menu({
+                        let this = this.clone();
+                        move |window, cx| {
+                            let this = this.clone();
+                            Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                                for option in DiagnosticsGrouping::ALL {
+                                    let this = this.clone();
+                                    menu = menu.toggleable_entry(
+                                        option.label(),
+                                        option == grouping,
+                                        IconPosition::Start,
+                                        None,
+                                        move |window, cx| {
+                                            this.update(cx, |this, cx| {
+                                                this.set_grouping(option, window, cx)
+                                            })
+                                            .ok();
+                                        },
+                                    );
+                                }
+                                menu
+                            }))
+                        }
+                    }),
+            )
+            .child(
+                PopoverMenu::new("diagnostics-fix-all")
+                    .trigger_with_tooltip(
+                        Button::new(
+                            "diagnostics-fix-all-trigger",
+                            if is_fixing { "Fixing…" } else { "Fix All" },
+                        )
+                        .label_size(LabelSize::Small)
+                        .disabled(is_fixing),
+                        Tooltip::text("Apply the preferred quick fix of each diagnostic"),
+                    )
+                    .menu(move |window, cx| {
+                        let this = this.clone();
+                        let sources = sources.clone();
+                        Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                            menu = menu.entry("All Sources", Some(Box::new(FixAll)), {
+                                let this = this.clone();
+                                move |window, cx| {
+                                    this.update(cx, |this, cx| {
+                                        this.fix_all_from_source(None, window, cx)
+                                    })
+                                    .ok();
+                                }
+                            });
+                            if !sources.is_empty() {
+                                menu = menu.separator();
+                            }
+                            for source in sources {
+                                let this = this.clone();
+                                menu = menu.entry(source.clone(), None, move |window, cx| {
+                                    this.update(cx, |this, cx| {
+                                        this.fix_all_from_source(Some(source.clone()), window, cx)
+                                    })
+                                    .ok();
+                                });
+                            }
+                            menu
+                        }))
+                    }),
+            )
+    }
+
     fn focus_in(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.focus_handle.is_focused(window) && !self.multibuffer.read(cx).is_empty() {
             self.editor.focus_handle(cx).focus(window)
@@ -443,6 +803,23 @@ impl ProjectDiagnosticsEditor {
         } else {
             lsp::DiagnosticSeverity::ERROR
         };
+        let grouping = self.grouping;
+        let filter = self.filter.clone();
+        let is_excluded = filter.excludes(buffer.read(cx));
+        let file_path_key = PathKey::for_buffer(&buffer, cx);
+        let file_path = buffer
+            .read(cx)
+            .file()
+            .map(|file| (file.worktree_id(cx).to_proto(), file.path().clone()));
+        let path_key_for_group = move |group_name: Option<String>| {
+            group_name
+                .zip(file_path.clone())
+                .and_then(|(group_name, (worktree_id, path))| {
+                    let group_path = RelPath::unix(&group_name).ok()?.join(&path);
+                    Some(PathKey::namespaced(worktree_id, group_path))
+                })
+                .unwrap_or_else(|| file_path_key.clone())
+        };
 
         cx.spawn_in(window, async move |this, cx| {
             let diagnostics = buffer_snapshot
@@ -481,13 +858,21 @@ impl ProjectDiagnosticsEditor {
                         diagnostic: entry.diagnostic,
                     })
             }
-            let mut blocks: Vec<DiagnosticBlock> = Vec::new();
+            let mut blocks_by_path: BTreeMap<PathKey, Vec<DiagnosticBlock>> = BTreeMap::new();
 
             for (_, group) in grouped {
                 let group_severity = group.iter().map(|d| d.diagnostic.severity).min();
-                if group_severity.is_none_or(|s| s > max_severity) {
+                if is_excluded || group_severity.is_none_or(|s| s > max_severity) {
+                    continue;
+                }
+                if !group.iter().any(|entry| filter.matches(entry.diagnostic)) {
                     continue;
                 }
+                let Some(primary) = group.iter().find(|entry| entry.diagnostic.is_primary) else {
+                    continue;
+                };
+                let path_key = path_key_for_group(grouping.group_name(primary.diagnostic));
+                let blocks = blocks_by_path.entry(path_key).or_default();
                 let more = cx.update(|_, cx| {
                     crate::diagnostic_renderer::DiagnosticRenderer::diagnostic_blocks_for_group(
                         group,
@@ -512,36 +897,40 @@ impl ProjectDiagnosticsEditor {
                 }
             }
 
-            let mut excerpt_ranges: Vec<ExcerptRange<Point>> = Vec::new();
             let context_lines = cx.update(|_, cx| multibuffer_context_lines(cx))?;
-            for b in blocks.iter() {
-                let excerpt_range = context_range_for_entry(
-                    b.initial_range.clone(),
-                    context_lines,
-                    buffer_snapshot.clone(),
-                    cx,
-                )
-                .await;
+            let mut excerpt_ranges_by_path = Vec::new();
+            for (path_key, blocks) in blocks_by_path {
+                let mut excerpt_ranges: Vec<ExcerptRange<Point>> = Vec::new();
+                for b in blocks.iter() {
+                    let excerpt_range = context_range_for_entry(
+                        b.initial_range.clone(),
+                        context_lines,
+                        buffer_snapshot.clone(),
+                        cx,
+                    )
+                    .await;
 
-                let i = excerpt_ranges
-                    .binary_search_by(|probe| {
-                        probe
-                            .context
-                            .start
-                            .cmp(&excerpt_range.start)
-                            .then(probe.context.end.cmp(&excerpt_range.end))
-                            .then(probe.primary.start.cmp(&b.initial_range.start))
-                            .then(probe.primary.end.cmp(&b.initial_range.end))
-                            .then(cmp::Ordering::Greater)
-                    })
-                    .unwrap_or_else(|i| i);
-                excerpt_ranges.insert(
-                    i,
-                    ExcerptRange {
-                        context: excerpt_range,
-                        primary: b.initial_range.clone(),
-                    },
-                )
+                    let i = excerpt_ranges
+                        .binary_search_by(|probe| {
+                            probe
+                                .context
+                                .start
+                                .cmp(&excerpt_range.start)
+                                .then(probe.context.end.cmp(&excerpt_range.end))
+                                .then(probe.primary.start.cmp(&b.initial_range.start))
+                                .then(probe.primary.end.cmp(&b.initial_range.end))
+                                .then(cmp::Ordering::Greater)
+                        })
+                        .unwrap_or_else(|i| i);
+                    excerpt_ranges.insert(
+                        i,
+                        ExcerptRange {
+                            context: excerpt_range,
+                            primary: b.initial_range.clone(),
+                        },
+                    )
+                }
+                excerpt_ranges_by_path.push((path_key, excerpt_ranges, blocks));
             }
 
             this.update_in(cx, |this, window, cx| {
@@ -552,15 +941,36 @@ impl ProjectDiagnosticsEditor {
                         });
                     })
                 }
-                let (anchor_ranges, _) = this.multibuffer.update(cx, |multi_buffer, cx| {
-                    multi_buffer.set_excerpt_ranges_for_path(
-                        PathKey::for_buffer(&buffer, cx),
-                        buffer.clone(),
-                        &buffer_snapshot,
-                        excerpt_ranges,
-                        cx,
-                    )
+                let old_path_keys = this
+                    .path_keys
+                    .remove(&buffer_id)
+                    .unwrap_or_else(|| vec![PathKey::for_buffer(&buffer, cx)]);
+                let mut path_keys = Vec::new();
+                let mut anchor_ranges = Vec::new();
+                let mut blocks = Vec::new();
+                this.multibuffer.update(cx, |multi_buffer, cx| {
+                    for path_key in old_path_keys {
+                        if !excerpt_ranges_by_path
+                            .iter()
+                            .any(|(new_path_key, _, _)| *new_path_key == path_key)
+                        {
+                            multi_buffer.remove_excerpts_for_path(path_key, cx);
+                        }
+                    }
+                    for (path_key, excerpt_ranges, path_blocks) in excerpt_ranges_by_path {
+                        let (path_anchor_ranges, _) = multi_buffer.set_excerpt_ranges_for_path(
+                            path_key.clone(),
+                            buffer.clone(),
+                            &buffer_snapshot,
+                            excerpt_ranges,
+                            cx,
+                        );
+                        path_keys.push(path_key);
+                        anchor_ranges.extend(path_anchor_ranges);
+                        blocks.extend(path_blocks);
+                    }
                 });
+                this.path_keys.insert(buffer_id, path_keys);
                 #[cfg(test)]
                 let cloned_blocks = blocks.clone();
 
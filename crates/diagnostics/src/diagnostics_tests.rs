@@ -1445,6 +1445,128 @@ async fn test_diagnostics_with_code(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_diagnostics_filtering_and_grouping(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "main.js": "
+                function test() {
+                    const x = 10;
+                    return y;
+                }
+            "
+            .unindent(),
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let workspace = window.root(cx).unwrap();
+    let uri = lsp::Uri::from_file_path(path!("/root/main.js")).unwrap();
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_diagnostics(
+                LanguageServerId(0),
+                lsp::PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: vec![
+                        lsp::Diagnostic {
+                            range: lsp::Range::new(
+                                lsp::Position::new(1, 10),
+                                lsp::Position::new(1, 11),
+                            ),
+                            severity: Some(lsp::DiagnosticSeverity::WARNING),
+                            source: Some("eslint".to_string()),
+                            message: "'x' is assigned a value but never used".to_string(),
+                            ..Default::default()
+                        },
+                        lsp::Diagnostic {
+                            range: lsp::Range::new(
+                                lsp::Position::new(2, 11),
+                                lsp::Position::new(2, 12),
+                            ),
+                            severity: Some(lsp::DiagnosticSeverity::ERROR),
+                            source: Some("ts".to_string()),
+                            message: "Cannot find name 'y'".to_string(),
+                            ..Default::default()
+                        },
+                    ],
+                    version: None,
+                },
+                None,
+                DiagnosticSourceKind::Pushed,
+                &[],
+                cx,
+            )
+            .unwrap();
+    });
+
+    let diagnostics = window.build_entity(cx, |window, cx| {
+        ProjectDiagnosticsEditor::new(true, project.clone(), workspace.downgrade(), window, cx)
+    });
+    let update_view = |cx: &mut VisualTestContext| {
+        cx.executor()
+            .advance_clock(DIAGNOSTICS_UPDATE_DELAY + Duration::from_millis(10));
+        cx.executor().run_until_parked();
+    };
+    let path_key_count = |cx: &mut VisualTestContext| {
+        diagnostics.read_with(cx, |diagnostics, _| {
+            diagnostics.path_keys.values().map(Vec::len).sum::<usize>()
+        })
+    };
+    let excerpt_count = |cx: &mut VisualTestContext| {
+        diagnostics.read_with(cx, |diagnostics, cx| {
+            diagnostics.multibuffer.read(cx).excerpt_ids().len()
+        })
+    };
+    update_view(cx);
+    assert_eq!(path_key_count(cx), 1);
+    assert_eq!(
+        diagnostics.read_with(cx, |d, cx| d.visible_primary_diagnostics(cx).len()),
+        2
+    );
+
+    // Grouping by severity puts each diagnostic under its own path.
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.set_grouping(DiagnosticsGrouping::Severity, window, cx)
+    });
+    update_view(cx);
+    assert_eq!(path_key_count(cx), 2);
+
+    // Filtering by source hides the other diagnostics.
+    let filter_editor =
+        diagnostics.read_with(cx, |diagnostics, _| diagnostics.filter_editor.clone());
+    filter_editor.update_in(cx, |editor, window, cx| {
+        editor.set_text("ESLint", window, cx)
+    });
+    update_view(cx);
+    assert_eq!(path_key_count(cx), 1);
+    let visible_messages = diagnostics.read_with(cx, |diagnostics, cx| {
+        diagnostics
+            .visible_primary_diagnostics(cx)
+            .into_iter()
+            .map(|(_, entry)| entry.diagnostic.message)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(visible_messages, ["'x' is assigned a value but never used"]);
+
+    // Excluding the file removes its excerpts.
+    let exclude_editor =
+        diagnostics.read_with(cx, |diagnostics, _| diagnostics.exclude_editor.clone());
+    exclude_editor.update_in(cx, |editor, window, cx| editor.set_text("*.js", window, cx));
+    update_view(cx);
+    assert_eq!(excerpt_count(cx), 0);
+    assert_eq!(path_key_count(cx), 0);
+}
+
 #[gpui::test]
 async fn go_to_diagnostic_with_severity(cx: &mut TestAppContext) {
     init_test(cx);
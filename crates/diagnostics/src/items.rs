@@ -1,12 +1,16 @@
 use std::time::Duration;
 
+use collections::BTreeMap;
 use editor::Editor;
 use gpui::{
-    Context, Entity, EventEmitter, IntoElement, ParentElement, Render, Styled, Subscription, Task,
-    WeakEntity, Window,
+    App, Context, Entity, EventEmitter, IntoElement, ParentElement, Render, Styled, Subscription,
+    Task, WeakEntity, Window,
 };
 use language::Diagnostic;
-use project::project_settings::{GoToDiagnosticSeverityFilter, ProjectSettings};
+use project::{
+    DiagnosticSummary, Project,
+    project_settings::{GoToDiagnosticSeverityFilter, ProjectSettings},
+};
 use settings::Settings;
 use ui::{Button, ButtonLike, Color, Icon, IconName, Label, Tooltip, h_flex, prelude::*};
 use util::ResultExt;
@@ -16,7 +20,8 @@ use crate::{Deploy, IncludeWarnings, ProjectDiagnosticsEditor};
 
 /// The status bar item that displays diagnostic counts.
 pub struct DiagnosticIndicator {
-    summary: project::DiagnosticSummary,
+    summary: DiagnosticSummary,
+    summaries_by_server: Vec<(SharedString, DiagnosticSummary)>,
     workspace: WeakEntity<Workspace>,
     current_diagnostic: Option<Diagnostic>,
     active_editor: Option<WeakEntity<Editor>>,
@@ -87,8 +92,17 @@ impl Render for DiagnosticIndicator {
             .child(
                 ButtonLike::new("diagnostic-indicator")
                     .child(diagnostic_indicator)
-                    .tooltip(|window, cx| {
-                        Tooltip::for_action("Project Diagnostics", &Deploy, window, cx)
+                    .tooltip({
+                        let meta = self.summary_breakdown();
+                        move |window, cx| {
+                            Tooltip::with_meta(
+                                "Project Diagnostics",
+                                Some(&Deploy),
+                                meta.clone(),
+                                window,
+                                cx,
+                            )
+                        }
                     })
                     .on_click(cx.listener(|this, _, window, cx| {
                         if let Some(workspace) = this.workspace.upgrade() {
@@ -122,7 +136,7 @@ impl DiagnosticIndicator {
 
             project::Event::DiskBasedDiagnosticsFinished { .. }
             | project::Event::LanguageServerRemoved(_) => {
-                this.summary = project.read(cx).diagnostic_summary(false, cx);
+                this.update_summary(&project, cx);
                 cx.notify();
             }
 
@@ -132,7 +146,7 @@ impl DiagnosticIndicator {
                         .timer(Duration::from_millis(30))
                         .await;
                     this.update(cx, |this, cx| {
-                        this.summary = project.read(cx).diagnostic_summary(false, cx);
+                        this.update_summary(&project, cx);
                         cx.notify();
                     })
                     .log_err();
@@ -143,15 +157,67 @@ impl DiagnosticIndicator {
         })
         .detach();
 
-        Self {
-            summary: project.read(cx).diagnostic_summary(false, cx),
+        let mut this = Self {
+            summary: DiagnosticSummary::default(),
+            summaries_by_server: Vec::new(),
             active_editor: None,
             workspace: workspace.weak_handle(),
             current_diagnostic: None,
             _observe_active_editor: None,
             diagnostics_update: Task::ready(()),
             diagnostic_summary_update: Task::ready(()),
+        };
+        this.update_summary(project, cx);
+        this
+    }
+
+    fn update_summary(&mut self, project: &Entity<Project>, cx: &App) {
+        let project = project.read(cx);
+        self.summary = project.diagnostic_summary(false, cx);
+
+        let mut summaries = BTreeMap::<_, DiagnosticSummary>::default();
+        for (_, server_id, path_summary) in project.diagnostic_summaries(false, cx) {
+            let summary = summaries.entry(server_id).or_default();
+            summary.error_count += path_summary.error_count;
+            summary.warning_count += path_summary.warning_count;
         }
+        let server_names = project
+            .language_server_statuses(cx)
+            .map(|(server_id, status)| (server_id, status.name.clone()))
+            .collect::<BTreeMap<_, _>>();
+        self.summaries_by_server = summaries
+            .into_iter()
+            .map(|(server_id, summary)| {
+                let name = server_names
+                    .get(&server_id)
+                    .map_or_else(|| server_id.to_string().into(), |name| name.0.clone());
+                (name, summary)
+            })
+            .collect();
+    }
+
+    /// Describes the diagnostic counts by severity, then by language server.
+    fn summary_breakdown(&self) -> SharedString {
+        let counts = |summary: &DiagnosticSummary| {
+            let plural = |count: usize| if count == 1 { "" } else { "s" };
+            format!(
+                "{} error{}, {} warning{}",
+                summary.error_count,
+                plural(summary.error_count),
+                summary.warning_count,
+                plural(summary.warning_count),
+            )
+        };
+        std::iter::once(counts(&self.summary))
+            .chain(
+                self.summaries_by_server
+                    .iter()
+                    .filter(|_| self.summaries_by_server.len() > 1)
+                    .map(|(name, summary)| format!("{name}: {}", counts(summary))),
+            )
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into()
     }
 
     fn go_to_next_diagnostic(&mut self, window: &mut Window, cx: &mut Context<Self>) {
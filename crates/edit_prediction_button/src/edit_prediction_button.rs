@@ -234,6 +234,39 @@ impl Render for EditPredictionButton {
                 )
             }
 
+            EditPredictionProvider::Custom => {
+                let enabled = self.editor_enabled.unwrap_or(true);
+                let this = cx.entity();
+
+                div().child(
+                    PopoverMenu::new("custom-edit-prediction")
+                        .menu(move |window, cx| {
+                            Some(
+                                this.update(cx, |this, cx| {
+                                    this.build_custom_context_menu(window, cx)
+                                }),
+                            )
+                        })
+                        .anchor(Corner::BottomRight)
+                        .trigger_with_tooltip(
+                            IconButton::new(
+                                "custom-edit-prediction-icon",
+                                IconName::AiOpenAiCompat,
+                            )
+                            .when(!enabled, |this| this.icon_color(Color::Disabled)),
+                            |window, cx| {
+                                Tooltip::for_action(
+                                    "Custom Edit Prediction",
+                                    &ToggleMenu,
+                                    window,
+                                    cx,
+                                )
+                            },
+                        )
+                        .with_handle(self.popover_menu_handle.clone()),
+                )
+            }
+
             EditPredictionProvider::Zed => {
                 let enabled = self.editor_enabled.unwrap_or(true);
 
@@ -493,6 +526,7 @@ impl EditPredictionButton {
             EditPredictionProvider::Zed
                 | EditPredictionProvider::Copilot
                 | EditPredictionProvider::Supermaven
+                | EditPredictionProvider::Custom
         ) {
             menu = menu
                 .separator()
@@ -719,6 +753,16 @@ impl EditPredictionButton {
         })
     }
 
+    fn build_custom_context_menu(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, |menu, window, cx| {
+            self.build_language_settings_menu(menu, window, cx)
+        })
+    }
+
     fn build_zeta_context_menu(
         &self,
         window: &mut Window,
@@ -283,6 +283,14 @@ pub struct DiffClipboardWithSelectionData {
     pub editor: Entity<Editor>,
 }
 
+#[derive(Clone, PartialEq, Action)]
+#[action(no_json, no_register)]
+pub struct DiffExternalChangesData {
+    /// The text of the file before it was changed on disk.
+    pub previous_text: String,
+    pub editor: Entity<Editor>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Default)]
 pub enum UuidVersion {
     #[default]
@@ -337,6 +345,15 @@ actions!(
     ]
 );
 
+actions!(
+    color_picker,
+    [
+        /// Toggles a color picker for the color literal under the cursor.
+        #[action(name = "Toggle")]
+        ToggleColorPicker
+    ]
+);
+
 actions!(
     editor,
     [
@@ -367,6 +384,8 @@ actions!(
         ClearFlycheck,
         /// Confirms the rename operation.
         ConfirmRename,
+        /// Confirms the rename operation, showing a preview of its edits before applying them.
+        ConfirmRenameWithPreview,
         /// Confirms completion by inserting at cursor.
         ConfirmCompletionInsert,
         /// Confirms completion by replacing existing text.
@@ -421,6 +440,8 @@ actions!(
         CopyPermalinkToLine,
         /// Cuts selected text to the clipboard.
         Cut,
+        /// Decreases the font size of this editor only.
+        DecreaseEditorFontSize,
         /// Deletes the character after the cursor.
         Delete,
         /// Deletes the current line.
@@ -433,6 +454,9 @@ actions!(
         DeleteToPreviousSubwordStart,
         /// Diffs the text stored in the clipboard against the current selection.
         DiffClipboardWithSelection,
+        /// Dismisses the marker on the tab of a file that was changed on disk by something other
+        /// than Zed.
+        DismissExternalChanges,
         /// Displays names of all active cursors.
         DisplayCursorNames,
         /// Duplicates the current line below.
@@ -510,6 +534,8 @@ actions!(
         HalfPageUp,
         /// Shows hover information for the symbol at cursor.
         Hover,
+        /// Increases the font size of this editor only.
+        IncreaseEditorFontSize,
         /// Increases indentation of selected lines.
         Indent,
         /// Inserts a UUID v4 at cursor position.
@@ -605,6 +631,10 @@ actions!(
         PageUp,
         /// Pastes from clipboard.
         Paste,
+        /// Shows an editable excerpt of the symbol's definition below the cursor.
+        PeekDefinition,
+        /// Shows the symbol's references below the cursor.
+        PeekReferences,
         /// Navigates to the previous edit prediction.
         PreviousEditPrediction,
         /// Redoes the last undone edit.
@@ -613,10 +643,15 @@ actions!(
         RedoSelection,
         /// Renames the symbol at cursor.
         Rename,
+        /// Resets the font size of this editor to the buffer font size.
+        ResetEditorFontSize,
         /// Restarts the language server for the current file.
         RestartLanguageServer,
         /// Reveals the current file in the system file manager.
         RevealInFileManager,
+        /// Opens a diff of the changes made to the file on disk by something other than Zed
+        /// while the editor wasn't focused.
+        ReviewExternalChanges,
         /// Reverses the order of selected lines.
         ReverseLines,
         /// Reloads the file from disk.
@@ -637,6 +672,9 @@ actions!(
         SelectAll,
         /// Selects all matches of the current selection.
         SelectAllMatches,
+        /// Selects all occurrences of the identifier under the cursor within the enclosing
+        /// function, as determined by the syntax tree.
+        SelectAllOccurrencesInScope,
         /// Selects to the start of the current excerpt.
         SelectToStartOfExcerpt,
         /// Selects to the start of the next excerpt.
@@ -731,6 +769,8 @@ actions!(
         OpenGitBlameCommit,
         /// Toggles the diagnostics panel.
         ToggleDiagnostics,
+        /// Shows how the buffer differs from its file on disk, updating as it's edited.
+        ToggleDiffSinceSave,
         /// Toggles indent guides display.
         ToggleIndentGuides,
         /// Toggles inlay hints display.
@@ -749,6 +789,8 @@ actions!(
         SwapSelectionEnds,
         /// Sets a mark at the current position.
         SetMark,
+        /// Protects the buffer from edits, or makes a read-only buffer writable.
+        ToggleReadOnly,
         /// Toggles relative line numbers display.
         ToggleRelativeLineNumbers,
         /// Toggles diff display for selected hunks.
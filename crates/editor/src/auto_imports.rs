@@ -0,0 +1,234 @@
+use anyhow::Result;
+use collections::HashSet;
+use fuzzy::StringMatchCandidate;
+use gpui::{App, AppContext as _, Context, Entity, Task};
+use language::{
+    Buffer, CharClassifier, CharScopeContext, CodeLabel, Transaction, point_from_lsp, point_to_lsp,
+};
+use lsp::{CompletionItemKind, LanguageServerId};
+use project::{
+    Completion, CompletionDisplayOptions, CompletionResponse, CompletionSource, Project,
+    auto_imports::{ExportKind, ImportSyntax, import_edit},
+    lsp_store::CompletionDocumentation,
+};
+use text::{Bias, ToOffset as _};
+
+/// Like snippets, auto-import completions don't come from a language server, so they're
+/// attributed to one that can't exist.
+const AUTO_IMPORT_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX);
+
+/// Completes the word before the cursor with symbols exported by other files in the project,
+/// each carrying the edit that imports it.
+pub(crate) fn auto_import_completions(
+    project: &Project,
+    buffer: &Entity<Buffer>,
+    buffer_position: text::Anchor,
+    cx: &mut App,
+) -> Task<Result<CompletionResponse>> {
+    let empty_response = || {
+        Task::ready(Ok(CompletionResponse {
+            completions: Vec::new(),
+            display_options: CompletionDisplayOptions::default(),
+            is_incomplete: false,
+        }))
+    };
+    let Some(auto_imports) = project.auto_imports().cloned() else {
+        return empty_response();
+    };
+    let snapshot = buffer.read(cx).snapshot();
+    if !snapshot
+        .settings_at(buffer_position, cx)
+        .completions
+        .auto_imports
+    {
+        return empty_response();
+    }
+    let Some(syntax) = snapshot
+        .language()
+        .and_then(|language| ImportSyntax::for_language(&language.name()))
+    else {
+        return empty_response();
+    };
+    let candidates =
+        auto_imports.update(cx, |auto_imports, cx| auto_imports.candidates(buffer, cx));
+    if candidates.is_empty() {
+        return empty_response();
+    }
+
+    let classifier = CharClassifier::new(snapshot.language_scope_at(buffer_position))
+        .scope_context(Some(CharScopeContext::Completion));
+    let executor = cx.background_executor().clone();
+    cx.background_spawn(async move {
+        let position = buffer_position.to_offset(&snapshot);
+        let mut word_start = position;
+        for c in snapshot.reversed_chars_at(position) {
+            if !classifier.is_word(c) {
+                break;
+            }
+            word_start -= c.len_utf8();
+        }
+        let query = snapshot
+            .text_for_range(word_start..position)
+            .collect::<String>();
+        let Some(query_start) = query.chars().next() else {
+            return Ok(CompletionResponse {
+                completions: Vec::new(),
+                display_options: CompletionDisplayOptions::default(),
+                is_incomplete: true,
+            });
+        };
+
+        const MAX_RESULTS: usize = 100;
+        let match_candidates = candidates
+            .iter()
+            .enumerate()
+            .map(|(ix, candidate)| StringMatchCandidate::new(ix, &candidate.export.name))
+            .collect::<Vec<_>>();
+        let mut matches = fuzzy::match_strings(
+            &match_candidates,
+            &query,
+            query.chars().any(|c| c.is_uppercase()),
+            true,
+            MAX_RESULTS,
+            &Default::default(),
+            executor,
+        )
+        .await;
+        let is_incomplete = matches.len() >= MAX_RESULTS;
+        // Every exported symbol would match a short query somewhere, so require it to match
+        // the start of the name.
+        matches.retain(|string_match| {
+            string_match
+                .string
+                .chars()
+                .flat_map(char::to_lowercase)
+                .zip(query_start.to_lowercase())
+                .all(|(name_char, query_char)| name_char == query_char)
+        });
+
+        let text = snapshot.text();
+        let replace_start = snapshot.anchor_before(word_start);
+        let lsp_range = lsp::Range {
+            start: point_to_lsp(snapshot.offset_to_point_utf16(word_start)),
+            end: point_to_lsp(snapshot.offset_to_point_utf16(position)),
+        };
+        let completions = matches
+            .into_iter()
+            .filter_map(|string_match| {
+                let candidate = &candidates[string_match.candidate_id];
+                let (import_range, import_text) = import_edit(&text, syntax, candidate)?;
+                let name = candidate.export.name.clone();
+                let kind = match candidate.export.kind {
+                    ExportKind::Function => CompletionItemKind::FUNCTION,
+                    ExportKind::Class => CompletionItemKind::CLASS,
+                    ExportKind::Variable => CompletionItemKind::VARIABLE,
+                    ExportKind::Type => CompletionItemKind::INTERFACE,
+                    ExportKind::Enum => CompletionItemKind::ENUM,
+                    ExportKind::Module => CompletionItemKind::MODULE,
+                };
+                let import_edit = lsp::TextEdit {
+                    range: lsp::Range {
+                        start: point_to_lsp(snapshot.offset_to_point_utf16(import_range.start)),
+                        end: point_to_lsp(snapshot.offset_to_point_utf16(import_range.end)),
+                    },
+                    new_text: import_text,
+                };
+                Some(Completion {
+                    replace_range: replace_start..buffer_position,
+                    new_text: name.clone(),
+                    source: CompletionSource::Lsp {
+                        insert_range: None,
+                        server_id: AUTO_IMPORT_SERVER_ID,
+                        resolved: true,
+                        lsp_completion: Box::new(lsp::CompletionItem {
+                            label: name.clone(),
+                            kind: Some(kind),
+                            detail: Some(format!("Import from {}", candidate.module)),
+                            text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+                                range: lsp_range,
+                                new_text: name.clone(),
+                            })),
+                            additional_text_edits: Some(vec![import_edit]),
+                            filter_text: Some(name.clone()),
+                            sort_text: Some(char::MAX.to_string()),
+                            ..lsp::CompletionItem::default()
+                        }),
+                        lsp_defaults: None,
+                    },
+                    label: CodeLabel::plain(name, None),
+                    icon_path: None,
+                    documentation: Some(CompletionDocumentation::SingleLine(
+                        candidate.module.clone().into(),
+                    )),
+                    insert_text_mode: None,
+                    confirm: None,
+                })
+            })
+            .collect();
+
+        Ok(CompletionResponse {
+            completions,
+            display_options: CompletionDisplayOptions::default(),
+            is_incomplete,
+        })
+    })
+}
+
+/// Adds the auto-import completions for names that other completions don't already offer, as
+/// language servers that offer a symbol import it themselves, if it needs importing.
+pub(crate) fn push_auto_imports(
+    responses: &mut Vec<CompletionResponse>,
+    mut auto_imports: CompletionResponse,
+) {
+    let offered_names = responses
+        .iter()
+        .flat_map(|response| &response.completions)
+        .map(|completion| completion.label.filter_text().to_string())
+        .collect::<HashSet<_>>();
+    auto_imports
+        .completions
+        .retain(|completion| !offered_names.contains(completion.label.filter_text()));
+    if !auto_imports.completions.is_empty() {
+        responses.push(auto_imports);
+    }
+}
+
+/// Applies the import of an auto-import completion, returning `None` for other completions.
+pub(crate) fn apply_auto_import(
+    buffer: &Entity<Buffer>,
+    completion: &Completion,
+    push_to_history: bool,
+    cx: &mut Context<crate::Editor>,
+) -> Option<Option<Transaction>> {
+    if completion.source.server_id() != Some(AUTO_IMPORT_SERVER_ID) {
+        return None;
+    }
+    let edits = completion
+        .source
+        .lsp_completion(false)?
+        .additional_text_edits
+        .clone()?;
+    Some(buffer.update(cx, |buffer, cx| {
+        let snapshot = buffer.snapshot();
+        let edits = edits
+            .into_iter()
+            .map(|edit| {
+                let start = snapshot.clip_point_utf16(point_from_lsp(edit.range.start), Bias::Left);
+                let end = snapshot.clip_point_utf16(point_from_lsp(edit.range.end), Bias::Left);
+                (start..end, edit.new_text)
+            })
+            .collect::<Vec<_>>();
+        buffer.finalize_last_transaction();
+        buffer.start_transaction();
+        buffer.edit(edits, None, cx);
+        if buffer.end_transaction(cx).is_some() {
+            let transaction = buffer.finalize_last_transaction()?.clone();
+            if !push_to_history {
+                buffer.forget_transaction(transaction.id);
+            }
+            Some(transaction)
+        } else {
+            None
+        }
+    }))
+}
@@ -4,6 +4,7 @@ use settings::Settings;
 use settings::SettingsStore;
 use smol::Timer;
 use std::time::Duration;
+use theme::AccessibilitySettings;
 
 pub struct BlinkManager {
     blink_interval: Duration,
@@ -55,7 +56,10 @@ impl BlinkManager {
     }
 
     fn blink_cursors(&mut self, epoch: usize, cx: &mut Context<Self>) {
-        if EditorSettings::get_global(cx).cursor_blink {
+        // The cursor doesn't blink when motion is reduced.
+        if EditorSettings::get_global(cx).cursor_blink
+            && !AccessibilitySettings::get_global(cx).reduce_motion
+        {
             if epoch == self.blink_epoch && self.enabled && !self.blinking_paused {
                 self.visible = !self.visible;
                 cx.notify();
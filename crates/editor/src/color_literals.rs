@@ -0,0 +1,279 @@
+//! Hex, `rgb()` and `hsl()` color literals, which are found in the buffer text when
+//! no language server provides document colors.
+
+use std::ops::Range;
+
+use gpui::{Hsla, Rgba};
+
+/// The ways a color can be written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorFormat {
+    Hex,
+    Rgb,
+    Hsl,
+}
+
+impl ColorFormat {
+    pub const ALL: [Self; 3] = [Self::Hex, Self::Rgb, Self::Hsl];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hex => "Hex",
+            Self::Rgb => "RGB",
+            Self::Hsl => "HSL",
+        }
+    }
+}
+
+/// A color written out in text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorLiteral {
+    /// The byte range of the literal in the text it was found in.
+    pub range: Range<usize>,
+    pub color: Rgba,
+    pub format: ColorFormat,
+}
+
+/// Finds the color literals in the given text.
+pub fn find_color_literals(text: &str) -> Vec<ColorLiteral> {
+    let mut literals = Vec::new();
+    let mut ix = 0;
+    while ix < text.len() {
+        let preceded_by_word = text[..ix]
+            .chars()
+            .next_back()
+            .is_some_and(|c| is_word_char(c) || c == '&');
+        let literal = if preceded_by_word {
+            None
+        } else if text[ix..].starts_with('#') {
+            parse_hex(text, ix)
+        } else {
+            parse_function(text, ix)
+        };
+        match literal {
+            Some(literal) => {
+                ix = literal.range.end;
+                literals.push(literal);
+            }
+            None => {
+                ix += text[ix..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    literals
+}
+
+/// Parses text that consists of a single color literal, ignoring surrounding whitespace.
+pub fn parse_color_literal(text: &str) -> Option<(Rgba, ColorFormat)> {
+    let text = text.trim();
+    match find_color_literals(text).as_slice() {
+        [literal] if literal.range == (0..text.len()) => Some((literal.color, literal.format)),
+        _ => None,
+    }
+}
+
+/// Writes out a color in the given format, including its alpha only when it's translucent.
+pub fn format_color(color: Rgba, format: ColorFormat) -> String {
+    let channel = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    let translucent = color.a < 1.;
+    match format {
+        ColorFormat::Hex => {
+            let mut hex = format!(
+                "#{:02x}{:02x}{:02x}",
+                channel(color.r),
+                channel(color.g),
+                channel(color.b)
+            );
+            if translucent {
+                hex.push_str(&format!("{:02x}", channel(color.a)));
+            }
+            hex
+        }
+        ColorFormat::Rgb => {
+            let (r, g, b) = (channel(color.r), channel(color.g), channel(color.b));
+            if translucent {
+                format!("rgba({r}, {g}, {b}, {})", format_alpha(color.a))
+            } else {
+                format!("rgb({r}, {g}, {b})")
+            }
+        }
+        ColorFormat::Hsl => {
+            let hsla = Hsla::from(color);
+            let h = (hsla.h * 360.).round() as u32 % 360;
+            let s = (hsla.s * 100.).round() as u32;
+            let l = (hsla.l * 100.).round() as u32;
+            if translucent {
+                format!("hsla({h}, {s}%, {l}%, {})", format_alpha(color.a))
+            } else {
+                format!("hsl({h}, {s}%, {l}%)")
+            }
+        }
+    }
+}
+
+fn format_alpha(alpha: f32) -> String {
+    let alpha = format!("{:.2}", alpha.clamp(0., 1.));
+    match alpha.trim_end_matches('0').trim_end_matches('.') {
+        "" => "0".to_string(),
+        alpha => alpha.to_string(),
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn parse_hex(text: &str, start: usize) -> Option<ColorLiteral> {
+    let digits_start = start + 1;
+    let digits_len = text[digits_start..]
+        .bytes()
+        .take_while(u8::is_ascii_hexdigit)
+        .count();
+    let end = digits_start + digits_len;
+    if text[end..].chars().next().is_some_and(is_word_char) {
+        return None;
+    }
+    let digits = &text[digits_start..end];
+    let channels = match digits_len {
+        3 | 4 => digits
+            .chars()
+            .map(|digit| u8::from_str_radix(&digit.to_string().repeat(2), 16).ok())
+            .collect::<Option<Vec<_>>>()?,
+        6 | 8 => (0..digits_len)
+            .step_by(2)
+            .map(|ix| u8::from_str_radix(&digits[ix..ix + 2], 16).ok())
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    let channel = |ix: usize| channels.get(ix).map_or(1., |value| *value as f32 / 255.);
+    Some(ColorLiteral {
+        range: start..end,
+        color: Rgba {
+            r: channel(0),
+            g: channel(1),
+            b: channel(2),
+            a: channel(3),
+        },
+        format: ColorFormat::Hex,
+    })
+}
+
+fn parse_function(text: &str, start: usize) -> Option<ColorLiteral> {
+    /// Function arguments are short, so don't look for the closing parenthesis past this.
+    const MAX_FUNCTION_LEN: usize = 64;
+
+    let rest = &text[start..];
+    let (format, arguments_start) = ["rgba(", "rgb(", "hsla(", "hsl("]
+        .into_iter()
+        .find(|name| rest.starts_with(name))
+        .map(|name| {
+            let format = if name.starts_with("rgb") {
+                ColorFormat::Rgb
+            } else {
+                ColorFormat::Hsl
+            };
+            (format, start + name.len())
+        })?;
+    let arguments_len = text[arguments_start..]
+        .char_indices()
+        .take_while(|(ix, _)| *ix < MAX_FUNCTION_LEN)
+        .find(|(_, c)| *c == ')')
+        .map(|(ix, _)| ix)?;
+    let arguments = text[arguments_start..arguments_start + arguments_len]
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|argument| !argument.is_empty())
+        .collect::<Vec<_>>();
+    let (components, alpha) = match arguments.as_slice() {
+        [a, b, c] => ([*a, *b, *c], None),
+        [a, b, c, alpha] => ([*a, *b, *c], Some(*alpha)),
+        _ => return None,
+    };
+    let alpha = match alpha {
+        Some(alpha) => parse_fraction(alpha, 1.)?,
+        None => 1.,
+    };
+    let color = match format {
+        ColorFormat::Rgb => Rgba {
+            r: parse_fraction(components[0], 255.)?,
+            g: parse_fraction(components[1], 255.)?,
+            b: parse_fraction(components[2], 255.)?,
+            a: alpha,
+        },
+        _ => {
+            let hue = components[0].strip_suffix("deg").unwrap_or(components[0]);
+            let hue = hue.parse::<f32>().ok()?.rem_euclid(360.) / 360.;
+            let saturation = parse_fraction(components[1], 100.)?;
+            let lightness = parse_fraction(components[2], 100.)?;
+            Rgba::from(Hsla {
+                h: hue,
+                s: saturation,
+                l: lightness,
+                a: alpha,
+            })
+        }
+    };
+    Some(ColorLiteral {
+        range: start..arguments_start + arguments_len + 1,
+        color,
+        format,
+    })
+}
+
+/// Parses a percentage, or a number out of `max`, into a fraction between 0 and 1.
+fn parse_fraction(text: &str, max: f32) -> Option<f32> {
+    let fraction = match text.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().ok()? / 100.,
+        None => text.parse::<f32>().ok()? / max,
+    };
+    Some(fraction.clamp(0., 1.))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_color_literals() {
+        let text =
+            "a { color: #f00; border: 1px solid rgba(0, 0, 255, 50%); fill: hsl(120deg 100% 50%) }";
+        let literals = find_color_literals(text);
+        assert_eq!(
+            literals
+                .iter()
+                .map(|literal| (&text[literal.range.clone()], literal.format))
+                .collect::<Vec<_>>(),
+            [
+                ("#f00", ColorFormat::Hex),
+                ("rgba(0, 0, 255, 50%)", ColorFormat::Rgb),
+                ("hsl(120deg 100% 50%)", ColorFormat::Hsl),
+            ]
+        );
+        assert_eq!(literals[1].color.a, 0.5);
+        assert_eq!(format_color(literals[2].color, ColorFormat::Hex), "#00ff00");
+
+        // Things that merely look like colors aren't.
+        assert_eq!(
+            find_color_literals("#[derive] &#123; #12345 #fffg issue#123 foorgb(1, 2, 3)"),
+            []
+        );
+    }
+
+    #[test]
+    fn test_format_color() {
+        let (color, format) = parse_color_literal(" #FF800080 ").unwrap();
+        assert_eq!(format, ColorFormat::Hex);
+        assert_eq!(format_color(color, ColorFormat::Hex), "#ff800080");
+        assert_eq!(
+            format_color(color, ColorFormat::Rgb),
+            "rgba(255, 128, 0, 0.5)"
+        );
+        assert_eq!(
+            format_color(color, ColorFormat::Hsl),
+            "hsla(30, 100%, 50%, 0.5)"
+        );
+
+        let (color, _) = parse_color_literal("rgb(255 255 255)").unwrap();
+        assert_eq!(format_color(color, ColorFormat::Hsl), "hsl(0, 0%, 100%)");
+        assert_eq!(parse_color_literal("#fff and more"), None);
+    }
+}
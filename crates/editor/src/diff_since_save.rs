@@ -0,0 +1,154 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use buffer_diff::BufferDiff;
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{AppContext as _, AsyncApp, Entity, Subscription, Task};
+use language::{Buffer, BufferEvent};
+use text::LineEnding;
+use ui::prelude::*;
+
+use crate::{Editor, ToggleDiffSinceSave};
+
+/// How long the buffer has to stay unchanged before it's compared with its file again.
+pub(crate) const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The comparison of an editor's buffer with its file on disk, shown as expanded diff hunks in
+/// place of the git diff, so that the changes a save would make can be previewed.
+pub(crate) struct DiffSinceSave {
+    /// Sends whether the file has to be loaded again, e.g. because the buffer was saved.
+    recalculate_diff_tx: mpsc::UnboundedSender<bool>,
+    _recalculate_diff: Task<Result<()>>,
+    _buffer_subscription: Subscription,
+}
+
+impl Editor {
+    pub fn toggle_diff_since_save(
+        &mut self,
+        _: &ToggleDiffSinceSave,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.diff_since_save.take().is_some() {
+            self.end_temporary_diff_override(cx);
+            cx.notify();
+            return;
+        }
+
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        if !buffer.read(cx).file().is_some_and(|file| file.is_local()) {
+            return;
+        }
+
+        let diff = cx.new(|cx| BufferDiff::new(&buffer.read(cx).text_snapshot(), cx));
+        self.start_temporary_diff_override();
+        self.set_render_diff_hunk_controls(
+            Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+            cx,
+        );
+        self.buffer.update(cx, |multibuffer, cx| {
+            multibuffer.add_diff(diff.clone(), cx);
+        });
+        self.set_expand_all_diff_hunks(cx);
+
+        let (recalculate_diff_tx, recalculate_diff_rx) = mpsc::unbounded();
+        recalculate_diff_tx.unbounded_send(true).ok();
+        let buffer_subscription = cx.subscribe(&buffer, |editor, _, event, _| {
+            let reload_saved_text = match event {
+                BufferEvent::Edited => false,
+                BufferEvent::Saved
+                | BufferEvent::Reloaded
+                | BufferEvent::FileHandleChanged
+                | BufferEvent::LanguageChanged => true,
+                _ => return,
+            };
+            if let Some(diff_since_save) = &editor.diff_since_save {
+                diff_since_save
+                    .recalculate_diff_tx
+                    .unbounded_send(reload_saved_text)
+                    .ok();
+            }
+        });
+
+        self.diff_since_save = Some(DiffSinceSave {
+            recalculate_diff_tx,
+            _recalculate_diff: cx.spawn(async move |_, cx| {
+                recalculate_diff(buffer, diff, recalculate_diff_rx, cx).await
+            }),
+            _buffer_subscription: buffer_subscription,
+        });
+        cx.notify();
+    }
+
+    /// Whether the buffer is shown compared with its file on disk rather than with git.
+    pub fn diff_since_save_enabled(&self) -> bool {
+        self.diff_since_save.is_some()
+    }
+}
+
+async fn recalculate_diff(
+    buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    mut recalculate_diff_rx: mpsc::UnboundedReceiver<bool>,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let mut saved_text = None;
+    while let Some(mut reload_saved_text) = recalculate_diff_rx.next().await {
+        loop {
+            cx.background_executor()
+                .timer(RECALCULATE_DIFF_DEBOUNCE)
+                .await;
+            let mut had_further_changes = false;
+            while let Ok(Some(next_reload_saved_text)) = recalculate_diff_rx.try_next() {
+                reload_saved_text |= next_reload_saved_text;
+                had_further_changes = true;
+            }
+            if !had_further_changes {
+                break;
+            }
+        }
+
+        if reload_saved_text {
+            let load = buffer.read_with(cx, |buffer, cx| {
+                buffer
+                    .file()
+                    .and_then(|file| file.as_local())
+                    .map(|file| file.load(cx))
+            })?;
+            // A file that can't be loaded, e.g. because it was deleted, is diffed as if it was
+            // empty, so that the whole buffer is shown as added.
+            saved_text = match load {
+                Some(load) => load.await.ok().map(|mut text| {
+                    LineEnding::normalize(&mut text);
+                    Arc::new(text)
+                }),
+                None => None,
+            };
+        }
+
+        let (snapshot, language, language_registry) = buffer.read_with(cx, |buffer, _| {
+            (
+                buffer.text_snapshot(),
+                buffer.language().cloned(),
+                buffer.language_registry(),
+            )
+        })?;
+        let diff_snapshot = BufferDiff::update_diff(
+            diff.clone(),
+            snapshot.clone(),
+            saved_text.clone(),
+            reload_saved_text,
+            reload_saved_text,
+            language,
+            language_registry,
+            cx,
+        )
+        .await?;
+        diff.update(cx, |diff, cx| {
+            diff.set_snapshot(diff_snapshot, &snapshot, cx);
+        })?;
+    }
+    Ok(())
+}
@@ -15,6 +15,13 @@ use text::Patch;
 pub use super::tab_map::TextSummary;
 pub type WrapEdit = text::Edit<u32>;
 
+/// Lines longer than this many bytes, such as those in minified files, are wrapped at a fixed
+/// column instead of measuring each of their characters.
+const MAX_MEASURED_LINE_LEN: usize = 64 * 1024;
+/// How many rows of an extremely long line are wrapped before yielding, so that rewrapping it
+/// can be interrupted by a newer edit or wrap width.
+const LONG_LINE_WRAP_ROWS_PER_CHUNK: usize = 1024;
+
 /// Handles soft wrapping of text.
 ///
 /// See the [`display_map` module documentation](crate::display_map) for more information.
@@ -433,6 +440,7 @@ impl WrapSnapshot {
         if row_edits.is_empty() {
             new_transforms = self.transforms.clone();
         } else {
+            let wrap_columns = ((wrap_width / line_wrapper.width_for_char('m')) as u32).max(1);
             let mut row_edits = row_edits.into_iter().peekable();
             let mut old_cursor = self.transforms.cursor::<TabPoint>(());
 
@@ -459,7 +467,8 @@ impl WrapSnapshot {
                     Highlights::default(),
                 );
                 let mut edit_transforms = Vec::<Transform>::new();
-                for _ in edit.new_rows.start..edit.new_rows.end {
+                for row in edit.new_rows.start..edit.new_rows.end {
+                    let mut line_too_long = false;
                     while let Some(chunk) = remaining.take().or_else(|| chunks.next()) {
                         if let Some(ix) = chunk.text.find('\n') {
                             let (prefix, suffix) = chunk.text.split_at(ix + 1);
@@ -480,9 +489,28 @@ impl WrapSnapshot {
                                 line_fragments.push(gpui::LineFragment::text(chunk.text));
                             }
                             line.push_str(chunk.text);
+                            if line.len() > MAX_MEASURED_LINE_LEN {
+                                line_too_long = true;
+                                break;
+                            }
                         }
                     }
 
+                    if line_too_long {
+                        wrap_long_line(&new_tab_snapshot, row, wrap_columns, &mut edit_transforms)
+                            .await;
+                        chunks = new_tab_snapshot.chunks(
+                            cmp::min(TabPoint::new(row + 1, 0), new_tab_snapshot.max_point())
+                                ..new_tab_snapshot.max_point(),
+                            false,
+                            Highlights::default(),
+                        );
+                        line.clear();
+                        line_fragments.clear();
+                        yield_now().await;
+                        continue;
+                    }
+
                     if line.is_empty() {
                         break;
                     }
@@ -1080,6 +1108,44 @@ impl sum_tree::Item for Transform {
     }
 }
 
+/// Wraps an extremely long line every `wrap_columns` columns, computing each wrapped row from
+/// the line's summaries so that the line's text never has to be copied or measured.
+async fn wrap_long_line(
+    tab_snapshot: &TabSnapshot,
+    row: u32,
+    wrap_columns: u32,
+    transforms: &mut Vec<Transform>,
+) {
+    let line_end = TabPoint::new(row, tab_snapshot.line_len(row));
+    let mut start = TabPoint::new(row, 0);
+    let mut wrapped_rows = 0;
+    while line_end.column() - start.column() > wrap_columns {
+        if wrapped_rows > 0 && wrapped_rows % LONG_LINE_WRAP_ROWS_PER_CHUNK == 0 {
+            yield_now().await;
+        }
+        wrapped_rows += 1;
+        let target = TabPoint::new(row, start.column() + wrap_columns);
+        let mut end = tab_snapshot.clip_point(target, Bias::Left);
+        if end <= start {
+            end = tab_snapshot.clip_point(target, Bias::Right);
+        }
+        if end >= line_end {
+            break;
+        }
+        push_isomorphic(transforms, tab_snapshot.text_summary_for_range(start..end));
+        transforms.push(Transform::wrap(0));
+        start = end;
+    }
+
+    let next_row_start = cmp::min(TabPoint::new(row + 1, 0), tab_snapshot.max_point());
+    if start < next_row_start {
+        push_isomorphic(
+            transforms,
+            tab_snapshot.text_summary_for_range(start..next_row_start),
+        );
+    }
+}
+
 fn push_isomorphic(transforms: &mut Vec<Transform>, summary: TextSummary) {
     if let Some(last_transform) = transforms.last_mut()
         && last_transform.is_isomorphic()
@@ -1454,6 +1520,43 @@ mod tests {
         wrap_map.read_with(cx, |map, _| assert!(map.pending_edits.is_empty()));
     }
 
+    #[gpui::test]
+    async fn test_wrap_extremely_long_line(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let text_system = cx.read(|cx| cx.text_system().clone());
+        let font = test_font();
+        let font_size = px(14.0);
+        let wrap_columns = 80;
+        let wrap_width = text_system
+            .line_wrapper(font.clone(), font_size)
+            .width_for_char('m')
+            * (wrap_columns as f32 + 0.5);
+
+        let long_line = "ab ".repeat(MAX_MEASURED_LINE_LEN / 2);
+        let buffer = cx.update(|cx| MultiBuffer::build_simple(&format!("{long_line}\nshort"), cx));
+        let buffer_snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+        let (_, inlay_snapshot) = InlayMap::new(buffer_snapshot);
+        let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
+        let (_, tab_snapshot) = TabMap::new(fold_snapshot, NonZeroU32::new(4).unwrap());
+        let (wrap_map, _) = cx
+            .update(|cx| WrapMap::new(tab_snapshot.clone(), font, font_size, Some(wrap_width), cx));
+        let mut notifications = observe(&wrap_map, cx);
+        if wrap_map.read_with(cx, |map, _| map.is_rewrapping()) {
+            notifications.next().await.unwrap();
+        }
+
+        // The long line is wrapped at a fixed column, while the short one is left alone.
+        let (snapshot, _) = wrap_map.update(cx, |map, cx| map.sync(tab_snapshot, Vec::new(), cx));
+        let text = snapshot.text();
+        let rows = text.lines().collect::<Vec<_>>();
+        assert_eq!(rows.len(), long_line.len().div_ceil(wrap_columns) + 1);
+        assert!(rows[..rows.len() - 1].iter().all(|row| row.len() == wrap_columns
+            || row.len() == long_line.len() % wrap_columns));
+        assert_eq!(rows.last(), Some(&"short"));
+        assert_eq!(rows.concat(), format!("{long_line}short"));
+    }
+
     fn init_test(cx: &mut gpui::TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
@@ -0,0 +1,143 @@
+use std::{
+    any::Any,
+    path::{Component, Path},
+};
+
+use gpui::{Context, ExternalPaths, Window};
+use language::{Bias, language_settings::language_settings};
+use project::ProjectPath;
+use workspace::DraggedSelection;
+
+use crate::Editor;
+
+impl Editor {
+    /// Inserts paths to the project entries or external files dropped onto the editor while
+    /// `alt` is held, formatted with the `drop_path_template` of the language at the drop
+    /// position. Returns whether the paths were inserted.
+    pub(crate) fn insert_dropped_paths(
+        &mut self,
+        dropped: &dyn Any,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !window.modifiers().alt || !self.mode.is_full() || self.read_only(cx) {
+            return false;
+        }
+        let Some(project) = self.project.clone() else {
+            return false;
+        };
+        let dropped_paths = if let Some(selection) = dropped.downcast_ref::<DraggedSelection>() {
+            let project = project.read(cx);
+            selection
+                .items()
+                .filter_map(|entry| {
+                    let path = project.path_for_entry(entry.entry_id, cx)?;
+                    project.absolute_path(&path, cx)
+                })
+                .collect::<Vec<_>>()
+        } else if let Some(paths) = dropped.downcast_ref::<ExternalPaths>() {
+            paths.paths().to_vec()
+        } else {
+            return false;
+        };
+        let Some(position_map) = self.last_position_map.clone() else {
+            return false;
+        };
+        if dropped_paths.is_empty() || !position_map.text_hitbox.is_hovered(window) {
+            return false;
+        }
+
+        let target = position_map
+            .point_for_position(window.mouse_position())
+            .previous_valid;
+        let anchor = position_map
+            .snapshot
+            .display_point_to_anchor(target, Bias::Left);
+        let Some(buffer) = self.buffer.read(cx).buffer_for_anchor(anchor, cx) else {
+            return false;
+        };
+        let buffer = buffer.read(cx);
+        let template = language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
+            .drop_path_template
+            .clone();
+        let directory = project::File::from_dyn(buffer.file())
+            .and_then(|file| {
+                let project_path = ProjectPath {
+                    worktree_id: file.worktree_id(cx),
+                    path: file.path.clone(),
+                };
+                project.read(cx).absolute_path(&project_path, cx)
+            })
+            .and_then(|path| path.parent().map(Path::to_path_buf));
+
+        let text = dropped_paths
+            .iter()
+            .map(|path| format_dropped_path(&template, path, directory.as_deref()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.change_selections(Default::default(), window, cx, |s| {
+            s.select_anchor_ranges([anchor..anchor]);
+        });
+        self.insert(&text, window, cx);
+        window.focus(&self.focus_handle);
+        true
+    }
+}
+
+/// Fills the placeholders of a `drop_path_template` for a file dropped into a buffer located in
+/// `directory`.
+pub(crate) fn format_dropped_path(template: &str, path: &Path, directory: Option<&Path>) -> String {
+    let relative_path = directory
+        .and_then(|directory| relative_path(path, directory))
+        .unwrap_or_else(|| path.to_string_lossy().into_owned());
+    let without_extension = match path.extension() {
+        Some(extension) => relative_path
+            .strip_suffix(&format!(".{}", extension.to_string_lossy()))
+            .unwrap_or(&relative_path),
+        None => &relative_path,
+    };
+    let module_path =
+        if without_extension.starts_with("../") || Path::new(without_extension).is_absolute() {
+            without_extension.to_string()
+        } else {
+            format!("./{without_extension}")
+        };
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file_stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    template
+        .replace("{relative_path}", &relative_path)
+        .replace("{module_path}", &module_path)
+        .replace("{file_name}", &file_name)
+        .replace("{file_stem}", &file_stem)
+}
+
+/// Returns `path` relative to `directory`, with `/` separators, or `None` when they share no
+/// common ancestor.
+fn relative_path(path: &Path, directory: &Path) -> Option<String> {
+    let path_components = path.components().collect::<Vec<_>>();
+    let directory_components = directory.components().collect::<Vec<_>>();
+    let common = path_components
+        .iter()
+        .zip(&directory_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return None;
+    }
+
+    let parents = directory_components[common..]
+        .iter()
+        .map(|_| Component::ParentDir);
+    let relative = parents
+        .chain(path_components[common..].iter().copied())
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    Some(relative.join("/"))
+}
@@ -13,17 +13,24 @@
 //!
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
+mod auto_imports;
 mod blink_manager;
 mod clangd_ext;
 pub mod code_context_menus;
+pub mod color_literals;
+mod diff_since_save;
 pub mod display_map;
+mod dropped_paths;
 mod editor_settings;
 mod editor_settings_controls;
 mod element;
+mod extract_refactorings;
+mod fold_summary;
 mod git;
 mod highlight_matching_bracket;
 mod hover_links;
 pub mod hover_popover;
+mod image_paste;
 mod indent_guides;
 mod inlay_hint_cache;
 pub mod items;
@@ -33,8 +40,11 @@ mod lsp_colors;
 mod lsp_ext;
 mod mouse_context_menu;
 pub mod movement;
+mod peek;
 mod persistence;
 mod proposed_changes_editor;
+pub mod read_only_indicator;
+mod rename_preview;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
@@ -55,7 +65,7 @@ pub use display_map::{ChunkRenderer, ChunkRendererContext, DisplayPoint, FoldPla
 pub use edit_prediction::Direction;
 pub use editor_settings::{
     CurrentLineHighlight, DocumentColorsRenderMode, EditorSettings, HideMouseMode,
-    ScrollBeyondLastLine, ScrollbarAxes, SearchSettings, ShowMinimap,
+    OnExternalChange, ScrollBeyondLastLine, ScrollbarAxes, SearchSettings, ShowMinimap,
 };
 pub use editor_settings_controls::*;
 pub use element::{
@@ -63,7 +73,7 @@ pub use element::{
 };
 pub use git::blame::BlameRenderer;
 pub use hover_popover::hover_markdown_style;
-pub use items::MAX_TAB_TITLE_LEN;
+pub use items::{MAX_TAB_TITLE_LEN, serialized_unsaved_buffers};
 pub use lsp::CompletionContext;
 pub use lsp_ext::lsp_tasks;
 pub use multi_buffer::{
@@ -73,6 +83,7 @@ pub use multi_buffer::{
 pub use proposed_changes_editor::{
     ProposedChangeLocation, ProposedChangesEditor, ProposedChangesEditorToolbar,
 };
+pub use rename_preview::RenamePreview;
 pub use text::Bias;
 
 use ::git::{
@@ -91,11 +102,12 @@ use code_context_menus::{
 };
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
-use dap::TelemetrySpawnLocation;
+use dap::{TelemetrySpawnLocation, debugger_settings::DebuggerSettings};
 use display_map::*;
 use edit_prediction::{EditPredictionProvider, EditPredictionProviderHandle};
 use editor_settings::{GoToDefinitionFallback, Minimap as MinimapSettings};
 use element::{AcceptEditPredictionBinding, LineWithInvisibles, PositionMap, layout_line};
+use extract_refactorings::ExtractRefactoringProvider;
 use futures::{
     FutureExt, StreamExt as _,
     future::{self, Shared, join},
@@ -150,7 +162,7 @@ use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
     CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint,
     Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectPath,
-    ProjectTransaction, TaskSourceKind,
+    ProjectTransaction, TaskRunStatus, TaskSourceKind,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -159,6 +171,7 @@ use project::{
         session::{Session, SessionEvent},
     },
     git_store::{GitStoreEvent, RepositoryEvent},
+    is_task_problems_server,
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
     project_settings::{DiagnosticSeverity, GoToDiagnosticSeverityFilter, ProjectSettings},
 };
@@ -169,7 +182,10 @@ use selections_collection::{
     MutableSelectionsCollection, SelectionsCollection, resolve_selections,
 };
 use serde::{Deserialize, Serialize};
-use settings::{GitGutterSetting, Settings, SettingsLocation, SettingsStore, update_settings_file};
+use settings::{
+    GitGutterSetting, InlineDiagnosticsMode, InlineValuesPosition, Settings, SettingsLocation,
+    SettingsStore, update_settings_file,
+};
 use smallvec::{SmallVec, smallvec};
 use snippet::Snippet;
 use std::{
@@ -186,7 +202,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use task::{ResolvedTask, RunnableTag, TaskTemplate, TaskVariables};
+use task::{ResolvedTask, RunnableTag, TaskId, TaskTemplate, TaskVariables};
 use text::{BufferId, FromAnchor, OffsetUtf16, Rope};
 use theme::{
     ActiveTheme, PlayerColor, StatusColors, SyntaxTheme, Theme, ThemeSettings,
@@ -299,6 +315,7 @@ impl InlayId {
 }
 
 pub enum ActiveDebugLine {}
+enum StaleDebuggerValue {}
 pub enum DebugStackFrameLine {}
 enum DocumentHighlightRead {}
 enum DocumentHighlightWrite {}
@@ -677,6 +694,29 @@ struct InlineDiagnostic {
     severity: lsp::DiagnosticSeverity,
 }
 
+impl InlineDiagnostic {
+    fn color(&self) -> Color {
+        match self.severity {
+            lsp::DiagnosticSeverity::ERROR => Color::Error,
+            lsp::DiagnosticSeverity::WARNING => Color::Warning,
+            lsp::DiagnosticSeverity::INFORMATION => Color::Info,
+            lsp::DiagnosticSeverity::HINT => Color::Hint,
+            _ => Color::Error,
+        }
+    }
+
+    fn truncated_message(&self, max_width: Option<u32>) -> SharedString {
+        match max_width {
+            Some(max_width) if self.message.chars().count() > max_width as usize => {
+                let kept_chars = (max_width as usize).saturating_sub(1);
+                let truncated = self.message.chars().take(kept_chars).collect::<String>();
+                format!("{truncated}…").into()
+            }
+            _ => self.message.clone(),
+        }
+    }
+}
+
 pub enum MenuEditPredictionsPolicy {
     Never,
     ByProvider,
@@ -864,6 +904,13 @@ pub struct ResolvedTasks {
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 struct BufferOffset(usize);
 
+/// A problem reported within a test by the last, failed, run of the test's task.
+#[derive(Clone, Debug)]
+pub(crate) struct TestFailure {
+    message: SharedString,
+    task_id: TaskId,
+}
+
 /// Addons allow storing per-editor state in other crates (e.g. Vim)
 pub trait Addon: 'static {
     fn extend_key_context(&self, _: &mut KeyContext, _: &App) {}
@@ -990,6 +1037,19 @@ enum SelectionDragState {
     },
 }
 
+/// A selection being dragged out of an editor, which other editors accept as a drop.
+#[derive(Clone)]
+struct SelectionDragSource {
+    editor: WeakEntity<Editor>,
+    selection: Selection<Anchor>,
+}
+
+/// The selection currently dragged with the mouse, if any.
+#[derive(Default)]
+struct ActiveSelectionDrag(Option<SelectionDragSource>);
+
+impl Global for ActiveSelectionDrag {}
+
 enum ColumnarSelectionState {
     FromMouse {
         selection_tail: Anchor,
@@ -1017,6 +1077,13 @@ struct PhantomBreakpointIndicator {
 pub struct Editor {
     focus_handle: FocusHandle,
     last_focused_descendant: Option<WeakFocusHandle>,
+    /// The version of the buffer before it was changed on disk while the editor wasn't focused,
+    /// until the change is reviewed or dismissed.
+    external_change: Option<clock::Global>,
+    /// The scroll anchor and position when the editor was last blurred. The position is
+    /// restored when the buffer is reloaded in the background, unless the editor was
+    /// scrolled since, which changed its anchor.
+    scroll_position_on_blur: Option<(ScrollAnchor, gpui::Point<ScrollOffset>)>,
     /// The text buffer being edited
     buffer: Entity<MultiBuffer>,
     /// Map of how text in the buffer should be displayed.
@@ -1047,6 +1114,7 @@ pub struct Editor {
     diagnostics_enabled: bool,
     word_completions_enabled: bool,
     inline_diagnostics: Vec<(Anchor, InlineDiagnostic)>,
+    inline_diagnostic_blocks: Vec<(Anchor, SharedString, CustomBlockId)>,
     soft_wrap_mode_override: Option<language_settings::SoftWrap>,
     hard_wrap: Option<usize>,
     project: Option<Entity<Project>>,
@@ -1097,6 +1165,7 @@ pub struct Editor {
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
     pending_rename: Option<RenameState>,
+    peek: Option<peek::PeekState>,
     searchable: bool,
     cursor_shape: CursorShape,
     current_line_highlight: Option<CurrentLineHighlight>,
@@ -1132,6 +1201,9 @@ pub struct Editor {
     gutter_dimensions: GutterDimensions,
     style: Option<EditorStyle>,
     text_style_refinement: Option<TextStyleRefinement>,
+    /// How much larger or smaller than the buffer font this editor's font is rendered, as
+    /// adjusted with `editor::IncreaseEditorFontSize`.
+    font_size_adjustment: Pixels,
     next_editor_action_id: EditorActionId,
     editor_actions: Rc<
         RefCell<BTreeMap<EditorActionId, Box<dyn Fn(&Editor, &mut Window, &mut Context<Self>)>>>,
@@ -1164,6 +1236,8 @@ pub struct Editor {
     last_position_map: Option<Rc<PositionMap>>,
     expect_bounds_change: Option<Bounds<Pixels>>,
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
+    /// The last task run from each runnable's gutter button, by the runnable's position.
+    runnable_runs: Vec<(Anchor, TaskId)>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
@@ -1176,9 +1250,13 @@ pub struct Editor {
     next_scroll_position: NextScrollCursorCenterTopBottom,
     addons: HashMap<TypeId, Box<dyn Addon>>,
     registered_buffers: HashMap<BufferId, OpenLspBufferHandle>,
+    /// Whether registering the buffers with their language servers is put off until the editor
+    /// is first shown.
+    language_servers_deferred: bool,
     load_diff_task: Option<Shared<Task<()>>>,
     /// Whether we are temporarily displaying a diff other than git's
     temporary_diff_override: bool,
+    diff_since_save: Option<diff_since_save::DiffSinceSave>,
     selection_mark_mode: bool,
     toggle_fold_multiple_buffers: Task<()>,
     _scroll_cursor_center_top_bottom_task: Task<()>,
@@ -1743,6 +1821,19 @@ impl Editor {
         Self::new(EditorMode::full(), buffer, project, window, cx)
     }
 
+    /// Creates an editor for a buffer restored with the workspace, which puts off starting the
+    /// buffer's language servers until the editor is first shown, so that files in background
+    /// tabs don't slow down startup.
+    pub fn for_restored_buffer(
+        buffer: Entity<Buffer>,
+        project: Option<Entity<Project>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        Self::new_internal(EditorMode::full(), buffer, project, None, true, window, cx)
+    }
+
     pub fn for_multibuffer(
         buffer: Entity<MultiBuffer>,
         project: Option<Entity<Project>>,
@@ -1781,7 +1872,7 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        Editor::new_internal(mode, buffer, project, None, window, cx)
+        Editor::new_internal(mode, buffer, project, None, false, window, cx)
     }
 
     fn new_internal(
@@ -1789,6 +1880,7 @@ impl Editor {
         buffer: Entity<MultiBuffer>,
         project: Option<Entity<Project>>,
         display_map: Option<Entity<DisplayMap>>,
+        defer_language_servers: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -1809,34 +1901,17 @@ impl Editor {
         let style = window.text_style();
         let font_size = style.font_size.to_pixels(window.rem_size());
         let editor = cx.entity().downgrade();
+        let weak_buffer = buffer.downgrade();
         let fold_placeholder = FoldPlaceholder {
             constrain_width: false,
             render: Arc::new(move |fold_id, fold_range, cx| {
-                let editor = editor.clone();
-                div()
-                    .id(fold_id)
-                    .bg(cx.theme().colors().ghost_element_background)
-                    .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
-                    .active(|style| style.bg(cx.theme().colors().ghost_element_active))
-                    .rounded_xs()
-                    .size_full()
-                    .cursor_pointer()
-                    .child("⋯")
-                    .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
-                    .on_click(move |_, _window, cx| {
-                        editor
-                            .update(cx, |editor, cx| {
-                                editor.unfold_ranges(
-                                    &[fold_range.start..fold_range.end],
-                                    true,
-                                    false,
-                                    cx,
-                                );
-                                cx.stop_propagation();
-                            })
-                            .ok();
-                    })
-                    .into_any()
+                fold_summary::render_fold_placeholder(
+                    editor.clone(),
+                    &weak_buffer,
+                    fold_id,
+                    fold_range,
+                    cx,
+                )
             }),
             merge_adjacent: true,
             ..FoldPlaceholder::default()
@@ -2072,13 +2147,18 @@ impl Editor {
                 )
                 .shared(),
             );
+            let extract_refactorings =
+                ExtractRefactoringProvider::new(cx.entity().downgrade(), project.downgrade());
             code_action_providers.push(Rc::new(project) as Rc<_>);
+            code_action_providers.push(Rc::new(extract_refactorings) as Rc<_>);
         }
 
         let mut editor = Self {
             focus_handle,
             show_cursor_when_unfocused: false,
             last_focused_descendant: None,
+            external_change: None,
+            scroll_position_on_blur: None,
             buffer: buffer.clone(),
             display_map: display_map.clone(),
             placeholder_display_map: None,
@@ -2099,6 +2179,7 @@ impl Editor {
             show_inline_diagnostics: ProjectSettings::get_global(cx).diagnostics.inline.enabled,
             inline_diagnostics_update: Task::ready(()),
             inline_diagnostics: Vec::new(),
+            inline_diagnostic_blocks: Vec::new(),
             soft_wrap_mode_override,
             diagnostics_max_severity,
             hard_wrap: None,
@@ -2151,6 +2232,7 @@ impl Editor {
             document_highlights_task: None,
             linked_editing_range_task: None,
             pending_rename: None,
+            peek: None,
             searchable: !is_minimap,
             cursor_shape: EditorSettings::get_global(cx)
                 .cursor_shape
@@ -2214,6 +2296,7 @@ impl Editor {
             blame: None,
             blame_subscription: None,
             tasks: BTreeMap::default(),
+            runnable_runs: Vec::new(),
 
             breakpoint_store,
             gutter_breakpoint_indicator: (None, None),
@@ -2255,14 +2338,17 @@ impl Editor {
             next_scroll_position: NextScrollCursorCenterTopBottom::default(),
             addons: HashMap::default(),
             registered_buffers: HashMap::default(),
+            language_servers_deferred: false,
             _scroll_cursor_center_top_bottom_task: Task::ready(()),
             selection_mark_mode: false,
             toggle_fold_multiple_buffers: Task::ready(()),
             serialize_selections: Task::ready(()),
             serialize_folds: Task::ready(()),
             text_style_refinement: None,
+            font_size_adjustment: Pixels::ZERO,
             load_diff_task: load_uncommitted_diff,
             temporary_diff_override: false,
+            diff_since_save: None,
             mouse_cursor_hidden: false,
             minimap: None,
             hide_mouse_mode: EditorSettings::get_global(cx)
@@ -2381,7 +2467,9 @@ impl Editor {
 
             editor.go_to_active_debug_line(window, cx);
 
-            if let Some(buffer) = buffer.read(cx).as_singleton()
+            if defer_language_servers {
+                editor.language_servers_deferred = true;
+            } else if let Some(buffer) = buffer.read(cx).as_singleton()
                 && let Some(project) = editor.project()
             {
                 let handle = project.update(cx, |project, cx| {
@@ -2959,7 +3047,29 @@ impl Editor {
     }
 
     pub fn read_only(&self, cx: &App) -> bool {
-        self.read_only || self.buffer.read(cx).read_only()
+        self.read_only || self.buffer.read(cx).read_only() || self.is_buffer_locked(cx)
+    }
+
+    /// Whether the editor's buffer is protected from edits, e.g. because it's a dependency or
+    /// generated code, in which case it can be made writable with [`ToggleReadOnly`].
+    pub fn is_buffer_locked(&self, cx: &App) -> bool {
+        self.buffer
+            .read(cx)
+            .as_singleton()
+            .is_some_and(|buffer| buffer.read(cx).is_locked())
+    }
+
+    pub fn toggle_read_only(&mut self, _: &ToggleReadOnly, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        if buffer.read(cx).read_only() {
+            return;
+        }
+        buffer.update(cx, |buffer, cx| {
+            let locked = buffer.is_locked();
+            buffer.set_locked(!locked, cx);
+        });
     }
 
     pub fn set_read_only(&mut self, read_only: bool) {
@@ -3182,6 +3292,13 @@ impl Editor {
             if self.git_blame_inline_enabled {
                 self.start_inline_blame_timer(window, cx);
             }
+            if ProjectSettings::get_global(cx)
+                .diagnostics
+                .inline
+                .current_line_only
+            {
+                self.refresh_inline_diagnostic_blocks(cx);
+            }
         }
 
         self.blink_manager.update(cx, BlinkManager::pause_blinking);
@@ -3949,6 +4066,10 @@ impl Editor {
             return true;
         }
 
+        if self.close_peek(window, cx) {
+            return true;
+        }
+
         if hide_hover(self, cx) {
             return true;
         }
@@ -6371,12 +6492,16 @@ impl Editor {
 
         let action_ix = action.item_ix.unwrap_or(actions_menu.selected_item);
         let action = actions_menu.actions.get(action_ix)?;
+        let runnable_position = actions_menu.actions.tasks().map(|tasks| tasks.position);
         let title = action.label();
         let buffer = actions_menu.buffer;
         let workspace = self.workspace()?;
 
         match action {
             CodeActionsItem::Task(task_source_kind, resolved_task) => {
+                if let Some(position) = runnable_position {
+                    self.record_runnable_run(position, resolved_task.id.clone(), cx);
+                }
                 workspace.update(cx, |workspace, cx| {
                     workspace.schedule_resolved_task(
                         task_source_kind,
@@ -8484,13 +8609,19 @@ impl Editor {
 
         let reveal_strategy = action.reveal;
         let task_context = Self::build_tasks_context(&project, &buffer, buffer_row, &tasks, cx);
-        cx.spawn_in(window, async move |_, cx| {
+        cx.spawn_in(window, async move |editor, cx| {
             let context = task_context.await?;
             let (task_source_kind, mut resolved_task) = tasks.resolve(&context).next()?;
 
             let resolved = &mut resolved_task.resolved;
             resolved.reveal = reveal_strategy;
 
+            editor
+                .update(cx, |editor, cx| {
+                    editor.record_runnable_run(tasks.offset, resolved_task.id.clone(), cx)
+                })
+                .ok();
+
             workspace
                 .update_in(cx, |workspace, window, cx| {
                     workspace.schedule_resolved_task(
@@ -8506,6 +8637,111 @@ impl Editor {
         .detach();
     }
 
+    fn record_runnable_run(&mut self, position: Anchor, task_id: TaskId, cx: &App) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let row = position.to_point(&snapshot).row;
+        self.runnable_runs
+            .retain(|(run_position, _)| run_position.to_point(&snapshot).row != row);
+        self.runnable_runs.push((position, task_id));
+    }
+
+    /// Returns the status of the last task run from the runnable on the given row.
+    pub(crate) fn runnable_run_status(
+        &self,
+        row: MultiBufferRow,
+        snapshot: &MultiBufferSnapshot,
+        cx: &App,
+    ) -> Option<TaskRunStatus> {
+        let (_, task_id) = self
+            .runnable_runs
+            .iter()
+            .find(|(position, _)| position.to_point(snapshot).row == row.0)?;
+        let inventory = self
+            .project
+            .as_ref()?
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()?;
+        inventory.read(cx).task_run_status(task_id)
+    }
+
+    /// Returns the failures of the tests whose last run failed, keyed by the rows of their
+    /// runnables. Failures are found from the problems that the run reported within the test.
+    pub(crate) fn test_failures(
+        &self,
+        snapshot: &MultiBufferSnapshot,
+        cx: &App,
+    ) -> BTreeMap<MultiBufferRow, TestFailure> {
+        let mut failures = BTreeMap::new();
+        for (position, task_id) in &self.runnable_runs {
+            let row = MultiBufferRow(position.to_point(snapshot).row);
+            if self.runnable_run_status(row, snapshot, cx) != Some(TaskRunStatus::Failed) {
+                continue;
+            }
+            let Some((buffer_snapshot, buffer_point, _)) =
+                snapshot.point_to_buffer_point(Point::new(row.0, 0))
+            else {
+                continue;
+            };
+            let Some(tasks) = self
+                .tasks
+                .get(&(buffer_snapshot.remote_id(), buffer_point.row))
+            else {
+                continue;
+            };
+            let test_range = tasks.context_range.start.0..tasks.context_range.end.0;
+            let problem = buffer_snapshot
+                .diagnostic_groups(None)
+                .into_iter()
+                .filter(|(server_id, _)| is_task_problems_server(*server_id))
+                .filter_map(|(_, group)| {
+                    let primary = group.entries.get(group.primary_ix)?;
+                    test_range
+                        .contains(&primary.range.start.to_offset(buffer_snapshot))
+                        .then(|| {
+                            (
+                                primary.diagnostic.severity,
+                                primary.diagnostic.message.clone(),
+                            )
+                        })
+                })
+                .min_by_key(|(severity, _)| *severity);
+            if let Some((_, message)) = problem {
+                let message = message.lines().next().unwrap_or_default();
+                failures.insert(
+                    row,
+                    TestFailure {
+                        message: SharedString::new(message),
+                        task_id: task_id.clone(),
+                    },
+                );
+            }
+        }
+        failures
+    }
+
+    /// The messages of the failing tests, shown at the end of their runnables' lines.
+    fn test_failure_inline_diagnostics(&self, cx: &App) -> Vec<(Anchor, InlineDiagnostic)> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.test_failures(&snapshot, cx)
+            .into_iter()
+            .map(|(row, failure)| {
+                let start = Point::new(row.0, 0);
+                (
+                    snapshot.anchor_before(start),
+                    InlineDiagnostic {
+                        message: format!("Test failed: {}", failure.message).into(),
+                        group_id: usize::MAX,
+                        is_primary: true,
+                        start,
+                        severity: lsp::DiagnosticSeverity::ERROR,
+                    },
+                )
+            })
+            .collect()
+    }
+
     fn find_closest_task(
         &mut self,
         cx: &mut Context<Self>,
@@ -8568,38 +8804,62 @@ impl Editor {
         is_active: bool,
         row: DisplayRow,
         breakpoint: Option<(Anchor, Breakpoint, Option<BreakpointSessionState>)>,
+        run_status: Option<TaskRunStatus>,
+        test_failure: Option<TestFailure>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        let color = Color::Muted;
+        let (icon, color) = match run_status {
+            None => (ui::IconName::PlayOutlined, Color::Muted),
+            Some(TaskRunStatus::Running) => (ui::IconName::PlayOutlined, Color::Accent),
+            Some(TaskRunStatus::Succeeded) => (ui::IconName::Check, Color::Success),
+            Some(TaskRunStatus::Failed) => (ui::IconName::XCircle, Color::Error),
+        };
         let position = breakpoint.as_ref().map(|(anchor, _, _)| *anchor);
 
-        IconButton::new(
-            ("run_indicator", row.0 as usize),
-            ui::IconName::PlayOutlined,
-        )
-        .shape(ui::IconButtonShape::Square)
-        .icon_size(IconSize::XSmall)
-        .icon_color(color)
-        .toggle_state(is_active)
-        .on_click(cx.listener(move |editor, e: &ClickEvent, window, cx| {
-            let quick_launch = match e {
-                ClickEvent::Keyboard(_) => true,
-                ClickEvent::Mouse(e) => e.down.button == MouseButton::Left,
-            };
+        IconButton::new(("run_indicator", row.0 as usize), icon)
+            .shape(ui::IconButtonShape::Square)
+            .icon_size(IconSize::XSmall)
+            .icon_color(color)
+            .toggle_state(is_active)
+            .when_some(test_failure.clone(), |button, failure| {
+                button.tooltip(move |window, cx| {
+                    Tooltip::with_meta(
+                        failure.message.clone(),
+                        None,
+                        "Click to show the test's output",
+                        window,
+                        cx,
+                    )
+                })
+            })
+            .on_click(cx.listener(move |editor, e: &ClickEvent, window, cx| {
+                if let Some(failure) = &test_failure {
+                    window.dispatch_action(
+                        Box::new(zed_actions::RevealOutput {
+                            task_id: Some(failure.task_id.0.clone()),
+                        }),
+                        cx,
+                    );
+                    return;
+                }
+                let quick_launch = match e {
+                    ClickEvent::Keyboard(_) => true,
+                    ClickEvent::Mouse(e) => e.down.button == MouseButton::Left,
+                };
 
-            window.focus(&editor.focus_handle(cx));
-            editor.toggle_code_actions(
-                &ToggleCodeActions {
-                    deployed_from: Some(CodeActionSource::RunMenu(row)),
-                    quick_launch,
-                },
-                window,
-                cx,
-            );
-        }))
-        .on_right_click(cx.listener(move |editor, event: &ClickEvent, window, cx| {
-            editor.set_breakpoint_context_menu(row, position, event.position(), window, cx);
-        }))
+                window.focus(&editor.focus_handle(cx));
+                editor.toggle_code_actions(
+                    &ToggleCodeActions {
+                        deployed_from: Some(CodeActionSource::RunMenu(row)),
+                        quick_launch,
+                    },
+                    window,
+                    cx,
+                );
+            }))
+            .on_right_click(cx.listener(move |editor, event: &ClickEvent, window, cx| {
+                editor.set_breakpoint_context_menu(row, position, event.position(), window, cx);
+            }))
     }
 
     pub fn context_menu_visible(&self) -> bool {
@@ -11646,6 +11906,54 @@ impl Editor {
         });
     }
 
+    /// Inserts the text of a selection dragged out of another editor at `target`, and removes
+    /// it from that editor when `is_cut` is true.
+    fn drop_selection_from_editor(
+        &mut self,
+        source: &SelectionDragSource,
+        target: DisplayPoint,
+        is_cut: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(source_editor) = source.editor.upgrade() else {
+            return;
+        };
+        let selection = source.selection.clone();
+        let text = source_editor
+            .read(cx)
+            .buffer
+            .read(cx)
+            .snapshot(cx)
+            .text_for_range(selection.start..selection.end)
+            .collect::<String>();
+        source_editor.update(cx, |source_editor, cx| {
+            source_editor.clear_selection_drag_state();
+            if is_cut {
+                source_editor.edit([(selection.start..selection.end, "")], cx);
+            }
+            cx.notify();
+        });
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = &display_map.buffer_snapshot;
+        let insert_point = display_map
+            .clip_point(target, Bias::Left)
+            .to_point(&display_map);
+        let insert_anchor = buffer.anchor_before(insert_point);
+        let inserted_start = insert_anchor.bias_left(buffer);
+        let inserted_end = insert_anchor.bias_right(buffer);
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                buffer.edit([(insert_anchor..insert_anchor, text)], None, cx);
+            });
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select_anchor_ranges([inserted_start..inserted_end]);
+            });
+        });
+        window.focus(&self.focus_handle);
+    }
+
     pub fn clear_selection_drag_state(&mut self) {
         self.selection_drag_state = SelectionDragState::None;
     }
@@ -12763,12 +13071,75 @@ impl Editor {
         );
     }
 
+    /// Handles the buffer being reloaded from disk, keeping the scroll position and remembering
+    /// the change if it happened while the editor wasn't focused.
+    fn handle_reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_focused(window) {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let Some(version_before_reload) = buffer.read(cx).version_before_reload().cloned() else {
+            return;
+        };
+
+        if let Some((scroll_anchor, scroll_position)) = self.scroll_position_on_blur
+            && scroll_anchor == self.scroll_manager.anchor()
+        {
+            self.set_scroll_position(scroll_position, window, cx);
+            self.scroll_position_on_blur = Some((self.scroll_manager.anchor(), scroll_position));
+        }
+        if EditorSettings::get_global(cx).on_external_change == OnExternalChange::Review {
+            // Keep the oldest unreviewed version, so the review covers every change since.
+            self.external_change.get_or_insert(version_before_reload);
+        }
+    }
+
+    pub fn review_external_changes(
+        &mut self,
+        _: &ReviewExternalChanges,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(previous_version) = self.external_change.take() else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let previous_text = buffer
+            .read(cx)
+            .rope_for_version(&previous_version)
+            .to_string();
+        cx.emit(EditorEvent::TitleChanged);
+        window.dispatch_action(
+            Box::new(DiffExternalChangesData {
+                previous_text,
+                editor: cx.entity(),
+            }),
+            cx,
+        );
+    }
+
+    pub fn dismiss_external_changes(
+        &mut self,
+        _: &DismissExternalChanges,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.external_change.take().is_some() {
+            cx.emit(EditorEvent::TitleChanged);
+        }
+    }
+
     pub fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
         if let Some(item) = cx.read_from_clipboard() {
             let entries = item.entries();
 
             match entries.first() {
+                Some(ClipboardEntry::Image(image)) if self.paste_image(image, window, cx) => {}
                 // For now, we only support applying metadata if there's one string. In the future, we can incorporate all the selections
                 // of all the pasted entries.
                 Some(ClipboardEntry::String(clipboard_string)) if entries.len() == 1 => self
@@ -14571,6 +14942,76 @@ impl Editor {
         Ok(())
     }
 
+    /// Selects the occurrences of the identifier under the cursor within the innermost function
+    /// containing it, or within the whole excerpt outside of functions. Only syntax nodes of the
+    /// same kind as the one under the cursor are selected, skipping comments, strings, and
+    /// fields that happen to share its name.
+    pub fn select_all_occurrences_in_scope(
+        &mut self,
+        _: &SelectAllOccurrencesInScope,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let head = self.selections.newest::<usize>(cx).head();
+        let (word_range, kind) = buffer.surrounding_word(head, None);
+        if word_range.is_empty() || kind != Some(CharKind::Word) {
+            return;
+        }
+        let Some((node, node_range)) = buffer.syntax_ancestor(word_range.start..word_range.start)
+        else {
+            return;
+        };
+        if node_range != word_range {
+            return;
+        }
+        let node_kind = node.kind();
+
+        let scope = buffer
+            .text_object_ranges(word_range.clone(), TreeSitterOptions::default())
+            .filter(|(range, object)| {
+                *object == TextObject::AroundFunction
+                    && range.start <= word_range.start
+                    && range.end >= word_range.end
+            })
+            .map(|(range, _)| range)
+            .min_by_key(|range| range.len())
+            .or_else(|| {
+                let mut excerpt = buffer.excerpt_containing(word_range.clone())?;
+                let buffer_range = excerpt.buffer_range();
+                Some(excerpt.map_range_from_buffer(buffer_range))
+            });
+        let Some(scope) = scope else {
+            return;
+        };
+
+        let word = buffer
+            .text_for_range(word_range.clone())
+            .collect::<String>();
+        let scope_text = buffer.text_for_range(scope.clone()).collect::<String>();
+        let new_selections = scope_text
+            .match_indices(word.as_str())
+            .map(|(ix, _)| scope.start + ix..scope.start + ix + word.len())
+            .filter(|range| {
+                buffer
+                    .syntax_ancestor(range.start..range.start)
+                    .is_some_and(|(node, node_range)| {
+                        node_range == *range && node.kind() == node_kind
+                    })
+            })
+            .collect::<Vec<_>>();
+        if new_selections.is_empty() {
+            return;
+        }
+
+        self.unfold_ranges(&new_selections, false, false, cx);
+        self.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+            selections.select_ranges(new_selections)
+        });
+    }
+
     pub fn select_next(
         &mut self,
         action: &SelectNext,
@@ -17176,6 +17617,58 @@ impl Editor {
         }))
     }
 
+    pub fn confirm_rename_with_preview(
+        &mut self,
+        _: &ConfirmRenameWithPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        // Previews are only supported for local projects, where the edits of a rename can be
+        // requested without applying them.
+        let project = self.project.clone()?;
+        if !project.read(cx).is_local() {
+            return self.confirm_rename(&ConfirmRename, window, cx);
+        }
+
+        let rename = self.take_rename(false, window, cx)?;
+        let workspace = self.workspace()?.downgrade();
+        let (buffer, start) = self
+            .buffer
+            .read(cx)
+            .text_anchor_for_position(rename.range.start, cx)?;
+        let (end_buffer, _) = self
+            .buffer
+            .read(cx)
+            .text_anchor_for_position(rename.range.end, cx)?;
+        if buffer != end_buffer {
+            return None;
+        }
+
+        let old_name = rename.old_name;
+        let new_name = rename.editor.read(cx).text(cx);
+        let rename_edit = project.update(cx, |project, cx| {
+            project.rename_edit(&buffer, start, new_name.clone(), cx)
+        });
+
+        Some(cx.spawn_in(window, async move |_, cx| {
+            let Some((server_id, edit)) = rename_edit.await? else {
+                return Ok(());
+            };
+            cx.update(|window, cx| {
+                RenamePreview::open(
+                    workspace,
+                    project,
+                    server_id,
+                    edit,
+                    format!("Rename: {} → {}", old_name, new_name),
+                    window,
+                    cx,
+                )
+            })?
+            .await
+        }))
+    }
+
     fn take_rename(
         &mut self,
         moving_cursor: bool,
@@ -17558,6 +18051,7 @@ impl Editor {
             group_id: diagnostic.diagnostic.group_id,
             blocks,
         });
+        self.refresh_inline_diagnostic_blocks(cx);
         cx.notify();
     }
 
@@ -17571,6 +18065,7 @@ impl Editor {
             self.display_map.update(cx, |display_map, cx| {
                 display_map.remove_blocks(group.blocks, cx);
             });
+            self.refresh_inline_diagnostic_blocks(cx);
             cx.notify();
         }
     }
@@ -17585,8 +18080,7 @@ impl Editor {
     pub fn disable_diagnostics(&mut self, cx: &mut Context<Self>) {
         self.diagnostics_enabled = false;
         self.dismiss_diagnostics(cx);
-        self.inline_diagnostics_update = Task::ready(());
-        self.inline_diagnostics.clear();
+        self.clear_inline_diagnostics(cx);
     }
 
     pub fn disable_word_completions(&mut self) {
@@ -17643,8 +18137,7 @@ impl Editor {
         self.set_max_diagnostics_severity(new_severity, cx);
         if self.diagnostics_max_severity == DiagnosticSeverity::Off {
             self.active_diagnostics = ActiveDiagnostic::None;
-            self.inline_diagnostics_update = Task::ready(());
-            self.inline_diagnostics.clear();
+            self.clear_inline_diagnostics(cx);
         } else {
             self.refresh_inline_diagnostics(false, window, cx);
         }
@@ -17679,8 +18172,7 @@ impl Editor {
             || !self.show_inline_diagnostics
             || max_severity == DiagnosticSeverity::Off
         {
-            self.inline_diagnostics_update = Task::ready(());
-            self.inline_diagnostics.clear();
+            self.clear_inline_diagnostics(cx);
             return;
         }
 
@@ -17742,12 +18234,150 @@ impl Editor {
             editor
                 .update(cx, |editor, cx| {
                     editor.inline_diagnostics = new_inline_diagnostics;
+                    editor.refresh_inline_diagnostic_blocks(cx);
                     cx.notify();
                 })
                 .ok();
         });
     }
 
+    fn clear_inline_diagnostics(&mut self, cx: &mut Context<Self>) {
+        self.inline_diagnostics_update = Task::ready(());
+        self.inline_diagnostics.clear();
+        self.refresh_inline_diagnostic_blocks(cx);
+    }
+
+    /// Returns the inline diagnostics that pass the severity filter and, if only the current
+    /// line's diagnostics are shown, start on a line with a cursor.
+    fn visible_inline_diagnostics(&self, cx: &App) -> Vec<(Anchor, InlineDiagnostic)> {
+        let settings = &ProjectSettings::get_global(cx).diagnostics.inline;
+        let Some(max_severity) = settings
+            .max_severity
+            .unwrap_or(self.diagnostics_max_severity)
+            .into_lsp()
+        else {
+            return Vec::new();
+        };
+        // Active diagnostics are all shown in the editor already, no need to display them inline
+        let active_diagnostics_group = match &self.active_diagnostics {
+            ActiveDiagnostic::Group(group) => Some(group.group_id),
+            _ => None,
+        };
+        let cursor_rows = settings.current_line_only.then(|| {
+            let snapshot = self.buffer.read(cx).snapshot(cx);
+            self.selections
+                .disjoint_anchors()
+                .iter()
+                .map(|selection| selection.head().to_point(&snapshot).row)
+                .collect::<HashSet<_>>()
+        });
+        self.inline_diagnostics
+            .iter()
+            .filter(|(_, diagnostic)| {
+                diagnostic.severity <= max_severity
+                    && Some(diagnostic.group_id) != active_diagnostics_group
+                    && cursor_rows
+                        .as_ref()
+                        .is_none_or(|rows| rows.contains(&diagnostic.start.row))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Shows each line's most severe inline diagnostic in a block below the line, when inline
+    /// diagnostics are shown below lines.
+    fn refresh_inline_diagnostic_blocks(&mut self, cx: &mut Context<Self>) {
+        let settings = ProjectSettings::get_global(cx).diagnostics.inline;
+        let mut diagnostics_by_row = BTreeMap::<u32, (Anchor, InlineDiagnostic)>::new();
+        if self.inline_diagnostics_enabled()
+            && self.show_inline_diagnostics
+            && settings.mode == InlineDiagnosticsMode::BelowLine
+        {
+            for (anchor, diagnostic) in self.visible_inline_diagnostics(cx) {
+                let sort_key = |diagnostic: &InlineDiagnostic| {
+                    (
+                        diagnostic.severity,
+                        Reverse(diagnostic.is_primary),
+                        diagnostic.start.column,
+                    )
+                };
+                let row = diagnostic.start.row;
+                if diagnostics_by_row
+                    .get(&row)
+                    .is_none_or(|(_, shown)| sort_key(&diagnostic) < sort_key(shown))
+                {
+                    diagnostics_by_row.insert(row, (anchor, diagnostic));
+                }
+            }
+        }
+
+        let new_blocks = diagnostics_by_row
+            .into_values()
+            .map(|(anchor, diagnostic)| {
+                let message = diagnostic.truncated_message(settings.max_width);
+                (anchor, message, diagnostic.color())
+            })
+            .collect::<Vec<_>>();
+        if new_blocks
+            .iter()
+            .map(|(anchor, message, _)| (anchor, message))
+            .eq(self
+                .inline_diagnostic_blocks
+                .iter()
+                .map(|(anchor, message, _)| (anchor, message)))
+        {
+            return;
+        }
+
+        let old_blocks = mem::take(&mut self.inline_diagnostic_blocks);
+        if !old_blocks.is_empty() {
+            self.remove_blocks(
+                old_blocks
+                    .into_iter()
+                    .map(|(_, _, block_id)| block_id)
+                    .collect(),
+                None,
+                cx,
+            );
+        }
+        let block_ids = self.insert_blocks(
+            new_blocks.iter().map(|(anchor, message, color)| {
+                let message = message.clone();
+                let color = *color;
+                BlockProperties {
+                    style: BlockStyle::Flex,
+                    placement: BlockPlacement::Below(*anchor),
+                    height: Some(1),
+                    render: Arc::new(move |cx: &mut BlockContext| {
+                        h_flex()
+                            .id(cx.block_id)
+                            .h(cx.line_height)
+                            .pl(cx.anchor_x)
+                            .child(
+                                div()
+                                    .px_1()
+                                    .rounded_xs()
+                                    .bg(color.color(cx).opacity(0.05))
+                                    .text_color(color.color(cx))
+                                    .text_sm()
+                                    .font_family(cx.editor_style.text.font().family)
+                                    .child(message.clone()),
+                            )
+                            .into_any_element()
+                    }),
+                    priority: 0,
+                }
+            }),
+            None,
+            cx,
+        );
+        self.inline_diagnostic_blocks = new_blocks
+            .into_iter()
+            .zip(block_ids)
+            .map(|((anchor, message, _), block_id)| (anchor, message, block_id))
+            .collect();
+    }
+
     fn pull_diagnostics(
         &mut self,
         buffer_id: Option<BufferId>,
@@ -19075,6 +19705,7 @@ impl Editor {
             self.buffer.clone(),
             None,
             Some(self.display_map.clone()),
+            false,
             window,
             cx,
         );
@@ -19157,6 +19788,86 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn increase_editor_font_size(
+        &mut self,
+        _: &IncreaseEditorFontSize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_font_size_adjustment(self.font_size_adjustment + px(1.0), cx);
+    }
+
+    pub fn decrease_editor_font_size(
+        &mut self,
+        _: &DecreaseEditorFontSize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_font_size_adjustment(self.font_size_adjustment - px(1.0), cx);
+    }
+
+    pub fn reset_editor_font_size(
+        &mut self,
+        _: &ResetEditorFontSize,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_font_size_adjustment(Pixels::ZERO, cx);
+    }
+
+    pub fn font_size_adjustment(&self) -> Pixels {
+        self.font_size_adjustment
+    }
+
+    /// Renders this editor's font larger or smaller than the buffer font by the given amount,
+    /// persisting it in the workspace.
+    pub fn set_font_size_adjustment(&mut self, adjustment: Pixels, cx: &mut Context<Self>) {
+        if self.font_size_adjustment == adjustment {
+            return;
+        }
+        self.font_size_adjustment = adjustment;
+        cx.notify();
+
+        if let Some(workspace_id) = self.workspace.as_ref().and_then(|workspace| workspace.1) {
+            let item_id = cx.entity().entity_id().as_u64() as ItemId;
+            cx.background_spawn(async move {
+                DB.save_font_size_adjustment(item_id, workspace_id, adjustment.into())
+                    .await
+                    .log_err();
+            })
+            .detach();
+        }
+    }
+
+    /// Returns the font family, size, and line height to render buffer text with, taking the
+    /// settings of the buffer's language and this editor's font size adjustment into account.
+    fn buffer_font_overrides(&self, cx: &App) -> (SharedString, Pixels, f32) {
+        let settings = ThemeSettings::get_global(cx);
+        let multi_buffer = self.buffer.read(cx);
+        let language_settings = multi_buffer
+            .is_singleton()
+            .then(|| multi_buffer.language_settings(cx));
+        let language_settings = language_settings.as_deref();
+
+        let font_family = language_settings
+            .and_then(|settings| settings.font_family.clone())
+            .unwrap_or_else(|| settings.buffer_font.family.clone());
+        let font_size = language_settings
+            .and_then(|settings| settings.font_size)
+            .map_or_else(
+                || settings.buffer_font_size(cx),
+                |font_size| theme::adjusted_font_size(font_size, cx),
+            );
+        let line_height = language_settings
+            .and_then(|settings| settings.line_height)
+            .map_or(settings.buffer_line_height, Into::into);
+        (
+            font_family,
+            theme::clamp_font_size(font_size + self.font_size_adjustment),
+            line_height.value(),
+        )
+    }
+
     pub fn set_text_style_refinement(&mut self, style: TextStyleRefinement) {
         self.text_style_refinement = Some(style);
     }
@@ -20594,6 +21305,7 @@ impl Editor {
         if !self.inline_value_cache.enabled {
             let inlays = std::mem::take(&mut self.inline_value_cache.inlays);
             self.splice_inlays(&inlays, Vec::new(), cx);
+            self.clear_highlights::<StaleDebuggerValue>(cx);
             return;
         }
 
@@ -20645,30 +21357,97 @@ impl Editor {
             editor
                 .update(cx, |editor, cx| {
                     let snapshot = editor.buffer.read(cx).snapshot(cx);
+                    let values_position = DebuggerSettings::get_global(cx).inline_values_position;
                     let mut new_inlays = Vec::default();
+                    let mut stale_values = Vec::default();
 
                     for (excerpt_id, buffer_snapshot, _) in snapshot.excerpts() {
                         let buffer_id = buffer_snapshot.remote_id();
-                        buffer_inline_values
-                            .get(&buffer_id)
-                            .into_iter()
-                            .flatten()
-                            .for_each(|hint| {
-                                let inlay = Inlay::debugger(
-                                    post_inc(&mut editor.next_inlay_id),
-                                    Anchor::in_buffer(excerpt_id, buffer_id, hint.position),
-                                    hint.text(),
-                                );
-                                if !inlay.text().chars().contains(&'\n') {
-                                    new_inlays.push(inlay);
-                                }
+                        let Some(hints) = buffer_inline_values.get(&buffer_id) else {
+                            continue;
+                        };
+                        // Values of the variables outside of the scope execution is in are dimmed.
+                        let current_scope = current_execution_position
+                            .filter(|position| position.buffer_id == Some(buffer_id))
+                            .and_then(|position| {
+                                innermost_debugger_scope(buffer_snapshot, position.text_anchor)
                             });
+                        let is_stale = |offset: usize| {
+                            current_scope
+                                .as_ref()
+                                .is_some_and(|scope| !scope.contains(&offset))
+                        };
+
+                        let values = match values_position {
+                            InlineValuesPosition::Inline => hints
+                                .iter()
+                                .map(|hint| {
+                                    let offset =
+                                        buffer_snapshot.summary_for_anchor::<usize>(&hint.position);
+                                    (hint.position, hint.text().to_string(), is_stale(offset))
+                                })
+                                .collect::<Vec<_>>(),
+                            InlineValuesPosition::EndOfLine => {
+                                let mut lines = BTreeMap::<u32, (String, bool)>::default();
+                                for hint in hints {
+                                    let offset =
+                                        buffer_snapshot.summary_for_anchor::<usize>(&hint.position);
+                                    let (name_range, _) =
+                                        buffer_snapshot.surrounding_word(offset, None);
+                                    let (text, stale) = lines
+                                        .entry(buffer_snapshot.offset_to_point(offset).row)
+                                        .or_insert_with(|| (String::from("  "), true));
+                                    if text.len() > 2 {
+                                        text.push_str(", ");
+                                    }
+                                    text.extend(buffer_snapshot.text_for_range(name_range));
+                                    text.push_str(&hint.text().to_string());
+                                    *stale &= is_stale(offset);
+                                }
+                                lines
+                                    .into_iter()
+                                    .map(|(row, (text, stale))| {
+                                        let end_of_line =
+                                            Point::new(row, buffer_snapshot.line_len(row));
+                                        (buffer_snapshot.anchor_after(end_of_line), text, stale)
+                                    })
+                                    .collect()
+                            }
+                        };
+
+                        for (position, text, stale) in values {
+                            if text.contains('\n') {
+                                continue;
+                            }
+                            let inlay = Inlay::debugger(
+                                post_inc(&mut editor.next_inlay_id),
+                                Anchor::in_buffer(excerpt_id, buffer_id, position),
+                                text,
+                            );
+                            if stale {
+                                stale_values.push(InlayHighlight {
+                                    inlay: inlay.id,
+                                    inlay_position: inlay.position,
+                                    range: 0..inlay.text().len(),
+                                });
+                            }
+                            new_inlays.push(inlay);
+                        }
                     }
 
                     let mut inlay_ids = new_inlays.iter().map(|inlay| inlay.id).collect();
                     std::mem::swap(&mut editor.inline_value_cache.inlays, &mut inlay_ids);
 
                     editor.splice_inlays(&inlay_ids, new_inlays, cx);
+                    editor.clear_highlights::<StaleDebuggerValue>(cx);
+                    editor.highlight_inlays::<StaleDebuggerValue>(
+                        stale_values,
+                        HighlightStyle {
+                            fade_out: Some(0.6),
+                            ..HighlightStyle::default()
+                        },
+                        cx,
+                    );
                 })
                 .ok()?;
             Some(())
@@ -20832,10 +21611,17 @@ impl Editor {
                 cx.notify();
             }
             multi_buffer::Event::DirtyChanged => cx.emit(EditorEvent::DirtyChanged),
-            multi_buffer::Event::Saved => cx.emit(EditorEvent::Saved),
-            multi_buffer::Event::FileHandleChanged
-            | multi_buffer::Event::Reloaded
-            | multi_buffer::Event::BufferDiffChanged => cx.emit(EditorEvent::TitleChanged),
+            multi_buffer::Event::Saved => {
+                self.external_change = None;
+                cx.emit(EditorEvent::Saved);
+            }
+            multi_buffer::Event::Reloaded => {
+                self.handle_reload(window, cx);
+                cx.emit(EditorEvent::TitleChanged);
+            }
+            multi_buffer::Event::FileHandleChanged | multi_buffer::Event::BufferDiffChanged => {
+                cx.emit(EditorEvent::TitleChanged)
+            }
             multi_buffer::Event::DiagnosticsUpdated => {
                 self.update_diagnostics_state(window, cx);
             }
@@ -20937,6 +21723,8 @@ impl Editor {
             if self.show_inline_diagnostics != show_inline_diagnostics {
                 self.show_inline_diagnostics = show_inline_diagnostics;
                 self.refresh_inline_diagnostics(false, window, cx);
+            } else {
+                self.refresh_inline_diagnostic_blocks(cx);
             }
 
             if self.git_blame_inline_enabled != inline_blame_enabled {
@@ -21528,6 +22316,8 @@ impl Editor {
     }
 
     pub fn handle_blur(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.scroll_position_on_blur =
+            Some((self.scroll_manager.anchor(), self.scroll_position(cx)));
         self.blink_manager.update(cx, BlinkManager::disable);
         self.buffer
             .update(cx, |buffer, cx| buffer.remove_active_selections(cx));
@@ -21874,6 +22664,15 @@ impl Editor {
             };
         }
 
+        if let Some(adjustment) = DB
+            .get_font_size_adjustment(item_id, workspace_id)
+            .log_err()
+            .flatten()
+            .flatten()
+        {
+            self.font_size_adjustment = px(adjustment as f32);
+        }
+
         self.read_scroll_position_from_db(item_id, workspace_id, window, cx);
     }
 
@@ -22945,6 +23744,8 @@ impl CompletionProvider for Entity<Project> {
     ) -> Task<Result<Vec<CompletionResponse>>> {
         self.update(cx, |project, cx| {
             let snippets = snippet_completions(project, buffer, buffer_position, cx);
+            let auto_imports =
+                auto_imports::auto_import_completions(project, buffer, buffer_position, cx);
             let project_completions = project.completions(buffer, buffer_position, options, cx);
             cx.background_spawn(async move {
                 let mut responses = project_completions.await?;
@@ -22952,6 +23753,7 @@ impl CompletionProvider for Entity<Project> {
                 if !snippets.completions.is_empty() {
                     responses.push(snippets);
                 }
+                auto_imports::push_auto_imports(&mut responses, auto_imports.await?);
                 Ok(responses)
             })
         })
@@ -22979,6 +23781,12 @@ impl CompletionProvider for Entity<Project> {
         push_to_history: bool,
         cx: &mut Context<Editor>,
     ) -> Task<Result<Option<language::Transaction>>> {
+        let completion = completions.borrow()[completion_index].clone();
+        if let Some(transaction) =
+            auto_imports::apply_auto_import(&buffer, &completion, push_to_history, cx)
+        {
+            return Task::ready(Ok(transaction));
+        }
         self.update(cx, |project, cx| {
             project.lsp_store().update(cx, |lsp_store, cx| {
                 lsp_store.apply_additional_edits_for_completion(
@@ -23159,6 +23967,21 @@ impl SemanticsProvider for Entity<Project> {
     }
 }
 
+/// Returns the innermost debugger scope of the buffer's language containing `position`.
+fn innermost_debugger_scope(
+    buffer: &BufferSnapshot,
+    position: text::Anchor,
+) -> Option<Range<usize>> {
+    let offset = buffer.summary_for_anchor::<usize>(&position);
+    buffer
+        .debug_variables_query(offset..offset)
+        .filter(|(range, kind)| {
+            *kind == language::DebuggerTextObject::Scope && range.contains(&offset)
+        })
+        .map(|(range, _)| range)
+        .min_by_key(|range| range.len())
+}
+
 fn inlay_hint_settings(
     location: Anchor,
     snapshot: &MultiBufferSnapshot,
@@ -23605,7 +24428,14 @@ impl Focusable for Editor {
 }
 
 impl Render for Editor {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if mem::take(&mut self.language_servers_deferred) {
+            cx.defer_in(window, |editor, window, cx| {
+                editor.register_buffers_with_language_servers(cx);
+                editor.update_lsp_data(false, None, window, cx);
+            });
+        }
+
         let settings = ThemeSettings::get_global(cx);
 
         let mut text_style = match self.mode {
@@ -23619,16 +24449,19 @@ impl Render for Editor {
                 line_height: relative(settings.buffer_line_height.value()),
                 ..Default::default()
             },
-            EditorMode::Full { .. } | EditorMode::Minimap { .. } => TextStyle {
-                color: cx.theme().colors().editor_foreground,
-                font_family: settings.buffer_font.family.clone(),
-                font_features: settings.buffer_font.features.clone(),
-                font_fallbacks: settings.buffer_font.fallbacks.clone(),
-                font_size: settings.buffer_font_size(cx).into(),
-                font_weight: settings.buffer_font.weight,
-                line_height: relative(settings.buffer_line_height.value()),
-                ..Default::default()
-            },
+            EditorMode::Full { .. } | EditorMode::Minimap { .. } => {
+                let (font_family, font_size, line_height) = self.buffer_font_overrides(cx);
+                TextStyle {
+                    color: cx.theme().colors().editor_foreground,
+                    font_family,
+                    font_features: settings.buffer_font.features.clone(),
+                    font_fallbacks: settings.buffer_font.fallbacks.clone(),
+                    font_size: font_size.into(),
+                    font_weight: settings.buffer_font.weight,
+                    line_height: relative(line_height),
+                    ..Default::default()
+                }
+            }
         };
         if let Some(text_style_refinement) = &self.text_style_refinement {
             text_style.refine(text_style_refinement)
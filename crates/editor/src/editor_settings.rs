@@ -7,8 +7,8 @@ use project::project_settings::DiagnosticSeverity;
 pub use settings::{
     CurrentLineHighlight, DisplayIn, DocumentColorsRenderMode, DoubleClickInMultibuffer,
     GoToDefinitionFallback, HideMouseMode, MinimapThumb, MinimapThumbBorder, MultiCursorModifier,
-    ScrollBeyondLastLine, ScrollbarDiagnostics, SeedQuerySetting, ShowMinimap, SnippetSortOrder,
-    VsCodeSettings,
+    OnExternalChange, ScrollBeyondLastLine, ScrollbarDiagnostics, SeedQuerySetting, ShowMinimap,
+    SnippetSortOrder, VsCodeSettings,
 };
 use settings::{Settings, SettingsContent};
 use ui::scrollbars::{ScrollbarVisibility, ShowScrollbar};
@@ -29,8 +29,10 @@ pub struct EditorSettings {
     pub scrollbar: Scrollbar,
     pub minimap: Minimap,
     pub gutter: Gutter,
+    pub fold_placeholder: FoldPlaceholderSettings,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
     pub vertical_scroll_margin: f64,
+    pub typewriter_scrolling: bool,
     pub autoscroll_on_clicks: bool,
     pub horizontal_scroll_margin: f32,
     pub scroll_sensitivity: f32,
@@ -55,8 +57,10 @@ pub struct EditorSettings {
     pub diagnostics_max_severity: Option<DiagnosticSeverity>,
     pub inline_code_actions: bool,
     pub drag_and_drop_selection: DragAndDropSelection,
+    pub image_paste: ImagePaste,
     pub lsp_document_colors: DocumentColorsRenderMode,
     pub minimum_contrast_for_highlights: f32,
+    pub on_external_change: OnExternalChange,
 }
 #[derive(Debug, Clone)]
 pub struct Jupyter {
@@ -124,6 +128,13 @@ pub struct Gutter {
     pub folds: bool,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FoldPlaceholderSettings {
+    pub summary: bool,
+    pub line_count: bool,
+    pub diagnostics: bool,
+}
+
 /// Forcefully enable or disable the scrollbar for each axis
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct ScrollbarAxes {
@@ -152,6 +163,26 @@ pub struct DragAndDropSelection {
     pub delay: u64,
 }
 
+/// How to handle images pasted into Markdown files.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct ImagePaste {
+    /// When true, pasting an image into a Markdown file saves it into the assets directory
+    /// and inserts a link to it.
+    ///
+    /// Default: true
+    pub enabled: bool,
+
+    /// The directory pasted images are saved into, relative to the directory of the Markdown file.
+    ///
+    /// Default: "assets"
+    pub directory: String,
+
+    /// When true, asks for the name of a pasted image before saving it.
+    ///
+    /// Default: true
+    pub prompt_for_name: bool,
+}
+
 /// Default options for buffer and project search items.
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct SearchSettings {
@@ -181,10 +212,12 @@ impl Settings for EditorSettings {
         let scrollbar = editor.scrollbar.unwrap();
         let minimap = editor.minimap.unwrap();
         let gutter = editor.gutter.unwrap();
+        let fold_placeholder = editor.fold_placeholder.unwrap();
         let axes = scrollbar.axes.unwrap();
         let toolbar = editor.toolbar.unwrap();
         let search = editor.search.unwrap();
         let drag_and_drop_selection = editor.drag_and_drop_selection.unwrap();
+        let image_paste = editor.image_paste.unwrap();
         Self {
             cursor_blink: editor.cursor_blink.unwrap(),
             cursor_shape: editor.cursor_shape.map(Into::into),
@@ -229,8 +262,14 @@ impl Settings for EditorSettings {
                 breakpoints: gutter.breakpoints.unwrap(),
                 folds: gutter.folds.unwrap(),
             },
+            fold_placeholder: FoldPlaceholderSettings {
+                summary: fold_placeholder.summary.unwrap(),
+                line_count: fold_placeholder.line_count.unwrap(),
+                diagnostics: fold_placeholder.diagnostics.unwrap(),
+            },
             scroll_beyond_last_line: editor.scroll_beyond_last_line.unwrap(),
             vertical_scroll_margin: editor.vertical_scroll_margin.unwrap() as f64,
+            typewriter_scrolling: editor.typewriter_scrolling.unwrap(),
             autoscroll_on_clicks: editor.autoscroll_on_clicks.unwrap(),
             horizontal_scroll_margin: editor.horizontal_scroll_margin.unwrap(),
             scroll_sensitivity: editor.scroll_sensitivity.unwrap(),
@@ -266,8 +305,14 @@ impl Settings for EditorSettings {
                 enabled: drag_and_drop_selection.enabled.unwrap(),
                 delay: drag_and_drop_selection.delay.unwrap(),
             },
+            image_paste: ImagePaste {
+                enabled: image_paste.enabled.unwrap(),
+                directory: image_paste.directory.unwrap(),
+                prompt_for_name: image_paste.prompt_for_name.unwrap(),
+            },
             lsp_document_colors: editor.lsp_document_colors.unwrap(),
             minimum_contrast_for_highlights: editor.minimum_contrast_for_highlights.unwrap(),
+            on_external_change: editor.on_external_change.unwrap(),
         }
     }
 
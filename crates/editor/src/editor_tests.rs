@@ -3,12 +3,14 @@ use crate::{
     JoinLines,
     code_context_menus::CodeContextMenu,
     edit_prediction_tests::FakeEditPredictionProvider,
+    editor_settings::FoldPlaceholderSettings,
+    fold_summary::FoldSummary,
     linked_editing_ranges::LinkedEditingRanges,
     scroll::scroll_amount::ScrollAmount,
     test::{
         assert_text_with_selections, build_editor,
         editor_lsp_test_context::{EditorLspTestContext, git_commit_lang},
-        editor_test_context::EditorTestContext,
+        editor_test_context::{EditorTestContext, assert_state_with_diff},
         select_ranges,
     },
 };
@@ -16,15 +18,16 @@ use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus, DiffHunkStatus, DiffHunkS
 use collections::HashMap;
 use futures::{StreamExt, channel::oneshot};
 use gpui::{
-    BackgroundExecutor, DismissEvent, Rgba, SemanticVersion, TestAppContext, UpdateGlobal,
-    VisualTestContext, WindowBounds, WindowOptions, div,
+    BackgroundExecutor, DismissEvent, Image, ImageFormat, Rgba, SemanticVersion, TestAppContext,
+    UpdateGlobal, VisualTestContext, WindowBounds, WindowOptions, div,
 };
 use indoc::indoc;
 use language::{
     BracketPairConfig,
     Capability::ReadWrite,
-    DiagnosticSourceKind, FakeLspAdapter, IndentGuideSettings, LanguageConfig,
-    LanguageConfigOverride, LanguageMatcher, LanguageName, Override, Point,
+    Diagnostic, DiagnosticEntry, DiagnosticSourceKind, FakeLspAdapter, IndentGuideSettings,
+    LanguageConfig, LanguageConfigOverride, LanguageMatcher, LanguageName, Override, Point,
+    PointUtf16, Unclipped,
     language_settings::{
         CompletionSettingsContent, FormatterList, LanguageSettingsContent, LspInsertMode,
         SelectedFormatter,
@@ -37,14 +40,14 @@ use multi_buffer::{IndentGuide, PathKey};
 use parking_lot::Mutex;
 use pretty_assertions::{assert_eq, assert_ne};
 use project::{
-    FakeFs,
+    FakeFs, Fs as _,
     debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
     project_settings::LspSettings,
 };
 use serde_json::{self, json};
 use settings::{
     AllLanguageSettingsContent, IndentGuideBackgroundColoring, IndentGuideColoring,
-    ProjectSettingsContent,
+    InlineDiagnosticsSettingsContent, ProjectSettingsContent,
 };
 use std::{cell::RefCell, future::Future, rc::Rc, sync::atomic::AtomicBool, time::Instant};
 use std::{
@@ -1150,6 +1153,69 @@ fn test_fold_action_multiple_line_breaks(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_fold_summary(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.update(|cx| {
+        MultiBuffer::build_simple(
+            &"
+                fn main() {
+                    // A comment that is long enough to be truncated in the summary
+                    let x = 1;
+
+                    x
+                }
+            "
+            .unindent(),
+            cx,
+        )
+    });
+    let snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+    let fold_range =
+        |range: Range<Point>| snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end);
+    let all = FoldPlaceholderSettings {
+        summary: true,
+        line_count: true,
+        diagnostics: true,
+    };
+
+    assert_eq!(
+        FoldSummary::new(
+            &snapshot,
+            fold_range(Point::new(0, 11)..Point::new(5, 0)),
+            &all
+        ),
+        FoldSummary {
+            text: Some("// A comment that is long enough to be t…".into()),
+            hidden_lines: 4,
+            error_count: 0,
+            warning_count: 0,
+        }
+    );
+    assert_eq!(
+        FoldSummary::new(
+            &snapshot,
+            fold_range(Point::new(1, 4)..Point::new(4, 5)),
+            &all
+        )
+        .hidden_lines,
+        3
+    );
+    assert_eq!(
+        FoldSummary::new(
+            &snapshot,
+            fold_range(Point::new(0, 11)..Point::new(5, 0)),
+            &FoldPlaceholderSettings {
+                summary: false,
+                line_count: false,
+                diagnostics: false,
+            }
+        ),
+        FoldSummary::default()
+    );
+}
+
 #[gpui::test]
 fn test_fold_at_level(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -2396,6 +2462,87 @@ async fn test_autoscroll(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_typewriter_scrolling(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.typewriter_scrolling = Some(true);
+            });
+        });
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let line_height = cx.update_editor(|editor, window, _| {
+        editor
+            .style()
+            .unwrap()
+            .text
+            .line_height_in_pixels(window.rem_size())
+    });
+    let window = cx.window;
+    cx.simulate_window_resize(window, size(px(1000.), 6. * line_height));
+
+    cx.set_state(
+        r#"ˇone
+            two
+            three
+            four
+            five
+            six
+            seven
+            eight
+            nine
+            ten
+        "#,
+    );
+
+    // Moving within the first half of the viewport does not scroll, as the
+    // cursor cannot be centered above the first line.
+    cx.update_editor(|editor, window, cx| {
+        editor.move_down(&Default::default(), window, cx);
+        editor.move_down(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 0.0)
+        );
+    });
+
+    // Past that, every cursor movement scrolls the text to keep the cursor centered.
+    cx.update_editor(|editor, window, cx| {
+        editor.move_down(&Default::default(), window, cx);
+        editor.move_down(&Default::default(), window, cx);
+        editor.move_down(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 3.0)
+        );
+    });
+    cx.update_editor(|editor, window, cx| {
+        editor.move_down(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 4.0)
+        );
+    });
+    cx.update_editor(|editor, window, cx| {
+        editor.move_up(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 3.0)
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_move_page_up_page_down(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -8192,6 +8339,57 @@ async fn test_select_all_matches(cx: &mut TestAppContext) {
     cx.assert_editor_state("«abcˇ»");
 }
 
+#[gpui::test]
+async fn test_select_all_occurrences_in_scope(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(Default::default(), cx).await;
+
+    // Fields, comments, and other functions sharing the name are left out.
+    cx.set_state(indoc! {"
+        fn a(s: &mut S) {
+            let vaˇlue = 1;
+            let other = value + 1; // value
+            s.value = value;
+        }
+
+        fn b() {
+            let value = 2;
+        }
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.select_all_occurrences_in_scope(&SelectAllOccurrencesInScope, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn a(s: &mut S) {
+            let «valueˇ» = 1;
+            let other = «valueˇ» + 1; // value
+            s.value = «valueˇ»;
+        }
+
+        fn b() {
+            let value = 2;
+        }
+    "});
+
+    // Nothing happens outside of identifiers.
+    cx.set_state(indoc! {"
+        fn a() {
+            // vaˇlue
+            let value = 1;
+        }
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.select_all_occurrences_in_scope(&SelectAllOccurrencesInScope, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn a() {
+            // vaˇlue
+            let value = 1;
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_select_all_matches_does_not_scroll(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -11207,6 +11405,175 @@ async fn test_redo_after_noop_format(cx: &mut TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_external_changes_marker(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_file(path!("/file.txt"), "one\n".into()).await;
+    let project = Project::test(fs.clone(), [path!("/file.txt").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/file.txt"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+    let save_file = async |text: &str| {
+        fs.save(
+            path!("/file.txt").as_ref(),
+            &text.into(),
+            Default::default(),
+        )
+        .await
+        .unwrap()
+    };
+    let has_external_changes =
+        |cx: &mut VisualTestContext| cx.read(|cx| editor.has_external_changes(cx));
+    let previous_text = |cx: &mut VisualTestContext| {
+        editor.read_with(cx, |editor, cx| {
+            let version = editor.external_change.clone()?;
+            let buffer = editor.buffer.read(cx).as_singleton()?;
+            Some(buffer.read(cx).rope_for_version(&version).to_string())
+        })
+    };
+
+    // Changes made while the editor is focused aren't marked.
+    editor.update_in(cx, |editor, window, cx| {
+        window.focus(&editor.focus_handle(cx))
+    });
+    save_file("two\n").await;
+    cx.run_until_parked();
+    assert_eq!(editor.read_with(cx, |editor, cx| editor.text(cx)), "two\n");
+    assert!(!has_external_changes(cx));
+
+    // Changes made while it isn't are marked until they're reviewed, with the text from before
+    // the first of them.
+    cx.update(|window, _| window.blur());
+    save_file("three\n").await;
+    cx.run_until_parked();
+    save_file("four\n").await;
+    cx.run_until_parked();
+    assert_eq!(editor.read_with(cx, |editor, cx| editor.text(cx)), "four\n");
+    assert!(has_external_changes(cx));
+    assert_eq!(previous_text(cx).as_deref(), Some("two\n"));
+    editor.update_in(cx, |editor, window, cx| {
+        editor.review_external_changes(&ReviewExternalChanges, window, cx)
+    });
+    assert!(!has_external_changes(cx));
+
+    // Or dismissed.
+    save_file("five\n").await;
+    cx.run_until_parked();
+    assert_eq!(previous_text(cx).as_deref(), Some("four\n"));
+    editor.update_in(cx, |editor, window, cx| {
+        editor.dismiss_external_changes(&DismissExternalChanges, window, cx)
+    });
+    assert!(!has_external_changes(cx));
+
+    // Files are reloaded without marking them when changes aren't reviewed.
+    cx.update(|_, cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.editor.on_external_change = Some(OnExternalChange::Reload);
+            });
+        });
+    });
+    save_file("six\n").await;
+    cx.run_until_parked();
+    assert_eq!(editor.read_with(cx, |editor, cx| editor.text(cx)), "six\n");
+    assert!(!has_external_changes(cx));
+}
+
+#[gpui::test]
+async fn test_reload_keeps_scroll_position_on_blur(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let lines = |count: usize, prefix: &str| {
+        (0..count)
+            .map(|ix| format!("{prefix}{ix}\n"))
+            .collect::<String>()
+    };
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_file(path!("/file.txt"), lines(100, "line ").into_bytes())
+        .await;
+    let project = Project::test(fs.clone(), [path!("/file.txt").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/file.txt"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+    let prepend_lines = async |count: usize, cx: &mut VisualTestContext| {
+        let text = editor.read_with(cx, |editor, cx| editor.text(cx));
+        fs.save(
+            path!("/file.txt").as_ref(),
+            &format!("{}{text}", lines(count, "new ")).into(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        cx.run_until_parked();
+    };
+    let scroll_top =
+        |cx: &mut VisualTestContext| editor.update(cx, |editor, cx| editor.scroll_position(cx).y);
+
+    editor.update_in(cx, |editor, window, cx| {
+        window.focus(&editor.focus_handle(cx));
+        editor.set_scroll_position(gpui::Point::new(0., 10.), window, cx);
+    });
+    cx.update(|window, _| window.blur());
+
+    // Reloading keeps the rows that were visible when the editor was blurred.
+    prepend_lines(5, cx).await;
+    assert_eq!(scroll_top(cx), 10.);
+
+    // Unless the editor was scrolled since, in which case it keeps showing the same text.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.set_scroll_position(gpui::Point::new(0., 20.), window, cx);
+    });
+    prepend_lines(5, cx).await;
+    assert_eq!(scroll_top(cx), 25.);
+}
+
+#[gpui::test]
+async fn test_cursor_blink_with_reduced_motion(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let blink_interval = Duration::from_millis(500);
+    let blink_manager = cx.new(|cx| BlinkManager::new(blink_interval, cx));
+    let visible =
+        |cx: &mut TestAppContext| blink_manager.read_with(cx, |manager, _| manager.visible());
+
+    blink_manager.update(cx, |manager, cx| manager.enable(cx));
+    assert!(visible(cx));
+    cx.executor().advance_clock(blink_interval);
+    assert!(!visible(cx));
+
+    // With reduced motion, the cursor stays visible.
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.accessibility = Some(settings::AccessibilitySettingsContent {
+                    reduced_motion: Some(settings::AccessibilityPreference::On),
+                    ..Default::default()
+                });
+            });
+        });
+    });
+    assert!(visible(cx));
+    for _ in 0..4 {
+        cx.executor().advance_clock(blink_interval);
+        assert!(visible(cx));
+    }
+}
+
 #[gpui::test]
 async fn test_multibuffer_format_during_save(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -16715,38 +17082,223 @@ async fn go_to_prev_overlapping_diagnostic(executor: BackgroundExecutor, cx: &mu
 }
 
 #[gpui::test]
-async fn test_go_to_hunk(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+async fn test_inline_diagnostics_below_line(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx, |_| {});
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.diagnostics.get_or_insert_default().inline =
+                    Some(InlineDiagnosticsSettingsContent {
+                        enabled: Some(true),
+                        update_debounce_ms: Some(0),
+                        mode: Some(InlineDiagnosticsMode::BelowLine),
+                        current_line_only: Some(true),
+                        max_width: Some(10),
+                        ..InlineDiagnosticsSettingsContent::default()
+                    });
+            });
+        });
+    });
 
     let mut cx = EditorTestContext::new(cx).await;
-
-    let diff_base = r#"
-        use some::mod;
-
-        const A: u32 = 42;
-
-        fn main() {
-            println!("hello");
-
-            println!("world");
-        }
-        "#
-    .unindent();
-
-    // Edits are modified, removed, modified, added
-    cx.set_state(
-        &r#"
-        use some::modified;
-
-        ˇ
-        fn main() {
-            println!("hello there");
-
-            println!("around the");
-            println!("world");
-        }
-        "#
-        .unindent(),
+    let lsp_store =
+        cx.update_editor(|editor, _, cx| editor.project().unwrap().read(cx).lsp_store());
+    cx.set_state(indoc! {"
+        ˇlet a = b;
+        let c = d;
+    "});
+    cx.update(|_, cx| {
+        lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .update_diagnostics(
+                    LanguageServerId(0),
+                    lsp::PublishDiagnosticsParams {
+                        uri: lsp::Uri::from_file_path(path!("/root/file")).unwrap(),
+                        version: None,
+                        diagnostics: vec![
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 8),
+                                    lsp::Position::new(0, 9),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::ERROR),
+                                message: "cannot find value `b`".to_string(),
+                                ..Default::default()
+                            },
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(1, 8),
+                                    lsp::Position::new(1, 9),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                message: "unused d".to_string(),
+                                ..Default::default()
+                            },
+                        ],
+                    },
+                    None,
+                    DiagnosticSourceKind::Pushed,
+                    &[],
+                    cx,
+                )
+                .unwrap()
+        });
+    });
+    executor.run_until_parked();
+
+    let block_messages = |cx: &mut EditorTestContext| {
+        cx.update_editor(|editor, _, _| {
+            editor
+                .inline_diagnostic_blocks
+                .iter()
+                .map(|(_, message, _)| message.to_string())
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(
+        block_messages(&mut cx),
+        vec!["cannot fi…"],
+        "Only the cursor line's diagnostic should be shown, truncated to the maximum width"
+    );
+
+    cx.update_editor(|editor, window, cx| {
+        editor.move_down(&MoveDown, window, cx);
+    });
+    executor.run_until_parked();
+    assert_eq!(block_messages(&mut cx), vec!["unused d"]);
+}
+
+#[gpui::test]
+async fn test_failing_test_decorations(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc! {"
+        ˇ#[test]
+        fn test_sum() {
+            assert_eq!(sum(2, 2), 5);
+        }
+    "});
+    let (lsp_store, inventory) = cx.update_editor(|editor, _, cx| {
+        let project = editor.project().unwrap().read(cx);
+        let inventory = project.task_store().read(cx).task_inventory().cloned();
+        (project.lsp_store(), inventory.unwrap())
+    });
+    let task_id = TaskId("cargo test -- test_sum".to_string());
+    cx.update_editor(|editor, _, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let buffer = editor.buffer().read(cx).as_singleton().unwrap();
+        let buffer_id = buffer.read(cx).remote_id();
+        let position = snapshot.anchor_before(Point::new(1, 0));
+        editor.insert_tasks(
+            (buffer_id, 1),
+            RunnableTasks {
+                templates: Vec::new(),
+                offset: position,
+                column: 0,
+                extra_variables: HashMap::default(),
+                context_range: BufferOffset(8)..BufferOffset(snapshot.len()),
+            },
+        );
+        editor.record_runnable_run(position, task_id.clone(), cx);
+    });
+    cx.update(|_, cx| {
+        inventory.update(cx, |inventory, cx| {
+            inventory.set_task_run_status(task_id.clone(), Some(TaskRunStatus::Failed), cx)
+        })
+    });
+    let server_id = cx.update(|_, cx| {
+        inventory.update(cx, |inventory, _| {
+            inventory.task_problems_server_id("cargo test -- test_sum")
+        })
+    });
+    cx.update(|_, cx| {
+        lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .update_diagnostic_entries(
+                    server_id,
+                    PathBuf::from(path!("/root/file")),
+                    None,
+                    None,
+                    vec![DiagnosticEntry {
+                        range: Unclipped(PointUtf16::new(2, 4))
+                            ..Unclipped(PointUtf16::new(2, u32::MAX)),
+                        diagnostic: Diagnostic {
+                            message: "assertion `left == right` failed\n  left: 4\n right: 5"
+                                .to_string(),
+                            severity: lsp::DiagnosticSeverity::ERROR,
+                            is_primary: true,
+                            ..Diagnostic::default()
+                        },
+                    }],
+                    cx,
+                )
+                .unwrap()
+        });
+    });
+    executor.run_until_parked();
+
+    let failure_messages = |cx: &mut EditorTestContext| {
+        cx.update_editor(|editor, _, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            editor
+                .test_failures(&snapshot, cx)
+                .into_iter()
+                .map(|(row, failure)| (row.0, failure.message.to_string()))
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(
+        failure_messages(&mut cx),
+        vec![(1, "assertion `left == right` failed".to_string())],
+        "The failure should be shown on the test's runnable row"
+    );
+
+    cx.update(|_, cx| {
+        inventory.update(cx, |inventory, cx| {
+            inventory.set_task_run_status(task_id.clone(), Some(TaskRunStatus::Succeeded), cx)
+        })
+    });
+    assert_eq!(
+        failure_messages(&mut cx),
+        Vec::new(),
+        "The failure should be cleared once the test passes"
+    );
+}
+
+#[gpui::test]
+async fn test_go_to_hunk(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let diff_base = r#"
+        use some::mod;
+
+        const A: u32 = 42;
+
+        fn main() {
+            println!("hello");
+
+            println!("world");
+        }
+        "#
+    .unindent();
+
+    // Edits are modified, removed, modified, added
+    cx.set_state(
+        &r#"
+        use some::modified;
+
+        ˇ
+        fn main() {
+            println!("hello there");
+
+            println!("around the");
+            println!("world");
+        }
+        "#
+        .unindent(),
     );
 
     cx.set_head_text(&diff_base);
@@ -21448,6 +22000,62 @@ async fn test_goto_definition_with_find_all_references_fallback(cx: &mut TestApp
     });
 }
 
+#[gpui::test]
+async fn test_peek_definition(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            definition_provider: Some(lsp::OneOf::Left(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+    cx.lsp
+        .set_request_handler::<lsp::request::GotoDefinition, _, _>(move |params, _| async move {
+            Ok(Some(lsp::GotoDefinitionResponse::Scalar(lsp::Location {
+                uri: params.text_document_position_params.text_document.uri,
+                range: lsp::Range::new(lsp::Position::new(4, 3), lsp::Position::new(4, 6)),
+            })))
+        });
+
+    let original_state = r#"fn one() {
+        let mut a = ˇtwo();
+    }
+
+    fn two() {}"#
+        .unindent();
+    cx.set_state(&original_state);
+    cx.update_editor(|editor, window, cx| editor.peek_definition(&PeekDefinition, window, cx))
+        .unwrap()
+        .await
+        .expect("Failed to peek definition");
+    cx.run_until_parked();
+
+    // Peeking neither moves the cursor nor opens another editor.
+    cx.assert_editor_state(&original_state);
+    let editors = cx.update_workspace(|workspace, _, cx| {
+        workspace.items_of_type::<Editor>(cx).collect::<Vec<_>>()
+    });
+    assert_eq!(editors.len(), 1);
+    cx.update_editor(|editor, window, cx| {
+        let peek_editor = editor.peek_editor().expect("Should show a peek").clone();
+        assert!(peek_editor.read(cx).text(cx).contains("fn two() {}"));
+        assert!(peek_editor.focus_handle(cx).is_focused(window));
+    });
+
+    cx.dispatch_action(Cancel);
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        assert!(
+            editor.peek_editor().is_none(),
+            "Escape should close the peek"
+        );
+        assert!(editor.focus_handle(cx).is_focused(window));
+    });
+    cx.assert_editor_state(&original_state);
+}
+
 #[gpui::test]
 async fn test_goto_definition_no_fallback(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -23164,6 +23772,76 @@ async fn test_rename_without_prepare(cx: &mut TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_rename_with_preview(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let capabilities = lsp::ServerCapabilities {
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        ..Default::default()
+    };
+    let mut cx = EditorLspTestContext::new_rust(capabilities, cx).await;
+
+    cx.set_state(indoc! {"
+        struct Fˇoo {}
+        fn f(_: Foo) {}
+    "});
+
+    cx.update_editor(|e, window, cx| e.rename(&Rename, window, cx))
+        .expect("Prepare rename was not started")
+        .await
+        .expect("Prepare rename failed");
+
+    let mut rename_handler =
+        cx.set_request_handler::<lsp::request::Rename, _, _>(move |url, _, _| async move {
+            let edit = |line, character| lsp::TextEdit {
+                range: lsp::Range {
+                    start: lsp::Position { line, character },
+                    end: lsp::Position {
+                        line,
+                        character: character + 3,
+                    },
+                },
+                new_text: "FooRenamed".to_string(),
+            };
+            Ok(Some(lsp::WorkspaceEdit::new(
+                std::collections::HashMap::from_iter(Some((url, vec![edit(0, 7), edit(1, 8)]))),
+            )))
+        });
+    let rename_task = cx
+        .update_editor(|e, window, cx| {
+            e.confirm_rename_with_preview(&ConfirmRenameWithPreview, window, cx)
+        })
+        .expect("Confirm rename was not started");
+    rename_handler.next().await.unwrap();
+    rename_task.await.expect("Confirm rename failed");
+    cx.run_until_parked();
+
+    // Nothing is renamed until the preview is applied.
+    cx.assert_editor_state(indoc! {"
+        struct Fˇoo {}
+        fn f(_: Foo) {}
+    "});
+
+    cx.update_workspace(|workspace, window, cx| {
+        let preview = workspace
+            .active_item_as::<RenamePreview>(cx)
+            .expect("Rename preview was not opened");
+        preview.update(cx, |preview, cx| {
+            preview.toggle_edit(0, 1, cx);
+            preview.apply(window, cx);
+        });
+    });
+    cx.run_until_parked();
+
+    cx.assert_editor_state(indoc! {"
+        struct FooRenamedˇ {}
+        fn f(_: Foo) {}
+    "});
+    cx.update_workspace(|workspace, _, cx| {
+        assert!(workspace.active_item_as::<RenamePreview>(cx).is_none());
+    });
+}
+
 #[gpui::test]
 async fn test_tree_sitter_brackets_newline_insertion(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -26169,6 +26847,114 @@ async fn test_paste_url_from_other_app_creates_markdown_link_over_selected_text(
     ));
 }
 
+#[test]
+fn test_format_dropped_path() {
+    use crate::dropped_paths::format_dropped_path;
+
+    let directory = Path::new(path!("/a/src/components"));
+    assert_eq!(
+        format_dropped_path(
+            "import {file_stem} from \"{module_path}\";",
+            Path::new(path!("/a/src/components/button/button.tsx")),
+            Some(directory),
+        ),
+        "import button from \"./button/button\";"
+    );
+    assert_eq!(
+        format_dropped_path(
+            "import {file_stem} from \"{module_path}\";",
+            Path::new(path!("/a/src/util/format.ts")),
+            Some(directory),
+        ),
+        "import format from \"../util/format\";"
+    );
+    assert_eq!(
+        format_dropped_path(
+            "[{file_name}]({relative_path})",
+            Path::new(path!("/a/docs/README.md")),
+            Some(directory),
+        ),
+        "[README.md](../../docs/README.md)"
+    );
+    assert_eq!(
+        format_dropped_path(
+            "#include \"{relative_path}\"",
+            Path::new(path!("/a/src/components/widget.h")),
+            Some(directory),
+        ),
+        "#include \"widget.h\""
+    );
+    assert_eq!(
+        format_dropped_path("{relative_path}", Path::new(path!("/b/notes.txt")), None),
+        path!("/b/notes.txt")
+    );
+}
+
+#[gpui::test]
+async fn test_paste_image_into_markdown_file(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings
+                    .editor
+                    .image_paste
+                    .get_or_insert_default()
+                    .prompt_for_name = Some(false);
+            });
+        });
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/root"), json!({ "docs": { "notes.md": "" } }))
+        .await;
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/docs/notes.md"), cx)
+        })
+        .await
+        .unwrap();
+    let markdown_language = Arc::new(Language::new(
+        LanguageConfig {
+            name: "Markdown".into(),
+            ..LanguageConfig::default()
+        },
+        None,
+    ));
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_language(Some(markdown_language), cx)
+    });
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+
+    let image = Image::from_bytes(ImageFormat::Png, vec![1, 2, 3]);
+    for _ in 0..2 {
+        editor.update_in(cx, |editor, window, cx| {
+            cx.write_to_clipboard(ClipboardItem::new_image(&image));
+            editor.paste(&Paste, window, cx);
+        });
+        cx.run_until_parked();
+    }
+
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "![image](assets/image.png)![image-1](assets/image-1.png)"
+    );
+    for path in [
+        path!("/root/docs/assets/image.png"),
+        path!("/root/docs/assets/image-1.png"),
+    ] {
+        assert_eq!(
+            fs.load_bytes(Path::new(path)).await.unwrap(),
+            vec![1, 2, 3],
+            "{path}"
+        );
+    }
+}
+
 #[gpui::test]
 async fn test_paste_url_from_zed_copy_creates_markdown_link_over_selected_text(
     cx: &mut gpui::TestAppContext,
@@ -26462,6 +27248,32 @@ async fn test_race_in_multibuffer_save(cx: &mut TestAppContext) {
     cx.update(|_, cx| assert!(editor.is_dirty(cx)));
 }
 
+#[gpui::test]
+async fn test_language_and_editor_font_size_overrides(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.languages.0.insert(
+            "Rust".into(),
+            LanguageSettingsContent {
+                font_size: Some(20.),
+                ..Default::default()
+            },
+        );
+    });
+    let mut cx = EditorLspTestContext::new_rust(Default::default(), cx).await;
+
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(editor.buffer_font_overrides(cx).1, px(20.));
+
+        editor.increase_editor_font_size(&IncreaseEditorFontSize, window, cx);
+        editor.increase_editor_font_size(&IncreaseEditorFontSize, window, cx);
+        assert_eq!(editor.font_size_adjustment(), px(2.));
+        assert_eq!(editor.buffer_font_overrides(cx).1, px(22.));
+
+        editor.reset_editor_font_size(&ResetEditorFontSize, window, cx);
+        assert_eq!(editor.buffer_font_overrides(cx).1, px(20.));
+    });
+}
+
 #[track_caller]
 fn extract_color_inlays(editor: &Editor, cx: &App) -> Vec<Rgba> {
     editor
@@ -26471,3 +27283,79 @@ fn extract_color_inlays(editor: &Editor, cx: &App) -> Vec<Rgba> {
         .map(Rgba::from)
         .collect()
 }
+
+#[gpui::test]
+async fn test_toggle_diff_since_save(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "file.txt": "one\ntwo\nthree\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/file.txt"), cx)
+        })
+        .await
+        .unwrap();
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let editor = cx.new_window_entity(|window, cx| {
+        Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx)
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_diff_since_save(&ToggleDiffSinceSave, window, cx);
+    });
+    buffer.update(cx, |buffer, cx| buffer.edit([(4..7, "TWO")], None, cx));
+    cx.executor()
+        .advance_clock(diff_since_save::RECALCULATE_DIFF_DEBOUNCE);
+    cx.run_until_parked();
+    assert_state_with_diff(
+        &editor,
+        cx,
+        &"
+          ˇone
+        - two
+        + TWO
+          three
+        "
+        .unindent(),
+    );
+
+    // Saving the buffer leaves nothing to preview.
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    cx.executor()
+        .advance_clock(diff_since_save::RECALCULATE_DIFF_DEBOUNCE);
+    cx.run_until_parked();
+    assert_state_with_diff(&editor, cx, "ˇone\nTWO\nthree\n");
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+    cx.executor()
+        .advance_clock(diff_since_save::RECALCULATE_DIFF_DEBOUNCE);
+    cx.run_until_parked();
+    assert_state_with_diff(
+        &editor,
+        cx,
+        &"
+        + zero
+          ˇone
+          TWO
+          three
+        "
+        .unindent(),
+    );
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_diff_since_save(&ToggleDiffSinceSave, window, cx);
+        assert!(!editor.diff_since_save_enabled());
+    });
+}
@@ -1,5 +1,5 @@
 use crate::{
-    ActiveDiagnostic, BlockId, CURSORS_VISIBLE_FOR, ChunkRendererContext, ChunkReplacement,
+    ActiveSelectionDrag, BlockId, CURSORS_VISIBLE_FOR, ChunkRendererContext, ChunkReplacement,
     CodeActionSource, ColumnarMode, ConflictsOurs, ConflictsOursMarker, ConflictsOuter,
     ConflictsTheirs, ConflictsTheirsMarker, ContextMenuPlacement, CursorShape, CustomBlockId,
     DisplayDiffHunk, DisplayPoint, DisplayRow, DocumentHighlightRead, DocumentHighlightWrite,
@@ -8,8 +8,8 @@ use crate::{
     HandleInput, HoveredCursor, InlayHintRefreshReason, JumpData, LineDown, LineHighlight, LineUp,
     MAX_LINE_LEN, MINIMAP_FONT_SIZE, MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts, PageDown,
     PageUp, PhantomBreakpointIndicator, Point, RowExt, RowRangeExt, SelectPhase,
-    SelectedTextHighlight, Selection, SelectionDragState, SoftWrap, StickyHeaderExcerpt, ToPoint,
-    ToggleFold, ToggleFoldAll,
+    SelectedTextHighlight, Selection, SelectionDragSource, SelectionDragState, SoftWrap,
+    StickyHeaderExcerpt, ToPoint, ToggleFold, ToggleFoldAll,
     code_context_menus::{CodeActionsMenu, MENU_ASIDE_MAX_WIDTH, MENU_ASIDE_MIN_WIDTH, MENU_GAP},
     display_map::{
         Block, BlockContext, BlockStyle, ChunkRendererId, DisplaySnapshot, EditorMargins,
@@ -68,7 +68,7 @@ use project::{
 };
 use settings::{
     GitGutterSetting, GitHunkStyleSetting, IndentGuideBackgroundColoring, IndentGuideColoring,
-    Settings,
+    InlineDiagnosticsMode, Settings,
 };
 use smallvec::{SmallVec, smallvec};
 use std::{
@@ -85,7 +85,7 @@ use std::{
 };
 use sum_tree::Bias;
 use text::{BufferId, SelectionGoal};
-use theme::{ActiveTheme, Appearance, BufferLineHeight, PlayerColor};
+use theme::{AccessibilitySettings, ActiveTheme, Appearance, BufferLineHeight, PlayerColor};
 use ui::utils::ensure_minimum_contrast;
 use ui::{
     ButtonLike, ContextMenu, Indicator, KeyBinding, POPOVER_Y_PADDING, Tooltip, h_flex, prelude::*,
@@ -270,12 +270,17 @@ impl EditorElement {
         register_action(editor, window, Editor::move_line_down);
         register_action(editor, window, Editor::transpose);
         register_action(editor, window, Editor::rewrap);
+        register_action(editor, window, Editor::increase_editor_font_size);
+        register_action(editor, window, Editor::decrease_editor_font_size);
+        register_action(editor, window, Editor::reset_editor_font_size);
         register_action(editor, window, Editor::cut);
         register_action(editor, window, Editor::kill_ring_cut);
         register_action(editor, window, Editor::kill_ring_yank);
         register_action(editor, window, Editor::copy);
         register_action(editor, window, Editor::copy_and_trim);
         register_action(editor, window, Editor::diff_clipboard_with_selection);
+        register_action(editor, window, Editor::review_external_changes);
+        register_action(editor, window, Editor::dismiss_external_changes);
         register_action(editor, window, Editor::paste);
         register_action(editor, window, Editor::undo);
         register_action(editor, window, Editor::redo);
@@ -350,6 +355,7 @@ impl EditorElement {
         register_action(editor, window, |editor, action, window, cx| {
             editor.select_all_matches(action, window, cx).log_err();
         });
+        register_action(editor, window, Editor::select_all_occurrences_in_scope);
         register_action(editor, window, Editor::select_line);
         register_action(editor, window, Editor::split_selection_into_lines);
         register_action(editor, window, Editor::add_selection_above);
@@ -453,6 +459,7 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_tab_bar);
         register_action(editor, window, Editor::toggle_line_numbers);
         register_action(editor, window, Editor::toggle_relative_line_numbers);
+        register_action(editor, window, Editor::toggle_read_only);
         register_action(editor, window, Editor::toggle_indent_guides);
         register_action(editor, window, Editor::toggle_inlay_hints);
         register_action(editor, window, Editor::toggle_edit_predictions);
@@ -479,6 +486,7 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_git_blame_inline);
         register_action(editor, window, Editor::open_git_blame_commit);
         register_action(editor, window, Editor::toggle_selected_diff_hunks);
+        register_action(editor, window, Editor::toggle_diff_since_save);
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
@@ -507,6 +515,20 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.peek_definition(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.peek_references(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
         register_action(editor, window, Editor::restart_language_server);
         register_action(editor, window, Editor::stop_language_server);
         register_action(editor, window, Editor::show_character_palette);
@@ -559,6 +581,13 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.confirm_rename_with_preview(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
         register_action(editor, window, |editor, action, window, cx| {
             if let Some(task) = editor.find_all_references(action, window, cx) {
                 task.detach_and_log_err(cx);
@@ -873,6 +902,24 @@ impl EditorElement {
         let pending_nonempty_selections = editor.has_pending_nonempty_selection();
         let point_for_position = position_map.point_for_position(event.position);
 
+        if let Some(source) = cx
+            .try_global::<ActiveSelectionDrag>()
+            .and_then(|drag| drag.0.clone())
+            && source.editor != cx.entity().downgrade()
+            && text_hitbox.is_hovered(window)
+        {
+            cx.default_global::<ActiveSelectionDrag>().0 = None;
+            editor.drop_selection_from_editor(
+                &source,
+                point_for_position.previous_valid,
+                is_cut_drop(event),
+                window,
+                cx,
+            );
+            cx.stop_propagation();
+            return;
+        }
+
         match editor.selection_drag_state {
             SelectionDragState::ReadyToDrag {
                 selection: _,
@@ -897,23 +944,26 @@ impl EditorElement {
                 }
             }
             SelectionDragState::Dragging { ref selection, .. } => {
+                // Let other editors accept the selection until every editor saw the mouse up.
+                window.defer(cx, |_, cx| {
+                    cx.default_global::<ActiveSelectionDrag>().0 = None;
+                });
                 let snapshot = editor.snapshot(window, cx);
                 let selection_display = selection.map(|anchor| anchor.to_display_point(&snapshot));
-                if !point_for_position.intersects_selection(&selection_display)
-                    && text_hitbox.is_hovered(window)
-                {
-                    let is_cut = !(cfg!(target_os = "macos") && event.modifiers.alt
-                        || cfg!(not(target_os = "macos")) && event.modifiers.control);
+                let dropped_inside = text_hitbox.is_hovered(window);
+                if !point_for_position.intersects_selection(&selection_display) && dropped_inside {
                     editor.move_selection_on_drop(
                         &selection.clone(),
                         point_for_position.previous_valid,
-                        is_cut,
+                        is_cut_drop(event),
                         window,
                         cx,
                     );
                 }
                 editor.selection_drag_state = SelectionDragState::None;
-                cx.stop_propagation();
+                if dropped_inside {
+                    cx.stop_propagation();
+                }
                 cx.notify();
                 return;
             }
@@ -1068,6 +1118,10 @@ impl EditorElement {
                             reversed: false,
                             goal: SelectionGoal::None,
                         };
+                        cx.default_global::<ActiveSelectionDrag>().0 = Some(SelectionDragSource {
+                            editor: cx.entity().downgrade(),
+                            selection: selection.clone(),
+                        });
                         editor.selection_drag_state = SelectionDragState::Dragging {
                             selection: selection.clone(),
                             drop_cursor,
@@ -1709,6 +1763,11 @@ impl EditorElement {
                         block_width,
                         origin: point(x, y),
                         line_height,
+                        thickness: if AccessibilitySettings::get_global(cx).high_contrast {
+                            CursorLayout::HIGH_CONTRAST_THICKNESS
+                        } else {
+                            CursorLayout::THICKNESS
+                        },
                         shape: selection.cursor_shape,
                         block_text,
                         cursor_name: None,
@@ -2142,47 +2201,18 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut App,
     ) -> HashMap<DisplayRow, AnyElement> {
-        let max_severity = match self
-            .editor
-            .read(cx)
-            .inline_diagnostics_enabled()
-            .then(|| {
-                ProjectSettings::get_global(cx)
-                    .diagnostics
-                    .inline
-                    .max_severity
-                    .unwrap_or_else(|| self.editor.read(cx).diagnostics_max_severity)
-                    .into_lsp()
-            })
-            .flatten()
-        {
-            Some(max_severity) => max_severity,
-            None => return HashMap::default(),
-        };
-
-        let active_diagnostics_group =
-            if let ActiveDiagnostic::Group(group) = &self.editor.read(cx).active_diagnostics {
-                Some(group.group_id)
-            } else {
-                None
-            };
-
+        let settings = ProjectSettings::get_global(cx).diagnostics.inline;
         let diagnostics_by_rows = self.editor.update(cx, |editor, cx| {
             let snapshot = editor.snapshot(window, cx);
-            editor
-                .inline_diagnostics
-                .iter()
-                .filter(|(_, diagnostic)| diagnostic.severity <= max_severity)
-                .filter(|(_, diagnostic)| match active_diagnostics_group {
-                    Some(active_diagnostics_group) => {
-                        // Active diagnostics are all shown in the editor already, no need to display them inline
-                        diagnostic.group_id != active_diagnostics_group
-                    }
-                    None => true,
-                })
-                .map(|(point, diag)| (point.to_display_point(&snapshot), diag.clone()))
-                .skip_while(|(point, _)| point.row() < start_row)
-                .take_while(|(point, _)| point.row() < end_row)
+            let show_diagnostics = editor.inline_diagnostics_enabled()
+                && settings.mode == InlineDiagnosticsMode::EndOfLine;
+            show_diagnostics
+                .then(|| editor.visible_inline_diagnostics(cx))
+                .into_iter()
+                .flatten()
+                .chain(editor.test_failure_inline_diagnostics(cx))
+                .map(|(point, diag)| (point.to_display_point(&snapshot), diag))
+                .filter(|(point, _)| (start_row..end_row).contains(&point.row()))
                 .filter(|(point, _)| !row_block_types.contains_key(&point.row()))
                 .fold(HashMap::default(), |mut acc, (point, diagnostic)| {
                     acc.entry(point.row())
@@ -2196,22 +2226,8 @@ impl EditorElement {
             return HashMap::default();
         }
 
-        let severity_to_color = |sev: &lsp::DiagnosticSeverity| match sev {
-            &lsp::DiagnosticSeverity::ERROR => Color::Error,
-            &lsp::DiagnosticSeverity::WARNING => Color::Warning,
-            &lsp::DiagnosticSeverity::INFORMATION => Color::Info,
-            &lsp::DiagnosticSeverity::HINT => Color::Hint,
-            _ => Color::Error,
-        };
-
-        let padding = ProjectSettings::get_global(cx).diagnostics.inline.padding as f32 * em_width;
-        let min_x = self.column_pixels(
-            ProjectSettings::get_global(cx)
-                .diagnostics
-                .inline
-                .min_column as usize,
-            window,
-        );
+        let padding = settings.padding as f32 * em_width;
+        let min_x = self.column_pixels(settings.min_column as usize, window);
 
         let mut elements = HashMap::default();
         for (row, mut diagnostics) in diagnostics_by_rows {
@@ -2274,13 +2290,11 @@ impl EditorElement {
                 .px_1()
                 .rounded_xs()
                 .opacity(opacity)
-                .bg(severity_to_color(&diagnostic_to_render.severity)
-                    .color(cx)
-                    .opacity(0.05))
-                .text_color(severity_to_color(&diagnostic_to_render.severity).color(cx))
+                .bg(diagnostic_to_render.color().color(cx).opacity(0.05))
+                .text_color(diagnostic_to_render.color().color(cx))
                 .text_sm()
                 .font_family(style.text.font().family)
-                .child(diagnostic_to_render.message.clone())
+                .child(diagnostic_to_render.truncated_message(settings.max_width))
                 .into_any();
 
             element.prepaint_as_root(point(pos_x, pos_y), AvailableSpace::min_size(), window, cx);
@@ -2945,6 +2959,7 @@ impl EditorElement {
 
             let offset_range_end =
                 snapshot.display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+            let mut test_failures = editor.test_failures(&snapshot.buffer_snapshot, cx);
 
             editor
                 .tasks
@@ -2996,6 +3011,8 @@ impl EditorElement {
                         Some(display_row) == active_task_indicator_row,
                         display_row,
                         breakpoints.remove(&display_row),
+                        editor.runnable_run_status(multibuffer_row, &snapshot.buffer_snapshot, cx),
+                        test_failures.remove(&multibuffer_row),
                         cx,
                     );
 
@@ -7515,6 +7532,12 @@ impl AcceptEditPredictionBinding {
     }
 }
 
+/// Whether dropping a dragged selection moves it rather than copying it.
+fn is_cut_drop(event: &MouseUpEvent) -> bool {
+    !(cfg!(target_os = "macos") && event.modifiers.alt
+        || cfg!(not(target_os = "macos")) && event.modifiers.control)
+}
+
 fn prepaint_gutter_button(
     button: IconButton,
     row: DisplayRow,
@@ -7708,7 +7731,8 @@ impl LineWithInvisibles {
         let mut row = 0;
         let mut line_exceeded_max_len = false;
         let font_size = text_style.font_size.to_pixels(window.rem_size());
-        let min_contrast = EditorSettings::get_global(cx).minimum_contrast_for_highlights;
+        let min_contrast = AccessibilitySettings::get_global(cx)
+            .minimum_contrast(EditorSettings::get_global(cx).minimum_contrast_for_highlights);
 
         let ellipsis = SharedString::from("⋯");
 
@@ -8611,6 +8635,9 @@ impl Element for EditorElement {
                                 (max_row - height_in_lines + 1. + settings.vertical_scroll_margin)
                                     .max(0.)
                             }
+                            ScrollBeyondLastLine::Lines(lines) => {
+                                (max_row - height_in_lines + 1. + lines as f64).max(0.)
+                            }
                         }
                     };
 
@@ -9692,6 +9719,7 @@ impl ScrollbarLayoutInformation {
             ScrollBeyondLastLine::VerticalScrollMargin => {
                 (1.0 + settings.vertical_scroll_margin) as f32 * glyph_grid_cell.height
             }
+            ScrollBeyondLastLine::Lines(lines) => (1.0 + lines as f32) * glyph_grid_cell.height,
         };
 
         let overscroll = size(longest_line_blame_width, vertical_overscroll);
@@ -10327,6 +10355,8 @@ pub struct CursorLayout {
     origin: gpui::Point<Pixels>,
     block_width: Pixels,
     line_height: Pixels,
+    /// The width of a bar cursor, or the height of an underline cursor.
+    thickness: Pixels,
     color: Hsla,
     shape: CursorShape,
     block_text: Option<ShapedLine>,
@@ -10341,6 +10371,11 @@ pub struct CursorName {
 }
 
 impl CursorLayout {
+    /// The width of a bar cursor, or the height of an underline cursor.
+    pub const THICKNESS: Pixels = px(2.);
+    /// The cursor thickness in high contrast mode.
+    pub const HIGH_CONTRAST_THICKNESS: Pixels = px(4.);
+
     pub fn new(
         origin: gpui::Point<Pixels>,
         block_width: Pixels,
@@ -10353,6 +10388,7 @@ impl CursorLayout {
             origin,
             block_width,
             line_height,
+            thickness: Self::THICKNESS,
             color,
             shape,
             block_text,
@@ -10360,6 +10396,12 @@ impl CursorLayout {
         }
     }
 
+    /// Sets the width of a bar cursor, or the height of an underline cursor.
+    pub fn with_thickness(mut self, thickness: Pixels) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
     pub fn bounding_rect(&self, origin: gpui::Point<Pixels>) -> Bounds<Pixels> {
         Bounds {
             origin: self.origin + origin,
@@ -10371,7 +10413,7 @@ impl CursorLayout {
         match self.shape {
             CursorShape::Bar => Bounds {
                 origin: self.origin + origin,
-                size: size(px(2.0), self.line_height),
+                size: size(self.thickness, self.line_height),
             },
             CursorShape::Block | CursorShape::Hollow => Bounds {
                 origin: self.origin + origin,
@@ -10380,8 +10422,8 @@ impl CursorLayout {
             CursorShape::Underline => Bounds {
                 origin: self.origin
                     + origin
-                    + gpui::Point::new(Pixels::ZERO, self.line_height - px(2.0)),
-                size: size(self.block_width, px(2.0)),
+                    + gpui::Point::new(Pixels::ZERO, self.line_height - self.thickness),
+                size: size(self.block_width, self.thickness),
             },
         }
     }
@@ -10402,7 +10444,7 @@ impl CursorLayout {
             } else {
                 match self.shape {
                     CursorShape::Bar => point(
-                        bounds.right() - px(2.),
+                        bounds.right() - self.thickness,
                         bounds.top() - text_size / 2. - px(1.),
                     ),
                     _ => point(
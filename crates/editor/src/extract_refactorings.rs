@@ -0,0 +1,1028 @@
+//! Extract variable and extract function refactorings based on the syntax tree, offered for
+//! languages whose language servers don't provide extract refactorings of their own.
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use collections::HashSet;
+use gpui::{App, Entity, Task, WeakEntity, Window};
+use language::{Buffer, BufferSnapshot, Node};
+use lsp::{CodeActionKind, LanguageServerId};
+use multi_buffer::ExcerptId;
+use project::{CodeAction, LspAction, Project, ProjectTransaction};
+use text::ToOffset as _;
+
+use crate::{CodeActionProvider, Editor};
+
+const EXTRACT_VARIABLE_TITLE: &str = "Extract into variable";
+const EXTRACT_FUNCTION_TITLE: &str = "Extract into function";
+
+pub(crate) struct ExtractRefactoringProvider {
+    editor: WeakEntity<Editor>,
+    project: WeakEntity<Project>,
+}
+
+impl ExtractRefactoringProvider {
+    pub(crate) fn new(editor: WeakEntity<Editor>, project: WeakEntity<Project>) -> Self {
+        Self { editor, project }
+    }
+}
+
+impl CodeActionProvider for ExtractRefactoringProvider {
+    fn id(&self) -> Arc<str> {
+        "extract_refactorings".into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<text::Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let buffer = buffer.read(cx);
+        let snapshot = buffer.snapshot();
+        let range = range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot);
+        let Some(range) = trim_whitespace(&snapshot, range) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let servers_extract = self.project.upgrade().is_none_or(|project| {
+            project
+                .read(cx)
+                .any_language_server_supports_code_action_kind(
+                    buffer,
+                    &CodeActionKind::REFACTOR_EXTRACT,
+                    cx,
+                )
+        });
+        if servers_extract {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        let anchor_range = snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end);
+        let available = [
+            (
+                EXTRACT_VARIABLE_TITLE,
+                extract_variable(&snapshot, range.clone()).is_some(),
+            ),
+            (
+                EXTRACT_FUNCTION_TITLE,
+                extract_function(&snapshot, range, cx).is_some(),
+            ),
+        ];
+        let actions = available
+            .into_iter()
+            .filter(|(_, is_available)| *is_available)
+            .map(|(title, _)| CodeAction {
+                // Like snippets, these actions don't come from a language server, so they're
+                // attributed to one that can't exist.
+                server_id: LanguageServerId(usize::MAX),
+                range: anchor_range.clone(),
+                lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+                    title: title.into(),
+                    kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+                    ..Default::default()
+                })),
+                resolved: true,
+            })
+            .collect();
+        Task::ready(Ok(actions))
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        excerpt_id: ExcerptId,
+        push_to_history: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let range = action.range.start.to_offset(&snapshot)..action.range.end.to_offset(&snapshot);
+        let edits = match action.lsp_action.title() {
+            EXTRACT_VARIABLE_TITLE => extract_variable(&snapshot, range),
+            EXTRACT_FUNCTION_TITLE => extract_function(&snapshot, range, cx),
+            _ => None,
+        };
+        let Some(mut edits) = edits else {
+            return Task::ready(Err(anyhow!("the selection can no longer be extracted")));
+        };
+        edits.sort_by_key(|edit| edit.range.start);
+
+        let (transaction, name_ranges) = buffer.update(cx, |buffer, cx| {
+            buffer.finalize_last_transaction();
+            buffer.start_transaction();
+            buffer.edit(
+                edits
+                    .iter()
+                    .map(|edit| (edit.range.clone(), edit.new_text.clone())),
+                None,
+                cx,
+            );
+            let transaction = buffer
+                .end_transaction(cx)
+                .and_then(|_| buffer.finalize_last_transaction().cloned());
+            if let Some(transaction) = &transaction
+                && !push_to_history
+            {
+                buffer.forget_transaction(transaction.id);
+            }
+
+            let mut name_ranges = Vec::new();
+            let mut new_edit_start = 0;
+            let mut old_edit_end = 0;
+            for edit in &edits {
+                new_edit_start += edit.range.start - old_edit_end;
+                name_ranges.extend(edit.name_ranges.iter().map(|range| {
+                    buffer.anchor_before(new_edit_start + range.start)
+                        ..buffer.anchor_after(new_edit_start + range.end)
+                }));
+                new_edit_start += edit.new_text.len();
+                old_edit_end = edit.range.end;
+            }
+            (transaction, name_ranges)
+        });
+
+        // Select every occurrence of the new name, so that typing renames it.
+        let editor = self.editor.clone();
+        window.spawn(cx, async move |cx| {
+            editor.update_in(cx, |editor, window, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let selections = name_ranges
+                    .into_iter()
+                    .filter_map(|range| {
+                        Some(
+                            snapshot.anchor_in_excerpt(excerpt_id, range.start)?
+                                ..snapshot.anchor_in_excerpt(excerpt_id, range.end)?,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                if !selections.is_empty() {
+                    editor.change_selections(Default::default(), window, cx, |s| {
+                        s.select_anchor_ranges(selections)
+                    });
+                }
+            })?;
+            Ok(ProjectTransaction(
+                transaction
+                    .map(|transaction| (buffer, transaction))
+                    .into_iter()
+                    .collect(),
+            ))
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ExtractionEdit {
+    range: Range<usize>,
+    new_text: String,
+    /// Where the extracted variable or function is named within `new_text`.
+    name_ranges: Vec<Range<usize>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExtractLanguage {
+    Rust,
+    JavaScript,
+    Python,
+}
+
+impl ExtractLanguage {
+    fn for_node(snapshot: &BufferSnapshot, offset: usize) -> Option<(Self, Node<'_>)> {
+        let layer = snapshot.syntax_layer_at(offset)?;
+        let language = match layer.language.name().as_ref() {
+            "Rust" => Self::Rust,
+            "TypeScript" | "TSX" | "JavaScript" => Self::JavaScript,
+            "Python" => Self::Python,
+            _ => return None,
+        };
+        Some((language, layer.node()))
+    }
+
+    fn variable_name(self) -> &'static str {
+        match self {
+            Self::Rust | Self::Python => "new_variable",
+            Self::JavaScript => "newVariable",
+        }
+    }
+
+    fn function_name(self) -> &'static str {
+        match self {
+            Self::Rust | Self::Python => "new_function",
+            Self::JavaScript => "newFunction",
+        }
+    }
+
+    fn is_block(self, kind: &str) -> bool {
+        match self {
+            Self::Rust => matches!(kind, "block" | "source_file"),
+            Self::JavaScript => matches!(kind, "statement_block" | "program"),
+            Self::Python => matches!(kind, "block" | "module"),
+        }
+    }
+
+    fn is_function(self, kind: &str) -> bool {
+        match self {
+            Self::Rust => matches!(kind, "function_item" | "closure_expression"),
+            Self::JavaScript => matches!(
+                kind,
+                "function_declaration"
+                    | "function_expression"
+                    | "function"
+                    | "generator_function_declaration"
+                    | "generator_function"
+                    | "arrow_function"
+                    | "method_definition"
+            ),
+            Self::Python => matches!(kind, "function_definition" | "lambda"),
+        }
+    }
+
+    fn is_loop(self, kind: &str) -> bool {
+        match self {
+            Self::Rust => matches!(
+                kind,
+                "loop_expression" | "while_expression" | "for_expression"
+            ),
+            Self::JavaScript => matches!(
+                kind,
+                "for_statement"
+                    | "for_in_statement"
+                    | "while_statement"
+                    | "do_statement"
+                    | "switch_statement"
+            ),
+            Self::Python => matches!(kind, "for_statement" | "while_statement"),
+        }
+    }
+
+    fn is_expression(self, node: Node) -> bool {
+        let kind = node.kind();
+        match self {
+            Self::Rust => {
+                (kind.ends_with("_expression")
+                    || kind.ends_with("_literal")
+                    || kind == "macro_invocation")
+                    && !matches!(kind, "assignment_expression" | "compound_assignment_expr")
+            }
+            Self::JavaScript => {
+                let is_assignment = matches!(
+                    kind,
+                    "assignment_expression"
+                        | "augmented_assignment_expression"
+                        | "update_expression"
+                        | "sequence_expression"
+                );
+                let is_value = matches!(
+                    kind,
+                    "string"
+                        | "template_string"
+                        | "number"
+                        | "regex"
+                        | "true"
+                        | "false"
+                        | "null"
+                        | "array"
+                        | "object"
+                        | "arrow_function"
+                        | "jsx_element"
+                        | "jsx_self_closing_element"
+                );
+                // Calling an extracted method would lose its `this`.
+                let is_method = kind == "member_expression"
+                    && node.parent().is_some_and(|parent| {
+                        parent.kind() == "call_expression"
+                            && parent.child_by_field_name("function") == Some(node)
+                    });
+                (kind.ends_with("_expression") || is_value) && !is_assignment && !is_method
+            }
+            Self::Python => matches!(
+                kind,
+                "call"
+                    | "binary_operator"
+                    | "boolean_operator"
+                    | "comparison_operator"
+                    | "not_operator"
+                    | "unary_operator"
+                    | "attribute"
+                    | "subscript"
+                    | "string"
+                    | "concatenated_string"
+                    | "integer"
+                    | "float"
+                    | "true"
+                    | "false"
+                    | "none"
+                    | "list"
+                    | "dictionary"
+                    | "set"
+                    | "tuple"
+                    | "list_comprehension"
+                    | "dictionary_comprehension"
+                    | "set_comprehension"
+                    | "generator_expression"
+                    | "conditional_expression"
+                    | "lambda"
+                    | "parenthesized_expression"
+                    | "await"
+            ),
+        }
+    }
+
+    /// Whether an identifier node is read or written, or `None` if it doesn't refer to a
+    /// variable.
+    fn identifier_access(self, node: Node) -> Option<Access> {
+        let kind = node.kind();
+        let read = Access {
+            read: true,
+            write: false,
+        };
+        let write = Access {
+            read: false,
+            write: true,
+        };
+        let read_write = Access {
+            read: true,
+            write: true,
+        };
+        match (self, kind) {
+            (Self::JavaScript, "shorthand_property_identifier_pattern") => return Some(write),
+            (Self::JavaScript, "shorthand_property_identifier") => return Some(read),
+            (_, "identifier") => {}
+            _ => return None,
+        }
+        let Some(parent) = node.parent() else {
+            return Some(read);
+        };
+        let is_field = |field| parent.child_by_field_name(field) == Some(node);
+        let access = match self {
+            Self::Rust => read,
+            Self::JavaScript => match parent.kind() {
+                "variable_declarator" if is_field("name") => write,
+                "assignment_expression" | "for_in_statement" | "assignment_pattern"
+                    if is_field("left") =>
+                {
+                    write
+                }
+                "augmented_assignment_expression" if is_field("left") => read_write,
+                "update_expression" => read_write,
+                "function_declaration"
+                | "generator_function_declaration"
+                | "function_expression"
+                | "class_declaration"
+                | "class"
+                    if is_field("name") =>
+                {
+                    write
+                }
+                "required_parameter" | "optional_parameter" if is_field("pattern") => write,
+                "arrow_function" if is_field("parameter") => write,
+                "pair_pattern" if is_field("value") => write,
+                "formal_parameters" | "array_pattern" | "rest_pattern" | "catch_clause"
+                | "import_specifier" | "import_clause" | "namespace_import" => write,
+                _ => read,
+            },
+            Self::Python => match parent.kind() {
+                "attribute" if is_field("attribute") => return None,
+                "keyword_argument" if is_field("name") => return None,
+                "global_statement" | "nonlocal_statement" | "dotted_name" => return None,
+                "assignment" | "for_statement" | "for_in_clause" if is_field("left") => write,
+                "augmented_assignment" if is_field("left") => read_write,
+                "default_parameter" | "typed_default_parameter" | "named_expression"
+                    if is_field("name") =>
+                {
+                    write
+                }
+                "function_definition" | "class_definition" if is_field("name") => write,
+                "aliased_import" if is_field("alias") => write,
+                "pattern_list"
+                | "tuple_pattern"
+                | "list_pattern"
+                | "list_splat_pattern"
+                | "dictionary_splat_pattern"
+                | "parameters"
+                | "lambda_parameters"
+                | "typed_parameter"
+                | "as_pattern_target" => write,
+                _ => read,
+            },
+        };
+        Some(access)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Access {
+    read: bool,
+    write: bool,
+}
+
+/// Replaces the selected expression with a new variable, declared just before the statement
+/// that contains it.
+fn extract_variable(snapshot: &BufferSnapshot, range: Range<usize>) -> Option<Vec<ExtractionEdit>> {
+    let range = trim_whitespace(snapshot, range)?;
+    let (language, root) = ExtractLanguage::for_node(snapshot, range.start)?;
+    let mut node = root.descendant_for_byte_range(range.start, range.end)?;
+    while node.byte_range() == range && !language.is_expression(node) {
+        node = node.parent()?;
+    }
+    if node.byte_range() != range || !language.is_expression(node) {
+        return None;
+    }
+    let parent = node.parent()?;
+    let parent_kind = parent.kind();
+    if (parent_kind.contains("assignment") || parent_kind.starts_with("for"))
+        && parent.child_by_field_name("left") == Some(node)
+    {
+        return None;
+    }
+
+    // Moving the expression out of a function or class would take it out of the scope of the
+    // names that it uses.
+    let mut statement = node;
+    loop {
+        let parent = statement.parent()?;
+        if language.is_block(parent.kind()) {
+            break;
+        }
+        if language.is_function(parent.kind()) || parent.kind() == "class_body" {
+            return None;
+        }
+        statement = parent;
+    }
+    let is_valid_statement = match language {
+        ExtractLanguage::Rust => {
+            statement.parent()?.kind() == "block" && !statement.kind().ends_with("_item")
+        }
+        ExtractLanguage::JavaScript => !matches!(
+            statement.kind(),
+            "function_declaration" | "generator_function_declaration" | "class_declaration"
+        ),
+        ExtractLanguage::Python => !matches!(
+            statement.kind(),
+            "function_definition" | "decorated_definition"
+        ),
+    };
+    if !is_valid_statement {
+        return None;
+    }
+
+    let name = unused_name(snapshot, language.variable_name());
+    let expression = snapshot.text_for_range(range.clone()).collect::<String>();
+    let statement_row = snapshot.offset_to_point(statement.start_byte()).row;
+    let indent = snapshot
+        .indent_size_for_line(statement_row)
+        .chars()
+        .collect::<String>();
+    let declaration_prefix = match language {
+        ExtractLanguage::Rust => "let ",
+        ExtractLanguage::JavaScript => "const ",
+        ExtractLanguage::Python => "",
+    };
+    let declaration_suffix = match language {
+        ExtractLanguage::Rust | ExtractLanguage::JavaScript => ";",
+        ExtractLanguage::Python => "",
+    };
+    let preceding_text = snapshot
+        .text_for_range(statement.start_byte()..range.start)
+        .collect::<String>();
+    let new_text = format!(
+        "{declaration_prefix}{name} = {expression}{declaration_suffix}\n{indent}{preceding_text}{name}"
+    );
+    let name_ranges = vec![
+        declaration_prefix.len()..declaration_prefix.len() + name.len(),
+        new_text.len() - name.len()..new_text.len(),
+    ];
+    Some(vec![ExtractionEdit {
+        range: statement.start_byte()..range.end,
+        new_text,
+        name_ranges,
+    }])
+}
+
+/// Moves the selected statements into a new top-level function, passing it the variables that
+/// they use from the enclosing function and returning the ones that they assign and that are
+/// used afterwards.
+fn extract_function(
+    snapshot: &BufferSnapshot,
+    range: Range<usize>,
+    cx: &App,
+) -> Option<Vec<ExtractionEdit>> {
+    let range = trim_whitespace(snapshot, range)?;
+    let (language, root) = ExtractLanguage::for_node(snapshot, range.start)?;
+    if language == ExtractLanguage::Rust {
+        return None;
+    }
+
+    let mut block = root.descendant_for_byte_range(range.start, range.end)?;
+    while !language.is_block(block.kind()) {
+        block = block.parent()?;
+    }
+    if block
+        .parent()
+        .is_some_and(|parent| parent.kind() == "class_definition")
+    {
+        return None;
+    }
+    let mut cursor = block.walk();
+    let statements = block
+        .named_children(&mut cursor)
+        .filter(|child| child.end_byte() > range.start && child.start_byte() < range.end)
+        .collect::<Vec<_>>();
+    if statements.first()?.start_byte() != range.start || statements.last()?.end_byte() != range.end
+    {
+        return None;
+    }
+
+    let mut is_async = false;
+    let mut is_extractable = true;
+    for statement in &statements {
+        for_each_descendant(*statement, &mut |node| match node.kind() {
+            "return_statement" | "yield" | "yield_expression" => {
+                is_extractable &= nested_within(node, &range, |kind| language.is_function(kind));
+            }
+            "break_statement" | "continue_statement" => {
+                is_extractable &= nested_within(node, &range, |kind| {
+                    language.is_function(kind) || language.is_loop(kind)
+                });
+            }
+            "this" | "super" => is_extractable = false,
+            "await" | "await_expression" => {
+                is_async |= !nested_within(node, &range, |kind| language.is_function(kind));
+            }
+            _ => {}
+        });
+    }
+    if !is_extractable {
+        return None;
+    }
+
+    // Variables from the outermost enclosing function, including those captured by nested
+    // functions, need to be passed in, while module-level ones remain in scope.
+    let function = std::iter::successors(Some(block), |node| node.parent())
+        .filter(|node| language.is_function(node.kind()))
+        .last();
+    let scope = function.unwrap_or(root);
+    let mut accesses = Vec::new();
+    for_each_descendant(scope, &mut |node| {
+        if let Some(access) = language.identifier_access(node) {
+            let name = snapshot
+                .text_for_range(node.byte_range())
+                .collect::<String>();
+            accesses.push((name, node, access));
+        }
+    });
+
+    let mut written_before = HashSet::default();
+    let mut read_after = HashSet::default();
+    let mut accessed = HashSet::default();
+    let mut params = Vec::new();
+    let mut written = Vec::new();
+    for (name, node, access) in &accesses {
+        if node.end_byte() <= range.start {
+            if access.write {
+                written_before.insert(name.as_str());
+            }
+        } else if node.start_byte() >= range.end {
+            if access.read {
+                read_after.insert(name.as_str());
+            }
+        } else {
+            if accessed.insert(name.as_str()) && access.read {
+                params.push(name.as_str());
+            }
+            if access.write && !written.contains(&name.as_str()) {
+                written.push(name.as_str());
+            }
+        }
+    }
+    if function.is_some() {
+        params.retain(|name| written_before.contains(name));
+    } else {
+        params.clear();
+    }
+    let returns = written
+        .into_iter()
+        .filter(|name| read_after.contains(name))
+        .collect::<Vec<_>>();
+
+    // In JavaScript, returned variables are either declared by the call or assigned by it.
+    let mut declaration_keyword = None;
+    if language == ExtractLanguage::JavaScript {
+        let declarations = returns
+            .iter()
+            .map(|name| {
+                accesses.iter().find_map(|(access_name, node, _)| {
+                    let declarator = node.parent()?;
+                    (access_name == name
+                        && range.contains(&node.start_byte())
+                        && declarator.kind() == "variable_declarator"
+                        && declarator.child_by_field_name("name") == Some(*node))
+                    .then(|| declarator.parent()?.child(0).map(|keyword| keyword.kind()))?
+                })
+            })
+            .collect::<Vec<_>>();
+        if declarations.iter().all(Option::is_some) {
+            if !declarations.is_empty() {
+                declaration_keyword = Some(
+                    if declarations.iter().all(|keyword| *keyword == Some("const")) {
+                        "const"
+                    } else {
+                        "let"
+                    },
+                );
+            }
+        } else if declarations.iter().any(Option::is_some) {
+            return None;
+        }
+    }
+
+    let name = unused_name(snapshot, language.function_name());
+    let params = params.join(", ");
+    let indent_unit = snapshot
+        .language_indent_size_at(range.start, cx)
+        .chars()
+        .collect::<String>();
+    let top_level_item = std::iter::successors(Some(block), |node| node.parent())
+        .find(|node| node.parent() == Some(root));
+    let function_indent = snapshot
+        .indent_size_for_line(
+            snapshot
+                .offset_to_point(top_level_item.map_or(range.start, |item| item.start_byte()))
+                .row,
+        )
+        .chars()
+        .collect::<String>();
+    let body_indent = format!("{function_indent}{indent_unit}");
+    let body = reindent(snapshot, range.clone(), &body_indent);
+
+    let async_prefix = if is_async { "async " } else { "" };
+    let await_prefix = if is_async { "await " } else { "" };
+    let (function_prefix, function_text) = match language {
+        ExtractLanguage::Python => {
+            let function_prefix = format!("{async_prefix}def ");
+            let return_line = if returns.is_empty() {
+                String::new()
+            } else {
+                format!("\n{body_indent}return {}", returns.join(", "))
+            };
+            let function_text = format!("{function_prefix}{name}({params}):\n{body}{return_line}");
+            (function_prefix, function_text)
+        }
+        _ => {
+            let function_prefix = format!("{async_prefix}function ");
+            let return_line = match returns.as_slice() {
+                [] => String::new(),
+                [returned] => format!("\n{body_indent}return {returned};"),
+                returns => format!("\n{body_indent}return {{ {} }};", returns.join(", ")),
+            };
+            let function_text = format!(
+                "{function_prefix}{name}({params}) {{\n{body}{return_line}\n{function_indent}}}"
+            );
+            (function_prefix, function_text)
+        }
+    };
+
+    let call = format!("{await_prefix}{name}({params})");
+    let (call_prefix, call_suffix) = match (language, returns.as_slice()) {
+        (ExtractLanguage::Python, []) => (String::new(), ""),
+        (ExtractLanguage::Python, returns) => (format!("{} = ", returns.join(", ")), ""),
+        (_, []) => (String::new(), ";"),
+        (_, [returned]) => match declaration_keyword {
+            Some(keyword) => (format!("{keyword} {returned} = "), ";"),
+            None => (format!("{returned} = "), ";"),
+        },
+        (_, returns) => match declaration_keyword {
+            Some(keyword) => (format!("{keyword} {{ {} }} = ", returns.join(", ")), ";"),
+            None => (format!("({{ {} }} = ", returns.join(", ")), ");"),
+        },
+    };
+    let call_text = format!("{call_prefix}{call}{call_suffix}");
+    let call_name_start = call_prefix.len() + await_prefix.len();
+    let separator = match language {
+        ExtractLanguage::Python => "\n\n\n",
+        _ => "\n\n",
+    };
+
+    // Top-level statements are replaced by the function followed by its call, while nested ones
+    // are replaced by the call, with the function following the top-level item they're in.
+    let edits = match top_level_item {
+        None => {
+            let new_text = format!("{function_text}{separator}{function_indent}{call_text}");
+            let call_start = new_text.len() - call_text.len();
+            vec![ExtractionEdit {
+                range,
+                name_ranges: vec![
+                    function_prefix.len()..function_prefix.len() + name.len(),
+                    call_start + call_name_start..call_start + call_name_start + name.len(),
+                ],
+                new_text,
+            }]
+        }
+        // Edits can't touch, so a call at the end of the item is followed by the function.
+        Some(item) if item.end_byte() == range.end => {
+            let new_text = format!("{call_text}{separator}{function_indent}{function_text}");
+            let function_start =
+                call_text.len() + separator.len() + function_indent.len() + function_prefix.len();
+            vec![ExtractionEdit {
+                range,
+                name_ranges: vec![
+                    call_name_start..call_name_start + name.len(),
+                    function_start..function_start + name.len(),
+                ],
+                new_text,
+            }]
+        }
+        Some(item) => {
+            let function_start = separator.len() + function_indent.len() + function_prefix.len();
+            vec![
+                ExtractionEdit {
+                    range: range.clone(),
+                    name_ranges: vec![call_name_start..call_name_start + name.len()],
+                    new_text: call_text,
+                },
+                ExtractionEdit {
+                    range: item.end_byte()..item.end_byte(),
+                    new_text: format!("{separator}{function_indent}{function_text}"),
+                    name_ranges: vec![function_start..function_start + name.len()],
+                },
+            ]
+        }
+    };
+    Some(edits)
+}
+
+fn trim_whitespace(snapshot: &BufferSnapshot, range: Range<usize>) -> Option<Range<usize>> {
+    let text = snapshot.text_for_range(range.clone()).collect::<String>();
+    let start = range.start + text.len() - text.trim_start().len();
+    let end = range.end - (text.len() - text.trim_end().len());
+    (start < end).then_some(start..end)
+}
+
+/// Returns the lines of the range, with their common indentation replaced by the given one.
+fn reindent(snapshot: &BufferSnapshot, range: Range<usize>, indent: &str) -> String {
+    let first_row = snapshot.offset_to_point(range.start).row;
+    let first_line_indent = snapshot
+        .indent_size_for_line(first_row)
+        .chars()
+        .collect::<String>();
+    let text = first_line_indent + &snapshot.text_for_range(range).collect::<String>();
+    let common_indent = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{}", &line[common_indent..])
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the given name, or the first one numbered after it that doesn't occur in the
+/// buffer.
+fn unused_name(snapshot: &BufferSnapshot, name: &str) -> String {
+    let text = snapshot.text();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let is_used = |candidate: &str| {
+        text.match_indices(candidate).any(|(ix, _)| {
+            !text[..ix].chars().next_back().is_some_and(is_word_char)
+                && !text[ix + candidate.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_word_char)
+        })
+    };
+    let mut candidate = name.to_string();
+    let mut suffix = 1;
+    while is_used(&candidate) {
+        suffix += 1;
+        candidate = format!("{name}{suffix}");
+    }
+    candidate
+}
+
+/// Whether the node is inside a node matching the predicate that's itself within the range.
+fn nested_within(node: Node, range: &Range<usize>, predicate: impl Fn(&str) -> bool) -> bool {
+    std::iter::successors(node.parent(), |node| node.parent())
+        .take_while(|ancestor| {
+            range.start <= ancestor.start_byte() && ancestor.end_byte() <= range.end
+        })
+        .any(|ancestor| predicate(ancestor.kind()))
+}
+
+fn for_each_descendant<'a>(node: Node<'a>, f: &mut impl FnMut(Node<'a>)) {
+    let mut cursor = node.walk();
+    loop {
+        f(cursor.node());
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{editor_tests::init_test, test::editor_test_context::EditorTestContext};
+    use indoc::indoc;
+    use language::{Language, tree_sitter_python, tree_sitter_rust, tree_sitter_typescript};
+    use std::rc::Rc;
+
+    #[gpui::test]
+    async fn test_extract_variable(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        set_language(
+            &mut cx,
+            languages::language("rust", tree_sitter_rust::LANGUAGE.into()),
+        );
+
+        cx.set_state(indoc! {"
+            fn total(items: &[Item]) -> u32 {
+                let count = items.len();
+                «count * PRICEˇ» + 1
+            }
+        "});
+        assert_eq!(available_extractions(&mut cx), [EXTRACT_VARIABLE_TITLE]);
+        extract(&mut cx, EXTRACT_VARIABLE_TITLE).await;
+        cx.assert_editor_state(indoc! {"
+            fn total(items: &[Item]) -> u32 {
+                let count = items.len();
+                let «new_variableˇ» = count * PRICE;
+                «new_variableˇ» + 1
+            }
+        "});
+
+        // Expressions that use a closure's parameters can't leave the closure.
+        cx.set_state(indoc! {"
+            fn total(items: &[Item]) -> u32 {
+                items.iter().map(|item| «item.priceˇ» * 2).sum()
+            }
+        "});
+        assert!(available_extractions(&mut cx).is_empty());
+
+        // Nor can assignment targets be extracted.
+        cx.set_state(indoc! {"
+            fn reset(item: &mut Item) {
+                «item.priceˇ» = 0;
+            }
+        "});
+        assert!(available_extractions(&mut cx).is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_extract_function_in_python(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        set_language(
+            &mut cx,
+            languages::language("python", tree_sitter_python::LANGUAGE.into()),
+        );
+
+        cx.set_state(indoc! {"
+            def report(orders, tax):
+                lines = []
+                «total = 0
+                for order in orders:
+                    total += order.price * tax
+                    lines.append(order.name)ˇ»
+                print(lines, total)
+        "});
+        assert_eq!(available_extractions(&mut cx), [EXTRACT_FUNCTION_TITLE]);
+        extract(&mut cx, EXTRACT_FUNCTION_TITLE).await;
+        cx.assert_editor_state(indoc! {"
+            def report(orders, tax):
+                lines = []
+                total = «new_functionˇ»(orders, tax, lines)
+                print(lines, total)
+
+
+            def «new_functionˇ»(orders, tax, lines):
+                total = 0
+                for order in orders:
+                    total += order.price * tax
+                    lines.append(order.name)
+                return total
+        "});
+
+        // Returning from the middle of the function can't be extracted.
+        cx.set_state(indoc! {"
+            def find(orders, name):
+                «for order in orders:
+                    if order.name == name:
+                        return orderˇ»
+        "});
+        assert!(available_extractions(&mut cx).is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_extract_function_in_typescript(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorTestContext::new(cx).await;
+        set_language(
+            &mut cx,
+            languages::language(
+                "typescript",
+                tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            ),
+        );
+
+        cx.set_state(indoc! {"
+            «const response = await fetch(url);
+            const body = await response.json();ˇ»
+            console.log(body);
+        "});
+        assert_eq!(available_extractions(&mut cx), [EXTRACT_FUNCTION_TITLE]);
+        extract(&mut cx, EXTRACT_FUNCTION_TITLE).await;
+        cx.assert_editor_state(indoc! {"
+            async function «newFunctionˇ»() {
+                const response = await fetch(url);
+                const body = await response.json();
+                return body;
+            }
+
+            const body = await «newFunctionˇ»();
+            console.log(body);
+        "});
+
+        cx.set_state(indoc! {"
+            function label(user) {
+                const name = «user.first + user.lastˇ»;
+                return name;
+            }
+        "});
+        assert_eq!(available_extractions(&mut cx), [EXTRACT_VARIABLE_TITLE]);
+        extract(&mut cx, EXTRACT_VARIABLE_TITLE).await;
+        cx.assert_editor_state(indoc! {"
+            function label(user) {
+                const «newVariableˇ» = user.first + user.last;
+                const name = «newVariableˇ»;
+                return name;
+            }
+        "});
+    }
+
+    fn set_language(cx: &mut EditorTestContext, language: Arc<Language>) {
+        cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    }
+
+    fn provider(cx: &mut EditorTestContext) -> Rc<ExtractRefactoringProvider> {
+        cx.update_editor(|editor, _, cx| {
+            Rc::new(ExtractRefactoringProvider::new(
+                cx.entity().downgrade(),
+                editor.project().unwrap().downgrade(),
+            ))
+        })
+    }
+
+    fn code_actions(cx: &mut EditorTestContext) -> Task<Result<Vec<CodeAction>>> {
+        let provider = provider(cx);
+        cx.update_editor(|editor, window, cx| {
+            let buffer = editor.buffer().read(cx).as_singleton().unwrap();
+            let selection = editor.selections.newest_anchor();
+            let range = selection.start.text_anchor..selection.end.text_anchor;
+            provider.code_actions(&buffer, range, window, cx)
+        })
+    }
+
+    fn available_extractions(cx: &mut EditorTestContext) -> Vec<String> {
+        let actions = code_actions(cx);
+        cx.executor()
+            .block(actions)
+            .unwrap()
+            .into_iter()
+            .map(|action| action.lsp_action.title().to_string())
+            .collect()
+    }
+
+    async fn extract(cx: &mut EditorTestContext, title: &str) {
+        let provider = provider(cx);
+        let action = code_actions(cx)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|action| action.lsp_action.title() == title)
+            .unwrap();
+        let apply = cx.update_editor(|editor, window, cx| {
+            let buffer = editor.buffer().read(cx).as_singleton().unwrap();
+            let excerpt_id = editor.buffer().read(cx).excerpt_ids()[0];
+            provider.apply_code_action(buffer, action, excerpt_id, true, window, cx)
+        });
+        apply.await.unwrap();
+        cx.run_until_parked();
+    }
+}
@@ -0,0 +1,171 @@
+use std::ops::Range;
+
+use gpui::{AnyElement, App, MouseButton, WeakEntity};
+use language::Point;
+use lsp::DiagnosticSeverity;
+use multi_buffer::{Anchor, MultiBuffer, MultiBufferRow, MultiBufferSnapshot, ToPoint as _};
+use settings::Settings as _;
+use theme::ThemeSettings;
+use ui::prelude::*;
+
+use crate::{
+    Editor, EditorSettings, display_map::FoldId, editor_settings::FoldPlaceholderSettings,
+};
+
+/// The maximum number of characters of the folded text shown in a fold placeholder.
+const MAX_SUMMARY_CHARS: usize = 40;
+
+/// What a fold placeholder shows about the text it hides.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FoldSummary {
+    /// The first non-blank folded line, trimmed and truncated.
+    pub text: Option<SharedString>,
+    /// The number of lines hidden by the fold.
+    pub hidden_lines: u32,
+    pub error_count: usize,
+    pub warning_count: usize,
+}
+
+impl FoldSummary {
+    pub(crate) fn new(
+        snapshot: &MultiBufferSnapshot,
+        range: Range<Anchor>,
+        settings: &FoldPlaceholderSettings,
+    ) -> Self {
+        let start = range.start.to_point(snapshot);
+        let end = range.end.to_point(snapshot);
+        let mut summary = Self::default();
+
+        if settings.summary {
+            summary.text = (start.row..=end.row).find_map(|row| {
+                let line_start = Point::new(row, 0).max(start);
+                let line_end = Point::new(row, snapshot.line_len(MultiBufferRow(row))).min(end);
+                let line = snapshot
+                    .text_for_range(line_start..line_end)
+                    .collect::<String>();
+                let line = line.trim();
+                if line.is_empty() {
+                    return None;
+                }
+                let mut chars = line.chars();
+                let mut text = chars.by_ref().take(MAX_SUMMARY_CHARS).collect::<String>();
+                if chars.next().is_some() {
+                    text.push('…');
+                }
+                Some(text.into())
+            });
+        }
+
+        if settings.line_count {
+            // A fold ending in the leading whitespace of a line leaves that line visible.
+            let end_row_hidden = snapshot
+                .text_for_range(Point::new(end.row, 0)..end)
+                .any(|chunk| chunk.contains(|c: char| !c.is_whitespace()));
+            summary.hidden_lines = end.row - start.row;
+            if !end_row_hidden && end.row > start.row {
+                summary.hidden_lines -= 1;
+            }
+        }
+
+        if settings.diagnostics {
+            for entry in snapshot.diagnostics_in_range::<Point>(start..end) {
+                if !entry.diagnostic.is_primary {
+                    continue;
+                }
+                match entry.diagnostic.severity {
+                    DiagnosticSeverity::ERROR => summary.error_count += 1,
+                    DiagnosticSeverity::WARNING => summary.warning_count += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+/// Renders the default placeholder of a fold: an ellipsis surrounded by a summary of the folded
+/// text. Clicking the placeholder peeks at the folded text, and clicking it with the secondary
+/// modifier unfolds it.
+pub(crate) fn render_fold_placeholder(
+    editor: WeakEntity<Editor>,
+    buffer: &WeakEntity<MultiBuffer>,
+    fold_id: FoldId,
+    fold_range: Range<Anchor>,
+    cx: &mut App,
+) -> AnyElement {
+    let settings = EditorSettings::get_global(cx).fold_placeholder;
+    let summary = buffer
+        .upgrade()
+        .map(|buffer| {
+            let snapshot = buffer.read(cx).snapshot(cx);
+            FoldSummary::new(&snapshot, fold_range.clone(), &settings)
+        })
+        .unwrap_or_default();
+    let colors = cx.theme().colors();
+    let status = cx.theme().status();
+
+    let mut details = Vec::new();
+    if summary.hidden_lines > 0 {
+        details.push(
+            div()
+                .text_color(colors.text_muted)
+                .child(pluralize(summary.hidden_lines as usize, "line")),
+        );
+    }
+    if summary.error_count > 0 {
+        details.push(
+            div()
+                .text_color(status.error)
+                .child(pluralize(summary.error_count, "error")),
+        );
+    }
+    if summary.warning_count > 0 {
+        details.push(
+            div()
+                .text_color(status.warning)
+                .child(pluralize(summary.warning_count, "warning")),
+        );
+    }
+
+    h_flex()
+        .id(fold_id)
+        .gap_1()
+        .when(summary.text.is_some() || !details.is_empty(), |this| {
+            this.px_0p5()
+        })
+        .bg(colors.ghost_element_background)
+        .hover(|style| style.bg(colors.ghost_element_hover))
+        .active(|style| style.bg(colors.ghost_element_active))
+        .rounded_xs()
+        .size_full()
+        .cursor_pointer()
+        .font(ThemeSettings::get_global(cx).buffer_font.clone())
+        .when_some(summary.text, |this, text| {
+            this.child(div().text_color(colors.text_muted).child(text))
+        })
+        .child("⋯")
+        .children(details)
+        .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+        .on_click(move |event, window, cx| {
+            editor
+                .update(cx, |editor, cx| {
+                    if event.modifiers().secondary() {
+                        editor.unfold_ranges(&[fold_range.start..fold_range.end], true, false, cx);
+                    } else {
+                        editor.peek_fold(fold_range.clone(), window, cx);
+                    }
+                    cx.stop_propagation();
+                })
+                .ok();
+        })
+        .into_any()
+}
+
+fn pluralize(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
@@ -13,10 +13,10 @@ use gpui::{
     Window, div, px,
 };
 use itertools::Itertools;
-use language::{DiagnosticEntry, Language, LanguageRegistry};
+use language::{CharKind, DiagnosticEntry, Language, LanguageRegistry};
 use lsp::DiagnosticSeverity;
 use markdown::{Markdown, MarkdownElement, MarkdownStyle};
-use multi_buffer::{ToOffset, ToPoint};
+use multi_buffer::{MultiBufferSnapshot, ToOffset, ToPoint};
 use project::{HoverBlock, HoverBlockKind, InlayHintLabelPart};
 use settings::Settings;
 use std::{borrow::Cow, cell::RefCell};
@@ -33,6 +33,8 @@ pub const MIN_POPOVER_CHARACTER_WIDTH: f32 = 20.;
 pub const MIN_POPOVER_LINE_HEIGHT: f32 = 4.;
 pub const POPOVER_RIGHT_OFFSET: Pixels = px(8.0);
 pub const HOVER_POPOVER_GAP: Pixels = px(10.);
+/// The maximum number of fields of a structured value shown in a debugger hover.
+const MAX_DEBUG_HOVER_FIELDS: usize = 50;
 
 /// Bindable action which uses the most recent selection head to trigger a hover
 pub fn hover(editor: &mut Editor, _: &Hover, window: &mut Window, cx: &mut Context<Editor>) {
@@ -303,6 +305,8 @@ fn show_hover(
             };
 
             let hover_request = cx.update(|_, cx| provider.hover(&buffer, buffer_position, cx))?;
+            let debug_hover =
+                this.update(cx, |editor, cx| evaluate_debug_hover(editor, anchor, cx))?;
 
             if let Some(delay) = delay {
                 delay.await;
@@ -462,6 +466,47 @@ fn show_hover(
                 })
             }
 
+            if let Some((range, expression, evaluation)) = debug_hover
+                && let Some((response, fields)) = evaluation.await.ok()
+            {
+                let mut text = format!("{expression} = {}", response.result);
+                for field in fields.iter().take(MAX_DEBUG_HOVER_FIELDS) {
+                    text.push_str(&format!("\n  {}: {}", field.name, field.value));
+                }
+                if fields.len() > MAX_DEBUG_HOVER_FIELDS {
+                    text.push_str("\n  …");
+                }
+                let language = snapshot
+                    .buffer_snapshot
+                    .language_at(anchor)
+                    .map(|language| language.name().to_string())
+                    .unwrap_or_default();
+                let blocks = vec![HoverBlock {
+                    text,
+                    kind: HoverBlockKind::Code { language },
+                }];
+                let parsed_content =
+                    parse_blocks(&blocks, language_registry.as_ref(), None, cx).await;
+                let scroll_handle = ScrollHandle::new();
+                hover_highlights.push(range.clone());
+                let subscription = this
+                    .update(cx, |_, cx| {
+                        parsed_content.as_ref().map(|parsed_content| {
+                            cx.observe(parsed_content, |_, _, cx| cx.notify())
+                        })
+                    })
+                    .ok()
+                    .flatten();
+                info_popovers.push(InfoPopover {
+                    symbol_range: RangeInEditor::Text(range),
+                    parsed_content,
+                    scroll_handle,
+                    keyboard_grace: Rc::new(RefCell::new(ignore_timeout)),
+                    anchor: Some(anchor),
+                    _subscription: subscription,
+                });
+            }
+
             for hover_result in hovers_response {
                 // Create symbol range of anchors for highlighting and filtering of future requests.
                 let range = hover_result
@@ -533,6 +578,56 @@ fn show_hover(
     None
 }
 
+/// Evaluates the expression under `anchor` in the stack frame the active debug session is stopped in.
+/// The expression is the hovered identifier along with the member accesses preceding it, e.g. `self.point.x`.
+fn evaluate_debug_hover(
+    editor: &Editor,
+    anchor: Anchor,
+    cx: &mut App,
+) -> Option<(
+    Range<Anchor>,
+    String,
+    Task<anyhow::Result<(dap::EvaluateResponse, Vec<dap::Variable>)>>,
+)> {
+    let (session, active_frame) = editor.project()?.read(cx).active_debug_session(cx)?;
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let range = debug_hover_expression_range(&snapshot, anchor.to_offset(&snapshot))?;
+    let expression = snapshot.text_for_range(range.clone()).collect::<String>();
+    let evaluation = session.update(cx, |session, cx| {
+        session.evaluate_hover(expression.clone(), active_frame.stack_frame_id, cx)
+    });
+    Some((
+        snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end),
+        expression,
+        evaluation,
+    ))
+}
+
+/// Returns the range of the identifier at `offset` along with the member accesses preceding it,
+/// or `None` if there's no identifier there.
+fn debug_hover_expression_range(
+    snapshot: &MultiBufferSnapshot,
+    offset: usize,
+) -> Option<Range<usize>> {
+    let (mut range, kind) = snapshot.surrounding_word(offset, None);
+    if kind != Some(CharKind::Word)
+        || snapshot
+            .chars_at(range.start)
+            .next()
+            .is_none_or(|first_char| first_char.is_ascii_digit())
+    {
+        return None;
+    }
+    while range.start > 1 && snapshot.reversed_chars_at(range.start).next() == Some('.') {
+        let (object_range, kind) = snapshot.surrounding_word(range.start - 1, None);
+        if kind != Some(CharKind::Word) || object_range.end != range.start - 1 {
+            break;
+        }
+        range.start = object_range.start;
+    }
+    Some(range)
+}
+
 fn same_info_hover(editor: &Editor, snapshot: &EditorSnapshot, anchor: Anchor) -> bool {
     editor
         .hover_state
@@ -1000,6 +1095,7 @@ mod tests {
     use gpui::App;
     use indoc::indoc;
     use markdown::parser::MarkdownEvent;
+    use multi_buffer::MultiBuffer;
     use settings::InlayHintSettingsContent;
     use smol::stream::StreamExt;
     use std::sync::atomic;
@@ -1487,6 +1583,34 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    fn test_debug_hover_expression_range(cx: &mut gpui::TestAppContext) {
+        let text = "let y = self.x[0] + a.b.c + point.y;";
+        let buffer = cx.update(|cx| MultiBuffer::build_simple(text, cx));
+        let snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+        let expression_at = |hovered: &str, occurrence: usize| {
+            let offset = text.match_indices(hovered).nth(occurrence).unwrap().0;
+            debug_hover_expression_range(&snapshot, offset).map(|range| text[range].to_string())
+        };
+
+        // Member accesses preceding the hovered identifier are included, but not those after it.
+        assert_eq!(expression_at("c", 0).as_deref(), Some("a.b.c"));
+        assert_eq!(expression_at("b", 0).as_deref(), Some("a.b"));
+        assert_eq!(expression_at("a", 0).as_deref(), Some("a"));
+
+        // Indexing ends the expression, and literals aren't evaluated.
+        assert_eq!(expression_at("x", 0).as_deref(), Some("self.x"));
+        assert_eq!(expression_at("self", 0).as_deref(), Some("self"));
+        assert_eq!(expression_at("0", 0), None);
+
+        // Hovering the middle of an identifier evaluates the whole identifier.
+        assert_eq!(expression_at("oin", 0).as_deref(), Some("point"));
+        assert_eq!(expression_at("y", 1).as_deref(), Some("point.y"));
+
+        // Operators aren't expressions.
+        assert_eq!(expression_at("+", 0), None);
+    }
+
     #[gpui::test]
     // https://github.com/zed-industries/zed/issues/15498
     async fn test_info_hover_with_hrs(cx: &mut gpui::TestAppContext) {
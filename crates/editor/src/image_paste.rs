@@ -0,0 +1,282 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{
+    DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Image, ImageFormat, Subscription,
+    Task, WeakEntity,
+};
+use multi_buffer::Anchor;
+use project::{Project, Worktree, WorktreeId};
+use settings::Settings as _;
+use ui::prelude::*;
+use util::{paths::PathStyle, rel_path::RelPath};
+use workspace::{ModalView, notifications::NotifyTaskExt as _};
+
+use crate::{Editor, EditorEvent, EditorSettings};
+
+/// An image pasted into a Markdown file, waiting to be saved next to it.
+pub(crate) struct PastedImage {
+    editor: WeakEntity<Editor>,
+    project: Entity<Project>,
+    worktree_id: WorktreeId,
+    /// The directory to save the image into, relative to the worktree root.
+    directory: Arc<RelPath>,
+    /// The directory to save the image into, relative to the Markdown file.
+    link_directory: Arc<RelPath>,
+    extension: &'static str,
+    bytes: Vec<u8>,
+    /// Where to insert the link to the image.
+    ranges: Vec<Range<Anchor>>,
+}
+
+impl PastedImage {
+    /// Saves the image as `name`, followed by the extension of its format, and replaces the
+    /// ranges the image was pasted over with a link to it.
+    fn save(self, name: &str, window: &mut Window, cx: &mut App) -> Task<Result<()>> {
+        let file_name = format!("{name}.{}", self.extension);
+        let file_name = match RelPath::unix(&file_name) {
+            Ok(file_name) if file_name.components().count() == 1 => file_name,
+            _ => return Task::ready(Err(anyhow!("Invalid image name {name:?}"))),
+        };
+        let path = self.directory.join(file_name);
+        let Some(worktree) = self.project.read(cx).worktree_for_id(self.worktree_id, cx) else {
+            return Task::ready(Err(anyhow!("The worktree of the Markdown file was closed")));
+        };
+        if worktree.read(cx).entry_for_path(&path).is_some() {
+            return Task::ready(Err(anyhow!(
+                "{} already exists",
+                path.display(PathStyle::Posix)
+            )));
+        }
+
+        let link = markdown_image_link(name, &self.link_directory.join(file_name));
+        let create = worktree.update(cx, |worktree, cx| {
+            worktree.create_entry(path.clone(), false, Some(self.bytes), cx)
+        });
+        let editor = self.editor;
+        let ranges = self.ranges;
+        window.spawn(cx, async move |cx| {
+            create
+                .await
+                .with_context(|| format!("saving {}", path.display(PathStyle::Posix)))?;
+            editor.update_in(cx, |editor, window, cx| {
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_anchor_ranges(ranges);
+                });
+                editor.insert(&link, window, cx);
+            })
+        })
+    }
+}
+
+impl Editor {
+    /// Saves an image pasted into a Markdown file into the assets directory next to the file,
+    /// and inserts a link to it. Returns whether the image was handled.
+    pub(crate) fn paste_image(
+        &mut self,
+        image: &Image,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let settings = EditorSettings::get_global(cx).image_paste.clone();
+        if !settings.enabled || !self.mode.is_full() || self.read_only(cx) {
+            return false;
+        }
+        let Some(project) = self.project.clone() else {
+            return false;
+        };
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return false;
+        };
+        let buffer = buffer.read(cx);
+        if !buffer
+            .language()
+            .is_some_and(|language| language.name() == "Markdown".into())
+        {
+            return false;
+        }
+        let Some(file) = project::File::from_dyn(buffer.file()) else {
+            return false;
+        };
+        let worktree_id = file.worktree_id(cx);
+        let link_directory = match RelPath::new(Path::new(&settings.directory), PathStyle::Posix) {
+            Ok(directory) => directory.into_arc(),
+            Err(error) => {
+                log::error!(
+                    "invalid image paste directory {:?}: {error:#}",
+                    settings.directory
+                );
+                return false;
+            }
+        };
+        let directory = file
+            .path
+            .parent()
+            .unwrap_or(RelPath::empty())
+            .join(&link_directory);
+        let Some(worktree) = project.read(cx).worktree_for_id(worktree_id, cx) else {
+            return false;
+        };
+
+        let extension = image_extension(image.format);
+        let name = unused_image_name(worktree.read(cx), &directory, extension);
+        let image = PastedImage {
+            editor: cx.entity().downgrade(),
+            project,
+            worktree_id,
+            directory,
+            link_directory,
+            extension,
+            bytes: image.bytes.clone(),
+            ranges: self.selections.disjoint_anchor_ranges().collect(),
+        };
+
+        if settings.prompt_for_name
+            && let Some(workspace) = self.workspace()
+        {
+            workspace.update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    ImageNameModal::new(image, name, window, cx)
+                });
+            });
+        } else {
+            image
+                .save(&name, window, cx)
+                .detach_and_notify_err(window, cx);
+        }
+        true
+    }
+}
+
+/// Returns the first of `image`, `image-1`, `image-2`, … that does not name a file in `directory`.
+fn unused_image_name(worktree: &Worktree, directory: &RelPath, extension: &str) -> String {
+    (0..)
+        .map(|ix| {
+            if ix == 0 {
+                "image".to_string()
+            } else {
+                format!("image-{ix}")
+            }
+        })
+        .find(|name| {
+            RelPath::unix(&format!("{name}.{extension}")).is_ok_and(|file_name| {
+                worktree
+                    .entry_for_path(&directory.join(file_name))
+                    .is_none()
+            })
+        })
+        .unwrap()
+}
+
+fn image_extension(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "png",
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::Webp => "webp",
+        ImageFormat::Gif => "gif",
+        ImageFormat::Svg => "svg",
+        ImageFormat::Bmp => "bmp",
+        ImageFormat::Tiff => "tiff",
+    }
+}
+
+fn markdown_image_link(alt_text: &str, path: &RelPath) -> String {
+    let path = path.as_unix_str();
+    if path.contains(char::is_whitespace) {
+        format!("![{alt_text}](<{path}>)")
+    } else {
+        format!("![{alt_text}]({path})")
+    }
+}
+
+/// Asks for the name of an image pasted into a Markdown file.
+pub struct ImageNameModal {
+    name_editor: Entity<Editor>,
+    image: Option<PastedImage>,
+    _subscription: Subscription,
+}
+
+impl ImageNameModal {
+    fn new(image: PastedImage, name: String, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Image name", window, cx);
+            editor.set_text(name, window, cx);
+            editor.select_all(&Default::default(), window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&name_editor, |_, _, event: &EditorEvent, cx| {
+            if matches!(event, EditorEvent::Blurred) {
+                cx.emit(DismissEvent);
+            }
+        });
+        Self {
+            name_editor,
+            image: Some(image),
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        if let Some(image) = self.image.take() {
+            image
+                .save(&name, window, cx)
+                .detach_and_notify_err(window, cx);
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl ModalView for ImageNameModal {}
+
+impl EventEmitter<DismissEvent> for ImageNameModal {}
+
+impl Focusable for ImageNameModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for ImageNameModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let directory = self
+            .image
+            .as_ref()
+            .map(|image| image.link_directory.display(PathStyle::Posix).into_owned())
+            .unwrap_or_default();
+        let description = if directory.is_empty() {
+            "Name the pasted image".to_string()
+        } else {
+            format!("Name the pasted image, saved in {directory}")
+        };
+
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("ImageNameModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.name_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .child(Label::new(description).color(Color::Muted)),
+            )
+    }
+}
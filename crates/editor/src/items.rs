@@ -59,6 +59,8 @@ use workspace::{
 };
 
 pub const MAX_TAB_TITLE_LEN: usize = 24;
+/// How many lines of a generated file are shown unfolded when it's opened.
+const GENERATED_FILE_PREVIEW_LINES: u32 = 10;
 
 impl FollowableItem for Editor {
     fn remote_id(&self) -> Option<ViewId> {
@@ -801,6 +803,10 @@ impl Item for Editor {
         self.buffer().read(cx).read(cx).has_conflict()
     }
 
+    fn has_external_changes(&self, _: &App) -> bool {
+        self.external_change.is_some()
+    }
+
     fn can_save(&self, cx: &App) -> bool {
         let buffer = &self.buffer().read(cx);
         if let Some(buffer) = buffer.as_singleton() {
@@ -1033,6 +1039,15 @@ impl Item for Editor {
     fn preserve_preview(&self, cx: &App) -> bool {
         self.buffer.read(cx).preserve_preview(cx)
     }
+
+    fn handle_drop(
+        &mut self,
+        dropped: &dyn std::any::Any,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.insert_dropped_paths(dropped, window, cx)
+    }
 }
 
 impl SerializableItem for Editor {
@@ -1126,7 +1141,8 @@ impl SerializableItem for Editor {
 
                     cx.update(|window, cx| {
                         cx.new(|cx| {
-                            let mut editor = Editor::for_buffer(buffer, Some(project), window, cx);
+                            let mut editor =
+                                Editor::for_restored_buffer(buffer, Some(project), window, cx);
 
                             editor.read_metadata_from_db(item_id, workspace_id, window, cx);
                             editor
@@ -1180,8 +1196,12 @@ impl SerializableItem for Editor {
 
                             cx.update(|window, cx| {
                                 cx.new(|cx| {
-                                    let mut editor =
-                                        Editor::for_buffer(buffer, Some(project), window, cx);
+                                    let mut editor = Editor::for_restored_buffer(
+                                        buffer,
+                                        Some(project),
+                                        window,
+                                        cx,
+                                    );
 
                                     editor.read_metadata_from_db(item_id, workspace_id, window, cx);
                                     editor
@@ -1314,6 +1334,22 @@ impl SerializableItem for Editor {
     }
 }
 
+/// Returns the paths and contents of the unsaved buffers that the workspace's editors restore when
+/// they're deserialized, which is only done when `session.restore_unsaved_buffers` is enabled.
+/// Untitled buffers have no path.
+pub fn serialized_unsaved_buffers(
+    workspace_id: WorkspaceId,
+    cx: &App,
+) -> Task<Result<Vec<(Option<PathBuf>, String)>>> {
+    if !ProjectSettings::get_global(cx)
+        .session
+        .restore_unsaved_buffers
+    {
+        return Task::ready(Ok(Vec::new()));
+    }
+    cx.background_spawn(async move { DB.get_serialized_contents(workspace_id) })
+}
+
 #[derive(Debug, Default)]
 struct EditorRestorationData {
     entries: HashMap<PathBuf, RestorationData>,
@@ -1370,6 +1406,18 @@ impl ProjectItem for Editor {
                 snapshot.anchor_before(Point::new(top_row, 0)),
             );
             editor.set_scroll_anchor(ScrollAnchor { anchor, offset }, window, cx);
+        } else if buffer.read(cx).is_generated() {
+            // Generated files open as a preview of their first lines, which usually say how
+            // they were generated, with the rest folded.
+            let snapshot = buffer.read(cx).snapshot();
+            let max_point = snapshot.max_point();
+            if max_point.row >= GENERATED_FILE_PREVIEW_LINES {
+                let fold_start = Point::new(
+                    GENERATED_FILE_PREVIEW_LINES - 1,
+                    snapshot.line_len(GENERATED_FILE_PREVIEW_LINES - 1),
+                );
+                editor.fold_ranges(vec![fold_start..max_point], false, window, cx);
+            }
         }
 
         editor
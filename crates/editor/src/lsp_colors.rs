@@ -1,10 +1,12 @@
 use std::{cmp, ops::Range};
 
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use futures::future::join_all;
 use gpui::{Hsla, Rgba};
 use itertools::Itertools;
-use language::point_from_lsp;
+use language::{
+    BufferSnapshot, PointUtf16, language_settings::language_settings, point_from_lsp, point_to_lsp,
+};
 use multi_buffer::Anchor;
 use project::{DocumentColor, lsp_store::LspFetchStrategy};
 use settings::Settings as _;
@@ -14,7 +16,8 @@ use util::post_inc;
 
 use crate::{
     DisplayPoint, Editor, EditorSettings, EditorSnapshot, InlayId, InlaySplice, RangeToAnchorExt,
-    display_map::Inlay, editor_settings::DocumentColorsRenderMode,
+    color_literals::find_color_literals, display_map::Inlay,
+    editor_settings::DocumentColorsRenderMode,
 };
 
 #[derive(Debug)]
@@ -142,6 +145,41 @@ impl LspColorData {
     }
 }
 
+/// Turns the color literals in an excerpt into document colors, as if a language server had
+/// reported them.
+fn color_literal_document_colors(
+    buffer_snapshot: &BufferSnapshot,
+    excerpt_range: &Range<PointUtf16>,
+) -> Vec<DocumentColor> {
+    let excerpt_range = excerpt_range.to_offset(buffer_snapshot);
+    let text = buffer_snapshot
+        .text_for_range(excerpt_range.clone())
+        .collect::<String>();
+    find_color_literals(&text)
+        .into_iter()
+        .map(|literal| {
+            let start =
+                buffer_snapshot.offset_to_point_utf16(excerpt_range.start + literal.range.start);
+            let end =
+                buffer_snapshot.offset_to_point_utf16(excerpt_range.start + literal.range.end);
+            DocumentColor {
+                lsp_range: lsp::Range {
+                    start: point_to_lsp(start),
+                    end: point_to_lsp(end),
+                },
+                color: lsp::Color {
+                    red: literal.color.r,
+                    green: literal.color.g,
+                    blue: literal.color.b,
+                    alpha: literal.color.a,
+                },
+                resolved: true,
+                color_presentations: Vec::new(),
+            }
+        })
+        .collect()
+}
+
 impl Editor {
     pub(super) fn refresh_colors(
         &mut self,
@@ -174,6 +212,20 @@ impl Editor {
             .unique_by(|buffer| buffer.read(cx).remote_id())
             .collect::<Vec<_>>();
 
+        let color_literal_buffers = visible_buffers
+            .iter()
+            .filter(|buffer| {
+                let buffer = buffer.read(cx);
+                language_settings(
+                    buffer.language().map(|language| language.name()),
+                    buffer.file(),
+                    cx,
+                )
+                .color_literals
+            })
+            .map(|buffer| buffer.read(cx).remote_id())
+            .collect::<HashSet<_>>();
+
         let all_colors_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
             visible_buffers
                 .into_iter()
@@ -226,6 +278,17 @@ impl Editor {
                 let Some(excerpts) = editor_excerpts.get(&buffer_id) else {
                     continue;
                 };
+                let colors = colors.map(|mut colors| {
+                    if colors.colors.is_empty() && color_literal_buffers.contains(&buffer_id) {
+                        colors.colors = excerpts
+                            .iter()
+                            .flat_map(|(_, buffer_snapshot, excerpt_range)| {
+                                color_literal_document_colors(buffer_snapshot, excerpt_range)
+                            })
+                            .collect();
+                    }
+                    colors
+                });
                 match colors {
                     Ok(colors) => {
                         if colors.colors.is_empty() {
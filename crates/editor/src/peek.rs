@@ -0,0 +1,268 @@
+use std::{cmp::Reverse, ops::Range, sync::Arc};
+
+use anyhow::Result;
+use gpui::{AppContext as _, Entity, Focusable as _, Task};
+use itertools::Itertools as _;
+use language::{Buffer, Point};
+use multi_buffer::{Anchor, MultiBuffer, PathKey, ToPoint as _};
+use project::LocationLink;
+use text::ToPoint as _;
+use ui::prelude::*;
+
+use crate::{
+    Editor, EditorEvent, GotoDefinitionKind, PeekDefinition, PeekReferences, SelectionEffects,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    hover_links, multibuffer_context_lines,
+    scroll::Autoscroll,
+};
+
+/// The height of a peek view, in lines.
+const PEEK_HEIGHT: u32 = 12;
+
+/// An editor showing the locations of a definition or of references, embedded below the line
+/// it was opened from. Peek editors can open peeks of their own.
+pub(crate) struct PeekState {
+    editor: Entity<Editor>,
+    block_id: CustomBlockId,
+}
+
+impl Editor {
+    pub fn peek_definition(
+        &mut self,
+        _: &PeekDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let provider = self.semantics_provider.clone()?;
+        let head = self.selections.newest_anchor().head();
+        let (buffer, buffer_head) = self.buffer.read(cx).text_anchor_for_position(head, cx)?;
+        let definitions =
+            provider.definitions(&buffer, buffer_head, GotoDefinitionKind::Symbol, cx)?;
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let (word_range, _) = snapshot.surrounding_word(head, None);
+        let word = snapshot.text_for_range(word_range).collect::<String>();
+        let title = if word.is_empty() {
+            "Definition".to_owned()
+        } else {
+            format!("Definition of {word}")
+        };
+
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let Some(definitions) = definitions.await? else {
+                return Ok(());
+            };
+            editor.update_in(cx, |editor, window, cx| {
+                let locations = definitions
+                    .into_iter()
+                    .filter(|link: &LocationLink| {
+                        hover_links::exclude_link_to_position(&buffer, &buffer_head, link, cx)
+                    })
+                    .map(|link| {
+                        let range = link.target.range.to_point(link.target.buffer.read(cx));
+                        (link.target.buffer, range)
+                    })
+                    .into_group_map();
+                editor.show_peek(head, title, locations, window, cx);
+            })
+        }))
+    }
+
+    pub fn peek_references(
+        &mut self,
+        _: &PeekReferences,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let project = self.project.clone()?;
+        let head = self.selections.newest_anchor().head();
+        let (buffer, buffer_head) = self.buffer.read(cx).text_anchor_for_position(head, cx)?;
+        let references = project.update(cx, |project, cx| {
+            project.references(&buffer, buffer_head, cx)
+        });
+
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let Some(references) = references.await? else {
+                return Ok(());
+            };
+            editor.update_in(cx, |editor, window, cx| {
+                let locations = references
+                    .into_iter()
+                    .map(|location| {
+                        let range = location.range.to_point(location.buffer.read(cx));
+                        (location.buffer, range)
+                    })
+                    .into_group_map();
+                let target = locations
+                    .iter()
+                    .flat_map(|(buffer, ranges)| {
+                        ranges.iter().map(|range| {
+                            buffer
+                                .read(cx)
+                                .text_for_range(range.clone())
+                                .collect::<String>()
+                        })
+                    })
+                    .filter(|text| !text.contains('\n'))
+                    .unique()
+                    .take(3)
+                    .join(", ");
+                let title = if target.is_empty() {
+                    "References".to_owned()
+                } else {
+                    format!("References to {target}")
+                };
+                editor.show_peek(head, title, locations, window, cx);
+            })
+        }))
+    }
+
+    /// Shows the text hidden by a fold in a peek view, without unfolding it.
+    pub(crate) fn peek_fold(
+        &mut self,
+        range: Range<Anchor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let multibuffer = self.buffer.read(cx);
+        let snapshot = multibuffer.snapshot(cx);
+        let mut locations = std::collections::HashMap::default();
+        for (buffer_snapshot, buffer_range, _) in snapshot.range_to_buffer_ranges(range.clone()) {
+            if let Some(buffer) = multibuffer.buffer(buffer_snapshot.remote_id()) {
+                locations.entry(buffer).or_insert_with(Vec::new).push(
+                    buffer_snapshot.offset_to_point(buffer_range.start)
+                        ..buffer_snapshot.offset_to_point(buffer_range.end),
+                );
+            }
+        }
+        let start = range.start.to_point(&snapshot);
+        let end = range.end.to_point(&snapshot);
+        let title = format!("Folded lines {}–{}", start.row + 1, end.row + 1);
+        self.show_peek(range.start, title, locations, window, cx);
+    }
+
+    fn show_peek(
+        &mut self,
+        position: Anchor,
+        title: String,
+        locations: std::collections::HashMap<Entity<Buffer>, Vec<Range<Point>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if locations.is_empty() {
+            return;
+        }
+        self.close_peek(window, cx);
+
+        let capability = self.buffer.read(cx).capability();
+        let mut ranges = Vec::new();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(capability);
+            for (buffer, mut ranges_for_buffer) in locations {
+                ranges_for_buffer.sort_by_key(|range| (range.start, Reverse(range.end)));
+                ranges_for_buffer.dedup();
+                let (new_ranges, _) = multibuffer.set_excerpts_for_path(
+                    PathKey::for_buffer(&buffer, cx),
+                    buffer,
+                    ranges_for_buffer,
+                    multibuffer_context_lines(cx),
+                    cx,
+                );
+                ranges.extend(new_ranges);
+            }
+            multibuffer.with_title(title.clone())
+        });
+        let project = self.project.clone();
+        let peek_editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, project, window, cx);
+            if let Some(first_range) = ranges.first() {
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_anchor_ranges([first_range.start..first_range.start]);
+                });
+            }
+            editor.highlight_background::<PeekState>(
+                &ranges,
+                |theme| theme.colors().editor_highlighted_line_background,
+                cx,
+            );
+            editor.register_buffers_with_language_servers(cx);
+            editor
+        });
+        cx.subscribe(&peek_editor, |_, _, event: &EditorEvent, cx| {
+            if event == &EditorEvent::Focused {
+                cx.emit(EditorEvent::FocusedIn)
+            }
+        })
+        .detach();
+
+        let outer_editor = cx.entity().downgrade();
+        let block_id = self.insert_blocks(
+            [BlockProperties {
+                style: BlockStyle::Flex,
+                placement: BlockPlacement::Below(position),
+                height: Some(PEEK_HEIGHT),
+                render: Arc::new({
+                    let peek_editor = peek_editor.clone();
+                    move |cx: &mut BlockContext| {
+                        let outer_editor = outer_editor.clone();
+                        v_flex()
+                            .id(cx.block_id)
+                            .occlude()
+                            .h(cx.line_height * PEEK_HEIGHT as f32)
+                            .pl(cx.margins.gutter.full_width())
+                            .border_y_1()
+                            .border_color(cx.theme().colors().border)
+                            .child(
+                                h_flex()
+                                    .px_2()
+                                    .justify_between()
+                                    .bg(cx.theme().colors().editor_subheader_background)
+                                    .child(Label::new(title.clone()).size(LabelSize::Small))
+                                    .child(
+                                        IconButton::new("close-peek", IconName::Close)
+                                            .icon_size(IconSize::Small)
+                                            .on_click(move |_, window, cx| {
+                                                outer_editor
+                                                    .update(cx, |editor, cx| {
+                                                        editor.close_peek(window, cx);
+                                                    })
+                                                    .ok();
+                                            }),
+                                    ),
+                            )
+                            .child(div().flex_1().min_h_0().child(peek_editor.clone()))
+                            .into_any_element()
+                    }
+                }),
+                priority: 0,
+            }],
+            Some(Autoscroll::fit()),
+            cx,
+        )[0];
+        window.focus(&peek_editor.focus_handle(cx));
+        self.peek = Some(PeekState {
+            editor: peek_editor,
+            block_id,
+        });
+        cx.notify();
+    }
+
+    /// Closes the peek view, returning whether one was open.
+    pub(crate) fn close_peek(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(peek) = self.peek.take() else {
+            return false;
+        };
+        if peek.editor.focus_handle(cx).contains_focused(window, cx) {
+            window.focus(&self.focus_handle);
+        }
+        self.remove_blocks(
+            [peek.block_id].into_iter().collect(),
+            Some(Autoscroll::fit()),
+            cx,
+        );
+        true
+    }
+
+    pub fn peek_editor(&self) -> Option<&Entity<Editor>> {
+        self.peek.as_ref().map(|peek| &peek.editor)
+    }
+}
@@ -104,6 +104,7 @@ impl Domain for EditorDb {
     //   language: Option<String>,
     //   mtime_seconds: Option<i64>,
     //   mtime_nanos: Option<i32>,
+    //   font_size_adjustment: Option<f64>,
     // )
     //
     // editor_selections(
@@ -197,6 +198,9 @@ impl Domain for EditorDb {
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        sql! (
+            ALTER TABLE editors ADD COLUMN font_size_adjustment REAL DEFAULT NULL;
+        ),
     ];
 }
 
@@ -233,6 +237,13 @@ impl EditorDb {
         }
     }
 
+    query! {
+        pub fn get_serialized_contents(workspace_id: WorkspaceId) -> Result<Vec<(Option<PathBuf>, String)>> {
+            SELECT path, contents FROM editors
+            WHERE workspace_id = ? AND contents IS NOT NULL
+        }
+    }
+
     // Returns the scroll top row, and offset
     query! {
         pub fn get_scroll_position(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<(u32, f64, f64)>> {
@@ -259,6 +270,26 @@ impl EditorDb {
         }
     }
 
+    query! {
+        pub fn get_font_size_adjustment(item_id: ItemId, workspace_id: WorkspaceId) -> Result<Option<Option<f64>>> {
+            SELECT font_size_adjustment
+            FROM editors
+            WHERE item_id = ? AND workspace_id = ?
+        }
+    }
+
+    query! {
+        pub async fn save_font_size_adjustment(
+            item_id: ItemId,
+            workspace_id: WorkspaceId,
+            font_size_adjustment: f64
+        ) -> Result<()> {
+            UPDATE OR IGNORE editors
+            SET font_size_adjustment = ?3
+            WHERE item_id = ?1 AND workspace_id = ?2
+        }
+    }
+
     query! {
         pub fn get_editor_selections(
             editor_id: ItemId,
@@ -465,4 +496,25 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_save_and_get_font_size_adjustment() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+        DB.save_serialized_editor(5678, workspace_id, SerializedEditor::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_font_size_adjustment(5678, workspace_id).unwrap(),
+            Some(None)
+        );
+
+        DB.save_font_size_adjustment(5678, workspace_id, 2.0)
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_font_size_adjustment(5678, workspace_id).unwrap(),
+            Some(Some(2.0))
+        );
+    }
 }
@@ -0,0 +1,86 @@
+use gpui::{Context, Entity, IntoElement, ParentElement, Render, Subscription, Window, div};
+use ui::{
+    Button, ButtonCommon, Clickable, Color, FluentBuilder, IconName, IconPosition, IconSize,
+    LabelSize, Tooltip,
+};
+use workspace::{StatusItemView, item::ItemHandle};
+
+use crate::{Editor, ToggleReadOnly};
+
+/// Shows a lock in the status bar when the active editor's buffer is read-only, which can be
+/// clicked to make the buffer writable.
+pub struct ReadOnlyIndicator {
+    editor: Option<Entity<Editor>>,
+    is_locked: bool,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl ReadOnlyIndicator {
+    pub fn new() -> Self {
+        Self {
+            editor: None,
+            is_locked: false,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn update_locked(&mut self, editor: Entity<Editor>, _: &mut Window, cx: &mut Context<Self>) {
+        let is_locked = editor.read(cx).is_buffer_locked(cx);
+        if self.is_locked != is_locked {
+            self.is_locked = is_locked;
+            cx.notify();
+        }
+    }
+}
+
+impl Default for ReadOnlyIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for ReadOnlyIndicator {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().when(self.is_locked, |el| {
+            el.child(
+                Button::new("read-only-indicator", "Read-only")
+                    .icon(IconName::LockOutlined)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::XSmall)
+                    .icon_color(Color::Muted)
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        if let Some(editor) = this.editor.clone() {
+                            editor.update(cx, |editor, cx| {
+                                editor.toggle_read_only(&ToggleReadOnly, window, cx)
+                            });
+                        }
+                    }))
+                    .tooltip(|window, cx| {
+                        Tooltip::for_action("Make Writable", &ToggleReadOnly, window, cx)
+                    }),
+            )
+        })
+    }
+}
+
+impl StatusItemView for ReadOnlyIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor = Some(cx.observe_in(&editor, window, Self::update_locked));
+            self.update_locked(editor.clone(), window, cx);
+            self.editor = Some(editor);
+        } else {
+            self.editor = None;
+            self.is_locked = false;
+            self._observe_active_editor = None;
+        }
+
+        cx.notify();
+    }
+}
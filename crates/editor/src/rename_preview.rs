@@ -0,0 +1,643 @@
+use std::{any::TypeId, ops::Range, path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use gpui::{
+    AnyElement, App, AppContext as _, Entity, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity,
+};
+use language::{Bias, Buffer, Capability, Point, range_from_lsp};
+use lsp::LanguageServerId;
+use multi_buffer::{MultiBuffer, PathKey};
+use project::Project;
+use ui::{Checkbox, ToggleState, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+use crate::{
+    Editor,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle},
+    multibuffer_context_lines,
+};
+
+/// Shows every edit of a rename before it is applied, grouped by file, so that individual edits,
+/// whole files, and the renames of files themselves can be left out.
+pub struct RenamePreview {
+    editor: Entity<Editor>,
+    project: Entity<Project>,
+    server_id: LanguageServerId,
+    title: SharedString,
+    operations: Vec<PreviewOperation>,
+    apply_task: Option<Task<()>>,
+}
+
+enum PreviewOperation {
+    Edit(FileEdits),
+    RenameFile {
+        rename: lsp::RenameFile,
+        label: SharedString,
+        included: bool,
+    },
+    /// Creating or deleting a file, which is always applied.
+    Other(lsp::DocumentChangeOperation),
+}
+
+struct FileEdits {
+    text_document: lsp::OptionalVersionedTextDocumentIdentifier,
+    label: SharedString,
+    edits: Vec<PreviewEdit>,
+}
+
+struct PreviewEdit {
+    edit: lsp::Edit,
+    row: u32,
+    included: bool,
+}
+
+impl RenamePreview {
+    /// Opens a preview of the given workspace edit in the active pane.
+    pub fn open(
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        server_id: LanguageServerId,
+        edit: lsp::WorkspaceEdit,
+        title: String,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let operations = workspace_edit_operations(edit);
+
+        // Edits that follow the rename of a file refer to it by its new path, which doesn't exist
+        // until the edit is applied.
+        let mut renamed_from = HashMap::default();
+        let mut paths = Vec::new();
+        for operation in &operations {
+            match operation {
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(rename)) => {
+                    let old_uri = renamed_from
+                        .get(&rename.old_uri)
+                        .cloned()
+                        .unwrap_or_else(|| rename.old_uri.clone());
+                    renamed_from.insert(rename.new_uri.clone(), old_uri);
+                }
+                lsp::DocumentChangeOperation::Edit(edit) => {
+                    let uri = renamed_from
+                        .get(&edit.text_document.uri)
+                        .unwrap_or(&edit.text_document.uri);
+                    paths.push(uri.to_file_path().ok());
+                }
+                lsp::DocumentChangeOperation::Op(_) => {}
+            }
+        }
+
+        let mut buffer_tasks = HashMap::default();
+        for path in paths.iter().flatten() {
+            if !buffer_tasks.contains_key(path) {
+                let task = project.update(cx, |project, cx| project.open_local_buffer(path, cx));
+                buffer_tasks.insert(path.clone(), task);
+            }
+        }
+
+        window.spawn(cx, async move |cx| {
+            let mut buffers = HashMap::<PathBuf, Entity<Buffer>>::default();
+            for (path, task) in buffer_tasks {
+                buffers.insert(path, task.await?);
+            }
+            let buffers = paths
+                .into_iter()
+                .map(|path| path.and_then(|path| buffers.get(&path).cloned()))
+                .collect::<Vec<_>>();
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let preview = cx.new(|cx| {
+                    Self::new(title, project, server_id, operations, buffers, window, cx)
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(preview), true, true, None, window, cx);
+                });
+            })
+        })
+    }
+
+    fn new(
+        title: String,
+        project: Entity<Project>,
+        server_id: LanguageServerId,
+        operations: Vec<lsp::DocumentChangeOperation>,
+        buffers: Vec<Option<Entity<Buffer>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer =
+            cx.new(|_| MultiBuffer::new(Capability::ReadOnly).with_title(title.clone()));
+        let mut buffers = buffers.into_iter();
+        let mut preview_operations = Vec::new();
+        // The excerpt ranges of each file, along with the edits they contain.
+        let mut excerpts = Vec::<(Entity<Buffer>, Vec<(usize, usize, Range<Point>)>)>::new();
+        for operation in operations {
+            match operation {
+                lsp::DocumentChangeOperation::Edit(document_edit) => {
+                    let buffer = buffers.next().flatten();
+                    let label = buffer
+                        .as_ref()
+                        .and_then(|buffer| {
+                            let file = buffer.read(cx).file()?;
+                            Some(file.full_path(cx).to_string_lossy().into_owned())
+                        })
+                        .unwrap_or_else(|| document_edit.text_document.uri.to_string());
+
+                    let mut edits = document_edit
+                        .edits
+                        .into_iter()
+                        .map(|edit| PreviewEdit {
+                            row: edit_range(&edit).start.line,
+                            edit,
+                            included: true,
+                        })
+                        .collect::<Vec<_>>();
+                    edits.sort_by_key(|edit| {
+                        let range = edit_range(&edit.edit);
+                        (range.start.line, range.start.character)
+                    });
+
+                    if let Some(buffer) = buffer {
+                        let snapshot = buffer.read(cx).snapshot();
+                        let operation_ix = preview_operations.len();
+                        let ranges = edits.iter().enumerate().map(|(edit_ix, edit)| {
+                            let range = range_from_lsp(edit_range(&edit.edit));
+                            let start = snapshot.clip_point_utf16(range.start, Bias::Left);
+                            let end = snapshot.clip_point_utf16(range.end, Bias::Left);
+                            let range = snapshot.point_utf16_to_point(start)
+                                ..snapshot.point_utf16_to_point(end);
+                            (operation_ix, edit_ix, range)
+                        });
+                        match excerpts
+                            .iter_mut()
+                            .find(|(existing, _)| *existing == buffer)
+                        {
+                            Some((_, existing)) => existing.extend(ranges),
+                            None => excerpts.push((buffer.clone(), ranges.collect())),
+                        }
+                    }
+
+                    preview_operations.push(PreviewOperation::Edit(FileEdits {
+                        text_document: document_edit.text_document,
+                        label: label.into(),
+                        edits,
+                    }));
+                }
+                lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(rename)) => {
+                    let label = format!(
+                        "{} → {}",
+                        uri_label(&rename.old_uri),
+                        uri_label(&rename.new_uri)
+                    );
+                    preview_operations.push(PreviewOperation::RenameFile {
+                        rename,
+                        label: label.into(),
+                        included: true,
+                    });
+                }
+                operation => preview_operations.push(PreviewOperation::Other(operation)),
+            }
+        }
+
+        let mut edit_anchors = Vec::new();
+        multibuffer.update(cx, |multibuffer, cx| {
+            for (buffer, mut edits) in excerpts {
+                edits.sort_by_key(|(_, _, range)| range.start);
+                let ranges = edits
+                    .iter()
+                    .map(|(_, _, range)| range.clone())
+                    .collect::<Vec<_>>();
+                let (anchor_ranges, _) = multibuffer.set_excerpts_for_path(
+                    PathKey::for_buffer(&buffer, cx),
+                    buffer,
+                    ranges,
+                    multibuffer_context_lines(cx),
+                    cx,
+                );
+                edit_anchors.extend(
+                    edits
+                        .into_iter()
+                        .zip(anchor_ranges)
+                        .map(|((operation_ix, edit_ix, _), range)| (operation_ix, edit_ix, range)),
+                );
+            }
+        });
+
+        let this = cx.weak_entity();
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            editor.highlight_background::<RenamePreview>(
+                &edit_anchors
+                    .iter()
+                    .map(|(_, _, range)| range.clone())
+                    .collect::<Vec<_>>(),
+                |theme| theme.colors().editor_document_highlight_write_background,
+                cx,
+            );
+            editor.insert_blocks(
+                edit_anchors
+                    .into_iter()
+                    .map(|(operation_ix, edit_ix, range)| {
+                        let this = this.clone();
+                        BlockProperties {
+                            style: BlockStyle::Sticky,
+                            placement: BlockPlacement::Above(range.start),
+                            height: Some(1),
+                            render: Arc::new(move |cx: &mut BlockContext| {
+                                render_edit_checkbox(&this, operation_ix, edit_ix, cx)
+                            }),
+                            priority: 0,
+                        }
+                    }),
+                None,
+                cx,
+            );
+            editor
+        });
+
+        Self {
+            editor,
+            project,
+            server_id,
+            title: title.into(),
+            operations: preview_operations,
+            apply_task: None,
+        }
+    }
+
+    fn file_edits(&self) -> impl Iterator<Item = (usize, &FileEdits)> {
+        self.operations
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, operation)| match operation {
+                PreviewOperation::Edit(file_edits) => Some((ix, file_edits)),
+                _ => None,
+            })
+    }
+
+    pub(crate) fn toggle_edit(
+        &mut self,
+        operation_ix: usize,
+        edit_ix: usize,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(PreviewOperation::Edit(file_edits)) = self.operations.get_mut(operation_ix)
+            && let Some(edit) = file_edits.edits.get_mut(edit_ix)
+        {
+            edit.included = !edit.included;
+            self.refresh(cx);
+        }
+    }
+
+    fn toggle_file(&mut self, operation_ix: usize, cx: &mut Context<Self>) {
+        match self.operations.get_mut(operation_ix) {
+            Some(PreviewOperation::Edit(file_edits)) => {
+                let included = !file_edits.edits.iter().all(|edit| edit.included);
+                for edit in &mut file_edits.edits {
+                    edit.included = included;
+                }
+            }
+            Some(PreviewOperation::RenameFile { included, .. }) => *included = !*included,
+            _ => return,
+        }
+        self.refresh(cx);
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.editor.update(cx, |_, cx| cx.notify());
+        cx.notify();
+    }
+
+    /// Builds a workspace edit out of the operations that are still selected. When the rename of
+    /// a file is left out, the edits that follow it are made to the file under its old path.
+    fn selected_edit(&self) -> lsp::WorkspaceEdit {
+        let mut skipped_renames = HashMap::default();
+        let mut operations = Vec::new();
+        for operation in &self.operations {
+            match operation {
+                PreviewOperation::Edit(file_edits) => {
+                    let edits = file_edits
+                        .edits
+                        .iter()
+                        .filter(|edit| edit.included)
+                        .map(|edit| edit.edit.clone())
+                        .collect::<Vec<_>>();
+                    if edits.is_empty() {
+                        continue;
+                    }
+                    let mut text_document = file_edits.text_document.clone();
+                    if let Some(old_uri) = skipped_renames.get(&text_document.uri) {
+                        text_document.uri = lsp::Uri::clone(old_uri);
+                    }
+                    operations.push(lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
+                        text_document,
+                        edits,
+                    }));
+                }
+                PreviewOperation::RenameFile {
+                    rename, included, ..
+                } => {
+                    if *included {
+                        operations.push(lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(
+                            rename.clone(),
+                        )));
+                    } else {
+                        skipped_renames.insert(rename.new_uri.clone(), rename.old_uri.clone());
+                    }
+                }
+                PreviewOperation::Other(operation) => operations.push(operation.clone()),
+            }
+        }
+        lsp::WorkspaceEdit {
+            document_changes: Some(lsp::DocumentChanges::Operations(operations)),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn apply(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.apply_task.is_some() {
+            return;
+        }
+        let edit = self.selected_edit();
+        let apply = self.project.update(cx, |project, cx| {
+            project.apply_workspace_edit(self.server_id, edit, true, cx)
+        });
+        self.apply_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = apply.await.context("applying rename");
+            this.update(cx, |this, cx| {
+                this.apply_task = None;
+                if result.log_err().is_some() {
+                    cx.emit(ItemEvent::CloseItem);
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let total = self
+            .file_edits()
+            .map(|(_, file_edits)| file_edits.edits.len())
+            .sum::<usize>();
+        let selected = self
+            .file_edits()
+            .flat_map(|(_, file_edits)| &file_edits.edits)
+            .filter(|edit| edit.included)
+            .count();
+
+        let files = self
+            .operations
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, operation)| {
+                let (state, label) = match operation {
+                    PreviewOperation::Edit(file_edits) => {
+                        let any = file_edits.edits.iter().any(|edit| edit.included);
+                        let all = file_edits.edits.iter().all(|edit| edit.included);
+                        let count = file_edits.edits.len();
+                        let label = format!(
+                            "{} ({count} {})",
+                            file_edits.label,
+                            if count == 1 { "edit" } else { "edits" }
+                        );
+                        (ToggleState::from_any_and_all(any, all), label.into())
+                    }
+                    PreviewOperation::RenameFile {
+                        label, included, ..
+                    } => (
+                        ToggleState::from(*included),
+                        SharedString::from(format!("Rename file: {label}")),
+                    ),
+                    PreviewOperation::Other(_) => return None,
+                };
+                Some(
+                    Checkbox::new(("rename-preview-file", ix), state)
+                        .label(label)
+                        .on_click(cx.listener(move |this, _, _, cx| this.toggle_file(ix, cx))),
+                )
+            });
+
+        v_flex()
+            .p_2()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(self.title.clone()))
+                            .child(
+                                Label::new(format!("{selected} of {total} edits selected"))
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("cancel-rename", "Cancel").on_click(
+                                    cx.listener(|_, _, _, cx| cx.emit(ItemEvent::CloseItem)),
+                                ),
+                            )
+                            .child(
+                                Button::new("apply-rename", "Apply")
+                                    .style(ButtonStyle::Filled)
+                                    .disabled(self.apply_task.is_some())
+                                    .on_click(
+                                        cx.listener(|this, _, window, cx| this.apply(window, cx)),
+                                    ),
+                            ),
+                    ),
+            )
+            .children(files)
+    }
+}
+
+fn render_edit_checkbox(
+    preview: &WeakEntity<RenamePreview>,
+    operation_ix: usize,
+    edit_ix: usize,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let Some(edit) = preview.upgrade().and_then(|preview| {
+        match preview.read(cx).operations.get(operation_ix)? {
+            PreviewOperation::Edit(file_edits) => {
+                let edit = file_edits.edits.get(edit_ix)?;
+                Some((
+                    edit.included,
+                    edit.row,
+                    edit_new_text(&edit.edit).to_owned(),
+                ))
+            }
+            _ => None,
+        }
+    }) else {
+        return Empty.into_any_element();
+    };
+    let (included, row, new_text) = edit;
+    let preview = preview.clone();
+    h_flex()
+        .id(cx.block_id)
+        .pl(cx.margins.gutter.full_width())
+        .child(
+            Checkbox::new(("rename-preview-edit", cx.block_id), included.into())
+                .label(format!("Line {}: replace with \"{new_text}\"", row + 1))
+                .on_click(move |_, _, cx| {
+                    preview
+                        .update(cx, |preview, cx| {
+                            preview.toggle_edit(operation_ix, edit_ix, cx)
+                        })
+                        .ok();
+                }),
+        )
+        .into_any_element()
+}
+
+fn workspace_edit_operations(edit: lsp::WorkspaceEdit) -> Vec<lsp::DocumentChangeOperation> {
+    if let Some(document_changes) = edit.document_changes {
+        match document_changes {
+            lsp::DocumentChanges::Edits(edits) => edits
+                .into_iter()
+                .map(lsp::DocumentChangeOperation::Edit)
+                .collect(),
+            lsp::DocumentChanges::Operations(operations) => operations,
+        }
+    } else {
+        edit.changes
+            .into_iter()
+            .flatten()
+            .map(|(uri, edits)| {
+                lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
+                    text_document: lsp::OptionalVersionedTextDocumentIdentifier {
+                        uri,
+                        version: None,
+                    },
+                    edits: edits.into_iter().map(lsp::Edit::Plain).collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn edit_range(edit: &lsp::Edit) -> lsp::Range {
+    match edit {
+        lsp::Edit::Plain(edit) => edit.range,
+        lsp::Edit::Annotated(edit) => edit.text_edit.range,
+        lsp::Edit::Snippet(edit) => edit.range,
+    }
+}
+
+fn edit_new_text(edit: &lsp::Edit) -> &str {
+    match edit {
+        lsp::Edit::Plain(edit) => &edit.new_text,
+        lsp::Edit::Annotated(edit) => &edit.text_edit.new_text,
+        lsp::Edit::Snippet(edit) => &edit.snippet.value,
+    }
+}
+
+fn uri_label(uri: &lsp::Uri) -> String {
+    match uri.to_file_path() {
+        Ok(path) => path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string_lossy().into_owned()),
+        Err(()) => uri.to_string(),
+    }
+}
+
+impl EventEmitter<ItemEvent> for RenamePreview {}
+
+impl Focusable for RenamePreview {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for RenamePreview {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .key_context("RenamePreview")
+            .child(self.render_header(cx))
+            .child(div().flex_1().min_h_0().child(self.editor.clone()))
+    }
+}
+
+impl Item for RenamePreview {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Pencil).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.title.clone()
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            Item::added_to_workspace(editor, workspace, window, cx)
+        });
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+}
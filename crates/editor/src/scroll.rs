@@ -222,26 +222,20 @@ impl ScrollManager {
         cx: &mut Context<Editor>,
     ) -> WasScrolled {
         let scroll_top = scroll_position.y.max(0.);
-        let scroll_top = match EditorSettings::get_global(cx).scroll_beyond_last_line {
-            ScrollBeyondLastLine::OnePage => scroll_top,
-            ScrollBeyondLastLine::Off => {
-                if let Some(height_in_lines) = self.visible_line_count {
-                    let max_row = map.max_point().row().as_f64();
-                    scroll_top.min(max_row - height_in_lines + 1.).max(0.)
-                } else {
-                    scroll_top
-                }
-            }
-            ScrollBeyondLastLine::VerticalScrollMargin => {
-                if let Some(height_in_lines) = self.visible_line_count {
-                    let max_row = map.max_point().row().as_f64();
-                    scroll_top
-                        .min(max_row - height_in_lines + 1. + self.vertical_scroll_margin)
-                        .max(0.)
-                } else {
-                    scroll_top
-                }
+        let overscroll_lines = match EditorSettings::get_global(cx).scroll_beyond_last_line {
+            ScrollBeyondLastLine::OnePage => None,
+            ScrollBeyondLastLine::Off => Some(0.),
+            ScrollBeyondLastLine::VerticalScrollMargin => Some(self.vertical_scroll_margin),
+            ScrollBeyondLastLine::Lines(lines) => Some(lines as f64),
+        };
+        let scroll_top = match (overscroll_lines, self.visible_line_count) {
+            (Some(overscroll_lines), Some(height_in_lines)) => {
+                let max_row = map.max_point().row().as_f64();
+                scroll_top
+                    .min(max_row - height_in_lines + 1. + overscroll_lines)
+                    .max(0.)
             }
+            _ => scroll_top,
         };
 
         let scroll_top_row = DisplayRow(scroll_top as u32);
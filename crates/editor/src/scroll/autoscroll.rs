@@ -1,11 +1,12 @@
 use crate::{
-    DisplayRow, Editor, EditorMode, LineWithInvisibles, RowExt, SelectionEffects,
+    DisplayRow, Editor, EditorMode, EditorSettings, LineWithInvisibles, RowExt, SelectionEffects,
     display_map::ToDisplayPoint,
     scroll::{ScrollOffset, WasScrolled},
 };
 use gpui::{Bounds, Context, Pixels, Window};
 use language::Point;
 use multi_buffer::Anchor;
+use settings::Settings as _;
 use std::cmp;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -210,6 +211,15 @@ impl Editor {
                 }
             }
         };
+        // In typewriter mode, the cursor stays centered rather than scrolling into view.
+        let strategy = match strategy {
+            AutoscrollStrategy::Fit | AutoscrollStrategy::Newest
+                if self.mode.is_full() && EditorSettings::get_global(cx).typewriter_scrolling =>
+            {
+                AutoscrollStrategy::Center
+            }
+            strategy => strategy,
+        };
         if let Autoscroll::Strategy(_, Some(anchor)) = autoscroll {
             target_top = anchor.to_display_point(&display_map).row().as_f64();
             target_bottom = target_top + 1.;
@@ -1,8 +1,12 @@
 mod download_file_capability;
+mod filesystem_capability;
+mod network_request_capability;
 mod npm_install_package_capability;
 mod process_exec_capability;
 
 pub use download_file_capability::*;
+pub use filesystem_capability::*;
+pub use network_request_capability::*;
 pub use npm_install_package_capability::*;
 pub use process_exec_capability::*;
 
@@ -17,4 +21,38 @@ pub enum ExtensionCapability {
     DownloadFile(DownloadFileCapability),
     #[serde(rename = "npm:install")]
     NpmInstallPackage(NpmInstallPackageCapability),
+    #[serde(rename = "network:request")]
+    NetworkRequest(NetworkRequestCapability),
+    #[serde(rename = "fs:read")]
+    FsRead(FilesystemCapability),
+    #[serde(rename = "fs:write")]
+    FsWrite(FilesystemCapability),
+}
+
+impl ExtensionCapability {
+    /// Returns a description of what the capability allows, to show to the user.
+    pub fn description(&self) -> String {
+        match self {
+            Self::ProcessExec(capability) => {
+                if capability.args.is_empty() {
+                    format!("Run `{}`", capability.command)
+                } else {
+                    format!("Run `{} {}`", capability.command, capability.args.join(" "))
+                }
+            }
+            Self::DownloadFile(capability) => format!(
+                "Download files from {}/{}",
+                capability.host,
+                capability.path.join("/")
+            ),
+            Self::NpmInstallPackage(capability) => {
+                format!("Install the npm package `{}`", capability.package)
+            }
+            Self::NetworkRequest(capability) => {
+                format!("Make network requests to {}", capability.host)
+            }
+            Self::FsRead(capability) => format!("Read files in {}", capability.path),
+            Self::FsWrite(capability) => format!("Read and write files in {}", capability.path),
+        }
+    }
 }
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FilesystemCapability {
+    /// The directory to allow access to, which may start with `~` for the home directory.
+    ///
+    /// Use `*` to allow access to any directory.
+    pub path: String,
+}
+
+impl FilesystemCapability {
+    /// Returns the directory this capability refers to, with `~` expanded, or `None` if it
+    /// allows access to any directory.
+    pub fn directory(&self) -> Option<PathBuf> {
+        if self.path == "*" {
+            return None;
+        }
+
+        Some(match self.path.strip_prefix("~") {
+            Some(relative_path) => {
+                util::paths::home_dir().join(relative_path.trim_start_matches(['/', '\\']))
+            }
+            None => PathBuf::from(&self.path),
+        })
+    }
+
+    /// Returns whether the capability allows access to the given path.
+    pub fn allows(&self, desired_path: &Path) -> bool {
+        match self.directory() {
+            Some(directory) => desired_path.starts_with(directory),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        let capability = FilesystemCapability {
+            path: "*".to_string(),
+        };
+        assert_eq!(capability.allows(Path::new("/etc/hosts")), true);
+
+        let capability = FilesystemCapability {
+            path: "/usr/share/dict".to_string(),
+        };
+        assert_eq!(capability.allows(Path::new("/usr/share/dict/words")), true);
+        assert_eq!(capability.allows(Path::new("/usr/share")), false);
+        assert_eq!(capability.allows(Path::new("/usr/share/dictionary")), false);
+
+        let capability = FilesystemCapability {
+            path: "~/.config/my-tool".to_string(),
+        };
+        assert_eq!(
+            capability.allows(&util::paths::home_dir().join(".config/my-tool/config.toml")),
+            true
+        );
+        assert_eq!(
+            capability.allows(&util::paths::home_dir().join(".config/other-tool")),
+            false
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NetworkRequestCapability {
+    /// The host to allow requests to.
+    ///
+    /// Use `*` to allow requests to any host, or `*.example.com` to allow requests to the
+    /// subdomains of `example.com`.
+    pub host: String,
+}
+
+impl NetworkRequestCapability {
+    /// Returns whether the capability allows making a request to the given URL.
+    pub fn allows(&self, url: &Url) -> bool {
+        let Some(desired_host) = url.host_str() else {
+            return false;
+        };
+
+        if self.host == "*" || self.host == desired_host {
+            return true;
+        }
+
+        self.host
+            .strip_prefix("*.")
+            .is_some_and(|domain| desired_host.ends_with(&format!(".{domain}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        let capability = NetworkRequestCapability {
+            host: "*".to_string(),
+        };
+        assert_eq!(
+            capability.allows(&"https://example.com/some/path".parse().unwrap()),
+            true
+        );
+
+        let capability = NetworkRequestCapability {
+            host: "api.github.com".to_string(),
+        };
+        assert_eq!(
+            capability.allows(&"https://api.github.com/repos".parse().unwrap()),
+            true
+        );
+        assert_eq!(
+            capability.allows(&"https://github.com/repos".parse().unwrap()),
+            false
+        );
+
+        let capability = NetworkRequestCapability {
+            host: "*.example.com".to_string(),
+        };
+        assert_eq!(
+            capability.allows(&"https://api.example.com".parse().unwrap()),
+            true
+        );
+        assert_eq!(
+            capability.allows(&"https://example.com".parse().unwrap()),
+            false
+        );
+        assert_eq!(
+            capability.allows(&"https://notexample.com".parse().unwrap()),
+            false
+        );
+    }
+}
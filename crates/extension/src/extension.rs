@@ -12,6 +12,7 @@ use ::lsp::LanguageServerName;
 use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use fs::normalize_path;
+use futures::channel::mpsc;
 use gpui::{App, Task};
 use language::LanguageName;
 use semantic_version::SemanticVersion;
@@ -117,6 +118,7 @@ pub trait Extension: Send + Sync + 'static {
         command: SlashCommand,
         arguments: Vec<String>,
         worktree: Option<Arc<dyn WorktreeDelegate>>,
+        output_events: mpsc::UnboundedSender<SlashCommandOutputEvent>,
     ) -> Result<SlashCommandOutput>;
 
     async fn context_server_command(
@@ -168,6 +170,59 @@ pub trait Extension: Send + Sync + 'static {
         locator_name: String,
         config: SpawnInTerminal,
     ) -> Result<DebugRequest>;
+
+    async fn render_panel(
+        &self,
+        panel_id: Arc<str>,
+        project: Arc<dyn ProjectDelegate>,
+    ) -> Result<PanelContent>;
+
+    async fn handle_panel_event(
+        &self,
+        panel_id: Arc<str>,
+        event: PanelEvent,
+        project: Arc<dyn ProjectDelegate>,
+    ) -> Result<()>;
+
+    async fn handle_status_item_event(
+        &self,
+        item_id: Arc<str>,
+        event: StatusItemEvent,
+    ) -> Result<()>;
+
+    async fn render_file(
+        &self,
+        viewer_id: Arc<str>,
+        path: String,
+        content: Vec<u8>,
+    ) -> Result<FileView>;
+
+    async fn formatter_command(
+        &self,
+        formatter_id: Arc<str>,
+        request: FormatRequest,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Command>;
+
+    async fn linter_command(
+        &self,
+        linter_id: Arc<str>,
+        request: LintRequest,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Command>;
+
+    async fn parse_linter_output(
+        &self,
+        linter_id: Arc<str>,
+        request: LintRequest,
+        output: LinterOutput,
+    ) -> Result<Vec<Lint>>;
+
+    async fn predict_edit(
+        &self,
+        provider_id: Arc<str>,
+        request: EditPredictionRequest,
+    ) -> Result<Vec<String>>;
 }
 
 pub fn parse_wasm_extension_version(
@@ -8,7 +8,10 @@ use language::{BinaryStatus, LanguageMatcher, LanguageName, LoadedLanguage};
 use lsp::LanguageServerName;
 use parking_lot::RwLock;
 
-use crate::{Extension, SlashCommand};
+use crate::{
+    EditPredictionProviderManifestEntry, Extension, FormatterManifestEntry, LinterManifestEntry,
+    SlashCommand, StatusItemContent, StatusItemPosition,
+};
 
 #[derive(Default)]
 struct GlobalExtensionHostProxy(Arc<ExtensionHostProxy>);
@@ -29,6 +32,12 @@ pub struct ExtensionHostProxy {
     slash_command_proxy: RwLock<Option<Arc<dyn ExtensionSlashCommandProxy>>>,
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     debug_adapter_provider_proxy: RwLock<Option<Arc<dyn ExtensionDebugAdapterProviderProxy>>>,
+    panel_proxy: RwLock<Option<Arc<dyn ExtensionPanelProxy>>>,
+    status_item_proxy: RwLock<Option<Arc<dyn ExtensionStatusItemProxy>>>,
+    file_viewer_proxy: RwLock<Option<Arc<dyn ExtensionFileViewerProxy>>>,
+    formatter_proxy: RwLock<Option<Arc<dyn ExtensionFormatterProxy>>>,
+    linter_proxy: RwLock<Option<Arc<dyn ExtensionLinterProxy>>>,
+    edit_prediction_provider_proxy: RwLock<Option<Arc<dyn ExtensionEditPredictionProviderProxy>>>,
 }
 
 impl ExtensionHostProxy {
@@ -54,6 +63,12 @@ impl ExtensionHostProxy {
             slash_command_proxy: RwLock::default(),
             context_server_proxy: RwLock::default(),
             debug_adapter_provider_proxy: RwLock::default(),
+            panel_proxy: RwLock::default(),
+            status_item_proxy: RwLock::default(),
+            file_viewer_proxy: RwLock::default(),
+            formatter_proxy: RwLock::default(),
+            linter_proxy: RwLock::default(),
+            edit_prediction_provider_proxy: RwLock::default(),
         }
     }
 
@@ -90,6 +105,35 @@ impl ExtensionHostProxy {
             .write()
             .replace(Arc::new(proxy));
     }
+
+    pub fn register_panel_proxy(&self, proxy: impl ExtensionPanelProxy) {
+        self.panel_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_status_item_proxy(&self, proxy: impl ExtensionStatusItemProxy) {
+        self.status_item_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_file_viewer_proxy(&self, proxy: impl ExtensionFileViewerProxy) {
+        self.file_viewer_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_formatter_proxy(&self, proxy: impl ExtensionFormatterProxy) {
+        self.formatter_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_linter_proxy(&self, proxy: impl ExtensionLinterProxy) {
+        self.linter_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_edit_prediction_provider_proxy(
+        &self,
+        proxy: impl ExtensionEditPredictionProviderProxy,
+    ) {
+        self.edit_prediction_provider_proxy
+            .write()
+            .replace(Arc::new(proxy));
+    }
 }
 
 pub trait ExtensionThemeProxy: Send + Sync + 'static {
@@ -446,3 +490,249 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
         proxy.unregister_debug_locator(locator_name)
     }
 }
+
+pub trait ExtensionPanelProxy: Send + Sync + 'static {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        title: SharedString,
+        cx: &mut App,
+    );
+
+    fn unregister_panel(&self, panel_id: Arc<str>, cx: &mut App);
+
+    fn refresh_panel(&self, panel_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionPanelProxy for ExtensionHostProxy {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        title: SharedString,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_panel(extension, panel_id, title, cx)
+    }
+
+    fn unregister_panel(&self, panel_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_panel(panel_id, cx)
+    }
+
+    fn refresh_panel(&self, panel_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.refresh_panel(panel_id, cx)
+    }
+}
+
+pub trait ExtensionStatusItemProxy: Send + Sync + 'static {
+    fn register_status_item(
+        &self,
+        extension: Arc<dyn Extension>,
+        item_id: Arc<str>,
+        position: StatusItemPosition,
+        cx: &mut App,
+    );
+
+    fn unregister_status_item(&self, item_id: Arc<str>, cx: &mut App);
+
+    fn update_status_item(
+        &self,
+        item_id: Arc<str>,
+        content: Option<StatusItemContent>,
+        cx: &mut App,
+    );
+}
+
+impl ExtensionStatusItemProxy for ExtensionHostProxy {
+    fn register_status_item(
+        &self,
+        extension: Arc<dyn Extension>,
+        item_id: Arc<str>,
+        position: StatusItemPosition,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.status_item_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_status_item(extension, item_id, position, cx)
+    }
+
+    fn unregister_status_item(&self, item_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.status_item_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_status_item(item_id, cx)
+    }
+
+    fn update_status_item(
+        &self,
+        item_id: Arc<str>,
+        content: Option<StatusItemContent>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.status_item_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.update_status_item(item_id, content, cx)
+    }
+}
+
+pub trait ExtensionFileViewerProxy: Send + Sync + 'static {
+    fn register_file_viewer(
+        &self,
+        extension: Arc<dyn Extension>,
+        viewer_id: Arc<str>,
+        path_suffixes: Vec<String>,
+        cx: &mut App,
+    );
+
+    fn unregister_file_viewer(&self, viewer_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionFileViewerProxy for ExtensionHostProxy {
+    fn register_file_viewer(
+        &self,
+        extension: Arc<dyn Extension>,
+        viewer_id: Arc<str>,
+        path_suffixes: Vec<String>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.file_viewer_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_file_viewer(extension, viewer_id, path_suffixes, cx)
+    }
+
+    fn unregister_file_viewer(&self, viewer_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.file_viewer_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_file_viewer(viewer_id, cx)
+    }
+}
+
+pub trait ExtensionFormatterProxy: Send + Sync + 'static {
+    fn register_formatter(
+        &self,
+        extension: Arc<dyn Extension>,
+        formatter_id: Arc<str>,
+        formatter: FormatterManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_formatter(&self, formatter_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionFormatterProxy for ExtensionHostProxy {
+    fn register_formatter(
+        &self,
+        extension: Arc<dyn Extension>,
+        formatter_id: Arc<str>,
+        formatter: FormatterManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.formatter_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_formatter(extension, formatter_id, formatter, cx)
+    }
+
+    fn unregister_formatter(&self, formatter_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.formatter_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_formatter(formatter_id, cx)
+    }
+}
+
+pub trait ExtensionLinterProxy: Send + Sync + 'static {
+    fn register_linter(
+        &self,
+        extension: Arc<dyn Extension>,
+        linter_id: Arc<str>,
+        linter: LinterManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_linter(&self, linter_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionLinterProxy for ExtensionHostProxy {
+    fn register_linter(
+        &self,
+        extension: Arc<dyn Extension>,
+        linter_id: Arc<str>,
+        linter: LinterManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.linter_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_linter(extension, linter_id, linter, cx)
+    }
+
+    fn unregister_linter(&self, linter_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.linter_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_linter(linter_id, cx)
+    }
+}
+
+pub trait ExtensionEditPredictionProviderProxy: Send + Sync + 'static {
+    fn register_edit_prediction_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: EditPredictionProviderManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_edit_prediction_provider(&self, provider_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionEditPredictionProviderProxy for ExtensionHostProxy {
+    fn register_edit_prediction_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: EditPredictionProviderManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.edit_prediction_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_edit_prediction_provider(extension, provider_id, provider, cx)
+    }
+
+    fn unregister_edit_prediction_provider(&self, provider_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.edit_prediction_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_edit_prediction_provider(provider_id, cx)
+    }
+}
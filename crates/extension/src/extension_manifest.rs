@@ -64,6 +64,9 @@ pub struct ExtensionManifest {
     pub description: Option<String>,
     #[serde(default)]
     pub repository: Option<String>,
+    /// The URL of the extension's changelog.
+    #[serde(default)]
+    pub changelog: Option<String>,
     #[serde(default)]
     pub authors: Vec<String>,
     #[serde(default)]
@@ -91,6 +94,18 @@ pub struct ExtensionManifest {
     pub debug_adapters: BTreeMap<Arc<str>, DebugAdapterManifestEntry>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub debug_locators: BTreeMap<Arc<str>, DebugLocatorManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub panels: BTreeMap<Arc<str>, PanelManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub status_items: BTreeMap<Arc<str>, StatusItemManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_viewers: BTreeMap<Arc<str>, FileViewerManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formatters: BTreeMap<Arc<str>, FormatterManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub linters: BTreeMap<Arc<str>, LinterManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub edit_prediction_providers: BTreeMap<Arc<str>, EditPredictionProviderManifestEntry>,
 }
 
 impl ExtensionManifest {
@@ -201,6 +216,56 @@ pub struct DebugAdapterManifestEntry {
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct DebugLocatorManifestEntry {}
 
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct PanelManifestEntry {
+    /// The title of the panel, shown on its tab.
+    pub title: String,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct StatusItemManifestEntry {
+    /// The side of the status bar the item is shown on.
+    #[serde(default)]
+    pub position: StatusItemPosition,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct FileViewerManifestEntry {
+    /// The extensions of the files the viewer opens, without the leading dot.
+    pub path_suffixes: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct FormatterManifestEntry {
+    /// The languages of the files the formatter formats.
+    pub languages: Vec<LanguageName>,
+    /// Whether the formatter can format a range of lines instead of the whole file.
+    #[serde(default)]
+    pub supports_ranges: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct LinterManifestEntry {
+    /// The languages of the files the linter lints.
+    pub languages: Vec<LanguageName>,
+    /// How long to wait after the last edit of a file before linting it, in milliseconds.
+    ///
+    /// Files are linted without waiting when they are saved.
+    #[serde(default)]
+    pub debounce_ms: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct EditPredictionProviderManifestEntry {}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusItemPosition {
+    Left,
+    #[default]
+    Right,
+}
+
 impl ExtensionManifest {
     pub async fn load(fs: Arc<dyn Fs>, extension_dir: &Path) -> Result<Self> {
         let extension_name = extension_dir
@@ -242,6 +307,7 @@ fn manifest_from_old_manifest(
         version: manifest_json.version,
         description: manifest_json.description,
         repository: manifest_json.repository,
+        changelog: None,
         authors: manifest_json.authors,
         schema_version: SchemaVersion::ZERO,
         lib: Default::default(),
@@ -270,6 +336,12 @@ fn manifest_from_old_manifest(
         capabilities: Vec::new(),
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        panels: Default::default(),
+        status_items: Default::default(),
+        file_viewers: Default::default(),
+        formatters: Default::default(),
+        linters: Default::default(),
+        edit_prediction_providers: Default::default(),
     }
 }
 
@@ -289,6 +361,7 @@ mod tests {
             schema_version: SchemaVersion::ZERO,
             description: None,
             repository: None,
+            changelog: None,
             authors: vec![],
             lib: Default::default(),
             themes: vec![],
@@ -302,6 +375,12 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            panels: Default::default(),
+            status_items: Default::default(),
+            file_viewers: Default::default(),
+            formatters: Default::default(),
+            linters: Default::default(),
+            edit_prediction_providers: Default::default(),
         }
     }
 
@@ -403,4 +482,149 @@ mod tests {
         );
         assert!(manifest.allow_exec("docker", &["ps"]).is_err()); // wrong first arg
     }
+
+    #[test]
+    fn test_parse_panels() {
+        let manifest: ExtensionManifest = toml::from_str(
+            r#"
+            id = "todo"
+            name = "To-Do"
+            version = "0.1.0"
+            schema_version = 1
+
+            [panels.todo-list]
+            title = "To-Dos"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.panels,
+            BTreeMap::from_iter([(
+                Arc::from("todo-list"),
+                PanelManifestEntry {
+                    title: "To-Dos".to_string(),
+                },
+            )])
+        );
+    }
+
+    #[test]
+    fn test_parse_status_items() {
+        let manifest: ExtensionManifest = toml::from_str(
+            r#"
+            id = "pomodoro"
+            name = "Pomodoro"
+            version = "0.1.0"
+            schema_version = 1
+
+            [status_items.timer]
+
+            [status_items.streak]
+            position = "left"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.status_items,
+            BTreeMap::from_iter([
+                (
+                    Arc::from("streak"),
+                    StatusItemManifestEntry {
+                        position: StatusItemPosition::Left,
+                    },
+                ),
+                (
+                    Arc::from("timer"),
+                    StatusItemManifestEntry {
+                        position: StatusItemPosition::Right,
+                    },
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_file_viewers() {
+        let manifest: ExtensionManifest = toml::from_str(
+            r#"
+            id = "psd-viewer"
+            name = "PSD Viewer"
+            version = "0.1.0"
+            schema_version = 1
+
+            [file_viewers.psd]
+            path_suffixes = ["psd", "psb"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.file_viewers,
+            BTreeMap::from_iter([(
+                Arc::from("psd"),
+                FileViewerManifestEntry {
+                    path_suffixes: vec!["psd".to_string(), "psb".to_string()],
+                },
+            )])
+        );
+
+        assert!(
+            toml::from_str::<ExtensionManifest>(
+                r#"
+                id = "psd-viewer"
+                name = "PSD Viewer"
+                version = "0.1.0"
+                schema_version = 1
+
+                [file_viewers.psd]
+                "#,
+            )
+            .is_err(),
+            "a file viewer without path suffixes should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_parse_formatters_and_linters() {
+        let manifest: ExtensionManifest = toml::from_str(
+            r#"
+            id = "shellcheck"
+            name = "ShellCheck"
+            version = "0.1.0"
+            schema_version = 1
+
+            [formatters.shfmt]
+            languages = ["Shell Script"]
+            supports_ranges = true
+
+            [linters.shellcheck]
+            languages = ["Shell Script"]
+            debounce_ms = 250
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.formatters,
+            BTreeMap::from_iter([(
+                Arc::from("shfmt"),
+                FormatterManifestEntry {
+                    languages: vec![LanguageName::new("Shell Script")],
+                    supports_ranges: true,
+                },
+            )])
+        );
+        assert_eq!(
+            manifest.linters,
+            BTreeMap::from_iter([(
+                Arc::from("shellcheck"),
+                LinterManifestEntry {
+                    languages: vec![LanguageName::new("Shell Script")],
+                    debounce_ms: Some(250),
+                },
+            )])
+        );
+    }
 }
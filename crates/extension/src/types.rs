@@ -1,7 +1,13 @@
 mod context_server;
 mod dap;
+mod edit_prediction;
+mod file_viewer;
+mod formatter;
+mod linter;
 mod lsp;
+mod panel;
 mod slash_command;
+mod status_item;
 
 use std::{ops::Range, path::PathBuf};
 
@@ -9,8 +15,14 @@ use util::redact::should_redact;
 
 pub use context_server::*;
 pub use dap::*;
+pub use edit_prediction::*;
+pub use file_viewer::*;
+pub use formatter::*;
+pub use linter::*;
 pub use lsp::*;
+pub use panel::*;
 pub use slash_command::*;
+pub use status_item::*;
 
 /// A list of environment variables.
 pub type EnvVars = Vec<(String, String)>;
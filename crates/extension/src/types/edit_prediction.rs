@@ -0,0 +1,14 @@
+/// A request for edit predictions from an edit prediction provider registered by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditPredictionRequest {
+    /// The path of the file, relative to the root of its worktree, if the buffer has one.
+    pub path: Option<String>,
+    /// The name of the language of the file, if it has one.
+    pub language_name: Option<String>,
+    /// The text before the cursor.
+    pub prefix: String,
+    /// The text after the cursor.
+    pub suffix: String,
+    /// How many alternative predictions to return.
+    pub candidates: u32,
+}
@@ -0,0 +1,10 @@
+use gpui::ImageFormat;
+
+/// What a file viewer provided by an extension shows for a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileView {
+    /// An encoded image, scaled down to fit the view.
+    Image { format: ImageFormat, bytes: Vec<u8> },
+    /// Text, shown as-is in the buffer font.
+    Text(String),
+}
@@ -0,0 +1,12 @@
+use std::ops::Range;
+
+/// A request to format a file with a formatter provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatRequest {
+    /// The path of the file, relative to the root of its worktree.
+    pub path: String,
+    /// The name of the language of the file.
+    pub language_name: String,
+    /// The zero-based, end-exclusive range of lines to format, if only part of the file should be formatted.
+    pub line_range: Option<Range<u32>>,
+}
@@ -0,0 +1,44 @@
+/// A request to lint a file with a linter provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintRequest {
+    /// The path of the file, relative to the root of its worktree.
+    pub path: String,
+    /// The name of the language of the file.
+    pub language_name: String,
+}
+
+/// The output of a linter's command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinterOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// The exit code of the command, if it exited normally.
+    pub exit_code: Option<i32>,
+}
+
+/// The severity of a lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// A zero-based position in a file, with the column in UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LintPosition {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A problem reported by a linter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub start: LintPosition,
+    pub end: LintPosition,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// The code of the rule that reported the problem.
+    pub code: Option<String>,
+}
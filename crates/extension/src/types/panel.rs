@@ -0,0 +1,67 @@
+/// The color of a label in an extension panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelLabelColor {
+    /// The default text color.
+    Default,
+    /// A dimmed color, for secondary information.
+    Muted,
+    /// The accent color.
+    Accent,
+    /// The color for successful states.
+    Success,
+    /// The color for warnings.
+    Warning,
+    /// The color for errors.
+    Error,
+}
+
+/// A node of the content of an extension panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PanelNode {
+    /// A line of text.
+    Label {
+        text: String,
+        color: PanelLabelColor,
+    },
+    /// A button, which sends [`PanelEvent::ButtonClicked`] when clicked.
+    Button { id: String, label: String },
+    /// A single-line text input, which sends [`PanelEvent::TextInputSubmitted`] when enter is pressed in it.
+    TextInput { id: String, placeholder: String },
+    /// An item of a tree, with the indices of its children.
+    TreeItem {
+        id: String,
+        label: String,
+        expanded: bool,
+        children: Vec<u32>,
+    },
+    /// The nodes at the given indices, laid out vertically.
+    Column(Vec<u32>),
+    /// The nodes at the given indices, laid out horizontally.
+    Row(Vec<u32>),
+    /// A horizontal divider.
+    Divider,
+}
+
+/// The content of an extension panel.
+///
+/// Nodes refer to their children by their index in `nodes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PanelContent {
+    /// The nodes of the content.
+    pub nodes: Vec<PanelNode>,
+    /// The index of the node to show at the top level of the panel.
+    pub root: u32,
+}
+
+/// An event that happened in an extension panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PanelEvent {
+    /// The button with the given ID was clicked.
+    ButtonClicked(String),
+    /// Enter was pressed in the text input with the given ID.
+    TextInputSubmitted { id: String, text: String },
+    /// The tree item with the given ID was clicked.
+    TreeItemClicked(String),
+    /// The disclosure arrow of the tree item with the given ID was clicked.
+    TreeItemToggled(String),
+}
@@ -31,6 +31,17 @@ pub struct SlashCommandOutputSection {
     pub label: String,
 }
 
+/// An event of the output of a slash command, streamed while the command runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashCommandOutputEvent {
+    /// Starts a section with the given label, which contains the text streamed until the matching [`SlashCommandOutputEvent::EndSection`].
+    StartSection(String),
+    /// Text to append to the output.
+    Text(String),
+    /// Ends the innermost section.
+    EndSection,
+}
+
 /// A completion for a slash command argument.
 #[derive(Debug, Clone)]
 pub struct SlashCommandArgumentCompletion {
@@ -0,0 +1,32 @@
+/// An entry of the menu of a status bar item provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusItemMenuEntry {
+    /// The ID of the entry, unique within the menu.
+    pub id: String,
+    /// The text of the entry.
+    pub label: String,
+}
+
+/// The content of a status bar item provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusItemContent {
+    /// The text of the item.
+    pub text: String,
+    /// The name of the icon to show before the text, in snake case.
+    pub icon: Option<String>,
+    /// The text to show when hovering over the item.
+    pub tooltip: Option<String>,
+    /// The entries of the menu to open when the item is clicked.
+    ///
+    /// If empty, clicking the item sends [`StatusItemEvent::Clicked`] instead.
+    pub menu: Vec<StatusItemMenuEntry>,
+}
+
+/// An event that happened on a status bar item provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusItemEvent {
+    /// The item was clicked.
+    Clicked,
+    /// The menu entry with the given ID was selected.
+    MenuEntrySelected(String),
+}
@@ -0,0 +1,3 @@
+//! Provides constructs for edit prediction providers.
+
+pub use crate::wit::zed::extension::edit_prediction::EditPredictionRequest;
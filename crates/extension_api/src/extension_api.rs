@@ -1,8 +1,14 @@
 //! The Zed Rust Extension API allows you write extensions for [Zed](https://zed.dev/) in Rust.
 
+pub mod edit_prediction;
+pub mod file_viewer;
+pub mod formatter;
 pub mod http_client;
+pub mod linter;
+pub mod panel;
 pub mod process;
 pub mod settings;
+pub mod status_bar;
 
 use core::fmt;
 
@@ -35,7 +41,8 @@ pub use wit::{
     },
     zed::extension::platform::{Architecture, Os, current_platform},
     zed::extension::slash_command::{
-        SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, SlashCommandOutputSection,
+        SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, SlashCommandOutputEvent,
+        SlashCommandOutputSection, stream_slash_command_output,
     },
 };
 
@@ -148,6 +155,9 @@ pub trait Extension: Send + Sync {
     }
 
     /// Returns the output from running the provided slash command.
+    ///
+    /// Output can also be streamed while the command runs with [`stream_slash_command_output`],
+    /// in which case the returned output is shown after the streamed output.
     fn run_slash_command(
         &self,
         _command: SlashCommand,
@@ -259,6 +269,96 @@ pub trait Extension: Send + Sync {
     ) -> Result<DebugRequest, String> {
         Err("`run_dap_locator` not implemented".to_string())
     }
+
+    /// Returns the content of the panel with the given ID.
+    ///
+    /// Call [`panel::refresh_panel`] when the data the panel shows changes to have it rendered again.
+    fn render_panel(
+        &mut self,
+        _panel_id: &str,
+        _project: &Project,
+    ) -> Result<panel::PanelContent, String> {
+        Err("`render_panel` not implemented".to_string())
+    }
+
+    /// Handles an event that happened in the panel with the given ID.
+    ///
+    /// The panel is rendered again once the event is handled.
+    fn handle_panel_event(
+        &mut self,
+        _panel_id: &str,
+        _event: panel::PanelEvent,
+        _project: &Project,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Handles an event that happened on the status bar item with the given ID.
+    ///
+    /// Call [`status_bar::update_status_item`] to change what the item shows.
+    fn handle_status_item_event(
+        &mut self,
+        _item_id: &str,
+        _event: status_bar::StatusItemEvent,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Returns what the file viewer with the given ID shows for the file at the given path, with the given content.
+    fn render_file(
+        &mut self,
+        _viewer_id: &str,
+        _path: &str,
+        _content: Vec<u8>,
+    ) -> Result<file_viewer::FileView, String> {
+        Err("`render_file` not implemented".to_string())
+    }
+
+    /// Returns the command that formats a file with the formatter with the given ID.
+    ///
+    /// Zed writes the content of the file to the command's standard input and replaces it
+    /// with what the command writes to its standard output.
+    fn formatter_command(
+        &mut self,
+        _formatter_id: &str,
+        _request: formatter::FormatRequest,
+        _worktree: &Worktree,
+    ) -> Result<Command> {
+        Err("`formatter_command` not implemented".to_string())
+    }
+
+    /// Returns the command that lints a file with the linter with the given ID.
+    ///
+    /// Zed writes the content of the file to the command's standard input and passes what the
+    /// command outputs to [`Extension::parse_linter_output`].
+    fn linter_command(
+        &mut self,
+        _linter_id: &str,
+        _request: linter::LintRequest,
+        _worktree: &Worktree,
+    ) -> Result<Command> {
+        Err("`linter_command` not implemented".to_string())
+    }
+
+    /// Returns the problems that the linter with the given ID reported in the given output of its command.
+    fn parse_linter_output(
+        &mut self,
+        _linter_id: &str,
+        _request: linter::LintRequest,
+        _output: linter::LinterOutput,
+    ) -> Result<Vec<linter::Lint>> {
+        Err("`parse_linter_output` not implemented".to_string())
+    }
+
+    /// Returns the texts that the edit prediction provider with the given ID predicts to be
+    /// inserted at the cursor, starting with the most likely one.
+    fn predict_edit(
+        &mut self,
+        _provider_id: &str,
+        _request: edit_prediction::EditPredictionRequest,
+    ) -> Result<Vec<String>> {
+        Err("`predict_edit` not implemented".to_string())
+    }
 }
 
 /// Registers the provided type as a Zed extension.
@@ -334,7 +434,7 @@ mod wit {
 
     wit_bindgen::generate!({
         skip: ["init-extension"],
-        path: "./wit/since_v0.6.0",
+        path: "./wit/since_v0.8.0",
     });
 }
 
@@ -518,6 +618,64 @@ impl wit::Guest for Component {
     ) -> Result<DebugRequest, String> {
         extension().run_dap_locator(locator_name, build_task)
     }
+
+    fn render_panel(panel_id: String, project: &Project) -> Result<panel::PanelContent, String> {
+        extension().render_panel(&panel_id, project)
+    }
+
+    fn handle_panel_event(
+        panel_id: String,
+        event: panel::PanelEvent,
+        project: &Project,
+    ) -> Result<(), String> {
+        extension().handle_panel_event(&panel_id, event, project)
+    }
+
+    fn handle_status_item_event(
+        item_id: String,
+        event: status_bar::StatusItemEvent,
+    ) -> Result<(), String> {
+        extension().handle_status_item_event(&item_id, event)
+    }
+
+    fn render_file(
+        viewer_id: String,
+        path: String,
+        content: Vec<u8>,
+    ) -> Result<file_viewer::FileView, String> {
+        extension().render_file(&viewer_id, &path, content)
+    }
+
+    fn formatter_command(
+        formatter_id: String,
+        request: formatter::FormatRequest,
+        worktree: &Worktree,
+    ) -> Result<wit::Command> {
+        extension().formatter_command(&formatter_id, request, worktree)
+    }
+
+    fn linter_command(
+        linter_id: String,
+        request: linter::LintRequest,
+        worktree: &Worktree,
+    ) -> Result<wit::Command> {
+        extension().linter_command(&linter_id, request, worktree)
+    }
+
+    fn parse_linter_output(
+        linter_id: String,
+        request: linter::LintRequest,
+        output: linter::LinterOutput,
+    ) -> Result<Vec<linter::Lint>> {
+        extension().parse_linter_output(&linter_id, request, output)
+    }
+
+    fn predict_edit(
+        provider_id: String,
+        request: edit_prediction::EditPredictionRequest,
+    ) -> Result<Vec<String>> {
+        extension().predict_edit(&provider_id, request)
+    }
 }
 
 /// The ID of a language server.
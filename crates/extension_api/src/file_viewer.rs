@@ -0,0 +1,3 @@
+//! Provides constructs for file viewers that show files Zed can't display as text.
+
+pub use crate::wit::zed::extension::file_viewer::{FileView, Image, ImageFormat};
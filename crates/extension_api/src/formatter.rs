@@ -0,0 +1,3 @@
+//! Provides constructs for formatters that run external tools.
+
+pub use crate::wit::zed::extension::formatter::FormatRequest;
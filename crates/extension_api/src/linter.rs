@@ -0,0 +1,5 @@
+//! Provides constructs for linters that run external tools.
+
+pub use crate::wit::zed::extension::linter::{
+    Lint, LintPosition, LintRequest, LintSeverity, LinterOutput,
+};
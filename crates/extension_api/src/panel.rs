@@ -0,0 +1,56 @@
+//! Provides constructs for panels that extensions show in Zed's docks.
+
+pub use crate::wit::zed::extension::panel::{
+    Button, Label, LabelColor, Node, PanelContent, PanelEvent, TextInput, TextInputSubmission,
+    TreeItem, refresh_panel,
+};
+
+/// Builds a [`PanelContent`], whose nodes refer to their children by index.
+///
+/// Add the children of a node before the node itself, so their indices can be listed in it.
+#[derive(Debug, Default)]
+pub struct PanelContentBuilder {
+    nodes: Vec<Node>,
+}
+
+impl PanelContentBuilder {
+    /// Returns a new, empty [`PanelContentBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node, returning its index.
+    pub fn push(&mut self, node: Node) -> u32 {
+        self.nodes.push(node);
+        self.nodes.len() as u32 - 1
+    }
+
+    /// Adds a [`Node::Label`] with the given text and color, returning its index.
+    pub fn label(&mut self, text: impl Into<String>, color: LabelColor) -> u32 {
+        self.push(Node::Label(Label {
+            text: text.into(),
+            color,
+        }))
+    }
+
+    /// Adds a [`Node::Button`], returning its index.
+    pub fn button(&mut self, id: impl Into<String>, label: impl Into<String>) -> u32 {
+        self.push(Node::Button(Button {
+            id: id.into(),
+            label: label.into(),
+        }))
+    }
+
+    /// Adds a [`Node::Column`] laying out the nodes at the given indices, returning its index.
+    pub fn column(&mut self, children: impl IntoIterator<Item = u32>) -> u32 {
+        self.push(Node::Column(children.into_iter().collect()))
+    }
+
+    /// Finishes the content, with the node at the given index shown at the top level of the panel.
+    pub fn build(self, root: u32) -> PanelContent {
+        PanelContent {
+            nodes: self.nodes,
+            root,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Provides constructs for status bar items that extensions show in Zed's status bar.
+
+pub use crate::wit::zed::extension::status_bar::{
+    StatusItemContent, StatusItemEvent, StatusItemMenuEntry, update_status_item,
+};
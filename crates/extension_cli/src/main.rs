@@ -109,6 +109,7 @@ async fn main() -> Result<()> {
         repository: manifest
             .repository
             .context("missing repository in extension manifest")?,
+        changelog: manifest.changelog,
         wasm_api_version: manifest.lib.version.map(|version| version.to_string()),
         provides: extension_provides,
     })?;
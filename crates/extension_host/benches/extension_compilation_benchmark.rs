@@ -37,7 +37,12 @@ fn extension_benchmarks(c: &mut Criterion) {
             |wasm_bytes| {
                 let _extension = cx
                     .executor()
-                    .block(wasm_host.load_extension(wasm_bytes, &manifest, cx.executor()))
+                    .block(wasm_host.load_extension(
+                        wasm_bytes,
+                        &manifest,
+                        Vec::new(),
+                        cx.executor(),
+                    ))
                     .unwrap();
             },
             BatchSize::SmallInput,
@@ -119,6 +124,7 @@ fn manifest() -> ExtensionManifest {
         description: Some("An extension for use in tests.".into()),
         authors: Vec::new(),
         repository: None,
+        changelog: None,
         themes: Default::default(),
         icon_themes: Vec::new(),
         lib: LibManifestEntry {
@@ -141,6 +147,12 @@ fn manifest() -> ExtensionManifest {
         )],
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        panels: Default::default(),
+        status_items: Default::default(),
+        file_viewers: Default::default(),
+        formatters: Default::default(),
+        linters: Default::default(),
+        edit_prediction_providers: Default::default(),
     }
 }
 
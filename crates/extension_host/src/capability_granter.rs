@@ -1,25 +1,50 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{Result, bail};
 use extension::{ExtensionCapability, ExtensionManifest};
+use semantic_version::SemanticVersion;
 use url::Url;
 
+/// The version of the extension API from which extensions have to declare the hosts they
+/// make network requests to in their manifest.
+const DECLARED_NETWORK_REQUESTS_MIN_API_VERSION: SemanticVersion = SemanticVersion::new(0, 8, 0);
+
 pub struct CapabilityGranter {
     granted_capabilities: Vec<ExtensionCapability>,
+    /// The capabilities declared in the manifest that the user has revoked.
+    revoked_capabilities: Vec<ExtensionCapability>,
     manifest: Arc<ExtensionManifest>,
 }
 
+/// A directory the extension may access through WASI.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GrantedDirectory {
+    pub path: PathBuf,
+    pub writable: bool,
+}
+
 impl CapabilityGranter {
     pub fn new(
         granted_capabilities: Vec<ExtensionCapability>,
+        revoked_capabilities: Vec<ExtensionCapability>,
         manifest: Arc<ExtensionManifest>,
     ) -> Self {
         Self {
             granted_capabilities,
+            revoked_capabilities,
             manifest,
         }
     }
 
+    /// Returns the capabilities declared in the manifest that haven't been revoked.
+    fn declared_capabilities(&self) -> impl Iterator<Item = &ExtensionCapability> {
+        self.manifest
+            .capabilities
+            .iter()
+            .filter(|capability| !self.revoked_capabilities.contains(capability))
+    }
+
     pub fn grant_exec(
         &self,
         desired_command: &str,
@@ -27,6 +52,18 @@ impl CapabilityGranter {
     ) -> Result<()> {
         self.manifest.allow_exec(desired_command, desired_args)?;
 
+        let is_declared = self
+            .declared_capabilities()
+            .any(|capability| match capability {
+                ExtensionCapability::ProcessExec(capability) => {
+                    capability.allows(desired_command, desired_args)
+                }
+                _ => false,
+            });
+        if !is_declared {
+            bail!("capability for process:exec {desired_command} {desired_args:?} was revoked");
+        }
+
         let is_allowed = self
             .granted_capabilities
             .iter()
@@ -81,13 +118,95 @@ impl CapabilityGranter {
 
         Ok(())
     }
+
+    pub fn grant_network_request(&self, desired_url: &Url) -> Result<()> {
+        // Extensions built against older versions of the API predate this capability, so
+        // they can't declare it.
+        let must_be_declared = self
+            .manifest
+            .lib
+            .version
+            .is_some_and(|version| version >= DECLARED_NETWORK_REQUESTS_MIN_API_VERSION);
+        let is_declared = self
+            .declared_capabilities()
+            .any(|capability| match capability {
+                ExtensionCapability::NetworkRequest(capability) => capability.allows(desired_url),
+                _ => false,
+            });
+        if must_be_declared && !is_declared {
+            bail!(
+                "capability for network:request {desired_url} was not listed in the extension manifest or was revoked",
+            );
+        }
+
+        let is_allowed = self
+            .granted_capabilities
+            .iter()
+            .any(|capability| match capability {
+                ExtensionCapability::NetworkRequest(capability) => capability.allows(desired_url),
+                _ => false,
+            });
+        if !is_allowed {
+            bail!(
+                "capability for network:request {desired_url} is not granted by the extension host",
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the directories outside of its work directory that the extension may access,
+    /// which are those it declares in its manifest that are granted by the extension host.
+    pub fn granted_directories(&self) -> Vec<GrantedDirectory> {
+        let mut directories = Vec::new();
+        for capability in self.declared_capabilities() {
+            let (capability, writable) = match capability {
+                ExtensionCapability::FsRead(capability) => (capability, false),
+                ExtensionCapability::FsWrite(capability) => (capability, true),
+                _ => continue,
+            };
+            // Extensions can only be granted access to specific directories.
+            let Some(path) = capability.directory() else {
+                log::warn!(
+                    "extension {} requested access to every directory, which is not allowed",
+                    self.manifest.id
+                );
+                continue;
+            };
+
+            let is_allowed =
+                self.granted_capabilities.iter().any(
+                    |granted_capability| match granted_capability {
+                        ExtensionCapability::FsRead(granted_capability) => {
+                            !writable && granted_capability.allows(&path)
+                        }
+                        ExtensionCapability::FsWrite(granted_capability) => {
+                            granted_capability.allows(&path)
+                        }
+                        _ => false,
+                    },
+                );
+            if is_allowed {
+                directories.push(GrantedDirectory { path, writable });
+            } else {
+                log::warn!(
+                    "capability for {} {path:?} is not granted by the extension host",
+                    if writable { "fs:write" } else { "fs:read" }
+                );
+            }
+        }
+        directories
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
-    use extension::{ProcessExecCapability, SchemaVersion};
+    use extension::{
+        FilesystemCapability, LibManifestEntry, NetworkRequestCapability, ProcessExecCapability,
+        SchemaVersion,
+    };
 
     use super::*;
 
@@ -99,6 +218,7 @@ mod tests {
             schema_version: SchemaVersion::ZERO,
             description: None,
             repository: None,
+            changelog: None,
             authors: vec![],
             lib: Default::default(),
             themes: vec![],
@@ -112,6 +232,12 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            panels: Default::default(),
+            status_items: Default::default(),
+            file_viewers: Default::default(),
+            formatters: Default::default(),
+            linters: Default::default(),
+            edit_prediction_providers: Default::default(),
         }
     }
 
@@ -126,7 +252,7 @@ mod tests {
         });
 
         // It returns an error when the extension host has no granted capabilities.
-        let granter = CapabilityGranter::new(Vec::new(), manifest.clone());
+        let granter = CapabilityGranter::new(Vec::new(), Vec::new(), manifest.clone());
         assert!(granter.grant_exec("ls", &["-la"]).is_err());
 
         // It succeeds when the extension host has the exact capability.
@@ -135,6 +261,7 @@ mod tests {
                 command: "ls".to_string(),
                 args: vec!["-la".to_string()],
             })],
+            Vec::new(),
             manifest.clone(),
         );
         assert!(granter.grant_exec("ls", &["-la"]).is_ok());
@@ -145,8 +272,135 @@ mod tests {
                 command: "*".to_string(),
                 args: vec!["**".to_string()],
             })],
-            manifest,
+            Vec::new(),
+            manifest.clone(),
         );
         assert!(granter.grant_exec("ls", &["-la"]).is_ok());
+
+        // It returns an error when the user has revoked the capability.
+        let granter = CapabilityGranter::new(
+            vec![ExtensionCapability::ProcessExec(ProcessExecCapability {
+                command: "*".to_string(),
+                args: vec!["**".to_string()],
+            })],
+            manifest.capabilities.clone(),
+            manifest,
+        );
+        assert!(granter.grant_exec("ls", &["-la"]).is_err());
+    }
+
+    #[test]
+    fn test_grant_network_request() {
+        let capability = ExtensionCapability::NetworkRequest(NetworkRequestCapability {
+            host: "api.example.com".to_string(),
+        });
+        let host_capability = ExtensionCapability::NetworkRequest(NetworkRequestCapability {
+            host: "*".to_string(),
+        });
+        let url = "https://api.example.com/v1/items".parse().unwrap();
+        let lib = LibManifestEntry {
+            kind: None,
+            version: Some(SemanticVersion::new(0, 8, 0)),
+        };
+
+        // It succeeds without the capability being declared for extensions built against
+        // older versions of the API.
+        let granter = CapabilityGranter::new(
+            vec![host_capability.clone()],
+            Vec::new(),
+            Arc::new(ExtensionManifest {
+                lib: LibManifestEntry {
+                    kind: None,
+                    version: Some(SemanticVersion::new(0, 7, 0)),
+                },
+                ..extension_manifest()
+            }),
+        );
+        assert!(granter.grant_network_request(&url).is_ok());
+
+        // It returns an error when the extension doesn't declare the capability.
+        let granter = CapabilityGranter::new(
+            vec![host_capability.clone()],
+            Vec::new(),
+            Arc::new(ExtensionManifest {
+                lib: lib.clone(),
+                ..extension_manifest()
+            }),
+        );
+        assert!(granter.grant_network_request(&url).is_err());
+
+        let manifest = Arc::new(ExtensionManifest {
+            lib,
+            capabilities: vec![capability.clone()],
+            ..extension_manifest()
+        });
+
+        // It returns an error when the extension host has no granted capabilities.
+        let granter = CapabilityGranter::new(Vec::new(), Vec::new(), manifest.clone());
+        assert!(granter.grant_network_request(&url).is_err());
+
+        // It succeeds when the capability is declared and granted.
+        let granter =
+            CapabilityGranter::new(vec![host_capability.clone()], Vec::new(), manifest.clone());
+        assert!(granter.grant_network_request(&url).is_ok());
+        assert!(
+            granter
+                .grant_network_request(&"https://example.com".parse().unwrap())
+                .is_err()
+        );
+
+        // It returns an error when the user has revoked the capability.
+        let granter = CapabilityGranter::new(vec![host_capability], vec![capability], manifest);
+        assert!(granter.grant_network_request(&url).is_err());
+    }
+
+    #[test]
+    fn test_granted_directories() {
+        let manifest = Arc::new(ExtensionManifest {
+            capabilities: vec![
+                ExtensionCapability::FsRead(FilesystemCapability {
+                    path: "/usr/share/dict".to_string(),
+                }),
+                ExtensionCapability::FsWrite(FilesystemCapability {
+                    path: "/tmp/my-extension".to_string(),
+                }),
+                ExtensionCapability::FsRead(FilesystemCapability {
+                    path: "*".to_string(),
+                }),
+            ],
+            ..extension_manifest()
+        });
+
+        let granter = CapabilityGranter::new(
+            vec![ExtensionCapability::FsRead(FilesystemCapability {
+                path: "*".to_string(),
+            })],
+            Vec::new(),
+            manifest.clone(),
+        );
+        assert_eq!(
+            granter.granted_directories(),
+            vec![GrantedDirectory {
+                path: PathBuf::from("/usr/share/dict"),
+                writable: false,
+            }]
+        );
+
+        let granter = CapabilityGranter::new(
+            vec![ExtensionCapability::FsWrite(FilesystemCapability {
+                path: "*".to_string(),
+            })],
+            vec![ExtensionCapability::FsRead(FilesystemCapability {
+                path: "/usr/share/dict".to_string(),
+            })],
+            manifest,
+        );
+        assert_eq!(
+            granter.granted_directories(),
+            vec![GrantedDirectory {
+                path: PathBuf::from("/tmp/my-extension"),
+                writable: true,
+            }]
+        );
     }
 }
@@ -15,9 +15,11 @@ use collections::{BTreeMap, BTreeSet, HashMap, HashSet, btree_map};
 pub use extension::ExtensionManifest;
 use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
 use extension::{
-    ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy, ExtensionEvents,
-    ExtensionGrammarProxy, ExtensionHostProxy, ExtensionLanguageProxy,
-    ExtensionLanguageServerProxy, ExtensionSlashCommandProxy, ExtensionSnippetProxy,
+    ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy,
+    ExtensionEditPredictionProviderProxy, ExtensionEvents, ExtensionFileViewerProxy,
+    ExtensionFormatterProxy, ExtensionGrammarProxy, ExtensionHostProxy, ExtensionLanguageProxy,
+    ExtensionLanguageServerProxy, ExtensionLinterProxy, ExtensionPanelProxy,
+    ExtensionSlashCommandProxy, ExtensionSnippetProxy, ExtensionStatusItemProxy,
     ExtensionThemeProxy,
 };
 use fs::{Fs, RemoveOptions};
@@ -46,12 +48,13 @@ use release_channel::ReleaseChannel;
 use remote::{RemoteClient, RemoteConnectionOptions};
 use semantic_version::SemanticVersion;
 use serde::{Deserialize, Serialize};
-use settings::Settings;
+use settings::{Settings, SettingsStore};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 use std::{
     cmp::Ordering,
     path::{self, Path, PathBuf},
+    rc::Rc,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -69,6 +72,9 @@ pub use extension_settings::ExtensionSettings;
 
 pub const RELOAD_DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
 const FS_WATCH_LATENCY: Duration = Duration::from_millis(100);
+const DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION: Duration = Duration::from_millis(500);
+/// The maximum length of an extension's log, beyond which its oldest lines are dropped.
+const MAX_EXTENSION_LOG_LEN: usize = 1024 * 1024;
 
 /// The current extension [`SchemaVersion`] supported by Zed.
 const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(1);
@@ -125,6 +131,14 @@ pub struct ExtensionStore {
     pub tasks: Vec<Task<()>>,
     pub remote_clients: HashMap<RemoteConnectionOptions, WeakEntity<RemoteClient>>,
     pub ssh_registered_tx: UnboundedSender<()>,
+    /// What each extension has written to its stdout and stderr.
+    extension_logs: HashMap<Arc<str>, String>,
+    /// The tasks watching the source directories of the dev extensions, to rebuild them
+    /// when they change.
+    dev_extension_watchers: HashMap<Arc<str>, Task<()>>,
+    /// Asks the user whether to allow an extension that is being installed the capabilities it
+    /// declares, before it is loaded.
+    permissions_prompt: Option<Rc<dyn Fn(Arc<ExtensionManifest>, &mut App) -> Task<bool>>>,
 }
 
 #[derive(Clone, Copy)]
@@ -141,6 +155,12 @@ pub enum Event {
     ExtensionInstalled(Arc<str>),
     ExtensionUninstalled(Arc<str>),
     ExtensionFailedToLoad(Arc<str>),
+    ExtensionLogUpdated(Arc<str>),
+    ExtensionPanicked {
+        extension_id: Arc<str>,
+        /// The panic message written by the extension, if any, followed by the Wasm backtrace.
+        message: String,
+    },
 }
 
 impl EventEmitter<Event> for ExtensionStore {}
@@ -278,6 +298,9 @@ impl ExtensionStore {
 
             remote_clients: HashMap::default(),
             ssh_registered_tx: connection_registered_tx,
+            extension_logs: HashMap::default(),
+            dev_extension_watchers: HashMap::default(),
+            permissions_prompt: None,
         };
 
         // The extensions store maintains an index file, which contains a complete
@@ -378,6 +401,29 @@ impl ExtensionStore {
             .await;
         }));
 
+        // Reload extensions when the user changes which of their capabilities are revoked,
+        // as the capabilities of an extension are determined when it is loaded.
+        let mut revoked_capabilities = ExtensionSettings::get_global(cx)
+            .revoked_capabilities
+            .clone();
+        cx.observe_global::<SettingsStore>(move |this, cx| {
+            let new_revoked_capabilities = &ExtensionSettings::get_global(cx).revoked_capabilities;
+            let changed_extension_ids = revoked_capabilities
+                .keys()
+                .chain(new_revoked_capabilities.keys())
+                .filter(|extension_id| {
+                    revoked_capabilities.get(*extension_id)
+                        != new_revoked_capabilities.get(*extension_id)
+                })
+                .cloned()
+                .collect::<HashSet<_>>();
+            revoked_capabilities = new_revoked_capabilities.clone();
+            for extension_id in changed_extension_ids {
+                drop(this.reload(Some(extension_id), cx));
+            }
+        })
+        .detach();
+
         // Watch the installed extensions directory for changes. Whenever changes are
         // detected, rebuild the extension index, and load/unload any extensions that
         // have been added, removed, or modified.
@@ -739,15 +785,6 @@ impl ExtensionStore {
                 .await
                 .context("downloading extension")?;
 
-            fs.remove_dir(
-                &extension_dir,
-                RemoveOptions {
-                    recursive: true,
-                    ignore_if_not_exists: true,
-                },
-            )
-            .await?;
-
             let content_length = response
                 .headers()
                 .get(http_client::http::header::CONTENT_LENGTH)
@@ -763,6 +800,39 @@ impl ExtensionStore {
                     bail!("downloaded extension size {actual_len} does not match content length {content_length}");
                 }
             }
+
+            // Extensions are only loaded once the user has allowed the capabilities they declare.
+            if let ExtensionOperation::Install = operation {
+                let permissions_prompt =
+                    this.read_with(cx, |this, _| this.permissions_prompt.clone())?;
+                if let Some(permissions_prompt) = permissions_prompt
+                    && let Some(manifest) = manifest_from_archive(&tar_gz_bytes)
+                        .await
+                        .log_err()
+                        .flatten()
+                    && !manifest.capabilities.is_empty()
+                {
+                    let allowed = cx
+                        .update(|cx| permissions_prompt(Arc::new(manifest), cx))?
+                        .await;
+                    if !allowed {
+                        log::info!(
+                            "not installing extension {extension_id}, as its permissions weren't allowed"
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+
+            fs.remove_dir(
+                &extension_dir,
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await?;
+
             let decompressed_bytes = GzipDecoder::new(BufReader::new(tar_gz_bytes.as_slice()));
             let archive = Archive::new(decompressed_bytes);
             archive.unpack(extension_dir).await?;
@@ -1074,6 +1144,113 @@ impl ExtensionStore {
         .detach_and_log_err(cx)
     }
 
+    /// Rebuilds each dev extension whenever its source changes, so that changes to it
+    /// take effect without having to rebuild it by hand.
+    fn watch_dev_extension_sources(&mut self, cx: &mut Context<Self>) {
+        let dev_extension_ids = self
+            .dev_extensions()
+            .map(|manifest| manifest.id.clone())
+            .collect::<HashSet<_>>();
+        self.dev_extension_watchers
+            .retain(|extension_id, _| dev_extension_ids.contains(extension_id));
+
+        for extension_id in dev_extension_ids {
+            if self.dev_extension_watchers.contains_key(&extension_id) {
+                continue;
+            }
+
+            let fs = self.fs.clone();
+            let installed_path = self.installed_dir.join(extension_id.as_ref());
+            let task = cx.spawn({
+                let extension_id = extension_id.clone();
+                async move |this, cx| {
+                    // Dev extensions are installed as symlinks to their source directory.
+                    let Some(source_dir) = fs.canonicalize(&installed_path).await.log_err() else {
+                        return;
+                    };
+                    let (mut events, _watcher) = fs.watch(&source_dir, FS_WATCH_LATENCY).await;
+                    while let Some(events) = events.next().await {
+                        if !events
+                            .iter()
+                            .any(|event| is_dev_extension_source_change(&source_dir, &event.path))
+                        {
+                            continue;
+                        }
+
+                        cx.background_executor()
+                            .timer(DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION)
+                            .await;
+                        while let Some(Some(_)) = events.next().now_or_never() {}
+
+                        log::info!("rebuilding dev extension {extension_id} after changes");
+                        if this
+                            .update(cx, |this, cx| {
+                                this.rebuild_dev_extension(extension_id.clone(), cx)
+                            })
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+            });
+            self.dev_extension_watchers.insert(extension_id, task);
+        }
+    }
+
+    /// Sets how the user is asked whether to allow an extension the capabilities it declares when
+    /// it is installed. The extension is only installed if the returned task resolves to `true`.
+    pub fn set_permissions_prompt(
+        &mut self,
+        prompt: impl Fn(Arc<ExtensionManifest>, &mut App) -> Task<bool> + 'static,
+    ) {
+        self.permissions_prompt = Some(Rc::new(prompt));
+    }
+
+    /// Returns what the extension with the given ID has written to its stdout and stderr.
+    pub fn extension_log(&self, extension_id: &str) -> Option<&str> {
+        self.extension_logs.get(extension_id).map(String::as_str)
+    }
+
+    pub(crate) fn append_extension_log(
+        &mut self,
+        extension_id: Arc<str>,
+        text: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let log = self.extension_logs.entry(extension_id.clone()).or_default();
+        log.push_str(text);
+        if log.len() > MAX_EXTENSION_LOG_LEN {
+            let excess = log.len() - MAX_EXTENSION_LOG_LEN;
+            let end = log[excess..]
+                .find('\n')
+                .map_or(log.len(), |ix| excess + ix + 1);
+            log.drain(..end);
+        }
+        cx.emit(Event::ExtensionLogUpdated(extension_id));
+    }
+
+    pub(crate) fn extension_panicked(
+        &mut self,
+        extension_id: Arc<str>,
+        backtrace: String,
+        cx: &mut Context<Self>,
+    ) {
+        // Rust extensions print their panic message to stderr before trapping.
+        let panic_message = self
+            .extension_log(&extension_id)
+            .and_then(|log| log.rfind("panicked at").map(|ix| log[ix..].trim()));
+        let message = match panic_message {
+            Some(panic_message) => format!("{panic_message}\n\n{backtrace}"),
+            None => backtrace,
+        };
+        log::error!("extension {extension_id} panicked: {message}");
+        cx.emit(Event::ExtensionPanicked {
+            extension_id,
+            message,
+        });
+    }
+
     /// Updates the set of installed extensions.
     ///
     /// First, this unloads any themes, languages, or grammars that are
@@ -1220,6 +1397,25 @@ impl ExtensionStore {
             for command_name in extension.manifest.slash_commands.keys() {
                 self.proxy.unregister_slash_command(command_name.clone());
             }
+            for panel_id in extension.manifest.panels.keys() {
+                self.proxy.unregister_panel(panel_id.clone(), cx);
+            }
+            for item_id in extension.manifest.status_items.keys() {
+                self.proxy.unregister_status_item(item_id.clone(), cx);
+            }
+            for viewer_id in extension.manifest.file_viewers.keys() {
+                self.proxy.unregister_file_viewer(viewer_id.clone(), cx);
+            }
+            for formatter_id in extension.manifest.formatters.keys() {
+                self.proxy.unregister_formatter(formatter_id.clone(), cx);
+            }
+            for linter_id in extension.manifest.linters.keys() {
+                self.proxy.unregister_linter(linter_id.clone(), cx);
+            }
+            for provider_id in extension.manifest.edit_prediction_providers.keys() {
+                self.proxy
+                    .unregister_edit_prediction_provider(provider_id.clone(), cx);
+            }
         }
 
         self.wasm_extensions
@@ -1318,6 +1514,7 @@ impl ExtensionStore {
             .filter_map(|name| new_index.extensions.get(name).cloned())
             .collect::<Vec<_>>();
         self.extension_index = new_index;
+        self.watch_dev_extension_sources(cx);
         cx.notify();
         cx.emit(Event::ExtensionsUpdated);
 
@@ -1445,6 +1642,60 @@ impl ExtensionStore {
                         this.proxy
                             .register_debug_locator(extension.clone(), debug_adapter.clone());
                     }
+
+                    for (panel_id, panel) in &manifest.panels {
+                        this.proxy.register_panel(
+                            extension.clone(),
+                            panel_id.clone(),
+                            panel.title.clone().into(),
+                            cx,
+                        );
+                    }
+
+                    for (item_id, item) in &manifest.status_items {
+                        this.proxy.register_status_item(
+                            extension.clone(),
+                            item_id.clone(),
+                            item.position,
+                            cx,
+                        );
+                    }
+
+                    for (viewer_id, viewer) in &manifest.file_viewers {
+                        this.proxy.register_file_viewer(
+                            extension.clone(),
+                            viewer_id.clone(),
+                            viewer.path_suffixes.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (formatter_id, formatter) in &manifest.formatters {
+                        this.proxy.register_formatter(
+                            extension.clone(),
+                            formatter_id.clone(),
+                            formatter.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (linter_id, linter) in &manifest.linters {
+                        this.proxy.register_linter(
+                            extension.clone(),
+                            linter_id.clone(),
+                            linter.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (provider_id, provider) in &manifest.edit_prediction_providers {
+                        this.proxy.register_edit_prediction_provider(
+                            extension.clone(),
+                            provider_id.clone(),
+                            provider.clone(),
+                            cx,
+                        );
+                    }
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
@@ -1836,6 +2087,43 @@ impl ExtensionStore {
     }
 }
 
+/// Returns whether a change to the given path within the source directory of a dev
+/// extension should trigger a rebuild, rather than being an output of the build itself.
+fn is_dev_extension_source_change(source_dir: &Path, path: &Path) -> bool {
+    let Ok(relative_path) = path.strip_prefix(source_dir) else {
+        return false;
+    };
+    let Some(path::Component::Normal(first_component)) = relative_path.components().next() else {
+        return false;
+    };
+    !matches!(
+        first_component.to_str(),
+        Some("target" | "grammars" | "extension.wasm" | "Cargo.lock" | ".git")
+    )
+}
+
+/// Reads the manifest of a packaged extension, without unpacking it.
+async fn manifest_from_archive(tar_gz_bytes: &[u8]) -> Result<Option<ExtensionManifest>> {
+    let archive = Archive::new(GzipDecoder::new(BufReader::new(tar_gz_bytes)));
+    let mut entries = archive.entries()?;
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        let path = entry.path()?;
+        let is_manifest = path
+            .components()
+            .filter(|component| *component != path::Component::CurDir)
+            .eq([path::Component::Normal("extension.toml".as_ref())]);
+        if is_manifest {
+            let mut manifest_content = String::new();
+            entry.read_to_string(&mut manifest_content).await?;
+            return Ok(Some(
+                toml::from_str(&manifest_content).context("invalid extension.toml")?,
+            ));
+        }
+    }
+    Ok(None)
+}
+
 fn load_plugin_queries(root_path: &Path) -> LanguageQueries {
     let mut result = LanguageQueries::default();
     if let Some(entries) = std::fs::read_dir(root_path).log_err() {
@@ -1,9 +1,10 @@
 use collections::HashMap;
 use extension::{
-    DownloadFileCapability, ExtensionCapability, NpmInstallPackageCapability, ProcessExecCapability,
+    DownloadFileCapability, ExtensionCapability, FilesystemCapability, NetworkRequestCapability,
+    NpmInstallPackageCapability, ProcessExecCapability,
 };
 use gpui::App;
-use settings::Settings;
+use settings::{ExtensionCapabilityContent, Settings};
 use std::sync::Arc;
 
 #[derive(Debug, Default, Clone)]
@@ -17,6 +18,12 @@ pub struct ExtensionSettings {
     pub auto_install_extensions: HashMap<Arc<str>, bool>,
     pub auto_update_extensions: HashMap<Arc<str>, bool>,
     pub granted_capabilities: Vec<ExtensionCapability>,
+    /// The capabilities declared by extensions that the user has revoked, by extension ID.
+    pub revoked_capabilities: HashMap<Arc<str>, Vec<ExtensionCapability>>,
+    /// The IDs of the status bar items provided by extensions that are hidden.
+    pub hidden_status_items: Vec<Arc<str>>,
+    /// The IDs of the status bar items provided by extensions to show first, in order.
+    pub status_item_order: Vec<Arc<str>>,
 }
 
 impl ExtensionSettings {
@@ -34,6 +41,59 @@ impl ExtensionSettings {
             .copied()
             .unwrap_or(true)
     }
+
+    /// Returns the capabilities declared by the given extension that the user has revoked.
+    pub fn revoked_capabilities(&self, extension_id: &str) -> &[ExtensionCapability] {
+        self.revoked_capabilities
+            .get(extension_id)
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+pub fn capability_from_content(content: ExtensionCapabilityContent) -> ExtensionCapability {
+    match content {
+        ExtensionCapabilityContent::ProcessExec { command, args } => {
+            ExtensionCapability::ProcessExec(ProcessExecCapability { command, args })
+        }
+        ExtensionCapabilityContent::DownloadFile { host, path } => {
+            ExtensionCapability::DownloadFile(DownloadFileCapability { host, path })
+        }
+        ExtensionCapabilityContent::NpmInstallPackage { package } => {
+            ExtensionCapability::NpmInstallPackage(NpmInstallPackageCapability { package })
+        }
+        ExtensionCapabilityContent::NetworkRequest { host } => {
+            ExtensionCapability::NetworkRequest(NetworkRequestCapability { host })
+        }
+        ExtensionCapabilityContent::FsRead { path } => {
+            ExtensionCapability::FsRead(FilesystemCapability { path })
+        }
+        ExtensionCapabilityContent::FsWrite { path } => {
+            ExtensionCapability::FsWrite(FilesystemCapability { path })
+        }
+    }
+}
+
+pub fn capability_to_content(capability: ExtensionCapability) -> ExtensionCapabilityContent {
+    match capability {
+        ExtensionCapability::ProcessExec(ProcessExecCapability { command, args }) => {
+            ExtensionCapabilityContent::ProcessExec { command, args }
+        }
+        ExtensionCapability::DownloadFile(DownloadFileCapability { host, path }) => {
+            ExtensionCapabilityContent::DownloadFile { host, path }
+        }
+        ExtensionCapability::NpmInstallPackage(NpmInstallPackageCapability { package }) => {
+            ExtensionCapabilityContent::NpmInstallPackage { package }
+        }
+        ExtensionCapability::NetworkRequest(NetworkRequestCapability { host }) => {
+            ExtensionCapabilityContent::NetworkRequest { host }
+        }
+        ExtensionCapability::FsRead(FilesystemCapability { path }) => {
+            ExtensionCapabilityContent::FsRead { path }
+        }
+        ExtensionCapability::FsWrite(FilesystemCapability { path }) => {
+            ExtensionCapabilityContent::FsWrite { path }
+        }
+    }
 }
 
 impl Settings for ExtensionSettings {
@@ -47,20 +107,35 @@ impl Settings for ExtensionSettings {
                 .clone()
                 .unwrap_or_default()
                 .into_iter()
-                .map(|capability| match capability {
-                    settings::ExtensionCapabilityContent::ProcessExec { command, args } => {
-                        ExtensionCapability::ProcessExec(ProcessExecCapability { command, args })
-                    }
-                    settings::ExtensionCapabilityContent::DownloadFile { host, path } => {
-                        ExtensionCapability::DownloadFile(DownloadFileCapability { host, path })
-                    }
-                    settings::ExtensionCapabilityContent::NpmInstallPackage { package } => {
-                        ExtensionCapability::NpmInstallPackage(NpmInstallPackageCapability {
-                            package,
-                        })
-                    }
+                .map(capability_from_content)
+                .collect(),
+            revoked_capabilities: content
+                .extension
+                .revoked_extension_capabilities
+                .iter()
+                .map(|(extension_id, capabilities)| {
+                    (
+                        extension_id.clone(),
+                        capabilities
+                            .iter()
+                            .cloned()
+                            .map(capability_from_content)
+                            .collect(),
+                    )
                 })
                 .collect(),
+            hidden_status_items: content
+                .extension
+                .extension_status_items
+                .as_ref()
+                .and_then(|status_items| status_items.hidden.clone())
+                .unwrap_or_default(),
+            status_item_order: content
+                .extension
+                .extension_status_items
+                .as_ref()
+                .and_then(|status_items| status_items.order.clone())
+                .unwrap_or_default(),
         }
     }
 }
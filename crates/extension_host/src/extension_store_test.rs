@@ -1,7 +1,7 @@
 use crate::{
     Event, ExtensionIndex, ExtensionIndexEntry, ExtensionIndexLanguageEntry,
     ExtensionIndexThemeEntry, ExtensionManifest, ExtensionSettings, ExtensionStore,
-    GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION, SchemaVersion,
+    GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION, SchemaVersion, is_dev_extension_source_change,
 };
 use async_compression::futures::bufread::GzipEncoder;
 use collections::{BTreeMap, HashSet};
@@ -147,6 +147,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         description: None,
                         authors: Vec::new(),
                         repository: None,
+                        changelog: None,
                         themes: Default::default(),
                         icon_themes: Vec::new(),
                         lib: Default::default(),
@@ -164,6 +165,12 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        panels: Default::default(),
+                        status_items: Default::default(),
+                        file_viewers: Default::default(),
+                        formatters: Default::default(),
+                        linters: Default::default(),
+                        edit_prediction_providers: Default::default(),
                     }),
                     dev: false,
                 },
@@ -179,6 +186,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         description: None,
                         authors: vec![],
                         repository: None,
+                        changelog: None,
                         themes: vec![
                             "themes/monokai-pro.json".into(),
                             "themes/monokai.json".into(),
@@ -194,6 +202,12 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        panels: Default::default(),
+                        status_items: Default::default(),
+                        file_viewers: Default::default(),
+                        formatters: Default::default(),
+                        linters: Default::default(),
+                        edit_prediction_providers: Default::default(),
                     }),
                     dev: false,
                 },
@@ -361,6 +375,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 description: None,
                 authors: vec![],
                 repository: None,
+                changelog: None,
                 themes: vec!["themes/gruvbox.json".into()],
                 icon_themes: Vec::new(),
                 lib: Default::default(),
@@ -373,6 +388,12 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 capabilities: Vec::new(),
                 debug_adapters: Default::default(),
                 debug_locators: Default::default(),
+                panels: Default::default(),
+                status_items: Default::default(),
+                file_viewers: Default::default(),
+                formatters: Default::default(),
+                linters: Default::default(),
+                edit_prediction_providers: Default::default(),
             }),
             dev: false,
         },
@@ -858,6 +879,28 @@ async fn test_extension_store_with_test_extension(cx: &mut TestAppContext) {
     assert!(fs.metadata(&expected_server_path).await.unwrap().is_none());
 }
 
+#[test]
+fn test_dev_extension_source_changes() {
+    let source_dir = Path::new("/code/my-extension");
+    for (path, is_source_change) in [
+        ("/code/my-extension/src/lib.rs", true),
+        ("/code/my-extension/extension.toml", true),
+        ("/code/my-extension/languages/foo/highlights.scm", true),
+        ("/code/my-extension/extension.wasm", false),
+        ("/code/my-extension/Cargo.lock", false),
+        ("/code/my-extension/target/debug/build", false),
+        ("/code/my-extension/grammars/foo.wasm", false),
+        ("/code/my-extension", false),
+        ("/code/other-extension/src/lib.rs", false),
+    ] {
+        assert_eq!(
+            is_dev_extension_source_change(source_dir, Path::new(path)),
+            is_source_change,
+            "{path}"
+        );
+    }
+}
+
 fn init_test(cx: &mut TestAppContext) {
     cx.update(|cx| {
         let store = SettingsStore::test(cx);
@@ -1,15 +1,17 @@
 pub mod wit;
 
 use crate::capability_granter::CapabilityGranter;
-use crate::{ExtensionManifest, ExtensionSettings};
+use crate::{ExtensionManifest, ExtensionSettings, ExtensionStore};
 use anyhow::{Context as _, Result, anyhow, bail};
 use async_trait::async_trait;
+use bytes::Bytes;
 use dap::{DebugRequest, StartDebuggingRequestArgumentsRequest};
 use extension::{
     CodeLabel, Command, Completion, ContextServerConfiguration, DebugAdapterBinary,
-    DebugTaskDefinition, ExtensionCapability, ExtensionHostProxy, KeyValueStoreDelegate,
-    ProjectDelegate, SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, Symbol,
-    WorktreeDelegate,
+    DebugTaskDefinition, EditPredictionRequest, ExtensionCapability, ExtensionHostProxy, FileView,
+    FormatRequest, KeyValueStoreDelegate, Lint, LintRequest, LinterOutput, PanelContent,
+    PanelEvent, ProjectDelegate, SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput,
+    SlashCommandOutputEvent, StatusItemEvent, Symbol, WorktreeDelegate,
 };
 use fs::{Fs, normalize_path};
 use futures::future::LocalBoxFuture;
@@ -21,7 +23,7 @@ use futures::{
     },
     future::BoxFuture,
 };
-use gpui::{App, AsyncApp, BackgroundExecutor, Task, Timer};
+use gpui::{App, AsyncApp, BackgroundExecutor, Context, Task, Timer};
 use http_client::HttpClient;
 use language::LanguageName;
 use lsp::LanguageServerName;
@@ -31,6 +33,7 @@ use release_channel::ReleaseChannel;
 use semantic_version::SemanticVersion;
 use settings::Settings;
 use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{LazyLock, OnceLock};
 use std::time::Duration;
 use std::{
@@ -40,7 +43,7 @@ use std::{
 use task::{DebugScenario, SpawnInTerminal, TaskTemplate, ZedDebugConfig};
 use util::paths::SanitizedPath;
 use wasmtime::{
-    CacheStore, Engine, Store,
+    CacheStore, Engine, ResourceLimiter, Store, StoreLimits, StoreLimitsBuilder,
     component::{Component, ResourceTable},
 };
 use wasmtime_wasi::{self as wasi, WasiView};
@@ -63,10 +66,19 @@ pub struct WasmHost {
 #[derive(Clone, Debug)]
 pub struct WasmExtension {
     tx: UnboundedSender<ExtensionCall>,
+    main_thread_message_tx: UnboundedSender<MainThreadCall>,
     pub manifest: Arc<ExtensionManifest>,
     pub work_dir: Arc<Path>,
     #[allow(unused)]
     pub zed_api_version: SemanticVersion,
+    memory_usage: Arc<AtomicUsize>,
+}
+
+impl WasmExtension {
+    /// Returns the number of bytes of linear memory that the extension has allocated.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage.load(Ordering::Relaxed)
+    }
 }
 
 impl Drop for WasmExtension {
@@ -108,7 +120,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn language_server_initialization_options(
@@ -133,7 +145,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn language_server_workspace_configuration(
@@ -156,7 +168,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn language_server_additional_initialization_options(
@@ -181,7 +193,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn language_server_additional_workspace_configuration(
@@ -206,7 +218,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn labels_for_completions(
@@ -232,7 +244,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn labels_for_symbols(
@@ -258,7 +270,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn complete_slash_command_argument(
@@ -277,7 +289,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn run_slash_command(
@@ -285,6 +297,7 @@ impl extension::Extension for WasmExtension {
         command: SlashCommand,
         arguments: Vec<String>,
         delegate: Option<Arc<dyn WorktreeDelegate>>,
+        output_events: mpsc::UnboundedSender<SlashCommandOutputEvent>,
     ) -> Result<SlashCommandOutput> {
         self.call(|extension, store| {
             async move {
@@ -294,16 +307,18 @@ impl extension::Extension for WasmExtension {
                     None
                 };
 
+                store.data_mut().slash_command_output_events = Some(output_events);
                 let output = extension
                     .call_run_slash_command(store, &command.into(), &arguments, resource)
-                    .await?
-                    .map_err(|err| store.data().extension_error(err))?;
+                    .await;
+                store.data_mut().slash_command_output_events = None;
+                let output = output?.map_err(|err| store.data().extension_error(err))?;
 
                 Ok(output.into())
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn context_server_command(
@@ -322,7 +337,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn context_server_configuration(
@@ -349,7 +364,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn suggest_docs_packages(&self, provider: Arc<str>) -> Result<Vec<String>> {
@@ -364,7 +379,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn index_docs(
@@ -390,7 +405,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn get_dap_binary(
@@ -412,7 +427,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
     async fn dap_request_kind(
         &self,
@@ -429,7 +444,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn dap_config_to_scenario(&self, config: ZedDebugConfig) -> Result<DebugScenario> {
@@ -443,7 +458,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
 
     async fn dap_locator_create_scenario(
@@ -467,7 +482,7 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
     }
     async fn run_dap_locator(
         &self,
@@ -483,7 +498,164 @@ impl extension::Extension for WasmExtension {
             }
             .boxed()
         })
-        .await?
+        .await
+    }
+
+    async fn render_panel(
+        &self,
+        panel_id: Arc<str>,
+        project: Arc<dyn ProjectDelegate>,
+    ) -> Result<PanelContent> {
+        self.call(|extension, store| {
+            async move {
+                let project_resource = store.data_mut().table().push(project)?;
+                let content = extension
+                    .call_render_panel(store, &panel_id, project_resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+                anyhow::Ok(content.into())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn handle_panel_event(
+        &self,
+        panel_id: Arc<str>,
+        event: PanelEvent,
+        project: Arc<dyn ProjectDelegate>,
+    ) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                let project_resource = store.data_mut().table().push(project)?;
+                extension
+                    .call_handle_panel_event(store, &panel_id, event.into(), project_resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn handle_status_item_event(
+        &self,
+        item_id: Arc<str>,
+        event: StatusItemEvent,
+    ) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                extension
+                    .call_handle_status_item_event(store, &item_id, event.into())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn render_file(
+        &self,
+        viewer_id: Arc<str>,
+        path: String,
+        content: Vec<u8>,
+    ) -> Result<FileView> {
+        self.call(|extension, store| {
+            async move {
+                let view = extension
+                    .call_render_file(store, &viewer_id, &path, &content)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+                anyhow::Ok(view.into())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn formatter_command(
+        &self,
+        formatter_id: Arc<str>,
+        request: FormatRequest,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Command> {
+        self.call(|extension, store| {
+            async move {
+                let resource = store.data_mut().table().push(worktree)?;
+                let command = extension
+                    .call_formatter_command(store, &formatter_id, &request.into(), resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(command.into())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn linter_command(
+        &self,
+        linter_id: Arc<str>,
+        request: LintRequest,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Command> {
+        self.call(|extension, store| {
+            async move {
+                let resource = store.data_mut().table().push(worktree)?;
+                let command = extension
+                    .call_linter_command(store, &linter_id, &request.into(), resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(command.into())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn parse_linter_output(
+        &self,
+        linter_id: Arc<str>,
+        request: LintRequest,
+        output: LinterOutput,
+    ) -> Result<Vec<Lint>> {
+        self.call(|extension, store| {
+            async move {
+                let lints = extension
+                    .call_parse_linter_output(store, &linter_id, &request.into(), &output.into())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                anyhow::Ok(lints.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn predict_edit(
+        &self,
+        provider_id: Arc<str>,
+        request: EditPredictionRequest,
+    ) -> Result<Vec<String>> {
+        self.call(|extension, store| {
+            async move {
+                let predictions = extension
+                    .call_predict_edit(store, &provider_id, &request.into())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(predictions)
+            }
+            .boxed()
+        })
+        .await
     }
 }
 
@@ -493,8 +665,63 @@ pub struct WasmState {
     ctx: wasi::WasiCtx,
     pub host: Arc<WasmHost>,
     pub(crate) capability_granter: CapabilityGranter,
+    /// Receives the output streamed by the slash command that is running, if any.
+    pub(crate) slash_command_output_events: Option<mpsc::UnboundedSender<SlashCommandOutputEvent>>,
+    limits: ExtensionLimits,
+}
+
+/// Limits the memory and tables of an extension, and keeps track of how much memory it has
+/// allocated.
+struct ExtensionLimits {
+    limits: StoreLimits,
+    memory_usage: Arc<AtomicUsize>,
+}
+
+impl ResourceLimiter for ExtensionLimits {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        let allowed = self.limits.memory_growing(current, desired, maximum)?;
+        if allowed {
+            self.memory_usage
+                .fetch_add(desired.saturating_sub(current), Ordering::Relaxed);
+        }
+        Ok(allowed)
+    }
+
+    fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        self.limits.table_growing(current, desired, maximum)
+    }
+
+    fn instances(&self) -> usize {
+        self.limits.instances()
+    }
+
+    fn tables(&self) -> usize {
+        self.limits.tables()
+    }
+
+    fn memories(&self) -> usize {
+        self.limits.memories()
+    }
 }
 
+/// The maximum size of the linear memories of an extension.
+const MAX_EXTENSION_MEMORY_SIZE: usize = 1024 * 1024 * 1024;
+/// The maximum number of elements in the tables of an extension.
+const MAX_EXTENSION_TABLE_ELEMENTS: usize = 100_000;
+
+/// How many bytes an extension may write to its stdout or stderr at once.
+const EXTENSION_LOG_STREAM_WRITE_BUDGET: usize = 64 * 1024;
+
 type MainThreadCall = Box<dyn Send + for<'a> FnOnce(&'a mut AsyncApp) -> LocalBoxFuture<'a, ()>>;
 
 type ExtensionCall = Box<
@@ -591,6 +818,7 @@ impl WasmHost {
         self: &Arc<Self>,
         wasm_bytes: Vec<u8>,
         manifest: &Arc<ExtensionManifest>,
+        revoked_capabilities: Vec<ExtensionCapability>,
         executor: BackgroundExecutor,
     ) -> Task<Result<WasmExtension>> {
         let this = self.clone();
@@ -600,19 +828,31 @@ impl WasmHost {
 
             let component = Component::from_binary(&this.engine, &wasm_bytes)
                 .context("failed to compile wasm component")?;
+            let capability_granter = CapabilityGranter::new(
+                this.granted_capabilities.clone(),
+                revoked_capabilities,
+                manifest.clone(),
+            );
+            let memory_usage = Arc::new(AtomicUsize::new(0));
             let mut store = wasmtime::Store::new(
                 &this.engine,
                 WasmState {
-                    ctx: this.build_wasi_ctx(&manifest).await?,
+                    ctx: this.build_wasi_ctx(&manifest, &capability_granter).await?,
                     manifest: manifest.clone(),
                     table: ResourceTable::new(),
                     host: this.clone(),
-                    capability_granter: CapabilityGranter::new(
-                        this.granted_capabilities.clone(),
-                        manifest.clone(),
-                    ),
+                    capability_granter,
+                    slash_command_output_events: None,
+                    limits: ExtensionLimits {
+                        limits: StoreLimitsBuilder::new()
+                            .memory_size(MAX_EXTENSION_MEMORY_SIZE)
+                            .table_elements(MAX_EXTENSION_TABLE_ELEMENTS)
+                            .build(),
+                        memory_usage: memory_usage.clone(),
+                    },
                 },
             );
+            store.limiter(|state| &mut state.limits);
             // Store will yield after 1 tick, and get a new deadline of 1 tick after each yield.
             store.set_epoch_deadline(1);
             store.epoch_deadline_async_yield_and_update(1);
@@ -644,12 +884,18 @@ impl WasmHost {
                 manifest: manifest.clone(),
                 work_dir: this.work_dir.join(manifest.id.as_ref()).into(),
                 tx,
+                main_thread_message_tx: this.main_thread_message_tx.clone(),
                 zed_api_version,
+                memory_usage,
             })
         })
     }
 
-    async fn build_wasi_ctx(&self, manifest: &Arc<ExtensionManifest>) -> Result<wasi::WasiCtx> {
+    async fn build_wasi_ctx(
+        &self,
+        manifest: &Arc<ExtensionManifest>,
+        capability_granter: &CapabilityGranter,
+    ) -> Result<wasi::WasiCtx> {
         let extension_work_dir = self.work_dir.join(manifest.id.as_ref());
         self.fs
             .create_dir(&extension_work_dir)
@@ -662,14 +908,39 @@ impl WasmHost {
         #[cfg(target_os = "windows")]
         let path = path.replace('\\', "/");
 
+        let log_stream = ExtensionLogStream {
+            extension_id: manifest.id.clone(),
+            main_thread_message_tx: self.main_thread_message_tx.clone(),
+        };
         let mut ctx = wasi::WasiCtxBuilder::new();
-        ctx.inherit_stdio()
+        ctx.stdout(log_stream.clone())
+            .stderr(log_stream)
             .env("PWD", &path)
             .env("RUST_BACKTRACE", "full");
 
         ctx.preopened_dir(&path, ".", dir_perms, file_perms)?;
         ctx.preopened_dir(&path, &path, dir_perms, file_perms)?;
 
+        for directory in capability_granter.granted_directories() {
+            let (dir_perms, file_perms) = if directory.writable {
+                (wasi::DirPerms::all(), wasi::FilePerms::all())
+            } else {
+                (wasi::DirPerms::READ, wasi::FilePerms::READ)
+            };
+            let guest_path = SanitizedPath::new(&directory.path).to_string();
+            #[cfg(target_os = "windows")]
+            let guest_path = guest_path.replace('\\', "/");
+            if let Err(error) =
+                ctx.preopened_dir(&directory.path, &guest_path, dir_perms, file_perms)
+            {
+                log::warn!(
+                    "failed to give extension {} access to {:?}: {error:#}",
+                    manifest.id,
+                    directory.path
+                );
+            }
+        }
+
         Ok(ctx.build())
     }
 
@@ -746,8 +1017,19 @@ impl WasmExtension {
             .read_to_end(&mut wasm_bytes)
             .context("failed to read wasm")?;
 
+        let revoked_capabilities = cx.update(|cx| {
+            ExtensionSettings::get_global(cx)
+                .revoked_capabilities(&manifest.id)
+                .to_vec()
+        })?;
+
         wasm_host
-            .load_extension(wasm_bytes, manifest, cx.background_executor().clone())
+            .load_extension(
+                wasm_bytes,
+                manifest,
+                revoked_capabilities,
+                cx.background_executor().clone(),
+            )
             .await
             .with_context(|| format!("failed to load wasm extension {}", manifest.id))
     }
@@ -757,7 +1039,7 @@ impl WasmExtension {
         T: 'static + Send,
         Fn: 'static
             + Send
-            + for<'a> FnOnce(&'a mut Extension, &'a mut Store<WasmState>) -> BoxFuture<'a, T>,
+            + for<'a> FnOnce(&'a mut Extension, &'a mut Store<WasmState>) -> BoxFuture<'a, Result<T>>,
     {
         let (return_tx, return_rx) = oneshot::channel();
         self.tx
@@ -775,12 +1057,26 @@ impl WasmExtension {
                     self.manifest.id,
                 )
             })?;
-        return_rx.await.with_context(|| {
+        let result = return_rx.await.with_context(|| {
             format!(
                 "wasm extension channel, extension {} (id {})",
                 self.manifest.name, self.manifest.id,
             )
-        })
+        })?;
+
+        // A trap is how a panic in the extension surfaces on the host, so report it to the
+        // extension store, along with the Wasm backtrace captured by Wasmtime.
+        if let Err(error) = &result
+            && error.downcast_ref::<wasmtime::Trap>().is_some()
+        {
+            let extension_id = self.manifest.id.clone();
+            let message = format!("{error:?}");
+            update_extension_store(&self.main_thread_message_tx, move |store, cx| {
+                store.extension_panicked(extension_id, message, cx)
+            });
+        }
+
+        result
     }
 }
 
@@ -830,6 +1126,67 @@ impl WasmState {
     }
 }
 
+/// Runs the given function on the [`ExtensionStore`], if there is one.
+fn update_extension_store(
+    main_thread_message_tx: &UnboundedSender<MainThreadCall>,
+    f: impl 'static + Send + FnOnce(&mut ExtensionStore, &mut Context<ExtensionStore>),
+) {
+    main_thread_message_tx
+        .unbounded_send(Box::new(move |cx| {
+            async move {
+                cx.update(|cx| {
+                    if let Some(store) = ExtensionStore::try_global(cx) {
+                        store.update(cx, f);
+                    }
+                })
+                .ok();
+            }
+            .boxed_local()
+        }))
+        .ok();
+}
+
+/// Appends what an extension writes to its stdout and stderr to its log.
+#[derive(Clone)]
+struct ExtensionLogStream {
+    extension_id: Arc<str>,
+    main_thread_message_tx: UnboundedSender<MainThreadCall>,
+}
+
+impl wasi::StdoutStream for ExtensionLogStream {
+    fn stream(&self) -> Box<dyn wasi::HostOutputStream> {
+        Box::new(self.clone())
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
+}
+
+impl wasi::HostOutputStream for ExtensionLogStream {
+    fn write(&mut self, bytes: Bytes) -> wasi::StreamResult<()> {
+        let extension_id = self.extension_id.clone();
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        update_extension_store(&self.main_thread_message_tx, move |store, cx| {
+            store.append_extension_log(extension_id, &text, cx)
+        });
+        Ok(())
+    }
+
+    fn flush(&mut self) -> wasi::StreamResult<()> {
+        Ok(())
+    }
+
+    fn check_write(&mut self) -> wasi::StreamResult<usize> {
+        Ok(EXTENSION_LOG_STREAM_WRITE_BUDGET)
+    }
+}
+
+#[async_trait]
+impl wasi::Subscribe for ExtensionLogStream {
+    async fn ready(&mut self) {}
+}
+
 impl wasi::WasiView for WasmState {
     fn table(&mut self) -> &mut ResourceTable {
         &mut self.table
@@ -0,0 +1,287 @@
+use std::sync::Arc;
+
+use extension::{Extension, ExtensionFileViewerProxy, ExtensionHostProxy, FileView};
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Global, Image,
+    ObjectFit, Task, Window, img,
+};
+use language::{File as _, LocalFile as _};
+use project::{Project, ProjectEntryId, ProjectPath};
+use ui::prelude::*;
+use util::paths::PathExt as _;
+use workspace::{
+    Pane, WorkspaceId,
+    item::{Item, ProjectItem, TabContentParams},
+};
+
+pub(crate) fn init(cx: &mut App) {
+    let registry = cx.new(|_| ExtensionFileViewerRegistry::default());
+    cx.set_global(GlobalExtensionFileViewerRegistry(registry.clone()));
+    ExtensionHostProxy::default_global(cx)
+        .register_file_viewer_proxy(ExtensionFileViewerRegistryProxy { registry });
+    workspace::register_project_item::<ExtensionFileView>(cx);
+}
+
+#[derive(Clone)]
+struct RegisteredFileViewer {
+    extension: Arc<dyn Extension>,
+    id: Arc<str>,
+    path_suffixes: Vec<String>,
+}
+
+/// The file viewers provided by the installed extensions.
+#[derive(Default)]
+struct ExtensionFileViewerRegistry {
+    viewers: Vec<RegisteredFileViewer>,
+}
+
+struct GlobalExtensionFileViewerRegistry(Entity<ExtensionFileViewerRegistry>);
+
+impl Global for GlobalExtensionFileViewerRegistry {}
+
+impl ExtensionFileViewerRegistry {
+    fn viewer_for_suffix(&self, suffix: &str) -> Option<&RegisteredFileViewer> {
+        self.viewers.iter().find(|viewer| {
+            viewer
+                .path_suffixes
+                .iter()
+                .any(|path_suffix| path_suffix.eq_ignore_ascii_case(suffix))
+        })
+    }
+}
+
+struct ExtensionFileViewerRegistryProxy {
+    registry: Entity<ExtensionFileViewerRegistry>,
+}
+
+impl ExtensionFileViewerProxy for ExtensionFileViewerRegistryProxy {
+    fn register_file_viewer(
+        &self,
+        extension: Arc<dyn Extension>,
+        viewer_id: Arc<str>,
+        path_suffixes: Vec<String>,
+        cx: &mut App,
+    ) {
+        self.registry.update(cx, |registry, _| {
+            registry.viewers.retain(|viewer| viewer.id != viewer_id);
+            registry.viewers.push(RegisteredFileViewer {
+                extension,
+                id: viewer_id,
+                path_suffixes,
+            });
+        });
+    }
+
+    fn unregister_file_viewer(&self, viewer_id: Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |registry, _| {
+            registry.viewers.retain(|viewer| viewer.id != viewer_id);
+        });
+    }
+}
+
+/// A file opened with a file viewer provided by an extension.
+pub struct ExtensionFile {
+    viewer: RegisteredFileViewer,
+    file: Arc<project::File>,
+    content: Vec<u8>,
+}
+
+impl project::ProjectItem for ExtensionFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Entity<Self>>>> {
+        let registry = cx
+            .try_global::<GlobalExtensionFileViewerRegistry>()?
+            .0
+            .clone();
+        let viewer = registry
+            .read(cx)
+            .viewer_for_suffix(path.path.extension()?)?
+            .clone();
+        let worktree = project.read(cx).worktree_for_id(path.worktree_id, cx)?;
+        let load = worktree.update(cx, |worktree, cx| worktree.load_binary_file(&path.path, cx));
+        Some(cx.spawn(async move |cx| {
+            let loaded = load.await?;
+            cx.new(|_| Self {
+                viewer,
+                file: loaded.file,
+                content: loaded.content,
+            })
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.file.entry_id
+    }
+
+    fn project_path(&self, cx: &App) -> Option<ProjectPath> {
+        Some(ProjectPath {
+            worktree_id: self.file.worktree_id(cx),
+            path: self.file.path.clone(),
+        })
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// Shows a file the way a file viewer provided by an extension renders it.
+pub struct ExtensionFileView {
+    file: Entity<ExtensionFile>,
+    focus_handle: FocusHandle,
+    view: Option<Result<RenderedFileView, SharedString>>,
+    _render_task: Task<()>,
+}
+
+enum RenderedFileView {
+    Image(Arc<Image>),
+    Text(SharedString),
+}
+
+impl ExtensionFileView {
+    fn new(file: Entity<ExtensionFile>, cx: &mut Context<Self>) -> Self {
+        let (viewer, path, content) = {
+            let file = file.read(cx);
+            (
+                file.viewer.clone(),
+                file.file.path.as_unix_str().to_string(),
+                file.content.clone(),
+            )
+        };
+        let _render_task = cx.spawn(async move |this, cx| {
+            let view = viewer
+                .extension
+                .render_file(viewer.id.clone(), path, content)
+                .await
+                .map(|view| match view {
+                    FileView::Image { format, bytes } => {
+                        RenderedFileView::Image(Arc::new(Image::from_bytes(format, bytes)))
+                    }
+                    FileView::Text(text) => RenderedFileView::Text(text.into()),
+                })
+                .map_err(|error| {
+                    log::error!("file viewer {} failed to render file: {error:#}", viewer.id);
+                    SharedString::from(error.to_string())
+                });
+            this.update(cx, |this, cx| {
+                this.view = Some(view);
+                cx.notify();
+            })
+            .ok();
+        });
+
+        Self {
+            file,
+            focus_handle: cx.focus_handle(),
+            view: None,
+            _render_task,
+        }
+    }
+}
+
+impl EventEmitter<()> for ExtensionFileView {}
+
+impl Focusable for ExtensionFileView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for ExtensionFileView {
+    type Event = ();
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        true
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let abs_path = self.file.read(cx).file.as_local()?.abs_path(cx);
+        Some(abs_path.compact().to_string_lossy().into_owned().into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.file.read(cx).file.file_name(cx).to_string().into()
+    }
+
+    fn clone_on_split(
+        &self,
+        _: Option<WorkspaceId>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>>
+    where
+        Self: Sized,
+    {
+        Some(cx.new(|cx| Self::new(self.file.clone(), cx)))
+    }
+}
+
+impl Render for ExtensionFileView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match &self.view {
+            None => Label::new("Loading…")
+                .color(Color::Muted)
+                .into_any_element(),
+            Some(Err(error)) => Label::new(error.clone())
+                .color(Color::Error)
+                .into_any_element(),
+            Some(Ok(RenderedFileView::Image(image))) => img(image.clone())
+                .object_fit(ObjectFit::ScaleDown)
+                .max_w_full()
+                .max_h_full()
+                .into_any_element(),
+            Some(Ok(RenderedFileView::Text(text))) => div()
+                .id("extension-file-view-text")
+                .size_full()
+                .overflow_scroll()
+                .font_buffer(cx)
+                .child(text.clone())
+                .into_any_element(),
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .flex()
+            .justify_center()
+            .items_center()
+            .bg(cx.theme().colors().editor_background)
+            .child(content)
+    }
+}
+
+impl ProjectItem for ExtensionFileView {
+    type Item = ExtensionFile;
+
+    fn for_project_item(
+        _: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, cx)
+    }
+}
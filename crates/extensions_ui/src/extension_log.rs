@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use editor::{Editor, actions::MoveToEnd, scroll::Autoscroll};
+use extension_host::ExtensionStore;
+use gpui::{App, Context, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Window};
+use ui::prelude::*;
+use workspace::{
+    Workspace, WorkspaceId,
+    item::{Item, ItemEvent},
+};
+
+/// Opens the log of the extension with the given ID, or activates it if it is already open.
+pub(crate) fn open_extension_log(
+    workspace: &mut Workspace,
+    extension_id: Arc<str>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .filter_map(|item| item.downcast::<ExtensionLogView>())
+        .find(|view| view.read(cx).extension_id == extension_id);
+
+    if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, window, cx);
+    } else {
+        let view = cx.new(|cx| ExtensionLogView::new(extension_id, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+}
+
+/// Shows what an extension has written to its stdout and stderr.
+pub struct ExtensionLogView {
+    extension_id: Arc<str>,
+    editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+impl ExtensionLogView {
+    fn new(extension_id: Arc<str>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let store = ExtensionStore::global(cx);
+        let log = store
+            .read(cx)
+            .extension_log(&extension_id)
+            .unwrap_or_default()
+            .to_string();
+
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::multi_line(window, cx);
+            editor.set_text(log, window, cx);
+            editor.set_show_git_diff_gutter(false, cx);
+            editor.set_show_runnables(false, cx);
+            editor.set_show_breakpoints(false, cx);
+            editor.set_read_only(true);
+            editor.set_show_edit_predictions(Some(false), window, cx);
+            editor.move_to_end(&MoveToEnd, window, cx);
+            editor
+        });
+
+        let _subscription = cx.subscribe_in(&store, window, |this, store, event, window, cx| {
+            if let extension_host::Event::ExtensionLogUpdated(extension_id) = event
+                && *extension_id == this.extension_id
+            {
+                let log = store
+                    .read(cx)
+                    .extension_log(extension_id)
+                    .unwrap_or_default()
+                    .to_string();
+                this.editor.update(cx, |editor, cx| {
+                    let last_offset = editor.buffer().read(cx).len(cx);
+                    let newest_cursor_is_at_end =
+                        editor.selections.newest::<usize>(cx).start >= last_offset;
+                    editor.set_read_only(false);
+                    editor.set_text(log, window, cx);
+                    editor.set_read_only(true);
+                    if newest_cursor_is_at_end {
+                        editor.move_to_end(&MoveToEnd, window, cx);
+                        editor.request_autoscroll(Autoscroll::bottom(), cx);
+                    }
+                });
+            }
+        });
+
+        Self {
+            extension_id,
+            editor,
+            _subscription,
+        }
+    }
+}
+
+impl EventEmitter<ItemEvent> for ExtensionLogView {}
+
+impl Focusable for ExtensionLogView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for ExtensionLogView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!("{} Log", self.extension_id).into()
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        Some(cx.new(|cx| Self::new(self.extension_id.clone(), window, cx)))
+    }
+}
+
+impl Render for ExtensionLogView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div().size_full().child(self.editor.clone())
+    }
+}
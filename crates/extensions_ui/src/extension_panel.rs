@@ -0,0 +1,527 @@
+use std::sync::Arc;
+
+use collections::HashMap;
+use editor::Editor;
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionPanelProxy, PanelContent, PanelEvent, PanelLabelColor,
+    PanelNode, ProjectDelegate,
+};
+use gpui::{
+    Action, AnyElement, App, Context, ElementId, Entity, EventEmitter, FocusHandle, Focusable,
+    Global, Pixels, SharedString, Subscription, Task, Window, actions, px,
+};
+use project::Project;
+use ui::{Divider, ListItem, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel},
+};
+
+/// Nodes nested deeper than this aren't rendered, which also guards against cycles between nodes.
+const MAX_NODE_DEPTH: usize = 64;
+
+actions!(
+    extension_panel,
+    [
+        /// Toggles focus on the extension panel.
+        ToggleFocus
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    let registry = cx.new(|_| ExtensionPanelRegistry::default());
+    cx.set_global(GlobalExtensionPanelRegistry(registry.clone()));
+    ExtensionHostProxy::default_global(cx)
+        .register_panel_proxy(ExtensionPanelRegistryProxy { registry });
+
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<ExtensionPanel>(window, cx);
+        });
+        let panel = cx.new(|cx| ExtensionPanel::new(workspace, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
+}
+
+#[derive(Clone)]
+struct RegisteredPanel {
+    extension: Arc<dyn Extension>,
+    id: Arc<str>,
+    title: SharedString,
+}
+
+enum ExtensionPanelRegistryEvent {
+    Refresh(Arc<str>),
+}
+
+/// The panels provided by the installed extensions.
+#[derive(Default)]
+struct ExtensionPanelRegistry {
+    panels: Vec<RegisteredPanel>,
+}
+
+impl EventEmitter<ExtensionPanelRegistryEvent> for ExtensionPanelRegistry {}
+
+struct GlobalExtensionPanelRegistry(Entity<ExtensionPanelRegistry>);
+
+impl Global for GlobalExtensionPanelRegistry {}
+
+impl ExtensionPanelRegistry {
+    fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalExtensionPanelRegistry>().0.clone()
+    }
+
+    fn get(&self, id: &str) -> Option<&RegisteredPanel> {
+        self.panels.iter().find(|panel| panel.id.as_ref() == id)
+    }
+}
+
+struct ExtensionPanelRegistryProxy {
+    registry: Entity<ExtensionPanelRegistry>,
+}
+
+impl ExtensionPanelProxy for ExtensionPanelRegistryProxy {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        title: SharedString,
+        cx: &mut App,
+    ) {
+        self.registry.update(cx, |registry, cx| {
+            registry.panels.retain(|panel| panel.id != panel_id);
+            registry.panels.push(RegisteredPanel {
+                extension,
+                id: panel_id,
+                title,
+            });
+            cx.notify();
+        });
+    }
+
+    fn unregister_panel(&self, panel_id: Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |registry, cx| {
+            registry.panels.retain(|panel| panel.id != panel_id);
+            cx.notify();
+        });
+    }
+
+    fn refresh_panel(&self, panel_id: Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |_, cx| {
+            cx.emit(ExtensionPanelRegistryEvent::Refresh(panel_id));
+        });
+    }
+}
+
+struct ExtensionProject {
+    worktree_ids: Vec<u64>,
+}
+
+impl ProjectDelegate for ExtensionProject {
+    fn worktree_ids(&self) -> Vec<u64> {
+        self.worktree_ids.clone()
+    }
+}
+
+/// A dock panel showing the panels provided by extensions, one tab per panel.
+pub struct ExtensionPanel {
+    project: Entity<Project>,
+    registry: Entity<ExtensionPanelRegistry>,
+    focus_handle: FocusHandle,
+    position: DockPosition,
+    width: Option<Pixels>,
+    active_panel_id: Option<Arc<str>>,
+    content: Option<Result<PanelContent, SharedString>>,
+    /// The editors of the text inputs of the active panel, by their ID.
+    text_inputs: HashMap<String, Entity<Editor>>,
+    _render_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ExtensionPanel {
+    fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        let registry = ExtensionPanelRegistry::global(cx);
+        let _subscriptions = vec![
+            cx.observe(&registry, |this, _, cx| this.panels_changed(cx)),
+            cx.subscribe(&registry, |this, _, event, cx| match event {
+                ExtensionPanelRegistryEvent::Refresh(panel_id) => {
+                    if this.active_panel_id.as_ref() == Some(panel_id) {
+                        this.render_content(cx);
+                    }
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            project: workspace.project().clone(),
+            registry,
+            focus_handle: cx.focus_handle(),
+            position: DockPosition::Right,
+            width: None,
+            active_panel_id: None,
+            content: None,
+            text_inputs: HashMap::default(),
+            _render_task: Task::ready(()),
+            _subscriptions,
+        };
+        this.panels_changed(cx);
+        this
+    }
+
+    fn panels_changed(&mut self, cx: &mut Context<Self>) {
+        let registry = self.registry.read(cx);
+        let active_panel_id = self
+            .active_panel_id
+            .clone()
+            .filter(|panel_id| registry.get(panel_id).is_some())
+            .or_else(|| registry.panels.first().map(|panel| panel.id.clone()));
+        self.activate_panel(active_panel_id, cx);
+    }
+
+    fn activate_panel(&mut self, panel_id: Option<Arc<str>>, cx: &mut Context<Self>) {
+        if self.active_panel_id != panel_id {
+            self.active_panel_id = panel_id;
+            self.content = None;
+            self.text_inputs.clear();
+        }
+        self.render_content(cx);
+    }
+
+    fn active_panel(&self, cx: &App) -> Option<RegisteredPanel> {
+        self.registry
+            .read(cx)
+            .get(self.active_panel_id.as_deref()?)
+            .cloned()
+    }
+
+    fn extension_project(&self, cx: &App) -> Arc<dyn ProjectDelegate> {
+        Arc::new(ExtensionProject {
+            worktree_ids: self
+                .project
+                .read(cx)
+                .visible_worktrees(cx)
+                .map(|worktree| worktree.read(cx).id().to_proto())
+                .collect(),
+        })
+    }
+
+    fn render_content(&mut self, cx: &mut Context<Self>) {
+        let Some(panel) = self.active_panel(cx) else {
+            self.content = None;
+            self._render_task = Task::ready(());
+            cx.notify();
+            return;
+        };
+        let project = self.extension_project(cx);
+        self._render_task = cx.spawn(async move |this, cx| {
+            let content = panel
+                .extension
+                .render_panel(panel.id.clone(), project)
+                .await;
+            this.update(cx, |this, cx| {
+                if this.active_panel_id.as_ref() != Some(&panel.id) {
+                    return;
+                }
+                this.content = Some(content.map_err(|error| {
+                    log::error!("failed to render extension panel {}: {error:#}", panel.id);
+                    error.to_string().into()
+                }));
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn send_event(&mut self, event: PanelEvent, cx: &mut Context<Self>) {
+        let Some(panel) = self.active_panel(cx) else {
+            return;
+        };
+        let project = self.extension_project(cx);
+        cx.spawn(async move |this, cx| {
+            let result = panel
+                .extension
+                .handle_panel_event(panel.id.clone(), event, project)
+                .await;
+            this.update(cx, |this, cx| {
+                if let Err(error) = result {
+                    log::error!(
+                        "failed to handle event in extension panel {}: {error:#}",
+                        panel.id
+                    );
+                }
+                if this.active_panel_id.as_ref() == Some(&panel.id) {
+                    this.render_content(cx);
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((id, editor)) = self
+            .text_inputs
+            .iter()
+            .find(|(_, editor)| editor.focus_handle(cx).is_focused(window))
+            .map(|(id, editor)| (id.clone(), editor.clone()))
+        else {
+            return;
+        };
+        let text = editor.update(cx, |editor, cx| {
+            let text = editor.text(cx);
+            editor.clear(window, cx);
+            text
+        });
+        self.send_event(PanelEvent::TextInputSubmitted { id, text }, cx);
+    }
+
+    fn text_input(
+        &mut self,
+        id: &str,
+        placeholder: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        self.text_inputs
+            .entry(id.to_string())
+            .or_insert_with(|| {
+                cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text(placeholder, window, cx);
+                    editor
+                })
+            })
+            .clone()
+    }
+
+    fn render_node(
+        &mut self,
+        content: &PanelContent,
+        ix: u32,
+        depth: usize,
+        indent_level: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let Some(node) = content
+            .nodes
+            .get(ix as usize)
+            .filter(|_| depth < MAX_NODE_DEPTH)
+        else {
+            return div().into_any_element();
+        };
+
+        match node {
+            PanelNode::Label { text, color } => Label::new(text.clone())
+                .color(label_color(*color))
+                .into_any_element(),
+            PanelNode::Button { id, label } => {
+                let event = PanelEvent::ButtonClicked(id.clone());
+                Button::new(
+                    ElementId::Name(format!("button-{id}").into()),
+                    label.clone(),
+                )
+                .style(ButtonStyle::Filled)
+                .on_click(cx.listener(move |this, _, _, cx| this.send_event(event.clone(), cx)))
+                .into_any_element()
+            }
+            PanelNode::TextInput { id, placeholder } => {
+                let editor = self.text_input(id, placeholder, window, cx);
+                div()
+                    .w_full()
+                    .px_1()
+                    .py_0p5()
+                    .border_1()
+                    .border_color(cx.theme().colors().border)
+                    .rounded_sm()
+                    .child(editor)
+                    .into_any_element()
+            }
+            PanelNode::TreeItem {
+                id,
+                label,
+                expanded,
+                children,
+            } => {
+                let clicked = PanelEvent::TreeItemClicked(id.clone());
+                let toggled = PanelEvent::TreeItemToggled(id.clone());
+                let item = ListItem::new(ElementId::Name(format!("tree-item-{id}").into()))
+                    .indent_level(indent_level)
+                    .indent_step_size(px(12.))
+                    .toggle((!children.is_empty()).then_some(*expanded))
+                    .on_toggle(
+                        cx.listener(move |this, _, _, cx| this.send_event(toggled.clone(), cx)),
+                    )
+                    .on_click(
+                        cx.listener(move |this, _, _, cx| this.send_event(clicked.clone(), cx)),
+                    )
+                    .child(Label::new(label.clone()));
+
+                let mut tree = v_flex().w_full().child(item);
+                if *expanded {
+                    for child in children {
+                        tree = tree.child(self.render_node(
+                            content,
+                            *child,
+                            depth + 1,
+                            indent_level + 1,
+                            window,
+                            cx,
+                        ));
+                    }
+                }
+                tree.into_any_element()
+            }
+            PanelNode::Column(children) => {
+                let mut column = v_flex().w_full().gap_1();
+                for child in children {
+                    column = column.child(self.render_node(
+                        content,
+                        *child,
+                        depth + 1,
+                        indent_level,
+                        window,
+                        cx,
+                    ));
+                }
+                column.into_any_element()
+            }
+            PanelNode::Row(children) => {
+                let mut row = h_flex().w_full().gap_1();
+                for child in children {
+                    row = row.child(self.render_node(
+                        content,
+                        *child,
+                        depth + 1,
+                        indent_level,
+                        window,
+                        cx,
+                    ));
+                }
+                row.into_any_element()
+            }
+            PanelNode::Divider => Divider::horizontal().into_any_element(),
+        }
+    }
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let panels = self.registry.read(cx).panels.clone();
+        h_flex()
+            .w_full()
+            .gap_1()
+            .p_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(panels.into_iter().enumerate().map(|(ix, panel)| {
+                let is_active = self.active_panel_id.as_ref() == Some(&panel.id);
+                Button::new(("extension-panel-tab", ix), panel.title.clone())
+                    .style(ButtonStyle::Subtle)
+                    .label_size(LabelSize::Small)
+                    .toggle_state(is_active)
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.activate_panel(Some(panel.id.clone()), cx)
+                    }))
+            }))
+    }
+}
+
+fn label_color(color: PanelLabelColor) -> Color {
+    match color {
+        PanelLabelColor::Default => Color::Default,
+        PanelLabelColor::Muted => Color::Muted,
+        PanelLabelColor::Accent => Color::Accent,
+        PanelLabelColor::Success => Color::Success,
+        PanelLabelColor::Warning => Color::Warning,
+        PanelLabelColor::Error => Color::Error,
+    }
+}
+
+impl Focusable for ExtensionPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<workspace::dock::PanelEvent> for ExtensionPanel {}
+
+impl Render for ExtensionPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let body = match self.content.clone() {
+            Some(Ok(content)) => self.render_node(&content, content.root, 0, 0, window, cx),
+            Some(Err(error)) => Label::new(error).color(Color::Error).into_any_element(),
+            None => Label::new("Loading…")
+                .color(Color::Muted)
+                .into_any_element(),
+        };
+
+        v_flex()
+            .key_context("ExtensionPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .child(self.render_tabs(cx))
+            .child(
+                v_flex()
+                    .id("extension-panel-content")
+                    .size_full()
+                    .p_2()
+                    .overflow_y_scroll()
+                    .child(body),
+            )
+    }
+}
+
+impl Panel for ExtensionPanel {
+    fn persistent_name() -> &'static str {
+        "ExtensionPanel"
+    }
+
+    fn position(&self, _: &Window, _: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _: &Window, _: &App) -> Pixels {
+        self.width.unwrap_or(px(300.))
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        if self.registry.read(cx).panels.is_empty() {
+            return None;
+        }
+        Some(IconName::Blocks)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Extension Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+
+    fn enabled(&self, cx: &App) -> bool {
+        !self.registry.read(cx).panels.is_empty()
+    }
+}
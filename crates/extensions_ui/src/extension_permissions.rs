@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use extension_host::extension_settings::capability_to_content;
+use extension_host::{ExtensionManifest, ExtensionSettings, ExtensionStore};
+use fs::Fs;
+use gpui::{
+    App, Context, Entity, EventEmitter, FocusHandle, Focusable, PromptLevel, Subscription, Task,
+    Window,
+};
+use settings::Settings as _;
+use ui::{SwitchField, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace, WorkspaceId,
+    item::{Item, ItemEvent},
+};
+
+/// Opens the permissions page of the extension with the given ID, or activates it if it is
+/// already open.
+pub(crate) fn open_extension_permissions(
+    workspace: &mut Workspace,
+    extension_id: Arc<str>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .filter_map(|item| item.downcast::<ExtensionPermissionsPage>())
+        .find(|page| page.read(cx).extension_id == extension_id);
+
+    if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, window, cx);
+    } else {
+        let fs = workspace.app_state().fs.clone();
+        let page = cx.new(|cx| ExtensionPermissionsPage::new(extension_id, fs, cx));
+        workspace.add_item_to_active_pane(Box::new(page), None, true, window, cx);
+    }
+}
+
+/// Asks the user whether to allow an extension that is being installed the capabilities it
+/// declares. Resolves to whether the extension should be installed.
+pub(crate) fn prompt_for_extension_permissions(
+    manifest: Arc<ExtensionManifest>,
+    cx: &mut App,
+) -> Task<bool> {
+    // Without a window to ask in, extensions are installed with the capabilities granted in the
+    // settings, as before they declared their permissions.
+    let Some(window) = cx.active_window().or_else(|| cx.windows().first().copied()) else {
+        return Task::ready(true);
+    };
+    let detail = manifest
+        .capabilities
+        .iter()
+        .map(|capability| format!("• {}", capability.description()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let answer = window.update(cx, |_, window, cx| {
+        window.prompt(
+            PromptLevel::Info,
+            &format!("{} requests the following permissions", manifest.name),
+            Some(&detail),
+            &["Install", "Cancel"],
+            cx,
+        )
+    });
+    let Some(answer) = answer.log_err() else {
+        return Task::ready(false);
+    };
+    cx.spawn(async move |_| answer.await.ok() == Some(0))
+}
+
+/// Shows the capabilities an extension declares, and lets the user revoke them.
+pub struct ExtensionPermissionsPage {
+    extension_id: Arc<str>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ExtensionPermissionsPage {
+    fn new(extension_id: Arc<str>, fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        let _subscriptions = vec![
+            cx.observe(&ExtensionStore::global(cx), |_, _, cx| cx.notify()),
+            cx.observe_global::<settings::SettingsStore>(|_, cx| cx.notify()),
+        ];
+        Self {
+            extension_id,
+            fs,
+            focus_handle: cx.focus_handle(),
+            _subscriptions,
+        }
+    }
+
+    fn set_capability_revoked(
+        &self,
+        capability: extension::ExtensionCapability,
+        revoked: bool,
+        cx: &mut App,
+    ) {
+        let extension_id = self.extension_id.clone();
+        let capability = capability_to_content(capability);
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let revoked_capabilities = &mut settings.extension.revoked_extension_capabilities;
+            let extension_revoked_capabilities = revoked_capabilities
+                .entry(extension_id.clone())
+                .or_default();
+            extension_revoked_capabilities.retain(|revoked| *revoked != capability);
+            if revoked {
+                extension_revoked_capabilities.push(capability);
+            }
+            if extension_revoked_capabilities.is_empty() {
+                revoked_capabilities.remove(&extension_id);
+            }
+        });
+    }
+}
+
+impl EventEmitter<ItemEvent> for ExtensionPermissionsPage {}
+
+impl Focusable for ExtensionPermissionsPage {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for ExtensionPermissionsPage {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        let name = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_manifest_for_id(&self.extension_id)
+            .map_or_else(
+                || self.extension_id.to_string(),
+                |manifest| manifest.name.clone(),
+            );
+        format!("{name} Permissions").into()
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        Some(cx.new(|cx| Self::new(self.extension_id.clone(), self.fs.clone(), cx)))
+    }
+}
+
+impl Render for ExtensionPermissionsPage {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let manifest = ExtensionStore::global(cx)
+            .read(cx)
+            .extension_manifest_for_id(&self.extension_id)
+            .cloned();
+        let revoked_capabilities = ExtensionSettings::get_global(cx)
+            .revoked_capabilities(&self.extension_id)
+            .to_vec();
+
+        let content =
+            match manifest {
+                None => Label::new("This extension isn't installed.")
+                    .color(Color::Muted)
+                    .into_any_element(),
+                Some(manifest) if manifest.capabilities.is_empty() => {
+                    Label::new("This extension doesn't request any permissions.")
+                        .color(Color::Muted)
+                        .into_any_element()
+                }
+                Some(manifest) => {
+                    v_flex()
+                        .gap_2()
+                        .children(manifest.capabilities.iter().enumerate().map(
+                            |(ix, capability)| {
+                                let is_revoked = revoked_capabilities.contains(capability);
+                                SwitchField::new(
+                                    ("capability", ix),
+                                    capability.description(),
+                                    None,
+                                    !is_revoked,
+                                    cx.listener({
+                                        let capability = capability.clone();
+                                        move |this, _, _, cx| {
+                                            this.set_capability_revoked(
+                                                capability.clone(),
+                                                !is_revoked,
+                                                cx,
+                                            )
+                                        }
+                                    }),
+                                )
+                            },
+                        ))
+                        .into_any_element()
+                }
+            };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .bg(cx.theme().colors().editor_background)
+            .child(Headline::new("Permissions").size(HeadlineSize::Medium))
+            .child(
+                Label::new(
+                    "The extension is reloaded when its permissions change. \
+                    Revoked permissions cause the requests that need them to fail.",
+                )
+                .color(Color::Muted),
+            )
+            .child(content)
+    }
+}
@@ -0,0 +1,216 @@
+use std::str::FromStr as _;
+use std::sync::Arc;
+
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionStatusItemProxy, StatusItemContent, StatusItemEvent,
+    StatusItemPosition,
+};
+use extension_host::ExtensionSettings;
+use gpui::{App, Context, Corner, Entity, Global, Subscription, Window};
+use settings::Settings as _;
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{ItemHandle, StatusItemView};
+
+pub(crate) fn init(cx: &mut App) {
+    let registry = cx.new(|_| ExtensionStatusItemRegistry::default());
+    cx.set_global(GlobalExtensionStatusItemRegistry(registry.clone()));
+    ExtensionHostProxy::default_global(cx)
+        .register_status_item_proxy(ExtensionStatusItemRegistryProxy { registry });
+}
+
+struct RegisteredStatusItem {
+    extension: Arc<dyn Extension>,
+    id: Arc<str>,
+    position: StatusItemPosition,
+    /// The content set by the extension, if the item is shown.
+    content: Option<StatusItemContent>,
+}
+
+/// The status bar items provided by the installed extensions.
+#[derive(Default)]
+struct ExtensionStatusItemRegistry {
+    items: Vec<RegisteredStatusItem>,
+}
+
+struct GlobalExtensionStatusItemRegistry(Entity<ExtensionStatusItemRegistry>);
+
+impl Global for GlobalExtensionStatusItemRegistry {}
+
+struct ExtensionStatusItemRegistryProxy {
+    registry: Entity<ExtensionStatusItemRegistry>,
+}
+
+impl ExtensionStatusItemProxy for ExtensionStatusItemRegistryProxy {
+    fn register_status_item(
+        &self,
+        extension: Arc<dyn Extension>,
+        item_id: Arc<str>,
+        position: StatusItemPosition,
+        cx: &mut App,
+    ) {
+        self.registry.update(cx, |registry, cx| {
+            registry.items.retain(|item| item.id != item_id);
+            registry.items.push(RegisteredStatusItem {
+                extension,
+                id: item_id,
+                position,
+                content: None,
+            });
+            cx.notify();
+        });
+    }
+
+    fn unregister_status_item(&self, item_id: Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |registry, cx| {
+            registry.items.retain(|item| item.id != item_id);
+            cx.notify();
+        });
+    }
+
+    fn update_status_item(
+        &self,
+        item_id: Arc<str>,
+        content: Option<StatusItemContent>,
+        cx: &mut App,
+    ) {
+        self.registry.update(cx, |registry, cx| {
+            let Some(item) = registry.items.iter_mut().find(|item| item.id == item_id) else {
+                log::warn!("status item {item_id} isn't declared in its extension's manifest");
+                return;
+            };
+            item.content = content;
+            cx.notify();
+        });
+    }
+}
+
+/// Shows the status bar items provided by extensions on one side of the status bar.
+pub struct ExtensionStatusItems {
+    position: StatusItemPosition,
+    registry: Entity<ExtensionStatusItemRegistry>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ExtensionStatusItems {
+    pub fn new(position: StatusItemPosition, cx: &mut Context<Self>) -> Self {
+        let registry = cx.global::<GlobalExtensionStatusItemRegistry>().0.clone();
+        let _subscriptions = vec![
+            cx.observe(&registry, |_, _, cx| cx.notify()),
+            cx.observe_global::<settings::SettingsStore>(|_, cx| cx.notify()),
+        ];
+        Self {
+            position,
+            registry,
+            _subscriptions,
+        }
+    }
+}
+
+fn send_event(extension: Arc<dyn Extension>, item_id: Arc<str>, event: StatusItemEvent, cx: &App) {
+    cx.background_spawn(async move {
+        extension
+            .handle_status_item_event(item_id, event)
+            .await
+            .log_err();
+    })
+    .detach();
+}
+
+impl Render for ExtensionStatusItems {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = ExtensionSettings::get_global(cx);
+        let mut items = self
+            .registry
+            .read(cx)
+            .items
+            .iter()
+            .filter(|item| item.position == self.position)
+            .filter(|item| !settings.hidden_status_items.contains(&item.id))
+            .filter_map(|item| {
+                Some((
+                    item.extension.clone(),
+                    item.id.clone(),
+                    item.content.clone()?,
+                ))
+            })
+            .collect::<Vec<_>>();
+        items.sort_by_key(|(_, id, _)| {
+            settings
+                .status_item_order
+                .iter()
+                .position(|ordered_id| ordered_id == id)
+                .unwrap_or(usize::MAX)
+        });
+
+        h_flex()
+            .gap_1()
+            .children(items.into_iter().map(|(extension, id, content)| {
+                let icon = content.icon.as_deref().and_then(|icon| {
+                    IconName::from_str(icon)
+                        .map_err(|_| log::warn!("unknown icon {icon:?} for status item {id}"))
+                        .ok()
+                });
+                let tooltip = content.tooltip.clone();
+                let button = Button::new(SharedString::from(format!("{id}-button")), content.text)
+                    .label_size(LabelSize::Small)
+                    .when_some(icon, |button, icon| {
+                        button
+                            .icon(icon)
+                            .icon_size(IconSize::Small)
+                            .icon_position(IconPosition::Start)
+                    })
+                    .when_some(tooltip, |button, tooltip| {
+                        button.tooltip(Tooltip::text(tooltip))
+                    });
+
+                if content.menu.is_empty() {
+                    button
+                        .on_click(move |_, _, cx| {
+                            send_event(extension.clone(), id.clone(), StatusItemEvent::Clicked, cx)
+                        })
+                        .into_any_element()
+                } else {
+                    let menu = content.menu;
+                    PopoverMenu::new(SharedString::from(format!("{id}-menu")))
+                        .trigger(button)
+                        .anchor(Corner::BottomLeft)
+                        .menu(move |window, cx| {
+                            Some(ContextMenu::build(window, cx, |mut context_menu, _, _| {
+                                for entry in &menu {
+                                    let extension = extension.clone();
+                                    let item_id = id.clone();
+                                    let entry_id = entry.id.clone();
+                                    context_menu = context_menu.entry(
+                                        entry.label.clone(),
+                                        None,
+                                        move |_, cx| {
+                                            send_event(
+                                                extension.clone(),
+                                                item_id.clone(),
+                                                StatusItemEvent::MenuEntrySelected(
+                                                    entry_id.clone(),
+                                                ),
+                                                cx,
+                                            )
+                                        },
+                                    );
+                                }
+                                context_menu
+                            }))
+                        })
+                        .into_any_element()
+                }
+            }))
+    }
+}
+
+impl StatusItemView for ExtensionStatusItems {
+    fn set_active_pane_item(
+        &mut self,
+        _: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+}
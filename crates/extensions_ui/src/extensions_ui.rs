@@ -1,4 +1,9 @@
 mod components;
+mod extension_file_viewer;
+mod extension_log;
+mod extension_panel;
+mod extension_permissions;
+mod extension_status_items;
 mod extension_suggest;
 mod extension_version_selector;
 
@@ -10,7 +15,7 @@ use anyhow::Context as _;
 use client::{ExtensionMetadata, ExtensionProvides};
 use collections::{BTreeMap, BTreeSet};
 use editor::{Editor, EditorElement, EditorStyle};
-use extension_host::{ExtensionManifest, ExtensionOperation, ExtensionStore};
+use extension_host::{ExtensionManifest, ExtensionOperation, ExtensionSettings, ExtensionStore};
 use fuzzy::{StringMatchCandidate, match_strings};
 use gpui::{
     Action, App, ClipboardItem, Context, Entity, EventEmitter, Flatten, Focusable,
@@ -24,16 +29,21 @@ use settings::{Settings, SettingsContent};
 use strum::IntoEnumIterator as _;
 use theme::ThemeSettings;
 use ui::{
-    CheckboxWithLabel, Chip, ContextMenu, PopoverMenu, ScrollableHandle, ToggleButton, Tooltip,
-    WithScrollbar, prelude::*,
+    CheckboxWithLabel, Chip, ContextMenu, DropdownMenu, PopoverMenu, ScrollableHandle,
+    ToggleButton, Tooltip, WithScrollbar, prelude::*,
 };
 use vim_mode_setting::VimModeSetting;
 use workspace::{
     Workspace, WorkspaceId,
     item::{Item, ItemEvent},
+    notifications::{
+        NotificationId, show_app_notification, simple_message_notification::MessageNotification,
+    },
 };
 use zed_actions::ExtensionCategoryFilter;
 
+pub use crate::extension_status_items::ExtensionStatusItems;
+
 use crate::components::{ExtensionCard, FeatureUpsell};
 use crate::extension_version_selector::{
     ExtensionVersionSelector, ExtensionVersionSelectorDelegate,
@@ -48,6 +58,26 @@ actions!(
 );
 
 pub fn init(cx: &mut App) {
+    extension_file_viewer::init(cx);
+    extension_panel::init(cx);
+    extension_status_items::init(cx);
+
+    if let Some(store) = ExtensionStore::try_global(cx) {
+        store.update(cx, |store, _| {
+            store.set_permissions_prompt(extension_permissions::prompt_for_extension_permissions)
+        });
+        cx.subscribe(&store, |_, event, cx| {
+            if let extension_host::Event::ExtensionPanicked {
+                extension_id,
+                message,
+            } = event
+            {
+                show_extension_panic_notification(extension_id.clone(), message.clone(), cx);
+            }
+        })
+        .detach();
+    }
+
     cx.observe_new(move |workspace: &mut Workspace, window, cx| {
         let Some(window) = window else {
             return;
@@ -181,6 +211,39 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+struct ExtensionPanicNotification;
+
+fn show_extension_panic_notification(extension_id: Arc<str>, message: String, cx: &mut App) {
+    show_app_notification(
+        NotificationId::composite::<ExtensionPanicNotification>(SharedString::from(
+            extension_id.clone(),
+        )),
+        cx,
+        move |cx| {
+            let workspace = cx.entity().downgrade();
+            let extension_id = extension_id.clone();
+            cx.new(|cx| {
+                MessageNotification::new(message.clone(), cx)
+                    .with_title(format!("Extension {extension_id} panicked"))
+                    .primary_message("Open Log")
+                    .primary_icon(IconName::FileTextOutlined)
+                    .primary_on_click(move |window, cx| {
+                        workspace
+                            .update(cx, |workspace, cx| {
+                                extension_log::open_extension_log(
+                                    workspace,
+                                    extension_id.clone(),
+                                    window,
+                                    cx,
+                                )
+                            })
+                            .ok();
+                    })
+            })
+        },
+    );
+}
+
 fn extension_provides_label(provides: ExtensionProvides) -> &'static str {
     match provides {
         ExtensionProvides::Themes => "Themes",
@@ -210,17 +273,50 @@ enum ExtensionFilter {
     All,
     Installed,
     NotInstalled,
+    UpdatesAvailable,
 }
 
 impl ExtensionFilter {
     pub fn include_dev_extensions(&self) -> bool {
         match self {
             Self::All | Self::Installed => true,
-            Self::NotInstalled => false,
+            Self::NotInstalled | Self::UpdatesAvailable => false,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, strum::EnumIter)]
+enum ExtensionSort {
+    /// The order in which the extensions were returned by the API.
+    #[default]
+    Relevance,
+    MostDownloads,
+    RecentlyUpdated,
+}
+
+impl ExtensionSort {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Relevance => "Relevance",
+            Self::MostDownloads => "Most Downloads",
+            Self::RecentlyUpdated => "Recently Updated",
         }
     }
 }
 
+/// Returns whether `latest_version` is newer than `installed_version`.
+///
+/// Versions that aren't valid semantic versions are never considered to be updates.
+fn is_newer_version(installed_version: &str, latest_version: &str) -> bool {
+    match (
+        semver::Version::parse(installed_version),
+        semver::Version::parse(latest_version),
+    ) {
+        (Ok(installed_version), Ok(latest_version)) => latest_version > installed_version,
+        _ => false,
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 enum Feature {
     Git,
@@ -281,6 +377,7 @@ pub struct ExtensionsPage {
     list: UniformListScrollHandle,
     is_fetching_extensions: bool,
     filter: ExtensionFilter,
+    sort: ExtensionSort,
     remote_extension_entries: Vec<ExtensionMetadata>,
     dev_extension_entries: Vec<Arc<ExtensionManifest>>,
     filtered_remote_extension_indices: Vec<usize>,
@@ -341,6 +438,7 @@ impl ExtensionsPage {
                 list: scroll_handle,
                 is_fetching_extensions: false,
                 filter: ExtensionFilter::All,
+                sort: ExtensionSort::default(),
                 dev_extension_entries: Vec::new(),
                 filtered_remote_extension_indices: Vec::new(),
                 remote_extension_entries: Vec::new(),
@@ -447,9 +545,28 @@ impl ExtensionsPage {
 
                         matches!(status, ExtensionStatus::NotInstalled)
                     }
+                    ExtensionFilter::UpdatesAvailable => {
+                        let status = Self::extension_status(&extension.id, cx);
+                        match status {
+                            ExtensionStatus::Installed(installed_version) => {
+                                is_newer_version(&installed_version, &extension.manifest.version)
+                            }
+                            _ => false,
+                        }
+                    }
                 })
                 .map(|(ix, _)| ix),
         );
+        let entries = &self.remote_extension_entries;
+        match self.sort {
+            ExtensionSort::Relevance => {}
+            ExtensionSort::MostDownloads => self
+                .filtered_remote_extension_indices
+                .sort_by_key(|ix| std::cmp::Reverse(entries[*ix].download_count)),
+            ExtensionSort::RecentlyUpdated => self
+                .filtered_remote_extension_indices
+                .sort_by_key(|ix| std::cmp::Reverse(entries[*ix].published_at)),
+        }
         cx.notify();
     }
 
@@ -591,6 +708,19 @@ impl ExtensionsPage {
                         h_flex()
                             .gap_1()
                             .justify_between()
+                            .child(
+                                Button::new(
+                                    SharedString::from(format!("log-{}", extension.id)),
+                                    "Log",
+                                )
+                                .color(Color::Accent)
+                                .on_click(cx.listener({
+                                    let extension_id = extension.id.clone();
+                                    move |this, _, window, cx| {
+                                        this.open_extension_log(extension_id.clone(), window, cx)
+                                    }
+                                })),
+                            )
                             .child(
                                 Button::new(
                                     SharedString::from(format!("rebuild-{}", extension.id)),
@@ -716,6 +846,13 @@ impl ExtensionsPage {
             ExtensionStatus::Installed(installed_version) => Some(installed_version),
             _ => None,
         };
+        let is_installed = installed_version.is_some();
+        let has_update = installed_version
+            .as_ref()
+            .is_some_and(|installed_version| is_newer_version(installed_version, &version));
+        let changelog_url = extension.manifest.changelog.clone().filter(|_| has_update);
+        let is_pinned =
+            is_installed && !ExtensionSettings::get_global(cx).should_auto_update(&extension.id);
 
         ExtensionCard::new()
             .overridden_by_dev_extension(has_dev_extension)
@@ -738,6 +875,7 @@ impl ExtensionsPage {
                                             .size(HeadlineSize::XSmall)
                                     }),
                             )
+                            .when(is_pinned, |parent| parent.child(Chip::new("Pinned")))
                             .map(|parent| {
                                 if extension.manifest.provides.is_empty() {
                                     return parent;
@@ -812,6 +950,20 @@ impl ExtensionsPage {
                     .child(
                         h_flex()
                             .gap_2()
+                            .when_some(changelog_url, |parent, changelog_url| {
+                                parent.child(
+                                    IconButton::new(
+                                        SharedString::from(format!("changelog-{}", extension.id)),
+                                        IconName::FileTextOutlined,
+                                    )
+                                    .icon_color(Color::Accent)
+                                    .icon_size(IconSize::Small)
+                                    .on_click(cx.listener(move |_, _, _, cx| {
+                                        cx.open_url(&changelog_url);
+                                    }))
+                                    .tooltip(Tooltip::text("View Changelog")),
+                                )
+                            })
                             .child(
                                 IconButton::new(
                                     SharedString::from(format!("repository-{}", extension.id)),
@@ -845,6 +997,7 @@ impl ExtensionsPage {
                                         &this,
                                         extension_id.clone(),
                                         authors.clone(),
+                                        is_installed.then_some(is_pinned),
                                         window,
                                         cx,
                                     ))
@@ -858,6 +1011,7 @@ impl ExtensionsPage {
         this: &Entity<Self>,
         extension_id: Arc<str>,
         authors: Vec<String>,
+        is_pinned: Option<bool>,
         window: &mut Window,
         cx: &mut App,
     ) -> Entity<ContextMenu> {
@@ -873,6 +1027,49 @@ impl ExtensionsPage {
                         }
                     }),
                 )
+                .when_some(is_pinned, |context_menu, is_pinned| {
+                    context_menu.entry(
+                        if is_pinned {
+                            "Unpin Version"
+                        } else {
+                            "Pin Installed Version"
+                        },
+                        None,
+                        window.handler_for(this, {
+                            let extension_id = extension_id.clone();
+                            move |this, _, cx| {
+                                this.set_extension_pinned(extension_id.clone(), !is_pinned, cx)
+                            }
+                        }),
+                    )
+                })
+                .when(is_pinned.is_some(), |context_menu| {
+                    context_menu
+                        .entry(
+                            "Permissions",
+                            None,
+                            window.handler_for(this, {
+                                let extension_id = extension_id.clone();
+                                move |this, window, cx| {
+                                    this.open_extension_permissions(
+                                        extension_id.clone(),
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            }),
+                        )
+                        .entry(
+                            "Open Log",
+                            None,
+                            window.handler_for(this, {
+                                let extension_id = extension_id.clone();
+                                move |this, window, cx| {
+                                    this.open_extension_log(extension_id.clone(), window, cx)
+                                }
+                            }),
+                        )
+                })
                 .entry("Copy Extension ID", None, {
                     let extension_id = extension_id.clone();
                     move |_, cx| {
@@ -888,6 +1085,59 @@ impl ExtensionsPage {
         })
     }
 
+    fn open_extension_log(
+        &mut self,
+        extension_id: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                extension_log::open_extension_log(workspace, extension_id, window, cx)
+            })
+            .ok();
+    }
+
+    fn open_extension_permissions(
+        &mut self,
+        extension_id: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                extension_permissions::open_extension_permissions(
+                    workspace,
+                    extension_id,
+                    window,
+                    cx,
+                )
+            })
+            .ok();
+    }
+
+    /// Pins an installed extension to its current version, so it isn't updated automatically,
+    /// or unpins it.
+    fn set_extension_pinned(
+        &mut self,
+        extension_id: Arc<str>,
+        pinned: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let fs = workspace.read(cx).app_state().fs.clone();
+        settings::update_settings_file(fs, cx, move |settings, _| {
+            let auto_update_extensions = &mut settings.extension.auto_update_extensions;
+            if pinned {
+                auto_update_extensions.insert(extension_id, false);
+            } else {
+                auto_update_extensions.remove(&extension_id);
+            }
+        });
+    }
+
     fn show_extension_version_list(
         &mut self,
         extension_id: Arc<str>,
@@ -1263,12 +1513,44 @@ impl ExtensionsPage {
                         "No not installed extensions."
                     }
                 }
+                ExtensionFilter::UpdatesAvailable => {
+                    if has_search {
+                        "No extensions with updates available that match your search."
+                    } else {
+                        "All installed extensions are up to date."
+                    }
+                }
             }
         };
 
         Label::new(message)
     }
 
+    fn render_sort_menu(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let this = cx.entity();
+        let current_sort = self.sort;
+        DropdownMenu::new(
+            "sort-extensions",
+            format!("Sort: {}", self.sort.label()),
+            ContextMenu::build(window, cx, |mut menu, window, _| {
+                for sort in ExtensionSort::iter() {
+                    menu = menu.toggleable_entry(
+                        sort.label(),
+                        current_sort == sort,
+                        IconPosition::Start,
+                        None,
+                        window.handler_for(&this, move |this, _, cx| {
+                            this.sort = sort;
+                            this.filter_extension_entries(cx);
+                            this.scroll_to_top(cx);
+                        }),
+                    );
+                }
+                menu
+            }),
+        )
+    }
+
     fn update_settings(
         &mut self,
         selection: &ToggleState,
@@ -1449,9 +1731,30 @@ impl Render for ExtensionsPage {
                                             .tooltip(move |_, cx| {
                                                 Tooltip::simple("Show not installed extensions", cx)
                                             })
+                                            .middle(),
+                                    )
+                                    .child(
+                                        ToggleButton::new("filter-updates-available", "Updates")
+                                            .style(ButtonStyle::Filled)
+                                            .size(ButtonSize::Large)
+                                            .toggle_state(
+                                                self.filter == ExtensionFilter::UpdatesAvailable,
+                                            )
+                                            .on_click(cx.listener(|this, _event, _, cx| {
+                                                this.filter = ExtensionFilter::UpdatesAvailable;
+                                                this.filter_extension_entries(cx);
+                                                this.scroll_to_top(cx);
+                                            }))
+                                            .tooltip(move |_, cx| {
+                                                Tooltip::simple(
+                                                    "Show installed extensions with updates available",
+                                                    cx,
+                                                )
+                                            })
                                             .last(),
                                     ),
-                            ),
+                            )
+                            .child(self.render_sort_menu(window, cx)),
                     ),
             )
             .child(
@@ -1575,3 +1878,19 @@ impl Item for ExtensionsPage {
         f(*event)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("0.1.0", "0.2.0"));
+        assert!(is_newer_version("0.9.0", "0.10.0"));
+        assert!(is_newer_version("1.0.0-beta.1", "1.0.0"));
+        assert!(!is_newer_version("0.2.0", "0.2.0"));
+        assert!(!is_newer_version("0.10.0", "0.9.0"));
+        assert!(!is_newer_version("0.2.0", "not-a-version"));
+        assert!(!is_newer_version("not-a-version", "0.2.0"));
+    }
+}
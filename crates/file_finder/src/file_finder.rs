@@ -6,14 +6,18 @@ mod open_path_prompt_tests;
 pub mod file_finder_settings;
 mod open_path_prompt;
 
-use futures::future::join_all;
+use futures::{
+    StreamExt as _,
+    channel::mpsc,
+    future::{self, join_all},
+};
 pub use open_path_prompt::OpenPathDelegate;
 
 use collections::HashMap;
 use editor::Editor;
 use file_finder_settings::{FileFinderSettings, FileFinderWidth};
 use file_icons::FileIcons;
-use fuzzy::{CharBag, PathMatch, PathMatchCandidate};
+use fuzzy::{CharBag, PathMatch, PathMatchCache, PathMatchCandidate};
 use gpui::{
     Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
     KeyContext, Modifiers, ModifiersChangedEvent, ParentElement, Render, Styled, Task, WeakEntity,
@@ -274,6 +278,7 @@ impl FileFinder {
                 Some(false) => Some(true),
                 None => Some(true),
             };
+            picker.delegate.path_match_cache = None;
             picker.delegate.include_ignored_refresh =
                 picker.delegate.update_matches(picker.query(cx), window, cx);
         });
@@ -414,6 +419,8 @@ pub struct FileFinderDelegate {
     focus_handle: FocusHandle,
     include_ignored: Option<bool>,
     include_ignored_refresh: Task<()>,
+    /// The candidates that matched the last search, reused when the query is extended.
+    path_match_cache: Option<PathMatchCache>,
 }
 
 /// Use a custom ordering for file finder: the regular one
@@ -832,6 +839,7 @@ impl FileFinderDelegate {
             focus_handle: cx.focus_handle(),
             include_ignored: FileFinderSettings::get_global(cx).include_ignored,
             include_ignored_refresh: Task::ready(()),
+            path_match_cache: None,
         }
     }
 
@@ -844,9 +852,12 @@ impl FileFinderDelegate {
             match event {
                 project::Event::WorktreeUpdatedEntries(_, _)
                 | project::Event::WorktreeAdded(_)
-                | project::Event::WorktreeRemoved(_) => file_finder
-                    .picker
-                    .update(cx, |picker, cx| picker.refresh(window, cx)),
+                | project::Event::WorktreeRemoved(_) => {
+                    file_finder.picker.update(cx, |picker, cx| {
+                        picker.delegate.path_match_cache = None;
+                        picker.refresh(window, cx)
+                    })
+                }
                 _ => {}
             };
         })
@@ -888,22 +899,47 @@ impl FileFinderDelegate {
         self.cancel_flag.store(true, atomic::Ordering::Release);
         self.cancel_flag = Arc::new(AtomicBool::new(false));
         let cancel_flag = self.cancel_flag.clone();
+        let path_match_cache = self.path_match_cache.clone();
         cx.spawn_in(window, async move |picker, cx| {
-            let matches = fuzzy::match_path_sets(
+            // Show the best matches found so far while searching large projects.
+            let (progress_tx, mut progress_rx) = mpsc::unbounded();
+            let search = fuzzy::match_path_sets_incremental(
                 candidate_sets.as_slice(),
                 query.path_query(),
                 &relative_to,
                 false,
                 100,
+                path_match_cache.as_ref(),
                 &cancel_flag,
                 cx.background_executor().clone(),
-            )
-            .await
-            .into_iter()
-            .map(ProjectPanelOrdMatch);
+                move |matches| {
+                    progress_tx.unbounded_send(matches).ok();
+                },
+            );
+            let report_progress = async {
+                while let Some(matches) = progress_rx.next().await {
+                    picker
+                        .update(cx, |picker, cx| {
+                            picker.delegate.set_search_matches(
+                                search_id,
+                                false,
+                                query.clone(),
+                                matches.into_iter().map(ProjectPanelOrdMatch),
+                                cx,
+                            )
+                        })
+                        .log_err();
+                }
+            };
+            let ((matches, path_match_cache), ()) = future::join(search, report_progress).await;
+            let matches = matches.into_iter().map(ProjectPanelOrdMatch);
             let did_cancel = cancel_flag.load(atomic::Ordering::Acquire);
             picker
                 .update(cx, |picker, cx| {
+                    // Searches are only cached when they weren't cancelled by a newer one.
+                    if path_match_cache.is_some() {
+                        picker.delegate.path_match_cache = path_match_cache;
+                    }
                     picker
                         .delegate
                         .set_search_matches(search_id, did_cancel, query, matches, cx)
@@ -108,6 +108,11 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn load_committed_bytes(&self, path: RepoPath) -> BoxFuture<'_, Option<Vec<u8>>> {
+        let load_text = self.load_committed_text(path);
+        async move { Some(load_text.await?.into_bytes()) }.boxed()
+    }
+
     fn load_commit(
         &self,
         _commit: String,
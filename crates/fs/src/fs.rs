@@ -4,6 +4,10 @@ mod mac_watcher;
 #[cfg(not(target_os = "macos"))]
 pub mod fs_watcher;
 
+mod sftp_fs;
+
+pub use sftp_fs::SftpFs;
+
 use anyhow::{Context as _, Result, anyhow};
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use ashpd::desktop::trash;
@@ -145,6 +149,12 @@ pub trait Fs: Send + Sync {
     fn is_fake(&self) -> bool;
     async fn is_case_sensitive(&self) -> Result<bool>;
 
+    /// The host the files are transferred from, when they can only be reached with a file
+    /// transfer protocol such as SFTP. Language servers, tasks and terminals can't run on it.
+    fn file_transfer_host(&self) -> Option<String> {
+        None
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     fn as_fake(&self) -> Arc<FakeFs> {
         panic!("called as_fake on a real fs");
@@ -0,0 +1,868 @@
+//! An [`Fs`] for hosts that can't run the Zed remote server, transferring files with the system
+//! `sftp` client and polling for changes.
+
+use std::{
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    process::Stdio,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context as _, Result, bail};
+use async_tar::Archive;
+use collections::HashMap;
+use futures::{AsyncRead, AsyncReadExt as _, Stream, StreamExt as _};
+use git::repository::GitRepository;
+use parking_lot::Mutex;
+use rope::Rope;
+use smol::io::AsyncWriteExt as _;
+use tempfile::TempDir;
+use text::LineEnding;
+use util::{ResultExt as _, command::new_smol_command};
+
+use crate::{
+    CopyOptions, CreateOptions, FileHandle, Fs, MTime, Metadata, PathEvent, PathEventKind,
+    RemoveOptions, RenameOptions, Watcher, chunks,
+};
+
+/// How often the watched directories are listed again to detect changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+pub struct SftpFs {
+    state: Arc<SftpState>,
+}
+
+struct SftpState {
+    host: String,
+    /// `[user@]host`, as passed to `sftp`.
+    destination: String,
+    port: Option<u16>,
+    args: Vec<String>,
+    /// Holds the local copies of transferred files and the connection's control socket.
+    temp_dir: TempDir,
+    next_transfer_id: AtomicUsize,
+    /// The directories listed so far, polled for changes when they are watched.
+    listings: Mutex<HashMap<PathBuf, Listing>>,
+}
+
+struct Listing {
+    entries: Vec<SftpEntry>,
+    /// When the entries were listed, or `None` when they changed since.
+    listed_at: Option<Instant>,
+    /// The entries as last returned by `read_dir` or reported by a poll, which the next poll
+    /// compares against.
+    reported_entries: Vec<SftpEntry>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum SftpEntryKind {
+    File,
+    Dir,
+    Symlink,
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SftpEntry {
+    name: String,
+    kind: SftpEntryKind,
+    len: u64,
+    mtime: MTime,
+}
+
+impl SftpFs {
+    pub fn new(
+        host: String,
+        username: Option<String>,
+        port: Option<u16>,
+        args: Vec<String>,
+    ) -> Result<Self> {
+        let destination = match username {
+            Some(username) => format!("{username}@{host}"),
+            None => host.clone(),
+        };
+        Ok(Self {
+            state: Arc::new(SftpState {
+                host,
+                destination,
+                port,
+                args,
+                temp_dir: tempfile::Builder::new()
+                    .prefix("zed-sftp")
+                    .tempdir()
+                    .context("creating a directory for sftp transfers")?,
+                next_transfer_id: AtomicUsize::new(0),
+                listings: Mutex::default(),
+            }),
+        })
+    }
+
+    async fn ensure_missing_or_replaceable(
+        &self,
+        path: &Path,
+        overwrite: bool,
+        ignore_if_exists: bool,
+    ) -> Result<bool> {
+        if self.metadata(path).await?.is_none() {
+            return Ok(true);
+        }
+        if ignore_if_exists {
+            return Ok(false);
+        }
+        if !overwrite {
+            bail!("{path:?} already exists");
+        }
+        Ok(true)
+    }
+}
+
+impl SftpState {
+    /// Runs `sftp` in batch mode and returns the output of each command.
+    async fn run(&self, commands: &[String]) -> Result<Vec<String>> {
+        let mut command = new_smol_command("sftp");
+        command.args(["-b", "-"]);
+        if let Some(port) = self.port {
+            command.arg("-P").arg(port.to_string());
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            command
+                .args(["-o", "ControlMaster=auto", "-o", "ControlPersist=60"])
+                .arg("-o")
+                .arg(format!(
+                    "ControlPath={}",
+                    self.temp_dir.path().join("control").display()
+                ));
+        }
+        command
+            .args(&self.args)
+            .arg(&self.destination)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let mut child = command.spawn().context("spawning sftp")?;
+        let mut stdin = child.stdin.take().context("missing sftp stdin")?;
+        let mut input = commands.join("\n");
+        input.push('\n');
+        stdin.write_all(input.as_bytes()).await?;
+        drop(stdin);
+
+        let output = child.output().await?;
+        if !output.status.success() {
+            bail!(
+                "sftp to {} failed: {}",
+                self.destination,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(split_command_output(
+            &String::from_utf8_lossy(&output.stdout),
+            commands.len(),
+        ))
+    }
+
+    fn transfer_path(&self) -> PathBuf {
+        let id = self.next_transfer_id.fetch_add(1, Ordering::SeqCst);
+        self.temp_dir.path().join(format!("transfer-{id}"))
+    }
+
+    /// Lists a directory. Unless the entries are `reported`, the changes since the directory was
+    /// last read are still reported by the next poll.
+    async fn list(&self, dir: &Path, reported: bool) -> Result<Vec<SftpEntry>> {
+        let output = self
+            .run(&[format!("ls -lan {}", quote_remote(dir))])
+            .await?;
+        let entries = output
+            .first()
+            .and_then(|output| parse_listing(output))
+            .unwrap_or_default();
+        let mut listings = self.listings.lock();
+        let listing = listings
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| Listing {
+                entries: Vec::new(),
+                listed_at: None,
+                reported_entries: entries.clone(),
+            });
+        listing.entries = entries.clone();
+        listing.listed_at = Some(Instant::now());
+        if reported {
+            listing.reported_entries = entries.clone();
+        }
+        Ok(entries)
+    }
+
+    /// Lists a directory, reusing a listing made within the last poll interval.
+    async fn cached_list(&self, dir: &Path) -> Result<Vec<SftpEntry>> {
+        if let Some(listing) = self.listings.lock().get(dir)
+            && listing
+                .listed_at
+                .is_some_and(|listed_at| listed_at.elapsed() < POLL_INTERVAL)
+        {
+            return Ok(listing.entries.clone());
+        }
+        self.list(dir, false).await
+    }
+
+    /// Marks the listing of the directory containing `path` as outdated, keeping its entries to
+    /// report the change on the next poll.
+    fn invalidate_parent(&self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && let Some(listing) = self.listings.lock().get_mut(parent)
+        {
+            listing.listed_at = None;
+        }
+    }
+
+    /// Lists the listed directories inside `roots` again, and returns the changes since they
+    /// were last listed.
+    async fn poll(&self, roots: &[PathBuf]) -> Vec<PathEvent> {
+        let dirs = self
+            .listings
+            .lock()
+            .keys()
+            .filter(|dir| roots.iter().any(|root| dir.starts_with(root)))
+            .cloned()
+            .collect::<Vec<_>>();
+        if dirs.is_empty() {
+            return Vec::new();
+        }
+        let commands = dirs
+            .iter()
+            .map(|dir| format!("-ls -lan {}", quote_remote(dir)))
+            .collect::<Vec<_>>();
+        let Some(outputs) = self.run(&commands).await.log_err() else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        let mut listings = self.listings.lock();
+        for (dir, output) in dirs.into_iter().zip(outputs) {
+            let entries = parse_listing(&output);
+            let Some(listing) = listings.get_mut(&dir) else {
+                continue;
+            };
+            events.extend(diff_listings(
+                &dir,
+                &listing.reported_entries,
+                entries.as_deref().unwrap_or_default(),
+            ));
+            match entries {
+                Some(entries) => {
+                    listing.entries = entries.clone();
+                    listing.listed_at = Some(Instant::now());
+                    listing.reported_entries = entries;
+                }
+                None => {
+                    listings.remove(&dir);
+                }
+            }
+        }
+        events
+    }
+}
+
+struct SftpWatcher {
+    roots: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl Watcher for SftpWatcher {
+    fn add(&self, path: &Path) -> Result<()> {
+        let mut roots = self.roots.lock();
+        if !roots.iter().any(|root| root == path) {
+            roots.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    fn remove(&self, path: &Path) -> Result<()> {
+        self.roots.lock().retain(|root| root != path);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Fs for SftpFs {
+    async fn create_dir(&self, path: &Path) -> Result<()> {
+        if self.is_dir(path).await {
+            return Ok(());
+        }
+        let mut ancestors = path
+            .ancestors()
+            .filter(|ancestor| ancestor.parent().is_some())
+            .collect::<Vec<_>>();
+        ancestors.reverse();
+        let commands = ancestors
+            .iter()
+            .map(|ancestor| format!("-mkdir {}", quote_remote(ancestor)))
+            .collect::<Vec<_>>();
+        self.state.run(&commands).await?;
+        for ancestor in ancestors {
+            self.state.invalidate_parent(ancestor);
+        }
+        anyhow::ensure!(self.is_dir(path).await, "failed to create {path:?}");
+        Ok(())
+    }
+
+    async fn create_symlink(&self, path: &Path, target: PathBuf) -> Result<()> {
+        self.state
+            .run(&[format!(
+                "ln -s {} {}",
+                quote_remote(&target),
+                quote_remote(path)
+            )])
+            .await?;
+        self.state.invalidate_parent(path);
+        Ok(())
+    }
+
+    async fn create_file(&self, path: &Path, options: CreateOptions) -> Result<()> {
+        if self
+            .ensure_missing_or_replaceable(path, options.overwrite, options.ignore_if_exists)
+            .await?
+        {
+            self.write(path, &[]).await?;
+        }
+        Ok(())
+    }
+
+    async fn create_file_with(
+        &self,
+        path: &Path,
+        mut content: Pin<&mut (dyn AsyncRead + Send)>,
+    ) -> Result<()> {
+        let mut bytes = Vec::new();
+        content.read_to_end(&mut bytes).await?;
+        self.write(path, &bytes).await
+    }
+
+    async fn extract_tar_file(
+        &self,
+        _path: &Path,
+        _content: Archive<Pin<&mut (dyn AsyncRead + Send)>>,
+    ) -> Result<()> {
+        bail!("extracting archives isn't supported over SFTP")
+    }
+
+    async fn copy_file(&self, source: &Path, target: &Path, options: CopyOptions) -> Result<()> {
+        if self
+            .ensure_missing_or_replaceable(target, options.overwrite, options.ignore_if_exists)
+            .await?
+        {
+            let content = self.load_bytes(source).await?;
+            self.write(target, &content).await?;
+        }
+        Ok(())
+    }
+
+    async fn rename(&self, source: &Path, target: &Path, options: RenameOptions) -> Result<()> {
+        let mut commands = Vec::new();
+        match self.metadata(target).await? {
+            Some(_) if options.ignore_if_exists => return Ok(()),
+            Some(_) if !options.overwrite => bail!("{target:?} already exists"),
+            Some(metadata) if !metadata.is_dir => {
+                commands.push(format!("rm {}", quote_remote(target)));
+            }
+            _ => {}
+        }
+        commands.push(format!(
+            "rename {} {}",
+            quote_remote(source),
+            quote_remote(target)
+        ));
+        self.state.run(&commands).await?;
+        self.state.invalidate_parent(source);
+        self.state.invalidate_parent(target);
+        Ok(())
+    }
+
+    async fn remove_dir(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        if self.metadata(path).await?.is_none() {
+            if options.ignore_if_not_exists {
+                return Ok(());
+            }
+            bail!("{path:?} does not exist");
+        }
+
+        let mut commands = Vec::new();
+        if options.recursive {
+            let mut dirs = vec![path.to_path_buf()];
+            let mut ix = 0;
+            while let Some(dir) = dirs.get(ix).cloned() {
+                for entry in self.state.list(&dir, false).await? {
+                    let entry_path = dir.join(&entry.name);
+                    if entry.kind == SftpEntryKind::Dir {
+                        dirs.push(entry_path);
+                    } else {
+                        commands.push(format!("rm {}", quote_remote(&entry_path)));
+                    }
+                }
+                ix += 1;
+            }
+            commands.extend(
+                dirs.iter()
+                    .rev()
+                    .map(|dir| format!("rmdir {}", quote_remote(dir))),
+            );
+            self.state
+                .listings
+                .lock()
+                .retain(|listed_dir, _| !listed_dir.starts_with(path));
+        } else {
+            commands.push(format!("rmdir {}", quote_remote(path)));
+        }
+        self.state.run(&commands).await?;
+        self.state.invalidate_parent(path);
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        if options.ignore_if_not_exists && self.metadata(path).await?.is_none() {
+            return Ok(());
+        }
+        self.state
+            .run(&[format!("rm {}", quote_remote(path))])
+            .await?;
+        self.state.invalidate_parent(path);
+        Ok(())
+    }
+
+    async fn open_handle(&self, _path: &Path) -> Result<Arc<dyn FileHandle>> {
+        bail!("file handles aren't supported over SFTP")
+    }
+
+    async fn open_sync(&self, path: &Path) -> Result<Box<dyn io::Read + Send + Sync>> {
+        Ok(Box::new(io::Cursor::new(self.load_bytes(path).await?)))
+    }
+
+    async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        let local_path = self.state.transfer_path();
+        self.state
+            .run(&[format!(
+                "get {} {}",
+                quote_remote(path),
+                quote(&local_path.to_string_lossy())
+            )])
+            .await?;
+        let content = smol::fs::read(&local_path).await;
+        smol::fs::remove_file(&local_path).await.log_err();
+        Ok(content?)
+    }
+
+    async fn atomic_write(&self, path: PathBuf, text: String) -> Result<()> {
+        self.write(&path, text.as_bytes()).await
+    }
+
+    async fn save(&self, path: &Path, text: &Rope, line_ending: LineEnding) -> Result<()> {
+        let content = chunks(text, line_ending).collect::<String>();
+        self.write(path, content.as_bytes()).await
+    }
+
+    async fn write(&self, path: &Path, content: &[u8]) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            self.create_dir(parent).await?;
+        }
+        let local_path = self.state.transfer_path();
+        smol::fs::write(&local_path, content).await?;
+        let result = self
+            .state
+            .run(&[format!(
+                "put {} {}",
+                quote(&local_path.to_string_lossy()),
+                quote_remote(path)
+            )])
+            .await;
+        smol::fs::remove_file(&local_path).await.log_err();
+        self.state.invalidate_parent(path);
+        result.map(|_| ())
+    }
+
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        let metadata = self
+            .metadata(path)
+            .await?
+            .with_context(|| format!("{path:?} does not exist"))?;
+        if !metadata.is_dir {
+            let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+                return Ok(path.to_path_buf());
+            };
+            return Ok(self.canonicalize(parent).await?.join(file_name));
+        }
+
+        let output = self
+            .state
+            .run(&[format!("cd {}", quote_remote(path)), "pwd".to_string()])
+            .await?;
+        output
+            .last()
+            .and_then(|output| {
+                output
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Remote working directory: "))
+            })
+            .map(|dir| PathBuf::from(dir.trim()))
+            .with_context(|| format!("failed to canonicalize {path:?}"))
+    }
+
+    async fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path)
+            .await
+            .is_ok_and(|metadata| metadata.is_some_and(|metadata| !metadata.is_dir))
+    }
+
+    async fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path)
+            .await
+            .is_ok_and(|metadata| metadata.is_some_and(|metadata| metadata.is_dir))
+    }
+
+    async fn metadata(&self, path: &Path) -> Result<Option<Metadata>> {
+        let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+            return Ok(Some(Metadata {
+                inode: inode(path),
+                mtime: MTime::from_seconds_and_nanos(0, 0),
+                is_symlink: false,
+                is_dir: true,
+                len: 0,
+                is_fifo: false,
+            }));
+        };
+        let file_name = file_name.to_string_lossy();
+        let entries = match self.state.cached_list(parent).await {
+            Ok(entries) => entries,
+            Err(_) => return Ok(None),
+        };
+        Ok(entries
+            .into_iter()
+            .find(|entry| entry.name == file_name)
+            .map(|entry| Metadata {
+                inode: inode(path),
+                mtime: entry.mtime,
+                is_symlink: entry.kind == SftpEntryKind::Symlink,
+                is_dir: entry.kind == SftpEntryKind::Dir,
+                len: entry.len,
+                is_fifo: false,
+            }))
+    }
+
+    async fn read_link(&self, _path: &Path) -> Result<PathBuf> {
+        bail!("reading symlinks isn't supported over SFTP")
+    }
+
+    async fn read_dir(
+        &self,
+        path: &Path,
+    ) -> Result<Pin<Box<dyn Send + Stream<Item = Result<PathBuf>>>>> {
+        let paths = self
+            .state
+            .list(path, true)
+            .await?
+            .into_iter()
+            .map(|entry| Ok(path.join(entry.name)))
+            .collect::<Vec<_>>();
+        Ok(Box::pin(futures::stream::iter(paths)))
+    }
+
+    async fn watch(
+        &self,
+        path: &Path,
+        _latency: Duration,
+    ) -> (
+        Pin<Box<dyn Send + Stream<Item = Vec<PathEvent>>>>,
+        Arc<dyn Watcher>,
+    ) {
+        let roots = Arc::new(Mutex::new(vec![path.to_path_buf()]));
+        let watcher = Arc::new(SftpWatcher {
+            roots: roots.clone(),
+        });
+        let state = self.state.clone();
+        let events = futures::stream::unfold((), move |()| {
+            let state = state.clone();
+            let roots = roots.clone();
+            async move {
+                loop {
+                    smol::Timer::after(POLL_INTERVAL).await;
+                    let roots = roots.lock().clone();
+                    let events = state.poll(&roots).await;
+                    if !events.is_empty() {
+                        return Some((events, ()));
+                    }
+                }
+            }
+        });
+        (events.boxed(), watcher)
+    }
+
+    fn open_repo(
+        &self,
+        _abs_dot_git: &Path,
+        _system_git_binary_path: Option<&Path>,
+    ) -> Option<Arc<dyn GitRepository>> {
+        None
+    }
+
+    async fn git_init(
+        &self,
+        _abs_work_directory: &Path,
+        _fallback_branch_name: String,
+    ) -> Result<()> {
+        bail!("git isn't supported over SFTP")
+    }
+
+    async fn git_clone(&self, _repo_url: &str, _abs_work_directory: &Path) -> Result<()> {
+        bail!("git isn't supported over SFTP")
+    }
+
+    fn is_fake(&self) -> bool {
+        false
+    }
+
+    async fn is_case_sensitive(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn file_transfer_host(&self) -> Option<String> {
+        Some(self.state.host.clone())
+    }
+}
+
+fn inode(path: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Quotes an argument of an `sftp` batch command.
+fn quote(argument: &str) -> String {
+    format!(
+        "\"{}\"",
+        argument.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+fn quote_remote(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(target_os = "windows") {
+        quote(&path.replace('\\', "/"))
+    } else {
+        quote(&path)
+    }
+}
+
+/// Splits the output of an `sftp` batch into the output of each command, using the echoed
+/// commands as separators.
+fn split_command_output(output: &str, command_count: usize) -> Vec<String> {
+    let mut outputs = Vec::with_capacity(command_count);
+    for line in output.lines() {
+        if line.starts_with("sftp> ") {
+            outputs.push(String::new());
+        } else if let Some(output) = outputs.last_mut() {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    outputs.resize(command_count, String::new());
+    outputs
+}
+
+/// Parses the output of `ls -lan`, or returns `None` when it lists nothing, not even `.`, which
+/// happens when the directory doesn't exist.
+fn parse_listing(output: &str) -> Option<Vec<SftpEntry>> {
+    let mut listed_anything = false;
+    let mut entries = Vec::new();
+    for line in output.lines() {
+        let fields = line.split_whitespace().take(8).collect::<Vec<_>>();
+        let [permissions, _, _, _, len, month, day, time_or_year] = fields[..] else {
+            continue;
+        };
+        let Ok(len) = len.parse::<u64>() else {
+            continue;
+        };
+        // Names may contain whitespace, so take the rest of the line after the date.
+        let Some(name) = skip_fields(line, 8).and_then(|path| path.rsplit('/').next()) else {
+            continue;
+        };
+        listed_anything = true;
+        if name.is_empty() || name == "." || name == ".." {
+            continue;
+        }
+
+        let kind = match permissions.chars().next() {
+            Some('-') => SftpEntryKind::File,
+            Some('d') => SftpEntryKind::Dir,
+            Some('l') => SftpEntryKind::Symlink,
+            _ => SftpEntryKind::Other,
+        };
+        entries.push(SftpEntry {
+            name: name.to_string(),
+            kind,
+            len,
+            mtime: parse_mtime(month, day, time_or_year, len)
+                .unwrap_or_else(|| MTime::from_seconds_and_nanos(0, 0)),
+        });
+    }
+    listed_anything.then_some(entries)
+}
+
+fn skip_fields(line: &str, count: usize) -> Option<&str> {
+    let mut rest = line.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace)?;
+        rest = rest[end..].trim_start();
+    }
+    Some(rest)
+}
+
+/// Parses the modification date of an `ls -l` line, such as `Oct 10 14:03` for recent files and
+/// `Oct 10  2023` for older ones.
+fn parse_mtime(month: &str, day: &str, time_or_year: &str, len: u64) -> Option<MTime> {
+    let month = MONTHS.iter().position(|name| *name == month)? as u8 + 1;
+    let month = time::Month::try_from(month).ok()?;
+    let day = day.parse::<u8>().ok()?;
+    let (year, hour, minute) = match time_or_year.split_once(':') {
+        Some((hour, minute)) => {
+            let today = time::OffsetDateTime::now_utc().date();
+            let mut year = today.year();
+            if time::Date::from_calendar_date(year, month, day).is_ok_and(|date| date > today) {
+                year -= 1;
+            }
+            (year, hour.parse().ok()?, minute.parse().ok()?)
+        }
+        None => (time_or_year.parse().ok()?, 0, 0),
+    };
+    let timestamp = time::Date::from_calendar_date(year, month, day)
+        .ok()?
+        .with_hms(hour, minute, 0)
+        .ok()?
+        .assume_utc()
+        .unix_timestamp();
+    // Listings only have minute precision, so the size stands in for the sub-second part to
+    // notice most changes made within the same minute.
+    Some(MTime::from_seconds_and_nanos(
+        timestamp.try_into().ok()?,
+        (len % 1_000_000_000) as u32,
+    ))
+}
+
+fn diff_listings(dir: &Path, old: &[SftpEntry], new: &[SftpEntry]) -> Vec<PathEvent> {
+    let mut events = Vec::new();
+    for entry in old {
+        if !new.iter().any(|new_entry| new_entry.name == entry.name) {
+            events.push(PathEvent {
+                path: dir.join(&entry.name),
+                kind: Some(PathEventKind::Removed),
+            });
+        }
+    }
+    for entry in new {
+        let kind = match old.iter().find(|old_entry| old_entry.name == entry.name) {
+            None => PathEventKind::Created,
+            Some(old_entry) if old_entry != entry => PathEventKind::Changed,
+            Some(_) => continue,
+        };
+        events.push(PathEvent {
+            path: dir.join(&entry.name),
+            kind: Some(kind),
+        });
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::path;
+
+    #[test]
+    fn test_split_command_output() {
+        let output = "Connected to host.\nsftp> ls -lan \"/a\"\nline 1\nline 2\nsftp> pwd\nRemote working directory: /a\n";
+        assert_eq!(
+            split_command_output(output, 3),
+            [
+                "line 1\nline 2\n".to_string(),
+                "Remote working directory: /a\n".to_string(),
+                String::new(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_listing() {
+        let output = "\
+drwxr-xr-x    3 1000     1000         4096 Oct 10 14:03 .
+drwxr-xr-x    5 1000     1000         4096 Oct  1  2023 ..
+-rw-r--r--    1 1000     1000          220 Feb 25  2020 /home/me/.profile
+drwxr-xr-x    2 1000     1000         4096 Oct 10 14:03 src
+-rw-r--r--    1 1000     1000           12 Mar  3  2021 notes with spaces.txt
+lrwxrwxrwx    1 1000     1000            7 Mar  3  2021 link
+";
+        let entries = parse_listing(output).unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.name.as_str(), entry.kind, entry.len))
+                .collect::<Vec<_>>(),
+            [
+                (".profile", SftpEntryKind::File, 220),
+                ("src", SftpEntryKind::Dir, 4096),
+                ("notes with spaces.txt", SftpEntryKind::File, 12),
+                ("link", SftpEntryKind::Symlink, 7),
+            ]
+        );
+        assert_eq!(
+            entries[0].mtime,
+            MTime::from_seconds_and_nanos(1582588800, 220)
+        );
+
+        assert_eq!(parse_listing(""), None);
+        assert_eq!(
+            parse_listing("drwxr-xr-x    2 1000     1000         4096 Oct  1  2023 .\n"),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_diff_listings() {
+        let entry = |name: &str, len: u64| SftpEntry {
+            name: name.to_string(),
+            kind: SftpEntryKind::File,
+            len,
+            mtime: MTime::from_seconds_and_nanos(0, len as u32),
+        };
+        let dir = Path::new(path!("/dir"));
+        let events = diff_listings(
+            dir,
+            &[entry("same", 1), entry("changed", 1), entry("removed", 1)],
+            &[entry("same", 1), entry("changed", 2), entry("created", 1)],
+        );
+        assert_eq!(
+            events,
+            [
+                PathEvent {
+                    path: dir.join("removed"),
+                    kind: Some(PathEventKind::Removed),
+                },
+                PathEvent {
+                    path: dir.join("changed"),
+                    kind: Some(PathEventKind::Changed),
+                },
+                PathEvent {
+                    path: dir.join("created"),
+                    kind: Some(PathEventKind::Created),
+                },
+            ]
+        );
+    }
+}
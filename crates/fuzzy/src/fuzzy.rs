@@ -2,9 +2,12 @@ mod char_bag;
 mod matcher;
 mod paths;
 mod strings;
+mod top_matches;
 
 pub use char_bag::CharBag;
 pub use paths::{
-    PathMatch, PathMatchCandidate, PathMatchCandidateSet, match_fixed_path_set, match_path_sets,
+    PathMatch, PathMatchCache, PathMatchCandidate, PathMatchCandidateSet, match_fixed_path_set,
+    match_path_sets, match_path_sets_incremental,
 };
 pub use strings::{StringMatch, StringMatchCandidate, match_strings};
+pub use top_matches::TopMatches;
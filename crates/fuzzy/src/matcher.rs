@@ -52,20 +52,20 @@ impl<'a> Matcher<'a> {
         }
     }
 
-    /// Filter and score fuzzy match candidates. Results are returned unsorted, in the same order as
-    /// the input candidates.
+    /// Filter and score fuzzy match candidates. Results are added to `results` in the same order
+    /// as the input candidates.
     pub(crate) fn match_candidates<C, R, F, T>(
         &mut self,
         prefix: &[char],
         lowercase_prefix: &[char],
         candidates: impl Iterator<Item = T>,
-        results: &mut Vec<R>,
+        results: &mut impl Extend<R>,
         cancel_flag: &AtomicBool,
-        build_match: F,
+        mut build_match: F,
     ) where
         C: MatchCandidate,
         T: Borrow<C>,
-        F: Fn(&C, f64, &Vec<usize>) -> R,
+        F: FnMut(&C, f64, &Vec<usize>) -> R,
     {
         let mut candidate_chars = Vec::new();
         let mut lowercase_candidate_chars = Vec::new();
@@ -111,11 +111,11 @@ impl<'a> Matcher<'a> {
             );
 
             if score > 0.0 {
-                results.push(build_match(
+                results.extend(Some(build_match(
                     candidate.borrow(),
                     score,
                     &self.match_positions,
-                ));
+                )));
             }
         }
     }
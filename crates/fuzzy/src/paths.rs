@@ -1,18 +1,28 @@
 use gpui::BackgroundExecutor;
+use parking_lot::Mutex;
 use std::{
+    cell::Cell,
     cmp::{self, Ordering},
+    mem,
     sync::{
         Arc,
-        atomic::{self, AtomicBool},
+        atomic::{self, AtomicBool, AtomicUsize},
     },
+    time::{Duration, Instant},
 };
 use util::{paths::PathStyle, rel_path::RelPath};
 
 use crate::{
-    CharBag,
+    CharBag, TopMatches,
     matcher::{MatchCandidate, Matcher},
 };
 
+/// The number of candidates that a background thread scores at a time.
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// How often the best matches found so far are reported while searching.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
 #[derive(Clone, Debug)]
 pub struct PathMatchCandidate<'a> {
     pub is_dir: bool,
@@ -98,7 +108,7 @@ pub fn match_fixed_path_set(
 
     let mut matcher = Matcher::new(&query, &lowercase_query, query_char_bag, smart_case, true);
 
-    let mut results = Vec::new();
+    let mut results = TopMatches::new(max_results);
     matcher.match_candidates(
         &[],
         &[],
@@ -115,8 +125,7 @@ pub fn match_fixed_path_set(
             distance_to_relative_ancestor: usize::MAX,
         },
     );
-    util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
-    results
+    results.into_sorted_vec()
 }
 
 pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
@@ -128,12 +137,75 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     cancel_flag: &AtomicBool,
     executor: BackgroundExecutor,
 ) -> Vec<PathMatch> {
+    match_path_sets_incremental(
+        candidate_sets,
+        query,
+        relative_to,
+        smart_case,
+        max_results,
+        None,
+        cancel_flag,
+        executor,
+        |_| {},
+    )
+    .await
+    .0
+}
+
+/// The candidates that matched a query, so that a search for a longer query that starts with it
+/// only needs to score those candidates.
+#[derive(Clone, Debug)]
+pub struct PathMatchCache {
+    query: String,
+    /// The id and length of each candidate set that was searched.
+    candidate_sets: Vec<(usize, usize)>,
+    /// The indices of the matching candidates across all of the candidate sets, in order.
+    matches: Arc<[usize]>,
+}
+
+impl PathMatchCache {
+    /// Returns the candidates that can match the query, if it starts with the cached query and
+    /// the candidate sets haven't changed.
+    fn matches_for<'a, Set: PathMatchCandidateSet<'a>>(
+        &self,
+        candidate_sets: &[Set],
+        query: &str,
+    ) -> Option<&[usize]> {
+        let same_sets = self.candidate_sets.len() == candidate_sets.len()
+            && self
+                .candidate_sets
+                .iter()
+                .zip(candidate_sets)
+                .all(|(&(id, len), set)| id == set.id() && len == set.len());
+        (same_sets && query.starts_with(&self.query)).then_some(&*self.matches)
+    }
+}
+
+/// Matches the query against the candidate sets like [`match_path_sets`], but reports the best
+/// matches found so far with `on_progress` while the search is running, and reuses the results
+/// of a previous search whose query this one extends.
+///
+/// Along with the matches, returns a cache of this search for the next query, unless the search
+/// was cancelled. The cache must be dropped when the candidate sets' contents change.
+pub async fn match_path_sets_incremental<'a, Set: PathMatchCandidateSet<'a>>(
+    candidate_sets: &'a [Set],
+    query: &str,
+    relative_to: &Option<Arc<RelPath>>,
+    smart_case: bool,
+    max_results: usize,
+    cache: Option<&PathMatchCache>,
+    cancel_flag: &AtomicBool,
+    executor: BackgroundExecutor,
+    on_progress: impl Fn(Vec<PathMatch>) + Sync,
+) -> (Vec<PathMatch>, Option<PathMatchCache>) {
     let path_count: usize = candidate_sets.iter().map(|s| s.len()).sum();
     if path_count == 0 {
-        return Vec::new();
+        return (Vec::new(), None);
     }
 
     let path_style = candidate_sets[0].path_style();
+    let cached_matches = cache.and_then(|cache| cache.matches_for(candidate_sets, query));
+    let raw_query = query;
 
     let query = query
         .chars()
@@ -155,76 +227,129 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     let lowercase_query = &lowercase_query;
     let query_char_bag = CharBag::from_iter(lowercase_query.iter().copied());
 
-    let num_cpus = executor.num_cpus().min(path_count);
-    let segment_size = path_count.div_ceil(num_cpus);
-    let mut segment_results = (0..num_cpus)
-        .map(|_| Vec::with_capacity(max_results))
-        .collect::<Vec<_>>();
+    // Candidates are scored in chunks that the background threads take in turn, so that a
+    // thread that finishes early keeps taking work from the others.
+    let chunk_count = path_count.div_ceil(CHUNK_SIZE);
+    let next_chunk = &AtomicUsize::new(0);
+    let top_matches = &Mutex::new(TopMatches::new(max_results));
+    let chunk_matches = &Mutex::new(Vec::new());
+    let last_progress = &Mutex::new(Instant::now());
+    let on_progress = &on_progress;
 
     executor
         .scoped(|scope| {
-            for (segment_idx, results) in segment_results.iter_mut().enumerate() {
+            for _ in 0..executor.num_cpus().min(chunk_count) {
                 scope.spawn(async move {
-                    let segment_start = segment_idx * segment_size;
-                    let segment_end = segment_start + segment_size;
                     let mut matcher =
                         Matcher::new(query, lowercase_query, query_char_bag, smart_case, true);
 
-                    let mut tree_start = 0;
-                    for candidate_set in candidate_sets {
+                    loop {
                         if cancel_flag.load(atomic::Ordering::Acquire) {
                             break;
                         }
+                        let chunk_ix = next_chunk.fetch_add(1, atomic::Ordering::Relaxed);
+                        if chunk_ix >= chunk_count {
+                            break;
+                        }
+                        let chunk_start = chunk_ix * CHUNK_SIZE;
+                        let chunk_end = cmp::min(chunk_start + CHUNK_SIZE, path_count);
+
+                        // When extending a previous query, only its matches can still match.
+                        let cached_matches = cached_matches.map(|matches| {
+                            let start = matches.partition_point(|&ix| ix < chunk_start);
+                            let end = matches.partition_point(|&ix| ix < chunk_end);
+                            &matches[start..end]
+                        });
+                        if cached_matches.is_some_and(|matches| matches.is_empty()) {
+                            chunk_matches.lock().push((chunk_ix, Vec::new()));
+                            continue;
+                        }
+                        let mut cached_matches =
+                            cached_matches.map(|matches| matches.iter().copied().peekable());
+
+                        let mut results = TopMatches::new(max_results);
+                        let mut matched = Vec::new();
+                        let candidate_ix = Cell::new(0);
+                        let mut tree_start = 0;
+                        for candidate_set in candidate_sets {
+                            let tree_end = tree_start + candidate_set.len();
+
+                            if tree_start < chunk_end && chunk_start < tree_end {
+                                let start = cmp::max(tree_start, chunk_start) - tree_start;
+                                let end = cmp::min(tree_end, chunk_end) - tree_start;
+                                let candidates = candidate_set
+                                    .candidates(start)
+                                    .take(end - start)
+                                    .enumerate()
+                                    .map(|(ix, candidate)| (tree_start + start + ix, candidate))
+                                    .filter(|(ix, _)| match &mut cached_matches {
+                                        Some(matches) => matches.next_if_eq(ix).is_some(),
+                                        None => true,
+                                    })
+                                    .map(|(ix, candidate)| {
+                                        candidate_ix.set(ix);
+                                        candidate
+                                    });
 
-                        let tree_end = tree_start + candidate_set.len();
-
-                        if tree_start < segment_end && segment_start < tree_end {
-                            let start = cmp::max(tree_start, segment_start) - tree_start;
-                            let end = cmp::min(tree_end, segment_end) - tree_start;
-                            let candidates = candidate_set.candidates(start).take(end - start);
-
-                            let worktree_id = candidate_set.id();
-                            let mut prefix = candidate_set
-                                .prefix()
-                                .as_unix_str()
-                                .chars()
-                                .collect::<Vec<_>>();
-                            if !candidate_set.root_is_file() && !prefix.is_empty() {
-                                prefix.push('/');
+                                let worktree_id = candidate_set.id();
+                                let mut prefix = candidate_set
+                                    .prefix()
+                                    .as_unix_str()
+                                    .chars()
+                                    .collect::<Vec<_>>();
+                                if !candidate_set.root_is_file() && !prefix.is_empty() {
+                                    prefix.push('/');
+                                }
+                                let lowercase_prefix = prefix
+                                    .iter()
+                                    .map(|c| c.to_ascii_lowercase())
+                                    .collect::<Vec<_>>();
+                                matcher.match_candidates(
+                                    &prefix,
+                                    &lowercase_prefix,
+                                    candidates,
+                                    &mut results,
+                                    cancel_flag,
+                                    |candidate, score, positions| {
+                                        matched.push(candidate_ix.get());
+                                        PathMatch {
+                                            score,
+                                            worktree_id,
+                                            positions: positions.clone(),
+                                            path: Arc::from(candidate.path),
+                                            is_dir: candidate.is_dir,
+                                            path_prefix: candidate_set.prefix(),
+                                            distance_to_relative_ancestor: relative_to
+                                                .as_ref()
+                                                .map_or(usize::MAX, |relative_to| {
+                                                    distance_between_paths(
+                                                        candidate.path,
+                                                        relative_to.as_ref(),
+                                                    )
+                                                }),
+                                        }
+                                    },
+                                );
                             }
-                            let lowercase_prefix = prefix
-                                .iter()
-                                .map(|c| c.to_ascii_lowercase())
-                                .collect::<Vec<_>>();
-                            matcher.match_candidates(
-                                &prefix,
-                                &lowercase_prefix,
-                                candidates,
-                                results,
-                                cancel_flag,
-                                |candidate, score, positions| PathMatch {
-                                    score,
-                                    worktree_id,
-                                    positions: positions.clone(),
-                                    path: Arc::from(candidate.path),
-                                    is_dir: candidate.is_dir,
-                                    path_prefix: candidate_set.prefix(),
-                                    distance_to_relative_ancestor: relative_to.as_ref().map_or(
-                                        usize::MAX,
-                                        |relative_to| {
-                                            distance_between_paths(
-                                                candidate.path,
-                                                relative_to.as_ref(),
-                                            )
-                                        },
-                                    ),
-                                },
-                            );
+                            if tree_end >= chunk_end {
+                                break;
+                            }
+                            tree_start = tree_end;
                         }
-                        if tree_end >= segment_end {
-                            break;
+
+                        chunk_matches.lock().push((chunk_ix, matched));
+                        let progress = {
+                            let mut top_matches = top_matches.lock();
+                            top_matches.append(results);
+                            let mut last_progress = last_progress.lock();
+                            (last_progress.elapsed() >= PROGRESS_INTERVAL).then(|| {
+                                *last_progress = Instant::now();
+                                top_matches.to_sorted_vec()
+                            })
+                        };
+                        if let Some(progress) = progress {
+                            on_progress(progress);
                         }
-                        tree_start = tree_end;
                     }
                 })
             }
@@ -232,12 +357,24 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
         .await;
 
     if cancel_flag.load(atomic::Ordering::Acquire) {
-        return Vec::new();
+        return (Vec::new(), None);
     }
 
-    let mut results = segment_results.concat();
-    util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
-    results
+    let mut chunk_matches = mem::take(&mut *chunk_matches.lock());
+    chunk_matches.sort_unstable_by_key(|(chunk_ix, _)| *chunk_ix);
+    let cache = PathMatchCache {
+        query: raw_query.to_string(),
+        candidate_sets: candidate_sets
+            .iter()
+            .map(|set| (set.id(), set.len()))
+            .collect(),
+        matches: chunk_matches
+            .into_iter()
+            .flat_map(|(_, matches)| matches)
+            .collect(),
+    };
+    let results = mem::replace(&mut *top_matches.lock(), TopMatches::new(0)).into_sorted_vec();
+    (results, Some(cache))
 }
 
 /// Compute the distance from a given path to some other path
@@ -257,12 +394,121 @@ fn distance_between_paths(path: &RelPath, relative_to: &RelPath) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use util::rel_path::RelPath;
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    use gpui::TestAppContext;
+    use util::{
+        paths::PathStyle,
+        rel_path::{RelPath, rel_path},
+    };
+
+    use super::*;
+
+    struct TestCandidateSet {
+        paths: Vec<Arc<RelPath>>,
+    }
+
+    impl<'a> PathMatchCandidateSet<'a> for TestCandidateSet {
+        type Candidates = Box<dyn 'a + Iterator<Item = PathMatchCandidate<'a>>>;
+
+        fn id(&self) -> usize {
+            0
+        }
+
+        fn len(&self) -> usize {
+            self.paths.len()
+        }
+
+        fn root_is_file(&self) -> bool {
+            false
+        }
+
+        fn prefix(&self) -> Arc<RelPath> {
+            RelPath::empty().into()
+        }
 
-    use super::distance_between_paths;
+        fn candidates(&'a self, start: usize) -> Self::Candidates {
+            Box::new(self.paths[start..].iter().map(|path| PathMatchCandidate {
+                is_dir: false,
+                path,
+                char_bag: CharBag::from_iter(path.as_unix_str().to_lowercase().chars()),
+            }))
+        }
+
+        fn path_style(&self) -> PathStyle {
+            PathStyle::Posix
+        }
+    }
 
     #[test]
     fn test_distance_between_paths_empty() {
         distance_between_paths(RelPath::empty(), RelPath::empty());
     }
+
+    #[gpui::test]
+    async fn test_match_path_sets_incremental(cx: &mut TestAppContext) {
+        let path_count = CHUNK_SIZE * 2 + 10;
+        let paths = (0..path_count)
+            .map(|ix| match ix % 3 {
+                0 => rel_path(&format!("src/foo_{ix}.rs")).into(),
+                1 => rel_path(&format!("src/bar_{ix}.rs")).into(),
+                _ => rel_path(&format!("docs/fob_{ix}.md")).into(),
+            })
+            .collect();
+        let candidate_sets = [TestCandidateSet { paths }];
+        let cancel_flag = AtomicBool::new(false);
+
+        let (matches, cache) = match_path_sets_incremental(
+            &candidate_sets,
+            "fo",
+            &None,
+            false,
+            10,
+            None,
+            &cancel_flag,
+            cx.executor(),
+            |_| {},
+        )
+        .await;
+        let cache = cache.unwrap();
+        assert_eq!(matches.len(), 10);
+        assert_eq!(
+            cache.matches.len(),
+            (0..path_count).filter(|ix| ix % 3 != 1).count()
+        );
+
+        // Extending the query reuses the candidates that matched, giving the same matches as a
+        // search from scratch.
+        let (reused_matches, reused_cache) = match_path_sets_incremental(
+            &candidate_sets,
+            "foo",
+            &None,
+            false,
+            10,
+            Some(&cache),
+            &cancel_flag,
+            cx.executor(),
+            |_| {},
+        )
+        .await;
+        let expected = match_path_sets(
+            &candidate_sets,
+            "foo",
+            &None,
+            false,
+            10,
+            &cancel_flag,
+            cx.executor(),
+        )
+        .await;
+        assert_eq!(reused_matches, expected);
+        assert_eq!(
+            reused_cache.unwrap().matches.len(),
+            (0..path_count).filter(|ix| ix % 3 == 0).count()
+        );
+
+        // A query that doesn't extend the cached one searches every candidate.
+        assert!(cache.matches_for(&candidate_sets, "fa").is_none());
+        assert!(cache.matches_for(&candidate_sets, "fob").is_some());
+    }
 }
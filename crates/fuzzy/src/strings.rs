@@ -1,5 +1,5 @@
 use crate::{
-    CharBag,
+    CharBag, TopMatches,
     matcher::{MatchCandidate, Matcher},
 };
 use gpui::BackgroundExecutor;
@@ -151,7 +151,7 @@ where
     let num_cpus = executor.num_cpus().min(candidates.len());
     let segment_size = candidates.len().div_ceil(num_cpus);
     let mut segment_results = (0..num_cpus)
-        .map(|_| Vec::with_capacity(max_results.min(candidates.len())))
+        .map(|_| TopMatches::new(max_results))
         .collect::<Vec<_>>();
 
     executor
@@ -193,7 +193,9 @@ where
         return Vec::new();
     }
 
-    let mut results = segment_results.concat();
-    util::truncate_to_bottom_n_sorted_by(&mut results, max_results, &|a, b| b.cmp(a));
-    results
+    let mut results = TopMatches::new(max_results);
+    for segment_results in segment_results {
+        results.append(segment_results);
+    }
+    results.into_sorted_vec()
 }
@@ -0,0 +1,92 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// Keeps the best `limit` matches seen so far, so that scoring a large set of candidates doesn't
+/// need to hold or sort every match.
+#[derive(Clone, Debug)]
+pub struct TopMatches<T: Ord> {
+    limit: usize,
+    /// A min-heap, so that the worst of the kept matches is the one to be replaced.
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> TopMatches<T> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit.min(1024) + 1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Adds a match, dropping the worst kept match if there are more than `limit`.
+    pub fn push(&mut self, item: T) {
+        if self.heap.len() < self.limit {
+            self.heap.push(Reverse(item));
+        } else if let Some(mut worst) = self.heap.peek_mut()
+            && item > worst.0
+        {
+            *worst = Reverse(item);
+        }
+    }
+
+    /// Adds all of the matches kept by another set.
+    pub fn append(&mut self, other: Self) {
+        for Reverse(item) in other.heap {
+            self.push(item);
+        }
+    }
+
+    /// Returns the kept matches, best first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // Sorting the reversed items ascending puts the best match first.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+}
+
+impl<T: Ord + Clone> TopMatches<T> {
+    /// Returns a copy of the kept matches, best first.
+    pub fn to_sorted_vec(&self) -> Vec<T> {
+        self.clone().into_sorted_vec()
+    }
+}
+
+impl<T: Ord> Extend<T> for TopMatches<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_matches() {
+        let mut matches = TopMatches::new(3);
+        matches.extend([5, 1, 9, 3, 7, 2]);
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches.to_sorted_vec(), [9, 7, 5]);
+
+        let mut other = TopMatches::new(3);
+        other.extend([8, 4]);
+        matches.append(other);
+        assert_eq!(matches.into_sorted_vec(), [9, 8, 7]);
+
+        let mut empty = TopMatches::new(0);
+        empty.push(1);
+        assert!(empty.is_empty());
+    }
+}
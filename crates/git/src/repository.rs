@@ -351,6 +351,11 @@ pub trait GitRepository: Send + Sync {
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
 
+    /// Returns the bytes of an entry in the repository's HEAD, such as a binary file, or None if HEAD does not exist or has no entry for the given path.
+    ///
+    /// Also returns `None` for symlinks.
+    fn load_committed_bytes(&self, path: RepoPath) -> BoxFuture<'_, Option<Vec<u8>>>;
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -893,6 +898,11 @@ impl GitRepository for RealGitRepository {
     }
 
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
+        let load_bytes = self.load_committed_bytes(path);
+        async move { String::from_utf8(load_bytes.await?).ok() }.boxed()
+    }
+
+    fn load_committed_bytes(&self, path: RepoPath) -> BoxFuture<'_, Option<Vec<u8>>> {
         let repo = self.repository.clone();
         self.executor
             .spawn(async move {
@@ -902,8 +912,7 @@ impl GitRepository for RealGitRepository {
                 if entry.filemode() == i32::from(git2::FileMode::Link) {
                     return None;
                 }
-                let content = repo.find_blob(entry.id()).log_err()?.content().to_owned();
-                String::from_utf8(content).ok()
+                Some(repo.find_blob(entry.id()).log_err()?.content().to_owned())
             })
             .boxed()
     }
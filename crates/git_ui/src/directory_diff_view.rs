@@ -0,0 +1,414 @@
+//! DirectoryDiffView summarizes the differences between two directories of a project.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity,
+};
+use project::{Fs, Project, ProjectPath};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::rel_path::RelPath;
+use workspace::{
+    Item, OpenOptions, OpenVisible, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr as _,
+};
+
+use crate::file_diff_view::FileDiffView;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirectoryDiffStatus {
+    /// The file only exists in the new directory.
+    Added,
+    /// The file only exists in the old directory.
+    Removed,
+    /// The file exists in both directories with different contents.
+    Changed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectoryDiffEntry {
+    /// The path of the file, relative to the compared directories.
+    pub path: Arc<RelPath>,
+    pub status: DirectoryDiffStatus,
+}
+
+/// Lists the files added, removed and changed between two directories. Selecting a changed file
+/// opens a [`FileDiffView`] for it.
+pub struct DirectoryDiffView {
+    old_dir: PathBuf,
+    new_dir: PathBuf,
+    entries: Vec<DirectoryDiffEntry>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+}
+
+impl DirectoryDiffView {
+    pub fn open(
+        old_dir: ProjectPath,
+        new_dir: ProjectPath,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project = workspace.project().read(cx);
+        let (old_dir, old_files) = match files_in_directory(project, &old_dir, cx) {
+            Ok(files) => files,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let (new_dir, new_files) = match files_in_directory(project, &new_dir, cx) {
+            Ok(files) => files,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+        window.spawn(cx, async move |cx| {
+            let entries =
+                diff_directories(fs.as_ref(), &old_dir, old_files, &new_dir, new_files).await;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| Self {
+                    old_dir,
+                    new_dir,
+                    entries,
+                    workspace: workspace.weak_handle(),
+                    focus_handle: cx.focus_handle(),
+                });
+                workspace.add_item_to_active_pane(Box::new(view.clone()), None, true, window, cx);
+                view
+            })
+        })
+    }
+
+    pub fn entries(&self) -> &[DirectoryDiffEntry] {
+        &self.entries
+    }
+
+    /// Opens a diff of a changed file, or the file itself if it was added or removed.
+    fn open_entry(&self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let old_path = self.old_dir.join(entry.path.as_std_path());
+        let new_path = self.new_dir.join(entry.path.as_std_path());
+        let status = entry.status;
+        workspace.update(cx, |workspace, cx| match status {
+            DirectoryDiffStatus::Changed => {
+                FileDiffView::open(old_path, new_path, workspace, window, cx)
+                    .detach_and_prompt_err("Failed to open diff", window, cx, |_, _, _| None);
+            }
+            DirectoryDiffStatus::Added | DirectoryDiffStatus::Removed => {
+                let path = if status == DirectoryDiffStatus::Added {
+                    new_path
+                } else {
+                    old_path
+                };
+                workspace
+                    .open_abs_path(
+                        path,
+                        OpenOptions {
+                            visible: Some(OpenVisible::None),
+                            ..Default::default()
+                        },
+                        window,
+                        cx,
+                    )
+                    .detach_and_prompt_err("Failed to open file", window, cx, |_, _, _| None);
+            }
+        });
+    }
+
+    fn dir_name(path: &Path) -> String {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string_lossy().into_owned())
+    }
+
+    fn count(&self, status: DirectoryDiffStatus) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+}
+
+/// Returns the absolute path of a directory and the files inside it, relative to the directory,
+/// with their sizes.
+fn files_in_directory(
+    project: &Project,
+    dir: &ProjectPath,
+    cx: &App,
+) -> Result<(PathBuf, Vec<(Arc<RelPath>, u64)>)> {
+    let worktree = project
+        .worktree_for_id(dir.worktree_id, cx)
+        .context("no worktree for the compared directory")?;
+    let worktree = worktree.read(cx);
+    let files = worktree
+        .traverse_from_path(true, false, false, &dir.path)
+        .take_while(|entry| entry.path.starts_with(&dir.path))
+        .filter_map(|entry| {
+            let path = entry.path.strip_prefix(&dir.path).ok()?;
+            Some((path.into_arc(), entry.size))
+        })
+        .collect();
+    Ok((worktree.absolutize(&dir.path), files))
+}
+
+/// Compares the files of two directories: by size first, and by contents when the sizes match.
+pub async fn diff_directories(
+    fs: &dyn Fs,
+    old_dir: &Path,
+    old_files: Vec<(Arc<RelPath>, u64)>,
+    new_dir: &Path,
+    new_files: Vec<(Arc<RelPath>, u64)>,
+) -> Vec<DirectoryDiffEntry> {
+    let mut sizes = BTreeMap::<Arc<RelPath>, (Option<u64>, Option<u64>)>::new();
+    for (path, size) in old_files {
+        sizes.entry(path).or_default().0 = Some(size);
+    }
+    for (path, size) in new_files {
+        sizes.entry(path).or_default().1 = Some(size);
+    }
+
+    let mut entries = Vec::new();
+    for (path, sizes) in sizes {
+        let status = match sizes {
+            (Some(_), None) => DirectoryDiffStatus::Removed,
+            (None, Some(_)) => DirectoryDiffStatus::Added,
+            (Some(old_size), Some(new_size)) if old_size != new_size => {
+                DirectoryDiffStatus::Changed
+            }
+            _ => {
+                let old_contents = fs.load_bytes(&old_dir.join(path.as_std_path())).await;
+                let new_contents = fs.load_bytes(&new_dir.join(path.as_std_path())).await;
+                match (old_contents, new_contents) {
+                    (Ok(old_contents), Ok(new_contents)) if old_contents == new_contents => {
+                        continue;
+                    }
+                    _ => DirectoryDiffStatus::Changed,
+                }
+            }
+        };
+        entries.push(DirectoryDiffEntry { path, status });
+    }
+    entries
+}
+
+impl EventEmitter<ItemEvent> for DirectoryDiffView {}
+
+impl Focusable for DirectoryDiffView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for DirectoryDiffView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!(
+            "{} ↔ {}",
+            Self::dir_name(&self.old_dir),
+            Self::dir_name(&self.new_dir)
+        )
+        .into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "{} ↔ {}",
+                self.old_dir.to_string_lossy(),
+                self.new_dir.to_string_lossy()
+            )
+            .into(),
+        )
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Directory Diff View Opened")
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+}
+
+impl Render for DirectoryDiffView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let summary = if self.entries.is_empty() {
+            "The directories have the same contents".to_string()
+        } else {
+            format!(
+                "{} added, {} removed, {} changed",
+                self.count(DirectoryDiffStatus::Added),
+                self.count(DirectoryDiffStatus::Removed),
+                self.count(DirectoryDiffStatus::Changed),
+            )
+        };
+
+        v_flex()
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(summary).color(Color::Muted)),
+            )
+            .child(
+                v_flex()
+                    .id("directory-diff-entries")
+                    .flex_1()
+                    .p_1()
+                    .overflow_y_scroll()
+                    .children(self.entries.iter().enumerate().map(|(ix, entry)| {
+                        let (icon, color) = match entry.status {
+                            DirectoryDiffStatus::Added => (IconName::SquarePlus, Color::Created),
+                            DirectoryDiffStatus::Removed => (IconName::SquareMinus, Color::Deleted),
+                            DirectoryDiffStatus::Changed => (IconName::SquareDot, Color::Modified),
+                        };
+                        ListItem::new(ix)
+                            .spacing(ListItemSpacing::Sparse)
+                            .start_slot(Icon::new(icon).size(IconSize::Small).color(color))
+                            .child(Label::new(entry.path.as_unix_str().to_string()))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.open_entry(ix, window, cx);
+                            }))
+                    })),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::{Settings, SettingsStore};
+    use util::{path, rel_path::rel_path};
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init_settings(cx);
+            theme::ThemeSettings::register(cx)
+        });
+    }
+
+    #[gpui::test]
+    async fn test_directory_diff_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            json!({
+                "old": {
+                    "changed.txt": "one",
+                    "resized.txt": "short",
+                    "removed.txt": "",
+                    "same.txt": "same",
+                    "nested": {
+                        "changed.txt": "one",
+                    },
+                },
+                "new": {
+                    "added.txt": "",
+                    "changed.txt": "two",
+                    "resized.txt": "much longer",
+                    "same.txt": "same",
+                    "nested": {
+                        "changed.txt": "two",
+                    },
+                },
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+
+        let diff_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                DirectoryDiffView::open(
+                    ProjectPath {
+                        worktree_id,
+                        path: rel_path("old").into(),
+                    },
+                    ProjectPath {
+                        worktree_id,
+                        path: rel_path("new").into(),
+                    },
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        let entries = diff_view.read_with(cx, |diff_view, _| {
+            diff_view
+                .entries()
+                .iter()
+                .map(|entry| (entry.path.as_unix_str().to_string(), entry.status))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(
+            entries,
+            [
+                ("added.txt".to_string(), DirectoryDiffStatus::Added),
+                ("changed.txt".to_string(), DirectoryDiffStatus::Changed),
+                (
+                    "nested/changed.txt".to_string(),
+                    DirectoryDiffStatus::Changed
+                ),
+                ("removed.txt".to_string(), DirectoryDiffStatus::Removed),
+                ("resized.txt".to_string(), DirectoryDiffStatus::Changed),
+            ]
+        );
+        assert_eq!(
+            diff_view.read_with(cx, |diff_view, cx| diff_view.tab_content_text(0, cx)),
+            "old ↔ new"
+        );
+    }
+}
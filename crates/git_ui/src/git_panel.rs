@@ -50,7 +50,7 @@ use project::{
     git_store::{GitStoreEvent, Repository, RepositoryEvent, RepositoryId},
 };
 use serde::{Deserialize, Serialize};
-use settings::{Settings, SettingsStore, StatusStyle};
+use settings::{CommitMessageStyle, Settings, SettingsStore, StatusStyle};
 use std::future::Future;
 use std::ops::Range;
 use std::path::Path;
@@ -1762,9 +1762,10 @@ impl GitPanel {
         });
 
         let temperature = AgentSettings::temperature_for_model(&model, cx);
+        let commit_message_style = GitPanelSettings::get_global(cx).commit_message_style;
 
         self.generate_commit_message_task = Some(cx.spawn(async move |this, cx| {
-             async move {
+            async move {
                 let _defer = cx.on_drop(&this, |this, _cx| {
                     this.generate_commit_message_task.take();
                 });
@@ -1799,18 +1800,16 @@ impl GitPanel {
                 }
 
                 let subject = this.update(cx, |this, cx| {
-                    this.commit_editor.read(cx).text(cx).lines().next().map(ToOwned::to_owned).unwrap_or_default()
+                    this.commit_editor
+                        .read(cx)
+                        .text(cx)
+                        .lines()
+                        .next()
+                        .map(ToOwned::to_owned)
+                        .unwrap_or_default()
                 })?;
 
-                let text_empty = subject.trim().is_empty();
-
-                let content = if text_empty {
-                    format!("{PROMPT}\nHere are the changes in this commit:\n{diff_text}")
-                } else {
-                    format!("{PROMPT}\nHere is the user's subject line:\n{subject}\nHere are the changes in this commit:\n{diff_text}\n")
-                };
-
-                const PROMPT: &str = include_str!("commit_message_prompt.txt");
+                let content = commit_message_prompt(commit_message_style, &subject, &diff_text);
 
                 let request = LanguageModelRequest {
                     thread_id: None,
@@ -1836,7 +1835,11 @@ impl GitPanel {
                             this.update(cx, |this, cx| {
                                 this.commit_message_buffer(cx).update(cx, |buffer, cx| {
                                     let insert_position = buffer.anchor_before(buffer.len());
-                                    buffer.edit([(insert_position..insert_position, "\n")], None, cx)
+                                    buffer.edit(
+                                        [(insert_position..insert_position, "\n")],
+                                        None,
+                                        cx,
+                                    )
                                 });
                             })?;
                         }
@@ -1846,8 +1849,13 @@ impl GitPanel {
                                 Ok(text) => {
                                     this.update(cx, |this, cx| {
                                         this.commit_message_buffer(cx).update(cx, |buffer, cx| {
-                                            let insert_position = buffer.anchor_before(buffer.len());
-                                            buffer.edit([(insert_position..insert_position, text)], None, cx);
+                                            let insert_position =
+                                                buffer.anchor_before(buffer.len());
+                                            buffer.edit(
+                                                [(insert_position..insert_position, text)],
+                                                None,
+                                                cx,
+                                            );
                                         });
                                     })?;
                                 }
@@ -1865,7 +1873,8 @@ impl GitPanel {
 
                 anyhow::Ok(())
             }
-            .log_err().await
+            .log_err()
+            .await
         }));
     }
 
@@ -3016,7 +3025,7 @@ impl GitPanel {
         let can_commit = self.can_commit();
         let editor_focus_handle = self.commit_editor.focus_handle(cx);
         Some(
-            IconButton::new("generate-commit-message", IconName::AiEdit)
+            IconButton::new("generate-commit-message", IconName::Sparkle)
                 .shape(ui::IconButtonShape::Square)
                 .icon_color(Color::Muted)
                 .tooltip(move |window, cx| {
@@ -4881,6 +4890,25 @@ impl Component for PanelRepoFooter {
     }
 }
 
+/// Builds the request for generating a commit message for `diff_text`, continuing the
+/// subject line the user already wrote, if any.
+fn commit_message_prompt(style: CommitMessageStyle, subject: &str, diff_text: &str) -> String {
+    const PROMPT: &str = include_str!("commit_message_prompt.txt");
+    const CONVENTIONAL_PROMPT: &str = include_str!("conventional_commit_prompt.txt");
+
+    let prompt = match style {
+        CommitMessageStyle::Standard => PROMPT.to_string(),
+        CommitMessageStyle::Conventional => format!("{PROMPT}\n{CONVENTIONAL_PROMPT}"),
+    };
+    if subject.trim().is_empty() {
+        format!("{prompt}\nHere are the changes in this commit:\n{diff_text}")
+    } else {
+        format!(
+            "{prompt}\nHere is the user's subject line:\n{subject}\nHere are the changes in this commit:\n{diff_text}\n"
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use git::{
@@ -5278,4 +5306,27 @@ mod tests {
             assert_eq!(current_message, "");
         });
     }
+
+    #[gpui::test]
+    fn test_commit_message_prompt(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            assert_eq!(
+                GitPanelSettings::get_global(cx).commit_message_style,
+                CommitMessageStyle::Conventional
+            );
+        });
+
+        let diff = "diff --git a/README.md b/README.md";
+        let standard = commit_message_prompt(CommitMessageStyle::Standard, "", diff);
+        assert!(!standard.contains("Conventional Commit"));
+        assert!(!standard.contains("subject line:"));
+        assert!(standard.ends_with(diff));
+
+        let conventional =
+            commit_message_prompt(CommitMessageStyle::Conventional, "fix(git): ", diff);
+        assert!(conventional.starts_with(&standard[..standard.find("\nHere are").unwrap()]));
+        assert!(conventional.contains("Format the subject line as a Conventional Commit"));
+        assert!(conventional.contains("Here is the user's subject line:\nfix(git): \n"));
+    }
 }
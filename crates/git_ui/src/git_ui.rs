@@ -3,7 +3,10 @@ use std::any::Any;
 use ::settings::Settings;
 use command_palette_hooks::CommandPaletteFilter;
 use commit_modal::CommitModal;
-use editor::{Editor, actions::DiffClipboardWithSelectionData};
+use editor::{
+    Editor,
+    actions::{DiffClipboardWithSelectionData, DiffExternalChangesData},
+};
 use ui::{
     Headline, HeadlineSize, Icon, IconName, IconSize, IntoElement, ParentElement, Render, Styled,
     StyledExt, div, h_flex, rems, v_flex,
@@ -36,6 +39,7 @@ mod commit_modal;
 pub mod commit_tooltip;
 mod commit_view;
 mod conflict_view;
+pub mod directory_diff_view;
 pub mod file_diff_view;
 pub mod git_panel;
 mod git_panel_settings;
@@ -220,6 +224,11 @@ pub fn init(cx: &mut App) {
                 };
             },
         );
+        workspace.register_action(|workspace, action: &DiffExternalChangesData, window, cx| {
+            if let Some(task) = TextDiffView::open_external_changes(action, workspace, window, cx) {
+                task.detach();
+            };
+        });
     })
     .detach();
 }
@@ -1,8 +1,12 @@
-//! TextDiffView currently provides a UI for displaying differences between the clipboard and selected text.
+//! TextDiffView provides a UI for displaying differences between a piece of text and a buffer, such as the
+//! clipboard and the selected text, or a file's contents before and after it changed on disk.
 
 use anyhow::Result;
 use buffer_diff::{BufferDiff, BufferDiffSnapshot};
-use editor::{Editor, EditorEvent, MultiBuffer, ToPoint, actions::DiffClipboardWithSelectionData};
+use editor::{
+    Editor, EditorEvent, MultiBuffer, ToPoint,
+    actions::{DiffClipboardWithSelectionData, DiffExternalChangesData},
+};
 use futures::{FutureExt, select_biased};
 use gpui::{
     AnyElement, AnyView, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter,
@@ -92,7 +96,6 @@ impl TextDiffView {
             clipboard_text.push_str("\n");
         }
 
-        let workspace = workspace.weak_handle();
         let diff_buffer = cx.new(|cx| BufferDiff::new(&source_buffer_snapshot.text, cx));
         let clipboard_buffer = build_clipboard_buffer(
             clipboard_text,
@@ -101,18 +104,81 @@ impl TextDiffView {
             cx,
         );
 
-        let task = window.spawn(cx, async move |cx| {
+        Some(Self::open_in_workspace(
+            clipboard_buffer,
+            "Clipboard",
+            true,
+            source_editor,
+            source_buffer,
+            expanded_selection_range,
+            diff_buffer,
+            workspace,
+            window,
+            cx,
+        ))
+    }
+
+    /// Opens a diff between a file's contents before it last changed on disk and its current contents.
+    pub fn open_external_changes(
+        diff_data: &DiffExternalChangesData,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let source_editor = diff_data.editor.clone();
+        let source_buffer = source_editor.read(cx).buffer().read(cx).as_singleton()?;
+        let source_buffer_snapshot = source_buffer.read(cx).snapshot();
+        let full_range = Point::zero()..source_buffer_snapshot.max_point();
+
+        let diff_buffer = cx.new(|cx| BufferDiff::new(&source_buffer_snapshot.text, cx));
+        let previous_buffer = build_clipboard_buffer(
+            diff_data.previous_text.clone(),
+            &source_buffer,
+            full_range.clone(),
+            cx,
+        );
+
+        Some(Self::open_in_workspace(
+            previous_buffer,
+            "Before External Change",
+            false,
+            source_editor,
+            source_buffer,
+            full_range,
+            diff_buffer,
+            workspace,
+            window,
+            cx,
+        ))
+    }
+
+    fn open_in_workspace(
+        base_buffer: Entity<Buffer>,
+        base_label: &'static str,
+        show_selection_location: bool,
+        source_editor: Entity<Editor>,
+        source_buffer: Entity<Buffer>,
+        source_range: Range<Point>,
+        diff_buffer: Entity<BufferDiff>,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let workspace = workspace.weak_handle();
+        window.spawn(cx, async move |cx| {
             let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
 
-            update_diff_buffer(&diff_buffer, &source_buffer, &clipboard_buffer, cx).await?;
+            update_diff_buffer(&diff_buffer, &source_buffer, &base_buffer, cx).await?;
 
             workspace.update_in(cx, |workspace, window, cx| {
                 let diff_view = cx.new(|cx| {
                     TextDiffView::new(
-                        clipboard_buffer,
+                        base_buffer,
+                        base_label,
+                        show_selection_location,
                         source_editor,
                         source_buffer,
-                        expanded_selection_range,
+                        source_range,
                         diff_buffer,
                         project,
                         window,
@@ -127,13 +193,13 @@ impl TextDiffView {
 
                 diff_view
             })
-        });
-
-        Some(task)
+        })
     }
 
     pub fn new(
         clipboard_buffer: Entity<Buffer>,
+        base_label: &str,
+        show_selection_location: bool,
         source_editor: Entity<Editor>,
         source_buffer: Entity<Buffer>,
         source_range: Range<Point>,
@@ -180,7 +246,9 @@ impl TextDiffView {
 
         let editor = source_editor.read(cx);
         let title = editor.buffer().read(cx).title(cx).to_string();
-        let selection_location_text = selection_location_text(editor, cx);
+        let selection_location_text = show_selection_location
+            .then(|| selection_location_text(editor, cx))
+            .flatten();
         let selection_location_title = selection_location_text
             .as_ref()
             .map(|text| format!("{} @ {}", title, text))
@@ -203,8 +271,8 @@ impl TextDiffView {
 
         Self {
             diff_editor,
-            title: format!("Clipboard ↔ {selection_location_title}").into(),
-            path: Some(format!("Clipboard ↔ {selection_location_path}").into()),
+            title: format!("{base_label} ↔ {selection_location_title}").into(),
+            path: Some(format!("{base_label} ↔ {selection_location_path}").into()),
             buffer_changes_tx,
             _recalculate_diff_task: cx.spawn(async move |_, cx| {
                 while buffer_changes_rx.recv().await.is_ok() {
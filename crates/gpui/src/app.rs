@@ -592,6 +592,7 @@ pub struct App {
     #[cfg(any(test, feature = "test-support", debug_assertions))]
     pub(crate) name: Option<&'static str>,
     quitting: bool,
+    reduce_motion: bool,
 }
 
 impl App {
@@ -663,6 +664,7 @@ impl App {
                 #[cfg(any(feature = "inspector", debug_assertions))]
                 inspector_element_registry: InspectorElementRegistry::default(),
                 quitting: false,
+                reduce_motion: false,
 
                 #[cfg(any(test, feature = "test-support", debug_assertions))]
                 name: None,
@@ -1152,6 +1154,29 @@ impl App {
         self.platform.should_auto_hide_scrollbars()
     }
 
+    /// Returns whether the user has asked the platform to minimize animations.
+    pub fn should_reduce_motion(&self) -> bool {
+        self.platform.should_reduce_motion()
+    }
+
+    /// Returns whether the user has asked the platform to increase contrast.
+    pub fn should_increase_contrast(&self) -> bool {
+        self.platform.should_increase_contrast()
+    }
+
+    /// Returns whether animations are skipped, showing their final state instead.
+    pub fn reduce_motion(&self) -> bool {
+        self.reduce_motion
+    }
+
+    /// Sets whether animations are skipped, showing their final state instead.
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        if self.reduce_motion != reduce_motion {
+            self.reduce_motion = reduce_motion;
+            self.refresh_windows();
+        }
+    }
+
     /// Restarts the application.
     pub fn restart(&mut self) {
         self.restart_observers
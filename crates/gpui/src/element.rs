@@ -351,6 +351,9 @@ impl<E: Element> Drawable<E> {
     fn request_layout(&mut self, window: &mut Window, cx: &mut App) -> LayoutId {
         match mem::take(&mut self.phase) {
             ElementDrawPhase::Start => {
+                if let Some(frame_profiler) = window.frame_profiler.as_mut() {
+                    frame_profiler.record_element();
+                }
                 let global_id = self.element.id().map(|element_id| {
                     window.element_id_stack.push(element_id);
                     GlobalElementId(window.element_id_stack.clone())
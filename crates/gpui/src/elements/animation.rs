@@ -143,13 +143,23 @@ impl<E: IntoElement + 'static> Element for AnimationElement<E> {
                 start: Instant::now(),
                 animation_ix: 0,
             });
-            let animation_ix = state.animation_ix;
+            let mut animation_ix = state.animation_ix;
 
             let mut delta = state.start.elapsed().as_secs_f32()
                 / self.animations[animation_ix].duration.as_secs_f32();
 
             let mut done = false;
-            if delta > 1.0 {
+            if cx.reduce_motion() {
+                // Without motion, one-shot animations show their final state right away, and
+                // repeating ones stay still.
+                done = true;
+                if self.animations.iter().all(|animation| animation.oneshot) {
+                    animation_ix = self.animations.len() - 1;
+                    delta = 1.0;
+                } else {
+                    delta = 0.0;
+                }
+            } else if delta > 1.0 {
                 if self.animations[animation_ix].oneshot {
                     if animation_ix >= self.animations.len() - 1 {
                         done = true;
@@ -261,3 +271,45 @@ mod easing {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    use crate::{
+        self as gpui, Animation, AnimationExt as _, TestAppContext, VisualTestContext, div, point,
+        px, size,
+    };
+
+    /// Draws an element with the given animation, returning the delta it was drawn with.
+    fn draw_animation(id: &'static str, animation: Animation, cx: &mut VisualTestContext) -> f32 {
+        let delta = Rc::new(Cell::new(None));
+        cx.draw(point(px(0.), px(0.)), size(px(10.), px(10.)), {
+            let delta = delta.clone();
+            move |_, _| {
+                div().with_animation(id, animation, move |div, animation_delta| {
+                    delta.set(Some(animation_delta));
+                    div
+                })
+            }
+        });
+        delta.get().expect("animation wasn't drawn")
+    }
+
+    #[gpui::test]
+    fn test_reduce_motion(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+
+        let oneshot = Animation::new(Duration::from_secs(60));
+        assert!(draw_animation("oneshot", oneshot.clone(), cx) < 0.5);
+
+        // With reduced motion, one-shot animations are drawn finished, and repeating ones are
+        // drawn still.
+        cx.update(|_, cx| cx.set_reduce_motion(true));
+        assert_eq!(draw_animation("reduced-oneshot", oneshot.clone(), cx), 1.0);
+        assert_eq!(
+            draw_animation("reduced-repeating", oneshot.repeat(), cx),
+            0.0
+        );
+    }
+}
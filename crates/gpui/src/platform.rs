@@ -259,6 +259,12 @@ pub(crate) trait Platform: 'static {
 
     fn set_cursor_style(&self, style: CursorStyle);
     fn should_auto_hide_scrollbars(&self) -> bool;
+    fn should_reduce_motion(&self) -> bool {
+        false
+    }
+    fn should_increase_contrast(&self) -> bool {
+        false
+    }
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     fn write_to_primary(&self, item: ClipboardItem);
@@ -768,6 +774,8 @@ pub(crate) trait PlatformAtlas: Send + Sync {
         build: &mut dyn FnMut() -> Result<Option<(Size<DevicePixels>, Cow<'a, [u8]>)>>,
     ) -> Result<Option<AtlasTile>>;
     fn remove(&self, key: &AtlasKey);
+    /// Returns the number of bytes of GPU memory used by the atlas's textures.
+    fn memory_usage(&self) -> usize;
 }
 
 struct AtlasTextureList<T> {
@@ -799,6 +807,11 @@ impl<T> AtlasTextureList<T> {
         self.textures.drain(..)
     }
 
+    #[allow(dead_code)]
+    fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.textures.iter().flatten()
+    }
+
     #[allow(dead_code)]
     fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
         self.textures.iter_mut().flatten()
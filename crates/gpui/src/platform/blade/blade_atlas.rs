@@ -124,6 +124,16 @@ impl PlatformAtlas for BladeAtlas {
             }
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        let lock = self.0.lock();
+        lock.storage
+            .monochrome_textures
+            .iter()
+            .chain(lock.storage.polychrome_textures.iter())
+            .map(BladeAtlasTexture::memory_usage)
+            .sum()
+    }
 }
 
 impl BladeAtlasState {
@@ -341,6 +351,11 @@ impl BladeAtlasTexture {
         self.format.block_info().size
     }
 
+    fn memory_usage(&self) -> usize {
+        let size = self.allocator.size();
+        size.width as usize * size.height as usize * self.bytes_per_pixel() as usize
+    }
+
     fn decrement_ref_count(&mut self) {
         self.live_atlas_keys -= 1;
     }
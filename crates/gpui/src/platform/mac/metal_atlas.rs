@@ -87,6 +87,15 @@ impl PlatformAtlas for MetalAtlas {
             }
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        let lock = self.0.lock();
+        lock.monochrome_textures
+            .iter()
+            .chain(lock.polychrome_textures.iter())
+            .map(MetalAtlasTexture::memory_usage)
+            .sum()
+    }
 }
 
 impl MetalAtlasState {
@@ -233,6 +242,11 @@ impl MetalAtlasTexture {
         }
     }
 
+    fn memory_usage(&self) -> usize {
+        let size = self.allocator.size();
+        size.width as usize * size.height as usize * self.bytes_per_pixel() as usize
+    }
+
     fn decrement_ref_count(&mut self) {
         self.live_atlas_keys -= 1;
     }
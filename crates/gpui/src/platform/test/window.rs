@@ -359,4 +359,8 @@ impl PlatformAtlas for TestAtlas {
         let mut state = self.0.lock();
         state.tiles.remove(key);
     }
+
+    fn memory_usage(&self) -> usize {
+        0
+    }
 }
@@ -118,6 +118,15 @@ impl PlatformAtlas for DirectXAtlas {
             }
         }
     }
+
+    fn memory_usage(&self) -> usize {
+        let lock = self.0.lock();
+        lock.monochrome_textures
+            .iter()
+            .chain(lock.polychrome_textures.iter())
+            .map(DirectXAtlasTexture::memory_usage)
+            .sum()
+    }
 }
 
 impl DirectXAtlasState {
@@ -243,6 +252,11 @@ impl DirectXAtlasState {
 }
 
 impl DirectXAtlasTexture {
+    fn memory_usage(&self) -> usize {
+        let size = self.allocator.size();
+        size.width as usize * size.height as usize * self.bytes_per_pixel as usize
+    }
+
     fn allocate(&mut self, size: Size<DevicePixels>) -> Option<AtlasTile> {
         let allocation = self.allocator.allocate(size.into())?;
         let tile = AtlasTile {
@@ -15,7 +15,7 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use smallvec::SmallVec;
 use windows::{
-    UI::ViewManagement::UISettings,
+    UI::ViewManagement::{AccessibilitySettings, UISettings},
     Win32::{
         Foundation::*,
         Graphics::{Direct3D11::ID3D11Device, Gdi::*},
@@ -567,6 +567,14 @@ impl Platform for WindowsPlatform {
         should_auto_hide_scrollbars().log_err().unwrap_or(false)
     }
 
+    fn should_reduce_motion(&self) -> bool {
+        should_reduce_motion().log_err().unwrap_or(false)
+    }
+
+    fn should_increase_contrast(&self) -> bool {
+        should_increase_contrast().log_err().unwrap_or(false)
+    }
+
     fn write_to_clipboard(&self, item: ClipboardItem) {
         write_to_clipboard(item);
     }
@@ -1006,6 +1014,18 @@ fn should_auto_hide_scrollbars() -> Result<bool> {
     Ok(ui_settings.AutoHideScrollBars()?)
 }
 
+#[inline]
+fn should_reduce_motion() -> Result<bool> {
+    let ui_settings = UISettings::new()?;
+    Ok(!ui_settings.AnimationsEnabled()?)
+}
+
+#[inline]
+fn should_increase_contrast() -> Result<bool> {
+    let accessibility_settings = AccessibilitySettings::new()?;
+    Ok(accessibility_settings.HighContrast()?)
+}
+
 fn check_device_lost(device: &ID3D11Device) -> bool {
     let device_state = unsafe { device.GetDeviceRemovedReason() };
     match device_state {
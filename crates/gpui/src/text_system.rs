@@ -522,6 +522,12 @@ impl WindowTextSystem {
         self.line_layout_cache.finish_frame()
     }
 
+    /// Returns the number of lines whose layout was reused from the cache and the number that
+    /// were shaped since the last call.
+    pub(crate) fn take_layout_cache_stats(&self) -> (usize, usize) {
+        self.line_layout_cache.take_cache_stats()
+    }
+
     /// Layout the given line of text, at the given font_size.
     /// Subsets of the line can be styled independently with the `runs` parameter.
     /// Generally, you should prefer to use [`Self::shape_line`] instead, which
@@ -6,7 +6,10 @@ use std::{
     borrow::Borrow,
     hash::{Hash, Hasher},
     ops::Range,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
 use super::LineWrapper;
@@ -393,6 +396,10 @@ pub(crate) struct LineLayoutCache {
     previous_frame: Mutex<FrameCache>,
     current_frame: RwLock<FrameCache>,
     platform_text_system: Arc<dyn PlatformTextSystem>,
+    /// The number of lines whose layout was reused, for frame profiling.
+    cache_hits: AtomicUsize,
+    /// The number of lines that were shaped by the platform text system, for frame profiling.
+    cache_misses: AtomicUsize,
 }
 
 #[derive(Default)]
@@ -415,9 +422,20 @@ impl LineLayoutCache {
             previous_frame: Mutex::default(),
             current_frame: RwLock::default(),
             platform_text_system,
+            cache_hits: AtomicUsize::new(0),
+            cache_misses: AtomicUsize::new(0),
         }
     }
 
+    /// Returns the number of lines whose layout was reused and the number that were shaped since
+    /// the last call.
+    pub fn take_cache_stats(&self) -> (usize, usize) {
+        (
+            self.cache_hits.swap(0, Ordering::Relaxed),
+            self.cache_misses.swap(0, Ordering::Relaxed),
+        )
+    }
+
     pub fn layout_index(&self) -> LineLayoutIndex {
         let frame = self.current_frame.read();
         LineLayoutIndex {
@@ -487,11 +505,13 @@ impl LineLayoutCache {
 
         let current_frame = self.current_frame.upgradable_read();
         if let Some(layout) = current_frame.wrapped_lines.get(key) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
             return layout.clone();
         }
 
         let previous_frame_entry = self.previous_frame.lock().wrapped_lines.remove_entry(key);
         if let Some((key, layout)) = previous_frame_entry {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
             let mut current_frame = RwLockUpgradableReadGuard::upgrade(current_frame);
             current_frame
                 .wrapped_lines
@@ -564,15 +584,18 @@ impl LineLayoutCache {
 
         let current_frame = self.current_frame.upgradable_read();
         if let Some(layout) = current_frame.lines.get(key) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
             return layout.clone();
         }
 
         let mut current_frame = RwLockUpgradableReadGuard::upgrade(current_frame);
         if let Some((key, layout)) = self.previous_frame.lock().lines.remove_entry(key) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
             current_frame.lines.insert(key.clone(), layout.clone());
             current_frame.used_lines.push(key);
             layout
         } else {
+            self.cache_misses.fetch_add(1, Ordering::Relaxed);
             let text = SharedString::from(text);
             let mut layout = self
                 .platform_text_system
@@ -188,8 +188,9 @@ impl LineWrapper {
         matches!(c, '⋯')
     }
 
+    /// The width of the given character with this wrapper's font and font size.
     #[inline(always)]
-    fn width_for_char(&mut self, c: char) -> Pixels {
+    pub fn width_for_char(&mut self, c: char) -> Pixels {
         if (c as u32) < 128 {
             if let Some(cached_width) = self.cached_ascii_char_widths[c as usize] {
                 cached_width
@@ -9,7 +9,7 @@ use collections::FxHashSet;
 use refineable::Refineable;
 use std::mem;
 use std::rc::Rc;
-use std::{any::TypeId, fmt, ops::Range};
+use std::{any::TypeId, fmt, ops::Range, time::Instant};
 
 struct AnyViewState {
     prepaint_range: Range<PrepaintStateIndex>,
@@ -44,7 +44,11 @@ impl<V: Render> Element for Entity<V> {
         window: &mut Window,
         cx: &mut App,
     ) -> (LayoutId, Self::RequestLayoutState) {
+        let start = window.is_frame_profiling_enabled().then(Instant::now);
         let mut element = self.update(cx, |view, cx| view.render(window, cx).into_any_element());
+        if let Some(start) = start {
+            window.record_view_render(std::any::type_name::<V>(), start, start.elapsed());
+        }
         let layout_id = window.with_rendered_view(self.entity_id(), |window| {
             element.request_layout(window, cx)
         });
@@ -352,6 +356,7 @@ impl std::fmt::Debug for AnyWeakView {
 
 mod any_view {
     use crate::{AnyElement, AnyView, App, IntoElement, Render, Window};
+    use std::time::Instant;
 
     pub(crate) fn render<V: 'static + Render>(
         view: &AnyView,
@@ -359,7 +364,12 @@ mod any_view {
         cx: &mut App,
     ) -> AnyElement {
         let view = view.clone().downcast::<V>().unwrap();
-        view.update(cx, |view, cx| view.render(window, cx).into_any_element())
+        let start = window.is_frame_profiling_enabled().then(Instant::now);
+        let element = view.update(cx, |view, cx| view.render(window, cx).into_any_element());
+        if let Some(start) = start {
+            window.record_view_render(std::any::type_name::<V>(), start, start.elapsed());
+        }
+        element
     }
 }
 
@@ -53,9 +53,12 @@ use util::post_inc;
 use util::{ResultExt, measure};
 use uuid::Uuid;
 
+mod frame_profiler;
 mod prompts;
 
 use crate::util::atomic_incr_if_not_zero;
+use frame_profiler::FrameProfiler;
+pub use frame_profiler::{FrameStats, ViewRenderStats};
 pub use prompts::*;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1024.), px(700.));
@@ -872,6 +875,7 @@ pub struct Window {
     pub(crate) pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
     pub(crate) client_inset: Option<Pixels>,
+    pub(crate) frame_profiler: Option<FrameProfiler>,
     #[cfg(any(feature = "inspector", debug_assertions))]
     inspector: Option<Entity<Inspector>>,
 }
@@ -1256,6 +1260,7 @@ impl Window {
             prompt: None,
             client_inset: None,
             image_cache_stack: Vec::new(),
+            frame_profiler: None,
             #[cfg(any(feature = "inspector", debug_assertions))]
             inspector: None,
         })
@@ -1348,6 +1353,16 @@ impl Window {
         view
     }
 
+    /// Replaces the root entity of the window with an existing one, keeping the previous root
+    /// alive if it's referenced elsewhere.
+    pub fn set_root<E>(&mut self, view: Entity<E>)
+    where
+        E: 'static + Render,
+    {
+        self.root = Some(view.into());
+        self.refresh();
+    }
+
     /// Returns the root entity of the window, if it has one.
     pub fn root<E>(&self) -> Option<Option<Entity<E>>>
     where
@@ -1912,6 +1927,12 @@ impl Window {
     /// the contents of the new [`Scene`], use [`Self::present`].
     #[profiling::function]
     pub fn draw(&mut self, cx: &mut App) -> ArenaClearNeeded {
+        let frame_start = Instant::now();
+        if let Some(frame_profiler) = self.frame_profiler.as_mut() {
+            frame_profiler.begin_frame(frame_start);
+            // Discard the text that was laid out outside of drawing frames.
+            self.text_system.take_layout_cache_stats();
+        }
         self.invalidate_entities();
         cx.entities.clear_accessed();
         debug_assert!(self.rendered_entity_stack.is_empty());
@@ -1977,6 +1998,11 @@ impl Window {
         self.invalidator.set_phase(DrawPhase::None);
         self.needs_present.set(true);
 
+        if let Some(frame_profiler) = self.frame_profiler.as_mut() {
+            let (cache_hits, cache_misses) = self.text_system.take_layout_cache_stats();
+            frame_profiler.end_frame(frame_start.elapsed(), cache_hits, cache_misses);
+        }
+
         ArenaClearNeeded
     }
 
@@ -2011,6 +2037,7 @@ impl Window {
     }
 
     fn draw_roots(&mut self, cx: &mut App) {
+        let layout_start = Instant::now();
         self.invalidator.set_phase(DrawPhase::Prepaint);
         self.tooltip_bounds.take();
 
@@ -2065,6 +2092,7 @@ impl Window {
         self.mouse_hit_test = self.next_frame.hit_test(self.mouse_position);
 
         // Now actually paint the elements.
+        let paint_start = Instant::now();
         self.invalidator.set_phase(DrawPhase::Paint);
         root_element.paint(self, cx);
 
@@ -2083,6 +2111,10 @@ impl Window {
 
         #[cfg(any(feature = "inspector", debug_assertions))]
         self.paint_inspector_hitbox(cx);
+
+        if let Some(frame_profiler) = self.frame_profiler.as_mut() {
+            frame_profiler.record_phases(paint_start - layout_start, paint_start.elapsed());
+        }
     }
 
     fn prepaint_tooltip(&mut self, cx: &mut App) -> Option<AnyElement> {
@@ -3210,6 +3242,9 @@ impl Window {
         cx.layout_id_buffer.clear();
         cx.layout_id_buffer.extend(children);
         let rem_size = self.rem_size();
+        if let Some(frame_profiler) = self.frame_profiler.as_mut() {
+            frame_profiler.record_layout_node();
+        }
 
         self.layout_engine
             .as_mut()
@@ -3236,6 +3271,9 @@ impl Window {
         self.invalidator.debug_assert_prepaint();
 
         let rem_size = self.rem_size();
+        if let Some(frame_profiler) = self.frame_profiler.as_mut() {
+            frame_profiler.record_layout_node();
+        }
         self.layout_engine
             .as_mut()
             .unwrap()
@@ -4407,6 +4445,51 @@ impl Window {
             .set_tabbing_identifier(tabbing_identifier)
     }
 
+    /// Enables or disables recording statistics about the frames drawn by this window, which can
+    /// be read with [`Self::frame_stats`].
+    pub fn set_frame_profiling_enabled(&mut self, enabled: bool) {
+        if enabled != self.frame_profiler.is_some() {
+            self.frame_profiler = enabled.then(FrameProfiler::new);
+        }
+    }
+
+    /// Returns true if statistics about the frames drawn by this window are being recorded.
+    pub fn is_frame_profiling_enabled(&self) -> bool {
+        self.frame_profiler.is_some()
+    }
+
+    /// Returns statistics about the most recently drawn frames, oldest first, if frame profiling
+    /// is enabled.
+    pub fn frame_stats(&self) -> impl DoubleEndedIterator<Item = &FrameStats> {
+        self.frame_profiler
+            .iter()
+            .flat_map(|frame_profiler| frame_profiler.frames())
+    }
+
+    /// Returns the recorded frames as JSON in the Trace Event Format, which can be loaded by
+    /// `chrome://tracing` and Perfetto, if frame profiling is enabled.
+    pub fn frame_trace(&self) -> Option<String> {
+        let frame_profiler = self.frame_profiler.as_ref()?;
+        serde_json::to_string(&frame_profiler.chrome_trace()).log_err()
+    }
+
+    /// Returns the number of bytes of GPU memory used by the textures that cache the window's
+    /// glyphs, images, and SVGs.
+    pub fn sprite_atlas_memory_usage(&self) -> usize {
+        self.sprite_atlas.memory_usage()
+    }
+
+    pub(crate) fn record_view_render(
+        &mut self,
+        view_type: &'static str,
+        start: Instant,
+        duration: Duration,
+    ) {
+        if let Some(frame_profiler) = self.frame_profiler.as_mut() {
+            frame_profiler.record_view_render(view_type, start, duration);
+        }
+    }
+
     /// Toggles the inspector mode on this window.
     #[cfg(any(feature = "inspector", debug_assertions))]
     pub fn toggle_inspector(&mut self, cx: &mut App) {
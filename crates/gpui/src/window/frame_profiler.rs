@@ -0,0 +1,256 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use serde_json::{Value, json};
+
+/// The number of frames whose statistics are kept.
+const MAX_FRAMES: usize = 600;
+
+/// The number of views whose render times are kept for each frame.
+const MAX_VIEWS_PER_FRAME: usize = 8;
+
+/// Statistics about a frame drawn by a window, recorded while frame profiling is enabled with
+/// [`Window::set_frame_profiling_enabled`](crate::Window::set_frame_profiling_enabled).
+#[derive(Clone, Debug, Default)]
+pub struct FrameStats {
+    /// When the frame started drawing, relative to when profiling was enabled.
+    pub start: Duration,
+    /// The time taken to draw the frame.
+    pub duration: Duration,
+    /// The time taken to render views, lay out, and prepaint elements.
+    pub layout_duration: Duration,
+    /// The time taken to paint elements.
+    pub paint_duration: Duration,
+    /// The number of elements that were laid out.
+    pub element_count: usize,
+    /// The number of nodes in the layout tree.
+    pub layout_node_count: usize,
+    /// The number of lines of text whose shaping was reused from the cache.
+    pub text_shaping_cache_hits: usize,
+    /// The number of lines of text that were shaped.
+    pub text_shaping_cache_misses: usize,
+    /// The views that took the longest to render, slowest first.
+    pub slowest_views: Vec<ViewRenderStats>,
+}
+
+impl FrameStats {
+    /// The fraction of lines of text whose shaping was reused from the cache, or `None` if no
+    /// text was laid out.
+    pub fn text_shaping_cache_hit_rate(&self) -> Option<f32> {
+        let total = self.text_shaping_cache_hits + self.text_shaping_cache_misses;
+        (total > 0).then(|| self.text_shaping_cache_hits as f32 / total as f32)
+    }
+}
+
+/// The time taken to render a view in a frame.
+#[derive(Clone, Debug)]
+pub struct ViewRenderStats {
+    /// The type name of the view.
+    pub view_type: &'static str,
+    /// When the view started rendering, relative to when profiling was enabled.
+    pub start: Duration,
+    /// The time taken by the view's `render` method, not including the views that it contains.
+    pub duration: Duration,
+}
+
+pub(crate) struct FrameProfiler {
+    enabled_at: Instant,
+    frames: VecDeque<FrameStats>,
+    current_frame: FrameStats,
+}
+
+impl FrameProfiler {
+    pub fn new() -> Self {
+        Self {
+            enabled_at: Instant::now(),
+            frames: VecDeque::new(),
+            current_frame: FrameStats::default(),
+        }
+    }
+
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = &FrameStats> + ExactSizeIterator {
+        self.frames.iter()
+    }
+
+    pub fn begin_frame(&mut self, start: Instant) {
+        self.current_frame = FrameStats {
+            start: start.duration_since(self.enabled_at),
+            ..Default::default()
+        };
+    }
+
+    pub fn record_element(&mut self) {
+        self.current_frame.element_count += 1;
+    }
+
+    pub fn record_layout_node(&mut self) {
+        self.current_frame.layout_node_count += 1;
+    }
+
+    pub fn record_phases(&mut self, layout_duration: Duration, paint_duration: Duration) {
+        self.current_frame.layout_duration = layout_duration;
+        self.current_frame.paint_duration = paint_duration;
+    }
+
+    pub fn record_view_render(
+        &mut self,
+        view_type: &'static str,
+        start: Instant,
+        duration: Duration,
+    ) {
+        let view = ViewRenderStats {
+            view_type,
+            start: start.duration_since(self.enabled_at),
+            duration,
+        };
+        let views = &mut self.current_frame.slowest_views;
+        let index = views.partition_point(|slower| slower.duration >= view.duration);
+        if index < MAX_VIEWS_PER_FRAME {
+            views.insert(index, view);
+            views.truncate(MAX_VIEWS_PER_FRAME);
+        }
+    }
+
+    pub fn end_frame(&mut self, duration: Duration, cache_hits: usize, cache_misses: usize) {
+        let mut frame = std::mem::take(&mut self.current_frame);
+        frame.duration = duration;
+        frame.text_shaping_cache_hits = cache_hits;
+        frame.text_shaping_cache_misses = cache_misses;
+        if self.frames.len() == MAX_FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Returns the recorded frames in the Trace Event Format, which can be loaded by
+    /// `chrome://tracing` and Perfetto.
+    pub fn chrome_trace(&self) -> Value {
+        chrome_trace(self.frames.iter())
+    }
+}
+
+fn chrome_trace<'a>(frames: impl Iterator<Item = &'a FrameStats>) -> Value {
+    fn event(name: &str, start: Duration, duration: Duration, args: Value) -> Value {
+        json!({
+            "name": name,
+            "ph": "X",
+            "ts": start.as_nanos() as f64 / 1000.0,
+            "dur": duration.as_nanos() as f64 / 1000.0,
+            "pid": 1,
+            "tid": 1,
+            "args": args,
+        })
+    }
+
+    let mut events = Vec::new();
+    for frame in frames {
+        events.push(event(
+            "Frame",
+            frame.start,
+            frame.duration,
+            json!({
+                "elements": frame.element_count,
+                "layout_nodes": frame.layout_node_count,
+                "text_shaping_cache_hits": frame.text_shaping_cache_hits,
+                "text_shaping_cache_misses": frame.text_shaping_cache_misses,
+            }),
+        ));
+        events.push(event(
+            "Layout",
+            frame.start,
+            frame.layout_duration,
+            json!({}),
+        ));
+        events.push(event(
+            "Paint",
+            frame.start + frame.layout_duration,
+            frame.paint_duration,
+            json!({}),
+        ));
+        for view in &frame.slowest_views {
+            events.push(event(view.view_type, view.start, view.duration, json!({})));
+        }
+    }
+    json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slowest_views() {
+        let mut profiler = FrameProfiler::new();
+        let start = Instant::now();
+        profiler.begin_frame(start);
+        for millis in [3, 9, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5] {
+            profiler.record_view_render("View", start, Duration::from_millis(millis));
+        }
+        profiler.end_frame(Duration::from_millis(5), 3, 1);
+
+        let frame = profiler.frames().next().unwrap();
+        assert_eq!(frame.slowest_views.len(), MAX_VIEWS_PER_FRAME);
+        assert_eq!(
+            frame
+                .slowest_views
+                .iter()
+                .map(|view| view.duration.as_millis())
+                .collect::<Vec<_>>(),
+            [9, 9, 6, 5, 5, 5, 4, 3]
+        );
+        assert_eq!(frame.text_shaping_cache_hit_rate(), Some(0.75));
+    }
+
+    #[test]
+    fn test_frame_history_is_bounded() {
+        let mut profiler = FrameProfiler::new();
+        for _ in 0..MAX_FRAMES + 10 {
+            profiler.begin_frame(Instant::now());
+            profiler.end_frame(Duration::ZERO, 0, 0);
+        }
+        assert_eq!(profiler.frames().len(), MAX_FRAMES);
+        assert_eq!(
+            profiler
+                .frames()
+                .next()
+                .unwrap()
+                .text_shaping_cache_hit_rate(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_chrome_trace() {
+        let frame = FrameStats {
+            start: Duration::from_millis(10),
+            duration: Duration::from_millis(4),
+            layout_duration: Duration::from_millis(3),
+            paint_duration: Duration::from_millis(1),
+            element_count: 12,
+            layout_node_count: 10,
+            text_shaping_cache_hits: 5,
+            text_shaping_cache_misses: 2,
+            slowest_views: vec![ViewRenderStats {
+                view_type: "workspace::Workspace",
+                start: Duration::from_millis(10),
+                duration: Duration::from_millis(2),
+            }],
+        };
+        let trace = chrome_trace([frame].iter());
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0]["name"], "Frame");
+        assert_eq!(events[0]["ph"], "X");
+        assert_eq!(events[0]["ts"], 10_000.0);
+        assert_eq!(events[0]["dur"], 4_000.0);
+        assert_eq!(events[0]["args"]["elements"], 12);
+        assert_eq!(events[2]["name"], "Paint");
+        assert_eq!(events[2]["ts"], 13_000.0);
+        assert_eq!(events[3]["name"], "workspace::Workspace");
+    }
+}
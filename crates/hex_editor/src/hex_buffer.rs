@@ -0,0 +1,246 @@
+use std::ops::Range;
+
+/// The number of bytes shown on each row of the hex editor.
+pub const BYTES_PER_ROW: usize = 16;
+
+/// An edit that replaced the bytes at `offset` with `new`, so that it can be undone.
+#[derive(Clone, Debug)]
+struct Edit {
+    offset: usize,
+    old: Vec<u8>,
+    new: Vec<u8>,
+}
+
+/// The bytes of a binary file, along with their undo history.
+pub struct HexBuffer {
+    bytes: Vec<u8>,
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+    /// The length of the undo stack when the bytes were last loaded or saved, or `None` if that
+    /// state can no longer be reached by undoing or redoing.
+    saved_undo_depth: Option<usize>,
+}
+
+impl HexBuffer {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            saved_undo_depth: Some(0),
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.saved_undo_depth != Some(self.undo_stack.len())
+    }
+
+    pub fn did_save(&mut self) {
+        self.saved_undo_depth = Some(self.undo_stack.len());
+    }
+
+    /// Replaces the bytes with the ones that were reloaded from disk, discarding the undo history.
+    pub fn reload(&mut self, bytes: Vec<u8>) {
+        *self = Self::new(bytes);
+    }
+
+    /// Replaces the given range with the given bytes.
+    pub fn replace(&mut self, range: Range<usize>, new: Vec<u8>) {
+        let range = range.start.min(self.len())..range.end.min(self.len());
+        let old = self.bytes[range.clone()].to_vec();
+        if old == new {
+            return;
+        }
+
+        if self
+            .saved_undo_depth
+            .is_some_and(|depth| depth > self.undo_stack.len())
+        {
+            self.saved_undo_depth = None;
+        }
+        self.redo_stack.clear();
+        self.bytes.splice(range.clone(), new.iter().copied());
+        self.undo_stack.push(Edit {
+            offset: range.start,
+            old,
+            new,
+        });
+    }
+
+    /// Undoes the last edit, and returns the range of the bytes it restored.
+    pub fn undo(&mut self) -> Option<Range<usize>> {
+        let edit = self.undo_stack.pop()?;
+        let range = edit.offset..edit.offset + edit.new.len();
+        self.bytes.splice(range, edit.old.iter().copied());
+        let restored = edit.offset..edit.offset + edit.old.len();
+        self.redo_stack.push(edit);
+        Some(restored)
+    }
+
+    /// Redoes the last undone edit, and returns the range of the bytes it restored.
+    pub fn redo(&mut self) -> Option<Range<usize>> {
+        let edit = self.redo_stack.pop()?;
+        let range = edit.offset..edit.offset + edit.old.len();
+        self.bytes.splice(range, edit.new.iter().copied());
+        let restored = edit.offset..edit.offset + edit.new.len();
+        self.undo_stack.push(edit);
+        Some(restored)
+    }
+}
+
+/// Returns the bytes to search for.
+///
+/// Hex byte pairs separated by whitespace (`7f 45 4c 46`) or prefixed with `0x`
+/// (`0x7f454c46`) are searched for as bytes, and anything else as a string.
+pub fn search_bytes(query: &str) -> Vec<u8> {
+    parse_hex_bytes(query).unwrap_or_else(|| query.as_bytes().to_vec())
+}
+
+fn parse_hex_bytes(query: &str) -> Option<Vec<u8>> {
+    let query = query.trim();
+    let pairs = if let Some(digits) = query
+        .strip_prefix("0x")
+        .or_else(|| query.strip_prefix("0X"))
+    {
+        if digits.len() % 2 != 0 {
+            return None;
+        }
+        (0..digits.len())
+            .step_by(2)
+            .map(|ix| digits.get(ix..ix + 2))
+            .collect::<Option<Vec<_>>>()?
+    } else if query.contains(char::is_whitespace) {
+        query.split_whitespace().collect()
+    } else {
+        return None;
+    };
+
+    pairs
+        .into_iter()
+        .map(|pair| {
+            if pair.len() == 2 {
+                u8::from_str_radix(pair, 16).ok()
+            } else {
+                None
+            }
+        })
+        .collect::<Option<Vec<_>>>()
+        .filter(|bytes| !bytes.is_empty())
+}
+
+/// Returns the ranges of the non-overlapping occurrences of the needle in the haystack.
+pub fn find_all(haystack: &[u8], needle: &[u8], case_sensitive: bool) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if needle.is_empty() {
+        return matches;
+    }
+
+    let mut offset = 0;
+    while offset + needle.len() <= haystack.len() {
+        let candidate = &haystack[offset..offset + needle.len()];
+        let is_match = if case_sensitive {
+            candidate == needle
+        } else {
+            candidate.eq_ignore_ascii_case(needle)
+        };
+        if is_match {
+            matches.push(offset..offset + needle.len());
+            offset += needle.len();
+        } else {
+            offset += 1;
+        }
+    }
+    matches
+}
+
+/// Formats the given bytes as space-separated hex pairs.
+pub fn format_hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the character shown for the given byte in the ASCII column.
+pub fn ascii_char(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut buffer = HexBuffer::new(vec![0x00, 0x11, 0x22]);
+        assert!(!buffer.is_dirty());
+
+        buffer.replace(1..2, vec![0xff]);
+        buffer.replace(3..3, vec![0x33, 0x44]);
+        assert_eq!(buffer.bytes(), [0x00, 0xff, 0x22, 0x33, 0x44]);
+        assert!(buffer.is_dirty());
+
+        assert_eq!(buffer.undo(), Some(3..3));
+        assert_eq!(buffer.undo(), Some(1..2));
+        assert_eq!(buffer.bytes(), [0x00, 0x11, 0x22]);
+        assert!(!buffer.is_dirty());
+        assert_eq!(buffer.undo(), None);
+
+        assert_eq!(buffer.redo(), Some(1..2));
+        assert_eq!(buffer.bytes(), [0x00, 0xff, 0x22]);
+        buffer.did_save();
+        assert!(!buffer.is_dirty());
+
+        // The saved state can't be reached anymore once the redo stack is discarded.
+        buffer.undo();
+        buffer.replace(0..1, vec![0xaa]);
+        assert!(buffer.is_dirty());
+        assert_eq!(buffer.redo(), None);
+        buffer.undo();
+        assert!(buffer.is_dirty());
+    }
+
+    #[test]
+    fn test_search() {
+        assert_eq!(search_bytes("7f 45 4C 46"), [0x7f, 0x45, 0x4c, 0x46]);
+        assert_eq!(search_bytes("0x7f454c46"), [0x7f, 0x45, 0x4c, 0x46]);
+        assert_eq!(search_bytes("cafe"), b"cafe");
+        assert_eq!(search_bytes("0x7f4"), b"0x7f4");
+        assert_eq!(search_bytes("hello world"), b"hello world");
+
+        let haystack = b"ELF elf ELFELF";
+        assert_eq!(find_all(haystack, b"ELF", true), [0..3, 8..11, 11..14]);
+        assert_eq!(
+            find_all(haystack, b"elf", false),
+            [0..3, 4..7, 8..11, 11..14]
+        );
+        assert_eq!(find_all(b"aaaa", b"aa", true), [0..2, 2..4]);
+        assert!(find_all(haystack, b"", true).is_empty());
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_hex_bytes(&[0xde, 0xad, 0x0b]), "de ad 0b");
+        assert_eq!(ascii_char(b'A'), 'A');
+        assert_eq!(ascii_char(b' '), ' ');
+        assert_eq!(ascii_char(0x00), '.');
+        assert_eq!(ascii_char(0xff), '.');
+    }
+}
@@ -0,0 +1,733 @@
+mod hex_buffer;
+mod selection_info;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{
+    AnyElement, App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    KeyDownEvent, ScrollStrategy, Subscription, Task, UniformListScrollHandle, Window, actions,
+    uniform_list,
+};
+use project::{Project, ProjectEntryId, ProjectPath, search::SearchQuery};
+use ui::prelude::*;
+use workspace::{
+    ItemHandle, Workspace, WorkspaceId,
+    invalid_buffer_view::InvalidBufferView,
+    item::{Item, ItemEvent, SaveOptions, TabContentParams},
+    notifications::DetachAndPromptErr as _,
+    searchable::{Direction, SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
+};
+use zed_actions::hex_editor::ReopenAsHex;
+
+use crate::hex_buffer::{
+    BYTES_PER_ROW, HexBuffer, ascii_char, find_all, format_hex_bytes, search_bytes,
+};
+pub use crate::selection_info::HexSelectionInfo;
+
+actions!(
+    hex_editor,
+    [
+        /// Moves the cursor to the previous byte.
+        MoveLeft,
+        /// Moves the cursor to the next byte.
+        MoveRight,
+        /// Moves the cursor to the previous row.
+        MoveUp,
+        /// Moves the cursor to the next row.
+        MoveDown,
+        /// Extends the selection to the previous byte.
+        SelectLeft,
+        /// Extends the selection to the next byte.
+        SelectRight,
+        /// Extends the selection to the previous row.
+        SelectUp,
+        /// Extends the selection to the next row.
+        SelectDown,
+        /// Switches the cursor between the hex and ASCII columns.
+        SwitchColumn,
+        /// Deletes the selected bytes.
+        Delete,
+        /// Undoes the last edit.
+        Undo,
+        /// Redoes the last undone edit.
+        Redo,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(reopen_as_hex);
+    })
+    .detach();
+}
+
+/// Opens the file of the active item in a hex editor, replacing the active item if it has no
+/// unsaved changes.
+fn reopen_as_hex(
+    workspace: &mut Workspace,
+    _: &ReopenAsHex,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_item) = workspace.active_item(cx) else {
+        return;
+    };
+    if active_item.downcast::<HexEditor>().is_some() {
+        return;
+    }
+
+    let project = workspace.project().clone();
+    let project_path = match active_item.downcast::<InvalidBufferView>() {
+        Some(invalid_buffer_view) => project
+            .read(cx)
+            .find_project_path(&invalid_buffer_view.read(cx).abs_path, cx),
+        None => active_item.project_path(cx),
+    };
+    let Some(project_path) = project_path else {
+        return;
+    };
+
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items_of_type::<HexEditor>()
+        .find(|editor| editor.read(cx).file.read(cx).project_path == project_path);
+    if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, window, cx);
+        return;
+    }
+
+    let open_file = HexFile::open(&project, project_path, cx);
+    let pane = workspace.active_pane().clone();
+    let replaced_item_id = (!active_item.is_dirty(cx)).then(|| active_item.item_id());
+    cx.spawn_in(window, async move |_, cx| {
+        let file = open_file.await?;
+        pane.update_in(cx, |pane, window, cx| {
+            let editor = cx.new(|cx| HexEditor::new(file, project, cx));
+            let index = pane.active_item_index() + 1;
+            pane.add_item(Box::new(editor), true, true, Some(index), window, cx);
+            if let Some(replaced_item_id) = replaced_item_id {
+                pane.remove_item(replaced_item_id, false, false, window, cx);
+            }
+        })
+    })
+    .detach_and_prompt_err("Failed to open file as hex", window, cx, |_, _, _| None);
+}
+
+/// The bytes of a file that is open in a hex editor, shared between the editors of the file.
+pub struct HexFile {
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    buffer: HexBuffer,
+}
+
+impl HexFile {
+    fn open(
+        project: &Entity<Project>,
+        project_path: ProjectPath,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let entry_id = project
+            .read(cx)
+            .entry_for_path(&project_path, cx)
+            .map(|entry| entry.id);
+        let load_bytes = Self::load_bytes(project, &project_path, cx);
+        cx.spawn(async move |cx| {
+            let bytes = load_bytes.await?;
+            cx.new(|_| Self {
+                project_path,
+                entry_id,
+                buffer: HexBuffer::new(bytes),
+            })
+        })
+    }
+
+    fn load_bytes(
+        project: &Entity<Project>,
+        project_path: &ProjectPath,
+        cx: &mut App,
+    ) -> Task<Result<Vec<u8>>> {
+        let Some(worktree) = project
+            .read(cx)
+            .worktree_for_id(project_path.worktree_id, cx)
+        else {
+            return Task::ready(Err(anyhow!("worktree not found for {project_path:?}")));
+        };
+        let load_file = worktree.update(cx, |worktree, cx| {
+            worktree.load_binary_file(&project_path.path, cx)
+        });
+        cx.background_spawn(async move { Ok(load_file.await?.content) })
+    }
+}
+
+impl project::ProjectItem for HexFile {
+    fn try_open(
+        _: &Entity<Project>,
+        _: &ProjectPath,
+        _: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        // Files are only opened as hex explicitly, with `hex_editor::ReopenAsHex`.
+        None
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.buffer.is_dirty()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Hex,
+    Ascii,
+}
+
+/// Shows the bytes of a file as offset, hex, and ASCII columns, and allows editing them.
+pub struct HexEditor {
+    file: Entity<HexFile>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    /// The offsets of the bytes at either end of the selection. The head is the cursor.
+    anchor: usize,
+    head: usize,
+    column: Column,
+    /// Whether the high nibble of the byte under the cursor was just typed.
+    pending_nibble: bool,
+    search_matches: Vec<Range<usize>>,
+    scroll_handle: UniformListScrollHandle,
+    _subscription: Subscription,
+}
+
+impl HexEditor {
+    fn new(file: Entity<HexFile>, project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let _subscription = cx.observe(&file, |this, file, cx| {
+            let len = file.read(cx).buffer.len();
+            this.anchor = this.anchor.min(len);
+            this.head = this.head.min(len);
+            cx.emit(SearchEvent::MatchesInvalidated);
+            cx.emit(ItemEvent::UpdateTab);
+            cx.notify();
+        });
+        Self {
+            file,
+            project,
+            focus_handle: cx.focus_handle(),
+            anchor: 0,
+            head: 0,
+            column: Column::Hex,
+            pending_nibble: false,
+            search_matches: Vec::new(),
+            scroll_handle: UniformListScrollHandle::new(),
+            _subscription,
+        }
+    }
+
+    fn len(&self, cx: &App) -> usize {
+        self.file.read(cx).buffer.len()
+    }
+
+    /// Returns the offset of the cursor.
+    pub fn cursor(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the range of the selected bytes, which includes the byte under the cursor.
+    pub fn selected_range(&self, cx: &App) -> Range<usize> {
+        let start = self.anchor.min(self.head);
+        let end = (self.anchor.max(self.head) + 1).min(self.len(cx));
+        start..end.max(start)
+    }
+
+    fn select(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        self.anchor = range.start;
+        self.head = range.end.saturating_sub(1).max(range.start);
+        self.pending_nibble = false;
+        self.scroll_handle
+            .scroll_to_item(self.head / BYTES_PER_ROW, ScrollStrategy::Center);
+        cx.emit(SearchEvent::ActiveMatchChanged);
+        cx.notify();
+    }
+
+    fn move_cursor(&mut self, offset: usize, extend: bool, cx: &mut Context<Self>) {
+        self.head = offset.min(self.len(cx));
+        if !extend {
+            self.anchor = self.head;
+        }
+        self.pending_nibble = false;
+        self.scroll_handle
+            .scroll_to_item(self.head / BYTES_PER_ROW, ScrollStrategy::Top);
+        cx.emit(SearchEvent::ActiveMatchChanged);
+        cx.notify();
+    }
+
+    fn move_left(&mut self, _: &MoveLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head.saturating_sub(1), false, cx);
+    }
+
+    fn move_right(&mut self, _: &MoveRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head + 1, false, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head.saturating_sub(BYTES_PER_ROW), false, cx);
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head + BYTES_PER_ROW, false, cx);
+    }
+
+    fn select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head.saturating_sub(1), true, cx);
+    }
+
+    fn select_right(&mut self, _: &SelectRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head + 1, true, cx);
+    }
+
+    fn select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head.saturating_sub(BYTES_PER_ROW), true, cx);
+    }
+
+    fn select_down(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.head + BYTES_PER_ROW, true, cx);
+    }
+
+    fn switch_column(&mut self, _: &SwitchColumn, _: &mut Window, cx: &mut Context<Self>) {
+        self.column = match self.column {
+            Column::Hex => Column::Ascii,
+            Column::Ascii => Column::Hex,
+        };
+        self.pending_nibble = false;
+        cx.notify();
+    }
+
+    fn delete(&mut self, _: &Delete, _: &mut Window, cx: &mut Context<Self>) {
+        let range = self.selected_range(cx);
+        if range.is_empty() {
+            return;
+        }
+        self.file.update(cx, |file, cx| {
+            file.buffer.replace(range.clone(), Vec::new());
+            cx.notify();
+        });
+        self.move_cursor(range.start, false, cx);
+    }
+
+    fn undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        let restored = self.file.update(cx, |file, cx| {
+            let restored = file.buffer.undo();
+            cx.notify();
+            restored
+        });
+        if let Some(restored) = restored {
+            self.select(restored, cx);
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        let restored = self.file.update(cx, |file, cx| {
+            let restored = file.buffer.redo();
+            cx.notify();
+            restored
+        });
+        if let Some(restored) = restored {
+            self.select(restored, cx);
+        }
+    }
+
+    /// Overwrites the byte under the cursor with the typed hex digit or ASCII character, or
+    /// appends it when the cursor is at the end of the file.
+    fn key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = event.keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform || modifiers.function {
+            return;
+        }
+        let Some(char) = event
+            .keystroke
+            .key_char
+            .as_ref()
+            .and_then(|key_char| key_char.chars().next())
+        else {
+            return;
+        };
+
+        let offset = self.head;
+        let current = self.file.read(cx).buffer.bytes().get(offset).copied();
+        let (byte, advance) = match self.column {
+            Column::Hex => {
+                let Some(digit) = char.to_digit(16) else {
+                    return;
+                };
+                let digit = digit as u8;
+                if self.pending_nibble {
+                    ((current.unwrap_or(0) & 0xf0) | digit, true)
+                } else {
+                    ((digit << 4) | (current.unwrap_or(0) & 0x0f), false)
+                }
+            }
+            Column::Ascii => {
+                if !char.is_ascii_graphic() && char != ' ' {
+                    return;
+                }
+                (char as u8, true)
+            }
+        };
+        cx.stop_propagation();
+
+        let range = offset..offset + usize::from(current.is_some());
+        self.file.update(cx, |file, cx| {
+            file.buffer.replace(range, vec![byte]);
+            cx.notify();
+        });
+        if advance {
+            self.move_cursor(offset + 1, false, cx);
+        } else {
+            self.anchor = offset;
+            self.pending_nibble = true;
+            cx.notify();
+        }
+    }
+
+    fn click_byte(
+        &mut self,
+        offset: usize,
+        column: Column,
+        event: &ClickEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle);
+        self.column = column;
+        self.move_cursor(offset, event.modifiers().shift, cx);
+    }
+
+    fn render_row(&self, row: usize, is_focused: bool, cx: &mut Context<Self>) -> AnyElement {
+        let bytes = self.file.read(cx).buffer.bytes();
+        let selected_range = self.selected_range(cx);
+        let row_start = row * BYTES_PER_ROW;
+        let colors = cx.theme().colors();
+
+        let cell = |offset: usize, column: Column, text: String| {
+            let is_cursor = offset == self.head;
+            let is_selected = selected_range.contains(&offset);
+            let is_match = self
+                .search_matches
+                .iter()
+                .any(|range| range.contains(&offset));
+            div()
+                .id((
+                    match column {
+                        Column::Hex => "hex-byte",
+                        Column::Ascii => "ascii-byte",
+                    },
+                    offset,
+                ))
+                .when(is_match, |this| this.bg(colors.search_match_background))
+                .when(is_selected, |this| this.bg(colors.element_selected))
+                .when(is_cursor, |this| {
+                    this.border_b_2()
+                        .border_color(if is_focused && column == self.column {
+                            colors.text_accent
+                        } else {
+                            colors.border
+                        })
+                })
+                .child(text)
+        };
+
+        let offsets = row_start..row_start + BYTES_PER_ROW;
+        h_flex()
+            .gap_4()
+            .px_2()
+            .child(
+                Label::new(format!("{row_start:08x}"))
+                    .buffer_font(cx)
+                    .color(Color::Muted),
+            )
+            .child(h_flex().gap_1().children(offsets.clone().map(|offset| {
+                let text = match bytes.get(offset) {
+                    Some(byte) => format!("{byte:02x}"),
+                    None => "  ".to_string(),
+                };
+                cell(offset, Column::Hex, text)
+                    .when(offset % BYTES_PER_ROW == BYTES_PER_ROW / 2, |this| {
+                        this.ml_2()
+                    })
+                    .when(offset <= bytes.len(), |this| {
+                        this.on_click(cx.listener(move |this, event, window, cx| {
+                            this.click_byte(offset, Column::Hex, event, window, cx)
+                        }))
+                    })
+            })))
+            .child(h_flex().children(offsets.map(|offset| {
+                let text = bytes
+                    .get(offset)
+                    .map_or(' ', |byte| ascii_char(*byte))
+                    .to_string();
+                cell(offset, Column::Ascii, text).when(offset <= bytes.len(), |this| {
+                    this.on_click(cx.listener(move |this, event, window, cx| {
+                        this.click_byte(offset, Column::Ascii, event, window, cx)
+                    }))
+                })
+            })))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for HexEditor {}
+impl EventEmitter<SearchEvent> for HexEditor {}
+
+impl Focusable for HexEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for HexEditor {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        true
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.file
+            .read(cx)
+            .project_path
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string()
+            .into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        Some(format!("{} (hex)", self.tab_content_text(0, cx)).into())
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.file.read(cx).buffer.is_dirty()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        _: SaveOptions,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let file = self.file.clone();
+        let abs_path = project
+            .read(cx)
+            .absolute_path(&file.read(cx).project_path, cx);
+        let fs = project.read(cx).fs().clone();
+        let bytes = file.read(cx).buffer.bytes().to_vec();
+        cx.spawn(async move |_, cx| {
+            let abs_path = abs_path.context("saving a file outside of the project")?;
+            fs.write(&abs_path, &bytes).await?;
+            file.update(cx, |file, cx| {
+                file.buffer.did_save();
+                cx.notify();
+            })
+        })
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let file = self.file.clone();
+        let load_bytes = HexFile::load_bytes(&project, &file.read(cx).project_path, cx);
+        cx.spawn(async move |_, cx| {
+            let bytes = load_bytes.await?;
+            file.update(cx, |file, cx| {
+                file.buffer.reload(bytes);
+                cx.notify();
+            })
+        })
+    }
+
+    fn clone_on_split(
+        &self,
+        _: Option<WorkspaceId>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        Some(cx.new(|cx| Self::new(self.file.clone(), self.project.clone(), cx)))
+    }
+
+    fn as_searchable(&self, handle: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(handle.clone()))
+    }
+}
+
+impl SearchableItem for HexEditor {
+    type Match = Range<usize>;
+
+    fn supported_options(&self) -> SearchOptions {
+        SearchOptions {
+            case: true,
+            word: false,
+            regex: false,
+            replacement: false,
+            selection: false,
+            find_in_results: false,
+        }
+    }
+
+    fn clear_matches(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.search_matches.clear();
+        cx.notify();
+    }
+
+    fn update_matches(&mut self, matches: &[Self::Match], _: &mut Window, cx: &mut Context<Self>) {
+        self.search_matches = matches.to_vec();
+        cx.notify();
+    }
+
+    fn query_suggestion(&mut self, _: &mut Window, cx: &mut Context<Self>) -> String {
+        let selected_range = self.selected_range(cx);
+        if selected_range.len() > 1 {
+            format_hex_bytes(&self.file.read(cx).buffer.bytes()[selected_range])
+        } else {
+            String::new()
+        }
+    }
+
+    fn activate_match(
+        &mut self,
+        index: usize,
+        matches: &[Self::Match],
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(range) = matches.get(index) {
+            self.select(range.clone(), cx);
+        }
+    }
+
+    fn select_matches(&mut self, matches: &[Self::Match], _: &mut Window, cx: &mut Context<Self>) {
+        // Only a single selection is supported, so select the first match.
+        if let Some(range) = matches.first() {
+            self.select(range.clone(), cx);
+        }
+    }
+
+    fn replace(&mut self, _: &Self::Match, _: &SearchQuery, _: &mut Window, _: &mut Context<Self>) {
+    }
+
+    fn find_matches(
+        &mut self,
+        query: Arc<SearchQuery>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Vec<Self::Match>> {
+        let bytes = self.file.read(cx).buffer.bytes().to_vec();
+        cx.background_spawn(async move {
+            find_all(
+                &bytes,
+                &search_bytes(query.as_str()),
+                query.case_sensitive(),
+            )
+        })
+    }
+
+    fn active_match_index(
+        &mut self,
+        direction: Direction,
+        matches: &[Self::Match],
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<usize> {
+        if matches.is_empty() {
+            return None;
+        }
+        let cursor = self.anchor.min(self.head);
+        match direction {
+            Direction::Next => matches
+                .iter()
+                .position(|range| range.start >= cursor)
+                .or(Some(0)),
+            Direction::Prev => matches
+                .iter()
+                .rposition(|range| range.start <= cursor)
+                .or(Some(matches.len() - 1)),
+        }
+    }
+}
+
+impl Render for HexEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_focused = self.focus_handle.is_focused(window);
+        // There's always a row for the end of the file, so that bytes can be appended.
+        let row_count = self.len(cx) / BYTES_PER_ROW + 1;
+
+        v_flex()
+            .key_context("HexEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::move_left))
+            .on_action(cx.listener(Self::move_right))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::select_left))
+            .on_action(cx.listener(Self::select_right))
+            .on_action(cx.listener(Self::select_up))
+            .on_action(cx.listener(Self::select_down))
+            .on_action(cx.listener(Self::switch_column))
+            .on_action(cx.listener(Self::delete))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
+            .on_key_down(cx.listener(Self::key_down))
+            .size_full()
+            .py_2()
+            .font_buffer(cx)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                uniform_list(
+                    "hex-rows",
+                    row_count,
+                    cx.processor(move |this, range: Range<usize>, _, cx| {
+                        range
+                            .map(|row| this.render_row(row, is_focused, cx))
+                            .collect::<Vec<_>>()
+                    }),
+                )
+                .size_full()
+                .track_scroll(self.scroll_handle.clone()),
+            )
+    }
+}
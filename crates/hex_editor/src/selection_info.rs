@@ -0,0 +1,62 @@
+use gpui::{Context, Entity, IntoElement, ParentElement, Render, Subscription, div};
+use ui::prelude::*;
+use workspace::{ItemHandle, StatusItemView, Workspace};
+
+use crate::HexEditor;
+
+/// Shows the cursor offset and the length of the selection of the active hex editor.
+pub struct HexSelectionInfo {
+    selection: Option<(usize, usize)>,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl HexSelectionInfo {
+    pub fn new(_workspace: &Workspace) -> Self {
+        Self {
+            selection: None,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn update_selection(&mut self, editor: &Entity<HexEditor>, cx: &mut Context<Self>) {
+        let editor = editor.read(cx);
+        self.selection = Some((editor.cursor(), editor.selected_range(cx).len()));
+        cx.notify();
+    }
+}
+
+impl Render for HexSelectionInfo {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let Some((cursor, selection_len)) = self.selection else {
+            return div();
+        };
+
+        let mut text = format!("0x{cursor:x}");
+        if selection_len > 1 {
+            text.push_str(&format!(" ({selection_len} bytes selected)"));
+        }
+        div().child(Button::new("hex-selection-info", text).label_size(LabelSize::Small))
+    }
+}
+
+impl StatusItemView for HexSelectionInfo {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self._observe_active_editor = None;
+
+        if let Some(editor) = active_pane_item.and_then(|item| item.act_as::<HexEditor>(cx)) {
+            self.update_selection(&editor, cx);
+
+            self._observe_active_editor = Some(cx.observe(&editor, |this, editor, cx| {
+                this.update_selection(&editor, cx);
+            }));
+        } else {
+            self.selection = None;
+        }
+        cx.notify();
+    }
+}
@@ -0,0 +1,210 @@
+use gpui::{AnyElement, App, Bounds, ImageSource, Pixels, Size, div, img, point, px, size};
+use project::image_store::CommittedImage;
+use ui::prelude::*;
+
+use crate::ImageDiffMode;
+
+/// The comparison of an image with its version in git's HEAD.
+pub struct ImageDiff {
+    /// The version of the image in HEAD, or `None` if the image isn't committed.
+    pub committed_image: Option<CommittedImage>,
+    pub mode: ImageDiffMode,
+    /// Where the divider is in swipe mode, and the opacity of the current image in onion-skin
+    /// mode, from 0 to 1.
+    pub mix: f32,
+}
+
+impl ImageDiff {
+    pub fn new(committed_image: Option<CommittedImage>, mode: ImageDiffMode) -> Self {
+        Self {
+            committed_image,
+            mode,
+            mix: 0.5,
+        }
+    }
+
+    pub fn cycle_mode(&mut self) {
+        self.mode = match self.mode {
+            ImageDiffMode::SideBySide => ImageDiffMode::Swipe,
+            ImageDiffMode::Swipe => ImageDiffMode::OnionSkin,
+            ImageDiffMode::OnionSkin => ImageDiffMode::SideBySide,
+        };
+    }
+
+    /// Whether dragging over the image changes [`Self::mix`].
+    pub fn is_adjustable(&self) -> bool {
+        self.committed_image.is_some() && self.mode != ImageDiffMode::SideBySide
+    }
+
+    /// Renders the comparison of the committed image with `current_image`, filling `viewport`.
+    pub fn render(
+        &self,
+        current_image: ImageSource,
+        current_size: Size<Pixels>,
+        viewport: Bounds<Pixels>,
+        cx: &App,
+    ) -> AnyElement {
+        let Some(committed_image) = self
+            .committed_image
+            .as_ref()
+            .filter(|_| self.mode != ImageDiffMode::SideBySide)
+        else {
+            return self.render_side_by_side(current_image, current_size, viewport, cx);
+        };
+
+        let committed_size = size(
+            px(committed_image.width as f32),
+            px(committed_image.height as f32),
+        );
+        let area = Bounds::new(point(px(0.), px(0.)), viewport.size);
+        let scale = fit_scale(&[committed_size, current_size], area.size);
+        let committed_bounds = centered_bounds(committed_size, area, scale);
+        let current_bounds = centered_bounds(current_size, area, scale);
+        let committed_image = render_image(committed_image.render_image.clone(), committed_bounds);
+
+        let comparison = match self.mode {
+            ImageDiffMode::Swipe => {
+                let divider = area.size.width * self.mix;
+                div()
+                    .size_full()
+                    .child(committed_image)
+                    .child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .left(divider)
+                            .w(area.size.width - divider)
+                            .h_full()
+                            .overflow_hidden()
+                            .child(render_image(
+                                current_image,
+                                Bounds::new(
+                                    point(
+                                        current_bounds.origin.x - divider,
+                                        current_bounds.origin.y,
+                                    ),
+                                    current_bounds.size,
+                                ),
+                            )),
+                    )
+                    .child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .left(divider - px(1.))
+                            .w(px(2.))
+                            .h_full()
+                            .bg(cx.theme().colors().border_focused),
+                    )
+            }
+            _ => div()
+                .size_full()
+                .child(committed_image)
+                .child(render_image(current_image, current_bounds).opacity(self.mix)),
+        };
+
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .child(comparison)
+            .child(render_version_label("HEAD", cx).left_2())
+            .child(render_version_label("Working Tree", cx).right_2())
+            .into_any_element()
+    }
+
+    fn render_side_by_side(
+        &self,
+        current_image: ImageSource,
+        current_size: Size<Pixels>,
+        viewport: Bounds<Pixels>,
+        cx: &App,
+    ) -> AnyElement {
+        let half = size(viewport.size.width / 2., viewport.size.height);
+        let area = Bounds::new(point(px(0.), px(0.)), half);
+        let committed_size = self.committed_image.as_ref().map(|committed_image| {
+            size(
+                px(committed_image.width as f32),
+                px(committed_image.height as f32),
+            )
+        });
+        let scale = fit_scale(
+            &committed_size
+                .into_iter()
+                .chain([current_size])
+                .collect::<Vec<_>>(),
+            half,
+        );
+
+        let side = || div().relative().w(half.width).h_full().overflow_hidden();
+        let committed_side = match (&self.committed_image, committed_size) {
+            (Some(committed_image), Some(committed_size)) => side().child(render_image(
+                committed_image.render_image.clone(),
+                centered_bounds(committed_size, area, scale),
+            )),
+            _ => side()
+                .flex()
+                .items_center()
+                .justify_center()
+                .child(Label::new("Not committed").color(Color::Muted)),
+        };
+
+        h_flex()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .child(
+                committed_side
+                    .border_r_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(render_version_label("HEAD", cx).left_2()),
+            )
+            .child(
+                side()
+                    .child(render_image(
+                        current_image,
+                        centered_bounds(current_size, area, scale),
+                    ))
+                    .child(render_version_label("Working Tree", cx).right_2()),
+            )
+            .into_any_element()
+    }
+}
+
+/// The scale at which all images of `sizes` fit in `area`, without scaling them up, so that they
+/// can be compared at the same scale.
+fn fit_scale(sizes: &[Size<Pixels>], area: Size<Pixels>) -> f32 {
+    sizes
+        .iter()
+        .filter(|size| size.width > Pixels::ZERO && size.height > Pixels::ZERO)
+        .map(|size| (area.width / size.width).min(area.height / size.height))
+        .fold(1., f32::min)
+}
+
+fn centered_bounds(image_size: Size<Pixels>, area: Bounds<Pixels>, scale: f32) -> Bounds<Pixels> {
+    let scaled_size = size(image_size.width * scale, image_size.height * scale);
+    let origin = area.center() - point(scaled_size.width / 2., scaled_size.height / 2.);
+    Bounds::new(origin, scaled_size)
+}
+
+fn render_image(image: impl Into<ImageSource>, bounds: Bounds<Pixels>) -> gpui::Img {
+    img(image)
+        .absolute()
+        .left(bounds.origin.x)
+        .top(bounds.origin.y)
+        .w(bounds.size.width)
+        .h(bounds.size.height)
+}
+
+fn render_version_label(label: &'static str, cx: &App) -> Div {
+    div()
+        .absolute()
+        .top_2()
+        .px_1p5()
+        .py_0p5()
+        .rounded_sm()
+        .bg(cx.theme().colors().elevated_surface_background)
+        .child(Label::new(label).size(LabelSize::Small))
+}
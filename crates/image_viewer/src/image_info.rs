@@ -1,14 +1,29 @@
-use gpui::{Context, Entity, IntoElement, ParentElement, Render, Subscription, div};
+use gpui::{Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, div};
 use project::image_store::{ImageFormat, ImageMetadata};
 use settings::Settings;
-use ui::prelude::*;
+use ui::{Tooltip, prelude::*};
 use util::size::format_file_size;
 use workspace::{ItemHandle, StatusItemView, Workspace};
 
-use crate::{ImageFileSizeUnit, ImageView, ImageViewerSettings};
+use crate::{ImageFileSizeUnit, ImageView, ImageViewerSettings, ToggleMetadataPanel};
+
+/// The version of the active image in git's HEAD, while the image is compared with it.
+#[derive(Clone, Copy)]
+enum DiffBase {
+    Committed {
+        width: u32,
+        height: u32,
+        file_size: u64,
+    },
+    Uncommitted,
+}
 
 pub struct ImageInfo {
     metadata: Option<ImageMetadata>,
+    diff_base: Option<DiffBase>,
+    zoom: Option<(WeakEntity<ImageView>, u32)>,
+    /// The shown frame index, frame count and whether playback is paused, for animated images.
+    frame: Option<(WeakEntity<ImageView>, usize, usize, bool)>,
     _observe_active_image: Option<Subscription>,
     observe_image_item: Option<Subscription>,
 }
@@ -17,6 +32,9 @@ impl ImageInfo {
     pub fn new(_workspace: &Workspace) -> Self {
         Self {
             metadata: None,
+            diff_base: None,
+            zoom: None,
+            frame: None,
             _observe_active_image: None,
             observe_image_item: None,
         }
@@ -24,24 +42,60 @@ impl ImageInfo {
 
     fn update_metadata(&mut self, image_view: &Entity<ImageView>, cx: &mut Context<Self>) {
         let image_item = image_view.read(cx).image_item.clone();
-        let current_metadata = image_item.read(cx).image_metadata;
+        self.zoom = image_view
+            .read(cx)
+            .zoom_percentage(cx)
+            .map(|zoom| (image_view.downgrade(), zoom));
+        self.frame = image_view
+            .read(cx)
+            .frame_position(cx)
+            .map(|(frame_ix, frame_count)| {
+                let paused = image_view.read(cx).is_paused();
+                (image_view.downgrade(), frame_ix, frame_count, paused)
+            });
+        self.diff_base = image_view.read(cx).diff().map(|diff| {
+            diff.committed_image
+                .as_ref()
+                .map_or(DiffBase::Uncommitted, |committed_image| {
+                    DiffBase::Committed {
+                        width: committed_image.width,
+                        height: committed_image.height,
+                        file_size: committed_image.file_size,
+                    }
+                })
+        });
+        let current_metadata = image_item.read(cx).image_metadata.clone();
         if current_metadata.is_some() {
             self.metadata = current_metadata;
             cx.notify();
         } else {
             self.observe_image_item = Some(cx.observe(&image_item, |this, item, cx| {
-                this.metadata = item.read(cx).image_metadata;
+                this.metadata = item.read(cx).image_metadata.clone();
                 cx.notify();
             }));
         }
     }
 }
 
-fn format_image_size(size: u64, image_unit_type: ImageFileSizeUnit) -> String {
+pub(crate) fn format_image_size(size: u64, image_unit_type: ImageFileSizeUnit) -> String {
     let use_decimal = matches!(image_unit_type, ImageFileSizeUnit::Decimal);
     format_file_size(size, use_decimal)
 }
 
+pub(crate) fn format_label(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "PNG",
+        ImageFormat::Jpeg => "JPEG",
+        ImageFormat::Gif => "GIF",
+        ImageFormat::WebP => "WebP",
+        ImageFormat::Tiff => "TIFF",
+        ImageFormat::Bmp => "BMP",
+        ImageFormat::Ico => "ICO",
+        ImageFormat::Avif => "Avif",
+        _ => "Unknown",
+    }
+}
+
 impl Render for ImageInfo {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let settings = ImageViewerSettings::get_global(cx);
@@ -51,8 +105,46 @@ impl Render for ImageInfo {
         };
 
         let mut components = Vec::new();
-        components.push(format!("{}x{}", metadata.width, metadata.height));
-        components.push(format_image_size(metadata.file_size, settings.unit));
+        match self.diff_base {
+            Some(DiffBase::Committed {
+                width,
+                height,
+                file_size,
+            }) => {
+                if (width, height) == (metadata.width, metadata.height) {
+                    components.push(format!("{}x{} (unchanged)", width, height));
+                } else {
+                    components.push(format!(
+                        "{width}x{height} → {}x{} ({:+}x{:+})",
+                        metadata.width,
+                        metadata.height,
+                        i64::from(metadata.width) - i64::from(width),
+                        i64::from(metadata.height) - i64::from(height),
+                    ));
+                }
+                let sign = if metadata.file_size >= file_size {
+                    "+"
+                } else {
+                    "-"
+                };
+                components.push(format!(
+                    "{} ({sign}{})",
+                    format_image_size(metadata.file_size, settings.unit),
+                    format_image_size(metadata.file_size.abs_diff(file_size), settings.unit)
+                ));
+            }
+            Some(DiffBase::Uncommitted) => {
+                components.push(format!("{}x{}", metadata.width, metadata.height));
+                components.push(format!(
+                    "{} (not committed)",
+                    format_image_size(metadata.file_size, settings.unit)
+                ));
+            }
+            None => {
+                components.push(format!("{}x{}", metadata.width, metadata.height));
+                components.push(format_image_size(metadata.file_size, settings.unit));
+            }
+        }
 
         if let Some(colors) = metadata.colors {
             components.push(format!(
@@ -62,24 +154,62 @@ impl Render for ImageInfo {
             ));
         }
 
-        components.push(
-            match metadata.format {
-                ImageFormat::Png => "PNG",
-                ImageFormat::Jpeg => "JPEG",
-                ImageFormat::Gif => "GIF",
-                ImageFormat::WebP => "WebP",
-                ImageFormat::Tiff => "TIFF",
-                ImageFormat::Bmp => "BMP",
-                ImageFormat::Ico => "ICO",
-                ImageFormat::Avif => "Avif",
-                _ => "Unknown",
-            }
-            .to_string(),
-        );
+        components.push(format_label(metadata.format).to_string());
 
-        div().child(
-            Button::new("image-metadata", components.join(" • ")).label_size(LabelSize::Small),
-        )
+        div()
+            .flex()
+            .child(
+                Button::new("image-metadata", components.join(" • "))
+                    .label_size(LabelSize::Small)
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(ToggleMetadataPanel), cx)
+                    })
+                    .tooltip(Tooltip::for_action_title(
+                        "Toggle Image Metadata",
+                        &ToggleMetadataPanel,
+                    )),
+            )
+            .when_some(self.zoom.clone(), |this, (image_view, zoom)| {
+                this.child(
+                    Button::new("image-zoom", format!("{zoom}%"))
+                        .label_size(LabelSize::Small)
+                        .on_click(move |_, _, cx| {
+                            image_view
+                                .update(cx, |image_view, cx| image_view.reset_zoom(cx))
+                                .ok();
+                        })
+                        .tooltip(Tooltip::text("Reset Zoom")),
+                )
+            })
+            .when_some(
+                self.frame.clone(),
+                |this, (image_view, frame_ix, frame_count, paused)| {
+                    this.child(
+                        Button::new(
+                            "image-frame",
+                            format!("Frame {}/{frame_count}", frame_ix + 1),
+                        )
+                        .label_size(LabelSize::Small)
+                        .icon(if paused {
+                            IconName::PlayFilled
+                        } else {
+                            IconName::DebugPause
+                        })
+                        .icon_size(IconSize::Small)
+                        .icon_position(IconPosition::Start)
+                        .on_click(move |_, _, cx| {
+                            image_view
+                                .update(cx, |image_view, cx| image_view.toggle_playback(cx))
+                                .ok();
+                        })
+                        .tooltip(Tooltip::text(if paused {
+                            "Play"
+                        } else {
+                            "Pause"
+                        })),
+                    )
+                },
+            )
     }
 }
 
@@ -101,6 +231,9 @@ impl StatusItemView for ImageInfo {
             }));
         } else {
             self.metadata = None;
+            self.diff_base = None;
+            self.zoom = None;
+            self.frame = None;
         }
         cx.notify();
     }
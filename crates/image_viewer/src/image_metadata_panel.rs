@@ -0,0 +1,364 @@
+use std::sync::Arc;
+
+use fs::Fs;
+use gpui::{
+    Action, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels, SharedString,
+    Subscription, Window,
+};
+use project::{
+    ImageItem,
+    image_store::{EmbeddedMetadata, ImageMetadata, Orientation},
+};
+use settings::{DockSide, Settings};
+use ui::prelude::*;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+use crate::{ImageView, ImageViewerSettings, ToggleMetadataPanel, format_image_size, format_label};
+
+/// A dock panel showing the metadata of the active image, including the EXIF and XMP metadata
+/// embedded in the file.
+pub struct ImageMetadataPanel {
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    image_item: Option<Entity<ImageItem>>,
+    _observe_image_item: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ImageMetadataPanel {
+    pub fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let workspace_subscription = cx.subscribe_in(
+            &workspace
+                .weak_handle()
+                .upgrade()
+                .expect("have a &mut Workspace"),
+            window,
+            |this, workspace, event, _, cx| {
+                if let workspace::Event::ActiveItemChanged = event {
+                    this.set_active_item(workspace.read(cx), cx);
+                }
+            },
+        );
+
+        let mut this = Self {
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            width: None,
+            image_item: None,
+            _observe_image_item: None,
+            _subscriptions: vec![workspace_subscription],
+        };
+        this.set_active_item(workspace, cx);
+        this
+    }
+
+    /// Shows the metadata of the workspace's active item if it's an image, and keeps showing the
+    /// last image otherwise, so that focusing the panel doesn't clear it.
+    fn set_active_item(&mut self, workspace: &Workspace, cx: &mut Context<Self>) {
+        let Some(image_view) = workspace
+            .active_item(cx)
+            .and_then(|item| item.act_as::<ImageView>(cx))
+        else {
+            return;
+        };
+        let image_item = image_view.read(cx).image_item.clone();
+        if self.image_item.as_ref() != Some(&image_item) {
+            self.observe_image_item(image_item, cx);
+        }
+    }
+
+    fn observe_image_item(&mut self, image_item: Entity<ImageItem>, cx: &mut Context<Self>) {
+        self._observe_image_item = Some(cx.observe(&image_item, |_, _, cx| cx.notify()));
+        self.image_item = Some(image_item);
+        cx.notify();
+    }
+
+    fn render_section(
+        title: &'static str,
+        rows: Vec<(&'static str, String)>,
+    ) -> Option<impl IntoElement> {
+        if rows.is_empty() {
+            return None;
+        }
+        Some(
+            v_flex()
+                .gap_1()
+                .child(
+                    Label::new(title)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .weight(FontWeight::MEDIUM),
+                )
+                .children(rows.into_iter().map(|(name, value)| {
+                    h_flex()
+                        .gap_2()
+                        .items_start()
+                        .child(
+                            div()
+                                .w_24()
+                                .flex_none()
+                                .child(Label::new(name).size(LabelSize::Small).color(Color::Muted)),
+                        )
+                        .child(Label::new(value).size(LabelSize::Small))
+                })),
+        )
+    }
+
+    fn render_metadata(&self, metadata: &ImageMetadata, cx: &App) -> impl IntoElement {
+        let embedded = &metadata.embedded;
+        let unit = ImageViewerSettings::get_global(cx).unit;
+
+        let mut file = vec![
+            (
+                "Dimensions",
+                format!("{} × {}", metadata.width, metadata.height),
+            ),
+            ("File size", format_image_size(metadata.file_size, unit)),
+            ("Format", format_label(metadata.format).to_string()),
+        ];
+        if let Some(colors) = metadata.colors {
+            file.push((
+                "Colors",
+                format!(
+                    "{} channels, {} bits per pixel",
+                    colors.channels,
+                    colors.bits_per_pixel()
+                ),
+            ));
+        }
+        if let Some((x, y)) = embedded.dpi {
+            file.push(("Resolution", format_dpi(x, y)));
+        }
+        if let Some(orientation) = embedded.orientation {
+            file.push(("Orientation", orientation_label(orientation).to_string()));
+        }
+
+        let camera = [
+            ("Make", embedded.camera_make.clone()),
+            ("Model", embedded.camera_model.clone()),
+            ("Lens", embedded.lens_model.clone()),
+            ("Taken", embedded.date_taken.clone()),
+            ("Software", embedded.software.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect();
+
+        let exposure = exposure_rows(embedded);
+
+        let mut location = Vec::new();
+        if let Some(gps) = embedded.gps {
+            let latitude_ref = if gps.latitude < 0. { "S" } else { "N" };
+            let longitude_ref = if gps.longitude < 0. { "W" } else { "E" };
+            location.push((
+                "Coordinates",
+                format!(
+                    "{:.5}° {latitude_ref}, {:.5}° {longitude_ref}",
+                    gps.latitude.abs(),
+                    gps.longitude.abs()
+                ),
+            ));
+            if let Some(altitude) = gps.altitude {
+                location.push(("Altitude", format!("{altitude:.0} m")));
+            }
+        }
+
+        v_flex()
+            .gap_4()
+            .children(Self::render_section("File", file))
+            .children(Self::render_section("Camera", camera))
+            .children(Self::render_section("Exposure", exposure))
+            .children(Self::render_section("Location", location))
+            .when(!embedded.xmp.is_empty(), |this| {
+                this.child(
+                    v_flex()
+                        .gap_1()
+                        .child(
+                            Label::new("XMP")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .weight(FontWeight::MEDIUM),
+                        )
+                        .children(embedded.xmp.iter().map(|(name, value)| {
+                            v_flex()
+                                .child(
+                                    Label::new(SharedString::from(name.clone()))
+                                        .size(LabelSize::XSmall)
+                                        .color(Color::Muted),
+                                )
+                                .child(
+                                    Label::new(SharedString::from(value.clone()))
+                                        .size(LabelSize::Small),
+                                )
+                        })),
+                )
+            })
+            .when(embedded.is_empty(), |this| {
+                this.child(
+                    Label::new("No EXIF or XMP metadata")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+    }
+}
+
+fn exposure_rows(embedded: &EmbeddedMetadata) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if let Some(exposure_time) = embedded.exposure_time
+        && let Some(seconds) = exposure_time.to_f64()
+    {
+        let exposure_time = if seconds < 1. && exposure_time.numerator > 0 {
+            format!("1/{:.0} s", 1. / seconds)
+        } else {
+            format!("{seconds} s")
+        };
+        rows.push(("Exposure", exposure_time));
+    }
+    if let Some(f_number) = embedded.f_number {
+        rows.push(("Aperture", format!("f/{f_number:.1}")));
+    }
+    if let Some(iso) = embedded.iso {
+        rows.push(("ISO", iso.to_string()));
+    }
+    if let Some(focal_length) = embedded.focal_length {
+        rows.push(("Focal length", format!("{focal_length:.0} mm")));
+    }
+    rows
+}
+
+fn format_dpi(x: f64, y: f64) -> String {
+    if x == y {
+        format!("{x:.0} DPI")
+    } else {
+        format!("{x:.0} × {y:.0} DPI")
+    }
+}
+
+fn orientation_label(orientation: Orientation) -> &'static str {
+    match orientation {
+        Orientation::NoTransforms => "Upright",
+        Orientation::Rotate90 => "Rotated 90° clockwise",
+        Orientation::Rotate180 => "Rotated 180°",
+        Orientation::Rotate270 => "Rotated 90° counterclockwise",
+        Orientation::FlipHorizontal => "Flipped horizontally",
+        Orientation::FlipVertical => "Flipped vertically",
+        Orientation::Rotate90FlipH => "Rotated 90° clockwise and flipped",
+        Orientation::Rotate270FlipH => "Rotated 90° counterclockwise and flipped",
+    }
+}
+
+impl Focusable for ImageMetadataPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for ImageMetadataPanel {}
+
+impl Render for ImageMetadataPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let metadata = self
+            .image_item
+            .as_ref()
+            .and_then(|image_item| image_item.read(cx).image_metadata.clone());
+
+        v_flex()
+            .id("image-metadata-panel")
+            .key_context("ImageMetadataPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_2()
+            .overflow_y_scroll()
+            .map(|this| match metadata {
+                Some(metadata) => this.child(self.render_metadata(&metadata, cx)),
+                None => this
+                    .items_center()
+                    .justify_center()
+                    .child(Label::new("Open an image to see its metadata").color(Color::Muted)),
+            })
+    }
+}
+
+impl Panel for ImageMetadataPanel {
+    fn persistent_name() -> &'static str {
+        "ImageMetadataPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match ImageViewerSettings::get_global(cx).metadata_panel_dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings
+                .image_viewer
+                .get_or_insert_default()
+                .metadata_panel
+                .get_or_insert_default()
+                .dock = Some(dock);
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| ImageViewerSettings::get_global(cx).metadata_panel_default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    // The panel is opened from the image information in the status bar, so it has no button.
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        None
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Image Metadata")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleMetadataPanel)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        12
+    }
+}
+
+/// Opens the image metadata panel, or closes it if it's open.
+pub(crate) fn toggle_metadata_panel(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let is_open = workspace.all_docks().iter().any(|dock| {
+        let dock = dock.read(cx);
+        dock.is_open()
+            && dock
+                .visible_panel()
+                .is_some_and(|panel| panel.to_any().downcast::<ImageMetadataPanel>().is_ok())
+    });
+    if is_open {
+        workspace.close_panel::<ImageMetadataPanel>(window, cx);
+    } else {
+        workspace.open_panel::<ImageMetadataPanel>(window, cx);
+    }
+}
@@ -1,33 +1,99 @@
+mod image_diff;
 mod image_info;
+mod image_metadata_panel;
 mod image_viewer_settings;
 
 use anyhow::Context as _;
 use editor::{EditorSettings, items::entry_git_aware_label_color};
 use file_icons::FileIcons;
 use gpui::{
-    AnyElement, App, Bounds, Context, Entity, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, ObjectFit, ParentElement, Render, Styled, Task, WeakEntity,
-    Window, canvas, div, fill, img, opaque_grey, point, size,
+    AnyElement, App, Bounds, Context, Entity, EventEmitter, FocusHandle, Focusable, ImageSource,
+    InteractiveElement, IntoElement, KeyContext, MouseButton, MouseDownEvent, MouseMoveEvent,
+    ObjectFit, ParentElement, Point, Render, ScrollWheelEvent, Size, Styled, Task, WeakEntity,
+    Window, actions, canvas, div, fill, img, opaque_grey, point, size,
 };
 use language::{DiskState, File as _};
 use persistence::IMAGE_VIEWER;
-use project::{ImageItem, Project, ProjectPath, image_store::ImageItemEvent};
+use project::{
+    ImageItem, Project, ProjectPath,
+    image_store::{ImageEdit, ImageItemEvent},
+};
 use settings::Settings;
 use theme::Theme;
 use ui::prelude::*;
-use util::paths::PathExt;
+use util::{ResultExt as _, paths::PathExt};
 use workspace::{
     ItemId, ItemSettings, Pane, ToolbarItemLocation, Workspace, WorkspaceId, delete_unloaded_items,
-    item::{BreadcrumbText, Item, ProjectItem, SerializableItem, TabContentParams},
+    item::{BreadcrumbText, Item, ProjectItem, SaveOptions, SerializableItem, TabContentParams},
 };
 
+pub use crate::image_diff::ImageDiff;
 pub use crate::image_info::*;
+pub use crate::image_metadata_panel::ImageMetadataPanel;
 pub use crate::image_viewer_settings::*;
 
+actions!(
+    image_viewer,
+    [
+        /// Zooms in on the image.
+        ZoomIn,
+        /// Zooms out of the image.
+        ZoomOut,
+        /// Resets the zoom and pan of the image to the configured zoom mode.
+        ResetZoom,
+        /// Pauses or resumes the playback of an animated image.
+        TogglePlayback,
+        /// Pauses the playback of an animated image and shows its next frame.
+        NextFrame,
+        /// Pauses the playback of an animated image and shows its previous frame.
+        PreviousFrame,
+        /// Opens or closes the panel showing the metadata of the active image.
+        ToggleMetadataPanel,
+        /// Rotates the image a quarter turn clockwise.
+        RotateCw,
+        /// Rotates the image a quarter turn counterclockwise.
+        RotateCcw,
+        /// Flips the image horizontally.
+        FlipHorizontal,
+        /// Flips the image vertically.
+        FlipVertical,
+        /// Starts selecting the area to crop the image to, or crops the image to the selected area.
+        Crop,
+        /// Stops selecting the area to crop the image to.
+        CancelCrop,
+        /// Compares the image with its version in git's HEAD, or stops comparing it.
+        ToggleDiff,
+        /// Switches to the next way of comparing the image with its version in git's HEAD.
+        CycleDiffMode,
+    ]
+);
+
+const ZOOM_STEP: f32 = 1.25;
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 32.;
+
 pub struct ImageView {
     image_item: Entity<ImageItem>,
     project: Entity<Project>,
     focus_handle: FocusHandle,
+    /// The zoom chosen by the user, or `None` to follow [`ImageViewerSettings::zoom_mode`].
+    zoom: Option<f32>,
+    /// The offset of the image's center from the center of the viewport.
+    pan: Point<Pixels>,
+    drag_position: Option<Point<Pixels>>,
+    viewport: Option<Bounds<Pixels>>,
+    /// The index of the frame shown, for animated images.
+    frame_ix: usize,
+    paused: bool,
+    /// Shows the next frame of a playing animation once the current frame's delay has passed.
+    playback: Option<Task<()>>,
+    /// Whether dragging selects the area to crop the image to, instead of panning it.
+    cropping: bool,
+    /// The corners of the area to crop the image to, in pixels of the image.
+    crop_selection: Option<(Point<f32>, Point<f32>)>,
+    /// The comparison of the image with its version in git's HEAD, while it's compared.
+    diff: Option<ImageDiff>,
+    load_diff: Option<Task<()>>,
 }
 
 impl ImageView {
@@ -39,10 +105,31 @@ impl ImageView {
     ) -> Self {
         cx.subscribe(&image_item, Self::on_image_event).detach();
         cx.on_release_in(window, |this, window, cx| {
-            let image_data = this.image_item.read(cx).image.clone();
+            let image_item = this.image_item.read(cx);
+            let image_data = image_item.image.clone();
+            let frames = image_item.frames.clone();
+            let still_image = image_item.still_image.clone();
+            let edited_image = image_item
+                .edited_image
+                .as_ref()
+                .map(|edited_image| edited_image.render_image.clone());
             if let Some(image) = image_data.clone().get_render_image(window, cx) {
                 cx.drop_image(image, None);
             }
+            for frame in frames.iter().flat_map(|frames| frames.iter()) {
+                cx.drop_image(frame.image.clone(), None);
+            }
+            let committed_image = this
+                .diff
+                .take()
+                .and_then(|diff| Some(diff.committed_image?.render_image));
+            for image in still_image
+                .into_iter()
+                .chain(edited_image)
+                .chain(committed_image)
+            {
+                cx.drop_image(image, None);
+            }
             image_data.remove_asset(cx);
         })
         .detach();
@@ -51,9 +138,395 @@ impl ImageView {
             image_item,
             project,
             focus_handle: cx.focus_handle(),
+            zoom: None,
+            pan: Point::default(),
+            drag_position: None,
+            viewport: None,
+            frame_ix: 0,
+            paused: false,
+            playback: None,
+            cropping: false,
+            crop_selection: None,
+            diff: None,
+            load_diff: None,
+        }
+    }
+
+    /// The comparison of the image with its version in git's HEAD, while it's compared.
+    pub fn diff(&self) -> Option<&ImageDiff> {
+        self.diff.as_ref()
+    }
+
+    fn toggle_diff(&mut self, _: &ToggleDiff, _: &mut Window, cx: &mut Context<Self>) {
+        if self.diff.is_some() || self.load_diff.is_some() {
+            self.load_diff = None;
+            self.set_diff(None, cx);
+        } else {
+            self.load_diff(ImageViewerSettings::get_global(cx).diff_mode, cx);
+        }
+    }
+
+    fn cycle_diff_mode(&mut self, _: &CycleDiffMode, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(diff) = &mut self.diff {
+            diff.cycle_mode();
+            cx.notify();
+        }
+    }
+
+    /// Loads the version of the image in git's HEAD to compare the image with, in `mode`.
+    fn load_diff(&mut self, mode: ImageDiffMode, cx: &mut Context<Self>) {
+        let load_committed_image = self.project.update(cx, |project, cx| {
+            project.load_committed_image(&self.image_item, cx)
+        });
+        self.load_diff = Some(cx.spawn(async move |this, cx| {
+            let committed_image = load_committed_image.await;
+            this.update(cx, |this, cx| {
+                this.load_diff = None;
+                if let Some(committed_image) = committed_image.log_err() {
+                    let mix = this.diff.as_ref().map(|diff| diff.mix);
+                    let mut diff = ImageDiff::new(committed_image, mode);
+                    diff.mix = mix.unwrap_or(diff.mix);
+                    this.set_diff(Some(diff), cx);
+                }
+            })
+            .ok();
+        }));
+    }
+
+    fn set_diff(&mut self, diff: Option<ImageDiff>, cx: &mut Context<Self>) {
+        if let Some(committed_image) = self.diff.take().and_then(|diff| diff.committed_image) {
+            cx.drop_image(committed_image.render_image, None);
+        }
+        self.diff = diff;
+        self.cropping = false;
+        self.crop_selection = None;
+        cx.notify();
+    }
+
+    /// Moves the divider or changes the opacity of the comparison to the position of the mouse.
+    fn adjust_diff(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) -> bool {
+        let (Some(diff), Some(viewport)) = (&mut self.diff, self.viewport) else {
+            return false;
+        };
+        if !diff.is_adjustable() || viewport.size.width <= Pixels::ZERO {
+            return false;
+        }
+        diff.mix = ((position.x - viewport.origin.x) / viewport.size.width).clamp(0., 1.);
+        cx.notify();
+        true
+    }
+
+    /// The index of the frame shown and the number of frames, for animated images.
+    pub fn frame_position(&self, cx: &App) -> Option<(usize, usize)> {
+        let frames = self.image_item.read(cx).frames.as_ref()?;
+        Some((self.frame_ix % frames.len(), frames.len()))
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Starts advancing the frames of an animated image, each after its own delay.
+    fn play(&mut self, cx: &mut Context<Self>) {
+        self.paused = false;
+        cx.notify();
+    }
+
+    /// Schedules showing the next frame of a playing animation.
+    ///
+    /// This is done when the image is rendered, so that playback stops while the image isn't
+    /// shown, e.g. in an inactive tab, and resumes from the same frame once it's shown again.
+    fn schedule_next_frame(&mut self, cx: &mut Context<Self>) {
+        if self.paused || self.playback.is_some() {
+            return;
+        }
+        let Some(frames) = self.image_item.read(cx).frames.as_ref() else {
+            return;
+        };
+        let delay = frames[self.frame_ix % frames.len()].delay;
+        self.playback = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            this.update(cx, |this, cx| {
+                this.playback = None;
+                this.step_frame(1, cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn pause(&mut self, cx: &mut Context<Self>) {
+        self.paused = true;
+        self.playback = None;
+        cx.notify();
+    }
+
+    pub fn toggle_playback(&mut self, cx: &mut Context<Self>) {
+        if self.paused {
+            self.play(cx);
+        } else {
+            self.pause(cx);
+        }
+    }
+
+    fn step_frame(&mut self, delta: isize, cx: &mut Context<Self>) {
+        let Some((frame_ix, frame_count)) = self.frame_position(cx) else {
+            return;
+        };
+        self.frame_ix = (frame_ix as isize + delta).rem_euclid(frame_count as isize) as usize;
+        cx.notify();
+    }
+
+    fn next_frame(&mut self, _: &NextFrame, _: &mut Window, cx: &mut Context<Self>) {
+        self.pause(cx);
+        self.step_frame(1, cx);
+    }
+
+    fn previous_frame(&mut self, _: &PreviousFrame, _: &mut Window, cx: &mut Context<Self>) {
+        self.pause(cx);
+        self.step_frame(-1, cx);
+    }
+
+    /// The current zoom of the image as a percentage of its actual size.
+    pub fn zoom_percentage(&self, cx: &App) -> Option<u32> {
+        Some((self.scale(cx)? * 100.).round() as u32)
+    }
+
+    fn image_size(&self, cx: &App) -> Option<Size<Pixels>> {
+        let (width, height) = self.image_item.read(cx).size()?;
+        Some(size(px(width as f32), px(height as f32)))
+    }
+
+    /// The bounds of the image in the window, at the current zoom and pan.
+    fn image_bounds(&self, cx: &App) -> Option<Bounds<Pixels>> {
+        let viewport = self.viewport?;
+        let image_size = self.image_size(cx)?;
+        let scale = self.scale(cx)?;
+        let scaled_size = size(image_size.width * scale, image_size.height * scale);
+        let origin =
+            viewport.center() + self.pan - point(scaled_size.width / 2., scaled_size.height / 2.);
+        Some(Bounds::new(origin, scaled_size))
+    }
+
+    /// The image pixel under `position`, clamped to the image.
+    fn image_position(&self, position: Point<Pixels>, cx: &App) -> Option<Point<f32>> {
+        let image_bounds = self.image_bounds(cx)?;
+        let image_size = self.image_size(cx)?;
+        let scale = self.scale(cx)?;
+        let offset = position - image_bounds.origin;
+        Some(point(
+            (f32::from(offset.x) / scale).clamp(0., f32::from(image_size.width)),
+            (f32::from(offset.y) / scale).clamp(0., f32::from(image_size.height)),
+        ))
+    }
+
+    fn edit_image(&mut self, edit: ImageEdit, cx: &mut Context<Self>) {
+        self.crop_selection = None;
+        self.image_item
+            .update(cx, |image_item, cx| image_item.edit(edit, cx));
+    }
+
+    fn rotate_cw(&mut self, _: &RotateCw, _: &mut Window, cx: &mut Context<Self>) {
+        self.edit_image(ImageEdit::RotateClockwise, cx);
+    }
+
+    fn rotate_ccw(&mut self, _: &RotateCcw, _: &mut Window, cx: &mut Context<Self>) {
+        self.edit_image(ImageEdit::RotateCounterclockwise, cx);
+    }
+
+    fn flip_horizontal(&mut self, _: &FlipHorizontal, _: &mut Window, cx: &mut Context<Self>) {
+        self.edit_image(ImageEdit::FlipHorizontal, cx);
+    }
+
+    fn flip_vertical(&mut self, _: &FlipVertical, _: &mut Window, cx: &mut Context<Self>) {
+        self.edit_image(ImageEdit::FlipVertical, cx);
+    }
+
+    fn crop(&mut self, _: &Crop, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.image_item.read(cx).can_edit() || self.diff.is_some() {
+            return;
+        }
+        if !self.cropping {
+            self.cropping = true;
+            cx.notify();
+            return;
+        }
+
+        let Some((start, end)) = self.crop_selection else {
+            return;
+        };
+        let (x, y) = (start.x.min(end.x).round(), start.y.min(end.y).round());
+        let (width, height) = (
+            start.x.max(end.x).round() - x,
+            start.y.max(end.y).round() - y,
+        );
+        self.cropping = false;
+        if width >= 1. && height >= 1. {
+            self.edit_image(
+                ImageEdit::Crop {
+                    x: x as u32,
+                    y: y as u32,
+                    width: width as u32,
+                    height: height as u32,
+                },
+                cx,
+            );
+            self.reset_zoom(cx);
+        }
+        self.crop_selection = None;
+        cx.notify();
+    }
+
+    fn cancel_crop(&mut self, _: &CancelCrop, _: &mut Window, cx: &mut Context<Self>) {
+        self.cropping = false;
+        self.crop_selection = None;
+        cx.notify();
+    }
+
+    fn scale(&self, cx: &App) -> Option<f32> {
+        if let Some(zoom) = self.zoom {
+            return Some(zoom);
+        }
+
+        let image_size = self.image_size(cx)?;
+        let viewport = self.viewport?;
+        if image_size.width <= Pixels::ZERO || image_size.height <= Pixels::ZERO {
+            return None;
+        }
+        let width_ratio = viewport.size.width / image_size.width;
+        let height_ratio = viewport.size.height / image_size.height;
+        Some(match ImageViewerSettings::get_global(cx).zoom_mode {
+            ImageZoomMode::Fit => width_ratio.min(height_ratio).min(1.),
+            ImageZoomMode::Fill => width_ratio.max(height_ratio),
+            ImageZoomMode::ActualSize => 1.,
+        })
+    }
+
+    /// Zooms to `scale`, keeping the image point under `anchor` in place.
+    fn zoom_to(&mut self, scale: f32, anchor: Option<Point<Pixels>>, cx: &mut Context<Self>) {
+        let Some(current_scale) = self.scale(cx) else {
+            return;
+        };
+        let scale = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+        if let Some(viewport) = self.viewport {
+            let anchor = anchor.unwrap_or_else(|| viewport.center());
+            let image_center = viewport.center() + self.pan;
+            let ratio = scale / current_scale;
+            let new_center = point(
+                anchor.x - (anchor.x - image_center.x) * ratio,
+                anchor.y - (anchor.y - image_center.y) * ratio,
+            );
+            self.pan = new_center - viewport.center();
+        }
+        self.zoom = Some(scale);
+        self.clamp_pan(cx);
+        cx.notify();
+    }
+
+    /// Keeps the image from being panned out of the viewport.
+    fn clamp_pan(&mut self, cx: &App) {
+        let (Some(viewport), Some(image_size), Some(scale)) =
+            (self.viewport, self.image_size(cx), self.scale(cx))
+        else {
+            return;
+        };
+        let max_x = ((image_size.width * scale - viewport.size.width) / 2.).max(Pixels::ZERO);
+        let max_y = ((image_size.height * scale - viewport.size.height) / 2.).max(Pixels::ZERO);
+        self.pan = point(
+            self.pan.x.clamp(-max_x, max_x),
+            self.pan.y.clamp(-max_y, max_y),
+        );
+    }
+
+    fn zoom_in(&mut self, _: &ZoomIn, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(scale) = self.scale(cx) {
+            self.zoom_to(scale * ZOOM_STEP, None, cx);
         }
     }
 
+    fn zoom_out(&mut self, _: &ZoomOut, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(scale) = self.scale(cx) {
+            self.zoom_to(scale / ZOOM_STEP, None, cx);
+        }
+    }
+
+    /// Resets the zoom and pan back to the configured [`ImageViewerSettings::zoom_mode`].
+    pub fn reset_zoom(&mut self, cx: &mut Context<Self>) {
+        self.zoom = None;
+        self.pan = Point::default();
+        cx.notify();
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.diff.is_some() {
+            return;
+        }
+        let delta = event.delta.pixel_delta(window.line_height());
+        if event.modifiers.secondary() {
+            // Trackpad pinches are reported as scrolls with the secondary modifier held.
+            if let Some(scale) = self.scale(cx) {
+                let factor = (1. + f32::from(delta.y) / 200.).max(0.1);
+                self.zoom_to(scale * factor, Some(event.position), cx);
+            }
+        } else {
+            self.pan = self.pan + delta;
+            self.clamp_pan(cx);
+            cx.notify();
+        }
+        cx.stop_propagation();
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle);
+        self.drag_position = Some(event.position);
+        if self.adjust_diff(event.position, cx) {
+            return;
+        }
+        if self.cropping {
+            self.crop_selection = self
+                .image_position(event.position, cx)
+                .map(|position| (position, position));
+        }
+        cx.notify();
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(drag_position) = self.drag_position else {
+            return;
+        };
+        if event.pressed_button != Some(MouseButton::Left) {
+            self.drag_position = None;
+            cx.notify();
+            return;
+        }
+        if self.diff.is_some() {
+            self.adjust_diff(event.position, cx);
+            return;
+        }
+        if self.cropping {
+            if let Some((start, _)) = self.crop_selection
+                && let Some(end) = self.image_position(event.position, cx)
+            {
+                self.crop_selection = Some((start, end));
+                cx.notify();
+            }
+            return;
+        }
+        self.pan = self.pan + (event.position - drag_position);
+        self.drag_position = Some(event.position);
+        self.clamp_pan(cx);
+        cx.notify();
+    }
+
     fn on_image_event(
         &mut self,
         _: Entity<ImageItem>,
@@ -61,9 +534,23 @@ impl ImageView {
         cx: &mut Context<Self>,
     ) {
         match event {
-            ImageItemEvent::MetadataUpdated
-            | ImageItemEvent::FileHandleChanged
-            | ImageItemEvent::Reloaded => {
+            ImageItemEvent::Reloaded => {
+                self.frame_ix = 0;
+                self.playback = None;
+                // The image may have been committed, or the change to it reverted.
+                if let Some(diff) = &self.diff {
+                    self.load_diff(diff.mode, cx);
+                }
+                cx.emit(ImageViewEvent::TitleChanged);
+                cx.notify();
+            }
+            ImageItemEvent::MetadataUpdated | ImageItemEvent::FileHandleChanged => {
+                cx.emit(ImageViewEvent::TitleChanged);
+                cx.notify();
+            }
+            ImageItemEvent::Edited => {
+                self.crop_selection = None;
+                self.clamp_pan(cx);
                 cx.emit(ImageViewEvent::TitleChanged);
                 cx.notify();
             }
@@ -186,12 +673,56 @@ impl Item for ImageView {
             image_item: self.image_item.clone(),
             project: self.project.clone(),
             focus_handle: cx.focus_handle(),
+            zoom: self.zoom,
+            pan: self.pan,
+            drag_position: None,
+            viewport: None,
+            frame_ix: self.frame_ix,
+            paused: self.paused,
+            playback: None,
+            cropping: false,
+            crop_selection: None,
+            diff: None,
+            load_diff: None,
         }))
     }
 
     fn has_deleted_file(&self, cx: &App) -> bool {
         self.image_item.read(cx).file.disk_state() == DiskState::Deleted
     }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.image_item.read(cx).is_edited()
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.image_item.read(cx).file.is_local()
+    }
+
+    fn save(
+        &mut self,
+        _: SaveOptions,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        project.update(cx, |project, cx| {
+            project.save_image(self.image_item.clone(), cx)
+        })
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        self.image_item
+            .update(cx, |image_item, cx| image_item.discard_edits(cx));
+        project.update(cx, |project, cx| {
+            project.reload_images([self.image_item.clone()].into_iter().collect(), cx)
+        })
+    }
 }
 
 fn breadcrumbs_text_for_image(project: &Project, image: &ImageItem, cx: &App) -> String {
@@ -296,7 +827,44 @@ impl Focusable for ImageView {
 
 impl Render for ImageView {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let image = self.image_item.read(cx).image.clone();
+        self.schedule_next_frame(cx);
+        let image_item = self.image_item.read(cx);
+        let image: ImageSource = match (
+            &image_item.frames,
+            &image_item.edited_image,
+            &image_item.still_image,
+        ) {
+            (Some(frames), _, _) => frames[self.frame_ix % frames.len()].image.clone().into(),
+            (None, Some(edited_image), _) => edited_image.render_image.clone().into(),
+            (None, None, Some(still_image)) => still_image.clone().into(),
+            (None, None, None) => image_item.image.clone().into(),
+        };
+        let scaled_image = self.viewport.zip(self.image_bounds(cx));
+        let crop_selection =
+            self.crop_selection
+                .zip(self.scale(cx))
+                .map(|((start, end), scale)| {
+                    let top_left = point(
+                        px(start.x.min(end.x) * scale),
+                        px(start.y.min(end.y) * scale),
+                    );
+                    let bottom_right = point(
+                        px(start.x.max(end.x) * scale),
+                        px(start.y.max(end.y) * scale),
+                    );
+                    Bounds::from_corners(top_left, bottom_right)
+                });
+
+        let image_size = self.image_size(cx);
+        let diff = self.diff.as_ref().zip(self.viewport).zip(image_size).map(
+            |((diff, viewport), image_size)| diff.render(image.clone(), image_size, viewport, cx),
+        );
+
+        let mut key_context = KeyContext::new_with_defaults();
+        key_context.add("ImageView");
+        if self.cropping {
+            key_context.add("cropping");
+        }
         let checkered_background = |bounds: Bounds<Pixels>,
                                     _,
                                     window: &mut Window,
@@ -343,26 +911,107 @@ impl Render for ImageView {
             .top_0()
             .left_0();
 
+        let this = cx.entity();
+        let viewport = canvas(
+            move |bounds, _, cx| {
+                this.update(cx, |this, cx| {
+                    if this.viewport != Some(bounds) {
+                        this.viewport = Some(bounds);
+                        this.clamp_pan(cx);
+                        cx.notify();
+                    }
+                })
+            },
+            |_, _, _, _| {},
+        )
+        .size_full()
+        .absolute()
+        .top_0()
+        .left_0();
+
         div()
             .track_focus(&self.focus_handle(cx))
+            .key_context(key_context)
+            .on_action(cx.listener(Self::zoom_in))
+            .on_action(cx.listener(Self::zoom_out))
+            .on_action(cx.listener(|this, _: &ResetZoom, _, cx| this.reset_zoom(cx)))
+            .on_action(cx.listener(|this, _: &TogglePlayback, _, cx| this.toggle_playback(cx)))
+            .on_action(cx.listener(Self::next_frame))
+            .on_action(cx.listener(Self::previous_frame))
+            .on_action(cx.listener(Self::rotate_cw))
+            .on_action(cx.listener(Self::rotate_ccw))
+            .on_action(cx.listener(Self::flip_horizontal))
+            .on_action(cx.listener(Self::flip_vertical))
+            .on_action(cx.listener(Self::crop))
+            .on_action(cx.listener(Self::cancel_crop))
+            .on_action(cx.listener(Self::toggle_diff))
+            .on_action(cx.listener(Self::cycle_diff_mode))
             .size_full()
+            .relative()
+            .overflow_hidden()
+            .map(|this| match &self.diff {
+                Some(diff) if diff.is_adjustable() => this.cursor_ew_resize(),
+                Some(_) => this.cursor_default(),
+                None if self.cropping => this.cursor_crosshair(),
+                None if self.drag_position.is_some() => this.cursor_grabbing(),
+                None => this.cursor_grab(),
+            })
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| {
+                    this.drag_position = None;
+                    cx.notify();
+                }),
+            )
             .child(checkered_background)
-            .child(
-                div()
-                    .flex()
-                    .justify_center()
-                    .items_center()
-                    .w_full()
-                    // TODO: In browser based Tailwind & Flex this would be h-screen and we'd use w-full
-                    .h_full()
-                    .child(
+            .child(viewport)
+            .map(|this| match (diff, scaled_image) {
+                (Some(diff), _) => this.child(diff),
+                (None, Some((viewport, image_bounds))) => {
+                    let origin = image_bounds.origin - viewport.origin;
+                    this.child(
                         img(image)
-                            .object_fit(ObjectFit::ScaleDown)
-                            .max_w_full()
-                            .max_h_full()
+                            .absolute()
+                            .left(origin.x)
+                            .top(origin.y)
+                            .w(image_bounds.size.width)
+                            .h(image_bounds.size.height)
                             .id("img"),
-                    ),
-            )
+                    )
+                    .when_some(crop_selection, |this, selection| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .left(origin.x + selection.origin.x)
+                                .top(origin.y + selection.origin.y)
+                                .w(selection.size.width)
+                                .h(selection.size.height)
+                                .border_1()
+                                .border_color(cx.theme().colors().border_focused)
+                                .bg(cx.theme().colors().element_selected.opacity(0.3)),
+                        )
+                    })
+                }
+                (None, None) => this.child(
+                    div()
+                        .flex()
+                        .justify_center()
+                        .items_center()
+                        .w_full()
+                        // TODO: In browser based Tailwind & Flex this would be h-screen and we'd use w-full
+                        .h_full()
+                        .child(
+                            img(image)
+                                .object_fit(ObjectFit::ScaleDown)
+                                .max_w_full()
+                                .max_h_full()
+                                .id("img"),
+                        ),
+                ),
+            })
     }
 }
 
@@ -387,6 +1036,18 @@ pub fn init(cx: &mut App) {
     ImageViewerSettings::register(cx);
     workspace::register_project_item::<ImageView>(cx);
     workspace::register_serializable_item::<ImageView>(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        workspace.register_action(|workspace, _: &ToggleMetadataPanel, window, cx| {
+            image_metadata_panel::toggle_metadata_panel(workspace, window, cx);
+        });
+        let panel = cx.new(|cx| ImageMetadataPanel::new(workspace, window, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
 }
 
 mod persistence {
@@ -441,3 +1102,305 @@ mod persistence {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::{Modifiers, TestAppContext, VisualTestContext};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::{path::Path, time::Duration};
+    use util::rel_path::rel_path;
+
+    /// A png file that consists of a single white pixel.
+    const WHITE_PIXEL_PNG: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F,
+        0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0A, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            ImageViewerSettings::register(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_zoom_and_pan(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({})).await;
+        fs.insert_file("/root/image.png", WHITE_PIXEL_PNG.to_vec())
+            .await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let worktree_id =
+            cx.update(|cx| project.read(cx).worktrees(cx).next().unwrap().read(cx).id());
+        let image = project
+            .update(cx, |project, cx| {
+                project.open_image(
+                    ProjectPath {
+                        worktree_id,
+                        path: rel_path("image.png").into(),
+                    },
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        let window = cx.add_window(|window, cx| ImageView::new(image, project, window, cx));
+        let view = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        cx.simulate_resize(size(px(10.), px(10.)));
+        view.update_in(cx, |view, window, _| window.focus(&view.focus_handle));
+        cx.run_until_parked();
+
+        // The pixel fits the viewport without being scaled up.
+        view.update(cx, |view, cx| {
+            assert_eq!(view.zoom_percentage(cx), Some(100));
+        });
+
+        cx.dispatch_action(ZoomIn);
+        view.update(cx, |view, cx| {
+            assert_eq!(view.zoom_percentage(cx), Some(125));
+            assert_eq!(view.pan, Point::default());
+        });
+        cx.dispatch_action(ZoomOut);
+        cx.dispatch_action(ZoomOut);
+        view.update(cx, |view, cx| {
+            assert_eq!(view.zoom_percentage(cx), Some(80))
+        });
+
+        // Zooming stops at the maximum zoom, where the image is larger than the viewport.
+        for _ in 0..40 {
+            cx.dispatch_action(ZoomIn);
+        }
+        view.update(cx, |view, cx| {
+            assert_eq!(view.zoom_percentage(cx), Some((MAX_ZOOM * 100.) as u32));
+        });
+
+        // Dragging pans the image, as far as its edges.
+        cx.simulate_mouse_down(point(px(5.), px(5.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(point(px(8.), px(3.)), MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, _| assert_eq!(view.pan, point(px(3.), px(-2.))));
+        cx.simulate_mouse_up(point(px(8.), px(3.)), MouseButton::Left, Modifiers::none());
+        for _ in 0..2 {
+            cx.simulate_mouse_down(point(px(1.), px(3.)), MouseButton::Left, Modifiers::none());
+            cx.simulate_mouse_move(point(px(9.), px(3.)), MouseButton::Left, Modifiers::none());
+            cx.simulate_mouse_up(point(px(9.), px(3.)), MouseButton::Left, Modifiers::none());
+        }
+        // The 32px image can move 11px either way within the 10px viewport.
+        view.update(cx, |view, _| assert_eq!(view.pan, point(px(11.), px(-2.))));
+
+        // Zooming out keeps the image within the viewport.
+        for _ in 0..40 {
+            cx.dispatch_action(ZoomOut);
+        }
+        view.update(cx, |view, cx| {
+            assert_eq!(view.zoom_percentage(cx), Some((MIN_ZOOM * 100.) as u32));
+            assert_eq!(view.pan, Point::default());
+        });
+
+        cx.dispatch_action(ResetZoom);
+        view.update(cx, |view, cx| {
+            assert_eq!(view.zoom, None);
+            assert_eq!(view.pan, Point::default());
+            assert_eq!(view.zoom_percentage(cx), Some(100));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_playback_stops_while_hidden(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut gif = Vec::new();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(&mut gif);
+            for color in [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]] {
+                encoder
+                    .encode_frame(image::Frame::from_parts(
+                        image::RgbaImage::from_pixel(1, 1, image::Rgba(color)),
+                        0,
+                        0,
+                        image::Delay::from_numer_denom_ms(100, 1),
+                    ))
+                    .unwrap();
+            }
+        }
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({})).await;
+        fs.insert_file("/root/image.gif", gif).await;
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        let worktree_id =
+            cx.update(|cx| project.read(cx).worktrees(cx).next().unwrap().read(cx).id());
+        let image = project
+            .update(cx, |project, cx| {
+                project.open_image(
+                    ProjectPath {
+                        worktree_id,
+                        path: rel_path("image.gif").into(),
+                    },
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+
+        struct Container {
+            image_view: Entity<ImageView>,
+            visible: bool,
+        }
+
+        impl Render for Container {
+            fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+                div()
+                    .size_full()
+                    .when(self.visible, |this| this.child(self.image_view.clone()))
+            }
+        }
+
+        let window = cx.add_window(|window, cx| Container {
+            image_view: cx.new(|cx| ImageView::new(image, project, window, cx)),
+            visible: true,
+        });
+        let container = window.root(cx).unwrap();
+        let image_view = container.read_with(cx, |container, _| container.image_view.clone());
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        cx.run_until_parked();
+        let frame_ix = |cx: &mut VisualTestContext| {
+            image_view.read_with(cx, |image_view, cx| {
+                image_view.frame_position(cx).unwrap().0
+            })
+        };
+        assert_eq!(frame_ix(cx), 0);
+
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert_eq!(frame_ix(cx), 1);
+
+        // Once the image isn't shown, at most the frame that was already due is shown.
+        container.update(cx, |container, cx| {
+            container.visible = false;
+            cx.notify();
+        });
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        let hidden_frame_ix = frame_ix(cx);
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert_eq!(frame_ix(cx), hidden_frame_ix);
+
+        // Playback resumes once it's shown again.
+        container.update(cx, |container, cx| {
+            container.visible = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert_eq!(frame_ix(cx), (hidden_frame_ix + 1) % 3);
+    }
+
+    #[gpui::test]
+    async fn test_diff_with_head(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/root", json!({ ".git": {} })).await;
+        fs.insert_file("/root/image.png", WHITE_PIXEL_PNG.to_vec())
+            .await;
+        fs.insert_file("/root/new.png", WHITE_PIXEL_PNG.to_vec())
+            .await;
+        // The fake repository only stores text, so HEAD has a plain-text image of 2 by 1 pixels.
+        fs.set_head_for_repo(
+            Path::new("/root/.git"),
+            &[("image.png", "P3\n2 1\n255\n0 0 0 0 0 0\n".into())],
+            "deadbeef",
+        );
+        let project = Project::test(fs, ["/root".as_ref()], cx).await;
+        cx.run_until_parked();
+        let worktree_id =
+            cx.update(|cx| project.read(cx).worktrees(cx).next().unwrap().read(cx).id());
+        let open_image = |path: &'static str, cx: &mut TestAppContext| {
+            project.update(cx, |project, cx| {
+                project.open_image(
+                    ProjectPath {
+                        worktree_id,
+                        path: rel_path(path).into(),
+                    },
+                    cx,
+                )
+            })
+        };
+        let image = open_image("image.png", cx).await.unwrap();
+        let new_image = open_image("new.png", cx).await.unwrap();
+
+        let window = cx.add_window({
+            let project = project.clone();
+            |window, cx| ImageView::new(image, project, window, cx)
+        });
+        let view = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        cx.simulate_resize(size(px(10.), px(10.)));
+        view.update_in(cx, |view, window, _| window.focus(&view.focus_handle));
+        cx.run_until_parked();
+
+        // The image is compared side by side with its committed version by default.
+        cx.dispatch_action(ToggleDiff);
+        cx.run_until_parked();
+        view.update(cx, |view, _| {
+            let diff = view.diff().unwrap();
+            assert_eq!(diff.mode, ImageDiffMode::SideBySide);
+            assert!(!diff.is_adjustable());
+            let committed_image = diff.committed_image.as_ref().unwrap();
+            assert_eq!((committed_image.width, committed_image.height), (2, 1));
+        });
+
+        // Dragging in swipe mode moves the divider, instead of panning the image.
+        cx.dispatch_action(CycleDiffMode);
+        view.update(cx, |view, _| {
+            assert_eq!(view.diff().unwrap().mode, ImageDiffMode::Swipe);
+            assert!(view.diff().unwrap().is_adjustable());
+        });
+        cx.simulate_mouse_down(point(px(2.), px(5.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_move(point(px(8.), px(5.)), MouseButton::Left, Modifiers::none());
+        cx.simulate_mouse_up(point(px(8.), px(5.)), MouseButton::Left, Modifiers::none());
+        view.update(cx, |view, _| {
+            assert_eq!(view.diff().unwrap().mix, 0.8);
+            assert_eq!(view.pan, Point::default());
+        });
+
+        // The divider position becomes the opacity in onion skin mode.
+        cx.dispatch_action(CycleDiffMode);
+        view.update(cx, |view, _| {
+            assert_eq!(view.diff().unwrap().mode, ImageDiffMode::OnionSkin);
+            assert_eq!(view.diff().unwrap().mix, 0.8);
+        });
+
+        // The image can't be cropped while it's compared.
+        cx.dispatch_action(Crop);
+        view.update(cx, |view, _| assert!(!view.cropping));
+
+        cx.dispatch_action(ToggleDiff);
+        view.update(cx, |view, _| assert!(view.diff().is_none()));
+
+        // An image that isn't committed is compared with nothing.
+        let new_view =
+            cx.update(|window, cx| cx.new(|cx| ImageView::new(new_image, project, window, cx)));
+        new_view.update_in(cx, |view, window, cx| {
+            view.toggle_diff(&ToggleDiff, window, cx)
+        });
+        cx.run_until_parked();
+        new_view.update(cx, |view, _| {
+            let diff = view.diff().unwrap();
+            assert!(diff.committed_image.is_none());
+            assert!(!diff.is_adjustable());
+        });
+    }
+}
@@ -1,20 +1,42 @@
-use gpui::App;
-pub use settings::ImageFileSizeUnit;
-use settings::Settings;
+use gpui::{App, Pixels, px};
+use settings::{DockSide, Settings};
+pub use settings::{ImageDiffMode, ImageFileSizeUnit, ImageZoomMode};
 
 /// The settings for the image viewer.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct ImageViewerSettings {
     /// The unit to use for displaying image file sizes.
     ///
     /// Default: "binary"
     pub unit: ImageFileSizeUnit,
+    /// How images are scaled when opened, and when their zoom is reset.
+    ///
+    /// Default: "fit"
+    pub zoom_mode: ImageZoomMode,
+    /// How images are compared with their version in git's HEAD.
+    ///
+    /// Default: "side_by_side"
+    pub diff_mode: ImageDiffMode,
+    /// Where to dock the image metadata panel.
+    ///
+    /// Default: "right"
+    pub metadata_panel_dock: DockSide,
+    /// The default width of the image metadata panel.
+    ///
+    /// Default: 300
+    pub metadata_panel_default_width: Pixels,
 }
 
 impl Settings for ImageViewerSettings {
     fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let image_viewer = content.image_viewer.clone().unwrap();
+        let metadata_panel = image_viewer.metadata_panel.unwrap();
         Self {
-            unit: content.image_viewer.clone().unwrap().unit.unwrap(),
+            unit: image_viewer.unit.unwrap(),
+            zoom_mode: image_viewer.zoom_mode.unwrap(),
+            diff_mode: image_viewer.diff_mode.unwrap(),
+            metadata_panel_dock: metadata_panel.dock.unwrap(),
+            metadata_panel_default_width: metadata_panel.default_width.map(px).unwrap(),
         }
     }
 }
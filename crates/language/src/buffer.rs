@@ -103,11 +103,18 @@ pub struct Buffer {
     transaction_depth: usize,
     was_dirty_before_starting_transaction: Option<bool>,
     reload_task: Option<Task<Result<()>>>,
+    /// The version of the buffer before its last reload changed its text.
+    version_before_reload: Option<clock::Global>,
+    locked: bool,
+    generated: bool,
     language: Option<Arc<Language>>,
     autoindent_requests: Vec<Arc<AutoindentRequest>>,
     wait_for_autoindent_txs: Vec<oneshot::Sender<()>>,
     pending_autoindent: Option<Task<()>>,
     sync_parse_timeout: Duration,
+    /// How long the most recent background parse took, used to decide whether
+    /// the next parse is worth waiting for on the main thread.
+    last_parse_duration: Duration,
     syntax_map: Mutex<SyntaxMap>,
     reparse: Option<Task<()>>,
     parse_status: (watch::Sender<ParseStatus>, watch::Receiver<ParseStatus>),
@@ -962,6 +969,29 @@ impl Buffer {
         self.capability == Capability::ReadOnly
     }
 
+    /// Whether the buffer is protected from accidental edits in editors, e.g. because it's a
+    /// dependency or generated code. Unlike a read-only [`Capability`], this can be lifted.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Protects the buffer from edits in editors, or lifts that protection.
+    pub fn set_locked(&mut self, locked: bool, cx: &mut Context<Self>) {
+        if self.locked != locked {
+            self.locked = locked;
+            cx.emit(BufferEvent::CapabilityChanged)
+        }
+    }
+
+    /// Whether the buffer's file was detected as generated, so that editors open it folded.
+    pub fn is_generated(&self) -> bool {
+        self.generated
+    }
+
+    pub fn set_generated(&mut self, generated: bool) {
+        self.generated = generated;
+    }
+
     /// Builds a [`Buffer`] with the given underlying [`TextBuffer`], diff base, [`File`] and [`Capability`].
     pub fn build(buffer: TextBuffer, file: Option<Arc<dyn File>>, capability: Capability) -> Self {
         let saved_mtime = file.as_ref().and_then(|file| file.disk_state().mtime());
@@ -972,6 +1002,9 @@ impl Buffer {
             saved_version: buffer.version(),
             preview_version: buffer.version(),
             reload_task: None,
+            version_before_reload: None,
+            locked: false,
+            generated: false,
             transaction_depth: 0,
             was_dirty_before_starting_transaction: None,
             has_unsaved_edits: Cell::new((buffer.version(), false)),
@@ -983,6 +1016,7 @@ impl Buffer {
             reparse: None,
             non_text_state_update_count: 0,
             sync_parse_timeout: Duration::from_millis(1),
+            last_parse_duration: Duration::ZERO,
             parse_status: watch::channel(ParseStatus::Idle),
             autoindent_requests: Default::default(),
             wait_for_autoindent_txs: Default::default(),
@@ -1342,11 +1376,14 @@ impl Buffer {
             this.update(cx, |this, cx| {
                 if this.version() == diff.base_version {
                     this.finalize_last_transaction();
+                    this.version_before_reload =
+                        (!diff.edits.is_empty()).then(|| diff.base_version.clone());
                     this.apply_diff(diff, cx);
                     tx.send(this.finalize_last_transaction().cloned()).ok();
                     this.has_conflict = false;
                     this.did_reload(this.version(), this.line_ending(), new_mtime, cx);
                 } else {
+                    this.version_before_reload = None;
                     if !diff.edits.is_empty()
                         || this
                             .edits_since::<usize>(&diff.base_version)
@@ -1365,6 +1402,12 @@ impl Buffer {
         rx
     }
 
+    /// The version of the buffer before its last reload from disk changed its text, which can be
+    /// used to recover the text the buffer had before the file was changed externally.
+    pub fn version_before_reload(&self) -> Option<&clock::Global> {
+        self.version_before_reload.as_ref()
+    }
+
     /// This method is called to signal that the buffer has been reloaded.
     pub fn did_reload(
         &mut self,
@@ -1511,7 +1554,12 @@ impl Buffer {
     ///
     /// Meanwhile, in the foreground, we block the main thread for up to 1ms
     /// waiting on the parse to complete. As soon as it completes, we proceed
-    /// synchronously, unless a 1ms timeout elapses.
+    /// synchronously, unless a 1ms timeout elapses. If the previous parse took
+    /// longer than that, as it does for large files, we don't wait at all, so
+    /// that typing never stalls on a parse that's unlikely to finish in time.
+    /// Until the new parse lands, highlights come from the interpolated tree.
+    /// Highlight queries aren't part of the parse: they run when chunks are
+    /// read, over the requested range only.
     ///
     /// If we time out waiting on the parse, we spawn a second task waiting
     /// until the parse does complete and return with the interpolated tree still
@@ -1541,28 +1589,35 @@ impl Buffer {
         let mut syntax_snapshot = syntax_map.snapshot();
         drop(syntax_map);
 
+        let executor = cx.background_executor().clone();
         let parse_task = cx.background_spawn({
             let language = language.clone();
             let language_registry = language_registry.clone();
             async move {
+                let started_at = executor.now();
                 syntax_snapshot.reparse(&text, language_registry, language);
-                syntax_snapshot
+                (syntax_snapshot, executor.now() - started_at)
             }
         });
 
         self.parse_status.0.send(ParseStatus::Parsing).unwrap();
-        match cx
-            .background_executor()
-            .block_with_timeout(self.sync_parse_timeout, parse_task)
-        {
-            Ok(new_syntax_snapshot) => {
+        let parse_result = if self.last_parse_duration > self.sync_parse_timeout {
+            Err(parse_task)
+        } else {
+            cx.background_executor()
+                .block_with_timeout(self.sync_parse_timeout, parse_task)
+        };
+        match parse_result {
+            Ok((new_syntax_snapshot, parse_duration)) => {
+                self.last_parse_duration = parse_duration;
                 self.did_finish_parsing(new_syntax_snapshot, cx);
                 self.reparse = None;
             }
             Err(parse_task) => {
                 self.reparse = Some(cx.spawn(async move |this, cx| {
-                    let new_syntax_map = parse_task.await;
+                    let (new_syntax_map, parse_duration) = parse_task.await;
                     this.update(cx, move |this, cx| {
+                        this.last_parse_duration = parse_duration;
                         let grammar_changed =
                             this.language.as_ref().is_none_or(|current_language| {
                                 !Arc::ptr_eq(&language, current_language)
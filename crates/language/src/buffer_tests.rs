@@ -265,6 +265,83 @@ async fn test_first_line_pattern(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_language_for_file_from_content(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        init_settings(cx, |settings| {
+            settings.first_line_patterns.insert(
+                "Ruby".into(),
+                vec![r"^# frozen_string_literal".into()].into(),
+            );
+        })
+    });
+
+    let languages = Arc::new(LanguageRegistry::test(cx.executor()));
+    for (name, path_suffixes) in [
+        ("Python", vec!["py"]),
+        ("Ruby", vec!["rb"]),
+        ("Shell Script", vec!["sh", "bash"]),
+        ("Dockerfile", vec!["Dockerfile"]),
+    ] {
+        languages.register_test_language(LanguageConfig {
+            name: name.into(),
+            matcher: LanguageMatcher {
+                path_suffixes: path_suffixes.into_iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+    let language_name = |path: &str, content: &str| {
+        cx.read(|cx| {
+            languages
+                .language_for_file(&file(path), Some(&content.into()), cx)
+                .map(|language| language.name())
+        })
+    };
+
+    // shebangs
+    assert_eq!(
+        language_name("bin/script", "#!/usr/bin/env python3\nprint()"),
+        Some("Python".into())
+    );
+    assert_eq!(
+        language_name("bin/script", "#!/bin/bash -e\n"),
+        Some("Shell Script".into())
+    );
+    assert_eq!(language_name("bin/script", "#!/usr/bin/env perl\n"), None);
+
+    // modelines, at the start or the end of the file, override the path
+    assert_eq!(
+        language_name("notes.txt", "# vim: set ft=ruby:\nputs 1"),
+        Some("Ruby".into())
+    );
+    assert_eq!(
+        language_name(
+            "a.rb",
+            &format!("{}# -*- mode: python -*-\n", "x = 1\n".repeat(20))
+        ),
+        Some("Python".into())
+    );
+
+    // user configured first line patterns
+    assert_eq!(
+        language_name("Gemfile", "# frozen_string_literal: true\n"),
+        Some("Ruby".into())
+    );
+    assert_eq!(
+        language_name("a.py", "# frozen_string_literal: true\n"),
+        Some("Python".into())
+    );
+
+    // filenames with an extra extension
+    assert_eq!(
+        language_name("Dockerfile.prod", "FROM alpine"),
+        Some("Dockerfile".into())
+    );
+    assert_eq!(language_name("script.py.bak", ""), None);
+}
+
 #[gpui::test]
 async fn test_language_for_file_with_custom_file_types(cx: &mut TestAppContext) {
     cx.update(|cx| {
@@ -599,6 +676,79 @@ async fn test_normalize_whitespace(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_slow_parse_does_not_block(cx: &mut gpui::TestAppContext) {
+    let buffer =
+        cx.new(|cx| Buffer::local("fn a() {}", cx).with_language(Arc::new(rust_lang()), cx));
+    cx.executor().run_until_parked();
+
+    // Once a parse has taken longer than the sync timeout, edits don't wait for
+    // the next one, and the interpolated tree is used until it completes.
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_sync_parse_timeout(Duration::from_secs(1));
+        buffer.last_parse_duration = Duration::from_secs(2);
+        let offset = buffer.text().find(')').unwrap();
+        buffer.edit([(offset..offset, "b: C")], None, cx);
+        assert!(buffer.is_parsing());
+    });
+    cx.executor().run_until_parked();
+    assert!(!buffer.update(cx, |buffer, _| buffer.is_parsing()));
+    assert_eq!(
+        get_tree_sexp(&buffer, cx),
+        concat!(
+            "(source_file (function_item name: (identifier) ",
+            "parameters: (parameters (parameter pattern: (identifier) type: (type_identifier))) ",
+            "body: (block)))"
+        )
+    );
+
+    // Fast parses complete synchronously again.
+    buffer.update(cx, |buffer, cx| {
+        assert_eq!(buffer.last_parse_duration, Duration::ZERO);
+        buffer.edit([(0..0, "\n")], None, cx);
+        assert!(!buffer.is_parsing());
+    });
+}
+
+#[gpui::test]
+async fn test_highlights_are_kept_until_slow_parse_lands(cx: &mut gpui::TestAppContext) {
+    let language = Arc::new(
+        rust_lang()
+            .with_highlights_query("(function_item name: (identifier) @function)")
+            .unwrap(),
+    );
+    language.set_theme(&theme::SyntaxTheme::new_test([("function", gpui::red())]));
+    let buffer = cx.new(|cx| Buffer::local("fn a() {}", cx).with_language(language, cx));
+    cx.executor().run_until_parked();
+
+    let highlighted_text = |buffer: &Buffer| {
+        let snapshot = buffer.snapshot();
+        snapshot
+            .chunks(0..snapshot.len(), true)
+            .filter(|chunk| chunk.syntax_highlight_id.is_some_and(|id| !id.is_default()))
+            .map(|chunk| chunk.text.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // While a slow parse is pending, the previous tree keeps highlighting the
+    // text it knows about, rather than the highlights disappearing.
+    buffer.update(cx, |buffer, cx| {
+        assert_eq!(highlighted_text(buffer), ["a"]);
+        buffer.set_sync_parse_timeout(Duration::from_secs(1));
+        buffer.last_parse_duration = Duration::from_secs(2);
+        buffer.edit([(buffer.len()..buffer.len(), "\nfn b() {}")], None, cx);
+        assert!(buffer.is_parsing());
+        assert_eq!(highlighted_text(buffer), ["a"]);
+    });
+
+    // Once the new tree lands, it highlights the new text as well.
+    cx.executor().run_until_parked();
+    buffer.update(cx, |buffer, _| {
+        assert!(!buffer.is_parsing());
+        assert_eq!(highlighted_text(buffer), ["a", "b"]);
+    });
+}
+
 #[gpui::test]
 async fn test_reparse(cx: &mut gpui::TestAppContext) {
     let text = "fn a() {}";
@@ -0,0 +1,106 @@
+//! Detection of a file's language from its content, for files whose path doesn't determine it.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// How many lines at the start and the end of a file are searched for modelines, like Vim does.
+pub(crate) const MODELINE_LINES: u32 = 5;
+/// How many bytes at the start and the end of a file are searched for modelines at most, so that
+/// files with very long lines aren't searched entirely.
+pub(crate) const MAX_MODELINE_SEARCH_LEN: usize = 4096;
+
+static VIM_MODELINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|\s)(?:vi|vim|ex|Vim|vim[<=>]?\d+):\s*(?:set?\s+)?(.*)").unwrap()
+});
+static EMACS_MODELINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"-\*-(.*?)-\*-").unwrap());
+static EMACS_MODE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(?:^|;)\s*mode\s*:\s*([^\s;]+)").unwrap());
+
+/// The language named by a Vim (`vim: set ft=python:`) or Emacs (`-*- mode: python -*-`)
+/// modeline in `text`.
+pub(crate) fn modeline_language(text: &str) -> Option<&str> {
+    text.lines().find_map(|line| {
+        if let Some(captures) = VIM_MODELINE.captures(line) {
+            let options = captures.get(1)?.as_str();
+            let language = options
+                .split(|c: char| c == ':' || c.is_whitespace())
+                .filter_map(|option| option.split_once('='))
+                .find_map(|(name, value)| {
+                    matches!(name, "ft" | "filetype" | "syn" | "syntax").then_some(value)
+                });
+            if let Some(language) = language.filter(|language| !language.is_empty()) {
+                return Some(language);
+            }
+        }
+
+        let variables = EMACS_MODELINE.captures(line)?.get(1)?.as_str().trim();
+        if variables.contains(':') {
+            Some(EMACS_MODE.captures(variables)?.get(1)?.as_str())
+        } else {
+            Some(variables).filter(|mode| !mode.is_empty())
+        }
+    })
+}
+
+/// The name of the interpreter of a script starting with a shebang line, without its version,
+/// e.g. `python` for `#!/usr/bin/env python3`.
+pub(crate) fn shebang_interpreter(first_line: &str) -> Option<&str> {
+    let mut args = first_line.strip_prefix("#!")?.split_whitespace();
+    let mut interpreter = file_name(args.next()?);
+    if interpreter == "env" {
+        interpreter = file_name(args.find(|arg| !arg.starts_with('-') && !arg.contains('='))?);
+    }
+    let unversioned = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    Some(if unversioned.is_empty() {
+        interpreter
+    } else {
+        unversioned
+    })
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modeline_language() {
+        assert_eq!(modeline_language("# vim: set ft=python :"), Some("python"));
+        assert_eq!(
+            modeline_language("// vim:ts=4:ft=javascript"),
+            Some("javascript")
+        );
+        assert_eq!(modeline_language("/* vi: filetype=c sw=2 */"), Some("c"));
+        assert_eq!(modeline_language("# vim: ts=4"), None);
+        assert_eq!(modeline_language(";; -*- mode: lisp; -*-"), Some("lisp"));
+        assert_eq!(
+            modeline_language("# -*- coding: utf-8; Mode: ruby -*-"),
+            Some("ruby")
+        );
+        assert_eq!(modeline_language("#!/bin/sh\n# -*- sh -*-"), Some("sh"));
+        assert_eq!(modeline_language("# -*- coding: utf-8 -*-"), None);
+        assert_eq!(modeline_language("let vimrc = 1;"), None);
+    }
+
+    #[test]
+    fn test_shebang_interpreter() {
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env python3"),
+            Some("python")
+        );
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/python3.11 -u"),
+            Some("python")
+        );
+        assert_eq!(shebang_interpreter("#! /bin/sh"), Some("sh"));
+        assert_eq!(
+            shebang_interpreter("#!/usr/bin/env -S LANG=C ruby --disable-gems"),
+            Some("ruby")
+        );
+        assert_eq!(shebang_interpreter("#!/usr/bin/env"), None);
+        assert_eq!(shebang_interpreter("# not a shebang"), None);
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! Notably we do *not* assign a single language to a single file; in real world a single file can consist of multiple programming languages - HTML is a good example of that - and `language` crate tends to reflect that status quo in its API.
 mod buffer;
+mod content_detection;
 mod diagnostic_set;
 mod highlight_map;
 mod language_registry;
@@ -777,6 +778,10 @@ pub struct LanguageConfig {
     /// A list of preferred debuggers for this language.
     #[serde(default)]
     pub debuggers: IndexSet<SharedString>,
+    /// Line prefixes that start a REPL code cell, in addition to a line comment
+    /// followed by `%%` (e.g. `##` in Julia).
+    #[serde(default)]
+    pub repl_cell_delimiters: Vec<Arc<str>>,
 }
 
 #[derive(Clone, Debug, Deserialize, Default, JsonSchema)]
@@ -973,6 +978,7 @@ impl Default for LanguageConfig {
             completion_query_characters: Default::default(),
             linked_edit_characters: Default::default(),
             debuggers: Default::default(),
+            repl_cell_delimiters: Default::default(),
         }
     }
 }
@@ -1,17 +1,21 @@
 use crate::{
     CachedLspAdapter, File, Language, LanguageConfig, LanguageId, LanguageMatcher,
     LanguageServerName, LspAdapter, ManifestName, PLAIN_TEXT, ToolchainLister,
-    language_settings::all_language_settings, task_context::ContextProvider, with_parser,
+    content_detection::{
+        MAX_MODELINE_SEARCH_LEN, MODELINE_LINES, modeline_language, shebang_interpreter,
+    },
+    language_settings::{AllLanguageSettings, all_language_settings},
+    task_context::ContextProvider,
+    with_parser,
 };
 use anyhow::{Context as _, Result, anyhow};
-use collections::{FxHashMap, HashMap, HashSet, hash_map};
+use collections::{HashMap, HashSet, hash_map};
 use settings::{AllLanguageSettingsContent, LanguageSettingsContent};
 
 use futures::{
     Future,
     channel::{mpsc, oneshot},
 };
-use globset::GlobSet;
 use gpui::{App, BackgroundExecutor, SharedString};
 use lsp::LanguageServerId;
 use parking_lot::{Mutex, RwLock};
@@ -722,13 +726,9 @@ impl LanguageRegistry {
         content: Option<&Rope>,
         cx: &App,
     ) -> Option<AvailableLanguage> {
-        let user_file_types = all_language_settings(Some(file), cx);
+        let user_settings = all_language_settings(Some(file), cx);
 
-        self.language_for_file_internal(
-            &file.full_path(cx),
-            content,
-            Some(&user_file_types.file_types),
-        )
+        self.language_for_file_internal(&file.full_path(cx), content, Some(user_settings))
     }
 
     pub fn language_for_file_path(self: &Arc<Self>, path: &Path) -> Option<AvailableLanguage> {
@@ -751,12 +751,21 @@ impl LanguageRegistry {
         }
     }
 
+    /// The language of the file at `path`, detected from a modeline in its `content` first, then
+    /// from its path, and then from the first line of its content.
     fn language_for_file_internal(
         self: &Arc<Self>,
         path: &Path,
         content: Option<&Rope>,
-        user_file_types: Option<&FxHashMap<Arc<str>, GlobSet>>,
+        user_settings: Option<&AllLanguageSettings>,
     ) -> Option<AvailableLanguage> {
+        if let Some(content) = content
+            && let Some(language) = self.language_for_modeline(content)
+        {
+            return Some(language);
+        }
+
+        let user_file_types = user_settings.map(|settings| &settings.file_types);
         let filename = path.file_name().and_then(|filename| filename.to_str());
         // `Path.extension()` returns None for files with a leading '.'
         // and no other extension which is not the desired behavior here,
@@ -773,77 +782,171 @@ impl LanguageRegistry {
                 content.chunks_in_range(0..end).collect::<String>()
             })
         });
-        self.find_matching_language(move |language_name, config, current_best_match| {
-            let path_matches_default_suffix = || {
-                let len =
-                    config
-                        .path_suffixes
-                        .iter()
-                        .fold(0, |acc: usize, path_suffix: &String| {
-                            let ext = ".".to_string() + path_suffix;
-
-                            let matched_suffix_len = path_suffixes
+        // A user's first line patterns take precedence over the languages' own patterns.
+        let user_content_language = LazyCell::new(|| {
+            let content = content.as_ref()?;
+            user_settings?
+                .first_line_patterns
+                .iter()
+                .find(|(_, patterns)| patterns.iter().any(|pattern| pattern.is_match(content)))
+                .map(|(language_name, _)| language_name.clone())
+        });
+        let matching_language =
+            self.find_matching_language(|language_name, config, current_best_match| {
+                let path_matches_default_suffix = || {
+                    let len =
+                        config
+                            .path_suffixes
+                            .iter()
+                            .fold(0, |acc: usize, path_suffix: &String| {
+                                let ext = ".".to_string() + path_suffix;
+
+                                let matched_suffix_len = path_suffixes
+                                    .iter()
+                                    .find(|(suffix, _)| {
+                                        suffix.ends_with(&ext) || suffix == path_suffix
+                                    })
+                                    .map(|(suffix, _)| suffix.len());
+
+                                match matched_suffix_len {
+                                    Some(len) => acc.max(len),
+                                    None => acc,
+                                }
+                            });
+                    (len > 0).then_some(len)
+                };
+
+                let path_matches_custom_suffix = || {
+                    user_file_types
+                        .and_then(|types| types.get(language_name.as_ref()))
+                        .map_or(None, |custom_suffixes| {
+                            path_suffixes
                                 .iter()
-                                .find(|(suffix, _)| suffix.ends_with(&ext) || suffix == path_suffix)
-                                .map(|(suffix, _)| suffix.len());
-
-                            match matched_suffix_len {
-                                Some(len) => acc.max(len),
-                                None => acc,
-                            }
-                        });
-                (len > 0).then_some(len)
-            };
+                                .find(|(_, candidate)| {
+                                    custom_suffixes.is_match_candidate(candidate)
+                                })
+                                .map(|(suffix, _)| suffix.len())
+                        })
+                };
 
-            let path_matches_custom_suffix = || {
-                user_file_types
-                    .and_then(|types| types.get(language_name.as_ref()))
-                    .map_or(None, |custom_suffixes| {
-                        path_suffixes
-                            .iter()
-                            .find(|(_, candidate)| custom_suffixes.is_match_candidate(candidate))
-                            .map(|(suffix, _)| suffix.len())
-                    })
-            };
+                let content_matches = || match user_content_language.as_ref() {
+                    Some(user_content_language) => {
+                        UniCase::new(user_content_language.as_ref())
+                            == UniCase::new(language_name.as_ref())
+                    }
+                    None => config.first_line_pattern.as_ref().is_some_and(|pattern| {
+                        content
+                            .as_ref()
+                            .is_some_and(|content| pattern.is_match(content))
+                    }),
+                };
+
+                // Only return a match for the given file if we have a better match than
+                // the current one.
+                match current_best_match {
+                    LanguageMatchPrecedence::PathOrContent(current_len) => {
+                        if let Some(len) = path_matches_custom_suffix() {
+                            // >= because user config should win tie with system ext len
+                            (len >= current_len)
+                                .then_some(LanguageMatchPrecedence::UserConfigured(len))
+                        } else if let Some(len) = path_matches_default_suffix() {
+                            // >= because user config should win tie with system ext len
+                            (len >= current_len)
+                                .then_some(LanguageMatchPrecedence::PathOrContent(len))
+                        } else {
+                            None
+                        }
+                    }
+                    LanguageMatchPrecedence::Undetermined => {
+                        if let Some(len) = path_matches_custom_suffix() {
+                            Some(LanguageMatchPrecedence::UserConfigured(len))
+                        } else if let Some(len) = path_matches_default_suffix() {
+                            Some(LanguageMatchPrecedence::PathOrContent(len))
+                        } else if content_matches() {
+                            Some(LanguageMatchPrecedence::PathOrContent(1))
+                        } else {
+                            None
+                        }
+                    }
+                    LanguageMatchPrecedence::UserConfigured(_) => None,
+                }
+            });
+        if matching_language.is_some() {
+            return matching_language;
+        }
 
-            let content_matches = || {
-                config.first_line_pattern.as_ref().is_some_and(|pattern| {
-                    content
-                        .as_ref()
-                        .is_some_and(|content| pattern.is_match(content))
-                })
-            };
+        // Scripts are detected by their interpreter, e.g. `#!/usr/bin/env ruby`.
+        if let Some(content) = content.as_ref()
+            && let Some(language) = content
+                .lines()
+                .next()
+                .and_then(shebang_interpreter)
+                .and_then(|interpreter| self.available_language_for_alias(interpreter))
+        {
+            return Some(language);
+        }
 
-            // Only return a match for the given file if we have a better match than
-            // the current one.
+        // Files like `Dockerfile.prod` are detected by their name without the extension.
+        let (stem, _) = filename?.rsplit_once('.')?;
+        self.find_matching_language(|_, config, current_best_match| {
+            let stem_matches = config
+                .path_suffixes
+                .iter()
+                .any(|suffix| !suffix.contains('.') && suffix == stem);
             match current_best_match {
-                LanguageMatchPrecedence::PathOrContent(current_len) => {
-                    if let Some(len) = path_matches_custom_suffix() {
-                        // >= because user config should win tie with system ext len
-                        (len >= current_len).then_some(LanguageMatchPrecedence::UserConfigured(len))
-                    } else if let Some(len) = path_matches_default_suffix() {
-                        // >= because user config should win tie with system ext len
-                        (len >= current_len).then_some(LanguageMatchPrecedence::PathOrContent(len))
-                    } else {
-                        None
-                    }
+                LanguageMatchPrecedence::Undetermined if stem_matches => {
+                    Some(LanguageMatchPrecedence::PathOrContent(stem.len()))
                 }
-                LanguageMatchPrecedence::Undetermined => {
-                    if let Some(len) = path_matches_custom_suffix() {
-                        Some(LanguageMatchPrecedence::UserConfigured(len))
-                    } else if let Some(len) = path_matches_default_suffix() {
-                        Some(LanguageMatchPrecedence::PathOrContent(len))
-                    } else if content_matches() {
-                        Some(LanguageMatchPrecedence::PathOrContent(1))
-                    } else {
-                        None
-                    }
-                }
-                LanguageMatchPrecedence::UserConfigured(_) => None,
+                _ => None,
             }
         })
     }
 
+    /// The language named by the modeline at the start or end of `content`, if any.
+    fn language_for_modeline(self: &Arc<Self>, content: &Rope) -> Option<AvailableLanguage> {
+        let max_point = content.max_point();
+        let head_end = content.point_to_offset(Point::new(MODELINE_LINES, 0).min(max_point));
+        let head_end = content.clip_offset(head_end.min(MAX_MODELINE_SEARCH_LEN), Bias::Left);
+        let tail_start =
+            content.point_to_offset(Point::new(max_point.row.saturating_sub(MODELINE_LINES), 0));
+        let tail_start = content.clip_offset(
+            tail_start.max(content.len().saturating_sub(MAX_MODELINE_SEARCH_LEN)),
+            Bias::Right,
+        );
+        [0..head_end, tail_start.max(head_end)..content.len()]
+            .into_iter()
+            .find_map(|range| {
+                let text = content.chunks_in_range(range).collect::<String>();
+                self.available_language_for_alias(modeline_language(&text)?)
+            })
+    }
+
+    /// The language with the given name, or a file extension or well-known alias of its name, as
+    /// used by shebangs and modelines.
+    fn available_language_for_alias(self: &Arc<Self>, alias: &str) -> Option<AvailableLanguage> {
+        let name = match alias {
+            "node" | "nodejs" => "JavaScript",
+            "shell" | "shell-script" => "Shell Script",
+            "golang" => "Go",
+            alias => alias,
+        };
+        let name = UniCase::new(name);
+        let state = self.state.read();
+        let languages = || state.available_languages.iter().filter(|l| !l.hidden);
+        languages()
+            .find(|language| UniCase::new(language.name.0.as_ref()) == name)
+            .or_else(|| {
+                languages().find(|language| {
+                    language
+                        .matcher
+                        .path_suffixes
+                        .iter()
+                        .any(|suffix| UniCase::new(suffix.as_str()) == name)
+                })
+            })
+            .cloned()
+    }
+
     fn find_matching_language(
         self: &Arc<Self>,
         callback: impl Fn(
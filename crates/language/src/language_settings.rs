@@ -1,14 +1,16 @@
 //! Provides `language`-related settings.
 
 use crate::{File, Language, LanguageName, LanguageServerName};
+use anyhow::Context as _;
 use collections::{FxHashMap, HashMap, HashSet};
 use ec4rs::{
     Properties as EditorconfigProperties,
     property::{FinalNewline, IndentSize, IndentStyle, MaxLineLen, TabWidth, TrimTrailingWs},
 };
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
-use gpui::{App, Modifiers, SharedString};
+use gpui::{App, Modifiers, Pixels, SharedString, px};
 use itertools::{Either, Itertools};
+use regex::Regex;
 
 pub use settings::{
     CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FormatOnSave,
@@ -18,6 +20,7 @@ pub use settings::{
 use settings::{ExtendingVec, Settings, SettingsContent, SettingsLocation, SettingsStore};
 use shellexpand;
 use std::{borrow::Cow, num::NonZeroU32, path::Path, sync::Arc};
+use util::ResultExt as _;
 
 /// Initializes the language settings.
 pub fn init(cx: &mut App) {
@@ -57,6 +60,7 @@ pub struct AllLanguageSettings {
     pub defaults: LanguageSettings,
     languages: HashMap<LanguageName, LanguageSettings>,
     pub(crate) file_types: FxHashMap<Arc<str>, GlobSet>,
+    pub(crate) first_line_patterns: FxHashMap<Arc<str>, Vec<Regex>>,
 }
 
 #[derive(Debug, Clone)]
@@ -125,6 +129,14 @@ pub struct LanguageSettings {
     pub show_whitespaces: settings::ShowWhitespaceSetting,
     /// Visible characters used to render whitespace when show_whitespaces is enabled.
     pub whitespace_map: WhitespaceMap,
+    /// The font family to render buffers with, overriding the buffer font family.
+    pub font_family: Option<SharedString>,
+    /// The font size to render buffers with, overriding the buffer font size.
+    pub font_size: Option<Pixels>,
+    /// The line height to render buffers with, overriding the buffer line height.
+    pub line_height: Option<settings::BufferLineHeight>,
+    /// The text inserted for each file dropped into buffers while holding `alt`.
+    pub drop_path_template: String,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     pub extend_comment_on_newline: bool,
     /// Inlay hint related settings.
@@ -146,6 +158,8 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// Whether to find color literals in the text when no language server provides document colors.
+    pub color_literals: bool,
     /// Task configuration for this language.
     pub tasks: LanguageTaskSettings,
     /// Whether to pop the completions menu while typing in an editor without
@@ -185,6 +199,11 @@ pub struct CompletionSettings {
     ///
     /// Default: "replace_suffix"
     pub lsp_insert_mode: LspInsertMode,
+    /// Whether to offer completions for symbols that other files in the project export,
+    /// adding the import they need when accepted.
+    ///
+    /// Default: true
+    pub auto_imports: bool,
 }
 
 /// The settings for indent guides.
@@ -377,6 +396,8 @@ pub struct EditPredictionSettings {
     pub mode: settings::EditPredictionsMode,
     /// Settings specific to GitHub Copilot.
     pub copilot: CopilotSettings,
+    /// Settings for the custom edit prediction provider.
+    pub custom: CustomEditPredictionSettings,
     /// Whether edit predictions are enabled in the assistant panel.
     /// This setting has no effect if globally disabled.
     pub enabled_in_text_threads: bool,
@@ -412,6 +433,20 @@ pub struct CopilotSettings {
     pub enterprise_uri: Option<String>,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct CustomEditPredictionSettings {
+    /// The base URL of the OpenAI-compatible API.
+    pub api_url: String,
+    /// The model to request completions from.
+    pub model: Option<String>,
+    /// The maximum number of tokens to generate per prediction.
+    pub max_tokens: u32,
+    /// The environment variable to read an API key from.
+    pub api_key_env_var: Option<String>,
+    /// The ID of the extension-registered provider to request predictions from instead.
+    pub extension_provider: Option<String>,
+}
+
 impl AllLanguageSettings {
     /// Returns the [`LanguageSettings`] for the language with the specified name.
     pub fn language<'a>(
@@ -548,6 +583,12 @@ impl settings::Settings for AllLanguageSettings {
                     space: SharedString::new(whitespace_map.space.unwrap().to_string()),
                     tab: SharedString::new(whitespace_map.tab.unwrap().to_string()),
                 },
+                font_family: settings
+                    .font_family
+                    .map(|family| SharedString::new(family.0.as_ref())),
+                font_size: settings.font_size.map(px),
+                line_height: settings.line_height,
+                drop_path_template: settings.drop_path_template.unwrap(),
                 extend_comment_on_newline: settings.extend_comment_on_newline.unwrap(),
                 inlay_hints: InlayHintSettings {
                     enabled: inlay_hints.enabled.unwrap(),
@@ -570,6 +611,7 @@ impl settings::Settings for AllLanguageSettings {
                     .unwrap(),
                 code_actions_on_format: settings.code_actions_on_format.unwrap(),
                 linked_edits: settings.linked_edits.unwrap(),
+                color_literals: settings.color_literals.unwrap(),
                 tasks: LanguageTaskSettings {
                     variables: tasks.variables,
                     enabled: tasks.enabled.unwrap(),
@@ -583,6 +625,7 @@ impl settings::Settings for AllLanguageSettings {
                     lsp: completions.lsp.unwrap(),
                     lsp_fetch_timeout_ms: completions.lsp_fetch_timeout_ms.unwrap(),
                     lsp_insert_mode: completions.lsp_insert_mode.unwrap(),
+                    auto_imports: completions.auto_imports.unwrap(),
                 },
                 debuggers: settings.debuggers.unwrap(),
             }
@@ -622,6 +665,15 @@ impl settings::Settings for AllLanguageSettings {
             enterprise_uri: copilot.enterprise_uri,
         };
 
+        let custom = edit_predictions.custom.unwrap();
+        let custom_settings = CustomEditPredictionSettings {
+            api_url: custom.api_url.unwrap(),
+            model: custom.model,
+            max_tokens: custom.max_tokens.unwrap(),
+            api_key_env_var: custom.api_key_env_var,
+            extension_provider: custom.extension_provider,
+        };
+
         let enabled_in_text_threads = edit_predictions.enabled_in_text_threads.unwrap();
 
         let mut file_types: FxHashMap<Arc<str>, GlobSet> = FxHashMap::default();
@@ -636,6 +688,25 @@ impl settings::Settings for AllLanguageSettings {
             file_types.insert(language.clone(), builder.build().unwrap());
         }
 
+        let first_line_patterns = all_languages
+            .first_line_patterns
+            .iter()
+            .map(|(language, patterns)| {
+                let patterns = patterns
+                    .0
+                    .iter()
+                    .filter_map(|pattern| {
+                        Regex::new(pattern)
+                            .with_context(|| {
+                                format!("invalid first line pattern for {language}: {pattern:?}")
+                            })
+                            .log_err()
+                    })
+                    .collect();
+                (language.clone(), patterns)
+            })
+            .collect();
+
         Self {
             edit_predictions: EditPredictionSettings {
                 provider: if let Some(provider) = edit_prediction_provider {
@@ -655,11 +726,13 @@ impl settings::Settings for AllLanguageSettings {
                     .collect(),
                 mode: edit_predictions_mode,
                 copilot: copilot_settings,
+                custom: custom_settings,
                 enabled_in_text_threads,
             },
             defaults: default_language_settings,
             languages,
             file_types,
+            first_line_patterns,
         }
     }
 
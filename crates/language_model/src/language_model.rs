@@ -4,6 +4,7 @@ mod registry;
 mod request;
 mod role;
 mod telemetry;
+mod usage_tracker;
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod fake_provider;
@@ -36,6 +37,7 @@ pub use crate::registry::*;
 pub use crate::request::*;
 pub use crate::role::*;
 pub use crate::telemetry::*;
+pub use crate::usage_tracker::*;
 
 pub const ANTHROPIC_PROVIDER_ID: LanguageModelProviderId =
     LanguageModelProviderId::new("anthropic");
@@ -64,6 +66,7 @@ pub fn init(client: Arc<Client>, cx: &mut App) {
 
 pub fn init_settings(cx: &mut App) {
     registry::init(cx);
+    usage_tracker::init(cx);
 }
 
 /// A completion event from a language model.
@@ -447,6 +450,21 @@ impl TokenUsage {
             + self.cache_read_input_tokens
             + self.cache_creation_input_tokens
     }
+
+    /// The tokens in `self` that aren't already accounted for in `previous`,
+    /// for providers that report running totals while a response streams.
+    pub fn saturating_sub(self, previous: Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(previous.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(previous.output_tokens),
+            cache_creation_input_tokens: self
+                .cache_creation_input_tokens
+                .saturating_sub(previous.cache_creation_input_tokens),
+            cache_read_input_tokens: self
+                .cache_read_input_tokens
+                .saturating_sub(previous.cache_read_input_tokens),
+        }
+    }
 }
 
 impl Add<TokenUsage> for TokenUsage {
@@ -590,10 +608,19 @@ pub trait LanguageModel: Send + Sync {
         cx: &AsyncApp,
     ) -> BoxFuture<'static, Result<LanguageModelTextStream, LanguageModelCompletionError>> {
         let future = self.stream_completion(request, cx);
+        let mut usage_reporter = LanguageModelUsageTracker::reporter(self, cx);
 
         async move {
             let events = future.await?;
-            let mut events = events.fuse();
+            let mut events = events
+                .inspect(move |event| {
+                    if let (Ok(LanguageModelCompletionEvent::UsageUpdate(usage)), Some(reporter)) =
+                        (event, usage_reporter.as_mut())
+                    {
+                        reporter.report(*usage);
+                    }
+                })
+                .fuse();
             let mut message_id = None;
             let mut first_item_text = None;
             let last_token_usage = Arc::new(Mutex::new(TokenUsage::default()));
@@ -0,0 +1,247 @@
+use crate::{
+    LanguageModel, LanguageModelId, LanguageModelName, LanguageModelProviderId,
+    LanguageModelProviderName, TokenUsage,
+};
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Global, prelude::*};
+
+pub fn init(cx: &mut App) {
+    let (reports_tx, mut reports_rx) = mpsc::unbounded::<UsageReport>();
+    let tracker = cx.new(|cx| {
+        cx.spawn(async move |this, cx| {
+            while let Some(report) = reports_rx.next().await {
+                this.update(cx, |this, cx| {
+                    this.add_usage(&report.model, report.usage, report.new_request);
+                    cx.emit(UsageUpdated);
+                    cx.notify();
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach();
+        LanguageModelUsageTracker::default()
+    });
+    cx.set_global(GlobalLanguageModelUsageTracker {
+        tracker,
+        reports_tx,
+    });
+}
+
+struct GlobalLanguageModelUsageTracker {
+    tracker: Entity<LanguageModelUsageTracker>,
+    reports_tx: mpsc::UnboundedSender<UsageReport>,
+}
+
+impl Global for GlobalLanguageModelUsageTracker {}
+
+/// The tokens used with a single model since Zed was started.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelTokenUsage {
+    pub provider_id: LanguageModelProviderId,
+    pub provider_name: LanguageModelProviderName,
+    pub model_id: LanguageModelId,
+    pub model_name: LanguageModelName,
+    pub usage: TokenUsage,
+    pub request_count: u64,
+}
+
+/// Accumulates the tokens used by every language model request, per model, whether it comes
+/// from an agent thread, the inline assistant, commit message generation, or any other feature
+/// built on [`LanguageModel`]. Edit predictions aren't included, as they don't use these models.
+#[derive(Default)]
+pub struct LanguageModelUsageTracker {
+    models: Vec<ModelTokenUsage>,
+}
+
+pub struct UsageUpdated;
+
+impl EventEmitter<UsageUpdated> for LanguageModelUsageTracker {}
+
+/// The model that usage is reported for, without holding on to the model itself.
+#[derive(Clone)]
+struct UsageModel {
+    provider_id: LanguageModelProviderId,
+    provider_name: LanguageModelProviderName,
+    model_id: LanguageModelId,
+    model_name: LanguageModelName,
+}
+
+impl UsageModel {
+    fn new(model: &(impl LanguageModel + ?Sized)) -> Self {
+        Self {
+            provider_id: model.provider_id(),
+            provider_name: model.provider_name(),
+            model_id: model.id(),
+            model_name: model.name(),
+        }
+    }
+}
+
+struct UsageReport {
+    model: UsageModel,
+    usage: TokenUsage,
+    new_request: bool,
+}
+
+/// Reports the usage of a single request from any thread, as the response streams.
+pub struct UsageReporter {
+    model: UsageModel,
+    reports_tx: mpsc::UnboundedSender<UsageReport>,
+    reported: Option<TokenUsage>,
+}
+
+impl UsageReporter {
+    /// Reports the running total of the request's usage. Only the tokens that weren't part
+    /// of the previous total are added to the tracker.
+    pub fn report(&mut self, usage: TokenUsage) {
+        let previous = self.reported.replace(usage);
+        let report = UsageReport {
+            model: self.model.clone(),
+            usage: usage.saturating_sub(previous.unwrap_or_default()),
+            new_request: previous.is_none(),
+        };
+        self.reports_tx.unbounded_send(report).ok();
+    }
+}
+
+impl LanguageModelUsageTracker {
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalLanguageModelUsageTracker>()
+            .map(|global| global.tracker.clone())
+    }
+
+    /// Adds `usage` to the totals of `model`. Pass `new_request` for the first
+    /// update of a request, so that requests are only counted once.
+    pub fn record(model: &dyn LanguageModel, usage: TokenUsage, new_request: bool, cx: &mut App) {
+        let Some(tracker) = Self::global(cx) else {
+            return;
+        };
+        tracker.update(cx, |tracker, cx| {
+            tracker.add_usage(&UsageModel::new(model), usage, new_request);
+            cx.emit(UsageUpdated);
+            cx.notify();
+        });
+    }
+
+    /// Returns a reporter for a new request to `model`, for streams that can't update the
+    /// tracker themselves.
+    pub fn reporter(model: &(impl LanguageModel + ?Sized), cx: &AsyncApp) -> Option<UsageReporter> {
+        cx.try_read_global(
+            |global: &GlobalLanguageModelUsageTracker, _| UsageReporter {
+                model: UsageModel::new(model),
+                reports_tx: global.reports_tx.clone(),
+                reported: None,
+            },
+        )
+    }
+
+    fn add_usage(&mut self, model: &UsageModel, usage: TokenUsage, new_request: bool) {
+        let ix = match self.models.iter().position(|entry| {
+            entry.provider_id == model.provider_id && entry.model_id == model.model_id
+        }) {
+            Some(ix) => ix,
+            None => {
+                self.models.push(ModelTokenUsage {
+                    provider_id: model.provider_id.clone(),
+                    provider_name: model.provider_name.clone(),
+                    model_id: model.model_id.clone(),
+                    model_name: model.model_name.clone(),
+                    usage: TokenUsage::default(),
+                    request_count: 0,
+                });
+                self.models.len() - 1
+            }
+        };
+
+        let entry = &mut self.models[ix];
+        entry.usage = entry.usage + usage;
+        if new_request {
+            entry.request_count += 1;
+        }
+    }
+
+    pub fn models(&self) -> &[ModelTokenUsage] {
+        &self.models
+    }
+
+    pub fn total(&self) -> TokenUsage {
+        self.models
+            .iter()
+            .fold(TokenUsage::default(), |total, entry| total + entry.usage)
+    }
+
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        self.models.clear();
+        cx.emit(UsageUpdated);
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_provider::FakeLanguageModel;
+
+    #[test]
+    fn test_add_usage() {
+        let model = UsageModel::new(&FakeLanguageModel::default());
+        let mut tracker = LanguageModelUsageTracker::default();
+        let usage = TokenUsage {
+            input_tokens: 100,
+            output_tokens: 20,
+            ..Default::default()
+        };
+        tracker.add_usage(&model, usage, true);
+        tracker.add_usage(&model, usage, false);
+
+        assert_eq!(tracker.models().len(), 1);
+        assert_eq!(tracker.models()[0].request_count, 1);
+        assert_eq!(tracker.total().input_tokens, 200);
+        assert_eq!(tracker.total().output_tokens, 40);
+    }
+
+    #[gpui::test]
+    fn test_usage_reporter(cx: &mut gpui::TestAppContext) {
+        cx.update(init);
+        let mut reporter =
+            LanguageModelUsageTracker::reporter(&FakeLanguageModel::default(), &cx.to_async())
+                .unwrap();
+
+        // Running totals are only counted once.
+        reporter.report(TokenUsage {
+            input_tokens: 100,
+            output_tokens: 20,
+            ..Default::default()
+        });
+        reporter.report(TokenUsage {
+            input_tokens: 100,
+            output_tokens: 50,
+            ..Default::default()
+        });
+        cx.run_until_parked();
+
+        let tracker = cx.update(|cx| LanguageModelUsageTracker::global(cx).unwrap());
+        tracker.read_with(cx, |tracker, _| {
+            assert_eq!(tracker.models().len(), 1);
+            assert_eq!(tracker.models()[0].request_count, 1);
+            assert_eq!(tracker.total().input_tokens, 100);
+            assert_eq!(tracker.total().output_tokens, 50);
+        });
+    }
+
+    #[test]
+    fn test_saturating_sub() {
+        let previous = TokenUsage {
+            input_tokens: 100,
+            output_tokens: 20,
+            ..Default::default()
+        };
+        let current = TokenUsage {
+            input_tokens: 100,
+            output_tokens: 50,
+            ..Default::default()
+        };
+        assert_eq!(current.saturating_sub(previous).output_tokens, 30);
+        assert_eq!(previous.saturating_sub(current).output_tokens, 0);
+    }
+}
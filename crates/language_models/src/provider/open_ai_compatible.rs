@@ -2,7 +2,7 @@ use anyhow::{Result, anyhow};
 use convert_case::{Case, Casing};
 use futures::{FutureExt, StreamExt, future, future::BoxFuture};
 use gpui::{AnyView, App, AsyncApp, Context, Entity, SharedString, Task, Window};
-use http_client::HttpClient;
+use http_client::{HttpClient, Url};
 use language_model::{
     AuthenticateError, LanguageModel, LanguageModelCompletionError, LanguageModelCompletionEvent,
     LanguageModelId, LanguageModelName, LanguageModelProvider, LanguageModelProviderId,
@@ -10,9 +10,9 @@ use language_model::{
     LanguageModelToolChoice, LanguageModelToolSchemaFormat, RateLimiter,
 };
 use menu;
-use open_ai::{ResponseStreamEvent, stream_completion};
+use open_ai::{ListedModel, ResponseStreamEvent, list_models, stream_completion};
 use settings::{Settings, SettingsStore};
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 use ui::{ElevationIndex, Tooltip, prelude::*};
 use ui_input::SingleLineInput;
 use util::{ResultExt, truncate_and_trailoff};
@@ -23,10 +23,14 @@ use crate::provider::open_ai::{OpenAiEventMapper, into_open_ai};
 pub use settings::OpenAiCompatibleAvailableModel as AvailableModel;
 pub use settings::OpenAiCompatibleModelCapabilities as ModelCapabilities;
 
+/// Context window assumed for discovered models when the server doesn't report one.
+const DEFAULT_DISCOVERED_MAX_TOKENS: u64 = 8192;
+
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct OpenAiCompatibleSettings {
     pub api_url: String,
     pub available_models: Vec<AvailableModel>,
+    pub discover_models: bool,
 }
 
 pub struct OpenAiCompatibleLanguageModelProvider {
@@ -41,30 +45,126 @@ pub struct State {
     api_key_env_var: EnvVar,
     api_key_state: ApiKeyState,
     settings: OpenAiCompatibleSettings,
+    http_client: Arc<dyn HttpClient>,
+    discovered_models: Vec<AvailableModel>,
+    fetch_models_task: Option<Task<Result<()>>>,
 }
 
 impl State {
     fn is_authenticated(&self) -> bool {
-        self.api_key_state.has_key()
+        self.api_key_state.has_key() || is_local_url(&self.settings.api_url)
     }
 
     fn set_api_key(&mut self, api_key: Option<String>, cx: &mut Context<Self>) -> Task<Result<()>> {
         let api_url = SharedString::new(self.settings.api_url.as_str());
-        self.api_key_state
-            .store(api_url, api_key, |this| &mut this.api_key_state, cx)
+        let task = self
+            .api_key_state
+            .store(api_url, api_key, |this| &mut this.api_key_state, cx);
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| this.restart_fetch_models_task(cx))
+                .ok();
+            result
+        })
     }
 
     fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
         let api_url = SharedString::new(self.settings.api_url.clone());
-        self.api_key_state.load_if_needed(
+        let task = self.api_key_state.load_if_needed(
             api_url,
             &self.api_key_env_var,
             |this| &mut this.api_key_state,
             cx,
-        )
+        );
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| this.restart_fetch_models_task(cx))
+                .ok();
+            if this
+                .read_with(cx, |this, _| this.is_authenticated())
+                .unwrap_or(false)
+            {
+                Ok(())
+            } else {
+                result
+            }
+        })
+    }
+
+    fn fetch_models(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        if !self.settings.discover_models {
+            self.discovered_models.clear();
+            return Task::ready(Ok(()));
+        }
+
+        let http_client = self.http_client.clone();
+        let api_url = self.settings.api_url.clone();
+        let api_key = self.api_key_state.key(&api_url);
+        cx.spawn(async move |this, cx| {
+            let models = list_models(http_client.as_ref(), &api_url, api_key.as_deref()).await?;
+            let mut models = models.into_iter().map(discovered_model).collect::<Vec<_>>();
+            models.sort_by(|a, b| a.name.cmp(&b.name));
+            this.update(cx, |this, cx| {
+                this.discovered_models = models;
+                cx.notify();
+            })
+        })
+    }
+
+    fn restart_fetch_models_task(&mut self, cx: &mut Context<Self>) {
+        let task = self.fetch_models(cx);
+        self.fetch_models_task.replace(task);
+    }
+
+    /// Models listed in settings, followed by any discovered models that
+    /// settings don't already describe.
+    fn available_models(&self) -> impl Iterator<Item = &AvailableModel> {
+        self.settings
+            .available_models
+            .iter()
+            .chain(self.discovered_models.iter().filter(|discovered| {
+                !self
+                    .settings
+                    .available_models
+                    .iter()
+                    .any(|model| model.name == discovered.name)
+            }))
     }
 }
 
+fn discovered_model(model: ListedModel) -> AvailableModel {
+    let max_tokens = model
+        .meta
+        .and_then(|meta| meta.n_ctx_train)
+        .filter(|n_ctx| *n_ctx > 0)
+        .unwrap_or(DEFAULT_DISCOVERED_MAX_TOKENS);
+    AvailableModel {
+        name: model.id,
+        display_name: None,
+        max_tokens,
+        max_output_tokens: None,
+        max_completion_tokens: None,
+        capabilities: ModelCapabilities::default(),
+    }
+}
+
+/// Whether the API URL points at a server on this machine, which typically
+/// doesn't require an API key.
+fn is_local_url(api_url: &str) -> bool {
+    let Some(host) = Url::parse(api_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+    else {
+        return false;
+    };
+    host == "localhost"
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
 impl OpenAiCompatibleLanguageModelProvider {
     pub fn new(id: Arc<str>, http_client: Arc<dyn HttpClient>, cx: &mut App) -> Self {
         fn resolve_settings<'a>(id: &'a str, cx: &'a App) -> Option<&'a OpenAiCompatibleSettings> {
@@ -87,7 +187,13 @@ impl OpenAiCompatibleLanguageModelProvider {
                         |this| &mut this.api_key_state,
                         cx,
                     );
+                    let refetch_models = this.settings.api_url != settings.api_url
+                        || this.settings.discover_models != settings.discover_models;
                     this.settings = settings;
+                    if refetch_models {
+                        this.discovered_models.clear();
+                        this.restart_fetch_models_task(cx);
+                    }
                     cx.notify();
                 }
             })
@@ -98,6 +204,9 @@ impl OpenAiCompatibleLanguageModelProvider {
                 api_key_env_var: EnvVar::new(api_key_env_var_name),
                 api_key_state: ApiKeyState::new(SharedString::new(settings.api_url.as_str())),
                 settings,
+                http_client: http_client.clone(),
+                discovered_models: Vec::new(),
+                fetch_models_task: None,
             }
         });
 
@@ -146,9 +255,8 @@ impl LanguageModelProvider for OpenAiCompatibleLanguageModelProvider {
     fn default_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
         self.state
             .read(cx)
-            .settings
-            .available_models
-            .first()
+            .available_models()
+            .next()
             .map(|model| self.create_language_model(model.clone()))
     }
 
@@ -159,9 +267,7 @@ impl LanguageModelProvider for OpenAiCompatibleLanguageModelProvider {
     fn provided_models(&self, cx: &App) -> Vec<Arc<dyn LanguageModel>> {
         self.state
             .read(cx)
-            .settings
-            .available_models
-            .iter()
+            .available_models()
             .map(|model| self.create_language_model(model.clone()))
             .collect()
     }
@@ -221,7 +327,7 @@ impl OpenAiCompatibleLanguageModel {
 
         let provider = self.provider_name.clone();
         let future = self.request_limiter.stream(async move {
-            let Some(api_key) = api_key else {
+            let Some(api_key) = api_key.or_else(|| is_local_url(&api_url).then(String::new)) else {
                 return Err(LanguageModelCompletionError::NoApiKey { provider });
             };
             let request = stream_completion(http_client.as_ref(), &api_url, &api_key, request);
@@ -459,6 +565,8 @@ impl Render for ConfigurationView {
                         .child(Icon::new(IconName::Check).color(Color::Success))
                         .child(Label::new(if env_var_set {
                             format!("API key set in {env_var_name} environment variable")
+                        } else if !state.api_key_state.has_key() {
+                            format!("Using local server at {}", truncate_and_trailoff(&state.settings.api_url, 32))
                         } else {
                             format!("API key configured for {}", truncate_and_trailoff(&state.settings.api_url, 32))
                         })),
@@ -485,3 +593,37 @@ impl Render for ConfigurationView {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use open_ai::ListedModelMeta;
+
+    #[test]
+    fn test_is_local_url() {
+        assert!(is_local_url("http://localhost:8080/v1"));
+        assert!(is_local_url("http://127.0.0.1:8080/v1"));
+        assert!(is_local_url("http://[::1]:8080/v1"));
+        assert!(!is_local_url("https://api.example.com/v1"));
+        assert!(!is_local_url("http://192.168.1.10:8080/v1"));
+        assert!(!is_local_url("not a url"));
+    }
+
+    #[test]
+    fn test_discovered_model_context_window() {
+        let model = discovered_model(ListedModel {
+            id: "qwen2.5-coder-7b".into(),
+            meta: Some(ListedModelMeta {
+                n_ctx_train: Some(32768),
+            }),
+        });
+        assert_eq!(model.name, "qwen2.5-coder-7b");
+        assert_eq!(model.max_tokens, 32768);
+
+        let model = discovered_model(ListedModel {
+            id: "unknown".into(),
+            meta: None,
+        });
+        assert_eq!(model.max_tokens, DEFAULT_DISCOVERED_MAX_TOKENS);
+    }
+}
@@ -100,6 +100,7 @@ impl settings::Settings for AllLanguageModelSettings {
                         OpenAiCompatibleSettings {
                             api_url: value.api_url,
                             available_models: value.available_models,
+                            discover_models: value.discover_models.unwrap_or(false),
                         },
                     )
                 })
@@ -1,4 +1,5 @@
 mod active_buffer_language;
+mod persistence;
 
 pub use active_buffer_language::ActiveBufferLanguage;
 use anyhow::Context as _;
@@ -11,13 +12,14 @@ use gpui::{
     Render, Styled, WeakEntity, Window, actions,
 };
 use language::{Buffer, LanguageMatcher, LanguageName, LanguageRegistry};
+use persistence::DB;
 use picker::{Picker, PickerDelegate};
 use project::Project;
 use settings::Settings;
 use std::{ops::Not as _, path::Path, sync::Arc};
 use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
 use util::ResultExt;
-use workspace::{ModalView, Workspace};
+use workspace::{ModalView, Workspace, WorkspaceId, item::ItemHandle};
 
 actions!(
     language_selector,
@@ -39,11 +41,17 @@ impl LanguageSelector {
     fn register(
         workspace: &mut Workspace,
         _window: Option<&mut Window>,
-        _: &mut Context<Workspace>,
+        cx: &mut Context<Workspace>,
     ) {
         workspace.register_action(move |workspace, _: &Toggle, window, cx| {
             Self::toggle(workspace, window, cx);
         });
+        cx.subscribe_self(|workspace, event, cx| {
+            if let workspace::Event::ItemAdded { item } = event {
+                restore_language_override(workspace, item.as_ref(), cx);
+            }
+        })
+        .detach();
     }
 
     fn toggle(
@@ -58,9 +66,10 @@ impl LanguageSelector {
             .read(cx)
             .active_excerpt(cx)?;
         let project = workspace.project().clone();
+        let workspace_id = workspace.database_id();
 
         workspace.toggle_modal(window, cx, move |window, cx| {
-            LanguageSelector::new(buffer, project, registry, window, cx)
+            LanguageSelector::new(buffer, project, workspace_id, registry, window, cx)
         });
         Some(())
     }
@@ -68,6 +77,7 @@ impl LanguageSelector {
     fn new(
         buffer: Entity<Buffer>,
         project: Entity<Project>,
+        workspace_id: Option<WorkspaceId>,
         language_registry: Arc<LanguageRegistry>,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -76,6 +86,7 @@ impl LanguageSelector {
             cx.entity().downgrade(),
             buffer,
             project,
+            workspace_id,
             language_registry,
         );
 
@@ -102,10 +113,64 @@ impl Focusable for LanguageSelector {
 impl EventEmitter<DismissEvent> for LanguageSelector {}
 impl ModalView for LanguageSelector {}
 
+/// Sets the language chosen in the language selector for the file of a newly opened editor, if
+/// one was chosen for it in this workspace before.
+fn restore_language_override(
+    workspace: &Workspace,
+    item: &dyn ItemHandle,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(workspace_id) = workspace.database_id() else {
+        return;
+    };
+    let Some(buffer) = item
+        .act_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+    else {
+        return;
+    };
+    let Some(abs_path) = buffer
+        .read(cx)
+        .file()
+        .and_then(|file| file.as_local())
+        .map(|file| file.abs_path(cx))
+    else {
+        return;
+    };
+    let Some(language_name) = DB
+        .get_language_override(workspace_id, abs_path)
+        .log_err()
+        .flatten()
+    else {
+        return;
+    };
+    if buffer
+        .read(cx)
+        .language()
+        .is_some_and(|language| language.name().as_ref() == language_name)
+    {
+        return;
+    }
+
+    let language = workspace
+        .app_state()
+        .languages
+        .language_for_name(&language_name);
+    let project = workspace.project().downgrade();
+    cx.spawn(async move |_, cx| {
+        let language = language.await?;
+        project.update(cx, |project, cx| {
+            project.set_language_for_buffer(&buffer, language, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 pub struct LanguageSelectorDelegate {
     language_selector: WeakEntity<LanguageSelector>,
     buffer: Entity<Buffer>,
     project: Entity<Project>,
+    workspace_id: Option<WorkspaceId>,
     language_registry: Arc<LanguageRegistry>,
     candidates: Vec<StringMatchCandidate>,
     matches: Vec<StringMatch>,
@@ -117,6 +182,7 @@ impl LanguageSelectorDelegate {
         language_selector: WeakEntity<LanguageSelector>,
         buffer: Entity<Buffer>,
         project: Entity<Project>,
+        workspace_id: Option<WorkspaceId>,
         language_registry: Arc<LanguageRegistry>,
     ) -> Self {
         let candidates = language_registry
@@ -137,6 +203,7 @@ impl LanguageSelectorDelegate {
             language_selector,
             buffer,
             project,
+            workspace_id,
             language_registry,
             candidates,
             matches: vec![],
@@ -197,6 +264,8 @@ impl PickerDelegate for LanguageSelectorDelegate {
         if let Some(mat) = self.matches.get(self.selected_index) {
             let language_name = &self.candidates[mat.candidate_id].string;
             let language = self.language_registry.language_for_name(language_name);
+            let language_registry = self.language_registry.clone();
+            let workspace_id = self.workspace_id;
             let project = self.project.downgrade();
             let buffer = self.buffer.downgrade();
             cx.spawn_in(window, async move |_, cx| {
@@ -204,8 +273,31 @@ impl PickerDelegate for LanguageSelectorDelegate {
                 let project = project.upgrade().context("project was dropped")?;
                 let buffer = buffer.upgrade().context("buffer was dropped")?;
                 project.update(cx, |project, cx| {
-                    project.set_language_for_buffer(&buffer, language, cx);
-                })
+                    project.set_language_for_buffer(&buffer, language.clone(), cx);
+                })?;
+
+                let Some(workspace_id) = workspace_id else {
+                    return Ok(());
+                };
+                let file = buffer.read_with(cx, |buffer, cx| {
+                    let file = buffer.file()?;
+                    let abs_path = file.as_local()?.abs_path(cx);
+                    let detected_language = language_registry
+                        .language_for_file(file, Some(buffer.as_rope()), cx)
+                        .map(|language| language.name());
+                    Some((abs_path, detected_language))
+                })?;
+                let Some((abs_path, detected_language)) = file else {
+                    return Ok(());
+                };
+                // Only remember the choice if it differs from what would be detected anyway, so
+                // that the file follows later changes to the language settings.
+                if detected_language == Some(language.name()) {
+                    DB.delete_language_override(workspace_id, abs_path).await
+                } else {
+                    DB.save_language_override(workspace_id, abs_path, language.name().to_string())
+                        .await
+                }
             })
             .detach_and_log_err(cx);
         }
@@ -0,0 +1,96 @@
+use db::{
+    query,
+    sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
+    sqlez_macros::sql,
+};
+use std::path::PathBuf;
+use workspace::{WorkspaceDb, WorkspaceId};
+
+pub struct LanguageSelectorDb(ThreadSafeConnection);
+
+impl Domain for LanguageSelectorDb {
+    const NAME: &str = stringify!(LanguageSelectorDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE language_overrides(
+            workspace_id INTEGER NOT NULL,
+            abs_path BLOB NOT NULL,
+            language_name TEXT NOT NULL,
+            PRIMARY KEY(workspace_id, abs_path),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        ) STRICT;
+    )];
+}
+
+db::static_connection!(DB, LanguageSelectorDb, [WorkspaceDb]);
+
+impl LanguageSelectorDb {
+    query! {
+        pub fn get_language_override(
+            workspace_id: WorkspaceId,
+            abs_path: PathBuf
+        ) -> Result<Option<String>> {
+            SELECT language_name
+            FROM language_overrides
+            WHERE workspace_id = ? AND abs_path = ?
+        }
+    }
+
+    query! {
+        pub async fn save_language_override(
+            workspace_id: WorkspaceId,
+            abs_path: PathBuf,
+            language_name: String
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO language_overrides(workspace_id, abs_path, language_name)
+            VALUES (?, ?, ?)
+        }
+    }
+
+    query! {
+        pub async fn delete_language_override(
+            workspace_id: WorkspaceId,
+            abs_path: PathBuf
+        ) -> Result<()> {
+            DELETE FROM language_overrides
+            WHERE workspace_id = ? AND abs_path = ?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_save_and_delete_language_override() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let abs_path = PathBuf::from("/project/bin/tool");
+        assert_eq!(
+            DB.get_language_override(workspace_id, abs_path.clone())
+                .unwrap(),
+            None
+        );
+
+        DB.save_language_override(workspace_id, abs_path.clone(), "Python".to_owned())
+            .await
+            .unwrap();
+        DB.save_language_override(workspace_id, abs_path.clone(), "Ruby".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_language_override(workspace_id, abs_path.clone())
+                .unwrap(),
+            Some("Ruby".to_owned())
+        );
+
+        DB.delete_language_override(workspace_id, abs_path.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_language_override(workspace_id, abs_path).unwrap(),
+            None
+        );
+    }
+}
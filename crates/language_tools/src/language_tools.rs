@@ -1,6 +1,7 @@
 mod key_context_view;
 pub mod lsp_button;
 pub mod lsp_log_view;
+mod memory_usage_view;
 mod syntax_tree_view;
 
 #[cfg(test)]
@@ -17,6 +18,7 @@ pub fn init(cx: &mut App) {
     lsp_log_view::init(false, cx);
     syntax_tree_view::init(cx);
     key_context_view::init(cx);
+    memory_usage_view::init(cx);
 }
 
 fn get_or_create_tool<T>(
@@ -0,0 +1,388 @@
+use collections::HashMap;
+use extension_host::ExtensionStore;
+use gpui::{
+    App, AppContext as _, Entity, EntityId, EventEmitter, FocusHandle, Focusable, ScrollHandle,
+    Task, WeakEntity, actions,
+};
+use language::Buffer;
+use project::Project;
+use ui::{Tooltip, prelude::*};
+use util::size::format_file_size;
+use workspace::{Item, SplitDirection, Workspace};
+
+actions!(
+    dev,
+    [
+        /// Opens a view showing how much memory is used by each subsystem.
+        OpenMemoryUsage
+    ]
+);
+
+/// A rough estimate of the number of bytes used by each node of a syntax tree.
+const SYNTAX_NODE_SIZE: usize = 64;
+
+/// The number of buffers listed as using the most memory.
+const LARGEST_BUFFER_COUNT: usize = 20;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &OpenMemoryUsage, window, cx| {
+            let project = workspace.project().downgrade();
+            let memory_usage_view = cx.new(|cx| MemoryUsageView::new(project, window, cx));
+            workspace.split_item(
+                SplitDirection::Right,
+                Box::new(memory_usage_view),
+                window,
+                cx,
+            )
+        });
+    })
+    .detach();
+}
+
+/// The memory used by a buffer.
+#[derive(Clone, Debug, PartialEq)]
+struct BufferMemory {
+    name: SharedString,
+    text: usize,
+    syntax_tree: usize,
+    diagnostics: usize,
+}
+
+impl BufferMemory {
+    fn new(buffer: &Buffer, cx: &App) -> Self {
+        let snapshot = buffer.snapshot();
+        let name = snapshot.file().map_or_else(
+            || "untitled".into(),
+            |file| file.full_path(cx).to_string_lossy().to_string().into(),
+        );
+        let syntax_tree = snapshot
+            .syntax_layers()
+            .map(|layer| layer.node().descendant_count() * SYNTAX_NODE_SIZE)
+            .sum();
+        let diagnostics = snapshot
+            .diagnostic_sets()
+            .iter()
+            .flat_map(|(_, set)| set.iter())
+            .map(|entry| {
+                let diagnostic = &entry.diagnostic;
+                size_of_val(entry)
+                    + diagnostic.message.len()
+                    + diagnostic.markdown.as_ref().map_or(0, String::len)
+                    + diagnostic.source.as_ref().map_or(0, String::len)
+            })
+            .sum();
+        Self {
+            name,
+            text: snapshot.len(),
+            syntax_tree,
+            diagnostics,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.text + self.syntax_tree + self.diagnostics
+    }
+}
+
+/// The memory used by each subsystem, at the time the report was made.
+#[derive(Default)]
+struct MemoryReport {
+    /// The memory used by the whole process, which is read in the background.
+    process: Option<u64>,
+    /// The open buffers, largest first.
+    buffers: Vec<BufferMemory>,
+    language_server_count: usize,
+    atlases: usize,
+    /// The memory used by each extension, largest first.
+    extensions: Vec<(SharedString, usize)>,
+}
+
+impl MemoryReport {
+    fn new(projects: &[Entity<Project>], atlases: usize, cx: &App) -> Self {
+        let mut buffers = HashMap::<EntityId, BufferMemory>::default();
+        let mut language_server_count = 0;
+        for project in projects {
+            let project = project.read(cx);
+            for buffer in project.buffer_store().read(cx).buffers() {
+                buffers
+                    .entry(buffer.entity_id())
+                    .or_insert_with(|| BufferMemory::new(buffer.read(cx), cx));
+            }
+            language_server_count += project
+                .lsp_store()
+                .read(cx)
+                .language_server_statuses()
+                .count();
+        }
+        let mut buffers = buffers.into_values().collect::<Vec<_>>();
+        buffers.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.name.cmp(&b.name)));
+
+        let mut extensions = ExtensionStore::try_global(cx)
+            .map(|store| {
+                store
+                    .read(cx)
+                    .wasm_extensions
+                    .iter()
+                    .map(|(manifest, extension)| {
+                        (manifest.name.clone().into(), extension.memory_usage())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        extensions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Self {
+            process: None,
+            buffers,
+            language_server_count,
+            atlases,
+            extensions,
+        }
+    }
+
+    fn text(&self) -> usize {
+        self.buffers.iter().map(|buffer| buffer.text).sum()
+    }
+
+    fn syntax_trees(&self) -> usize {
+        self.buffers.iter().map(|buffer| buffer.syntax_tree).sum()
+    }
+
+    fn diagnostics(&self) -> usize {
+        self.buffers.iter().map(|buffer| buffer.diagnostics).sum()
+    }
+
+    fn extensions(&self) -> usize {
+        self.extensions.iter().map(|(_, memory)| memory).sum()
+    }
+}
+
+/// Returns the resident memory of the process, in bytes.
+fn process_memory() -> Option<u64> {
+    let pid = sysinfo::get_current_pid().ok()?;
+    let mut system = sysinfo::System::new();
+    system.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::Some(&[pid]),
+        sysinfo::ProcessRefreshKind::new().with_memory(),
+    );
+    Some(system.process(pid)?.memory())
+}
+
+struct MemoryUsageView {
+    project: WeakEntity<Project>,
+    report: MemoryReport,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    _process_memory_task: Task<()>,
+}
+
+impl MemoryUsageView {
+    fn new(project: WeakEntity<Project>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            project,
+            report: MemoryReport::default(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            _process_memory_task: Task::ready(()),
+        };
+        this.refresh(window, cx);
+        this
+    }
+
+    fn refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Other windows can only be accessed by updating them, which isn't possible for the
+        // window that is being updated, so its project is kept by the view.
+        let mut projects = Vec::new();
+        let mut atlases = window.sprite_atlas_memory_usage();
+        projects.extend(self.project.upgrade());
+        let current_window = window.window_handle().window_id();
+        for handle in cx.windows() {
+            if handle.window_id() == current_window {
+                continue;
+            }
+            handle
+                .update(cx, |root, window, cx| {
+                    atlases += window.sprite_atlas_memory_usage();
+                    if let Ok(workspace) = root.downcast::<Workspace>() {
+                        projects.push(workspace.read(cx).project().clone());
+                    }
+                })
+                .ok();
+        }
+
+        self.report = MemoryReport::new(&projects, atlases, cx);
+        self._process_memory_task = cx.spawn(async move |this, cx| {
+            let process = cx.background_spawn(async { process_memory() }).await;
+            this.update(cx, |this, cx| {
+                this.report.process = process;
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+}
+
+fn format_memory(bytes: usize) -> String {
+    format_file_size(bytes as u64, false)
+}
+
+impl EventEmitter<()> for MemoryUsageView {}
+
+impl Focusable for MemoryUsageView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for MemoryUsageView {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(workspace::item::ItemEvent)) {}
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Memory Usage".into()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>>
+    where
+        Self: Sized,
+    {
+        let project = self.project.clone();
+        Some(cx.new(|cx| MemoryUsageView::new(project, window, cx)))
+    }
+}
+
+impl Render for MemoryUsageView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let report = &self.report;
+        let row = |label: SharedString, detail: Option<String>, bytes: Option<usize>| {
+            h_flex()
+                .gap_4()
+                .justify_between()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .min_w_0()
+                        .child(Label::new(label).truncate())
+                        .when_some(detail, |this, detail| {
+                            this.child(Label::new(detail).color(Color::Muted))
+                        }),
+                )
+                .child(Label::new(bytes.map_or("–".into(), format_memory)))
+        };
+        let heading = |text: &'static str| {
+            div()
+                .pt_4()
+                .pb_1()
+                .child(Label::new(text).size(LabelSize::Small).color(Color::Muted))
+        };
+
+        let diagnostic_count = report.buffers.iter().filter(|b| b.diagnostics > 0).count();
+        v_flex()
+            .id("memory-usage-view")
+            .track_focus(&self.focus_handle)
+            .track_scroll(&self.scroll_handle)
+            .overflow_y_scroll()
+            .size_full()
+            .p_4()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(Headline::new("Memory Usage").size(HeadlineSize::Small))
+                    .child(
+                        IconButton::new("refresh-memory-usage", IconName::RotateCw)
+                            .tooltip(Tooltip::text("Refresh"))
+                            .on_click(cx.listener(|this, _, window, cx| this.refresh(window, cx))),
+                    ),
+            )
+            .child(heading("Subsystems"))
+            .child(row(
+                "Process".into(),
+                Some("resident".into()),
+                report.process.map(|bytes| bytes as usize),
+            ))
+            .child(row(
+                "Buffers".into(),
+                Some(format!("{} open", report.buffers.len())),
+                Some(report.text()),
+            ))
+            .child(row(
+                "Syntax trees".into(),
+                Some("estimated".into()),
+                Some(report.syntax_trees()),
+            ))
+            .child(row(
+                "Language servers".into(),
+                Some(format!(
+                    "{} running, diagnostics in {diagnostic_count} buffers",
+                    report.language_server_count
+                )),
+                Some(report.diagnostics()),
+            ))
+            .child(row(
+                "Glyph and image atlases".into(),
+                None,
+                Some(report.atlases),
+            ))
+            .child(row(
+                "Extensions".into(),
+                Some(format!("{} loaded", report.extensions.len())),
+                Some(report.extensions()),
+            ))
+            .when(!report.extensions.is_empty(), |this| {
+                this.child(heading("Extensions")).children(
+                    report
+                        .extensions
+                        .iter()
+                        .map(|(name, bytes)| row(name.clone(), None, Some(*bytes))),
+                )
+            })
+            .child(heading("Largest buffers"))
+            .children(
+                report
+                    .buffers
+                    .iter()
+                    .take(LARGEST_BUFFER_COUNT)
+                    .map(|buffer| {
+                        row(
+                            buffer.name.clone(),
+                            Some(format!(
+                                "text {}, syntax {}, diagnostics {}",
+                                format_memory(buffer.text),
+                                format_memory(buffer.syntax_tree),
+                                format_memory(buffer.diagnostics)
+                            )),
+                            Some(buffer.total()),
+                        )
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn test_buffer_memory(cx: &mut TestAppContext) {
+        let buffer = cx.new(|cx| Buffer::local("fn main() {}\n", cx));
+        let memory = cx.read(|cx| BufferMemory::new(buffer.read(cx), cx));
+        assert_eq!(memory.name, "untitled");
+        assert_eq!(memory.text, 13);
+        assert_eq!(memory.syntax_tree, 0);
+        assert_eq!(memory.diagnostics, 0);
+        assert_eq!(memory.total(), 13);
+    }
+}
@@ -946,7 +946,30 @@ fn python_env_kind_display(k: &PythonEnvironmentKind) -> &'static str {
     }
 }
 
-pub(crate) struct PythonToolchainProvider;
+/// Whether the virtual environment at `prefix` was created by uv, which records
+/// its version in `pyvenv.cfg`.
+async fn is_uv_venv(prefix: &Path, fs: &dyn Fs) -> bool {
+    fs.load(&prefix.join("pyvenv.cfg"))
+        .await
+        .is_ok_and(|pyvenv_cfg| pyvenv_cfg_has_uv_key(&pyvenv_cfg))
+}
+
+fn pyvenv_cfg_has_uv_key(pyvenv_cfg: &str) -> bool {
+    pyvenv_cfg.lines().any(|line| {
+        line.split_once('=')
+            .is_some_and(|(key, _)| key.trim() == "uv")
+    })
+}
+
+pub(crate) struct PythonToolchainProvider {
+    fs: Arc<dyn Fs>,
+}
+
+impl PythonToolchainProvider {
+    pub fn new(fs: Arc<dyn Fs>) -> Self {
+        Self { fs }
+    }
+}
 
 static ENV_PRIORITY_LIST: &[PythonEnvironmentKind] = &[
     // Prioritize non-Conda environments.
@@ -1118,10 +1141,13 @@ impl ToolchainLister for PythonToolchainProvider {
                 .then_with(exe_ordering)
         });
 
-        let mut toolchains: Vec<_> = toolchains
-            .into_iter()
-            .filter_map(venv_to_toolchain)
-            .collect();
+        let mut toolchains = {
+            let mut converted = Vec::with_capacity(toolchains.len());
+            for venv in toolchains {
+                converted.extend(venv_to_toolchain(venv, self.fs.as_ref()).await);
+            }
+            converted
+        };
         toolchains.dedup();
         ToolchainList {
             toolchains,
@@ -1154,7 +1180,9 @@ impl ToolchainLister for PythonToolchainProvider {
         let toolchain = pet::resolve::resolve_environment(&path, &locators, &environment)
             .context("Could not find a virtual environment in provided path")?;
         let venv = toolchain.resolved.unwrap_or(toolchain.discovered);
-        venv_to_toolchain(venv).context("Could not convert a venv into a toolchain")
+        venv_to_toolchain(venv, self.fs.as_ref())
+            .await
+            .context("Could not convert a venv into a toolchain")
     }
 
     async fn activation_script(
@@ -1178,7 +1206,16 @@ impl ToolchainLister for PythonToolchainProvider {
                     activation_script.push("conda activate".to_string());
                 }
             }
-            Some(PythonEnvironmentKind::Venv | PythonEnvironmentKind::VirtualEnv) => {
+            // Environments managed by these tools are regular virtual environments
+            // with the standard activation scripts.
+            Some(
+                PythonEnvironmentKind::Venv
+                | PythonEnvironmentKind::VirtualEnv
+                | PythonEnvironmentKind::Poetry
+                | PythonEnvironmentKind::Pipenv
+                | PythonEnvironmentKind::VirtualEnvWrapper
+                | PythonEnvironmentKind::PyenvVirtualEnv,
+            ) => {
                 if let Some(prefix) = &toolchain.prefix {
                     let activate_keyword = match shell {
                         ShellKind::Cmd => ".",
@@ -1232,16 +1269,29 @@ impl ToolchainLister for PythonToolchainProvider {
     }
 }
 
-fn venv_to_toolchain(venv: PythonEnvironment) -> Option<Toolchain> {
+async fn venv_to_toolchain(venv: PythonEnvironment, fs: &dyn Fs) -> Option<Toolchain> {
     let mut name = String::from("Python");
     if let Some(ref version) = venv.version {
         _ = write!(name, " {version}");
     }
 
-    let name_and_kind = match (&venv.name, &venv.kind) {
-        (Some(name), Some(kind)) => Some(format!("({name}; {})", python_env_kind_display(kind))),
+    let created_by_uv = match (&venv.kind, &venv.prefix) {
+        (Some(PythonEnvironmentKind::Venv | PythonEnvironmentKind::VirtualEnv), Some(prefix)) => {
+            is_uv_venv(prefix, fs).await
+        }
+        _ => false,
+    };
+    let kind = venv.kind.as_ref().map(|kind| {
+        if created_by_uv {
+            "uv"
+        } else {
+            python_env_kind_display(kind)
+        }
+    });
+    let name_and_kind = match (&venv.name, kind) {
+        (Some(name), Some(kind)) => Some(format!("({name}; {kind})")),
         (Some(name), None) => Some(format!("({name})")),
-        (None, Some(kind)) => Some(format!("({})", python_env_kind_display(kind))),
+        (None, Some(kind)) => Some(format!("({kind})")),
         (None, None) => None,
     };
 
@@ -2182,6 +2232,41 @@ mod tests {
     use settings::SettingsStore;
     use std::num::NonZeroU32;
 
+    #[test]
+    fn test_pyvenv_cfg_has_uv_key() {
+        assert!(super::pyvenv_cfg_has_uv_key(
+            "home = /usr/bin\nimplementation = CPython\nuv = 0.8.3\nversion_info = 3.12.4\n"
+        ));
+        assert!(!super::pyvenv_cfg_has_uv_key(
+            "home = /usr/bin\ninclude-system-site-packages = false\nversion = 3.12.4\n"
+        ));
+    }
+
+    #[gpui::test]
+    async fn test_is_uv_venv(cx: &mut TestAppContext) {
+        let fs = project::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            util::path!("/project"),
+            serde_json::json!({
+                ".venv": { "pyvenv.cfg": "home = /usr/bin\nuv = 0.8.3\n" },
+                "env": { "pyvenv.cfg": "home = /usr/bin\n" },
+            }),
+        )
+        .await;
+
+        for (path, expected) in [
+            (util::path!("/project/.venv"), true),
+            (util::path!("/project/env"), false),
+            (util::path!("/project/missing"), false),
+        ] {
+            assert_eq!(
+                super::is_uv_venv(std::path::Path::new(path), fs.as_ref()).await,
+                expected,
+                "{path}"
+            );
+        }
+    }
+
     #[gpui::test]
     async fn test_python_autoindent(cx: &mut TestAppContext) {
         cx.executor().set_block_on_ticks(usize::MAX..=usize::MAX);
@@ -8,7 +8,7 @@ use http_client::github::{GitHubLspBinaryVersion, latest_github_release};
 use http_client::github_download::{GithubBinaryMetadata, download_server_binary};
 pub use language::*;
 use lsp::{InitializeParams, LanguageServerBinary};
-use project::lsp_store::rust_analyzer_ext::CARGO_DIAGNOSTICS_SOURCE_NAME;
+use project::lsp_store::rust_analyzer_ext::{CARGO_DIAGNOSTICS_SOURCE_NAME, CargoOptions};
 use project::project_settings::ProjectSettings;
 use regex::Regex;
 use serde_json::json;
@@ -801,6 +801,11 @@ impl ContextProvider for RustContextProvider {
                 .collect();
         }
 
+        if let Some(file) = &file {
+            let cargo_options = CargoOptions::for_worktree(file.worktree_id(cx), cx);
+            apply_cargo_options(&mut task_templates, &cargo_options);
+        }
+
         Task::ready(Some(TaskTemplates(task_templates)))
     }
 
@@ -809,6 +814,24 @@ impl ContextProvider for RustContextProvider {
     }
 }
 
+/// Passes the worktree's cargo options to the tasks, right after the cargo subcommand.
+fn apply_cargo_options(task_templates: &mut [TaskTemplate], cargo_options: &CargoOptions) {
+    for task_template in task_templates {
+        if task_template.args.is_empty() {
+            continue;
+        }
+        let mut args = task_template.args.split_off(1);
+        if task_template.args[0] != "clean" {
+            task_template.args.extend(cargo_options.feature_args());
+        }
+        task_template.args.extend(cargo_options.target_args());
+        task_template.args.append(&mut args);
+        if let Some((name, value)) = cargo_options.toolchain_env() {
+            task_template.env.insert(name, value);
+        }
+    }
+}
+
 /// Part of the data structure of Cargo metadata
 #[derive(Debug, serde::Deserialize)]
 struct CargoMetadata {
@@ -1573,4 +1596,55 @@ mod tests {
         );
         check([], "/project/src/main.rs", "--");
     }
+
+    #[test]
+    fn test_apply_cargo_options() {
+        let mut task_templates = vec![
+            TaskTemplate {
+                label: "Test".into(),
+                command: "cargo".into(),
+                args: vec!["test".into(), "--".into(), "--nocapture".into()],
+                ..TaskTemplate::default()
+            },
+            TaskTemplate {
+                label: "Clean".into(),
+                command: "cargo".into(),
+                args: vec!["clean".into()],
+                ..TaskTemplate::default()
+            },
+        ];
+        apply_cargo_options(
+            &mut task_templates,
+            &CargoOptions {
+                features: vec!["serde".into(), "tokio".into()],
+                target: Some("aarch64-apple-darwin".into()),
+                toolchain: Some("nightly".into()),
+                ..CargoOptions::default()
+            },
+        );
+
+        assert_eq!(
+            task_templates[0].args,
+            [
+                "test",
+                "--features",
+                "serde,tokio",
+                "--target",
+                "aarch64-apple-darwin",
+                "--",
+                "--nocapture"
+            ]
+        );
+        assert_eq!(
+            task_templates[1].args,
+            ["clean", "--target", "aarch64-apple-darwin"]
+        );
+        assert_eq!(
+            task_templates[1]
+                .env
+                .get("RUSTUP_TOOLCHAIN")
+                .map(String::as_str),
+            Some("nightly")
+        );
+    }
 }
@@ -0,0 +1,126 @@
+mod local_history_picker;
+mod local_history_view;
+mod persistence;
+
+use std::path::PathBuf;
+
+use chrono::{TimeDelta, Utc};
+use editor::Editor;
+use gpui::{App, Context, Entity, actions};
+use language::{Buffer, BufferEvent};
+use project::buffer_store::BufferStoreEvent;
+use settings::Settings;
+use util::ResultExt as _;
+use workspace::Workspace;
+
+pub use crate::persistence::LocalHistoryVersion;
+use crate::{local_history_picker::LocalHistoryPicker, persistence::DB};
+
+actions!(
+    local_history,
+    [
+        /// Opens a timeline of the versions of the active file recorded each time it was saved.
+        ShowLocalHistory
+    ]
+);
+
+/// Settings for the local history of files.
+#[derive(Clone, Debug)]
+pub struct LocalHistorySettings {
+    /// Whether to record a version of a file each time it's saved.
+    ///
+    /// Default: true
+    pub enabled: bool,
+    /// The maximum number of versions kept for each file.
+    ///
+    /// Default: 50
+    pub max_versions_per_file: usize,
+    /// The number of days after which versions are removed.
+    ///
+    /// Default: 30
+    pub max_age_days: u32,
+    /// The maximum size, in bytes, of a file whose versions are recorded.
+    ///
+    /// Default: 1048576
+    pub max_file_size: usize,
+}
+
+impl Settings for LocalHistorySettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let local_history = content.local_history.clone().unwrap();
+        Self {
+            enabled: local_history.enabled.unwrap(),
+            max_versions_per_file: local_history.max_versions_per_file.unwrap(),
+            max_age_days: local_history.max_age_days.unwrap(),
+            max_file_size: local_history.max_file_size.unwrap(),
+        }
+    }
+}
+
+pub fn init(cx: &mut App) {
+    LocalHistorySettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, cx| {
+        workspace.register_action(|workspace, _: &ShowLocalHistory, window, cx| {
+            let Some(abs_path) = workspace
+                .active_item_as::<Editor>(cx)
+                .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+                .and_then(|buffer| local_abs_path(buffer.read(cx), cx))
+            else {
+                return;
+            };
+            LocalHistoryPicker::toggle(abs_path, workspace, window, cx);
+        });
+
+        let project = workspace.project().read(cx);
+        if !project.is_local() {
+            return;
+        }
+        let buffer_store = project.buffer_store().clone();
+        for buffer in buffer_store.read(cx).buffers() {
+            watch_buffer(&buffer, cx);
+        }
+        cx.subscribe(&buffer_store, |_, _, event, cx| {
+            if let BufferStoreEvent::BufferAdded(buffer) = event {
+                watch_buffer(buffer, cx);
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+fn watch_buffer(buffer: &Entity<Buffer>, cx: &mut Context<Workspace>) {
+    cx.subscribe(buffer, |_, buffer, event, cx| {
+        if matches!(event, BufferEvent::Saved) {
+            record_version(buffer.read(cx), cx);
+        }
+    })
+    .detach();
+}
+
+/// Records the saved contents of a buffer in its file's local history.
+fn record_version(buffer: &Buffer, cx: &App) {
+    let settings = LocalHistorySettings::get_global(cx);
+    if !settings.enabled || buffer.len() > settings.max_file_size {
+        return;
+    }
+    let Some(abs_path) = local_abs_path(buffer, cx) else {
+        return;
+    };
+    let contents = buffer.text();
+    let max_versions = settings.max_versions_per_file;
+    let saved_at = Utc::now();
+    let oldest = saved_at - TimeDelta::days(settings.max_age_days.into());
+    cx.background_spawn(async move {
+        DB.save_version(abs_path, saved_at, contents, max_versions, oldest)
+            .await
+            .log_err();
+    })
+    .detach();
+}
+
+/// Returns the path of a buffer's file, if it's on the local disk.
+fn local_abs_path(buffer: &Buffer, cx: &App) -> Option<PathBuf> {
+    Some(buffer.file()?.as_local()?.abs_path(cx))
+}
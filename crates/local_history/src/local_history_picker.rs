@@ -0,0 +1,264 @@
+use std::{path::PathBuf, sync::Arc};
+
+use chrono::{DateTime, Local};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr as _};
+
+use crate::{LocalHistoryVersion, local_history_view::LocalHistoryView, persistence::DB};
+
+/// Lists the versions of a file recorded in its local history, most recent first.
+pub struct LocalHistoryPicker {
+    picker: Entity<Picker<LocalHistoryPickerDelegate>>,
+}
+
+impl LocalHistoryPicker {
+    pub fn toggle(
+        abs_path: PathBuf,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        cx.spawn_in(window, async move |workspace, cx| {
+            let versions = {
+                let abs_path = abs_path.clone();
+                cx.background_spawn(async move { DB.get_versions(abs_path) })
+                    .await?
+            };
+            workspace.update_in(cx, |workspace, window, cx| {
+                let workspace_handle = cx.entity().downgrade();
+                workspace.toggle_modal(window, cx, move |window, cx| {
+                    let delegate = LocalHistoryPickerDelegate::new(
+                        cx.entity().downgrade(),
+                        workspace_handle,
+                        abs_path,
+                        versions,
+                    );
+                    let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+                    Self { picker }
+                });
+            })
+        })
+        .detach_and_prompt_err("Failed to load local history", window, cx, |_, _, _| None);
+    }
+}
+
+impl Render for LocalHistoryPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("LocalHistoryPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for LocalHistoryPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for LocalHistoryPicker {}
+impl ModalView for LocalHistoryPicker {}
+
+pub struct LocalHistoryPickerDelegate {
+    local_history_picker: WeakEntity<LocalHistoryPicker>,
+    workspace: WeakEntity<Workspace>,
+    abs_path: PathBuf,
+    versions: Vec<LocalHistoryVersion>,
+    /// The time each version was saved, as shown in the picker.
+    labels: Vec<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl LocalHistoryPickerDelegate {
+    fn new(
+        local_history_picker: WeakEntity<LocalHistoryPicker>,
+        workspace: WeakEntity<Workspace>,
+        abs_path: PathBuf,
+        versions: Vec<LocalHistoryVersion>,
+    ) -> Self {
+        let labels = versions
+            .iter()
+            .map(|version| format_saved_at(version.saved_at.into()))
+            .collect();
+        Self {
+            local_history_picker,
+            workspace,
+            abs_path,
+            versions,
+            labels,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+pub(crate) fn format_saved_at(saved_at: DateTime<Local>) -> String {
+    saved_at.format("%b %-d, %H:%M:%S").to_string()
+}
+
+fn format_len(len: usize) -> String {
+    if len < 1024 {
+        format!("{len} B")
+    } else if len < 1024 * 1024 {
+        format!("{:.1} KB", len as f64 / 1024.)
+    } else {
+        format!("{:.1} MB", len as f64 / (1024. * 1024.))
+    }
+}
+
+impl PickerDelegate for LocalHistoryPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search local history…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No versions of this file have been saved".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let version = self.versions[mat.candidate_id].clone();
+            let abs_path = self.abs_path.clone();
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    LocalHistoryView::open(abs_path, version, workspace, window, cx)
+                        .detach_and_prompt_err(
+                            "Failed to open local history",
+                            window,
+                            cx,
+                            |_, _, _| None,
+                        );
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.local_history_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(id, label)| StringMatchCandidate::new(id, label))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let version = &self.versions[mat.candidate_id];
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .child(HighlightedLabel::new(
+                            self.labels[mat.candidate_id].clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(format_len(version.len))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_len() {
+        assert_eq!(format_len(12), "12 B");
+        assert_eq!(format_len(2048), "2.0 KB");
+        assert_eq!(format_len(3 * 1024 * 1024 + 512 * 1024), "3.5 MB");
+    }
+}
@@ -0,0 +1,254 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use buffer_diff::{BufferDiff, BufferDiffSnapshot};
+use editor::{Editor, MultiBuffer};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity,
+};
+use language::Buffer;
+use project::Project;
+use ui::prelude::*;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr as _,
+};
+
+use crate::{LocalHistoryVersion, local_history_picker::format_saved_at, persistence::DB};
+
+/// Shows a version of a file from its local history as a diff against the file's current
+/// contents, so that it can be restored.
+pub struct LocalHistoryView {
+    abs_path: PathBuf,
+    version: LocalHistoryVersion,
+    contents: String,
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    editor: Entity<Editor>,
+}
+
+impl LocalHistoryView {
+    pub fn open(
+        abs_path: PathBuf,
+        version: LocalHistoryVersion,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        let project = workspace.project().clone();
+        let workspace = workspace.weak_handle();
+        window.spawn(cx, async move |cx| {
+            let id = version.id;
+            let contents = cx
+                .background_spawn(async move { DB.get_version_contents(id) })
+                .await?
+                .context("version was removed from the local history")?;
+            let current_buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(&abs_path, cx))?
+                .await?;
+            let (current_text, language) = current_buffer.read_with(cx, |buffer, _| {
+                (buffer.snapshot(), buffer.language().cloned())
+            })?;
+
+            let version_buffer = cx.new(|cx| {
+                let mut buffer = Buffer::local(contents.clone(), cx);
+                buffer.set_language(language, cx);
+                buffer
+            })?;
+            let version_snapshot = version_buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+            let diff_snapshot = cx
+                .update(|_, cx| {
+                    BufferDiffSnapshot::new_with_base_buffer(
+                        version_snapshot.text.clone(),
+                        Some(current_text.text().into()),
+                        current_text,
+                        cx,
+                    )
+                })?
+                .await;
+            let diff = cx.new(|cx| {
+                let mut diff = BufferDiff::new(&version_snapshot.text, cx);
+                diff.set_snapshot(diff_snapshot, &version_snapshot.text, cx);
+                diff
+            })?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let view = cx.new(|cx| {
+                    Self::new(
+                        abs_path,
+                        version,
+                        contents,
+                        workspace,
+                        version_buffer,
+                        diff,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            })
+        })
+    }
+
+    fn new(
+        abs_path: PathBuf,
+        version: LocalHistoryVersion,
+        contents: String,
+        workspace: &Workspace,
+        version_buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let project = workspace.project().clone();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(version_buffer, cx);
+            multibuffer.add_diff(diff, cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        });
+        Self {
+            abs_path,
+            version,
+            contents,
+            workspace: workspace.weak_handle(),
+            project,
+            editor,
+        }
+    }
+
+    fn file_name(&self) -> SharedString {
+        self.abs_path.file_name().map_or_else(
+            || "untitled".into(),
+            |name| name.to_string_lossy().to_string().into(),
+        )
+    }
+
+    /// Replaces the file's contents with this version, leaving it unsaved so that the change can
+    /// be reviewed or undone.
+    fn restore(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let abs_path = self.abs_path.clone();
+        let contents = self.contents.clone();
+        let project = self.project.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(&abs_path, cx))?
+                .await?;
+            buffer.update(cx, |buffer, cx| {
+                if buffer.text() != contents {
+                    buffer.set_text(contents.as_str(), cx);
+                }
+            })?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let pane = workspace.active_pane().clone();
+                workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+            })?;
+            this.update(cx, |_, cx| cx.emit(ItemEvent::CloseItem))
+        })
+        .detach_and_prompt_err("Failed to restore version", window, cx, |_, _, _| None);
+    }
+}
+
+impl EventEmitter<ItemEvent> for LocalHistoryView {}
+
+impl Focusable for LocalHistoryView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for LocalHistoryView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        format!(
+            "{} ({})",
+            self.file_name(),
+            format_saved_at(self.version.saved_at.into())
+        )
+        .into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(self.abs_path.to_string_lossy().to_string().into())
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+}
+
+impl Render for LocalHistoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        Label::new(format!(
+                            "{} as saved on {}, compared with its current contents",
+                            self.file_name(),
+                            format_saved_at(self.version.saved_at.into())
+                        ))
+                        .color(Color::Muted),
+                    )
+                    .child(
+                        Button::new("restore-local-history-version", "Restore")
+                            .style(ButtonStyle::Filled)
+                            .icon(IconName::Undo)
+                            .icon_position(IconPosition::Start)
+                            .icon_size(IconSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| this.restore(window, cx))),
+                    ),
+            )
+            .child(self.editor.clone())
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use db::{
+    query,
+    sqlez::{
+        bindable::Column, domain::Domain, statement::Statement,
+        thread_safe_connection::ThreadSafeConnection,
+    },
+    sqlez_macros::sql,
+};
+use std::path::PathBuf;
+
+/// A version of a file recorded in its local history when it was saved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalHistoryVersion {
+    pub id: i64,
+    pub saved_at: DateTime<Utc>,
+    /// The length of the file's contents, in bytes.
+    pub len: usize,
+}
+
+impl Column for LocalHistoryVersion {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (id, next_index): (i64, i32) = Column::column(statement, start_index)?;
+        let (saved_at, next_index): (i64, i32) = Column::column(statement, next_index)?;
+        let (len, next_index): (usize, i32) = Column::column(statement, next_index)?;
+        let version = Self {
+            id,
+            saved_at: DateTime::from_timestamp(saved_at, 0).unwrap_or_default(),
+            len,
+        };
+        Ok((version, next_index))
+    }
+}
+
+pub struct LocalHistoryDb(ThreadSafeConnection);
+
+impl Domain for LocalHistoryDb {
+    const NAME: &str = stringify!(LocalHistoryDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE local_history_versions(
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            abs_path BLOB NOT NULL,
+            saved_at INTEGER NOT NULL,
+            contents TEXT NOT NULL
+        ) STRICT;
+        CREATE INDEX local_history_versions_by_path ON local_history_versions(abs_path);
+    )];
+}
+
+db::static_connection!(DB, LocalHistoryDb, []);
+
+impl LocalHistoryDb {
+    query! {
+        pub fn get_versions(abs_path: PathBuf) -> Result<Vec<LocalHistoryVersion>> {
+            SELECT id, saved_at, length(CAST(contents AS BLOB))
+            FROM local_history_versions
+            WHERE abs_path = ?
+            ORDER BY id DESC
+        }
+    }
+
+    query! {
+        pub fn get_version_contents(id: i64) -> Result<Option<String>> {
+            SELECT contents
+            FROM local_history_versions
+            WHERE id = ?
+        }
+    }
+
+    /// Records a version of a file, unless it's the same as the latest one. Afterwards, only the
+    /// `max_versions` latest versions of the file are kept, and versions of any file saved before
+    /// `oldest` are removed.
+    pub async fn save_version(
+        &self,
+        abs_path: PathBuf,
+        saved_at: DateTime<Utc>,
+        contents: String,
+        max_versions: usize,
+        oldest: DateTime<Utc>,
+    ) -> Result<()> {
+        log::debug!("Saving local history version of {abs_path:?}");
+        self.write(move |conn| {
+            conn.with_savepoint("save_local_history_version", || {
+                let latest = conn.select_row_bound::<PathBuf, String>(sql!(
+                    SELECT contents
+                    FROM local_history_versions
+                    WHERE abs_path = ?
+                    ORDER BY id DESC
+                    LIMIT 1;
+                ))?(abs_path.clone())?;
+                if latest.as_ref() != Some(&contents) {
+                    conn.exec_bound(sql!(
+                        INSERT INTO local_history_versions (abs_path, saved_at, contents)
+                        VALUES (?1, ?2, ?3);
+                    ))?((abs_path.clone(), saved_at.timestamp(), contents))?;
+                }
+                conn.exec_bound(sql!(
+                    DELETE FROM local_history_versions
+                    WHERE abs_path = ?1 AND id NOT IN (
+                        SELECT id
+                        FROM local_history_versions
+                        WHERE abs_path = ?1
+                        ORDER BY id DESC
+                        LIMIT ?2
+                    );
+                ))?((abs_path, max_versions))?;
+                conn.exec_bound(sql!(
+                    DELETE FROM local_history_versions WHERE saved_at < ?;
+                ))?(oldest.timestamp())?;
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[gpui::test]
+    async fn test_save_and_get_versions() {
+        let abs_path = PathBuf::from("/project/main.rs");
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let oldest = start - TimeDelta::days(1);
+        assert_eq!(DB.get_versions(abs_path.clone()).unwrap(), []);
+
+        for (minutes, contents) in [(0, "one"), (1, "two"), (2, "two"), (3, "three")] {
+            DB.save_version(
+                abs_path.clone(),
+                start + TimeDelta::minutes(minutes),
+                contents.to_owned(),
+                2,
+                oldest,
+            )
+            .await
+            .unwrap();
+        }
+
+        // Saving the same contents twice records one version, and only the latest versions are
+        // kept.
+        let versions = DB.get_versions(abs_path.clone()).unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| version.saved_at - start)
+                .collect::<Vec<_>>(),
+            [TimeDelta::minutes(3), TimeDelta::minutes(1)]
+        );
+        assert_eq!(
+            DB.get_version_contents(versions[0].id).unwrap().as_deref(),
+            Some("three")
+        );
+        assert_eq!(versions[1].len, 3);
+
+        // Versions older than the maximum age are removed.
+        DB.save_version(
+            PathBuf::from("/project/other.rs"),
+            start + TimeDelta::days(3),
+            String::new(),
+            2,
+            start + TimeDelta::days(2),
+        )
+        .await
+        .unwrap();
+        assert_eq!(DB.get_versions(abs_path).unwrap(), []);
+    }
+}
@@ -1,3 +1,4 @@
+use fs::normalize_path;
 use gpui::{
     DefiniteLength, FontStyle, FontWeight, HighlightStyle, Hsla, SharedString, StrikethroughStyle,
     UnderlineStyle, px,
@@ -30,6 +31,7 @@ impl ParsedMarkdownElement {
             Self::Paragraph(text) => match text.get(0)? {
                 MarkdownParagraphChunk::Text(t) => t.source_range.clone(),
                 MarkdownParagraphChunk::Image(image) => image.source_range.clone(),
+                MarkdownParagraphChunk::DisplayMath(math) => math.source_range.clone(),
             },
             Self::HorizontalRule(range) => range.clone(),
             Self::Image(image) => image.source_range.clone(),
@@ -48,6 +50,8 @@ pub type MarkdownParagraph = Vec<MarkdownParagraphChunk>;
 pub enum MarkdownParagraphChunk {
     Text(ParsedMarkdownText),
     Image(Image),
+    /// Math written between `$$` delimiters, which is shown on its own line.
+    DisplayMath(ParsedMarkdownMath),
 }
 
 #[derive(Debug)]
@@ -56,6 +60,54 @@ pub struct ParsedMarkdown {
     pub children: Vec<ParsedMarkdownElement>,
 }
 
+impl ParsedMarkdown {
+    /// Returns all the images in the document, including those nested in other elements.
+    pub fn images(&self) -> Vec<&Image> {
+        fn collect_from_paragraph<'a>(
+            paragraph: &'a MarkdownParagraph,
+            images: &mut Vec<&'a Image>,
+        ) {
+            images.extend(paragraph.iter().filter_map(|chunk| match chunk {
+                MarkdownParagraphChunk::Image(image) => Some(image),
+                _ => None,
+            }));
+        }
+
+        fn collect<'a>(elements: &'a [ParsedMarkdownElement], images: &mut Vec<&'a Image>) {
+            for element in elements {
+                match element {
+                    ParsedMarkdownElement::Heading(heading) => {
+                        collect_from_paragraph(&heading.contents, images)
+                    }
+                    ParsedMarkdownElement::ListItem(list_item) => {
+                        collect(&list_item.content, images)
+                    }
+                    ParsedMarkdownElement::Table(table) => {
+                        for row in std::iter::once(&table.header).chain(&table.body) {
+                            for cell in &row.children {
+                                collect_from_paragraph(cell, images);
+                            }
+                        }
+                    }
+                    ParsedMarkdownElement::BlockQuote(block_quote) => {
+                        collect(&block_quote.children, images)
+                    }
+                    ParsedMarkdownElement::Paragraph(paragraph) => {
+                        collect_from_paragraph(paragraph, images)
+                    }
+                    ParsedMarkdownElement::Image(image) => images.push(image),
+                    ParsedMarkdownElement::CodeBlock(_)
+                    | ParsedMarkdownElement::HorizontalRule(_) => {}
+                }
+            }
+        }
+
+        let mut images = Vec::new();
+        collect(&self.children, &mut images);
+        images
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct ParsedMarkdownListItem {
@@ -83,6 +135,14 @@ pub struct ParsedMarkdownCodeBlock {
     pub highlights: Option<Vec<(Range<usize>, HighlightId)>>,
 }
 
+#[derive(Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct ParsedMarkdownMath {
+    pub source_range: Range<usize>,
+    /// The math rendered as Unicode text.
+    pub contents: SharedString,
+}
+
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct ParsedMarkdownHeading {
@@ -264,15 +324,48 @@ pub enum Link {
         /// The absolute path to the item.
         path: PathBuf,
     },
+    /// A link to a path in a remote project.
+    RemotePath {
+        /// The path as provided in the Markdown document.
+        display_path: PathBuf,
+        /// The path to the item, starting with the name of its worktree.
+        path: PathBuf,
+    },
+}
+
+/// The directory containing a Markdown document, which relative links are resolved against.
+#[derive(Debug, Clone)]
+pub enum FileLocationDirectory {
+    /// An absolute path on the local filesystem.
+    Local(PathBuf),
+    /// A path in a remote project, starting with the name of its worktree.
+    Remote(PathBuf),
 }
 
 impl Link {
-    pub fn identify(file_location_directory: Option<PathBuf>, text: String) -> Option<Link> {
+    pub fn identify(
+        file_location_directory: Option<FileLocationDirectory>,
+        text: String,
+    ) -> Option<Link> {
         if text.starts_with("http") {
             return Some(Link::Web { url: text });
         }
 
         let path = PathBuf::from(&text);
+        if let Some(FileLocationDirectory::Remote(file_location_directory)) =
+            &file_location_directory
+        {
+            // Remote paths can't be checked for existence here, so only skip links that are
+            // clearly not paths, such as anchors and other URL schemes.
+            if path.is_relative() && !text.starts_with('#') && !text.contains(':') {
+                return Some(Link::RemotePath {
+                    path: normalize_path(&file_location_directory.join(&path)),
+                    display_path: path,
+                });
+            }
+            return None;
+        }
+
         if path.is_absolute() && path.exists() {
             return Some(Link::Path {
                 display_path: path.clone(),
@@ -280,7 +373,8 @@ impl Link {
             });
         }
 
-        if let Some(file_location_directory) = file_location_directory {
+        if let Some(FileLocationDirectory::Local(file_location_directory)) = file_location_directory
+        {
             let display_path = path;
             let path = file_location_directory.join(text);
             if path.exists() {
@@ -296,7 +390,9 @@ impl Display for Link {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Link::Web { url } => write!(f, "{}", url),
-            Link::Path { display_path, .. } => write!(f, "{}", display_path.display()),
+            Link::Path { display_path, .. } | Link::RemotePath { display_path, .. } => {
+                write!(f, "{}", display_path.display())
+            }
         }
     }
 }
@@ -316,7 +412,7 @@ impl Image {
     pub fn identify(
         text: String,
         source_range: Range<usize>,
-        file_location_directory: Option<PathBuf>,
+        file_location_directory: Option<FileLocationDirectory>,
     ) -> Option<Self> {
         let link = Link::identify(file_location_directory, text)?;
         Some(Self {
@@ -0,0 +1,468 @@
+//! Renders TeX math, as written between `$` and `$$` delimiters in Markdown, as Unicode text.
+//!
+//! This covers the commands that are commonly used in Markdown documents (Greek letters,
+//! operators, relations, fractions, roots, sub- and superscripts, and accents). Commands
+//! that can't be represented as text are left as they were written.
+
+use std::{iter::Peekable, str::Chars};
+
+/// Renders the given TeX math as Unicode text.
+pub fn render_tex(tex: &str) -> String {
+    let mut renderer = TexRenderer {
+        chars: tex.chars().peekable(),
+    };
+    renderer.render_until(None).trim().to_string()
+}
+
+struct TexRenderer<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl TexRenderer<'_> {
+    /// Renders tokens until the given closing character, or the end of the input.
+    fn render_until(&mut self, closing: Option<char>) -> String {
+        let mut output = String::new();
+        while let Some(char) = self.chars.next() {
+            if Some(char) == closing {
+                break;
+            }
+            match char {
+                '\\' => {
+                    let name = self.read_command_name();
+                    output.push_str(&self.render_command(&name));
+                }
+                '{' => output.push_str(&self.render_until(Some('}'))),
+                '^' => output.push_str(&script(&self.read_argument(), superscript_char, '^')),
+                '_' => output.push_str(&script(&self.read_argument(), subscript_char, '_')),
+                '\'' => output.push('′'),
+                '-' => output.push('−'),
+                '&' => push_space(&mut output),
+                '~' => output.push(' '),
+                char if char.is_whitespace() => push_space(&mut output),
+                ')' | ']' => {
+                    if output.ends_with(' ') {
+                        output.pop();
+                    }
+                    output.push(char);
+                }
+                char => output.push(char),
+            }
+        }
+        output
+    }
+
+    fn read_command_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(char) = self.chars.peek().copied() {
+            if !char.is_ascii_alphabetic() {
+                break;
+            }
+            name.push(char);
+            self.chars.next();
+        }
+        if name.is_empty()
+            && let Some(char) = self.chars.next()
+        {
+            name.push(char);
+        }
+        name
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|char| char.is_whitespace()).is_some() {}
+    }
+
+    /// Renders the next argument, which is either a group or a single token.
+    fn read_argument(&mut self) -> String {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('{') => self.render_until(Some('}')),
+            Some('\\') => {
+                let name = self.read_command_name();
+                self.render_command(&name)
+            }
+            Some(char) => char.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Reads the next argument verbatim, for commands whose argument is text rather than math.
+    fn read_raw_argument(&mut self) -> String {
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'{').is_none() {
+            return self.chars.next().map(String::from).unwrap_or_default();
+        }
+        let mut depth = 0;
+        let mut argument = String::new();
+        for char in self.chars.by_ref() {
+            match char {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            argument.push(char);
+        }
+        argument
+    }
+
+    fn read_optional_argument(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&'[')?;
+        Some(self.render_until(Some(']')))
+    }
+
+    fn render_command(&mut self, name: &str) -> String {
+        if let Some(symbol) = symbol(name) {
+            return symbol.to_string();
+        }
+        if FUNCTION_NAMES.contains(&name) {
+            return name.to_string();
+        }
+
+        match name {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.read_argument();
+                let denominator = self.read_argument();
+                format!(
+                    "{}/{}",
+                    parenthesize(&numerator),
+                    parenthesize(&denominator)
+                )
+            }
+            "sqrt" => {
+                let root = match self.read_optional_argument().as_deref() {
+                    None | Some("2") => "√".to_string(),
+                    Some("3") => "∛".to_string(),
+                    Some("4") => "∜".to_string(),
+                    Some(degree) => format!("{}√", script(degree, superscript_char, '^')),
+                };
+                format!("{root}{}", parenthesize(&self.read_argument()))
+            }
+            "text" | "textrm" | "textit" | "textbf" | "mathrm" | "operatorname" => {
+                self.read_raw_argument()
+            }
+            "mathit" | "mathbf" | "boldsymbol" | "mathsf" | "mathtt" | "displaystyle" => {
+                self.read_argument()
+            }
+            "mathbb" => self
+                .read_argument()
+                .chars()
+                .map(double_struck_char)
+                .collect(),
+            "hat" | "widehat" => accent(&self.read_argument(), '\u{0302}'),
+            "tilde" | "widetilde" => accent(&self.read_argument(), '\u{0303}'),
+            "bar" | "overline" => accent(&self.read_argument(), '\u{0305}'),
+            "dot" => accent(&self.read_argument(), '\u{0307}'),
+            "ddot" => accent(&self.read_argument(), '\u{0308}'),
+            "vec" => accent(&self.read_argument(), '\u{20D7}'),
+            "left" | "right" | "big" | "Big" | "bigg" | "Bigg" => {
+                // A `.` stands for an invisible delimiter.
+                self.chars.next_if_eq(&'.');
+                String::new()
+            }
+            "begin" | "end" => {
+                self.read_raw_argument();
+                String::new()
+            }
+            "\\" => "\n".to_string(),
+            "," | ":" | ";" | " " | "quad" => " ".to_string(),
+            "qquad" => "  ".to_string(),
+            "!" | "limits" | "nolimits" => String::new(),
+            "{" | "}" | "%" | "$" | "&" | "#" | "_" | "|" => name.to_string(),
+            _ => format!("\\{name}"),
+        }
+    }
+}
+
+fn push_space(output: &mut String) {
+    if !output.is_empty() && !output.ends_with([' ', '(', '[']) {
+        output.push(' ');
+    }
+}
+
+/// Wraps the given rendered math in parentheses, unless it's a single symbol or number.
+fn parenthesize(math: &str) -> String {
+    if math.chars().count() <= 1 || math.chars().all(|char| char.is_ascii_digit()) {
+        math.to_string()
+    } else {
+        format!("({math})")
+    }
+}
+
+/// Renders the given math as a sub- or superscript, falling back to the TeX notation when
+/// it contains characters that have no sub- or superscript form.
+fn script(math: &str, script_char: fn(char) -> Option<char>, fallback_prefix: char) -> String {
+    if let Some(scripted) = math.chars().map(script_char).collect::<Option<String>>() {
+        return scripted;
+    }
+    if math.chars().count() <= 1 {
+        format!("{fallback_prefix}{math}")
+    } else {
+        format!("{fallback_prefix}({math})")
+    }
+}
+
+fn accent(math: &str, combining_char: char) -> String {
+    math.chars()
+        .flat_map(|char| [char, combining_char])
+        .collect()
+}
+
+fn superscript_char(char: char) -> Option<char> {
+    Some(match char {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        '′' => '′',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        'T' => 'ᵀ',
+        _ => return None,
+    })
+}
+
+fn subscript_char(char: char) -> Option<char> {
+    Some(match char {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    })
+}
+
+fn double_struck_char(char: char) -> char {
+    match char {
+        'C' => 'ℂ',
+        'H' => 'ℍ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        char => char,
+    }
+}
+
+const FUNCTION_NAMES: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "csc", "deg", "det", "dim", "exp",
+    "gcd", "inf", "ker", "lim", "ln", "log", "max", "min", "mod", "sec", "sin", "sinh", "sup",
+    "tan", "tanh",
+];
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "infty" => "∞",
+        "pm" => "±",
+        "mp" => "∓",
+        "times" => "×",
+        "div" => "÷",
+        "cdot" => "⋅",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "wedge" | "land" => "∧",
+        "vee" | "lor" => "∨",
+        "neg" | "lnot" => "¬",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "ll" => "≪",
+        "gg" => "≫",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "supset" => "⊃",
+        "subseteq" => "⊆",
+        "supseteq" => "⊇",
+        "mid" => "∣",
+        "parallel" => "∥",
+        "perp" => "⊥",
+        "to" | "rightarrow" => "→",
+        "gets" | "leftarrow" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" => "⇔",
+        "longrightarrow" => "⟶",
+        "implies" => "⟹",
+        "iff" => "⟺",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "forall" => "∀",
+        "exists" => "∃",
+        "nexists" => "∄",
+        "emptyset" | "varnothing" => "∅",
+        "angle" => "∠",
+        "triangle" => "△",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        "prime" => "′",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "lbrace" => "{",
+        "rbrace" => "}",
+        "vert" => "|",
+        "Vert" => "‖",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_tex() {
+        assert_eq!(render_tex("E = mc^2"), "E = mc²");
+        assert_eq!(render_tex(r"\alpha + \beta \leq \gamma"), "α + β ≤ γ");
+        assert_eq!(render_tex("x_{i+1} = x_i - 1"), "xᵢ₊₁ = xᵢ − 1");
+        assert_eq!(render_tex(r"\frac{a+b}{2}"), "(a+b)/2");
+        assert_eq!(render_tex(r"\sqrt{x^2 + y^2}"), "√(x² + y²)");
+        assert_eq!(render_tex(r"\sqrt[3]{x}"), "∛x");
+        assert_eq!(render_tex(r"\sum_{i=1}^{n} i"), "∑ᵢ₌₁ⁿ i");
+        assert_eq!(render_tex(r"\forall x \in \mathbb{R}"), "∀ x ∈ ℝ");
+        assert_eq!(render_tex(r"\text{if } x > 0"), "if x > 0");
+        assert_eq!(render_tex(r"\left( \frac{1}{x} \right)"), "(1/x)");
+        assert_eq!(render_tex(r"\lim_{x \to \infty} f(x)"), "lim_(x → ∞) f(x)");
+        assert_eq!(render_tex(r"\unknown{x}"), r"\unknownx");
+    }
+}
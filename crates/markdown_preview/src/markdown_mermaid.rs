@@ -0,0 +1,35 @@
+//! Renders Mermaid diagrams with the Mermaid CLI (`mmdc`), which has to be installed separately.
+
+use std::{process::Stdio, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use gpui::{Image, ImageFormat};
+use smol::io::AsyncWriteExt as _;
+
+/// The language of the code blocks that contain Mermaid diagrams.
+pub const MERMAID_LANGUAGE: &str = "mermaid";
+
+/// Renders the given Mermaid diagram as an SVG image.
+pub async fn render_mermaid_diagram(source: &str, dark: bool) -> Result<Arc<Image>> {
+    let mut child = util::command::new_smol_command("mmdc")
+        .args(["--input", "-", "--output", "-", "--outputFormat", "svg"])
+        .args(["--backgroundColor", "transparent", "--quiet"])
+        .args(["--theme", if dark { "dark" } else { "default" }])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run the Mermaid CLI (mmdc)")?;
+
+    let mut stdin = child.stdin.take().context("failed to open mmdc's stdin")?;
+    stdin.write_all(source.as_bytes()).await?;
+    drop(stdin);
+
+    let output = child.output().await?;
+    anyhow::ensure!(
+        output.status.success(),
+        "mmdc failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(Arc::new(Image::from_bytes(ImageFormat::Svg, output.stdout)))
+}
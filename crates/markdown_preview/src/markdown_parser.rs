@@ -1,4 +1,4 @@
-use crate::markdown_elements::*;
+use crate::{markdown_elements::*, markdown_math::render_tex};
 use async_recursion::async_recursion;
 use collections::FxHashMap;
 use gpui::{DefiniteLength, FontWeight, px, relative};
@@ -6,11 +6,11 @@ use html5ever::{ParseOpts, local_name, parse_document, tendril::TendrilSink};
 use language::LanguageRegistry;
 use markup5ever_rcdom::RcDom;
 use pulldown_cmark::{Alignment, Event, Options, Parser, Tag, TagEnd};
-use std::{cell::RefCell, collections::HashMap, ops::Range, path::PathBuf, rc::Rc, sync::Arc, vec};
+use std::{cell::RefCell, collections::HashMap, ops::Range, rc::Rc, sync::Arc, vec};
 
 pub async fn parse_markdown(
     markdown_input: &str,
-    file_location_directory: Option<PathBuf>,
+    file_location_directory: Option<FileLocationDirectory>,
     language_registry: Option<Arc<LanguageRegistry>>,
 ) -> ParsedMarkdown {
     let mut options = Options::all();
@@ -34,7 +34,7 @@ struct MarkdownParser<'a> {
     cursor: usize,
     /// The blocks that we have successfully parsed so far
     parsed: Vec<ParsedMarkdownElement>,
-    file_location_directory: Option<PathBuf>,
+    file_location_directory: Option<FileLocationDirectory>,
     language_registry: Option<Arc<LanguageRegistry>>,
 }
 
@@ -55,7 +55,7 @@ impl Default for MarkdownListItem {
 impl<'a> MarkdownParser<'a> {
     fn new(
         tokens: Vec<(Event<'a>, Range<usize>)>,
-        file_location_directory: Option<PathBuf>,
+        file_location_directory: Option<FileLocationDirectory>,
         language_registry: Option<Arc<LanguageRegistry>>,
     ) -> Self {
         Self {
@@ -104,6 +104,8 @@ impl<'a> MarkdownParser<'a> {
             | Event::Html(_)
             | Event::InlineHtml(_)
             | Event::FootnoteReference(_)
+            | Event::InlineMath(_)
+            | Event::DisplayMath(_)
             | Event::Start(Tag::Link { .. })
             | Event::Start(Tag::Emphasis)
             | Event::Start(Tag::Strong)
@@ -321,6 +323,34 @@ impl<'a> MarkdownParser<'a> {
                         }
                     }
                 }
+                Event::InlineMath(tex) => {
+                    text.push_str(&render_tex(tex));
+                    highlights.push((
+                        prev_len..text.len(),
+                        MarkdownHighlight::Style(MarkdownHighlightStyle {
+                            italic: true,
+                            ..Default::default()
+                        }),
+                    ));
+                }
+                Event::DisplayMath(tex) => {
+                    if !text.is_empty() {
+                        markdown_text_like.push(MarkdownParagraphChunk::Text(ParsedMarkdownText {
+                            source_range: source_range.clone(),
+                            contents: std::mem::take(&mut text),
+                            highlights: std::mem::take(&mut highlights),
+                            region_ranges: std::mem::take(&mut region_ranges),
+                            regions: std::mem::take(&mut regions),
+                        }));
+                    }
+                    let (_, math_source_range) = self.current().unwrap();
+                    markdown_text_like.push(MarkdownParagraphChunk::DisplayMath(
+                        ParsedMarkdownMath {
+                            source_range: math_source_range.clone(),
+                            contents: render_tex(tex).into(),
+                        },
+                    ));
+                }
                 Event::Code(t) => {
                     text.push_str(t.as_ref());
                     region_ranges.push(prev_len..text.len());
@@ -1263,6 +1293,70 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_remote_image_links() {
+        let parsed = parse_markdown(
+            "![logo](../images/logo.png)",
+            Some(FileLocationDirectory::Remote("project/docs".into())),
+            None,
+        )
+        .await;
+
+        let ParsedMarkdownElement::Paragraph(chunks) = &parsed.children[0] else {
+            panic!("Expected a paragraph");
+        };
+        assert_eq!(
+            chunks,
+            &[MarkdownParagraphChunk::Image(Image {
+                source_range: 0..27,
+                link: Link::RemotePath {
+                    display_path: "../images/logo.png".into(),
+                    path: "project/images/logo.png".into(),
+                },
+                alt_text: Some("logo".into()),
+                height: None,
+                width: None,
+            })],
+        );
+    }
+
+    #[gpui::test]
+    async fn test_inline_math() {
+        let parsed = parse("Energy $E = mc^2$ is conserved").await;
+
+        assert_eq!(
+            parsed.children,
+            vec![ParsedMarkdownElement::Paragraph(vec![
+                MarkdownParagraphChunk::Text(ParsedMarkdownText {
+                    source_range: 0..30,
+                    contents: "Energy E = mc² is conserved".to_string(),
+                    highlights: vec![(
+                        7..15,
+                        MarkdownHighlight::Style(MarkdownHighlightStyle {
+                            italic: true,
+                            ..Default::default()
+                        }),
+                    )],
+                    region_ranges: Vec::new(),
+                    regions: Vec::new(),
+                })
+            ])]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_display_math() {
+        let parsed = parse("Euler's identity:\n\n$$e^{i\\pi} + 1 = 0$$").await;
+
+        let ParsedMarkdownElement::Paragraph(chunks) = &parsed.children[1] else {
+            panic!("Expected a paragraph");
+        };
+        let [MarkdownParagraphChunk::DisplayMath(math)] = chunks.as_slice() else {
+            panic!("Expected display math");
+        };
+        assert_eq!(math.contents, "e^(iπ) + 1 = 0");
+    }
+
     #[test]
     fn test_parse_length() {
         // Test percentage values
@@ -1,14 +1,18 @@
 use std::sync::Arc;
 use std::time::Duration;
-use std::{ops::Range, path::PathBuf};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
 use anyhow::Result;
-use editor::scroll::Autoscroll;
-use editor::{Editor, EditorEvent, SelectionEffects};
+use collections::{HashMap, HashSet};
+use editor::scroll::{Autoscroll, ScrollAnchor};
+use editor::{Editor, EditorEvent, SelectionEffects, ToOffset as _};
 use gpui::{
-    App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, IsZero, ListState, ParentElement, Render, RetainAllImageCache, Styled,
-    Subscription, Task, WeakEntity, Window, list,
+    App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable, Image, ImageFormat,
+    InteractiveElement, IntoElement, IsZero, ListOffset, ListState, ParentElement, Render,
+    RetainAllImageCache, Styled, Subscription, Task, WeakEntity, Window, list,
 };
 use language::LanguageRegistry;
 use settings::Settings;
@@ -17,7 +21,8 @@ use ui::{WithScrollbar, prelude::*};
 use workspace::item::{Item, ItemHandle};
 use workspace::{Pane, Workspace};
 
-use crate::markdown_elements::ParsedMarkdownElement;
+use crate::markdown_elements::{FileLocationDirectory, Link, ParsedMarkdownElement};
+use crate::markdown_mermaid::{MERMAID_LANGUAGE, render_mermaid_diagram};
 use crate::markdown_renderer::CheckboxClickedEvent;
 use crate::{
     MovePageDown, MovePageUp, OpenFollowingPreview, OpenPreview, OpenPreviewToTheSide,
@@ -39,6 +44,15 @@ pub struct MarkdownPreviewView {
     language_registry: Arc<LanguageRegistry>,
     parsing_markdown_task: Option<Task<Result<()>>>,
     mode: MarkdownPreviewMode,
+    /// The images that have been loaded from a remote project, keyed by their path.
+    remote_images: Arc<HashMap<PathBuf, Arc<Image>>>,
+    requested_remote_images: HashSet<PathBuf>,
+    /// The diagrams that have been rendered for Mermaid code blocks, keyed by their source.
+    mermaid_diagrams: Arc<HashMap<SharedString, Arc<Image>>>,
+    requested_mermaid_diagrams: HashSet<SharedString>,
+    /// The block that the editor and the preview were last scrolled to, when keeping their
+    /// scroll positions in sync.
+    scroll_synced_block: Option<usize>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -203,6 +217,12 @@ impl MarkdownPreviewView {
     ) -> Entity<Self> {
         cx.new(|cx| {
             let list_state = ListState::new(0, gpui::ListAlignment::Top, px(1000.));
+            let this_handle = cx.weak_entity();
+            list_state.set_scroll_handler(move |_, window, cx| {
+                this_handle
+                    .update(cx, |this, cx| this.scroll_editor_to_preview(window, cx))
+                    .ok();
+            });
 
             let mut this = Self {
                 selected_block: 0,
@@ -215,6 +235,11 @@ impl MarkdownPreviewView {
                 parsing_markdown_task: None,
                 image_cache: RetainAllImageCache::new(cx),
                 mode,
+                remote_images: Arc::default(),
+                requested_remote_images: HashSet::default(),
+                mermaid_diagrams: Arc::default(),
+                requested_mermaid_diagrams: HashSet::default(),
+                scroll_synced_block: None,
             };
 
             this.set_editor(active_editor, window, cx);
@@ -284,6 +309,9 @@ impl MarkdownPreviewView {
                         this.list_state.scroll_to_reveal_item(this.selected_block);
                         cx.notify();
                     }
+                    EditorEvent::ScrollPositionChanged { .. } => {
+                        this.scroll_preview_to_editor(editor, cx);
+                    }
                     _ => {}
                 };
             },
@@ -345,11 +373,148 @@ impl MarkdownPreviewView {
                 let scroll_top = view.list_state.logical_scroll_top();
                 view.list_state.reset(markdown_blocks_count);
                 view.list_state.scroll_to(scroll_top);
+                view.load_remote_images(cx);
+                view.render_mermaid_diagrams(cx);
                 cx.notify();
             })
         })
     }
 
+    /// Loads the images in the document that are in a remote project, since they can't be
+    /// read from the local filesystem when rendering.
+    fn load_remote_images(&mut self, cx: &mut Context<Self>) {
+        let Some(contents) = &self.contents else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+
+        let paths = contents
+            .images()
+            .into_iter()
+            .filter_map(|image| match &image.link {
+                Link::RemotePath { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+            .filter(|path| self.requested_remote_images.insert(path.clone()))
+            .collect::<Vec<_>>();
+        for path in paths {
+            let Some(format) = image_format_for_path(&path) else {
+                continue;
+            };
+            let Some(project_path) = project.read(cx).find_project_path(&path, cx) else {
+                continue;
+            };
+            let Some(worktree) = project
+                .read(cx)
+                .worktree_for_id(project_path.worktree_id, cx)
+            else {
+                continue;
+            };
+            let load = worktree.update(cx, |worktree, cx| {
+                worktree.load_binary_file(&project_path.path, cx)
+            });
+            cx.spawn(async move |this, cx| {
+                let loaded = load.await?;
+                let image = Arc::new(Image::from_bytes(format, loaded.content));
+                this.update(cx, |this, cx| {
+                    Arc::make_mut(&mut this.remote_images).insert(path, image);
+                    cx.notify();
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    fn render_mermaid_diagrams(&mut self, cx: &mut Context<Self>) {
+        let Some(contents) = &self.contents else {
+            return;
+        };
+        let dark = !cx.theme().appearance().is_light();
+
+        let sources = contents
+            .children
+            .iter()
+            .filter_map(|block| match block {
+                ParsedMarkdownElement::CodeBlock(code_block)
+                    if code_block.language.as_deref() == Some(MERMAID_LANGUAGE) =>
+                {
+                    Some(code_block.contents.clone())
+                }
+                _ => None,
+            })
+            .filter(|source| self.requested_mermaid_diagrams.insert(source.clone()))
+            .collect::<Vec<_>>();
+        for source in sources {
+            cx.spawn(async move |this, cx| {
+                // Diagrams that fail to render are shown as code blocks.
+                let diagram = render_mermaid_diagram(&source, dark).await?;
+                this.update(cx, |this, cx| {
+                    Arc::make_mut(&mut this.mermaid_diagrams).insert(source, diagram);
+                    cx.notify();
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    /// Scrolls the preview to the block at the top of the editor.
+    fn scroll_preview_to_editor(&mut self, editor: &Entity<Editor>, cx: &mut Context<Self>) {
+        let editor = editor.read(cx);
+        let buffer = editor.buffer().read(cx).snapshot(cx);
+        let top_offset = editor.scroll_manager.anchor().anchor.to_offset(&buffer);
+        let block = self.get_block_index_under_cursor(top_offset..top_offset);
+
+        // Don't move the preview while the editor is scrolled within the block that the
+        // preview is already showing, so that the two don't keep scrolling each other.
+        if self.scroll_synced_block != Some(block) {
+            self.scroll_synced_block = Some(block);
+            self.list_state.scroll_to(ListOffset {
+                item_ix: block,
+                offset_in_item: px(0.),
+            });
+            cx.notify();
+        }
+    }
+
+    /// Scrolls the editor to the source of the block at the top of the preview.
+    fn scroll_editor_to_preview(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(state) = &self.active_editor else {
+            return;
+        };
+        let block = self.list_state.logical_scroll_top().item_ix;
+        if self.scroll_synced_block == Some(block) {
+            return;
+        }
+        let Some(source_range) = self
+            .contents
+            .as_ref()
+            .and_then(|contents| contents.children.get(block))
+            .and_then(|block| block.source_range())
+        else {
+            return;
+        };
+
+        self.scroll_synced_block = Some(block);
+        state.editor.update(cx, |editor, cx| {
+            let anchor = editor
+                .buffer()
+                .read(cx)
+                .snapshot(cx)
+                .anchor_before(source_range.start);
+            editor.set_scroll_anchor(
+                ScrollAnchor {
+                    anchor,
+                    offset: Default::default(),
+                },
+                window,
+                cx,
+            );
+        });
+    }
+
     fn move_cursor_to_block(
         &self,
         window: &mut Window,
@@ -369,16 +534,15 @@ impl MarkdownPreviewView {
         }
     }
 
-    /// The absolute path of the file that is currently being previewed.
-    fn get_folder_for_active_editor(editor: &Editor, cx: &App) -> Option<PathBuf> {
-        if let Some(file) = editor.file_at(0, cx) {
-            if let Some(file) = file.as_local() {
-                file.abs_path(cx).parent().map(|p| p.to_path_buf())
-            } else {
-                None
-            }
+    /// The directory of the file that is currently being previewed.
+    fn get_folder_for_active_editor(editor: &Editor, cx: &App) -> Option<FileLocationDirectory> {
+        let file = editor.file_at(0, cx)?;
+        if let Some(file) = file.as_local() {
+            let directory = file.abs_path(cx).parent()?.to_path_buf();
+            Some(FileLocationDirectory::Local(directory))
         } else {
-            None
+            let directory = file.full_path(cx).parent()?.to_path_buf();
+            Some(FileLocationDirectory::Remote(directory))
         }
     }
 
@@ -508,6 +672,8 @@ impl Render for MarkdownPreviewView {
 
                             let mut render_cx =
                                 RenderContext::new(Some(this.workspace.clone()), window, cx)
+                                    .with_remote_images(this.remote_images.clone())
+                                    .with_mermaid_diagrams(this.mermaid_diagrams.clone())
                                     .with_checkbox_clicked_callback(cx.listener(
                                         move |this, e: &CheckboxClickedEvent, window, cx| {
                                             if let Some(editor) = this
@@ -601,3 +767,17 @@ impl Render for MarkdownPreviewView {
             .vertical_scrollbar_for(self.list_state.clone(), window, cx)
     }
 }
+
+fn image_format_for_path(path: &Path) -> Option<ImageFormat> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "png" => ImageFormat::Png,
+        "jpg" | "jpeg" => ImageFormat::Jpeg,
+        "webp" => ImageFormat::Webp,
+        "gif" => ImageFormat::Gif,
+        "svg" => ImageFormat::Svg,
+        "bmp" => ImageFormat::Bmp,
+        "tif" | "tiff" => ImageFormat::Tiff,
+        _ => return None,
+    })
+}
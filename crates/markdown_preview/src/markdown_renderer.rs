@@ -1,9 +1,11 @@
 use crate::markdown_elements::{
     HeadingLevel, Image, Link, MarkdownParagraph, MarkdownParagraphChunk, ParsedMarkdown,
     ParsedMarkdownBlockQuote, ParsedMarkdownCodeBlock, ParsedMarkdownElement,
-    ParsedMarkdownHeading, ParsedMarkdownListItem, ParsedMarkdownListItemType, ParsedMarkdownTable,
-    ParsedMarkdownTableAlignment, ParsedMarkdownTableRow,
+    ParsedMarkdownHeading, ParsedMarkdownListItem, ParsedMarkdownListItemType, ParsedMarkdownMath,
+    ParsedMarkdownTable, ParsedMarkdownTableAlignment, ParsedMarkdownTableRow,
 };
+use crate::markdown_mermaid::MERMAID_LANGUAGE;
+use collections::HashMap;
 use fs::normalize_path;
 use gpui::{
     AbsoluteLength, AnyElement, App, AppContext as _, ClipboardItem, Context, DefiniteLength, Div,
@@ -14,6 +16,7 @@ use gpui::{
 use settings::Settings;
 use std::{
     ops::{Mul, Range},
+    path::{Path, PathBuf},
     sync::Arc,
     vec,
 };
@@ -61,6 +64,8 @@ pub struct RenderContext {
     syntax_theme: Arc<SyntaxTheme>,
     indent: usize,
     checkbox_clicked_callback: Option<CheckboxClickedCallback>,
+    remote_images: Arc<HashMap<PathBuf, Arc<gpui::Image>>>,
+    mermaid_diagrams: Arc<HashMap<SharedString, Arc<gpui::Image>>>,
 }
 
 impl RenderContext {
@@ -94,6 +99,8 @@ impl RenderContext {
             code_block_background_color: theme.colors().surface_background,
             code_span_background_color: theme.colors().editor_document_highlight_read_background,
             checkbox_clicked_callback: None,
+            remote_images: Arc::default(),
+            mermaid_diagrams: Arc::default(),
         }
     }
 
@@ -105,6 +112,26 @@ impl RenderContext {
         self
     }
 
+    /// Sets the images that have been loaded for [`Link::RemotePath`] images, keyed by their
+    /// path.
+    pub fn with_remote_images(
+        mut self,
+        remote_images: Arc<HashMap<PathBuf, Arc<gpui::Image>>>,
+    ) -> Self {
+        self.remote_images = remote_images;
+        self
+    }
+
+    /// Sets the diagrams that have been rendered for Mermaid code blocks, keyed by their
+    /// source.
+    pub fn with_mermaid_diagrams(
+        mut self,
+        mermaid_diagrams: Arc<HashMap<SharedString, Arc<gpui::Image>>>,
+    ) -> Self {
+        self.mermaid_diagrams = mermaid_diagrams;
+        self
+    }
+
     fn next_id(&mut self, span: &Range<usize>) -> ElementId {
         let id = format!("markdown-{}-{}-{}", self.next_id, span.start, span.end);
         self.next_id += 1;
@@ -601,6 +628,17 @@ fn render_markdown_code_block(
     parsed: &ParsedMarkdownCodeBlock,
     cx: &mut RenderContext,
 ) -> AnyElement {
+    if parsed.language.as_deref() == Some(MERMAID_LANGUAGE)
+        && let Some(diagram) = cx.mermaid_diagrams.get(&parsed.contents)
+    {
+        return cx
+            .with_common_p(div())
+            .flex()
+            .justify_center()
+            .child(img(diagram.clone()).max_w_full())
+            .into_any();
+    }
+
     let body = if let Some(highlights) = parsed.highlights.as_ref() {
         StyledText::new(parsed.contents.clone()).with_default_highlights(
             &cx.buffer_text_style,
@@ -736,6 +774,13 @@ fn render_markdown_text(parsed_new: &MarkdownParagraph, cx: &mut RenderContext)
                                         });
                                     }
                                 }
+                                Link::RemotePath { path, .. } => {
+                                    if let Some(workspace) = &workspace {
+                                        _ = workspace.update(cx, |workspace, cx| {
+                                            open_remote_path(workspace, path, window, cx);
+                                        });
+                                    }
+                                }
                             },
                         ),
                     )
@@ -746,21 +791,56 @@ fn render_markdown_text(parsed_new: &MarkdownParagraph, cx: &mut RenderContext)
             MarkdownParagraphChunk::Image(image) => {
                 any_element.push(render_markdown_image(image, cx));
             }
+
+            MarkdownParagraphChunk::DisplayMath(math) => {
+                any_element.push(render_markdown_display_math(math, cx));
+            }
         }
     }
 
     any_element
 }
 
+fn render_markdown_display_math(math: &ParsedMarkdownMath, cx: &mut RenderContext) -> AnyElement {
+    div()
+        .w_full()
+        .py(cx.scaled_rems(0.5))
+        .flex()
+        .justify_center()
+        .italic()
+        .child(math.contents.clone())
+        .into_any()
+}
+
+fn open_remote_path(
+    workspace: &mut Workspace,
+    path: &Path,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    if let Some(project_path) = workspace.project().read(cx).find_project_path(path, cx) {
+        workspace
+            .open_path(project_path, None, true, window, cx)
+            .detach_and_log_err(cx);
+    }
+}
+
 fn render_markdown_rule(cx: &mut RenderContext) -> AnyElement {
     let rule = div().w_full().h(cx.scaled_rems(0.125)).bg(cx.border_color);
     div().py(cx.scaled_rems(0.5)).child(rule).into_any()
 }
 
 fn render_markdown_image(image: &Image, cx: &mut RenderContext) -> AnyElement {
-    let image_resource = match image.link.clone() {
-        Link::Web { url } => Resource::Uri(url.into()),
-        Link::Path { path, .. } => Resource::Path(Arc::from(path)),
+    let image_source = match image.link.clone() {
+        Link::Web { url } => ImageSource::Resource(Resource::Uri(url.into())),
+        Link::Path { path, .. } => ImageSource::Resource(Resource::Path(Arc::from(path))),
+        Link::RemotePath { path, .. } => match cx.remote_images.get(&path) {
+            Some(image) => ImageSource::from(image.clone()),
+            None => {
+                // The image hasn't been loaded from the remote project yet.
+                return div().children(image.alt_text.clone()).into_any();
+            }
+        },
     };
 
     let element_id = cx.next_id(&image.source_range);
@@ -770,7 +850,7 @@ fn render_markdown_image(image: &Image, cx: &mut RenderContext) -> AnyElement {
         .id(element_id)
         .cursor_pointer()
         .child(
-            img(ImageSource::Resource(image_resource))
+            img(image_source)
                 .max_w_full()
                 .with_fallback({
                     let alt_text = image.alt_text.clone();
@@ -814,6 +894,13 @@ fn render_markdown_image(image: &Image, cx: &mut RenderContext) -> AnyElement {
                                 });
                             }
                         }
+                        Link::RemotePath { path, .. } => {
+                            if let Some(workspace) = &workspace {
+                                _ = workspace.update(cx, |workspace, cx| {
+                                    open_remote_path(workspace, path, window, cx);
+                                });
+                            }
+                        }
                     }
                 }
             }
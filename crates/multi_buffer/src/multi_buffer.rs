@@ -2440,6 +2440,7 @@ impl MultiBuffer {
             BufferEvent::DiagnosticsUpdated => Event::DiagnosticsUpdated,
             BufferEvent::CapabilityChanged => {
                 self.capability = buffer.read(cx).capability();
+                cx.notify();
                 return;
             }
             BufferEvent::Operation { .. } | BufferEvent::ReloadNeeded => return,
@@ -449,11 +449,15 @@ pub async fn stream_completion(
     request: Request,
 ) -> Result<BoxStream<'static, Result<ResponseStreamEvent>>> {
     let uri = format!("{api_url}/chat/completions");
-    let request_builder = HttpRequest::builder()
+    let mut request_builder = HttpRequest::builder()
         .method(Method::POST)
         .uri(uri)
-        .header("Content-Type", "application/json")
-        .header("Authorization", format!("Bearer {}", api_key.trim()));
+        .header("Content-Type", "application/json");
+    // Local OpenAI-compatible servers (e.g. llama.cpp) usually don't require a key.
+    if !api_key.trim().is_empty() {
+        request_builder =
+            request_builder.header("Authorization", format!("Bearer {}", api_key.trim()));
+    }
 
     let request = request_builder.body(AsyncBody::from(serde_json::to_string(&request)?))?;
     let mut response = client.send(request).await?;
@@ -515,6 +519,55 @@ pub async fn stream_completion(
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct ListModelsResponse {
+    pub data: Vec<ListedModel>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ListedModel {
+    pub id: String,
+    /// Extra metadata reported by llama.cpp's server.
+    #[serde(default)]
+    pub meta: Option<ListedModelMeta>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ListedModelMeta {
+    pub n_ctx_train: Option<u64>,
+}
+
+pub async fn list_models(
+    client: &dyn HttpClient,
+    api_url: &str,
+    api_key: Option<&str>,
+) -> Result<Vec<ListedModel>> {
+    let uri = format!("{api_url}/models");
+    let mut request_builder = HttpRequest::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .header("Accept", "application/json");
+    if let Some(api_key) = api_key.map(str::trim).filter(|key| !key.is_empty()) {
+        request_builder = request_builder.header("Authorization", format!("Bearer {api_key}"));
+    }
+
+    let request = request_builder.body(AsyncBody::default())?;
+    let mut response = client.send(request).await?;
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+
+    anyhow::ensure!(
+        response.status().is_success(),
+        "Failed to list models from {}. Status: {:?}, Body: {}",
+        api_url,
+        response.status(),
+        body,
+    );
+    let response: ListModelsResponse =
+        serde_json::from_str(&body).context("Unable to parse models response")?;
+    Ok(response.data)
+}
+
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum OpenAiEmbeddingModel {
     #[serde(rename = "text-embedding-3-small")]
@@ -81,6 +81,8 @@ actions!(
         SelectParent,
         /// Toggles the pin status of the active editor.
         ToggleActiveEditorPin,
+        /// Toggles sorting the outline's symbols by name instead of by their position in the buffer.
+        ToggleSortByName,
         /// Unfolds the selected directory.
         UnfoldDirectory,
         /// Toggles focus on the outline panel.
@@ -101,6 +103,7 @@ pub struct OutlinePanel {
     workspace: WeakEntity<Workspace>,
     active: bool,
     pinned: bool,
+    sort_by_name: bool,
     scroll_handle: UniformListScrollHandle,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     focus_handle: FocusHandle,
@@ -666,6 +669,8 @@ pub enum Event {
 struct SerializedOutlinePanel {
     width: Option<Pixels>,
     active: Option<bool>,
+    #[serde(default)]
+    sort_by_name: bool,
 }
 
 pub fn init_settings(cx: &mut App) {
@@ -714,6 +719,7 @@ impl OutlinePanel {
                 panel.update(cx, |panel, cx| {
                     panel.width = serialized_panel.width.map(|px| px.round());
                     panel.active = serialized_panel.active.unwrap_or(false);
+                    panel.sort_by_name = serialized_panel.sort_by_name;
                     cx.notify();
                 });
             }
@@ -856,6 +862,7 @@ impl OutlinePanel {
                 mode: ItemsDisplayMode::Outline,
                 active: false,
                 pinned: false,
+                sort_by_name: false,
                 workspace: workspace_handle,
                 project,
                 fs: workspace.app_state().fs.clone(),
@@ -921,12 +928,17 @@ impl OutlinePanel {
         };
         let width = self.width;
         let active = Some(self.active);
+        let sort_by_name = self.sort_by_name;
         self.pending_serialization = cx.background_spawn(
             async move {
                 KEY_VALUE_STORE
                     .write_kvp(
                         serialization_key,
-                        serde_json::to_string(&SerializedOutlinePanel { width, active })?,
+                        serde_json::to_string(&SerializedOutlinePanel {
+                            width,
+                            active,
+                            sort_by_name,
+                        })?,
                     )
                     .await?;
                 anyhow::Ok(())
@@ -4314,6 +4326,10 @@ impl OutlinePanel {
                     .or_default()
                     .extend(outline_has_children);
 
+                if self.sort_by_name {
+                    visible_outlines = sort_outlines_by_name(&visible_outlines);
+                }
+
                 for outline in visible_outlines {
                     let outline_entry = OutlineEntryOutline {
                         buffer_id,
@@ -4441,6 +4457,17 @@ impl OutlinePanel {
         cx.notify();
     }
 
+    fn toggle_sort_by_name(
+        &mut self,
+        _: &ToggleSortByName,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sort_by_name = !self.sort_by_name;
+        self.serialize(cx);
+        self.update_cached_entries(None, window, cx);
+    }
+
     fn selected_entry(&self) -> Option<&PanelEntry> {
         match &self.selected_entry {
             SelectedEntry::Invalidated(entry) => entry.as_ref(),
@@ -4718,6 +4745,22 @@ impl OutlinePanel {
                 .p_2()
                 .w_full()
                 .child(self.filter_editor.clone())
+                .child(
+                    IconButton::new("outline-panel-sort", IconName::ListFilter)
+                        .shape(IconButtonShape::Square)
+                        .toggle_state(self.sort_by_name)
+                        .tooltip(Tooltip::for_action_title(
+                            if self.sort_by_name {
+                                "Sort by Position"
+                            } else {
+                                "Sort by Name"
+                            },
+                            &ToggleSortByName,
+                        ))
+                        .on_click(cx.listener(|outline_panel, _, window, cx| {
+                            outline_panel.toggle_sort_by_name(&ToggleSortByName, window, cx);
+                        })),
+                )
                 .child(
                     div().child(
                         IconButton::new(
@@ -4830,6 +4873,39 @@ fn file_name(path: &Path) -> String {
     }
 }
 
+/// Sorts outlines, given in buffer order, by name, keeping each item's children
+/// directly after it and sorting them among themselves.
+fn sort_outlines_by_name<'a>(outlines: &[&'a Outline]) -> Vec<&'a Outline> {
+    let mut subtrees = Vec::new();
+    let mut subtree_start = 0;
+    for ix in 1..=outlines.len() {
+        if outlines
+            .get(ix)
+            .is_none_or(|outline| outline.depth <= outlines[subtree_start].depth)
+        {
+            subtrees.push(&outlines[subtree_start..ix]);
+            subtree_start = ix;
+        }
+    }
+    subtrees.sort_by_cached_key(|subtree| outline_name(subtree[0]).to_lowercase());
+    subtrees
+        .into_iter()
+        .flat_map(|subtree| {
+            let mut sorted = vec![subtree[0]];
+            sorted.extend(sort_outlines_by_name(&subtree[1..]));
+            sorted
+        })
+        .collect()
+}
+
+fn outline_name(outline: &Outline) -> &str {
+    outline
+        .name_ranges
+        .first()
+        .and_then(|name_range| outline.text.get(name_range.clone()))
+        .unwrap_or(&outline.text)
+}
+
 impl Panel for OutlinePanel {
     fn persistent_name() -> &'static str {
         "Outline Panel"
@@ -4975,6 +5051,7 @@ impl Render for OutlinePanel {
             .on_action(cx.listener(Self::copy_path))
             .on_action(cx.listener(Self::copy_relative_path))
             .on_action(cx.listener(Self::toggle_active_editor_pin))
+            .on_action(cx.listener(Self::toggle_sort_by_name))
             .on_action(cx.listener(Self::unfold_directory))
             .on_action(cx.listener(Self::fold_directory))
             .on_action(cx.listener(Self::open_excerpts))
@@ -5227,6 +5304,42 @@ mod tests {
 
     const SELECTED_MARKER: &str = "  <==== selected";
 
+    #[test]
+    fn test_sort_outlines_by_name() {
+        let outline = |depth: usize, text: &str, name_start: usize| Outline {
+            depth,
+            range: language::Anchor::MIN..language::Anchor::MAX,
+            text: text.to_string(),
+            highlight_ranges: Vec::new(),
+            name_ranges: vec![name_start..text.len()],
+            body_range: None,
+            annotation_range: None,
+        };
+        let outlines = [
+            outline(0, "struct Zebra", 7),
+            outline(0, "impl Zebra", 5),
+            outline(1, "fn walk", 3),
+            outline(1, "fn eat", 3),
+            outline(2, "let grass", 4),
+            outline(0, "fn apple", 3),
+        ];
+        let sorted = sort_outlines_by_name(&outlines.iter().collect::<Vec<_>>())
+            .into_iter()
+            .map(|outline| (outline.depth, outline.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sorted,
+            [
+                (0, "fn apple"),
+                (0, "struct Zebra"),
+                (0, "impl Zebra"),
+                (1, "fn eat"),
+                (2, "let grass"),
+                (1, "fn walk"),
+            ]
+        );
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_project_search_results_toggling(cx: &mut TestAppContext) {
         init_test(cx);
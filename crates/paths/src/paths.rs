@@ -281,6 +281,14 @@ pub fn snippets_dir() -> &'static PathBuf {
     SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
 }
 
+/// Returns the path to the scratches directory.
+///
+/// This is where the contents of scratch buffers are saved.
+pub fn scratches_dir() -> &'static PathBuf {
+    static SCRATCHES_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SCRATCHES_DIR.get_or_init(|| data_dir().join("scratches"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
@@ -0,0 +1,400 @@
+//! Reading PDF files with PDFium.
+//!
+//! PDFium isn't thread-safe, so it's only used from a single thread, which is started when the
+//! first document is opened. Each open document is kept loaded on that thread until its
+//! [`PdfDocument`] is dropped, and requests about documents are handled one at a time, in order.
+//!
+//! PDFium is loaded at runtime, from next to the executable or from the system's library search
+//! path. When it can't be found, opening a document fails with [`PdfiumNotFound`].
+
+use std::{
+    ffi::c_int,
+    fmt, ptr,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+};
+
+use anyhow::{Context as _, Result, anyhow};
+use collections::HashMap;
+use futures::channel::oneshot;
+use gpui::RenderImage;
+
+use crate::{
+    pdf_layout::{PageRect, PageSize, PdfMatch},
+    pdfium::{self, DocumentHandle, Pdfium},
+};
+
+/// The error returned when a document is opened but the PDFium library couldn't be loaded.
+#[derive(Debug)]
+pub struct PdfiumNotFound;
+
+impl fmt::Display for PdfiumNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the PDFium library couldn't be loaded")
+    }
+}
+
+impl std::error::Error for PdfiumNotFound {}
+
+type DocumentId = usize;
+
+enum Request {
+    Open {
+        id: DocumentId,
+        bytes: Arc<[u8]>,
+        response: oneshot::Sender<Result<Vec<PageSize>>>,
+    },
+    RenderPage {
+        id: DocumentId,
+        page_index: usize,
+        width: u32,
+        response: oneshot::Sender<Result<Arc<RenderImage>>>,
+    },
+    Search {
+        id: DocumentId,
+        query: String,
+        case_sensitive: bool,
+        response: oneshot::Sender<Result<Vec<PdfMatch>>>,
+    },
+    Close {
+        id: DocumentId,
+    },
+}
+
+static NEXT_DOCUMENT_ID: AtomicUsize = AtomicUsize::new(0);
+
+static REQUESTS: LazyLock<mpsc::Sender<Request>> = LazyLock::new(|| {
+    let (requests_tx, requests_rx) = mpsc::channel();
+    if let Err(error) = std::thread::Builder::new()
+        .name("PDFium".into())
+        .spawn(move || handle_requests(requests_rx))
+    {
+        log::error!("failed to start the PDFium thread: {error}");
+    }
+    requests_tx
+});
+
+/// A document that's loaded on the PDFium thread, until this is dropped.
+pub struct PdfDocument {
+    id: DocumentId,
+}
+
+impl PdfDocument {
+    /// Loads the document, returning it along with the size of each of its pages.
+    pub async fn open(bytes: Arc<[u8]>) -> Result<(Self, Vec<PageSize>)> {
+        let this = Self {
+            id: NEXT_DOCUMENT_ID.fetch_add(1, Ordering::Relaxed),
+        };
+        let page_sizes = send_request(|response| Request::Open {
+            id: this.id,
+            bytes,
+            response,
+        })
+        .await?;
+        Ok((this, page_sizes))
+    }
+
+    /// Renders a page to an image of the given width, in physical pixels.
+    pub async fn render_page(&self, page_index: usize, width: u32) -> Result<Arc<RenderImage>> {
+        send_request(|response| Request::RenderPage {
+            id: self.id,
+            page_index,
+            width,
+            response,
+        })
+        .await
+    }
+
+    /// Returns the occurrences of the query in the text of the document, in order.
+    pub async fn search(&self, query: String, case_sensitive: bool) -> Result<Vec<PdfMatch>> {
+        send_request(|response| Request::Search {
+            id: self.id,
+            query,
+            case_sensitive,
+            response,
+        })
+        .await
+    }
+}
+
+impl Drop for PdfDocument {
+    fn drop(&mut self) {
+        REQUESTS.send(Request::Close { id: self.id }).ok();
+    }
+}
+
+async fn send_request<T>(request: impl FnOnce(oneshot::Sender<Result<T>>) -> Request) -> Result<T> {
+    let (response_tx, response_rx) = oneshot::channel();
+    REQUESTS
+        .send(request(response_tx))
+        .map_err(|_| anyhow!("the PDFium thread isn't running"))?;
+    response_rx
+        .await
+        .context("the PDFium thread stopped before responding")?
+}
+
+/// Handles requests on the PDFium thread until every sender has been dropped.
+fn handle_requests(requests: mpsc::Receiver<Request>) {
+    let pdfium = Pdfium::load()
+        .inspect_err(|error| log::error!("failed to load PDFium: {error:#}"))
+        .ok();
+    let mut documents = HashMap::<DocumentId, LoadedDocument>::default();
+
+    for request in requests {
+        match request {
+            Request::Open {
+                id,
+                bytes,
+                response,
+            } => {
+                let result = match &pdfium {
+                    Some(pdfium) => LoadedDocument::load(pdfium, bytes).and_then(|document| {
+                        match document.page_sizes(pdfium) {
+                            Ok(page_sizes) => {
+                                documents.insert(id, document);
+                                Ok(page_sizes)
+                            }
+                            Err(error) => {
+                                document.close(pdfium);
+                                Err(error)
+                            }
+                        }
+                    }),
+                    None => Err(PdfiumNotFound.into()),
+                };
+                response.send(result).ok();
+            }
+            Request::RenderPage {
+                id,
+                page_index,
+                width,
+                response,
+            } => {
+                let result = with_document(&pdfium, &documents, id, |pdfium, document| {
+                    document.render_page(pdfium, page_index, width)
+                });
+                response.send(result).ok();
+            }
+            Request::Search {
+                id,
+                query,
+                case_sensitive,
+                response,
+            } => {
+                let result = with_document(&pdfium, &documents, id, |pdfium, document| {
+                    document.search(pdfium, &query, case_sensitive)
+                });
+                response.send(result).ok();
+            }
+            Request::Close { id } => {
+                if let (Some(pdfium), Some(document)) = (&pdfium, documents.remove(&id)) {
+                    document.close(pdfium);
+                }
+            }
+        }
+    }
+}
+
+fn with_document<T>(
+    pdfium: &Option<Pdfium>,
+    documents: &HashMap<DocumentId, LoadedDocument>,
+    id: DocumentId,
+    f: impl FnOnce(&Pdfium, &LoadedDocument) -> Result<T>,
+) -> Result<T> {
+    let pdfium = pdfium.as_ref().ok_or(PdfiumNotFound)?;
+    let document = documents.get(&id).context("document isn't open")?;
+    f(pdfium, document)
+}
+
+/// A document opened by PDFium, which must only be used on the PDFium thread.
+struct LoadedDocument {
+    handle: DocumentHandle,
+    /// The file's contents, which PDFium reads from until the document is closed.
+    _bytes: Arc<[u8]>,
+}
+
+impl LoadedDocument {
+    fn load(pdfium: &Pdfium, bytes: Arc<[u8]>) -> Result<Self> {
+        let len = c_int::try_from(bytes.len()).context("PDF is too large")?;
+        // SAFETY: The bytes are kept alive until the document is closed.
+        let handle =
+            unsafe { (pdfium.FPDF_LoadMemDocument)(bytes.as_ptr().cast(), len, ptr::null()) };
+        anyhow::ensure!(
+            !handle.is_null(),
+            "failed to open PDF: {}",
+            pdfium.last_error()
+        );
+        Ok(Self {
+            handle,
+            _bytes: bytes,
+        })
+    }
+
+    fn close(self, pdfium: &Pdfium) {
+        // SAFETY: The document is open, and isn't used after this.
+        unsafe { (pdfium.FPDF_CloseDocument)(self.handle) };
+    }
+
+    fn page_count(&self, pdfium: &Pdfium) -> usize {
+        // SAFETY: The document is open.
+        let page_count = unsafe { (pdfium.FPDF_GetPageCount)(self.handle) };
+        page_count.max(0) as usize
+    }
+
+    fn page_size(&self, pdfium: &Pdfium, page_index: usize) -> Result<PageSize> {
+        let (mut width, mut height) = (0.0, 0.0);
+        // SAFETY: The document is open, and PDFium checks that the page index is in bounds.
+        let found = unsafe {
+            (pdfium.FPDF_GetPageSizeByIndex)(
+                self.handle,
+                c_int::try_from(page_index)?,
+                &mut width,
+                &mut height,
+            )
+        };
+        anyhow::ensure!(found != 0, "failed to get the size of page {page_index}");
+        Ok(PageSize {
+            width: width as f32,
+            height: height as f32,
+        })
+    }
+
+    fn page_sizes(&self, pdfium: &Pdfium) -> Result<Vec<PageSize>> {
+        (0..self.page_count(pdfium))
+            .map(|page_index| self.page_size(pdfium, page_index))
+            .collect()
+    }
+
+    /// Calls the function with the page, which is only loaded for the duration of the call.
+    fn with_page<T>(
+        &self,
+        pdfium: &Pdfium,
+        page_index: usize,
+        f: impl FnOnce(pdfium::PageHandle) -> Result<T>,
+    ) -> Result<T> {
+        let page_index = c_int::try_from(page_index)?;
+        // SAFETY: The document is open.
+        let page = unsafe { (pdfium.FPDF_LoadPage)(self.handle, page_index) };
+        anyhow::ensure!(
+            !page.is_null(),
+            "failed to load page {page_index}: {}",
+            pdfium.last_error()
+        );
+        let result = f(page);
+        // SAFETY: The page was loaded above, and isn't used after this.
+        unsafe { (pdfium.FPDF_ClosePage)(page) };
+        result
+    }
+
+    fn render_page(
+        &self,
+        pdfium: &Pdfium,
+        page_index: usize,
+        width: u32,
+    ) -> Result<Arc<RenderImage>> {
+        let size = self.page_size(pdfium, page_index)?;
+        let width = width.max(1);
+        let height = ((width as f32 * size.height / size.width).round() as u32).max(1);
+        let (bitmap_width, bitmap_height) = (c_int::try_from(width)?, c_int::try_from(height)?);
+
+        self.with_page(pdfium, page_index, |page| {
+            // SAFETY: The size is positive, and the bitmap is destroyed below.
+            let bitmap = unsafe { (pdfium.FPDFBitmap_Create)(bitmap_width, bitmap_height, 1) };
+            anyhow::ensure!(
+                !bitmap.is_null(),
+                "failed to allocate a bitmap for the page"
+            );
+
+            // SAFETY: The bitmap and page are valid, and the rendered area is within the bitmap.
+            // PDFium's bitmaps are BGRA, as images are, so rows only need to be copied.
+            let data = unsafe {
+                (pdfium.FPDFBitmap_FillRect)(bitmap, 0, 0, bitmap_width, bitmap_height, 0xFFFFFFFF);
+                (pdfium.FPDF_RenderPageBitmap)(
+                    bitmap,
+                    page,
+                    0,
+                    0,
+                    bitmap_width,
+                    bitmap_height,
+                    0,
+                    pdfium::FPDF_ANNOT,
+                );
+                let buffer = (pdfium.FPDFBitmap_GetBuffer)(bitmap).cast::<u8>();
+                let stride = (pdfium.FPDFBitmap_GetStride)(bitmap) as usize;
+                let row_len = width as usize * 4;
+                let mut data = Vec::with_capacity(row_len * height as usize);
+                for row in 0..height as usize {
+                    data.extend_from_slice(std::slice::from_raw_parts(
+                        buffer.add(row * stride),
+                        row_len,
+                    ));
+                }
+                (pdfium.FPDFBitmap_Destroy)(bitmap);
+                data
+            };
+
+            let image = image::RgbaImage::from_raw(width, height, data)
+                .context("rendered page has an unexpected size")?;
+            Ok(Arc::new(RenderImage::new(vec![image::Frame::new(image)])))
+        })
+    }
+
+    fn search(&self, pdfium: &Pdfium, query: &str, case_sensitive: bool) -> Result<Vec<PdfMatch>> {
+        let query = query.encode_utf16().chain([0]).collect::<Vec<u16>>();
+        let flags = if case_sensitive {
+            pdfium::FPDF_MATCHCASE
+        } else {
+            0
+        };
+        let mut matches = Vec::new();
+        for page_index in 0..self.page_count(pdfium) {
+            self.with_page(pdfium, page_index, |page| {
+                // SAFETY: The page is loaded, the query is nul-terminated, and the text page and
+                // search are closed before the page is.
+                unsafe {
+                    let text_page = (pdfium.FPDFText_LoadPage)(page);
+                    anyhow::ensure!(!text_page.is_null(), "failed to load the text of the page");
+                    let search = (pdfium.FPDFText_FindStart)(text_page, query.as_ptr(), flags, 0);
+                    if !search.is_null() {
+                        while (pdfium.FPDFText_FindNext)(search) != 0 {
+                            let start = (pdfium.FPDFText_GetSchResultIndex)(search);
+                            let count = (pdfium.FPDFText_GetSchCount)(search);
+                            let rect_count = (pdfium.FPDFText_CountRects)(text_page, start, count);
+                            let rects = (0..rect_count)
+                                .map(|rect_index| {
+                                    let (mut left, mut top, mut right, mut bottom) =
+                                        (0.0, 0.0, 0.0, 0.0);
+                                    (pdfium.FPDFText_GetRect)(
+                                        text_page,
+                                        rect_index,
+                                        &mut left,
+                                        &mut top,
+                                        &mut right,
+                                        &mut bottom,
+                                    );
+                                    PageRect {
+                                        left: left as f32,
+                                        bottom: bottom as f32,
+                                        right: right as f32,
+                                        top: top as f32,
+                                    }
+                                })
+                                .collect();
+                            matches.push(PdfMatch {
+                                page: page_index,
+                                rects,
+                            });
+                        }
+                        (pdfium.FPDFText_FindClose)(search);
+                    }
+                    (pdfium.FPDFText_ClosePage)(text_page);
+                }
+                Ok(())
+            })?;
+        }
+        Ok(matches)
+    }
+}
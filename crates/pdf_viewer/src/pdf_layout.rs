@@ -0,0 +1,115 @@
+/// The number of pixels per PDF point at a zoom of 100%, so that pages are shown at their physical
+/// size on a 96 DPI screen.
+pub const PIXELS_PER_POINT: f32 = 96.0 / 72.0;
+
+/// The zoom levels that zooming in and out steps through.
+pub const ZOOM_LEVELS: &[f32] = &[0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 2.0, 3.0, 4.0];
+
+/// Returns the next zoom level above the given zoom.
+pub fn zoom_in(zoom: f32) -> f32 {
+    ZOOM_LEVELS
+        .iter()
+        .copied()
+        .find(|level| *level > zoom + f32::EPSILON)
+        .unwrap_or(zoom)
+}
+
+/// Returns the next zoom level below the given zoom.
+pub fn zoom_out(zoom: f32) -> f32 {
+    ZOOM_LEVELS
+        .iter()
+        .rev()
+        .copied()
+        .find(|level| *level < zoom - f32::EPSILON)
+        .unwrap_or(zoom)
+}
+
+pub fn format_zoom(zoom: f32) -> String {
+    format!("{}%", (zoom * 100.0).round() as i32)
+}
+
+/// The size of a page, in PDF points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PageSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+/// A rectangle on a page, in PDF points, whose origin is at the bottom left of the page.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PageRect {
+    pub left: f32,
+    pub bottom: f32,
+    pub right: f32,
+    pub top: f32,
+}
+
+/// A rectangle in pixels relative to the top left of a page as it's shown.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PixelRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PageRect {
+    /// Converts the rectangle to pixels on a page of the given size shown at the given zoom.
+    pub fn to_pixels(&self, page: PageSize, zoom: f32) -> PixelRect {
+        let scale = PIXELS_PER_POINT * zoom;
+        PixelRect {
+            x: self.left * scale,
+            y: (page.height - self.top) * scale,
+            width: (self.right - self.left) * scale,
+            height: (self.top - self.bottom) * scale,
+        }
+    }
+}
+
+/// A match of a text search, which may span several lines of a page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PdfMatch {
+    pub page: usize,
+    pub rects: Vec<PageRect>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_levels() {
+        assert_eq!(zoom_in(1.0), 1.25);
+        assert_eq!(zoom_in(1.1), 1.25);
+        assert_eq!(zoom_in(4.0), 4.0);
+        assert_eq!(zoom_out(1.0), 0.75);
+        assert_eq!(zoom_out(1.1), 1.0);
+        assert_eq!(zoom_out(0.25), 0.25);
+        assert_eq!(format_zoom(1.25), "125%");
+        assert_eq!(format_zoom(0.5), "50%");
+    }
+
+    #[test]
+    fn test_rect_to_pixels() {
+        let page = PageSize {
+            width: 612.0,
+            height: 792.0,
+        };
+        let rect = PageRect {
+            left: 72.0,
+            bottom: 700.0,
+            right: 144.0,
+            top: 720.0,
+        };
+        assert_eq!(
+            rect.to_pixels(page, 1.0),
+            PixelRect {
+                x: 96.0,
+                y: 96.0,
+                width: 96.0,
+                height: 20.0 * PIXELS_PER_POINT,
+            }
+        );
+        assert_eq!(rect.to_pixels(page, 2.0).x, 192.0);
+    }
+}
@@ -0,0 +1,649 @@
+mod pdf_document;
+mod pdf_layout;
+mod pdfium;
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use collections::HashMap;
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment,
+    ListState, RenderImage, Task, Window, actions, img, list,
+};
+use project::{Project, ProjectEntryId, ProjectPath, search::SearchQuery};
+use ui::{Tooltip, prelude::*};
+use workspace::{
+    Pane, WorkspaceId,
+    item::{Item, ItemEvent, ProjectItem, TabContentParams},
+    searchable::{Direction, SearchEvent, SearchOptions, SearchableItem, SearchableItemHandle},
+};
+
+use crate::{
+    pdf_document::{PdfDocument, PdfiumNotFound},
+    pdf_layout::{PIXELS_PER_POINT, PageSize, PdfMatch, format_zoom, zoom_in, zoom_out},
+};
+
+actions!(
+    pdf_viewer,
+    [
+        /// Scrolls to the next page.
+        NextPage,
+        /// Scrolls to the previous page.
+        PreviousPage,
+        /// Zooms in.
+        ZoomIn,
+        /// Zooms out.
+        ZoomOut,
+        /// Resets the zoom to 100%.
+        ResetZoom,
+    ]
+);
+
+/// The space between pages, in pixels.
+const PAGE_GAP: f32 = 12.0;
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<PdfView>(cx);
+}
+
+/// The bytes of a PDF file.
+pub struct PdfFile {
+    bytes: Arc<[u8]>,
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+}
+
+impl project::ProjectItem for PdfFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        if !path.path.extension()?.eq_ignore_ascii_case("pdf") {
+            return None;
+        }
+
+        let entry_id = project
+            .read(cx)
+            .entry_for_path(path, cx)
+            .map(|entry| entry.id);
+        let project_path = path.clone();
+        let Some(worktree) = project.read(cx).worktree_for_id(path.worktree_id, cx) else {
+            return Some(Task::ready(Err(anyhow!(
+                "worktree not found for {project_path:?}"
+            ))));
+        };
+        let load_file = worktree.update(cx, |worktree, cx| {
+            worktree.load_binary_file(&project_path.path, cx)
+        });
+        Some(cx.spawn(async move |cx| {
+            let bytes = load_file.await?.content;
+            cx.new(|_| Self {
+                bytes: bytes.into(),
+                project_path,
+                entry_id,
+            })
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// A page that has been rendered at a zoom level.
+struct RenderedPage {
+    zoom: f32,
+    image: Arc<RenderImage>,
+}
+
+enum PdfState {
+    Loading,
+    /// The PDFium library couldn't be loaded, so no PDF can be shown.
+    PdfiumNotFound,
+    Failed(SharedString),
+    Loaded {
+        document: Arc<PdfDocument>,
+        page_sizes: Vec<PageSize>,
+    },
+}
+
+/// Shows the pages of a PDF file, rendered with PDFium.
+pub struct PdfView {
+    file: Entity<PdfFile>,
+    project: Entity<Project>,
+    focus_handle: FocusHandle,
+    state: PdfState,
+    rendered_pages: HashMap<usize, RenderedPage>,
+    render_tasks: HashMap<usize, Task<()>>,
+    zoom: f32,
+    list_state: ListState,
+    search_matches: Vec<PdfMatch>,
+    active_match: Option<usize>,
+    _load_task: Task<()>,
+}
+
+impl PdfView {
+    fn new(
+        file: Entity<PdfFile>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let bytes = file.read(cx).bytes.clone();
+        let load_task = cx.spawn_in(window, async move |this, cx| {
+            let document = PdfDocument::open(bytes).await;
+            this.update(cx, |this, cx| {
+                this.state = match document {
+                    Ok((document, page_sizes)) => {
+                        this.list_state.reset(page_sizes.len());
+                        PdfState::Loaded {
+                            document: Arc::new(document),
+                            page_sizes,
+                        }
+                    }
+                    Err(error) if error.is::<PdfiumNotFound>() => PdfState::PdfiumNotFound,
+                    Err(error) => PdfState::Failed(error.to_string().into()),
+                };
+                cx.notify();
+            })
+            .ok();
+        });
+
+        let list_state = ListState::new(0, ListAlignment::Top, px(1000.));
+        let this = cx.entity().downgrade();
+        list_state.set_scroll_handler(move |_, _, cx| {
+            this.update(cx, |_, cx| cx.notify()).ok();
+        });
+
+        Self {
+            file,
+            project,
+            focus_handle: cx.focus_handle(),
+            state: PdfState::Loading,
+            rendered_pages: HashMap::default(),
+            render_tasks: HashMap::default(),
+            zoom: 1.0,
+            list_state,
+            search_matches: Vec::new(),
+            active_match: None,
+            _load_task: load_task,
+        }
+    }
+
+    fn page_sizes(&self) -> &[PageSize] {
+        match &self.state {
+            PdfState::Loaded { page_sizes, .. } => page_sizes,
+            _ => &[],
+        }
+    }
+
+    fn document(&self) -> Option<Arc<PdfDocument>> {
+        match &self.state {
+            PdfState::Loaded { document, .. } => Some(document.clone()),
+            _ => None,
+        }
+    }
+
+    fn current_page(&self) -> usize {
+        self.list_state.logical_scroll_top().item_ix
+    }
+
+    fn go_to_page(&mut self, page: usize, cx: &mut Context<Self>) {
+        let page_count = self.page_sizes().len();
+        if page_count == 0 {
+            return;
+        }
+        self.list_state.scroll_to(gpui::ListOffset {
+            item_ix: page.min(page_count - 1),
+            offset_in_item: px(0.),
+        });
+        cx.notify();
+    }
+
+    fn next_page(&mut self, _: &NextPage, _: &mut Window, cx: &mut Context<Self>) {
+        self.go_to_page(self.current_page() + 1, cx);
+    }
+
+    fn previous_page(&mut self, _: &PreviousPage, _: &mut Window, cx: &mut Context<Self>) {
+        self.go_to_page(self.current_page().saturating_sub(1), cx);
+    }
+
+    fn set_zoom(&mut self, zoom: f32, cx: &mut Context<Self>) {
+        if zoom == self.zoom {
+            return;
+        }
+        // Keep the current page in view, since the heights of all of the pages change.
+        let current_page = self.current_page();
+        self.zoom = zoom;
+        self.render_tasks.clear();
+        self.list_state.reset(self.page_sizes().len());
+        self.go_to_page(current_page, cx);
+    }
+
+    fn zoom_in(&mut self, _: &ZoomIn, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_zoom(zoom_in(self.zoom), cx);
+    }
+
+    fn zoom_out(&mut self, _: &ZoomOut, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_zoom(zoom_out(self.zoom), cx);
+    }
+
+    fn reset_zoom(&mut self, _: &ResetZoom, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_zoom(1.0, cx);
+    }
+
+    /// Renders the page in the background if it hasn't been rendered at the current zoom.
+    fn request_page(&mut self, page: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let is_rendered = self
+            .rendered_pages
+            .get(&page)
+            .is_some_and(|rendered| rendered.zoom == self.zoom);
+        if is_rendered || self.render_tasks.contains_key(&page) {
+            return;
+        }
+        let Some(size) = self.page_sizes().get(page).copied() else {
+            return;
+        };
+        let Some(document) = self.document() else {
+            return;
+        };
+
+        let zoom = self.zoom;
+        let width = (size.width * PIXELS_PER_POINT * zoom * window.scale_factor()).ceil() as u32;
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let image = document.render_page(page, width).await;
+            this.update_in(cx, |this, window, cx| {
+                this.render_tasks.remove(&page);
+                match image {
+                    Ok(image) => {
+                        let previous = this
+                            .rendered_pages
+                            .insert(page, RenderedPage { zoom, image });
+                        if let Some(previous) = previous {
+                            cx.drop_image(previous.image, Some(window));
+                        }
+                    }
+                    Err(error) => log::error!("failed to render page {}: {error:#}", page + 1),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        self.render_tasks.insert(page, task);
+    }
+
+    fn render_page(
+        &mut self,
+        page: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        self.request_page(page, window, cx);
+
+        let Some(size) = self.page_sizes().get(page).copied() else {
+            return div().into_any_element();
+        };
+        let zoom = self.zoom;
+        let scale = PIXELS_PER_POINT * zoom;
+        let (width, height) = (px(size.width * scale), px(size.height * scale));
+        let colors = cx.theme().colors();
+        let active_match = self.active_match;
+
+        let highlights = self
+            .search_matches
+            .iter()
+            .enumerate()
+            .filter(|(_, search_match)| search_match.page == page)
+            .flat_map(|(index, search_match)| {
+                let is_active = active_match == Some(index);
+                search_match.rects.iter().map(move |rect| {
+                    let rect = rect.to_pixels(size, zoom);
+                    let color = if is_active {
+                        colors.search_match_background
+                    } else {
+                        colors.search_match_background.opacity(0.5)
+                    };
+                    div()
+                        .absolute()
+                        .left(px(rect.x))
+                        .top(px(rect.y))
+                        .w(px(rect.width))
+                        .h(px(rect.height))
+                        .bg(color)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        h_flex()
+            .w_full()
+            .justify_center()
+            .pb(px(PAGE_GAP))
+            .child(
+                div()
+                    .relative()
+                    .w(width)
+                    .h(height)
+                    .flex_none()
+                    .bg(gpui::white())
+                    .shadow_md()
+                    .children(
+                        self.rendered_pages
+                            .get(&page)
+                            .map(|rendered| img(rendered.image.clone()).size_full()),
+                    )
+                    .children(highlights),
+            )
+            .into_any_element()
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let page_count = self.page_sizes().len();
+        let current_page = self.current_page();
+
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_center()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().toolbar_background)
+            .child(
+                IconButton::new("previous-page", IconName::ChevronUp)
+                    .disabled(current_page == 0)
+                    .tooltip(Tooltip::for_action_title("Previous Page", &PreviousPage))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.previous_page(&PreviousPage, window, cx)
+                    })),
+            )
+            .child(
+                Label::new(format!(
+                    "Page {} of {}",
+                    (current_page + 1).min(page_count),
+                    page_count
+                ))
+                .size(LabelSize::Small),
+            )
+            .child(
+                IconButton::new("next-page", IconName::ChevronDown)
+                    .disabled(current_page + 1 >= page_count)
+                    .tooltip(Tooltip::for_action_title("Next Page", &NextPage))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.next_page(&NextPage, window, cx)),
+                    ),
+            )
+            .child(div().w_4())
+            .child(
+                IconButton::new("zoom-out", IconName::Dash)
+                    .tooltip(Tooltip::for_action_title("Zoom Out", &ZoomOut))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.zoom_out(&ZoomOut, window, cx)),
+                    ),
+            )
+            .child(
+                Button::new("reset-zoom", format_zoom(self.zoom))
+                    .label_size(LabelSize::Small)
+                    .tooltip(Tooltip::for_action_title("Reset Zoom", &ResetZoom))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.reset_zoom(&ResetZoom, window, cx)),
+                    ),
+            )
+            .child(
+                IconButton::new("zoom-in", IconName::Plus)
+                    .tooltip(Tooltip::for_action_title("Zoom In", &ZoomIn))
+                    .on_click(cx.listener(|this, _, window, cx| this.zoom_in(&ZoomIn, window, cx))),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for PdfView {}
+impl EventEmitter<SearchEvent> for PdfView {}
+
+impl Focusable for PdfView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for PdfView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        true
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.file
+            .read(cx)
+            .project_path
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string()
+            .into()
+    }
+
+    fn clone_on_split(
+        &self,
+        _: Option<WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        Some(cx.new(|cx| Self::new(self.file.clone(), self.project.clone(), window, cx)))
+    }
+
+    fn as_searchable(&self, handle: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(handle.clone()))
+    }
+}
+
+impl SearchableItem for PdfView {
+    type Match = PdfMatch;
+
+    fn supported_options(&self) -> SearchOptions {
+        SearchOptions {
+            case: true,
+            word: false,
+            regex: false,
+            replacement: false,
+            selection: false,
+            find_in_results: false,
+        }
+    }
+
+    fn clear_matches(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        self.search_matches.clear();
+        self.active_match = None;
+        cx.notify();
+    }
+
+    fn update_matches(&mut self, matches: &[Self::Match], _: &mut Window, cx: &mut Context<Self>) {
+        self.search_matches = matches.to_vec();
+        self.active_match = None;
+        cx.notify();
+    }
+
+    fn query_suggestion(&mut self, _: &mut Window, _: &mut Context<Self>) -> String {
+        String::new()
+    }
+
+    fn activate_match(
+        &mut self,
+        index: usize,
+        matches: &[Self::Match],
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(search_match) = matches.get(index) {
+            self.active_match = Some(index);
+            self.go_to_page(search_match.page, cx);
+            cx.emit(SearchEvent::ActiveMatchChanged);
+        }
+    }
+
+    fn select_matches(
+        &mut self,
+        matches: &[Self::Match],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches.is_empty() {
+            self.activate_match(0, matches, window, cx);
+        }
+    }
+
+    fn replace(&mut self, _: &Self::Match, _: &SearchQuery, _: &mut Window, _: &mut Context<Self>) {
+    }
+
+    fn find_matches(
+        &mut self,
+        query: Arc<SearchQuery>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Vec<Self::Match>> {
+        let Some(document) = self.document() else {
+            return Task::ready(Vec::new());
+        };
+        cx.background_spawn(async move {
+            if query.as_str().is_empty() {
+                return Vec::new();
+            }
+            document
+                .search(query.as_str().to_string(), query.case_sensitive())
+                .await
+                .unwrap_or_else(|error| {
+                    log::error!("failed to search PDF: {error:#}");
+                    Vec::new()
+                })
+        })
+    }
+
+    fn active_match_index(
+        &mut self,
+        direction: Direction,
+        matches: &[Self::Match],
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<usize> {
+        if matches.is_empty() {
+            return None;
+        }
+        if let Some(active_match) = self.active_match
+            && active_match < matches.len()
+        {
+            return Some(active_match);
+        }
+        let current_page = self.current_page();
+        match direction {
+            Direction::Next => matches
+                .iter()
+                .position(|search_match| search_match.page >= current_page)
+                .or(Some(0)),
+            Direction::Prev => matches
+                .iter()
+                .rposition(|search_match| search_match.page <= current_page)
+                .or(Some(matches.len() - 1)),
+        }
+    }
+}
+
+impl Render for PdfView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match &self.state {
+            PdfState::Loading => h_flex()
+                .size_full()
+                .justify_center()
+                .child(Label::new("Loading PDF…").color(Color::Muted))
+                .into_any_element(),
+            PdfState::PdfiumNotFound => v_flex()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .gap_1()
+                .child(Label::new("PDFium Not Found"))
+                .child(
+                    Label::new(format!(
+                        "To view PDF files, install the PDFium library, or place {} next to Zed's executable.",
+                        pdfium::library_file_name().to_string_lossy()
+                    ))
+                    .color(Color::Muted),
+                )
+                .into_any_element(),
+            PdfState::Failed(error) => v_flex()
+                .size_full()
+                .items_center()
+                .justify_center()
+                .gap_1()
+                .child(Label::new("Failed to open PDF"))
+                .child(Label::new(error.clone()).color(Color::Muted))
+                .into_any_element(),
+            PdfState::Loaded { .. } => list(
+                self.list_state.clone(),
+                cx.processor(|this, page, window, cx| this.render_page(page, window, cx)),
+            )
+            .size_full()
+            .pt(px(PAGE_GAP))
+            .into_any_element(),
+        };
+
+        v_flex()
+            .key_context("PdfView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::next_page))
+            .on_action(cx.listener(Self::previous_page))
+            .on_action(cx.listener(Self::zoom_in))
+            .on_action(cx.listener(Self::zoom_out))
+            .on_action(cx.listener(Self::reset_zoom))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_toolbar(cx))
+            .child(div().flex_1().min_h_0().child(content))
+    }
+}
+
+impl ProjectItem for PdfView {
+    type Item = PdfFile;
+
+    fn for_project_item(
+        project: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, project, window, cx)
+    }
+}
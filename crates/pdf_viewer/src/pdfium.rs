@@ -0,0 +1,129 @@
+//! Minimal bindings to the parts of the PDFium C API that the viewer uses, loaded at runtime.
+//!
+//! PDFium isn't thread-safe, so these must only be used from the PDFium thread.
+
+use std::ffi::{OsString, c_char, c_double, c_int, c_ulong, c_ushort, c_void};
+
+use anyhow::{Context as _, Result};
+use libloading::Library;
+
+pub type DocumentHandle = *mut c_void;
+pub type PageHandle = *mut c_void;
+pub type BitmapHandle = *mut c_void;
+pub type TextPageHandle = *mut c_void;
+pub type SearchHandle = *mut c_void;
+
+/// Renders annotations, such as form fields, along with the page's content.
+pub const FPDF_ANNOT: c_int = 0x01;
+/// Makes text searches case sensitive.
+pub const FPDF_MATCHCASE: c_ulong = 0x01;
+
+macro_rules! pdfium_functions {
+    ($($name:ident: fn($($arg:ty),*) $(-> $ret:ty)?;)*) => {
+        #[allow(non_snake_case)]
+        pub struct Pdfium {
+            $(pub $name: unsafe extern "C" fn($($arg),*) $(-> $ret)?,)*
+            _library: Library,
+        }
+
+        impl Pdfium {
+            fn from_library(library: Library) -> Result<Self> {
+                Ok(Self {
+                    $(
+                        // SAFETY: The signature matches the one declared in PDFium's headers.
+                        $name: unsafe {
+                            *library
+                                .get::<unsafe extern "C" fn($($arg),*) $(-> $ret)?>(
+                                    concat!(stringify!($name), "\0").as_bytes(),
+                                )
+                                .context(concat!("PDFium is missing ", stringify!($name)))?
+                        },
+                    )*
+                    _library: library,
+                })
+            }
+        }
+    };
+}
+
+pdfium_functions! {
+    FPDF_InitLibrary: fn();
+    FPDF_GetLastError: fn() -> c_ulong;
+    FPDF_LoadMemDocument: fn(*const c_void, c_int, *const c_char) -> DocumentHandle;
+    FPDF_CloseDocument: fn(DocumentHandle);
+    FPDF_GetPageCount: fn(DocumentHandle) -> c_int;
+    FPDF_GetPageSizeByIndex: fn(DocumentHandle, c_int, *mut c_double, *mut c_double) -> c_int;
+    FPDF_LoadPage: fn(DocumentHandle, c_int) -> PageHandle;
+    FPDF_ClosePage: fn(PageHandle);
+    FPDF_RenderPageBitmap: fn(
+        BitmapHandle,
+        PageHandle,
+        c_int,
+        c_int,
+        c_int,
+        c_int,
+        c_int,
+        c_int
+    );
+    FPDFBitmap_Create: fn(c_int, c_int, c_int) -> BitmapHandle;
+    FPDFBitmap_FillRect: fn(BitmapHandle, c_int, c_int, c_int, c_int, c_ulong);
+    FPDFBitmap_GetBuffer: fn(BitmapHandle) -> *mut c_void;
+    FPDFBitmap_GetStride: fn(BitmapHandle) -> c_int;
+    FPDFBitmap_Destroy: fn(BitmapHandle);
+    FPDFText_LoadPage: fn(PageHandle) -> TextPageHandle;
+    FPDFText_ClosePage: fn(TextPageHandle);
+    FPDFText_FindStart: fn(TextPageHandle, *const c_ushort, c_ulong, c_int) -> SearchHandle;
+    FPDFText_FindNext: fn(SearchHandle) -> c_int;
+    FPDFText_GetSchResultIndex: fn(SearchHandle) -> c_int;
+    FPDFText_GetSchCount: fn(SearchHandle) -> c_int;
+    FPDFText_FindClose: fn(SearchHandle);
+    FPDFText_CountRects: fn(TextPageHandle, c_int, c_int) -> c_int;
+    FPDFText_GetRect: fn(
+        TextPageHandle,
+        c_int,
+        *mut c_double,
+        *mut c_double,
+        *mut c_double,
+        *mut c_double
+    ) -> c_int;
+}
+
+/// The file name of the PDFium library on this platform.
+pub fn library_file_name() -> OsString {
+    libloading::library_filename("pdfium")
+}
+
+impl Pdfium {
+    /// Loads and initializes the PDFium library, preferring a copy next to the executable over
+    /// the system's.
+    pub fn load() -> Result<Self> {
+        let file_name = library_file_name();
+        let bundled_path = std::env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.parent()?.join(&file_name)));
+
+        // SAFETY: PDFium doesn't run any code when it's loaded.
+        let library = bundled_path
+            .and_then(|path| unsafe { Library::new(path) }.ok())
+            .map_or_else(|| unsafe { Library::new(&file_name) }, Ok)
+            .with_context(|| format!("failed to load {}", file_name.to_string_lossy()))?;
+        let pdfium = Self::from_library(library)?;
+        // SAFETY: This is called once, before any other function.
+        unsafe { (pdfium.FPDF_InitLibrary)() };
+        Ok(pdfium)
+    }
+
+    /// Describes the error that made the last call to PDFium fail.
+    pub fn last_error(&self) -> &'static str {
+        // SAFETY: This has no preconditions.
+        match unsafe { (self.FPDF_GetLastError)() } {
+            0 => "no error",
+            2 => "file not found or could not be opened",
+            3 => "file not in PDF format or corrupted",
+            4 => "password required or incorrect password",
+            5 => "unsupported security scheme",
+            6 => "page not found or content error",
+            _ => "unknown error",
+        }
+    }
+}
@@ -0,0 +1,808 @@
+//! An index of the symbols that the project's TypeScript, JavaScript and Python files export,
+//! so that completions can offer them together with the import they need, for language
+//! servers that don't do this themselves.
+
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    path::PathBuf,
+    sync::{Arc, LazyLock},
+};
+
+use collections::HashSet;
+use fs::Fs;
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{Context, Entity, Subscription, Task};
+use language::{Buffer, LanguageName};
+use regex::Regex;
+use util::rel_path::RelPath;
+use worktree::{File, WorktreeId};
+
+use crate::{
+    ProjectPath,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
+};
+
+/// Files larger than this are unlikely to be hand-written modules, so they aren't indexed.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// How a language imports symbols from other files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportSyntax {
+    JavaScript,
+    Python,
+}
+
+impl ImportSyntax {
+    pub fn for_language(name: &LanguageName) -> Option<Self> {
+        match name.as_ref() {
+            "TypeScript" | "TSX" | "JavaScript" => Some(Self::JavaScript),
+            "Python" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn for_path(path: &RelPath) -> Option<Self> {
+        match path.extension()? {
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" => Some(Self::Python),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportKind {
+    Function,
+    Class,
+    Variable,
+    Type,
+    Enum,
+    Module,
+}
+
+/// A symbol that a file makes available to other files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    pub name: String,
+    pub kind: ExportKind,
+    /// Whether this is a JavaScript default export, which is imported without braces.
+    pub is_default: bool,
+}
+
+/// A symbol that can be imported into a particular file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AutoImportCandidate {
+    pub export: Export,
+    /// What the import statement names the module by, e.g. `./utils` or `app.utils`.
+    pub module: String,
+}
+
+pub struct AutoImports {
+    worktree_store: Entity<WorktreeStore>,
+    exports: BTreeMap<ProjectPath, Arc<[Export]>>,
+    scanned_worktrees: HashSet<WorktreeId>,
+    scan_tx: mpsc::UnboundedSender<(ProjectPath, Option<PathBuf>)>,
+    _scan_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl AutoImports {
+    pub fn new(
+        worktree_store: Entity<WorktreeStore>,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let (scan_tx, scan_rx) = mpsc::unbounded();
+        let subscription = cx.subscribe(&worktree_store, |this, _, event, cx| match event {
+            WorktreeStoreEvent::WorktreeRemoved(_, worktree_id) => {
+                this.scanned_worktrees.remove(worktree_id);
+                this.exports
+                    .retain(|path, _| path.worktree_id != *worktree_id);
+            }
+            WorktreeStoreEvent::WorktreeUpdatedEntries(worktree_id, changes) => {
+                if this.scanned_worktrees.contains(worktree_id) {
+                    for (path, _, _) in changes.iter() {
+                        this.queue_path(*worktree_id, path.clone(), cx);
+                    }
+                }
+            }
+            _ => {}
+        });
+        Self {
+            worktree_store,
+            exports: BTreeMap::new(),
+            scanned_worktrees: HashSet::default(),
+            scan_tx,
+            _scan_task: Self::process_scans(scan_rx, fs, cx),
+            _subscription: subscription,
+        }
+    }
+
+    /// Returns the symbols exported by other files in the buffer's worktree that the buffer
+    /// could import. The worktree is indexed the first time this is called for it, so until
+    /// that finishes there may be fewer candidates.
+    pub fn candidates(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Vec<AutoImportCandidate> {
+        let buffer = buffer.read(cx);
+        let Some(syntax) = buffer
+            .language()
+            .and_then(|language| ImportSyntax::for_language(&language.name()))
+        else {
+            return Vec::new();
+        };
+        let Some(file) = File::from_dyn(buffer.file()) else {
+            return Vec::new();
+        };
+        let worktree_id = file.worktree_id(cx);
+        let importer = file.path().clone();
+        if self.scanned_worktrees.insert(worktree_id) {
+            self.scan_worktree(worktree_id, cx);
+        }
+
+        let mut candidates = Vec::new();
+        for (path, exports) in &self.exports {
+            if path.worktree_id != worktree_id
+                || path.path == importer
+                || ImportSyntax::for_path(&path.path) != Some(syntax)
+            {
+                continue;
+            }
+            let Some(module) = module_specifier(syntax, &importer, &path.path) else {
+                continue;
+            };
+            candidates.extend(exports.iter().map(|export| AutoImportCandidate {
+                export: export.clone(),
+                module: module.clone(),
+            }));
+        }
+        candidates
+    }
+
+    fn scan_worktree(&mut self, worktree_id: WorktreeId, cx: &mut Context<Self>) {
+        let Some(worktree) = self
+            .worktree_store
+            .read(cx)
+            .worktree_for_id(worktree_id, cx)
+        else {
+            return;
+        };
+        let worktree = worktree.read(cx);
+        if !worktree.is_local() {
+            return;
+        }
+        for entry in worktree.files(false, 0) {
+            if entry.size <= MAX_FILE_SIZE && ImportSyntax::for_path(&entry.path).is_some() {
+                let path = ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                };
+                let abs_path = worktree.absolutize(&entry.path);
+                self.scan_tx.unbounded_send((path, Some(abs_path))).ok();
+            }
+        }
+    }
+
+    /// Reindexes a path that changed, or forgets it if it's no longer a file that's indexed.
+    fn queue_path(&mut self, worktree_id: WorktreeId, path: Arc<RelPath>, cx: &mut Context<Self>) {
+        let abs_path = self
+            .worktree_store
+            .read(cx)
+            .worktree_for_id(worktree_id, cx)
+            .and_then(|worktree| {
+                let worktree = worktree.read(cx);
+                let entry = worktree.entry_for_path(&path)?;
+                (entry.is_file()
+                    && !entry.is_ignored
+                    && entry.size <= MAX_FILE_SIZE
+                    && ImportSyntax::for_path(&path).is_some())
+                .then(|| worktree.absolutize(&path))
+            });
+        self.scan_tx
+            .unbounded_send((ProjectPath { worktree_id, path }, abs_path))
+            .ok();
+    }
+
+    fn process_scans(
+        mut scan_rx: mpsc::UnboundedReceiver<(ProjectPath, Option<PathBuf>)>,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        cx.spawn(async move |this, cx| {
+            while let Some(request) = scan_rx.next().await {
+                let mut requests = vec![request];
+                while let Ok(Some(request)) = scan_rx.try_next() {
+                    requests.push(request);
+                }
+
+                let mut loaded = Vec::with_capacity(requests.len());
+                for (path, abs_path) in requests {
+                    let text = match abs_path {
+                        Some(abs_path) => fs.load(&abs_path).await.ok(),
+                        None => None,
+                    };
+                    loaded.push((path, text));
+                }
+                let scanned = cx
+                    .background_spawn(async move {
+                        loaded
+                            .into_iter()
+                            .map(|(path, text)| {
+                                let exports = text
+                                    .zip(ImportSyntax::for_path(&path.path))
+                                    .map(|(text, syntax)| find_exports(&text, syntax))
+                                    .unwrap_or_default();
+                                (path, exports)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .await;
+
+                let updated = this.update(cx, |this, _| {
+                    for (path, exports) in scanned {
+                        // A removed directory is reported once, so forget the files in it too.
+                        this.exports.retain(|other, _| {
+                            other.worktree_id != path.worktree_id
+                                || !other.path.starts_with(&path.path)
+                        });
+                        if !exports.is_empty() {
+                            this.exports.insert(path, exports.into());
+                        }
+                    }
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+        })
+    }
+}
+
+static JAVASCRIPT_DECLARATION_EXPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^export\s+(?:declare\s+)?(default\s+)?(?:async\s+)?(?:abstract\s+)?(function|class|const\s+enum|const|let|var|interface|type|enum|namespace)[\s*]+([A-Za-z_$][\w$]*)",
+    )
+    .unwrap()
+});
+static JAVASCRIPT_DEFAULT_EXPORT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^export\s+default\s+([A-Za-z_$][\w$]*)\s*;?\s*$").unwrap());
+static JAVASCRIPT_LIST_EXPORT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^export\s+(?:type\s+)?\{([^}]*)\}\s*;?\s*$").unwrap());
+static PYTHON_DEFINITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:async\s+def|def|class)\s+([A-Za-z]\w*)").unwrap());
+static PYTHON_ASSIGNMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z]\w*)\s*(?::[^=]+)?=(?:[^=]|$)").unwrap());
+
+/// Finds the symbols that a file exports, by looking at its top-level declarations.
+pub fn find_exports(text: &str, syntax: ImportSyntax) -> Vec<Export> {
+    let mut exports = Vec::new();
+    for line in text.lines() {
+        match syntax {
+            ImportSyntax::JavaScript => {
+                if let Some(captures) = JAVASCRIPT_DECLARATION_EXPORT.captures(line) {
+                    let keyword = &captures[2];
+                    let kind = match keyword {
+                        "function" => ExportKind::Function,
+                        "class" => ExportKind::Class,
+                        "interface" | "type" => ExportKind::Type,
+                        "namespace" => ExportKind::Module,
+                        _ if keyword.ends_with("enum") => ExportKind::Enum,
+                        _ => ExportKind::Variable,
+                    };
+                    exports.push(Export {
+                        name: captures[3].to_string(),
+                        kind,
+                        is_default: captures.get(1).is_some(),
+                    });
+                } else if let Some(captures) = JAVASCRIPT_DEFAULT_EXPORT.captures(line) {
+                    exports.push(Export {
+                        name: captures[1].to_string(),
+                        kind: ExportKind::Variable,
+                        is_default: true,
+                    });
+                } else if let Some(captures) = JAVASCRIPT_LIST_EXPORT.captures(line) {
+                    for specifier in captures[1].split(',') {
+                        let specifier = specifier.trim();
+                        let specifier = specifier.strip_prefix("type ").unwrap_or(specifier);
+                        let name = match specifier.split_once(" as ") {
+                            Some((_, alias)) => alias.trim(),
+                            None => specifier,
+                        };
+                        if !name.is_empty() && name != "default" {
+                            exports.push(Export {
+                                name: name.to_string(),
+                                kind: ExportKind::Variable,
+                                is_default: false,
+                            });
+                        }
+                    }
+                }
+            }
+            ImportSyntax::Python => {
+                // Names starting with an underscore are private by convention.
+                if let Some(captures) = PYTHON_DEFINITION.captures(line) {
+                    let kind = if line.starts_with("class") {
+                        ExportKind::Class
+                    } else {
+                        ExportKind::Function
+                    };
+                    exports.push(Export {
+                        name: captures[1].to_string(),
+                        kind,
+                        is_default: false,
+                    });
+                } else if let Some(captures) = PYTHON_ASSIGNMENT.captures(line) {
+                    exports.push(Export {
+                        name: captures[1].to_string(),
+                        kind: ExportKind::Variable,
+                        is_default: false,
+                    });
+                }
+            }
+        }
+    }
+    let mut seen = HashSet::default();
+    exports.retain(|export| seen.insert(export.name.clone()));
+    exports
+}
+
+/// Returns how a file refers to another file of the same worktree in an import statement.
+fn module_specifier(
+    syntax: ImportSyntax,
+    importer: &RelPath,
+    exporter: &RelPath,
+) -> Option<String> {
+    let stem = exporter.file_stem()?;
+    let exporter_dir = exporter.parent()?.components().collect::<Vec<_>>();
+    match syntax {
+        ImportSyntax::JavaScript => {
+            let stem = stem.strip_suffix(".d").unwrap_or(stem);
+            let importer_dir = importer.parent()?.components().collect::<Vec<_>>();
+            let common = importer_dir
+                .iter()
+                .zip(&exporter_dir)
+                .take_while(|(a, b)| a == b)
+                .count();
+            let mut components = vec![".."; importer_dir.len() - common];
+            components.extend(&exporter_dir[common..]);
+            // `index` files are imported through their directory.
+            if stem != "index" || components.is_empty() || components.ends_with(&[".."]) {
+                components.push(stem);
+            }
+            let specifier = components.join("/");
+            if specifier.starts_with("..") {
+                Some(specifier)
+            } else {
+                Some(format!("./{specifier}"))
+            }
+        }
+        ImportSyntax::Python => {
+            let mut components = exporter_dir;
+            // Packages are commonly kept in a `src` directory that isn't part of their name.
+            if components.first() == Some(&"src") {
+                components.remove(0);
+            }
+            if stem != "__init__" {
+                components.push(stem);
+            }
+            let is_identifier = |component: &&str| {
+                component
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && component.chars().all(|c| c.is_alphanumeric() || c == '_')
+            };
+            (!components.is_empty() && components.iter().all(is_identifier))
+                .then(|| components.join("."))
+        }
+    }
+}
+
+/// Returns the edit that imports the candidate into a file with the given text, as a byte
+/// range and the text to replace it with, or `None` if the file already imports that name.
+pub fn import_edit(
+    text: &str,
+    syntax: ImportSyntax,
+    candidate: &AutoImportCandidate,
+) -> Option<(Range<usize>, String)> {
+    match syntax {
+        ImportSyntax::JavaScript => javascript_import_edit(text, candidate),
+        ImportSyntax::Python => python_import_edit(text, candidate),
+    }
+}
+
+static JAVASCRIPT_IMPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^import\s+(type\s+)?(.*?)\s*from\s*(['"])([^'"]+)['"]\s*(;?)"#).unwrap()
+});
+
+fn javascript_import_edit(
+    text: &str,
+    candidate: &AutoImportCandidate,
+) -> Option<(Range<usize>, String)> {
+    let name = candidate.export.name.as_str();
+    let mut quote = '"';
+    let mut semicolon = true;
+    let mut seen_import = false;
+    let mut insert_at = 0;
+    let mut in_import = false;
+    let mut in_comment = false;
+    // Prefer adding to an existing import of the module, once it's known the name isn't
+    // imported by a later statement.
+    let mut merge_edit = None;
+    for (line_range, line) in lines_with_ranges(text) {
+        let trimmed = line.trim();
+        if in_import {
+            in_import = !trimmed.contains(['"', '\'']);
+            if names_bound(trimmed).any(|bound| bound == name) {
+                return None;
+            }
+            insert_at = line_range.end;
+            continue;
+        }
+        if in_comment {
+            in_comment = !trimmed.contains("*/");
+            continue;
+        }
+        if let Some(captures) = JAVASCRIPT_IMPORT.captures(line) {
+            let clause = &captures[2];
+            if names_bound(clause).any(|bound| bound == name) {
+                return None;
+            }
+            if !seen_import {
+                seen_import = true;
+                quote = captures[3].chars().next().unwrap_or(quote);
+                semicolon = !captures[5].is_empty();
+            }
+            let is_type_import = captures.get(1).is_some();
+            if merge_edit.is_none()
+                && &captures[4] == candidate.module.as_str()
+                && !candidate.export.is_default
+                && !is_type_import
+                && let Some(open) = clause.find('{')
+                && let Some(close) = clause.rfind('}')
+            {
+                let clause_start = line_range.start + captures.get(2)?.start();
+                let inner = &clause[open + 1..close];
+                merge_edit = if inner.trim().is_empty() {
+                    let start = clause_start + open + 1;
+                    Some((start..clause_start + close, format!(" {name} ")))
+                } else {
+                    let end = clause_start + open + 1 + inner.trim_end().len();
+                    let separator = if inner.trim_end().ends_with(',') {
+                        " "
+                    } else {
+                        ", "
+                    };
+                    Some((end..end, format!("{separator}{name}")))
+                };
+            }
+            insert_at = line_range.end;
+        } else if trimmed.starts_with("import ") || trimmed == "import" {
+            // Only the last line of an import that spans several lines names the module.
+            in_import = !trimmed.contains(['"', '\'']);
+            insert_at = line_range.end;
+        } else if trimmed.starts_with("/*") {
+            in_comment = !trimmed.contains("*/");
+        } else if trimmed.starts_with("'use ") || trimmed.starts_with("\"use ") {
+            if !seen_import {
+                insert_at = line_range.end;
+            }
+        } else if !trimmed.is_empty() && !trimmed.starts_with("//") {
+            break;
+        }
+    }
+    if merge_edit.is_some() {
+        return merge_edit;
+    }
+
+    let clause = if candidate.export.is_default {
+        name.to_string()
+    } else {
+        format!("{{ {name} }}")
+    };
+    let semicolon = if semicolon { ";" } else { "" };
+    let statement = format!(
+        "import {clause} from {quote}{}{quote}{semicolon}",
+        candidate.module
+    );
+    Some(insertion_after(text, insert_at, statement))
+}
+
+static PYTHON_FROM_IMPORT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^from\s+([\w.]+)\s+import\s+(.*)$").unwrap());
+
+fn python_import_edit(
+    text: &str,
+    candidate: &AutoImportCandidate,
+) -> Option<(Range<usize>, String)> {
+    let name = candidate.export.name.as_str();
+    let mut insert_at = 0;
+    let mut in_parentheses = false;
+    let mut docstring_delimiter = None;
+    let mut seen_statement = false;
+    let mut merge_edit = None;
+    for (line_range, line) in lines_with_ranges(text) {
+        let trimmed = line.trim();
+        if in_parentheses {
+            in_parentheses = !trimmed.contains(')');
+            if names_bound(trimmed).any(|bound| bound == name) {
+                return None;
+            }
+            insert_at = line_range.end;
+            continue;
+        }
+        if let Some(delimiter) = docstring_delimiter {
+            if trimmed.contains(delimiter) {
+                docstring_delimiter = None;
+                insert_at = line_range.end;
+            }
+            continue;
+        }
+        if let Some(captures) = PYTHON_FROM_IMPORT.captures(line) {
+            seen_statement = true;
+            let clause = captures[2].trim_end();
+            if names_bound(clause).any(|bound| bound == name) {
+                return None;
+            }
+            in_parentheses = clause.starts_with('(') && !clause.contains(')');
+            if merge_edit.is_none()
+                && &captures[1] == candidate.module.as_str()
+                && !clause.starts_with('(')
+                && !clause.contains(['*', '#', '\\'])
+            {
+                let end = line_range.start + line.trim_end().len();
+                merge_edit = Some((end..end, format!(", {name}")));
+            }
+            insert_at = line_range.end;
+        } else if line.starts_with("import ") {
+            seen_statement = true;
+            if names_bound(&line["import ".len()..]).any(|bound| bound == name) {
+                return None;
+            }
+            insert_at = line_range.end;
+        } else if !seen_statement
+            && let Some(delimiter) = ["\"\"\"", "'''"]
+                .into_iter()
+                .find(|delimiter| trimmed.starts_with(delimiter))
+        {
+            // Imports go after the module's docstring.
+            seen_statement = true;
+            if trimmed[delimiter.len()..].contains(delimiter) {
+                insert_at = line_range.end;
+            } else {
+                docstring_delimiter = Some(delimiter);
+            }
+        } else if trimmed.starts_with('#') {
+            if !seen_statement {
+                insert_at = line_range.end;
+            }
+        } else if !trimmed.is_empty() {
+            break;
+        }
+    }
+    if merge_edit.is_some() {
+        return merge_edit;
+    }
+
+    let statement = format!("from {} import {name}", candidate.module);
+    Some(insertion_after(text, insert_at, statement))
+}
+
+/// Inserts a line at the given offset, which is either 0 or the end of a line.
+fn insertion_after(text: &str, offset: usize, line: String) -> (Range<usize>, String) {
+    if offset > 0 && !text[..offset].ends_with('\n') {
+        (offset..offset, format!("\n{line}"))
+    } else {
+        (offset..offset, format!("{line}\n"))
+    }
+}
+
+/// The lines of the text, with the byte ranges they span including their newline.
+fn lines_with_ranges(text: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start..*offset, line.trim_end_matches(['\n', '\r'])))
+    })
+}
+
+/// The names that an import clause, or a line of one, makes available.
+fn names_bound(clause: &str) -> impl Iterator<Item = &str> {
+    clause
+        .split(|c: char| matches!(c, ',' | '{' | '}' | '(' | ')'))
+        .filter_map(|specifier| {
+            let specifier = specifier.trim();
+            let specifier = specifier.strip_prefix("type ").unwrap_or(specifier);
+            let specifier = specifier.strip_prefix("* as ").unwrap_or(specifier);
+            match specifier.split_once(" as ") {
+                Some((_, alias)) => Some(alias.trim()),
+                None => specifier.split_whitespace().next(),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::rel_path::rel_path;
+
+    fn candidate(name: &str, module: &str, is_default: bool) -> AutoImportCandidate {
+        AutoImportCandidate {
+            export: Export {
+                name: name.to_string(),
+                kind: ExportKind::Function,
+                is_default,
+            },
+            module: module.to_string(),
+        }
+    }
+
+    fn apply(text: &str, syntax: ImportSyntax, candidate: &AutoImportCandidate) -> String {
+        let (range, new_text) = import_edit(text, syntax, candidate).unwrap();
+        let mut text = text.to_string();
+        text.replace_range(range, &new_text);
+        text
+    }
+
+    #[test]
+    fn test_find_exports() {
+        let exports = find_exports(
+            "export function add(a, b) {}\n\
+             export default class Parser {}\n\
+             export const enum Mode {}\n\
+             export interface Options {}\n\
+             export { helper, internal as publicName, default };\n\
+             function notExported() {}\n  export const nested = 1;\n",
+            ImportSyntax::JavaScript,
+        );
+        assert_eq!(
+            exports
+                .iter()
+                .map(|export| (export.name.as_str(), export.kind, export.is_default))
+                .collect::<Vec<_>>(),
+            [
+                ("add", ExportKind::Function, false),
+                ("Parser", ExportKind::Class, true),
+                ("Mode", ExportKind::Enum, false),
+                ("Options", ExportKind::Type, false),
+                ("helper", ExportKind::Variable, false),
+                ("publicName", ExportKind::Variable, false),
+            ]
+        );
+
+        let exports = find_exports(
+            "import os\n\nVERSION = \"1.0\"\nlimit: int = 3\n_private = 1\n\n\
+             async def fetch():\n    inner = 2\n\nclass Client:\n    pass\n\nif VERSION == 1:\n    pass\n",
+            ImportSyntax::Python,
+        );
+        assert_eq!(
+            exports
+                .iter()
+                .map(|export| (export.name.as_str(), export.kind))
+                .collect::<Vec<_>>(),
+            [
+                ("VERSION", ExportKind::Variable),
+                ("limit", ExportKind::Variable),
+                ("fetch", ExportKind::Function),
+                ("Client", ExportKind::Class),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_specifier() {
+        let specifier = |syntax, importer, exporter| {
+            module_specifier(syntax, rel_path(importer), rel_path(exporter))
+        };
+        assert_eq!(
+            specifier(ImportSyntax::JavaScript, "src/app.ts", "src/utils.ts").as_deref(),
+            Some("./utils")
+        );
+        assert_eq!(
+            specifier(
+                ImportSyntax::JavaScript,
+                "src/ui/button.tsx",
+                "src/lib/index.ts"
+            )
+            .as_deref(),
+            Some("../lib")
+        );
+        assert_eq!(
+            specifier(ImportSyntax::JavaScript, "src/app.ts", "types.d.ts").as_deref(),
+            Some("../types")
+        );
+        assert_eq!(
+            specifier(ImportSyntax::Python, "main.py", "src/app/models/user.py").as_deref(),
+            Some("app.models.user")
+        );
+        assert_eq!(
+            specifier(ImportSyntax::Python, "main.py", "app/__init__.py").as_deref(),
+            Some("app")
+        );
+        assert_eq!(
+            specifier(ImportSyntax::Python, "main.py", "my-scripts/run.py"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_javascript_import_edit() {
+        let add = candidate("add", "./math", false);
+        assert_eq!(
+            apply(
+                "'use client';\nimport React from 'react'\n\nconst a = 1;\n",
+                ImportSyntax::JavaScript,
+                &add
+            ),
+            "'use client';\nimport React from 'react'\nimport { add } from './math'\n\nconst a = 1;\n"
+        );
+        assert_eq!(
+            apply(
+                "import { sub } from \"./math\";\nadd(1, 2);\n",
+                ImportSyntax::JavaScript,
+                &add
+            ),
+            "import { sub, add } from \"./math\";\nadd(1, 2);\n"
+        );
+        assert_eq!(
+            apply("// Entry point\nmain();", ImportSyntax::JavaScript, &add),
+            "import { add } from \"./math\";\n// Entry point\nmain();"
+        );
+        assert_eq!(
+            apply(
+                "import {\n  sub,\n} from \"./other\";\n",
+                ImportSyntax::JavaScript,
+                &candidate("Parser", "./parser", true)
+            ),
+            "import {\n  sub,\n} from \"./other\";\nimport Parser from \"./parser\";\n"
+        );
+        assert_eq!(
+            import_edit(
+                "import { add as plus } from \"./math\";\nimport {\n  add,\n} from \"./other\";\n",
+                ImportSyntax::JavaScript,
+                &add
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_python_import_edit() {
+        let fetch = candidate("fetch", "app.net", false);
+        assert_eq!(
+            apply(
+                "\"\"\"The CLI.\"\"\"\n\nimport os\nfrom app.net import get\n\nos.exit()\n",
+                ImportSyntax::Python,
+                &fetch
+            ),
+            "\"\"\"The CLI.\"\"\"\n\nimport os\nfrom app.net import get, fetch\n\nos.exit()\n"
+        );
+        assert_eq!(
+            apply(
+                "#!/usr/bin/env python\n\"\"\"\nThe CLI.\n\"\"\"\nmain()\n",
+                ImportSyntax::Python,
+                &fetch
+            ),
+            "#!/usr/bin/env python\n\"\"\"\nThe CLI.\n\"\"\"\nfrom app.net import fetch\nmain()\n"
+        );
+        assert_eq!(
+            apply(
+                "from app.http import (\n    get,\n)\nimport os",
+                ImportSyntax::Python,
+                &fetch
+            ),
+            "from app.http import (\n    get,\n)\nimport os\nfrom app.net import fetch"
+        );
+        assert_eq!(
+            import_edit(
+                "from app.other import fetch\n",
+                ImportSyntax::Python,
+                &fetch
+            ),
+            None
+        );
+    }
+}
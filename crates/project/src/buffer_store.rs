@@ -23,11 +23,12 @@ use rpc::{
     AnyProtoClient, ErrorCode, ErrorExt as _, TypedEnvelope,
     proto::{self},
 };
+use settings::{Settings as _, SettingsLocation};
 use smol::channel::Receiver;
-use std::{io, pin::pin, sync::Arc, time::Instant};
+use std::{io, path::PathBuf, pin::pin, sync::Arc, time::Instant};
 use text::BufferId;
 use util::{ResultExt as _, TryFutureExt, debug_panic, maybe, rel_path::RelPath};
-use worktree::{File, PathChange, ProjectEntryId, Worktree, WorktreeId};
+use worktree::{File, PathChange, ProjectEntryId, Worktree, WorktreeId, WorktreeSettings};
 
 /// A set of open buffers.
 pub struct BufferStore {
@@ -65,10 +66,40 @@ struct RemoteBufferStore {
 
 struct LocalBufferStore {
     local_buffer_ids_by_entry_id: HashMap<ProjectEntryId, BufferId>,
+    /// Buffers by the canonical path of their file, so that a file opened through a
+    /// symlink or with different casing reuses the buffer that's already open for it.
+    local_buffer_ids_by_canonical_path: HashMap<PathBuf, BufferId>,
+    /// The canonical path of each buffer's file, and the path of the file it was resolved for.
+    canonical_paths_by_buffer_id: HashMap<BufferId, (ProjectPath, PathBuf)>,
+    /// Files that were opened anyway after being detected as too large or binary.
+    paths_exempt_from_heuristics: HashSet<ProjectPath>,
     worktree_store: Entity<WorktreeStore>,
     _subscription: Subscription,
 }
 
+/// Why a file wasn't opened as a buffer, because it's unlikely to be meant for editing as text.
+///
+/// Such files can still be opened after [`BufferStore::skip_file_heuristics`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtectedFileError {
+    TooLarge { size: u64, max_size: u64 },
+    Binary,
+}
+
+impl std::fmt::Display for ProtectedFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLarge { size, max_size } => write!(
+                f,
+                "File is too large to open ({size} bytes, the maximum is {max_size} bytes)"
+            ),
+            Self::Binary => write!(f, "File appears to be binary"),
+        }
+    }
+}
+
+impl std::error::Error for ProtectedFileError {}
+
 enum OpenBuffer {
     Complete { buffer: WeakEntity<Buffer> },
     Operations(Vec<Operation>),
@@ -366,6 +397,81 @@ impl RemoteBufferStore {
 }
 
 impl LocalBufferStore {
+    fn insert_canonical_path(
+        &mut self,
+        buffer_id: BufferId,
+        project_path: ProjectPath,
+        canonical_path: PathBuf,
+    ) {
+        self.remove_canonical_path(buffer_id);
+        self.local_buffer_ids_by_canonical_path
+            .insert(canonical_path.clone(), buffer_id);
+        self.canonical_paths_by_buffer_id
+            .insert(buffer_id, (project_path, canonical_path));
+    }
+
+    fn remove_canonical_path(&mut self, buffer_id: BufferId) {
+        if let Some((_, canonical_path)) = self.canonical_paths_by_buffer_id.remove(&buffer_id)
+            && self.local_buffer_ids_by_canonical_path.get(&canonical_path) == Some(&buffer_id)
+        {
+            self.local_buffer_ids_by_canonical_path
+                .remove(&canonical_path);
+        }
+    }
+
+    /// Resolves the canonical path of a buffer's file again once the file was renamed, deleted
+    /// or recreated, so that the buffer isn't reused for a different file at its old path.
+    fn buffer_file_changed(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<BufferStore>) {
+        let buffer_id = buffer.read(cx).remote_id();
+        let Some(file) = File::from_dyn(buffer.read(cx).file()) else {
+            self.remove_canonical_path(buffer_id);
+            return;
+        };
+        let project_path = ProjectPath {
+            worktree_id: file.worktree_id(cx),
+            path: file.path.clone(),
+        };
+        if file.disk_state == DiskState::Deleted {
+            self.remove_canonical_path(buffer_id);
+            return;
+        }
+        if self
+            .canonical_paths_by_buffer_id
+            .get(&buffer_id)
+            .is_some_and(|(resolved_path, _)| *resolved_path == project_path)
+        {
+            return;
+        }
+        self.remove_canonical_path(buffer_id);
+
+        let Some(local_worktree) = file.worktree.read(cx).as_local() else {
+            return;
+        };
+        let canonical_path = canonical_path_key(
+            local_worktree.fs().clone(),
+            local_worktree.absolutize(&file.path),
+            local_worktree.paths_are_case_insensitive(),
+        );
+        let buffer = buffer.downgrade();
+        cx.spawn(async move |this, cx| {
+            let canonical_path = canonical_path.await?;
+            this.update(cx, |this, cx| {
+                // The file may have changed again while its path was being resolved.
+                let file = File::from_dyn(buffer.upgrade()?.read(cx).file())?;
+                if file.worktree_id(cx) != project_path.worktree_id
+                    || file.path != project_path.path
+                {
+                    return None;
+                }
+                this.as_local_mut()?
+                    .insert_canonical_path(buffer_id, project_path, canonical_path);
+                Some(())
+            })
+            .ok()?
+        })
+        .detach();
+    }
+
     fn save_local_buffer(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -619,22 +725,93 @@ impl LocalBufferStore {
         worktree: Entity<Worktree>,
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<Entity<Buffer>>> {
-        let load_buffer = worktree.update(cx, |worktree, cx| {
-            let load_file = worktree.load_file(path.as_ref(), cx);
-            let reservation = cx.reserve_entity();
-            let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
-            cx.spawn(async move |_, cx| {
-                let loaded = load_file.await?;
-                let text_buffer = cx
-                    .background_spawn(async move { text::Buffer::new(0, buffer_id, loaded.text) })
-                    .await;
-                cx.insert_entity(reservation, |_| {
-                    Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite)
-                })
-            })
+        let canonical_path = worktree.read(cx).as_local().map(|local_worktree| {
+            canonical_path_key(
+                local_worktree.fs().clone(),
+                local_worktree.absolutize(&path),
+                local_worktree.paths_are_case_insensitive(),
+            )
         });
+        let worktree_id = worktree.read(cx).id();
+        let is_exempt_from_heuristics = self.paths_exempt_from_heuristics.contains(&ProjectPath {
+            worktree_id,
+            path: path.clone(),
+        });
+        let heuristics = WorktreeSettings::get(
+            Some(SettingsLocation {
+                worktree_id,
+                path: &path,
+            }),
+            cx,
+        )
+        .file_heuristics
+        .clone();
+        let file_size = worktree
+            .read(cx)
+            .as_local()
+            .filter(|_| !is_exempt_from_heuristics)
+            .map(|local_worktree| {
+                let fs = local_worktree.fs().clone();
+                let abs_path = local_worktree.absolutize(&path);
+                async move { Some(fs.metadata(&abs_path).await.ok()??.len) }
+            });
 
         cx.spawn(async move |this, cx| {
+            let canonical_path = match canonical_path {
+                Some(canonical_path) => canonical_path.await,
+                None => None,
+            };
+            if let Some(canonical_path) = &canonical_path {
+                let existing_buffer = this.read_with(cx, |this, _| {
+                    let buffer_id = this
+                        .as_local()?
+                        .local_buffer_ids_by_canonical_path
+                        .get(canonical_path)?;
+                    this.get(*buffer_id)
+                })?;
+                if let Some(buffer) = existing_buffer {
+                    return Ok(buffer);
+                }
+            }
+
+            if let Some(file_size) = file_size
+                && let Some(size) = file_size.await
+                && heuristics.is_too_large(size)
+            {
+                return Err(anyhow!(ProtectedFileError::TooLarge {
+                    size,
+                    max_size: heuristics.max_file_size,
+                }));
+            }
+
+            let load_path = path.clone();
+            let load_buffer = worktree.update(cx, |worktree, cx| {
+                let load_file = worktree.load_file(path.as_ref(), cx);
+                let reservation = cx.reserve_entity();
+                let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
+                cx.spawn(async move |_, cx| {
+                    let loaded = load_file.await?;
+                    let (text_buffer, is_generated) = cx
+                        .background_spawn(async move {
+                            if !is_exempt_from_heuristics
+                                && heuristics.is_binary(loaded.text.as_bytes())
+                            {
+                                return Err(anyhow!(ProtectedFileError::Binary));
+                            }
+                            let is_generated = heuristics.preview_generated_files
+                                && heuristics.is_generated(&load_path, &loaded.text);
+                            let text_buffer = text::Buffer::new(0, buffer_id, loaded.text);
+                            Ok((text_buffer, is_generated))
+                        })
+                        .await?;
+                    cx.insert_entity(reservation, |_| {
+                        let mut buffer =
+                            Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite);
+                        buffer.set_generated(is_generated);
+                        buffer
+                    })
+                })
+            })?;
             let buffer = match load_buffer.await {
                 Ok(buffer) => Ok(buffer),
                 Err(error) if is_not_found_error(&error) => cx.new(|cx| {
@@ -658,7 +835,19 @@ impl LocalBufferStore {
             this.update(cx, |this, cx| {
                 this.add_buffer(buffer.clone(), cx)?;
                 let buffer_id = buffer.read(cx).remote_id();
+                let mut locked = false;
                 if let Some(file) = File::from_dyn(buffer.read(cx).file()) {
+                    let settings = WorktreeSettings::get(
+                        Some(SettingsLocation {
+                            worktree_id: file.worktree_id(cx),
+                            path: &file.path,
+                        }),
+                        cx,
+                    );
+                    locked = settings.is_path_read_only(&file.path)
+                        || (settings.read_only_outside_project
+                            && !file.worktree.read(cx).is_visible())
+                        || buffer.read(cx).is_generated();
                     this.path_to_buffer_id.insert(
                         ProjectPath {
                             worktree_id: file.worktree_id(cx),
@@ -671,6 +860,19 @@ impl LocalBufferStore {
                         this.local_buffer_ids_by_entry_id
                             .insert(entry_id, buffer_id);
                     }
+                    if let Some(canonical_path) = canonical_path {
+                        this.insert_canonical_path(
+                            buffer_id,
+                            ProjectPath {
+                                worktree_id,
+                                path: file.path.clone(),
+                            },
+                            canonical_path,
+                        );
+                    }
+                }
+                if locked {
+                    buffer.update(cx, |buffer, cx| buffer.set_locked(true, cx));
                 }
 
                 anyhow::Ok(())
@@ -739,6 +941,9 @@ impl BufferStore {
         Self {
             state: BufferStoreState::Local(LocalBufferStore {
                 local_buffer_ids_by_entry_id: Default::default(),
+                local_buffer_ids_by_canonical_path: Default::default(),
+                canonical_paths_by_buffer_id: Default::default(),
+                paths_exempt_from_heuristics: Default::default(),
                 worktree_store: worktree_store.clone(),
                 _subscription: cx.subscribe(&worktree_store, |this, _, event, cx| {
                     if let WorktreeStoreEvent::WorktreeAdded(worktree) = event {
@@ -782,6 +987,13 @@ impl BufferStore {
         }
     }
 
+    fn as_local(&self) -> Option<&LocalBufferStore> {
+        match &self.state {
+            BufferStoreState::Local(state) => Some(state),
+            _ => None,
+        }
+    }
+
     fn as_local_mut(&mut self) -> Option<&mut LocalBufferStore> {
         match &mut self.state {
             BufferStoreState::Local(state) => Some(state),
@@ -858,7 +1070,9 @@ impl BufferStore {
 
         cx.background_spawn(async move {
             task.await.map_err(|e| {
-                if e.error_code() != ErrorCode::Internal {
+                if let Some(error) = e.downcast_ref::<ProtectedFileError>() {
+                    anyhow!(*error)
+                } else if e.error_code() != ErrorCode::Internal {
                     anyhow!(e.error_code())
                 } else {
                     anyhow!("{e}")
@@ -867,6 +1081,13 @@ impl BufferStore {
         })
     }
 
+    /// Opens the file at `project_path` as text from now on, even if it's too large or binary.
+    pub fn skip_file_heuristics(&mut self, project_path: ProjectPath) {
+        if let Some(local) = self.as_local_mut() {
+            local.paths_exempt_from_heuristics.insert(project_path);
+        }
+    }
+
     pub fn create_buffer(
         &mut self,
         project_searchable: bool,
@@ -926,7 +1147,10 @@ impl BufferStore {
         buffer_entity.update(cx, move |_, cx| {
             cx.on_release(move |buffer, cx| {
                 handle
-                    .update(cx, |_, cx| {
+                    .update(cx, |this, cx| {
+                        if let Some(local) = this.as_local_mut() {
+                            local.remove_canonical_path(buffer.remote_id());
+                        }
                         cx.emit(BufferStoreEvent::BufferDropped(buffer.remote_id()))
                     })
                     .ok();
@@ -1159,6 +1383,9 @@ impl BufferStore {
     ) {
         match event {
             BufferEvent::FileHandleChanged => {
+                if let Some(local) = self.as_local_mut() {
+                    local.buffer_file_changed(&buffer, cx);
+                }
                 self.buffer_changed_file(buffer, cx);
             }
             BufferEvent::Reloaded => {
@@ -1727,6 +1954,23 @@ impl OpenBuffer {
     }
 }
 
+/// Resolves the symlinks in a file's path, so that all the paths leading to a file map to the
+/// same key. Returns `None` if the file doesn't exist.
+async fn canonical_path_key(
+    fs: Arc<dyn Fs>,
+    abs_path: PathBuf,
+    case_insensitive: bool,
+) -> Option<PathBuf> {
+    let canonical_path = fs.canonicalize(&abs_path).await.ok()?;
+    if case_insensitive {
+        Some(PathBuf::from(
+            canonical_path.to_string_lossy().to_lowercase(),
+        ))
+    } else {
+        Some(canonical_path)
+    }
+}
+
 fn is_not_found_error(error: &anyhow::Error) -> bool {
     error
         .root_cause()
@@ -5,7 +5,7 @@ use anyhow::{Context as _, Result};
 pub use breakpoints_in_file::{BreakpointSessionState, BreakpointWithPosition};
 use breakpoints_in_file::{BreakpointsInFile, StatefulBreakpoint};
 use collections::{BTreeMap, HashMap};
-use dap::{StackFrameId, client::SessionId};
+use dap::{Capabilities, StackFrameId, client::SessionId};
 use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
 use itertools::Itertools;
 use language::{Buffer, BufferSnapshot, proto::serialize_anchor as serialize_text_anchor};
@@ -1016,6 +1016,36 @@ pub struct SourceBreakpoint {
     pub state: BreakpointState,
 }
 
+impl SourceBreakpoint {
+    /// Converts the breakpoint for an adapter with the given capabilities, dropping the
+    /// condition, hit condition and log message if the adapter doesn't support them.
+    pub fn to_dap(self, capabilities: &Capabilities) -> dap::SourceBreakpoint {
+        let mut breakpoint = dap::SourceBreakpoint::from(self);
+        if breakpoint.condition.is_some()
+            && !capabilities
+                .supports_conditional_breakpoints
+                .unwrap_or_default()
+        {
+            log::warn!("Debug adapter doesn't support conditional breakpoints, ignoring condition");
+            breakpoint.condition = None;
+        }
+        if breakpoint.hit_condition.is_some()
+            && !capabilities
+                .supports_hit_conditional_breakpoints
+                .unwrap_or_default()
+        {
+            log::warn!("Debug adapter doesn't support hit counts, ignoring hit condition");
+            breakpoint.hit_condition = None;
+        }
+        if breakpoint.log_message.is_some() && !capabilities.supports_log_points.unwrap_or_default()
+        {
+            log::warn!("Debug adapter doesn't support log points, ignoring log message");
+            breakpoint.log_message = None;
+        }
+        breakpoint
+    }
+}
+
 impl From<SourceBreakpoint> for dap::SourceBreakpoint {
     fn from(bp: SourceBreakpoint) -> Self {
         Self {
@@ -109,6 +109,7 @@ impl DapLocator for CargoLocator {
             }),
             config,
             tcp_connection: None,
+            start_with: Vec::new(),
         })
     }
 
@@ -187,6 +187,7 @@ impl DapLocator for GoLocator {
                     build: None,
                     config,
                     tcp_connection: None,
+                    start_with: Vec::new(),
                 })
             }
             "run" => {
@@ -231,6 +232,7 @@ impl DapLocator for GoLocator {
                     build: None,
                     config,
                     tcp_connection: None,
+                    start_with: Vec::new(),
                 })
             }
             _ => None,
@@ -246,7 +248,10 @@ impl DapLocator for GoLocator {
 mod tests {
     use super::*;
     use gpui::TestAppContext;
-    use task::{HideStrategy, RevealStrategy, RevealTarget, Shell, TaskTemplate};
+    use task::{
+        DependsOrder, HideStrategy, NotifyStrategy, RevealStrategy, RevealTarget, Shell,
+        TaskTemplate,
+    };
 
     #[gpui::test]
     async fn test_create_scenario_for_go_build(_: &mut TestAppContext) {
@@ -256,6 +261,8 @@ mod tests {
             command: "go".into(),
             args: vec!["build".into(), ".".into()],
             env: Default::default(),
+            env_files: vec![],
+            secrets: Default::default(),
             cwd: Some("${ZED_WORKTREE_ROOT}".into()),
             use_new_terminal: false,
             allow_concurrent_runs: false,
@@ -266,6 +273,12 @@ mod tests {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            problem_matchers: vec![],
+            notify: NotifyStrategy::Never,
+            depends_on: vec![],
+            depends_order: DependsOrder::Sequence,
+            watch: None,
+            inputs: vec![],
         };
 
         let scenario = locator
@@ -283,6 +296,8 @@ mod tests {
             command: "cargo".into(),
             args: vec!["build".into()],
             env: Default::default(),
+            env_files: vec![],
+            secrets: Default::default(),
             cwd: Some("${ZED_WORKTREE_ROOT}".into()),
             use_new_terminal: false,
             allow_concurrent_runs: false,
@@ -293,6 +308,12 @@ mod tests {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            problem_matchers: vec![],
+            notify: NotifyStrategy::Never,
+            depends_on: vec![],
+            depends_order: DependsOrder::Sequence,
+            watch: None,
+            inputs: vec![],
         };
 
         let scenario = locator
@@ -421,6 +442,8 @@ mod tests {
             command: "go".into(),
             args: vec!["clean".into()],
             env: Default::default(),
+            env_files: vec![],
+            secrets: Default::default(),
             cwd: Some("${ZED_WORKTREE_ROOT}".into()),
             use_new_terminal: false,
             allow_concurrent_runs: false,
@@ -431,6 +454,12 @@ mod tests {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            problem_matchers: vec![],
+            notify: NotifyStrategy::Never,
+            depends_on: vec![],
+            depends_order: DependsOrder::Sequence,
+            watch: None,
+            inputs: vec![],
         };
 
         let scenario = locator
@@ -53,6 +53,7 @@ impl DapLocator for NodeLocator {
             build: None,
             config,
             tcp_connection: None,
+            start_with: Vec::new(),
         })
     }
 
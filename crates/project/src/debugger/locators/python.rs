@@ -87,6 +87,7 @@ impl DapLocator for PythonLocator {
             build: None,
             config,
             tcp_connection: None,
+            start_with: Vec::new(),
         })
     }
 
@@ -109,6 +110,8 @@ mod test {
             command: "$ZED_CUSTOM_PYTHON_ACTIVE_ZED_TOOLCHAIN".into(),
             args: vec!["-m".into(), "$ZED_CUSTOM_PYTHON_MODULE_NAME".into()],
             env: Default::default(),
+            env_files: vec![],
+            secrets: Default::default(),
             cwd: Some("$ZED_WORKTREE_ROOT".into()),
             use_new_terminal: false,
             allow_concurrent_runs: false,
@@ -119,6 +122,12 @@ mod test {
             shell: task::Shell::System,
             show_summary: false,
             show_command: false,
+            problem_matchers: vec![],
+            notify: task::NotifyStrategy::Never,
+            depends_on: vec![],
+            depends_order: task::DependsOrder::Sequence,
+            watch: None,
+            inputs: vec![],
         };
 
         let expected_scenario = DebugScenario {
@@ -133,6 +142,7 @@ mod test {
                 "module": "$ZED_CUSTOM_PYTHON_MODULE_NAME",
             }),
             tcp_connection: None,
+            start_with: Vec::new(),
         };
 
         assert_eq!(
@@ -6,12 +6,13 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
-    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
-    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, Initialize, Launch,
+    LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand, NextCommand,
+    PauseCommand, RestartCommand, RestartStackFrameCommand, ScopesCommand,
+    SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
+    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
+    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
@@ -254,6 +255,7 @@ impl RunningMode {
         abs_path: Arc<Path>,
         reason: BreakpointUpdatedReason,
         breakpoint_store: &Entity<BreakpointStore>,
+        capabilities: &Capabilities,
         cx: &mut App,
     ) -> Task<()> {
         let breakpoints =
@@ -265,7 +267,7 @@ impl RunningMode {
                 .chain(self.tmp_breakpoint.iter().filter_map(|breakpoint| {
                     breakpoint.path.eq(&abs_path).then(|| breakpoint.clone())
                 }))
-                .map(Into::into)
+                .map(|breakpoint| breakpoint.to_dap(capabilities))
                 .collect();
 
         let raw_breakpoints = breakpoint_store
@@ -335,6 +337,7 @@ impl RunningMode {
         &self,
         ignore_breakpoints: bool,
         breakpoint_store: &Entity<BreakpointStore>,
+        capabilities: &Capabilities,
         cx: &App,
     ) -> Task<HashMap<Arc<Path>, anyhow::Error>> {
         let mut breakpoint_tasks = Vec::new();
@@ -349,7 +352,7 @@ impl RunningMode {
                 breakpoints
                     .into_iter()
                     .filter(|bp| bp.state.is_enabled())
-                    .map(Into::into)
+                    .map(|bp| bp.to_dap(capabilities))
                     .collect()
             };
 
@@ -442,6 +445,7 @@ impl RunningMode {
             .exception_breakpoint_filters
             .clone()
             .unwrap_or_default();
+        let capabilities = capabilities.clone();
         let configuration_sequence = cx.spawn({
             async move |session, cx| {
                 let adapter_name = session.read_with(cx, |this, _| this.adapter())?;
@@ -454,7 +458,9 @@ impl RunningMode {
                     })?;
                 initialized_rx.await?;
                 let errors_by_path = cx
-                    .update(|cx| this.send_source_breakpoints(false, &breakpoint_store, cx))?
+                    .update(|cx| {
+                        this.send_source_breakpoints(false, &breakpoint_store, &capabilities, cx)
+                    })?
                     .await;
 
                 dap_store.update(cx, |_, cx| {
@@ -679,6 +685,9 @@ pub struct Session {
     loaded_sources: Vec<dap::Source>,
     output_token: OutputToken,
     output: Box<circular_buffer::CircularBuffer<MAX_TRACKED_OUTPUT_EVENTS, dap::OutputEvent>>,
+    /// The expressions evaluated from the console whose results are structured objects,
+    /// by the output token of the result's output event.
+    evaluated_expressions: BTreeMap<OutputToken, SharedString>,
     threads: IndexMap<ThreadId, Thread>,
     thread_states: ThreadStates,
     watchers: HashMap<SharedString, Watcher>,
@@ -773,6 +782,8 @@ pub enum SessionEvent {
     Modules,
     LoadedSources,
     Stopped(Option<ThreadId>),
+    /// The debuggee stopped because it hit a breakpoint.
+    BreakpointHit,
     StackTrace,
     Variables,
     Watchers,
@@ -817,12 +828,19 @@ impl Session {
         cx.new::<Self>(|cx| {
             cx.subscribe(&breakpoint_store, |this, store, event, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(path, reason) => {
+                    let capabilities = this.capabilities.clone();
                     if let Some(local) = (!this.ignore_breakpoints)
                         .then(|| this.as_running_mut())
                         .flatten()
                     {
                         local
-                            .send_breakpoints_from_path(path.clone(), *reason, &store, cx)
+                            .send_breakpoints_from_path(
+                                path.clone(),
+                                *reason,
+                                &store,
+                                &capabilities,
+                                cx,
+                            )
                             .detach();
                     };
                 }
@@ -850,6 +868,7 @@ impl Session {
                 thread_states: ThreadStates::default(),
                 output_token: OutputToken(0),
                 output: circular_buffer::CircularBuffer::boxed(),
+                evaluated_expressions: BTreeMap::default(),
                 requests: HashMap::default(),
                 modules: Vec::default(),
                 loaded_sources: Vec::default(),
@@ -1324,6 +1343,7 @@ impl Session {
                     path,
                     BreakpointUpdatedReason::Toggled,
                     &self.breakpoint_store,
+                    &self.capabilities,
                     cx,
                 );
 
@@ -1390,8 +1410,10 @@ impl Session {
 
     fn handle_stopped_event(&mut self, event: StoppedEvent, cx: &mut Context<Self>) {
         self.mode.stopped();
+        let hit_breakpoint = matches!(event.reason, dap::StoppedEventReason::Breakpoint);
         // todo(debugger): Find a clean way to get around the clone
         let breakpoint_store = self.breakpoint_store.clone();
+        let capabilities = self.capabilities.clone();
         if let Some((local, path)) = self.as_running_mut().and_then(|local| {
             let breakpoint = local.tmp_breakpoint.take()?;
             let path = breakpoint.path;
@@ -1402,6 +1424,7 @@ impl Session {
                     path,
                     BreakpointUpdatedReason::Toggled,
                     &breakpoint_store,
+                    &capabilities,
                     cx,
                 )
                 .detach();
@@ -1436,6 +1459,9 @@ impl Session {
                 .map(Into::into)
                 .filter(|_| !event.preserve_focus_hint.unwrap_or(false)),
         ));
+        if hit_breakpoint {
+            cx.emit(SessionEvent::BreakpointHit);
+        }
         cx.emit(SessionEvent::InvalidateInlineValue);
         cx.notify();
     }
@@ -1673,6 +1699,7 @@ impl Session {
         self.invalidate_command_type::<ThreadsCommand>();
         self.invalidate_command_type::<DataBreakpointInfoCommand>();
         self.invalidate_command_type::<ReadMemory>();
+        self.invalidate_command_type::<DisassembleCommand>();
         let executor = self.as_running().map(|running| running.executor.clone());
         if let Some(executor) = executor {
             self.memory.clear(&executor);
@@ -1692,6 +1719,12 @@ impl Session {
         self.output_token.0 += 1;
     }
 
+    /// Returns the expression that was evaluated to produce the output event with the given
+    /// token, if the event is the result of an evaluation and that result can be inspected.
+    pub fn evaluated_expression(&self, token: OutputToken) -> Option<SharedString> {
+        self.evaluated_expressions.get(&token).cloned()
+    }
+
     pub fn any_stopped_thread(&self) -> bool {
         self.thread_states.any_stopped_thread()
     }
@@ -1903,7 +1936,7 @@ impl Session {
         self.ignore_breakpoints = ignore;
 
         if let Some(local) = self.as_running() {
-            local.send_source_breakpoints(ignore, &self.breakpoint_store, cx)
+            local.send_source_breakpoints(ignore, &self.breakpoint_store, &self.capabilities, cx)
         } else {
             // todo(debugger): We need to propagate this change to downstream sessions and send a message to upstream sessions
             unimplemented!()
@@ -2501,6 +2534,50 @@ impl Session {
         })
     }
 
+    /// Evaluates `expression` for an editor hover, without echoing it to the console.
+    /// Resolves to the value and, for structured values, their fields.
+    pub fn evaluate_hover(
+        &mut self,
+        expression: String,
+        frame_id: StackFrameId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<(dap::EvaluateResponse, Vec<dap::Variable>)>> {
+        if !self
+            .capabilities
+            .supports_evaluate_for_hovers
+            .unwrap_or_default()
+        {
+            return Task::ready(Err(anyhow!(
+                "debug adapter doesn't support evaluating hovers"
+            )));
+        }
+
+        let request = self.mode.request_dap(EvaluateCommand {
+            expression,
+            context: Some(EvaluateArgumentsContext::Hover),
+            frame_id: Some(frame_id),
+            source: None,
+        });
+        cx.spawn(async move |this, cx| {
+            let response = request.await?;
+            if response.variables_reference == 0 {
+                return Ok((response, Vec::new()));
+            }
+            let fields = this
+                .update(cx, |this, _| {
+                    this.mode.request_dap(VariablesCommand {
+                        variables_reference: response.variables_reference,
+                        filter: None,
+                        start: None,
+                        count: None,
+                        format: None,
+                    })
+                })?
+                .await?;
+            Ok((response, fields))
+        })
+    }
+
     pub fn refresh_watchers(&mut self, frame_id: u64, cx: &mut Context<Self>) {
         let watches = self.watchers.clone();
         for (_, watch) in watches.into_iter() {
@@ -2558,6 +2635,24 @@ impl Session {
         self.request(command, |_, response, _| response.ok(), cx)
     }
 
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// away from the one at `memory_reference`.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Arc<[dap::DisassembledInstruction]>>> {
+        let command = DisassembleCommand {
+            memory_reference,
+            instruction_offset,
+            instruction_count,
+        };
+
+        self.request(command, |_, response, _| response.log_err(), cx)
+    }
+
     pub fn set_variable_value(
         &mut self,
         stack_frame_id: u64,
@@ -2608,6 +2703,7 @@ impl Session {
             location_reference: None,
         };
         self.push_output(event);
+        let evaluated_expression = SharedString::from(expression.clone());
         let request = self.mode.request_dap(EvaluateCommand {
             expression,
             context,
@@ -2633,6 +2729,17 @@ impl Session {
                             location_reference: None,
                         };
                         this.push_output(event);
+                        if response.variables_reference > 0 {
+                            this.evaluated_expressions
+                                .insert(this.output_token, evaluated_expression);
+                            let oldest_tracked_token = OutputToken(
+                                this.output_token
+                                    .0
+                                    .saturating_sub(MAX_TRACKED_OUTPUT_EVENTS),
+                            );
+                            this.evaluated_expressions
+                                .retain(|token, _| *token > oldest_tracked_token);
+                        }
                     }
                     Err(e) => {
                         let event = dap::OutputEvent {
@@ -0,0 +1,441 @@
+//! Tasks detected in the package manifests and build files of a worktree, so that
+//! their scripts, targets and recipes can be run without writing a `tasks.json`.
+
+use std::path::Path;
+
+use collections::HashSet;
+use serde::Deserialize;
+use task::TaskTemplate;
+use util::{ResultExt as _, rel_path::RelPath};
+
+/// A kind of file that tasks are detected in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ManifestKind {
+    PackageJson,
+    CargoToml,
+    Makefile,
+    Justfile,
+}
+
+impl ManifestKind {
+    pub(crate) fn for_path(path: &RelPath) -> Option<Self> {
+        match path.file_name()? {
+            "package.json" => Some(Self::PackageJson),
+            "Cargo.toml" => Some(Self::CargoToml),
+            "Makefile" | "makefile" | "GNUmakefile" => Some(Self::Makefile),
+            "justfile" | "Justfile" | ".justfile" => Some(Self::Justfile),
+            _ => None,
+        }
+    }
+}
+
+/// The directory a manifest was found in.
+pub(crate) struct ManifestDirectory<'a> {
+    /// The directory relative to the worktree root, empty for the root itself.
+    pub path: &'a RelPath,
+    /// The absolute path of the directory, which the detected tasks run in.
+    pub abs_path: &'a Path,
+    /// Paths of the files next to the manifest, and of those in the `src`, `src/bin`
+    /// and `examples` directories, relative to the manifest's directory.
+    pub files: HashSet<String>,
+}
+
+/// Detects the tasks defined by the manifest of the given `kind` with `content`.
+pub(crate) fn detect_tasks(
+    kind: ManifestKind,
+    content: &str,
+    directory: &ManifestDirectory,
+) -> Vec<TaskTemplate> {
+    let commands = match kind {
+        ManifestKind::PackageJson => package_json_commands(content, directory),
+        ManifestKind::CargoToml => cargo_commands(content, directory),
+        ManifestKind::Makefile => makefile_commands(content),
+        ManifestKind::Justfile => justfile_commands(content),
+    };
+
+    let cwd = directory.abs_path.to_string_lossy().into_owned();
+    commands
+        .into_iter()
+        .map(|(command, args)| {
+            let mut label = std::iter::once(command.as_str())
+                .chain(args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !directory.path.is_empty() {
+                label.push_str(&format!(" ({})", directory.path.as_unix_str()));
+            }
+            TaskTemplate {
+                label,
+                command,
+                args,
+                cwd: Some(cwd.clone()),
+                ..TaskTemplate::default()
+            }
+        })
+        .collect()
+}
+
+fn package_json_commands(
+    content: &str,
+    directory: &ManifestDirectory,
+) -> Vec<(String, Vec<String>)> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PackageJson {
+        #[serde(default)]
+        scripts: serde_json::Map<String, serde_json::Value>,
+        package_manager: Option<String>,
+    }
+
+    let Some(package_json) = serde_json::from_str::<PackageJson>(content).log_err() else {
+        return Vec::new();
+    };
+    let package_manager = package_json
+        .package_manager
+        .as_deref()
+        .and_then(|package_manager| package_manager.split('@').next())
+        .filter(|package_manager| ["npm", "pnpm", "yarn", "bun"].contains(package_manager))
+        .or_else(|| {
+            [
+                ("pnpm-lock.yaml", "pnpm"),
+                ("yarn.lock", "yarn"),
+                ("bun.lock", "bun"),
+                ("bun.lockb", "bun"),
+            ]
+            .into_iter()
+            .find(|(lockfile, _)| directory.files.contains(*lockfile))
+            .map(|(_, package_manager)| package_manager)
+        })
+        .unwrap_or("npm");
+
+    package_json
+        .scripts
+        .keys()
+        .map(|script| {
+            (
+                package_manager.to_string(),
+                vec!["run".to_string(), script.clone()],
+            )
+        })
+        .collect()
+}
+
+fn cargo_commands(content: &str, directory: &ManifestDirectory) -> Vec<(String, Vec<String>)> {
+    #[derive(Deserialize)]
+    struct CargoToml {
+        package: Option<Package>,
+        workspace: Option<toml::Value>,
+        #[serde(default)]
+        bin: Vec<Target>,
+        #[serde(default)]
+        example: Vec<Target>,
+    }
+
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Target {
+        name: Option<String>,
+    }
+
+    let Some(manifest) = toml::from_str::<CargoToml>(content).log_err() else {
+        return Vec::new();
+    };
+    let Some(package) = manifest.package else {
+        if manifest.workspace.is_some() {
+            return vec![
+                cargo(["build", "--workspace"]),
+                cargo(["test", "--workspace"]),
+            ];
+        }
+        return Vec::new();
+    };
+
+    let mut bins = manifest
+        .bin
+        .into_iter()
+        .filter_map(|target| target.name)
+        .collect::<Vec<_>>();
+    if directory.files.contains("src/main.rs") {
+        bins.push(package.name.clone());
+    }
+    bins.extend(rust_file_targets(&directory.files, "src/bin/"));
+
+    let mut examples = manifest
+        .example
+        .into_iter()
+        .filter_map(|target| target.name)
+        .collect::<Vec<_>>();
+    examples.extend(rust_file_targets(&directory.files, "examples/"));
+
+    let mut commands = vec![cargo(["build"]), cargo(["test"])];
+    let mut seen = HashSet::default();
+    bins.retain(|bin| seen.insert(bin.clone()));
+    match bins.as_slice() {
+        [] => {}
+        [_] => commands.push(cargo(["run"])),
+        bins => commands.extend(bins.iter().map(|bin| cargo(["run", "--bin", bin.as_str()]))),
+    }
+    let mut seen = HashSet::default();
+    commands.extend(
+        examples
+            .iter()
+            .filter(|example| seen.insert(example.as_str()))
+            .map(|example| cargo(["run", "--example", example.as_str()])),
+    );
+    commands
+}
+
+fn cargo<'a>(args: impl IntoIterator<Item = &'a str>) -> (String, Vec<String>) {
+    (
+        "cargo".to_string(),
+        args.into_iter().map(ToOwned::to_owned).collect(),
+    )
+}
+
+/// Names of the targets Cargo discovers in `directory`: `name.rs` files, and
+/// `name/main.rs` files in subdirectories.
+fn rust_file_targets(files: &HashSet<String>, directory: &str) -> Vec<String> {
+    let mut targets = files
+        .iter()
+        .filter_map(|file| {
+            let file = file.strip_prefix(directory)?;
+            match file.split_once('/') {
+                Some((name, "main.rs")) => Some(name.to_string()),
+                Some(_) => None,
+                None => file.strip_suffix(".rs").map(ToOwned::to_owned),
+            }
+        })
+        .collect::<Vec<_>>();
+    targets.sort();
+    targets
+}
+
+/// Finds the explicit targets of a Makefile, skipping special targets, pattern
+/// rules and targets that name files.
+fn makefile_commands(content: &str) -> Vec<(String, Vec<String>)> {
+    let mut seen = HashSet::default();
+    let mut commands = Vec::new();
+    for line in content.lines() {
+        if line.starts_with(['\t', ' ', '#']) {
+            continue;
+        }
+        let Some((targets, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') || targets.contains(['=', '$', '%', '(']) {
+            continue;
+        }
+        for target in targets.split_whitespace() {
+            if target.starts_with('.') || target.contains(['/', '.']) {
+                continue;
+            }
+            if seen.insert(target) {
+                commands.push(("make".to_string(), vec![target.to_string()]));
+            }
+        }
+    }
+    commands
+}
+
+/// Finds the public recipes of a justfile that can run without arguments.
+fn justfile_commands(content: &str) -> Vec<(String, Vec<String>)> {
+    let mut commands = Vec::new();
+    for line in content.lines() {
+        if line.starts_with([' ', '\t', '#', '[']) {
+            continue;
+        }
+        let line = line.strip_prefix('@').unwrap_or(line);
+        let name_end = line
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(line.len());
+        let (name, rest) = line.split_at(name_end);
+        if name.is_empty()
+            || name.starts_with('_')
+            || ["set", "alias", "export", "import", "mod"].contains(&name)
+        {
+            continue;
+        }
+        let Some((parameters, body)) = rest.split_once(':') else {
+            continue;
+        };
+        if body.starts_with('=') {
+            continue;
+        }
+        let has_required_parameter = parameters.split_whitespace().any(|parameter| {
+            let parameter = parameter.trim_start_matches('$');
+            !parameter.contains('=')
+                && !parameter.starts_with('*')
+                && parameter.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '+')
+        });
+        if !has_required_parameter {
+            commands.push(("just".to_string(), vec![name.to_string()]));
+        }
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(
+        kind: ManifestKind,
+        content: &str,
+        path: &str,
+        files: &[&str],
+    ) -> Vec<(String, Option<String>)> {
+        let directory = ManifestDirectory {
+            path: RelPath::unix(path).unwrap(),
+            abs_path: Path::new("/project"),
+            files: files.iter().map(|file| file.to_string()).collect(),
+        };
+        detect_tasks(kind, content, &directory)
+            .into_iter()
+            .map(|template| (template.label, template.cwd))
+            .collect()
+    }
+
+    fn without_cwd(labels: Vec<(String, Option<String>)>) -> Vec<String> {
+        labels.into_iter().map(|(label, _)| label).collect()
+    }
+
+    #[test]
+    fn test_package_json_scripts() {
+        let content =
+            r#"{ "name": "web", "scripts": { "build": "vite build", "test": "vitest" } }"#;
+        assert_eq!(
+            labels(ManifestKind::PackageJson, content, "packages/web", &[]),
+            vec![
+                (
+                    "npm run build (packages/web)".to_string(),
+                    Some("/project".to_string())
+                ),
+                (
+                    "npm run test (packages/web)".to_string(),
+                    Some("/project".to_string())
+                ),
+            ]
+        );
+        assert_eq!(
+            without_cwd(labels(
+                ManifestKind::PackageJson,
+                content,
+                "",
+                &["pnpm-lock.yaml"]
+            )),
+            vec!["pnpm run build", "pnpm run test"]
+        );
+        assert_eq!(
+            without_cwd(labels(
+                ManifestKind::PackageJson,
+                r#"{ "packageManager": "yarn@4.1.0", "scripts": { "dev": "vite" } }"#,
+                "",
+                &["pnpm-lock.yaml"]
+            )),
+            vec!["yarn run dev"]
+        );
+    }
+
+    #[test]
+    fn test_cargo_targets() {
+        let content = r#"
+            [package]
+            name = "app"
+
+            [[bin]]
+            name = "tool"
+            path = "tools/tool.rs"
+
+            [[example]]
+            name = "custom"
+            path = "demos/custom.rs"
+        "#;
+        assert_eq!(
+            without_cwd(labels(
+                ManifestKind::CargoToml,
+                content,
+                "",
+                &[
+                    "src/main.rs",
+                    "src/lib.rs",
+                    "examples/basic.rs",
+                    "examples/multi/main.rs",
+                    "examples/multi/util.rs",
+                ]
+            )),
+            vec![
+                "cargo build",
+                "cargo test",
+                "cargo run --bin tool",
+                "cargo run --bin app",
+                "cargo run --example custom",
+                "cargo run --example basic",
+                "cargo run --example multi",
+            ]
+        );
+        assert_eq!(
+            without_cwd(labels(
+                ManifestKind::CargoToml,
+                "[workspace]\nmembers = [\"crates/*\"]",
+                "",
+                &[]
+            )),
+            vec!["cargo build --workspace", "cargo test --workspace"]
+        );
+    }
+
+    #[test]
+    fn test_makefile_targets() {
+        let content = "\
+CC := gcc
+CFLAGS = -O2
+.PHONY: all clean
+all: app
+app: main.o
+\t$(CC) -o app main.o
+main.o: main.c
+%.o: %.c
+build/out: all
+clean install:
+\trm -f app
+";
+        assert_eq!(
+            without_cwd(labels(ManifestKind::Makefile, content, "", &[])),
+            vec!["make all", "make app", "make clean", "make install"]
+        );
+    }
+
+    #[test]
+    fn test_justfile_recipes() {
+        let content = "\
+set shell := [\"bash\", \"-c\"]
+version := \"1.0\"
+alias b := build
+
+# Build everything
+build:
+    cargo build
+
+[private]
+_helper:
+    echo helper
+
+@test filter='':
+    cargo test {{filter}}
+
+deploy target:
+    ./deploy {{target}}
+
+lint *args:
+    cargo clippy {{args}}
+";
+        assert_eq!(
+            without_cwd(labels(ManifestKind::Justfile, content, "", &[])),
+            vec!["just build", "just test", "just lint"]
+        );
+    }
+}
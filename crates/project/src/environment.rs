@@ -2,12 +2,12 @@ use futures::{FutureExt, future::Shared};
 use language::Buffer;
 use std::{path::Path, sync::Arc};
 use task::Shell;
-use util::ResultExt;
+use util::{ResultExt, rel_path::RelPath};
 use worktree::Worktree;
 
-use collections::HashMap;
+use collections::{BTreeMap, HashMap};
 use gpui::{AppContext as _, Context, Entity, EventEmitter, Task};
-use settings::Settings as _;
+use settings::{Settings as _, SettingsLocation};
 
 use crate::{
     project_settings::{DirenvSettings, ProjectSettings},
@@ -88,10 +88,40 @@ impl ProjectEnvironment {
         self.get_worktree_environment(worktree, cx)
     }
 
+    /// Returns the environment for processes started in the worktree, with the
+    /// worktree's `project_env` setting applied on top.
     pub fn get_worktree_environment(
         &mut self,
         worktree: Entity<Worktree>,
         cx: &mut Context<Self>,
+    ) -> Shared<Task<Option<HashMap<String, String>>>> {
+        let project_env = ProjectSettings::get(
+            Some(SettingsLocation {
+                worktree_id: worktree.read(cx).id(),
+                path: RelPath::empty(),
+            }),
+            cx,
+        )
+        .project_env
+        .clone();
+        let worktree_root = worktree.read(cx).abs_path();
+        let environment = self.get_worktree_base_environment(worktree, cx);
+        if project_env.is_empty() {
+            return environment;
+        }
+
+        cx.background_spawn(async move {
+            let mut environment = environment.await?;
+            apply_project_env(&mut environment, &project_env, &worktree_root);
+            Some(environment)
+        })
+        .shared()
+    }
+
+    fn get_worktree_base_environment(
+        &mut self,
+        worktree: Entity<Worktree>,
+        cx: &mut Context<Self>,
     ) -> Shared<Task<Option<HashMap<String, String>>>> {
         if cfg!(any(test, feature = "test-support")) {
             return Task::ready(Some(HashMap::default())).shared();
@@ -158,6 +188,27 @@ impl ProjectEnvironment {
     }
 }
 
+/// Sets the variables from the `project_env` setting, expanding references to
+/// variables that are already set, including `ZED_WORKTREE_ROOT`.
+fn apply_project_env(
+    env: &mut HashMap<String, String>,
+    project_env: &BTreeMap<String, String>,
+    worktree_root: &Path,
+) {
+    let worktree_root = worktree_root.to_string_lossy().into_owned();
+    for (key, value) in project_env {
+        let value = shellexpand::env_with_context_no_errors(value, |var: &str| {
+            if var == "ZED_WORKTREE_ROOT" {
+                Some(worktree_root.clone())
+            } else {
+                env.get(var).cloned()
+            }
+        })
+        .into_owned();
+        env.insert(key.clone(), value);
+    }
+}
+
 fn set_origin_marker(env: &mut HashMap<String, String>, origin: EnvironmentOrigin) {
     env.insert(ZED_ENVIRONMENT_ORIGIN_MARKER.to_string(), origin.into());
 }
@@ -198,6 +249,7 @@ async fn load_directory_shell_environment(
     shell: &Shell,
     abs_path: &Path,
     load_direnv: &DirenvSettings,
+    load_mise: bool,
 ) -> (
     Option<HashMap<String, String>>,
     Option<EnvironmentErrorMessage>,
@@ -218,7 +270,7 @@ async fn load_directory_shell_environment(
                 );
             };
 
-            load_shell_environment(shell, dir, load_direnv).await
+            load_shell_environment(shell, dir, load_direnv, load_mise).await
         }
         Err(err) => (
             None,
@@ -235,6 +287,7 @@ async fn load_shell_environment(
     shell: &Shell,
     dir: &Path,
     load_direnv: &DirenvSettings,
+    load_mise: bool,
 ) -> (
     Option<HashMap<String, String>>,
     Option<EnvironmentErrorMessage>,
@@ -249,7 +302,7 @@ async fn load_shell_environment(
         (Some(fake_env), None)
     } else if cfg!(target_os = "windows",) {
         let (shell, args) = shell.program_and_args();
-        let envs = match shell_env::capture(shell, args, dir).await {
+        let mut envs = match shell_env::capture(shell, args, dir).await {
             Ok(envs) => envs,
             Err(err) => {
                 util::log_err(&err);
@@ -264,8 +317,13 @@ async fn load_shell_environment(
         };
 
         // Note: direnv is not available on Windows, so we skip direnv processing
-        // and just return the shell environment
-        (Some(envs), None)
+        let mise_error = if load_mise {
+            apply_mise_environment(&mut envs, dir).await
+        } else {
+            None
+        };
+
+        (Some(envs), mise_error)
     } else {
         let dir_ = dir.to_owned();
         let (shell, args) = shell.program_and_args();
@@ -303,7 +361,27 @@ async fn load_shell_environment(
             }
         }
 
-        (Some(envs), direnv_error)
+        // mise runs after direnv, so that tools pinned by mise take precedence.
+        let mise_error = if load_mise {
+            apply_mise_environment(&mut envs, dir).await
+        } else {
+            None
+        };
+
+        (Some(envs), direnv_error.or(mise_error))
+    }
+}
+
+async fn apply_mise_environment(
+    envs: &mut HashMap<String, String>,
+    dir: &Path,
+) -> Option<EnvironmentErrorMessage> {
+    match crate::mise::load_mise_environment(envs, dir).await {
+        Ok(mise_environment) => {
+            envs.extend(mise_environment);
+            None
+        }
+        Err(err) => err,
     }
 }
 
@@ -313,6 +391,7 @@ fn get_directory_env_impl(
     cx: &Context<ProjectEnvironment>,
 ) -> Task<Option<HashMap<String, String>>> {
     let load_direnv = ProjectSettings::get_global(cx).load_direnv.clone();
+    let load_mise = ProjectSettings::get_global(cx).load_mise;
 
     let shell = shell.clone();
     cx.spawn(async move |this, cx| {
@@ -320,7 +399,8 @@ fn get_directory_env_impl(
             .background_spawn({
                 let abs_path = abs_path.clone();
                 async move {
-                    load_directory_shell_environment(&shell, &abs_path, &load_direnv).await
+                    load_directory_shell_environment(&shell, &abs_path, &load_direnv, load_mise)
+                        .await
                 }
             })
             .await;
@@ -3552,6 +3552,23 @@ impl Repository {
         })
     }
 
+    /// Loads the bytes of a file as it is in HEAD, such as an image, or `None` if it isn't in HEAD.
+    pub fn load_committed_bytes(
+        &mut self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<Option<Vec<u8>>>> {
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => {
+                    Ok(backend.load_committed_bytes(path).await)
+                }
+                RepositoryState::Remote { .. } => {
+                    bail!("loading committed files isn't supported in remote projects yet")
+                }
+            }
+        })
+    }
+
     pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
         let id = self.id;
         self.send_job(None, move |git_repo, cx| async move {
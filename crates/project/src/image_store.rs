@@ -1,20 +1,28 @@
+mod embedded_metadata;
+
 use crate::{
     Project, ProjectEntryId, ProjectItem, ProjectPath,
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet, hash_map};
+pub use embedded_metadata::{EmbeddedMetadata, GpsPosition, Rational};
 use futures::{StreamExt, channel::oneshot};
 use gpui::{
     App, AsyncApp, Context, Entity, EventEmitter, Img, Subscription, Task, WeakEntity, prelude::*,
 };
-pub use image::ImageFormat;
-use image::{ExtendedColorType, GenericImageView, ImageReader};
+use image::{
+    AnimationDecoder, ExtendedColorType, ImageDecoder, ImageReader,
+    codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder},
+};
+pub use image::{ImageFormat, metadata::Orientation};
 use language::{DiskState, File};
 use rpc::{AnyProtoClient, ErrorExt as _};
+use std::io::Cursor;
 use std::num::NonZeroU64;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use util::{ResultExt, rel_path::RelPath};
 use worktree::{LoadedBinaryFile, PathChange, Worktree};
 
@@ -39,6 +47,8 @@ pub enum ImageItemEvent {
     Reloaded,
     FileHandleChanged,
     MetadataUpdated,
+    /// The unsaved edits of the image changed, or were saved or discarded.
+    Edited,
 }
 
 impl EventEmitter<ImageItemEvent> for ImageItem {}
@@ -49,13 +59,16 @@ pub enum ImageStoreEvent {
 
 impl EventEmitter<ImageStoreEvent> for ImageStore {}
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ImageMetadata {
+    /// The width of the image, once its [`EmbeddedMetadata::orientation`] is applied.
     pub width: u32,
+    /// The height of the image, once its [`EmbeddedMetadata::orientation`] is applied.
     pub height: u32,
     pub file_size: u64,
     pub colors: Option<ImageColorInfo>,
     pub format: ImageFormat,
+    pub embedded: Arc<EmbeddedMetadata>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -93,10 +106,100 @@ impl ImageColorInfo {
     }
 }
 
+/// A decoded frame of an animated image.
+pub struct ImageFrame {
+    pub image: Arc<gpui::RenderImage>,
+    /// How long the frame is shown before the next one.
+    pub delay: Duration,
+}
+
+/// Frames shown for less than this are shown for [`DEFAULT_FRAME_DELAY`] instead, like browsers do.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+/// The most memory the decoded frames of an animation may take for it to be played back.
+const MAX_DECODED_ANIMATION_SIZE: usize = 256 * 1024 * 1024;
+
+/// An edit made to an image in the image viewer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageEdit {
+    RotateClockwise,
+    RotateCounterclockwise,
+    FlipHorizontal,
+    FlipVertical,
+    /// Crops the image to a rectangle, in pixels of the image with the previous edits applied.
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl ImageEdit {
+    fn apply(self, image: image::DynamicImage) -> image::DynamicImage {
+        match self {
+            ImageEdit::RotateClockwise => image.rotate90(),
+            ImageEdit::RotateCounterclockwise => image.rotate270(),
+            ImageEdit::FlipHorizontal => image.fliph(),
+            ImageEdit::FlipVertical => image.flipv(),
+            ImageEdit::Crop {
+                x,
+                y,
+                width,
+                height,
+            } => image.crop_imm(x, y, width, height),
+        }
+    }
+}
+
+/// An image with its unsaved [`ImageEdit`]s applied.
+pub struct EditedImage {
+    pub image: Arc<image::DynamicImage>,
+    pub render_image: Arc<gpui::RenderImage>,
+}
+
+/// An image as it is in git's HEAD, to compare the image in the working tree with.
+pub struct CommittedImage {
+    pub render_image: Arc<gpui::RenderImage>,
+    /// The width of the image, once its EXIF orientation is applied.
+    pub width: u32,
+    /// The height of the image, once its EXIF orientation is applied.
+    pub height: u32,
+    pub file_size: u64,
+    pub format: ImageFormat,
+}
+
+impl CommittedImage {
+    pub fn decode(content: &[u8]) -> Result<Self> {
+        let image = apply_image_edits(content, &[])?;
+        Ok(Self {
+            render_image: create_render_image(&image),
+            width: image.width(),
+            height: image.height(),
+            file_size: content.len() as u64,
+            format: image::guess_format(content)?,
+        })
+    }
+}
+
 pub struct ImageItem {
     pub id: ImageId,
     pub file: Arc<worktree::File>,
     pub image: Arc<gpui::Image>,
+    /// The decoded frames of an animated GIF, APNG or WebP image, or `None` for still images.
+    ///
+    /// Frames are decoded once when the image is loaded, so that playing them back doesn't need
+    /// to decode the image again.
+    pub frames: Option<Arc<[ImageFrame]>>,
+    /// The image to show instead of the file's contents as they are: the image with its EXIF
+    /// orientation applied, for images that aren't stored upright, or the first frame of an
+    /// animation too large to play back.
+    pub still_image: Option<Arc<gpui::RenderImage>>,
+    /// The unsaved edits of the image, in the order they were made.
+    edits: Vec<ImageEdit>,
+    /// The image with its unsaved edits applied, once they're applied.
+    pub edited_image: Option<EditedImage>,
+    edit_task: Option<Task<()>>,
     reload_task: Option<Task<()>>,
     pub image_metadata: Option<ImageMetadata>,
 }
@@ -119,11 +222,16 @@ impl ImageItem {
         let image_bytes = fs.load_bytes(&image_path).await?;
         let image_format = image::guess_format(&image_bytes)?;
 
-        let mut image_reader = ImageReader::new(std::io::Cursor::new(image_bytes));
+        let mut image_reader = ImageReader::new(Cursor::new(&image_bytes));
         image_reader.set_format(image_format);
-        let image = image_reader.decode()?;
+        let mut decoder = image_reader.into_decoder()?;
+        let exif = decoder.exif_metadata().log_err().flatten();
+        let embedded = EmbeddedMetadata::parse(exif.as_deref(), &image_bytes);
 
-        let (width, height) = image.dimensions();
+        let (mut width, mut height) = decoder.dimensions();
+        if embedded.orientation.is_some_and(swaps_dimensions) {
+            (width, height) = (height, width);
+        }
         let file_metadata = fs
             .metadata(image_path.as_path())
             .await?
@@ -134,7 +242,8 @@ impl ImageItem {
             height,
             file_size: file_metadata.len,
             format: image_format,
-            colors: ImageColorInfo::from_color_type(image.color()),
+            colors: ImageColorInfo::from_color_type(decoder.color_type()),
+            embedded: Arc::new(embedded),
         })
     }
 
@@ -149,6 +258,81 @@ impl ImageItem {
         Some(self.file.as_local()?.abs_path(cx))
     }
 
+    /// Whether the image has edits that weren't saved yet.
+    pub fn is_edited(&self) -> bool {
+        !self.edits.is_empty()
+    }
+
+    /// Whether the image can be edited. Animated images can't be, and neither can images in remote
+    /// worktrees, as those can't be saved yet.
+    pub fn can_edit(&self) -> bool {
+        self.frames.is_none() && self.file.is_local
+    }
+
+    /// The width and height of the image, with its unsaved edits applied.
+    pub fn size(&self) -> Option<(u32, u32)> {
+        match &self.edited_image {
+            Some(edited_image) => Some((edited_image.image.width(), edited_image.image.height())),
+            None => {
+                let metadata = self.image_metadata.as_ref()?;
+                Some((metadata.width, metadata.height))
+            }
+        }
+    }
+
+    /// Makes an unsaved edit to the image.
+    pub fn edit(&mut self, edit: ImageEdit, cx: &mut Context<Self>) {
+        if !self.can_edit() {
+            return;
+        }
+        self.edits.push(edit);
+        cx.emit(ImageItemEvent::Edited);
+        cx.notify();
+
+        // Every edit is applied to the image on disk again, so that a slow edit finishing after
+        // a later one can't leave the image in an outdated state.
+        let content = self.image.bytes.clone();
+        let edits = self.edits.clone();
+        self.edit_task = Some(cx.spawn(async move |this, cx| {
+            let edited_image = cx
+                .background_spawn(async move {
+                    let image = apply_image_edits(&content, &edits)?;
+                    let render_image = create_render_image(&image);
+                    anyhow::Ok(EditedImage {
+                        image: Arc::new(image),
+                        render_image,
+                    })
+                })
+                .await;
+            if let Some(edited_image) = edited_image.log_err() {
+                this.update(cx, |this, cx| {
+                    this.set_edited_image(Some(edited_image), cx);
+                    cx.emit(ImageItemEvent::Edited);
+                    cx.notify();
+                })
+                .log_err();
+            }
+        }));
+    }
+
+    /// Discards the unsaved edits of the image.
+    pub fn discard_edits(&mut self, cx: &mut Context<Self>) {
+        if !self.is_edited() {
+            return;
+        }
+        self.edits.clear();
+        self.edit_task = None;
+        self.set_edited_image(None, cx);
+        cx.emit(ImageItemEvent::Edited);
+        cx.notify();
+    }
+
+    fn set_edited_image(&mut self, edited_image: Option<EditedImage>, cx: &mut Context<Self>) {
+        if let Some(previous) = std::mem::replace(&mut self.edited_image, edited_image) {
+            cx.drop_image(previous.render_image, None);
+        }
+    }
+
     fn file_updated(&mut self, new_file: Arc<worktree::File>, cx: &mut Context<Self>) {
         let mut file_changed = false;
 
@@ -174,19 +358,30 @@ impl ImageItem {
     }
 
     fn reload(&mut self, cx: &mut Context<Self>) -> Option<oneshot::Receiver<()>> {
+        // Like buffers with unsaved changes, edited images keep their edits instead of reloading.
+        if self.is_edited() {
+            return None;
+        }
         let local_file = self.file.as_local()?;
         let (tx, rx) = futures::channel::oneshot::channel();
 
         let content = local_file.load_bytes(cx);
         self.reload_task = Some(cx.spawn(async move |this, cx| {
-            if let Some(image) = content
-                .await
-                .context("Failed to load image content")
-                .and_then(create_gpui_image)
-                .log_err()
-            {
+            let decoded = match content.await.context("Failed to load image content") {
+                Ok(content) => {
+                    cx.background_spawn(async move {
+                        let (frames, still_image) = decode_frames_or_still_image(&content);
+                        anyhow::Ok((create_gpui_image(content)?, frames, still_image))
+                    })
+                    .await
+                }
+                Err(error) => Err(error),
+            };
+            if let Some((image, frames, still_image)) = decoded.log_err() {
                 this.update(cx, |this, cx| {
                     this.image = image;
+                    this.frames = frames;
+                    this.still_image = still_image;
                     cx.emit(ImageItemEvent::Reloaded);
                 })
                 .log_err();
@@ -246,7 +441,7 @@ impl ProjectItem for ImageItem {
     }
 
     fn is_dirty(&self) -> bool {
-        false
+        self.is_edited()
     }
 }
 
@@ -427,6 +622,76 @@ impl ImageStore {
         self.state.reload_images(images, cx)
     }
 
+    /// Writes the image with its unsaved edits applied to its file, in the file's format.
+    pub fn save_image(
+        &self,
+        image: Entity<ImageItem>,
+        cx: &mut Context<ImageStore>,
+    ) -> Task<Result<()>> {
+        let image_item = image.read(cx);
+        if !image_item.is_edited() {
+            return Task::ready(Ok(()));
+        }
+        let Some(edited_image) = image_item
+            .edited_image
+            .as_ref()
+            .map(|edited_image| edited_image.image.clone())
+        else {
+            return Task::ready(Err(anyhow::anyhow!(
+                "the image's edits haven't been applied yet"
+            )));
+        };
+        let file = image_item.file.clone();
+        if !file.is_local {
+            return Task::ready(Err(anyhow::anyhow!(
+                "images in remote worktrees can't be saved yet"
+            )));
+        }
+        let content = image_item.image.bytes.clone();
+
+        cx.spawn(async move |_, cx| {
+            let content = cx
+                .background_spawn(async move {
+                    let format = image::guess_format(&content)?;
+                    encode_image(&edited_image, format)
+                })
+                .await?;
+            let new_file = file
+                .worktree
+                .update(cx, |worktree, cx| {
+                    worktree.write_binary_file(file.path.clone(), content.clone(), cx)
+                })?
+                .await?;
+            let file_size = content.len() as u64;
+            let saved_image = create_gpui_image(content)?;
+
+            image.update(cx, |image, cx| {
+                image.image = saved_image;
+                if let Some(still_image) = image.still_image.take() {
+                    cx.drop_image(still_image, None);
+                }
+                image.edits.clear();
+                image.edit_task = None;
+                if let Some(edited_image) = image.edited_image.take() {
+                    if let Some(metadata) = &mut image.image_metadata {
+                        metadata.width = edited_image.image.width();
+                        metadata.height = edited_image.image.height();
+                        metadata.file_size = file_size;
+                        // The edited image is encoded upright.
+                        Arc::make_mut(&mut metadata.embedded).orientation = None;
+                    }
+                    cx.drop_image(edited_image.render_image, None);
+                }
+                // The file is updated directly, as reloading it would only decode the same image.
+                image.file = new_file;
+                cx.emit(ImageItemEvent::Edited);
+                cx.emit(ImageItemEvent::MetadataUpdated);
+                cx.emit(ImageItemEvent::Reloaded);
+                cx.notify();
+            })
+        })
+    }
+
     fn add_image(&mut self, image: Entity<ImageItem>, cx: &mut Context<ImageStore>) -> Result<()> {
         let image_id = image.read(cx).id;
 
@@ -467,12 +732,22 @@ impl ImageStoreImpl for Entity<LocalImageStore> {
         });
         cx.spawn(async move |image_store, cx| {
             let LoadedBinaryFile { file, content } = load_file.await?;
-            let image = create_gpui_image(content)?;
+            let (image, frames, still_image) = cx
+                .background_spawn(async move {
+                    let (frames, still_image) = decode_frames_or_still_image(&content);
+                    anyhow::Ok((create_gpui_image(content)?, frames, still_image))
+                })
+                .await?;
 
             let entity = cx.new(|cx| ImageItem {
                 id: cx.entity_id().as_non_zero_u64().into(),
                 file: file.clone(),
                 image,
+                frames,
+                still_image,
+                edits: Vec::new(),
+                edited_image: None,
+                edit_task: None,
                 image_metadata: None,
                 reload_task: None,
             })?;
@@ -693,6 +968,142 @@ fn create_gpui_image(content: Vec<u8>) -> anyhow::Result<Arc<gpui::Image>> {
     )))
 }
 
+/// Whether applying `orientation` turns the image by a quarter turn, swapping its width and height.
+fn swaps_dimensions(orientation: Orientation) -> bool {
+    matches!(
+        orientation,
+        Orientation::Rotate90
+            | Orientation::Rotate270
+            | Orientation::Rotate90FlipH
+            | Orientation::Rotate270FlipH
+    )
+}
+
+/// Decodes an image that has to be rotated or flipped to be shown upright according to its EXIF
+/// orientation, applying that orientation.
+///
+/// Returns `None` for images that are stored upright, which are rendered as they are.
+fn decode_oriented_image(content: &[u8]) -> Option<Arc<gpui::RenderImage>> {
+    let mut decoder = ImageReader::new(Cursor::new(content))
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    let orientation = decoder.orientation().ok()?;
+    if orientation == Orientation::NoTransforms {
+        return None;
+    }
+
+    let mut image = image::DynamicImage::from_decoder(decoder).log_err()?;
+    image.apply_orientation(orientation);
+    Some(create_render_image(&image))
+}
+
+fn create_render_image(image: &image::DynamicImage) -> Arc<gpui::RenderImage> {
+    let mut buffer = image.to_rgba8();
+    // Convert from RGBA to BGRA.
+    for pixel in buffer.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    Arc::new(gpui::RenderImage::new([image::Frame::new(buffer)]))
+}
+
+/// Decodes an image with its EXIF orientation applied, and applies `edits` to it in order.
+fn apply_image_edits(content: &[u8], edits: &[ImageEdit]) -> Result<image::DynamicImage> {
+    let mut decoder = ImageReader::new(Cursor::new(content))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = image::DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(edits.iter().fold(image, |image, edit| edit.apply(image)))
+}
+
+fn encode_image(image: &image::DynamicImage, format: ImageFormat) -> Result<Vec<u8>> {
+    let mut content = Vec::new();
+    match format {
+        // JPEG has no alpha channel.
+        ImageFormat::Jpeg if image.color().has_alpha() => {
+            image::DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_to(&mut Cursor::new(&mut content), format)?
+        }
+        _ => image.write_to(&mut Cursor::new(&mut content), format)?,
+    }
+    Ok(content)
+}
+
+/// Decodes the frames of an animated image, or the image to show in place of the file's contents
+/// when it isn't played back. See [`ImageItem::still_image`].
+fn decode_frames_or_still_image(
+    content: &[u8],
+) -> (Option<Arc<[ImageFrame]>>, Option<Arc<gpui::RenderImage>>) {
+    match decode_animation_frames(content, MAX_DECODED_ANIMATION_SIZE) {
+        Some(AnimationFrames::All(frames)) => (Some(frames), None),
+        Some(AnimationFrames::First(frame)) => (None, Some(frame)),
+        None => (None, decode_oriented_image(content)),
+    }
+}
+
+/// The decoded frames of an animated image.
+enum AnimationFrames {
+    All(Arc<[ImageFrame]>),
+    /// The first frame only, for animations whose frames would take too much memory, which are
+    /// shown as still images.
+    First(Arc<gpui::RenderImage>),
+}
+
+/// Decodes every frame of an animated GIF, APNG or WebP image, keeping only the first one once
+/// they take more than `max_decoded_size` bytes.
+///
+/// Returns `None` for still images, and for animations that fail to decode, which are then shown
+/// as still images.
+fn decode_animation_frames(content: &[u8], max_decoded_size: usize) -> Option<AnimationFrames> {
+    let frames = match image::guess_format(content).ok()? {
+        image::ImageFormat::Gif => GifDecoder::new(Cursor::new(content)).ok()?.into_frames(),
+        image::ImageFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(content)).ok()?;
+            if !decoder.is_apng().ok()? {
+                return None;
+            }
+            decoder.apng().ok()?.into_frames()
+        }
+        image::ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(Cursor::new(content)).ok()?;
+            if !decoder.has_animation() {
+                return None;
+            }
+            decoder.into_frames()
+        }
+        _ => return None,
+    };
+
+    let mut decoded_frames = Vec::new();
+    let mut decoded_size = 0;
+    for frame in frames {
+        let frame = frame.log_err()?;
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let delay = Duration::from_nanos(numerator as u64 * 1_000_000 / denominator.max(1) as u64);
+        let mut buffer = frame.into_buffer();
+        decoded_size += buffer.len();
+        if decoded_size > max_decoded_size && !decoded_frames.is_empty() {
+            return Some(AnimationFrames::First(decoded_frames.swap_remove(0).image));
+        }
+        // Convert from RGBA to BGRA.
+        for pixel in buffer.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        decoded_frames.push(ImageFrame {
+            image: Arc::new(gpui::RenderImage::new([image::Frame::new(buffer)])),
+            delay: if delay < MIN_FRAME_DELAY {
+                DEFAULT_FRAME_DELAY
+            } else {
+                delay
+            },
+        });
+    }
+    (decoded_frames.len() > 1).then(|| AnimationFrames::All(decoded_frames.into()))
+}
+
 impl ImageStoreImpl for Entity<RemoteImageStore> {
     fn open_image(
         &self,
@@ -781,4 +1192,98 @@ mod tests {
 
         assert_eq!(image1, image2);
     }
+
+    #[test]
+    fn test_decode_animation_frames() {
+        let mut gif = Vec::new();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(&mut gif);
+            for (color, delay_ms) in [([255, 0, 0, 255], 50), ([0, 0, 255, 255], 0)] {
+                encoder
+                    .encode_frame(image::Frame::from_parts(
+                        image::RgbaImage::from_pixel(2, 2, image::Rgba(color)),
+                        0,
+                        0,
+                        image::Delay::from_numer_denom_ms(delay_ms, 1),
+                    ))
+                    .unwrap();
+            }
+        }
+
+        let Some(AnimationFrames::All(frames)) =
+            decode_animation_frames(&gif, MAX_DECODED_ANIMATION_SIZE)
+        else {
+            panic!("expected every frame to be decoded");
+        };
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].delay, Duration::from_millis(50));
+        // Frames without a delay are shown for the default delay.
+        assert_eq!(frames[1].delay, DEFAULT_FRAME_DELAY);
+        // Frames are stored as BGRA.
+        assert_eq!(frames[0].image.as_bytes(0).unwrap()[..4], [0, 0, 255, 255]);
+
+        // Animations too large to keep decoded are shown as their first frame.
+        let Some(AnimationFrames::First(first_frame)) = decode_animation_frames(&gif, 20) else {
+            panic!("expected only the first frame to be decoded");
+        };
+        assert_eq!(first_frame.frame_count(), 1);
+        assert_eq!(first_frame.as_bytes(0).unwrap()[..4], [0, 0, 255, 255]);
+
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(2, 2, image::Rgba([255, 255, 255, 255]))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert!(decode_animation_frames(&png, MAX_DECODED_ANIMATION_SIZE).is_none());
+    }
+
+    #[test]
+    fn test_apply_image_edits() {
+        let red = image::Rgba([255, 0, 0, 255]);
+        let blue = image::Rgba([0, 0, 255, 255]);
+        let mut png = Vec::new();
+        image::RgbaImage::from_fn(3, 1, |x, _| if x == 0 { red } else { blue })
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let image = apply_image_edits(&png, &[ImageEdit::RotateClockwise]).unwrap();
+        assert_eq!((image.width(), image.height()), (1, 3));
+        assert_eq!(image.to_rgba8().get_pixel(0, 0), &red);
+
+        let image = apply_image_edits(
+            &png,
+            &[ImageEdit::RotateCounterclockwise, ImageEdit::FlipVertical],
+        )
+        .unwrap();
+        assert_eq!((image.width(), image.height()), (1, 3));
+        assert_eq!(image.to_rgba8().get_pixel(0, 0), &red);
+
+        let image = apply_image_edits(
+            &png,
+            &[
+                ImageEdit::FlipHorizontal,
+                ImageEdit::Crop {
+                    x: 1,
+                    y: 0,
+                    width: 2,
+                    height: 1,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(image.to_rgba8().get_pixel(1, 0), &red);
+
+        let committed_image = CommittedImage::decode(&png).unwrap();
+        assert_eq!((committed_image.width, committed_image.height), (3, 1));
+        assert_eq!(committed_image.file_size, png.len() as u64);
+        assert_eq!(committed_image.format, ImageFormat::Png);
+
+        let jpeg = encode_image(&image, ImageFormat::Jpeg).unwrap();
+        assert_eq!(image::guess_format(&jpeg).unwrap(), ImageFormat::Jpeg);
+        let png = encode_image(&image, ImageFormat::Png).unwrap();
+        assert_eq!(
+            apply_image_edits(&png, &[]).unwrap().to_rgba8(),
+            image.to_rgba8()
+        );
+    }
 }
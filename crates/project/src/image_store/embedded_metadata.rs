@@ -0,0 +1,518 @@
+//! Parsing of the EXIF and XMP metadata that cameras and editing software embed in image files.
+
+use image::metadata::Orientation;
+
+/// Metadata embedded in an image file by the camera or the software that produced it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmbeddedMetadata {
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub lens_model: Option<String>,
+    pub software: Option<String>,
+    /// When the photo was taken, or when the file was last changed if that's unknown, as written
+    /// in the file (`YYYY:MM:DD HH:MM:SS`).
+    pub date_taken: Option<String>,
+    /// The exposure time, in seconds.
+    pub exposure_time: Option<Rational>,
+    pub f_number: Option<f64>,
+    pub iso: Option<u32>,
+    /// The focal length of the lens, in millimeters.
+    pub focal_length: Option<f64>,
+    pub gps: Option<GpsPosition>,
+    /// How the image has to be transformed to be shown upright.
+    pub orientation: Option<Orientation>,
+    /// The horizontal and vertical resolution, in dots per inch.
+    pub dpi: Option<(f64, f64)>,
+    /// The simple properties of the XMP packet, as their qualified name (e.g. `xmp:CreatorTool`)
+    /// and value. Array values are joined with commas.
+    pub xmp: Vec<(String, String)>,
+}
+
+/// Where a photo was taken, in degrees north and east, and meters above sea level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
+}
+
+/// An EXIF rational, kept as a fraction so that e.g. exposure times can be shown as `1/250`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+impl Rational {
+    pub fn to_f64(self) -> Option<f64> {
+        (self.denominator != 0).then(|| self.numerator as f64 / self.denominator as f64)
+    }
+}
+
+impl EmbeddedMetadata {
+    /// Parses an image's EXIF block, as returned by [`image::ImageDecoder::exif_metadata`], and
+    /// the XMP packet found in the file's contents.
+    pub fn parse(exif: Option<&[u8]>, content: &[u8]) -> Self {
+        let mut metadata = Self::default();
+        if let Some(exif) = exif {
+            metadata.parse_exif(exif);
+        }
+        metadata.xmp = parse_xmp(content);
+        metadata
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn parse_exif(&mut self, exif: &[u8]) {
+        let Some(tiff) = Tiff::new(exif.strip_prefix(b"Exif\0\0").unwrap_or(exif)) else {
+            return;
+        };
+        let Some(ifd0) = tiff.u32(4).and_then(|offset| tiff.entries(offset as usize)) else {
+            return;
+        };
+
+        let mut resolution = (None, None);
+        let mut resolution_unit = 2;
+        let mut exif_ifd = None;
+        let mut gps_ifd = None;
+        for entry in ifd0 {
+            match entry.tag {
+                0x010F => self.camera_make = tiff.ascii(&entry),
+                0x0110 => self.camera_model = tiff.ascii(&entry),
+                0x0112 => {
+                    self.orientation = tiff
+                        .uint(&entry, 0)
+                        .and_then(|value| Orientation::from_exif(value as u8))
+                }
+                0x011A => resolution.0 = tiff.rational(&entry, 0).and_then(Rational::to_f64),
+                0x011B => resolution.1 = tiff.rational(&entry, 0).and_then(Rational::to_f64),
+                0x0128 => resolution_unit = tiff.uint(&entry, 0).unwrap_or(2),
+                0x0131 => self.software = tiff.ascii(&entry),
+                0x0132 => self.date_taken = self.date_taken.take().or(tiff.ascii(&entry)),
+                0x8769 => exif_ifd = tiff.uint(&entry, 0),
+                0x8825 => gps_ifd = tiff.uint(&entry, 0),
+                _ => {}
+            }
+        }
+
+        if let (Some(x), Some(y)) = resolution {
+            // A resolution unit of 3 means dots per centimeter.
+            let scale = if resolution_unit == 3 { 2.54 } else { 1. };
+            self.dpi = Some((x * scale, y * scale));
+        }
+
+        for entry in exif_ifd
+            .and_then(|offset| tiff.entries(offset as usize))
+            .into_iter()
+            .flatten()
+        {
+            match entry.tag {
+                0x829A => self.exposure_time = tiff.rational(&entry, 0),
+                0x829D => self.f_number = tiff.rational(&entry, 0).and_then(Rational::to_f64),
+                0x8827 => self.iso = tiff.uint(&entry, 0),
+                // The original date is preferred over the date the file was last changed.
+                0x9003 => self.date_taken = tiff.ascii(&entry).or(self.date_taken.take()),
+                0x920A => self.focal_length = tiff.rational(&entry, 0).and_then(Rational::to_f64),
+                0xA434 => self.lens_model = tiff.ascii(&entry),
+                _ => {}
+            }
+        }
+
+        if let Some(entries) = gps_ifd.and_then(|offset| tiff.entries(offset as usize)) {
+            self.gps = parse_gps(&tiff, entries);
+        }
+    }
+}
+
+fn parse_gps(tiff: &Tiff, entries: Vec<Entry>) -> Option<GpsPosition> {
+    let mut latitude = None;
+    let mut longitude = None;
+    let mut latitude_sign = 1.;
+    let mut longitude_sign = 1.;
+    let mut altitude = None;
+    let mut altitude_sign = 1.;
+    let degrees = |entry: &Entry| {
+        let mut degrees = 0.;
+        for (ix, divisor) in [1., 60., 3600.].into_iter().enumerate() {
+            degrees += tiff.rational(entry, ix)?.to_f64()? / divisor;
+        }
+        Some(degrees)
+    };
+    for entry in &entries {
+        match entry.tag {
+            1 if tiff.ascii(entry).as_deref() == Some("S") => latitude_sign = -1.,
+            2 => latitude = degrees(entry),
+            3 if tiff.ascii(entry).as_deref() == Some("W") => longitude_sign = -1.,
+            4 => longitude = degrees(entry),
+            // An altitude reference of 1 means below sea level.
+            5 if tiff.uint(entry, 0) == Some(1) => altitude_sign = -1.,
+            6 => altitude = tiff.rational(entry, 0).and_then(Rational::to_f64),
+            _ => {}
+        }
+    }
+    Some(GpsPosition {
+        latitude: latitude? * latitude_sign,
+        longitude: longitude? * longitude_sign,
+        altitude: altitude.map(|altitude| altitude * altitude_sign),
+    })
+}
+
+/// A TIFF structure, which is how EXIF blocks are laid out.
+struct Tiff<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+/// An entry of a TIFF image file directory.
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: u32,
+    /// The offset of the entry's value, which is stored inline when it fits in four bytes.
+    value_offset: usize,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let big_endian = match data.get(..4)? {
+            b"II*\0" => false,
+            b"MM\0*" => true,
+            _ => return None,
+        };
+        Some(Self { data, big_endian })
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn entries(&self, offset: usize) -> Option<Vec<Entry>> {
+        let count = self.u16(offset)? as usize;
+        (0..count)
+            .map(|ix| {
+                let entry_offset = offset + 2 + ix * 12;
+                let kind = self.u16(entry_offset + 2)?;
+                let count = self.u32(entry_offset + 4)?;
+                let size = value_size(kind).saturating_mul(count as usize);
+                let value_offset = if size <= 4 {
+                    entry_offset + 8
+                } else {
+                    self.u32(entry_offset + 8)? as usize
+                };
+                Some(Entry {
+                    tag: self.u16(entry_offset)?,
+                    kind,
+                    count,
+                    value_offset,
+                })
+            })
+            .collect()
+    }
+
+    fn ascii(&self, entry: &Entry) -> Option<String> {
+        if entry.kind != 2 {
+            return None;
+        }
+        let bytes = self
+            .data
+            .get(entry.value_offset..entry.value_offset + entry.count as usize)?;
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn uint(&self, entry: &Entry, ix: usize) -> Option<u32> {
+        if ix >= entry.count as usize {
+            return None;
+        }
+        match entry.kind {
+            1 | 7 => self
+                .data
+                .get(entry.value_offset + ix)
+                .map(|byte| *byte as u32),
+            3 => self.u16(entry.value_offset + ix * 2).map(u32::from),
+            4 => self.u32(entry.value_offset + ix * 4),
+            _ => None,
+        }
+    }
+
+    fn rational(&self, entry: &Entry, ix: usize) -> Option<Rational> {
+        if !matches!(entry.kind, 5 | 10) || ix >= entry.count as usize {
+            return None;
+        }
+        let offset = entry.value_offset + ix * 8;
+        Some(Rational {
+            numerator: self.u32(offset)?,
+            denominator: self.u32(offset + 4)?,
+        })
+    }
+}
+
+fn value_size(kind: u16) -> usize {
+    match kind {
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 1,
+    }
+}
+
+/// Extracts the simple properties of the XMP packet in `content`, if any.
+fn parse_xmp(content: &[u8]) -> Vec<(String, String)> {
+    let Some(start) = find(content, b"<x:xmpmeta") else {
+        return Vec::new();
+    };
+    let end = find(&content[start..], b"</x:xmpmeta>").map_or(content.len(), |end| start + end);
+    let Ok(xmp) = std::str::from_utf8(&content[start..end]) else {
+        return Vec::new();
+    };
+
+    let mut properties = Vec::new();
+    let mut rest = xmp;
+    while let Some(tag_start) = rest.find('<') {
+        rest = &rest[tag_start + 1..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..tag_end];
+        rest = &rest[tag_end + 1..];
+        if tag.starts_with(['/', '?', '!']) {
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        if name == "rdf:Description" {
+            properties.extend(parse_xmp_attributes(attributes));
+        } else if is_xmp_property(name) && !self_closing {
+            let closing_tag = format!("</{name}>");
+            if let Some(value_end) = rest.find(&closing_tag) {
+                let values = rest[..value_end]
+                    .split('<')
+                    .map(|segment| segment.split_once('>').map_or(segment, |(_, text)| text))
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                    .map(unescape_xml)
+                    .collect::<Vec<_>>();
+                if !values.is_empty() {
+                    properties.push((name.to_string(), values.join(", ")));
+                }
+                rest = &rest[value_end + closing_tag.len()..];
+            }
+        }
+    }
+    properties
+}
+
+fn parse_xmp_attributes(mut attributes: &str) -> Vec<(String, String)> {
+    let mut properties = Vec::new();
+    while let Some(equals) = attributes.find('=') {
+        let name = attributes[..equals].trim();
+        let value = attributes[equals + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(value_end) = value[1..].find(quote) else {
+            break;
+        };
+        attributes = &value[value_end + 2..];
+        let value = value[1..value_end + 1].trim();
+        if is_xmp_property(name) && !value.is_empty() {
+            properties.push((name.to_string(), unescape_xml(value)));
+        }
+    }
+    properties
+}
+
+/// Whether `name` is a metadata property, rather than part of the XMP or RDF structure.
+fn is_xmp_property(name: &str) -> bool {
+    name.contains(':')
+        && !name.starts_with("xmlns:")
+        && !name.starts_with("rdf:")
+        && !name.starts_with("x:")
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a little-endian EXIF block with the given IFD0, EXIF and GPS entries, each a tag,
+    /// type, count and value bytes.
+    fn exif_block(
+        ifd0: &[(u16, u16, u32, Vec<u8>)],
+        exif: &[(u16, u16, u32, Vec<u8>)],
+        gps: &[(u16, u16, u32, Vec<u8>)],
+    ) -> Vec<u8> {
+        fn write_ifd(data: &mut Vec<u8>, entries: &[(u16, u16, u32, Vec<u8>)]) {
+            let start = data.len();
+            let mut values_offset = start + 2 + entries.len() * 12 + 4;
+            let mut values = Vec::new();
+            data.extend((entries.len() as u16).to_le_bytes());
+            for (tag, kind, count, value) in entries {
+                data.extend(tag.to_le_bytes());
+                data.extend(kind.to_le_bytes());
+                data.extend(count.to_le_bytes());
+                if value.len() <= 4 {
+                    let mut inline = value.clone();
+                    inline.resize(4, 0);
+                    data.extend(inline);
+                } else {
+                    data.extend((values_offset as u32).to_le_bytes());
+                    values.extend(value);
+                    values_offset += value.len();
+                }
+            }
+            data.extend(0u32.to_le_bytes());
+            data.extend(values);
+        }
+
+        let pointer = |offset: usize| (offset as u32).to_le_bytes().to_vec();
+        let mut ifd0 = ifd0.to_vec();
+        ifd0.push((0x8769, 4, 1, Vec::new()));
+        ifd0.push((0x8825, 4, 1, Vec::new()));
+
+        // Lay out IFD0 first to know where the EXIF and GPS directories start.
+        let mut data = b"II*\0".to_vec();
+        data.extend(8u32.to_le_bytes());
+        let mut scratch = data.clone();
+        write_ifd(&mut scratch, &ifd0);
+        let exif_offset = scratch.len();
+        let mut scratch_exif = scratch.clone();
+        write_ifd(&mut scratch_exif, exif);
+        let gps_offset = scratch_exif.len();
+
+        let len = ifd0.len();
+        ifd0[len - 2].3 = pointer(exif_offset);
+        ifd0[len - 1].3 = pointer(gps_offset);
+        write_ifd(&mut data, &ifd0);
+        write_ifd(&mut data, exif);
+        write_ifd(&mut data, gps);
+        data
+    }
+
+    fn ascii(text: &str) -> (u16, u32, Vec<u8>) {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        (2, bytes.len() as u32, bytes)
+    }
+
+    fn rationals(values: &[(u32, u32)]) -> (u16, u32, Vec<u8>) {
+        let bytes = values
+            .iter()
+            .flat_map(|(numerator, denominator)| {
+                numerator
+                    .to_le_bytes()
+                    .into_iter()
+                    .chain(denominator.to_le_bytes())
+            })
+            .collect();
+        (5, values.len() as u32, bytes)
+    }
+
+    fn entry(tag: u16, (kind, count, value): (u16, u32, Vec<u8>)) -> (u16, u16, u32, Vec<u8>) {
+        (tag, kind, count, value)
+    }
+
+    #[test]
+    fn test_parse_exif() {
+        let exif = exif_block(
+            &[
+                entry(0x010F, ascii("Acme")),
+                entry(0x0110, ascii("Acme One")),
+                entry(0x0112, (3, 1, 6u16.to_le_bytes().to_vec())),
+                entry(0x011A, rationals(&[(300, 1)])),
+                entry(0x011B, rationals(&[(300, 1)])),
+                entry(0x0132, ascii("2024:01:02 10:00:00")),
+            ],
+            &[
+                entry(0x829A, rationals(&[(1, 250)])),
+                entry(0x829D, rationals(&[(28, 10)])),
+                entry(0x8827, (3, 1, 400u16.to_le_bytes().to_vec())),
+                entry(0x9003, ascii("2024:01:01 12:30:00")),
+                entry(0x920A, rationals(&[(50, 1)])),
+            ],
+            &[
+                entry(1, ascii("N")),
+                entry(2, rationals(&[(48, 1), (51, 1), (36, 1)])),
+                entry(3, ascii("W")),
+                entry(4, rationals(&[(2, 1), (21, 1), (0, 1)])),
+                entry(6, rationals(&[(35, 1)])),
+            ],
+        );
+
+        let metadata = EmbeddedMetadata::parse(Some(&exif), &[]);
+        assert_eq!(metadata.camera_make.as_deref(), Some("Acme"));
+        assert_eq!(metadata.camera_model.as_deref(), Some("Acme One"));
+        assert_eq!(metadata.orientation, Some(Orientation::Rotate90));
+        assert_eq!(metadata.dpi, Some((300., 300.)));
+        assert_eq!(metadata.date_taken.as_deref(), Some("2024:01:01 12:30:00"));
+        assert_eq!(
+            metadata.exposure_time,
+            Some(Rational {
+                numerator: 1,
+                denominator: 250
+            })
+        );
+        assert_eq!(metadata.f_number, Some(2.8));
+        assert_eq!(metadata.iso, Some(400));
+        assert_eq!(metadata.focal_length, Some(50.));
+
+        let gps = metadata.gps.unwrap();
+        assert!((gps.latitude - 48.86).abs() < 0.001);
+        assert!((gps.longitude + 2.35).abs() < 0.001);
+        assert_eq!(gps.altitude, Some(35.));
+    }
+
+    #[test]
+    fn test_parse_xmp() {
+        let content = br#"garbage<x:xmpmeta xmlns:x="adobe:ns:meta/">
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                <rdf:Description rdf:about="" xmp:CreatorTool="Editor &amp; Co" xmp:Rating="5">
+                    <dc:creator><rdf:Seq><rdf:li>Alice</rdf:li><rdf:li>Bob</rdf:li></rdf:Seq></dc:creator>
+                </rdf:Description>
+            </rdf:RDF>
+        </x:xmpmeta>garbage"#;
+
+        let metadata = EmbeddedMetadata::parse(None, content);
+        assert_eq!(
+            metadata.xmp,
+            [
+                ("xmp:CreatorTool".to_string(), "Editor & Co".to_string()),
+                ("xmp:Rating".to_string(), "5".to_string()),
+                ("dc:creator".to_string(), "Alice, Bob".to_string()),
+            ]
+        );
+        assert!(EmbeddedMetadata::parse(None, b"no metadata").is_empty());
+    }
+}
@@ -10,6 +10,7 @@
 //!
 //! Most of the interesting work happens at the local layer, as bulk of the complexity is with managing the lifecycle of language servers. The actual implementation of the LSP protocol is handled by [`lsp`] crate.
 pub mod clangd_ext;
+pub mod extension_tools;
 pub mod json_language_server_ext;
 pub mod log_store;
 pub mod lsp_ext_command;
@@ -31,6 +32,7 @@ use crate::{
         LanguageServerTree, LanguageServerTreeNode, LaunchDisposition, ManifestQueryDelegate,
         ManifestTree,
     },
+    organize_imports,
     prettier_store::{self, PrettierStore, PrettierStoreEvent},
     project_settings::{LspSettings, ProjectSettings},
     toolchain_store::{LocalToolchainStore, ToolchainStoreEvent},
@@ -222,6 +224,9 @@ pub struct LocalLspStore {
     registered_buffers: HashMap<BufferId, usize>,
     buffers_opened_in_servers: HashMap<BufferId, HashSet<LanguageServerId>>,
     buffer_pull_diagnostics_result_ids: HashMap<LanguageServerId, HashMap<PathBuf, Option<String>>>,
+    /// The IDs under which the diagnostics of the linters provided by extensions are stored.
+    extension_linter_server_ids: HashMap<Arc<str>, LanguageServerId>,
+    extension_lint_tasks: HashMap<(BufferId, Arc<str>), Task<()>>,
 }
 
 impl LocalLspStore {
@@ -1157,6 +1162,7 @@ impl LocalLspStore {
                         .collect::<Vec<_>>()
                 })
             })?;
+            let mut found_actions = false;
             for (_, language_server) in adapters_and_servers.iter() {
                 let actions = Self::get_server_code_actions_from_action_kinds(
                     &lsp_store,
@@ -1166,6 +1172,7 @@ impl LocalLspStore {
                     cx,
                 )
                 .await?;
+                found_actions |= !actions.is_empty();
                 Self::execute_code_actions_on_server(
                     &lsp_store,
                     language_server,
@@ -1176,6 +1183,26 @@ impl LocalLspStore {
                 )
                 .await?;
             }
+
+            if !found_actions && kind == CodeActionKind::SOURCE_ORGANIZE_IMPORTS {
+                buffer.update(cx, |buffer, cx| {
+                    let edits = organize_imports::organize_buffer_imports(buffer);
+                    if edits.is_empty() {
+                        return;
+                    }
+                    buffer.finalize_last_transaction();
+                    buffer.start_transaction();
+                    buffer.edit(edits, None, cx);
+                    if buffer.end_transaction(cx).is_some()
+                        && let Some(transaction) = buffer.finalize_last_transaction().cloned()
+                    {
+                        if !push_to_history {
+                            buffer.forget_transaction(transaction.id);
+                        }
+                        project_transaction.0.insert(cx.entity(), transaction);
+                    }
+                })?;
+            }
         }
         Ok(project_transaction)
     }
@@ -1443,6 +1470,30 @@ impl LocalLspStore {
                         },
                     )?;
                 }
+                Formatter::Extension { name } => {
+                    let logger = zlog::scoped!(logger => "extension");
+                    zlog::trace!(logger => "formatting");
+                    let _timer = zlog::time!(logger => "Formatting buffer via extension");
+
+                    let diff = LspStore::format_via_extension(&lsp_store, buffer, name, cx)
+                        .await
+                        .with_context(|| {
+                            format!("Failed to format buffer via extension formatter: {}", name)
+                        })?;
+                    let Some(diff) = diff else {
+                        zlog::trace!(logger => "No changes");
+                        continue;
+                    };
+
+                    extend_formatting_transaction(
+                        buffer,
+                        formatting_transaction_id,
+                        cx,
+                        |buffer, cx| {
+                            buffer.apply_diff(diff, cx);
+                        },
+                    )?;
+                }
                 Formatter::LanguageServer { name } => {
                     let logger = zlog::scoped!(logger => "language-server");
                     zlog::trace!(logger => "formatting");
@@ -1568,7 +1619,24 @@ impl LocalLspStore {
                     }
 
                     if actions_and_servers.is_empty() {
-                        zlog::warn!(logger => "No code actions were resolved, continuing");
+                        if code_action_kind == CodeActionKind::SOURCE_ORGANIZE_IMPORTS {
+                            zlog::trace!(logger => "No code actions were resolved, organizing imports without a language server");
+                            let edits = buffer.handle.read_with(cx, |buffer, _| {
+                                organize_imports::organize_buffer_imports(buffer)
+                            })?;
+                            if !edits.is_empty() {
+                                extend_formatting_transaction(
+                                    buffer,
+                                    formatting_transaction_id,
+                                    cx,
+                                    |buffer, cx| {
+                                        buffer.edit(edits, None, cx);
+                                    },
+                                )?;
+                            }
+                        } else {
+                            zlog::warn!(logger => "No code actions were resolved, continuing");
+                        }
                         continue;
                     }
 
@@ -2342,7 +2410,8 @@ impl LocalLspStore {
         let Some(file) = File::from_dyn(buffer.file()) else {
             return;
         };
-        if !file.is_local() {
+        // Language servers can't run on hosts only reachable over SFTP.
+        if !file.is_local() || self.fs.file_transfer_host().is_some() {
             return;
         }
 
@@ -3759,6 +3828,8 @@ impl LspStore {
                 registered_buffers: HashMap::default(),
                 buffers_opened_in_servers: HashMap::default(),
                 buffer_pull_diagnostics_result_ids: HashMap::default(),
+                extension_linter_server_ids: HashMap::default(),
+                extension_lint_tasks: HashMap::default(),
                 watched_manifest_filenames: ManifestProvidersStore::global(cx)
                     .manifest_file_names(),
             }),
@@ -3960,13 +4031,19 @@ impl LspStore {
     ) {
         match event {
             language::BufferEvent::Edited => {
+                self.lint_via_extensions(&buffer, true, cx);
                 self.on_buffer_edited(buffer, cx);
             }
 
             language::BufferEvent::Saved => {
+                self.lint_via_extensions(&buffer, false, cx);
                 self.on_buffer_saved(buffer, cx);
             }
 
+            language::BufferEvent::LanguageChanged => {
+                self.lint_via_extensions(&buffer, false, cx);
+            }
+
             _ => {}
         }
     }
@@ -8870,6 +8947,70 @@ impl LspStore {
         self.as_local()?.language_server_for_id(id)
     }
 
+    /// Requests the edit that renaming the symbol at `position` would make, without applying it,
+    /// along with the language server that returned it. Only local projects are supported.
+    pub fn rename_edit(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: PointUtf16,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<(LanguageServerId, lsp::WorkspaceEdit)>>> {
+        let Some(abs_path) = File::from_dyn(buffer.read(cx).file()).map(|file| file.abs_path(cx))
+        else {
+            return Task::ready(Ok(None));
+        };
+        let server = buffer.update(cx, |buffer, cx| {
+            self.language_servers_for_local_buffer(buffer, cx)
+                .map(|(_, server)| server.clone())
+                .find(|server| {
+                    server
+                        .capabilities()
+                        .rename_provider
+                        .is_some_and(|provider| provider != OneOf::Left(false))
+                })
+        });
+        let Some(server) = server else {
+            return Task::ready(Ok(None));
+        };
+
+        cx.background_spawn(async move {
+            let params = lsp::RenameParams {
+                text_document_position: lsp::TextDocumentPositionParams {
+                    text_document: lsp::TextDocumentIdentifier::new(file_path_to_lsp_url(
+                        &abs_path,
+                    )?),
+                    position: point_to_lsp(position),
+                },
+                new_name,
+                work_done_progress_params: Default::default(),
+            };
+            let edit = server
+                .request::<lsp::request::Rename>(params)
+                .await
+                .into_response()?;
+            Ok(edit.map(|edit| (server.server_id(), edit)))
+        })
+    }
+
+    /// Applies a workspace edit returned by the given language server, such as a rename edit
+    /// that was previewed. Only local projects are supported.
+    pub fn apply_workspace_edit(
+        &mut self,
+        server_id: LanguageServerId,
+        edit: lsp::WorkspaceEdit,
+        push_to_history: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        let Some(server) = self.language_server_for_id(server_id) else {
+            return Task::ready(Err(anyhow!("language server {server_id} not found")));
+        };
+        let this = cx.entity();
+        cx.spawn(async move |_, cx| {
+            LocalLspStore::deserialize_workspace_edit(this, edit, push_to_history, server, cx).await
+        })
+    }
+
     fn on_lsp_progress(
         &mut self,
         progress: lsp::ProgressParams,
@@ -0,0 +1,500 @@
+//! Formatters and linters that extensions provide by running external tools.
+//!
+//! Zed writes the content of the buffer to the tool's standard input. A formatter writes the
+//! formatted content to its standard output, and the output of a linter is passed back to its
+//! extension to be parsed into diagnostics.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use collections::HashMap;
+use extension::{
+    Extension, ExtensionFormatterProxy, ExtensionHostProxy, ExtensionLinterProxy, FormatRequest,
+    FormatterManifestEntry, Lint, LintRequest, LintSeverity, LinterManifestEntry, LinterOutput,
+    WorktreeDelegate,
+};
+use futures::AsyncWriteExt as _;
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, Global, WeakEntity};
+use language::{
+    Buffer, Diagnostic, DiagnosticEntry, DiagnosticSourceKind, Diff, LanguageName, LocalFile as _,
+    LspAdapterDelegate, PointUtf16, Unclipped,
+};
+use lsp::{DiagnosticSeverity, LanguageServerId, LanguageServerName, NumberOrString};
+use text::{Rope, ToPoint as _};
+use util::{ResultExt as _, rel_path::RelPath};
+
+use crate::File;
+
+use super::{FormattableBuffer, LanguageServerStatus, LocalLspAdapterDelegate, LspStore};
+
+pub fn init(cx: &mut App) {
+    let registry = ExtensionToolRegistry::default_global(cx);
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_formatter_proxy(ExtensionToolRegistryProxy {
+        registry: registry.clone(),
+    });
+    proxy.register_linter_proxy(ExtensionToolRegistryProxy { registry });
+}
+
+/// How long to wait after the last edit of a buffer before linting it, for linters that don't
+/// declare their own debounce.
+const DEFAULT_LINT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Clone)]
+struct ExtensionFormatter {
+    extension: Arc<dyn Extension>,
+    id: Arc<str>,
+    manifest_entry: FormatterManifestEntry,
+}
+
+#[derive(Clone)]
+struct ExtensionLinter {
+    extension: Arc<dyn Extension>,
+    id: Arc<str>,
+    manifest_entry: LinterManifestEntry,
+}
+
+/// The formatters and linters provided by the installed extensions.
+#[derive(Default)]
+pub struct ExtensionToolRegistry {
+    formatters: HashMap<Arc<str>, ExtensionFormatter>,
+    linters: HashMap<Arc<str>, ExtensionLinter>,
+}
+
+struct GlobalExtensionToolRegistry(Entity<ExtensionToolRegistry>);
+
+impl Global for GlobalExtensionToolRegistry {}
+
+impl ExtensionToolRegistry {
+    /// Returns the global [`ExtensionToolRegistry`].
+    ///
+    /// Inserts a default [`ExtensionToolRegistry`] if one does not yet exist.
+    pub fn default_global(cx: &mut App) -> Entity<Self> {
+        if !cx.has_global::<GlobalExtensionToolRegistry>() {
+            let registry = cx.new(|_| Self::default());
+            cx.set_global(GlobalExtensionToolRegistry(registry));
+        }
+        cx.global::<GlobalExtensionToolRegistry>().0.clone()
+    }
+
+    fn try_global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalExtensionToolRegistry>()
+            .map(|registry| registry.0.clone())
+    }
+
+    fn linters_for_language(&self, language: &LanguageName) -> Vec<ExtensionLinter> {
+        self.linters
+            .values()
+            .filter(|linter| linter.manifest_entry.languages.contains(language))
+            .cloned()
+            .collect()
+    }
+}
+
+struct ExtensionToolRegistryProxy {
+    registry: Entity<ExtensionToolRegistry>,
+}
+
+impl ExtensionFormatterProxy for ExtensionToolRegistryProxy {
+    fn register_formatter(
+        &self,
+        extension: Arc<dyn Extension>,
+        formatter_id: Arc<str>,
+        formatter: FormatterManifestEntry,
+        cx: &mut App,
+    ) {
+        self.registry.update(cx, |registry, _| {
+            registry.formatters.insert(
+                formatter_id.clone(),
+                ExtensionFormatter {
+                    extension,
+                    id: formatter_id,
+                    manifest_entry: formatter,
+                },
+            );
+        });
+    }
+
+    fn unregister_formatter(&self, formatter_id: Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |registry, _| {
+            registry.formatters.remove(&formatter_id);
+        });
+    }
+}
+
+impl ExtensionLinterProxy for ExtensionToolRegistryProxy {
+    fn register_linter(
+        &self,
+        extension: Arc<dyn Extension>,
+        linter_id: Arc<str>,
+        linter: LinterManifestEntry,
+        cx: &mut App,
+    ) {
+        self.registry.update(cx, |registry, _| {
+            registry.linters.insert(
+                linter_id.clone(),
+                ExtensionLinter {
+                    extension,
+                    id: linter_id,
+                    manifest_entry: linter,
+                },
+            );
+        });
+    }
+
+    fn unregister_linter(&self, linter_id: Arc<str>, cx: &mut App) {
+        self.registry.update(cx, |registry, _| {
+            registry.linters.remove(&linter_id);
+        });
+    }
+}
+
+struct ExtensionToolWorktree(Arc<dyn LspAdapterDelegate>);
+
+#[async_trait]
+impl WorktreeDelegate for ExtensionToolWorktree {
+    fn id(&self) -> u64 {
+        self.0.worktree_id().to_proto()
+    }
+
+    fn root_path(&self) -> String {
+        self.0.worktree_root_path().to_string_lossy().into_owned()
+    }
+
+    async fn read_text_file(&self, path: &RelPath) -> Result<String> {
+        self.0.read_text_file(path).await
+    }
+
+    async fn which(&self, binary_name: String) -> Option<String> {
+        self.0
+            .which(binary_name.as_ref())
+            .await
+            .map(|path| path.to_string_lossy().into_owned())
+    }
+
+    async fn shell_env(&self) -> Vec<(String, String)> {
+        self.0.shell_env().await.into_iter().collect()
+    }
+}
+
+/// Runs the command of a tool with the given text on its standard input.
+///
+/// The command is killed if the returned future is dropped before it completes.
+async fn run_tool(
+    extension: &Arc<dyn Extension>,
+    command: extension::Command,
+    working_dir: PathBuf,
+    buffer_env: Option<&HashMap<String, String>>,
+    text: &Rope,
+) -> Result<std::process::Output> {
+    let mut child =
+        util::command::new_smol_command(extension.path_from_extension(&command.command));
+    if let Some(buffer_env) = buffer_env {
+        child.envs(buffer_env);
+    }
+    let mut child = child
+        .envs(command.env)
+        .args(command.args)
+        .current_dir(working_dir)
+        .stdin(smol::process::Stdio::piped())
+        .stdout(smol::process::Stdio::piped())
+        .stderr(smol::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let stdin = child.stdin.as_mut().context("failed to acquire stdin")?;
+    for chunk in text.chunks() {
+        stdin.write_all(chunk.as_bytes()).await?;
+    }
+    stdin.flush().await?;
+
+    Ok(child.output().await?)
+}
+
+fn diagnostic_severity(severity: LintSeverity) -> DiagnosticSeverity {
+    match severity {
+        LintSeverity::Error => DiagnosticSeverity::ERROR,
+        LintSeverity::Warning => DiagnosticSeverity::WARNING,
+        LintSeverity::Information => DiagnosticSeverity::INFORMATION,
+        LintSeverity::Hint => DiagnosticSeverity::HINT,
+    }
+}
+
+impl LspStore {
+    /// Formats the buffer with the formatter with the given ID, provided by an extension.
+    pub(super) async fn format_via_extension(
+        lsp_store: &WeakEntity<LspStore>,
+        buffer: &FormattableBuffer,
+        formatter_id: &str,
+        cx: &mut AsyncApp,
+    ) -> Result<Option<Diff>> {
+        let formatter = cx
+            .update(|cx| {
+                let registry = ExtensionToolRegistry::try_global(cx)?;
+                registry.read(cx).formatters.get(formatter_id).cloned()
+            })?
+            .with_context(|| format!("no extension provides a formatter named {formatter_id}"))?;
+
+        let (worktree, request, text) = buffer.handle.read_with(cx, |handle, _| {
+            let file = File::from_dyn(handle.file()).context("buffer has no file")?;
+            let language_name = handle.language().context("buffer has no language")?.name();
+            anyhow::ensure!(
+                formatter.manifest_entry.languages.contains(&language_name),
+                "formatter {formatter_id} doesn't support {language_name}"
+            );
+
+            // Formatters that support ranges format the lines spanning all of the ranges.
+            let line_range = formatter
+                .manifest_entry
+                .supports_ranges
+                .then(|| {
+                    let ranges = buffer.ranges.as_ref()?;
+                    let start = ranges
+                        .iter()
+                        .map(|range| range.start.to_point(handle).row)
+                        .min()?;
+                    let end = ranges
+                        .iter()
+                        .map(|range| range.end.to_point(handle).row)
+                        .max()?;
+                    Some(start..end + 1)
+                })
+                .flatten();
+            anyhow::Ok((
+                file.worktree.clone(),
+                FormatRequest {
+                    path: file.path.as_unix_str().to_string(),
+                    language_name: language_name.to_string(),
+                    line_range,
+                },
+                handle.as_rope().clone(),
+            ))
+        })??;
+        let delegate = lsp_store.update(cx, |lsp_store, cx| {
+            let local = lsp_store
+                .as_local()
+                .context("formatting with extensions is only supported in local projects")?;
+            anyhow::Ok(LocalLspAdapterDelegate::from_local_lsp(
+                local, &worktree, cx,
+            ))
+        })??;
+
+        let working_dir = delegate.worktree_root_path().to_path_buf();
+        let command = formatter
+            .extension
+            .formatter_command(
+                formatter.id.clone(),
+                request,
+                Arc::new(ExtensionToolWorktree(delegate)),
+            )
+            .await?;
+        let output = run_tool(
+            &formatter.extension,
+            command,
+            working_dir,
+            buffer.env.as_ref(),
+            &text,
+        )
+        .await?;
+        anyhow::ensure!(
+            output.status.success(),
+            "command failed with exit code {:?}:\nstdout: {}\nstderr: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr),
+        );
+
+        let stdout = String::from_utf8(output.stdout)?;
+        Ok(Some(
+            buffer
+                .handle
+                .update(cx, |buffer, cx| buffer.diff(stdout, cx))?
+                .await,
+        ))
+    }
+
+    /// Lints the buffer with the linters that extensions provide for its language.
+    ///
+    /// Any linting of the buffer that is still running is cancelled. When `debounce` is true,
+    /// linting waits for the buffer to stop being edited first.
+    pub(super) fn lint_via_extensions(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(registry) = ExtensionToolRegistry::try_global(cx) else {
+            return;
+        };
+        let Some(local) = self.as_local() else {
+            return;
+        };
+        let (buffer_id, language_name, file) = {
+            let buffer = buffer.read(cx);
+            let Some(language) = buffer.language() else {
+                return;
+            };
+            let Some(file) = File::from_dyn(buffer.file()) else {
+                return;
+            };
+            (buffer.remote_id(), language.name(), file.clone())
+        };
+
+        let linters = registry.read(cx).linters_for_language(&language_name);
+        if linters.is_empty() {
+            return;
+        }
+
+        let delegate = LocalLspAdapterDelegate::from_local_lsp(local, &file.worktree, cx);
+        let abs_path = file.abs_path(cx);
+        let request = LintRequest {
+            path: file.path.as_unix_str().to_string(),
+            language_name: language_name.to_string(),
+        };
+
+        for linter in linters {
+            let Some(server_id) = self.extension_linter_server_id(&linter.id) else {
+                continue;
+            };
+            let debounce = debounce.then(|| {
+                linter
+                    .manifest_entry
+                    .debounce_ms
+                    .map_or(DEFAULT_LINT_DEBOUNCE, Duration::from_millis)
+            });
+            let key = (buffer_id, linter.id.clone());
+            let task = cx.spawn({
+                let buffer = buffer.clone();
+                let delegate = delegate.clone();
+                let abs_path = abs_path.clone();
+                let request = request.clone();
+                let key = key.clone();
+                async move |this, cx| {
+                    if let Some(debounce) = debounce {
+                        cx.background_executor().timer(debounce).await;
+                    }
+
+                    let lints = Self::run_linter(&linter, request, delegate, &buffer, cx).await;
+                    this.update(cx, |this, cx| {
+                        if let Some(local) = this.as_local_mut() {
+                            local.extension_lint_tasks.remove(&key);
+                        }
+                        let lints = match lints {
+                            Ok(lints) => lints,
+                            Err(error) => {
+                                log::error!(
+                                    "failed to lint {abs_path:?} with {}: {error:#}",
+                                    linter.id
+                                );
+                                return;
+                            }
+                        };
+                        let Some(local) = this.as_local_mut() else {
+                            return;
+                        };
+                        let diagnostics = lints
+                            .into_iter()
+                            .map(|lint| {
+                                let group_id = local.next_diagnostic_group_id;
+                                local.next_diagnostic_group_id += 1;
+                                lint_diagnostic_entry(&linter.id, lint, group_id)
+                            })
+                            .collect();
+                        this.update_diagnostic_entries(
+                            server_id,
+                            abs_path,
+                            None,
+                            None,
+                            diagnostics,
+                            cx,
+                        )
+                        .log_err();
+                    })
+                    .ok();
+                }
+            });
+
+            if let Some(local) = self.as_local_mut() {
+                local.extension_lint_tasks.insert(key, task);
+            }
+        }
+    }
+
+    async fn run_linter(
+        linter: &ExtensionLinter,
+        request: LintRequest,
+        delegate: Arc<LocalLspAdapterDelegate>,
+        buffer: &Entity<Buffer>,
+        cx: &mut AsyncApp,
+    ) -> Result<Vec<Lint>> {
+        let text = buffer.read_with(cx, |buffer, _| buffer.as_rope().clone())?;
+        let working_dir = delegate.worktree_root_path().to_path_buf();
+        let command = linter
+            .extension
+            .linter_command(
+                linter.id.clone(),
+                request.clone(),
+                Arc::new(ExtensionToolWorktree(delegate)),
+            )
+            .await?;
+        let output = run_tool(&linter.extension, command, working_dir, None, &text).await?;
+        linter
+            .extension
+            .parse_linter_output(
+                linter.id.clone(),
+                request,
+                LinterOutput {
+                    stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                    stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                    exit_code: output.status.code(),
+                },
+            )
+            .await
+    }
+
+    /// Returns the ID under which the diagnostics of the linter with the given ID are stored.
+    fn extension_linter_server_id(&mut self, linter_id: &Arc<str>) -> Option<LanguageServerId> {
+        let local = self.as_local_mut()?;
+        if let Some(server_id) = local.extension_linter_server_ids.get(linter_id) {
+            return Some(*server_id);
+        }
+
+        let server_id = local.languages.next_language_server_id();
+        local
+            .extension_linter_server_ids
+            .insert(linter_id.clone(), server_id);
+        self.language_server_statuses.insert(
+            server_id,
+            LanguageServerStatus {
+                name: LanguageServerName(linter_id.to_string().into()),
+                pending_work: Default::default(),
+                has_pending_diagnostic_updates: false,
+                progress_tokens: Default::default(),
+                worktree: None,
+            },
+        );
+        Some(server_id)
+    }
+}
+
+fn lint_diagnostic_entry(
+    linter_id: &str,
+    lint: Lint,
+    group_id: usize,
+) -> DiagnosticEntry<Unclipped<PointUtf16>> {
+    DiagnosticEntry {
+        range: Unclipped(PointUtf16::new(lint.start.line, lint.start.column))
+            ..Unclipped(PointUtf16::new(lint.end.line, lint.end.column)),
+        diagnostic: Diagnostic {
+            source: Some(linter_id.to_string()),
+            code: lint.code.map(NumberOrString::String),
+            severity: diagnostic_severity(lint.severity),
+            message: lint.message,
+            group_id,
+            is_primary: true,
+            source_kind: DiagnosticSourceKind::Other,
+            ..Diagnostic::default()
+        },
+    }
+}
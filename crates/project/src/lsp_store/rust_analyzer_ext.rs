@@ -1,14 +1,198 @@
 use ::serde::{Deserialize, Serialize};
 use anyhow::Context as _;
+use collections::HashSet;
 use gpui::{App, AsyncApp, Entity, Task, WeakEntity};
 use language::{Buffer, ServerHealth};
-use lsp::{LanguageServer, LanguageServerId, LanguageServerName};
+use lsp::{LanguageServer, LanguageServerId, LanguageServerName, LanguageServerSelector};
 use rpc::proto;
+use serde_json::Value;
+use settings::{Settings as _, SettingsContent, SettingsLocation};
+use util::rel_path::RelPath;
 
-use crate::{LspStore, LspStoreEvent, Project, ProjectPath, lsp_store};
+use crate::{
+    LspStore, LspStoreEvent, Project, ProjectPath, WorktreeId, lsp_store,
+    project_settings::ProjectSettings,
+};
 
 pub const RUST_ANALYZER_NAME: LanguageServerName = LanguageServerName::new_static("rust-analyzer");
 pub const CARGO_DIAGNOSTICS_SOURCE_NAME: &str = "rustc";
+const RUSTUP_TOOLCHAIN: &str = "RUSTUP_TOOLCHAIN";
+
+/// Cargo configuration of a worktree, shared by rust-analyzer and the Rust tasks.
+///
+/// Stored in the `lsp.rust-analyzer.initialization_options.cargo` settings, so that
+/// rust-analyzer picks it up without any extra plumbing.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CargoOptions {
+    /// Whether to pass `--all-features`.
+    pub all_features: bool,
+    /// Features to pass with `--features`, unused when `all_features` is set.
+    pub features: Vec<String>,
+    /// Whether to pass `--no-default-features`.
+    pub no_default_features: bool,
+    /// Target triple to pass with `--target`.
+    pub target: Option<String>,
+    /// Toolchain to use, set as `RUSTUP_TOOLCHAIN`.
+    pub toolchain: Option<String>,
+}
+
+impl CargoOptions {
+    pub fn for_worktree(worktree_id: WorktreeId, cx: &App) -> Self {
+        ProjectSettings::get(
+            Some(SettingsLocation {
+                worktree_id,
+                path: RelPath::empty(),
+            }),
+            cx,
+        )
+        .lsp
+        .get(&RUST_ANALYZER_NAME)
+        .and_then(|settings| settings.initialization_options.as_ref())
+        .map(Self::from_initialization_options)
+        .unwrap_or_default()
+    }
+
+    pub fn from_initialization_options(options: &Value) -> Self {
+        let Some(cargo) = options.get("cargo") else {
+            return Self::default();
+        };
+        let (all_features, features) = match cargo.get("features") {
+            Some(Value::String(features)) if features == "all" => (true, Vec::new()),
+            Some(Value::Array(features)) => (
+                false,
+                features
+                    .iter()
+                    .filter_map(|feature| Some(feature.as_str()?.to_owned()))
+                    .collect(),
+            ),
+            _ => (false, Vec::new()),
+        };
+        Self {
+            all_features,
+            features,
+            no_default_features: cargo
+                .get("noDefaultFeatures")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            target: cargo
+                .get("target")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+            toolchain: cargo
+                .get("extraEnv")
+                .and_then(|env| env.get(RUSTUP_TOOLCHAIN))
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+        }
+    }
+
+    /// Writes these options into rust-analyzer's initialization options, leaving unrelated keys untouched.
+    pub fn write_to_initialization_options(&self, options: &mut Value) {
+        if !options.is_object() {
+            *options = Value::Object(Default::default());
+        }
+        let Some(options) = options.as_object_mut() else {
+            return;
+        };
+        let cargo = options
+            .entry("cargo")
+            .or_insert_with(|| Value::Object(Default::default()));
+        if !cargo.is_object() {
+            *cargo = Value::Object(Default::default());
+        }
+        let Some(cargo) = cargo.as_object_mut() else {
+            return;
+        };
+
+        if self.all_features {
+            cargo.insert("features".into(), Value::from("all"));
+        } else if !self.features.is_empty() {
+            cargo.insert("features".into(), Value::from(self.features.clone()));
+        } else {
+            cargo.remove("features");
+        }
+        if self.no_default_features {
+            cargo.insert("noDefaultFeatures".into(), Value::Bool(true));
+        } else {
+            cargo.remove("noDefaultFeatures");
+        }
+        match &self.target {
+            Some(target) => cargo.insert("target".into(), Value::from(target.clone())),
+            None => cargo.remove("target"),
+        };
+
+        let extra_env = cargo
+            .entry("extraEnv")
+            .or_insert_with(|| Value::Object(Default::default()));
+        if let Some(extra_env) = extra_env.as_object_mut() {
+            match &self.toolchain {
+                Some(toolchain) => {
+                    extra_env.insert(RUSTUP_TOOLCHAIN.into(), Value::from(toolchain.clone()))
+                }
+                None => extra_env.remove(RUSTUP_TOOLCHAIN),
+            };
+        }
+        if cargo
+            .get("extraEnv")
+            .and_then(Value::as_object)
+            .is_some_and(|extra_env| extra_env.is_empty())
+        {
+            cargo.remove("extraEnv");
+        }
+        if cargo.is_empty() {
+            options.remove("cargo");
+        }
+    }
+
+    pub fn write_to_settings(&self, settings: &mut SettingsContent) {
+        let lsp_settings = settings
+            .project
+            .lsp
+            .entry(RUST_ANALYZER_NAME.0.as_ref().into())
+            .or_default();
+        let options = lsp_settings
+            .initialization_options
+            .get_or_insert_with(|| Value::Object(Default::default()));
+        self.write_to_initialization_options(options);
+    }
+
+    /// Feature flags to pass to cargo commands that build the crate.
+    pub fn feature_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.all_features {
+            args.push("--all-features".to_owned());
+        } else if !self.features.is_empty() {
+            args.push("--features".to_owned());
+            args.push(self.features.join(","));
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".to_owned());
+        }
+        args
+    }
+
+    pub fn target_args(&self) -> Vec<String> {
+        self.target
+            .iter()
+            .flat_map(|target| ["--target".to_owned(), target.clone()])
+            .collect()
+    }
+
+    pub fn toolchain_env(&self) -> Option<(String, String)> {
+        Some((RUSTUP_TOOLCHAIN.to_owned(), self.toolchain.clone()?))
+    }
+}
+
+/// Restarts the rust-analyzer instance serving the given buffer, e.g. to apply new cargo options.
+pub fn restart_rust_analyzer(project: &Entity<Project>, buffer: Entity<Buffer>, cx: &mut App) {
+    project.update(cx, |project, cx| {
+        project.restart_language_servers_for_buffers(
+            vec![buffer],
+            HashSet::from_iter([LanguageServerSelector::Name(RUST_ANALYZER_NAME)]),
+            cx,
+        );
+    });
+}
 
 /// Experimental: Informs the end user about the state of the server
 ///
@@ -270,3 +454,68 @@ fn find_rust_analyzer_server(
         })
         .ok()?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cargo_options_round_trip() {
+        let mut initialization_options = json!({
+            "checkOnSave": true,
+            "cargo": {
+                "features": ["serde", "tokio"],
+                "extraEnv": { "RUSTFLAGS": "-Dwarnings" }
+            }
+        });
+        let mut options = CargoOptions::from_initialization_options(&initialization_options);
+        assert_eq!(
+            options,
+            CargoOptions {
+                features: vec!["serde".into(), "tokio".into()],
+                ..CargoOptions::default()
+            }
+        );
+        assert_eq!(options.feature_args(), ["--features", "serde,tokio"]);
+
+        options.all_features = true;
+        options.no_default_features = true;
+        options.target = Some("wasm32-unknown-unknown".into());
+        options.toolchain = Some("nightly".into());
+        options.write_to_initialization_options(&mut initialization_options);
+        assert_eq!(
+            initialization_options,
+            json!({
+                "checkOnSave": true,
+                "cargo": {
+                    "features": "all",
+                    "noDefaultFeatures": true,
+                    "target": "wasm32-unknown-unknown",
+                    "extraEnv": { "RUSTFLAGS": "-Dwarnings", "RUSTUP_TOOLCHAIN": "nightly" }
+                }
+            })
+        );
+        assert_eq!(
+            CargoOptions::from_initialization_options(&initialization_options),
+            options
+        );
+        assert_eq!(
+            options.feature_args(),
+            ["--all-features", "--no-default-features"]
+        );
+        assert_eq!(
+            options.target_args(),
+            ["--target", "wasm32-unknown-unknown"]
+        );
+
+        CargoOptions::default().write_to_initialization_options(&mut initialization_options);
+        assert_eq!(
+            initialization_options,
+            json!({
+                "checkOnSave": true,
+                "cargo": { "extraEnv": { "RUSTFLAGS": "-Dwarnings" } }
+            })
+        );
+    }
+}
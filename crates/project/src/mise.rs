@@ -0,0 +1,51 @@
+use crate::environment::EnvironmentErrorMessage;
+
+use {collections::HashMap, std::path::Path, util::ResultExt};
+
+pub async fn load_mise_environment(
+    env: &HashMap<String, String>,
+    dir: &Path,
+) -> Result<HashMap<String, String>, Option<EnvironmentErrorMessage>> {
+    let Ok(mise_path) = which::which("mise") else {
+        return Err(None);
+    };
+
+    let args = &["env", "--json"];
+    let Some(mise_output) = smol::process::Command::new(&mise_path)
+        .args(args)
+        .envs(env)
+        .current_dir(dir)
+        .output()
+        .await
+        .log_err()
+    else {
+        return Err(Some(EnvironmentErrorMessage(String::from(
+            "Failed to run mise. See logs for more info",
+        ))));
+    };
+
+    if !mise_output.status.success() {
+        log::error!(
+            "Loading mise environment failed ({}), stderr: {}",
+            mise_output.status,
+            String::from_utf8_lossy(&mise_output.stderr)
+        );
+        return Err(Some(EnvironmentErrorMessage(String::from(
+            "Failed to run mise. See logs for more info",
+        ))));
+    }
+
+    let output = String::from_utf8_lossy(&mise_output.stdout);
+    serde_json::from_str(&output).map_err(|err| {
+        log::error!(
+            "json parse error {}, while parsing output of `{} {}`:\n{}",
+            err,
+            mise_path.display(),
+            args.join(" "),
+            output
+        );
+        Some(EnvironmentErrorMessage(String::from(
+            "Mise returned invalid json. See logs for more info",
+        )))
+    })
+}
@@ -0,0 +1,430 @@
+//! Sorts import statements for languages whose language servers don't organize imports.
+//!
+//! Only runs of adjacent import statements are reordered, so that blank lines keep separating
+//! groups of imports, and statements whose order matters, such as JavaScript's side-effect
+//! imports, are left in place.
+
+use std::{cmp::Ordering, ops::Range};
+
+use language::{Buffer, LanguageName};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ImportLanguage {
+    Rust,
+    JavaScript,
+    Python,
+}
+
+impl ImportLanguage {
+    fn for_language(name: &LanguageName) -> Option<Self> {
+        match name.as_ref() {
+            "Rust" => Some(Self::Rust),
+            "TypeScript" | "TSX" | "JavaScript" => Some(Self::JavaScript),
+            "Python" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn is_import_start(self, line: &str) -> bool {
+        let line = line.trim_start();
+        match self {
+            Self::Rust => {
+                let line = match line.strip_prefix("pub") {
+                    Some(rest) if rest.starts_with('(') => {
+                        rest.find(')').map_or("", |ix| &rest[ix + 1..])
+                    }
+                    Some(rest) => rest,
+                    None => return line.starts_with("use "),
+                };
+                line.starts_with(char::is_whitespace) && line.trim_start().starts_with("use ")
+            }
+            // Side-effect imports run code, so they must not be reordered.
+            Self::JavaScript => {
+                line.strip_prefix("import")
+                    .is_some_and(|rest| rest.starts_with([' ', '{', '*']))
+                    && !line["import".len()..].trim_start().starts_with(['\'', '"'])
+            }
+            Self::Python => line.starts_with("import ") || line.starts_with("from "),
+        }
+    }
+
+    /// Lines that belong to the import statement after them.
+    fn is_attached_line(self, line: &str) -> bool {
+        let line = line.trim_start();
+        match self {
+            Self::Rust => line.starts_with("//") || line.starts_with("#["),
+            Self::JavaScript => line.starts_with("//"),
+            Self::Python => line.starts_with('#'),
+        }
+    }
+
+    /// Returns the index of the line that ends the import statement starting at the given line.
+    fn statement_end(self, lines: &[(Range<usize>, &str)], start: usize) -> Option<usize> {
+        match self {
+            Self::Rust => {
+                let mut depth = 0;
+                for (ix, (_, line)) in lines.iter().enumerate().skip(start) {
+                    for c in line.chars() {
+                        match c {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            ';' if depth == 0 => return Some(ix),
+                            _ => {}
+                        }
+                    }
+                }
+                None
+            }
+            // The module specifier is the statement's first string.
+            Self::JavaScript => (start..lines.len()).find(|ix| lines[*ix].1.contains(['\'', '"'])),
+            Self::Python => {
+                let mut is_parenthesized = false;
+                for (ix, (_, line)) in lines.iter().enumerate().skip(start) {
+                    let line = line.split('#').next().unwrap_or_default().trim_end();
+                    is_parenthesized |= line.contains('(');
+                    if is_parenthesized {
+                        if line.contains(')') {
+                            return Some(ix);
+                        }
+                    } else if !line.ends_with('\\') {
+                        return Some(ix);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    fn sort_key(self, statement: &str) -> (u8, String) {
+        match self {
+            Self::Rust => {
+                let path = statement
+                    .split_once("use ")
+                    .map_or(statement, |(_, path)| path);
+                (0, path.trim_end_matches(';').trim().to_string())
+            }
+            Self::JavaScript => {
+                let specifier = statement
+                    .split(['\'', '"'])
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_lowercase();
+                (u8::from(specifier.starts_with('.')), specifier)
+            }
+            Self::Python => {
+                let mut words = statement.split_whitespace();
+                let keyword = words.next().unwrap_or_default();
+                let module = words.next().unwrap_or_default();
+                let group = match (module, keyword) {
+                    ("__future__", _) => 0,
+                    (_, "import") => 1,
+                    _ => 2,
+                };
+                (group, module.to_lowercase())
+            }
+        }
+    }
+
+    /// Sorts the names imported by a single-line statement.
+    fn sort_names(self, statement: &str) -> String {
+        if statement.contains('\n') {
+            return statement.to_string();
+        }
+        let names_range = match self {
+            Self::Rust | Self::JavaScript => {
+                let (Some(start), Some(end)) = (statement.find('{'), statement.rfind('}')) else {
+                    return statement.to_string();
+                };
+                if statement[start + 1..end].contains(['{', '}']) {
+                    return statement.to_string();
+                }
+                start + 1..end
+            }
+            Self::Python => {
+                let Some(start) = statement.find(" import ").map(|ix| ix + " import ".len()) else {
+                    return statement.to_string();
+                };
+                let end = statement.find('#').unwrap_or(statement.len());
+                let names = &statement[start..end];
+                let names_start = start + names.len() - names.trim_start_matches('(').len();
+                let names_end = start + names.trim_end().trim_end_matches(')').len();
+                if !statement.starts_with("from ") || names_start > names_end {
+                    return statement.to_string();
+                }
+                names_start..names_end
+            }
+        };
+
+        let names = &statement[names_range.clone()];
+        let padding = if names.starts_with(' ') { " " } else { "" };
+        let mut sorted_names = names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+        sorted_names.sort_by(|a, b| compare_names(self, a, b));
+        sorted_names.dedup();
+        format!(
+            "{}{padding}{}{padding}{}",
+            &statement[..names_range.start],
+            sorted_names.join(", "),
+            &statement[names_range.end..]
+        )
+    }
+}
+
+fn compare_names(language: ImportLanguage, a: &str, b: &str) -> Ordering {
+    let key = |name: &str| {
+        let name = name.strip_prefix("type ").unwrap_or(name);
+        let is_self =
+            language == ImportLanguage::Rust && (name == "self" || name.starts_with("self "));
+        (!is_self, name.to_lowercase(), name.to_string())
+    };
+    key(a).cmp(&key(b))
+}
+
+/// Returns the edits that organize the buffer's imports, if its language is supported.
+pub(crate) fn organize_buffer_imports(buffer: &Buffer) -> Vec<(Range<usize>, String)> {
+    buffer.language().map_or_else(Vec::new, |language| {
+        organize_imports(&buffer.text(), &language.name())
+    })
+}
+
+/// Returns the edits that sort and deduplicate each run of adjacent import statements.
+fn organize_imports(text: &str, language: &LanguageName) -> Vec<(Range<usize>, String)> {
+    let Some(language) = ImportLanguage::for_language(language) else {
+        return Vec::new();
+    };
+    let lines = text
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start..*offset, line.trim_end_matches(['\n', '\r'])))
+        })
+        .collect::<Vec<_>>();
+    let indentation = |line: &str| line.len() - line.trim_start().len();
+    let line_text = |range: Range<usize>| {
+        if range.is_empty() {
+            return "";
+        }
+        text[lines[range.start].0.start..lines[range.end - 1].0.end].trim_end_matches(['\n', '\r'])
+    };
+
+    let mut edits = Vec::new();
+    let mut row = 0;
+    while row < lines.len() {
+        // Attributes before a run's first statement belong to it, while comments are more
+        // likely to describe the whole run, or the file.
+        let run_start = row;
+        let mut statement_start = row;
+        while statement_start < lines.len()
+            && language == ImportLanguage::Rust
+            && lines[statement_start].1.trim_start().starts_with("#[")
+        {
+            statement_start += 1;
+        }
+        if statement_start == lines.len() || !language.is_import_start(lines[statement_start].1) {
+            row += 1;
+            continue;
+        }
+
+        let indent = indentation(lines[statement_start].1);
+        let mut statements = Vec::new();
+        let mut attached_start = run_start;
+        let mut run_end = run_start;
+        while let Some(end) = language.statement_end(&lines, statement_start) {
+            let statement = line_text(statement_start..end + 1);
+            statements.push((
+                language.sort_key(statement),
+                line_text(attached_start..statement_start).to_string(),
+                language.sort_names(statement),
+            ));
+            run_end = end + 1;
+
+            attached_start = run_end;
+            statement_start = run_end;
+            while statement_start < lines.len()
+                && indentation(lines[statement_start].1) == indent
+                && language.is_attached_line(lines[statement_start].1)
+            {
+                statement_start += 1;
+            }
+            if statement_start == lines.len()
+                || indentation(lines[statement_start].1) != indent
+                || !language.is_import_start(lines[statement_start].1)
+            {
+                break;
+            }
+        }
+        if run_end == run_start {
+            row += 1;
+            continue;
+        }
+
+        statements.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        statements.dedup_by(|(_, prefix, a), (_, _, b)| a == b && prefix.is_empty());
+        let new_text = statements
+            .into_iter()
+            .flat_map(|(_, prefix, statement)| {
+                (!prefix.is_empty())
+                    .then_some(prefix)
+                    .into_iter()
+                    .chain([statement])
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let old_range = lines[run_start].0.start..lines[run_end - 1].0.end;
+        let old_text = line_text(run_start..run_end);
+        if new_text != old_text {
+            edits.push((old_range.start..old_range.start + old_text.len(), new_text));
+        }
+        row = run_end;
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn organize(text: &str, language: &str) -> String {
+        let mut text = text.to_string();
+        for (range, new_text) in organize_imports(&text, &language.into()).into_iter().rev() {
+            text.replace_range(range, &new_text);
+        }
+        text
+    }
+
+    #[test]
+    fn test_organize_rust_imports() {
+        assert_eq!(
+            organize(
+                indoc! {"
+                    //! Crate docs.
+
+                    use std::{sync::Arc, ops::Range};
+                    use anyhow::Result;
+                    #[cfg(test)]
+                    use gpui::TestAppContext;
+                    use collections::{
+                        HashMap,
+                        BTreeMap,
+                    };
+                    use anyhow::Result;
+
+                    use crate::{self as project, Project};
+                    pub(crate) use super::Item;
+
+                    fn main() {}
+                "},
+                "Rust"
+            ),
+            indoc! {"
+                //! Crate docs.
+
+                use anyhow::Result;
+                use collections::{
+                    HashMap,
+                    BTreeMap,
+                };
+                #[cfg(test)]
+                use gpui::TestAppContext;
+                use std::{ops::Range, sync::Arc};
+
+                use crate::{self as project, Project};
+                pub(crate) use super::Item;
+
+                fn main() {}
+            "}
+        );
+    }
+
+    #[test]
+    fn test_organize_javascript_imports() {
+        assert_eq!(
+            organize(
+                indoc! {"
+                    'use client';
+                    import { useState, type FC, useEffect } from 'react';
+                    import { format } from './format';
+                    import './styles.css';
+                    import {
+                        b,
+                        a,
+                    } from \"zod\";
+                    import Button from '../components/button';
+                    import * as path from 'path';
+
+                    export const x = 1;
+                "},
+                "TypeScript"
+            ),
+            indoc! {"
+                'use client';
+                import { type FC, useEffect, useState } from 'react';
+                import { format } from './format';
+                import './styles.css';
+                import * as path from 'path';
+                import {
+                    b,
+                    a,
+                } from \"zod\";
+                import Button from '../components/button';
+
+                export const x = 1;
+            "}
+        );
+    }
+
+    #[test]
+    fn test_organize_python_imports() {
+        assert_eq!(
+            organize(
+                indoc! {"
+                    \"\"\"Module docs.\"\"\"
+                    from typing import Optional, Any
+                    import sys
+                    from __future__ import annotations
+                    # Vendored
+                    from acme import (
+                        widget,
+                        gadget,
+                    )
+                    import os
+
+                    def main():
+                        import json
+                        from b import x
+                        from a import y
+                "},
+                "Python"
+            ),
+            indoc! {"
+                \"\"\"Module docs.\"\"\"
+                from __future__ import annotations
+                import os
+                import sys
+                # Vendored
+                from acme import (
+                    widget,
+                    gadget,
+                )
+                from typing import Any, Optional
+
+                def main():
+                    import json
+                    from a import y
+                    from b import x
+            "}
+        );
+    }
+
+    #[test]
+    fn test_organized_imports_are_unchanged() {
+        let text = "import os\nimport sys\n\nprint(os.sep)\n";
+        assert!(organize_imports(text, &"Python".into()).is_empty());
+        assert!(organize_imports("use a::b;\nuse c::d;\n", &"Shell Script".into()).is_empty());
+    }
+}
@@ -1,18 +1,22 @@
 pub mod agent_server_store;
+pub mod auto_imports;
 pub mod buffer_store;
 mod color_extractor;
 pub mod connection_manager;
 pub mod context_server_store;
 pub mod debounced_delay;
 pub mod debugger;
+mod detected_tasks;
 pub mod git_store;
 pub mod image_store;
 pub mod lsp_command;
 pub mod lsp_store;
 mod manifest_tree;
+mod organize_imports;
 pub mod prettier_store;
 pub mod project_settings;
 pub mod search;
+mod task_env;
 mod task_inventory;
 pub mod task_store;
 pub mod terminals;
@@ -24,6 +28,7 @@ mod project_tests;
 
 mod direnv;
 mod environment;
+mod mise;
 use buffer_diff::BufferDiff;
 use context_server_store::ContextServerStore;
 pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent};
@@ -37,6 +42,7 @@ use task::Shell;
 
 use crate::{
     agent_server_store::AllAgentServersSettings,
+    auto_imports::AutoImports,
     git_store::GitStore,
     lsp_store::{SymbolLocation, log_store::LogKind},
 };
@@ -137,9 +143,10 @@ pub use fs::*;
 pub use language::Location;
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
+pub use task_env::task_secret_url;
 pub use task_inventory::{
     BasicContextProvider, ContextProviderWithTasks, DebugScenarioContext, Inventory, TaskContexts,
-    TaskSourceKind,
+    TaskRunStatus, TaskSourceKind, is_task_problems_server,
 };
 
 pub use buffer_store::ProjectTransaction;
@@ -211,6 +218,7 @@ pub struct Project {
     search_included_history: SearchHistory,
     search_excluded_history: SearchHistory,
     snippets: Entity<SnippetProvider>,
+    auto_imports: Option<Entity<AutoImports>>,
     environment: Entity<ProjectEnvironment>,
     settings_observer: Entity<SettingsObserver>,
     toolchain_store: Option<Entity<ToolchainStore>>,
@@ -1032,6 +1040,7 @@ impl Project {
         DapStore::init(&client, cx);
         BreakpointStore::init(&client);
         context_server_store::init(cx);
+        lsp_store::extension_tools::init(cx);
     }
 
     pub fn local(
@@ -1051,6 +1060,8 @@ impl Project {
             let worktree_store = cx.new(|_| WorktreeStore::local(false, fs.clone()));
             cx.subscribe(&worktree_store, Self::on_worktree_store_event)
                 .detach();
+            let auto_imports =
+                cx.new(|cx| AutoImports::new(worktree_store.clone(), fs.clone(), cx));
 
             let weak_self = cx.weak_entity();
             let context_server_store =
@@ -1174,6 +1185,7 @@ impl Project {
                 _subscriptions: vec![cx.on_release(Self::release)],
                 active_entry: None,
                 snippets,
+                auto_imports: Some(auto_imports),
                 languages,
                 collab_client: client,
                 task_store,
@@ -1361,6 +1373,7 @@ impl Project {
                 ],
                 active_entry: None,
                 snippets,
+                auto_imports: None,
                 languages,
                 collab_client: client,
                 task_store,
@@ -1606,6 +1619,7 @@ impl Project {
                 user_store: user_store.clone(),
                 task_store,
                 snippets,
+                auto_imports: None,
                 fs,
                 remote_client: None,
                 settings_observer: settings_observer.clone(),
@@ -1982,6 +1996,11 @@ impl Project {
         &self.snippets
     }
 
+    /// The index of symbols that completions can import, which only local projects have.
+    pub fn auto_imports(&self) -> Option<&Entity<AutoImports>> {
+        self.auto_imports.as_ref()
+    }
+
     pub fn search_history(&self, kind: SearchInputKind) -> &SearchHistory {
         match kind {
             SearchInputKind::Query => &self.search_history,
@@ -2538,6 +2557,36 @@ impl Project {
         }
     }
 
+    /// The host the files are transferred from when the project was opened over SFTP. Language
+    /// servers, tasks and terminals are unavailable in such projects.
+    pub fn file_transfer_host(&self) -> Option<String> {
+        if self.is_local() {
+            self.fs.file_transfer_host()
+        } else {
+            None
+        }
+    }
+
+    /// Translates an absolute path in this project to one that the local system's file manager
+    /// and default apps can open, if they can access it at all.
+    pub fn system_path(&self, abs_path: &Path, cx: &App) -> Option<PathBuf> {
+        if self.is_local() {
+            return self
+                .file_transfer_host()
+                .is_none()
+                .then(|| abs_path.to_path_buf());
+        }
+        match self.remote_connection_options(cx)? {
+            RemoteConnectionOptions::Wsl(options) if cfg!(target_os = "windows") => {
+                Some(PathBuf::from(util::paths::wsl_path_to_windows(
+                    &abs_path.to_string_lossy().replace('\\', "/"),
+                    &options.distro_name,
+                )))
+            }
+            _ => None,
+        }
+    }
+
     pub fn create_buffer(
         &mut self,
         searchable: bool,
@@ -2621,6 +2670,13 @@ impl Project {
         })
     }
 
+    /// Opens the file at `project_path` as text from now on, even if it's too large or binary.
+    pub fn skip_file_heuristics(&mut self, project_path: ProjectPath, cx: &mut App) {
+        self.buffer_store.update(cx, |buffer_store, _| {
+            buffer_store.skip_file_heuristics(project_path)
+        });
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn open_buffer_with_lsp(
         &mut self,
@@ -3517,6 +3573,37 @@ impl Project {
             .update(cx, |image_store, cx| image_store.reload_images(images, cx))
     }
 
+    pub fn save_image(&self, image: Entity<ImageItem>, cx: &mut Context<Self>) -> Task<Result<()>> {
+        self.image_store
+            .update(cx, |image_store, cx| image_store.save_image(image, cx))
+    }
+
+    /// Loads the version of `image` in git's HEAD, or `None` if the image isn't in a repository
+    /// or isn't committed.
+    pub fn load_committed_image(
+        &self,
+        image: &Entity<ImageItem>,
+        cx: &mut App,
+    ) -> Task<Result<Option<image_store::CommittedImage>>> {
+        let project_path = image.read(cx).project_path(cx);
+        let Some((repository, repo_path)) = self
+            .git_store
+            .read(cx)
+            .repository_and_path_for_project_path(&project_path, cx)
+        else {
+            return Task::ready(Ok(None));
+        };
+        let load_bytes = repository.update(cx, |repository, _| {
+            repository.load_committed_bytes(repo_path)
+        });
+        cx.background_spawn(async move {
+            let Some(content) = load_bytes.await?? else {
+                return Ok(None);
+            };
+            image_store::CommittedImage::decode(&content).map(Some)
+        })
+    }
+
     pub fn format(
         &mut self,
         buffers: HashSet<Entity<Buffer>>,
@@ -3853,6 +3940,36 @@ impl Project {
         )
     }
 
+    /// Requests the edit that renaming the symbol at `position` would make, without applying it,
+    /// so that it can be previewed. Returns `None` for remote projects.
+    pub fn rename_edit<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        new_name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<(LanguageServerId, lsp::WorkspaceEdit)>>> {
+        if !self.is_local() {
+            return Task::ready(Ok(None));
+        }
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.rename_edit(buffer, position, new_name, cx)
+        })
+    }
+
+    pub fn apply_workspace_edit(
+        &mut self,
+        server_id: LanguageServerId,
+        edit: lsp::WorkspaceEdit,
+        push_to_history: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.apply_workspace_edit(server_id, edit, push_to_history, cx)
+        })
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -5158,6 +5275,47 @@ impl Project {
         })
     }
 
+    /// Whether any language server for the buffer advertises code actions of the given kind, or
+    /// of a kind that contains it or that it contains.
+    pub fn any_language_server_supports_code_action_kind(
+        &self,
+        buffer: &Buffer,
+        kind: &lsp::CodeActionKind,
+        cx: &App,
+    ) -> bool {
+        let Some(language) = buffer.language() else {
+            return false;
+        };
+        let lsp_store = self.lsp_store.read(cx);
+        let relevant_language_servers = lsp_store
+            .languages
+            .lsp_adapters(&language.name())
+            .into_iter()
+            .map(|lsp_adapter| lsp_adapter.name())
+            .collect::<HashSet<_>>();
+        let is_related_kind = |advertised: &lsp::CodeActionKind| {
+            let (advertised, kind) = (advertised.as_str(), kind.as_str());
+            advertised == kind
+                || kind.starts_with(&format!("{advertised}."))
+                || advertised.starts_with(&format!("{kind}."))
+        };
+        lsp_store
+            .language_server_statuses()
+            .filter_map(|(server_id, server_status)| {
+                relevant_language_servers
+                    .contains(&server_status.name)
+                    .then_some(server_id)
+            })
+            .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+            .any(|capabilities| match &capabilities.code_action_provider {
+                Some(lsp::CodeActionProviderCapability::Options(options)) => options
+                    .code_action_kinds
+                    .as_ref()
+                    .is_some_and(|kinds| kinds.iter().any(is_related_kind)),
+                _ => false,
+            })
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use collections::HashMap;
+use collections::{BTreeMap, HashMap};
 use context_server::ContextServerCommand;
 use dap::adapters::DebugAdapterName;
 use fs::Fs;
@@ -70,6 +70,12 @@ pub struct ProjectSettings {
     /// Configuration for how direnv configuration should be loaded
     pub load_direnv: DirenvSettings,
 
+    /// Whether to load the environment of mise for the project
+    pub load_mise: bool,
+
+    /// Environment variables to set for language servers, formatters and tasks
+    pub project_env: BTreeMap<String, String>,
+
     /// Configuration for session-related features
     pub session: SessionSettings,
 }
@@ -421,6 +427,19 @@ pub struct InlineDiagnosticsSettings {
     pub min_column: u32,
 
     pub max_severity: Option<DiagnosticSeverity>,
+    /// Where to show inline diagnostics.
+    ///
+    /// Default: end_of_line
+    pub mode: settings::InlineDiagnosticsMode,
+    /// Whether to only show the inline diagnostics of the lines with a cursor.
+    ///
+    /// Default: false
+    pub current_line_only: bool,
+    /// The maximum width of an inline diagnostic in columns. Longer messages
+    /// are truncated.
+    ///
+    /// Default: None
+    pub max_width: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
@@ -511,11 +530,16 @@ impl Settings for ProjectSettings {
                     padding: inline_diagnostics.padding.unwrap(),
                     min_column: inline_diagnostics.min_column.unwrap(),
                     max_severity: inline_diagnostics.max_severity.map(Into::into),
+                    mode: inline_diagnostics.mode.unwrap(),
+                    current_line_only: inline_diagnostics.current_line_only.unwrap(),
+                    max_width: inline_diagnostics.max_width,
                 },
             },
             git: git_settings,
             node: content.node.clone().unwrap().into(),
             load_direnv: project.load_direnv.clone().unwrap(),
+            load_mise: project.load_mise.unwrap(),
+            project_env: project.project_env.clone().unwrap(),
             session: SessionSettings {
                 restore_unsaved_buffers: content.session.unwrap().restore_unsaved_buffers.unwrap(),
             },
@@ -296,6 +296,45 @@ async fn test_git_provider_project_setting(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_project_env_setting(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            ".zed": {
+                "settings.json": r#"{
+                    "project_env": {
+                        "RUSTUP_TOOLCHAIN": "nightly",
+                        "TOOLS_DIR": "$ZED_WORKTREE_ROOT/tools",
+                        "UNKNOWN": "${NOT_SET}"
+                    }
+                }"#
+            },
+            "main.rs": "fn main() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let worktree = project.update(cx, |project, cx| project.worktrees(cx).next().unwrap());
+    cx.executor().run_until_parked();
+
+    let environment = project
+        .update(cx, |project, cx| {
+            project.environment().update(cx, |environment, cx| {
+                environment.get_worktree_environment(worktree, cx)
+            })
+        })
+        .await
+        .unwrap();
+    assert_eq!(environment["RUSTUP_TOOLCHAIN"], "nightly");
+    assert_eq!(environment["TOOLS_DIR"], format!("{}/tools", path!("/dir")));
+    assert_eq!(environment["UNKNOWN"], "${NOT_SET}");
+}
+
 #[gpui::test]
 async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3908,6 +3947,136 @@ async fn test_rename_file_to_new_directory(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_read_only_files(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.js": "",
+            "node_modules": {
+                "dep": {
+                    "index.js": "",
+                },
+            },
+        }),
+    )
+    .await;
+    fs.insert_tree(path!("/outside"), json!({ "lib.js": "" }))
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    for (path, expected_locked) in [
+        (path!("/dir/main.js"), false),
+        (path!("/dir/node_modules/dep/index.js"), true),
+        (path!("/outside/lib.js"), true),
+    ] {
+        let buffer = project
+            .update(cx, |project, cx| project.open_local_buffer(path, cx))
+            .await
+            .unwrap();
+        buffer.update(cx, |buffer, cx| {
+            assert_eq!(buffer.is_locked(), expected_locked, "{path}");
+            assert!(!buffer.read_only(), "{path}");
+            buffer.set_locked(false, cx);
+            assert!(!buffer.is_locked());
+        });
+    }
+}
+
+#[gpui::test]
+async fn test_file_heuristics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings
+                    .project
+                    .worktree
+                    .file_heuristics
+                    .get_or_insert_default()
+                    .max_file_size = Some(64);
+            });
+        });
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "fn main() {}\n",
+            "large.txt": "a line of text\n".repeat(10),
+            "data.bin": "PK\u{0}\u{0}",
+            "schema.rs": "// @generated by codegen\n".to_string() + &"struct A;\n".repeat(20),
+            "bundle.js.map": "{}",
+            "bundle": r#"{"version":3,"sources":["a.js"],"mappings":"AAAA"}"#,
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let error = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/large.txt"), cx)
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error
+            .root_cause()
+            .downcast_ref::<buffer_store::ProtectedFileError>(),
+        Some(&buffer_store::ProtectedFileError::TooLarge {
+            size: 150,
+            max_size: 64
+        })
+    );
+    let error = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/data.bin"), cx)
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error
+            .root_cause()
+            .downcast_ref::<buffer_store::ProtectedFileError>(),
+        Some(&buffer_store::ProtectedFileError::Binary)
+    );
+
+    // Once skipped, the heuristics no longer prevent opening the file.
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    project.update(cx, |project, cx| {
+        project.skip_file_heuristics((worktree_id, rel_path("data.bin")).into(), cx)
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/data.bin"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| assert_eq!(buffer.text(), "PK\u{0}\u{0}"));
+
+    for (path, expected_generated) in [
+        (path!("/dir/main.rs"), false),
+        (path!("/dir/schema.rs"), true),
+        (path!("/dir/bundle.js.map"), true),
+        (path!("/dir/bundle"), true),
+    ] {
+        let buffer = project
+            .update(cx, |project, cx| project.open_local_buffer(path, cx))
+            .await
+            .unwrap();
+        buffer.read_with(cx, |buffer, _| {
+            assert_eq!(buffer.is_generated(), expected_generated, "{path}");
+            assert_eq!(buffer.is_locked(), expected_generated, "{path}");
+        });
+    }
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_save_file(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4500,6 +4669,241 @@ async fn test_buffer_deduping(cx: &mut gpui::TestAppContext) {
     assert_eq!(buffer_a_3.entity_id(), buffer_a_id);
 }
 
+#[gpui::test]
+async fn test_buffer_deduping_through_symlinks(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.txt": "a-contents",
+        }),
+    )
+    .await;
+    fs.insert_symlink(path!("/dir/link.txt"), path!("/dir/a.txt").into())
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let buffer_a = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/a.txt"), cx))
+        .await
+        .unwrap();
+    let buffer_link = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/link.txt"), cx))
+        .await
+        .unwrap();
+
+    // The symlink resolves to the file that's already open, so its buffer is reused.
+    assert_eq!(buffer_link.entity_id(), buffer_a.entity_id());
+    buffer_link.update(cx, |buffer, cx| buffer.edit([(0..0, "edited ")], None, cx));
+    assert_eq!(
+        buffer_a.read_with(cx, |buffer, _| buffer.text()),
+        "edited a-contents"
+    );
+}
+
+#[gpui::test]
+async fn test_buffer_deduping_with_case_insensitive_paths(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings(cx, |settings| {
+                settings.project.worktree.case_insensitive_paths = Some(true);
+            });
+        });
+    });
+
+    // The fake file system is case-sensitive, so both casings exist as files, as they would
+    // appear to on a case-insensitive file system.
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.txt": "a-contents",
+            "A.txt": "a-contents",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let buffer_lower = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/a.txt"), cx))
+        .await
+        .unwrap();
+    let buffer_upper = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/A.txt"), cx))
+        .await
+        .unwrap();
+    assert_eq!(buffer_upper.entity_id(), buffer_lower.entity_id());
+}
+
+#[gpui::test]
+async fn test_buffer_deduping_after_rename(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.txt": "a-contents",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let buffer_a = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/a.txt"), cx))
+        .await
+        .unwrap();
+
+    // Once the file is renamed, a new file at its old path gets a buffer of its own.
+    fs.rename(
+        Path::new(path!("/dir/a.txt")),
+        Path::new(path!("/dir/b.txt")),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.run_until_parked();
+    fs.insert_file(path!("/dir/a.txt"), "new-contents".into())
+        .await;
+    cx.run_until_parked();
+
+    let new_buffer_a = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/a.txt"), cx))
+        .await
+        .unwrap();
+    assert_ne!(new_buffer_a.entity_id(), buffer_a.entity_id());
+    assert_eq!(
+        new_buffer_a.read_with(cx, |buffer, _| buffer.text()),
+        "new-contents"
+    );
+
+    // The renamed file is still found through its new path.
+    let buffer_b = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/b.txt"), cx))
+        .await
+        .unwrap();
+    assert_eq!(buffer_b.entity_id(), buffer_a.entity_id());
+}
+
+#[gpui::test]
+async fn test_auto_import_candidates(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "src": {
+                "app.ts": "",
+                "math.ts": "export function add(a: number, b: number) {}\nexport const PI = 3.14;\n",
+                "lib": {
+                    "index.ts": "export default class Parser {}\n",
+                },
+            },
+            "scripts": {
+                "build.py": "def build():\n    pass\n",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    project.read_with(cx, |project, _| project.languages().add(typescript_lang()));
+    let buffer = project
+        .update(cx, |p, cx| {
+            p.open_local_buffer(path!("/dir/src/app.ts"), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    let auto_imports = project.read_with(cx, |project, _| project.auto_imports().unwrap().clone());
+    let candidates = |cx: &mut gpui::TestAppContext| {
+        let mut candidates = auto_imports
+            .update(cx, |auto_imports, cx| auto_imports.candidates(&buffer, cx))
+            .into_iter()
+            .map(|candidate| (candidate.export.name, candidate.module))
+            .collect::<Vec<_>>();
+        candidates.sort();
+        candidates
+    };
+
+    // The worktree is indexed when candidates are first requested.
+    assert!(candidates(cx).is_empty());
+    cx.run_until_parked();
+    assert_eq!(
+        candidates(cx),
+        [
+            ("PI".to_string(), "./math".to_string()),
+            ("Parser".to_string(), "./lib".to_string()),
+            ("add".to_string(), "./math".to_string()),
+        ]
+    );
+
+    // Changed files are reindexed, and removed files are forgotten.
+    fs.save(
+        path!("/dir/src/math.ts").as_ref(),
+        &"export function subtract(a: number, b: number) {}\n".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    fs.remove_file(path!("/dir/src/lib/index.ts").as_ref(), Default::default())
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        candidates(cx),
+        [("subtract".to_string(), "./math".to_string())]
+    );
+}
+
+#[gpui::test]
+async fn test_organize_imports_without_language_server(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "app.ts": "import { b } from './b';\nimport { z, y } from 'lib';\n\nb(y, z);\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    project.read_with(cx, |project, _| project.languages().add(typescript_lang()));
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/app.ts"), cx))
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let transaction = project
+        .update(cx, |project, cx| {
+            project.apply_code_action_kind(
+                HashSet::from_iter([buffer.clone()]),
+                CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                true,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert!(transaction.0.contains_key(&buffer));
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            buffer.text(),
+            "import { y, z } from 'lib';\nimport { b } from './b';\n\nb(y, z);\n"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_buffer_is_dirty(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use credentials_provider::CredentialsProvider;
+use fs::Fs;
+use gpui::AsyncApp;
+use task::SpawnInTerminal;
+
+/// The prefix of the URLs of the keychain entries holding task secrets, followed by the secret's name.
+pub const TASK_SECRET_URL_PREFIX: &str = "zed-task-secret://";
+
+/// Returns the URL of the keychain entry holding the task secret called `name`.
+pub fn task_secret_url(name: &str) -> String {
+    format!("{TASK_SECRET_URL_PREFIX}{name}")
+}
+
+/// Resolves the environment to spawn a task's process with. From the lowest precedence to the highest:
+/// * the variables loaded from the task's `env_files`, in order
+/// * `base_env`, the environment inherited from the Zed CLI and the terminal `env` setting
+/// * the task's `env`, including the project's environment and the task variables
+/// * the task's `secrets`, read from the system keychain
+///
+/// Env files are only loaded from local projects, relative to `cwd`.
+pub(crate) async fn resolve_task_env(
+    base_env: HashMap<String, String>,
+    spawn_task: &SpawnInTerminal,
+    cwd: Option<&Path>,
+    fs: Option<&dyn Fs>,
+    cx: &AsyncApp,
+) -> Result<HashMap<String, String>> {
+    let mut env = HashMap::default();
+    if let Some(fs) = fs {
+        for env_file in &spawn_task.env_files {
+            let path = match cwd {
+                Some(cwd) if env_file.is_relative() => cwd.join(env_file),
+                _ => env_file.clone(),
+            };
+            let content = fs
+                .load(&path)
+                .await
+                .with_context(|| format!("loading env file {path:?}"))?;
+            for entry in dotenvy::from_read_iter(content.as_bytes()) {
+                let (key, value) = entry.with_context(|| format!("parsing env file {path:?}"))?;
+                env.insert(key, value);
+            }
+        }
+    } else if !spawn_task.env_files.is_empty() {
+        log::warn!(
+            "Not loading the env files of task `{}` in a remote project",
+            spawn_task.label
+        );
+    }
+
+    env.extend(base_env);
+    env.extend(spawn_task.env.clone());
+
+    if !spawn_task.secrets.is_empty() {
+        let credentials_provider = cx.update(|cx| <dyn CredentialsProvider>::global(cx))?;
+        for (variable, secret) in &spawn_task.secrets {
+            let (_, value) = credentials_provider
+                .read_credentials(&task_secret_url(secret), cx)
+                .await
+                .with_context(|| format!("reading secret `{secret}` from the keychain"))?
+                .with_context(|| format!("secret `{secret}` is not in the keychain"))?;
+            let value = String::from_utf8(value)
+                .with_context(|| format!("secret `{secret}` is not valid UTF-8"))?;
+            env.insert(variable.clone(), value);
+        }
+    }
+    Ok(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+
+    use super::*;
+
+    #[gpui::test]
+    async fn test_env_files_do_not_override_set_variables(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/project",
+            json!({
+                ".env": "DATABASE_URL=postgres://localhost/dev\nPORT=3000\nDEBUG=1\n",
+                ".env.local": "# local overrides\nPORT=4000\n",
+            }),
+        )
+        .await;
+
+        let spawn_task = SpawnInTerminal {
+            label: "serve".to_string(),
+            env: HashMap::from_iter([("DEBUG".to_string(), "0".to_string())]),
+            env_files: vec![".env".into(), ".env.local".into()],
+            ..SpawnInTerminal::default()
+        };
+        let base_env = HashMap::from_iter([("PATH".to_string(), "/bin".to_string())]);
+        let env = resolve_task_env(
+            base_env,
+            &spawn_task,
+            Some(Path::new("/project")),
+            Some(fs.as_ref()),
+            &cx.to_async(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            env,
+            HashMap::from_iter(
+                [
+                    ("DATABASE_URL", "postgres://localhost/dev"),
+                    ("PORT", "4000"),
+                    ("DEBUG", "0"),
+                    ("PATH", "/bin"),
+                ]
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+            )
+        );
+
+        let missing_file = SpawnInTerminal {
+            env_files: vec!["missing.env".into()],
+            ..spawn_task
+        };
+        assert!(
+            resolve_task_env(
+                HashMap::default(),
+                &missing_file,
+                Some(Path::new("/project")),
+                Some(fs.as_ref()),
+                &cx.to_async(),
+            )
+            .await
+            .is_err()
+        );
+    }
+}
@@ -43,6 +43,43 @@ pub struct Inventory {
     last_scheduled_scenarios: VecDeque<(DebugScenario, DebugScenarioContext)>,
     templates_from_settings: InventoryFor<TaskTemplate>,
     scenarios_from_settings: InventoryFor<DebugScenario>,
+    detected_templates: HashMap<WorktreeId, HashMap<Arc<RelPath>, Vec<TaskTemplate>>>,
+    task_run_statuses: HashMap<TaskId, TaskRunStatus>,
+    task_problems_server_ids: HashMap<String, LanguageServerId>,
+}
+
+/// The outcome of the last run of a scheduled task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Problems found in task output are reported as the diagnostics of a language
+/// server per task, which never refers to a running server. Their ids count
+/// down from this one.
+const FIRST_TASK_PROBLEMS_SERVER_ID: usize = usize::MAX - 1;
+const MAX_TASK_PROBLEMS_SERVERS: usize = 1 << 16;
+
+/// Whether `task` is a language task that runs a `package.json` script, which is
+/// also among the tasks detected in that manifest.
+fn is_detected_package_script(
+    task: &TaskTemplate,
+    worktree_tasks: &[(TaskSourceKind, TaskTemplate)],
+) -> bool {
+    task.tags.iter().any(|tag| tag == "package-script")
+        && worktree_tasks.iter().any(|(kind, detected)| {
+            matches!(kind, TaskSourceKind::Manifest { .. })
+                && detected.cwd == task.cwd
+                && detected.args == task.args
+        })
+}
+
+/// Whether the diagnostics of this language server are problems found in task output.
+pub fn is_task_problems_server(server_id: LanguageServerId) -> bool {
+    (FIRST_TASK_PROBLEMS_SERVER_ID + 1 - MAX_TASK_PROBLEMS_SERVERS..=FIRST_TASK_PROBLEMS_SERVER_ID)
+        .contains(&server_id.0)
 }
 
 impl std::fmt::Debug for Inventory {
@@ -52,6 +89,9 @@ impl std::fmt::Debug for Inventory {
             .field("last_scheduled_scenarios", &self.last_scheduled_scenarios)
             .field("templates_from_settings", &self.templates_from_settings)
             .field("scenarios_from_settings", &self.scenarios_from_settings)
+            .field("detected_templates", &self.detected_templates)
+            .field("task_run_statuses", &self.task_run_statuses)
+            .field("task_problems_server_ids", &self.task_problems_server_ids)
             .finish()
     }
 }
@@ -153,6 +193,11 @@ pub enum TaskSourceKind {
         language_name: SharedString,
         server: LanguageServerId,
     },
+    /// Tasks detected in the worktree's package manifests and build files, such as package.json or a Makefile
+    Manifest {
+        id: WorktreeId,
+        manifest_path: Arc<RelPath>,
+    },
 }
 
 /// A collection of task contexts, derived from the current state of the workspace.
@@ -235,6 +280,9 @@ impl TaskSourceKind {
                 server,
                 language_name,
             } => format!("lsp_{language_name}_{server}"),
+            Self::Manifest { id, manifest_path } => {
+                format!("manifest_{id}_{}", manifest_path.as_unix_str())
+            }
         }
     }
 }
@@ -246,6 +294,9 @@ impl Inventory {
             last_scheduled_scenarios: VecDeque::default(),
             templates_from_settings: InventoryFor::default(),
             scenarios_from_settings: InventoryFor::default(),
+            detected_templates: HashMap::default(),
+            task_run_statuses: HashMap::default(),
+            task_problems_server_ids: HashMap::default(),
         })
     }
 
@@ -275,6 +326,19 @@ impl Inventory {
         self.last_scheduled_scenarios.back()
     }
 
+    /// Returns the debug scenarios configured in the `debug.json` files of the given worktrees, then the global ones.
+    pub fn configured_debug_scenarios(
+        &self,
+        worktree_ids: impl IntoIterator<Item = WorktreeId>,
+    ) -> Vec<(TaskSourceKind, DebugScenario)> {
+        let mut scenarios = Vec::new();
+        for worktree_id in worktree_ids {
+            scenarios.extend(self.worktree_scenarios_from_settings(worktree_id));
+        }
+        scenarios.extend(self.global_debug_scenarios_from_settings());
+        scenarios
+    }
+
     pub fn list_debug_scenarios(
         &self,
         task_contexts: &TaskContexts,
@@ -386,7 +450,10 @@ impl Inventory {
         let global_tasks = self.global_templates_from_settings().collect::<Vec<_>>();
         let mut worktree_tasks = worktree
             .into_iter()
-            .flat_map(|worktree| self.worktree_templates_from_settings(worktree))
+            .flat_map(|worktree| {
+                self.worktree_templates_from_settings(worktree)
+                    .chain(self.detected_templates(worktree))
+            })
             .collect::<Vec<_>>();
 
         let task_source_kind = language.as_ref().map(|language| TaskSourceKind::Language {
@@ -405,12 +472,14 @@ impl Inventory {
             });
         cx.background_spawn(async move {
             if let Some(t) = language_tasks {
-                worktree_tasks.extend(t.await.into_iter().flat_map(|tasks| {
-                    tasks
-                        .0
-                        .into_iter()
-                        .filter_map(|task| Some((task_source_kind.clone()?, task)))
-                }));
+                let language_tasks = t
+                    .await
+                    .into_iter()
+                    .flat_map(|tasks| tasks.0)
+                    .filter(|task| !is_detected_package_script(task, &worktree_tasks))
+                    .filter_map(|task| Some((task_source_kind.clone()?, task)))
+                    .collect::<Vec<_>>();
+                worktree_tasks.extend(language_tasks);
             }
             worktree_tasks.extend(global_tasks);
             worktree_tasks
@@ -489,7 +558,10 @@ impl Inventory {
             });
         let worktree_tasks = worktree
             .into_iter()
-            .flat_map(|worktree| self.worktree_templates_from_settings(worktree))
+            .flat_map(|worktree| {
+                self.worktree_templates_from_settings(worktree)
+                    .chain(self.detected_templates(worktree))
+            })
             .collect::<Vec<_>>();
         let task_contexts = task_contexts.clone();
         cx.background_spawn(async move {
@@ -498,7 +570,9 @@ impl Inventory {
                     templates
                         .0
                         .into_iter()
+                        .filter(|task| !is_detected_package_script(task, &worktree_tasks))
                         .flat_map(|task| Some((task_source_kind.clone()?, task)))
+                        .collect::<Vec<_>>()
                 })
             } else {
                 None
@@ -512,7 +586,9 @@ impl Inventory {
             let new_resolved_tasks = worktree_tasks
                 .flat_map(|(kind, task)| {
                     let id_base = kind.to_id_base();
-                    if let TaskSourceKind::Worktree { id, .. } = &kind {
+                    if let TaskSourceKind::Worktree { id, .. }
+                    | TaskSourceKind::Manifest { id, .. } = &kind
+                    {
                         None.or_else(|| {
                             let (_, _, item_context) =
                                 task_contexts.active_item_context.as_ref().filter(
@@ -528,7 +604,9 @@ impl Inventory {
                             task.resolve_task(&id_base, worktree_context)
                         })
                         .or_else(|| {
-                            if let TaskSourceKind::Worktree { id, .. } = &kind {
+                            if let TaskSourceKind::Worktree { id, .. }
+                            | TaskSourceKind::Manifest { id, .. } = &kind
+                            {
                                 let worktree_context = task_contexts
                                     .other_worktree_contexts
                                     .iter()
@@ -603,6 +681,58 @@ impl Inventory {
         }
     }
 
+    /// Returns the status of the last run of the task with the given id, if it was run.
+    pub fn task_run_status(&self, task_id: &TaskId) -> Option<TaskRunStatus> {
+        self.task_run_statuses.get(task_id).copied()
+    }
+
+    /// Returns the language server that the problems found in the output of the
+    /// task with the given label are reported as, so that each task's run only
+    /// replaces the problems of its own previous runs.
+    pub fn task_problems_server_id(&mut self, task_label: &str) -> LanguageServerId {
+        let next_id = LanguageServerId(
+            FIRST_TASK_PROBLEMS_SERVER_ID
+                - self.task_problems_server_ids.len() % MAX_TASK_PROBLEMS_SERVERS,
+        );
+        *self
+            .task_problems_server_ids
+            .entry(task_label.to_string())
+            .or_insert(next_id)
+    }
+
+    /// Records the status of a task's run, or forgets it when `status` is `None`.
+    pub fn set_task_run_status(
+        &mut self,
+        task_id: TaskId,
+        status: Option<TaskRunStatus>,
+        cx: &mut Context<Self>,
+    ) {
+        let changed = match status {
+            Some(status) => self.task_run_statuses.insert(task_id, status) != Some(status),
+            None => self.task_run_statuses.remove(&task_id).is_some(),
+        };
+        if changed {
+            cx.notify();
+        }
+    }
+
+    /// Returns the scheduled tasks whose last run failed, the most recently scheduled last.
+    pub fn failed_tasks(&self) -> Vec<(TaskSourceKind, ResolvedTask)> {
+        let mut seen = HashSet::default();
+        let mut failed = self
+            .last_scheduled_tasks
+            .iter()
+            .rev()
+            .filter(|(_, task)| {
+                self.task_run_status(&task.id) == Some(TaskRunStatus::Failed)
+                    && seen.insert(task.id.clone())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        failed.reverse();
+        failed
+    }
+
     /// Deletes a resolved task from history, using its id.
     /// A similar may still resurface in `used_and_current_resolved_tasks` when its [`TaskTemplate`] is resolved again.
     pub fn delete_previously_used(&mut self, id: &TaskId) {
@@ -635,6 +765,74 @@ impl Inventory {
         self.templates_from_settings.worktree_scenarios(worktree)
     }
 
+    fn detected_templates(
+        &self,
+        worktree: WorktreeId,
+    ) -> impl '_ + Iterator<Item = (TaskSourceKind, TaskTemplate)> {
+        self.detected_templates
+            .get(&worktree)
+            .into_iter()
+            .flatten()
+            .flat_map(move |(manifest_path, templates)| {
+                templates.iter().map(move |template| {
+                    (
+                        TaskSourceKind::Manifest {
+                            id: worktree,
+                            manifest_path: manifest_path.clone(),
+                        },
+                        template.clone(),
+                    )
+                })
+            })
+    }
+
+    /// Replaces the tasks detected in the manifest at `manifest_path`, e.g. after it was edited or removed.
+    /// Previously used tasks are kept in the history, unless the manifest does not define them anymore.
+    ///
+    /// Returns whether the detected tasks changed.
+    pub(crate) fn update_detected_tasks(
+        &mut self,
+        worktree_id: WorktreeId,
+        manifest_path: Arc<RelPath>,
+        templates: Vec<TaskTemplate>,
+    ) -> bool {
+        let worktree_templates = self.detected_templates.entry(worktree_id).or_default();
+        let previous_templates = worktree_templates
+            .get(&manifest_path)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if previous_templates == templates.as_slice() {
+            return false;
+        }
+
+        self.last_scheduled_tasks
+            .retain(|(kind, resolved_task)| match kind {
+                TaskSourceKind::Manifest {
+                    id,
+                    manifest_path: path,
+                } if *id == worktree_id && *path == manifest_path => templates
+                    .iter()
+                    .any(|template| template == resolved_task.original_task()),
+                _ => true,
+            });
+
+        let worktree_templates = self.detected_templates.entry(worktree_id).or_default();
+        if templates.is_empty() {
+            worktree_templates.remove(&manifest_path);
+        } else {
+            worktree_templates.insert(manifest_path, templates);
+        }
+        true
+    }
+
+    /// Forgets the tasks detected in the manifests of a removed worktree.
+    pub(crate) fn remove_detected_tasks(&mut self, worktree_id: WorktreeId) {
+        self.detected_templates.remove(&worktree_id);
+        self.last_scheduled_tasks.retain(
+            |(kind, _)| !matches!(kind, TaskSourceKind::Manifest { id, .. } if *id == worktree_id),
+        );
+    }
+
     /// Updates in-memory task metadata from the JSON string given.
     /// Will fail if the JSON is not a valid array of objects, but will continue if any object will not parse into a [`TaskTemplate`].
     ///
@@ -831,7 +1029,8 @@ fn task_source_kind_preference(kind: &TaskSourceKind) -> u32 {
         TaskSourceKind::Language { .. } => 1,
         TaskSourceKind::UserInput => 2,
         TaskSourceKind::Worktree { .. } => 3,
-        TaskSourceKind::AbsPath { .. } => 4,
+        TaskSourceKind::Manifest { .. } => 4,
+        TaskSourceKind::AbsPath { .. } => 5,
     }
 }
 
@@ -1095,7 +1294,10 @@ mod tests {
     use pretty_assertions::assert_eq;
     use serde_json::json;
     use settings::SettingsLocation;
-    use std::path::Path;
+    use std::{
+        path::Path,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
     use util::rel_path::rel_path;
 
     use crate::task_store::TaskStore;
@@ -1392,6 +1594,54 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_configured_debug_scenarios(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(|cx| Inventory::new(cx));
+        let worktree_1 = WorktreeId::from_usize(1);
+        let worktree_2 = WorktreeId::from_usize(2);
+        let scenario = |label: &str| {
+            format!(
+                r#"[{{"label": "{label}", "adapter": "CodeLLDB", "request": "launch", "program": "main"}}]"#
+            )
+        };
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_scenarios(
+                    TaskSettingsLocation::Global(Path::new("")),
+                    Some(&scenario("global")),
+                )
+                .unwrap();
+            for (worktree_id, label) in [(worktree_1, "worktree 1"), (worktree_2, "worktree 2")] {
+                inventory
+                    .update_file_based_scenarios(
+                        TaskSettingsLocation::Worktree(SettingsLocation {
+                            worktree_id,
+                            path: rel_path(".zed"),
+                        }),
+                        Some(&scenario(label)),
+                    )
+                    .unwrap();
+            }
+        });
+
+        let labels = |worktree_ids: Vec<WorktreeId>, cx: &mut TestAppContext| {
+            inventory.read_with(cx, |inventory, _| {
+                inventory
+                    .configured_debug_scenarios(worktree_ids)
+                    .into_iter()
+                    .map(|(_, scenario)| scenario.label.to_string())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(labels(vec![], cx), vec!["global"]);
+        assert_eq!(labels(vec![worktree_2], cx), vec!["worktree 2", "global"]);
+        assert_eq!(
+            labels(vec![worktree_1, worktree_2], cx),
+            vec!["worktree 1", "worktree 2", "global"]
+        );
+    }
+
     #[gpui::test]
     async fn test_inventory_static_task_filters(cx: &mut TestAppContext) {
         init_test(cx);
@@ -1543,6 +1793,89 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_failed_tasks(cx: &mut TestAppContext) {
+        init_test(cx);
+        let inventory = cx.update(|cx| Inventory::new(cx));
+        let notifications = Arc::new(AtomicUsize::new(0));
+        cx.update(|cx| {
+            let notifications = notifications.clone();
+            cx.observe(&inventory, move |_, _| {
+                notifications.fetch_add(1, Ordering::SeqCst);
+            })
+            .detach();
+        });
+        let notification_count = || notifications.load(Ordering::SeqCst);
+
+        let kind = TaskSourceKind::UserInput;
+        let [first, second, third] = ["first", "second", "third"].map(|label| {
+            TaskTemplate {
+                label: label.to_string(),
+                command: "echo".to_string(),
+                ..TaskTemplate::default()
+            }
+            .resolve_task(&kind.to_id_base(), &TaskContext::default())
+            .unwrap()
+        });
+        let failed_task_labels = |cx: &mut TestAppContext| {
+            inventory.read_with(cx, |inventory, _| {
+                inventory
+                    .failed_tasks()
+                    .into_iter()
+                    .map(|(_, task)| task.resolved_label)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        inventory.update(cx, |inventory, cx| {
+            for task in [&first, &second, &third, &first] {
+                inventory.task_scheduled(kind.clone(), task.clone());
+                inventory.set_task_run_status(task.id.clone(), Some(TaskRunStatus::Running), cx);
+            }
+        });
+        assert!(failed_task_labels(cx).is_empty());
+
+        inventory.update(cx, |inventory, cx| {
+            inventory.set_task_run_status(first.id.clone(), Some(TaskRunStatus::Failed), cx);
+            inventory.set_task_run_status(second.id.clone(), Some(TaskRunStatus::Succeeded), cx);
+            inventory.set_task_run_status(third.id.clone(), Some(TaskRunStatus::Failed), cx);
+        });
+        assert_eq!(
+            inventory.read_with(cx, |inventory, _| inventory.task_run_status(&second.id)),
+            Some(TaskRunStatus::Succeeded)
+        );
+        assert_eq!(
+            failed_task_labels(cx),
+            vec!["third", "first"],
+            "failed tasks should be listed once each, the most recently scheduled last"
+        );
+
+        inventory.update(cx, |inventory, _| {
+            inventory.task_scheduled(kind.clone(), third.clone());
+        });
+        assert_eq!(failed_task_labels(cx), vec!["first", "third"]);
+
+        let notifications_before = notification_count();
+        inventory.update(cx, |inventory, cx| {
+            inventory.set_task_run_status(first.id.clone(), None, cx);
+        });
+        assert_eq!(notification_count(), notifications_before + 1);
+        inventory.update(cx, |inventory, cx| {
+            inventory.set_task_run_status(first.id.clone(), None, cx);
+            inventory.set_task_run_status(third.id.clone(), Some(TaskRunStatus::Failed), cx);
+        });
+        assert_eq!(
+            notification_count(),
+            notifications_before + 1,
+            "statuses that don't change shouldn't notify"
+        );
+        assert_eq!(
+            inventory.read_with(cx, |inventory, _| inventory.task_run_status(&first.id)),
+            None
+        );
+        assert_eq!(failed_task_labels(cx), vec!["third"]);
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         zlog::init_test();
         TaskStore::init(None);
@@ -4,7 +4,7 @@ use std::{
 };
 
 use anyhow::Context as _;
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use fs::Fs;
 use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Task, WeakEntity};
 use language::{
@@ -15,11 +15,14 @@ use rpc::{AnyProtoClient, TypedEnvelope, proto};
 use settings::{InvalidSettingsError, SettingsLocation};
 use task::{TaskContext, TaskVariables, VariableName};
 use text::{BufferId, OffsetRangeExt};
-use util::ResultExt;
+use util::{ResultExt, rel_path::RelPath};
+use worktree::{PathChange, Snapshot, UpdatedEntriesSet, Worktree};
 
 use crate::{
-    BasicContextProvider, Inventory, ProjectEnvironment, buffer_store::BufferStore,
-    worktree_store::WorktreeStore,
+    BasicContextProvider, Inventory, ProjectEnvironment,
+    buffer_store::BufferStore,
+    detected_tasks::{self, ManifestDirectory, ManifestKind},
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
 // platform-dependent warning
@@ -165,6 +168,8 @@ impl TaskStore {
         environment: Entity<ProjectEnvironment>,
         cx: &mut Context<Self>,
     ) -> Self {
+        cx.subscribe(&worktree_store, Self::on_worktree_store_event)
+            .detach();
         Self::Functional(StoreState {
             mode: StoreMode::Local {
                 downstream_client: None,
@@ -275,9 +280,115 @@ impl TaskStore {
             .map(|json| json.trim())
             .filter(|json| !json.is_empty());
 
-        task_inventory.update(cx, |inventory, _| {
-            inventory.update_file_based_tasks(location, raw_tasks_json)
+        task_inventory.update(cx, |inventory, cx| {
+            let result = inventory.update_file_based_tasks(location, raw_tasks_json);
+            cx.notify();
+            result
+        })
+    }
+
+    fn on_worktree_store_event(
+        &mut self,
+        _: Entity<WorktreeStore>,
+        event: &WorktreeStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            WorktreeStoreEvent::WorktreeAdded(worktree) => {
+                cx.subscribe(worktree, |this, worktree, event, cx| {
+                    if let worktree::Event::UpdatedEntries(changes) = event {
+                        this.update_detected_tasks(&worktree, changes, cx);
+                    }
+                })
+                .detach();
+            }
+            WorktreeStoreEvent::WorktreeRemoved(_, worktree_id) => {
+                if let Some(task_inventory) = self.task_inventory() {
+                    task_inventory.update(cx, |inventory, _| {
+                        inventory.remove_detected_tasks(*worktree_id)
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Detects tasks in the package manifests and build files that changed in the worktree.
+    fn update_detected_tasks(
+        &self,
+        worktree: &Entity<Worktree>,
+        changes: &UpdatedEntriesSet,
+        cx: &mut Context<Self>,
+    ) {
+        let TaskStore::Functional(state) = self else {
+            return;
+        };
+        if !matches!(state.mode, StoreMode::Local { .. }) {
+            return;
+        }
+
+        let worktree = worktree.read(cx);
+        let worktree_id = worktree.id();
+        let mut manifests = Vec::new();
+        for (path, _, change) in changes.iter() {
+            let Some(kind) = ManifestKind::for_path(path) else {
+                continue;
+            };
+            let is_ignored = worktree
+                .entry_for_path(path)
+                .is_none_or(|entry| entry.is_ignored);
+            if change == &PathChange::Removed || is_ignored {
+                manifests.push((path.clone(), None));
+                continue;
+            }
+            let Some(directory) = path.parent() else {
+                continue;
+            };
+            manifests.push((
+                path.clone(),
+                Some((
+                    kind,
+                    worktree.absolutize(path),
+                    directory.into_arc(),
+                    worktree.absolutize(directory),
+                    manifest_directory_files(worktree, directory),
+                )),
+            ));
+        }
+        if manifests.is_empty() {
+            return;
+        }
+
+        let fs = state.worktree_store.read(cx).fs();
+        let task_inventory = state.task_inventory.downgrade();
+        cx.spawn(async move |_, cx| {
+            let mut changed = false;
+            for (manifest_path, manifest) in manifests {
+                let mut templates = Vec::new();
+                if let Some((kind, abs_path, directory, abs_directory, files)) = manifest
+                    && let Some(content) = fs.load(&abs_path).await.log_err()
+                {
+                    let directory = ManifestDirectory {
+                        path: &directory,
+                        abs_path: &abs_directory,
+                        files,
+                    };
+                    templates = detected_tasks::detect_tasks(kind, &content, &directory);
+                }
+                let Ok(updated) = task_inventory.update(cx, |inventory, _| {
+                    inventory.update_detected_tasks(worktree_id, manifest_path, templates)
+                }) else {
+                    return;
+                };
+                changed |= updated;
+            }
+            // Manifests are reported as updated even when only their metadata changed, so
+            // observers like watch tasks are only notified when the detected tasks changed.
+            if changed {
+                task_inventory.update(cx, |_, cx| cx.notify()).ok();
+            }
         })
+        .detach();
     }
 
     pub(super) fn update_user_debug_scenarios(
@@ -300,6 +411,31 @@ impl TaskStore {
     }
 }
 
+/// Lists the files that tasks can be detected from next to a manifest in `directory`.
+fn manifest_directory_files(snapshot: &Snapshot, directory: &RelPath) -> HashSet<String> {
+    let mut files = HashSet::default();
+    let mut subdirectories = vec![directory.into_arc()];
+    for subdirectory in ["src", "src/bin", "examples"] {
+        subdirectories.push(directory.join(RelPath::unix(subdirectory).unwrap()));
+    }
+    for subdirectory in &subdirectories {
+        for entry in snapshot.child_entries(subdirectory) {
+            let Ok(path) = entry.path.strip_prefix(directory) else {
+                continue;
+            };
+            if entry.is_file() {
+                files.insert(path.as_unix_str().to_string());
+            } else if path.starts_with(RelPath::unix("examples").unwrap()) {
+                let main = entry.path.join(RelPath::unix("main.rs").unwrap());
+                if snapshot.entry_for_path(&main).is_some() {
+                    files.insert(format!("{}/main.rs", path.as_unix_str()));
+                }
+            }
+        }
+    }
+    files
+}
+
 fn local_task_context_for_location(
     worktree_store: Entity<WorktreeStore>,
     toolchain_store: Arc<dyn LanguageToolchainStore>,
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context as _, Result, anyhow};
 use collections::HashMap;
 use gpui::{App, AppContext as _, Context, Entity, Task, WeakEntity};
 
@@ -14,11 +14,12 @@ use std::{
 };
 use task::{Shell, ShellBuilder, ShellKind, SpawnInTerminal};
 use terminal::{
-    TaskState, TaskStatus, Terminal, TerminalBuilder, terminal_settings::TerminalSettings,
+    TaskState, TaskStatus, Terminal, TerminalBuilder, WatchStatus,
+    terminal_settings::TerminalSettings,
 };
 use util::{get_default_system_shell, maybe, rel_path::RelPath};
 
-use crate::{Project, ProjectPath};
+use crate::{Project, ProjectPath, task_env};
 
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
@@ -48,6 +49,11 @@ impl Project {
         spawn_task: SpawnInTerminal,
         cx: &mut Context<Self>,
     ) -> Task<Result<Entity<Terminal>>> {
+        if let Some(host) = self.file_transfer_host() {
+            return Task::ready(Err(anyhow!(
+                "Tasks can't run in projects opened over SFTP on {host}"
+            )));
+        }
         let is_via_remote = self.remote_client.is_some();
 
         let path: Option<Arc<Path>> = if let Some(cwd) = &spawn_task.cwd {
@@ -91,6 +97,8 @@ impl Project {
             spawned_task: spawn_task.clone(),
             status: TaskStatus::Running,
             completion_rx,
+            problems: Vec::new(),
+            watch_status: spawn_task.watch.as_ref().map(|_| WatchStatus::Starting),
         });
         let remote_client = self.remote_client.clone();
         let shell = match &remote_client {
@@ -120,6 +128,15 @@ impl Project {
         let lang_registry = self.languages.clone();
         let fs = self.fs.clone();
         cx.spawn(async move |project, cx| {
+            let env = task_env::resolve_task_env(
+                env,
+                &spawn_task,
+                local_path.as_deref(),
+                (!is_via_remote).then_some(fs.as_ref()),
+                cx,
+            )
+            .await
+            .with_context(|| format!("resolving the environment of task `{}`", spawn_task.label))?;
             let shell_kind = ShellKind::new(&shell);
             let activation_script = maybe!(async {
                 for toolchain in toolchains {
@@ -165,7 +182,6 @@ impl Project {
                 };
 
                 let (shell, env) = {
-                    env.extend(spawn_task.env);
                     match remote_client {
                         Some(remote_client) => match activation_script.clone() {
                             activation_script if !activation_script.is_empty() => {
@@ -0,0 +1,464 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use collections::HashSet;
+use editor::Editor;
+use gpui::{AsyncApp, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, WeakEntity};
+use project::{Project, ProjectEntryId, ProjectPath, WorktreeId};
+use regex::Regex;
+use ui::prelude::*;
+use util::{ResultExt, paths::PathStyle, rel_path::RelPath};
+use workspace::ModalView;
+use worktree::CreatedEntry;
+
+/// A project entry renamed by a [`BatchRenameModal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BatchRenameEntry {
+    pub entry_id: ProjectEntryId,
+    pub worktree_id: WorktreeId,
+    pub path: Arc<RelPath>,
+}
+
+/// A rename of a project entry to a new name in the same directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PlannedRename {
+    pub entry_id: ProjectEntryId,
+    pub worktree_id: WorktreeId,
+    pub old_path: Arc<RelPath>,
+    pub new_path: Arc<RelPath>,
+}
+
+/// Pairs each entry with the name on the corresponding line of `names`, skipping entries whose
+/// name is unchanged. Fails without renaming anything when a name is invalid, when two entries
+/// would get the same path, or when a new path is taken by an entry that is not renamed away.
+pub(crate) fn plan_renames(
+    entries: &[BatchRenameEntry],
+    names: &str,
+    path_style: PathStyle,
+    path_exists: impl Fn(WorktreeId, &RelPath) -> bool,
+) -> Result<Vec<PlannedRename>> {
+    let names = names.lines().collect::<Vec<_>>();
+    if names.len() != entries.len() {
+        return Err(anyhow!(
+            "Expected {} names, one per line, but found {}",
+            entries.len(),
+            names.len()
+        ));
+    }
+
+    let mut renames = Vec::new();
+    for (ix, (entry, name)) in entries.iter().zip(names).enumerate() {
+        let name = name.trim_end_matches('\r');
+        if name.trim().is_empty() {
+            return Err(anyhow!("The name on line {} is empty", ix + 1));
+        }
+        let file_name = match RelPath::new(Path::new(name), path_style) {
+            Ok(file_name) if file_name.components().count() == 1 => file_name,
+            _ => return Err(anyhow!("Invalid name {name:?} on line {}", ix + 1)),
+        };
+        let new_path = match entry.path.parent() {
+            Some(parent) => parent.join(&file_name),
+            None => file_name.into_arc(),
+        };
+        if new_path != entry.path {
+            renames.push(PlannedRename {
+                entry_id: entry.entry_id,
+                worktree_id: entry.worktree_id,
+                old_path: entry.path.clone(),
+                new_path,
+            });
+        }
+    }
+
+    let renamed_paths = renames
+        .iter()
+        .map(|rename| (rename.worktree_id, rename.old_path.clone()))
+        .collect::<HashSet<_>>();
+    let mut new_paths = HashSet::default();
+    for rename in &renames {
+        let new_path = (rename.worktree_id, rename.new_path.clone());
+        let path = rename.new_path.display(path_style);
+        if !new_paths.insert(new_path.clone()) {
+            return Err(anyhow!("Multiple entries would be renamed to {path}"));
+        }
+        if !renamed_paths.contains(&new_path) && path_exists(rename.worktree_id, &rename.new_path) {
+            return Err(anyhow!("{path} already exists"));
+        }
+    }
+    Ok(renames)
+}
+
+/// Applies the renames one by one, first moving the entries whose names are taken by other
+/// renamed entries to temporary names. When a rename fails, the completed ones are reverted.
+pub(crate) async fn apply_renames(
+    project: WeakEntity<Project>,
+    renames: Vec<PlannedRename>,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let new_paths = renames
+        .iter()
+        .map(|rename| (rename.worktree_id, rename.new_path.clone()))
+        .collect::<HashSet<_>>();
+    let mut current = renames
+        .iter()
+        .map(|rename| (rename.entry_id, rename.old_path.clone()))
+        .collect::<Vec<_>>();
+    let mut completed = Vec::<(ProjectEntryId, ProjectPath)>::new();
+
+    let result = async {
+        let temporary_steps = renames.iter().enumerate().filter_map(|(ix, rename)| {
+            new_paths
+                .contains(&(rename.worktree_id, rename.old_path.clone()))
+                .then(|| (ix, temporary_path(&rename.old_path, ix)))
+        });
+        let final_steps = renames
+            .iter()
+            .enumerate()
+            .map(|(ix, rename)| (ix, rename.new_path.clone()));
+        for (ix, new_path) in temporary_steps
+            .collect::<Vec<_>>()
+            .into_iter()
+            .chain(final_steps)
+        {
+            let worktree_id = renames[ix].worktree_id;
+            let (entry_id, old_path) = current[ix].clone();
+            let new_entry = project
+                .update(cx, |project, cx| {
+                    project.rename_entry(entry_id, (worktree_id, new_path.clone()).into(), cx)
+                })?
+                .await?;
+            let new_entry_id = match new_entry {
+                CreatedEntry::Included(entry) => entry.id,
+                CreatedEntry::Excluded { .. } => entry_id,
+            };
+            completed.push((new_entry_id, (worktree_id, old_path).into()));
+            current[ix] = (new_entry_id, new_path);
+        }
+        anyhow::Ok(())
+    }
+    .await;
+
+    if result.is_err() {
+        for (entry_id, old_path) in completed.into_iter().rev() {
+            if let Some(task) = project
+                .update(cx, |project, cx| {
+                    project.rename_entry(entry_id, old_path, cx)
+                })
+                .log_err()
+            {
+                task.await.log_err();
+            }
+        }
+    }
+    result
+}
+
+fn temporary_path(path: &RelPath, ix: usize) -> Arc<RelPath> {
+    let file_name = path.file_name().unwrap_or_default();
+    let temporary_name = format!(".{file_name}.batch-rename-{ix}");
+    let temporary_name = RelPath::unix(&temporary_name).expect("a valid file name");
+    match path.parent() {
+        Some(parent) => parent.join(temporary_name),
+        None => temporary_name.into_arc(),
+    }
+}
+
+/// Applies a regex find and replace to every line of `names`.
+pub(crate) fn replace_in_names(names: &str, find: &Regex, replace: &str) -> String {
+    names
+        .lines()
+        .map(|name| find.replace_all(name, replace))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lists the names of several project entries in an editor, one per line, and renames the
+/// entries to the edited names together.
+pub struct BatchRenameModal {
+    project: Entity<Project>,
+    entries: Vec<BatchRenameEntry>,
+    names_editor: Entity<Editor>,
+    find_editor: Entity<Editor>,
+    replace_editor: Entity<Editor>,
+    error: Option<SharedString>,
+    renaming: bool,
+}
+
+impl BatchRenameModal {
+    pub(crate) fn new(
+        project: Entity<Project>,
+        entries: Vec<BatchRenameEntry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let names = entries
+            .iter()
+            .map(|entry| entry.path.file_name().unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let names_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(1, 20, window, cx);
+            editor.set_text(names, window, cx);
+            editor
+        });
+        let find_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Find (regex)", window, cx);
+            editor
+        });
+        let replace_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Replace with ($1 inserts a group)", window, cx);
+            editor
+        });
+        Self {
+            project,
+            entries,
+            names_editor,
+            find_editor,
+            replace_editor,
+            error: None,
+            renaming: false,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    /// Replaces in the names when the find or replace box is focused, and renames otherwise.
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.find_editor.focus_handle(cx).is_focused(window)
+            || self.replace_editor.focus_handle(cx).is_focused(window)
+        {
+            self.replace_all(window, cx);
+        } else {
+            self.rename(window, cx);
+        }
+    }
+
+    fn save(&mut self, _: &workspace::Save, window: &mut Window, cx: &mut Context<Self>) {
+        self.rename(window, cx);
+    }
+
+    fn replace_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let find = self.find_editor.read(cx).text(cx);
+        if find.is_empty() {
+            return;
+        }
+        let find = match Regex::new(&find) {
+            Ok(find) => find,
+            Err(error) => {
+                self.error = Some(format!("Invalid regex: {error}").into());
+                cx.notify();
+                return;
+            }
+        };
+        let replace = self.replace_editor.read(cx).text(cx);
+        let names = self.names_editor.read(cx).text(cx);
+        let names = replace_in_names(&names, &find, &replace);
+        self.names_editor.update(cx, |editor, cx| {
+            editor.set_text(names, window, cx);
+        });
+        self.error = None;
+        cx.notify();
+    }
+
+    fn rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.renaming {
+            return;
+        }
+        let names = self.names_editor.read(cx).text(cx);
+        let project = self.project.read(cx);
+        let path_style = project.path_style(cx);
+        let renames = plan_renames(&self.entries, &names, path_style, |worktree_id, path| {
+            project
+                .worktree_for_id(worktree_id, cx)
+                .is_some_and(|worktree| worktree.read(cx).entry_for_path(path).is_some())
+        });
+        let renames = match renames {
+            Ok(renames) => renames,
+            Err(error) => {
+                self.error = Some(error.to_string().into());
+                cx.notify();
+                return;
+            }
+        };
+        if renames.is_empty() {
+            cx.emit(DismissEvent);
+            return;
+        }
+
+        self.renaming = true;
+        self.error = None;
+        cx.notify();
+        let project = self.project.downgrade();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = apply_renames(project, renames, cx).await;
+            this.update(cx, |this, cx| {
+                this.renaming = false;
+                match result {
+                    Ok(()) => cx.emit(DismissEvent),
+                    Err(error) => {
+                        this.error = Some(format!("Renaming failed: {error:#}").into());
+                        cx.notify();
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl ModalView for BatchRenameModal {}
+
+impl EventEmitter<DismissEvent> for BatchRenameModal {}
+
+impl Focusable for BatchRenameModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.names_editor.focus_handle(cx)
+    }
+}
+
+impl Render for BatchRenameModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border_variant;
+        let footer = match &self.error {
+            Some(error) => Label::new(error.clone()).color(Color::Error),
+            None if self.renaming => Label::new("Renaming…").color(Color::Muted),
+            None => Label::new(format!(
+                "Edit the names of {} entries, one per line, then confirm or save to rename them",
+                self.entries.len()
+            ))
+            .color(Color::Muted),
+        };
+
+        v_flex()
+            .w(rems(34.))
+            .elevation_2(cx)
+            .key_context("BatchRenameModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::save))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .px_2()
+                    .py_1()
+                    .child(self.names_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .border_r_1()
+                            .border_color(border_color)
+                            .child(self.find_editor.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .child(self.replace_editor.clone()),
+                    ),
+            )
+            .child(h_flex().px_2().py_1().child(footer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::rel_path::rel_path;
+
+    fn entries(paths: &[&str]) -> Vec<BatchRenameEntry> {
+        paths
+            .iter()
+            .enumerate()
+            .map(|(ix, path)| BatchRenameEntry {
+                entry_id: ProjectEntryId::from_proto(ix as u64),
+                worktree_id: WorktreeId::from_usize(0),
+                path: rel_path(path).into(),
+            })
+            .collect()
+    }
+
+    fn plan(entries: &[BatchRenameEntry], names: &str, existing: &[&str]) -> Result<Vec<String>> {
+        let renames = plan_renames(entries, names, PathStyle::Posix, |_, path| {
+            existing.contains(&path.as_unix_str())
+        })?;
+        Ok(renames
+            .into_iter()
+            .map(|rename| {
+                format!(
+                    "{} -> {}",
+                    rename.old_path.as_unix_str(),
+                    rename.new_path.as_unix_str()
+                )
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_plan_renames() {
+        let entries = entries(&["src/a.rs", "src/b.rs", "c.rs"]);
+        let existing = ["src/a.rs", "src/b.rs", "c.rs", "src/taken.rs"];
+
+        assert_eq!(
+            plan(&entries, "a.rs\nd.rs\ne.rs", &existing).unwrap(),
+            ["src/b.rs -> src/d.rs", "c.rs -> e.rs"]
+        );
+        // Swapped names are allowed, since both entries are renamed away.
+        assert_eq!(
+            plan(&entries, "b.rs\na.rs\nc.rs", &existing).unwrap(),
+            ["src/a.rs -> src/b.rs", "src/b.rs -> src/a.rs"]
+        );
+
+        assert_eq!(
+            plan(&entries, "a.rs\nb.rs", &existing)
+                .unwrap_err()
+                .to_string(),
+            "Expected 3 names, one per line, but found 2"
+        );
+        assert_eq!(
+            plan(&entries, "a.rs\n\nc.rs", &existing)
+                .unwrap_err()
+                .to_string(),
+            "The name on line 2 is empty"
+        );
+        assert_eq!(
+            plan(&entries, "a.rs\nb.rs\nsrc/c.rs", &existing)
+                .unwrap_err()
+                .to_string(),
+            "Invalid name \"src/c.rs\" on line 3"
+        );
+        assert_eq!(
+            plan(&entries, "d.rs\nd.rs\nc.rs", &existing)
+                .unwrap_err()
+                .to_string(),
+            "Multiple entries would be renamed to src/d.rs"
+        );
+        assert_eq!(
+            plan(&entries, "taken.rs\nb.rs\nc.rs", &existing)
+                .unwrap_err()
+                .to_string(),
+            "src/taken.rs already exists"
+        );
+    }
+
+    #[test]
+    fn test_replace_in_names() {
+        let find = Regex::new(r"^(\w+)\.test\.ts$").unwrap();
+        assert_eq!(
+            replace_in_names("a.test.ts\nb.ts\nc.test.ts", &find, "$1.spec.ts"),
+            "a.spec.ts\nb.ts\nc.spec.ts"
+        );
+    }
+}
@@ -1,7 +1,9 @@
+mod batch_rename;
 mod project_panel_settings;
 mod utils;
 
 use anyhow::{Context as _, Result};
+use batch_rename::{BatchRenameEntry, BatchRenameModal};
 use client::{ErrorCode, ErrorExt};
 use collections::{BTreeSet, HashMap, hash_map};
 use command_palette_hooks::CommandPaletteFilter;
@@ -14,16 +16,18 @@ use editor::{
     },
 };
 use file_icons::FileIcons;
+use fuzzy::StringMatchCandidate;
 use git::status::GitSummary;
-use git_ui::file_diff_view::FileDiffView;
+use git_ui::{directory_diff_view::DirectoryDiffView, file_diff_view::FileDiffView};
 use gpui::{
-    Action, AnyElement, App, AsyncWindowContext, Bounds, ClipboardItem, Context, CursorStyle,
-    DismissEvent, Div, DragMoveEvent, Entity, EventEmitter, ExternalPaths, FocusHandle, Focusable,
-    Hsla, InteractiveElement, KeyContext, ListHorizontalSizingBehavior, ListSizingBehavior,
-    Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
-    PromptLevel, Render, ScrollStrategy, Stateful, Styled, Subscription, Task,
-    UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, div, hsla,
-    linear_color_stop, linear_gradient, point, px, size, transparent_white, uniform_list,
+    Action, AnyElement, App, AsyncWindowContext, BackgroundExecutor, Bounds, ClipboardItem,
+    Context, CursorStyle, DismissEvent, Div, DragMoveEvent, Entity, EventEmitter, ExternalPaths,
+    FocusHandle, Focusable, Hsla, InteractiveElement, KeyContext, KeyDownEvent,
+    ListHorizontalSizingBehavior, ListSizingBehavior, Modifiers, ModifiersChangedEvent,
+    MouseButton, MouseDownEvent, ParentElement, Pixels, Point, PromptLevel, Render, ScrollStrategy,
+    ScrollWheelEvent, Stateful, Styled, Subscription, Task, UniformListScrollHandle, WeakEntity,
+    Window, actions, anchored, deferred, div, hsla, linear_color_stop, linear_gradient, point, px,
+    size, transparent_white, uniform_list,
 };
 use language::DiagnosticSeverity;
 use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
@@ -49,7 +53,7 @@ use std::{
     collections::HashSet,
     ops::Range,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, atomic::AtomicBool},
     time::Duration,
 };
 use theme::ThemeSettings;
@@ -91,6 +95,10 @@ struct State {
     edit_state: Option<EditState>,
     unfolded_dir_ids: HashSet<ProjectEntryId>,
     expanded_dir_ids: HashMap<WorktreeId, Vec<ProjectEntryId>>,
+    /// Directories shown expanded because they contain entries matching the filter.
+    filter_expanded_dir_ids: HashSet<ProjectEntryId>,
+    /// The entry matching the filter best.
+    filter_match: Option<SelectedEntry>,
 }
 
 impl State {
@@ -104,6 +112,8 @@ impl State {
             unfolded_dir_ids: old.unfolded_dir_ids.clone(),
             selection: old.selection,
             expanded_dir_ids: old.expanded_dir_ids.clone(),
+            filter_expanded_dir_ids: Default::default(),
+            filter_match: None,
         }
     }
 }
@@ -122,6 +132,12 @@ pub struct ProjectPanel {
     marked_entries: Vec<SelectedEntry>,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     filename_editor: Entity<Editor>,
+    filter_editor: Entity<Editor>,
+    show_filter: bool,
+    /// Whether to select the entry matching the filter best once the visible entries are updated.
+    reveal_filter_match: bool,
+    /// Whether following the active file is paused because the project tree is being browsed.
+    follow_paused: bool,
     clipboard: Option<ClipboardEntry>,
     _dragged_entry_destination: Option<Arc<Path>>,
     workspace: WeakEntity<Workspace>,
@@ -282,6 +298,9 @@ actions!(
         Paste,
         /// Renames the selected file or directory.
         Rename,
+        /// Opens the names of the marked files and directories in an editor to rename
+        /// them together.
+        BatchRename,
         /// Opens the selected file in the editor.
         Open,
         /// Opens the selected file in a permanent tab.
@@ -320,8 +339,12 @@ actions!(
         SelectNextDirectory,
         /// Selects the previous directory.
         SelectPrevDirectory,
-        /// Opens a diff view to compare two marked files.
+        /// Opens a diff view to compare two marked files, or a summary of the differences
+        /// between two marked folders.
         CompareMarkedFiles,
+        /// Shows or focuses the filter box narrowing the project tree to matching paths,
+        /// or hides it when it is focused.
+        ToggleFilter,
     ]
 );
 
@@ -391,6 +414,15 @@ pub fn init(cx: &mut App) {
             }
         });
 
+        workspace.register_action(|workspace, action: &ToggleFilter, window, cx| {
+            workspace.open_panel::<ProjectPanel>(window, cx);
+            if let Some(panel) = workspace.panel::<ProjectPanel>(cx) {
+                panel.update(cx, |panel, cx| {
+                    panel.toggle_filter(action, window, cx);
+                });
+            }
+        });
+
         workspace.register_action(|workspace, action: &Rename, window, cx| {
             workspace.open_panel::<ProjectPanel>(window, cx);
             if let Some(panel) = workspace.panel::<ProjectPanel>(cx) {
@@ -516,7 +548,9 @@ impl ProjectPanel {
                 window,
                 |this, project, event, window, cx| match event {
                     project::Event::ActiveEntryChanged(Some(entry_id)) => {
-                        if ProjectPanelSettings::get_global(cx).auto_reveal_entries {
+                        this.follow_paused = false;
+                        let settings = ProjectPanelSettings::get_global(cx);
+                        if settings.auto_reveal_entries || settings.follow_active_file {
                             this.reveal_entry(project.clone(), *entry_id, true, window, cx)
                                 .ok();
                         }
@@ -528,6 +562,9 @@ impl ProjectPanel {
                             .and_then(|ws| ws.read(cx).active_item(cx))
                             .map(|item| {
                                 item.act_as_type(TypeId::of::<FileDiffView>(), cx).is_some()
+                                    || item
+                                        .act_as_type(TypeId::of::<DirectoryDiffView>(), cx)
+                                        .is_some()
                             })
                             .unwrap_or(false);
                         if !is_active_item_file_diff_view {
@@ -570,7 +607,9 @@ impl ProjectPanel {
                     project::Event::WorktreeUpdatedEntries(_, _)
                     | project::Event::WorktreeAdded(_)
                     | project::Event::WorktreeOrderChanged => {
-                        this.update_visible_entries(None, false, false, window, cx);
+                        if !this.follow_active_file(window, cx) {
+                            this.update_visible_entries(None, false, false, window, cx);
+                        }
                         cx.notify();
                     }
                     project::Event::ExpandedAllForEntry(worktree_id, entry_id) => {
@@ -653,6 +692,26 @@ impl ProjectPanel {
             )
             .detach();
 
+            let filter_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Filter files…", window, cx);
+                editor
+            });
+
+            cx.subscribe_in(
+                &filter_editor,
+                window,
+                |project_panel, _, editor_event, window, cx| {
+                    if let EditorEvent::BufferEdited = editor_event
+                        && project_panel.show_filter
+                    {
+                        project_panel.reveal_filter_match = true;
+                        project_panel.update_visible_entries(None, false, false, window, cx);
+                    }
+                },
+            )
+            .detach();
+
             cx.observe_global::<FileIcons>(|_, cx| {
                 cx.notify();
             })
@@ -691,6 +750,10 @@ impl ProjectPanel {
                 marked_entries: Default::default(),
                 context_menu: None,
                 filename_editor,
+                filter_editor,
+                show_filter: false,
+                reveal_filter_match: false,
+                follow_paused: false,
                 clipboard: None,
                 _dragged_entry_destination: None,
                 workspace: workspace.weak_handle(),
@@ -713,6 +776,8 @@ impl ProjectPanel {
                     ancestors: Default::default(),
                     expanded_dir_ids: Default::default(),
                     unfolded_dir_ids: Default::default(),
+                    filter_expanded_dir_ids: Default::default(),
+                    filter_match: None,
                 },
                 update_visible_entries_task: Task::ready(()),
             };
@@ -940,6 +1005,65 @@ impl ProjectPanel {
         if !self.focus_handle.is_focused(window) {
             self.confirm(&Confirm, window, cx);
         }
+        if self.context_menu.is_none() {
+            self.follow_paused = false;
+            self.follow_active_file(window, cx);
+        }
+    }
+
+    /// Reveals the active file when `follow_active_file` is enabled, unless following is paused
+    /// or the project tree is filtered. Returns whether the file was revealed.
+    fn follow_active_file(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        if !ProjectPanelSettings::get_global(cx).follow_active_file
+            || self.follow_paused
+            || self.filter_query(cx).is_some()
+        {
+            return false;
+        }
+        let Some(entry_id) = self.project.read(cx).active_entry() else {
+            return false;
+        };
+        self.reveal_entry(self.project.clone(), entry_id, true, window, cx)
+            .is_ok()
+    }
+
+    fn toggle_filter(&mut self, _: &ToggleFilter, window: &mut Window, cx: &mut Context<Self>) {
+        if self.show_filter && self.filter_editor.focus_handle(cx).is_focused(window) {
+            self.hide_filter(window, cx);
+        } else {
+            self.show_filter = true;
+            window.focus(&self.filter_editor.focus_handle(cx));
+            cx.notify();
+        }
+    }
+
+    /// Hides and clears the filter box, revealing the selected entry in the unfiltered tree.
+    fn hide_filter(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_filter = false;
+        self.reveal_filter_match = false;
+        self.filter_editor
+            .update(cx, |editor, cx| editor.clear(window, cx));
+        if let Some(selection) = self.state.selection {
+            self.expand_entry(selection.worktree_id, selection.entry_id, cx);
+            self.update_visible_entries(
+                Some((selection.worktree_id, selection.entry_id)),
+                false,
+                true,
+                window,
+                cx,
+            );
+        }
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn filter_query(&self, cx: &App) -> Option<String> {
+        if !self.show_filter {
+            return None;
+        }
+        let query = self.filter_editor.read(cx).text(cx);
+        let query = query.trim();
+        (!query.is_empty()).then(|| query.to_string())
     }
 
     fn deploy_context_menu(
@@ -972,13 +1096,21 @@ impl ProjectPanel {
             let is_read_only = project.is_read_only(cx);
             let is_remote = project.is_via_collab();
             let is_local = project.is_local();
+            let has_system_path = project
+                .system_path(&worktree.absolutize(&entry.path), cx)
+                .is_some();
 
             let settings = ProjectPanelSettings::get_global(cx);
             let visible_worktrees_count = project.visible_worktrees(cx).count();
             let should_hide_rename = is_root
                 && (cfg!(target_os = "windows")
                     || (settings.hide_root && visible_worktrees_count == 1));
-            let should_show_compare = !is_dir && self.file_abs_paths_to_diff(cx).is_some();
+            let should_show_batch_rename = self.disjoint_entries(cx).len() > 1;
+            let should_show_compare = if is_dir {
+                is_local && self.dir_paths_to_diff(cx).is_some()
+            } else {
+                self.file_abs_paths_to_diff(cx).is_some()
+            };
 
             let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
                 menu.context(self.focus_handle.clone()).map(|menu| {
@@ -990,13 +1122,13 @@ impl ProjectPanel {
                         menu.action("New File", Box::new(NewFile))
                             .action("New Folder", Box::new(NewDirectory))
                             .separator()
-                            .when(is_local && cfg!(target_os = "macos"), |menu| {
+                            .when(has_system_path && cfg!(target_os = "macos"), |menu| {
                                 menu.action("Reveal in Finder", Box::new(RevealInFileManager))
                             })
-                            .when(is_local && cfg!(not(target_os = "macos")), |menu| {
+                            .when(has_system_path && cfg!(not(target_os = "macos")), |menu| {
                                 menu.action("Reveal in File Manager", Box::new(RevealInFileManager))
                             })
-                            .when(is_local, |menu| {
+                            .when(has_system_path, |menu| {
                                 menu.action("Open in Default App", Box::new(OpenWithSystem))
                             })
                             .action("Open in Terminal", Box::new(OpenInTerminal))
@@ -1011,8 +1143,12 @@ impl ProjectPanel {
                                 menu.action("Fold Directory", Box::new(FoldDirectory))
                             })
                             .when(should_show_compare, |menu| {
-                                menu.separator()
-                                    .action("Compare marked files", Box::new(CompareMarkedFiles))
+                                let label = if is_dir {
+                                    "Compare marked folders"
+                                } else {
+                                    "Compare marked files"
+                                };
+                                menu.separator().action(label, Box::new(CompareMarkedFiles))
                             })
                             .separator()
                             .action("Cut", Box::new(Cut))
@@ -1034,6 +1170,9 @@ impl ProjectPanel {
                             .when(!should_hide_rename, |menu| {
                                 menu.action("Rename", Box::new(Rename))
                             })
+                            .when(should_show_batch_rename, |menu| {
+                                menu.action("Batch Rename…", Box::new(BatchRename))
+                            })
                             .when(!is_root & !is_remote, |menu| {
                                 menu.action("Trash", Box::new(Trash { skip_prompt: false }))
                             })
@@ -1681,6 +1820,10 @@ impl ProjectPanel {
             self.hover_expand_task.take();
             return;
         }
+        if self.show_filter && self.filter_editor.focus_handle(cx).is_focused(window) {
+            self.hide_filter(window, cx);
+            return;
+        }
 
         let previous_edit_state = self.state.edit_state.take();
         self.update_visible_entries(None, false, false, window, cx);
@@ -1883,6 +2026,39 @@ impl ProjectPanel {
         self.rename_impl(None, window, cx);
     }
 
+    fn batch_rename(&mut self, _: &BatchRename, window: &mut Window, cx: &mut Context<Self>) {
+        let project = self.project.read(cx);
+        let mut entries = self
+            .disjoint_entries(cx)
+            .into_iter()
+            .filter_map(|selection| {
+                let project_path = project.path_for_entry(selection.entry_id, cx)?;
+                Some(BatchRenameEntry {
+                    entry_id: selection.entry_id,
+                    worktree_id: selection.worktree_id,
+                    path: project_path.path,
+                })
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            return;
+        }
+        entries.sort_by(|a, b| {
+            a.worktree_id.cmp(&b.worktree_id).then_with(|| {
+                compare_paths((a.path.as_std_path(), true), (b.path.as_std_path(), true))
+            })
+        });
+
+        let project = self.project.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    BatchRenameModal::new(project, entries, window, cx)
+                });
+            })
+            .ok();
+    }
+
     fn trash(&mut self, action: &Trash, window: &mut Window, cx: &mut Context<Self>) {
         self.remove(true, action.skip_prompt, window, cx);
     }
@@ -2788,8 +2964,13 @@ impl ProjectPanel {
         _: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some((worktree, entry)) = self.selected_sub_entry(cx) {
-            cx.reveal_path(&worktree.read(cx).absolutize(&entry.path));
+        if let Some((worktree, entry)) = self.selected_sub_entry(cx)
+            && let Some(path) = self
+                .project
+                .read(cx)
+                .system_path(&worktree.read(cx).absolutize(&entry.path), cx)
+        {
+            cx.reveal_path(&path);
         }
     }
 
@@ -2826,12 +3007,53 @@ impl ProjectPanel {
         Some((previous_to_last, last_path))
     }
 
+    fn dir_paths_to_diff(&self, cx: &Context<Self>) -> Option<(ProjectPath, ProjectPath)> {
+        let mut selected_dirs = self
+            .marked_entries
+            .iter()
+            .filter_map(|entry| {
+                let project = self.project.read(cx);
+                let worktree = project.worktree_for_id(entry.worktree_id, cx)?;
+                let entry = worktree.read(cx).entry_for_id(entry.entry_id)?;
+                if !entry.is_dir() {
+                    return None;
+                }
+                Some(ProjectPath {
+                    worktree_id: worktree.read(cx).id(),
+                    path: entry.path.clone(),
+                })
+            })
+            .rev();
+
+        let last_dir = selected_dirs.next()?;
+        let previous_to_last = selected_dirs.next()?;
+        Some((previous_to_last, last_dir))
+    }
+
     fn compare_marked_files(
         &mut self,
         _: &CompareMarkedFiles,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let is_dir_selected = self
+            .selected_entry(cx)
+            .is_some_and(|(_, entry)| entry.is_dir());
+        if is_dir_selected {
+            if !self.project.read(cx).is_local() {
+                return;
+            }
+            if let Some((old_dir, new_dir)) = self.dir_paths_to_diff(cx) {
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        DirectoryDiffView::open(old_dir, new_dir, workspace, window, cx)
+                            .detach_and_log_err(cx);
+                    })
+                    .ok();
+            }
+            return;
+        }
+
         let selected_files = self.file_abs_paths_to_diff(cx);
         if let Some((file_path1, file_path2)) = selected_files {
             self.workspace
@@ -2844,9 +3066,13 @@ impl ProjectPanel {
     }
 
     fn open_system(&mut self, _: &OpenWithSystem, _: &mut Window, cx: &mut Context<Self>) {
-        if let Some((worktree, entry)) = self.selected_entry(cx) {
-            let abs_path = worktree.absolutize(&entry.path);
-            cx.open_with_system(&abs_path);
+        if let Some((worktree, entry)) = self.selected_entry(cx)
+            && let Some(path) = self
+                .project
+                .read(cx)
+                .system_path(&worktree.absolutize(&entry.path), cx)
+        {
+            cx.open_with_system(&path);
         }
     }
 
@@ -3184,8 +3410,11 @@ impl ProjectPanel {
         cx: &mut Context<Self>,
     ) {
         let now = Instant::now();
+        let filter_query = self.filter_query(cx);
+        let executor = cx.background_executor().clone();
         let settings = ProjectPanelSettings::get_global(cx);
-        let auto_collapse_dirs = settings.auto_fold_dirs;
+        // Folded directories would hide the ancestors of filter matches.
+        let auto_collapse_dirs = settings.auto_fold_dirs && filter_query.is_none();
         let hide_gitignore = settings.hide_gitignore;
         let project = self.project.read(cx);
         let repo_snapshots = project.git_store().read(cx).repo_snapshots(cx);
@@ -3207,9 +3436,40 @@ impl ProjectPanel {
         self.update_visible_entries_task = cx.spawn_in(window, async move |this, cx| {
             let new_state = cx
                 .background_spawn(async move {
+                    let mut best_filter_match = None;
                     for worktree_snapshot in visible_worktrees {
                         let worktree_id = worktree_snapshot.id();
 
+                        let filtered_entries = match filter_query.as_deref() {
+                            Some(query) => Some(
+                                filter_entries(
+                                    &worktree_snapshot,
+                                    query,
+                                    hide_gitignore,
+                                    executor.clone(),
+                                )
+                                .await,
+                            ),
+                            None => None,
+                        };
+                        if let Some(filtered_entries) = &filtered_entries {
+                            new_state
+                                .filter_expanded_dir_ids
+                                .extend(filtered_entries.expanded.iter().copied());
+                            if let Some((entry_id, score)) = filtered_entries.best_match
+                                && best_filter_match
+                                    .is_none_or(|(_, best_score)| score > best_score)
+                            {
+                                best_filter_match = Some((
+                                    SelectedEntry {
+                                        worktree_id,
+                                        entry_id,
+                                    },
+                                    score,
+                                ));
+                            }
+                        }
+
                         let expanded_dir_ids = match new_state.expanded_dir_ids.entry(worktree_id) {
                             hash_map::Entry::Occupied(e) => e.into_mut(),
                             hash_map::Entry::Vacant(e) => {
@@ -3242,7 +3502,29 @@ impl ProjectPanel {
                             GitTraversal::new(&repo_snapshots, worktree_snapshot.entries(true, 0));
                         let mut auto_folded_ancestors = vec![];
                         let worktree_abs_path = worktree_snapshot.abs_path();
+                        // A directory matching the filter without containing matches,
+                        // whose contents are shown unfiltered.
+                        let mut unfiltered_dir: Option<Arc<RelPath>> = None;
                         while let Some(entry) = entry_iter.entry() {
+                            if let Some(filtered_entries) = &filtered_entries {
+                                if unfiltered_dir
+                                    .as_ref()
+                                    .is_some_and(|dir| !entry.path.starts_with(dir))
+                                {
+                                    unfiltered_dir = None;
+                                }
+                                if unfiltered_dir.is_none() {
+                                    if !filtered_entries.visible.contains(&entry.id) {
+                                        entry_iter.advance_to_sibling();
+                                        continue;
+                                    }
+                                    if entry.is_dir()
+                                        && !filtered_entries.expanded.contains(&entry.id)
+                                    {
+                                        unfiltered_dir = Some(entry.path.clone());
+                                    }
+                                }
+                            }
                             if hide_root && Some(entry.entry) == worktree_snapshot.root_entry() {
                                 if new_entry_parent_id == Some(entry.id) {
                                     visible_worktree_entries.push(Self::create_new_git_entry(
@@ -3383,7 +3665,11 @@ impl ProjectPanel {
                                 }
                             }
 
-                            if expanded_dir_ids.binary_search(&entry.id).is_err()
+                            let is_filter_expanded = filtered_entries
+                                .as_ref()
+                                .is_some_and(|filtered| filtered.expanded.contains(&entry.id));
+                            if !is_filter_expanded
+                                && expanded_dir_ids.binary_search(&entry.id).is_err()
                                 && entry_iter.advance_to_sibling()
                             {
                                 continue;
@@ -3424,6 +3710,7 @@ impl ProjectPanel {
                             entry_id,
                         });
                     }
+                    new_state.filter_match = best_filter_match.map(|(entry, _)| entry);
                     new_state
                 })
                 .await;
@@ -3448,7 +3735,13 @@ impl ProjectPanel {
                         window.focus(&editor.focus_handle(cx));
                     });
                 }
-                if autoscroll {
+                if std::mem::take(&mut this.reveal_filter_match)
+                    && let Some(filter_match) = this.state.filter_match
+                {
+                    this.state.selection = Some(filter_match);
+                    this.marked_entries.clear();
+                    this.autoscroll(cx);
+                } else if autoscroll {
                     this.autoscroll(cx);
                 }
                 cx.notify();
@@ -4944,7 +5237,8 @@ impl ProjectPanel {
             .get(&worktree_id)
             .map(Vec::as_slice)
             .unwrap_or(&[]);
-        let is_expanded = expanded_entry_ids.binary_search(&entry.id).is_ok();
+        let is_expanded = expanded_entry_ids.binary_search(&entry.id).is_ok()
+            || self.state.filter_expanded_dir_ids.contains(&entry.id);
 
         let icon = match entry.kind {
             EntryKind::File => {
@@ -5025,18 +5319,53 @@ impl ProjectPanel {
         }
     }
 
+    fn render_filter(&self, cx: &App) -> impl IntoElement {
+        let has_matches = self
+            .state
+            .visible_entries
+            .iter()
+            .any(|worktree| !worktree.entries.is_empty());
+
+        h_flex()
+            .w_full()
+            .h_8()
+            .px_2()
+            .gap_1p5()
+            .flex_none()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Icon::new(IconName::Filter)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(div().flex_1().child(self.filter_editor.clone()))
+            .when(!has_matches, |this| {
+                this.child(
+                    Label::new("No matches")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+    }
+
     fn dispatch_context(&self, window: &Window, cx: &Context<Self>) -> KeyContext {
         let mut dispatch_context = KeyContext::new_with_defaults();
         dispatch_context.add("ProjectPanel");
         dispatch_context.add("menu");
 
-        let identifier = if self.filename_editor.focus_handle(cx).is_focused(window) {
+        let is_filtering = self.filter_editor.focus_handle(cx).is_focused(window);
+        let identifier = if is_filtering || self.filename_editor.focus_handle(cx).is_focused(window)
+        {
             "editing"
         } else {
             "not_editing"
         };
 
         dispatch_context.add(identifier);
+        if is_filtering {
+            dispatch_context.add("filtering");
+        }
         dispatch_context
     }
 
@@ -5095,12 +5424,13 @@ impl ProjectPanel {
         let mut entry = entry;
         loop {
             let is_expanded_dir = entry.is_dir()
-                && self
+                && (self
                     .state
                     .expanded_dir_ids
                     .get(&worktree.id())
                     .map(|ids| ids.binary_search(&entry.id).is_ok())
-                    .unwrap_or(false);
+                    .unwrap_or(false)
+                    || self.state.filter_expanded_dir_ids.contains(&entry.id));
             if is_expanded_dir {
                 break;
             }
@@ -5278,6 +5608,74 @@ impl StickyCandidate for StickyProjectPanelCandidate {
     }
 }
 
+/// The entries of a worktree that remain visible when filtering the project tree.
+struct FilteredEntries {
+    /// The entries matching the filter and their ancestors.
+    visible: HashSet<ProjectEntryId>,
+    /// The directories containing entries matching the filter.
+    expanded: HashSet<ProjectEntryId>,
+    best_match: Option<(ProjectEntryId, f64)>,
+}
+
+/// Fuzzy matches the entries of a worktree against a filter query: against their file names,
+/// or against their paths if the query contains a `/`.
+async fn filter_entries(
+    worktree: &worktree::Snapshot,
+    query: &str,
+    hide_gitignore: bool,
+    executor: BackgroundExecutor,
+) -> FilteredEntries {
+    let match_paths = query.contains('/');
+    let entries = worktree
+        .entries(true, 0)
+        .filter(|entry| !entry.path.is_empty() && (!hide_gitignore || !entry.is_ignored))
+        .collect::<Vec<_>>();
+    let candidates = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, entry)| {
+            let text = if match_paths {
+                entry.path.as_unix_str()
+            } else {
+                entry.path.file_name()?
+            };
+            Some(StringMatchCandidate::new(ix, text))
+        })
+        .collect::<Vec<_>>();
+    let matches = fuzzy::match_strings(
+        &candidates,
+        query,
+        true,
+        true,
+        usize::MAX,
+        &AtomicBool::new(false),
+        executor,
+    )
+    .await;
+
+    let mut filtered_entries = FilteredEntries {
+        visible: HashSet::default(),
+        expanded: HashSet::default(),
+        best_match: matches
+            .first()
+            .map(|best_match| (entries[best_match.candidate_id].id, best_match.score)),
+    };
+    for string_match in &matches {
+        let entry = entries[string_match.candidate_id];
+        filtered_entries.visible.insert(entry.id);
+        for ancestor in entry.path.ancestors().skip(1) {
+            let Some(ancestor) = worktree.entry_for_path(ancestor) else {
+                continue;
+            };
+            filtered_entries.visible.insert(ancestor.id);
+            if !filtered_entries.expanded.insert(ancestor.id) {
+                break;
+            }
+        }
+    }
+    filtered_entries
+}
+
 fn item_width_estimate(depth: usize, item_text_chars: usize, is_symlink: bool) -> usize {
     const ICON_SIZE_FACTOR: usize = 2;
     let mut item_width = depth * ICON_SIZE_FACTOR + item_text_chars;
@@ -5396,6 +5794,17 @@ impl Render for ProjectPanel {
                     },
                 ))
                 .key_context(self.dispatch_context(window, cx))
+                // Browsing the project tree pauses following the active file.
+                .capture_key_down(cx.listener(|this, _: &KeyDownEvent, _, _| {
+                    this.follow_paused = true;
+                }))
+                .capture_any_mouse_down(cx.listener(|this, _: &MouseDownEvent, _, _| {
+                    this.follow_paused = true;
+                }))
+                .on_scroll_wheel(cx.listener(|this, _: &ScrollWheelEvent, _, _| {
+                    this.follow_paused = true;
+                }))
+                .on_action(cx.listener(Self::toggle_filter))
                 .on_action(cx.listener(Self::scroll_up))
                 .on_action(cx.listener(Self::scroll_down))
                 .on_action(cx.listener(Self::scroll_cursor_center))
@@ -5432,6 +5841,7 @@ impl Render for ProjectPanel {
                     el.on_action(cx.listener(Self::new_file))
                         .on_action(cx.listener(Self::new_directory))
                         .on_action(cx.listener(Self::rename))
+                        .on_action(cx.listener(Self::batch_rename))
                         .on_action(cx.listener(Self::delete))
                         .on_action(cx.listener(Self::trash))
                         .on_action(cx.listener(Self::cut))
@@ -5467,11 +5877,14 @@ impl Render for ProjectPanel {
                         .on_action(cx.listener(Self::open_in_terminal))
                 })
                 .when(project.is_via_remote_server(), |el| {
-                    el.on_action(cx.listener(Self::open_in_terminal))
+                    el.on_action(cx.listener(Self::reveal_in_finder))
+                        .on_action(cx.listener(Self::open_system))
+                        .on_action(cx.listener(Self::open_in_terminal))
                 })
                 .track_focus(&self.focus_handle(cx))
                 .child(
                     v_flex()
+                        .when(self.show_filter, |this| this.child(self.render_filter(cx)))
                         .child(
                             uniform_list("entries", item_count, {
                                 cx.processor(|this, range: Range<usize>, window, cx| {
@@ -25,6 +25,7 @@ pub struct ProjectPanelSettings {
     pub indent_guides: IndentGuidesSettings,
     pub sticky_scroll: bool,
     pub auto_reveal_entries: bool,
+    pub follow_active_file: bool,
     pub auto_fold_dirs: bool,
     pub starts_open: bool,
     pub scrollbar: ScrollbarSettings,
@@ -72,6 +73,7 @@ impl Settings for ProjectPanelSettings {
             },
             sticky_scroll: project_panel.sticky_scroll.unwrap(),
             auto_reveal_entries: project_panel.auto_reveal_entries.unwrap(),
+            follow_active_file: project_panel.follow_active_file.unwrap(),
             auto_fold_dirs: project_panel.auto_fold_dirs.unwrap(),
             starts_open: project_panel.starts_open.unwrap(),
             scrollbar: ScrollbarSettings {
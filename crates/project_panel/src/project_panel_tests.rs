@@ -1,5 +1,7 @@
 use super::*;
+use crate::batch_rename::{BatchRenameEntry, apply_renames, plan_renames};
 use collections::HashSet;
+use git_ui::directory_diff_view::DirectoryDiffStatus;
 use gpui::{Empty, Entity, TestAppContext, VisualTestContext, WindowHandle};
 use pretty_assertions::assert_eq;
 use project::FakeFs;
@@ -6492,6 +6494,187 @@ async fn test_hide_root(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_filter_entries(cx: &mut gpui::TestAppContext) {
+    init_test_with_editor(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/project_root",
+        json!({
+            "dir_1": {
+                "a.py": "",
+                "b.py": "",
+                "nested": {
+                    "target.py": "",
+                },
+            },
+            "dir_2": {
+                "c.py": "",
+                "target_notes.txt": "",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/project_root".as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let panel = workspace.update(cx, ProjectPanel::new).unwrap();
+    cx.run_until_parked();
+
+    let set_filter = |query: &str, cx: &mut VisualTestContext| {
+        panel.update_in(cx, |panel, window, cx| {
+            if !panel.show_filter {
+                panel.toggle_filter(&ToggleFilter, window, cx);
+            }
+            panel
+                .filter_editor
+                .update(cx, |editor, cx| editor.set_text(query, window, cx));
+        });
+        cx.run_until_parked();
+    };
+
+    set_filter("dir_2", cx);
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &["v project_root", "    > dir_2  <== selected"],
+        "A matching directory without matching entries inside should be shown collapsed"
+    );
+
+    toggle_expand_dir(&panel, "project_root/dir_2", cx);
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &[
+            "v project_root",
+            "    v dir_2  <== selected",
+            "          c.py",
+            "          target_notes.txt",
+        ],
+        "Expanding a matching directory should show all of its entries"
+    );
+
+    set_filter("target.py", cx);
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &[
+            "v project_root",
+            "    v dir_1",
+            "        v nested",
+            "              target.py  <== selected",
+        ],
+        "Only the matching entry and its ancestors should be shown, with the best match selected"
+    );
+
+    set_filter("zzz", cx);
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        Vec::<String>::new(),
+        "Nothing should be shown when no entries match"
+    );
+
+    set_filter("target.py", cx);
+    panel.update_in(cx, |panel, window, cx| panel.hide_filter(window, cx));
+    cx.run_until_parked();
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &[
+            "v project_root",
+            "    v dir_1",
+            "        v nested",
+            "              target.py  <== selected",
+            "          a.py",
+            "          b.py",
+            "    v dir_2",
+            "          c.py",
+            "          target_notes.txt",
+        ],
+        "Hiding the filter should reveal the selected entry in the unfiltered tree"
+    );
+}
+
+#[gpui::test]
+async fn test_follow_active_file(cx: &mut gpui::TestAppContext) {
+    init_test_with_editor(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings(cx, |settings| {
+                let project_panel = settings.project_panel.get_or_insert_default();
+                project_panel.auto_reveal_entries = Some(false);
+                project_panel.follow_active_file = Some(true);
+            });
+        })
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/project_root",
+        json!({
+            "dir_1": {
+                "file_1.py": "",
+            },
+            "dir_2": {
+                "file_2.py": "",
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/project_root".as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let panel = workspace.update(cx, ProjectPanel::new).unwrap();
+    cx.run_until_parked();
+
+    project.update(cx, |project, cx| {
+        let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+        project.set_active_path(
+            Some(ProjectPath {
+                worktree_id,
+                path: rel_path("dir_1/file_1.py").into(),
+            }),
+            cx,
+        );
+    });
+    cx.run_until_parked();
+    let revealed = [
+        "v project_root",
+        "    v dir_1",
+        "          file_1.py  <== selected  <== marked",
+        "    > dir_2",
+    ];
+    assert_eq!(visible_entries_as_strings(&panel, 0..20, cx), &revealed);
+
+    toggle_expand_dir(&panel, "project_root/dir_1", cx);
+    fs.insert_file("/project_root/dir_2/file_3.py", Vec::new())
+        .await;
+    cx.run_until_parked();
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &revealed,
+        "The active file should be revealed again when the project tree changes"
+    );
+
+    toggle_expand_dir(&panel, "project_root/dir_1", cx);
+    panel.update(cx, |panel, _| panel.follow_paused = true);
+    fs.insert_file("/project_root/dir_2/file_4.py", Vec::new())
+        .await;
+    cx.run_until_parked();
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &["v project_root", "    > dir_1  <== selected", "    > dir_2"],
+        "Following should be paused while the project tree is browsed"
+    );
+
+    panel.update_in(cx, |panel, window, cx| panel.focus_out(window, cx));
+    cx.run_until_parked();
+    assert_eq!(
+        visible_entries_as_strings(&panel, 0..20, cx),
+        &revealed,
+        "Following should resume when focus leaves the project panel"
+    );
+}
+
 #[gpui::test]
 async fn test_compare_selected_files(cx: &mut gpui::TestAppContext) {
     init_test_with_editor(cx);
@@ -6678,6 +6861,122 @@ async fn test_compare_files_context_menu(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_batch_rename(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "a.txt": "a",
+            "b.txt": "b",
+            "c.txt": "c",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    cx.run_until_parked();
+
+    let entries = project.read_with(cx, |project, cx| {
+        let worktree = project.worktrees(cx).next().unwrap().read(cx);
+        ["a.txt", "b.txt", "c.txt"]
+            .into_iter()
+            .map(|path| BatchRenameEntry {
+                entry_id: worktree.entry_for_path(rel_path(path)).unwrap().id,
+                worktree_id: worktree.id(),
+                path: rel_path(path).into(),
+            })
+            .collect::<Vec<_>>()
+    });
+    let renames = plan_renames(
+        &entries,
+        "b.txt\na.txt\nd.txt",
+        PathStyle::local(),
+        |_, _| true,
+    )
+    .unwrap();
+    cx.update(|cx| {
+        let project = project.downgrade();
+        cx.spawn(async move |cx| apply_renames(project, renames, cx).await)
+    })
+    .await
+    .unwrap();
+    cx.run_until_parked();
+
+    assert_eq!(
+        fs.load(path!("/root/a.txt").as_ref()).await.unwrap(),
+        "b",
+        "swapped names should swap the files"
+    );
+    assert_eq!(fs.load(path!("/root/b.txt").as_ref()).await.unwrap(), "a");
+    assert_eq!(fs.load(path!("/root/d.txt").as_ref()).await.unwrap(), "c");
+    assert_eq!(
+        fs.files(),
+        [
+            PathBuf::from(path!("/root/a.txt")),
+            PathBuf::from(path!("/root/b.txt")),
+            PathBuf::from(path!("/root/d.txt")),
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_compare_marked_folders(cx: &mut gpui::TestAppContext) {
+    init_test_with_editor(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/root",
+        json!({
+            "dir1": {
+                "file.txt": "one",
+            },
+            "dir2": {
+                "file.txt": "two",
+            },
+            "file3.txt": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/root".as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let panel = workspace.update(cx, ProjectPanel::new).unwrap();
+    cx.run_until_parked();
+
+    select_path_with_mark(&panel, "root/dir1", cx);
+    let selected_dirs = panel.update(cx, |panel, cx| panel.dir_paths_to_diff(cx));
+    assert_eq!(selected_dirs, None);
+
+    select_path_with_mark(&panel, "root/file3.txt", cx);
+    select_path_with_mark(&panel, "root/dir2", cx);
+    let selected_dirs = panel.update(cx, |panel, cx| panel.dir_paths_to_diff(cx));
+    let (old_dir, new_dir) = selected_dirs.expect("two folders are marked");
+    assert_eq!(old_dir.path.as_unix_str(), "dir1");
+    assert_eq!(new_dir.path.as_unix_str(), "dir2");
+
+    panel.update_in(cx, |panel, window, cx| {
+        panel.compare_marked_files(&CompareMarkedFiles, window, cx)
+    });
+    cx.run_until_parked();
+
+    let diff_view = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_item_as::<DirectoryDiffView>(cx)
+        })
+        .unwrap()
+        .expect("a directory diff view is opened");
+    diff_view.read_with(cx, |diff_view, _| {
+        let entries = diff_view.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path.as_unix_str(), "file.txt");
+        assert_eq!(entries[0].status, DirectoryDiffStatus::Changed);
+    });
+}
+
 fn select_path(panel: &Entity<ProjectPanel>, path: &str, cx: &mut VisualTestContext) {
     let path = rel_path(path);
     panel.update_in(cx, |panel, window, cx| {
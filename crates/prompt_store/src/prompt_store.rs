@@ -90,6 +90,54 @@ impl From<Uuid> for UserPromptId {
     }
 }
 
+/// A rule as written to and read from a shared JSON file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportedPrompt {
+    pub title: String,
+    #[serde(default)]
+    pub default: bool,
+    pub body: String,
+}
+
+/// Values substituted for the `{{selection}}`, `{{filename}}`, and `{{language}}`
+/// variables when a prompt is inserted.
+#[derive(Clone, Debug, Default)]
+pub struct PromptVariables {
+    pub selection: Option<String>,
+    pub filename: Option<String>,
+    pub language: Option<String>,
+}
+
+impl PromptVariables {
+    /// Replaces the known variables in `body`. Variables without a value expand
+    /// to an empty string, and unknown variables are left untouched.
+    pub fn expand(&self, body: &str) -> String {
+        let mut expanded = String::with_capacity(body.len());
+        let mut rest = body;
+        while let Some(start) = rest.find("{{") {
+            expanded.push_str(&rest[..start]);
+            let after_open = &rest[start + 2..];
+            let Some(end) = after_open.find("}}") else {
+                rest = &rest[start..];
+                break;
+            };
+            let value = match after_open[..end].trim() {
+                "selection" => Some(&self.selection),
+                "filename" => Some(&self.filename),
+                "language" => Some(&self.language),
+                _ => None,
+            };
+            match value {
+                Some(value) => expanded.push_str(value.as_deref().unwrap_or_default()),
+                None => expanded.push_str(&rest[start..start + 2 + end + 2]),
+            }
+            rest = &after_open[end + 2..];
+        }
+        expanded.push_str(rest);
+        expanded
+    }
+}
+
 impl std::fmt::Display for PromptId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -279,6 +327,59 @@ impl PromptStore {
         })
     }
 
+    /// Serializes all user rules to JSON so they can be shared.
+    pub fn export_json(&self, cx: &App) -> Task<Result<String>> {
+        let prompts = self
+            .metadata_cache
+            .read()
+            .metadata
+            .iter()
+            .filter(|metadata| !metadata.id.is_built_in())
+            .map(|metadata| {
+                let title = metadata.title.clone().unwrap_or_default().to_string();
+                let default = metadata.default;
+                let body = self.load(metadata.id, cx);
+                async move {
+                    anyhow::Ok(ExportedPrompt {
+                        title,
+                        default,
+                        body: body.await?,
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+        cx.background_spawn(async move {
+            let prompts = futures::future::try_join_all(prompts).await?;
+            Ok(serde_json::to_string_pretty(&prompts)?)
+        })
+    }
+
+    /// Adds the rules from a JSON file produced by [`Self::export_json`] as new rules,
+    /// returning how many were imported.
+    pub fn import_json(&self, json: &str, cx: &Context<Self>) -> Task<Result<usize>> {
+        let prompts = match serde_json::from_str::<Vec<ExportedPrompt>>(json) {
+            Ok(prompts) => prompts,
+            Err(error) => return Task::ready(Err(error).context("invalid rules file")),
+        };
+        let count = prompts.len();
+        let saves = prompts
+            .into_iter()
+            .map(|prompt| {
+                self.save(
+                    PromptId::new(),
+                    Some(prompt.title.into()),
+                    prompt.default,
+                    Rope::from(prompt.body.as_str()),
+                    cx,
+                )
+            })
+            .collect::<Vec<_>>();
+        cx.background_spawn(async move {
+            futures::future::try_join_all(saves).await?;
+            Ok(count)
+        })
+    }
+
     pub fn all_prompt_metadata(&self) -> Vec<PromptMetadata> {
         self.metadata_cache.read().metadata.clone()
     }
@@ -469,3 +570,25 @@ impl PromptStore {
 pub struct GlobalPromptStore(Shared<Task<Result<Entity<PromptStore>, Arc<anyhow::Error>>>>);
 
 impl Global for GlobalPromptStore {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_prompt_variables() {
+        let variables = PromptVariables {
+            selection: Some("let x = 1;".into()),
+            filename: Some("main.rs".into()),
+            language: None,
+        };
+        assert_eq!(
+            variables.expand("Explain {{selection}} from {{ filename }} in {{language}}."),
+            "Explain let x = 1; from main.rs in ."
+        );
+        assert_eq!(
+            variables.expand("Keep {{unknown}} and {{unterminated"),
+            "Keep {{unknown}} and {{unterminated"
+        );
+    }
+}
@@ -117,6 +117,15 @@ pub struct TerminalAssistantPromptContext {
     pub user_prompt: String,
 }
 
+#[derive(Serialize)]
+pub struct TerminalExplainPromptContext {
+    pub os: String,
+    pub arch: String,
+    pub shell: Option<String>,
+    pub working_directory: Option<String>,
+    pub command: String,
+}
+
 pub struct PromptLoadingParams<'a> {
     pub fs: Arc<dyn Fs>,
     pub repo_path: Option<PathBuf>,
@@ -441,6 +450,25 @@ impl PromptBuilder {
             .lock()
             .render("terminal_assistant_prompt", &context)
     }
+
+    pub fn generate_terminal_explain_prompt(
+        &self,
+        command: &str,
+        shell: Option<&str>,
+        working_directory: Option<&str>,
+    ) -> Result<String, RenderError> {
+        let context = TerminalExplainPromptContext {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            shell: shell.map(|s| s.to_string()),
+            working_directory: working_directory.map(|s| s.to_string()),
+            command: command.to_string(),
+        };
+
+        self.handlebars
+            .lock()
+            .render("terminal_explain_prompt", &context)
+    }
 }
 
 #[cfg(test)]
@@ -537,4 +565,32 @@ mod test {
         let result = handlebars.render("test_template", &data).unwrap();
         assert_eq!(result, "grep is disabled");
     }
+
+    #[test]
+    fn test_terminal_explain_prompt_renders() {
+        let prompt_builder = PromptBuilder::new(None).unwrap();
+
+        let prompt = prompt_builder
+            .generate_terminal_explain_prompt(
+                "find . -name '*.log' -delete && echo \"<done>\"",
+                Some("/bin/zsh"),
+                Some("/home/user/project"),
+            )
+            .unwrap();
+        assert!(prompt.contains(&format!("Current OS name is '{}'", std::env::consts::OS)));
+        assert!(prompt.contains("Current shell is '/bin/zsh'."));
+        assert!(prompt.contains("Current working directory is '/home/user/project'."));
+        // The command is included verbatim, without HTML escaping.
+        assert!(
+            prompt
+                .trim_end()
+                .ends_with("find . -name '*.log' -delete && echo \"<done>\"")
+        );
+
+        let prompt = prompt_builder
+            .generate_terminal_explain_prompt("ls", None, None)
+            .unwrap();
+        assert!(!prompt.contains("Current shell"));
+        assert!(!prompt.contains("Current working directory"));
+    }
 }
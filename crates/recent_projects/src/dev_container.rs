@@ -0,0 +1,679 @@
+use std::{
+    collections::BTreeMap,
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use gpui::{App, Context, Entity, SharedString, Task, Window, actions};
+use project::{Fs, Project};
+use remote::{ConnectionState, DockerConnectionOptions, RemoteClient, RemoteConnectionOptions};
+use serde::Deserialize;
+use ui::{Tooltip, prelude::*};
+use workspace::{ItemHandle, OpenOptions, StatusItemView, Workspace};
+
+use crate::open_remote_project;
+
+actions!(
+    dev_container,
+    [
+        /// Builds and starts the dev container described by the project's
+        /// `devcontainer.json`, and reopens the project inside it.
+        ReopenInContainer
+    ]
+);
+
+const CONFIG_PATHS: [&str; 2] = [".devcontainer/devcontainer.json", ".devcontainer.json"];
+const LOCAL_FOLDER_LABEL: &str = "dev.zed.local_folder";
+const WORKSPACE_FOLDER_LABEL: &str = "dev.zed.workspace_folder";
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let project = workspace.project().clone();
+        let indicator = cx.new(|cx| DevContainerIndicator::new(project, cx));
+        workspace.status_bar().update(cx, |status_bar, cx| {
+            status_bar.add_right_item(indicator, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ReopenInContainer, window, cx| {
+            let Some(indicator) = workspace
+                .status_bar()
+                .read(cx)
+                .item_of_type::<DevContainerIndicator>()
+            else {
+                return;
+            };
+            indicator.update(cx, |indicator, cx| {
+                indicator.reopen_in_container(workspace, window, cx)
+            });
+        });
+    })
+    .detach();
+}
+
+/// The subset of the [dev container configuration](https://containers.dev/implementors/json_reference/)
+/// that Zed understands.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DevContainerConfig {
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub build: Option<DevContainerBuild>,
+    /// The deprecated spelling of `build.dockerfile`.
+    pub docker_file: Option<String>,
+    /// The deprecated spelling of `build.context`.
+    pub context: Option<String>,
+    pub docker_compose_file: Option<serde_json_lenient::Value>,
+    pub workspace_folder: Option<String>,
+    pub container_user: Option<String>,
+    pub remote_user: Option<String>,
+    #[serde(default)]
+    pub container_env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub run_args: Vec<String>,
+    pub post_create_command: Option<LifecycleCommand>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DevContainerBuild {
+    pub dockerfile: Option<String>,
+    pub context: Option<String>,
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum LifecycleCommand {
+    Shell(String),
+    Args(Vec<String>),
+}
+
+impl DevContainerConfig {
+    pub(crate) fn parse(content: &str) -> Result<Self> {
+        let config: Self = serde_json_lenient::from_str(content)?;
+        if config.docker_compose_file.is_some() {
+            bail!("dev containers defined with Docker Compose are not supported");
+        }
+        if config.image.is_none() && config.dockerfile().is_none() {
+            bail!("the dev container must specify either `image` or `build.dockerfile`");
+        }
+        Ok(config)
+    }
+
+    fn dockerfile(&self) -> Option<&str> {
+        self.build
+            .as_ref()
+            .and_then(|build| build.dockerfile.as_deref())
+            .or(self.docker_file.as_deref())
+    }
+
+    fn build_context(&self) -> &str {
+        self.build
+            .as_ref()
+            .and_then(|build| build.context.as_deref())
+            .or(self.context.as_deref())
+            .unwrap_or(".")
+    }
+
+    fn workspace_folder(&self, project_root: &Path) -> String {
+        self.workspace_folder.clone().unwrap_or_else(|| {
+            let folder_name = project_root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "workspace".to_string());
+            format!("/workspaces/{folder_name}")
+        })
+    }
+
+    /// The user that the remote server, terminals and tasks run as.
+    fn user(&self) -> Option<String> {
+        self.remote_user.clone().or(self.container_user.clone())
+    }
+}
+
+/// Finds the dev container configuration of the project rooted at `project_root`.
+pub(crate) async fn find_config(fs: &dyn Fs, project_root: &Path) -> Option<PathBuf> {
+    for config_path in CONFIG_PATHS {
+        let config_path = project_root.join(config_path);
+        if fs.is_file(&config_path).await {
+            return Some(config_path);
+        }
+    }
+    None
+}
+
+/// Returns the arguments to `docker run` that create the container for the given project.
+fn docker_run_args(
+    config: &DevContainerConfig,
+    project_root: &Path,
+    image: &str,
+    workspace_folder: &str,
+) -> Vec<String> {
+    let mut args = vec![
+        "run".to_string(),
+        "--detach".to_string(),
+        "--label".to_string(),
+        format!("{LOCAL_FOLDER_LABEL}={}", project_root.display()),
+        "--label".to_string(),
+        format!("{WORKSPACE_FOLDER_LABEL}={workspace_folder}"),
+        "--mount".to_string(),
+        format!(
+            "type=bind,source={},target={workspace_folder}",
+            project_root.display()
+        ),
+    ];
+    if let Some(user) = &config.container_user {
+        args.push("--user".to_string());
+        args.push(user.clone());
+    }
+    for (key, value) in &config.container_env {
+        args.push("--env".to_string());
+        args.push(format!("{key}={value}"));
+    }
+    args.extend(config.run_args.iter().cloned());
+    // Keep the container alive regardless of the image's own entrypoint.
+    args.extend([
+        "--entrypoint".to_string(),
+        "/bin/sh".to_string(),
+        image.to_string(),
+        "-c".to_string(),
+        "while sleep 1000; do :; done".to_string(),
+    ]);
+    args
+}
+
+async fn run_docker(args: &[String]) -> Result<String> {
+    let output = util::command::new_smol_command("docker")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("failed to run docker, is it installed?")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "`docker {}` failed: {}",
+            args.first().map(String::as_str).unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Starts the dev container of the project at `project_root`, building and creating it if needed.
+///
+/// Returns the options to connect to the container, and the folder the project is mounted at.
+async fn start_container(
+    project_root: &Path,
+    config_path: &Path,
+    config: &DevContainerConfig,
+    set_status: &mut dyn FnMut(&str),
+) -> Result<(DockerConnectionOptions, PathBuf)> {
+    let label_filter = format!("label={LOCAL_FOLDER_LABEL}={}", project_root.display());
+    let existing_container = run_docker(&[
+        "ps".into(),
+        "--all".into(),
+        "--quiet".into(),
+        "--filter".into(),
+        label_filter,
+    ])
+    .await?;
+
+    let (container_id, workspace_folder) = match existing_container.lines().next() {
+        Some(container_id) => {
+            set_status("Starting container");
+            let container_id = container_id.to_string();
+            run_docker(&["start".into(), container_id.clone()]).await?;
+            let workspace_folder = run_docker(&[
+                "inspect".into(),
+                "--format".into(),
+                format!("{{{{ index .Config.Labels \"{WORKSPACE_FOLDER_LABEL}\" }}}}"),
+                container_id.clone(),
+            ])
+            .await?;
+            (container_id, workspace_folder)
+        }
+        None => {
+            let image = match config.dockerfile() {
+                Some(dockerfile) => {
+                    set_status("Building image");
+                    let config_dir = config_path.parent().unwrap_or(project_root);
+                    let image = image_name(project_root);
+                    let mut args = vec![
+                        "build".to_string(),
+                        "--file".to_string(),
+                        config_dir.join(dockerfile).to_string_lossy().into_owned(),
+                        "--tag".to_string(),
+                        image.clone(),
+                    ];
+                    if let Some(build) = &config.build {
+                        for (key, value) in &build.args {
+                            args.push("--build-arg".to_string());
+                            args.push(format!("{key}={value}"));
+                        }
+                    }
+                    args.push(
+                        config_dir
+                            .join(config.build_context())
+                            .to_string_lossy()
+                            .into_owned(),
+                    );
+                    run_docker(&args).await?;
+                    image
+                }
+                None => {
+                    let image = config.image.clone().context("no image specified")?;
+                    set_status("Pulling image");
+                    run_docker(&["pull".into(), image.clone()]).await?;
+                    image
+                }
+            };
+
+            set_status("Creating container");
+            let workspace_folder = config.workspace_folder(project_root);
+            let container_id = run_docker(&docker_run_args(
+                config,
+                project_root,
+                &image,
+                &workspace_folder,
+            ))
+            .await?;
+
+            if let Some(command) = &config.post_create_command {
+                set_status("Running post-create command");
+                let mut args = vec!["exec".to_string()];
+                if let Some(user) = config.user() {
+                    args.push("--user".to_string());
+                    args.push(user);
+                }
+                args.extend([
+                    "--workdir".to_string(),
+                    workspace_folder.clone(),
+                    container_id.clone(),
+                ]);
+                match command {
+                    LifecycleCommand::Shell(command) => {
+                        args.extend(["/bin/sh".to_string(), "-c".to_string(), command.clone()])
+                    }
+                    LifecycleCommand::Args(command) => args.extend(command.iter().cloned()),
+                }
+                run_docker(&args)
+                    .await
+                    .context("the post-create command failed")?;
+            }
+            (container_id, workspace_folder)
+        }
+    };
+
+    let name = config.name.clone().unwrap_or_else(|| {
+        project_root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| container_id.clone())
+    });
+    Ok((
+        DockerConnectionOptions {
+            container_id,
+            name,
+            user: config.user(),
+        },
+        PathBuf::from(workspace_folder),
+    ))
+}
+
+fn image_name(project_root: &Path) -> String {
+    let mut hasher = DefaultHasher::new();
+    project_root.hash(&mut hasher);
+    format!("zed-dev-container-{:016x}", hasher.finish())
+}
+
+enum DevContainerState {
+    /// The project isn't local, or has no dev container configuration.
+    None,
+    /// The local project has a dev container configuration it can be reopened in.
+    Available {
+        project_root: PathBuf,
+        config_path: PathBuf,
+    },
+    Starting {
+        project_root: PathBuf,
+        config_path: PathBuf,
+        status: SharedString,
+    },
+    Failed {
+        project_root: PathBuf,
+        config_path: PathBuf,
+        error: SharedString,
+    },
+    /// The project is open inside a dev container.
+    Remote(Entity<RemoteClient>),
+}
+
+/// Shows whether the project can be, or is being, opened in a dev container, and the state of that container.
+pub struct DevContainerIndicator {
+    project: Entity<Project>,
+    state: DevContainerState,
+    _task: Task<()>,
+}
+
+impl DevContainerIndicator {
+    fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            project: project.clone(),
+            state: DevContainerState::None,
+            _task: Task::ready(()),
+        };
+
+        if let Some(remote_client) = project.read(cx).remote_client() {
+            if matches!(
+                remote_client.read(cx).connection_options(),
+                RemoteConnectionOptions::Docker(_)
+            ) {
+                cx.observe(&remote_client, |_, _, cx| cx.notify()).detach();
+                this.state = DevContainerState::Remote(remote_client);
+            }
+        } else if project.read(cx).is_local() && project.read(cx).file_transfer_host().is_none() {
+            cx.subscribe(&project, |this, _, event, cx| {
+                if matches!(
+                    event,
+                    project::Event::WorktreeAdded(_) | project::Event::WorktreeRemoved(_)
+                ) {
+                    this.detect_config(cx);
+                }
+            })
+            .detach();
+            this.detect_config(cx);
+        }
+        this
+    }
+
+    fn detect_config(&mut self, cx: &mut Context<Self>) {
+        if !matches!(
+            self.state,
+            DevContainerState::None | DevContainerState::Available { .. }
+        ) {
+            return;
+        }
+
+        let fs = self.project.read(cx).fs().clone();
+        let roots = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .collect::<Vec<_>>();
+        self._task = cx.spawn(async move |this, cx| {
+            let mut state = DevContainerState::None;
+            for project_root in roots {
+                if let Some(config_path) = find_config(fs.as_ref(), &project_root).await {
+                    state = DevContainerState::Available {
+                        project_root,
+                        config_path,
+                    };
+                    break;
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.state = state;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn reopen_in_container(
+        &mut self,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (project_root, config_path) = match &self.state {
+            DevContainerState::Available {
+                project_root,
+                config_path,
+            }
+            | DevContainerState::Failed {
+                project_root,
+                config_path,
+                ..
+            } => (project_root.clone(), config_path.clone()),
+            DevContainerState::None
+            | DevContainerState::Starting { .. }
+            | DevContainerState::Remote(_) => return,
+        };
+        let Some(window_handle) = window.window_handle().downcast::<Workspace>() else {
+            return;
+        };
+        let app_state = workspace.app_state().clone();
+        let fs = app_state.fs.clone();
+
+        self.set_starting("Reading configuration", cx);
+        self._task = cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                let content = fs.load(&config_path).await?;
+                let config = DevContainerConfig::parse(&content)
+                    .with_context(|| format!("invalid {}", config_path.display()))?;
+                let (connection_options, workspace_folder) =
+                    start_container(&project_root, &config_path, &config, &mut |status| {
+                        this.update(cx, |this, cx| this.set_starting(status, cx))
+                            .ok();
+                    })
+                    .await?;
+
+                this.update(cx, |this, cx| this.set_starting("Connecting", cx))?;
+                open_remote_project(
+                    RemoteConnectionOptions::Docker(connection_options),
+                    vec![workspace_folder],
+                    app_state,
+                    OpenOptions {
+                        replace_window: Some(window_handle),
+                        ..Default::default()
+                    },
+                    cx,
+                )
+                .await
+            }
+            .await;
+
+            if let Err(error) = result {
+                log::error!("failed to open the dev container: {error:#}");
+                this.update(cx, |this, cx| {
+                    this.state = DevContainerState::Failed {
+                        project_root,
+                        config_path,
+                        error: format!("{error:#}").into(),
+                    };
+                    cx.notify();
+                })
+                .ok();
+            }
+        });
+    }
+
+    fn set_starting(&mut self, status: &str, cx: &mut Context<Self>) {
+        let (project_root, config_path) = match &self.state {
+            DevContainerState::Available {
+                project_root,
+                config_path,
+            }
+            | DevContainerState::Starting {
+                project_root,
+                config_path,
+                ..
+            }
+            | DevContainerState::Failed {
+                project_root,
+                config_path,
+                ..
+            } => (project_root.clone(), config_path.clone()),
+            DevContainerState::None | DevContainerState::Remote(_) => return,
+        };
+        self.state = DevContainerState::Starting {
+            project_root,
+            config_path,
+            status: SharedString::from(status.to_string()),
+        };
+        cx.notify();
+    }
+}
+
+impl Render for DevContainerIndicator {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (label, color, tooltip): (SharedString, Color, SharedString) = match &self.state {
+            DevContainerState::None => return div(),
+            DevContainerState::Available { config_path, .. } => (
+                "Dev Container".into(),
+                Color::Muted,
+                format!(
+                    "Reopen in the container described by {}",
+                    config_path.display()
+                )
+                .into(),
+            ),
+            DevContainerState::Starting { status, .. } => (
+                format!("Dev Container: {status}…").into(),
+                Color::Info,
+                status.clone(),
+            ),
+            DevContainerState::Failed { error, .. } => (
+                "Dev Container Failed".into(),
+                Color::Error,
+                format!("{error}\n\nClick to retry").into(),
+            ),
+            DevContainerState::Remote(remote_client) => {
+                let remote_client = remote_client.read(cx);
+                let RemoteConnectionOptions::Docker(options) = remote_client.connection_options()
+                else {
+                    return div();
+                };
+                let (state, color) = match remote_client.connection_state() {
+                    ConnectionState::Connecting => ("Starting", Color::Info),
+                    ConnectionState::Connected => ("Running", Color::Success),
+                    ConnectionState::HeartbeatMissed | ConnectionState::Reconnecting => {
+                        ("Reconnecting", Color::Warning)
+                    }
+                    ConnectionState::Disconnected => ("Stopped", Color::Error),
+                };
+                (
+                    format!("{}: {state}", options.name).into(),
+                    color,
+                    format!("Dev container {} ({})", options.name, options.short_id()).into(),
+                )
+            }
+        };
+        let clickable = matches!(
+            self.state,
+            DevContainerState::Available { .. } | DevContainerState::Failed { .. }
+        );
+
+        div().child(
+            Button::new("dev-container", label)
+                .icon(IconName::Blocks)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::Small)
+                .icon_color(color)
+                .label_size(LabelSize::Small)
+                .tooltip(Tooltip::text(tooltip))
+                .when(clickable, |button| {
+                    button.on_click(cx.listener(|_, _, window, cx| {
+                        window.dispatch_action(Box::new(ReopenInContainer), cx);
+                    }))
+                }),
+        )
+    }
+}
+
+impl StatusItemView for DevContainerIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_config() {
+        let config = DevContainerConfig::parse(indoc! {r#"
+            // Comments and trailing commas are allowed.
+            {
+                "name": "Rust",
+                "build": {
+                    "dockerfile": "Dockerfile",
+                    "args": { "VARIANT": "bookworm" },
+                },
+                "remoteUser": "vscode",
+                "containerEnv": { "CARGO_HOME": "/cargo" },
+                "postCreateCommand": ["cargo", "fetch"],
+            }
+        "#})
+        .unwrap();
+        assert_eq!(config.name.as_deref(), Some("Rust"));
+        assert_eq!(config.dockerfile(), Some("Dockerfile"));
+        assert_eq!(config.build_context(), ".");
+        assert_eq!(config.user().as_deref(), Some("vscode"));
+        assert_eq!(
+            config.post_create_command,
+            Some(LifecycleCommand::Args(vec![
+                "cargo".to_string(),
+                "fetch".to_string()
+            ]))
+        );
+
+        let config =
+            DevContainerConfig::parse(r#"{ "image": "ubuntu", "dockerFile": "x" }"#).unwrap();
+        assert_eq!(config.dockerfile(), Some("x"));
+
+        assert!(DevContainerConfig::parse(r#"{ "name": "no image" }"#).is_err());
+        assert!(DevContainerConfig::parse(r#"{ "dockerComposeFile": "compose.yml" }"#).is_err());
+    }
+
+    #[test]
+    fn test_docker_run_args() {
+        let config = DevContainerConfig::parse(indoc! {r#"
+            {
+                "image": "ubuntu",
+                "containerUser": "dev",
+                "containerEnv": { "FOO": "bar" },
+                "runArgs": ["--cap-add=SYS_PTRACE"],
+            }
+        "#})
+        .unwrap();
+        let project_root = Path::new("/home/me/project");
+        let workspace_folder = config.workspace_folder(project_root);
+        assert_eq!(workspace_folder, "/workspaces/project");
+        assert_eq!(
+            docker_run_args(&config, project_root, "ubuntu", &workspace_folder),
+            [
+                "run",
+                "--detach",
+                "--label",
+                "dev.zed.local_folder=/home/me/project",
+                "--label",
+                "dev.zed.workspace_folder=/workspaces/project",
+                "--mount",
+                "type=bind,source=/home/me/project,target=/workspaces/project",
+                "--user",
+                "dev",
+                "--env",
+                "FOO=bar",
+                "--cap-add=SYS_PTRACE",
+                "--entrypoint",
+                "/bin/sh",
+                "ubuntu",
+                "-c",
+                "while sleep 1000; do :; done",
+            ]
+        );
+    }
+}
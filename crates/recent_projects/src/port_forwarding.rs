@@ -0,0 +1,362 @@
+use std::time::Duration;
+
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task,
+    Window, actions,
+};
+use remote::{PortForward, RemoteClient, RemoteConnectionOptions};
+use settings::Settings;
+use ui::{ListItem, ListItemSpacing, Modal, ModalHeader, Section, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::{ItemHandle, ModalView, StatusItemView, Workspace};
+
+use crate::SshSettings;
+
+actions!(
+    remote,
+    [
+        /// Shows the ports detected on the remote host and lets you forward them.
+        TogglePortForwarding
+    ]
+);
+
+const PORT_DETECTION_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let Some(remote_client) = workspace.project().read(cx).remote_client() else {
+            return;
+        };
+        if !matches!(
+            remote_client.read(cx).connection_options(),
+            RemoteConnectionOptions::Ssh(_)
+        ) {
+            return;
+        }
+
+        let port_forwarding = cx.new(|cx| PortForwarding::new(remote_client, cx));
+        workspace.status_bar().update(cx, |status_bar, cx| {
+            status_bar.add_right_item(port_forwarding, window, cx);
+        });
+        workspace.register_action(|workspace, _: &TogglePortForwarding, window, cx| {
+            let Some(port_forwarding) = workspace
+                .status_bar()
+                .read(cx)
+                .item_of_type::<PortForwarding>()
+            else {
+                return;
+            };
+            workspace.toggle_modal(window, cx, |window, cx| {
+                PortForwardingModal::new(port_forwarding, window, cx)
+            });
+        });
+    })
+    .detach();
+}
+
+/// A port some process is listening on, on the remote host.
+pub struct RemotePort {
+    pub remote_port: u16,
+    pub label: Option<SharedString>,
+    pub forward: Option<PortForward>,
+}
+
+/// Keeps track of the ports listened on by the remote host of an SSH project,
+/// forwarding them to the local machine on demand or according to the user's rules.
+pub struct PortForwarding {
+    remote_client: Entity<RemoteClient>,
+    ports: Vec<RemotePort>,
+    _detect_ports: Task<()>,
+}
+
+impl PortForwarding {
+    fn new(remote_client: Entity<RemoteClient>, cx: &mut Context<Self>) -> Self {
+        let detect_ports = cx.spawn(async move |this, cx| {
+            loop {
+                let Ok(listening_ports) = this.update(cx, |this, cx| {
+                    if !SshSettings::get_global(cx).port_forwarding.detect_ports {
+                        return None;
+                    }
+                    let remote_client = this.remote_client.read(cx);
+                    if remote_client.is_disconnected() {
+                        return None;
+                    }
+                    Some(remote_client.listening_ports(cx))
+                }) else {
+                    break;
+                };
+
+                if let Some(listening_ports) = listening_ports {
+                    match listening_ports.await {
+                        Ok(ports) => {
+                            if this
+                                .update(cx, |this, cx| this.update_ports(ports, cx))
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        Err(error) => log::debug!("failed to detect remote ports: {error:#}"),
+                    }
+                }
+
+                cx.background_executor()
+                    .timer(PORT_DETECTION_INTERVAL)
+                    .await;
+            }
+        });
+
+        Self {
+            remote_client,
+            ports: Vec::new(),
+            _detect_ports: detect_ports,
+        }
+    }
+
+    pub fn ports(&self) -> &[RemotePort] {
+        &self.ports
+    }
+
+    fn update_ports(&mut self, listening_ports: Vec<u16>, cx: &mut Context<Self>) {
+        let settings = SshSettings::get_global(cx).port_forwarding.clone();
+
+        self.ports
+            .retain(|port| listening_ports.contains(&port.remote_port));
+        for remote_port in listening_ports {
+            if remote_port < settings.min_port
+                || self
+                    .ports
+                    .iter()
+                    .any(|port| port.remote_port == remote_port)
+            {
+                continue;
+            }
+
+            let rule = settings.rules.iter().find(|rule| rule.port == remote_port);
+            if rule.and_then(|rule| rule.ignore).unwrap_or(false) {
+                continue;
+            }
+
+            let mut port = RemotePort {
+                remote_port,
+                label: rule
+                    .and_then(|rule| rule.label.clone())
+                    .map(SharedString::from),
+                forward: None,
+            };
+            if rule
+                .and_then(|rule| rule.auto_forward)
+                .unwrap_or(settings.auto_forward)
+            {
+                let local_port = rule.and_then(|rule| rule.local_port).unwrap_or(remote_port);
+                port.forward = self
+                    .remote_client
+                    .read(cx)
+                    .forward_port(local_port, remote_port)
+                    .log_err();
+            }
+            self.ports.push(port);
+        }
+        self.ports.sort_by_key(|port| port.remote_port);
+        cx.notify();
+    }
+
+    pub fn toggle_forward(&mut self, remote_port: u16, cx: &mut Context<Self>) {
+        let local_port = SshSettings::get_global(cx)
+            .port_forwarding
+            .rules
+            .iter()
+            .find(|rule| rule.port == remote_port)
+            .and_then(|rule| rule.local_port)
+            .unwrap_or(remote_port);
+        let remote_client = self.remote_client.read(cx);
+        let Some(port) = self
+            .ports
+            .iter_mut()
+            .find(|port| port.remote_port == remote_port)
+        else {
+            return;
+        };
+
+        if port.forward.take().is_none() {
+            port.forward = remote_client
+                .forward_port(local_port, remote_port)
+                .log_err();
+        }
+        cx.notify();
+    }
+}
+
+impl Render for PortForwarding {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.ports.is_empty() {
+            return div();
+        }
+
+        let forwarded_count = self
+            .ports
+            .iter()
+            .filter(|port| port.forward.is_some())
+            .count();
+        let label = if forwarded_count == 0 {
+            format!("{} Ports", self.ports.len())
+        } else {
+            format!("{forwarded_count}/{} Ports Forwarded", self.ports.len())
+        };
+
+        div().child(
+            Button::new("port-forwarding", label)
+                .icon(IconName::ArrowRightLeft)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::Small)
+                .icon_color(Color::Muted)
+                .label_size(LabelSize::Small)
+                .tooltip(Tooltip::text("Remote Port Forwarding"))
+                .on_click(cx.listener(|_, _, window, cx| {
+                    window.dispatch_action(Box::new(TogglePortForwarding), cx);
+                })),
+        )
+    }
+}
+
+impl StatusItemView for PortForwarding {
+    fn set_active_pane_item(
+        &mut self,
+        _active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+}
+
+pub struct PortForwardingModal {
+    port_forwarding: Entity<PortForwarding>,
+    focus_handle: FocusHandle,
+    _observe_port_forwarding: Subscription,
+}
+
+impl PortForwardingModal {
+    fn new(
+        port_forwarding: Entity<PortForwarding>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle);
+        Self {
+            _observe_port_forwarding: cx.observe(&port_forwarding, |_, _, cx| cx.notify()),
+            port_forwarding,
+            focus_handle,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_port(&self, ix: usize, port: &RemotePort) -> ListItem {
+        let remote_port = port.remote_port;
+        let port_forwarding = self.port_forwarding.clone();
+
+        let end_slot = match &port.forward {
+            Some(forward) => {
+                let url = format!("http://localhost:{}", forward.local_port);
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(
+                            ("open-port", ix),
+                            format!("localhost:{}", forward.local_port),
+                        )
+                        .label_size(LabelSize::Small)
+                        .color(Color::Accent)
+                        .tooltip(Tooltip::text("Open in Browser"))
+                        .on_click(move |_, _, cx| cx.open_url(&url)),
+                    )
+                    .child(
+                        IconButton::new(("stop-forwarding", ix), IconName::Close)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Stop Forwarding"))
+                            .on_click(move |_, _, cx| {
+                                port_forwarding.update(cx, |port_forwarding, cx| {
+                                    port_forwarding.toggle_forward(remote_port, cx)
+                                })
+                            }),
+                    )
+                    .into_any_element()
+            }
+            None => Button::new(("forward-port", ix), "Forward")
+                .label_size(LabelSize::Small)
+                .on_click(move |_, _, cx| {
+                    port_forwarding.update(cx, |port_forwarding, cx| {
+                        port_forwarding.toggle_forward(remote_port, cx)
+                    })
+                })
+                .into_any_element(),
+        };
+
+        ListItem::new(("remote-port", ix))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .start_slot(Icon::new(IconName::Server).color(Color::Muted))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(remote_port.to_string()))
+                    .children(
+                        port.label
+                            .clone()
+                            .map(|label| Label::new(label).color(Color::Muted)),
+                    ),
+            )
+            .end_slot(end_slot)
+    }
+}
+
+impl ModalView for PortForwardingModal {}
+
+impl EventEmitter<DismissEvent> for PortForwardingModal {}
+
+impl Focusable for PortForwardingModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for PortForwardingModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let port_forwarding = self.port_forwarding.read(cx);
+        let ports = port_forwarding
+            .ports()
+            .iter()
+            .enumerate()
+            .map(|(ix, port)| self.render_port(ix, port))
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("PortForwardingModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(34.))
+            .child(
+                Modal::new("port-forwarding", None)
+                    .header(ModalHeader::new().headline("Remote Ports"))
+                    .section(Section::new().padded(false).map(|section| {
+                        if ports.is_empty() {
+                            section.child(
+                                div().p_2().child(
+                                    Label::new("No listening ports detected on the remote host")
+                                        .color(Color::Muted),
+                                ),
+                            )
+                        } else {
+                            section.children(ports)
+                        }
+                    })),
+            )
+    }
+}
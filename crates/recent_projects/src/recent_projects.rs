@@ -1,4 +1,6 @@
+mod dev_container;
 pub mod disconnected_overlay;
+mod port_forwarding;
 mod remote_connections;
 mod remote_servers;
 mod ssh_config;
@@ -9,31 +11,54 @@ mod wsl_picker;
 use remote::RemoteConnectionOptions;
 pub use remote_connections::open_remote_project;
 
+use collections::HashMap;
+pub use dev_container::{DevContainerIndicator, ReopenInContainer};
 use disconnected_overlay::DisconnectedOverlay;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
     Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    Subscription, Task, WeakEntity, Window,
+    Subscription, Task, WeakEntity, Window, actions,
 };
 use ordered_float::OrderedFloat;
 use picker::{
     Picker, PickerDelegate,
     highlighted_match_with_paths::{HighlightedMatch, HighlightedMatchWithPaths},
 };
+pub use port_forwarding::{PortForwarding, TogglePortForwarding};
 pub use remote_connections::SshSettings;
-pub use remote_servers::RemoteServerProjects;
+pub use remote_servers::{Reconnect, RemoteServerProjects};
 use settings::Settings;
-use std::{path::Path, sync::Arc};
-use ui::{KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*, tooltip_container};
+use std::{path::Path, sync::Arc, time::Duration};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{
+    KeyBinding, ListItem, ListItemSpacing, ListSubHeader, Tooltip, prelude::*, tooltip_container,
+};
 use util::{ResultExt, paths::PathExt};
 use workspace::{
-    CloseIntent, HistoryManager, ModalView, OpenOptions, PathList, SerializedWorkspaceLocation,
-    WORKSPACE_DB, Workspace, WorkspaceId, with_active_or_new_workspace,
+    CloseIntent, HistoryManager, ModalView, OpenOptions, PathList, RecentWorkspaceMetadata,
+    SerializedWorkspaceLocation, WORKSPACE_DB, Workspace, WorkspaceId,
+    with_active_or_new_workspace,
 };
 use zed_actions::{OpenRecent, OpenRemote};
 
+actions!(
+    recent_projects,
+    [
+        /// Pins or unpins the selected recent project, keeping it at the top of the list.
+        TogglePinned,
+        /// Gives the selected recent project a custom name.
+        RenameProject,
+        /// Removes unpinned recent projects that haven't been opened for 30 days.
+        RemoveStaleProjects,
+    ]
+);
+
+const STALE_PROJECT_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
 pub fn init(cx: &mut App) {
     SshSettings::register(cx);
+    port_forwarding::init(cx);
+    dev_container::init(cx);
 
     #[cfg(target_os = "windows")]
     cx.on_action(|open_wsl: &zed_actions::wsl_actions::OpenFolderInWsl, cx| {
@@ -64,6 +89,40 @@ pub fn init(cx: &mut App) {
                     return;
                 };
 
+                // Folders picked through a distro's share, like `\\wsl.localhost\Ubuntu\home`,
+                // are opened in that distro right away.
+                if let [path] = paths.as_slice()
+                    && let Some((distro_name, path)) =
+                        util::paths::parse_wsl_share_path(&path.to_string_lossy())
+                {
+                    let Ok(app_state) =
+                        workspace.read_with(cx, |workspace, _| workspace.app_state().clone())
+                    else {
+                        return;
+                    };
+                    let replace_window = if create_new_window {
+                        None
+                    } else {
+                        cx.window_handle().downcast::<Workspace>()
+                    };
+                    open_remote_project(
+                        RemoteConnectionOptions::Wsl(remote::WslConnectionOptions {
+                            distro_name,
+                            user: None,
+                        }),
+                        vec![path.into()],
+                        app_state,
+                        OpenOptions {
+                            replace_window,
+                            ..Default::default()
+                        },
+                        cx,
+                    )
+                    .await
+                    .log_err();
+                    return;
+                }
+
                 let paths = paths
                     .into_iter()
                     .filter_map(|path| SanitizedPath::new(&path).local_to_wsl())
@@ -133,6 +192,18 @@ pub fn init(cx: &mut App) {
         });
     });
 
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &Reconnect, _, cx| {
+            let Some(remote_client) = workspace.project().read(cx).remote_client() else {
+                return;
+            };
+            remote_client
+                .update(cx, |remote_client, cx| remote_client.manual_reconnect(cx))
+                .log_err();
+        });
+    })
+    .detach();
+
     cx.observe_new(DisconnectedOverlay::register).detach();
 }
 
@@ -163,14 +234,10 @@ impl RecentProjects {
         // We do not want to block the UI on a potentially lengthy call to DB, so we're gonna swap
         // out workspace locations once the future runs to completion.
         cx.spawn_in(window, async move |this, cx| {
-            let workspaces = WORKSPACE_DB
-                .recent_workspaces_on_disk()
-                .await
-                .log_err()
-                .unwrap_or_default();
+            let recent_projects = cx.background_spawn(RecentProjectsData::load()).await;
             this.update_in(cx, move |this, window, cx| {
                 this.picker.update(cx, move |picker, cx| {
-                    picker.delegate.set_workspaces(workspaces);
+                    picker.delegate.set_data(recent_projects);
                     picker.update_matches(picker.query(cx), window, cx)
                 })
             })
@@ -197,6 +264,34 @@ impl RecentProjects {
             Self::new(delegate, 34., window, cx)
         })
     }
+
+    fn toggle_pinned(&mut self, _: &TogglePinned, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let ix = picker.delegate.selected_index();
+            picker.delegate.toggle_pinned(ix, window, cx);
+        });
+    }
+
+    fn rename_project(&mut self, _: &RenameProject, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let ix = picker.delegate.selected_index();
+            if let Some(current_name) = picker.delegate.start_renaming(ix) {
+                picker.set_query(current_name, window, cx);
+                picker.refresh_placeholder(window, cx);
+            }
+        });
+    }
+
+    fn remove_stale_projects(
+        &mut self,
+        _: &RemoveStaleProjects,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker.delegate.remove_stale_projects(window, cx);
+        });
+    }
 }
 
 impl EventEmitter<DismissEvent> for RecentProjects {}
@@ -212,6 +307,9 @@ impl Render for RecentProjects {
         v_flex()
             .key_context("RecentProjects")
             .w(rems(self.rem_width))
+            .on_action(cx.listener(Self::toggle_pinned))
+            .on_action(cx.listener(Self::rename_project))
+            .on_action(cx.listener(Self::remove_stale_projects))
             .child(self.picker.clone())
             .on_mouse_down_out(cx.listener(|this, _, window, cx| {
                 this.picker.update(cx, |this, cx| {
@@ -221,16 +319,85 @@ impl Render for RecentProjects {
     }
 }
 
+/// Everything shown by the recent projects picker, loaded off the main thread.
+struct RecentProjectsData {
+    workspaces: Vec<(WorkspaceId, SerializedWorkspaceLocation, PathList)>,
+    metadata: HashMap<WorkspaceId, RecentWorkspaceMetadata>,
+    branches: HashMap<WorkspaceId, SharedString>,
+}
+
+impl RecentProjectsData {
+    async fn load() -> Self {
+        let workspaces = WORKSPACE_DB
+            .recent_workspaces_on_disk()
+            .await
+            .log_err()
+            .unwrap_or_default();
+        let metadata = WORKSPACE_DB
+            .recent_workspaces_metadata()
+            .log_err()
+            .unwrap_or_default();
+        let branches = workspaces
+            .iter()
+            .filter(|(_, location, _)| matches!(location, SerializedWorkspaceLocation::Local))
+            .filter_map(|(id, _, paths)| Some((*id, current_branch(paths.paths().first()?)?)))
+            .collect();
+        Self {
+            workspaces,
+            metadata,
+            branches,
+        }
+    }
+}
+
+/// Reads the checked out branch of a local repository, without spawning git for every recent project.
+fn current_branch(path: &Path) -> Option<SharedString> {
+    let head = std::fs::read_to_string(path.join(".git").join("HEAD")).ok()?;
+    let branch = head.trim().strip_prefix("ref: refs/heads/")?;
+    Some(SharedString::from(branch.to_owned()))
+}
+
+/// The name of the group a project is listed under when the picker isn't filtered.
+fn group_name(location: &SerializedWorkspaceLocation, paths: &PathList) -> SharedString {
+    match location {
+        SerializedWorkspaceLocation::Local => paths
+            .paths()
+            .first()
+            .and_then(|path| path.parent())
+            .map(|parent| parent.compact().to_string_lossy().into_owned().into())
+            .unwrap_or_else(|| "Local".into()),
+        SerializedWorkspaceLocation::Remote(RemoteConnectionOptions::Ssh(options)) => {
+            format!("SSH: {}", options.host).into()
+        }
+        SerializedWorkspaceLocation::Remote(RemoteConnectionOptions::Wsl(options)) => {
+            format!("WSL: {}", options.distro_name).into()
+        }
+        SerializedWorkspaceLocation::Remote(RemoteConnectionOptions::Docker(options)) => {
+            format!("Container: {}", options.name).into()
+        }
+    }
+}
+
+enum RecentProjectEntry {
+    Header(SharedString),
+    Project(StringMatch),
+}
+
 pub struct RecentProjectsDelegate {
     workspace: WeakEntity<Workspace>,
     workspaces: Vec<(WorkspaceId, SerializedWorkspaceLocation, PathList)>,
+    metadata: HashMap<WorkspaceId, RecentWorkspaceMetadata>,
+    branches: HashMap<WorkspaceId, SharedString>,
     selected_match_index: usize,
-    matches: Vec<StringMatch>,
+    entries: Vec<RecentProjectEntry>,
     render_paths: bool,
     create_new_window: bool,
     // Flag to reset index when there is a new query vs not reset index when user delete an item
     reset_selected_match_index: bool,
     has_any_non_local_projects: bool,
+    /// The project being renamed, in which case the query holds its new name.
+    renaming: Option<WorkspaceId>,
+    query: String,
 }
 
 impl RecentProjectsDelegate {
@@ -238,12 +405,16 @@ impl RecentProjectsDelegate {
         Self {
             workspace,
             workspaces: Vec::new(),
+            metadata: HashMap::default(),
+            branches: HashMap::default(),
             selected_match_index: 0,
-            matches: Default::default(),
+            entries: Default::default(),
             create_new_window,
             render_paths,
             reset_selected_match_index: true,
             has_any_non_local_projects: false,
+            renaming: None,
+            query: String::new(),
         }
     }
 
@@ -257,12 +428,82 @@ impl RecentProjectsDelegate {
             .iter()
             .all(|(_, location, _)| matches!(location, SerializedWorkspaceLocation::Local));
     }
+
+    fn set_data(&mut self, data: RecentProjectsData) {
+        self.set_workspaces(data.workspaces);
+        self.metadata = data.metadata;
+        self.branches = data.branches;
+    }
+
+    fn display_name(&self, workspace_id: WorkspaceId) -> Option<&str> {
+        self.metadata
+            .get(&workspace_id)?
+            .display_name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+    }
+
+    fn is_pinned(&self, workspace_id: WorkspaceId) -> bool {
+        self.metadata
+            .get(&workspace_id)
+            .is_some_and(|metadata| metadata.pinned)
+    }
+
+    fn project_at(
+        &self,
+        ix: usize,
+    ) -> Option<&(WorkspaceId, SerializedWorkspaceLocation, PathList)> {
+        match self.entries.get(ix)? {
+            RecentProjectEntry::Project(hit) => self.workspaces.get(hit.candidate_id),
+            RecentProjectEntry::Header(_) => None,
+        }
+    }
+
+    /// Lists pinned projects first, then groups the others by parent directory or remote host,
+    /// ordering the groups by their most recently opened project.
+    fn grouped_entries(&self, matches: Vec<StringMatch>) -> Vec<RecentProjectEntry> {
+        let mut pinned = Vec::new();
+        let mut groups: Vec<(SharedString, Vec<StringMatch>)> = Vec::new();
+        for hit in matches {
+            let (workspace_id, location, paths) = &self.workspaces[hit.candidate_id];
+            if self.is_pinned(*workspace_id) {
+                pinned.push(hit);
+                continue;
+            }
+            let name = group_name(location, paths);
+            match groups.iter_mut().find(|(group, _)| *group == name) {
+                Some((_, group)) => group.push(hit),
+                None => groups.push((name, vec![hit])),
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (name, matches) in (!pinned.is_empty())
+            .then(|| (SharedString::new_static("Pinned"), pinned))
+            .into_iter()
+            .chain(groups)
+        {
+            entries.push(RecentProjectEntry::Header(name));
+            entries.extend(matches.into_iter().map(RecentProjectEntry::Project));
+        }
+        entries
+    }
+
+    fn first_selectable_index(&self, ix: usize) -> usize {
+        (ix..self.entries.len())
+            .chain((0..ix).rev())
+            .find(|ix| matches!(self.entries[*ix], RecentProjectEntry::Project(_)))
+            .unwrap_or(0)
+    }
 }
 impl EventEmitter<DismissEvent> for RecentProjectsDelegate {}
 impl PickerDelegate for RecentProjectsDelegate {
-    type ListItem = ListItem;
+    type ListItem = AnyElement;
 
     fn placeholder_text(&self, window: &mut Window, _: &mut App) -> Arc<str> {
+        if self.renaming.is_some() {
+            return "Enter a name for this project, or leave empty to reset it".into();
+        }
         let (create_window, reuse_window) = if self.create_new_window {
             (
                 window.keystroke_text_for(&menu::Confirm),
@@ -280,7 +521,7 @@ impl PickerDelegate for RecentProjectsDelegate {
     }
 
     fn match_count(&self) -> usize {
-        self.matches.len()
+        self.entries.len()
     }
 
     fn selected_index(&self) -> usize {
@@ -296,12 +537,42 @@ impl PickerDelegate for RecentProjectsDelegate {
         self.selected_match_index = ix;
     }
 
+    fn can_select(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> bool {
+        matches!(self.entries.get(ix), Some(RecentProjectEntry::Project(_)))
+    }
+
     fn update_matches(
         &mut self,
         query: String,
         _: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> gpui::Task<()> {
+        self.query = query.clone();
+        if let Some(renaming) = self.renaming {
+            // While renaming, the query is the project's new name, so only that project is listed.
+            self.entries = self
+                .workspaces
+                .iter()
+                .position(|(workspace_id, _, _)| *workspace_id == renaming)
+                .map(|candidate_id| {
+                    RecentProjectEntry::Project(StringMatch {
+                        candidate_id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string: String::new(),
+                    })
+                })
+                .into_iter()
+                .collect();
+            self.selected_match_index = 0;
+            return Task::ready(());
+        }
+
         let query = query.trim_start();
         let smart_case = query.chars().any(|c| c.is_uppercase());
         let candidates = self
@@ -309,17 +580,23 @@ impl PickerDelegate for RecentProjectsDelegate {
             .iter()
             .enumerate()
             .filter(|(_, (id, _, _))| !self.is_current_workspace(*id, cx))
-            .map(|(id, (_, _, paths))| {
-                let combined_string = paths
-                    .paths()
-                    .iter()
-                    .map(|path| path.compact().to_string_lossy().into_owned())
+            .map(|(id, (workspace_id, _, paths))| {
+                let combined_string = self
+                    .display_name(*workspace_id)
+                    .map(ToOwned::to_owned)
+                    .into_iter()
+                    .chain(
+                        paths
+                            .paths()
+                            .iter()
+                            .map(|path| path.compact().to_string_lossy().into_owned()),
+                    )
                     .collect::<Vec<_>>()
                     .join("");
                 StringMatchCandidate::new(id, &combined_string)
             })
             .collect::<Vec<_>>();
-        self.matches = smol::block_on(fuzzy::match_strings(
+        let mut matches = smol::block_on(fuzzy::match_strings(
             candidates.as_slice(),
             query,
             smart_case,
@@ -328,35 +605,68 @@ impl PickerDelegate for RecentProjectsDelegate {
             &Default::default(),
             cx.background_executor().clone(),
         ));
-        self.matches.sort_unstable_by(|a, b| {
+        matches.sort_unstable_by(|a, b| {
             b.score
                 .partial_cmp(&a.score) // Descending score
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .then_with(|| a.candidate_id.cmp(&b.candidate_id)) // Ascending candidate_id for ties
         });
+        self.entries = if query.is_empty() {
+            self.grouped_entries(matches)
+        } else {
+            matches
+                .into_iter()
+                .map(RecentProjectEntry::Project)
+                .collect()
+        };
 
         if self.reset_selected_match_index {
             self.selected_match_index = self
-                .matches
+                .entries
                 .iter()
                 .enumerate()
                 .rev()
-                .max_by_key(|(_, m)| OrderedFloat(m.score))
+                .filter_map(|(ix, entry)| match entry {
+                    RecentProjectEntry::Project(hit) => Some((ix, hit.score)),
+                    RecentProjectEntry::Header(_) => None,
+                })
+                .max_by_key(|(_, score)| OrderedFloat(*score))
                 .map(|(ix, _)| ix)
                 .unwrap_or(0);
+        } else {
+            self.selected_match_index = self.first_selectable_index(
+                self.selected_match_index
+                    .min(self.entries.len().saturating_sub(1)),
+            );
         }
         self.reset_selected_match_index = true;
         Task::ready(())
     }
 
     fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
-        if let Some((selected_match, workspace)) = self
-            .matches
-            .get(self.selected_index())
+        if let Some(workspace_id) = self.renaming.take() {
+            let display_name = Some(self.query.trim().to_owned()).filter(|name| !name.is_empty());
+            self.metadata.entry(workspace_id).or_default().display_name = display_name.clone();
+            cx.background_spawn(async move {
+                WORKSPACE_DB
+                    .set_workspace_display_name(workspace_id, display_name)
+                    .await
+            })
+            .detach_and_log_err(cx);
+            cx.defer_in(window, |picker, window, cx| {
+                picker.set_query("", window, cx);
+                picker.refresh_placeholder(window, cx);
+            });
+            return;
+        }
+
+        if let Some((
+            (candidate_workspace_id, candidate_workspace_location, candidate_workspace_paths),
+            workspace,
+        )) = self
+            .project_at(self.selected_index())
             .zip(self.workspace.upgrade())
         {
-            let (candidate_workspace_id, candidate_workspace_location, candidate_workspace_paths) =
-                &self.workspaces[selected_match.candidate_id];
             let replace_current_window = if self.create_new_window {
                 secondary
             } else {
@@ -455,11 +765,23 @@ impl PickerDelegate for RecentProjectsDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
-        let hit = self.matches.get(ix)?;
+        let hit = match self.entries.get(ix)? {
+            RecentProjectEntry::Header(title) => {
+                return Some(
+                    ListSubHeader::new(title.clone())
+                        .inset(true)
+                        .into_any_element(),
+                );
+            }
+            RecentProjectEntry::Project(hit) => hit,
+        };
 
-        let (_, location, paths) = self.workspaces.get(hit.candidate_id)?;
+        let (workspace_id, location, paths) = self.workspaces.get(hit.candidate_id)?;
+        let workspace_id = *workspace_id;
+        let display_name = self.display_name(workspace_id);
+        let pinned = self.is_pinned(workspace_id);
 
-        let mut path_start_offset = 0;
+        let mut path_start_offset = display_name.map_or(0, str::len);
 
         let (match_labels, paths): (Vec<_>, Vec<_>) = paths
             .paths()
@@ -473,10 +795,35 @@ impl PickerDelegate for RecentProjectsDelegate {
             })
             .unzip();
 
-        let highlighted_match = HighlightedMatchWithPaths {
-            match_label: HighlightedMatch::join(match_labels.into_iter().flatten(), ", "),
-            paths,
+        let match_label = match display_name {
+            Some(display_name) => HighlightedMatch {
+                text: display_name.to_owned(),
+                highlight_positions: hit
+                    .positions
+                    .iter()
+                    .copied()
+                    .take_while(|position| *position < display_name.len())
+                    .collect(),
+                color: Color::Default,
+            },
+            None => HighlightedMatch::join(match_labels.into_iter().flatten(), ", "),
         };
+        let highlighted_match = HighlightedMatchWithPaths { match_label, paths };
+
+        let last_opened = self
+            .metadata
+            .get(&workspace_id)
+            .and_then(|metadata| metadata.last_opened)
+            .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+            .map(|timestamp| {
+                time_format::format_localized_timestamp(
+                    timestamp,
+                    OffsetDateTime::now_utc(),
+                    UtcOffset::UTC,
+                    time_format::TimestampFormat::Relative,
+                )
+            });
+        let branch = self.branches.get(&workspace_id).cloned();
 
         Some(
             ListItem::new(ix)
@@ -496,22 +843,79 @@ impl PickerDelegate for RecentProjectsDelegate {
                                     Icon::new(match options {
                                         RemoteConnectionOptions::Ssh { .. } => IconName::Server,
                                         RemoteConnectionOptions::Wsl { .. } => IconName::Linux,
+                                        RemoteConnectionOptions::Docker { .. } => IconName::Blocks,
                                     })
                                     .color(Color::Muted)
                                     .into_any_element()
                                 }
                             })
                         })
-                        .child({
-                            let mut highlighted = highlighted_match.clone();
-                            if !self.render_paths {
-                                highlighted.paths.clear();
-                            }
-                            highlighted.render(window, cx)
-                        }),
+                        .child(
+                            v_flex()
+                                .child({
+                                    let mut highlighted = highlighted_match.clone();
+                                    if !self.render_paths {
+                                        highlighted.paths.clear();
+                                    }
+                                    highlighted.render(window, cx)
+                                })
+                                .when(
+                                    self.render_paths
+                                        && (branch.is_some() || last_opened.is_some()),
+                                    |this| {
+                                        this.child(
+                                            h_flex()
+                                                .gap_1()
+                                                .when_some(branch, |this, branch| {
+                                                    this.child(
+                                                        Icon::new(IconName::GitBranch)
+                                                            .size(IconSize::XSmall)
+                                                            .color(Color::Muted),
+                                                    )
+                                                    .child(
+                                                        Label::new(branch)
+                                                            .size(LabelSize::Small)
+                                                            .color(Color::Muted),
+                                                    )
+                                                })
+                                                .when_some(last_opened, |this, last_opened| {
+                                                    this.child(
+                                                        Label::new(last_opened)
+                                                            .size(LabelSize::Small)
+                                                            .color(Color::Muted),
+                                                    )
+                                                }),
+                                        )
+                                    },
+                                ),
+                        ),
                 )
                 .map(|el| {
-                    let delete_button = div()
+                    let buttons = h_flex()
+                        .gap_1()
+                        .child(
+                            IconButton::new("pin", IconName::Pin)
+                                .icon_size(IconSize::Small)
+                                .when(pinned, |button| button.icon_color(Color::Accent))
+                                .on_click(cx.listener(move |this, _event, window, cx| {
+                                    cx.stop_propagation();
+                                    window.prevent_default();
+
+                                    this.delegate.toggle_pinned(ix, window, cx)
+                                }))
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action(
+                                        if pinned {
+                                            "Unpin Project"
+                                        } else {
+                                            "Pin Project"
+                                        },
+                                        &TogglePinned,
+                                        window,
+                                        cx,
+                                    )
+                                }),
+                        )
                         .child(
                             IconButton::new("delete", IconName::Close)
                                 .icon_size(IconSize::Small)
@@ -525,10 +929,10 @@ impl PickerDelegate for RecentProjectsDelegate {
                         )
                         .into_any_element();
 
-                    if self.selected_index() == ix {
-                        el.end_slot::<AnyElement>(delete_button)
+                    if self.selected_index() == ix || pinned {
+                        el.end_slot::<AnyElement>(buttons)
                     } else {
-                        el.end_hover_slot::<AnyElement>(delete_button)
+                        el.end_hover_slot::<AnyElement>(buttons)
                     }
                 })
                 .tooltip(move |_, cx| {
@@ -537,7 +941,8 @@ impl PickerDelegate for RecentProjectsDelegate {
                         highlighted_location: tooltip_highlighted_location,
                     })
                     .into()
-                }),
+                })
+                .into_any_element(),
         )
     }
 
@@ -554,6 +959,17 @@ impl PickerDelegate for RecentProjectsDelegate {
                 .justify_end()
                 .border_t_1()
                 .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("remove-stale", "Remove Stale")
+                        .key_binding(KeyBinding::for_action(&RemoveStaleProjects, window, cx))
+                        .tooltip(Tooltip::text(
+                            "Remove unpinned projects not opened in the last 30 days",
+                        ))
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(RemoveStaleProjects.boxed_clone(), cx)
+                        }),
+                )
+                .child(div().flex_1())
                 .child(
                     Button::new("remote", "Open Remote Folder")
                         .key_binding(KeyBinding::for_action(
@@ -586,7 +1002,6 @@ impl PickerDelegate for RecentProjectsDelegate {
         )
     }
 }
-
 // Compute the highlighted text for the name and path
 fn highlights_for_path(
     path: &Path,
@@ -641,38 +1056,97 @@ impl RecentProjectsDelegate {
         window: &mut Window,
         cx: &mut Context<Picker<Self>>,
     ) {
-        if let Some(selected_match) = self.matches.get(ix) {
-            let (workspace_id, _, _) = self.workspaces[selected_match.candidate_id];
-            cx.spawn_in(window, async move |this, cx| {
-                let _ = WORKSPACE_DB.delete_workspace_by_id(workspace_id).await;
-                let workspaces = WORKSPACE_DB
-                    .recent_workspaces_on_disk()
-                    .await
-                    .unwrap_or_default();
-                this.update_in(cx, move |picker, window, cx| {
-                    picker.delegate.set_workspaces(workspaces);
-                    picker
-                        .delegate
-                        .set_selected_index(ix.saturating_sub(1), window, cx);
-                    picker.delegate.reset_selected_match_index = false;
-                    picker.update_matches(picker.query(cx), window, cx);
-                    // After deleting a project, we want to update the history manager to reflect the change.
-                    // But we do not emit a update event when user opens a project, because it's handled in `workspace::load_workspace`.
-                    if let Some(history_manager) = HistoryManager::global(cx) {
-                        history_manager
-                            .update(cx, |this, cx| this.delete_history(workspace_id, cx));
-                    }
-                })
-            })
-            .detach();
+        if let Some((workspace_id, _, _)) = self.project_at(ix) {
+            self.delete_recent_projects(vec![*workspace_id], ix, window, cx);
         }
     }
 
-    fn is_current_workspace(
+    fn delete_recent_projects(
         &self,
-        workspace_id: WorkspaceId,
+        workspace_ids: Vec<WorkspaceId>,
+        ix: usize,
+        window: &mut Window,
         cx: &mut Context<Picker<Self>>,
-    ) -> bool {
+    ) {
+        cx.spawn_in(window, async move |this, cx| {
+            let _ = WORKSPACE_DB
+                .delete_workspaces_by_id(workspace_ids.clone())
+                .await;
+            let recent_projects = cx.background_spawn(RecentProjectsData::load()).await;
+            this.update_in(cx, move |picker, window, cx| {
+                picker.delegate.set_data(recent_projects);
+                picker
+                    .delegate
+                    .set_selected_index(ix.saturating_sub(1), window, cx);
+                picker.delegate.reset_selected_match_index = false;
+                picker.update_matches(picker.query(cx), window, cx);
+                // After deleting a project, we want to update the history manager to reflect the change.
+                // But we do not emit a update event when user opens a project, because it's handled in `workspace::load_workspace`.
+                if let Some(history_manager) = HistoryManager::global(cx) {
+                    history_manager.update(cx, |this, cx| {
+                        for workspace_id in workspace_ids {
+                            this.delete_history(workspace_id, cx);
+                        }
+                    });
+                }
+            })
+        })
+        .detach();
+    }
+
+    /// Removes unpinned projects that haven't been opened within [`STALE_PROJECT_AGE`].
+    fn remove_stale_projects(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let stale_projects = self
+            .workspaces
+            .iter()
+            .map(|(workspace_id, _, _)| *workspace_id)
+            .filter(|workspace_id| !self.is_current_workspace(*workspace_id, cx))
+            .filter(|workspace_id| {
+                self.metadata.get(workspace_id).is_some_and(|metadata| {
+                    !metadata.pinned
+                        && metadata.last_opened.is_some_and(|last_opened| {
+                            now - last_opened > STALE_PROJECT_AGE.as_secs() as i64
+                        })
+                })
+            })
+            .collect::<Vec<_>>();
+        if !stale_projects.is_empty() {
+            self.delete_recent_projects(stale_projects, 0, window, cx);
+        }
+    }
+
+    fn toggle_pinned(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some((workspace_id, _, _)) = self.project_at(ix) else {
+            return;
+        };
+        let workspace_id = *workspace_id;
+        let metadata = self.metadata.entry(workspace_id).or_default();
+        metadata.pinned = !metadata.pinned;
+        let pinned = metadata.pinned;
+        cx.background_spawn(async move {
+            WORKSPACE_DB
+                .set_workspace_pinned(workspace_id, pinned)
+                .await
+        })
+        .detach_and_log_err(cx);
+        self.reset_selected_match_index = false;
+        cx.defer_in(window, |picker, window, cx| picker.refresh(window, cx));
+    }
+
+    /// Starts renaming the project at `ix`, returning its current name to prefill the query with.
+    fn start_renaming(&mut self, ix: usize) -> Option<String> {
+        let (workspace_id, _, _) = self.project_at(ix)?;
+        let workspace_id = *workspace_id;
+        self.renaming = Some(workspace_id);
+        Some(
+            self.display_name(workspace_id)
+                .unwrap_or_default()
+                .to_owned(),
+        )
+    }
+
+    fn is_current_workspace(&self, workspace_id: WorkspaceId, cx: &App) -> bool {
         if let Some(workspace) = self.workspace.upgrade() {
             let workspace = workspace.read(cx);
             if Some(workspace_id) == workspace.database_id() {
@@ -776,12 +1250,12 @@ mod tests {
                 recent_projects_picker.update(cx, |picker, cx| {
                     assert_eq!(picker.query(cx), "");
                     let delegate = &mut picker.delegate;
-                    delegate.matches = vec![StringMatch {
+                    delegate.entries = vec![RecentProjectEntry::Project(StringMatch {
                         candidate_id: 0,
                         score: 1.0,
                         positions: Vec::new(),
                         string: "fake candidate".to_string(),
-                    }];
+                    })];
                     delegate.set_workspaces(vec![(
                         WorkspaceId::default(),
                         SerializedWorkspaceLocation::Local,
@@ -1,4 +1,7 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{Context as _, Result};
 use askpass::EncryptedPassword;
@@ -14,13 +17,14 @@ use gpui::{
 
 use language::CursorShape;
 use markdown::{Markdown, MarkdownElement, MarkdownStyle};
+use project::SftpFs;
 use release_channel::ReleaseChannel;
 use remote::{
     ConnectionIdentifier, RemoteClient, RemoteConnectionOptions, RemotePlatform,
     SshConnectionOptions,
 };
 pub use settings::SshConnection;
-use settings::{ExtendingVec, Settings, WslConnection};
+use settings::{ExtendingVec, PortForwardRule, Settings, WslConnection};
 use theme::ThemeSettings;
 use ui::{
     ActiveTheme, Color, CommonAnimationExt, Context, Icon, IconName, IconSize, InteractiveElement,
@@ -33,6 +37,19 @@ pub struct SshSettings {
     pub wsl_connections: ExtendingVec<WslConnection>,
     /// Whether to read ~/.ssh/config for ssh connection sources.
     pub read_ssh_config: bool,
+    pub port_forwarding: PortForwardingSettings,
+}
+
+#[derive(Clone, Debug)]
+pub struct PortForwardingSettings {
+    /// Whether to detect ports that processes start listening on in remote projects.
+    pub detect_ports: bool,
+    /// Whether to automatically forward every newly detected port.
+    pub auto_forward: bool,
+    /// Ports below this number are never detected.
+    pub min_port: u16,
+    /// Rules to label, auto-forward, or ignore specific remote ports.
+    pub rules: Vec<PortForwardRule>,
 }
 
 impl SshSettings {
@@ -54,11 +71,24 @@ impl SshSettings {
                 options.upload_binary_over_ssh = conn.upload_binary_over_ssh.unwrap_or_default();
                 options.args = Some(conn.args);
                 options.port_forwards = conn.port_forwards;
+                options.proxy_jump = conn.proxy_jump;
+                options.forward_agent = conn.forward_agent.unwrap_or_default();
+                options.remote_server_path = conn.remote_server_path;
                 break;
             }
         }
     }
 
+    /// Whether the settings ask to open projects on the host over SFTP.
+    pub fn is_sftp_only(&self, options: &SshConnectionOptions) -> bool {
+        self.ssh_connections().any(|conn| {
+            conn.host == options.host
+                && conn.username == options.username
+                && conn.port == options.port
+                && conn.sftp_only.unwrap_or_default()
+        })
+    }
+
     pub fn connection_options_for(
         &self,
         host: String,
@@ -110,6 +140,15 @@ impl Settings for SshSettings {
             ssh_connections: remote.ssh_connections.clone().unwrap_or_default().into(),
             wsl_connections: remote.wsl_connections.clone().unwrap_or_default().into(),
             read_ssh_config: remote.read_ssh_config.unwrap(),
+            port_forwarding: {
+                let port_forwarding = remote.port_forwarding.clone().unwrap();
+                PortForwardingSettings {
+                    detect_ports: port_forwarding.detect_ports.unwrap(),
+                    auto_forward: port_forwarding.auto_forward.unwrap(),
+                    min_port: port_forwarding.min_port.unwrap(),
+                    rules: port_forwarding.rules.unwrap(),
+                }
+            },
         }
     }
 }
@@ -287,6 +326,11 @@ impl RemoteConnectionModal {
                 (options.connection_string(), options.nickname.clone(), false)
             }
             RemoteConnectionOptions::Wsl(options) => (options.distro_name.clone(), None, true),
+            RemoteConnectionOptions::Docker(options) => (
+                options.short_id().to_string(),
+                Some(options.name.clone()),
+                false,
+            ),
         };
         Self {
             prompt: cx.new(|cx| {
@@ -599,6 +643,12 @@ pub async fn open_remote_project(
     open_options: workspace::OpenOptions,
     cx: &mut AsyncApp,
 ) -> Result<()> {
+    if let RemoteConnectionOptions::Ssh(options) = &connection_options
+        && cx.update(|cx| SshSettings::get_global(cx).is_sftp_only(options))?
+    {
+        return open_sftp_project(options, paths, app_state, open_options, cx).await;
+    }
+
     let window = if let Some(window) = open_options.replace_window {
         window
     } else {
@@ -702,6 +752,9 @@ pub async fn open_remote_project(
                         match connection_options {
                             RemoteConnectionOptions::Ssh(_) => "Failed to connect over SSH",
                             RemoteConnectionOptions::Wsl(_) => "Failed to connect to WSL",
+                            RemoteConnectionOptions::Docker(_) => {
+                                "Failed to connect to the dev container"
+                            }
                         },
                         Some(&e.to_string()),
                         &["Retry", "Ok"],
@@ -730,3 +783,66 @@ pub async fn open_remote_project(
     // Already showed the error to the user
     Ok(())
 }
+
+/// Opens a project whose files are transferred over SFTP, for hosts that can't run the remote
+/// server.
+async fn open_sftp_project(
+    options: &SshConnectionOptions,
+    paths: Vec<PathBuf>,
+    app_state: Arc<AppState>,
+    open_options: workspace::OpenOptions,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let fs = SftpFs::new(
+        options.host.clone(),
+        options.username.clone(),
+        options.port,
+        sftp_args(options.args.as_deref().unwrap_or_default()),
+    )?;
+    let app_state = Arc::new(AppState {
+        languages: app_state.languages.clone(),
+        client: app_state.client.clone(),
+        user_store: app_state.user_store.clone(),
+        workspace_store: app_state.workspace_store.clone(),
+        fs: Arc::new(fs),
+        build_window_options: app_state.build_window_options,
+        node_runtime: app_state.node_runtime.clone(),
+        session: app_state.session.clone(),
+    });
+    // `sftp` resolves relative paths from the home directory, but doesn't expand `~`.
+    let paths = paths
+        .into_iter()
+        .map(|path| match path.strip_prefix("~") {
+            Ok(path) => Path::new(".").join(path),
+            Err(_) => path,
+        })
+        .collect::<Vec<_>>();
+    cx.update(|cx| workspace::open_paths(&paths, app_state, open_options, cx))?
+        .await?;
+    Ok(())
+}
+
+/// Keeps the SSH arguments that `sftp` also accepts.
+fn sftp_args(ssh_args: &[String]) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut ssh_args = ssh_args.iter();
+    while let Some(arg) = ssh_args.next() {
+        match arg.as_str() {
+            "-i" | "-o" | "-F" | "-J" => {
+                if let Some(value) = ssh_args.next() {
+                    args.push(arg.clone());
+                    args.push(value.clone());
+                }
+            }
+            "-4" | "-6" | "-C" => args.push(arg.clone()),
+            _ if ["-i", "-o", "-F", "-J"]
+                .iter()
+                .any(|flag| arg.starts_with(flag)) =>
+            {
+                args.push(arg.clone())
+            }
+            _ => {}
+        }
+    }
+    args
+}
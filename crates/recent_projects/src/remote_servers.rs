@@ -3,7 +3,7 @@ use crate::{
         Connection, RemoteConnectionModal, RemoteConnectionPrompt, SshConnection,
         SshConnectionHeader, SshSettings, connect, connect_over_ssh, open_remote_project,
     },
-    ssh_config::parse_ssh_config_hosts,
+    ssh_config::{SshConfigHost, parse_ssh_config, parse_ssh_config_hosts},
 };
 use editor::Editor;
 use file_finder::OpenPathDelegate;
@@ -11,15 +11,15 @@ use futures::{FutureExt, channel::oneshot, future::Shared, select};
 use gpui::{
     AnyElement, App, ClickEvent, ClipboardItem, Context, DismissEvent, Entity, EventEmitter,
     FocusHandle, Focusable, PromptLevel, ScrollHandle, Subscription, Task, WeakEntity, Window,
-    canvas,
+    actions, canvas,
 };
 use log::info;
 use paths::{global_ssh_config_file, user_ssh_config_file};
 use picker::Picker;
 use project::{Fs, Project};
 use remote::{
-    RemoteClient, RemoteConnectionOptions, SshConnectionOptions, WslConnectionOptions,
-    remote_client::ConnectionIdentifier,
+    ConnectionState, RemoteClient, RemoteConnectionOptions, SshConnectionOptions,
+    WslConnectionOptions, remote_client::ConnectionIdentifier,
 };
 use settings::{
     RemoteSettingsContent, Settings as _, SettingsStore, SshProject, update_settings_file,
@@ -28,17 +28,18 @@ use settings::{
 use smol::stream::StreamExt as _;
 use std::{
     borrow::Cow,
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     path::PathBuf,
     rc::Rc,
     sync::{
         Arc,
         atomic::{self, AtomicUsize},
     },
+    time::Duration,
 };
 use ui::{
-    IconButtonShape, List, ListItem, ListSeparator, Modal, ModalHeader, Navigable, NavigableEntry,
-    Section, Tooltip, WithScrollbar, prelude::*,
+    IconButtonShape, Indicator, List, ListItem, ListSeparator, Modal, ModalHeader, Navigable,
+    NavigableEntry, Section, Tooltip, WithScrollbar, prelude::*,
 };
 use util::{
     ResultExt,
@@ -50,6 +51,16 @@ use workspace::{
     open_remote_project_with_existing_connection,
 };
 
+actions!(
+    remote,
+    [
+        /// Restarts the connection to the remote server of the current project.
+        Reconnect
+    ]
+);
+
+const CONNECTION_HEALTH_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct RemoteServerProjects {
     mode: Mode,
     focus_handle: FocusHandle,
@@ -57,7 +68,9 @@ pub struct RemoteServerProjects {
     retained_connections: Vec<Entity<RemoteClient>>,
     ssh_config_updates: Task<()>,
     ssh_config_servers: BTreeSet<SharedString>,
+    ssh_config_details: BTreeMap<SharedString, SshConfigHost>,
     create_new_window: bool,
+    _refresh_connection_health: Task<()>,
     _subscription: Subscription,
 }
 
@@ -205,6 +218,10 @@ impl ProjectPicker {
             RemoteConnectionOptions::Wsl(connection) => ProjectPickerData::Wsl {
                 distro_name: connection.distro_name.clone().into(),
             },
+            RemoteConnectionOptions::Docker(connection) => ProjectPickerData::Ssh {
+                connection_string: connection.short_id().to_string().into(),
+                nickname: Some(connection.name.clone().into()),
+            },
         };
         let _path_task = cx
             .spawn_in(window, {
@@ -491,7 +508,7 @@ enum ViewServerOptionsState {
     Ssh {
         connection: SshConnectionOptions,
         server_index: SshServerIndex,
-        entries: [NavigableEntry; 4],
+        entries: [NavigableEntry; 5],
     },
     Wsl {
         connection: WslConnectionOptions,
@@ -592,11 +609,25 @@ impl RemoteServerProjects {
                         recent_projects.ssh_config_updates = spawn_ssh_config_watch(fs.clone(), cx);
                     } else {
                         recent_projects.ssh_config_servers.clear();
+                        recent_projects.ssh_config_details.clear();
                         recent_projects.ssh_config_updates = Task::ready(());
                     }
                 }
+                cx.notify();
             });
 
+        // Connection state and latency change without notifying this view, so poll them.
+        let _refresh_connection_health = cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(CONNECTION_HEALTH_REFRESH_INTERVAL)
+                    .await;
+                if this.update(cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        });
+
         Self {
             mode,
             focus_handle,
@@ -604,7 +635,9 @@ impl RemoteServerProjects {
             retained_connections: Vec::new(),
             ssh_config_updates,
             ssh_config_servers: BTreeSet::new(),
+            ssh_config_details: BTreeMap::new(),
             create_new_window,
+            _refresh_connection_health,
             _subscription,
         }
     }
@@ -981,6 +1014,61 @@ impl RemoteServerProjects {
         }
     }
 
+    fn render_connection_health(
+        ix: usize,
+        sessions: Vec<Entity<RemoteClient>>,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let client = sessions.first()?.read(cx);
+        let (color, status): (Color, SharedString) = match client.connection_state() {
+            ConnectionState::Connecting => (Color::Info, "Connecting".into()),
+            ConnectionState::Connected => (
+                Color::Success,
+                match client.latency() {
+                    Some(latency) => format!("{} ms", latency.as_millis()).into(),
+                    None => "Connected".into(),
+                },
+            ),
+            ConnectionState::HeartbeatMissed => (Color::Warning, "Not responding".into()),
+            ConnectionState::Reconnecting => (Color::Warning, "Reconnecting".into()),
+            ConnectionState::Disconnected => (Color::Error, "Disconnected".into()),
+        };
+        let session_count = sessions.len();
+
+        Some(
+            h_flex()
+                .ml_auto()
+                .gap_1()
+                .child(Indicator::dot().color(color))
+                .child(
+                    Label::new(status)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .when(session_count > 1, |this| {
+                    this.child(
+                        Label::new(format!("· {session_count} windows"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .child(
+                    IconButton::new(("reconnect-server", ix), IconName::RotateCw)
+                        .icon_size(IconSize::XSmall)
+                        .shape(IconButtonShape::Square)
+                        .tooltip(Tooltip::text("Reconnect"))
+                        .on_click(move |_, _, cx| {
+                            for session in &sessions {
+                                session
+                                    .update(cx, |session, cx| session.manual_reconnect(cx))
+                                    .log_err();
+                            }
+                        }),
+                )
+                .into_any_element(),
+        )
+    }
+
     fn render_ssh_connection(
         &mut self,
         ix: usize,
@@ -989,6 +1077,15 @@ impl RemoteServerProjects {
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         let connection = ssh_server.connection().into_owned();
+        let sessions = remote_sessions(&connection.clone().into(), cx);
+        let jump_host = match &connection {
+            Connection::Ssh(connection) => connection.proxy_jump.clone().or_else(|| {
+                self.ssh_config_details
+                    .get(&connection.host)
+                    .and_then(|details| details.proxy_jump.clone())
+            }),
+            Connection::Wsl(_) => None,
+        };
 
         let (main_label, aux_label, is_wsl) = match &connection {
             Connection::Ssh(connection) => {
@@ -1037,7 +1134,15 @@ impl RemoteServerProjects {
                         aux_label.map(|label| {
                             Label::new(label).size(LabelSize::Small).color(Color::Muted)
                         }),
-                    ),
+                    )
+                    .when_some(jump_host, |this, jump_host| {
+                        this.child(
+                            Label::new(format!("via {jump_host}"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .children(Self::render_connection_health(ix, sessions, cx)),
             )
             .child(match &ssh_server {
                 RemoteEntry::Project {
@@ -1324,6 +1429,18 @@ impl RemoteServerProjects {
         update_settings_file(fs, cx, move |setting, cx| f(&mut setting.remote, cx));
     }
 
+    fn toggle_forward_agent(&mut self, server: SshServerIndex, cx: &mut Context<Self>) {
+        self.update_settings_file(cx, move |setting, _| {
+            if let Some(connection) = setting
+                .ssh_connections
+                .as_mut()
+                .and_then(|connections| connections.get_mut(server.0))
+            {
+                connection.forward_agent = Some(!connection.forward_agent.unwrap_or_default());
+            }
+        });
+    }
+
     fn delete_ssh_server(&mut self, server: SshServerIndex, cx: &mut Context<Self>) {
         self.update_settings_file(cx, move |setting, _| {
             if let Some(connections) = setting.ssh_connections.as_mut() {
@@ -1394,6 +1511,10 @@ impl RemoteServerProjects {
                     args: connection_options.args.unwrap_or_default(),
                     upload_binary_over_ssh: None,
                     port_forwards: connection_options.port_forwards,
+                    sftp_only: None,
+                    proxy_jump: connection_options.proxy_jump,
+                    forward_agent: connection_options.forward_agent.then_some(true),
+                    remote_server_path: None,
                 })
         });
     }
@@ -1678,8 +1799,38 @@ impl RemoteServerProjects {
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
         let connection_string = SharedString::new(connection.host.clone());
+        let ssh_config_details = self.ssh_config_details.get(connection.host.as_str());
+        let jump_host = connection
+            .proxy_jump
+            .clone()
+            .or_else(|| ssh_config_details.and_then(|details| details.proxy_jump.clone()));
+        let identity_files = ssh_config_details
+            .map(|details| details.identity_files.join(", "))
+            .filter(|identity_files| !identity_files.is_empty());
+        let details = [
+            ("Jump host:", jump_host),
+            ("Identity file:", identity_files),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value?)))
+        .collect::<Vec<_>>();
+        let forward_agent = SshSettings::get_global(cx)
+            .ssh_connections()
+            .nth(index.0)
+            .and_then(|connection| connection.forward_agent)
+            .unwrap_or_default();
 
         v_flex()
+            .when(!details.is_empty(), |this| {
+                this.child(v_flex().px_3().pb_1().children(details.into_iter().map(
+                    |(label, value)| {
+                        h_flex()
+                            .gap_1()
+                            .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                            .child(Label::new(value).size(LabelSize::Small))
+                    },
+                )))
+            })
             .child({
                 let label = if connection.nickname.is_some() {
                     "Edit Nickname"
@@ -1764,6 +1915,29 @@ impl RemoteServerProjects {
                             }),
                     )
             })
+            .child(
+                div()
+                    .id("ssh-options-forward-agent")
+                    .track_focus(&entries[2].focus_handle)
+                    .on_action(cx.listener(move |this, _: &menu::Confirm, _, cx| {
+                        this.toggle_forward_agent(index, cx);
+                    }))
+                    .child(
+                        ListItem::new("forward-agent")
+                            .toggle_state(entries[2].focus_handle.contains_focused(window, cx))
+                            .inset(true)
+                            .spacing(ui::ListItemSpacing::Sparse)
+                            .start_slot(Icon::new(IconName::LockOutlined).color(Color::Muted))
+                            .child(Label::new("Forward SSH Agent"))
+                            .end_slot(
+                                Label::new(if forward_agent { "On" } else { "Off" })
+                                    .color(Color::Muted),
+                            )
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_forward_agent(index, cx);
+                            })),
+                    ),
+            )
             .child({
                 fn remove_ssh_server(
                     remote_servers: Entity<RemoteServerProjects>,
@@ -1802,7 +1976,7 @@ impl RemoteServerProjects {
                 }
                 div()
                     .id("ssh-options-copy-server-address")
-                    .track_focus(&entries[2].focus_handle)
+                    .track_focus(&entries[3].focus_handle)
                     .on_action(cx.listener({
                         let connection_string = connection_string.clone();
                         move |_, _: &menu::Confirm, window, cx| {
@@ -1818,7 +1992,7 @@ impl RemoteServerProjects {
                     }))
                     .child(
                         ListItem::new("remove-server")
-                            .toggle_state(entries[2].focus_handle.contains_focused(window, cx))
+                            .toggle_state(entries[3].focus_handle.contains_focused(window, cx))
                             .inset(true)
                             .spacing(ui::ListItemSpacing::Sparse)
                             .start_slot(Icon::new(IconName::Trash).color(Color::Error))
@@ -2152,6 +2326,8 @@ fn spawn_ssh_config_watch(fs: Arc<dyn Fs>, cx: &Context<RemoteServerProjects>) -
     cx.spawn(async move |remote_server_projects, cx| {
         let mut global_hosts = BTreeSet::default();
         let mut user_hosts = BTreeSet::default();
+        let mut global_details = BTreeMap::default();
+        let mut user_details = BTreeMap::default();
         let mut running_receivers = 2;
 
         loop {
@@ -2160,8 +2336,10 @@ fn spawn_ssh_config_watch(fs: Arc<dyn Fs>, cx: &Context<RemoteServerProjects>) -
                     match new_global_file_contents {
                         Some(new_global_file_contents) => {
                             global_hosts = parse_ssh_config_hosts(&new_global_file_contents);
+                            global_details = parse_ssh_config(&new_global_file_contents);
                             if remote_server_projects.update(cx, |remote_server_projects, cx| {
                                 remote_server_projects.ssh_config_servers = global_hosts.iter().chain(user_hosts.iter()).map(SharedString::from).collect();
+                                remote_server_projects.ssh_config_details = merge_ssh_config_details(&global_details, &user_details);
                                 cx.notify();
                             }).is_err() {
                                 return;
@@ -2179,8 +2357,10 @@ fn spawn_ssh_config_watch(fs: Arc<dyn Fs>, cx: &Context<RemoteServerProjects>) -
                     match new_user_file_contents {
                         Some(new_user_file_contents) => {
                             user_hosts = parse_ssh_config_hosts(&new_user_file_contents);
+                            user_details = parse_ssh_config(&new_user_file_contents);
                             if remote_server_projects.update(cx, |remote_server_projects, cx| {
                                 remote_server_projects.ssh_config_servers = global_hosts.iter().chain(user_hosts.iter()).map(SharedString::from).collect();
+                                remote_server_projects.ssh_config_details = merge_ssh_config_details(&global_details, &user_details);
                                 cx.notify();
                             }).is_err() {
                                 return;
@@ -2199,6 +2379,47 @@ fn spawn_ssh_config_watch(fs: Arc<dyn Fs>, cx: &Context<RemoteServerProjects>) -
     })
 }
 
+/// Options in the user's config take precedence over the system-wide config, as they do for ssh.
+fn merge_ssh_config_details(
+    global_details: &BTreeMap<String, SshConfigHost>,
+    user_details: &BTreeMap<String, SshConfigHost>,
+) -> BTreeMap<SharedString, SshConfigHost> {
+    global_details
+        .iter()
+        .chain(user_details.iter())
+        .map(|(host, details)| (SharedString::from(host), details.clone()))
+        .collect()
+}
+
+/// Whether two connection options point at the same server, regardless of their other settings.
+fn is_same_server(a: &RemoteConnectionOptions, b: &RemoteConnectionOptions) -> bool {
+    match (a, b) {
+        (RemoteConnectionOptions::Ssh(a), RemoteConnectionOptions::Ssh(b)) => {
+            a.host == b.host && a.username == b.username && a.port == b.port
+        }
+        (RemoteConnectionOptions::Wsl(a), RemoteConnectionOptions::Wsl(b)) => {
+            a.distro_name == b.distro_name && a.user == b.user
+        }
+        (RemoteConnectionOptions::Docker(a), RemoteConnectionOptions::Docker(b)) => {
+            a.container_id == b.container_id
+        }
+        _ => false,
+    }
+}
+
+/// The remote clients of all open windows that are connected to the given server.
+///
+/// Windows connected to the same server share a single ssh connection, so these sessions
+/// are multiplexed over it.
+fn remote_sessions(options: &RemoteConnectionOptions, cx: &App) -> Vec<Entity<RemoteClient>> {
+    cx.windows()
+        .into_iter()
+        .filter_map(|window| window.downcast::<Workspace>())
+        .filter_map(|workspace| workspace.read(cx).ok()?.project().read(cx).remote_client())
+        .filter(|client| is_same_server(&client.read(cx).connection_options(), options))
+        .collect()
+}
+
 fn get_text(element: &Entity<Editor>, cx: &mut App) -> String {
     element.read(cx).text(cx).trim().to_string()
 }
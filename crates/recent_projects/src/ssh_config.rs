@@ -1,4 +1,14 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Options from `~/.ssh/config` that apply to a host alias.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SshConfigHost {
+    pub host_name: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub proxy_jump: Option<String>,
+    pub identity_files: Vec<String>,
+}
 
 pub fn parse_ssh_config_hosts(config: &str) -> BTreeSet<String> {
     let mut hosts = BTreeSet::new();
@@ -48,6 +58,119 @@ fn parse_hosts_from(line: &str, hosts: &mut BTreeSet<String>) {
     );
 }
 
+/// Resolves the options that apply to each host alias declared in the config.
+///
+/// Like ssh, the first value found for an option wins, and wildcard blocks such as
+/// `Host *` contribute to every alias they match. `Match` blocks and `Include`
+/// directives are not evaluated.
+pub fn parse_ssh_config(config: &str) -> BTreeMap<String, SshConfigHost> {
+    let mut blocks: Vec<(Vec<String>, Vec<(String, String)>)> = Vec::new();
+    let mut in_match_block = false;
+    for line in config.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((keyword, value)) = split_option(line) else {
+            continue;
+        };
+        if keyword.eq_ignore_ascii_case("host") {
+            in_match_block = false;
+            let patterns = value.split_whitespace().map(str::to_owned).collect();
+            blocks.push((patterns, Vec::new()));
+        } else if keyword.eq_ignore_ascii_case("match") {
+            in_match_block = true;
+        } else if !in_match_block && let Some((_, options)) = blocks.last_mut() {
+            options.push((keyword.to_ascii_lowercase(), value.to_owned()));
+        }
+    }
+
+    parse_ssh_config_hosts(config)
+        .into_iter()
+        .map(|alias| {
+            let mut host = SshConfigHost::default();
+            for (patterns, options) in &blocks {
+                if !host_matches(&alias, patterns) {
+                    continue;
+                }
+                for (keyword, value) in options {
+                    match keyword.as_str() {
+                        "hostname" => {
+                            host.host_name.get_or_insert_with(|| value.clone());
+                        }
+                        "user" => {
+                            host.user.get_or_insert_with(|| value.clone());
+                        }
+                        "port" => {
+                            if host.port.is_none() {
+                                host.port = value.parse().ok();
+                            }
+                        }
+                        "proxyjump" => {
+                            host.proxy_jump.get_or_insert_with(|| value.clone());
+                        }
+                        // Unlike other options, every IdentityFile is tried in order.
+                        "identityfile" => host.identity_files.push(value.clone()),
+                        _ => {}
+                    }
+                }
+            }
+            if host.proxy_jump.as_deref() == Some("none") {
+                host.proxy_jump = None;
+            }
+            (alias, host)
+        })
+        .collect()
+}
+
+fn split_option(line: &str) -> Option<(&str, &str)> {
+    let (keyword, value) = line.split_once(|c: char| c.is_whitespace() || c == '=')?;
+    let value = value
+        .trim_start_matches(|c: char| c.is_whitespace() || c == '=')
+        .trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+    Some((keyword, value))
+}
+
+fn host_matches(alias: &str, patterns: &[String]) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        if let Some(pattern) = pattern.strip_prefix('!') {
+            if glob_matches(pattern, alias) {
+                return false;
+            }
+        } else if glob_matches(pattern, alias) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    match pattern.chars().next() {
+        None => text.is_empty(),
+        Some('*') => {
+            let rest = &pattern[1..];
+            text.char_indices()
+                .map(|(ix, _)| ix)
+                .chain([text.len()])
+                .any(|ix| glob_matches(rest, &text[ix..]))
+        }
+        Some(c) => {
+            let mut chars = text.chars();
+            match chars.next() {
+                Some(t) if c == '?' || c == t => {
+                    glob_matches(&pattern[c.len_utf8()..], chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,4 +216,57 @@ mod tests {
 
         assert_eq!(expected_hosts, parse_ssh_config_hosts(hosts));
     }
+
+    #[test]
+    fn test_parse_ssh_config() {
+        let config = "
+            Host bastion
+              HostName bastion.example.com
+              User jump
+
+            Host dev-direct
+              ProxyJump none
+
+            Host dev-*
+              ProxyJump bastion
+              IdentityFile ~/.ssh/work_ed25519
+
+            Host dev-box
+              HostName 10.0.0.5
+              Port 2222
+              ProxyJump other
+
+            Match host *.internal
+              User ignored
+
+            Host *
+              User = me
+              IdentityFile \"~/.ssh/id_ed25519\"";
+
+        let hosts = parse_ssh_config(config);
+        assert_eq!(
+            hosts["bastion"],
+            SshConfigHost {
+                host_name: Some("bastion.example.com".to_owned()),
+                user: Some("jump".to_owned()),
+                port: None,
+                proxy_jump: None,
+                identity_files: vec!["~/.ssh/id_ed25519".to_owned()],
+            }
+        );
+        assert_eq!(
+            hosts["dev-box"],
+            SshConfigHost {
+                host_name: Some("10.0.0.5".to_owned()),
+                user: Some("me".to_owned()),
+                port: Some(2222),
+                proxy_jump: Some("bastion".to_owned()),
+                identity_files: vec![
+                    "~/.ssh/work_ed25519".to_owned(),
+                    "~/.ssh/id_ed25519".to_owned()
+                ],
+            }
+        );
+        assert_eq!(hosts["dev-direct"].proxy_jump, None);
+    }
 }
@@ -5,8 +5,9 @@ pub mod remote_client;
 mod transport;
 
 pub use remote_client::{
-    ConnectionIdentifier, ConnectionState, RemoteClient, RemoteClientDelegate, RemoteClientEvent,
-    RemoteConnectionOptions, RemotePlatform,
+    ConnectionIdentifier, ConnectionState, PortForward, RemoteClient, RemoteClientDelegate,
+    RemoteClientEvent, RemoteConnectionOptions, RemotePlatform,
 };
+pub use transport::docker::DockerConnectionOptions;
 pub use transport::ssh::{SshConnectionOptions, SshPortForwardOption};
 pub use transport::wsl::WslConnectionOptions;
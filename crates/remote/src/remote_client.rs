@@ -3,6 +3,7 @@ use crate::{
     protocol::MessageId,
     proxy::ProxyLaunchError,
     transport::{
+        docker::{DockerConnectionOptions, DockerRemoteConnection},
         ssh::SshRemoteConnection,
         wsl::{WslConnectionOptions, WslRemoteConnection},
     },
@@ -251,6 +252,7 @@ pub struct RemoteClient {
     connection_options: RemoteConnectionOptions,
     path_style: PathStyle,
     state: Option<State>,
+    latency: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -344,6 +346,7 @@ impl RemoteClient {
                     connection_options,
                     path_style,
                     state: Some(State::Connecting),
+                    latency: None,
                 })?;
 
                 let io_task = ssh_connection.start_proxy(
@@ -509,6 +512,7 @@ impl RemoteClient {
             return Ok(());
         }
 
+        self.latency = None;
         self.set_state(State::Reconnecting, cx);
 
         log::info!("Trying to reconnect to ssh server... Attempt {}", attempts);
@@ -663,15 +667,23 @@ impl RemoteClient {
                     _ = keepalive_timer => {
                         log::debug!("Sending heartbeat to server...");
 
+                        let ping_started_at = Instant::now();
                         let result = select_biased! {
                             _ = connection_activity_rx.next().fuse() => {
-                                Ok(())
+                                Ok(None)
                             }
                             ping_result = client.ping(HEARTBEAT_TIMEOUT).fuse() => {
-                                ping_result
+                                ping_result.map(|()| Some(ping_started_at.elapsed()))
                             }
                         };
 
+                        if let Ok(Some(latency)) = result {
+                            this.update(cx, |this, cx| {
+                                this.latency = Some(latency);
+                                cx.notify();
+                            })?;
+                        }
+
                         if result.is_err() {
                             missed_heartbeats += 1;
                             log::warn!(
@@ -819,6 +831,22 @@ impl RemoteClient {
         connection.build_command(program, args, env, working_dir, port_forward)
     }
 
+    pub fn listening_ports(&self, cx: &App) -> Task<Result<Vec<u16>>> {
+        let Some(connection) = self.remote_connection() else {
+            return Task::ready(Err(anyhow!("no remote connection")));
+        };
+        connection.listening_ports(cx)
+    }
+
+    /// Forwards `local_port` on this machine to `remote_port` on the remote host,
+    /// until the returned [`PortForward`] is dropped.
+    pub fn forward_port(&self, local_port: u16, remote_port: u16) -> Result<PortForward> {
+        let Some(connection) = self.remote_connection() else {
+            return Err(anyhow!("no remote connection"));
+        };
+        connection.forward_port(local_port, remote_port)
+    }
+
     pub fn upload_directory(
         &self,
         src_path: PathBuf,
@@ -850,6 +878,20 @@ impl RemoteClient {
         self.connection_state() == ConnectionState::Disconnected
     }
 
+    /// The round-trip time of the last heartbeat that was answered by the server.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// Restarts the connection to the server, keeping the server's state.
+    pub fn manual_reconnect(&mut self, cx: &mut Context<Self>) -> Result<()> {
+        log::info!(
+            "reconnecting to {} on request",
+            self.connection_options.display_name()
+        );
+        self.reconnect(cx)
+    }
+
     pub fn path_style(&self) -> PathStyle {
         self.path_style
     }
@@ -998,6 +1040,11 @@ impl ConnectionPool {
                                 .await
                                 .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
                         }
+                        RemoteConnectionOptions::Docker(opts) => {
+                            DockerRemoteConnection::new(opts, delegate, cx)
+                                .await
+                                .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
+                        }
                     };
 
                     cx.update_global(|pool: &mut Self, _| {
@@ -1033,6 +1080,7 @@ impl ConnectionPool {
 pub enum RemoteConnectionOptions {
     Ssh(SshConnectionOptions),
     Wsl(WslConnectionOptions),
+    Docker(DockerConnectionOptions),
 }
 
 impl RemoteConnectionOptions {
@@ -1040,6 +1088,7 @@ impl RemoteConnectionOptions {
         match self {
             RemoteConnectionOptions::Ssh(opts) => opts.host.clone(),
             RemoteConnectionOptions::Wsl(opts) => opts.distro_name.clone(),
+            RemoteConnectionOptions::Docker(opts) => opts.name.clone(),
         }
     }
 }
@@ -1056,6 +1105,30 @@ impl From<WslConnectionOptions> for RemoteConnectionOptions {
     }
 }
 
+impl From<DockerConnectionOptions> for RemoteConnectionOptions {
+    fn from(opts: DockerConnectionOptions) -> Self {
+        RemoteConnectionOptions::Docker(opts)
+    }
+}
+
+/// A TCP port forwarded from the local machine to the remote host.
+///
+/// The forward is torn down when this value is dropped.
+pub struct PortForward {
+    pub local_port: u16,
+    pub remote_port: u16,
+    pub(crate) _process: smol::process::Child,
+}
+
+impl fmt::Debug for PortForward {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PortForward")
+            .field("local_port", &self.local_port)
+            .field("remote_port", &self.remote_port)
+            .finish()
+    }
+}
+
 #[async_trait(?Send)]
 pub(crate) trait RemoteConnection: Send + Sync {
     fn start_proxy(
@@ -1091,6 +1164,17 @@ pub(crate) trait RemoteConnection: Send + Sync {
     fn path_style(&self) -> PathStyle;
     fn shell(&self) -> String;
     fn default_system_shell(&self) -> String;
+    /// Lists the TCP ports that processes on the remote host are listening on.
+    fn listening_ports(&self, _cx: &App) -> Task<Result<Vec<u16>>> {
+        Task::ready(Err(anyhow!(
+            "listing ports is not supported for this connection"
+        )))
+    }
+    fn forward_port(&self, _local_port: u16, _remote_port: u16) -> Result<PortForward> {
+        Err(anyhow!(
+            "port forwarding is not supported for this connection"
+        ))
+    }
 
     #[cfg(any(test, feature = "test-support"))]
     fn simulate_disconnect(&self, _: &AsyncApp) {}
@@ -0,0 +1,494 @@
+use crate::{
+    RemoteClientDelegate, RemotePlatform,
+    remote_client::{CommandTemplate, RemoteConnection, RemoteConnectionOptions},
+};
+use anyhow::{Context as _, Result, anyhow, bail};
+use async_trait::async_trait;
+use collections::HashMap;
+use futures::channel::mpsc::{Sender, UnboundedReceiver, UnboundedSender};
+use gpui::{App, AppContext as _, AsyncApp, SemanticVersion, Task};
+use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
+use rpc::proto::Envelope;
+use smol::process;
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Instant,
+};
+use util::{
+    paths::{PathStyle, RemotePathBuf},
+    rel_path::RelPath,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DockerConnectionOptions {
+    /// The ID of the container, as reported by `docker ps`.
+    pub container_id: String,
+    /// The name shown for the container in the UI.
+    pub name: String,
+    /// The user to run the remote server and commands as, instead of the container's default user.
+    pub user: Option<String>,
+}
+
+impl DockerConnectionOptions {
+    pub fn short_id(&self) -> &str {
+        &self.container_id[..self.container_id.len().min(12)]
+    }
+}
+
+pub(crate) struct DockerRemoteConnection {
+    remote_binary_path: Option<PathBuf>,
+    platform: RemotePlatform,
+    home_dir: String,
+    shell: String,
+    default_system_shell: String,
+    connection_options: DockerConnectionOptions,
+}
+
+impl DockerRemoteConnection {
+    pub(crate) async fn new(
+        connection_options: DockerConnectionOptions,
+        delegate: Arc<dyn RemoteClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        log::info!(
+            "Connecting to container {} ({}) with user {:?}",
+            connection_options.name,
+            connection_options.container_id,
+            connection_options.user
+        );
+        let (release_channel, version, commit) = cx.update(|cx| {
+            (
+                ReleaseChannel::global(cx),
+                AppVersion::global(cx),
+                AppCommitSha::try_global(cx),
+            )
+        })?;
+
+        let mut this = Self {
+            connection_options,
+            remote_binary_path: None,
+            platform: RemotePlatform { os: "", arch: "" },
+            home_dir: String::new(),
+            shell: String::new(),
+            default_system_shell: String::from("/bin/sh"),
+        };
+        delegate.set_status(Some("Starting container"), cx);
+        this.ensure_running().await?;
+        delegate.set_status(Some("Detecting container environment"), cx);
+        this.platform = this.detect_platform().await?;
+        this.home_dir = this.detect_home_dir().await?;
+        this.shell = this.detect_shell().await?;
+        this.remote_binary_path = Some(
+            this.ensure_server_binary(&delegate, release_channel, version, commit, cx)
+                .await?,
+        );
+
+        Ok(this)
+    }
+
+    /// Restarts the container if it was stopped, e.g. by a restart of the Docker daemon.
+    async fn ensure_running(&self) -> Result<()> {
+        let container_id = self.connection_options.container_id.as_str();
+        let running =
+            run_docker(&["inspect", "--format", "{{.State.Running}}", container_id]).await?;
+        if running != "true" {
+            run_docker(&["start", container_id]).await?;
+        }
+        Ok(())
+    }
+
+    async fn detect_platform(&self) -> Result<RemotePlatform> {
+        let arch_str = self.run_docker_exec("uname", &["-m"]).await?;
+        let arch = match arch_str.trim() {
+            "x86_64" => "x86_64",
+            "aarch64" | "arm64" => "aarch64",
+            arch => bail!("unsupported container architecture: {arch}"),
+        };
+        Ok(RemotePlatform { os: "linux", arch })
+    }
+
+    async fn detect_home_dir(&self) -> Result<String> {
+        let home_dir = self.run_docker_exec("sh", &["-c", "echo $HOME"]).await?;
+        if home_dir.is_empty() {
+            bail!("could not determine the home directory inside the container");
+        }
+        Ok(home_dir)
+    }
+
+    async fn detect_shell(&self) -> Result<String> {
+        Ok(self
+            .run_docker_exec("sh", &["-c", "echo $SHELL"])
+            .await
+            .ok()
+            .and_then(|shell_path| {
+                Path::new(shell_path.trim())
+                    .file_name()
+                    .map(|it| it.to_str().unwrap().to_owned())
+            })
+            .filter(|shell| !shell.is_empty())
+            .unwrap_or_else(|| "sh".to_string()))
+    }
+
+    fn absolute_path(&self, path: &RelPath) -> String {
+        format!("{}/{}", self.home_dir, path.display(PathStyle::Posix))
+    }
+
+    async fn run_docker_exec(&self, program: &str, args: &[&str]) -> Result<String> {
+        let mut docker_args = vec!["exec"];
+        if let Some(user) = &self.connection_options.user {
+            docker_args.extend(["--user", user.as_str()]);
+        }
+        docker_args.push(self.connection_options.container_id.as_str());
+        docker_args.push(program);
+        docker_args.extend(args);
+        run_docker(&docker_args).await
+    }
+
+    async fn ensure_server_binary(
+        &self,
+        delegate: &Arc<dyn RemoteClientDelegate>,
+        release_channel: ReleaseChannel,
+        version: SemanticVersion,
+        commit: Option<AppCommitSha>,
+        cx: &mut AsyncApp,
+    ) -> Result<PathBuf> {
+        let version_str = match release_channel {
+            ReleaseChannel::Nightly => {
+                let commit = commit.map(|s| s.full()).unwrap_or_default();
+                format!("{}-{}", version, commit)
+            }
+            ReleaseChannel::Dev => "build".to_string(),
+            _ => version.to_string(),
+        };
+
+        let binary_name = format!(
+            "zed-remote-server-{}-{}",
+            release_channel.dev_name(),
+            version_str
+        );
+
+        let dst_path = self.absolute_path(
+            &paths::remote_server_dir_relative().join(RelPath::unix(&binary_name).unwrap()),
+        );
+        let server_dir = self.absolute_path(paths::remote_server_dir_relative());
+        self.run_docker_exec("mkdir", &["-p", server_dir.as_str()])
+            .await
+            .map_err(|e| anyhow!("Failed to create directory: {}", e))?;
+
+        #[cfg(debug_assertions)]
+        if let Some(remote_server_path) =
+            super::build_remote_server_from_source(&self.platform, delegate.as_ref(), cx).await?
+        {
+            let tmp_path = format!(
+                "{}/download-{}-{}",
+                server_dir,
+                std::process::id(),
+                remote_server_path.file_name().unwrap().to_string_lossy()
+            );
+            self.upload_file(&remote_server_path, &tmp_path, delegate, cx)
+                .await?;
+            self.extract_and_install(&tmp_path, &dst_path, delegate, cx)
+                .await?;
+            return Ok(dst_path.into());
+        }
+
+        if self.run_docker_exec(&dst_path, &["version"]).await.is_ok() {
+            return Ok(dst_path.into());
+        }
+
+        delegate.set_status(Some("Installing remote server"), cx);
+
+        let wanted_version = match release_channel {
+            ReleaseChannel::Nightly | ReleaseChannel::Dev => None,
+            _ => Some(cx.update(|cx| AppVersion::global(cx))?),
+        };
+
+        let src_path = delegate
+            .download_server_binary_locally(self.platform, release_channel, wanted_version, cx)
+            .await?;
+
+        let tmp_path = format!("{}.{}.gz", dst_path, std::process::id());
+        self.upload_file(&src_path, &tmp_path, delegate, cx).await?;
+        self.extract_and_install(&tmp_path, &dst_path, delegate, cx)
+            .await?;
+
+        Ok(dst_path.into())
+    }
+
+    async fn upload_file(
+        &self,
+        src_path: &Path,
+        dst_path: &str,
+        delegate: &Arc<dyn RemoteClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<()> {
+        delegate.set_status(Some("Uploading remote server to container"), cx);
+
+        let t0 = Instant::now();
+        let size = smol::fs::metadata(src_path).await?.len();
+        log::info!(
+            "uploading remote server to container {:?} ({}kb)",
+            dst_path,
+            size / 1024
+        );
+
+        let src_path_str = src_path.to_string_lossy();
+        let dst = format!("{}:{}", self.connection_options.container_id, dst_path);
+        run_docker(&["cp", src_path_str.as_ref(), dst.as_str()])
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to copy file {} to container {:?}: {}",
+                    src_path.display(),
+                    dst_path,
+                    e
+                )
+            })?;
+
+        if let Some(user) = &self.connection_options.user {
+            // `docker cp` creates files owned by root, regardless of the user we exec as.
+            self.run_docker_as_root("chown", &[user.as_str(), dst_path])
+                .await?;
+        }
+
+        log::info!("uploaded remote server in {:?}", t0.elapsed());
+        Ok(())
+    }
+
+    async fn run_docker_as_root(&self, program: &str, args: &[&str]) -> Result<String> {
+        let mut docker_args = vec![
+            "exec",
+            "--user",
+            "root",
+            self.connection_options.container_id.as_str(),
+            program,
+        ];
+        docker_args.extend(args);
+        run_docker(&docker_args).await
+    }
+
+    async fn extract_and_install(
+        &self,
+        tmp_path: &str,
+        dst_path: &str,
+        delegate: &Arc<dyn RemoteClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<()> {
+        delegate.set_status(Some("Extracting remote server"), cx);
+
+        let script = if let Some(uncompressed) = tmp_path.strip_suffix(".gz") {
+            format!(
+                "set -e; gunzip -f '{}' && chmod 755 '{}' && mv -f '{}' '{}'",
+                tmp_path, uncompressed, uncompressed, dst_path
+            )
+        } else {
+            format!(
+                "set -e; chmod 755 '{}' && mv -f '{}' '{}'",
+                tmp_path, tmp_path, dst_path
+            )
+        };
+
+        self.run_docker_exec("sh", &["-c", &script])
+            .await
+            .map_err(|e| anyhow!("Failed to extract server binary: {}", e))?;
+        Ok(())
+    }
+
+    fn docker_exec_args(
+        &self,
+        interactive: bool,
+        env: &HashMap<String, String>,
+        working_dir: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = vec!["exec".to_string(), "-i".to_string()];
+        if interactive {
+            args.push("-t".to_string());
+        }
+        if let Some(user) = &self.connection_options.user {
+            args.push("--user".to_string());
+            args.push(user.clone());
+        }
+        if let Some(working_dir) = working_dir {
+            args.push("--workdir".to_string());
+            args.push(working_dir.to_string());
+        }
+        let mut env = env.iter().collect::<Vec<_>>();
+        env.sort();
+        for (key, value) in env {
+            args.push("--env".to_string());
+            args.push(format!("{key}={value}"));
+        }
+        args.push(self.connection_options.container_id.clone());
+        args
+    }
+}
+
+#[async_trait(?Send)]
+impl RemoteConnection for DockerRemoteConnection {
+    fn start_proxy(
+        &self,
+        unique_identifier: String,
+        reconnect: bool,
+        incoming_tx: UnboundedSender<Envelope>,
+        outgoing_rx: UnboundedReceiver<Envelope>,
+        connection_activity_tx: Sender<()>,
+        delegate: Arc<dyn RemoteClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<i32>> {
+        delegate.set_status(Some("Starting proxy"), cx);
+
+        let Some(remote_binary_path) = &self.remote_binary_path else {
+            return Task::ready(Err(anyhow!("Remote binary path not set")));
+        };
+
+        let mut proxy_command = format!(
+            "exec {} proxy --identifier {}",
+            remote_binary_path.display(),
+            unique_identifier
+        );
+        if reconnect {
+            proxy_command.push_str(" --reconnect");
+        }
+
+        let mut env = HashMap::default();
+        for env_var in ["RUST_LOG", "RUST_BACKTRACE", "ZED_GENERATE_MINIDUMPS"] {
+            if let Ok(value) = std::env::var(env_var) {
+                env.insert(env_var.to_string(), value);
+            }
+        }
+
+        let mut args = self.docker_exec_args(false, &env, Some(self.home_dir.as_str()));
+        args.extend(["sh".to_string(), "-lc".to_string(), proxy_command]);
+        let proxy_process = match docker_command(&args).kill_on_drop(true).spawn() {
+            Ok(process) => process,
+            Err(error) => {
+                return Task::ready(Err(anyhow!("failed to spawn remote server: {}", error)));
+            }
+        };
+
+        super::handle_rpc_messages_over_child_process_stdio(
+            proxy_process,
+            incoming_tx,
+            outgoing_rx,
+            connection_activity_tx,
+            cx,
+        )
+    }
+
+    fn upload_directory(
+        &self,
+        src_path: PathBuf,
+        dest_path: RemotePathBuf,
+        cx: &App,
+    ) -> Task<Result<()>> {
+        let container_id = self.connection_options.container_id.clone();
+        cx.background_spawn(async move {
+            let src = src_path.to_string_lossy();
+            let dst = format!("{}:{}", container_id, dest_path);
+            run_docker(&["cp", src.as_ref(), dst.as_str()])
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "failed to upload directory {} -> {}: {}",
+                        src_path.display(),
+                        dest_path,
+                        e
+                    )
+                })?;
+            Ok(())
+        })
+    }
+
+    async fn kill(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn has_been_killed(&self) -> bool {
+        false
+    }
+
+    fn build_command(
+        &self,
+        program: Option<String>,
+        args: &[String],
+        env: &HashMap<String, String>,
+        working_dir: Option<String>,
+        port_forward: Option<(u16, String, u16)>,
+    ) -> Result<CommandTemplate> {
+        if port_forward.is_some() {
+            bail!("port forwarding is not supported for dev containers");
+        }
+
+        let working_dir = working_dir
+            .map(|working_dir| RemotePathBuf::new(working_dir, PathStyle::Posix).to_string())
+            .map(|working_dir| match working_dir.strip_prefix("~") {
+                Some(rest) => format!("{}{}", self.home_dir, rest),
+                None => working_dir,
+            })
+            .unwrap_or_else(|| self.home_dir.clone());
+
+        let mut docker_args =
+            self.docker_exec_args(program.is_none(), env, Some(working_dir.as_str()));
+        match program {
+            Some(program) => {
+                docker_args.push(program);
+                docker_args.extend(args.iter().cloned());
+            }
+            None => {
+                docker_args.push(self.shell.clone());
+                docker_args.push("-l".to_string());
+            }
+        }
+
+        Ok(CommandTemplate {
+            program: "docker".to_string(),
+            args: docker_args,
+            env: HashMap::default(),
+        })
+    }
+
+    fn connection_options(&self) -> RemoteConnectionOptions {
+        RemoteConnectionOptions::Docker(self.connection_options.clone())
+    }
+
+    fn path_style(&self) -> PathStyle {
+        PathStyle::Posix
+    }
+
+    fn shell(&self) -> String {
+        self.shell.clone()
+    }
+
+    fn default_system_shell(&self) -> String {
+        self.default_system_shell.clone()
+    }
+}
+
+async fn run_docker(args: &[&str]) -> Result<String> {
+    let output = docker_command(args)
+        .output()
+        .await
+        .context("failed to run docker, is it installed?")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Command 'docker {}' failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+fn docker_command(args: &[impl AsRef<std::ffi::OsStr>]) -> process::Command {
+    let mut command = util::command::new_smol_command("docker");
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .args(args);
+    command
+}
@@ -1,6 +1,6 @@
 use crate::{
     RemoteClientDelegate, RemotePlatform,
-    remote_client::{CommandTemplate, RemoteConnection, RemoteConnectionOptions},
+    remote_client::{CommandTemplate, PortForward, RemoteConnection, RemoteConnectionOptions},
 };
 use anyhow::{Context as _, Result, anyhow};
 use async_trait::async_trait;
@@ -36,7 +36,7 @@ use util::{
 pub(crate) struct SshRemoteConnection {
     socket: SshSocket,
     master_process: Mutex<Option<Child>>,
-    remote_binary_path: Option<Arc<RelPath>>,
+    remote_binary_path: Option<String>,
     ssh_platform: RemotePlatform,
     ssh_path_style: PathStyle,
     ssh_shell: String,
@@ -55,6 +55,9 @@ pub struct SshConnectionOptions {
 
     pub nickname: Option<String>,
     pub upload_binary_over_ssh: bool,
+    pub proxy_jump: Option<String>,
+    pub forward_agent: bool,
+    pub remote_server_path: Option<String>,
 }
 
 impl From<settings::SshConnection> for SshConnectionOptions {
@@ -68,6 +71,9 @@ impl From<settings::SshConnection> for SshConnectionOptions {
             nickname: val.nickname,
             upload_binary_over_ssh: val.upload_binary_over_ssh.unwrap_or_default(),
             port_forwards: val.port_forwards,
+            proxy_jump: val.proxy_jump,
+            forward_agent: val.forward_agent.unwrap_or_default(),
+            remote_server_path: val.remote_server_path,
         }
     }
 }
@@ -148,6 +154,46 @@ impl RemoteConnection for SshRemoteConnection {
         )
     }
 
+    fn listening_ports(&self, cx: &App) -> Task<Result<Vec<u16>>> {
+        let socket = self.socket.clone();
+        let os = self.ssh_platform.os;
+        cx.background_spawn(async move {
+            if os == "macos" {
+                let output = socket
+                    .run_command("lsof", &["-nP", "-iTCP", "-sTCP:LISTEN"])
+                    .await?;
+                Ok(parse_lsof_listening_ports(&output))
+            } else {
+                let output = socket
+                    .run_command(
+                        "sh",
+                        &["-c", "cat /proc/net/tcp /proc/net/tcp6 2>/dev/null; true"],
+                    )
+                    .await?;
+                Ok(parse_proc_net_tcp_listening_ports(&output))
+            }
+        })
+    }
+
+    fn forward_port(&self, local_port: u16, remote_port: u16) -> Result<PortForward> {
+        let mut command = util::command::new_smol_command("ssh");
+        self.socket
+            .ssh_options(&mut command)
+            .kill_on_drop(true)
+            .arg("-N")
+            .arg("-L")
+            .arg(format!("{local_port}:localhost:{remote_port}"))
+            .arg(self.socket.connection_options.ssh_url());
+        let process = command
+            .spawn()
+            .with_context(|| format!("forwarding port {remote_port} to {local_port}"))?;
+        Ok(PortForward {
+            local_port,
+            remote_port,
+            _process: process,
+        })
+    }
+
     fn upload_directory(
         &self,
         src_path: PathBuf,
@@ -208,7 +254,7 @@ impl RemoteConnection for SshRemoteConnection {
 
         let mut start_proxy_command = shell_script!(
             "exec {binary_path} proxy --identifier {identifier}",
-            binary_path = &remote_binary_path.display(self.path_style()),
+            binary_path = &remote_binary_path,
             identifier = &unique_identifier,
         );
 
@@ -390,13 +436,43 @@ impl SshRemoteConnection {
             )
         })?;
         this.remote_binary_path = Some(
-            this.ensure_server_binary(&delegate, release_channel, version, commit, cx)
-                .await?,
+            match this.socket.connection_options.remote_server_path.clone() {
+                Some(remote_server_path) => {
+                    this.verify_server_binary(remote_server_path, &delegate, cx)
+                        .await?
+                }
+                None => this
+                    .ensure_server_binary(&delegate, release_channel, version, commit, cx)
+                    .await?
+                    .display(ssh_path_style)
+                    .into_owned(),
+            },
         );
 
         Ok(this)
     }
 
+    async fn verify_server_binary(
+        &self,
+        remote_server_path: String,
+        delegate: &Arc<dyn RemoteClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<String> {
+        delegate.set_status(Some("Checking remote server binary"), cx);
+        let version = self
+            .socket
+            .run_command(&remote_server_path, &["version"])
+            .await
+            .with_context(|| {
+                format!("failed to run the remote server binary at {remote_server_path}")
+            })?;
+        log::info!(
+            "using remote server binary at {remote_server_path} ({})",
+            version.trim()
+        );
+        Ok(remote_server_path)
+    }
+
     async fn ensure_server_binary(
         &self,
         delegate: &Arc<dyn RemoteClientDelegate>,
@@ -819,6 +895,43 @@ impl SshSocket {
     }
 }
 
+/// Parses the listening TCP ports out of the contents of `/proc/net/tcp{,6}`.
+fn parse_proc_net_tcp_listening_ports(output: &str) -> Vec<u16> {
+    const TCP_LISTEN: &str = "0A";
+    let mut ports = output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let local_address = fields.nth(1)?;
+            let state = fields.nth(1)?;
+            if state != TCP_LISTEN {
+                return None;
+            }
+            let (_, port) = local_address.rsplit_once(':')?;
+            u16::from_str_radix(port, 16).ok()
+        })
+        .collect::<Vec<_>>();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+/// Parses the listening TCP ports out of `lsof -nP -iTCP -sTCP:LISTEN` output.
+fn parse_lsof_listening_ports(output: &str) -> Vec<u16> {
+    let mut ports = output
+        .lines()
+        .filter(|line| line.ends_with("(LISTEN)"))
+        .filter_map(|line| {
+            let name = line.split_whitespace().rev().nth(1)?;
+            let (_, port) = name.rsplit_once(':')?;
+            port.parse().ok()
+        })
+        .collect::<Vec<_>>();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
 fn parse_port_number(port_str: &str) -> Result<u16> {
     port_str
         .parse()
@@ -863,6 +976,8 @@ impl SshConnectionOptions {
         let mut port: Option<u16> = None;
         let mut args = Vec::new();
         let mut port_forwards: Vec<SshPortForwardOption> = Vec::new();
+        let mut proxy_jump: Option<String> = None;
+        let mut forward_agent = false;
 
         // disallowed: -E, -e, -F, -f, -G, -g, -M, -N, -n, -O, -q, -S, -s, -T, -t, -V, -v, -W
         const ALLOWED_OPTS: &[&str] = &[
@@ -876,6 +991,17 @@ impl SshConnectionOptions {
         let mut tokens = shlex::split(input).context("invalid input")?.into_iter();
 
         'outer: while let Some(arg) = tokens.next() {
+            if arg == "-A" {
+                forward_agent = true;
+                continue;
+            }
+            if arg == "-J" {
+                proxy_jump = tokens.next();
+                continue;
+            } else if let Some(j) = arg.strip_prefix("-J") {
+                proxy_jump = Some(j.to_string());
+                continue;
+            }
             if ALLOWED_OPTS.contains(&(&arg as &str)) {
                 args.push(arg.to_string());
                 continue;
@@ -954,6 +1080,9 @@ impl SshConnectionOptions {
             password: None,
             nickname: None,
             upload_binary_over_ssh: false,
+            proxy_jump,
+            forward_agent,
+            remote_server_path: None,
         })
     }
 
@@ -976,6 +1105,14 @@ impl SshConnectionOptions {
     pub fn additional_args(&self) -> Vec<String> {
         let mut args = self.args.iter().flatten().cloned().collect::<Vec<String>>();
 
+        if let Some(proxy_jump) = &self.proxy_jump {
+            args.push("-J".to_string());
+            args.push(proxy_jump.clone());
+        }
+        if self.forward_agent {
+            args.push("-A".to_string());
+        }
+
         if let Some(forwards) = &self.port_forwards {
             args.extend(forwards.iter().map(|pf| {
                 let local_host = match &pf.local_host {
@@ -1150,4 +1287,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_listening_ports() {
+        let proc_net_tcp = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 1 1 0000000000000000 100 0 0 10 0
+   1: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 2 1 0000000000000000 100 0 0 10 0
+   2: 0100007F:0BB8 0100007F:D2F4 01 00000000:00000000 00:00000000 00000000  1000        0 3 1 0000000000000000 20 4 30 10 -1
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:0BB8 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4 1 0000000000000000 100 0 0 10 0
+   1: 00000000000000000000000000000000:1F90 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 5 1 0000000000000000 100 0 0 10 0
+";
+        assert_eq!(
+            parse_proc_net_tcp_listening_ports(proc_net_tcp),
+            vec![22, 3000, 8080]
+        );
+
+        let lsof = "\
+COMMAND   PID USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+node    12345 me     23u  IPv4 0x1234567890abcdef      0t0  TCP 127.0.0.1:3000 (LISTEN)
+node    12345 me     24u  IPv6 0x1234567890abcdef      0t0  TCP [::1]:3000 (LISTEN)
+python  23456 me      3u  IPv4 0x1234567890abcdef      0t0  TCP *:8000 (LISTEN)
+";
+        assert_eq!(parse_lsof_listening_ports(lsof), vec![3000, 8000]);
+    }
+
+    #[test]
+    fn test_parse_command_line_jump_hosts() {
+        let options =
+            SshConnectionOptions::parse_command_line("ssh -A -J bastion,inner -p 2222 me@host")
+                .unwrap();
+        assert_eq!(options.host, "host");
+        assert_eq!(options.username.as_deref(), Some("me"));
+        assert_eq!(options.port, Some(2222));
+        assert_eq!(options.proxy_jump.as_deref(), Some("bastion,inner"));
+        assert!(options.forward_agent);
+        assert_eq!(
+            options.additional_args(),
+            vec![
+                "-J".to_string(),
+                "bastion,inner".to_string(),
+                "-A".to_string()
+            ]
+        );
+    }
 }
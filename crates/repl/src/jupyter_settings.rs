@@ -2,11 +2,12 @@ use collections::HashMap;
 
 use editor::EditorSettings;
 use gpui::App;
-use settings::Settings;
+use settings::{NotebookSaveMode, Settings};
 
 #[derive(Debug, Default)]
 pub struct JupyterSettings {
     pub kernel_selections: HashMap<String, String>,
+    pub notebook_save_mode: NotebookSaveMode,
 }
 
 impl JupyterSettings {
@@ -23,6 +24,7 @@ impl Settings for JupyterSettings {
         let jupyter = content.editor.jupyter.clone().unwrap();
         Self {
             kernel_selections: jupyter.kernel_selections.unwrap_or_default(),
+            notebook_save_mode: jupyter.notebook_save_mode.unwrap_or_default(),
         }
     }
 }
@@ -1,22 +1,28 @@
 #![allow(unused, dead_code)]
 use std::sync::Arc;
 
-use editor::{Editor, EditorMode, MultiBuffer};
+use editor::{Editor, EditorEvent, EditorMode, MultiBuffer};
 use futures::future::Shared;
 use gpui::{
-    App, Entity, Hsla, RetainAllImageCache, Task, TextStyleRefinement, image_cache, prelude::*,
+    App, ClickEvent, Entity, EventEmitter, Hsla, RetainAllImageCache, Subscription, Task,
+    TextStyleRefinement, WeakEntity, image_cache, prelude::*,
 };
 use language::{Buffer, Language, LanguageRegistry};
 use markdown_preview::{markdown_parser::parse_markdown, markdown_renderer::render_markdown_block};
 use nbformat::v4::{CellId, CellMetadata, CellType};
+use runtimelib::JupyterMessageContent;
+use serde_json::Value;
 use settings::Settings as _;
 use theme::ThemeSettings;
 use ui::{IconButtonShape, prelude::*};
 use util::ResultExt;
+use workspace::Workspace;
 
 use crate::{
-    notebook::{CODE_BLOCK_INSET, GUTTER_WIDTH},
-    outputs::{Output, plain::TerminalOutput, user_error::ErrorView},
+    notebook::{CODE_BLOCK_INSET, GUTTER_WIDTH, output_for_message, push_output},
+    outputs::{
+        ExecutionStatus, ExecutionView, Output, plain::TerminalOutput, user_error::ErrorView,
+    },
 };
 
 #[derive(Copy, Clone, PartialEq, PartialOrd)]
@@ -77,6 +83,14 @@ impl Clickable for CellControl {
     }
 }
 
+/// Events emitted by a cell to the notebook that contains it.
+pub enum CellEvent {
+    /// The source or the outputs of the cell changed.
+    Changed,
+    /// The cell asked to be run.
+    Run,
+}
+
 /// A notebook cell
 #[derive(Clone)]
 pub enum Cell {
@@ -112,9 +126,67 @@ fn convert_outputs(
         .collect()
 }
 
+/// Creates the editor for the source of a cell, which emits [`CellEvent::Changed`] from the cell
+/// when the source is edited.
+fn cell_editor<T: EventEmitter<CellEvent>>(
+    text: &str,
+    language: Shared<Task<Option<Arc<Language>>>>,
+    window: &mut Window,
+    cx: &mut Context<T>,
+) -> (Entity<Editor>, Task<()>, Subscription) {
+    let buffer = cx.new(|cx| Buffer::local(text, cx));
+    let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+
+    let editor = cx.new(|cx| {
+        let mut editor = Editor::new(
+            EditorMode::AutoHeight {
+                min_lines: 1,
+                max_lines: Some(1024),
+            },
+            multi_buffer,
+            None,
+            window,
+            cx,
+        );
+
+        let theme = ThemeSettings::get_global(cx);
+
+        let refinement = TextStyleRefinement {
+            font_family: Some(theme.buffer_font.family.clone()),
+            font_size: Some(theme.buffer_font_size(cx).into()),
+            color: Some(cx.theme().colors().editor_foreground),
+            background_color: Some(gpui::transparent_black()),
+            ..Default::default()
+        };
+
+        editor.set_show_gutter(false, cx);
+        editor.set_text_style_refinement(refinement);
+        editor
+    });
+
+    let language_task = cx.spawn_in(window, async move |_, cx| {
+        let language = language.await;
+
+        buffer
+            .update(cx, |buffer, cx| {
+                buffer.set_language(language, cx);
+            })
+            .ok();
+    });
+
+    let subscription = cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+        if let EditorEvent::BufferEdited = event {
+            cx.emit(CellEvent::Changed);
+        }
+    });
+
+    (editor, language_task, subscription)
+}
+
 impl Cell {
     pub fn load(
         cell: &nbformat::v4::Cell,
+        json: Option<&Value>,
         languages: &Arc<LanguageRegistry>,
         notebook_language: Shared<Task<Option<Arc<Language>>>>,
         window: &mut Window,
@@ -130,35 +202,33 @@ impl Cell {
                 let source = source.join("");
 
                 let entity = cx.new(|cx| {
-                    let markdown_parsing_task = {
+                    let markdown_language = {
                         let languages = languages.clone();
-                        let source = source.clone();
-
-                        cx.spawn_in(window, async move |this, cx| {
-                            let parsed_markdown = cx
-                                .background_spawn(async move {
-                                    parse_markdown(&source, None, Some(languages)).await
-                                })
-                                .await;
-
-                            this.update(cx, |cell: &mut MarkdownCell, _| {
-                                cell.parsed_markdown = Some(parsed_markdown);
-                            })
-                            .log_err();
+                        cx.spawn(async move |_, _| {
+                            languages.language_for_name("Markdown").await.ok()
                         })
+                        .shared()
                     };
+                    let (editor, language_task, editor_subscription) =
+                        cell_editor(&source, markdown_language, window, cx);
 
-                    MarkdownCell {
-                        markdown_parsing_task,
+                    let mut cell = MarkdownCell {
+                        markdown_parsing_task: Task::ready(()),
                         image_cache: RetainAllImageCache::new(cx),
                         languages: languages.clone(),
                         id: id.clone(),
                         metadata: metadata.clone(),
                         source: source.clone(),
                         parsed_markdown: None,
+                        editor,
+                        editing: source.is_empty(),
                         selected: false,
                         cell_position: None,
-                    }
+                        language_task,
+                        _editor_subscription: editor_subscription,
+                    };
+                    cell.parse_markdown(window, cx);
+                    cell
                 });
 
                 Cell::Markdown(entity)
@@ -171,59 +241,29 @@ impl Cell {
                 outputs,
             } => Cell::Code(cx.new(|cx| {
                 let text = source.join("");
+                let (editor, language_task, editor_subscription) =
+                    cell_editor(&text, notebook_language, window, cx);
 
-                let buffer = cx.new(|cx| Buffer::local(text.clone(), cx));
-                let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
-
-                let editor_view = cx.new(|cx| {
-                    let mut editor = Editor::new(
-                        EditorMode::AutoHeight {
-                            min_lines: 1,
-                            max_lines: Some(1024),
-                        },
-                        multi_buffer,
-                        None,
-                        window,
-                        cx,
-                    );
-
-                    let theme = ThemeSettings::get_global(cx);
-
-                    let refinement = TextStyleRefinement {
-                        font_family: Some(theme.buffer_font.family.clone()),
-                        font_size: Some(theme.buffer_font_size(cx).into()),
-                        color: Some(cx.theme().colors().editor_foreground),
-                        background_color: Some(gpui::transparent_black()),
-                        ..Default::default()
-                    };
-
-                    editor.set_text(text, window, cx);
-                    editor.set_show_gutter(false, cx);
-                    editor.set_text_style_refinement(refinement);
-
-                    // editor.set_read_only(true);
-                    editor
-                });
-
-                let buffer = buffer.clone();
-                let language_task = cx.spawn_in(window, async move |this, cx| {
-                    let language = notebook_language.await;
-
-                    buffer.update(cx, |buffer, cx| {
-                        buffer.set_language(language.clone(), cx);
-                    });
-                });
+                let saved_outputs = json
+                    .and_then(|json| json.get("outputs"))
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
 
                 CodeCell {
                     id: id.clone(),
                     metadata: metadata.clone(),
                     execution_count: *execution_count,
-                    source: source.join(""),
-                    editor: editor_view,
+                    source: text,
+                    editor,
                     outputs: convert_outputs(outputs, window, cx),
+                    saved_outputs,
+                    execution: None,
+                    clear_outputs_on_next_output: false,
                     selected: false,
                     language_task,
                     cell_position: None,
+                    _editor_subscription: editor_subscription,
                 }
             })),
             nbformat::v4::Cell::Raw {
@@ -339,11 +379,61 @@ pub struct MarkdownCell {
     source: String,
     parsed_markdown: Option<markdown_preview::markdown_elements::ParsedMarkdown>,
     markdown_parsing_task: Task<()>,
+    editor: Entity<Editor>,
+    /// Whether the source is shown in an editor rather than rendered.
+    editing: bool,
     selected: bool,
     cell_position: Option<CellPosition>,
     languages: Arc<LanguageRegistry>,
+    language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
+impl MarkdownCell {
+    pub fn source_text(&self, cx: &App) -> String {
+        self.editor.read(cx).text(cx)
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    pub fn start_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editing = true;
+        window.focus(&self.editor.focus_handle(cx));
+        cx.notify();
+    }
+
+    /// Renders the source of the cell after it was edited.
+    pub fn finish_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editing = false;
+        self.source = self.source_text(cx);
+        self.parse_markdown(window, cx);
+        cx.notify();
+    }
+
+    fn parse_markdown(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let languages = self.languages.clone();
+        let source = self.source.clone();
+
+        self.markdown_parsing_task = cx.spawn_in(window, async move |this, cx| {
+            let parsed_markdown = cx
+                .background_spawn(
+                    async move { parse_markdown(&source, None, Some(languages)).await },
+                )
+                .await;
+
+            this.update(cx, |cell, cx| {
+                cell.parsed_markdown = Some(parsed_markdown);
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+}
+
+impl EventEmitter<CellEvent> for MarkdownCell {}
+
 impl RenderableCell for MarkdownCell {
     const CELL_TYPE: CellType = CellType::Markdown;
 
@@ -388,6 +478,38 @@ impl RenderableCell for MarkdownCell {
 
 impl Render for MarkdownCell {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.editing {
+            return v_flex()
+                .size_full()
+                .children(self.cell_position_spacer(true, window, cx))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .pr_6()
+                        .rounded_xs()
+                        .items_start()
+                        .gap(DynamicSpacing::Base08.rems(cx))
+                        .bg(self.selected_bg_color(window, cx))
+                        .child(self.gutter(window, cx))
+                        .child(
+                            div().py_1p5().w_full().child(
+                                div()
+                                    .flex()
+                                    .size_full()
+                                    .flex_1()
+                                    .py_3()
+                                    .px_5()
+                                    .rounded_lg()
+                                    .border_1()
+                                    .border_color(cx.theme().colors().border)
+                                    .bg(cx.theme().colors().editor_background)
+                                    .child(div().w_full().child(self.editor.clone())),
+                            ),
+                        ),
+                )
+                .children(self.cell_position_spacer(false, window, cx));
+        }
+
         let Some(parsed) = self.parsed_markdown.as_ref() else {
             return div();
         };
@@ -410,7 +532,13 @@ impl Render for MarkdownCell {
                     .child(self.gutter(window, cx))
                     .child(
                         v_flex()
+                            .id("markdown-cell")
                             .image_cache(self.image_cache.clone())
+                            .on_click(cx.listener(|this, event: &ClickEvent, window, cx| {
+                                if event.click_count() == 2 {
+                                    this.start_editing(window, cx);
+                                }
+                            }))
                             .size_full()
                             .flex_1()
                             .p_3()
@@ -434,22 +562,105 @@ pub struct CodeCell {
     execution_count: Option<i32>,
     source: String,
     editor: Entity<editor::Editor>,
+    /// The outputs that were loaded from the notebook file.
     outputs: Vec<Output>,
+    /// The outputs in the format they're stored in the notebook file, which are updated as the
+    /// cell is executed.
+    saved_outputs: Vec<Value>,
+    /// The outputs of the last execution of the cell, which replace the loaded outputs.
+    execution: Option<Entity<ExecutionView>>,
+    clear_outputs_on_next_output: bool,
     selected: bool,
     cell_position: Option<CellPosition>,
     language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
 impl CodeCell {
-    pub fn is_dirty(&self, cx: &App) -> bool {
-        self.editor.read(cx).buffer().read(cx).is_dirty(cx)
+    pub fn source_text(&self, cx: &App) -> String {
+        self.editor.read(cx).text(cx)
+    }
+
+    pub fn saved_outputs(&self) -> &[Value] {
+        &self.saved_outputs
+    }
+
+    pub fn execution_view(&self) -> Option<&Entity<ExecutionView>> {
+        self.execution.as_ref()
     }
+
     pub fn has_outputs(&self) -> bool {
-        !self.outputs.is_empty()
+        !self.outputs.is_empty() || !self.saved_outputs.is_empty() || self.execution.is_some()
     }
 
     pub fn clear_outputs(&mut self) {
         self.outputs.clear();
+        self.saved_outputs.clear();
+        self.execution = None;
+        self.execution_count = None;
+    }
+
+    /// Replaces the outputs of the cell with the outputs of a new execution.
+    pub fn start_execution(
+        &mut self,
+        status: ExecutionStatus,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace = window
+            .root::<Workspace>()
+            .flatten()
+            .map(|workspace| workspace.downgrade())
+            .unwrap_or_else(WeakEntity::new_invalid);
+
+        self.outputs.clear();
+        self.saved_outputs.clear();
+        self.clear_outputs_on_next_output = false;
+        self.execution = Some(cx.new(|cx| ExecutionView::new(status, workspace, cx)));
+        cx.emit(CellEvent::Changed);
+        cx.notify();
+    }
+
+    /// Accepts a message that the kernel sent while executing the cell.
+    pub fn handle_message(
+        &mut self,
+        message: &JupyterMessageContent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(execution) = self.execution.as_ref() {
+            execution.update(cx, |execution, cx| {
+                execution.push_message(message, window, cx);
+            });
+        }
+
+        match message {
+            JupyterMessageContent::ExecuteReply(reply) => {
+                self.execution_count = serde_json::to_value(&reply.execution_count)
+                    .ok()
+                    .and_then(|count| count.as_i64())
+                    .map(|count| count as i32);
+                cx.emit(CellEvent::Changed);
+            }
+            JupyterMessageContent::ClearOutput(options) => {
+                if options.wait {
+                    self.clear_outputs_on_next_output = true;
+                } else {
+                    self.saved_outputs.clear();
+                    cx.emit(CellEvent::Changed);
+                }
+            }
+            message => {
+                if let Some(output) = output_for_message(message) {
+                    if std::mem::take(&mut self.clear_outputs_on_next_output) {
+                        self.saved_outputs.clear();
+                    }
+                    push_output(&mut self.saved_outputs, output);
+                    cx.emit(CellEvent::Changed);
+                }
+            }
+        }
+        cx.notify();
     }
 
     fn output_control(&self) -> Option<CellControlType> {
@@ -526,10 +737,9 @@ impl RenderableCell for CodeCell {
             CellControl::new("rerun-cell", CellControlType::RerunCell)
         } else {
             CellControl::new("run-cell", CellControlType::RunCell)
-                .on_click(cx.listener(move |this, _, window, cx| this.run(window, cx)))
         };
 
-        Some(cell_control)
+        Some(cell_control.on_click(cx.listener(move |this, _, window, cx| this.run(window, cx))))
     }
 
     fn selected(&self) -> bool {
@@ -552,8 +762,8 @@ impl RenderableCell for CodeCell {
 }
 
 impl RunnableCell for CodeCell {
-    fn run(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Running code cell: {}", self.id);
+    fn run(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(CellEvent::Run);
     }
 
     fn execution_count(&self) -> Option<i32> {
@@ -567,6 +777,8 @@ impl RunnableCell for CodeCell {
     }
 }
 
+impl EventEmitter<CellEvent> for CodeCell {}
+
 impl Render for CodeCell {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
@@ -621,45 +833,50 @@ impl Render for CodeCell {
                                 .border_1()
                                 // .border_color(cx.theme().colors().border)
                                 // .bg(cx.theme().colors().editor_background)
-                                .child(div().w_full().children(self.outputs.iter().map(
-                                    |output| {
-                                        let content = match output {
-                                            Output::Plain { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Markdown { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Stream { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Image { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::Message(message) => Some(
-                                                div().child(message.clone()).into_any_element(),
-                                            ),
-                                            Output::Table { content, .. } => {
-                                                Some(content.clone().into_any_element())
-                                            }
-                                            Output::ErrorOutput(error_view) => {
-                                                error_view.render(window, cx)
-                                            }
-                                            Output::ClearOutputWaitMarker => None,
-                                        };
-
-                                        div()
-                                            // .w_full()
-                                            // .mt_3()
-                                            // .p_3()
-                                            // .rounded_sm()
-                                            // .bg(cx.theme().colors().editor_background)
-                                            // .border(px(1.))
-                                            // .border_color(cx.theme().colors().border)
-                                            // .shadow_xs()
-                                            .children(content)
-                                    },
-                                ))),
+                                .when_some(self.execution.clone(), |this, execution| {
+                                    this.child(div().w_full().child(execution))
+                                })
+                                .when(self.execution.is_none(), |this| {
+                                    this.child(div().w_full().children(self.outputs.iter().map(
+                                        |output| {
+                                            let content = match output {
+                                                Output::Plain { content, .. } => {
+                                                    Some(content.clone().into_any_element())
+                                                }
+                                                Output::Markdown { content, .. } => {
+                                                    Some(content.clone().into_any_element())
+                                                }
+                                                Output::Stream { content, .. } => {
+                                                    Some(content.clone().into_any_element())
+                                                }
+                                                Output::Image { content, .. } => {
+                                                    Some(content.clone().into_any_element())
+                                                }
+                                                Output::Message(message) => Some(
+                                                    div().child(message.clone()).into_any_element(),
+                                                ),
+                                                Output::Table { content, .. } => {
+                                                    Some(content.clone().into_any_element())
+                                                }
+                                                Output::ErrorOutput(error_view) => {
+                                                    error_view.render(window, cx)
+                                                }
+                                                Output::ClearOutputWaitMarker => None,
+                                            };
+
+                                            div()
+                                                // .w_full()
+                                                // .mt_3()
+                                                // .p_3()
+                                                // .rounded_sm()
+                                                // .bg(cx.theme().colors().editor_background)
+                                                // .border(px(1.))
+                                                // .border_color(cx.theme().colors().border)
+                                                // .shadow_xs()
+                                                .children(content)
+                                        },
+                                    )))
+                                }),
                         ),
                     ),
             )
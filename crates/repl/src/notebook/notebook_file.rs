@@ -0,0 +1,303 @@
+use anyhow::{Context as _, Result};
+use runtimelib::JupyterMessageContent;
+use serde::Serialize as _;
+use serde_json::{Map, Value};
+
+/// The cell type of a cell in an `.ipynb` file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SavedCellType {
+    Code,
+    Markdown,
+    Raw,
+}
+
+impl SavedCellType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SavedCellType::Code => "code",
+            SavedCellType::Markdown => "markdown",
+            SavedCellType::Raw => "raw",
+        }
+    }
+}
+
+/// A cell to write to an `.ipynb` file.
+pub struct SavedCell {
+    /// The JSON of the cell when the notebook was loaded, so that fields that the notebook editor
+    /// doesn't know about, like the cell's metadata or attachments, are written back unchanged.
+    pub template: Option<Value>,
+    pub cell_type: SavedCellType,
+    pub id: String,
+    pub source: String,
+    /// The outputs of a code cell, in the format they're stored in the file.
+    pub outputs: Vec<Value>,
+    pub execution_count: Option<i32>,
+}
+
+/// Serializes a notebook with the given cells, keeping the notebook's metadata from the JSON
+/// that was loaded.
+///
+/// When `include_outputs` is false, the outputs and execution counts of code cells are left out,
+/// so that diffs of the file only show changes to the sources of the cells.
+pub fn serialize_notebook(
+    notebook: &Value,
+    cells: Vec<SavedCell>,
+    include_outputs: bool,
+) -> Result<String> {
+    let mut notebook = notebook
+        .as_object()
+        .cloned()
+        .context("notebook is not a JSON object")?;
+
+    let cells = cells
+        .into_iter()
+        .map(|cell| serialize_cell(cell, include_outputs))
+        .collect();
+    notebook.insert("cells".into(), Value::Array(cells));
+    notebook
+        .entry("metadata")
+        .or_insert_with(|| Value::Object(Map::new()));
+    notebook.insert("nbformat".into(), 4.into());
+    // Cell ids were introduced in nbformat 4.5.
+    let minor = notebook
+        .get("nbformat_minor")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    notebook.insert("nbformat_minor".into(), minor.max(5).into());
+
+    // Jupyter writes notebooks with an indentation of one space and a trailing newline, so we do
+    // the same to avoid rewriting every line of the file on save.
+    let mut text = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut text, formatter);
+    Value::Object(notebook).serialize(&mut serializer)?;
+    text.push(b'\n');
+    Ok(String::from_utf8(text)?)
+}
+
+fn serialize_cell(cell: SavedCell, include_outputs: bool) -> Value {
+    let mut json = cell
+        .template
+        .and_then(|template| template.as_object().cloned())
+        .unwrap_or_default();
+
+    json.insert("cell_type".into(), cell.cell_type.as_str().into());
+    if cell.cell_type == SavedCellType::Code {
+        let execution_count = cell.execution_count.filter(|_| include_outputs);
+        json.insert(
+            "execution_count".into(),
+            execution_count.map_or(Value::Null, Value::from),
+        );
+    } else {
+        json.remove("execution_count");
+    }
+    json.insert("id".into(), cell.id.into());
+    json.entry("metadata")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if cell.cell_type == SavedCellType::Code {
+        let outputs = if include_outputs {
+            cell.outputs
+        } else {
+            Vec::new()
+        };
+        json.insert("outputs".into(), Value::Array(outputs));
+    } else {
+        json.remove("outputs");
+    }
+    json.insert("source".into(), split_source(&cell.source).into());
+
+    Value::Object(json)
+}
+
+/// Splits a cell's source into lines that keep their line endings, which is how Jupyter stores
+/// sources so that each line of a cell is on its own line of the file.
+pub fn split_source(source: &str) -> Vec<String> {
+    source
+        .split_inclusive('\n')
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Converts a message that a kernel sent in reply to executing a cell to the output that's stored
+/// in the file, if the message produces one.
+pub fn output_for_message(message: &JupyterMessageContent) -> Option<Value> {
+    let mut output = Map::new();
+    match message {
+        JupyterMessageContent::ExecuteResult(result) => {
+            output.insert("output_type".into(), "execute_result".into());
+            output.insert("data".into(), serde_json::to_value(&result.data).ok()?);
+            output.insert(
+                "metadata".into(),
+                serde_json::to_value(&result.metadata).ok()?,
+            );
+            output.insert(
+                "execution_count".into(),
+                serde_json::to_value(&result.execution_count).ok()?,
+            );
+        }
+        JupyterMessageContent::DisplayData(display_data) => {
+            output.insert("output_type".into(), "display_data".into());
+            output.insert(
+                "data".into(),
+                serde_json::to_value(&display_data.data).ok()?,
+            );
+            output.insert(
+                "metadata".into(),
+                serde_json::to_value(&display_data.metadata).ok()?,
+            );
+        }
+        JupyterMessageContent::StreamContent(stream) => {
+            output.insert("output_type".into(), "stream".into());
+            output.insert("name".into(), serde_json::to_value(&stream.name).ok()?);
+            output.insert("text".into(), stream.text.clone().into());
+        }
+        JupyterMessageContent::ErrorOutput(error) => {
+            output.insert("output_type".into(), "error".into());
+            output.insert("ename".into(), error.ename.clone().into());
+            output.insert("evalue".into(), error.evalue.clone().into());
+            output.insert("traceback".into(), error.traceback.clone().into());
+        }
+        _ => return None,
+    }
+    Some(Value::Object(output))
+}
+
+/// Appends an output to a cell's outputs, merging consecutive text written to the same stream,
+/// like Jupyter does.
+pub fn push_output(outputs: &mut Vec<Value>, output: Value) {
+    if let Some(last) = outputs.last_mut()
+        && is_stream(last)
+        && is_stream(&output)
+        && last.get("name") == output.get("name")
+    {
+        let text = format!(
+            "{}{}",
+            stream_text(last).unwrap_or_default(),
+            stream_text(&output).unwrap_or_default()
+        );
+        last["text"] = text.into();
+        return;
+    }
+    outputs.push(output);
+}
+
+fn is_stream(output: &Value) -> bool {
+    output.get("output_type").and_then(Value::as_str) == Some("stream")
+}
+
+/// Returns the text of a stream output, which may be stored as a string or as a list of lines.
+fn stream_text(output: &Value) -> Option<String> {
+    match output.get("text")? {
+        Value::String(text) => Some(text.clone()),
+        Value::Array(lines) => Some(lines.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn code_cell(id: &str, source: &str) -> SavedCell {
+        SavedCell {
+            template: None,
+            cell_type: SavedCellType::Code,
+            id: id.into(),
+            source: source.into(),
+            outputs: vec![json!({"output_type": "stream", "name": "stdout", "text": "2\n"})],
+            execution_count: Some(3),
+        }
+    }
+
+    #[test]
+    fn test_split_source() {
+        assert_eq!(split_source(""), Vec::<String>::new());
+        assert_eq!(split_source("a = 1"), vec!["a = 1"]);
+        assert_eq!(split_source("a = 1\nb = 2\n"), vec!["a = 1\n", "b = 2\n"]);
+        assert_eq!(split_source("a = 1\n\nb"), vec!["a = 1\n", "\n", "b"]);
+    }
+
+    #[test]
+    fn test_serialize_notebook() {
+        let notebook = json!({
+            "cells": [],
+            "metadata": {"kernelspec": {"name": "python3"}},
+            "nbformat": 4,
+            "nbformat_minor": 2,
+        });
+        let markdown = SavedCell {
+            template: Some(json!({
+                "cell_type": "markdown",
+                "id": "old",
+                "metadata": {"tags": ["intro"]},
+                "source": ["old"],
+            })),
+            cell_type: SavedCellType::Markdown,
+            id: "intro".into(),
+            source: "# Title\nText".into(),
+            outputs: Vec::new(),
+            execution_count: None,
+        };
+
+        let text = serialize_notebook(&notebook, vec![markdown, code_cell("code", "1 + 1")], true)
+            .unwrap();
+        assert!(text.starts_with("{\n \"cells\": [\n  {\n"));
+        assert!(text.ends_with("}\n"));
+
+        let saved: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            saved["metadata"],
+            json!({"kernelspec": {"name": "python3"}})
+        );
+        assert_eq!(saved["nbformat_minor"], 5);
+        assert_eq!(
+            saved["cells"][0],
+            json!({
+                "cell_type": "markdown",
+                "id": "intro",
+                "metadata": {"tags": ["intro"]},
+                "source": ["# Title\n", "Text"],
+            })
+        );
+        assert_eq!(saved["cells"][1]["execution_count"], 3);
+        assert_eq!(saved["cells"][1]["outputs"][0]["text"], "2\n");
+        assert_eq!(saved["cells"][1]["source"], json!(["1 + 1"]));
+    }
+
+    #[test]
+    fn test_serialize_notebook_without_outputs() {
+        let notebook = json!({"cells": [], "metadata": {}, "nbformat": 4, "nbformat_minor": 5});
+        let text = serialize_notebook(&notebook, vec![code_cell("code", "1 + 1")], false).unwrap();
+
+        let saved: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(saved["cells"][0]["execution_count"], Value::Null);
+        assert_eq!(saved["cells"][0]["outputs"], json!([]));
+        assert_eq!(saved["cells"][0]["source"], json!(["1 + 1"]));
+    }
+
+    #[test]
+    fn test_push_output_merges_streams() {
+        let mut outputs = Vec::new();
+        push_output(
+            &mut outputs,
+            json!({"output_type": "stream", "name": "stdout", "text": ["a\n", "b\n"]}),
+        );
+        push_output(
+            &mut outputs,
+            json!({"output_type": "stream", "name": "stdout", "text": "c\n"}),
+        );
+        push_output(
+            &mut outputs,
+            json!({"output_type": "stream", "name": "stderr", "text": "oops\n"}),
+        );
+        push_output(
+            &mut outputs,
+            json!({"output_type": "display_data", "data": {}, "metadata": {}}),
+        );
+
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(outputs[0]["text"], "a\nb\nc\n");
+        assert_eq!(outputs[1]["name"], "stderr");
+    }
+}
@@ -1,26 +1,34 @@
 #![allow(unused, dead_code)]
 use std::future::Future;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{Context as _, Result};
 use client::proto::ViewId;
 use collections::HashMap;
-use feature_flags::{FeatureFlagAppExt as _, NotebookFeatureFlag};
 use futures::FutureExt;
 use futures::future::Shared;
 use gpui::{
     AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, ListScrollEvent, ListState,
-    Point, Task, actions, list, prelude::*,
+    Point, Subscription, Task, actions, list, prelude::*,
 };
 use language::{Language, LanguageRegistry};
-use project::{Project, ProjectEntryId, ProjectPath};
+use project::{Fs, Project, ProjectEntryId, ProjectPath};
+use serde_json::{Value, json};
+use settings::{NotebookSaveMode, Settings as _};
 use ui::{Tooltip, prelude::*};
 use workspace::item::{ItemEvent, SaveOptions, TabContentParams};
 use workspace::searchable::SearchableItemHandle;
 use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation};
 use workspace::{ToolbarItemEvent, ToolbarItemView};
 
-use super::{Cell, CellPosition, RenderableCell};
+use super::{
+    Cell, CellEvent, CellPosition, CodeCell, RenderableCell, RunnableCell, SavedCell,
+    SavedCellType, serialize_notebook,
+};
+use crate::{JupyterSettings, Session, repl_store::ReplStore};
 
 use nbformat::v4::CellId;
 use nbformat::v4::Metadata as NotebookMetadata;
@@ -30,6 +38,8 @@ actions!(
     [
         /// Opens a Jupyter notebook file.
         OpenNotebook,
+        /// Runs the selected cell and selects the next one.
+        RunCell,
         /// Runs all cells in the notebook.
         RunAll,
         /// Clears all cell outputs.
@@ -54,21 +64,7 @@ pub(crate) const CODE_BLOCK_INSET: f32 = MEDIUM_SPACING_SIZE;
 pub(crate) const CONTROL_SIZE: f32 = 20.0;
 
 pub fn init(cx: &mut App) {
-    if cx.has_flag::<NotebookFeatureFlag>() || std::env::var("LOCAL_NOTEBOOK_DEV").is_ok() {
-        workspace::register_project_item::<NotebookEditor>(cx);
-    }
-
-    cx.observe_flag::<NotebookFeatureFlag, _>({
-        move |is_enabled, cx| {
-            if is_enabled {
-                workspace::register_project_item::<NotebookEditor>(cx);
-            } else {
-                // todo: there is no way to unregister a project item, so if the feature flag
-                // gets turned off they need to restart Zed.
-            }
-        }
-    })
-    .detach();
+    workspace::register_project_item::<NotebookEditor>(cx);
 }
 
 pub struct NotebookEditor {
@@ -84,6 +80,13 @@ pub struct NotebookEditor {
     selected_cell_index: usize,
     cell_order: Vec<CellId>,
     cell_map: HashMap<CellId, Cell>,
+    /// The JSON of each cell when the notebook was loaded, which is written back on save.
+    cell_templates: HashMap<CellId, Value>,
+    notebook_language: Shared<Task<Option<Arc<Language>>>>,
+    /// The kernel session that runs the cells, which is started when a cell is first run.
+    session: Option<Entity<Session>>,
+    dirty: bool,
+    _cell_subscriptions: HashMap<CellId, Subscription>,
 }
 
 impl NotebookEditor {
@@ -103,32 +106,9 @@ impl NotebookEditor {
             .spawn_in(window, async move |_, _| notebook_language.await)
             .shared();
 
-        let mut cell_order = vec![]; // Vec<CellId>
-        let mut cell_map = HashMap::default(); // HashMap<CellId, Cell>
-
-        for (index, cell) in notebook_item
-            .read(cx)
-            .notebook
-            .clone()
-            .cells
-            .iter()
-            .enumerate()
-        {
-            let cell_id = cell.id();
-            cell_order.push(cell_id.clone());
-            cell_map.insert(
-                cell_id.clone(),
-                Cell::load(cell, &languages, notebook_language.clone(), window, cx),
-            );
-        }
-
-        let notebook_handle = cx.entity().downgrade();
-        let cell_count = cell_order.len();
-
-        let this = cx.entity();
-        let cell_list = ListState::new(cell_count, gpui::ListAlignment::Top, px(1000.));
+        let cell_list = ListState::new(0, gpui::ListAlignment::Top, px(1000.));
 
-        Self {
+        let mut this = Self {
             project,
             languages: languages.clone(),
             focus_handle,
@@ -136,9 +116,190 @@ impl NotebookEditor {
             remote_id: None,
             cell_list,
             selected_cell_index: 0,
-            cell_order: cell_order.clone(),
-            cell_map: cell_map.clone(),
+            cell_order: Vec::new(),
+            cell_map: HashMap::default(),
+            cell_templates: HashMap::default(),
+            notebook_language,
+            session: None,
+            dirty: false,
+            _cell_subscriptions: HashMap::default(),
+        };
+        this.load_cells(window, cx);
+        this
+    }
+
+    /// Replaces the cells with the ones in the notebook item.
+    fn load_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.cell_order.clear();
+        self.cell_map.clear();
+        self.cell_templates.clear();
+        self._cell_subscriptions.clear();
+
+        let notebook_item = self.notebook_item.read(cx);
+        let cells = notebook_item.notebook.cells.clone();
+        let templates = notebook_item
+            .raw
+            .get("cells")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for (index, cell) in cells.iter().enumerate() {
+            self.insert_cell(
+                self.cell_order.len(),
+                cell,
+                templates.get(index),
+                window,
+                cx,
+            );
         }
+
+        self.selected_cell_index = 0;
+        self.dirty = false;
+        self.cell_list.reset(self.cell_order.len());
+        cx.notify();
+    }
+
+    fn insert_cell(
+        &mut self,
+        index: usize,
+        cell: &nbformat::v4::Cell,
+        template: Option<&Value>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cell_id = cell.id().clone();
+        let loaded = Cell::load(
+            cell,
+            template,
+            &self.languages,
+            self.notebook_language.clone(),
+            window,
+            cx,
+        );
+
+        let subscription = match &loaded {
+            Cell::Code(code_cell) => cx.subscribe_in(
+                code_cell,
+                window,
+                |this, code_cell, event, window, cx| match event {
+                    CellEvent::Changed => this.mark_dirty(cx),
+                    CellEvent::Run => this.run_code_cell(code_cell.clone(), window, cx),
+                },
+            ),
+            Cell::Markdown(markdown_cell) => cx.subscribe_in(
+                markdown_cell,
+                window,
+                |this, markdown_cell, event, window, cx| match event {
+                    CellEvent::Changed => this.mark_dirty(cx),
+                    CellEvent::Run => markdown_cell.update(cx, |cell, cx| {
+                        cell.finish_editing(window, cx);
+                    }),
+                },
+            ),
+            Cell::Raw(_) => Subscription::new(|| {}),
+        };
+
+        if let Some(template) = template {
+            self.cell_templates
+                .insert(cell_id.clone(), template.clone());
+        }
+        self._cell_subscriptions
+            .insert(cell_id.clone(), subscription);
+        self.cell_map.insert(cell_id.clone(), loaded);
+        self.cell_order.insert(index, cell_id);
+    }
+
+    fn mark_dirty(&mut self, cx: &mut Context<Self>) {
+        if !self.dirty {
+            self.dirty = true;
+            cx.emit(ItemEvent::UpdateTab);
+        }
+        cx.notify();
+    }
+
+    /// Returns the session that runs the cells, starting a kernel for the notebook if needed.
+    fn session(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Option<Entity<Session>> {
+        if let Some(session) = self.session.as_ref() {
+            return Some(session.clone());
+        }
+
+        let notebook_item = self.notebook_item.read(cx);
+        let worktree_id = notebook_item.project_path.worktree_id;
+        let kernel_name = notebook_item
+            .notebook
+            .metadata
+            .kernelspec
+            .as_ref()
+            .map(|spec| spec.name.clone());
+        let language_name = notebook_item.language_name();
+        let working_directory = notebook_item.path.parent().map(|path| path.to_path_buf());
+
+        let store = ReplStore::global(cx);
+        let kernel_specification = store.read(cx).kernelspec_for_notebook(
+            worktree_id,
+            kernel_name.as_deref(),
+            language_name.as_deref(),
+        );
+        let Some(kernel_specification) = kernel_specification else {
+            log::error!(
+                "No kernel found for notebook with kernel {:?} and language {:?}",
+                kernel_name,
+                language_name
+            );
+            return None;
+        };
+
+        let fs = store.read(cx).fs().clone();
+        let session = cx.new(|cx| {
+            Session::for_notebook(fs, kernel_specification, working_directory, window, cx)
+        });
+        self.session = Some(session.clone());
+        Some(session)
+    }
+
+    fn run_code_cell(
+        &mut self,
+        code_cell: Entity<CodeCell>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(session) = self.session(window, cx) else {
+            return;
+        };
+        let code = code_cell.read(cx).source_text(cx);
+        session.update(cx, |session, cx| {
+            session.execute_cell(code, code_cell, window, cx);
+        });
+    }
+
+    fn run_cell(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(cell) = self
+            .cell_order
+            .get(index)
+            .and_then(|cell_id| self.cell_map.get(cell_id))
+            .cloned()
+        else {
+            return;
+        };
+
+        match cell {
+            Cell::Code(code_cell) => self.run_code_cell(code_cell, window, cx),
+            Cell::Markdown(markdown_cell) => markdown_cell.update(cx, |cell, cx| {
+                cell.finish_editing(window, cx);
+            }),
+            Cell::Raw(_) => {}
+        }
+    }
+
+    fn run_selected_cell(&mut self, _: &RunCell, window: &mut Window, cx: &mut Context<Self>) {
+        let index = self.selected_cell_index;
+        self.run_cell(index, window, cx);
+
+        if index + 1 < self.cell_count() {
+            self.set_selected_index(index + 1, true, window, cx);
+        }
+        cx.notify();
     }
 
     fn has_outputs(&self, window: &mut Window, cx: &mut Context<Self>) -> bool {
@@ -154,15 +315,19 @@ impl NotebookEditor {
     fn clear_outputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         for cell in self.cell_map.values() {
             if let Cell::Code(code_cell) = cell {
-                code_cell.update(cx, |cell, _cx| {
+                code_cell.update(cx, |cell, cx| {
                     cell.clear_outputs();
+                    cx.notify();
                 });
             }
         }
+        self.mark_dirty(cx);
     }
 
     fn run_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Cells would all run here, if that was implemented!");
+        for index in 0..self.cell_count() {
+            self.run_cell(index, window, cx);
+        }
     }
 
     fn open_notebook(&mut self, _: &OpenNotebook, _window: &mut Window, _cx: &mut Context<Self>) {
@@ -170,19 +335,118 @@ impl NotebookEditor {
     }
 
     fn move_cell_up(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell up triggered");
+        let index = self.selected_cell_index;
+        if index > 0 && index < self.cell_count() {
+            self.swap_cells(index - 1, index, window, cx);
+            self.set_selected_index(index - 1, true, window, cx);
+        }
     }
 
     fn move_cell_down(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell down triggered");
+        let index = self.selected_cell_index;
+        if index + 1 < self.cell_count() {
+            self.swap_cells(index, index + 1, window, cx);
+            self.set_selected_index(index + 1, true, window, cx);
+        }
+    }
+
+    fn swap_cells(&mut self, a: usize, b: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.cell_order.swap(a, b);
+        self.cell_list.splice(a.min(b)..a.max(b) + 1, 2);
+        self.mark_dirty(cx);
     }
 
     fn add_markdown_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add markdown block triggered");
+        self.add_cell(SavedCellType::Markdown, window, cx);
     }
 
     fn add_code_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add code block triggered");
+        self.add_cell(SavedCellType::Code, window, cx);
+    }
+
+    /// Adds an empty cell after the selected cell, and selects it.
+    fn add_cell(&mut self, cell_type: SavedCellType, window: &mut Window, cx: &mut Context<Self>) {
+        let id = uuid::Uuid::new_v4().to_string();
+        let template = match cell_type {
+            SavedCellType::Code => json!({
+                "cell_type": "code",
+                "execution_count": null,
+                "id": id,
+                "metadata": {},
+                "outputs": [],
+                "source": [],
+            }),
+            SavedCellType::Markdown => json!({
+                "cell_type": "markdown",
+                "id": id,
+                "metadata": {},
+                "source": [],
+            }),
+            SavedCellType::Raw => json!({
+                "cell_type": "raw",
+                "id": id,
+                "metadata": {},
+                "source": [],
+            }),
+        };
+        let cell = match serde_json::from_value::<nbformat::v4::Cell>(template.clone()) {
+            Ok(cell) => cell,
+            Err(error) => {
+                log::error!("Failed to create a notebook cell: {error}");
+                return;
+            }
+        };
+
+        let index = if self.cell_count() == 0 {
+            0
+        } else {
+            (self.selected_cell_index + 1).min(self.cell_count())
+        };
+        self.insert_cell(index, &cell, Some(&template), window, cx);
+        self.cell_list.splice(index..index, 1);
+        self.set_selected_index(index, true, window, cx);
+        self.mark_dirty(cx);
+    }
+
+    /// Returns the cells in the format they're written to the notebook file.
+    fn saved_cells(&self, cx: &App) -> Vec<SavedCell> {
+        self.cell_order
+            .iter()
+            .filter_map(|cell_id| {
+                let cell = self.cell_map.get(cell_id)?;
+                let template = self.cell_templates.get(cell_id).cloned();
+                let id = cell_id.to_string();
+                Some(match cell {
+                    Cell::Code(cell) => {
+                        let cell = cell.read(cx);
+                        SavedCell {
+                            template,
+                            cell_type: SavedCellType::Code,
+                            id,
+                            source: cell.source_text(cx),
+                            outputs: cell.saved_outputs().to_vec(),
+                            execution_count: cell.execution_count(),
+                        }
+                    }
+                    Cell::Markdown(cell) => SavedCell {
+                        template,
+                        cell_type: SavedCellType::Markdown,
+                        id,
+                        source: cell.read(cx).source_text(cx),
+                        outputs: Vec::new(),
+                        execution_count: None,
+                    },
+                    Cell::Raw(cell) => SavedCell {
+                        template,
+                        cell_type: SavedCellType::Raw,
+                        id,
+                        source: cell.read(cx).source().clone(),
+                        outputs: Vec::new(),
+                        execution_count: None,
+                    },
+                })
+            })
+            .collect()
     }
 
     fn cell_count(&self) -> usize {
@@ -494,6 +758,7 @@ impl Render for NotebookEditor {
             .on_action(
                 cx.listener(|this, &ClearOutputs, window, cx| this.clear_outputs(window, cx)),
             )
+            .on_action(cx.listener(Self::run_selected_cell))
             .on_action(cx.listener(|this, &RunAll, window, cx| this.run_cells(window, cx)))
             .on_action(cx.listener(|this, &MoveCellUp, window, cx| this.move_cell_up(window, cx)))
             .on_action(
@@ -553,6 +818,8 @@ pub struct NotebookItem {
     languages: Arc<LanguageRegistry>,
     // Raw notebook data
     notebook: nbformat::v4::Notebook,
+    /// The JSON of the notebook file, whose metadata is kept when the notebook is saved.
+    raw: Value,
     // Store our version of the notebook in memory (cell_order, cell_map)
     id: ProjectEntryId,
 }
@@ -575,23 +842,7 @@ impl project::ProjectItem for NotebookItem {
                     .with_context(|| format!("finding the absolute path of {path:?}"))?;
 
                 // todo: watch for changes to the file
-                let file_content = fs.load(abs_path.as_path()).await?;
-                let notebook = nbformat::parse_notebook(&file_content);
-
-                let notebook = match notebook {
-                    Ok(nbformat::Notebook::V4(notebook)) => notebook,
-                    // 4.1 - 4.4 are converted to 4.5
-                    Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
-                        // TODO: Decide if we want to mutate the notebook by including Cell IDs
-                        // and any other conversions
-
-                        nbformat::upgrade_legacy_notebook(legacy_notebook)?
-                    }
-                    // Bad notebooks and notebooks v4.0 and below are not supported
-                    Err(e) => {
-                        anyhow::bail!("Failed to parse notebook: {:?}", e);
-                    }
-                };
+                let (notebook, raw) = NotebookItem::load(fs.as_ref(), &abs_path).await?;
 
                 let id = project
                     .update(cx, |project, cx| {
@@ -604,6 +855,7 @@ impl project::ProjectItem for NotebookItem {
                     project_path: path,
                     languages,
                     notebook,
+                    raw,
                     id,
                 })
             }))
@@ -626,6 +878,27 @@ impl project::ProjectItem for NotebookItem {
 }
 
 impl NotebookItem {
+    async fn load(fs: &dyn Fs, abs_path: &Path) -> Result<(nbformat::v4::Notebook, Value)> {
+        let file_content = fs.load(abs_path).await?;
+        let raw = serde_json::from_str(&file_content)?;
+        let notebook = nbformat::parse_notebook(&file_content);
+
+        let notebook = match notebook {
+            Ok(nbformat::Notebook::V4(notebook)) => notebook,
+            // 4.1 - 4.4 are converted to 4.5
+            Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
+                // Cell IDs that are added by the upgrade are written to the file when it's saved
+                nbformat::upgrade_legacy_notebook(legacy_notebook)?
+            }
+            // Bad notebooks and notebooks v4.0 and below are not supported
+            Err(e) => {
+                anyhow::bail!("Failed to parse notebook: {:?}", e);
+            }
+        };
+
+        Ok((notebook, raw))
+    }
+
     pub fn language_name(&self) -> Option<String> {
         self.notebook
             .metadata
@@ -654,7 +927,7 @@ impl NotebookItem {
     }
 }
 
-impl EventEmitter<()> for NotebookEditor {}
+impl EventEmitter<ItemEvent> for NotebookEditor {}
 
 // pub struct NotebookControls {
 //     pane_focused: bool,
@@ -702,7 +975,11 @@ impl EventEmitter<()> for NotebookEditor {}
 // }
 
 impl Item for NotebookEditor {
-    type Event = ();
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
 
     fn clone_on_split(
         &self,
@@ -773,19 +1050,32 @@ impl Item for NotebookEditor {
         // TODO
     }
 
-    // TODO
     fn can_save(&self, _cx: &App) -> bool {
-        false
+        true
     }
-    // TODO
+
     fn save(
         &mut self,
         _options: SaveOptions,
-        _project: Entity<Project>,
+        project: Entity<Project>,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save() must be implemented if can_save() returns true")
+        let include_outputs =
+            JupyterSettings::get_global(cx).notebook_save_mode == NotebookSaveMode::Full;
+        let notebook_item = self.notebook_item.read(cx);
+        let text = serialize_notebook(&notebook_item.raw, self.saved_cells(cx), include_outputs);
+        let abs_path = notebook_item.path.clone();
+        let fs = project.read(cx).fs().clone();
+
+        cx.spawn(async move |this, cx| {
+            fs.atomic_write(abs_path, text?).await?;
+            this.update(cx, |this, cx| {
+                this.dirty = false;
+                cx.emit(ItemEvent::UpdateTab);
+                cx.notify();
+            })
+        })
     }
 
     // TODO
@@ -798,25 +1088,32 @@ impl Item for NotebookEditor {
     ) -> Task<Result<()>> {
         unimplemented!("save_as() must be implemented if can_save() returns true")
     }
-    // TODO
     fn reload(
         &mut self,
-        _project: Entity<Project>,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("reload() must be implemented if can_save() returns true")
-    }
+        let notebook_item = self.notebook_item.clone();
+        let abs_path = notebook_item.read(cx).path.clone();
+        let fs = project.read(cx).fs().clone();
 
-    fn is_dirty(&self, cx: &App) -> bool {
-        self.cell_map.values().any(|cell| {
-            if let Cell::Code(code_cell) = cell {
-                code_cell.read(cx).is_dirty(cx)
-            } else {
-                false
-            }
+        cx.spawn_in(window, async move |this, cx| {
+            let (notebook, raw) = NotebookItem::load(fs.as_ref(), &abs_path).await?;
+            notebook_item.update(cx, |notebook_item, _| {
+                notebook_item.notebook = notebook;
+                notebook_item.raw = raw;
+            })?;
+            this.update_in(cx, |this, window, cx| {
+                this.load_cells(window, cx);
+                cx.emit(ItemEvent::UpdateTab);
+            })
         })
     }
+
+    fn is_dirty(&self, _cx: &App) -> bool {
+        self.dirty
+    }
 }
 
 // TODO: Implement this to allow us to persist to the database, etc:
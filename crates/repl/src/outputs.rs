@@ -14,6 +14,7 @@
 //! The module supports several output types, including:
 //! - Plain text
 //! - Markdown
+//! - HTML (converted to Markdown)
 //! - Images (PNG and JPEG)
 //! - Tables
 //! - Error messages
@@ -45,7 +46,7 @@ mod image;
 use image::ImageView;
 
 mod markdown;
-use markdown::MarkdownView;
+use markdown::{MarkdownView, html_to_markdown};
 
 mod table;
 use table::TableView;
@@ -60,10 +61,11 @@ use workspace::Workspace;
 /// When deciding what to render from a collection of mediatypes, we need to rank them in order of importance
 fn rank_mime_type(mimetype: &MimeType) -> usize {
     match mimetype {
-        MimeType::DataTable(_) => 6,
-        MimeType::Png(_) => 4,
-        MimeType::Jpeg(_) => 3,
-        MimeType::Markdown(_) => 2,
+        MimeType::DataTable(_) => 7,
+        MimeType::Png(_) => 5,
+        MimeType::Jpeg(_) => 4,
+        MimeType::Markdown(_) => 3,
+        MimeType::Html(_) => 2,
         MimeType::Plain(_) => 1,
         // All other media types are not supported in Zed at this time
         _ => 0,
@@ -278,6 +280,13 @@ impl Output {
                     display_id,
                 }
             }
+            Some(MimeType::Html(html)) => match html_to_markdown(html) {
+                Ok(text) => Output::Markdown {
+                    content: cx.new(|cx| MarkdownView::from(text, cx)),
+                    display_id,
+                },
+                Err(error) => Output::Message(format!("Failed to render HTML: {}", error)),
+            },
             Some(MimeType::Png(data)) | Some(MimeType::Jpeg(data)) => match ImageView::from(data) {
                 Ok(view) => Output::Image {
                     content: cx.new(|_| view),
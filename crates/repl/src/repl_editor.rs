@@ -366,6 +366,13 @@ fn jupytext_cells(
     let jupytext_prefixes = comment_prefixes
         .iter()
         .map(|comment_prefix| format!("{comment_prefix}%%"))
+        .chain(
+            language
+                .config()
+                .repl_cell_delimiters
+                .iter()
+                .map(|delimiter| delimiter.to_string()),
+        )
         .collect::<Vec<_>>();
 
     let mut snippet_start_row = None;
@@ -632,6 +639,52 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_julia_cell_ranges(cx: &mut App) {
+        let julia = Arc::new(Language::new(
+            LanguageConfig {
+                name: "Julia".into(),
+                line_comments: vec!["# ".into()],
+                repl_cell_delimiters: vec!["##".into()],
+                ..Default::default()
+            },
+            None,
+        ));
+
+        let buffer = cx.new(|cx| {
+            Buffer::local(
+                indoc! { r#"
+                    ## Setup
+                    x = 1
+
+                    # %%
+                    println(x + 1)
+                    ## Plot
+                    println(x + 2)
+                "# },
+                cx,
+            )
+            .with_language(julia, cx)
+        });
+        let snapshot = buffer.read(cx).snapshot();
+
+        let (snippets, next_cursor) =
+            runnable_ranges(&snapshot, Point::new(1, 0)..Point::new(1, 0), cx);
+        let snippets = snippets
+            .into_iter()
+            .map(|range| snapshot.text_for_range(range).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(snippets, vec!["## Setup\nx = 1"]);
+        assert_eq!(next_cursor, Some(Point::new(3, 0)));
+
+        let (snippets, _) = runnable_ranges(&snapshot, Point::new(4, 0)..Point::new(4, 0), cx);
+        let snippets = snippets
+            .into_iter()
+            .map(|range| snapshot.text_for_range(range).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(snippets, vec!["# %%\nprintln(x + 1)"]);
+    }
+
     #[gpui::test]
     fn test_markdown_code_blocks(cx: &mut App) {
         use crate::kernels::LocalKernelSpecification;
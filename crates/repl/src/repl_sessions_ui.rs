@@ -246,7 +246,7 @@ impl Render for ReplSessionsPage {
 
         // When there are no sessions, show the command to run code in an editor
         if sessions.is_empty() {
-            let instructions = "To run code in a Jupyter kernel, select some code and use the 'repl::Run' command.";
+            let instructions = "To run code in a Jupyter kernel, select some code or place your cursor in a `# %%` cell and use the 'repl::Run' command.";
 
             return ReplSessionsContainer::new("No Jupyter Kernel Sessions").child(
                 v_flex()
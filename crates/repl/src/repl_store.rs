@@ -221,6 +221,35 @@ impl ReplStore {
         }
     }
 
+    /// Returns the kernel to run a notebook with, preferring the kernel named in the notebook's
+    /// metadata, then the kernel selected for the worktree, then any kernel for its language.
+    pub fn kernelspec_for_notebook(
+        &self,
+        worktree_id: WorktreeId,
+        kernel_name: Option<&str>,
+        language: Option<&str>,
+    ) -> Option<KernelSpecification> {
+        let specifications = self
+            .kernel_specifications_for_worktree(worktree_id)
+            .collect::<Vec<_>>();
+
+        kernel_name
+            .and_then(|kernel_name| {
+                specifications
+                    .iter()
+                    .find(|spec| spec.name().eq_ignore_ascii_case(kernel_name))
+            })
+            .map(|spec| (*spec).clone())
+            .or_else(|| self.selected_kernel_for_worktree.get(&worktree_id).cloned())
+            .or_else(|| {
+                let language = language?;
+                specifications
+                    .iter()
+                    .find(|spec| spec.language().eq_ignore_ascii_case(language))
+                    .map(|spec| (*spec).clone())
+            })
+    }
+
     fn kernelspec_legacy_by_lang_only(
         &self,
         language_at_cursor: Arc<Language>,
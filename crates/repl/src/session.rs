@@ -1,5 +1,6 @@
-use crate::components::KernelListItem;
+use crate::components::{KernelListItem, KernelSelector};
 use crate::kernels::RemoteRunningKernel;
+use crate::notebook::CodeCell;
 use crate::setup_editor_session_actions;
 use crate::{
     KernelStatus,
@@ -27,7 +28,7 @@ use runtimelib::{
     ExecuteRequest, ExecutionState, InterruptRequest, JupyterMessage, JupyterMessageContent,
     ShutdownRequest,
 };
-use std::{env::temp_dir, ops::Range, sync::Arc, time::Duration};
+use std::{env::temp_dir, ops::Range, path::PathBuf, sync::Arc, time::Duration};
 use theme::ActiveTheme;
 use ui::{IconButtonShape, Tooltip, prelude::*};
 use util::ResultExt as _;
@@ -35,8 +36,12 @@ use util::ResultExt as _;
 pub struct Session {
     fs: Arc<dyn Fs>,
     editor: WeakEntity<Editor>,
+    /// The directory to start the kernel in when the session doesn't belong to an editor.
+    working_directory: Option<PathBuf>,
     pub kernel: Kernel,
     blocks: HashMap<String, EditorBlock>,
+    /// The notebook cells that are being executed, by the id of their execute request.
+    cells: HashMap<String, WeakEntity<CodeCell>>,
     pub kernel_specification: KernelSpecification,
     _buffer_subscription: Subscription,
 }
@@ -218,8 +223,10 @@ impl Session {
         let mut session = Self {
             fs,
             editor,
+            working_directory: None,
             kernel: Kernel::StartingKernel(Task::ready(()).shared()),
             blocks: HashMap::default(),
+            cells: HashMap::default(),
             kernel_specification,
             _buffer_subscription: subscription,
         };
@@ -228,13 +235,39 @@ impl Session {
         session
     }
 
+    /// Creates a session that runs the cells of a notebook rather than the code in an editor.
+    pub fn for_notebook(
+        fs: Arc<dyn Fs>,
+        kernel_specification: KernelSpecification,
+        working_directory: Option<PathBuf>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut session = Self {
+            fs,
+            editor: WeakEntity::new_invalid(),
+            working_directory,
+            kernel: Kernel::StartingKernel(Task::ready(()).shared()),
+            blocks: HashMap::default(),
+            cells: HashMap::default(),
+            kernel_specification,
+            _buffer_subscription: Subscription::new(|| {}),
+        };
+
+        session.start_kernel(window, cx);
+        session
+    }
+
     fn start_kernel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let kernel_language = self.kernel_specification.language();
-        let entity_id = self.editor.entity_id();
-        let working_directory = self
-            .editor
-            .upgrade()
+        let editor = self.editor.upgrade();
+        let entity_id = match &editor {
+            Some(editor) => editor.entity_id(),
+            None => cx.entity_id(),
+        };
+        let working_directory = editor
             .and_then(|editor| editor.read(cx).working_directory(cx))
+            .or_else(|| self.working_directory.clone())
             .unwrap_or_else(temp_dir);
 
         telemetry::event!(
@@ -294,8 +327,14 @@ impl Session {
     pub fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
         self.kernel(Kernel::ErroredLaunch(error_message.clone()), cx);
 
-        self.blocks.values().for_each(|block| {
-            block.execution_view.update(cx, |execution_view, cx| {
+        let execution_views = self
+            .blocks
+            .values()
+            .map(|block| block.execution_view.clone())
+            .chain(self.cell_execution_views(cx))
+            .collect::<Vec<_>>();
+        execution_views.into_iter().for_each(|execution_view| {
+            execution_view.update(cx, |execution_view, cx| {
                 match execution_view.status {
                     ExecutionStatus::Finished => {
                         // Do nothing when the output was good
@@ -361,6 +400,75 @@ impl Session {
             .ok();
 
         self.blocks.clear();
+        self.cells.clear();
+    }
+
+    fn cell_execution_views(&self, cx: &App) -> Vec<Entity<ExecutionView>> {
+        self.cells
+            .values()
+            .filter_map(|cell| cell.upgrade())
+            .filter_map(|cell| cell.read(cx).execution_view().cloned())
+            .collect()
+    }
+
+    fn execution_status(&self) -> ExecutionStatus {
+        match &self.kernel {
+            Kernel::Restarting => ExecutionStatus::Restarting,
+            Kernel::RunningKernel(_) => ExecutionStatus::Queued,
+            Kernel::StartingKernel(_) => ExecutionStatus::ConnectingToKernel,
+            Kernel::ErroredLaunch(error) => ExecutionStatus::KernelErrored(error.clone()),
+            Kernel::ShuttingDown => ExecutionStatus::ShuttingDown,
+            Kernel::Shutdown => ExecutionStatus::Shutdown,
+        }
+    }
+
+    /// Sends a message to the kernel, or queues it to be sent once the kernel has started.
+    fn send_or_queue(&mut self, message: JupyterMessage, cx: &mut Context<Self>) {
+        match &self.kernel {
+            Kernel::RunningKernel(_) => {
+                self.send(message, cx).ok();
+            }
+            Kernel::StartingKernel(task) => {
+                // Queue up the execution as a task to run after the kernel starts
+                let task = task.clone();
+
+                cx.spawn(async move |this, cx| {
+                    task.await;
+                    this.update(cx, |session, cx| {
+                        session.send(message, cx).ok();
+                    })
+                    .ok();
+                })
+                .detach();
+            }
+            _ => {}
+        }
+    }
+
+    /// Executes the code of a notebook cell, sending the outputs to the cell.
+    pub fn execute_cell(
+        &mut self,
+        code: String,
+        cell: Entity<CodeCell>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if code.is_empty() {
+            return;
+        }
+
+        let message: JupyterMessage = ExecuteRequest {
+            code,
+            ..ExecuteRequest::default()
+        }
+        .into();
+
+        let status = self.execution_status();
+        cell.update(cx, |cell, cx| cell.start_execution(status, window, cx));
+        self.cells
+            .insert(message.header.msg_id.clone(), cell.downgrade());
+
+        self.send_or_queue(message, cx);
     }
 
     pub fn execute(
@@ -406,14 +514,7 @@ impl Session {
             })
             .ok();
 
-        let status = match &self.kernel {
-            Kernel::Restarting => ExecutionStatus::Restarting,
-            Kernel::RunningKernel(_) => ExecutionStatus::Queued,
-            Kernel::StartingKernel(_) => ExecutionStatus::ConnectingToKernel,
-            Kernel::ErroredLaunch(error) => ExecutionStatus::KernelErrored(error.clone()),
-            Kernel::ShuttingDown => ExecutionStatus::ShuttingDown,
-            Kernel::Shutdown => ExecutionStatus::Shutdown,
-        };
+        let status = self.execution_status();
 
         let parent_message_id = message.header.msg_id.clone();
         let session_view = cx.entity().downgrade();
@@ -453,25 +554,7 @@ impl Session {
         self.blocks
             .insert(message.header.msg_id.clone(), editor_block);
 
-        match &self.kernel {
-            Kernel::RunningKernel(_) => {
-                self.send(message, cx).ok();
-            }
-            Kernel::StartingKernel(task) => {
-                // Queue up the execution as a task to run after the kernel starts
-                let task = task.clone();
-
-                cx.spawn(async move |this, cx| {
-                    task.await;
-                    this.update(cx, |session, cx| {
-                        session.send(message, cx).ok();
-                    })
-                    .ok();
-                })
-                .detach();
-            }
-            _ => {}
-        }
+        self.send_or_queue(message, cx);
 
         if move_down {
             editor.update(cx, move |editor, cx| {
@@ -517,8 +600,14 @@ impl Session {
                     return;
                 };
 
-                self.blocks.iter_mut().for_each(|(_, block)| {
-                    block.execution_view.update(cx, |execution_view, cx| {
+                let execution_views = self
+                    .blocks
+                    .values()
+                    .map(|block| block.execution_view.clone())
+                    .chain(self.cell_execution_views(cx))
+                    .collect::<Vec<_>>();
+                execution_views.into_iter().for_each(|execution_view| {
+                    execution_view.update(cx, |execution_view, cx| {
                         execution_view.update_display_data(&update.data, &display_id, window, cx);
                     });
                 });
@@ -529,6 +618,14 @@ impl Session {
 
         if let Some(block) = self.blocks.get_mut(parent_message_id) {
             block.handle_message(message, window, cx);
+        } else if let Some(cell) = self
+            .cells
+            .get(parent_message_id)
+            .and_then(|cell| cell.upgrade())
+        {
+            cell.update(cx, |cell, cx| {
+                cell.handle_message(&message.content, window, cx);
+            });
         }
     }
 
@@ -668,6 +765,26 @@ impl Render for Session {
             Kernel::Restarting => (Some("Restarting".into()), None),
         };
 
+        let file_name = self.editor.upgrade().and_then(|editor| {
+            let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+            let file = buffer.read(cx).file()?;
+            Some(file.file_name(cx).to_string())
+        });
+
+        let kernel_selector =
+            crate::worktree_id_for_editor(self.editor.clone(), cx).map(|worktree_id| {
+                let editor = self.editor.clone();
+                KernelSelector::new(
+                    Box::new(move |kernel_specification, window, cx| {
+                        crate::assign_kernelspec(kernel_specification, editor.clone(), window, cx)
+                            .log_err();
+                    }),
+                    worktree_id,
+                    Button::new("change-kernel", "Change Kernel").style(ButtonStyle::Subtle),
+                    Tooltip::text("Pick another kernel or environment for this file"),
+                )
+            });
+
         KernelListItem::new(self.kernel_specification.clone())
             .status_color(match &self.kernel {
                 Kernel::RunningKernel(kernel) => match kernel.execution_state() {
@@ -682,6 +799,16 @@ impl Render for Session {
             })
             .child(Label::new(self.kernel_specification.name()))
             .children(status_text.map(|status_text| Label::new(format!("({status_text})"))))
+            .children(file_name.map(|file_name| Label::new(file_name).color(Color::Muted)))
+            .buttons(kernel_selector)
+            .button(
+                Button::new("restart", "Restart")
+                    .style(ButtonStyle::Subtle)
+                    .disabled(self.kernel.is_shutting_down())
+                    .on_click(cx.listener(move |session, _, window, cx| {
+                        session.restart(window, cx);
+                    })),
+            )
             .button(
                 Button::new("shutdown", "Shutdown")
                     .style(ButtonStyle::Subtle)
@@ -12,6 +12,9 @@ pub struct ExtensionApiManifest {
     pub description: Option<String>,
     pub authors: Vec<String>,
     pub repository: String,
+    /// The URL of the extension's changelog, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
     pub schema_version: Option<i32>,
     pub wasm_api_version: Option<String>,
     #[serde(default)]
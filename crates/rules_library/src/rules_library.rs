@@ -3,9 +3,9 @@ use collections::{HashMap, HashSet};
 use editor::{CompletionProvider, SelectionEffects};
 use editor::{CurrentLineHighlight, Editor, EditorElement, EditorEvent, EditorStyle, actions::Tab};
 use gpui::{
-    Action, App, Bounds, Entity, EventEmitter, Focusable, PromptLevel, Subscription, Task,
-    TextStyle, TitlebarOptions, WindowBounds, WindowHandle, WindowOptions, actions, point, size,
-    transparent_black,
+    Action, App, Bounds, Entity, EventEmitter, Focusable, PathPromptOptions, PromptLevel,
+    Subscription, Task, TextStyle, TitlebarOptions, WindowBounds, WindowHandle, WindowOptions,
+    actions, point, size, transparent_black,
 };
 use language::{Buffer, LanguageRegistry, language_settings::SoftWrap};
 use language_model::{
@@ -44,7 +44,11 @@ actions!(
         /// Duplicates the selected rule.
         DuplicateRule,
         /// Toggles whether the selected rule is a default rule.
-        ToggleDefaultRule
+        ToggleDefaultRule,
+        /// Exports all user rules to a JSON file.
+        ExportRules,
+        /// Imports rules from a JSON file.
+        ImportRules
     ]
 );
 
@@ -902,6 +906,48 @@ impl RulesLibrary {
         }
     }
 
+    pub fn export_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let path = cx.prompt_for_new_path(paths::home_dir(), Some("rules.json"));
+        let json = self.store.read(cx).export_json(cx);
+        cx.spawn_in(window, async move |_, cx| {
+            let Some(path) = path.await?? else {
+                return Ok(());
+            };
+            let json = json.await?;
+            cx.background_spawn(async move { std::fs::write(path, json) })
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn import_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Import".into()),
+        });
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = paths.await??.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let json = cx
+                .background_spawn(async move { std::fs::read_to_string(path) })
+                .await?;
+            let import = this.update(cx, |this, cx| {
+                this.store
+                    .update(cx, |store, cx| store.import_json(&json, cx))
+            })?;
+            import.await?;
+            this.update_in(cx, |this, window, cx| {
+                this.picker
+                    .update(cx, |picker, cx| picker.refresh(window, cx));
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn focus_active_rule(&mut self, _: &Tab, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(active_rule) = self.active_rule_id {
             self.rule_editors[&active_rule]
@@ -1369,6 +1415,12 @@ impl Render for RulesLibrary {
                 .on_action(cx.listener(|this, &ToggleDefaultRule, window, cx| {
                     this.toggle_default_for_active_rule(window, cx)
                 }))
+                .on_action(
+                    cx.listener(|this, &ExportRules, window, cx| this.export_rules(window, cx)),
+                )
+                .on_action(
+                    cx.listener(|this, &ImportRules, window, cx| this.import_rules(window, cx)),
+                )
                 .size_full()
                 .overflow_hidden()
                 .font(ui_font)
@@ -0,0 +1,247 @@
+mod language_picker;
+mod scratches_picker;
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+use chrono::{Local, NaiveDateTime};
+use editor::Editor;
+use fs::Fs;
+use gpui::{App, AppContext as _, Context, Task, Window, actions};
+use language::{BufferEvent, LanguageName};
+use settings::Settings;
+use util::ResultExt as _;
+use workspace::{OpenOptions, OpenVisible, Workspace, notifications::DetachAndPromptErr as _};
+
+use crate::{language_picker::LanguagePicker, scratches_picker::ScratchesPicker};
+
+actions!(
+    scratch,
+    [
+        /// Creates a new scratch buffer, asking for its language unless a default language is set.
+        NewScratch,
+        /// Opens a picker listing the saved scratch buffers.
+        OpenScratches
+    ]
+);
+
+/// How long to wait after a scratch buffer is edited before saving it.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// The maximum number of characters shown as a preview of a scratch buffer.
+const PREVIEW_LENGTH: usize = 80;
+
+/// Settings for scratch buffers.
+#[derive(Clone, Debug)]
+pub struct ScratchSettings {
+    /// Whether to save scratch buffers as they're edited.
+    ///
+    /// Default: true
+    pub persist: bool,
+    /// The language of new scratch buffers, or `None` to pick one when creating each.
+    ///
+    /// Default: null
+    pub default_language: Option<String>,
+}
+
+impl Settings for ScratchSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let scratch = content.scratch.clone().unwrap();
+        Self {
+            persist: scratch.persist.unwrap(),
+            default_language: scratch.default_language,
+        }
+    }
+}
+
+pub fn init(cx: &mut App) {
+    ScratchSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &NewScratch, window, cx| {
+            new_scratch(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &OpenScratches, window, cx| {
+            ScratchesPicker::toggle(workspace, window, cx);
+        });
+    })
+    .detach();
+
+    cx.observe_new(|editor: &mut Editor, _, cx| save_scratch_on_edit(editor, cx))
+        .detach();
+}
+
+fn new_scratch(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    match ScratchSettings::get_global(cx).default_language.clone() {
+        Some(language) => create_scratch(workspace, LanguageName::new(&language), window, cx),
+        None => LanguagePicker::toggle(workspace, window, cx),
+    }
+}
+
+/// Opens a new scratch buffer in the given language. When scratch buffers are persisted, it's
+/// backed by a file in the scratches directory, and otherwise it's an untitled buffer.
+pub(crate) fn create_scratch(
+    workspace: &mut Workspace,
+    language_name: LanguageName,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let languages = workspace.app_state().languages.clone();
+    let fs = workspace.app_state().fs.clone();
+    let project = workspace.project().clone();
+    let persist = ScratchSettings::get_global(cx).persist;
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let language = languages.language_for_name(language_name.as_ref()).await?;
+        if persist {
+            let extension = language
+                .config()
+                .matcher
+                .path_suffixes
+                .first()
+                .map_or("txt", String::as_str);
+            let path = new_scratch_path(fs.as_ref(), extension).await?;
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_abs_path(
+                        path,
+                        OpenOptions {
+                            visible: Some(OpenVisible::None),
+                            ..Default::default()
+                        },
+                        window,
+                        cx,
+                    )
+                })?
+                .await?;
+        } else {
+            let buffer = project
+                .update(cx, |project, cx| project.create_buffer(true, cx))?
+                .await?;
+            project.update(cx, |project, cx| {
+                project.set_language_for_buffer(&buffer, language, cx);
+            })?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let editor =
+                    cx.new(|cx| Editor::for_buffer(buffer, Some(project.clone()), window, cx));
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to create scratch buffer", window, cx, |_, _, _| {
+        None
+    });
+}
+
+/// Creates an empty file for a new scratch buffer, and returns its path.
+async fn new_scratch_path(fs: &dyn Fs, extension: &str) -> Result<PathBuf> {
+    let dir = paths::scratches_dir();
+    fs.create_dir(dir).await?;
+    let created = Local::now().naive_local();
+    let mut path = dir.join(scratch_file_name(created, None, extension));
+    let mut index = 1;
+    while fs.metadata(&path).await?.is_some() {
+        index += 1;
+        path = dir.join(scratch_file_name(created, Some(index), extension));
+    }
+    fs.create_file(&path, Default::default()).await?;
+    Ok(path)
+}
+
+fn scratch_file_name(created: NaiveDateTime, index: Option<usize>, extension: &str) -> String {
+    let timestamp = created.format("%Y-%m-%d-%H%M%S");
+    match index {
+        Some(index) => format!("scratch-{timestamp}-{index}.{extension}"),
+        None => format!("scratch-{timestamp}.{extension}"),
+    }
+}
+
+fn is_scratch_path(path: &Path) -> bool {
+    path.parent() == Some(paths::scratches_dir().as_path())
+}
+
+/// Returns the first line of a scratch buffer that isn't blank, so that it can be recognized in
+/// the scratches picker.
+fn preview(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.chars().take(PREVIEW_LENGTH).collect())
+        .unwrap_or_default()
+}
+
+/// Saves the editor's buffer shortly after each edit, if it's a scratch buffer. This is set up
+/// for every editor so that scratch buffers restored with the workspace are saved too.
+fn save_scratch_on_edit(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let Some(project) = editor.project().map(|project| project.downgrade()) else {
+        return;
+    };
+    let is_scratch = buffer
+        .read(cx)
+        .file()
+        .and_then(|file| file.as_local())
+        .is_some_and(|file| is_scratch_path(&file.abs_path(cx)));
+    if !is_scratch {
+        return;
+    }
+
+    // Replacing the pending save cancels it, so that edits in quick succession are saved once.
+    let mut pending_save: Option<Task<()>> = None;
+    cx.subscribe(&buffer, move |_, buffer, event, cx| {
+        if !matches!(event, BufferEvent::Edited) || !ScratchSettings::get_global(cx).persist {
+            return;
+        }
+        let project = project.clone();
+        pending_save.replace(cx.spawn(async move |_, cx| {
+            cx.background_executor().timer(SAVE_DELAY).await;
+            let Ok(Some(save)) = project.update(cx, |project, cx| {
+                buffer
+                    .read(cx)
+                    .is_dirty()
+                    .then(|| project.save_buffer(buffer, cx))
+            }) else {
+                return;
+            };
+            save.await.log_err();
+        }));
+    })
+    .detach();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_scratch_file_name() {
+        let created = NaiveDate::from_ymd_opt(2025, 3, 7)
+            .unwrap()
+            .and_hms_opt(9, 5, 30)
+            .unwrap();
+        assert_eq!(
+            scratch_file_name(created, None, "rs"),
+            "scratch-2025-03-07-090530.rs"
+        );
+        assert_eq!(
+            scratch_file_name(created, Some(2), "md"),
+            "scratch-2025-03-07-090530-2.md"
+        );
+        assert!(is_scratch_path(&paths::scratches_dir().join("scratch.rs")));
+        assert!(!is_scratch_path(Path::new("/tmp/scratch.rs")));
+    }
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(preview(""), "");
+        assert_eq!(preview("\n   \n  let x = 1;\nlet y = 2;"), "let x = 1;");
+        assert_eq!(preview(&"a".repeat(100)).len(), PREVIEW_LENGTH);
+    }
+}
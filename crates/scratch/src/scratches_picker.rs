@@ -0,0 +1,302 @@
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use file_icons::FileIcons;
+use fs::Fs;
+use futures::StreamExt as _;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    ModalView, OpenOptions, OpenVisible, Workspace, notifications::DetachAndPromptErr as _,
+};
+
+use crate::preview;
+
+/// A scratch buffer saved in the scratches directory.
+struct Scratch {
+    path: PathBuf,
+    name: String,
+    preview: String,
+    modified: SystemTime,
+}
+
+/// Returns the saved scratch buffers that aren't empty, most recently edited first.
+async fn load_scratches(fs: Arc<dyn Fs>) -> Result<Vec<Scratch>> {
+    let dir = paths::scratches_dir();
+    if !fs.is_dir(dir).await {
+        return Ok(Vec::new());
+    }
+
+    let mut scratches = Vec::new();
+    let mut paths = fs.read_dir(dir).await?;
+    while let Some(path) = paths.next().await {
+        let path = path?;
+        let Some(metadata) = fs.metadata(&path).await? else {
+            continue;
+        };
+        if metadata.is_dir || metadata.len == 0 {
+            continue;
+        }
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let name = name.to_string_lossy().into_owned();
+        let text = fs.load(&path).await.unwrap_or_default();
+        scratches.push(Scratch {
+            preview: preview(&text),
+            name,
+            modified: metadata.mtime.timestamp_for_user(),
+            path,
+        });
+    }
+    scratches.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(scratches)
+}
+
+/// Lists the saved scratch buffers, so that they can be reopened.
+pub struct ScratchesPicker {
+    picker: Entity<Picker<ScratchesPickerDelegate>>,
+}
+
+impl ScratchesPicker {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let fs = workspace.app_state().fs.clone();
+        cx.spawn_in(window, async move |workspace, cx| {
+            let scratches = load_scratches(fs).await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let workspace_handle = cx.entity().downgrade();
+                workspace.toggle_modal(window, cx, move |window, cx| {
+                    let delegate = ScratchesPickerDelegate::new(
+                        cx.entity().downgrade(),
+                        workspace_handle,
+                        scratches,
+                    );
+                    let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+                    Self { picker }
+                });
+            })
+        })
+        .detach_and_prompt_err("Failed to list scratch buffers", window, cx, |_, _, _| None);
+    }
+}
+
+impl Render for ScratchesPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("ScratchesPicker")
+            .w(rems(40.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for ScratchesPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ScratchesPicker {}
+impl ModalView for ScratchesPicker {}
+
+pub struct ScratchesPickerDelegate {
+    scratches_picker: WeakEntity<ScratchesPicker>,
+    workspace: WeakEntity<Workspace>,
+    scratches: Vec<Scratch>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl ScratchesPickerDelegate {
+    fn new(
+        scratches_picker: WeakEntity<ScratchesPicker>,
+        workspace: WeakEntity<Workspace>,
+        scratches: Vec<Scratch>,
+    ) -> Self {
+        Self {
+            scratches_picker,
+            workspace,
+            scratches,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+impl PickerDelegate for ScratchesPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search scratch buffers…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No scratch buffers".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let path = self.scratches[mat.candidate_id].path.clone();
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace
+                        .open_abs_path(
+                            path,
+                            OpenOptions {
+                                visible: Some(OpenVisible::None),
+                                ..Default::default()
+                            },
+                            window,
+                            cx,
+                        )
+                        .detach_and_prompt_err(
+                            "Failed to open scratch buffer",
+                            window,
+                            cx,
+                            |_, _, _| None,
+                        );
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.scratches_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        // Match both the name and the preview, so that scratches can be found by their contents.
+        let candidates = self
+            .scratches
+            .iter()
+            .enumerate()
+            .map(|(id, scratch)| {
+                StringMatchCandidate::new(id, &format!("{} {}", scratch.name, scratch.preview))
+            })
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let scratch = &self.scratches[mat.candidate_id];
+        let preview_start = scratch.name.len() + 1;
+        let name_positions = mat
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < scratch.name.len())
+            .collect();
+        let preview_positions = mat
+            .positions
+            .iter()
+            .filter_map(|position| position.checked_sub(preview_start))
+            .collect();
+        let modified = DateTime::<Local>::from(scratch.modified)
+            .format("%b %-d, %H:%M")
+            .to_string();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot::<Icon>(
+                    FileIcons::get_icon(&scratch.path, cx)
+                        .map(|icon| Icon::from_path(icon).color(Color::Muted)),
+                )
+                .child(
+                    v_flex()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(HighlightedLabel::new(scratch.name.clone(), name_positions))
+                                .child(
+                                    Label::new(modified)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                        )
+                        .when(!scratch.preview.is_empty(), |this| {
+                            this.child(
+                                HighlightedLabel::new(scratch.preview.clone(), preview_positions)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            )
+                        }),
+                ),
+        )
+    }
+}
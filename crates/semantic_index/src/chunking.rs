@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+/// Chunks are cut at a blank line once they reach this many lines...
+const MIN_CHUNK_LINES: usize = 20;
+/// ...and unconditionally once they reach this many lines or bytes.
+const MAX_CHUNK_LINES: usize = 60;
+const MAX_CHUNK_BYTES: usize = 4 * 1024;
+
+/// A contiguous range of lines in a file, small enough to be embedded on its own.
+#[derive(Debug, PartialEq)]
+pub struct Chunk {
+    /// The zero-based lines covered by the chunk.
+    pub rows: Range<u32>,
+    pub text: String,
+}
+
+/// Splits `text` into chunks of whole lines, preferring to break at blank lines
+/// so that items like functions tend to stay together.
+pub fn chunk_text(text: &str) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut start_row = 0;
+    let mut current = String::new();
+    let mut current_lines = 0;
+
+    for (row, line) in text.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        if is_blank && current_lines >= MIN_CHUNK_LINES {
+            push_chunk(&mut chunks, start_row..row, &mut current);
+            current_lines = 0;
+            start_row = row + 1;
+            continue;
+        }
+
+        if current_lines == 0 && is_blank {
+            start_row = row + 1;
+            continue;
+        }
+
+        current.push_str(line);
+        current_lines += 1;
+        if current_lines >= MAX_CHUNK_LINES || current.len() >= MAX_CHUNK_BYTES {
+            push_chunk(&mut chunks, start_row..row + 1, &mut current);
+            current_lines = 0;
+            start_row = row + 1;
+        }
+    }
+
+    if current_lines > 0 {
+        let end_row = start_row + current_lines;
+        push_chunk(&mut chunks, start_row..end_row, &mut current);
+    }
+
+    chunks
+}
+
+fn push_chunk(chunks: &mut Vec<Chunk>, rows: Range<usize>, text: &mut String) {
+    let text = std::mem::take(text);
+    if text.trim().is_empty() {
+        return;
+    }
+    chunks.push(Chunk {
+        rows: rows.start as u32..rows.end as u32,
+        text,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text() {
+        assert_eq!(chunk_text(""), Vec::new());
+        assert_eq!(
+            chunk_text("\n\nfn main() {}\n"),
+            vec![Chunk {
+                rows: 2..3,
+                text: "fn main() {}\n".into()
+            }]
+        );
+
+        // Long runs of lines are split at blank lines once they're long enough.
+        let first = "a\n".repeat(MIN_CHUNK_LINES);
+        let second = "b\n".repeat(3);
+        let text = format!("{first}\n{second}");
+        let chunks = chunk_text(&text);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].rows, 0..MIN_CHUNK_LINES as u32);
+        assert_eq!(chunks[0].text, first);
+        assert_eq!(
+            chunks[1].rows,
+            MIN_CHUNK_LINES as u32 + 1..MIN_CHUNK_LINES as u32 + 4
+        );
+        assert_eq!(chunks[1].text, second);
+
+        // Without blank lines, chunks are cut at the maximum size.
+        let text = "c\n".repeat(MAX_CHUNK_LINES + 1);
+        let chunks = chunk_text(&text);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].rows, 0..MAX_CHUNK_LINES as u32);
+        assert_eq!(
+            chunks[1].rows,
+            MAX_CHUNK_LINES as u32..MAX_CHUNK_LINES as u32 + 1
+        );
+    }
+}
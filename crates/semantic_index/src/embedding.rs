@@ -0,0 +1,124 @@
+use anyhow::{Context as _, Result};
+use futures::AsyncReadExt as _;
+use http_client::{AsyncBody, HttpClient, Method, Request};
+use serde::{Deserialize, Serialize};
+
+use crate::SemanticIndexSettings;
+
+/// The number of texts sent in a single embedding request.
+pub const EMBEDDING_BATCH_SIZE: usize = 32;
+
+pub type Embedding = Vec<f32>;
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    #[serde(default)]
+    index: Option<usize>,
+    embedding: Embedding,
+}
+
+/// Requests embeddings for `texts` from the OpenAI-compatible API configured in
+/// `settings`. The returned embeddings are normalized, so that their dot
+/// product is their cosine similarity.
+pub async fn embed(
+    http_client: &dyn HttpClient,
+    settings: &SemanticIndexSettings,
+    texts: &[String],
+) -> Result<Vec<Embedding>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let body = EmbeddingRequest {
+        model: &settings.model,
+        input: texts,
+    };
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(format!(
+            "{}/embeddings",
+            settings.api_url.trim_end_matches('/')
+        ))
+        .header("Content-Type", "application/json");
+    if let Some(api_key) = settings
+        .api_key_env_var
+        .as_ref()
+        .and_then(|name| std::env::var(name).ok())
+        .filter(|key| !key.is_empty())
+    {
+        request = request.header("Authorization", format!("Bearer {api_key}"));
+    }
+    let request = request.body(AsyncBody::from(serde_json::to_string(&body)?))?;
+
+    let mut response = http_client.send(request).await?;
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "embedding request to {} failed with status {}: {}",
+        settings.api_url,
+        response.status(),
+        body
+    );
+
+    let mut response: EmbeddingResponse =
+        serde_json::from_str(&body).context("failed to parse embedding response")?;
+    anyhow::ensure!(
+        response.data.len() == texts.len(),
+        "expected {} embeddings, got {}",
+        texts.len(),
+        response.data.len()
+    );
+    response.data.sort_by_key(|data| data.index);
+
+    Ok(response
+        .data
+        .into_iter()
+        .map(|data| normalize(data.embedding))
+        .collect())
+}
+
+fn normalize(mut embedding: Embedding) -> Embedding {
+    let norm = embedding
+        .iter()
+        .map(|value| value * value)
+        .sum::<f32>()
+        .sqrt();
+    if norm > 0. {
+        for value in &mut embedding {
+            *value /= norm;
+        }
+    }
+    embedding
+}
+
+/// The cosine similarity of two normalized embeddings.
+pub fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        let a = normalize(vec![3., 4.]);
+        let b = normalize(vec![6., 8.]);
+        let c = normalize(vec![-4., 3.]);
+        assert!((similarity(&a, &b) - 1.).abs() < 1e-6);
+        assert!(similarity(&a, &c).abs() < 1e-6);
+        assert_eq!(normalize(vec![0., 0.]), vec![0., 0.]);
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    mem,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use fs::Fs;
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, Task, WeakEntity};
+use http_client::HttpClient;
+use project::{Project, WorktreeId};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use sha2::{Digest as _, Sha256};
+use util::ResultExt as _;
+use worktree::Snapshot;
+
+use crate::{
+    SemanticIndexSettings,
+    chunking::chunk_text,
+    embedding::{EMBEDDING_BATCH_SIZE, Embedding, embed, similarity},
+};
+
+const REINDEX_DEBOUNCE: Duration = Duration::from_secs(2);
+/// Files larger than this are assumed to be generated or data, and aren't indexed.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// An embedding index over the files in a project's local, visible worktrees.
+///
+/// Each worktree's index is persisted in the embeddings directory, and is kept
+/// up to date as files change. Only chunks whose contents changed since they
+/// were last indexed are sent to the embedding API.
+pub struct ProjectIndex {
+    project: WeakEntity<Project>,
+    fs: Arc<dyn Fs>,
+    http_client: Arc<dyn HttpClient>,
+    worktree_indices: HashMap<WorktreeId, Arc<WorktreeIndex>>,
+    status: IndexStatus,
+    last_error: Option<String>,
+    /// Whether an indexing pass is running. Changes made while it runs are picked up by another
+    /// pass once it finishes, rather than by cancelling it and losing the files it has embedded.
+    reindex_running: bool,
+    reindex_pending: bool,
+    _reindex_task: Task<()>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexStatus {
+    Idle,
+    Indexing { remaining_files: usize },
+}
+
+/// A snippet of a project file that is relevant to a query.
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub abs_path: PathBuf,
+    /// The zero-based lines of the snippet.
+    pub rows: Range<u32>,
+    pub score: f32,
+    pub text: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct WorktreeIndex {
+    #[serde(skip)]
+    abs_path: PathBuf,
+    /// The model the embeddings were computed with.
+    model: String,
+    /// Indexed files, keyed by their Unix-style path relative to the worktree root.
+    files: HashMap<String, IndexedFile>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    mtime: Option<(u64, u32)>,
+    chunks: Vec<IndexedChunk>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct IndexedChunk {
+    rows: Range<u32>,
+    digest: String,
+    embedding: Embedding,
+}
+
+struct FileToIndex {
+    path: String,
+    abs_path: PathBuf,
+    mtime: Option<(u64, u32)>,
+}
+
+impl ProjectIndex {
+    pub fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        cx.subscribe(&project, |this, _, event, cx| match event {
+            project::Event::WorktreeAdded(_)
+            | project::Event::WorktreeRemoved(_)
+            | project::Event::WorktreeUpdatedEntries(_, _) => this.schedule_reindex(true, cx),
+            _ => {}
+        })
+        .detach();
+
+        let mut this = Self {
+            project: project.downgrade(),
+            fs: project.read(cx).fs().clone(),
+            http_client: cx.http_client(),
+            worktree_indices: HashMap::default(),
+            status: IndexStatus::Idle,
+            last_error: None,
+            reindex_running: false,
+            reindex_pending: false,
+            _reindex_task: Task::ready(()),
+        };
+        this.schedule_reindex(false, cx);
+        this
+    }
+
+    pub fn status(&self) -> IndexStatus {
+        self.status
+    }
+
+    /// The error from the most recent indexing pass, if it failed.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn indexed_file_count(&self) -> usize {
+        self.worktree_indices
+            .values()
+            .map(|index| index.files.len())
+            .sum()
+    }
+
+    fn schedule_reindex(&mut self, debounce: bool, cx: &mut Context<Self>) {
+        if self.reindex_running {
+            self.reindex_pending = true;
+            return;
+        }
+
+        self._reindex_task = cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(REINDEX_DEBOUNCE).await;
+            }
+            this.update(cx, |this, _| this.reindex_running = true).ok();
+            let result = Self::reindex(this.clone(), cx).await;
+            this.update(cx, |this, cx| {
+                this.reindex_running = false;
+                this.status = IndexStatus::Idle;
+                this.last_error = result.err().map(|error| format!("{error:#}"));
+                if let Some(error) = &this.last_error {
+                    log::error!("failed to update semantic index: {error}");
+                }
+                if mem::take(&mut this.reindex_pending) {
+                    this.schedule_reindex(true, cx);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    async fn reindex(this: WeakEntity<Self>, cx: &mut AsyncApp) -> Result<()> {
+        let (snapshots, fs, http_client, settings) = this.update(cx, |this, cx| {
+            let snapshots = this
+                .project
+                .upgrade()
+                .map(|project| {
+                    project
+                        .read(cx)
+                        .visible_worktrees(cx)
+                        .filter(|worktree| worktree.read(cx).is_local())
+                        .map(|worktree| worktree.read(cx).snapshot())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            this.worktree_indices
+                .retain(|id, _| snapshots.iter().any(|snapshot| snapshot.id() == *id));
+            (
+                snapshots,
+                this.fs.clone(),
+                this.http_client.clone(),
+                SemanticIndexSettings::get_global(cx).clone(),
+            )
+        })?;
+        if !settings.enabled {
+            return Ok(());
+        }
+
+        for snapshot in snapshots {
+            let worktree_id = snapshot.id();
+            let previous = this.read_with(cx, |this, _| {
+                this.worktree_indices.get(&worktree_id).cloned()
+            })?;
+            let previous = match previous {
+                Some(previous) if previous.model == settings.model => previous,
+                _ => Arc::new(
+                    load_worktree_index(fs.as_ref(), snapshot.abs_path())
+                        .await
+                        .filter(|index| index.model == settings.model)
+                        .unwrap_or_default(),
+                ),
+            };
+
+            let files = cx
+                .background_spawn({
+                    let snapshot = snapshot.clone();
+                    async move { files_to_index(&snapshot) }
+                })
+                .await;
+            let changed_files = files
+                .iter()
+                .filter(|file| {
+                    previous
+                        .files
+                        .get(&file.path)
+                        .is_none_or(|indexed| indexed.mtime != file.mtime)
+                })
+                .collect::<Vec<_>>();
+
+            let mut index = WorktreeIndex {
+                abs_path: snapshot.abs_path().to_path_buf(),
+                model: settings.model.clone(),
+                files: files
+                    .iter()
+                    .filter_map(|file| {
+                        let indexed = previous.files.get(&file.path)?;
+                        (indexed.mtime == file.mtime).then(|| (file.path.clone(), indexed.clone()))
+                    })
+                    .collect(),
+            };
+
+            for (ix, file) in changed_files.iter().enumerate() {
+                this.update(cx, |this, cx| {
+                    this.status = IndexStatus::Indexing {
+                        remaining_files: changed_files.len() - ix,
+                    };
+                    cx.notify();
+                })?;
+
+                // Files that can't be read as text are skipped, and retried when they change.
+                let Some(text) = fs.load(&file.abs_path).await.ok() else {
+                    continue;
+                };
+                let previous_chunks = previous
+                    .files
+                    .get(&file.path)
+                    .map(|indexed| indexed.chunks.as_slice())
+                    .unwrap_or_default();
+                // A file that fails to embed, e.g. because the embedding server rejected one of
+                // its chunks, is skipped so that the rest of the worktree still gets indexed. As
+                // it's missing from the saved index, it's retried on the next pass.
+                let chunks =
+                    match embed_file(text, previous_chunks, http_client.as_ref(), &settings, cx)
+                        .await
+                    {
+                        Ok(chunks) => chunks,
+                        Err(error) => {
+                            log::error!("failed to embed {:?}: {error:#}", file.abs_path);
+                            continue;
+                        }
+                    };
+                index.files.insert(
+                    file.path.clone(),
+                    IndexedFile {
+                        mtime: file.mtime,
+                        chunks,
+                    },
+                );
+            }
+
+            let index = Arc::new(index);
+            if !changed_files.is_empty() || index.files.len() != previous.files.len() {
+                save_worktree_index(fs.as_ref(), &index).await.log_err();
+            }
+            this.update(cx, |this, cx| {
+                this.worktree_indices.insert(worktree_id, index);
+                cx.notify();
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the snippets that are most similar to `query`, most relevant first.
+    pub fn search(&self, query: String, cx: &App) -> Task<Result<Vec<SearchResult>>> {
+        let settings = SemanticIndexSettings::get_global(cx).clone();
+        let indices = self.worktree_indices.values().cloned().collect::<Vec<_>>();
+        let fs = self.fs.clone();
+        let http_client = self.http_client.clone();
+
+        cx.background_spawn(async move {
+            let query_embedding = embed(http_client.as_ref(), &settings, &[query])
+                .await?
+                .pop()
+                .context("no embedding returned for query")?;
+
+            let mut matches = Vec::new();
+            for index in &indices {
+                for (path, file) in &index.files {
+                    for chunk in &file.chunks {
+                        let score = similarity(&query_embedding, &chunk.embedding);
+                        if score >= settings.min_similarity {
+                            matches.push((index.abs_path.join(path), chunk.rows.clone(), score));
+                        }
+                    }
+                }
+            }
+            matches.sort_by(|a, b| b.2.total_cmp(&a.2));
+            matches.truncate(settings.max_results);
+
+            let mut results = Vec::with_capacity(matches.len());
+            for (abs_path, rows, score) in matches {
+                let Some(text) = fs.load(&abs_path).await.log_err() else {
+                    continue;
+                };
+                let text = text
+                    .split_inclusive('\n')
+                    .skip(rows.start as usize)
+                    .take(rows.len())
+                    .collect();
+                results.push(SearchResult {
+                    abs_path,
+                    rows,
+                    score,
+                    text,
+                });
+            }
+            Ok(results)
+        })
+    }
+}
+
+fn files_to_index(snapshot: &Snapshot) -> Vec<FileToIndex> {
+    snapshot
+        .files(false, 0)
+        .filter(|entry| !entry.is_external && !entry.is_private && entry.size <= MAX_FILE_SIZE)
+        .map(|entry| FileToIndex {
+            path: entry.path.as_unix_str().to_string(),
+            abs_path: snapshot.absolutize(&entry.path),
+            mtime: entry
+                .mtime
+                .and_then(|mtime| mtime.to_seconds_and_nanos_for_persistence()),
+        })
+        .collect()
+}
+
+/// Chunks and embeds `text`, reusing the embeddings of chunks whose contents
+/// haven't changed since the file was last indexed.
+async fn embed_file(
+    text: String,
+    previous_chunks: &[IndexedChunk],
+    http_client: &dyn HttpClient,
+    settings: &SemanticIndexSettings,
+    cx: &AsyncApp,
+) -> Result<Vec<IndexedChunk>> {
+    let chunks = cx
+        .background_spawn(async move {
+            chunk_text(&text)
+                .into_iter()
+                .map(|chunk| {
+                    let digest = format!("{:x}", Sha256::digest(chunk.text.as_bytes()));
+                    (chunk, digest)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await;
+
+    let mut embeddings = chunks
+        .iter()
+        .map(|(_, digest)| {
+            previous_chunks
+                .iter()
+                .find(|chunk| chunk.digest == *digest)
+                .map(|chunk| chunk.embedding.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let missing = embeddings
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, embedding)| embedding.is_none().then_some(ix))
+        .collect::<Vec<_>>();
+    for batch in missing.chunks(EMBEDDING_BATCH_SIZE) {
+        let texts = batch
+            .iter()
+            .map(|ix| chunks[*ix].0.text.clone())
+            .collect::<Vec<_>>();
+        for (ix, embedding) in batch
+            .iter()
+            .zip(embed(http_client, settings, &texts).await?)
+        {
+            embeddings[*ix] = Some(embedding);
+        }
+    }
+
+    Ok(chunks
+        .into_iter()
+        .zip(embeddings)
+        .filter_map(|((chunk, digest), embedding)| {
+            Some(IndexedChunk {
+                rows: chunk.rows,
+                digest,
+                embedding: embedding?,
+            })
+        })
+        .collect())
+}
+
+fn index_path(worktree_abs_path: &Path) -> PathBuf {
+    let digest = Sha256::digest(worktree_abs_path.to_string_lossy().as_bytes());
+    paths::embeddings_dir().join(format!("{digest:x}.json"))
+}
+
+async fn load_worktree_index(fs: &dyn Fs, worktree_abs_path: &Path) -> Option<WorktreeIndex> {
+    let path = index_path(worktree_abs_path);
+    if !fs.is_file(&path).await {
+        return None;
+    }
+    let content = fs.load(&path).await.log_err()?;
+    let mut index: WorktreeIndex = serde_json::from_str(&content).log_err()?;
+    index.abs_path = worktree_abs_path.to_path_buf();
+    Some(index)
+}
+
+async fn save_worktree_index(fs: &dyn Fs, index: &WorktreeIndex) -> Result<()> {
+    fs.create_dir(paths::embeddings_dir()).await?;
+    fs.atomic_write(index_path(&index.abs_path), serde_json::to_string(index)?)
+        .await
+}
@@ -0,0 +1,83 @@
+mod chunking;
+mod embedding;
+mod project_index;
+
+use collections::HashMap;
+use gpui::{App, AppContext as _, Context, Entity, EntityId, Global};
+use project::Project;
+use settings::Settings;
+
+pub use project_index::{IndexStatus, ProjectIndex, SearchResult};
+
+pub fn init(cx: &mut App) {
+    SemanticIndexSettings::register(cx);
+    let db = cx.new(|_| SemanticDb::default());
+    cx.set_global(GlobalSemanticDb(db));
+}
+
+#[derive(Clone, Debug)]
+pub struct SemanticIndexSettings {
+    pub enabled: bool,
+    pub api_url: String,
+    pub model: String,
+    pub api_key_env_var: Option<String>,
+    pub max_results: usize,
+    pub min_similarity: f32,
+}
+
+impl Settings for SemanticIndexSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let semantic_index = content.semantic_index.clone().unwrap();
+
+        Self {
+            enabled: semantic_index.enabled.unwrap(),
+            api_url: semantic_index.api_url.unwrap(),
+            model: semantic_index.model.unwrap(),
+            api_key_env_var: semantic_index.api_key_env_var,
+            max_results: semantic_index.max_results.unwrap(),
+            min_similarity: semantic_index.min_similarity.unwrap(),
+        }
+    }
+}
+
+struct GlobalSemanticDb(Entity<SemanticDb>);
+
+impl Global for GlobalSemanticDb {}
+
+/// Owns the semantic index of every open project.
+#[derive(Default)]
+pub struct SemanticDb {
+    project_indices: HashMap<EntityId, Entity<ProjectIndex>>,
+}
+
+impl SemanticDb {
+    /// Returns the index for `project`, creating it and starting to index the
+    /// project if needed. Returns `None` when the semantic index is disabled or
+    /// the project isn't local.
+    pub fn project_index(project: &Entity<Project>, cx: &mut App) -> Option<Entity<ProjectIndex>> {
+        if !SemanticIndexSettings::get_global(cx).enabled || !project.read(cx).is_local() {
+            return None;
+        }
+        let db = cx.try_global::<GlobalSemanticDb>()?.0.clone();
+        Some(db.update(cx, |db, cx| db.index_for_project(project, cx)))
+    }
+
+    fn index_for_project(
+        &mut self,
+        project: &Entity<Project>,
+        cx: &mut Context<Self>,
+    ) -> Entity<ProjectIndex> {
+        let project_id = project.entity_id();
+        if let Some(index) = self.project_indices.get(&project_id) {
+            return index.clone();
+        }
+
+        let index = cx.new(|cx| ProjectIndex::new(project.clone(), cx));
+        cx.observe_release(project, move |db, _, _| {
+            db.project_indices.remove(&project_id);
+        })
+        .detach();
+        self.project_indices.insert(project_id, index.clone());
+        index
+    }
+}
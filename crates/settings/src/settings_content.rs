@@ -67,6 +67,9 @@ pub struct SettingsContent {
     pub agent: Option<AgentSettingsContent>,
     pub agent_servers: Option<AllAgentServersSettings>,
 
+    /// Settings for reduced motion and high contrast.
+    pub accessibility: Option<AccessibilitySettingsContent>,
+
     /// Configuration of audio in Zed.
     pub audio: Option<AudioSettingsContent>,
 
@@ -120,6 +123,9 @@ pub struct SettingsContent {
 
     pub outline_panel: Option<OutlinePanelSettingsContent>,
 
+    /// Configuration for the TODO panel.
+    pub todo_panel: Option<TodoPanelSettingsContent>,
+
     pub project_panel: Option<ProjectPanelSettingsContent>,
 
     /// Configuration for the Message Editor
@@ -136,6 +142,15 @@ pub struct SettingsContent {
     /// The URL of the Zed server to connect to.
     pub server_url: Option<String>,
 
+    /// Configuration for the local history of files.
+    pub local_history: Option<LocalHistorySettingsContent>,
+
+    /// Configuration for the local semantic index used to retrieve project context for the agent.
+    pub semantic_index: Option<SemanticIndexSettingsContent>,
+
+    /// Configuration for scratch buffers.
+    pub scratch: Option<ScratchSettingsContent>,
+
     /// Configuration for session-related features
     pub session: Option<SessionSettingsContent>,
     /// Control what info is collected by Zed.
@@ -281,6 +296,48 @@ pub struct TitleBarSettingsContent {
     pub show_menus: Option<bool>,
 }
 
+/// Settings for reduced motion and high contrast.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct AccessibilitySettingsContent {
+    /// Whether to skip animations, showing their final state instead, and to stop the cursor
+    /// from blinking.
+    ///
+    /// Default: system
+    pub reduced_motion: Option<AccessibilityPreference>,
+    /// Whether to draw thicker cursors and focus rings, and to enforce a minimum contrast
+    /// between text and its background over the theme's colors.
+    ///
+    /// Default: system
+    pub high_contrast: Option<AccessibilityPreference>,
+}
+
+/// Whether an accessibility mode is on.
+#[derive(
+    Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessibilityPreference {
+    /// Follow the operating system's accessibility preferences, where they're available.
+    #[default]
+    System,
+    /// Always turn the mode on.
+    On,
+    /// Never turn the mode on.
+    Off,
+}
+
+impl AccessibilityPreference {
+    /// Returns whether the mode is on, given whether the operating system asks for it.
+    pub fn is_on(self, system: bool) -> bool {
+        match self {
+            Self::System => system,
+            Self::On => true,
+            Self::Off => false,
+        }
+    }
+}
+
 /// Configuration of audio in Zed.
 #[skip_serializing_none]
 #[derive(Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug)]
@@ -323,6 +380,48 @@ pub struct AudioSettingsContent {
     /// You need to rejoin a call for this setting to apply
     #[serde(rename = "experimental.legacy_audio_compatible")]
     pub legacy_audio_compatible: Option<bool>,
+    /// Sounds played when things happen in Zed, such as a task finishing.
+    pub cues: Option<AudioCuesSettingsContent>,
+}
+
+/// Sounds played when things happen in Zed, each of which can be enabled and given its own
+/// volume.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug)]
+pub struct AudioCuesSettingsContent {
+    /// Played when the cursor moves to a line with an error or a warning.
+    ///
+    /// Default: disabled
+    pub diagnostic_on_line: Option<AudioCueSettingsContent>,
+    /// Played when a task finishes successfully.
+    ///
+    /// Default: disabled
+    pub task_succeeded: Option<AudioCueSettingsContent>,
+    /// Played when a task fails.
+    ///
+    /// Default: disabled
+    pub task_failed: Option<AudioCueSettingsContent>,
+    /// Played when the debugger stops at a breakpoint.
+    ///
+    /// Default: disabled
+    pub breakpoint_hit: Option<AudioCueSettingsContent>,
+    /// Played when someone joins the call you're in.
+    ///
+    /// Default: enabled
+    pub participant_joined: Option<AudioCueSettingsContent>,
+    /// Played when someone leaves the call you're in.
+    ///
+    /// Default: disabled
+    pub participant_left: Option<AudioCueSettingsContent>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug)]
+pub struct AudioCueSettingsContent {
+    /// Whether to play the sound.
+    pub enabled: Option<bool>,
+    /// The volume of the sound, from 0 to 1.
+    pub volume: Option<f32>,
 }
 
 /// Control what info is collected by Zed.
@@ -379,6 +478,10 @@ pub struct DebuggerSettingsContent {
     ///
     /// Default: Bottom
     pub dock: Option<DockPosition>,
+    /// Where to show the values of variables while the debugged program is stopped.
+    ///
+    /// Default: inline
+    pub inline_values_position: Option<InlineValuesPosition>,
 }
 
 /// The granularity of one 'step' in the stepping requests `next`, `stepIn`, `stepOut`, and `stepBack`.
@@ -405,6 +508,16 @@ pub enum DockPosition {
     Right,
 }
 
+/// Where to show the values of variables while the debugged program is stopped.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineValuesPosition {
+    /// Show each value right after the variable it belongs to.
+    Inline,
+    /// Show the values of each line's variables together, at the end of the line.
+    EndOfLine,
+}
+
 /// Settings for slash commands.
 #[skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, JsonSchema, MergeFrom, PartialEq, Eq)]
@@ -476,6 +589,23 @@ pub struct GitPanelSettingsContent {
     ///
     /// Default: false
     pub collapse_untracked_diff: Option<bool>,
+
+    /// The format of generated commit messages.
+    ///
+    /// Default: conventional
+    pub commit_message_style: Option<CommitMessageStyle>,
+}
+
+#[derive(
+    Default, Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitMessageStyle {
+    /// A capitalized subject line, optionally followed by a body.
+    Standard,
+    /// A subject line following the Conventional Commits format, e.g. `fix(parser): handle empty input`.
+    #[default]
+    Conventional,
 }
 
 #[derive(
@@ -651,6 +781,76 @@ pub struct JournalSettingsContent {
     pub hour_format: Option<HourFormat>,
 }
 
+/// Settings for the local semantic index of project files.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct SemanticIndexSettingsContent {
+    /// Whether to index the files of local projects and include the snippets
+    /// most relevant to each message sent to the agent.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The base URL of an OpenAI-compatible API exposing an `/embeddings` endpoint.
+    ///
+    /// Default: "http://localhost:11434/v1"
+    pub api_url: Option<String>,
+    /// The embedding model to request.
+    ///
+    /// Default: "nomic-embed-text"
+    pub model: Option<String>,
+    /// The name of an environment variable containing the API key, if the API requires one.
+    ///
+    /// Default: null
+    pub api_key_env_var: Option<String>,
+    /// The maximum number of snippets to include with each message.
+    ///
+    /// Default: 5
+    pub max_results: Option<usize>,
+    /// The minimum similarity, between 0 and 1, a snippet needs to be included.
+    ///
+    /// Default: 0.5
+    pub min_similarity: Option<f32>,
+}
+
+/// Settings for the local history of files.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to record a version of a file in Zed's data directory each time it's saved, so
+    /// that it can be restored from the local history.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The maximum number of versions kept for each file. Older versions are removed first.
+    ///
+    /// Default: 50
+    pub max_versions_per_file: Option<usize>,
+    /// The number of days after which versions are removed.
+    ///
+    /// Default: 30
+    pub max_age_days: Option<u32>,
+    /// The maximum size, in bytes, of a file whose versions are recorded.
+    ///
+    /// Default: 1048576
+    pub max_file_size: Option<usize>,
+}
+
+/// Settings for scratch buffers.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct ScratchSettingsContent {
+    /// Whether to save scratch buffers as they're edited, so that they're restored after
+    /// restarting and can be reopened from the scratches picker.
+    ///
+    /// Default: true
+    pub persist: Option<bool>,
+    /// The language of new scratch buffers. When unset, a language picker is shown when
+    /// creating a scratch buffer.
+    ///
+    /// Default: null
+    pub default_language: Option<String>,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum HourFormat {
@@ -659,6 +859,33 @@ pub enum HourFormat {
     Hour24,
 }
 
+/// Settings for the TODO panel, which lists the comment tags in the project's files.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct TodoPanelSettingsContent {
+    /// Whether to show the TODO panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customize default width (in pixels) taken by the TODO panel.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+    /// The position of the TODO panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockSide>,
+    /// The comment tags to list, such as `TODO` and `FIXME`. Tags are case-sensitive, and are
+    /// only found in comments.
+    ///
+    /// Default: ["TODO", "FIXME", "HACK", "XXX", "BUG"]
+    pub tags: Option<Vec<String>>,
+    /// Whether to mark the lines with comment tags in the gutter of editors.
+    ///
+    /// Default: true
+    pub gutter_badges: Option<bool>,
+}
+
 #[skip_serializing_none]
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
 pub struct OutlinePanelSettingsContent {
@@ -763,6 +990,57 @@ pub struct ImageViewerSettingsContent {
     ///
     /// Default: "binary"
     pub unit: Option<ImageFileSizeUnit>,
+    /// How images are scaled when opened, and when their zoom is reset.
+    ///
+    /// Default: "fit"
+    pub zoom_mode: Option<ImageZoomMode>,
+    /// How images are compared with their version in git's HEAD, when the comparison is
+    /// opened.
+    ///
+    /// Default: "side_by_side"
+    pub diff_mode: Option<ImageDiffMode>,
+    /// The panel showing the EXIF and XMP metadata of the active image.
+    pub metadata_panel: Option<ImageMetadataPanelSettingsContent>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+pub struct ImageMetadataPanelSettingsContent {
+    /// Where to dock the image metadata panel.
+    ///
+    /// Default: right
+    pub dock: Option<DockSide>,
+    /// Customize default width (in pixels) taken by the image metadata panel.
+    ///
+    /// Default: 300
+    pub default_width: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageZoomMode {
+    /// Shrinks images to fit the pane, never scaling them up.
+    #[default]
+    Fit,
+    /// Scales images to cover the whole pane, cropping what doesn't fit.
+    Fill,
+    /// Shows images at their actual size (1:1).
+    ActualSize,
+}
+
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageDiffMode {
+    /// Shows the committed and the current image next to each other.
+    #[default]
+    SideBySide,
+    /// Shows the committed image left of a divider that can be dragged across, and the current
+    /// image right of it.
+    Swipe,
+    /// Shows the current image over the committed image, with an opacity that can be dragged.
+    OnionSkin,
 }
 
 #[skip_serializing_none]
@@ -782,6 +1060,45 @@ pub struct RemoteSettingsContent {
     pub ssh_connections: Option<Vec<SshConnection>>,
     pub wsl_connections: Option<Vec<WslConnection>>,
     pub read_ssh_config: Option<bool>,
+    /// Configuration for forwarding ports of remote projects to the local machine.
+    pub port_forwarding: Option<PortForwardingSettingsContent>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct PortForwardingSettingsContent {
+    /// Whether to detect ports that processes start listening on in remote projects.
+    ///
+    /// Default: true
+    pub detect_ports: Option<bool>,
+    /// Whether to automatically forward every newly detected port.
+    ///
+    /// Default: false
+    pub auto_forward: Option<bool>,
+    /// Ports below this number are never detected.
+    ///
+    /// Default: 1024
+    pub min_port: Option<u16>,
+    /// Rules to label, auto-forward, or ignore specific remote ports.
+    ///
+    /// Default: []
+    pub rules: Option<Vec<PortForwardRule>>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct PortForwardRule {
+    /// The remote port this rule applies to.
+    pub port: u16,
+    /// A label to show for this port, e.g. "Frontend".
+    pub label: Option<String>,
+    /// Whether to forward this port as soon as it's detected,
+    /// overriding `auto_forward`.
+    pub auto_forward: Option<bool>,
+    /// Whether to never show this port.
+    pub ignore: Option<bool>,
+    /// The local port to forward to. Defaults to the remote port.
+    pub local_port: Option<u16>,
 }
 
 #[skip_serializing_none]
@@ -803,6 +1120,27 @@ pub struct SshConnection {
     pub upload_binary_over_ssh: Option<bool>,
 
     pub port_forwards: Option<Vec<SshPortForwardOption>>,
+    /// Whether to browse and edit files over SFTP instead of running the Zed remote server,
+    /// for hosts that can't run it. Language servers, tasks and git are unavailable, and
+    /// changes made outside of Zed are detected by polling.
+    ///
+    /// Default: false
+    pub sftp_only: Option<bool>,
+    /// Jump hosts to connect through, as accepted by `ssh -J`
+    /// (e.g. "bastion" or "user@bastion:2222,inner").
+    ///
+    /// Default: null
+    pub proxy_jump: Option<String>,
+    /// Whether to forward the local SSH agent to the server, so that git
+    /// operations on the server can use your local keys.
+    ///
+    /// Default: false
+    pub forward_agent: Option<bool>,
+    /// Path to an existing remote server binary on the host. When set, Zed
+    /// uses this binary instead of downloading or uploading one.
+    ///
+    /// Default: null
+    pub remote_server_path: Option<String>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema, MergeFrom, Debug)]
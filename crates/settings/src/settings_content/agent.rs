@@ -60,6 +60,11 @@ pub struct AgentSettingsContent {
     ///
     /// Default: false
     pub always_allow_tool_actions: Option<bool>,
+    /// Kinds of tool actions to allow without asking for confirmation, even
+    /// when `always_allow_tool_actions` is disabled.
+    ///
+    /// Default: []
+    pub always_allow_tool_kinds: Option<Vec<ToolPermissionKind>>,
     /// Where to show a popup notification when the agent is waiting for user input.
     ///
     /// Default: "primary_screen"
@@ -109,6 +114,9 @@ pub struct AgentSettingsContent {
     ///
     /// Default: 4
     pub message_editor_min_lines: Option<usize>,
+    /// Prices used to estimate the cost of requests, and limits above which
+    /// the agent asks for confirmation before sending a request.
+    pub token_usage: Option<TokenUsageSettingsContent>,
 }
 
 impl AgentSettingsContent {
@@ -151,6 +159,13 @@ impl AgentSettingsContent {
         self.always_allow_tool_actions = Some(allow);
     }
 
+    pub fn allow_tool_kind(&mut self, kind: ToolPermissionKind) {
+        let kinds = self.always_allow_tool_kinds.get_or_insert_default();
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+
     pub fn set_play_sound_when_agent_done(&mut self, allow: bool) {
         self.play_sound_when_agent_done = Some(allow);
     }
@@ -186,6 +201,28 @@ pub struct ContextServerPresetContent {
     pub tools: IndexMap<Arc<str>, bool>,
 }
 
+/// A class of tool actions that can be allowed as a whole.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolPermissionKind {
+    /// Reading files and other project data.
+    Read,
+    /// Editing files.
+    Edit,
+    /// Deleting files and directories.
+    Delete,
+    /// Moving files and directories.
+    Move,
+    /// Searching the project.
+    Search,
+    /// Running commands and tasks.
+    Execute,
+    /// Fetching content from the web.
+    Fetch,
+    /// Any other tool, such as those provided by MCP servers.
+    Other,
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
 #[serde(rename_all = "snake_case")]
 pub enum DefaultAgentView {
@@ -227,6 +264,44 @@ pub struct LanguageModelParameters {
     pub temperature: Option<f32>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, Default)]
+pub struct TokenUsageSettingsContent {
+    /// Ask for confirmation before sending a message when the conversation
+    /// is estimated to use more than this many input tokens.
+    ///
+    /// Default: null
+    pub warn_above_tokens: Option<u64>,
+    /// Ask for confirmation before sending a message when the request is
+    /// estimated to cost more than this amount, based on `model_prices`.
+    ///
+    /// Default: null
+    pub warn_above_cost: Option<f64>,
+    /// Prices per million tokens used to estimate the cost of requests.
+    /// The last entry that matches a model's provider and name is used.
+    ///
+    /// Default: []
+    #[serde(default)]
+    pub model_prices: Vec<LanguageModelPrice>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct LanguageModelPrice {
+    pub provider: Option<LanguageModelProviderSetting>,
+    pub model: Option<SharedString>,
+    /// The price of one million input tokens.
+    pub input: f64,
+    /// The price of one million output tokens.
+    pub output: f64,
+    /// The price of one million tokens read from the prompt cache.
+    /// Defaults to the input price.
+    pub cache_read: Option<f64>,
+    /// The price of one million tokens written to the prompt cache.
+    /// Defaults to the input price.
+    pub cache_write: Option<f64>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, MergeFrom)]
 pub struct LanguageModelProviderSetting(pub String);
 
@@ -62,6 +62,8 @@ pub struct EditorSettingsContent {
     pub minimap: Option<MinimapContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
+    /// Fold placeholder related settings
+    pub fold_placeholder: Option<FoldPlaceholderContent>,
     /// Whether the editor will scroll beyond the last line.
     ///
     /// Default: one_page
@@ -70,6 +72,11 @@ pub struct EditorSettingsContent {
     ///
     /// Default: 3.
     pub vertical_scroll_margin: Option<f32>,
+    /// Whether to keep the cursor vertically centered while typing and moving it, scrolling the
+    /// text instead of the cursor (typewriter scrolling).
+    ///
+    /// Default: false
+    pub typewriter_scrolling: Option<bool>,
     /// Whether to scroll when clicking near the edge of the visible text area.
     ///
     /// Default: false
@@ -185,10 +192,19 @@ pub struct EditorSettingsContent {
     /// Drag and drop related settings
     pub drag_and_drop_selection: Option<DragAndDropSelectionContent>,
 
+    /// Settings for pasting images into Markdown files.
+    pub image_paste: Option<ImagePasteContent>,
+
     /// How to render LSP `textDocument/documentColor` colors in the editor.
     ///
     /// Default: [`DocumentColorsRenderMode::Inlay`]
     pub lsp_document_colors: Option<DocumentColorsRenderMode>,
+
+    /// What to do when a file open in an unfocused editor is changed on disk by
+    /// something other than Zed, such as a task, a formatter or a git checkout.
+    ///
+    /// Default: review
+    pub on_external_change: Option<OnExternalChange>,
 }
 
 // Toolbar related settings
@@ -330,6 +346,25 @@ pub struct GutterContent {
     pub folds: Option<bool>,
 }
 
+/// Fold placeholder related settings
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct FoldPlaceholderContent {
+    /// Whether to show the first non-blank folded line inside the fold placeholder.
+    ///
+    /// Default: true
+    pub summary: Option<bool>,
+    /// Whether to show the number of folded lines inside the fold placeholder.
+    ///
+    /// Default: true
+    pub line_count: Option<bool>,
+    /// Whether to show the number of errors and warnings in the folded text
+    /// inside the fold placeholder.
+    ///
+    /// Default: true
+    pub diagnostics: Option<bool>,
+}
+
 /// How to render LSP `textDocument/documentColor` colors in the editor.
 #[derive(
     Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom,
@@ -396,6 +431,30 @@ pub enum SeedQuerySetting {
     Never,
 }
 
+/// What to do when a file open in an unfocused editor is changed on disk.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OnExternalChange {
+    /// Reload the file silently.
+    Reload,
+    /// Reload the file, and mark its tab until the change is reviewed or dismissed.
+    #[default]
+    Review,
+}
+
 /// What to do when multibuffer is double clicked in some of its excerpts (parts of singleton buffers).
 #[derive(
     Default,
@@ -511,7 +570,6 @@ pub enum MultiCursorModifier {
     MergeFrom,
     PartialEq,
     Eq,
-    strum::VariantArray,
     strum::VariantNames,
 )]
 #[serde(rename_all = "snake_case")]
@@ -524,6 +582,13 @@ pub enum ScrollBeyondLastLine {
 
     /// The editor will scroll beyond the last line by the same number of lines as vertical_scroll_margin.
     VerticalScrollMargin,
+
+    /// The editor will scroll beyond the last line by the given number of lines.
+    Lines(u32),
+}
+
+impl strum::VariantArray for ScrollBeyondLastLine {
+    const VARIANTS: &'static [Self] = &[Self::Off, Self::OnePage, Self::VerticalScrollMargin];
 }
 
 /// The shape of a selection cursor.
@@ -661,6 +726,25 @@ pub struct JupyterContent {
     ///
     /// Default: `{}`
     pub kernel_selections: Option<HashMap<String, String>>,
+
+    /// What to write to disk when saving a Jupyter notebook.
+    ///
+    /// Default: full
+    pub notebook_save_mode: Option<NotebookSaveMode>,
+}
+
+/// What to write to disk when saving a Jupyter notebook.
+#[derive(
+    Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NotebookSaveMode {
+    /// Save the cells along with their outputs and execution counts.
+    #[default]
+    Full,
+    /// Save only the sources of the cells, without outputs or execution counts, so that diffs
+    /// of the notebook only show changes to its code and text.
+    SourcesOnly,
 }
 
 /// Whether to allow drag and drop text selection in buffer.
@@ -678,6 +762,27 @@ pub struct DragAndDropSelectionContent {
     pub delay: Option<u64>,
 }
 
+/// Settings for pasting images into Markdown files.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct ImagePasteContent {
+    /// When true, pasting an image into a Markdown file saves it into the assets directory
+    /// and inserts a link to it.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+
+    /// The directory pasted images are saved into, relative to the directory of the Markdown file.
+    ///
+    /// Default: "assets"
+    pub directory: Option<String>,
+
+    /// When true, asks for the name of a pasted image before saving it.
+    ///
+    /// Default: true
+    pub prompt_for_name: Option<bool>,
+}
+
 /// When to show the minimap in the editor.
 ///
 /// Default: never
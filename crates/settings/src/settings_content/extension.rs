@@ -22,6 +22,27 @@ pub struct ExtensionSettingsContent {
     /// The capabilities granted to extensions.
     #[serde(default)]
     pub granted_extension_capabilities: Option<Vec<ExtensionCapabilityContent>>,
+    /// The capabilities declared by extensions that the user has revoked, by extension ID.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub revoked_extension_capabilities: HashMap<Arc<str>, Vec<ExtensionCapabilityContent>>,
+    /// Controls the status bar items provided by extensions.
+    pub extension_status_items: Option<ExtensionStatusItemsSettingsContent>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct ExtensionStatusItemsSettingsContent {
+    /// The IDs of the items to hide.
+    ///
+    /// Default: []
+    pub hidden: Option<Vec<Arc<str>>>,
+    /// The IDs of the items to show first, in this order, on their side of the status bar.
+    /// Other items are shown after them.
+    ///
+    /// Default: []
+    pub order: Option<Vec<Arc<str>>>,
 }
 
 /// A capability for an extension.
@@ -44,4 +65,20 @@ pub enum ExtensionCapabilityContent {
     NpmInstallPackage {
         package: String,
     },
+    #[serde(rename = "network:request")]
+    NetworkRequest {
+        /// The host to allow requests to. Use `*` for any host, or `*.example.com` for the
+        /// subdomains of `example.com`.
+        host: String,
+    },
+    #[serde(rename = "fs:read")]
+    FsRead {
+        /// The directory to allow reading from. Use `*` for any directory.
+        path: String,
+    },
+    #[serde(rename = "fs:write")]
+    FsWrite {
+        /// The directory to allow reading from and writing to. Use `*` for any directory.
+        path: String,
+    },
 }
@@ -11,7 +11,7 @@ use serde_with::skip_serializing_none;
 use settings_macros::MergeFrom;
 use std::sync::Arc;
 
-use crate::{ExtendingVec, merge_from};
+use crate::{BufferLineHeight, ExtendingVec, FontFamilyName, merge_from};
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -32,11 +32,18 @@ pub struct AllLanguageSettingsContent {
     /// with languages.
     #[serde(default)]
     pub file_types: HashMap<Arc<str>, ExtendingVec<String>>,
+    /// Settings for associating files with languages by matching their first
+    /// line against regular expressions, for files whose path doesn't determine
+    /// their language.
+    #[serde(default)]
+    pub first_line_patterns: HashMap<Arc<str>, ExtendingVec<String>>,
 }
 
 impl merge_from::MergeFrom for AllLanguageSettingsContent {
     fn merge_from(&mut self, other: &Self) {
         self.file_types.merge_from(&other.file_types);
+        self.first_line_patterns
+            .merge_from(&other.first_line_patterns);
         self.features.merge_from(&other.features);
         self.edit_predictions.merge_from(&other.edit_predictions);
 
@@ -82,6 +89,9 @@ pub enum EditPredictionProvider {
     Copilot,
     Supermaven,
     Zed,
+    /// A fill-in-the-middle model served from an OpenAI-compatible
+    /// `/completions` endpoint, configured in `edit_predictions.custom`.
+    Custom,
 }
 
 impl EditPredictionProvider {
@@ -90,7 +100,8 @@ impl EditPredictionProvider {
             EditPredictionProvider::Zed => true,
             EditPredictionProvider::None
             | EditPredictionProvider::Copilot
-            | EditPredictionProvider::Supermaven => false,
+            | EditPredictionProvider::Supermaven
+            | EditPredictionProvider::Custom => false,
         }
     }
 }
@@ -108,6 +119,8 @@ pub struct EditPredictionSettingsContent {
     pub mode: Option<EditPredictionsMode>,
     /// Settings specific to GitHub Copilot.
     pub copilot: Option<CopilotSettingsContent>,
+    /// Settings for the `custom` edit prediction provider.
+    pub custom: Option<CustomEditPredictionSettingsContent>,
     /// Whether edit predictions are enabled in the assistant prompt editor.
     /// This has no effect if globally disabled.
     pub enabled_in_text_threads: Option<bool>,
@@ -130,6 +143,33 @@ pub struct CopilotSettingsContent {
     pub enterprise_uri: Option<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct CustomEditPredictionSettingsContent {
+    /// The base URL of an OpenAI-compatible API that serves a fill-in-the-middle
+    /// model through its `/completions` endpoint (e.g. llama.cpp, vLLM, Ollama).
+    ///
+    /// Default: "http://localhost:8080/v1"
+    pub api_url: Option<String>,
+    /// The model to request completions from.
+    ///
+    /// Default: none
+    pub model: Option<String>,
+    /// The maximum number of tokens to generate per prediction.
+    ///
+    /// Default: 64
+    pub max_tokens: Option<u32>,
+    /// The name of an environment variable holding the API key, if the endpoint requires one.
+    ///
+    /// Default: none
+    pub api_key_env_var: Option<String>,
+    /// The ID of an edit prediction provider registered by an extension. When set,
+    /// predictions are requested from that extension instead of `api_url`.
+    ///
+    /// Default: none
+    pub extension_provider: Option<String>,
+}
+
 /// The mode in which edit predictions should be displayed.
 #[derive(
     Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom,
@@ -275,6 +315,26 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: "•" for spaces, "→" for tabs.
     pub whitespace_map: Option<WhitespaceMapContent>,
+    /// The font family to render buffers of this language with, overriding `buffer_font_family`.
+    ///
+    /// Default: null
+    pub font_family: Option<FontFamilyName>,
+    /// The font size to render buffers of this language with, overriding `buffer_font_size`.
+    /// Adjustments made with `zed::IncreaseBufferFontSize` are applied on top of it.
+    ///
+    /// Default: null
+    pub font_size: Option<f32>,
+    /// The line height to render buffers of this language with, overriding `buffer_line_height`.
+    ///
+    /// Default: null
+    pub line_height: Option<BufferLineHeight>,
+    /// The text inserted for each file dropped into buffers of this language while holding `alt`,
+    /// where `{relative_path}` is the path of the file relative to the buffer's directory,
+    /// `{module_path}` is that path without its extension and starting with `./` or `../`,
+    /// and `{file_name}` and `{file_stem}` are the name of the file with and without its extension.
+    ///
+    /// Default: "{relative_path}"
+    pub drop_path_template: Option<String>,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     ///
     /// Default: true
@@ -314,6 +374,11 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// Whether to show color swatches next to hex, `rgb()` and `hsl()` color literals
+    /// when no language server provides document colors.
+    ///
+    /// Default: false
+    pub color_literals: Option<bool>,
     /// Whether indentation should be adjusted based on the context whilst typing.
     ///
     /// Default: true
@@ -514,6 +579,12 @@ pub struct CompletionSettingsContent {
     ///
     /// Default: "replace_suffix"
     pub lsp_insert_mode: Option<LspInsertMode>,
+    /// Whether to offer completions for symbols that other files in the project export,
+    /// adding the import they need when accepted. Supported for TypeScript, JavaScript and
+    /// Python, and only offered for symbols that language servers don't already complete.
+    ///
+    /// Default: true
+    pub auto_imports: Option<bool>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom)]
@@ -715,6 +786,11 @@ pub enum Formatter {
     },
     /// Files should be formatted using a code action executed by language servers.
     CodeAction(String),
+    /// Format code using a formatter provided by an extension.
+    Extension {
+        /// The ID of the formatter.
+        name: Arc<str>,
+    },
 }
 
 /// The settings for indent guides.
@@ -218,7 +218,14 @@ pub enum OpenAiReasoningEffort {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, JsonSchema, MergeFrom)]
 pub struct OpenAiCompatibleSettingsContent {
     pub api_url: String,
+    #[serde(default)]
     pub available_models: Vec<OpenAiCompatibleAvailableModel>,
+    /// Whether to query the server's `/models` endpoint for the models it serves,
+    /// in addition to the ones listed in `available_models`.
+    /// Useful for local servers such as llama.cpp.
+    ///
+    /// Default: false
+    pub discover_models: Option<bool>,
 }
 
 #[skip_serializing_none]
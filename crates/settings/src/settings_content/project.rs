@@ -40,6 +40,20 @@ pub struct ProjectSettingsContent {
     /// Configuration for how direnv configuration should be loaded
     pub load_direnv: Option<DirenvSettings>,
 
+    /// Whether to load the environment of mise (`mise env --json`) for the project,
+    /// so that tools installed with mise are found by language servers and tasks.
+    ///
+    /// Default: false
+    pub load_mise: Option<bool>,
+
+    /// Environment variables to set for language servers, formatters and tasks started
+    /// in the project, on top of the environment loaded from the shell, direnv and mise.
+    /// Values can reference other variables as `$VAR` or `${VAR}`, including
+    /// `$ZED_WORKTREE_ROOT`.
+    ///
+    /// Default: {}
+    pub project_env: Option<BTreeMap<String, String>>,
+
     /// Settings for slash commands.
     pub slash_commands: Option<SlashCommandSettings>,
 
@@ -83,6 +97,59 @@ pub struct WorktreeSettingsContent {
     /// Treat the files matching these globs as `.env` files.
     /// Default: ["**/.env*", "**/*.pem", "**/*.key", "**/*.cert", "**/*.crt", "**/secrets.yml"]
     pub private_files: Option<ExtendingVec<String>>,
+
+    /// Treat the files matching these globs, such as dependencies and generated code, as
+    /// read-only, until they're made writable.
+    /// Default: ["**/node_modules", "**/vendor", "**/site-packages"]
+    pub read_only_files: Option<ExtendingVec<String>>,
+
+    /// Whether to treat the files outside of the project's folders as read-only, until they're
+    /// made writable.
+    ///
+    /// Default: true
+    pub read_only_outside_project: Option<bool>,
+
+    /// Whether to ignore the casing of file paths when checking if a file is already open,
+    /// even if the file system reports being case-sensitive.
+    ///
+    /// Default: false
+    pub case_insensitive_paths: Option<bool>,
+
+    /// How opened files are detected as binary or generated, so that they open in a protective
+    /// state instead of as editable text.
+    pub file_heuristics: Option<FileHeuristicsContent>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom)]
+pub struct FileHeuristicsContent {
+    /// Files larger than this many bytes aren't opened in the editor, but can be opened anyway
+    /// or as hex.
+    ///
+    /// Default: 20971520
+    pub max_file_size: Option<u64>,
+
+    /// Whether to treat files with NUL bytes near their start as binary, which aren't opened in
+    /// the editor, but can be opened anyway or as hex.
+    ///
+    /// Default: true
+    pub detect_binary: Option<bool>,
+
+    /// Markers that make a file count as generated when found in its first lines.
+    ///
+    /// Default: ["@generated", "DO NOT EDIT", "auto-generated", "autogenerated"]
+    pub generated_markers: Option<ExtendingVec<String>>,
+
+    /// Globs of files that are always treated as generated.
+    ///
+    /// Default: ["**/*.min.js", "**/*.min.css", "**/*.map"]
+    pub generated_files: Option<ExtendingVec<String>>,
+
+    /// Whether generated files, including files containing only a source map, open read-only
+    /// with everything but their first lines folded.
+    ///
+    /// Default: true
+    pub preview_generated_files: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -420,6 +487,42 @@ pub struct InlineDiagnosticsSettingsContent {
     pub min_column: Option<u32>,
 
     pub max_severity: Option<DiagnosticSeverityContent>,
+    /// Where to show inline diagnostics.
+    ///
+    /// Default: end_of_line
+    pub mode: Option<InlineDiagnosticsMode>,
+    /// Whether to only show the inline diagnostics of the lines with a cursor.
+    ///
+    /// Default: false
+    pub current_line_only: Option<bool>,
+    /// The maximum width of an inline diagnostic in columns. Longer messages
+    /// are truncated. When `null`, messages are not truncated.
+    ///
+    /// Default: null
+    pub max_width: Option<u32>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineDiagnosticsMode {
+    /// Show the diagnostic after the end of the line, like ghost text.
+    #[default]
+    EndOfLine,
+    /// Show the diagnostic in a block below the line.
+    BelowLine,
 }
 
 #[skip_serializing_none]
@@ -100,12 +100,19 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: false
     pub use_system_window_tabs: Option<bool>,
+    /// Whether projects open as tabs in a strip above the title bar of the current window,
+    /// instead of in new windows (Linux and Windows only).
+    ///
+    /// Default: false
+    pub workspace_tabs: Option<bool>,
     /// Whether to show padding for zoomed panels.
     /// When enabled, zoomed bottom panels will have some top padding,
     /// while zoomed left/right panels will have padding to the right/left (respectively).
     ///
     /// Default: true
     pub zoomed_padding: Option<bool>,
+    /// Settings for presentation mode, used when sharing the screen or recording.
+    pub presentation_mode: Option<PresentationModeSettings>,
 }
 
 #[skip_serializing_none]
@@ -417,6 +424,27 @@ pub enum PaneSplitDirectionVertical {
     Right,
 }
 
+#[skip_serializing_none]
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct PresentationModeSettings {
+    /// How many pixels larger the interface and editor fonts are while presenting.
+    ///
+    /// Default: 4.0
+    pub font_size_increase: Option<f32>,
+    /// Whether to close the docks while presenting. They're reopened afterwards.
+    ///
+    /// Default: true
+    pub hide_docks: Option<bool>,
+    /// Whether to hide the status bar while presenting.
+    ///
+    /// Default: true
+    pub hide_status_bar: Option<bool>,
+    /// Whether to show the keys that are pressed while presenting.
+    ///
+    /// Default: true
+    pub show_keystrokes: Option<bool>,
+}
+
 #[skip_serializing_none]
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -509,6 +537,13 @@ pub struct ProjectPanelSettingsContent {
     ///
     /// Default: true
     pub auto_reveal_entries: Option<bool>,
+    /// Whether to keep the active file revealed and selected in the project panel,
+    /// also when the project tree changes or focus leaves the panel.
+    /// Browsing the project panel pauses following until focus leaves the panel
+    /// or another file becomes active.
+    ///
+    /// Default: false
+    pub follow_active_file: Option<bool>,
     /// Whether to fold directories automatically
     /// when directory has only one directory inside.
     ///
@@ -613,6 +613,15 @@ fn user_settings_data() -> Vec<SettingsPage> {
                     }),
                     metadata: None,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Workspace Tabs",
+                    description: "Whether projects open as tabs in the current window instead of in new windows (Linux and Windows only)",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| &settings_content.workspace.workspace_tabs,
+                        pick_mut: |settings_content| &mut settings_content.workspace.workspace_tabs,
+                    }),
+                    metadata: None,
+                }),
                 SettingsPageItem::SectionHeader("Layout"),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Zoomed Padding",
@@ -814,6 +823,17 @@ fn user_settings_data() -> Vec<SettingsPage> {
                     }),
                     metadata: None,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "On External Change",
+                    description: "What to do when an open file that isn't focused is changed on disk",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| &settings_content.editor.on_external_change,
+                        pick_mut: |settings_content| {
+                            &mut settings_content.editor.on_external_change
+                        },
+                    }),
+                    metadata: None,
+                }),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Go To Definition Fallback",
                     description: "Whether to follow-up empty go to definition responses from the language server",
@@ -2678,6 +2698,8 @@ fn init_renderers(cx: &mut App) {
             render_dropdown(*settings_field, file, window, cx)
         })
         .add_renderer::<settings::ScrollBeyondLastLine>(|settings_field, file, _, window, cx| {
+            // todo(settings_ui): Do we want to expose the lines variant of scroll beyond last line?
+            // right now there's a manual impl of strum::VariantArray
             render_dropdown(*settings_field, file, window, cx)
         })
         .add_renderer::<settings::SnippetSortOrder>(|settings_field, file, _, window, cx| {
@@ -2711,6 +2733,9 @@ fn init_renderers(cx: &mut App) {
                 render_dropdown(*settings_field, file, window, cx)
             },
         )
+        .add_renderer::<settings::OnExternalChange>(|settings_field, file, _, window, cx| {
+            render_dropdown(*settings_field, file, window, cx)
+        })
         .add_renderer::<settings::GoToDefinitionFallback>(|settings_field, file, _, window, cx| {
             render_dropdown(*settings_field, file, window, cx)
         })
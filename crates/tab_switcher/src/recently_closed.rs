@@ -0,0 +1,335 @@
+use std::{rc::Rc, sync::Arc};
+
+use fs::Fs;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EntityId, EventEmitter, FocusHandle, Focusable, Task,
+    WeakEntity, Window, actions, rems,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{
+    DocumentationAside, DocumentationEdge, DocumentationSide, HighlightedLabel, ListItem,
+    ListItemSpacing, prelude::*,
+};
+use util::{ResultExt as _, paths::PathStyle};
+use workspace::{ClosedItem, ModalView, Pane, Workspace};
+
+use crate::PANEL_WIDTH_REMS;
+
+/// How many lines of a closed file are shown next to the picker.
+const PREVIEW_LINES: usize = 16;
+
+actions!(
+    tab_switcher,
+    [
+        /// Lists the items recently closed in the workspace, to reopen one of them.
+        ToggleRecentlyClosed
+    ]
+);
+
+pub(crate) fn register(
+    workspace: &mut Workspace,
+    _window: Option<&mut Window>,
+    _: &mut Context<Workspace>,
+) {
+    workspace.register_action(|workspace, _: &ToggleRecentlyClosed, window, cx| {
+        RecentlyClosed::toggle(workspace, window, cx);
+    });
+}
+
+pub struct RecentlyClosed {
+    pub(crate) picker: Entity<Picker<RecentlyClosedDelegate>>,
+}
+
+impl RecentlyClosed {
+    fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let items = workspace.recently_closed_items(cx);
+        let fs = workspace.app_state().fs.clone();
+        let path_style = workspace.project().read(cx).path_style(cx);
+        let weak_workspace = workspace.weak_handle();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = RecentlyClosedDelegate::new(
+                cx.entity().downgrade(),
+                weak_workspace,
+                fs,
+                items,
+                path_style,
+            );
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            Self { picker }
+        });
+    }
+}
+
+impl ModalView for RecentlyClosed {}
+
+impl EventEmitter<DismissEvent> for RecentlyClosed {}
+
+impl Focusable for RecentlyClosed {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for RecentlyClosed {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("RecentlyClosed")
+            .w(rems(PANEL_WIDTH_REMS))
+            .child(self.picker.clone())
+    }
+}
+
+pub struct RecentlyClosedDelegate {
+    recently_closed: WeakEntity<RecentlyClosed>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    items: Vec<(Entity<Pane>, ClosedItem)>,
+    path_style: PathStyle,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    /// The first lines of the selected item's file, keyed by the item they were loaded for.
+    preview: Option<(EntityId, SharedString)>,
+    _preview_task: Task<()>,
+}
+
+impl RecentlyClosedDelegate {
+    fn new(
+        recently_closed: WeakEntity<RecentlyClosed>,
+        workspace: WeakEntity<Workspace>,
+        fs: Arc<dyn Fs>,
+        items: Vec<(Entity<Pane>, ClosedItem)>,
+        path_style: PathStyle,
+    ) -> Self {
+        Self {
+            recently_closed,
+            workspace,
+            fs,
+            items,
+            path_style,
+            matches: Vec::new(),
+            selected_index: 0,
+            preview: None,
+            _preview_task: Task::ready(()),
+        }
+    }
+
+    fn selected_item(&self) -> Option<&(Entity<Pane>, ClosedItem)> {
+        let mat = self.matches.get(self.selected_index)?;
+        self.items.get(mat.candidate_id)
+    }
+
+    fn load_preview(&mut self, cx: &mut Context<Picker<Self>>) {
+        let Some((_, item)) = self.selected_item() else {
+            self.preview = None;
+            return;
+        };
+        let item_id = item.item_id;
+        if self
+            .preview
+            .as_ref()
+            .is_some_and(|(preview_item_id, _)| *preview_item_id == item_id)
+        {
+            return;
+        }
+        let Some(abs_path) = item.abs_path.clone() else {
+            self.preview = None;
+            return;
+        };
+        let fs = self.fs.clone();
+        self._preview_task = cx.spawn(async move |picker, cx| {
+            let Some(text) = fs.load(&abs_path).await.log_err() else {
+                return;
+            };
+            let preview = text
+                .lines()
+                .take(PREVIEW_LINES)
+                .collect::<Vec<_>>()
+                .join("\n");
+            picker
+                .update(cx, |picker, cx| {
+                    picker.delegate.preview = Some((item_id, preview.into()));
+                    cx.notify();
+                })
+                .ok();
+        });
+    }
+}
+
+impl PickerDelegate for RecentlyClosedDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search recently closed items…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No recently closed items".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        self.load_preview(cx);
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(id, (_, item))| {
+                StringMatchCandidate::new(id, &item.project_path.path.display(self.path_style))
+            })
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                // Keep the most recently closed items first among equally good matches.
+                let mut matches = match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await;
+                matches.sort_by(|a, b| {
+                    b.score
+                        .total_cmp(&a.score)
+                        .then(a.candidate_id.cmp(&b.candidate_id))
+                });
+                matches
+            };
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                    delegate.load_preview(cx);
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some((pane, item)) = self.selected_item().cloned() {
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    workspace
+                        .reopen_closed_item_by_id(pane, item.item_id, window, cx)
+                        .detach_and_log_err(cx);
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.recently_closed
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let (_, item) = self.items.get(mat.candidate_id)?;
+        let path = &item.project_path.path;
+        let file_name = path.file_name().unwrap_or_default().to_string();
+        let file_name_start = mat.string.len() - file_name.len();
+        let file_name_positions = mat
+            .positions
+            .iter()
+            .filter_map(|position| position.checked_sub(file_name_start))
+            .collect::<Vec<_>>();
+        let directory = path
+            .parent()
+            .map(|parent| parent.display(self.path_style).into_owned())
+            .unwrap_or_default();
+        let directory_positions = mat
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < directory.len())
+            .collect::<Vec<_>>();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(file_name, file_name_positions))
+                        .when(!directory.is_empty(), |this| {
+                            this.child(
+                                HighlightedLabel::new(directory, directory_positions)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                ),
+        )
+    }
+
+    fn documentation_aside(
+        &self,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<DocumentationAside> {
+        let (_, item) = self.selected_item()?;
+        let (preview_item_id, preview) = self.preview.as_ref()?;
+        if *preview_item_id != item.item_id || preview.is_empty() {
+            return None;
+        }
+        let preview = preview.clone();
+        Some(DocumentationAside::new(
+            DocumentationSide::Right,
+            DocumentationEdge::Top,
+            Rc::new(move |cx| {
+                Label::new(preview.clone())
+                    .size(LabelSize::Small)
+                    .buffer_font(cx)
+                    .into_any_element()
+            }),
+        ))
+    }
+}
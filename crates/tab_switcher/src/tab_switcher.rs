@@ -1,3 +1,4 @@
+mod recently_closed;
 #[cfg(test)]
 mod tab_switcher_tests;
 
@@ -28,6 +29,8 @@ use workspace::{
     pane::{Event as PaneEvent, render_item_indicator, tab_details},
 };
 
+pub use recently_closed::{RecentlyClosed, ToggleRecentlyClosed};
+
 const PANEL_WIDTH_REMS: f32 = 28.;
 
 /// Toggles the tab switcher interface.
@@ -57,6 +60,7 @@ impl ModalView for TabSwitcher {}
 
 pub fn init(cx: &mut App) {
     cx.observe_new(TabSwitcher::register).detach();
+    cx.observe_new(recently_closed::register).detach();
 }
 
 impl TabSwitcher {
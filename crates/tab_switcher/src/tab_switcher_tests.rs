@@ -280,6 +280,73 @@ async fn test_close_preserves_selected_position(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_reopen_recently_closed_item(cx: &mut gpui::TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            path!("/root"),
+            json!({
+                "1.txt": "First file",
+                "2.txt": "Second file",
+                "3.txt": "Third file",
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let tab_1 = open_buffer("1.txt", &workspace, cx).await;
+    open_buffer("2.txt", &workspace, cx).await;
+    let tab_3 = open_buffer("3.txt", &workspace, cx).await;
+    let pane = workspace.read_with(cx, |workspace, _| workspace.active_pane().clone());
+    for tab in [tab_1, tab_3] {
+        pane.update_in(cx, |pane, window, cx| {
+            pane.close_item_by_id(tab.item_id(), SaveIntent::Close, window, cx)
+        })
+        .await
+        .unwrap();
+    }
+
+    cx.dispatch_action(ToggleRecentlyClosed);
+    let picker = workspace.update(cx, |workspace, cx| {
+        workspace
+            .active_modal::<RecentlyClosed>(cx)
+            .expect("recently closed picker is not open")
+            .read(cx)
+            .picker
+            .clone()
+    });
+    picker.update(cx, |picker, _| {
+        assert_eq!(picker.delegate.match_count(), 2);
+    });
+
+    // The most recently closed item is listed first, so select the one closed before it.
+    cx.dispatch_action(menu::SelectNext);
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+    cx.read(|cx| {
+        let active_editor = workspace.read(cx).active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "1.txt");
+    });
+
+    // Reopening the last closed item now reopens the remaining one.
+    workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.reopen_closed_item(window, cx)
+        })
+        .await
+        .unwrap();
+    cx.read(|cx| {
+        let active_editor = workspace.read(cx).active_item_as::<Editor>(cx).unwrap();
+        assert_eq!(active_editor.read(cx).title(cx), "3.txt");
+    });
+}
+
 fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
     cx.update(|cx| {
         let state = AppState::test(cx);
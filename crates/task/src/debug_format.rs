@@ -278,6 +278,10 @@ pub struct DebugScenario {
     /// that is already running or is started by another process.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tcp_connection: Option<TcpArgumentsTemplate>,
+    /// Labels of other debug scenarios to start along with this one,
+    /// e.g. the server a client connects to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub start_with: Vec<SharedString>,
 }
 
 /// A group of Debug Tasks defined in a JSON file.
@@ -396,6 +400,11 @@ impl DebugTaskFile {
                                 "description": "The max amount of time in milliseconds to connect to a tcp DAP before returning an error (default: 2000ms)"
                             }
                         }
+                    },
+                    "start_with": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Labels of other debug configurations to start along with this one, e.g. the server a client connects to"
                     }
                 },
                 "allOf": adapter_conditions
@@ -408,6 +417,7 @@ impl DebugTaskFile {
 #[cfg(test)]
 mod tests {
     use crate::DebugScenario;
+    use gpui::SharedString;
     use serde_json::json;
 
     #[test]
@@ -490,6 +500,25 @@ mod tests {
         assert_eq!("Attach to process", deserialized.label.as_ref());
     }
 
+    #[test]
+    fn test_start_with_is_not_sent_to_the_adapter() {
+        let json = r#"{
+            "label": "Client",
+            "adapter": "JavaScript",
+            "request": "launch",
+            "program": "client.js",
+            "start_with": ["Server"]
+        }"#;
+
+        let deserialized: DebugScenario = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            json!({ "request": "launch", "program": "client.js" }),
+            deserialized.config
+        );
+        assert_eq!(vec![SharedString::from("Server")], deserialized.start_with);
+    }
+
     #[test]
     fn test_build_task_definition_without_label() {
         use crate::BuildTaskDefinition;
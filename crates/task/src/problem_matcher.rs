@@ -0,0 +1,188 @@
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use util::ResultExt as _;
+
+/// Describes how to find problems, such as compiler errors, in a task's output.
+///
+/// The pattern is matched against every line of the output, and must capture the
+/// problem's location in named groups:
+/// * `file` — the path of the file, absolute or relative to the task's working directory (required)
+/// * `line` — the one-based line number (required)
+/// * `column` — the one-based column number
+/// * `severity` — `error`, `warning`, `info` or `hint`; anything else falls back to the matcher's severity
+/// * `message` — the problem's description
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcher {
+    /// The regular expression to match against each line of output.
+    pub pattern: String,
+    /// The severity of problems that don't capture one.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+}
+
+/// Marks a task as a long-running watch task, that rebuilds whenever its inputs change
+/// instead of exiting. Both patterns are matched against every line of the output.
+///
+/// While a rebuild is in progress the task is shown as compiling, and when it ends, the
+/// problem matchers are run against the rebuild's output.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WatchMatcher {
+    /// The regular expression matching the line printed when a rebuild starts.
+    pub begins: String,
+    /// The regular expression matching the line printed when a rebuild ends.
+    pub ends: String,
+}
+
+/// How severe a problem found in task output is.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+/// A problem found in a task's output by a [`ProblemMatcher`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The path of the file, as printed by the task.
+    pub path: String,
+    /// The one-based line number.
+    pub line: u32,
+    /// The one-based column number, if the task printed one.
+    pub column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub message: String,
+}
+
+impl ProblemSeverity {
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "error" | "err" | "fatal" => Some(Self::Error),
+            "warning" | "warn" => Some(Self::Warning),
+            "info" | "information" | "note" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// Finds the problems in `output` using each of `matchers`, in the order they
+/// were printed. Matchers with invalid patterns are skipped.
+pub fn find_problems(matchers: &[ProblemMatcher], output: &str) -> Vec<Problem> {
+    let matchers = matchers
+        .iter()
+        .filter_map(|matcher| {
+            let regex = Regex::new(&matcher.pattern).log_err()?;
+            Some((regex, matcher.severity))
+        })
+        .collect::<Vec<_>>();
+    if matchers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    for line in output.lines() {
+        for (regex, default_severity) in &matchers {
+            let Some(captures) = regex.captures(line) else {
+                continue;
+            };
+            let Some(path) = captures.name("file").map(|file| file.as_str().trim()) else {
+                continue;
+            };
+            let Some(line) = captures
+                .name("line")
+                .and_then(|line| line.as_str().parse().ok())
+            else {
+                continue;
+            };
+            problems.push(Problem {
+                path: path.to_string(),
+                line,
+                column: captures
+                    .name("column")
+                    .and_then(|column| column.as_str().parse().ok()),
+                severity: captures
+                    .name("severity")
+                    .and_then(|severity| ProblemSeverity::parse(severity.as_str()))
+                    .unwrap_or(*default_severity),
+                message: captures
+                    .name("message")
+                    .map(|message| message.as_str().trim().to_string())
+                    .unwrap_or_default(),
+            });
+            break;
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_problems() {
+        let matchers = [
+            ProblemMatcher {
+                pattern: r"^(?P<file>[^:\s]+):(?P<line>\d+):(?P<column>\d+): (?P<severity>\w+): (?P<message>.*)$".into(),
+                severity: ProblemSeverity::Error,
+            },
+            ProblemMatcher {
+                pattern: r"^TODO in (?P<file>\S+) line (?P<line>\d+)$".into(),
+                severity: ProblemSeverity::Hint,
+            },
+            ProblemMatcher {
+                pattern: "(unclosed".into(),
+                severity: ProblemSeverity::Error,
+            },
+        ];
+        let output = "\
+            Compiling...\n\
+            src/main.c:3:5: warning: unused variable 'x'\n\
+            src/lib.c:10:1: error: expected ';'\n\
+            TODO in src/lib.c line 12\n\
+            src/other.c:1:1: fatality: unknown severity\n\
+            Done.\n";
+
+        assert_eq!(
+            find_problems(&matchers, output),
+            vec![
+                Problem {
+                    path: "src/main.c".into(),
+                    line: 3,
+                    column: Some(5),
+                    severity: ProblemSeverity::Warning,
+                    message: "unused variable 'x'".into(),
+                },
+                Problem {
+                    path: "src/lib.c".into(),
+                    line: 10,
+                    column: Some(1),
+                    severity: ProblemSeverity::Error,
+                    message: "expected ';'".into(),
+                },
+                Problem {
+                    path: "src/lib.c".into(),
+                    line: 12,
+                    column: None,
+                    severity: ProblemSeverity::Hint,
+                    message: String::new(),
+                },
+                Problem {
+                    path: "src/other.c".into(),
+                    line: 1,
+                    column: Some(1),
+                    severity: ProblemSeverity::Error,
+                    message: "unknown severity".into(),
+                },
+            ]
+        );
+        assert_eq!(find_problems(&[], output), Vec::new());
+    }
+}
@@ -2,6 +2,7 @@
 
 mod adapter_schema;
 mod debug_format;
+mod problem_matcher;
 mod serde_helpers;
 mod shell_builder;
 pub mod static_source;
@@ -23,10 +24,12 @@ pub use debug_format::{
     AttachRequest, BuildTaskDefinition, DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest,
     Request, TcpArgumentsTemplate, ZedDebugConfig,
 };
+pub use problem_matcher::{Problem, ProblemMatcher, ProblemSeverity, WatchMatcher, find_problems};
 pub use shell_builder::{ShellBuilder, ShellKind};
 pub use task_template::{
-    DebugArgsRequest, HideStrategy, RevealStrategy, TaskTemplate, TaskTemplates,
-    substitute_variables_in_map, substitute_variables_in_str,
+    DebugArgsRequest, DependsOrder, HideStrategy, NotifyStrategy, RevealStrategy, TaskInput,
+    TaskInputKind, TaskTemplate, TaskTemplates, substitute_variables_in_map,
+    substitute_variables_in_str,
 };
 pub use vscode_debug_format::VsCodeDebugTaskFile;
 pub use vscode_format::VsCodeTaskFile;
@@ -57,6 +60,10 @@ pub struct SpawnInTerminal {
     pub cwd: Option<PathBuf>,
     /// Env overrides for the command, will be appended to the terminal's environment from the settings.
     pub env: HashMap<String, String>,
+    /// Dotenv files to load the variables missing from the environment from, in order.
+    pub env_files: Vec<PathBuf>,
+    /// Environment variables to read from the system keychain, mapped to the names of their secrets.
+    pub secrets: HashMap<String, String>,
     /// Whether to use a new terminal tab or reuse the existing one to spawn the process.
     pub use_new_terminal: bool,
     /// Whether to allow multiple instances of the same task to be run, or rather wait for the existing ones to finish.
@@ -75,6 +82,12 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// How to find problems in the task's output once it finishes.
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// When to show a notification after the task finishes.
+    pub notify: NotifyStrategy,
+    /// How to tell when a long-running watch task is rebuilding.
+    pub watch: Option<WatchMatcher>,
 }
 
 impl SpawnInTerminal {
@@ -118,6 +131,8 @@ pub struct ResolvedTask {
     pub id: TaskId,
     /// A template the task got resolved from.
     original_task: TaskTemplate,
+    /// The context the task got resolved with.
+    context: TaskContext,
     /// Full, unshortened label of the task after all resolutions are made.
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
@@ -133,6 +148,11 @@ impl ResolvedTask {
         &self.original_task
     }
 
+    /// The context the task got resolved with, also used to resolve the tasks it depends on.
+    pub fn context(&self) -> &TaskContext {
+        &self.context
+    }
+
     /// Variables that were substituted during the task template resolution.
     pub fn substituted_variables(&self) -> &HashSet<VariableName> {
         &self.substituted_variables
@@ -177,6 +197,9 @@ pub enum VariableName {
     /// Custom variable, provided by the plugin or other external source.
     /// Will be printed with `CUSTOM_` prefix to avoid potential conflicts with other variables.
     Custom(Cow<'static, str>),
+    /// The value the user gave for a task input, referenced as `${input:<id>}` in templates.
+    /// Unlike other variables, it is not set in the task's environment.
+    Input(Cow<'static, str>),
 }
 
 impl VariableName {
@@ -194,6 +217,9 @@ impl FromStr for VariableName {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(input_id) = s.strip_prefix(INPUT_VARIABLE_NAME_PREFIX) {
+            return Ok(Self::Input(Cow::Owned(input_id.to_owned())));
+        }
         let without_prefix = s.strip_prefix(ZED_VARIABLE_NAME_PREFIX).ok_or(())?;
         let value = match without_prefix {
             "FILE" => Self::File,
@@ -225,6 +251,8 @@ impl FromStr for VariableName {
 /// A prefix that all [`VariableName`] variants are prefixed with when used in environment variables and similar template contexts.
 pub const ZED_VARIABLE_NAME_PREFIX: &str = "ZED_";
 const ZED_CUSTOM_VARIABLE_NAME_PREFIX: &str = "CUSTOM_";
+/// A prefix of the [`VariableName::Input`] variables, that are prompted for when the task is spawned.
+pub(crate) const INPUT_VARIABLE_NAME_PREFIX: &str = "input:";
 
 impl std::fmt::Display for VariableName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -245,6 +273,7 @@ impl std::fmt::Display for VariableName {
                 f,
                 "{ZED_VARIABLE_NAME_PREFIX}{ZED_CUSTOM_VARIABLE_NAME_PREFIX}{s}"
             ),
+            Self::Input(id) => write!(f, "{INPUT_VARIABLE_NAME_PREFIX}{id}"),
         }
     }
 }
@@ -3,14 +3,15 @@ use collections::{HashMap, HashSet};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::{borrow::Cow, path::PathBuf};
 use util::schemars::DefaultDenyUnknownFields;
 use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    AttachRequest, INPUT_VARIABLE_NAME_PREFIX, ProblemMatcher, ResolvedTask, RevealTarget, Shell,
+    SpawnInTerminal, TaskContext, TaskId, VariableName, WatchMatcher, ZED_VARIABLE_NAME_PREFIX,
+    serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -32,6 +33,14 @@ pub struct TaskTemplate {
     /// Env overrides for the command, will be appended to the terminal's environment from the settings.
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Paths of dotenv files to load environment variables from, relative to the task's working directory.
+    /// Later files override earlier ones, and none of them override the variables set otherwise.
+    #[serde(default)]
+    pub env_files: Vec<String>,
+    /// Environment variables whose values are read from the system keychain when the task is spawned,
+    /// mapped to the names of the secrets holding them.
+    #[serde(default)]
+    pub secrets: HashMap<String, String>,
     /// Current working directory to spawn the command into, defaults to current project root.
     #[serde(default)]
     pub cwd: Option<String>,
@@ -72,6 +81,34 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// How to find problems, such as compiler errors, in the task's output.
+    /// Problems found when the task finishes are shown as diagnostics.
+    #[serde(default)]
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// When to show a notification after the command has finished:
+    /// * `never` — never notify (default)
+    /// * `always` — always notify, with the exit status and number of problems found
+    /// * `on_failure` — notify only when the task fails or problems were found
+    #[serde(default)]
+    pub notify: NotifyStrategy,
+    /// Labels of the tasks to run before this one, resolved with the same context.
+    /// If any of them fails, the rest are not started and this task is not run.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks listed in `depends_on`:
+    /// * `sequence` — one after another, in the order they are listed (default)
+    /// * `parallel` — all at once
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Marks the task as a long-running watch task, such as `tsc --watch` or `cargo watch`,
+    /// with patterns matching the lines it prints when a rebuild begins and ends.
+    /// Its state is shown in the status bar, and it restarts when its definition changes.
+    #[serde(default)]
+    pub watch: Option<WatchMatcher>,
+    /// Values to ask for when the task is spawned, referenced as `${input:<id>}` in its label,
+    /// command, arguments, environment and working directory.
+    #[serde(default)]
+    pub inputs: Vec<TaskInput>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -109,6 +146,67 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// When to show a notification after the command has finished.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyStrategy {
+    /// Never notify.
+    #[default]
+    Never,
+    /// Always notify, with the exit status and number of problems found.
+    Always,
+    /// Notify only when the task fails or problems were found in its output.
+    OnFailure,
+}
+
+/// How to run the tasks a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Run the tasks one after another, in the order they are listed.
+    #[default]
+    Sequence,
+    /// Run all tasks at once.
+    Parallel,
+}
+
+/// A value to ask for when the task is spawned.
+/// The last value given for each input is suggested the next time it is asked for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TaskInput {
+    /// The name of the input, as referenced in `${input:<id>}`.
+    pub id: String,
+    /// How to ask for the value:
+    /// * `prompt_string` — as free text (default)
+    /// * `pick_string` — by picking one of the `options`
+    /// * `pick_file` — by picking a file
+    #[serde(default, rename = "type")]
+    pub kind: TaskInputKind,
+    /// The prompt shown when asking for the value.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The values to pick from, for `pick_string` inputs.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// The value to suggest when the input was never given before.
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// How to ask for the value of a [`TaskInput`].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskInputKind {
+    /// Ask for free text.
+    #[default]
+    PromptString,
+    /// Pick one of the input's options.
+    PickString,
+    /// Pick a file.
+    PickFile,
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -126,6 +224,54 @@ impl TaskTemplates {
 }
 
 impl TaskTemplate {
+    /// Lists the inputs the task references that `cx` has no values for, in the order they are
+    /// first referenced. Inputs that are referenced but not declared are asked for as free text.
+    ///
+    /// The task should be resolved again once the values of these inputs are added to the context,
+    /// as references to inputs without values are left as is.
+    pub fn unresolved_inputs(&self, cx: &TaskContext) -> Vec<TaskInput> {
+        let fields = [&self.label, &self.command]
+            .into_iter()
+            .chain(self.cwd.as_ref())
+            .chain(&self.args)
+            .chain(&self.env_files)
+            .chain(self.env.iter().flat_map(|(key, value)| [key, value]));
+
+        let mut inputs = Vec::<TaskInput>::new();
+        for field in fields {
+            let mut rest = field.as_str();
+            while let Some(start) = rest.find(INPUT_REFERENCE_START) {
+                rest = &rest[start + INPUT_REFERENCE_START.len()..];
+                let Some(end) = rest.find('}') else {
+                    break;
+                };
+                let id = &rest[..end];
+                rest = &rest[end + 1..];
+
+                let variable = VariableName::Input(Cow::Owned(id.to_owned()));
+                if cx.task_variables.get(&variable).is_some()
+                    || inputs.iter().any(|input| input.id == id)
+                {
+                    continue;
+                }
+                inputs.push(
+                    self.inputs
+                        .iter()
+                        .find(|input| input.id == id)
+                        .cloned()
+                        .unwrap_or_else(|| TaskInput {
+                            id: id.to_owned(),
+                            kind: TaskInputKind::PromptString,
+                            description: None,
+                            options: Vec::new(),
+                            default: None,
+                        }),
+                );
+            }
+        }
+        inputs
+    }
+
     /// Replaces all `VariableName` task variables in the task template string fields.
     /// If any replacement fails or the new string substitutions still have [`ZED_VARIABLE_NAME_PREFIX`],
     /// `None` is returned.
@@ -213,6 +359,16 @@ impl TaskTemplate {
             &mut substituted_variables,
         )?;
 
+        let env_files = substitute_all_template_variables_in_vec(
+            &self.env_files,
+            &task_variables,
+            &variable_names,
+            &mut substituted_variables,
+        )?
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
             .log_err()?;
@@ -236,8 +392,14 @@ impl TaskTemplate {
                 &mut substituted_variables,
             )?;
 
-            // Last step: set the task variables as environment variables too
-            env.extend(task_variables.into_iter().map(|(k, v)| (k, v.to_owned())));
+            // Last step: set the task variables as environment variables too, except for the inputs,
+            // whose names are not valid environment variable names
+            env.extend(
+                task_variables
+                    .into_iter()
+                    .filter(|(k, _)| !k.starts_with(INPUT_VARIABLE_NAME_PREFIX))
+                    .map(|(k, v)| (k, v.to_owned())),
+            );
             env
         };
 
@@ -245,6 +407,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            context: cx.clone(),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
                 id,
@@ -262,6 +425,8 @@ impl TaskTemplate {
                 command: Some(command),
                 args: args_with_substitutions,
                 env,
+                env_files,
+                secrets: self.secrets.clone(),
                 use_new_terminal: self.use_new_terminal,
                 allow_concurrent_runs: self.allow_concurrent_runs,
                 reveal: self.reveal,
@@ -271,11 +436,16 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers: self.problem_matchers.clone(),
+                notify: self.notify,
+                watch: self.watch.clone(),
             },
         })
     }
 }
 
+const INPUT_REFERENCE_START: &str = "${input:";
+
 const MAX_DISPLAY_VARIABLE_LENGTH: usize = 15;
 
 fn truncate_variables(task_variables: &HashMap<String, &str>) -> HashMap<String, String> {
@@ -326,6 +496,16 @@ fn substitute_all_template_variables_in_str<A: AsRef<str>>(
     substituted_variables: &mut HashSet<VariableName>,
 ) -> Option<String> {
     let substituted_string = shellexpand::env_with_context(template_str, |var| {
+        // Inputs without values are left as is, to be prompted for before the task is spawned.
+        if var.starts_with(INPUT_VARIABLE_NAME_PREFIX) {
+            let Some(value) = task_variables.get(var) else {
+                return Ok(None);
+            };
+            if let Some(substituted_variable) = variable_names.get(var) {
+                substituted_variables.insert(substituted_variable.clone());
+            }
+            return Ok(Some(value.as_ref().to_owned()));
+        }
         // Colons denote a default value in case the variable is not set. We want to preserve that default, as otherwise shellexpand will substitute it for us.
         let colon_position = var.find(':').unwrap_or(var.len());
         let (variable_name, default) = var.split_at(colon_position);
@@ -894,6 +1074,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dependencies_resolve_with_dependent_context() {
+        let templates: TaskTemplates = serde_json::from_value(serde_json::json!([
+            { "label": "build", "command": "make", "args": ["$ZED_STEM"] },
+            {
+                "label": "run",
+                "command": "./$ZED_STEM",
+                "depends_on": ["build"],
+                "depends_order": "parallel"
+            }
+        ]))
+        .unwrap();
+        let [build, run] = templates.0.as_slice() else {
+            panic!("expected two templates, got {templates:?}");
+        };
+        assert_eq!(build.depends_on, Vec::<String>::new());
+        assert_eq!(build.depends_order, DependsOrder::Sequence);
+        assert_eq!(run.depends_on, vec!["build".to_string()]);
+        assert_eq!(run.depends_order, DependsOrder::Parallel);
+
+        let context = TaskContext {
+            cwd: Some(PathBuf::from("/project")),
+            task_variables: TaskVariables::from_iter([(VariableName::Stem, "main".to_string())]),
+            project_env: HashMap::default(),
+        };
+        let resolved_run = run.resolve_task(TEST_ID_BASE, &context).unwrap();
+        assert_eq!(resolved_run.context(), &context);
+
+        let resolved_build = build
+            .resolve_task(TEST_ID_BASE, resolved_run.context())
+            .unwrap();
+        assert_eq!(resolved_build.resolved.args, vec!["main".to_string()]);
+        assert_eq!(resolved_build.resolved.cwd, Some(PathBuf::from("/project")));
+    }
+
+    #[test]
+    fn test_inputs_resolve_after_prompting() {
+        let template: TaskTemplate = serde_json::from_value(serde_json::json!({
+            "label": "serve on ${input:port}",
+            "command": "serve",
+            "args": ["--port", "${input:port}", "--root", "${input:root}"],
+            "env": { "MODE": "${input:mode}" },
+            "inputs": [
+                { "id": "port", "description": "Port to serve on", "default": "8080" },
+                { "id": "mode", "type": "pick_string", "options": ["debug", "release"] }
+            ]
+        }))
+        .unwrap();
+
+        let context = TaskContext::default();
+        let inputs = template.unresolved_inputs(&context);
+        assert_eq!(
+            inputs
+                .iter()
+                .map(|input| (input.id.as_str(), input.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("port", TaskInputKind::PromptString),
+                ("root", TaskInputKind::PromptString),
+                ("mode", TaskInputKind::PickString),
+            ]
+        );
+        assert_eq!(inputs[0].default.as_deref(), Some("8080"));
+        assert_eq!(inputs[2].options, vec!["debug", "release"]);
+
+        let unprompted = template.resolve_task(TEST_ID_BASE, &context).unwrap();
+        assert_eq!(unprompted.resolved.args[1], "${input:port}");
+
+        let context = TaskContext {
+            task_variables: TaskVariables::from_iter(
+                [("port", "3000"), ("root", "dist"), ("mode", "release")]
+                    .map(|(id, value)| (VariableName::Input(Cow::Borrowed(id)), value.to_string())),
+            ),
+            ..TaskContext::default()
+        };
+        assert_eq!(template.unresolved_inputs(&context), Vec::new());
+        let resolved = template.resolve_task(TEST_ID_BASE, &context).unwrap();
+        assert_eq!(resolved.resolved.label, "serve on 3000");
+        assert_eq!(
+            resolved.resolved.args,
+            vec!["--port", "3000", "--root", "dist"]
+        );
+        assert_eq!(
+            resolved.resolved.env.get("MODE").map(String::as_str),
+            Some("release")
+        );
+        assert!(
+            resolved
+                .resolved
+                .env
+                .keys()
+                .all(|key| !key.starts_with(INPUT_VARIABLE_NAME_PREFIX)),
+            "inputs should not be set as environment variables"
+        );
+    }
+
     #[test]
     fn test_variable_default_values() {
         let task_with_defaults = TaskTemplate {
@@ -41,6 +41,7 @@ impl VsCodeDebugTaskDefinition {
                 timeout: None,
             }),
             config,
+            start_with: Vec::new(),
         };
         Ok(definition)
     }
@@ -148,7 +149,8 @@ mod tests {
                     "port": 17,
                 }),
                 tcp_connection: None,
-                build: None
+                build: None,
+                start_with: Vec::new(),
             }])
         );
     }
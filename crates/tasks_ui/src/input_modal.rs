@@ -0,0 +1,422 @@
+use std::sync::{Arc, atomic::AtomicBool};
+
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::Editor;
+use futures::channel::oneshot;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, Focusable,
+    PathPromptOptions, Subscription, Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::DirectoryLister;
+use task::{TaskInput, TaskInputKind, TaskVariables, VariableName};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, TaskInputProvider, Workspace, WorkspaceId};
+
+const TASK_INPUTS_KEY: &str = "task_inputs";
+
+/// Asks for task inputs with a modal, and a path prompt for `pick_file` inputs,
+/// suggesting the values last given in the workspace.
+pub(crate) struct TaskInputPrompt;
+
+impl TaskInputProvider for TaskInputPrompt {
+    fn prompt(
+        &self,
+        task_label: String,
+        inputs: Vec<TaskInput>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Option<TaskVariables>> {
+        let key = workspace.database_id().map(task_inputs_key);
+        let project = workspace.project().clone();
+        cx.spawn_in(window, async move |workspace, cx| {
+            let mut last_values = match key.clone() {
+                Some(key) => cx
+                    .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&key) })
+                    .await
+                    .log_err()
+                    .flatten()
+                    .and_then(|values| {
+                        serde_json::from_str::<HashMap<String, String>>(&values).log_err()
+                    })
+                    .unwrap_or_default(),
+                None => HashMap::default(),
+            };
+
+            let mut values = TaskVariables::default();
+            for input in inputs {
+                let suggested_value = last_values
+                    .get(&input.id)
+                    .cloned()
+                    .or_else(|| input.default.clone());
+                let value = match input.kind {
+                    TaskInputKind::PickFile => {
+                        let prompt = workspace
+                            .update_in(cx, |workspace, window, cx| {
+                                workspace.prompt_for_open_path(
+                                    PathPromptOptions {
+                                        files: true,
+                                        directories: false,
+                                        multiple: false,
+                                        prompt: input.description.clone().map(Into::into),
+                                    },
+                                    DirectoryLister::Project(project.clone()),
+                                    window,
+                                    cx,
+                                )
+                            })
+                            .ok()?;
+                        let path = prompt.await.ok()??.into_iter().next()?;
+                        path.to_string_lossy().into_owned()
+                    }
+                    TaskInputKind::PromptString | TaskInputKind::PickString => {
+                        let placeholder_text = match &input.description {
+                            Some(description) => format!("{description} (task `{task_label}`)"),
+                            None => format!("Value of `{}` for task `{task_label}`", input.id),
+                        };
+                        prompt_in_modal(
+                            &workspace,
+                            placeholder_text,
+                            input.clone(),
+                            suggested_value,
+                            cx,
+                        )
+                        .await?
+                    }
+                };
+                last_values.insert(input.id.clone(), value.clone());
+                values.insert(VariableName::Input(input.id.into()), value);
+            }
+
+            if let Some(key) = key
+                && let Some(last_values) = serde_json::to_string(&last_values).log_err()
+            {
+                cx.background_spawn(
+                    async move { KEY_VALUE_STORE.write_kvp(key, last_values).await },
+                )
+                .await
+                .log_err();
+            }
+            Some(values)
+        })
+    }
+}
+
+/// Asks for the value of a `prompt_string` or `pick_string` input with a [`TaskInputModal`].
+/// Resolves to `None` if the modal is dismissed.
+pub(crate) async fn prompt_in_modal(
+    workspace: &WeakEntity<Workspace>,
+    placeholder_text: String,
+    input: TaskInput,
+    suggested_value: Option<String>,
+    cx: &mut AsyncWindowContext,
+) -> Option<String> {
+    let (tx, rx) = oneshot::channel();
+    workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                TaskInputModal::new(placeholder_text, input, suggested_value, tx, window, cx)
+            })
+        })
+        .ok()?;
+    rx.await.ok()
+}
+
+/// Asks for the value of a secret with a [`SecretInputModal`], which masks what's typed.
+/// Resolves to `None` if the modal is dismissed.
+pub(crate) async fn prompt_for_secret(
+    workspace: &WeakEntity<Workspace>,
+    placeholder_text: String,
+    cx: &mut AsyncWindowContext,
+) -> Option<String> {
+    let (tx, rx) = oneshot::channel();
+    workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                SecretInputModal::new(placeholder_text, tx, window, cx)
+            })
+        })
+        .ok()?;
+    rx.await.ok()
+}
+
+fn task_inputs_key(workspace_id: WorkspaceId) -> String {
+    format!("{TASK_INPUTS_KEY}-{}", i64::from(workspace_id))
+}
+
+/// A modal asking for the value of a `prompt_string` or `pick_string` task input.
+struct TaskInputModal {
+    picker: Entity<Picker<TaskInputDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TaskInputModal {
+    fn new(
+        placeholder_text: String,
+        input: TaskInput,
+        suggested_value: Option<String>,
+        tx: oneshot::Sender<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let initial_query = match input.kind {
+            TaskInputKind::PickString => None,
+            TaskInputKind::PromptString | TaskInputKind::PickFile => suggested_value.clone(),
+        };
+        let delegate = TaskInputDelegate {
+            candidates: input
+                .options
+                .iter()
+                .enumerate()
+                .map(|(id, option)| StringMatchCandidate::new(id, option))
+                .collect(),
+            selected_option: suggested_value
+                .and_then(|value| input.options.iter().position(|option| option == &value)),
+            input,
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            placeholder_text: placeholder_text.into(),
+            tx: Some(tx),
+        };
+        let picker = cx.new(|cx| {
+            let picker = Picker::uniform_list(delegate, window, cx).modal(true);
+            if let Some(query) = initial_query {
+                picker.set_query(query, window, cx);
+            }
+            picker
+        });
+        let _subscription = cx.subscribe(&picker, |_, _, _: &DismissEvent, cx| {
+            cx.emit(DismissEvent);
+        });
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl Render for TaskInputModal {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TaskInputModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for TaskInputModal {}
+
+impl Focusable for TaskInputModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for TaskInputModal {}
+
+struct TaskInputDelegate {
+    input: TaskInput,
+    candidates: Vec<StringMatchCandidate>,
+    /// The option picked the last time, selected while nothing is typed.
+    selected_option: Option<usize>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    placeholder_text: Arc<str>,
+    tx: Option<oneshot::Sender<String>>,
+}
+
+impl PickerDelegate for TaskInputDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        match self.input.kind {
+            TaskInputKind::PickString => self.matches.len(),
+            TaskInputKind::PromptString | TaskInputKind::PickFile => 1,
+        }
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _: &mut Window, _: &mut App) -> Arc<str> {
+        self.placeholder_text.clone()
+    }
+
+    fn no_matches_text(&self, _: &mut Window, _: &mut App) -> Option<SharedString> {
+        Some("No matching options".into())
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        self.query = query.clone();
+        if self.input.kind != TaskInputKind::PickString {
+            self.selected_index = 0;
+            return Task::ready(());
+        }
+
+        if query.is_empty() {
+            self.matches = self
+                .candidates
+                .iter()
+                .map(|candidate| StringMatch {
+                    candidate_id: candidate.id,
+                    score: 0.,
+                    positions: Vec::new(),
+                    string: candidate.string.clone(),
+                })
+                .collect();
+            self.selected_index = self.selected_option.unwrap_or(0);
+            return Task::ready(());
+        }
+
+        let candidates = self.candidates.clone();
+        cx.spawn(async move |picker, cx| {
+            let matches = fuzzy::match_strings(
+                &candidates,
+                &query,
+                true,
+                true,
+                100,
+                &AtomicBool::default(),
+                cx.background_executor().clone(),
+            )
+            .await;
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = 0;
+                })
+                .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let value = match self.input.kind {
+            TaskInputKind::PickString => {
+                let Some(selected) = self.matches.get(self.selected_index) else {
+                    return;
+                };
+                selected.string.clone()
+            }
+            TaskInputKind::PromptString | TaskInputKind::PickFile => self.query.clone(),
+        };
+        if let Some(tx) = self.tx.take() {
+            tx.send(value).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let label = match self.input.kind {
+            TaskInputKind::PickString => {
+                let hit = self.matches.get(ix)?;
+                HighlightedLabel::new(hit.string.clone(), hit.positions.clone()).into_any_element()
+            }
+            TaskInputKind::PromptString | TaskInputKind::PickFile if self.query.is_empty() => {
+                Label::new("Use an empty value")
+                    .color(Color::Muted)
+                    .into_any_element()
+            }
+            TaskInputKind::PromptString | TaskInputKind::PickFile => {
+                Label::new(format!("Use \"{}\"", self.query)).into_any_element()
+            }
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(label),
+        )
+    }
+}
+
+/// A modal asking for the value of a secret, without showing it.
+struct SecretInputModal {
+    editor: Entity<Editor>,
+    tx: Option<oneshot::Sender<String>>,
+}
+
+impl SecretInputModal {
+    fn new(
+        placeholder_text: String,
+        tx: oneshot::Sender<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(&placeholder_text, window, cx);
+            editor.set_masked(true, cx);
+            editor
+        });
+        Self {
+            editor,
+            tx: Some(tx),
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let value = self.editor.update(cx, |editor, cx| {
+            let value = editor.text(cx);
+            editor.clear(window, cx);
+            value
+        });
+        if let Some(tx) = self.tx.take() {
+            tx.send(value).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for SecretInputModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("SecretInputModal")
+            .w(rems(34.))
+            .elevation_2(cx)
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(div().px_2().py_1().child(self.editor.clone()))
+    }
+}
+
+impl EventEmitter<DismissEvent> for SecretInputModal {}
+
+impl Focusable for SecretInputModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl ModalView for SecretInputModal {}
@@ -1,21 +1,39 @@
 use std::{path::Path, sync::Arc};
 
 use collections::HashMap;
+use credentials_provider::CredentialsProvider;
 use editor::Editor;
-use gpui::{App, AppContext as _, Context, Entity, Task, Window};
+use gpui::{App, AppContext as _, Context, Entity, Task, Window, actions};
 use project::{Location, TaskContexts, TaskSourceKind, Worktree};
-use task::{RevealTarget, TaskContext, TaskId, TaskTemplate, TaskVariables, VariableName};
-use workspace::Workspace;
+use task::{
+    RevealTarget, TaskContext, TaskId, TaskInput, TaskInputKind, TaskTemplate, TaskVariables,
+    VariableName,
+};
+use workspace::{Toast, Workspace, notifications::NotificationId};
 
+mod input_modal;
 mod modal;
 
+actions!(
+    task,
+    [
+        /// Stores a secret in the system keychain, for tasks to set in their environment with `secrets`.
+        StoreSecret,
+        /// Reruns every task whose last run failed.
+        RerunFailed
+    ]
+);
+
 pub use modal::{Rerun, ShowAttachModal, Spawn, TaskOverrides, TasksModal};
 
 pub fn init(cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>| {
+            workspace.set_task_input_provider(input_modal::TaskInputPrompt);
             workspace
                 .register_action(spawn_task_or_modal)
+                .register_action(store_secret)
+                .register_action(rerun_failed)
                 .register_action(move |workspace, action: &modal::Rerun, window, cx| {
                     if let Some((task_source_kind, mut last_scheduled_task)) = workspace
                         .project()
@@ -140,6 +158,91 @@ fn spawn_task_or_modal(
     }
 }
 
+fn rerun_failed(
+    workspace: &mut Workspace,
+    _: &RerunFailed,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let failed_tasks = workspace
+        .project()
+        .read(cx)
+        .task_store()
+        .read(cx)
+        .task_inventory()
+        .map(|inventory| inventory.read(cx).failed_tasks())
+        .unwrap_or_default();
+    if failed_tasks.is_empty() {
+        workspace.show_toast(
+            Toast::new(
+                NotificationId::unique::<RerunFailed>(),
+                "No failed tasks to rerun",
+            )
+            .autohide(),
+            cx,
+        );
+        return;
+    }
+    for (task_source_kind, failed_task) in failed_tasks {
+        workspace.schedule_resolved_task(task_source_kind, failed_task, false, window, cx);
+    }
+}
+
+fn store_secret(
+    _: &mut Workspace,
+    _: &StoreSecret,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    cx.spawn_in(window, async move |workspace, cx| {
+        let prompt_string = |id: &str| TaskInput {
+            id: id.to_string(),
+            kind: TaskInputKind::PromptString,
+            description: None,
+            options: Vec::new(),
+            default: None,
+        };
+        let Some(name) = input_modal::prompt_in_modal(
+            &workspace,
+            "Name of the secret".to_string(),
+            prompt_string("name"),
+            None,
+            cx,
+        )
+        .await
+        .filter(|name| !name.trim().is_empty()) else {
+            return Ok(());
+        };
+        let Some(value) =
+            input_modal::prompt_for_secret(&workspace, format!("Value of secret `{name}`"), cx)
+                .await
+        else {
+            return Ok(());
+        };
+
+        let credentials_provider = cx.update(|_, cx| <dyn CredentialsProvider>::global(cx))?;
+        credentials_provider
+            .write_credentials(
+                &project::task_secret_url(&name),
+                "zed",
+                value.as_bytes(),
+                cx,
+            )
+            .await?;
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<StoreSecret>(),
+                    format!("Stored secret `{name}` in the keychain"),
+                )
+                .autohide(),
+                cx,
+            )
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 pub fn toggle_modal(
     workspace: &mut Workspace,
     reveal_target: Option<RevealTarget>,
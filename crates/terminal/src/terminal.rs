@@ -42,15 +42,16 @@ use mappings::mouse::{
 use collections::{HashMap, VecDeque};
 use futures::StreamExt;
 use pty_info::{ProcessIdGetter, PtyProcessInfo};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 use smol::channel::{Receiver, Sender};
-use task::{HideStrategy, Shell, SpawnInTerminal};
+use task::{HideStrategy, Problem, Shell, SpawnInTerminal, find_problems};
 use terminal_hyperlinks::RegexSearches;
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
 use urlencoding;
-use util::truncate_and_trailoff;
+use util::{ResultExt as _, truncate_and_trailoff};
 
 use std::{
     borrow::Cow,
@@ -60,7 +61,7 @@ use std::{
     path::PathBuf,
     process::ExitStatus,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use thiserror::Error;
 
@@ -120,6 +121,11 @@ const DEBUG_TERMINAL_HEIGHT: Pixels = px(30.);
 const DEBUG_CELL_WIDTH: Pixels = px(5.);
 const DEBUG_LINE_HEIGHT: Pixels = px(5.);
 
+/// How many lines of a watch task's output are searched for its last rebuild.
+const WATCH_TASK_SCAN_LINES: usize = 2000;
+/// How long to wait for more output before looking for a watch task's rebuilds.
+const WATCH_TASK_SCAN_DEBOUNCE: Duration = Duration::from_millis(200);
+
 ///Upward flowing events, for changing the title and such
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Event {
@@ -132,6 +138,10 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// The terminal's task has finished, and its problems have been collected.
+    TaskFinished,
+    /// The terminal's watch task has started or finished a rebuild.
+    WatchStatusChanged,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -402,6 +412,7 @@ impl TerminalBuilder {
                 window_id,
             },
             child_exited: None,
+            watch_task_scan: None,
         };
 
         Ok(TerminalBuilder {
@@ -614,6 +625,7 @@ impl TerminalBuilder {
                 window_id,
             },
             child_exited: None,
+            watch_task_scan: None,
         };
 
         if cfg!(not(target_os = "windows")) && !activation_script.is_empty() && no_task {
@@ -816,6 +828,7 @@ pub struct Terminal {
     template: CopyTemplate,
     activation_script: Vec<String>,
     child_exited: Option<ExitStatus>,
+    watch_task_scan: Option<Task<()>>,
 }
 
 struct CopyTemplate {
@@ -832,6 +845,22 @@ pub struct TaskState {
     pub status: TaskStatus,
     pub completion_rx: Receiver<Option<ExitStatus>>,
     pub spawned_task: SpawnInTerminal,
+    /// The problems found in the task's output by its problem matchers, once it has finished,
+    /// or for watch tasks, once a rebuild has ended.
+    pub problems: Vec<Problem>,
+    /// The state of the task's rebuilds, if it is a watch task.
+    pub watch_status: Option<WatchStatus>,
+}
+
+/// The state of a long-running watch task, as told by its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchStatus {
+    /// The task has not printed the beginning or the end of a rebuild yet.
+    Starting,
+    /// The task is rebuilding.
+    Compiling,
+    /// The task has finished its last rebuild, and is waiting for changes.
+    Idle,
 }
 
 /// A status of the current terminal tab's task.
@@ -916,6 +945,7 @@ impl Terminal {
             }
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
+                self.schedule_watch_task_scan(cx);
 
                 if let TerminalType::Pty { info, .. } = &mut self.terminal_type {
                     if info.has_changed() {
@@ -2028,6 +2058,71 @@ impl Terminal {
         self.task.as_ref()
     }
 
+    fn schedule_watch_task_scan(&mut self, cx: &mut Context<Self>) {
+        let is_watch_task = self.task.as_ref().is_some_and(|task| {
+            task.status == TaskStatus::Running && task.spawned_task.watch.is_some()
+        });
+        if !is_watch_task || self.watch_task_scan.is_some() {
+            return;
+        }
+        self.watch_task_scan = Some(cx.spawn(async move |terminal, cx| {
+            cx.background_executor()
+                .timer(WATCH_TASK_SCAN_DEBOUNCE)
+                .await;
+            terminal
+                .update(cx, |terminal, cx| {
+                    terminal.watch_task_scan = None;
+                    terminal.scan_watch_task_output(cx);
+                })
+                .ok();
+        }));
+    }
+
+    /// Finds the last rebuild in the watch task's output, and the problems printed during it.
+    fn scan_watch_task_output(&mut self, cx: &mut Context<Self>) {
+        let Some(watch) = self
+            .task
+            .as_ref()
+            .filter(|task| task.status == TaskStatus::Running)
+            .and_then(|task| task.spawned_task.watch.as_ref())
+        else {
+            return;
+        };
+        let (Some(begins), Some(ends)) = (
+            Regex::new(&watch.begins).log_err(),
+            Regex::new(&watch.ends).log_err(),
+        ) else {
+            return;
+        };
+
+        let lines = self.last_n_non_empty_lines(WATCH_TASK_SCAN_LINES);
+        let last_begin = lines.iter().rposition(|line| begins.is_match(line));
+        let last_end = lines.iter().rposition(|line| ends.is_match(line));
+        let (status, rebuild_output) = match (last_begin, last_end) {
+            (Some(begin), Some(end)) if end >= begin => (WatchStatus::Idle, &lines[begin..=end]),
+            (Some(_), _) => (WatchStatus::Compiling, &lines[..0]),
+            (None, Some(end)) => (WatchStatus::Idle, &lines[..=end]),
+            (None, None) => return,
+        };
+
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        let problems = if status == WatchStatus::Idle {
+            find_problems(
+                &task.spawned_task.problem_matchers,
+                &rebuild_output.join("\n"),
+            )
+        } else {
+            task.problems.clone()
+        };
+        if task.watch_status != Some(status) || task.problems != problems {
+            task.watch_status = Some(status);
+            task.problems = problems;
+            cx.emit(Event::WatchStatusChanged);
+        }
+    }
+
     pub fn wait_for_completed_task(&self, cx: &App) -> Task<Option<ExitStatus>> {
         if let Some(task) = self.task() {
             if task.status == TaskStatus::Running {
@@ -2058,6 +2153,13 @@ impl Terminal {
         if let Some(e) = e {
             self.child_exited = Some(e);
         }
+        let output = self
+            .task
+            .as_ref()
+            .filter(|task| {
+                task.status == TaskStatus::Running && !task.spawned_task.problem_matchers.is_empty()
+            })
+            .map(|_| self.get_content());
         let task = match &mut self.task {
             Some(task) => task,
             None => {
@@ -2078,6 +2180,9 @@ impl Terminal {
                 task.status.register_terminal_exit();
             }
         };
+        if let Some(output) = output {
+            task.problems = find_problems(&task.spawned_task.problem_matchers, &output);
+        }
 
         let (finished_successfully, task_line, command_line) = task_summary(task, error_code);
         let mut lines_to_show = Vec::new();
@@ -2095,6 +2200,7 @@ impl Terminal {
             // After the task summary is output once, no more text is appended to the terminal.
             unsafe { append_text_to_term(&mut self.term.lock(), &lines_to_show) };
         }
+        cx.emit(Event::TaskFinished);
 
         match task.spawned_task.hide {
             HideStrategy::Never => {}
@@ -2549,6 +2655,94 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_watch_task_output(cx: &mut TestAppContext) {
+        let terminal = cx.new(|cx| {
+            TerminalBuilder::new_display_only(CursorShape::default(), AlternateScroll::On, None, 0)
+                .unwrap()
+                .subscribe(cx)
+        });
+        terminal.update(cx, |terminal, _| {
+            terminal.task = Some(TaskState {
+                status: TaskStatus::Running,
+                completion_rx: smol::channel::unbounded().1,
+                spawned_task: SpawnInTerminal {
+                    problem_matchers: vec![task::ProblemMatcher {
+                        pattern: r"^(?P<file>[^:]+):(?P<line>\d+):(?P<column>\d+) - (?P<severity>\w+): (?P<message>.*)$".to_string(),
+                        severity: task::ProblemSeverity::Error,
+                    }],
+                    watch: Some(task::WatchMatcher {
+                        begins: "Starting compilation".to_string(),
+                        ends: r"Found \d+ errors?\.".to_string(),
+                    }),
+                    ..SpawnInTerminal::default()
+                },
+                problems: Vec::new(),
+                watch_status: Some(WatchStatus::Starting),
+            });
+        });
+
+        let (event_tx, event_rx) = smol::channel::unbounded::<Event>();
+        cx.update(|cx| {
+            cx.subscribe(&terminal, move |_, event, _| {
+                if *event == Event::WatchStatusChanged {
+                    event_tx.send_blocking(event.clone()).unwrap();
+                }
+            })
+        })
+        .detach();
+        let mut scan = |output: &str| {
+            terminal.update(cx, |terminal, cx| {
+                terminal.write_output(output.as_bytes(), cx);
+                terminal.scan_watch_task_output(cx);
+                let task = terminal.task().unwrap();
+                (task.watch_status.unwrap(), task.problems.clone())
+            })
+        };
+
+        assert_eq!(
+            scan("Watching for changes\r\n"),
+            (WatchStatus::Starting, vec![])
+        );
+        assert!(event_rx.try_recv().is_err());
+
+        assert_eq!(
+            scan("Starting compilation\r\n"),
+            (WatchStatus::Compiling, vec![])
+        );
+        assert_eq!(event_rx.try_recv(), Ok(Event::WatchStatusChanged));
+
+        let problem = Problem {
+            path: "src/main.ts".to_string(),
+            line: 3,
+            column: Some(5),
+            severity: task::ProblemSeverity::Error,
+            message: "Cannot find name 'x'.".to_string(),
+        };
+        assert_eq!(
+            scan("src/main.ts:3:5 - error: Cannot find name 'x'.\r\nFound 1 error.\r\n"),
+            (WatchStatus::Idle, vec![problem.clone()])
+        );
+        assert_eq!(event_rx.try_recv(), Ok(Event::WatchStatusChanged));
+
+        // The problems of the previous rebuild are kept until the next one ends.
+        assert_eq!(
+            scan("Starting compilation\r\n"),
+            (WatchStatus::Compiling, vec![problem])
+        );
+        assert_eq!(event_rx.try_recv(), Ok(Event::WatchStatusChanged));
+
+        assert_eq!(scan("Found 0 errors.\r\n"), (WatchStatus::Idle, vec![]));
+        assert_eq!(event_rx.try_recv(), Ok(Event::WatchStatusChanged));
+
+        // Output that doesn't start or end a rebuild doesn't change anything.
+        assert_eq!(
+            scan("Watching for changes\r\n"),
+            (WatchStatus::Idle, vec![])
+        );
+        assert!(event_rx.try_recv().is_err());
+    }
+
     #[test]
     fn test_rgb_for_index() {
         // Test every possible value in the color cube.
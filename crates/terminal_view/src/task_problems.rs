@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use collections::HashMap;
+use gpui::{App, Entity, WeakEntity};
+use language::{Diagnostic, DiagnosticEntry, DiagnosticSeverity, PointUtf16, Unclipped};
+use project::Project;
+use task::{NotifyStrategy, Problem, ProblemSeverity};
+use terminal::{TaskStatus, Terminal, WatchStatus};
+use util::ResultExt as _;
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+struct TaskFinishedToast;
+
+/// Reports the problems found in the output of the terminal's finished task as
+/// diagnostics, and notifies about the task finishing if it asked for it.
+pub(crate) fn task_finished(
+    terminal: &Entity<Terminal>,
+    project: &WeakEntity<Project>,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut App,
+) {
+    let terminal = terminal.read(cx);
+    let Some(task) = terminal.task() else {
+        return;
+    };
+    let spawned_task = &task.spawned_task;
+    let label = spawned_task.label.clone();
+    let full_label = spawned_task.full_label.clone();
+    let has_problem_matchers = !spawned_task.problem_matchers.is_empty();
+    let notify = spawned_task.notify;
+    let status = task.status;
+    let problems = task.problems.clone();
+    let cwd = spawned_task
+        .cwd
+        .clone()
+        .or_else(|| terminal.working_directory());
+
+    if has_problem_matchers && let Some(project) = project.upgrade() {
+        publish_problems(&project, &label, &full_label, cwd.as_deref(), &problems, cx);
+    }
+
+    let failed = !matches!(status, TaskStatus::Completed { success: true });
+    let should_notify = match notify {
+        NotifyStrategy::Never => false,
+        NotifyStrategy::Always => true,
+        NotifyStrategy::OnFailure => failed || !problems.is_empty(),
+    };
+    if !should_notify {
+        return;
+    }
+
+    let mut message = match status {
+        TaskStatus::Completed { success: true } => format!("Task `{label}` finished successfully"),
+        TaskStatus::Completed { success: false } => format!("Task `{label}` failed"),
+        TaskStatus::Running | TaskStatus::Unknown => format!("Task `{label}` stopped"),
+    };
+    if has_problem_matchers {
+        match problems.len() {
+            0 => message.push_str(" with no problems"),
+            1 => message.push_str(" with 1 problem"),
+            count => message.push_str(&format!(" with {count} problems")),
+        }
+    }
+
+    workspace
+        .update(cx, |workspace, cx| {
+            let toast = Toast::new(NotificationId::unique::<TaskFinishedToast>(), message);
+            let toast = if problems.is_empty() {
+                toast.autohide()
+            } else {
+                toast.on_click("Show Problems", |window, cx| {
+                    window.dispatch_action(Box::new(diagnostics::Deploy), cx);
+                })
+            };
+            workspace.show_toast(toast, cx);
+        })
+        .ok();
+}
+
+/// Reports the problems found in the output of the terminal's watch task as
+/// diagnostics, each time one of its rebuilds ends.
+pub(crate) fn watch_status_changed(
+    terminal: &Entity<Terminal>,
+    project: &WeakEntity<Project>,
+    cx: &mut App,
+) {
+    let terminal = terminal.read(cx);
+    let Some(task) = terminal.task() else {
+        return;
+    };
+    let spawned_task = &task.spawned_task;
+    if task.watch_status != Some(WatchStatus::Idle) || spawned_task.problem_matchers.is_empty() {
+        return;
+    }
+    let label = spawned_task.label.clone();
+    let full_label = spawned_task.full_label.clone();
+    let problems = task.problems.clone();
+    let cwd = spawned_task
+        .cwd
+        .clone()
+        .or_else(|| terminal.working_directory());
+
+    if let Some(project) = project.upgrade() {
+        publish_problems(&project, &label, &full_label, cwd.as_deref(), &problems, cx);
+    }
+}
+
+/// Replaces the diagnostics from the task's previous run with `problems`,
+/// leaving the ones reported by other tasks alone.
+fn publish_problems(
+    project: &Entity<Project>,
+    task_label: &str,
+    task_full_label: &str,
+    cwd: Option<&Path>,
+    problems: &[Problem],
+    cx: &mut App,
+) {
+    let lsp_store = project.read(cx).lsp_store();
+    let Some(inventory) = project
+        .read(cx)
+        .task_store()
+        .read(cx)
+        .task_inventory()
+        .cloned()
+    else {
+        return;
+    };
+    if !project.read(cx).is_local() {
+        return;
+    }
+    let server_id = inventory.update(cx, |inventory, _| {
+        inventory.task_problems_server_id(task_full_label)
+    });
+    let project = project.read(cx);
+
+    let mut entries_by_path = HashMap::<PathBuf, Vec<_>>::default();
+    for (path, _, _) in project
+        .diagnostic_summaries(false, cx)
+        .filter(|(_, summary_server_id, _)| *summary_server_id == server_id)
+    {
+        if let Some(abs_path) = project.absolute_path(&path, cx) {
+            entries_by_path.entry(abs_path).or_default();
+        }
+    }
+
+    for (group_id, problem) in problems.iter().enumerate() {
+        let path = Path::new(&problem.path);
+        let abs_path = match cwd {
+            Some(cwd) if path.is_relative() => cwd.join(path),
+            _ => path.to_path_buf(),
+        };
+        let row = problem.line.saturating_sub(1);
+        let column = problem.column.unwrap_or(1).saturating_sub(1);
+        let message = if problem.message.is_empty() {
+            format!("Problem reported by task `{task_label}`")
+        } else {
+            problem.message.clone()
+        };
+        entries_by_path
+            .entry(abs_path)
+            .or_default()
+            .push(DiagnosticEntry {
+                range: Unclipped(PointUtf16::new(row, column))
+                    ..Unclipped(PointUtf16::new(row, u32::MAX)),
+                diagnostic: Diagnostic {
+                    source: Some(task_label.to_string()),
+                    severity: diagnostic_severity(problem.severity),
+                    message,
+                    group_id,
+                    is_primary: true,
+                    ..Diagnostic::default()
+                },
+            });
+    }
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        for (abs_path, entries) in entries_by_path {
+            lsp_store
+                .update_diagnostic_entries(server_id, abs_path, None, None, entries, cx)
+                .log_err();
+        }
+    });
+}
+
+fn diagnostic_severity(severity: ProblemSeverity) -> DiagnosticSeverity {
+    match severity {
+        ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+        ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+        ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+        ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+    }
+}
@@ -25,7 +25,7 @@ use terminal::{
     },
     terminal_settings::TerminalSettings,
 };
-use theme::{ActiveTheme, Theme, ThemeSettings};
+use theme::{AccessibilitySettings, ActiveTheme, Theme, ThemeSettings};
 use ui::utils::ensure_minimum_contrast;
 use ui::{ParentElement, Tooltip};
 use util::ResultExt;
@@ -867,7 +867,9 @@ impl Element for TerminalElement {
                 let buffer_font_size = settings.buffer_font_size(cx);
 
                 let terminal_settings = TerminalSettings::get_global(cx);
-                let minimum_contrast = terminal_settings.minimum_contrast;
+                let accessibility = *AccessibilitySettings::get_global(cx);
+                let minimum_contrast =
+                    accessibility.minimum_contrast(terminal_settings.minimum_contrast);
 
                 let font_family = terminal_settings.font_family.as_ref().map_or_else(
                     || settings.buffer_font.family.clone(),
@@ -1108,14 +1110,19 @@ impl Element for TerminalElement {
                                 AlacCursorShape::Hidden => unreachable!(),
                             };
 
-                            CursorLayout::new(
+                            let cursor = CursorLayout::new(
                                 cursor_position,
                                 block_width,
                                 dimensions.line_height,
                                 theme.players().local().cursor,
                                 shape,
                                 text,
-                            )
+                            );
+                            if accessibility.high_contrast {
+                                cursor.with_thickness(CursorLayout::HIGH_CONTRAST_THICKNESS)
+                            } else {
+                                cursor
+                            }
                         },
                     )
                 };
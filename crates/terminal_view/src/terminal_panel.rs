@@ -58,6 +58,7 @@ pub fn init(cx: &mut App) {
         |workspace: &mut Workspace, _window, _: &mut Context<Workspace>| {
             workspace.register_action(TerminalPanel::new_terminal);
             workspace.register_action(TerminalPanel::open_terminal);
+            workspace.register_action(TerminalPanel::reveal_task_output);
             workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
                 if is_enabled_in_workspace(workspace, cx) {
                     workspace.toggle_panel_focus::<TerminalPanel>(window, cx);
@@ -290,6 +291,10 @@ impl TerminalPanel {
                 .ok();
         }
 
+        crate::watch_tasks::restore_watch_tasks(workspace.clone(), &mut cx)
+            .await
+            .log_err();
+
         // Since panels/docks are loaded outside from the workspace, we cleanup here, instead of through the workspace.
         if let Some(workspace) = workspace.upgrade() {
             let cleanup_task = workspace.update_in(&mut cx, |workspace, window, cx| {
@@ -660,6 +665,14 @@ impl TerminalPanel {
         &self,
         label: &str,
         cx: &mut App,
+    ) -> Vec<(usize, Entity<Pane>, Entity<TerminalView>)> {
+        self.task_terminals(|spawned_task| spawned_task.full_label == label, cx)
+    }
+
+    fn task_terminals(
+        &self,
+        predicate: impl Fn(&SpawnInTerminal) -> bool,
+        cx: &mut App,
     ) -> Vec<(usize, Entity<Pane>, Entity<TerminalView>)> {
         let Some(workspace) = self.workspace.upgrade() else {
             return Vec::new();
@@ -672,7 +685,7 @@ impl TerminalPanel {
                 .filter_map(|(index, item)| Some((index, item.act_as::<TerminalView>(cx)?)))
                 .filter_map(|(index, terminal_view)| {
                     let task_state = terminal_view.read(cx).terminal().read(cx).task()?;
-                    if &task_state.spawned_task.full_label == label {
+                    if predicate(&task_state.spawned_task) {
                         Some((index, terminal_view))
                     } else {
                         None
@@ -698,6 +711,54 @@ impl TerminalPanel {
             .collect()
     }
 
+    /// Finds the tab showing `terminal`, and whether it is in the terminal panel
+    /// rather than in the center of the workspace.
+    pub(crate) fn find_task_terminal(
+        &self,
+        terminal: &Entity<Terminal>,
+        cx: &mut App,
+    ) -> Option<(usize, Entity<Pane>, bool)> {
+        let label = terminal.read(cx).task()?.spawned_task.full_label.clone();
+        let (index, pane, _) = self
+            .terminals_for_task(&label, cx)
+            .into_iter()
+            .find(|(_, _, terminal_view)| terminal_view.read(cx).terminal() == terminal)?;
+        let in_terminal_panel = self.center.panes().contains(&&pane);
+        Some((index, pane, in_terminal_panel))
+    }
+
+    fn reveal_task_output(
+        workspace: &mut Workspace,
+        action: &zed_actions::RevealOutput,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(terminal_panel) = workspace.panel::<Self>(cx) else {
+            return;
+        };
+        let Some((index, pane, _)) = terminal_panel.update(cx, |panel, cx| {
+            panel
+                .task_terminals(
+                    |spawned_task| {
+                        action
+                            .task_id
+                            .as_ref()
+                            .is_none_or(|task_id| &spawned_task.id.0 == task_id)
+                    },
+                    cx,
+                )
+                .pop()
+        }) else {
+            return;
+        };
+        if terminal_panel.read(cx).center.panes().contains(&&pane) {
+            workspace.open_panel::<Self>(window, cx);
+        }
+        pane.update(cx, |pane, cx| {
+            pane.activate_item(index, true, true, window, cx)
+        });
+    }
+
     fn activate_terminal_view(
         &self,
         pane: &Entity<Pane>,
@@ -1,10 +1,12 @@
 mod persistence;
+mod task_problems;
 pub mod terminal_element;
 pub mod terminal_panel;
 mod terminal_path_like_target;
 pub mod terminal_scrollbar;
 mod terminal_slash_command;
 pub mod terminal_tab_tooltip;
+pub mod watch_tasks;
 
 use assistant_slash_command::SlashCommandRegistry;
 use editor::{EditorSettings, actions::SelectAll};
@@ -867,6 +869,7 @@ fn subscribe_for_terminal_events(
     window: &mut Window,
     cx: &mut Context<TerminalView>,
 ) -> Vec<Subscription> {
+    watch_tasks::watch_terminal(terminal, &workspace, cx);
     let terminal_subscription = cx.observe(terminal, |_, _, cx| cx.notify());
     let mut previous_cwd = None;
     let terminal_events_subscription = cx.subscribe_in(
@@ -962,6 +965,12 @@ fn subscribe_for_terminal_events(
                 },
                 Event::BreadcrumbsChanged => cx.emit(ItemEvent::UpdateBreadcrumbs),
                 Event::CloseTerminal => cx.emit(ItemEvent::CloseItem),
+                Event::TaskFinished => {
+                    task_problems::task_finished(terminal, &terminal_view.project, &workspace, cx)
+                }
+                Event::WatchStatusChanged => {
+                    task_problems::watch_status_changed(terminal, &terminal_view.project, cx)
+                }
                 Event::SelectionsChanged => {
                     window.invalidate_character_coordinates();
                     cx.emit(SearchEvent::ActiveMatchChanged)
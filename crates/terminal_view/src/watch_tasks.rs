@@ -0,0 +1,625 @@
+use std::path::PathBuf;
+
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{AsyncWindowContext, Entity, EventEmitter, Subscription, WeakEntity};
+use project::{Inventory, TaskSourceKind, WorktreeId};
+use serde::{Deserialize, Serialize};
+use task::{
+    Problem, ProblemSeverity, ResolvedTask, TaskContext, TaskId, TaskTemplate, TaskVariables,
+};
+use terminal::{Event, TaskStatus, Terminal, WatchStatus};
+use ui::{ButtonLike, Tooltip, h_flex, prelude::*};
+use util::ResultExt as _;
+use workspace::{StatusItemView, ToolbarItemEvent, Workspace, WorkspaceId, item::ItemHandle};
+
+use crate::terminal_panel::TerminalPanel;
+
+const WATCH_TASKS_KEY: &str = "watch_tasks";
+
+/// The status bar item that displays the state of the workspace's watch tasks.
+pub struct WatchTaskStatus {
+    workspace: WeakEntity<Workspace>,
+    tasks: Vec<WatchedTask>,
+    /// Sources of the watch tasks restored from the previous session, by their ids.
+    restored_sources: HashMap<TaskId, (TaskSourceKind, ResolvedTask)>,
+    _observe_inventory: Option<Subscription>,
+}
+
+struct WatchedTask {
+    terminal: WeakEntity<Terminal>,
+    /// Where the task came from, to restart it when its definition changes.
+    source: Option<(TaskSourceKind, ResolvedTask)>,
+    _subscriptions: [Subscription; 2],
+}
+
+/// A running watch task, stored to be started again when the workspace is reopened.
+///
+/// The project environment isn't stored, as it may hold secrets: it is resolved
+/// again for the task's directory when the task is restored.
+#[derive(Serialize, Deserialize)]
+struct SerializedWatchTask {
+    template: TaskTemplate,
+    cwd: Option<PathBuf>,
+    task_variables: HashMap<String, String>,
+}
+
+impl SerializedWatchTask {
+    fn new(resolved_task: &ResolvedTask) -> Self {
+        let context = resolved_task.context();
+        Self {
+            template: resolved_task.original_task().clone(),
+            cwd: context.cwd.clone(),
+            task_variables: context
+                .task_variables
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn context(&self, project_env: HashMap<String, String>) -> TaskContext {
+        TaskContext {
+            cwd: self.cwd.clone(),
+            task_variables: TaskVariables::from_iter(
+                self.task_variables
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.parse().ok()?, value.clone()))),
+            ),
+            project_env,
+        }
+    }
+}
+
+impl WatchTaskStatus {
+    pub fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let inventory = workspace
+            .project()
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned();
+        Self {
+            workspace: workspace.weak_handle(),
+            tasks: Vec::new(),
+            restored_sources: HashMap::default(),
+            _observe_inventory: inventory
+                .map(|inventory| cx.observe_in(&inventory, window, Self::restart_changed_tasks)),
+        }
+    }
+
+    /// Starts tracking the terminal's task, if it is a watch task.
+    pub(crate) fn watch_terminal(&mut self, terminal: Entity<Terminal>, cx: &mut Context<Self>) {
+        let Some(task) = terminal.read(cx).task() else {
+            return;
+        };
+        if task.spawned_task.watch.is_none()
+            || self
+                .tasks
+                .iter()
+                .any(|watched| watched.terminal.entity_id() == terminal.entity_id())
+        {
+            return;
+        }
+
+        let task_id = task.spawned_task.id.clone();
+        let source = self.restored_sources.remove(&task_id).or_else(|| {
+            self.inventory(cx)?
+                .read(cx)
+                .last_scheduled_task(Some(&task_id))
+        });
+        let terminal_id = terminal.entity_id();
+        self.tasks.push(WatchedTask {
+            terminal: terminal.downgrade(),
+            source,
+            _subscriptions: [
+                cx.subscribe(&terminal, |this, _, event, cx| {
+                    if matches!(event, Event::WatchStatusChanged | Event::TaskFinished) {
+                        this.serialize(cx);
+                        cx.notify();
+                    }
+                }),
+                cx.observe_release(&terminal, move |this, _, cx| {
+                    this.tasks
+                        .retain(|watched| watched.terminal.entity_id() != terminal_id);
+                    this.serialize(cx);
+                    cx.notify();
+                }),
+            ],
+        });
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn inventory(&self, cx: &App) -> Option<Entity<Inventory>> {
+        let workspace = self.workspace.upgrade()?;
+        let project = workspace.read(cx).project().read(cx);
+        project.task_store().read(cx).task_inventory().cloned()
+    }
+
+    /// Restarts the running watch tasks whose definitions have changed.
+    fn restart_changed_tasks(
+        &mut self,
+        inventory: Entity<Inventory>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let mut restarts = Vec::new();
+        for watched in &self.tasks {
+            let Some(terminal) = watched.terminal.upgrade() else {
+                continue;
+            };
+            let Some((kind, resolved_task)) = &watched.source else {
+                continue;
+            };
+            if terminal
+                .read(cx)
+                .task()
+                .is_none_or(|task| task.status != TaskStatus::Running)
+            {
+                continue;
+            }
+            let worktree_id = task_worktree(&workspace, kind, resolved_task, cx);
+            let template = inventory.read(cx).task_template_by_label(
+                None,
+                worktree_id,
+                &resolved_task.original_task().label,
+                cx,
+            );
+            restarts.push((terminal, kind.clone(), resolved_task.clone(), template));
+        }
+        if restarts.is_empty() {
+            return;
+        }
+
+        cx.spawn_in(window, async move |this, cx| {
+            for (terminal, kind, resolved_task, template) in restarts {
+                let Some(template) = template.await else {
+                    continue;
+                };
+                if &template == resolved_task.original_task() {
+                    continue;
+                }
+                let Some(new_task) =
+                    template.resolve_task(&kind.to_id_base(), resolved_task.context())
+                else {
+                    continue;
+                };
+
+                let terminal_id = terminal.entity_id();
+                this.update(cx, |this, _| {
+                    for watched in &mut this.tasks {
+                        if watched.terminal.entity_id() == terminal_id {
+                            watched.source = None;
+                        }
+                    }
+                })?;
+                terminal.update(cx, |terminal, _| terminal.kill_active_task())?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    workspace.schedule_resolved_task(kind, new_task, false, window, cx)
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn serialize(&self, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).database_id())
+        else {
+            return;
+        };
+        let tasks = self
+            .tasks
+            .iter()
+            .filter_map(|watched| {
+                let terminal = watched.terminal.upgrade()?;
+                let (_, resolved_task) = watched.source.as_ref()?;
+                (terminal.read(cx).task()?.status == TaskStatus::Running)
+                    .then(|| SerializedWatchTask::new(resolved_task))
+            })
+            .collect::<Vec<_>>();
+        let Some(tasks) = serde_json::to_string(&tasks).log_err() else {
+            return;
+        };
+        let key = watch_tasks_key(workspace_id);
+        cx.background_spawn(async move { KEY_VALUE_STORE.write_kvp(key, tasks).await })
+            .detach_and_log_err(cx);
+    }
+
+    fn reveal(&self, terminal: &Entity<Terminal>, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let Some(terminal_panel) = workspace.read(cx).panel::<TerminalPanel>(cx) else {
+            return;
+        };
+        let Some((index, pane, in_terminal_panel)) =
+            terminal_panel.update(cx, |panel, cx| panel.find_task_terminal(terminal, cx))
+        else {
+            return;
+        };
+        if in_terminal_panel {
+            workspace.update(cx, |workspace, cx| {
+                workspace.open_panel::<TerminalPanel>(window, cx)
+            });
+        }
+        pane.update(cx, |pane, cx| {
+            pane.activate_item(index, true, true, window, cx)
+        });
+    }
+
+    fn render_task(
+        &self,
+        index: usize,
+        terminal: Entity<Terminal>,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement + use<>> {
+        let task = terminal.read(cx).task()?;
+        let label = task.spawned_task.label.clone();
+        let state = WatchTaskState::new(task.status, task.watch_status, &task.problems);
+        let icon = match state {
+            WatchTaskState::Compiling => Icon::new(IconName::ArrowCircle)
+                .size(IconSize::Small)
+                .color(Color::Accent)
+                .with_rotate_animation(2)
+                .into_any_element(),
+            WatchTaskState::Errors(_) | WatchTaskState::Failed => Icon::new(IconName::XCircle)
+                .size(IconSize::Small)
+                .color(Color::Error)
+                .into_any_element(),
+            WatchTaskState::Warnings(_) => Icon::new(IconName::Warning)
+                .size(IconSize::Small)
+                .color(Color::Warning)
+                .into_any_element(),
+            WatchTaskState::Watching => Icon::new(IconName::Check)
+                .size(IconSize::Small)
+                .color(Color::Success)
+                .into_any_element(),
+            WatchTaskState::Starting => Icon::new(IconName::Eye)
+                .size(IconSize::Small)
+                .color(Color::Muted)
+                .into_any_element(),
+            WatchTaskState::Exited => Icon::new(IconName::Stop)
+                .size(IconSize::Small)
+                .color(Color::Muted)
+                .into_any_element(),
+        };
+        let status = state.label();
+
+        Some(
+            ButtonLike::new(("watch-task", index))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(icon)
+                        .child(Label::new(format!("{label}: {status}")).size(LabelSize::Small)),
+                )
+                .tooltip(Tooltip::text("Show Task Output"))
+                .on_click(
+                    cx.listener(move |this, _, window, cx| this.reveal(&terminal, window, cx)),
+                ),
+        )
+    }
+}
+
+/// What the status bar shows for a watch task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchTaskState {
+    Starting,
+    Compiling,
+    /// The last rebuild ended with errors.
+    Errors(usize),
+    /// The last rebuild ended with warnings, but no errors.
+    Warnings(usize),
+    /// The last rebuild ended without problems.
+    Watching,
+    Exited,
+    Failed,
+}
+
+impl WatchTaskState {
+    fn new(status: TaskStatus, watch_status: Option<WatchStatus>, problems: &[Problem]) -> Self {
+        let error_count = problems
+            .iter()
+            .filter(|problem| problem.severity == ProblemSeverity::Error)
+            .count();
+        let warning_count = problems.len() - error_count;
+        match (status, watch_status) {
+            (TaskStatus::Running, Some(WatchStatus::Compiling)) => Self::Compiling,
+            (TaskStatus::Running, Some(WatchStatus::Idle)) if error_count > 0 => {
+                Self::Errors(error_count)
+            }
+            (TaskStatus::Running, Some(WatchStatus::Idle)) if warning_count > 0 => {
+                Self::Warnings(warning_count)
+            }
+            (TaskStatus::Running, Some(WatchStatus::Idle)) => Self::Watching,
+            (TaskStatus::Running, _) => Self::Starting,
+            (TaskStatus::Completed { success: true }, _) => Self::Exited,
+            (TaskStatus::Completed { success: false } | TaskStatus::Unknown, _) => Self::Failed,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Starting => "starting".to_string(),
+            Self::Compiling => "compiling".to_string(),
+            Self::Errors(count) => pluralize(*count, "error"),
+            Self::Warnings(count) => pluralize(*count, "warning"),
+            Self::Watching => "watching".to_string(),
+            Self::Exited => "exited".to_string(),
+            Self::Failed => "failed".to_string(),
+        }
+    }
+}
+
+impl Render for WatchTaskStatus {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let terminals = self
+            .tasks
+            .iter()
+            .filter_map(|watched| watched.terminal.upgrade())
+            .collect::<Vec<_>>();
+        h_flex().gap_2().children(
+            terminals
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, terminal)| self.render_task(index, terminal, cx)),
+        )
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for WatchTaskStatus {}
+
+impl StatusItemView for WatchTaskStatus {
+    fn set_active_pane_item(
+        &mut self,
+        _: Option<&dyn ItemHandle>,
+        _: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+}
+
+/// Starts tracking the terminal's task in the workspace's status bar, if it is a watch task.
+pub(crate) fn watch_terminal(
+    terminal: &Entity<Terminal>,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut App,
+) {
+    if terminal
+        .read(cx)
+        .task()
+        .is_none_or(|task| task.spawned_task.watch.is_none())
+    {
+        return;
+    }
+    let terminal = terminal.clone();
+    let workspace = workspace.clone();
+    // Terminal views are often created while the workspace is being updated.
+    cx.defer(move |cx| {
+        let Some(status) = workspace.upgrade().and_then(|workspace| {
+            workspace
+                .read(cx)
+                .status_bar()
+                .read(cx)
+                .item_of_type::<WatchTaskStatus>()
+        }) else {
+            return;
+        };
+        status.update(cx, |status, cx| status.watch_terminal(terminal, cx));
+    });
+}
+
+/// Starts the watch tasks that were running when the workspace was last closed.
+pub(crate) async fn restore_watch_tasks(
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<()> {
+    let Some(workspace_id) = workspace.read_with(cx, |workspace, _| workspace.database_id())?
+    else {
+        return Ok(());
+    };
+    let key = watch_tasks_key(workspace_id);
+    let Some(serialized) = cx
+        .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&key) })
+        .await?
+    else {
+        return Ok(());
+    };
+    let tasks = serde_json::from_str::<Vec<SerializedWatchTask>>(&serialized)?;
+    let environment = workspace.read_with(cx, |workspace, cx| {
+        workspace.project().read(cx).environment().clone()
+    })?;
+
+    for task in tasks {
+        let project_env = match task.cwd.clone() {
+            Some(cwd) => environment
+                .update(cx, |environment, cx| {
+                    environment.get_directory_environment(cwd.into(), cx)
+                })?
+                .await
+                .unwrap_or_default(),
+            None => HashMap::default(),
+        };
+        let kind = TaskSourceKind::UserInput;
+        let Some(resolved_task) = task
+            .template
+            .resolve_task(&kind.to_id_base(), &task.context(project_env))
+        else {
+            continue;
+        };
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            if let Some(status) = workspace
+                .status_bar()
+                .read(cx)
+                .item_of_type::<WatchTaskStatus>()
+            {
+                status.update(cx, |status, _| {
+                    status.restored_sources.insert(
+                        resolved_task.id.clone(),
+                        (kind.clone(), resolved_task.clone()),
+                    );
+                });
+            }
+            workspace.schedule_resolved_task(kind, resolved_task, true, window, cx);
+        })?;
+    }
+    Ok(())
+}
+
+fn task_worktree(
+    workspace: &Entity<Workspace>,
+    kind: &TaskSourceKind,
+    resolved_task: &ResolvedTask,
+    cx: &App,
+) -> Option<WorktreeId> {
+    match kind {
+        TaskSourceKind::Worktree { id, .. } | TaskSourceKind::Manifest { id, .. } => Some(*id),
+        _ => {
+            let cwd = resolved_task.context().cwd.as_deref()?;
+            let (worktree, _) = workspace
+                .read(cx)
+                .project()
+                .read(cx)
+                .find_worktree(cwd, cx)?;
+            Some(worktree.read(cx).id())
+        }
+    }
+}
+
+fn watch_tasks_key(workspace_id: WorkspaceId) -> String {
+    format!("{WATCH_TASKS_KEY}-{}", i64::from(workspace_id))
+}
+
+fn pluralize(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(severity: ProblemSeverity) -> Problem {
+        Problem {
+            path: "src/main.ts".to_string(),
+            line: 1,
+            column: None,
+            severity,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_watch_task_state() {
+        let running = TaskStatus::Running;
+        let error = problem(ProblemSeverity::Error);
+        let warning = problem(ProblemSeverity::Warning);
+
+        let state = WatchTaskState::new(running, Some(WatchStatus::Starting), &[]);
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Starting, "starting")
+        );
+
+        let state = WatchTaskState::new(running, Some(WatchStatus::Compiling), &[]);
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Compiling, "compiling")
+        );
+
+        let problems = [error.clone(), warning.clone()];
+        let state = WatchTaskState::new(running, Some(WatchStatus::Idle), &problems);
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Errors(1), "1 error")
+        );
+
+        // The previous rebuild's problems are kept while compiling, but not shown.
+        let state = WatchTaskState::new(running, Some(WatchStatus::Compiling), &problems);
+        assert_eq!(state, WatchTaskState::Compiling);
+
+        let problems = [warning.clone(), warning];
+        let state = WatchTaskState::new(running, Some(WatchStatus::Idle), &problems);
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Warnings(2), "2 warnings")
+        );
+
+        let state = WatchTaskState::new(running, Some(WatchStatus::Idle), &[]);
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Watching, "watching")
+        );
+
+        let state = WatchTaskState::new(
+            TaskStatus::Completed { success: true },
+            Some(WatchStatus::Idle),
+            &[],
+        );
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Exited, "exited")
+        );
+
+        let state = WatchTaskState::new(
+            TaskStatus::Completed { success: false },
+            Some(WatchStatus::Idle),
+            &[error],
+        );
+        assert_eq!(
+            (state, state.label().as_str()),
+            (WatchTaskState::Failed, "failed")
+        );
+    }
+
+    #[test]
+    fn test_serialized_watch_task() {
+        let template = TaskTemplate {
+            label: "tsc: $ZED_WORKTREE_ROOT".to_string(),
+            command: "tsc".to_string(),
+            args: vec!["--watch".to_string()],
+            watch: Some(task::WatchMatcher {
+                begins: "Starting compilation".to_string(),
+                ends: "Watching for file changes".to_string(),
+            }),
+            ..TaskTemplate::default()
+        };
+        let context = TaskContext {
+            cwd: Some(PathBuf::from("/project")),
+            task_variables: TaskVariables::from_iter([(
+                task::VariableName::WorktreeRoot,
+                "/project".to_string(),
+            )]),
+            project_env: HashMap::from_iter([("NODE_ENV".to_string(), "development".to_string())]),
+        };
+        let kind = TaskSourceKind::UserInput;
+        let resolved_task = template.resolve_task(&kind.to_id_base(), &context).unwrap();
+
+        let serialized = serde_json::to_string(&SerializedWatchTask::new(&resolved_task)).unwrap();
+        assert!(!serialized.contains("NODE_ENV"));
+        let deserialized = serde_json::from_str::<SerializedWatchTask>(&serialized).unwrap();
+        let restored_task = deserialized
+            .template
+            .resolve_task(
+                &kind.to_id_base(),
+                &deserialized.context(context.project_env.clone()),
+            )
+            .unwrap();
+        assert_eq!(restored_task.id, resolved_task.id);
+        assert_eq!(restored_task.resolved_label, resolved_task.resolved_label);
+        assert_eq!(restored_task.resolved, resolved_task.resolved);
+    }
+}
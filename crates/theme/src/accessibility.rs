@@ -0,0 +1,118 @@
+use gpui::App;
+use settings::{AccessibilityPreference, Settings};
+
+/// The minimum APCA contrast enforced between text and its background in high contrast mode,
+/// regardless of the `minimum_contrast` settings.
+pub const HIGH_CONTRAST_MINIMUM_CONTRAST: f32 = 75.;
+
+/// Settings for reduced motion and high contrast, with the `system` preference resolved
+/// against the operating system's accessibility preferences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccessibilitySettings {
+    /// Whether animations are skipped and the cursor doesn't blink.
+    pub reduce_motion: bool,
+    /// Whether cursors and focus rings are drawn thicker, and text is drawn with a minimum
+    /// contrast against its background.
+    pub high_contrast: bool,
+    reduced_motion_preference: AccessibilityPreference,
+    high_contrast_preference: AccessibilityPreference,
+}
+
+impl AccessibilitySettings {
+    fn new(
+        reduced_motion_preference: AccessibilityPreference,
+        high_contrast_preference: AccessibilityPreference,
+        cx: &App,
+    ) -> Self {
+        Self {
+            reduce_motion: reduced_motion_preference.is_on(cx.should_reduce_motion()),
+            high_contrast: high_contrast_preference.is_on(cx.should_increase_contrast()),
+            reduced_motion_preference,
+            high_contrast_preference,
+        }
+    }
+
+    /// Resolves the `system` preferences again, for when the operating system's accessibility
+    /// preferences may have changed.
+    pub fn reload_system_preferences(cx: &mut App) {
+        let settings = *Self::get_global(cx);
+        let reloaded = Self::new(
+            settings.reduced_motion_preference,
+            settings.high_contrast_preference,
+            cx,
+        );
+        if reloaded != settings {
+            Self::override_global(reloaded, cx);
+            cx.set_reduce_motion(reloaded.reduce_motion);
+            cx.refresh_windows();
+        }
+    }
+
+    /// Returns the minimum contrast to draw text with, given the one from the settings.
+    pub fn minimum_contrast(&self, minimum_contrast: f32) -> f32 {
+        if self.high_contrast {
+            minimum_contrast.max(HIGH_CONTRAST_MINIMUM_CONTRAST)
+        } else {
+            minimum_contrast
+        }
+    }
+}
+
+impl Settings for AccessibilitySettings {
+    fn from_settings(content: &settings::SettingsContent, cx: &mut App) -> Self {
+        let accessibility = content.accessibility.clone().unwrap();
+        Self::new(
+            accessibility.reduced_motion.unwrap(),
+            accessibility.high_contrast.unwrap(),
+            cx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use settings::{AccessibilitySettingsContent, SettingsStore};
+
+    #[gpui::test]
+    fn test_high_contrast_minimum_contrast(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            crate::init(crate::LoadThemes::JustBase, cx);
+        });
+
+        let set_high_contrast = |preference, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings(cx, |settings| {
+                        settings.accessibility = Some(AccessibilitySettingsContent {
+                            high_contrast: Some(preference),
+                            ..Default::default()
+                        });
+                    });
+                });
+            });
+        };
+
+        set_high_contrast(AccessibilityPreference::Off, cx);
+        cx.update(|cx| {
+            let settings = AccessibilitySettings::get_global(cx);
+            assert!(!settings.high_contrast);
+            assert_eq!(settings.minimum_contrast(0.), 0.);
+            assert_eq!(settings.minimum_contrast(45.), 45.);
+        });
+
+        set_high_contrast(AccessibilityPreference::On, cx);
+        cx.update(|cx| {
+            let settings = AccessibilitySettings::get_global(cx);
+            assert!(settings.high_contrast);
+            assert_eq!(
+                settings.minimum_contrast(0.),
+                HIGH_CONTRAST_MINIMUM_CONTRAST
+            );
+            assert_eq!(settings.minimum_contrast(90.), 90.);
+        });
+    }
+}
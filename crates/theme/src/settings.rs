@@ -780,6 +780,21 @@ pub fn reset_agent_buffer_font_size(cx: &mut App) {
     }
 }
 
+/// Zooms the whole interface by adjusting the UI, buffer, and agent panel font sizes by the same
+/// amount.
+pub fn adjust_zoom(cx: &mut App, delta: Pixels) {
+    adjust_ui_font_size(cx, |size| size + delta);
+    adjust_buffer_font_size(cx, |size| size + delta);
+    adjust_agent_ui_font_size(cx, |size| size + delta);
+}
+
+/// Resets the UI, buffer, and agent panel font sizes to their default values.
+pub fn reset_zoom(cx: &mut App) {
+    reset_ui_font_size(cx);
+    reset_buffer_font_size(cx);
+    reset_agent_ui_font_size(cx);
+}
+
 /// Ensures font size is within the valid range.
 pub fn clamp_font_size(size: Pixels) -> Pixels {
     size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
@@ -8,6 +8,7 @@
 //!
 //! A theme is a collection of colors used to build a consistent appearance for UI components across the application.
 
+mod accessibility;
 mod default_colors;
 mod fallback_themes;
 mod font_family_cache;
@@ -34,6 +35,7 @@ use gpui::{
 use serde::Deserialize;
 use uuid::Uuid;
 
+pub use crate::accessibility::*;
 pub use crate::default_colors::*;
 use crate::fallback_themes::apply_theme_color_defaults;
 pub use crate::font_family_cache::*;
@@ -106,7 +108,9 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
     }
 
     ThemeSettings::register(cx);
+    AccessibilitySettings::register(cx);
     FontFamilyCache::init_global(cx);
+    cx.set_reduce_motion(AccessibilitySettings::get_global(cx).reduce_motion);
 
     let mut prev_buffer_font_size_settings =
         ThemeSettings::get_global(cx).buffer_font_size_settings();
@@ -142,6 +146,9 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
             prev_agent_buffer_font_size_settings = agent_buffer_font_size_settings;
             reset_agent_buffer_font_size(cx);
         }
+
+        let reduce_motion = AccessibilitySettings::get_global(cx).reduce_motion;
+        cx.set_reduce_motion(reduce_motion);
     })
     .detach();
 }
@@ -393,6 +393,64 @@ impl TitleBar {
             );
         }
 
+        let (is_presenting, presenter_id, is_following_presenter) = self
+            .workspace
+            .read_with(cx, |workspace, _| {
+                (
+                    workspace.is_presenting(),
+                    workspace.presenter(),
+                    workspace.is_following_presenter(),
+                )
+            })
+            .unwrap_or_default();
+
+        if is_shared && !is_connecting_to_project {
+            children.push(
+                Button::new(
+                    "toggle_presenting",
+                    if is_presenting {
+                        "Stop Presenting"
+                    } else {
+                        "Present"
+                    },
+                )
+                .tooltip(Tooltip::text(if is_presenting {
+                    "Stop making collaborators follow you"
+                } else {
+                    "Make all collaborators follow you"
+                }))
+                .style(ButtonStyle::Subtle)
+                .selected_style(ButtonStyle::Tinted(TintColor::Accent))
+                .toggle_state(is_presenting)
+                .label_size(LabelSize::Small)
+                .on_click(move |_, window, cx| {
+                    if is_presenting {
+                        window.dispatch_action(Box::new(workspace::StopPresenting), cx);
+                    } else {
+                        window.dispatch_action(Box::new(workspace::StartPresenting), cx);
+                    }
+                })
+                .into_any_element(),
+            );
+        }
+
+        if let Some(presenter) = presenter_id
+            .and_then(|peer_id| room.remote_participant_for_peer_id(peer_id))
+            .filter(|_| !is_following_presenter)
+        {
+            let tooltip = format!("Follow {} again", presenter.user.github_login);
+            children.push(
+                Button::new("follow_presenter", "Rejoin Presentation")
+                    .tooltip(Tooltip::text(tooltip))
+                    .style(ButtonStyle::Tinted(TintColor::Accent))
+                    .label_size(LabelSize::Small)
+                    .on_click(move |_, window, cx| {
+                        window.dispatch_action(Box::new(workspace::FollowPresenter), cx);
+                    })
+                    .into_any_element(),
+            );
+        }
+
         if can_use_microphone {
             children.push(
                 IconButton::new(
@@ -1,8 +1,9 @@
 use settings::{Settings, SettingsStore};
 
 use gpui::{
-    AnyWindowHandle, Context, Hsla, InteractiveElement, MouseButton, ParentElement, ScrollHandle,
-    Styled, SystemWindowTab, SystemWindowTabController, Window, WindowId, actions, canvas, div,
+    AnyWindowHandle, Context, Entity, Hsla, InteractiveElement, MouseButton, ParentElement,
+    ScrollHandle, Styled, SystemWindowTab, SystemWindowTabController, Window, WindowId, actions,
+    canvas, div,
 };
 
 use theme::ThemeSettings;
@@ -11,16 +12,22 @@ use ui::{
     LabelSize, Tab, h_flex, prelude::*, right_click_menu,
 };
 use workspace::{
-    CloseWindow, ItemSettings, Workspace, WorkspaceSettings,
+    CloseWindow, ItemSettings, Workspace, WorkspaceSettings, activate_next_workspace_tab,
+    activate_previous_workspace_tab, activate_workspace_tab,
     item::{ClosePosition, ShowCloseButton},
+    workspace_tabs, workspace_tabs_enabled,
 };
 
 actions!(
     window,
     [
+        /// Shows the next window tab.
         ShowNextWindowTab,
+        /// Shows the previous window tab.
         ShowPreviousWindowTab,
+        /// Merges all windows into tabs of the current window.
         MergeAllWindows,
+        /// Moves the current window tab into its own window.
         MoveTabToNewWindow
     ]
 );
@@ -95,6 +102,20 @@ impl SystemWindowTabs {
 
         cx.observe_new(|workspace: &mut Workspace, _, _| {
             workspace.register_action_renderer(|div, _, window, cx| {
+                if workspace_tabs_enabled(cx) {
+                    let (workspaces, _) = workspace_tabs(window, cx);
+                    return div.when(workspaces.len() > 1, |div| {
+                        div.on_action(|_: &ShowNextWindowTab, window, cx| {
+                            activate_next_workspace_tab(window, cx);
+                        })
+                        .on_action(
+                            |_: &ShowPreviousWindowTab, window, cx| {
+                                activate_previous_workspace_tab(window, cx);
+                            },
+                        )
+                    });
+                }
+
                 let window_id = window.window_handle().window_id();
                 let controller = cx.global::<SystemWindowTabController>();
 
@@ -346,6 +367,88 @@ impl SystemWindowTabs {
             .child(menu)
     }
 
+    fn render_workspace_tab(
+        &self,
+        ix: usize,
+        workspace: Entity<Workspace>,
+        is_active: bool,
+        active_background_color: Hsla,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement + use<> {
+        let settings = ItemSettings::get_global(cx);
+        let close_side = settings.close_position;
+        let show_close_button = settings.show_close_button;
+        let rem_size = window.rem_size();
+        let title = workspace.read(cx).project_name(cx);
+
+        let tab = h_flex()
+            .id(ix)
+            .group("tab")
+            .w_full()
+            .overflow_hidden()
+            .h(Tab::content_height(cx))
+            .relative()
+            .px(DynamicSpacing::Base16.px(cx))
+            .justify_center()
+            .border_l_1()
+            .border_color(cx.theme().colors().border)
+            .cursor_pointer()
+            .on_click(move |_, window, cx| activate_workspace_tab(ix, window, cx))
+            .child(
+                Label::new(title)
+                    .size(LabelSize::Small)
+                    .truncate()
+                    .color(if is_active {
+                        Color::Default
+                    } else {
+                        Color::Muted
+                    }),
+            )
+            .map(|this| match show_close_button {
+                ShowCloseButton::Hidden => this,
+                _ => this.child(
+                    div()
+                        .absolute()
+                        .top_2()
+                        .w_4()
+                        .h_4()
+                        .map(|this| match close_side {
+                            ClosePosition::Left => this.left_1(),
+                            ClosePosition::Right => this.right_1(),
+                        })
+                        .child(
+                            IconButton::new("close", IconName::Close)
+                                .shape(IconButtonShape::Square)
+                                .icon_color(Color::Muted)
+                                .icon_size(IconSize::XSmall)
+                                .on_click(move |_, window, cx| {
+                                    activate_workspace_tab(ix, window, cx);
+                                    workspace.update(cx, |workspace, cx| {
+                                        workspace.close_window(&CloseWindow, window, cx)
+                                    });
+                                })
+                                .map(|this| match show_close_button {
+                                    ShowCloseButton::Hover => this.visible_on_hover("tab"),
+                                    _ => this,
+                                }),
+                        ),
+                ),
+            });
+
+        div()
+            .flex_1()
+            .min_w(rem_size * 10)
+            .when(is_active, |this| this.bg(active_background_color))
+            .border_t_1()
+            .border_color(if is_active {
+                active_background_color
+            } else {
+                cx.theme().colors().border
+            })
+            .child(tab)
+    }
+
     fn handle_tab_drop(dragged_tab: &DraggedWindowTab, ix: usize, cx: &mut Context<Self>) {
         SystemWindowTabController::update_tab_position(cx, dragged_tab.id, ix);
     }
@@ -392,24 +495,48 @@ impl Render for SystemWindowTabs {
             .unwrap_or(&current_window_tab)
             .clone();
 
-        let tab_items = tabs
-            .iter()
-            .enumerate()
-            .map(|(ix, item)| {
-                self.render_tab(
-                    ix,
-                    item.clone(),
-                    tabs.clone(),
-                    active_background_color,
-                    inactive_background_color,
-                    window,
-                    cx,
-                )
-            })
-            .collect::<Vec<_>>();
+        let show_workspace_tabs = workspace_tabs_enabled(cx);
+        let tab_items = if show_workspace_tabs {
+            let (workspaces, active_ix) = workspace_tabs(window, cx);
+            workspaces
+                .into_iter()
+                .enumerate()
+                .map(|(ix, workspace)| {
+                    self.render_workspace_tab(
+                        ix,
+                        workspace,
+                        ix == active_ix,
+                        active_background_color,
+                        window,
+                        cx,
+                    )
+                    .into_any_element()
+                })
+                .collect::<Vec<_>>()
+        } else {
+            tabs.iter()
+                .enumerate()
+                .map(|(ix, item)| {
+                    self.render_tab(
+                        ix,
+                        item.clone(),
+                        tabs.clone(),
+                        active_background_color,
+                        inactive_background_color,
+                        window,
+                        cx,
+                    )
+                    .into_any_element()
+                })
+                .collect::<Vec<_>>()
+        };
 
         let number_of_tabs = tab_items.len().max(1);
-        if (!window.tab_bar_visible() && !visible)
+        if show_workspace_tabs {
+            if number_of_tabs == 1 {
+                return h_flex().into_any_element();
+            }
+        } else if (!window.tab_bar_visible() && !visible)
             || (!use_system_window_tabs && number_of_tabs == 1)
         {
             return h_flex().into_any_element();
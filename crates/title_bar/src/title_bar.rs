@@ -9,6 +9,10 @@ mod title_bar_settings;
 #[cfg(feature = "stories")]
 mod stories;
 
+pub use system_window_tabs::{
+    MergeAllWindows, MoveTabToNewWindow, ShowNextWindowTab, ShowPreviousWindowTab,
+};
+
 use crate::{
     application_menu::{ApplicationMenu, show_menus},
     platform_title_bar::PlatformTitleBar,
@@ -311,12 +315,24 @@ impl TitleBar {
                 (options.nickname.map(|nick| nick.into()), IconName::Server)
             }
             RemoteConnectionOptions::Wsl(_) => (None, IconName::Linux),
+            RemoteConnectionOptions::Docker(_) => (None, IconName::Blocks),
         };
         let nickname = nickname.unwrap_or_else(|| host.clone());
 
         let (indicator_color, meta) = match self.project.read(cx).remote_connection_state(cx)? {
             remote::ConnectionState::Connecting => (Color::Info, format!("Connecting to: {host}")),
-            remote::ConnectionState::Connected => (Color::Success, format!("Connected to: {host}")),
+            remote::ConnectionState::Connected => {
+                let latency = self
+                    .project
+                    .read(cx)
+                    .remote_client()
+                    .and_then(|client| client.read(cx).latency());
+                let meta = match latency {
+                    Some(latency) => format!("Connected to: {host} ({} ms)", latency.as_millis()),
+                    None => format!("Connected to: {host}"),
+                };
+                (Color::Success, meta)
+            }
             remote::ConnectionState::HeartbeatMissed => (
                 Color::Warning,
                 format!("Connection attempt to {host} missed. Retrying..."),
@@ -398,6 +414,22 @@ impl TitleBar {
             );
         }
 
+        if let Some(host) = self.project.read(cx).file_transfer_host() {
+            return Some(
+                Button::new("sftp_host", format!("{host} (SFTP)"))
+                    .icon(IconName::Server)
+                    .icon_size(IconSize::Small)
+                    .icon_position(IconPosition::Start)
+                    .icon_color(Color::Muted)
+                    .style(ButtonStyle::Subtle)
+                    .label_size(LabelSize::Small)
+                    .tooltip(Tooltip::text(
+                        "Opened over SFTP: language servers, tasks and git are unavailable",
+                    ))
+                    .into_any_element(),
+            );
+        }
+
         let host = self.project.read(cx).host()?;
         let host_user = self.user_store.read(cx).get_cached_user(host.user_id)?;
         let participant_index = self
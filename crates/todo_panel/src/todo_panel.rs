@@ -0,0 +1,773 @@
+mod todos;
+
+use std::{collections::BTreeMap, ops::Range, path::PathBuf, sync::Arc, time::Duration};
+
+use editor::{Editor, EditorEvent, MultiBufferRow};
+use fs::Fs;
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{
+    Action, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    SharedString, Subscription, Task, WeakEntity, Window, actions, px, uniform_list,
+};
+use language::{BufferEvent, Point};
+use project::{Project, ProjectPath};
+use settings::{DockSide, Settings, SettingsStore};
+use ui::{ListItem, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+pub use crate::todos::{Todo, find_todos};
+
+actions!(
+    todo_panel,
+    [
+        /// Toggles focus on the TODO panel.
+        ToggleFocus,
+        /// Switches the TODO panel between grouping comments by file and by tag.
+        ToggleGrouping
+    ]
+);
+
+/// Files larger than this, in bytes, aren't scanned for comment tags.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// How long to wait after a buffer is edited before updating its gutter badges.
+const GUTTER_UPDATE_DELAY: Duration = Duration::from_millis(300);
+
+/// Settings for the TODO panel.
+#[derive(Clone, Debug)]
+pub struct TodoPanelSettings {
+    /// Whether to show the TODO panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: bool,
+    /// Where to dock the TODO panel.
+    ///
+    /// Default: right
+    pub dock: DockSide,
+    /// The default width of the TODO panel.
+    ///
+    /// Default: 300
+    pub default_width: Pixels,
+    /// The comment tags to list.
+    ///
+    /// Default: ["TODO", "FIXME", "HACK", "XXX", "BUG"]
+    pub tags: Vec<String>,
+    /// Whether to mark the lines with comment tags in the gutter of editors.
+    ///
+    /// Default: true
+    pub gutter_badges: bool,
+}
+
+impl Settings for TodoPanelSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut App) -> Self {
+        let panel = content.todo_panel.clone().unwrap();
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap(),
+            default_width: panel.default_width.map(px).unwrap(),
+            tags: panel.tags.unwrap(),
+            gutter_badges: panel.gutter_badges.unwrap(),
+        }
+    }
+}
+
+pub fn init(cx: &mut App) {
+    TodoPanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<TodoPanel>(window, cx);
+        });
+        let panel = cx.new(|cx| TodoPanel::new(workspace, window, cx));
+        workspace.add_panel(panel, window, cx);
+    })
+    .detach();
+
+    cx.observe_new(|editor: &mut Editor, _, cx| show_todos_in_gutter(editor, cx))
+        .detach();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Grouping {
+    File,
+    Tag,
+}
+
+#[derive(Clone, Debug)]
+enum ListEntry {
+    Header { label: SharedString, count: usize },
+    Todo { path: ProjectPath, todo: Todo },
+}
+
+/// A file to scan for comment tags, or `None` to forget a file that was removed or is no longer
+/// scanned.
+type ScanRequest = (ProjectPath, Option<PathBuf>);
+
+/// A dock panel listing the comment tags, such as `TODO` and `FIXME`, in the project's files.
+/// Files are scanned once when the project is opened, and again whenever they change on disk.
+pub struct TodoPanel {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    filter_editor: Entity<Editor>,
+    grouping: Grouping,
+    /// The tags the files were scanned for.
+    tags: Vec<String>,
+    todos: BTreeMap<ProjectPath, Vec<Todo>>,
+    /// The rows shown in the panel, after grouping and filtering the comments.
+    entries: Vec<ListEntry>,
+    scan_tx: mpsc::UnboundedSender<ScanRequest>,
+    _scan_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl TodoPanel {
+    fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let project = workspace.project().clone();
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter comments…", window, cx);
+            editor
+        });
+        let (scan_tx, scan_rx) = mpsc::unbounded();
+
+        let subscriptions = vec![
+            cx.subscribe(&project, |this, _, event, cx| match event {
+                project::Event::WorktreeAdded(worktree_id) => {
+                    this.scan_worktree(*worktree_id, cx);
+                }
+                project::Event::WorktreeRemoved(worktree_id) => {
+                    this.todos
+                        .retain(|path, _| path.worktree_id != *worktree_id);
+                    this.update_entries(cx);
+                }
+                project::Event::WorktreeUpdatedEntries(worktree_id, changes) => {
+                    for (path, _, _) in changes.iter() {
+                        this.queue_path(
+                            ProjectPath {
+                                worktree_id: *worktree_id,
+                                path: path.clone(),
+                            },
+                            cx,
+                        );
+                    }
+                }
+                _ => {}
+            }),
+            cx.subscribe(&filter_editor, |this, _, event: &EditorEvent, cx| {
+                if matches!(event, EditorEvent::BufferEdited) {
+                    this.update_entries(cx);
+                }
+            }),
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                let tags = &TodoPanelSettings::get_global(cx).tags;
+                if *tags != this.tags {
+                    this.tags = tags.clone();
+                    this.scan_all(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            project,
+            workspace: workspace.weak_handle(),
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            width: None,
+            filter_editor,
+            grouping: Grouping::File,
+            tags: TodoPanelSettings::get_global(cx).tags.clone(),
+            todos: BTreeMap::new(),
+            entries: Vec::new(),
+            scan_tx,
+            _scan_task: Self::process_scans(scan_rx, cx),
+            _subscriptions: subscriptions,
+        };
+        this.scan_all(cx);
+        this
+    }
+
+    fn scan_all(&mut self, cx: &mut Context<Self>) {
+        self.todos.clear();
+        self.update_entries(cx);
+        let worktree_ids = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .map(|worktree| worktree.read(cx).id())
+            .collect::<Vec<_>>();
+        for worktree_id in worktree_ids {
+            self.scan_worktree(worktree_id, cx);
+        }
+    }
+
+    fn scan_worktree(&mut self, worktree_id: project::WorktreeId, cx: &mut Context<Self>) {
+        let project = self.project.read(cx);
+        if !project.is_local() {
+            return;
+        }
+        let Some(worktree) = project.worktree_for_id(worktree_id, cx) else {
+            return;
+        };
+        let worktree = worktree.read(cx);
+        if !worktree.is_visible() {
+            return;
+        }
+        for entry in worktree.files(false, 0) {
+            if entry.size <= MAX_FILE_SIZE {
+                let path = ProjectPath {
+                    worktree_id,
+                    path: entry.path.clone(),
+                };
+                let abs_path = worktree.absolutize(&entry.path);
+                self.scan_tx.unbounded_send((path, Some(abs_path))).ok();
+            }
+        }
+    }
+
+    /// Rescans a path that changed, or forgets it if it's no longer a file that's scanned.
+    fn queue_path(&mut self, path: ProjectPath, cx: &mut Context<Self>) {
+        let project = self.project.read(cx);
+        if !project.is_local() {
+            return;
+        }
+        let abs_path = project
+            .worktree_for_id(path.worktree_id, cx)
+            .filter(|worktree| worktree.read(cx).is_visible())
+            .and_then(|worktree| {
+                let worktree = worktree.read(cx);
+                let entry = worktree.entry_for_path(&path.path)?;
+                (entry.is_file() && !entry.is_ignored && entry.size <= MAX_FILE_SIZE)
+                    .then(|| worktree.absolutize(&path.path))
+            });
+        self.scan_tx.unbounded_send((path, abs_path)).ok();
+    }
+
+    /// Scans the queued files in batches, updating the panel after each batch.
+    fn process_scans(
+        mut scan_rx: mpsc::UnboundedReceiver<ScanRequest>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        cx.spawn(async move |this, cx| {
+            while let Some(request) = scan_rx.next().await {
+                let mut requests = vec![request];
+                while let Ok(Some(request)) = scan_rx.try_next() {
+                    requests.push(request);
+                }
+                let Ok((fs, tags)) =
+                    this.read_with(cx, |this, _| (this.fs.clone(), this.tags.clone()))
+                else {
+                    return;
+                };
+
+                let mut loaded = Vec::with_capacity(requests.len());
+                for (path, abs_path) in requests {
+                    let text = match abs_path {
+                        Some(abs_path) => fs.load(&abs_path).await.ok(),
+                        None => None,
+                    };
+                    loaded.push((path, text));
+                }
+                let scanned = cx
+                    .background_spawn(async move {
+                        loaded
+                            .into_iter()
+                            .map(|(path, text)| {
+                                let todos = text
+                                    .map(|text| find_todos(&text, &tags))
+                                    .unwrap_or_default();
+                                (path, todos)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .await;
+
+                let updated = this.update(cx, |this, cx| {
+                    for (path, todos) in scanned {
+                        // A removed directory is reported once, so forget the files in it too.
+                        this.todos.retain(|other, _| {
+                            other.worktree_id != path.worktree_id
+                                || !other.path.starts_with(&path.path)
+                        });
+                        if !todos.is_empty() {
+                            this.todos.insert(path, todos);
+                        }
+                    }
+                    this.update_entries(cx);
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+        })
+    }
+
+    fn toggle_grouping(&mut self, _: &ToggleGrouping, _: &mut Window, cx: &mut Context<Self>) {
+        self.grouping = match self.grouping {
+            Grouping::File => Grouping::Tag,
+            Grouping::Tag => Grouping::File,
+        };
+        self.update_entries(cx);
+    }
+
+    fn file_label(&self, path: &ProjectPath, cx: &App) -> SharedString {
+        self.project
+            .read(cx)
+            .short_full_path_for_project_path(path, cx)
+            .map_or_else(
+                || path.path.as_unix_str().to_string().into(),
+                SharedString::from,
+            )
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        let query = self.filter_editor.read(cx).text(cx).to_lowercase();
+        let matching_todos = self
+            .todos
+            .iter()
+            .map(|(path, todos)| {
+                let label = self.file_label(path, cx);
+                let todos = todos
+                    .iter()
+                    .filter(|todo| {
+                        query.is_empty()
+                            || label.to_lowercase().contains(&query)
+                            || todo.tag.to_lowercase().contains(&query)
+                            || todo.text.to_lowercase().contains(&query)
+                    })
+                    .collect::<Vec<_>>();
+                (path, label, todos)
+            })
+            .filter(|(_, _, todos)| !todos.is_empty())
+            .collect::<Vec<_>>();
+
+        self.entries.clear();
+        match self.grouping {
+            Grouping::File => {
+                for (path, label, todos) in matching_todos {
+                    self.entries.push(ListEntry::Header {
+                        label,
+                        count: todos.len(),
+                    });
+                    self.entries
+                        .extend(todos.into_iter().map(|todo| ListEntry::Todo {
+                            path: path.clone(),
+                            todo: todo.clone(),
+                        }));
+                }
+            }
+            Grouping::Tag => {
+                for tag in &self.tags {
+                    let todos = matching_todos
+                        .iter()
+                        .flat_map(|(path, _, todos)| {
+                            todos
+                                .iter()
+                                .filter(|todo| todo.tag.as_ref() == tag)
+                                .map(|todo| ListEntry::Todo {
+                                    path: (*path).clone(),
+                                    todo: (*todo).clone(),
+                                })
+                        })
+                        .collect::<Vec<_>>();
+                    if !todos.is_empty() {
+                        self.entries.push(ListEntry::Header {
+                            label: tag.clone().into(),
+                            count: todos.len(),
+                        });
+                        self.entries.extend(todos);
+                    }
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn todo_count(&self) -> usize {
+        self.todos.values().map(Vec::len).sum()
+    }
+
+    fn open_todo(&self, path: ProjectPath, todo: &Todo, window: &mut Window, cx: &mut App) {
+        let point = Point::new(todo.row, todo.column);
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let open = workspace.update(cx, |workspace, cx| {
+            workspace.open_path(path, None, true, window, cx)
+        });
+        window
+            .spawn(cx, async move |cx| {
+                let item = open.await?;
+                if let Some(editor) = item.downcast::<Editor>() {
+                    editor.update_in(cx, |editor, window, cx| {
+                        editor.go_to_singleton_buffer_point(point, window, cx);
+                    })?;
+                }
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            ListEntry::Header { label, count } => ListItem::new(ix)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2()
+                        .justify_between()
+                        .child(Label::new(label.clone()).truncate())
+                        .child(
+                            Label::new(count.to_string())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .into_any_element(),
+            ListEntry::Todo { path, todo } => {
+                let detail = match self.grouping {
+                    Grouping::File => todo.tag.clone(),
+                    Grouping::Tag => path.path.file_name().unwrap_or_default().to_string().into(),
+                };
+                let path = path.clone();
+                let todo = todo.clone();
+                ListItem::new(ix)
+                    .indent_level(1)
+                    .indent_step_size(px(12.))
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .child(
+                                Label::new(detail)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Warning),
+                            )
+                            .child(
+                                Label::new(if todo.text.is_empty() {
+                                    todo.tag.clone()
+                                } else {
+                                    todo.text.clone()
+                                })
+                                .size(LabelSize::Small)
+                                .truncate(),
+                            )
+                            .child(
+                                Label::new(format!("{}", todo.row + 1))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_todo(path.clone(), &todo, window, cx);
+                    }))
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn render_empty_state(&self, cx: &App) -> impl IntoElement {
+        let message = if !self.project.read(cx).is_local() {
+            "Comment tags aren't listed for remote projects"
+        } else if self.todos.is_empty() {
+            "No comment tags in this project"
+        } else {
+            "No comments match the filter"
+        };
+        v_flex()
+            .size_full()
+            .items_center()
+            .justify_center()
+            .child(Label::new(message).color(Color::Muted))
+    }
+}
+
+/// Marks the lines of an editor's buffer that have comment tags in the gutter, keeping them up
+/// to date as the buffer is edited.
+fn show_todos_in_gutter(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() {
+        return;
+    }
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+
+    // Replacing the pending update cancels it, so that edits in quick succession are scanned once.
+    let mut pending_update = Some(update_gutter_badges(Duration::ZERO, cx));
+    cx.subscribe(&buffer, move |_, _, event, cx| {
+        if matches!(event, BufferEvent::Edited | BufferEvent::Reloaded) {
+            pending_update.replace(update_gutter_badges(GUTTER_UPDATE_DELAY, cx));
+        }
+    })
+    .detach();
+    cx.observe_global::<SettingsStore>(|_, cx| {
+        update_gutter_badges(Duration::ZERO, cx).detach();
+    })
+    .detach();
+}
+
+fn update_gutter_badges(delay: Duration, cx: &mut Context<Editor>) -> Task<()> {
+    enum TodoGutterBadges {}
+
+    cx.spawn(async move |editor, cx| {
+        if !delay.is_zero() {
+            cx.background_executor().timer(delay).await;
+        }
+        let Ok(Some((snapshot, tags))) = editor.update(cx, |editor, cx| {
+            let settings = TodoPanelSettings::get_global(cx);
+            if !settings.gutter_badges {
+                editor.clear_gutter_highlights::<TodoGutterBadges>(cx);
+                return None;
+            }
+            Some((editor.buffer().read(cx).snapshot(cx), settings.tags.clone()))
+        }) else {
+            return;
+        };
+        let ranges = cx
+            .background_spawn(async move {
+                find_todos(&snapshot.text(), &tags)
+                    .into_iter()
+                    .map(|todo| {
+                        let start = Point::new(todo.row, 0);
+                        let end = Point::new(todo.row, snapshot.line_len(MultiBufferRow(todo.row)));
+                        snapshot.anchor_before(start)..snapshot.anchor_after(end)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await;
+        editor
+            .update(cx, |editor, cx| {
+                editor.highlight_gutter::<TodoGutterBadges>(
+                    ranges,
+                    |cx| cx.theme().status().warning,
+                    cx,
+                );
+            })
+            .log_err();
+    })
+}
+
+impl Focusable for TodoPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TodoPanel {}
+
+impl Render for TodoPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let grouping_tooltip = match self.grouping {
+            Grouping::File => "Group by Tag",
+            Grouping::Tag => "Group by File",
+        };
+
+        v_flex()
+            .key_context("TodoPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::toggle_grouping))
+            .size_full()
+            .child(
+                h_flex()
+                    .p_1()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(div().flex_1().px_1().child(self.filter_editor.clone()))
+                    .child(
+                        IconButton::new("todo-panel-grouping", IconName::ListTree)
+                            .icon_size(IconSize::Small)
+                            .toggle_state(self.grouping == Grouping::Tag)
+                            .tooltip(Tooltip::for_action_title(grouping_tooltip, &ToggleGrouping))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_grouping(&ToggleGrouping, window, cx)
+                            })),
+                    ),
+            )
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(self.render_empty_state(cx))
+                } else {
+                    this.child(
+                        uniform_list(
+                            "todo-panel-entries",
+                            self.entries.len(),
+                            cx.processor(|this, range: Range<usize>, _window, cx| {
+                                range.map(|ix| this.render_entry(ix, cx)).collect()
+                            }),
+                        )
+                        .size_full(),
+                    )
+                }
+            })
+    }
+}
+
+impl Panel for TodoPanel {
+    fn persistent_name() -> &'static str {
+        "TodoPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match TodoPanelSettings::get_global(cx).dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.todo_panel.get_or_insert_default().dock = Some(dock);
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| TodoPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        TodoPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTodo)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("TODO Panel")
+    }
+
+    fn icon_label(&self, _: &Window, _: &App) -> Option<String> {
+        match self.todo_count() {
+            0 => None,
+            count => Some(count.to_string()),
+        }
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::{TestAppContext, VisualTestContext};
+    use serde_json::json;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            TodoPanelSettings::register(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_todo_panel_scans_incrementally(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "main.rs": "// TODO: write main\nfn main() {}\n",
+                "lib.rs": "// FIXME broken\n",
+                "notes.txt": "nothing to do\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project, window, cx));
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            cx.new(|cx| TodoPanel::new(workspace, window, cx))
+        });
+        cx.run_until_parked();
+
+        let labels = |panel: &Entity<TodoPanel>, cx: &mut VisualTestContext| {
+            panel.read_with(cx, |panel, _| {
+                panel
+                    .entries
+                    .iter()
+                    .map(|entry| match entry {
+                        ListEntry::Header { label, count } => format!("{label} ({count})"),
+                        ListEntry::Todo { todo, .. } => format!("  {} {}", todo.tag, todo.text),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            labels(&panel, cx),
+            [
+                "lib.rs (1)",
+                "  FIXME broken",
+                "main.rs (1)",
+                "  TODO write main"
+            ]
+        );
+
+        // Changed files are rescanned, and removed files are forgotten.
+        fs.save(
+            path!("/project/notes.txt").as_ref(),
+            &"# TODO: call back\n".into(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        fs.remove_file(path!("/project/lib.rs").as_ref(), Default::default())
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            labels(&panel, cx),
+            [
+                "main.rs (1)",
+                "  TODO write main",
+                "notes.txt (1)",
+                "  TODO call back"
+            ]
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_grouping(&ToggleGrouping, window, cx);
+            panel.filter_editor.update(cx, |editor, cx| {
+                editor.set_text("main", window, cx);
+            });
+        });
+        cx.run_until_parked();
+        assert_eq!(labels(&panel, cx), ["TODO (1)", "  TODO write main"]);
+    }
+}
@@ -0,0 +1,107 @@
+use gpui::SharedString;
+
+/// The markers that start a comment in the languages Zed supports. A tag is only recognized
+/// after one of them on the same line, so that identifiers like `TODO_LIST` or strings that
+/// mention a tag aren't listed.
+const COMMENT_MARKERS: &[&str] = &["//", "/*", "#", "--", ";", "<!--", "%", "*"];
+
+/// A comment tag, such as `TODO` or `FIXME`, found in a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Todo {
+    /// The zero-based row of the tag.
+    pub row: u32,
+    /// The zero-based column, in bytes, of the tag.
+    pub column: u32,
+    pub tag: SharedString,
+    /// The rest of the comment after the tag, without any author in parentheses or colon.
+    pub text: SharedString,
+}
+
+/// Finds the comment tags in `text`. Tags are case-sensitive, and must be whole words.
+pub fn find_todos(text: &str, tags: &[String]) -> Vec<Todo> {
+    let mut todos = Vec::new();
+    for (row, line) in text.lines().enumerate() {
+        let Some(comment_start) = COMMENT_MARKERS
+            .iter()
+            .filter_map(|marker| line.find(marker))
+            .min()
+        else {
+            continue;
+        };
+        let comment = &line[comment_start..];
+        if let Some((offset, tag)) = find_tag(comment, tags) {
+            todos.push(Todo {
+                row: row as u32,
+                column: (comment_start + offset) as u32,
+                tag: tag.to_string().into(),
+                text: todo_text(&comment[offset + tag.len()..]).into(),
+            });
+        }
+    }
+    todos
+}
+
+/// Returns the offset of the first tag in `comment` and the tag.
+fn find_tag<'a>(comment: &str, tags: &'a [String]) -> Option<(usize, &'a str)> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    tags.iter()
+        .filter(|tag| !tag.is_empty())
+        .filter_map(|tag| {
+            comment.match_indices(tag.as_str()).find_map(|(offset, _)| {
+                let before = comment[..offset].chars().next_back();
+                let after = comment[offset + tag.len()..].chars().next();
+                (!before.is_some_and(is_word_char) && !after.is_some_and(is_word_char))
+                    .then_some((offset, tag.as_str()))
+            })
+        })
+        .min_by_key(|(offset, _)| *offset)
+}
+
+fn todo_text(rest: &str) -> String {
+    let mut text = rest.trim_start();
+    if let Some(after_author) = text
+        .strip_prefix('(')
+        .and_then(|text| Some(&text[text.find(')')? + 1..]))
+    {
+        text = after_author;
+    }
+    text = text.trim_start_matches([':', '-', ' ', '\t']);
+    for suffix in ["*/", "-->"] {
+        text = text.trim_end().trim_end_matches(suffix);
+    }
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todos(text: &str) -> Vec<String> {
+        let tags = ["TODO".to_string(), "FIXME".to_string()];
+        find_todos(text, &tags)
+            .into_iter()
+            .map(|todo| format!("{}:{} {} {}", todo.row, todo.column, todo.tag, todo.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_todos() {
+        assert_eq!(
+            todos(
+                "fn main() {\n    // TODO: handle errors\n    let x = 1; # FIXME(nate) - overflow\n}\n"
+            ),
+            ["1:7 TODO handle errors", "2:17 FIXME overflow"]
+        );
+        assert_eq!(
+            todos("/* TODO tidy up */\n<!-- FIXME: broken link -->"),
+            ["0:3 TODO tidy up", "1:5 FIXME broken link"]
+        );
+    }
+
+    #[test]
+    fn test_find_todos_ignores_code_and_partial_words() {
+        assert_eq!(todos("let TODO = 1;\n"), Vec::<String>::new());
+        assert_eq!(todos("// TODOS and FIXMEs\n"), Vec::<String>::new());
+        assert_eq!(todos("// see TODO_LIST\n"), Vec::<String>::new());
+    }
+}
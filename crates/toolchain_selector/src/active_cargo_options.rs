@@ -0,0 +1,120 @@
+use editor::Editor;
+use gpui::{Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, div};
+use language::{Buffer, BufferEvent};
+use project::{
+    Project, WorktreeId,
+    lsp_store::rust_analyzer_ext::{CargoOptions, restart_rust_analyzer},
+};
+use settings::SettingsStore;
+use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip, Window};
+use workspace::{StatusItemView, Workspace, item::ItemHandle};
+
+use crate::CargoOptionsSelector;
+
+/// Shows the cargo options of the active Rust buffer's worktree and restarts rust-analyzer when they change.
+pub struct ActiveCargoOptions {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    active_buffer: Option<(WorktreeId, WeakEntity<Buffer>, Subscription)>,
+    options: Option<(WorktreeId, CargoOptions)>,
+    _settings_subscription: Subscription,
+}
+
+impl ActiveCargoOptions {
+    pub fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        Self {
+            workspace: workspace.weak_handle(),
+            project: workspace.project().clone(),
+            active_buffer: None,
+            options: None,
+            _settings_subscription: cx.observe_global::<SettingsStore>(Self::refresh),
+        }
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        let Some((worktree_id, buffer)) = self
+            .active_buffer
+            .as_ref()
+            .and_then(|(worktree_id, buffer, _)| Some((*worktree_id, buffer.upgrade()?)))
+            .filter(|(_, buffer)| {
+                buffer
+                    .read(cx)
+                    .language()
+                    .is_some_and(|language| language.name().as_ref() == "Rust")
+            })
+        else {
+            self.options = None;
+            cx.notify();
+            return;
+        };
+
+        let options = CargoOptions::for_worktree(worktree_id, cx);
+        if let Some((previous_worktree_id, previous_options)) = &self.options
+            && *previous_worktree_id == worktree_id
+            && *previous_options != options
+        {
+            restart_rust_analyzer(&self.project, buffer, cx);
+        }
+        self.options = Some((worktree_id, options));
+        cx.notify();
+    }
+
+    fn label(options: &CargoOptions) -> String {
+        let mut label = if options.all_features {
+            "all features".to_owned()
+        } else if !options.features.is_empty() {
+            format!("features: {}", options.features.join(", "))
+        } else if options.no_default_features {
+            "no default features".to_owned()
+        } else {
+            "default features".to_owned()
+        };
+        for extra in [&options.target, &options.toolchain].into_iter().flatten() {
+            label.push_str(" · ");
+            label.push_str(extra);
+        }
+        label
+    }
+}
+
+impl Render for ActiveCargoOptions {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().when_some(self.options.as_ref(), |el, (_, options)| {
+            el.child(
+                Button::new("change-cargo-options", Self::label(options))
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        if let Some(workspace) = this.workspace.upgrade() {
+                            workspace.update(cx, |workspace, cx| {
+                                CargoOptionsSelector::toggle(workspace, window, cx)
+                            });
+                        }
+                    }))
+                    .tooltip(Tooltip::text("Select Cargo Features, Target and Toolchain")),
+            )
+        })
+    }
+}
+
+impl StatusItemView for ActiveCargoOptions {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.active_buffer = active_pane_item
+            .and_then(|item| item.downcast::<Editor>())
+            .and_then(|editor| {
+                let (_, buffer, _) = editor.read(cx).active_excerpt(cx)?;
+                let worktree_id = buffer.read(cx).file()?.worktree_id(cx);
+                let subscription = cx.subscribe(&buffer, |this, _, event: &BufferEvent, cx| {
+                    if matches!(event, BufferEvent::LanguageChanged) {
+                        this.refresh(cx);
+                    }
+                });
+                Some((worktree_id, buffer.downgrade(), subscription))
+            });
+        self.refresh(cx);
+    }
+}
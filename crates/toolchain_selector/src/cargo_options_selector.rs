@@ -0,0 +1,505 @@
+use std::{collections::BTreeSet, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use editor::Editor;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, AsyncApp, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ParentElement, Render, Styled, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use project::{Project, WorktreeId, lsp_store::rust_analyzer_ext::CargoOptions};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::{ResultExt, command::new_smol_command};
+use workspace::{ModalView, Workspace};
+
+use crate::SelectCargoOptions;
+
+pub struct CargoOptionsSelector {
+    picker: Entity<Picker<CargoOptionsSelectorDelegate>>,
+}
+
+impl CargoOptionsSelector {
+    pub(crate) fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(move |workspace, _: &SelectCargoOptions, window, cx| {
+            Self::toggle(workspace, window, cx);
+        });
+    }
+
+    pub fn toggle(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Option<()> {
+        let (_, buffer, _) = workspace
+            .active_item(cx)?
+            .act_as::<Editor>(cx)?
+            .read(cx)
+            .active_excerpt(cx)?;
+        let language = buffer.read(cx).language()?;
+        if language.name().as_ref() != "Rust" {
+            return None;
+        }
+        let worktree_id = buffer.read(cx).file()?.worktree_id(cx);
+        let project = workspace.project().clone();
+
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            CargoOptionsSelector::new(worktree_id, project, window, cx)
+        });
+        Some(())
+    }
+
+    fn new(
+        worktree_id: WorktreeId,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = CargoOptionsSelectorDelegate::new(
+            cx.entity().downgrade(),
+            worktree_id,
+            project.clone(),
+            cx,
+        );
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let worktree_path = project
+            .read(cx)
+            .is_local()
+            .then(|| project.read(cx).worktree_for_id(worktree_id, cx))
+            .flatten()
+            .map(|worktree| worktree.read(cx).abs_path());
+        if let Some(worktree_path) = worktree_path {
+            let picker = picker.downgrade();
+            cx.spawn_in(window, async move |_, cx| {
+                let available = cx
+                    .background_spawn(async move { AvailableOptions::load(&worktree_path).await })
+                    .await;
+                picker
+                    .update_in(cx, |picker, window, cx| {
+                        picker.delegate.available = available;
+                        picker.delegate.rebuild_entries();
+                        picker.refresh(window, cx);
+                    })
+                    .ok();
+            })
+            .detach();
+        }
+
+        Self { picker }
+    }
+}
+
+impl Render for CargoOptionsSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CargoOptionsSelector")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for CargoOptionsSelector {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for CargoOptionsSelector {}
+impl ModalView for CargoOptionsSelector {}
+
+/// Features, targets and toolchains reported by cargo and rustup for a worktree.
+#[derive(Default)]
+struct AvailableOptions {
+    features: BTreeSet<String>,
+    targets: Vec<String>,
+    toolchains: Vec<String>,
+}
+
+impl AvailableOptions {
+    async fn load(worktree_path: &Path) -> Self {
+        let features = command_output(
+            "cargo",
+            &["metadata", "--no-deps", "--format-version", "1"],
+            worktree_path,
+        )
+        .await
+        .and_then(|output| serde_json::from_str::<serde_json::Value>(&output).log_err())
+        .map(|metadata| features_from_metadata(&metadata))
+        .unwrap_or_default();
+        let targets = command_output("rustup", &["target", "list", "--installed"], worktree_path)
+            .await
+            .map(|output| output.lines().map(|line| line.trim().to_owned()).collect())
+            .unwrap_or_default();
+        let toolchains = command_output("rustup", &["toolchain", "list"], worktree_path)
+            .await
+            .map(|output| {
+                output
+                    .lines()
+                    .filter_map(|line| Some(line.split_whitespace().next()?.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            features,
+            targets,
+            toolchains,
+        }
+    }
+}
+
+async fn command_output(program: &str, args: &[&str], cwd: &Path) -> Option<String> {
+    let output = new_smol_command(program)
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .log_err()?;
+    if !output.status.success() {
+        log::debug!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+fn features_from_metadata(metadata: &serde_json::Value) -> BTreeSet<String> {
+    metadata
+        .get("packages")
+        .and_then(|packages| packages.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|package| package.get("features")?.as_object())
+        .flat_map(|features| features.keys())
+        .filter(|feature| feature.as_str() != "default")
+        .cloned()
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CargoOptionsEntry {
+    AllFeatures,
+    DefaultFeatures,
+    NoDefaultFeatures,
+    Feature(String),
+    Target(Option<String>),
+    Toolchain(Option<String>),
+}
+
+impl CargoOptionsEntry {
+    fn label(&self) -> String {
+        match self {
+            Self::AllFeatures => "All features".to_owned(),
+            Self::DefaultFeatures => "Default features".to_owned(),
+            Self::NoDefaultFeatures => "No default features".to_owned(),
+            Self::Feature(feature) => format!("Feature: {feature}"),
+            Self::Target(None) => "Target: host".to_owned(),
+            Self::Target(Some(target)) => format!("Target: {target}"),
+            Self::Toolchain(None) => "Toolchain: default".to_owned(),
+            Self::Toolchain(Some(toolchain)) => format!("Toolchain: {toolchain}"),
+        }
+    }
+
+    fn is_active(&self, options: &CargoOptions) -> bool {
+        match self {
+            Self::AllFeatures => options.all_features,
+            Self::DefaultFeatures => {
+                !options.all_features && options.features.is_empty() && !options.no_default_features
+            }
+            Self::NoDefaultFeatures => options.no_default_features,
+            Self::Feature(feature) => options.all_features || options.features.contains(feature),
+            Self::Target(target) => &options.target == target,
+            Self::Toolchain(toolchain) => &options.toolchain == toolchain,
+        }
+    }
+
+    /// Applies the entry to the options, returning whether the picker should stay open for further toggles.
+    fn apply(&self, options: &mut CargoOptions) -> bool {
+        match self {
+            Self::AllFeatures => {
+                options.all_features = !options.all_features;
+                true
+            }
+            Self::DefaultFeatures => {
+                options.all_features = false;
+                options.features.clear();
+                options.no_default_features = false;
+                true
+            }
+            Self::NoDefaultFeatures => {
+                options.no_default_features = !options.no_default_features;
+                true
+            }
+            Self::Feature(feature) => {
+                options.all_features = false;
+                if let Some(ix) = options.features.iter().position(|f| f == feature) {
+                    options.features.remove(ix);
+                } else {
+                    options.features.push(feature.clone());
+                }
+                true
+            }
+            Self::Target(target) => {
+                options.target = target.clone();
+                false
+            }
+            Self::Toolchain(toolchain) => {
+                options.toolchain = toolchain.clone();
+                false
+            }
+        }
+    }
+}
+
+pub struct CargoOptionsSelectorDelegate {
+    selector: WeakEntity<CargoOptionsSelector>,
+    worktree_id: WorktreeId,
+    project: Entity<Project>,
+    options: CargoOptions,
+    available: AvailableOptions,
+    entries: Vec<CargoOptionsEntry>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl CargoOptionsSelectorDelegate {
+    fn new(
+        selector: WeakEntity<CargoOptionsSelector>,
+        worktree_id: WorktreeId,
+        project: Entity<Project>,
+        cx: &App,
+    ) -> Self {
+        let mut this = Self {
+            selector,
+            worktree_id,
+            project,
+            options: CargoOptions::for_worktree(worktree_id, cx),
+            available: AvailableOptions::default(),
+            entries: Vec::new(),
+            candidates: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        this.rebuild_entries();
+        this
+    }
+
+    fn rebuild_entries(&mut self) {
+        let mut features = self.available.features.clone();
+        features.extend(self.options.features.iter().cloned());
+        let mut targets = self.available.targets.clone();
+        targets.extend(self.options.target.clone());
+        targets.sort();
+        targets.dedup();
+        let mut toolchains = self.available.toolchains.clone();
+        toolchains.extend(self.options.toolchain.clone());
+        toolchains.sort();
+        toolchains.dedup();
+
+        self.entries = [
+            CargoOptionsEntry::AllFeatures,
+            CargoOptionsEntry::DefaultFeatures,
+            CargoOptionsEntry::NoDefaultFeatures,
+        ]
+        .into_iter()
+        .chain(features.into_iter().map(CargoOptionsEntry::Feature))
+        .chain([CargoOptionsEntry::Target(None)])
+        .chain(targets.into_iter().map(Some).map(CargoOptionsEntry::Target))
+        .chain([CargoOptionsEntry::Toolchain(None)])
+        .chain(
+            toolchains
+                .into_iter()
+                .map(Some)
+                .map(CargoOptionsEntry::Toolchain),
+        )
+        .collect();
+        self.candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| StringMatchCandidate::new(ix, &entry.label()))
+            .collect();
+    }
+}
+
+impl PickerDelegate for CargoOptionsSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Toggle cargo features, target or toolchain…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.entries.get(mat.candidate_id))
+        else {
+            return;
+        };
+        let keep_open = entry.apply(&mut self.options);
+        write_cargo_options(
+            self.project.clone(),
+            self.worktree_id,
+            self.options.clone(),
+            cx,
+        )
+        .detach_and_log_err(cx);
+        if keep_open {
+            cx.notify();
+        } else {
+            self.dismissed(window, cx);
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let entry = self.entries.get(mat.candidate_id)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    mat.string.clone(),
+                    mat.positions.clone(),
+                ))
+                .when(entry.is_active(&self.options), |item| {
+                    item.end_slot(
+                        Icon::new(IconName::Check)
+                            .size(IconSize::Small)
+                            .color(Color::Accent),
+                    )
+                }),
+        )
+    }
+}
+
+/// Stores the cargo options in the worktree's `.zed/settings.json`, creating it if needed.
+fn write_cargo_options(
+    project: Entity<Project>,
+    worktree_id: WorktreeId,
+    options: CargoOptions,
+    cx: &mut App,
+) -> Task<anyhow::Result<()>> {
+    let settings_path = paths::local_settings_file_relative_path();
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        let (directory_missing, file_missing) = project
+            .read_with(cx, |project, cx| {
+                let worktree = project.worktree_for_id(worktree_id, cx)?;
+                let worktree = worktree.read(cx);
+                Some((
+                    settings_path
+                        .parent()
+                        .is_some_and(|directory| worktree.entry_for_path(directory).is_none()),
+                    worktree.entry_for_path(settings_path).is_none(),
+                ))
+            })?
+            .context("worktree was removed")?;
+        if directory_missing && let Some(directory) = settings_path.parent() {
+            project
+                .update(cx, |project, cx| {
+                    project.create_entry((worktree_id, directory), true, cx)
+                })?
+                .await?;
+        }
+        if file_missing {
+            project
+                .update(cx, |project, cx| {
+                    project.create_entry((worktree_id, settings_path), false, cx)
+                })?
+                .await?;
+        }
+
+        project.update(cx, |project, cx| {
+            project.update_local_settings_file(
+                worktree_id,
+                settings_path.into(),
+                cx,
+                move |settings, _| options.write_to_settings(settings),
+            );
+        })
+    })
+}
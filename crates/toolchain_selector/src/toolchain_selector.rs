@@ -1,6 +1,10 @@
+mod active_cargo_options;
 mod active_toolchain;
+mod cargo_options_selector;
 
+pub use active_cargo_options::ActiveCargoOptions;
 pub use active_toolchain::ActiveToolchain;
+pub use cargo_options_selector::CargoOptionsSelector;
 use convert_case::Casing as _;
 use editor::Editor;
 use file_finder::OpenPathDelegate;
@@ -33,12 +37,15 @@ actions!(
         /// Selects a toolchain for the current project.
         Select,
         /// Adds a new toolchain for the current project.
-        AddToolchain
+        AddToolchain,
+        /// Selects cargo features, target and toolchain for the current Rust worktree.
+        SelectCargoOptions
     ]
 );
 
 pub fn init(cx: &mut App) {
     cx.observe_new(ToolchainSelector::register).detach();
+    cx.observe_new(CargoOptionsSelector::register).detach();
 }
 
 pub struct ToolchainSelector {
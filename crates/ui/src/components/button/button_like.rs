@@ -1,10 +1,12 @@
 use documented::Documented;
 use gpui::{
-    AnyElement, AnyView, ClickEvent, CursorStyle, DefiniteLength, Hsla, MouseButton,
-    MouseClickEvent, MouseDownEvent, MouseUpEvent, Rems, StyleRefinement, relative,
+    AnyElement, AnyView, BoxShadow, ClickEvent, CursorStyle, DefiniteLength, Hsla, MouseButton,
+    MouseClickEvent, MouseDownEvent, MouseUpEvent, Rems, StyleRefinement, point, relative,
     transparent_black,
 };
+use settings::Settings;
 use smallvec::SmallVec;
+use theme::AccessibilitySettings;
 
 use crate::{DynamicSpacing, ElevationIndex, prelude::*};
 
@@ -611,9 +613,25 @@ impl RenderOnce for ButtonLike {
                 let hovered_style = style.hovered(self.layer, cx);
                 let focus_color =
                     |refinement: StyleRefinement| refinement.bg(hovered_style.background);
+                // In high contrast mode, focused buttons get a ring as well as a background. It's
+                // drawn as a shadow rather than a border, so that it doesn't change the layout.
+                let focus_ring = AccessibilitySettings::get_global(cx)
+                    .high_contrast
+                    .then(|| cx.theme().colors().border_focused);
                 this.cursor(self.cursor_style)
                     .hover(focus_color)
-                    .focus(focus_color)
+                    .focus(move |refinement| {
+                        let refinement = focus_color(refinement);
+                        match focus_ring {
+                            Some(color) => refinement.shadow(vec![BoxShadow {
+                                color,
+                                offset: point(px(0.), px(0.)),
+                                blur_radius: px(0.),
+                                spread_radius: px(2.),
+                            }]),
+                            None => refinement,
+                        }
+                    })
                     .active(|active| active.bg(style.active(cx).background))
             })
             .when_some(
@@ -440,6 +440,32 @@ mod tests {
         assert_eq!(adjusted, black); // Should remain unchanged
     }
 
+    #[test]
+    fn test_high_contrast_minimum_contrast() {
+        let bg = hsla(0.0, 0.0, 0.15, 1.0);
+        let fg = hsla(210.0 / 360.0, 0.3, 0.45, 1.0);
+        let initial_contrast = apca_contrast(fg, bg).abs();
+        assert!(
+            initial_contrast < theme::HIGH_CONTRAST_MINIMUM_CONTRAST,
+            "Initial contrast should be below the high contrast minimum, got {}",
+            initial_contrast
+        );
+
+        // Without a minimum contrast the color is left alone
+        assert_eq!(ensure_minimum_contrast(fg, bg, 0.0), fg);
+
+        // In high contrast mode the color is adjusted to meet the stricter minimum
+        let adjusted = ensure_minimum_contrast(fg, bg, theme::HIGH_CONTRAST_MINIMUM_CONTRAST);
+        assert_ne!(adjusted, fg);
+        let new_contrast = apca_contrast(adjusted, bg).abs();
+        assert!(
+            new_contrast >= theme::HIGH_CONTRAST_MINIMUM_CONTRAST,
+            "Adjusted contrast {} should be >= {}",
+            new_contrast,
+            theme::HIGH_CONTRAST_MINIMUM_CONTRAST
+        );
+    }
+
     #[test]
     fn test_one_light_theme_exact_colors() {
         // Test with exact colors from One Light theme
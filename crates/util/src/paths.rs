@@ -372,6 +372,47 @@ pub fn is_absolute(path_like: &str, path_style: PathStyle) -> bool {
                         .is_some_and(|path| path.starts_with('/') || path.starts_with('\\')))
 }
 
+/// Splits a Windows path into the WSL share, such as `\\wsl.localhost\Ubuntu\home\me`,
+/// into the name of the distro and the path inside it (`Ubuntu` and `/home/me`).
+pub fn parse_wsl_share_path(path: &str) -> Option<(String, String)> {
+    let path = path.replace('\\', "/");
+    let path = path
+        .strip_prefix("//?/UNC/")
+        .or_else(|| path.strip_prefix("//"))?;
+    let (host, rest) = path.split_once('/')?;
+    if !host.eq_ignore_ascii_case("wsl.localhost") && !host.eq_ignore_ascii_case("wsl$") {
+        return None;
+    }
+    let (distro_name, path) = match rest.split_once('/') {
+        Some((distro_name, path)) => (distro_name, path.trim_end_matches('/')),
+        None => (rest, ""),
+    };
+    if distro_name.is_empty() {
+        return None;
+    }
+    Some((distro_name.to_string(), format!("/{path}")))
+}
+
+/// Translates a path inside a WSL distro to one Windows can access, either on a Windows drive
+/// (`/mnt/c/Users` becomes `C:\Users`) or through the distro's share.
+pub fn wsl_path_to_windows(path: &str, distro_name: &str) -> String {
+    if let Some(rest) = path.strip_prefix("/mnt/") {
+        let (drive, rest) = rest.split_once('/').unwrap_or((rest, ""));
+        if drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic()) {
+            return format!(
+                "{}:\\{}",
+                drive.to_ascii_uppercase(),
+                rest.replace('/', "\\")
+            );
+        }
+    }
+    format!(
+        "\\\\wsl.localhost\\{}{}",
+        distro_name,
+        path.trim_end_matches('/').replace('/', "\\")
+    )
+}
+
 /// A delimiter to use in `path_query:row_number:column_number` strings parsing.
 pub const FILE_ROW_COLUMN_DELIMITER: char = ':';
 
@@ -1769,4 +1810,37 @@ mod tests {
         let path = Path::new("/a/b/c/long.app.tar.gz");
         assert_eq!(path.multiple_extensions(), Some("app.tar.gz".to_string()));
     }
+
+    #[test]
+    fn test_wsl_path_translation() {
+        assert_eq!(
+            parse_wsl_share_path(r"\\wsl.localhost\Ubuntu\home\me\project"),
+            Some(("Ubuntu".to_string(), "/home/me/project".to_string()))
+        );
+        assert_eq!(
+            parse_wsl_share_path(r"\\?\UNC\wsl$\Debian\etc\"),
+            Some(("Debian".to_string(), "/etc".to_string()))
+        );
+        assert_eq!(
+            parse_wsl_share_path("//WSL.LOCALHOST/Ubuntu"),
+            Some(("Ubuntu".to_string(), "/".to_string()))
+        );
+        assert_eq!(parse_wsl_share_path(r"\\server\share\file"), None);
+        assert_eq!(parse_wsl_share_path(r"C:\Users\me"), None);
+        assert_eq!(parse_wsl_share_path(r"\\wsl.localhost\"), None);
+
+        assert_eq!(
+            wsl_path_to_windows("/mnt/c/Users/me/file.txt", "Ubuntu"),
+            r"C:\Users\me\file.txt"
+        );
+        assert_eq!(wsl_path_to_windows("/mnt/d", "Ubuntu"), r"D:\");
+        assert_eq!(
+            wsl_path_to_windows("/home/me/project/", "Ubuntu"),
+            r"\\wsl.localhost\Ubuntu\home\me\project"
+        );
+        assert_eq!(
+            wsl_path_to_windows("/mnt/wsl/shared", "Ubuntu"),
+            r"\\wsl.localhost\Ubuntu\mnt\wsl\shared"
+        );
+    }
 }
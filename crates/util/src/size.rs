@@ -4,15 +4,19 @@ pub fn format_file_size(size: u64, use_decimal: bool) -> String {
             format!("{size}B")
         } else if size < 1000 * 1000 {
             format!("{:.1}KB", size as f64 / 1000.0)
-        } else {
+        } else if size < 1000 * 1000 * 1000 {
             format!("{:.1}MB", size as f64 / (1000.0 * 1000.0))
+        } else {
+            format!("{:.1}GB", size as f64 / (1000.0 * 1000.0 * 1000.0))
         }
     } else if size < 1024 {
         format!("{size}B")
     } else if size < 1024 * 1024 {
         format!("{:.1}KiB", size as f64 / 1024.0)
-    } else {
+    } else if size < 1024 * 1024 * 1024 {
         format!("{:.1}MiB", size as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1}GiB", size as f64 / (1024.0 * 1024.0 * 1024.0))
     }
 }
 
@@ -30,6 +34,7 @@ mod tests {
         assert_eq!(format_file_size(1000000, true), "1.0MB");
         assert_eq!(format_file_size(1500000, true), "1.5MB");
         assert_eq!(format_file_size(10000000, true), "10.0MB");
+        assert_eq!(format_file_size(1500000000, true), "1.5GB");
     }
 
     #[test]
@@ -42,5 +47,6 @@ mod tests {
         assert_eq!(format_file_size(1048576, false), "1.0MiB");
         assert_eq!(format_file_size(1572864, false), "1.5MiB");
         assert_eq!(format_file_size(10485760, false), "10.0MiB");
+        assert_eq!(format_file_size(1610612736, false), "1.5GiB");
     }
 }
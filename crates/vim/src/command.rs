@@ -26,7 +26,7 @@ use std::{
     sync::OnceLock,
     time::Instant,
 };
-use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
+use task::{HideStrategy, NotifyStrategy, RevealStrategy, SpawnInTerminal, TaskId};
 use ui::ActiveTheme;
 use util::{ResultExt, rel_path::RelPath};
 use workspace::{Item, SaveIntent, notifications::NotifyResultExt};
@@ -1906,6 +1906,8 @@ impl ShellExec {
                     command_label: command.clone(),
                     cwd,
                     env: HashMap::default(),
+                    env_files: Vec::new(),
+                    secrets: HashMap::default(),
                     use_new_terminal: true,
                     allow_concurrent_runs: true,
                     reveal: RevealStrategy::NoFocus,
@@ -1915,6 +1917,9 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matchers: Vec::new(),
+                    notify: NotifyStrategy::Never,
+                    watch: None,
                 };
 
                 let task_status = workspace.spawn_in_terminal(spawn_in_terminal, window, cx);
@@ -1,14 +1,15 @@
 use std::{path::Path, sync::Arc};
 
-use gpui::{EventEmitter, FocusHandle, Focusable};
+use gpui::{Action as _, EventEmitter, FocusHandle, Focusable};
+use project::buffer_store::ProtectedFileError;
 use ui::{
     App, Button, ButtonCommon, ButtonStyle, Clickable, Context, FluentBuilder, InteractiveElement,
     KeyBinding, Label, LabelCommon, LabelSize, ParentElement, Render, SharedString, Styled as _,
     Window, h_flex, v_flex,
 };
-use zed_actions::workspace::OpenWithSystem;
+use zed_actions::{hex_editor::ReopenAsHex, workspace::OpenWithSystem};
 
-use crate::Item;
+use crate::{Item, OpenAnyway};
 
 /// A view to display when a certain buffer fails to open.
 pub struct InvalidBufferView {
@@ -17,6 +18,11 @@ pub struct InvalidBufferView {
     /// An error message, happened when opening the buffer.
     pub error: SharedString,
     is_local: bool,
+    /// Whether the file failed to open because it isn't valid UTF-8, so it can be opened as hex.
+    is_binary: bool,
+    /// Whether the file wasn't opened because it was detected as too large or binary, so it can
+    /// be opened as text anyway.
+    is_protected: bool,
     focus_handle: FocusHandle,
 }
 
@@ -28,8 +34,17 @@ impl InvalidBufferView {
         _: &mut Window,
         cx: &mut App,
     ) -> Self {
+        let root_cause = e.root_cause();
+        let protected_file_error = root_cause.downcast_ref::<ProtectedFileError>();
+        let is_binary = root_cause.is::<std::string::FromUtf8Error>()
+            || root_cause
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|e| e.kind() == std::io::ErrorKind::InvalidData)
+            || protected_file_error.is_some();
         Self {
             is_local,
+            is_binary,
+            is_protected: protected_file_error.is_some(),
             abs_path: Arc::from(abs_path),
             error: format!("{}", e.root_cause()).into(),
             focus_handle: cx.focus_handle(),
@@ -110,6 +125,38 @@ impl Render for InvalidBufferView {
                                         )),
                                 ),
                             )
+                        })
+                        .when(self.is_local && self.is_protected, |contents| {
+                            contents.child(
+                                h_flex().justify_center().child(
+                                    Button::new("open-anyway", "Open Anyway")
+                                        .on_click(|_, window, cx| {
+                                            window.dispatch_action(OpenAnyway.boxed_clone(), cx);
+                                        })
+                                        .style(ButtonStyle::Outlined)
+                                        .key_binding(KeyBinding::for_action(
+                                            &OpenAnyway,
+                                            window,
+                                            cx,
+                                        )),
+                                ),
+                            )
+                        })
+                        .when(self.is_binary, |contents| {
+                            contents.child(
+                                h_flex().justify_center().child(
+                                    Button::new("reopen-as-hex", "Open as Hex")
+                                        .on_click(|_, window, cx| {
+                                            window.dispatch_action(ReopenAsHex.boxed_clone(), cx);
+                                        })
+                                        .style(ButtonStyle::Outlined)
+                                        .key_binding(KeyBinding::for_action(
+                                            &ReopenAsHex,
+                                            window,
+                                            cx,
+                                        )),
+                                ),
+                            )
                         }),
                 ),
             )
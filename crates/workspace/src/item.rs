@@ -284,6 +284,11 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     fn has_conflict(&self, _: &App) -> bool {
         false
     }
+    /// Whether the item's file was changed on disk by something other than Zed while the item
+    /// wasn't focused, and the change hasn't been reviewed yet.
+    fn has_external_changes(&self, _: &App) -> bool {
+        false
+    }
     fn can_save(&self, _cx: &App) -> bool {
         false
     }
@@ -365,6 +370,17 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     fn include_in_nav_history() -> bool {
         true
     }
+
+    /// Handles project entries or external paths dropped onto the item's content, instead of
+    /// the pane opening them. Returns whether the drop was handled.
+    fn handle_drop(
+        &mut self,
+        _dropped: &dyn Any,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> bool {
+        false
+    }
 }
 
 pub trait SerializableItem: Item {
@@ -491,6 +507,7 @@ pub trait ItemHandle: 'static + Send {
     fn is_dirty(&self, cx: &App) -> bool;
     fn has_deleted_file(&self, cx: &App) -> bool;
     fn has_conflict(&self, cx: &App) -> bool;
+    fn has_external_changes(&self, cx: &App) -> bool;
     fn can_save(&self, cx: &App) -> bool;
     fn can_save_as(&self, cx: &App) -> bool;
     fn save(
@@ -531,6 +548,7 @@ pub trait ItemHandle: 'static + Send {
     fn preserve_preview(&self, cx: &App) -> bool;
     fn include_in_nav_history(&self) -> bool;
     fn relay_action(&self, action: Box<dyn Action>, window: &mut Window, cx: &mut App);
+    fn handle_drop(&self, dropped: &dyn Any, window: &mut Window, cx: &mut App) -> bool;
     fn can_autosave(&self, cx: &App) -> bool {
         let is_deleted = self.project_entry_ids(cx).is_empty();
         self.is_dirty(cx) && !self.has_conflict(cx) && self.can_save(cx) && !is_deleted
@@ -952,6 +970,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.read(cx).has_conflict(cx)
     }
 
+    fn has_external_changes(&self, cx: &App) -> bool {
+        self.read(cx).has_external_changes(cx)
+    }
+
     fn can_save(&self, cx: &App) -> bool {
         self.read(cx).can_save(cx)
     }
@@ -1047,6 +1069,10 @@ impl<T: Item> ItemHandle for Entity<T> {
             window.dispatch_action(action, cx);
         })
     }
+
+    fn handle_drop(&self, dropped: &dyn Any, window: &mut Window, cx: &mut App) -> bool {
+        self.update(cx, |this, cx| this.handle_drop(dropped, window, cx))
+    }
 }
 
 impl From<Box<dyn ItemHandle>> for AnyView {
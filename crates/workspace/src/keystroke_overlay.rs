@@ -0,0 +1,236 @@
+use std::{collections::VecDeque, time::Duration};
+
+use gpui::{Animation, AnimationExt as _, Keystroke, Subscription, Task};
+use ui::{prelude::*, text_for_keystrokes};
+
+use crate::{ToggleKeystrokeOverlay, Workspace};
+
+/// How long the overlay stays visible after the last key is pressed, before it fades out.
+const KEYSTROKE_DURATION: Duration = Duration::from_secs(2);
+
+/// How long the overlay takes to fade out.
+const FADE_DURATION: Duration = Duration::from_millis(300);
+
+/// The maximum number of keystrokes shown in the overlay at once.
+const MAX_KEYSTROKES: usize = 6;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(Workspace::toggle_keystroke_overlay);
+    })
+    .detach();
+}
+
+impl Workspace {
+    fn toggle_keystroke_overlay(
+        &mut self,
+        _: &ToggleKeystrokeOverlay,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_keystroke_overlay_visible(self.keystroke_overlay.is_none(), cx);
+    }
+
+    pub(crate) fn set_keystroke_overlay_visible(&mut self, visible: bool, cx: &mut Context<Self>) {
+        if visible {
+            if self.keystroke_overlay.is_none() {
+                self.keystroke_overlay = Some(cx.new(KeystrokeOverlay::new));
+            }
+        } else {
+            self.keystroke_overlay = None;
+        }
+        cx.notify();
+    }
+
+    /// Whether the overlay showing the keys that are pressed is visible.
+    pub fn is_keystroke_overlay_visible(&self) -> bool {
+        self.keystroke_overlay.is_some()
+    }
+}
+
+/// An overlay that shows the keys that are pressed and the actions they run, for screencasts,
+/// pairing, and debugging key bindings.
+pub struct KeystrokeOverlay {
+    /// The latest keystrokes, oldest first, with the name of the action each one ran.
+    keystrokes: VecDeque<(Keystroke, Option<SharedString>)>,
+    /// While the keystrokes fade out, an id that's unique to this fade, so that the animation
+    /// restarts each time.
+    fade: Option<usize>,
+    fade_count: usize,
+    _hide_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl KeystrokeOverlay {
+    fn new(cx: &mut Context<Self>) -> Self {
+        let subscription = cx.observe_keystrokes(|this, event, window, cx| {
+            let action = event
+                .action
+                .as_ref()
+                .map(|action| humanize_action_name(action.name()).into());
+            this.push(event.keystroke.clone(), action, window, cx);
+        });
+        Self {
+            keystrokes: VecDeque::new(),
+            fade: None,
+            fade_count: 0,
+            _hide_task: Task::ready(()),
+            _subscription: subscription,
+        }
+    }
+
+    fn push(
+        &mut self,
+        keystroke: Keystroke,
+        action: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.fade.is_some() {
+            self.keystrokes.clear();
+            self.fade = None;
+        }
+        if self.keystrokes.len() == MAX_KEYSTROKES {
+            self.keystrokes.pop_front();
+        }
+        self.keystrokes.push_back((keystroke, action));
+        self._hide_task = cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(KEYSTROKE_DURATION).await;
+            this.update(cx, |this, cx| {
+                this.fade_count += 1;
+                this.fade = Some(this.fade_count);
+                cx.notify();
+            })
+            .ok();
+            cx.background_executor().timer(FADE_DURATION).await;
+            this.update(cx, |this, cx| {
+                this.keystrokes.clear();
+                this.fade = None;
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+}
+
+/// Turns an action name like `editor::MoveLineUp` into `Move Line Up`.
+fn humanize_action_name(name: &str) -> String {
+    let name = name.rsplit("::").next().unwrap_or(name);
+    let mut result = String::with_capacity(name.len() + 4);
+    for (index, character) in name.char_indices() {
+        if index > 0 && character.is_uppercase() {
+            result.push(' ');
+        }
+        result.push(character);
+    }
+    result
+}
+
+impl Render for KeystrokeOverlay {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        let latest_action = self.keystrokes.back().map(|(_, action)| action.clone());
+
+        let content = v_flex()
+            .items_center()
+            .gap_1()
+            .px_4()
+            .py_2()
+            .rounded_lg()
+            .border_1()
+            .border_color(colors.border)
+            .bg(colors.elevated_surface_background.opacity(0.9))
+            .shadow_lg()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .text_color(colors.text)
+                    .text_2xl()
+                    .font_buffer(cx)
+                    .children(self.keystrokes.iter().map(|(keystroke, _)| {
+                        text_for_keystrokes(std::slice::from_ref(keystroke), cx)
+                    })),
+            )
+            .children(latest_action.map(|action| {
+                match action {
+                    Some(action) => Label::new(action)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    None => Label::new("No action")
+                        .size(LabelSize::Small)
+                        .color(Color::Disabled)
+                        .italic(),
+                }
+            }));
+
+        h_flex()
+            .absolute()
+            .bottom_8()
+            .left_0()
+            .right_0()
+            .justify_center()
+            .when(!self.keystrokes.is_empty(), |this| {
+                this.child(match self.fade {
+                    Some(fade) => content
+                        .with_animation(
+                            ("keystroke-overlay-fade", fade),
+                            Animation::new(FADE_DURATION),
+                            |content, delta| content.opacity(1. - delta),
+                        )
+                        .into_any_element(),
+                    None => content.into_any_element(),
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::init_test;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use project::Project;
+
+    #[gpui::test]
+    async fn test_keystroke_overlay(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let overlay = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_keystroke_overlay(&ToggleKeystrokeOverlay, window, cx);
+            workspace.keystroke_overlay.clone().unwrap()
+        });
+
+        cx.simulate_keystrokes("a b c d e f g");
+        overlay.read_with(cx, |overlay, _| {
+            let keys = overlay
+                .keystrokes
+                .iter()
+                .map(|(keystroke, _)| keystroke.key.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, ["b", "c", "d", "e", "f", "g"]);
+        });
+
+        // The keystrokes fade out once no keys have been pressed for a while.
+        cx.executor().advance_clock(KEYSTROKE_DURATION);
+        overlay.read_with(cx, |overlay, _| assert!(overlay.fade.is_some()));
+        cx.executor().advance_clock(FADE_DURATION);
+        overlay.read_with(cx, |overlay, _| assert!(overlay.keystrokes.is_empty()));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_keystroke_overlay(&ToggleKeystrokeOverlay, window, cx);
+            assert!(!workspace.is_keystroke_overlay_visible());
+        });
+    }
+
+    #[test]
+    fn test_humanize_action_name() {
+        assert_eq!(humanize_action_name("editor::MoveLineUp"), "Move Line Up");
+        assert_eq!(humanize_action_name("Save"), "Save");
+    }
+}
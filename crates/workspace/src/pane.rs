@@ -433,6 +433,15 @@ pub struct NavigationEntry {
     pub is_preview: bool,
 }
 
+/// An item that was closed in a pane, and can be reopened from its path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClosedItem {
+    pub item_id: EntityId,
+    pub timestamp: usize,
+    pub project_path: ProjectPath,
+    pub abs_path: Option<PathBuf>,
+}
+
 #[derive(Clone)]
 pub struct DraggedTab {
     pub pane: Entity<Pane>,
@@ -3252,6 +3261,21 @@ impl Pane {
             .log_err();
     }
 
+    /// Lets the active item handle something dropped onto the pane's content, unless the drop
+    /// splits the pane.
+    fn drop_onto_active_item(
+        &mut self,
+        dropped: &dyn Any,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.drag_split_direction.is_some() {
+            return false;
+        }
+        self.active_item()
+            .is_some_and(|item| item.handle_drop(dropped, window, cx))
+    }
+
     fn handle_dragged_selection_drop(
         &mut self,
         dragged_selection: &DraggedSelection,
@@ -3801,11 +3825,17 @@ impl Render for Pane {
                             }))
                             .on_drop(cx.listener(
                                 move |this, selection: &DraggedSelection, window, cx| {
-                                    this.handle_dragged_selection_drop(selection, None, window, cx)
+                                    if !this.drop_onto_active_item(selection, window, cx) {
+                                        this.handle_dragged_selection_drop(
+                                            selection, None, window, cx,
+                                        )
+                                    }
                                 },
                             ))
-                            .on_drop(cx.listener(move |this, paths, window, cx| {
-                                this.handle_external_paths_drop(paths, window, cx)
+                            .on_drop(cx.listener(move |this, paths: &ExternalPaths, window, cx| {
+                                if !this.drop_onto_active_item(paths, window, cx) {
+                                    this.handle_external_paths_drop(paths, window, cx)
+                                }
                             }))
                             .map(|div| {
                                 let size = DefiniteLength::Fraction(0.5);
@@ -4013,6 +4043,51 @@ impl NavHistory {
     pub fn path_for_item(&self, item_id: EntityId) -> Option<(ProjectPath, Option<PathBuf>)> {
         self.0.lock().paths_by_item.get(&item_id).cloned()
     }
+
+    /// The timestamp of the most recently closed item, if any.
+    pub fn latest_closed_timestamp(&self) -> Option<usize> {
+        self.0
+            .lock()
+            .closed_stack
+            .back()
+            .map(|entry| entry.timestamp)
+    }
+
+    /// The closed items that can be reopened, most recently closed first.
+    pub fn closed_items(&self) -> Vec<ClosedItem> {
+        let state = self.0.lock();
+        state
+            .closed_stack
+            .iter()
+            .rev()
+            .filter_map(|entry| {
+                let (project_path, abs_path) = state.paths_by_item.get(&entry.item.id())?.clone();
+                Some(ClosedItem {
+                    item_id: entry.item.id(),
+                    timestamp: entry.timestamp,
+                    project_path,
+                    abs_path,
+                })
+            })
+            .collect()
+    }
+
+    /// Moves the most recent closing of the given item to the top of the closed stack,
+    /// so that it's the next one to be reopened.
+    pub fn move_closed_item_to_top(&mut self, item_id: EntityId) -> bool {
+        let mut state = self.0.lock();
+        let Some(ix) = state
+            .closed_stack
+            .iter()
+            .rposition(|entry| entry.item.id() == item_id)
+        else {
+            return false;
+        };
+        if let Some(entry) = state.closed_stack.remove(ix) {
+            state.closed_stack.push_back(entry);
+        }
+        true
+    }
 }
 
 impl NavHistoryState {
@@ -4075,6 +4150,7 @@ pub fn render_item_indicator(item: Box<dyn ItemHandle>, cx: &App) -> Option<Indi
         let indicator_color = match (item.has_conflict(cx), item.is_dirty(cx)) {
             (true, _) => Color::Warning,
             (_, true) => Color::Accent,
+            (false, false) if item.has_external_changes(cx) => Color::Info,
             (false, false) => return None,
         };
 
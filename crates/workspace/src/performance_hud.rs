@@ -0,0 +1,343 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use collections::HashMap;
+use gpui::{FrameStats, Task};
+use project::DirectoryLister;
+use ui::prelude::*;
+use zed_actions::dev::{ExportFrameTrace, TogglePerformanceHud};
+
+use crate::{Workspace, notifications::DetachAndPromptErr as _};
+
+/// How often the HUD shows the statistics of the latest frames.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The number of frames shown in the frame time graph.
+const GRAPH_FRAME_COUNT: usize = 60;
+
+/// The time available to draw a frame at 60 frames per second.
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+/// The number of views listed as the slowest to render.
+const SLOWEST_VIEW_COUNT: usize = 5;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(Workspace::toggle_performance_hud);
+        workspace.register_action(Workspace::export_frame_trace);
+    })
+    .detach();
+}
+
+impl Workspace {
+    fn toggle_performance_hud(
+        &mut self,
+        _: &TogglePerformanceHud,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.performance_hud.take().is_some() {
+            window.set_frame_profiling_enabled(false);
+        } else {
+            window.set_frame_profiling_enabled(true);
+            self.performance_hud = Some(cx.new(|cx| PerformanceHud::new(window, cx)));
+        }
+        cx.notify();
+    }
+
+    fn export_frame_trace(
+        &mut self,
+        _: &ExportFrameTrace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(trace) = window.frame_trace() else {
+            self.show_error(
+                &anyhow!("Toggle the performance HUD to record frames before exporting a trace"),
+                cx,
+            );
+            return;
+        };
+
+        let fs = self.app_state.fs.clone();
+        let lister = DirectoryLister::Local(self.project.clone(), fs.clone());
+        let path = self.prompt_for_new_path(lister, Some("zed-trace.json".into()), window, cx);
+        cx.spawn(async move |_, _| {
+            let Some(path) = path.await.ok().flatten().into_iter().flatten().next() else {
+                return Ok(());
+            };
+            fs.atomic_write(path, trace).await
+        })
+        .detach_and_prompt_err("Failed to export frame trace", window, cx, |_, _, _| None);
+    }
+}
+
+/// An overlay that shows how long the window takes to draw frames, so that slowness can be
+/// diagnosed.
+pub struct PerformanceHud {
+    _refresh_task: Task<()>,
+}
+
+impl PerformanceHud {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let refresh_task = cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor().timer(REFRESH_INTERVAL).await;
+                if this.update(cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            _refresh_task: refresh_task,
+        }
+    }
+}
+
+/// Statistics about the frames drawn in the last second.
+struct Summary {
+    frame_count: usize,
+    mean_duration: Duration,
+    max_duration: Duration,
+    latest_frame: FrameStats,
+    text_shaping_cache_hit_rate: Option<f32>,
+    slowest_views: Vec<(&'static str, Duration)>,
+    /// The durations of the latest frames, oldest first.
+    graph: Vec<Duration>,
+}
+
+impl Summary {
+    fn new<'a>(frames: impl DoubleEndedIterator<Item = &'a FrameStats>) -> Option<Self> {
+        let mut frames = frames.rev().peekable();
+        let latest_frame = (*frames.peek()?).clone();
+        let since = latest_frame.start.saturating_sub(Duration::from_secs(1));
+
+        let mut frame_count = 0;
+        let mut total_duration = Duration::ZERO;
+        let mut max_duration = Duration::ZERO;
+        let mut cache_hits = 0;
+        let mut cache_misses = 0;
+        let mut view_durations = HashMap::<&'static str, Duration>::default();
+        let mut graph = Vec::new();
+        for (index, frame) in frames.enumerate() {
+            let in_graph = index < GRAPH_FRAME_COUNT;
+            let in_last_second = frame.start >= since;
+            if in_graph {
+                graph.push(frame.duration);
+            }
+            if !in_last_second {
+                if in_graph {
+                    continue;
+                }
+                break;
+            }
+            frame_count += 1;
+            total_duration += frame.duration;
+            max_duration = max_duration.max(frame.duration);
+            cache_hits += frame.text_shaping_cache_hits;
+            cache_misses += frame.text_shaping_cache_misses;
+            for view in &frame.slowest_views {
+                let duration = view_durations.entry(view.view_type).or_default();
+                *duration = (*duration).max(view.duration);
+            }
+        }
+        graph.reverse();
+
+        let mut slowest_views = view_durations.into_iter().collect::<Vec<_>>();
+        slowest_views.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        slowest_views.truncate(SLOWEST_VIEW_COUNT);
+
+        let cache_lookups = cache_hits + cache_misses;
+        Some(Self {
+            frame_count,
+            mean_duration: total_duration / frame_count.max(1) as u32,
+            max_duration,
+            latest_frame,
+            text_shaping_cache_hit_rate: (cache_lookups > 0)
+                .then(|| cache_hits as f32 / cache_lookups as f32),
+            slowest_views,
+            graph,
+        })
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Returns the view's type name without its module path.
+fn short_type_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    let start = name.rfind("::").map_or(0, |index| index + 2);
+    &type_name[start..]
+}
+
+impl Render for PerformanceHud {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let summary = Summary::new(window.frame_stats());
+        let colors = cx.theme().colors();
+        let status = cx.theme().status();
+
+        let row = |label: &'static str, value: String| {
+            h_flex()
+                .gap_4()
+                .justify_between()
+                .child(div().text_color(colors.text_muted).child(label))
+                .child(value)
+        };
+
+        let content = match summary {
+            None => div().child("Waiting for frames…").into_any_element(),
+            Some(summary) => {
+                let graph_height = 32.;
+                let graph = h_flex().h(px(graph_height)).items_end().gap_px().children(
+                    summary.graph.iter().map(|duration| {
+                        let fraction = duration.as_secs_f32() / (FRAME_BUDGET.as_secs_f32() * 2.);
+                        div()
+                            .w(px(3.))
+                            .h(px((fraction.min(1.) * graph_height).max(1.)))
+                            .bg(if *duration > FRAME_BUDGET {
+                                status.error
+                            } else {
+                                status.success
+                            })
+                    }),
+                );
+
+                let frame = &summary.latest_frame;
+                v_flex()
+                    .gap_0p5()
+                    .child(graph)
+                    .child(row(
+                        "Frames in last second",
+                        summary.frame_count.to_string(),
+                    ))
+                    .child(row(
+                        "Mean frame time",
+                        format_duration(summary.mean_duration),
+                    ))
+                    .child(row("Max frame time", format_duration(summary.max_duration)))
+                    .child(row(
+                        "Last frame layout / paint",
+                        format!(
+                            "{} / {}",
+                            format_duration(frame.layout_duration),
+                            format_duration(frame.paint_duration)
+                        ),
+                    ))
+                    .child(row("Elements", frame.element_count.to_string()))
+                    .child(row("Layout nodes", frame.layout_node_count.to_string()))
+                    .child(row(
+                        "Text shaping cache hits",
+                        summary
+                            .text_shaping_cache_hit_rate
+                            .map_or("–".into(), |rate| format!("{:.1}%", rate * 100.)),
+                    ))
+                    .child(
+                        div()
+                            .pt_1()
+                            .text_color(colors.text_muted)
+                            .child("Slowest views"),
+                    )
+                    .children(summary.slowest_views.iter().map(|(view_type, duration)| {
+                        h_flex()
+                            .gap_4()
+                            .justify_between()
+                            .child(short_type_name(view_type).to_string())
+                            .child(format_duration(*duration))
+                    }))
+                    .into_any_element()
+            }
+        };
+
+        v_flex()
+            .id("performance-hud")
+            .occlude()
+            .absolute()
+            .top_2()
+            .right_2()
+            .w_72()
+            .p_2()
+            .gap_1()
+            .rounded_md()
+            .border_1()
+            .border_color(colors.border)
+            .bg(colors.elevated_surface_background.opacity(0.9))
+            .text_color(colors.text)
+            .text_xs()
+            .font_buffer(cx)
+            .child(
+                h_flex().justify_between().child("Performance").child(
+                    IconButton::new("close-performance-hud", IconName::Close)
+                        .icon_size(IconSize::XSmall)
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(Box::new(TogglePerformanceHud), cx)
+                        }),
+                ),
+            )
+            .child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(start_millis: u64, duration_millis: u64, views: &[(&'static str, u64)]) -> FrameStats {
+        FrameStats {
+            start: Duration::from_millis(start_millis),
+            duration: Duration::from_millis(duration_millis),
+            text_shaping_cache_hits: 3,
+            text_shaping_cache_misses: 1,
+            slowest_views: views
+                .iter()
+                .map(|(view_type, millis)| gpui::ViewRenderStats {
+                    view_type: *view_type,
+                    start: Duration::from_millis(start_millis),
+                    duration: Duration::from_millis(*millis),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        assert!(Summary::new([].iter()).is_none());
+
+        let frames = [
+            frame(0, 40, &[("editor::Editor", 30)]),
+            frame(
+                1500,
+                10,
+                &[("editor::Editor", 4), ("workspace::pane::Pane", 2)],
+            ),
+            frame(2000, 20, &[("editor::Editor", 8)]),
+            frame(2400, 6, &[("workspace::pane::Pane", 3)]),
+        ];
+        let summary = Summary::new(frames.iter()).unwrap();
+        assert_eq!(summary.frame_count, 3);
+        assert_eq!(summary.mean_duration, Duration::from_millis(12));
+        assert_eq!(summary.max_duration, Duration::from_millis(20));
+        assert_eq!(summary.latest_frame.start, Duration::from_millis(2400));
+        assert_eq!(summary.text_shaping_cache_hit_rate, Some(0.75));
+        assert_eq!(
+            summary.slowest_views,
+            [
+                ("editor::Editor", Duration::from_millis(8)),
+                ("workspace::pane::Pane", Duration::from_millis(3)),
+            ]
+        );
+        assert_eq!(summary.graph, [40, 10, 20, 6].map(Duration::from_millis));
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name("workspace::pane::Pane"), "Pane");
+        assert_eq!(
+            short_type_name("editor::Editor<language::Buffer>"),
+            "Editor<language::Buffer>"
+        );
+        assert_eq!(short_type_name("Workspace"), "Workspace");
+    }
+}
@@ -20,7 +20,9 @@ use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
 
 use language::{LanguageName, Toolchain, ToolchainScope};
 use project::WorktreeId;
-use remote::{RemoteConnectionOptions, SshConnectionOptions, WslConnectionOptions};
+use remote::{
+    DockerConnectionOptions, RemoteConnectionOptions, SshConnectionOptions, WslConnectionOptions,
+};
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
@@ -42,7 +44,7 @@ use model::{
     SerializedPaneGroup, SerializedWorkspace,
 };
 
-use self::model::{DockStructure, SerializedWorkspaceLocation};
+use self::model::{DockStructure, RecentWorkspaceMetadata, SerializedWorkspaceLocation};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub(crate) struct SerializedAxis(pub(crate) gpui::Axis);
@@ -702,6 +704,10 @@ impl Domain for WorkspaceDb {
         sql!(
             DROP TABLE ssh_connections;
         ),
+        sql!(
+            ALTER TABLE workspaces ADD COLUMN pinned INTEGER DEFAULT 0 NOT NULL; //bool
+            ALTER TABLE workspaces ADD COLUMN display_name TEXT;
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1127,6 +1133,13 @@ impl WorkspaceDb {
                 distro = Some(options.distro_name);
                 user = options.user;
             }
+            RemoteConnectionOptions::Docker(options) => {
+                // Containers have no distro, so that column holds the container's display name.
+                kind = RemoteConnectionKind::Docker;
+                host = Some(options.container_id);
+                distro = Some(options.name);
+                user = options.user;
+            }
         }
         Self::get_or_create_remote_connection_query(this, kind, host, port, user, distro)
     }
@@ -1296,6 +1309,13 @@ impl WorkspaceDb {
                 username: user,
                 ..Default::default()
             })),
+            RemoteConnectionKind::Docker => {
+                Some(RemoteConnectionOptions::Docker(DockerConnectionOptions {
+                    container_id: host?,
+                    name: distro?,
+                    user,
+                }))
+            }
         }
     }
 
@@ -1324,6 +1344,67 @@ impl WorkspaceDb {
         }
     }
 
+    pub async fn delete_workspaces_by_id(&self, ids: Vec<WorkspaceId>) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("delete_workspaces", || {
+                let mut delete = conn.exec_bound(sql!(
+                    DELETE FROM workspaces
+                    WHERE workspace_id IS ?
+                ))?;
+                for id in ids {
+                    delete(id)?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    pub fn recent_workspaces_metadata(
+        &self,
+    ) -> Result<HashMap<WorkspaceId, RecentWorkspaceMetadata>> {
+        Ok(self
+            .recent_workspaces_metadata_query()?
+            .into_iter()
+            .map(|(id, pinned, display_name, last_opened)| {
+                (
+                    id,
+                    RecentWorkspaceMetadata {
+                        pinned,
+                        display_name,
+                        last_opened,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    query! {
+        fn recent_workspaces_metadata_query() -> Result<Vec<(WorkspaceId, bool, Option<String>, Option<i64>)>> {
+            SELECT workspace_id, pinned, display_name, unixepoch(timestamp)
+            FROM workspaces
+            WHERE
+                paths IS NOT NULL OR
+                remote_connection_id IS NOT NULL
+        }
+    }
+
+    query! {
+        pub async fn set_workspace_pinned(workspace_id: WorkspaceId, pinned: bool) -> Result<()> {
+            UPDATE workspaces
+            SET pinned = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
+    query! {
+        pub async fn set_workspace_display_name(workspace_id: WorkspaceId, display_name: Option<String>) -> Result<()> {
+            UPDATE workspaces
+            SET display_name = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
     // Returns the recent locations which are still valid on disk and deletes ones which no longer
     // exist.
     pub async fn recent_workspaces_on_disk(
@@ -1370,14 +1451,14 @@ impl WorkspaceDb {
     }
 
     // Returns the locations of the workspaces that were still opened when the last
-    // session was closed (i.e. when Zed was quit).
+    // session was closed (i.e. when Zed was quit), along with the windows they were shown in.
     // If `last_session_window_order` is provided, the returned locations are ordered
     // according to that.
     pub fn last_session_workspace_locations(
         &self,
         last_session_id: &str,
         last_session_window_stack: Option<Vec<WindowId>>,
-    ) -> Result<Vec<(SerializedWorkspaceLocation, PathList)>> {
+    ) -> Result<Vec<(SerializedWorkspaceLocation, PathList, Option<WindowId>)>> {
         let mut workspaces = Vec::new();
 
         for (paths, window_id, remote_connection_id) in
@@ -1410,10 +1491,7 @@ impl WorkspaceDb {
             });
         }
 
-        Ok(workspaces
-            .into_iter()
-            .map(|(location, paths, _)| (location, paths))
-            .collect::<Vec<_>>())
+        Ok(workspaces)
     }
 
     fn get_center_pane_group(&self, workspace_id: WorkspaceId) -> Result<SerializedPaneGroup> {
@@ -2559,6 +2637,43 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_recent_workspaces_metadata() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_recent_workspaces_metadata").await;
+
+        let mut workspace_1 = default_workspace(&["/tmp1"], &Default::default());
+        workspace_1.id = WorkspaceId(1);
+        let mut workspace_2 = default_workspace(&["/tmp2"], &Default::default());
+        workspace_2.id = WorkspaceId(2);
+        db.save_workspace(workspace_1.clone()).await;
+        db.save_workspace(workspace_2.clone()).await;
+
+        db.set_workspace_pinned(WorkspaceId(1), true).await.unwrap();
+        db.set_workspace_display_name(WorkspaceId(2), Some("Second".to_owned()))
+            .await
+            .unwrap();
+        let metadata = db.recent_workspaces_metadata().unwrap();
+        assert!(metadata[&WorkspaceId(1)].pinned);
+        assert_eq!(metadata[&WorkspaceId(1)].display_name, None);
+        assert!(!metadata[&WorkspaceId(2)].pinned);
+        assert_eq!(
+            metadata[&WorkspaceId(2)].display_name.as_deref(),
+            Some("Second")
+        );
+        assert!(metadata[&WorkspaceId(2)].last_opened.is_some());
+
+        // Saving the workspace again keeps the user's metadata.
+        db.save_workspace(workspace_1).await;
+        assert!(db.recent_workspaces_metadata().unwrap()[&WorkspaceId(1)].pinned);
+
+        db.delete_workspaces_by_id(vec![WorkspaceId(1), WorkspaceId(2)])
+            .await
+            .unwrap();
+        assert!(db.recent_workspaces_metadata().unwrap().is_empty());
+    }
+
     #[gpui::test]
     async fn test_last_session_workspace_locations() {
         let dir1 = tempfile::TempDir::with_prefix("dir1").unwrap();
@@ -2615,27 +2730,33 @@ mod tests {
             [
                 (
                     SerializedWorkspaceLocation::Local,
-                    PathList::new(&[dir4.path()])
+                    PathList::new(&[dir4.path()]),
+                    Some(WindowId::from(2)),
                 ),
                 (
                     SerializedWorkspaceLocation::Local,
-                    PathList::new(&[dir3.path()])
+                    PathList::new(&[dir3.path()]),
+                    Some(WindowId::from(8)),
                 ),
                 (
                     SerializedWorkspaceLocation::Local,
-                    PathList::new(&[dir2.path()])
+                    PathList::new(&[dir2.path()]),
+                    Some(WindowId::from(5)),
                 ),
                 (
                     SerializedWorkspaceLocation::Local,
-                    PathList::new(&[dir1.path()])
+                    PathList::new(&[dir1.path()]),
+                    Some(WindowId::from(9)),
                 ),
                 (
                     SerializedWorkspaceLocation::Local,
-                    PathList::new(&[dir1.path(), dir2.path(), dir3.path()])
+                    PathList::new(&[dir1.path(), dir2.path(), dir3.path()]),
+                    Some(WindowId::from(3)),
                 ),
                 (
                     SerializedWorkspaceLocation::Local,
-                    PathList::new(&[dir4.path(), dir3.path(), dir2.path()])
+                    PathList::new(&[dir4.path(), dir3.path(), dir2.path()]),
+                    Some(WindowId::from(4)),
                 ),
             ]
         );
@@ -2711,28 +2832,32 @@ mod tests {
             have[0],
             (
                 SerializedWorkspaceLocation::Remote(remote_connections[3].clone()),
-                PathList::default()
+                PathList::default(),
+                Some(WindowId::from(2)),
             )
         );
         assert_eq!(
             have[1],
             (
                 SerializedWorkspaceLocation::Remote(remote_connections[2].clone()),
-                PathList::default()
+                PathList::default(),
+                Some(WindowId::from(8)),
             )
         );
         assert_eq!(
             have[2],
             (
                 SerializedWorkspaceLocation::Remote(remote_connections[1].clone()),
-                PathList::default()
+                PathList::default(),
+                Some(WindowId::from(5)),
             )
         );
         assert_eq!(
             have[3],
             (
                 SerializedWorkspaceLocation::Remote(remote_connections[0].clone()),
-                PathList::default()
+                PathList::default(),
+                Some(WindowId::from(9)),
             )
         );
     }
@@ -2882,6 +3007,28 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_get_or_create_docker_connection() {
+        let db = WorkspaceDb::open_test_db("test_get_or_create_docker_connection").await;
+
+        let options = RemoteConnectionOptions::Docker(DockerConnectionOptions {
+            container_id: "4f1c2a9b7d3e".into(),
+            name: "my-project".into(),
+            user: Some("vscode".into()),
+        });
+        let connection_id = db
+            .get_or_create_remote_connection(options.clone())
+            .await
+            .unwrap();
+        let same_connection_id = db
+            .get_or_create_remote_connection(options.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(connection_id, same_connection_id);
+        assert_eq!(db.remote_connection(connection_id).unwrap(), options);
+    }
+
     #[gpui::test]
     async fn test_simple_split() {
         zlog::init_test();
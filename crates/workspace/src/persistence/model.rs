@@ -32,6 +32,7 @@ pub(crate) struct RemoteConnectionId(pub u64);
 pub(crate) enum RemoteConnectionKind {
     Ssh,
     Wsl,
+    Docker,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -47,6 +48,15 @@ impl SerializedWorkspaceLocation {
     }
 }
 
+/// User-managed details of a recent workspace, shown in the recent projects picker.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct RecentWorkspaceMetadata {
+    pub pinned: bool,
+    pub display_name: Option<String>,
+    /// Unix timestamp of the last time the workspace was opened.
+    pub last_opened: Option<i64>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct SerializedWorkspace {
     pub(crate) id: WorkspaceId,
@@ -75,6 +85,7 @@ impl RemoteConnectionKind {
         match self {
             RemoteConnectionKind::Ssh => "ssh",
             RemoteConnectionKind::Wsl => "wsl",
+            RemoteConnectionKind::Docker => "docker",
         }
     }
 
@@ -82,6 +93,7 @@ impl RemoteConnectionKind {
         match text {
             "ssh" => Some(Self::Ssh),
             "wsl" => Some(Self::Wsl),
+            "docker" => Some(Self::Docker),
             _ => None,
         }
     }
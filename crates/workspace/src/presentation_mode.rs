@@ -0,0 +1,124 @@
+use settings::Settings as _;
+use ui::prelude::*;
+
+use crate::{DockPosition, TogglePresentationMode, Workspace, WorkspaceSettings};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(Workspace::toggle_presentation_mode);
+    })
+    .detach();
+}
+
+/// What presentation mode changed, so that it can be undone when leaving it.
+pub(crate) struct PresentationMode {
+    font_size_increase: Pixels,
+    closed_docks: Vec<DockPosition>,
+    pub(crate) hide_status_bar: bool,
+    /// Whether the keystroke overlay was shown when entering presentation mode.
+    showed_keystroke_overlay: bool,
+}
+
+impl Workspace {
+    /// Whether the workspace is in presentation mode, which makes it easier to follow when
+    /// sharing the screen or recording.
+    pub fn is_presenting(&self) -> bool {
+        self.presentation_mode.is_some()
+    }
+
+    fn toggle_presentation_mode(
+        &mut self,
+        _: &TogglePresentationMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(presentation_mode) = self.presentation_mode.take() {
+            theme::adjust_zoom(cx, -presentation_mode.font_size_increase);
+            for position in presentation_mode.closed_docks {
+                self.dock_at_position(position)
+                    .update(cx, |dock, cx| dock.set_open(true, window, cx));
+            }
+            if presentation_mode.showed_keystroke_overlay {
+                self.set_keystroke_overlay_visible(false, cx);
+            }
+        } else {
+            let settings = WorkspaceSettings::get_global(cx).presentation_mode;
+            theme::adjust_zoom(cx, settings.font_size_increase);
+
+            let mut closed_docks = Vec::new();
+            if settings.hide_docks {
+                for dock in self.all_docks() {
+                    if dock.read(cx).is_open() {
+                        closed_docks.push(dock.read(cx).position());
+                    }
+                }
+                self.close_all_docks(window, cx);
+            }
+
+            let showed_keystroke_overlay =
+                settings.show_keystrokes && !self.is_keystroke_overlay_visible();
+            if showed_keystroke_overlay {
+                self.set_keystroke_overlay_visible(true, cx);
+            }
+
+            self.presentation_mode = Some(PresentationMode {
+                font_size_increase: settings.font_size_increase,
+                closed_docks,
+                hide_status_bar: settings.hide_status_bar,
+                showed_keystroke_overlay,
+            });
+        }
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dock::test::TestPanel, tests::init_test};
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use project::Project;
+    use theme::ThemeSettings;
+
+    #[gpui::test]
+    async fn test_presentation_mode(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+
+        let ui_font_size = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, cx));
+            workspace.add_panel(panel, window, cx);
+            workspace
+                .right_dock()
+                .update(cx, |dock, cx| dock.set_open(true, window, cx));
+            ThemeSettings::get_global(cx).ui_font_size(cx)
+        });
+
+        // Presenting enlarges fonts, and hides the docks and status bar.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_presentation_mode(&TogglePresentationMode, window, cx);
+            assert!(workspace.is_presenting());
+            assert!(!workspace.right_dock().read(cx).is_open());
+            assert!(!workspace.status_bar_visible(cx));
+            assert!(workspace.is_keystroke_overlay_visible());
+            assert_eq!(
+                ThemeSettings::get_global(cx).ui_font_size(cx),
+                ui_font_size + px(4.)
+            );
+        });
+
+        // Leaving presentation mode restores everything.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_presentation_mode(&TogglePresentationMode, window, cx);
+            assert!(!workspace.is_presenting());
+            assert!(workspace.right_dock().read(cx).is_open());
+            assert!(workspace.status_bar_visible(cx));
+            assert!(!workspace.is_keystroke_overlay_visible());
+            assert_eq!(ThemeSettings::get_global(cx).ui_font_size(cx), ui_font_size);
+        });
+    }
+}
@@ -1,14 +1,30 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, process::ExitStatus, rc::Rc, sync::Arc};
 
-use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{App, AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{Inventory, TaskRunStatus, TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
-use task::{DebugScenario, ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
+use task::{
+    DebugScenario, DependsOrder, ResolvedTask, SpawnInTerminal, TaskContext, TaskId, TaskInput,
+    TaskTemplate,
+};
 use ui::Window;
 
-use crate::{Toast, Workspace, notifications::NotificationId};
+use crate::{TaskInputProvider, Toast, Workspace, notifications::NotificationId};
+
+struct TaskDependenciesToast;
+
+/// The runs of the tasks in one dependency tree, so that a task that several
+/// others depend on is only run once, with all of them waiting for that run.
+type TaskRuns = Rc<
+    RefCell<HashMap<TaskId, Shared<LocalBoxFuture<'static, Result<usize, Arc<anyhow::Error>>>>>>,
+>;
 
 impl Workspace {
     pub fn schedule_task(
@@ -54,7 +70,37 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
+        let inputs = resolved_task
+            .original_task()
+            .unresolved_inputs(resolved_task.context());
+        if !inputs.is_empty()
+            && let Some(task_input_provider) = self.task_input_provider.clone()
+        {
+            let task = self.prompt_for_task_inputs(
+                task_input_provider,
+                task_source_kind,
+                resolved_task,
+                inputs,
+                omit_history,
+                window,
+                cx,
+            );
+            self.scheduled_tasks.push(task);
+            return;
+        }
+
         let spawn_in_terminal = resolved_task.resolved.clone();
+        let task_id = resolved_task.id.clone();
+        let id_base = task_source_kind.to_id_base();
+        let worktree_id = match &task_source_kind {
+            TaskSourceKind::Worktree { id, .. } | TaskSourceKind::Manifest { id, .. } => Some(*id),
+            _ => resolved_task.context().cwd.as_deref().and_then(|cwd| {
+                let (worktree, _) = self.project.read(cx).find_worktree(cwd, cx)?;
+                Some(worktree.read(cx).id())
+            }),
+        };
+        let compound_task =
+            (!resolved_task.original_task().depends_on.is_empty()).then(|| resolved_task.clone());
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
                 debugger_provider.task_scheduled(cx);
@@ -71,17 +117,23 @@ impl Workspace {
             });
         }
 
-        if let Some(terminal_provider) = self.terminal_provider.as_ref() {
+        if let Some(compound_task) = compound_task {
+            let task = self.run_with_dependencies(id_base, worktree_id, compound_task, window, cx);
+            self.scheduled_tasks.push(task);
+        } else if let Some(terminal_provider) = self.terminal_provider.as_ref() {
             let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
+            self.set_task_run_status(task_id.clone(), Some(TaskRunStatus::Running), cx);
 
-            let task = cx.spawn(async |w, cx| {
+            let task = cx.spawn(async move |w, cx| {
                 let res = cx.background_spawn(task_status).await;
-                match res {
+                let run_status = match res {
                     Some(Ok(status)) => {
                         if status.success() {
                             log::debug!("Task spawn succeeded");
+                            Some(TaskRunStatus::Succeeded)
                         } else {
                             log::debug!("Task spawn failed, code: {:?}", status.code());
+                            Some(TaskRunStatus::Failed)
                         }
                     }
                     Some(Err(e)) => {
@@ -89,15 +141,149 @@ impl Workspace {
                         _ = w.update(cx, |w, cx| {
                             let id = NotificationId::unique::<ResolvedTask>();
                             w.show_toast(Toast::new(id, format!("Task spawn failed: {e}")), cx);
-                        })
+                        });
+                        Some(TaskRunStatus::Failed)
+                    }
+                    None => {
+                        log::debug!("Task spawn got cancelled");
+                        None
                     }
-                    None => log::debug!("Task spawn got cancelled"),
                 };
+                _ = w.update(cx, |w, cx| w.set_task_run_status(task_id, run_status, cx));
             });
             self.scheduled_tasks.push(task);
         }
     }
 
+    /// Asks for the values of the task's `inputs`, then schedules the task resolved with them.
+    fn prompt_for_task_inputs(
+        &mut self,
+        task_input_provider: Arc<dyn TaskInputProvider>,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        inputs: Vec<TaskInput>,
+        omit_history: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let values = task_input_provider.prompt(
+            resolved_task.original_task().label.clone(),
+            inputs,
+            self,
+            window,
+            cx,
+        );
+        cx.spawn_in(window, async move |workspace, cx| {
+            let Some(values) = values.await else {
+                log::debug!("Task input prompt got dismissed");
+                return;
+            };
+            let mut context = resolved_task.context().clone();
+            context.task_variables.extend(values);
+            let Some(resolved_task) = resolved_task
+                .original_task()
+                .resolve_task(&task_source_kind.to_id_base(), &context)
+            else {
+                return;
+            };
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.schedule_resolved_task(
+                        task_source_kind,
+                        resolved_task,
+                        omit_history,
+                        window,
+                        cx,
+                    )
+                })
+                .ok();
+        })
+    }
+
+    /// Runs the tasks `resolved_task` depends on, then the task itself, and
+    /// reports how many of them succeeded or which one stopped the run.
+    fn run_with_dependencies(
+        &self,
+        id_base: String,
+        worktree_id: Option<WorktreeId>,
+        resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(inventory) = self.task_inventory(cx) else {
+            return Task::ready(());
+        };
+
+        let label = resolved_task.resolved.label.clone();
+        let task_id = resolved_task.id.clone();
+        inventory.update(cx, |inventory, cx| {
+            inventory.set_task_run_status(task_id.clone(), Some(TaskRunStatus::Running), cx)
+        });
+        cx.spawn_in(window, async move |workspace, cx| {
+            let result = run_task_tree(
+                workspace.clone(),
+                inventory,
+                id_base,
+                worktree_id,
+                resolved_task,
+                Vec::new(),
+                TaskRuns::default(),
+                cx.clone(),
+            )
+            .await;
+            let run_status = match result {
+                Ok(_) => TaskRunStatus::Succeeded,
+                Err(_) => TaskRunStatus::Failed,
+            };
+            inventory
+                .update(cx, |inventory, cx| {
+                    inventory.set_task_run_status(task_id, Some(run_status), cx)
+                })
+                .ok();
+
+            let toast = match result {
+                Ok(succeeded) => Toast::new(
+                    NotificationId::unique::<TaskDependenciesToast>(),
+                    format!("Task `{label}` finished: all {succeeded} tasks succeeded"),
+                )
+                .autohide(),
+                Err(e) => {
+                    log::error!("Task `{label}` stopped: {e:#}");
+                    Toast::new(
+                        NotificationId::unique::<TaskDependenciesToast>(),
+                        format!("Task `{label}` stopped: {e:#}"),
+                    )
+                }
+            };
+            workspace
+                .update(cx, |workspace, cx| workspace.show_toast(toast, cx))
+                .ok();
+        })
+    }
+
+    /// Records the status of a task's run in the project's task inventory.
+    fn set_task_run_status(
+        &self,
+        task_id: TaskId,
+        status: Option<TaskRunStatus>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(inventory) = self.task_inventory(cx) {
+            inventory.update(cx, |inventory, cx| {
+                inventory.set_task_run_status(task_id, status, cx)
+            });
+        }
+    }
+
+    fn task_inventory(&self, cx: &App) -> Option<Entity<Inventory>> {
+        self.project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -132,3 +318,105 @@ impl Workspace {
         }
     }
 }
+
+/// Runs the dependencies of `task` in its `depends_order`, then `task` itself,
+/// returning the number of tasks that succeeded. Stops at the first failure, so
+/// the tasks depending on the failed one are never started.
+///
+/// A dependency shared by several tasks in the tree is run once, and only
+/// counted by the first task that started it.
+fn run_task_tree(
+    workspace: WeakEntity<Workspace>,
+    inventory: Entity<Inventory>,
+    id_base: String,
+    worktree_id: Option<WorktreeId>,
+    task: ResolvedTask,
+    mut ancestors: Vec<String>,
+    task_runs: TaskRuns,
+    mut cx: AsyncWindowContext,
+) -> LocalBoxFuture<'static, Result<usize>> {
+    async move {
+        let template = task.original_task();
+        ancestors.push(template.label.clone());
+
+        let mut dependencies = Vec::with_capacity(template.depends_on.len());
+        for dependency_label in &template.depends_on {
+            if ancestors.contains(dependency_label) {
+                bail!("task `{dependency_label}` depends on itself");
+            }
+            let dependency = inventory
+                .read_with(&cx, |inventory, cx| {
+                    inventory.task_template_by_label(None, worktree_id, dependency_label, cx)
+                })?
+                .await
+                .with_context(|| format!("no task is labeled `{dependency_label}`"))?;
+            let dependency = dependency
+                .resolve_task(&id_base, task.context())
+                .with_context(|| format!("failed to resolve task `{dependency_label}`"))?;
+            dependencies.push(dependency);
+        }
+
+        let runs = dependencies.into_iter().map(|dependency| {
+            let existing_run = task_runs.borrow().get(&dependency.id).cloned();
+            let (run, started_here) = match existing_run {
+                Some(run) => (run, false),
+                None => {
+                    let dependency_id = dependency.id.clone();
+                    let run = run_task_tree(
+                        workspace.clone(),
+                        inventory.clone(),
+                        id_base.clone(),
+                        worktree_id,
+                        dependency,
+                        ancestors.clone(),
+                        task_runs.clone(),
+                        cx.clone(),
+                    )
+                    .map(|result| result.map_err(Arc::new))
+                    .boxed_local()
+                    .shared();
+                    task_runs.borrow_mut().insert(dependency_id, run.clone());
+                    (run, true)
+                }
+            };
+            async move {
+                match run.await {
+                    Ok(succeeded) if started_here => Ok(succeeded),
+                    Ok(_) => Ok(0),
+                    Err(e) => Err(anyhow!("{e:#}")),
+                }
+            }
+        });
+        let mut succeeded = 0;
+        match template.depends_order {
+            DependsOrder::Sequence => {
+                for run in runs {
+                    succeeded += run.await?;
+                }
+            }
+            DependsOrder::Parallel => {
+                succeeded += futures::future::try_join_all(runs)
+                    .await?
+                    .into_iter()
+                    .sum::<usize>();
+            }
+        }
+
+        let label = &task.resolved.label;
+        let status = workspace
+            .update_in(&mut cx, |workspace, window, cx| {
+                workspace.spawn_in_terminal(task.resolved.clone(), window, cx)
+            })?
+            .await;
+        match status {
+            Some(Ok(status)) if status.success() => Ok(succeeded + 1),
+            Some(Ok(status)) => match status.code() {
+                Some(code) => bail!("task `{label}` failed with exit code {code}"),
+                None => bail!("task `{label}` failed"),
+            },
+            Some(Err(e)) => Err(e.context(format!("failed to spawn task `{label}`"))),
+            None => Err(anyhow!("task `{label}` was cancelled")),
+        }
+    }
+    .boxed_local()
+}
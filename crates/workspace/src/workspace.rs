@@ -2,12 +2,15 @@ pub mod dock;
 pub mod history_manager;
 pub mod invalid_buffer_view;
 pub mod item;
+mod keystroke_overlay;
 mod modal_layer;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
 mod path_list;
+mod performance_hud;
 mod persistence;
+mod presentation_mode;
 pub mod searchable;
 pub mod shared_screen;
 mod status_bar;
@@ -16,6 +19,7 @@ mod theme_preview;
 mod toast_layer;
 mod toolbar;
 mod workspace_settings;
+mod workspace_tabs;
 
 pub use crate::notifications::NotificationFrame;
 pub use dock::Panel;
@@ -64,7 +68,7 @@ pub use pane_group::*;
 use persistence::{DB, SerializedWindowBounds, model::SerializedWorkspace};
 pub use persistence::{
     DB as WORKSPACE_DB, WorkspaceDb, delete_unloaded_items,
-    model::{ItemId, SerializedWorkspaceLocation},
+    model::{ItemId, RecentWorkspaceMetadata, SerializedWorkspaceLocation},
 };
 use postage::stream::Stream;
 use project::{
@@ -99,7 +103,7 @@ use std::{
     sync::{Arc, LazyLock, Weak, atomic::AtomicUsize},
     time::Duration,
 };
-use task::{DebugScenario, SpawnInTerminal, TaskContext};
+use task::{DebugScenario, SpawnInTerminal, TaskContext, TaskInput, TaskVariables};
 use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
@@ -115,13 +119,22 @@ pub use workspace_settings::{
     AutosaveSetting, BottomDockLayout, RestoreOnStartupBehavior, StatusBarSettings, TabBarSettings,
     WorkspaceSettings,
 };
+pub use workspace_tabs::{
+    WorkspaceTabTarget, activate_next_workspace_tab, activate_previous_workspace_tab,
+    activate_workspace_tab, add_workspace_tab, group_workspaces_by_window, workspace_tabs,
+    workspace_tabs_enabled,
+};
 use zed_actions::{Spawn, feedback::FileBugReport};
 
+use crate::invalid_buffer_view::InvalidBufferView;
+use crate::keystroke_overlay::KeystrokeOverlay;
 use crate::notifications::NotificationId;
+use crate::performance_hud::PerformanceHud;
 use crate::persistence::{
     SerializedAxis,
     model::{DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup},
 };
+use crate::presentation_mode::PresentationMode;
 
 pub const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(200);
 
@@ -148,6 +161,19 @@ pub trait TerminalProvider {
     ) -> Task<Option<Result<ExitStatus>>>;
 }
 
+pub trait TaskInputProvider {
+    /// Asks for the values of a task's inputs, one after another.
+    /// Resolves to `None` if asking for any of them was dismissed.
+    fn prompt(
+        &self,
+        task_label: String,
+        inputs: Vec<TaskInput>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Option<TaskVariables>>;
+}
+
 pub trait DebuggerProvider {
     // `active_buffer` is used to resolve build task's name against language-specific tasks.
     fn start_session(
@@ -218,6 +244,8 @@ actions!(
         NewWindow,
         /// Opens a file or directory.
         Open,
+        /// Opens the active file as text, even though it was detected as too large or binary.
+        OpenAnyway,
         /// Opens multiple files.
         OpenFiles,
         /// Opens the current location in terminal.
@@ -246,8 +274,13 @@ actions!(
         ToggleCenteredLayout,
         /// Toggles edit prediction feature globally for all files.
         ToggleEditPrediction,
+        /// Toggles an overlay that shows the keys that are pressed and the actions they run.
+        ToggleKeystrokeOverlay,
         /// Toggles the left dock.
         ToggleLeftDock,
+        /// Toggles presentation mode, which enlarges fonts, hides the docks and status bar, and
+        /// shows pressed keys, for screen sharing and recordings.
+        TogglePresentationMode,
         /// Toggles the right dock.
         ToggleRightDock,
         /// Toggles zoom on the active pane.
@@ -547,7 +580,11 @@ pub fn init(app_state: Arc<AppState>, cx: &mut App) {
     component::init();
     theme_preview::init(cx);
     toast_layer::init(cx);
+    performance_hud::init(cx);
+    presentation_mode::init(cx);
+    keystroke_overlay::init(cx);
     history_manager::init(cx);
+    workspace_tabs::init(cx);
 
     cx.on_action(|_: &CloseWindow, cx| Workspace::close_global(cx));
     cx.on_action(|_: &Reload, cx| reload(cx));
@@ -1122,12 +1159,17 @@ pub struct Workspace {
     status_bar: Entity<StatusBar>,
     modal_layer: Entity<ModalLayer>,
     toast_layer: Entity<ToastLayer>,
+    performance_hud: Option<Entity<PerformanceHud>>,
+    presentation_mode: Option<PresentationMode>,
+    keystroke_overlay: Option<Entity<KeystrokeOverlay>>,
     titlebar_item: Option<AnyView>,
     notifications: Notifications,
     suppressed_notifications: HashSet<NotificationId>,
     project: Entity<Project>,
     follower_states: HashMap<CollaboratorId, FollowerState>,
     last_leaders_by_pane: HashMap<WeakEntity<Pane>, CollaboratorId>,
+    presenting: bool,
+    presenter_id: Option<PeerId>,
     window_edited: bool,
     last_window_title: Option<String>,
     dirty_items: HashMap<EntityId, Subscription>,
@@ -1149,6 +1191,7 @@ pub struct Workspace {
     on_prompt_for_open_path: Option<PromptForOpenPath>,
     terminal_provider: Option<Box<dyn TerminalProvider>>,
     debugger_provider: Option<Arc<dyn DebuggerProvider>>,
+    task_input_provider: Option<Arc<dyn TaskInputProvider>>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
     _items_serializer: Task<Result<()>>,
     session_id: Option<String>,
@@ -1192,6 +1235,12 @@ impl Workspace {
                     this.update_window_title(window, cx);
                 }
 
+                project::Event::CollaboratorJoined(_) => {
+                    if this.presenting {
+                        this.send_presenting_update(cx);
+                    }
+                }
+
                 project::Event::CollaboratorLeft(peer_id) => {
                     this.collaborator_left(*peer_id, window, cx);
                 }
@@ -1434,6 +1483,8 @@ impl Workspace {
 
                 ThemeSettings::reload_current_theme(cx);
                 ThemeSettings::reload_current_icon_theme(cx);
+                // The operating system's high contrast preference changes the window's appearance.
+                theme::AccessibilitySettings::reload_system_preferences(cx);
             }),
             cx.on_release(move |this, cx| {
                 this.app_state.workspace_store.update(cx, move |store, _| {
@@ -1460,6 +1511,9 @@ impl Workspace {
             status_bar,
             modal_layer,
             toast_layer,
+            performance_hud: None,
+            presentation_mode: None,
+            keystroke_overlay: None,
             titlebar_item: None,
             notifications: Notifications::default(),
             suppressed_notifications: HashSet::default(),
@@ -1469,6 +1523,8 @@ impl Workspace {
             project: project.clone(),
             follower_states: Default::default(),
             last_leaders_by_pane: Default::default(),
+            presenting: false,
+            presenter_id: None,
             dispatching_keystrokes: Default::default(),
             window_edited: false,
             last_window_title: None,
@@ -1492,6 +1548,7 @@ impl Workspace {
             on_prompt_for_open_path: None,
             terminal_provider: None,
             debugger_provider: None,
+            task_input_provider: None,
             serializable_items_tx,
             _items_serializer,
             session_id: Some(session_id),
@@ -1511,6 +1568,31 @@ impl Workspace {
             WindowHandle<Workspace>,
             Vec<Option<anyhow::Result<Box<dyn ItemHandle>>>>,
         )>,
+    > {
+        Self::new_local_in(
+            abs_paths,
+            app_state,
+            requesting_window,
+            WorkspaceTabTarget::default(),
+            env,
+            cx,
+        )
+    }
+
+    /// Like [`Self::new_local`], opening the workspace as a tab of `tab_target` when it doesn't
+    /// replace `requesting_window` and workspace tabs are enabled.
+    fn new_local_in(
+        abs_paths: Vec<PathBuf>,
+        app_state: Arc<AppState>,
+        requesting_window: Option<WindowHandle<Workspace>>,
+        tab_target: WorkspaceTabTarget,
+        env: Option<HashMap<String, String>>,
+        cx: &mut App,
+    ) -> Task<
+        anyhow::Result<(
+            WindowHandle<Workspace>,
+            Vec<Option<anyhow::Result<Box<dyn ItemHandle>>>>,
+        )>,
     > {
         let project_handle = Project::local(
             app_state.client.clone(),
@@ -1594,6 +1676,12 @@ impl Workspace {
                 })?;
             }
 
+            let tab_window = if requesting_window.is_none() {
+                cx.update(|cx| workspace_tabs::window_for_new_workspace_tab(tab_target, cx))?
+            } else {
+                None
+            };
+
             let window = if let Some(window) = requesting_window {
                 let centered_layout = serialized_workspace
                     .as_ref()
@@ -1615,6 +1703,27 @@ impl Workspace {
                     });
                 })?;
                 window
+            } else if let Some(window) = tab_window {
+                let centered_layout = serialized_workspace
+                    .as_ref()
+                    .map(|w| w.centered_layout)
+                    .unwrap_or(false);
+
+                cx.update_window(window.into(), |_, window, cx| {
+                    let workspace = cx.new(|cx| {
+                        let mut workspace = Workspace::new(
+                            Some(workspace_id),
+                            project_handle.clone(),
+                            app_state.clone(),
+                            window,
+                            cx,
+                        );
+                        workspace.centered_layout = centered_layout;
+                        workspace
+                    });
+                    workspace_tabs::add_workspace_tab(workspace, window, cx);
+                })?;
+                window
             } else {
                 let window_bounds_override = window_bounds_env_override();
 
@@ -1751,6 +1860,10 @@ impl Workspace {
 
     pub fn status_bar_visible(&self, cx: &App) -> bool {
         StatusBarSettings::get_global(cx).show
+            && !self
+                .presentation_mode
+                .as_ref()
+                .is_some_and(|presentation_mode| presentation_mode.hide_status_bar)
     }
 
     pub fn app_state(&self) -> &Arc<AppState> {
@@ -2033,19 +2146,64 @@ impl Workspace {
         self.navigate_history(pane, NavigationMode::GoingForward, window, cx)
     }
 
+    /// Reopens the item that was closed most recently in any of the workspace's panes.
     pub fn reopen_closed_item(
         &mut self,
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) -> Task<Result<()>> {
+        let pane = self
+            .panes
+            .iter()
+            .filter_map(|pane| Some((pane.read(cx).nav_history().latest_closed_timestamp()?, pane)))
+            .max_by_key(|(timestamp, _)| *timestamp)
+            .map_or_else(|| self.active_pane(), |(_, pane)| pane)
+            .downgrade();
+        self.navigate_history(pane, NavigationMode::ReopeningClosedItem, window, cx)
+    }
+
+    /// Reopens a specific closed item in the pane it was closed in.
+    pub fn reopen_closed_item_by_id(
+        &mut self,
+        pane: Entity<Pane>,
+        item_id: EntityId,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        let moved = pane.update(cx, |pane, _| {
+            pane.nav_history_mut().move_closed_item_to_top(item_id)
+        });
+        if !moved {
+            return Task::ready(Ok(()));
+        }
         self.navigate_history(
-            self.active_pane().downgrade(),
+            pane.downgrade(),
             NavigationMode::ReopeningClosedItem,
             window,
             cx,
         )
     }
 
+    /// The items closed in the workspace's panes that can be reopened, most recently
+    /// closed first. Only the latest closing of each path is listed.
+    pub fn recently_closed_items(&self, cx: &App) -> Vec<(Entity<Pane>, ClosedItem)> {
+        let mut items = self
+            .panes
+            .iter()
+            .flat_map(|pane| {
+                pane.read(cx)
+                    .nav_history()
+                    .closed_items()
+                    .into_iter()
+                    .map(|item| (pane.clone(), item))
+            })
+            .collect::<Vec<_>>();
+        items.sort_by_key(|(_, item)| cmp::Reverse(item.timestamp));
+        let mut seen_paths = HashSet::default();
+        items.retain(|(_, item)| seen_paths.insert(item.project_path.clone()));
+        items
+    }
+
     pub fn client(&self) -> &Arc<Client> {
         &self.app_state.client
     }
@@ -2075,6 +2233,10 @@ impl Workspace {
         self.debugger_provider.clone()
     }
 
+    pub fn set_task_input_provider(&mut self, provider: impl TaskInputProvider + 'static) {
+        self.task_input_provider = Some(Arc::new(provider));
+    }
+
     pub fn prompt_for_open_path(
         &mut self,
         path_prompt_options: PathPromptOptions,
@@ -2255,10 +2417,31 @@ impl Workspace {
     }
 
     pub fn close_window(&mut self, _: &CloseWindow, window: &mut Window, cx: &mut Context<Self>) {
-        let prepare = self.prepare_to_close(CloseIntent::CloseWindow, window, cx);
+        // When the window has several workspace tabs, only the one being shown is closed.
+        if let Some(root) = window.root::<Workspace>().flatten()
+            && root.entity_id() != cx.entity_id()
+        {
+            window.defer(cx, move |window, cx| {
+                root.update(cx, |workspace, cx| {
+                    workspace.close_window(&CloseWindow, window, cx)
+                });
+            });
+            return;
+        }
+        let close_intent = if workspace_tabs(window, cx).0.len() > 1 {
+            CloseIntent::ReplaceWindow
+        } else {
+            CloseIntent::CloseWindow
+        };
+
+        let prepare = self.prepare_to_close(close_intent, window, cx);
         cx.spawn_in(window, async move |_, cx| {
             if prepare.await? {
-                cx.update(|window, _cx| window.remove_window())?;
+                cx.update(|window, cx| {
+                    if !workspace_tabs::remove_active_workspace_tab(window, cx) {
+                        window.remove_window();
+                    }
+                })?;
             }
             anyhow::Ok(())
         })
@@ -2371,6 +2554,35 @@ impl Workspace {
         })
     }
 
+    fn open_anyway(&mut self, _: &OpenAnyway, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(invalid_buffer_view) = self
+            .active_item(cx)
+            .and_then(|item| item.downcast::<InvalidBufferView>())
+        else {
+            return;
+        };
+        let Some(project_path) = self
+            .project
+            .read(cx)
+            .find_project_path(&invalid_buffer_view.read(cx).abs_path, cx)
+        else {
+            return;
+        };
+
+        self.project.update(cx, |project, cx| {
+            project.skip_file_heuristics(project_path.clone(), cx)
+        });
+        let pane = self.active_pane.clone();
+        let open = self.open_path(project_path, Some(pane.downgrade()), true, window, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            open.await?;
+            pane.update_in(cx, |pane, window, cx| {
+                pane.remove_item(invalid_buffer_view.item_id(), false, false, window, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn save_all(&mut self, action: &SaveAll, window: &mut Window, cx: &mut Context<Self>) {
         self.save_all_internal(
             action.save_intent.unwrap_or(SaveIntent::SaveAll),
@@ -4193,6 +4405,9 @@ impl Workspace {
     }
 
     fn collaborator_left(&mut self, peer_id: PeerId, window: &mut Window, cx: &mut Context<Self>) {
+        if self.presenter_id == Some(peer_id) {
+            self.presenter_id = None;
+        }
         self.follower_states.retain(|leader_id, state| {
             if *leader_id == CollaboratorId::PeerId(peer_id) {
                 for item in state.items_by_leader_view_id.values() {
@@ -4403,6 +4618,100 @@ impl Workspace {
         self.follower_states.contains_key(&id.into())
     }
 
+    /// Whether the local user is presenting, i.e. everyone in the project follows them.
+    pub fn is_presenting(&self) -> bool {
+        self.presenting
+    }
+
+    /// The collaborator currently presenting to this workspace, if any.
+    pub fn presenter(&self) -> Option<PeerId> {
+        self.presenter_id
+    }
+
+    /// Whether the local user currently follows the presenter. When `false` while
+    /// [`Self::presenter`] is set, the user has temporarily stepped out of the presentation.
+    pub fn is_following_presenter(&self) -> bool {
+        self.presenter_id
+            .is_some_and(|presenter_id| self.is_being_followed(presenter_id))
+    }
+
+    pub fn start_presenting(
+        &mut self,
+        _: &StartPresenting,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.presenting {
+            return;
+        }
+        self.presenting = true;
+        self.send_presenting_update(cx);
+        cx.notify();
+    }
+
+    pub fn stop_presenting(
+        &mut self,
+        _: &StopPresenting,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.presenting {
+            return;
+        }
+        self.presenting = false;
+        self.send_presenting_update(cx);
+        cx.notify();
+    }
+
+    /// Resumes following the presenter after having temporarily unfollowed them.
+    pub fn follow_presenter(
+        &mut self,
+        _: &FollowPresenter,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(presenter_id) = self.presenter_id {
+            self.follow(presenter_id, window, cx);
+        }
+    }
+
+    fn send_presenting_update(&self, cx: &App) -> Option<()> {
+        let room_id = self.active_call()?.read(cx).room()?.read(cx).id();
+        let project_id = self.project.read(cx).remote_id();
+        self.app_state
+            .client
+            .send(proto::UpdatePresenting {
+                room_id,
+                project_id,
+                presenting: self.presenting,
+            })
+            .log_err()
+    }
+
+    fn handle_update_presenting(
+        &mut self,
+        presenter_id: PeerId,
+        presenting: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if presenting {
+            if self.presenter_id == Some(presenter_id) {
+                return;
+            }
+            // Only one participant presents at a time, the latest one wins.
+            self.presenting = false;
+            self.presenter_id = Some(presenter_id);
+            if !self.is_being_followed(presenter_id) {
+                self.follow(presenter_id, window, cx);
+            }
+        } else if self.presenter_id == Some(presenter_id) {
+            self.presenter_id = None;
+            self.unfollow(presenter_id, window, cx);
+        }
+        cx.notify();
+    }
+
     fn active_item_path_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         cx.emit(Event::ActiveItemChanged);
         let active_entry = self.active_project_path(cx);
@@ -4420,12 +4729,13 @@ impl Workspace {
         self.update_window_title(window, cx);
     }
 
-    fn update_window_title(&mut self, window: &mut Window, cx: &mut App) {
+    /// The names of the project's visible worktrees, as shown in the window title.
+    pub fn project_name(&self, cx: &App) -> String {
         let project = self.project().read(cx);
-        let mut title = String::new();
+        let mut name = String::new();
 
         for (i, worktree) in project.visible_worktrees(cx).enumerate() {
-            let name = {
+            let worktree_name = {
                 let settings_location = SettingsLocation {
                     worktree_id: worktree.read(cx).id(),
                     path: RelPath::empty(),
@@ -4438,14 +4748,31 @@ impl Workspace {
                 }
             };
             if i > 0 {
-                title.push_str(", ");
+                name.push_str(", ");
             }
-            title.push_str(name);
+            name.push_str(worktree_name);
+        }
+
+        if name.is_empty() {
+            name = "empty project".to_string();
         }
+        name
+    }
+
+    /// Whether this workspace is the one shown in its window, rather than a background workspace tab.
+    fn is_window_root(&self, window: &Window) -> bool {
+        window
+            .root::<Workspace>()
+            .flatten()
+            .is_none_or(|root| root.entity_id() == self.weak_self.entity_id())
+    }
 
-        if title.is_empty() {
-            title = "empty project".to_string();
+    fn update_window_title(&mut self, window: &mut Window, cx: &mut App) {
+        if !self.is_window_root(window) {
+            return;
         }
+        let project = self.project().read(cx);
+        let mut title = self.project_name(cx);
 
         if let Some(path) = self.active_item(cx).and_then(|item| item.project_path(cx)) {
             let filename = path.path.file_name().or_else(|| {
@@ -4487,7 +4814,9 @@ impl Workspace {
         let is_edited = !self.project.read(cx).is_disconnected(cx) && !self.dirty_items.is_empty();
         if is_edited != self.window_edited {
             self.window_edited = is_edited;
-            window.set_window_edited(self.window_edited)
+            if self.is_window_root(window) {
+                window.set_window_edited(self.window_edited)
+            }
         }
     }
 
@@ -5577,9 +5906,13 @@ impl Workspace {
             .on_action(cx.listener(Self::close_inactive_items_and_panes))
             .on_action(cx.listener(Self::close_all_items_and_panes))
             .on_action(cx.listener(Self::save_all))
+            .on_action(cx.listener(Self::open_anyway))
             .on_action(cx.listener(Self::send_keystrokes))
             .on_action(cx.listener(Self::add_folder_to_project))
             .on_action(cx.listener(Self::follow_next_collaborator))
+            .on_action(cx.listener(Self::start_presenting))
+            .on_action(cx.listener(Self::stop_presenting))
+            .on_action(cx.listener(Self::follow_presenter))
             .on_action(cx.listener(Self::close_window))
             .on_action(cx.listener(Self::activate_pane_at_index))
             .on_action(cx.listener(Self::move_item_to_pane_at_index))
@@ -6733,7 +7066,9 @@ impl Render for Workspace {
                                         }
                                     })
                                 }))
-                                .children(self.render_notifications(window, cx)),
+                                .children(self.render_notifications(window, cx))
+                                .children(self.performance_hud.clone())
+                                .children(self.keystroke_overlay.clone()),
                         )
                         .when(self.status_bar_visible(cx), |parent| {
                             parent.child(self.status_bar.clone())
@@ -6754,6 +7089,7 @@ impl WorkspaceStore {
             _subscriptions: vec![
                 client.add_request_handler(cx.weak_entity(), Self::handle_follow),
                 client.add_message_handler(cx.weak_entity(), Self::handle_update_followers),
+                client.add_message_handler(cx.weak_entity(), Self::handle_update_presenting),
             ],
             client,
         }
@@ -6830,6 +7166,35 @@ impl WorkspaceStore {
         })?
     }
 
+    async fn handle_update_presenting(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdatePresenting>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let presenter_id = envelope.original_sender_id()?;
+        let update = envelope.payload;
+
+        this.update(&mut cx, |this, cx| {
+            this.workspaces.retain(|workspace| {
+                workspace
+                    .update(cx, |workspace, window, cx| {
+                        let project_id = workspace.project.read(cx).remote_id();
+                        if update.project_id != project_id && update.project_id.is_some() {
+                            return;
+                        }
+                        workspace.handle_update_presenting(
+                            presenter_id,
+                            update.presenting,
+                            window,
+                            cx,
+                        );
+                    })
+                    .is_ok()
+            });
+            Ok(())
+        })?
+    }
+
     pub fn workspaces(&self) -> &HashSet<WindowHandle<Workspace>> {
         &self.workspaces
     }
@@ -6890,7 +7255,7 @@ pub async fn last_opened_workspace_location() -> Option<(SerializedWorkspaceLoca
 pub fn last_session_workspace_locations(
     last_session_id: &str,
     last_session_window_stack: Option<Vec<WindowId>>,
-) -> Option<Vec<(SerializedWorkspaceLocation, PathList)>> {
+) -> Option<Vec<(SerializedWorkspaceLocation, PathList, Option<WindowId>)>> {
     DB.last_session_workspace_locations(last_session_id, last_session_window_stack)
         .log_err()
 }
@@ -6913,7 +7278,13 @@ actions!(
         /// Shares the current project with collaborators.
         ShareProject,
         /// Shares your screen with collaborators.
-        ScreenShare
+        ScreenShare,
+        /// Starts presenting: everyone in the shared project follows you.
+        StartPresenting,
+        /// Stops presenting to the other collaborators.
+        StopPresenting,
+        /// Resumes following the collaborator who is currently presenting.
+        FollowPresenter
     ]
 );
 actions!(
@@ -7206,6 +7577,8 @@ pub struct OpenOptions {
     pub focus: Option<bool>,
     pub open_new_workspace: Option<bool>,
     pub replace_window: Option<WindowHandle<Workspace>>,
+    /// Where a new workspace is opened when workspace tabs are enabled.
+    pub workspace_tab: WorkspaceTabTarget,
     pub env: Option<HashMap<String, String>>,
 }
 
@@ -7223,6 +7596,7 @@ pub fn open_paths(
 > {
     let abs_paths = abs_paths.to_vec();
     let mut existing = None;
+    let mut existing_tab = None;
     let mut best_match = None;
     let mut open_visible = OpenVisible::All;
 
@@ -7236,22 +7610,20 @@ pub fn open_paths(
                 .collect::<Vec<_>>();
 
             cx.update(|cx| {
-                for window in local_workspace_windows(cx) {
-                    if let Ok(workspace) = window.read(cx) {
-                        let m = workspace.project.read(cx).visibility_for_paths(
-                            &abs_paths,
-                            &all_metadatas,
-                            open_options.open_new_workspace == None,
-                            cx,
-                        );
-                        if m > best_match {
-                            existing = Some(window);
-                            best_match = m;
-                        } else if best_match.is_none()
-                            && open_options.open_new_workspace == Some(false)
-                        {
-                            existing = Some(window)
-                        }
+                for (window, workspace) in workspace_tabs::local_workspaces(cx) {
+                    let m = workspace.read(cx).project.read(cx).visibility_for_paths(
+                        &abs_paths,
+                        &all_metadatas,
+                        open_options.open_new_workspace == None,
+                        cx,
+                    );
+                    if m > best_match {
+                        existing_tab = Some(workspace);
+                        existing = Some(window);
+                        best_match = m;
+                    } else if best_match.is_none() && open_options.open_new_workspace == Some(false)
+                    {
+                        existing = Some(window)
                     }
                 }
             })?;
@@ -7289,6 +7661,14 @@ pub fn open_paths(
         }
 
         if let Some(existing) = existing {
+            if let Some(existing_tab) = existing_tab {
+                cx.update_window(existing.into(), |_, window, cx| {
+                    let (workspaces, _) = workspace_tabs(window, cx);
+                    if let Some(ix) = workspaces.iter().position(|tab| *tab == existing_tab) {
+                        activate_workspace_tab(ix, window, cx);
+                    }
+                })?;
+            }
             let open_task = existing
                 .update(cx, |workspace, window, cx| {
                     window.activate_window();
@@ -7316,10 +7696,11 @@ pub fn open_paths(
             Ok((existing, open_task))
         } else {
             cx.update(move |cx| {
-                Workspace::new_local(
+                Workspace::new_local_in(
                     abs_paths,
                     app_state.clone(),
                     open_options.replace_window,
+                    open_options.workspace_tab,
                     open_options.env,
                     cx,
                 )
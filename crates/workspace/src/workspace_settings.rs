@@ -2,7 +2,7 @@ use std::num::NonZeroUsize;
 
 use crate::DockPosition;
 use collections::HashMap;
-use gpui::App;
+use gpui::{App, Pixels, px};
 use serde::Deserialize;
 pub use settings::AutosaveSetting;
 use settings::Settings;
@@ -32,7 +32,17 @@ pub struct WorkspaceSettings {
     pub resize_all_panels_in_dock: Vec<DockPosition>,
     pub close_on_file_delete: bool,
     pub use_system_window_tabs: bool,
+    pub workspace_tabs: bool,
     pub zoomed_padding: bool,
+    pub presentation_mode: PresentationModeSettings,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PresentationModeSettings {
+    pub font_size_increase: Pixels,
+    pub hide_docks: bool,
+    pub hide_status_bar: bool,
+    pub show_keystrokes: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, Debug, Default)]
@@ -106,7 +116,17 @@ impl Settings for WorkspaceSettings {
                 .collect(),
             close_on_file_delete: workspace.close_on_file_delete.unwrap(),
             use_system_window_tabs: workspace.use_system_window_tabs.unwrap(),
+            workspace_tabs: workspace.workspace_tabs.unwrap(),
             zoomed_padding: workspace.zoomed_padding.unwrap(),
+            presentation_mode: {
+                let presentation_mode = workspace.presentation_mode.unwrap();
+                PresentationModeSettings {
+                    font_size_increase: px(presentation_mode.font_size_increase.unwrap()),
+                    hide_docks: presentation_mode.hide_docks.unwrap(),
+                    hide_status_bar: presentation_mode.hide_status_bar.unwrap(),
+                    show_keystrokes: presentation_mode.show_keystrokes.unwrap(),
+                }
+            },
         }
     }
 
@@ -0,0 +1,275 @@
+use collections::HashMap;
+use gpui::{App, AppContext as _, Entity, Focusable as _, Global, Window, WindowHandle, WindowId};
+use settings::Settings as _;
+
+use crate::{
+    PathList, SerializedWorkspaceLocation, Workspace, WorkspaceSettings, local_workspace_windows,
+};
+
+pub fn init(cx: &mut App) {
+    cx.on_window_closed(|cx| {
+        let open_windows = cx
+            .windows()
+            .into_iter()
+            .map(|window| window.window_id())
+            .collect::<Vec<_>>();
+        if let Some(tabs) = cx.try_global::<WorkspaceTabs>()
+            && tabs
+                .windows
+                .keys()
+                .any(|window_id| !open_windows.contains(window_id))
+        {
+            cx.global_mut::<WorkspaceTabs>()
+                .windows
+                .retain(|window_id, _| open_windows.contains(window_id));
+        }
+    })
+    .detach();
+}
+
+/// The workspaces sharing each window, on platforms without native window tabs.
+///
+/// The active workspace of a window is its root view; the others are kept alive here until
+/// they're activated or closed.
+#[derive(Default)]
+struct WorkspaceTabs {
+    windows: HashMap<WindowId, WindowWorkspaces>,
+}
+
+impl Global for WorkspaceTabs {}
+
+struct WindowWorkspaces {
+    workspaces: Vec<Entity<Workspace>>,
+    active_ix: usize,
+}
+
+/// Where a new local workspace is opened when workspace tabs are enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WorkspaceTabTarget {
+    /// A new tab of the active local window, or a new window if there's none.
+    #[default]
+    ActiveWindow,
+    /// A new tab of the given window.
+    Window(WindowHandle<Workspace>),
+    /// A new window.
+    NewWindow,
+}
+
+/// Whether new projects open as workspace tabs in the active window instead of in new windows.
+pub fn workspace_tabs_enabled(cx: &App) -> bool {
+    cfg!(not(target_os = "macos")) && WorkspaceSettings::get_global(cx).workspace_tabs
+}
+
+/// The workspaces shown as tabs in `window`, and the index of the active one.
+pub fn workspace_tabs(window: &Window, cx: &App) -> (Vec<Entity<Workspace>>, usize) {
+    let Some(root) = window.root::<Workspace>().flatten() else {
+        return (Vec::new(), 0);
+    };
+    let Some(tabs) = cx
+        .try_global::<WorkspaceTabs>()
+        .and_then(|tabs| tabs.windows.get(&window.window_handle().window_id()))
+        .filter(|tabs| !tabs.workspaces.is_empty())
+    else {
+        return (vec![root], 0);
+    };
+
+    let mut workspaces = tabs.workspaces.clone();
+    let active_ix = match workspaces.iter().position(|workspace| *workspace == root) {
+        Some(ix) => ix,
+        None => {
+            // The root was replaced in place, e.g. when opening a project in the current window.
+            let ix = tabs.active_ix.min(workspaces.len() - 1);
+            workspaces[ix] = root;
+            ix
+        }
+    };
+    (workspaces, active_ix)
+}
+
+/// Adds `workspace` as a new tab of `window` and activates it.
+pub fn add_workspace_tab(workspace: Entity<Workspace>, window: &mut Window, cx: &mut App) {
+    let (mut workspaces, _) = workspace_tabs(window, cx);
+    workspaces.push(workspace);
+    let active_ix = workspaces.len() - 1;
+    set_workspace_tabs(workspaces, active_ix, window, cx);
+}
+
+/// Shows the workspace tab at `ix` in `window`.
+pub fn activate_workspace_tab(ix: usize, window: &mut Window, cx: &mut App) {
+    let (workspaces, active_ix) = workspace_tabs(window, cx);
+    if ix != active_ix && ix < workspaces.len() {
+        set_workspace_tabs(workspaces, ix, window, cx);
+    }
+}
+
+pub fn activate_next_workspace_tab(window: &mut Window, cx: &mut App) {
+    let (workspaces, active_ix) = workspace_tabs(window, cx);
+    if workspaces.len() > 1 {
+        activate_workspace_tab((active_ix + 1) % workspaces.len(), window, cx);
+    }
+}
+
+pub fn activate_previous_workspace_tab(window: &mut Window, cx: &mut App) {
+    let (workspaces, active_ix) = workspace_tabs(window, cx);
+    if workspaces.len() > 1 {
+        let ix = active_ix.checked_sub(1).unwrap_or(workspaces.len() - 1);
+        activate_workspace_tab(ix, window, cx);
+    }
+}
+
+/// Removes the active workspace tab of `window`, showing its neighbor instead.
+///
+/// Returns false if it's the window's only workspace, in which case the window should be closed.
+pub(crate) fn remove_active_workspace_tab(window: &mut Window, cx: &mut App) -> bool {
+    let (mut workspaces, active_ix) = workspace_tabs(window, cx);
+    if workspaces.len() <= 1 {
+        return false;
+    }
+    workspaces.remove(active_ix);
+    let active_ix = active_ix.min(workspaces.len() - 1);
+    set_workspace_tabs(workspaces, active_ix, window, cx);
+    true
+}
+
+/// The window a new project should be added to as a tab, if workspace tabs are enabled.
+pub(crate) fn window_for_new_workspace_tab(
+    target: WorkspaceTabTarget,
+    cx: &App,
+) -> Option<WindowHandle<Workspace>> {
+    if !workspace_tabs_enabled(cx) {
+        return None;
+    }
+    match target {
+        WorkspaceTabTarget::ActiveWindow => {}
+        WorkspaceTabTarget::Window(window) => return window.read(cx).is_ok().then_some(window),
+        WorkspaceTabTarget::NewWindow => return None,
+    }
+    let is_local = |window: &WindowHandle<Workspace>| {
+        window.read(cx).is_ok_and(|workspace| {
+            let project = workspace.project().read(cx);
+            project.is_local() && !project.is_via_collab()
+        })
+    };
+    cx.active_window()
+        .and_then(|window| window.downcast::<Workspace>())
+        .filter(is_local)
+        .or_else(|| local_workspace_windows(cx).into_iter().find(is_local))
+}
+
+/// The local workspaces in every window, including the ones in background tabs.
+pub(crate) fn local_workspaces(cx: &App) -> Vec<(WindowHandle<Workspace>, Entity<Workspace>)> {
+    let tabs = cx.try_global::<WorkspaceTabs>();
+    local_workspace_windows(cx)
+        .into_iter()
+        .flat_map(|window| {
+            let mut workspaces = window
+                .read(cx)
+                .ok()
+                .and_then(|workspace| workspace.weak_handle().upgrade())
+                .into_iter()
+                .collect::<Vec<_>>();
+            if let Some(tabs) = tabs.and_then(|tabs| tabs.windows.get(&window.window_id())) {
+                for workspace in &tabs.workspaces {
+                    if !workspaces.contains(workspace) {
+                        workspaces.push(workspace.clone());
+                    }
+                }
+            }
+            workspaces
+                .into_iter()
+                .map(move |workspace| (window, workspace))
+        })
+        .collect()
+}
+
+/// Groups the workspaces of the last session by the window they were shown in, so that the local
+/// workspaces that were tabs of one window are restored as tabs of one window again.
+///
+/// Each group keeps the position of its first workspace.
+pub fn group_workspaces_by_window(
+    locations: Vec<(SerializedWorkspaceLocation, PathList, Option<WindowId>)>,
+) -> Vec<(SerializedWorkspaceLocation, Vec<PathList>)> {
+    let mut groups: Vec<(SerializedWorkspaceLocation, Vec<PathList>)> = Vec::new();
+    let mut group_ixs_by_window = HashMap::default();
+    for (location, paths, window_id) in locations {
+        if location == SerializedWorkspaceLocation::Local
+            && let Some(window_id) = window_id
+        {
+            if let Some(&ix) = group_ixs_by_window.get(&window_id) {
+                groups[ix].1.push(paths);
+                continue;
+            }
+            group_ixs_by_window.insert(window_id, groups.len());
+        }
+        groups.push((location, vec![paths]));
+    }
+    groups
+}
+
+fn set_workspace_tabs(
+    workspaces: Vec<Entity<Workspace>>,
+    active_ix: usize,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let active_workspace = workspaces[active_ix].clone();
+    cx.default_global::<WorkspaceTabs>().windows.insert(
+        window.window_handle().window_id(),
+        WindowWorkspaces {
+            workspaces,
+            active_ix,
+        },
+    );
+
+    if window.root::<Workspace>().flatten().as_ref() != Some(&active_workspace) {
+        window.set_root(active_workspace.clone());
+    }
+    active_workspace.update(cx, |workspace, cx| {
+        workspace.last_window_title = None;
+        workspace.update_window_title(window, cx);
+        window.set_window_edited(workspace.window_edited);
+        let focus_handle = workspace.active_pane().focus_handle(cx);
+        window.focus(&focus_handle);
+        cx.notify();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use remote::{RemoteConnectionOptions, SshConnectionOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_group_workspaces_by_window() {
+        let paths = |path: &str| PathList::new(&[Path::new(path)]);
+        let remote = SerializedWorkspaceLocation::Remote(RemoteConnectionOptions::Ssh(
+            SshConnectionOptions {
+                host: "host".to_string(),
+                ..Default::default()
+            },
+        ));
+        let local = SerializedWorkspaceLocation::Local;
+        let window_1 = Some(WindowId::from(1));
+        let window_2 = Some(WindowId::from(2));
+
+        let groups = group_workspaces_by_window(vec![
+            (local.clone(), paths("/a"), window_1),
+            (local.clone(), paths("/b"), window_2),
+            (remote.clone(), paths("/c"), window_1),
+            (local.clone(), paths("/d"), window_1),
+            (local.clone(), paths("/e"), None),
+            (local.clone(), paths("/f"), None),
+        ]);
+        assert_eq!(
+            groups,
+            [
+                (local.clone(), vec![paths("/a"), paths("/d")]),
+                (local.clone(), vec![paths("/b")]),
+                (remote, vec![paths("/c")]),
+                (local.clone(), vec![paths("/e")]),
+                (local, vec![paths("/f")]),
+            ]
+        );
+    }
+}
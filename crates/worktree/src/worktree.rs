@@ -68,7 +68,7 @@ use util::{
     paths::{PathMatcher, PathStyle, SanitizedPath, home_dir},
     rel_path::RelPath,
 };
-pub use worktree_settings::WorktreeSettings;
+pub use worktree_settings::{FileHeuristics, WorktreeSettings};
 
 pub const FS_WATCH_LATENCY: Duration = Duration::from_millis(100);
 
@@ -735,6 +735,20 @@ impl Worktree {
         }
     }
 
+    pub fn write_binary_file(
+        &self,
+        path: Arc<RelPath>,
+        content: Vec<u8>,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<Arc<File>>> {
+        match self {
+            Worktree::Local(this) => this.write_binary_file(path, content, cx),
+            Worktree::Remote(_) => Task::ready(Err(anyhow!(
+                "remote worktrees can't yet write binary files"
+            ))),
+        }
+    }
+
     pub fn create_entry(
         &mut self,
         path: Arc<RelPath>,
@@ -1017,6 +1031,11 @@ impl LocalWorktree {
         self.fs_case_sensitive
     }
 
+    /// Whether paths that only differ in casing refer to the same file in this worktree.
+    pub fn paths_are_case_insensitive(&self) -> bool {
+        !self.fs_case_sensitive || self.settings.case_insensitive_paths
+    }
+
     fn restart_background_scanners(&mut self, cx: &Context<Worktree>) {
         let (scan_requests_tx, scan_requests_rx) = channel::unbounded();
         let (path_prefixes_to_scan_tx, path_prefixes_to_scan_rx) = channel::unbounded();
@@ -1442,14 +1461,34 @@ impl LocalWorktree {
         cx: &Context<Worktree>,
     ) -> Task<Result<Arc<File>>> {
         let fs = self.fs.clone();
-        let is_private = self.is_path_private(&path);
         let abs_path = self.absolutize(&path);
+        let write =
+            cx.background_spawn(async move { fs.save(&abs_path, &text, line_ending).await });
+        self.refresh_written_file(path, write, cx)
+    }
 
-        let write = cx.background_spawn({
-            let fs = fs.clone();
-            let abs_path = abs_path.clone();
-            async move { fs.save(&abs_path, &text, line_ending).await }
-        });
+    fn write_binary_file(
+        &self,
+        path: Arc<RelPath>,
+        content: Vec<u8>,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<Arc<File>>> {
+        let fs = self.fs.clone();
+        let abs_path = self.absolutize(&path);
+        let write = cx.background_spawn(async move { fs.write(&abs_path, &content).await });
+        self.refresh_written_file(path, write, cx)
+    }
+
+    /// Refreshes the entry of the file at `path` once `write` has written it.
+    fn refresh_written_file(
+        &self,
+        path: Arc<RelPath>,
+        write: Task<Result<()>>,
+        cx: &Context<Worktree>,
+    ) -> Task<Result<Arc<File>>> {
+        let fs = self.fs.clone();
+        let is_private = self.is_path_private(&path);
+        let abs_path = self.absolutize(&path);
 
         cx.spawn(async move |this, cx| {
             write.await?;
@@ -15,6 +15,54 @@ pub struct WorktreeSettings {
     pub file_scan_inclusions: PathMatcher,
     pub file_scan_exclusions: PathMatcher,
     pub private_files: PathMatcher,
+    pub read_only_files: PathMatcher,
+    pub read_only_outside_project: bool,
+    pub case_insensitive_paths: bool,
+    pub file_heuristics: FileHeuristics,
+}
+
+/// How many bytes at the start of a file are searched for NUL bytes, like Git does.
+const BINARY_DETECTION_LEN: usize = 8000;
+/// How many lines at the start of a file are searched for markers of generated files.
+pub const GENERATED_MARKER_LINES: usize = 10;
+
+/// How opened files are detected as binary or generated.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FileHeuristics {
+    pub max_file_size: u64,
+    pub detect_binary: bool,
+    pub generated_markers: Vec<String>,
+    pub generated_files: PathMatcher,
+    pub preview_generated_files: bool,
+}
+
+impl FileHeuristics {
+    pub fn is_too_large(&self, size: u64) -> bool {
+        size > self.max_file_size
+    }
+
+    pub fn is_binary(&self, content: &[u8]) -> bool {
+        self.detect_binary && content[..content.len().min(BINARY_DETECTION_LEN)].contains(&0)
+    }
+
+    pub fn is_generated(&self, path: &RelPath, text: &str) -> bool {
+        self.generated_files.is_match(path.as_std_path())
+            || text.lines().take(GENERATED_MARKER_LINES).any(|line| {
+                self.generated_markers
+                    .iter()
+                    .any(|marker| line.contains(marker.as_str()))
+            })
+            || is_source_map(text)
+    }
+}
+
+/// Whether `text` contains only a source map, whatever the file is named.
+fn is_source_map(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with('{')
+        && text.ends_with('}')
+        && text.contains("\"mappings\"")
+        && text.contains("\"sources\"")
 }
 
 impl WorktreeSettings {
@@ -23,6 +71,11 @@ impl WorktreeSettings {
             .any(|ancestor| self.private_files.is_match(ancestor.as_std_path()))
     }
 
+    pub fn is_path_read_only(&self, path: &RelPath) -> bool {
+        path.ancestors()
+            .any(|ancestor| self.read_only_files.is_match(ancestor.as_std_path()))
+    }
+
     pub fn is_path_excluded(&self, path: &RelPath) -> bool {
         path.ancestors()
             .any(|ancestor| self.file_scan_exclusions.is_match(ancestor.as_std_path()))
@@ -40,6 +93,8 @@ impl Settings for WorktreeSettings {
         let file_scan_exclusions = worktree.file_scan_exclusions.unwrap();
         let file_scan_inclusions = worktree.file_scan_inclusions.unwrap();
         let private_files = worktree.private_files.unwrap().0;
+        let read_only_files = worktree.read_only_files.unwrap().0;
+        let file_heuristics = worktree.file_heuristics.unwrap();
         let parsed_file_scan_inclusions: Vec<String> = file_scan_inclusions
             .iter()
             .flat_map(|glob| {
@@ -63,6 +118,29 @@ impl Settings for WorktreeSettings {
             private_files: path_matchers(private_files, "private_files")
                 .log_err()
                 .unwrap_or_default(),
+            read_only_files: path_matchers(read_only_files, "read_only_files")
+                .log_err()
+                .unwrap_or_default(),
+            read_only_outside_project: worktree.read_only_outside_project.unwrap(),
+            case_insensitive_paths: worktree.case_insensitive_paths.unwrap(),
+            file_heuristics: FileHeuristics {
+                max_file_size: file_heuristics.max_file_size.unwrap(),
+                detect_binary: file_heuristics.detect_binary.unwrap(),
+                generated_markers: file_heuristics
+                    .generated_markers
+                    .unwrap()
+                    .0
+                    .into_iter()
+                    .filter(|marker| !marker.is_empty())
+                    .collect(),
+                generated_files: path_matchers(
+                    file_heuristics.generated_files.unwrap().0,
+                    "generated_files",
+                )
+                .log_err()
+                .unwrap_or_default(),
+                preview_generated_files: file_heuristics.preview_generated_files.unwrap(),
+            },
         }
     }
 
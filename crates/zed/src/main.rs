@@ -39,6 +39,7 @@ use std::{
     path::{Path, PathBuf},
     process,
     sync::Arc,
+    time::Instant,
 };
 use theme::{
     ActiveTheme, IconThemeNotFoundError, SystemAppearance, ThemeNotFoundError, ThemeRegistry,
@@ -48,13 +49,13 @@ use util::{ResultExt, TryFutureExt, maybe};
 use uuid::Uuid;
 use workspace::{
     AppState, PathList, SerializedWorkspaceLocation, Toast, Workspace, WorkspaceSettings,
-    WorkspaceStore, notifications::NotificationId,
+    WorkspaceStore, WorkspaceTabTarget, notifications::NotificationId,
 };
 use zed::{
     OpenListener, OpenRequest, RawOpenRequest, app_menus, build_window_options,
     derive_paths_with_position, edit_prediction_registry, handle_cli_connection,
     handle_keymap_file_changes, handle_settings_changed, handle_settings_file_changes,
-    initialize_workspace, open_paths_with_positions,
+    initialize_workspace, open_paths_with_positions, startup_timings::StartupTimings,
 };
 
 use crate::zed::OpenRequestKind;
@@ -165,6 +166,8 @@ fn fail_to_open_window(e: anyhow::Error, _cx: &mut App) {
 }
 
 pub fn main() {
+    let started_at = Instant::now();
+
     #[cfg(unix)]
     util::prevent_root_execution();
 
@@ -371,6 +374,9 @@ pub fn main() {
     });
 
     app.run(move |cx| {
+        StartupTimings::init(started_at, cx);
+        StartupTimings::record("launch application", started_at, cx);
+
         menu::init();
         zed_actions::init();
 
@@ -379,6 +385,7 @@ pub fn main() {
         if let Some(app_commit_sha) = app_commit_sha {
             AppCommitSha::set_global(app_commit_sha, cx);
         }
+        let settings_started_at = Instant::now();
         settings::init(cx);
         zlog_settings::init(cx);
         handle_settings_file_changes(
@@ -389,6 +396,7 @@ pub fn main() {
         );
         handle_keymap_file_changes(user_keymap_file_rx, cx);
         client::init_settings(cx);
+        StartupTimings::record("initialize settings", settings_started_at, cx);
         let user_agent = format!(
             "Zed/{} ({}; {})",
             AppVersion::global(cx),
@@ -447,8 +455,10 @@ pub fn main() {
         let node_runtime = NodeRuntime::new(client.http_client(), Some(shell_env_loaded_rx), rx);
 
         debug_adapter_extension::init(extension_host_proxy.clone(), cx);
+        let languages_started_at = Instant::now();
         language::init(cx);
         languages::init(languages.clone(), fs.clone(), node_runtime.clone(), cx);
+        StartupTimings::record("register languages", languages_started_at, cx);
         let user_store = cx.new(|cx| UserStore::new(client.clone(), cx));
         let workspace_store = cx.new(|cx| WorkspaceStore::new(client.clone(), cx));
 
@@ -526,6 +536,7 @@ pub fn main() {
             cx,
         );
 
+        let themes_started_at = Instant::now();
         SystemAppearance::init(cx);
         theme::init(theme::LoadThemes::All(Box::new(Assets)), cx);
         theme_extension::init(
@@ -533,6 +544,7 @@ pub fn main() {
             ThemeRegistry::global(cx),
             cx.background_executor().clone(),
         );
+        StartupTimings::record("load themes", themes_started_at, cx);
         command_palette::init(cx);
         let copilot_language_server_id = app_state.languages.next_language_server_id();
         copilot::init(
@@ -543,6 +555,7 @@ pub fn main() {
             cx,
         );
         supermaven::init(app_state.client.clone(), cx);
+        let agent_started_at = Instant::now();
         language_model::init(app_state.client.clone(), cx);
         language_models::init(app_state.user_store.clone(), app_state.client.clone(), cx);
         agent_settings::init(cx);
@@ -551,6 +564,7 @@ pub fn main() {
         web_search::init(cx);
         web_search_providers::init(app_state.client.clone(), cx);
         snippet_provider::init(cx);
+        custom_edit_prediction::init(cx);
         edit_prediction_registry::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         let prompt_builder = PromptBuilder::load(app_state.fs.clone(), stdout_is_a_pty(), cx);
         agent_ui::init(
@@ -562,7 +576,9 @@ pub fn main() {
             cx,
         );
         assistant_tools::init(app_state.client.http_client(), cx);
+        StartupTimings::record("initialize agent", agent_started_at, cx);
         repl::init(app_state.fs.clone(), cx);
+        let extensions_started_at = Instant::now();
         extension_host::init(
             extension_host_proxy,
             app_state.fs.clone(),
@@ -570,27 +586,35 @@ pub fn main() {
             app_state.node_runtime.clone(),
             cx,
         );
+        StartupTimings::record("load extensions", extensions_started_at, cx);
         recent_projects::init(cx);
 
         load_embedded_fonts(cx);
 
         app_state.languages.set_theme(cx.theme().clone());
+        let ui_started_at = Instant::now();
         editor::init(cx);
         image_viewer::init(cx);
+        csv_viewer::init(cx);
+        hex_editor::init(cx);
+        pdf_viewer::init(cx);
         repl::notebook::init(cx);
         diagnostics::init(cx);
 
         audio::init(cx);
+        audio_cues::init(cx);
         workspace::init(app_state.clone(), cx);
         ui_prompt::init(cx);
 
         go_to_line::init(cx);
+        color_picker::init(cx);
         file_finder::init(cx);
         tab_switcher::init(cx);
         outline::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        todo_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -598,6 +622,11 @@ pub fn main() {
         vim::init(cx);
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
+        scratch::init(cx);
+        crash_recovery::init(cx);
+        local_history::init(cx);
+        bookmarks::init(cx);
+        semantic_index::init(cx);
         language_selector::init(cx);
         line_ending_selector::init(cx);
         toolchain_selector::init(cx);
@@ -618,6 +647,7 @@ pub fn main() {
         zeta::init(cx);
         inspector_ui::init(app_state.clone(), cx);
         json_schema_store::init(cx);
+        StartupTimings::record("initialize editor and UI", ui_started_at, cx);
 
         cx.observe_global::<SettingsStore>({
             let fs = fs.clone();
@@ -713,9 +743,15 @@ pub fn main() {
                 cx.spawn({
                     let app_state = app_state.clone();
                     async move |cx| {
+                        let restore_started_at = Instant::now();
                         if let Err(e) = restore_or_create_workspace(app_state, cx).await {
                             fail_to_open_window_async(e, cx)
                         }
+                        cx.update(|cx| {
+                            StartupTimings::record("open workspace", restore_started_at, cx);
+                            StartupTimings::finish(cx);
+                        })
+                        .ok();
                     }
                 })
                 .detach();
@@ -998,20 +1034,34 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
         let mut results: Vec<Result<(), Error>> = Vec::new();
         let mut tasks = Vec::new();
 
-        for (index, (location, paths)) in locations.into_iter().enumerate() {
+        for (index, (location, tabs)) in locations.into_iter().enumerate() {
             match location {
                 SerializedWorkspaceLocation::Local => {
                     let app_state = app_state.clone();
                     let task = cx.spawn(async move |cx| {
-                        let open_task = cx.update(|cx| {
-                            workspace::open_paths(
-                                &paths.paths(),
-                                app_state,
-                                workspace::OpenOptions::default(),
-                                cx,
-                            )
-                        })?;
-                        open_task.await.map(|_| ())
+                        // The first workspace gets a window of its own, and the others that were
+                        // workspace tabs of the same window are added to it as tabs again.
+                        let mut tab_window = None;
+                        for paths in tabs {
+                            let workspace_tab = match tab_window {
+                                Some(window) => WorkspaceTabTarget::Window(window),
+                                None => WorkspaceTabTarget::NewWindow,
+                            };
+                            let open_task = cx.update(|cx| {
+                                workspace::open_paths(
+                                    &paths.paths(),
+                                    app_state.clone(),
+                                    workspace::OpenOptions {
+                                        workspace_tab,
+                                        ..Default::default()
+                                    },
+                                    cx,
+                                )
+                            })?;
+                            let (window, _) = open_task.await?;
+                            tab_window.get_or_insert(window);
+                        }
+                        anyhow::Ok(())
                     });
 
                     // If we're using system window tabs and this is the first workspace,
@@ -1024,6 +1074,7 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
                 }
                 SerializedWorkspaceLocation::Remote(mut connection_options) => {
                     let app_state = app_state.clone();
+                    let paths = tabs.into_iter().next().unwrap_or_default();
                     if let RemoteConnectionOptions::Ssh(options) = &mut connection_options {
                         cx.update(|cx| {
                             SshSettings::get_global(cx)
@@ -1115,10 +1166,11 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
     Ok(())
 }
 
+/// The workspaces to restore on startup, grouped by the window they're restored in.
 pub(crate) async fn restorable_workspace_locations(
     cx: &mut AsyncApp,
     app_state: &Arc<AppState>,
-) -> Option<Vec<(SerializedWorkspaceLocation, PathList)>> {
+) -> Option<Vec<(SerializedWorkspaceLocation, Vec<PathList>)>> {
     let mut restore_behavior = cx
         .update(|cx| WorkspaceSettings::get(None, cx).restore_on_startup)
         .ok()?;
@@ -1148,7 +1200,7 @@ pub(crate) async fn restorable_workspace_locations(
         workspace::RestoreOnStartupBehavior::LastWorkspace => {
             workspace::last_opened_workspace_location()
                 .await
-                .map(|location| vec![location])
+                .map(|(location, paths)| vec![(location, vec![paths])])
         }
         workspace::RestoreOnStartupBehavior::LastSession => {
             if let Some(last_session_id) = last_session_id {
@@ -1166,7 +1218,7 @@ pub(crate) async fn restorable_workspace_locations(
                     locations.reverse();
                 }
 
-                locations
+                locations.map(workspace::group_workspaces_by_window)
             } else {
                 None
             }
@@ -6,6 +6,7 @@ pub(crate) mod mac_only_instance;
 mod migrate;
 mod open_listener;
 mod quick_action_bar;
+pub mod startup_timings;
 #[cfg(target_os = "windows")]
 pub(crate) mod windows_only_instance;
 
@@ -22,15 +23,20 @@ use editor::ProposedChangesEditorToolbar;
 use editor::{Editor, MultiBuffer};
 use feature_flags::{FeatureFlagAppExt, PanicFeatureFlag};
 use futures::future::Either;
-use futures::{StreamExt, channel::mpsc, select_biased};
+use futures::{
+    StreamExt,
+    channel::{mpsc, oneshot},
+    select_biased,
+};
 use git_ui::git_panel::GitPanel;
 use git_ui::project_diff::ProjectDiffToolbar;
 use gpui::{
-    Action, App, AppContext as _, Context, DismissEvent, Element, Entity, Focusable, KeyBinding,
-    ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString, Styled, Task,
-    TitlebarOptions, UpdateGlobal, Window, WindowKind, WindowOptions, actions, image_cache, point,
-    px, retain_all,
+    Action, App, AppContext as _, AsyncApp, Context, DismissEvent, Element, Entity, Focusable,
+    KeyBinding, ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString, Styled,
+    Task, TitlebarOptions, UpdateGlobal, Window, WindowKind, WindowOptions, actions, image_cache,
+    point, px, retain_all,
 };
+use hex_editor::HexSelectionInfo;
 use image_viewer::ImageInfo;
 use language::Capability;
 use language_onboarding::BasedPyrightBanner;
@@ -60,6 +66,7 @@ use settings::{
     initial_local_debug_tasks_content, initial_project_settings_content, initial_tasks_content,
     update_settings_file,
 };
+use startup_timings::StartupTimings;
 use std::time::Duration;
 use std::{
     borrow::Cow,
@@ -186,6 +193,18 @@ pub fn init(cx: &mut App) {
             );
         });
     });
+    cx.on_action(|_: &zed_actions::dev::OpenStartupReport, cx| {
+        with_active_or_new_workspace(cx, |workspace, window, cx| {
+            open_bundled_file(
+                workspace,
+                StartupTimings::report(cx).into(),
+                "Startup Report",
+                "Markdown",
+                window,
+                cx,
+            );
+        });
+    });
     cx.on_action(|_: &zed_actions::OpenTelemetryLog, cx| {
         with_active_or_new_workspace(cx, |workspace, window, cx| {
             open_telemetry_log_file(workspace, window, cx);
@@ -399,8 +418,14 @@ pub fn initialize_workspace(
             cx.new(|_| language_selector::ActiveBufferLanguage::new(workspace));
         let active_toolchain_language =
             cx.new(|cx| toolchain_selector::ActiveToolchain::new(workspace, window, cx));
+        let active_cargo_options =
+            cx.new(|cx| toolchain_selector::ActiveCargoOptions::new(workspace, cx));
         let vim_mode_indicator = cx.new(|cx| vim::ModeIndicator::new(window, cx));
         let image_info = cx.new(|_cx| ImageInfo::new(workspace));
+        let read_only_indicator = cx.new(|_| editor::read_only_indicator::ReadOnlyIndicator::new());
+        let hex_selection_info = cx.new(|_cx| HexSelectionInfo::new(workspace));
+        let watch_task_status =
+            cx.new(|cx| terminal_view::watch_tasks::WatchTaskStatus::new(workspace, window, cx));
 
         let lsp_button_menu_handle = PopoverMenuHandle::default();
         let lsp_button =
@@ -413,17 +438,29 @@ pub fn initialize_workspace(
 
         let cursor_position =
             cx.new(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
+        let left_extension_status_items = cx.new(|cx| {
+            extensions_ui::ExtensionStatusItems::new(extension::StatusItemPosition::Left, cx)
+        });
+        let right_extension_status_items = cx.new(|cx| {
+            extensions_ui::ExtensionStatusItems::new(extension::StatusItemPosition::Right, cx)
+        });
         workspace.status_bar().update(cx, |status_bar, cx| {
             status_bar.add_left_item(search_button, window, cx);
             status_bar.add_left_item(lsp_button, window, cx);
             status_bar.add_left_item(diagnostic_summary, window, cx);
             status_bar.add_left_item(activity_indicator, window, cx);
+            status_bar.add_left_item(watch_task_status, window, cx);
+            status_bar.add_left_item(left_extension_status_items, window, cx);
+            status_bar.add_right_item(right_extension_status_items, window, cx);
             status_bar.add_right_item(edit_prediction_button, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
             status_bar.add_right_item(active_toolchain_language, window, cx);
+            status_bar.add_right_item(active_cargo_options, window, cx);
             status_bar.add_right_item(vim_mode_indicator, window, cx);
+            status_bar.add_right_item(read_only_indicator, window, cx);
             status_bar.add_right_item(cursor_position, window, cx);
             status_bar.add_right_item(image_info, window, cx);
+            status_bar.add_right_item(hex_selection_info, window, cx);
         });
 
         let handle = cx.entity().downgrade();
@@ -558,47 +595,86 @@ fn show_software_emulation_warning_if_needed(
     }
 }
 
+/// How long to wait for a window to be drawn before loading the panels that aren't loaded first.
+const DEFERRED_PANELS_TIMEOUT: Duration = Duration::from_secs(1);
+
 fn initialize_panels(
     prompt_builder: Arc<PromptBuilder>,
     window: &mut Window,
     cx: &mut Context<Workspace>,
 ) {
     cx.spawn_in(window, async move |workspace_handle, cx| {
-        let project_panel = ProjectPanel::load(workspace_handle.clone(), cx.clone());
-        let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
-        let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
-        let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
-        let channels_panel =
-            collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
-        let notification_panel = collab_ui::notification_panel::NotificationPanel::load(
-            workspace_handle.clone(),
-            cx.clone(),
+        let app = AsyncApp::clone(cx);
+
+        // The panels that are most often shown when a workspace opens are loaded first.
+        let project_panel = StartupTimings::measure(
+            "load project panel",
+            &app,
+            ProjectPanel::load(workspace_handle.clone(), cx.clone()),
+        );
+        let outline_panel = StartupTimings::measure(
+            "load outline panel",
+            &app,
+            OutlinePanel::load(workspace_handle.clone(), cx.clone()),
+        );
+        let terminal_panel = StartupTimings::measure(
+            "load terminal panel",
+            &app,
+            TerminalPanel::load(workspace_handle.clone(), cx.clone()),
         );
-        let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
-
-        let (
-            project_panel,
-            outline_panel,
-            terminal_panel,
-            git_panel,
-            channels_panel,
-            notification_panel,
-            debug_panel,
-        ) = futures::try_join!(
-            project_panel,
-            outline_panel,
-            git_panel,
-            terminal_panel,
-            channels_panel,
-            notification_panel,
-            debug_panel,
-        )?;
+        let git_panel = StartupTimings::measure(
+            "load git panel",
+            &app,
+            GitPanel::load(workspace_handle.clone(), cx.clone()),
+        );
+
+        let (project_panel, outline_panel, terminal_panel, git_panel) =
+            futures::try_join!(project_panel, outline_panel, terminal_panel, git_panel)?;
 
         workspace_handle.update_in(cx, |workspace, window, cx| {
             workspace.add_panel(project_panel, window, cx);
             workspace.add_panel(outline_panel, window, cx);
             workspace.add_panel(terminal_panel, window, cx);
             workspace.add_panel(git_panel, window, cx);
+        })?;
+
+        // The rest are loaded once the window has been drawn, so that they don't delay it. A
+        // panel that was open when the workspace was closed is reopened when it's added.
+        if !cfg!(test) {
+            let (drawn_tx, drawn_rx) = oneshot::channel();
+            cx.update(|window, _| {
+                window.on_next_frame(move |_, _| {
+                    drawn_tx.send(()).ok();
+                })
+            })?;
+            // Windows that aren't drawn, such as minimized ones, still get their panels.
+            let timeout = cx.background_executor().timer(DEFERRED_PANELS_TIMEOUT);
+            futures::future::select(drawn_rx, timeout).await;
+        }
+
+        let channels_panel = StartupTimings::measure(
+            "load collab panel",
+            &app,
+            collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone()),
+        );
+        let notification_panel = StartupTimings::measure(
+            "load notification panel",
+            &app,
+            collab_ui::notification_panel::NotificationPanel::load(
+                workspace_handle.clone(),
+                cx.clone(),
+            ),
+        );
+        let debug_panel = StartupTimings::measure(
+            "load debug panel",
+            &app,
+            DebugPanel::load(workspace_handle.clone(), cx),
+        );
+
+        let (channels_panel, notification_panel, debug_panel) =
+            futures::try_join!(channels_panel, notification_panel, debug_panel)?;
+
+        workspace_handle.update_in(cx, |workspace, window, cx| {
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
             workspace.add_panel(debug_panel, window, cx);
@@ -606,9 +682,12 @@ fn initialize_panels(
 
         let is_assistant2_enabled = !cfg!(test);
         let agent_panel = if is_assistant2_enabled {
-            let agent_panel =
-                agent_ui::AgentPanel::load(workspace_handle.clone(), prompt_builder, cx.clone())
-                    .await?;
+            let agent_panel = StartupTimings::measure(
+                "load agent panel",
+                &app,
+                agent_ui::AgentPanel::load(workspace_handle.clone(), prompt_builder, cx.clone()),
+            )
+            .await?;
 
             Some(agent_panel)
         } else {
@@ -634,7 +713,9 @@ fn initialize_panels(
 
                 workspace
                     .register_action(agent_ui::AgentPanel::toggle_focus)
-                    .register_action(agent_ui::InlineAssistant::inline_assist);
+                    .register_action(agent_ui::InlineAssistant::inline_assist)
+                    .register_action(agent_ui::InlineAssistant::run_rule)
+                    .register_action(agent_ui::InlineAssistant::explain_terminal_command);
             }
         })?;
 
@@ -827,6 +908,15 @@ fn register_actions(
                 }
             }
         })
+        .register_action(|_, _: &zed_actions::ZoomIn, _window, cx| {
+            theme::adjust_zoom(cx, px(1.0));
+        })
+        .register_action(|_, _: &zed_actions::ZoomOut, _window, cx| {
+            theme::adjust_zoom(cx, px(-1.0));
+        })
+        .register_action(|_, _: &zed_actions::ResetZoom, _window, cx| {
+            theme::reset_zoom(cx);
+        })
         .register_action(|_, _: &install_cli::RegisterZedScheme, window, cx| {
             cx.spawn_in(window, async move |workspace, cx| {
                 install_cli::register_zed_scheme(cx).await?;
@@ -2732,6 +2822,111 @@ mod tests {
         });
     }
 
+    #[cfg(not(target_os = "macos"))]
+    #[gpui::test]
+    async fn test_workspace_tabs(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.workspace.workspace_tabs = Some(true);
+                });
+            });
+        });
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                path!("/root"),
+                json!({
+                    "a": { "a.txt": "" },
+                    "b": { "b.txt": "" },
+                }),
+            )
+            .await;
+
+        let open = |path: &str, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                open_paths(
+                    &[PathBuf::from(path)],
+                    app_state.clone(),
+                    OpenOptions::default(),
+                    cx,
+                )
+            })
+        };
+        let tab_names = |window: WindowHandle<Workspace>, cx: &mut TestAppContext| {
+            cx.update_window(window.into(), |_, window, cx| {
+                let (workspaces, active_ix) = workspace::workspace_tabs(window, cx);
+                let names = workspaces
+                    .iter()
+                    .map(|workspace| workspace.read(cx).project_name(cx))
+                    .collect::<Vec<_>>();
+                (names, active_ix)
+            })
+            .unwrap()
+        };
+
+        // Opening a folder adds it as a tab of the active window.
+        let (window, _) = open(path!("/root/a"), cx).await.unwrap();
+        let (tab_window, _) = open(path!("/root/b"), cx).await.unwrap();
+        cx.run_until_parked();
+        assert_eq!(tab_window, window);
+        assert_eq!(cx.update(|cx| cx.windows().len()), 1);
+        assert_eq!(tab_names(window, cx), (vec!["a".into(), "b".into()], 1));
+        window
+            .read_with(cx, |workspace, cx| {
+                assert_eq!(workspace.project_name(cx), "b");
+            })
+            .unwrap();
+
+        // Switching tabs makes the other workspace the window's root.
+        cx.update_window(window.into(), |_, window, cx| {
+            workspace::activate_workspace_tab(0, window, cx);
+        })
+        .unwrap();
+        assert_eq!(tab_names(window, cx), (vec!["a".into(), "b".into()], 0));
+        window
+            .read_with(cx, |workspace, cx| {
+                assert_eq!(workspace.project_name(cx), "a");
+            })
+            .unwrap();
+
+        // Opening a file of a background tab's project shows that tab instead of a new one.
+        open(path!("/root/b/b.txt"), cx).await.unwrap();
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.windows().len()), 1);
+        assert_eq!(tab_names(window, cx), (vec!["a".into(), "b".into()], 1));
+        window
+            .read_with(cx, |workspace, cx| {
+                let active_path = workspace
+                    .active_item(cx)
+                    .and_then(|item| item.project_path(cx))
+                    .unwrap();
+                assert_eq!(active_path.path.as_ref(), rel_path("b.txt"));
+            })
+            .unwrap();
+
+        // Closing a tab keeps the window open with the remaining tab.
+        window
+            .update(cx, |workspace, window, cx| {
+                workspace.close_window(&CloseWindow, window, cx);
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.windows().len()), 1);
+        assert_eq!(tab_names(window, cx), (vec!["a".into()], 0));
+
+        // Closing the last tab closes the window.
+        window
+            .update(cx, |workspace, window, cx| {
+                workspace.close_window(&CloseWindow, window, cx);
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.windows().len()), 0);
+    }
+
     #[gpui::test]
     async fn test_open_paths(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
@@ -4491,6 +4686,7 @@ mod tests {
                 "assistant",
                 "assistant2",
                 "auto_update",
+                "bookmarks",
                 "branches",
                 "buffer_search",
                 "channel_modal",
@@ -4498,9 +4694,11 @@ mod tests {
                 "client",
                 "collab",
                 "collab_panel",
+                "color_picker",
                 "command_palette",
                 "console",
                 "context_server",
+                "csv_viewer",
                 "copilot",
                 "debug_panel",
                 "debugger",
@@ -4508,18 +4706,21 @@ mod tests {
                 "diagnostics",
                 "edit_prediction",
                 "editor",
+                "extension_panel",
                 "feedback",
                 "file_finder",
                 "git",
                 "git_onboarding",
                 "git_panel",
                 "go_to_line",
+                "hex_editor",
                 "icon_theme_selector",
                 "journal",
                 "keymap_editor",
                 "keystroke_input",
                 "language_selector",
                 "line_ending",
+                "local_history",
                 "lsp_tool",
                 "markdown",
                 "menu",
@@ -4530,13 +4731,16 @@ mod tests {
                 "outline_panel",
                 "pane",
                 "panel",
+                "pdf_viewer",
                 "picker",
                 "project_panel",
                 "project_search",
                 "project_symbols",
                 "projects",
+                "remote",
                 "repl",
                 "rules_library",
+                "scratch",
                 "search",
                 "settings_profile_selector",
                 "snippets",
@@ -4550,6 +4754,7 @@ mod tests {
                 "terminal_panel",
                 "theme_selector",
                 "toast",
+                "todo_panel",
                 "toolchain",
                 "variable_list",
                 "vim",
@@ -4678,6 +4883,7 @@ mod tests {
             vim_mode_setting::init(cx);
             theme::init(theme::LoadThemes::JustBase, cx);
             audio::init(cx);
+            audio_cues::init(cx);
             channel::init(&app_state.client, app_state.user_store.clone(), cx);
             call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
             notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -4691,6 +4897,7 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            todo_panel::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
@@ -4699,6 +4906,13 @@ mod tests {
                 cx,
             );
             image_viewer::init(cx);
+            csv_viewer::init(cx);
+            hex_editor::init(cx);
+            pdf_viewer::init(cx);
+            scratch::init(cx);
+            crash_recovery::init(cx);
+            local_history::init(cx);
+            bookmarks::init(cx);
             language_model::init(app_state.client.clone(), cx);
             language_models::init(app_state.user_store.clone(), app_state.client.clone(), cx);
             web_search::init(cx);
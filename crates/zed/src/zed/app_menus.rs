@@ -149,6 +149,10 @@ pub fn app_menus() -> Vec<Menu> {
                     },
                 ),
                 MenuItem::action("Select All Occurrences", editor::actions::SelectAllMatches),
+                MenuItem::action(
+                    "Select All Occurrences in Scope",
+                    editor::actions::SelectAllOccurrencesInScope,
+                ),
                 MenuItem::separator(),
                 MenuItem::action("Move Line Up", editor::actions::MoveLineUp),
                 MenuItem::action("Move Line Down", editor::actions::MoveLineDown),
@@ -253,6 +257,11 @@ pub fn app_menus() -> Vec<Menu> {
                 MenuItem::action("Minimize", super::Minimize),
                 MenuItem::action("Zoom", super::Zoom),
                 MenuItem::separator(),
+                MenuItem::action("Show Previous Tab", title_bar::ShowPreviousWindowTab),
+                MenuItem::action("Show Next Tab", title_bar::ShowNextWindowTab),
+                MenuItem::action("Move Tab to New Window", title_bar::MoveTabToNewWindow),
+                MenuItem::action("Merge All Windows", title_bar::MergeAllWindows),
+                MenuItem::separator(),
             ],
         },
         Menu {
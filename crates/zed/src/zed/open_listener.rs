@@ -13,7 +13,7 @@ use futures::channel::{mpsc, oneshot};
 use futures::future::join_all;
 use futures::{FutureExt, SinkExt, StreamExt};
 use git_ui::file_diff_view::FileDiffView;
-use gpui::{App, AsyncApp, Global, WindowHandle};
+use gpui::{App, AppContext as _, AsyncApp, BackgroundExecutor, Global, WindowHandle};
 use language::Point;
 use onboarding::FIRST_OPEN;
 use onboarding::show_onboarding_view;
@@ -25,7 +25,7 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 use util::ResultExt;
-use util::paths::PathWithPosition;
+use util::paths::{PathWithPosition, parse_wsl_share_path};
 use workspace::PathList;
 use workspace::item::ItemHandle;
 use workspace::{AppState, OpenOptions, SerializedWorkspaceLocation, Workspace};
@@ -102,9 +102,41 @@ impl OpenRequest {
             }
         }
 
+        if cfg!(target_os = "windows")
+            && this.remote_connection.is_none()
+            && this.diff_paths.is_empty()
+        {
+            this.parse_wsl_share_paths();
+        }
+
         Ok(this)
     }
 
+    /// Opens paths in a WSL distro's share, like `\\wsl.localhost\Ubuntu\home\me`, inside that
+    /// distro, so that they're edited with the distro's toolchain rather than through the share.
+    fn parse_wsl_share_paths(&mut self) {
+        let Some(paths) = self
+            .open_paths
+            .iter()
+            .map(|path| parse_wsl_share_path(path))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        let Some((distro_name, _)) = paths.first() else {
+            return;
+        };
+        if paths.iter().any(|(distro, _)| distro != distro_name) {
+            return;
+        }
+
+        self.remote_connection = Some(RemoteConnectionOptions::Wsl(WslConnectionOptions {
+            distro_name: distro_name.clone(),
+            user: None,
+        }));
+        self.open_paths = paths.into_iter().map(|(_, path)| path).collect();
+    }
+
     fn parse_file_path(&mut self, file: &str) {
         if let Some(decoded) = urlencoding::decode(file).log_err() {
             self.open_paths.push(decoded.into_owned())
@@ -326,6 +358,7 @@ pub async fn handle_cli_connection(
                 paths,
                 diff_paths,
                 wait,
+                stdin,
                 wsl,
                 open_new_workspace,
                 env,
@@ -359,17 +392,30 @@ pub async fn handle_cli_connection(
                     return;
                 }
 
-                let open_workspace_result = open_workspaces(
-                    paths,
-                    diff_paths,
-                    open_new_workspace,
-                    &responses,
-                    wait,
-                    app_state.clone(),
-                    env,
-                    cx,
-                )
-                .await;
+                let open_workspace_result = if let Some(text) = stdin {
+                    open_stdin_buffer(
+                        text,
+                        open_new_workspace,
+                        &responses,
+                        wait,
+                        app_state.clone(),
+                        env,
+                        cx,
+                    )
+                    .await
+                } else {
+                    open_workspaces(
+                        paths,
+                        diff_paths,
+                        open_new_workspace,
+                        &responses,
+                        wait,
+                        app_state.clone(),
+                        env,
+                        cx,
+                    )
+                    .await
+                };
 
                 let status = if open_workspace_result.is_err() { 1 } else { 0 };
                 responses.send(CliResponse::Exit { status }).log_err();
@@ -396,6 +442,11 @@ async fn open_workspaces(
             restorable_workspace_locations(cx, &app_state)
                 .await
                 .unwrap_or_default()
+                .into_iter()
+                .flat_map(|(location, tabs)| {
+                    tabs.into_iter().map(move |paths| (location.clone(), paths))
+                })
+                .collect()
         }
     } else {
         vec![(
@@ -555,24 +606,8 @@ async fn open_local_workspace(
                     } else {
                         let _ = futures::future::try_join_all(item_release_futures).await;
                     };
-                }
-                .fuse();
-
-                futures::pin_mut!(wait);
-
-                loop {
-                    // Repeatedly check if CLI is still open to avoid wasting resources
-                    // waiting for files or workspaces to close.
-                    let mut timer = background.timer(Duration::from_secs(1)).fuse();
-                    futures::select_biased! {
-                        _ = wait => break,
-                        _ = timer => {
-                            if responses.send(CliResponse::Ping).is_err() {
-                                break;
-                            }
-                        }
-                    }
-                }
+                };
+                wait_while_cli_is_open(wait, responses, &background).await;
             }
         }
         Err(error) => {
@@ -587,6 +622,80 @@ async fn open_local_workspace(
     errored
 }
 
+/// Opens the text that was piped to the CLI in a new, unsaved buffer, in the first local
+/// workspace unless a new workspace was requested.
+async fn open_stdin_buffer(
+    text: String,
+    open_new_workspace: Option<bool>,
+    responses: &IpcSender<CliResponse>,
+    wait: bool,
+    app_state: Arc<AppState>,
+    env: Option<collections::HashMap<String, String>>,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    let existing_window = if open_new_workspace == Some(true) {
+        None
+    } else {
+        cx.update(|cx| workspace::local_workspace_windows(cx).into_iter().next())?
+    };
+    let window = match existing_window {
+        Some(window) => window,
+        None => {
+            cx.update(|cx| Workspace::new_local(Vec::new(), app_state, None, env, cx))?
+                .await?
+                .0
+        }
+    };
+
+    let editor = window.update(cx, |workspace, window, cx| {
+        window.activate_window();
+        let project = workspace.project().clone();
+        let buffer = project.update(cx, |project, cx| {
+            project.create_local_buffer(&text, None, true, cx)
+        });
+        let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project), window, cx));
+        workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+        editor
+    })?;
+
+    if wait {
+        let (released_tx, released_rx) = oneshot::channel();
+        let _subscription = cx.update(|cx| {
+            cx.observe_release(&editor, move |_, _| {
+                let _ = released_tx.send(());
+            })
+        })?;
+        drop(editor);
+        let background = cx.background_executor().clone();
+        wait_while_cli_is_open(released_rx, responses, &background).await;
+    }
+    Ok(())
+}
+
+/// Waits for the future to complete, stopping early if the CLI exits.
+async fn wait_while_cli_is_open(
+    wait: impl Future,
+    responses: &IpcSender<CliResponse>,
+    background: &BackgroundExecutor,
+) {
+    let wait = wait.fuse();
+    futures::pin_mut!(wait);
+
+    loop {
+        // Repeatedly check if CLI is still open to avoid wasting resources
+        // waiting for files or workspaces to close.
+        let mut timer = background.timer(Duration::from_secs(1)).fuse();
+        futures::select_biased! {
+            _ = wait => break,
+            _ = timer => {
+                if responses.send(CliResponse::Ping).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
 pub async fn derive_paths_with_position(
     fs: &dyn Fs,
     path_strings: impl IntoIterator<Item = impl AsRef<str>>,
@@ -647,6 +756,9 @@ mod tests {
                 port_forwards: None,
                 nickname: None,
                 upload_binary_over_ssh: false,
+                proxy_jump: None,
+                forward_agent: false,
+                remote_server_path: None,
             })
         );
         assert_eq!(request.open_paths, vec!["/"]);
@@ -763,6 +875,51 @@ mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_open_stdin_buffer(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let (response_tx, _) = ipc::channel::<CliResponse>().unwrap();
+
+        assert_eq!(cx.windows().len(), 0);
+        cx.spawn({
+            let app_state = app_state.clone();
+            |mut cx| async move {
+                open_stdin_buffer(
+                    "piped text\n".into(),
+                    None,
+                    &response_tx,
+                    false,
+                    app_state,
+                    None,
+                    &mut cx,
+                )
+                .await
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(cx.windows().len(), 1);
+        let workspace = cx.windows()[0].downcast::<Workspace>().unwrap();
+        workspace
+            .update(cx, |workspace, _, cx| {
+                let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+                let editor = editor.read(cx);
+                assert_eq!(editor.text(cx), "piped text\n");
+                assert!(
+                    editor
+                        .buffer()
+                        .read(cx)
+                        .as_singleton()
+                        .unwrap()
+                        .read(cx)
+                        .file()
+                        .is_none()
+                );
+            })
+            .unwrap();
+    }
+
     async fn open_workspace_file(
         path: &str,
         open_new_workspace: Option<bool>,
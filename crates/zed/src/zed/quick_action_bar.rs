@@ -119,6 +119,8 @@ impl Render for QuickActionBar {
         let inline_diagnostics_enabled = editor_value.show_inline_diagnostics();
         let git_blame_inline_enabled = editor_value.git_blame_inline_enabled();
         let show_git_blame_gutter = editor_value.show_git_blame_gutter();
+        let diff_since_save_enabled = editor_value.diff_since_save_enabled();
+        let is_singleton = editor_value.buffer().read(cx).is_singleton();
         let auto_signature_help_enabled = editor_value.auto_signature_help_enabled(cx);
         let show_line_numbers = editor_value.line_numbers_enabled(cx);
         let has_edit_prediction_provider = editor_value.edit_prediction_provider().is_some();
@@ -561,6 +563,29 @@ impl Render for QuickActionBar {
                                 },
                             );
 
+                            if is_singleton {
+                                menu = menu.toggleable_entry(
+                                    "Diff Since Save",
+                                    diff_since_save_enabled,
+                                    IconPosition::Start,
+                                    Some(editor::actions::ToggleDiffSinceSave.boxed_clone()),
+                                    {
+                                        let editor = editor.clone();
+                                        move |window, cx| {
+                                            editor
+                                                .update(cx, |editor, cx| {
+                                                    editor.toggle_diff_since_save(
+                                                        &editor::actions::ToggleDiffSinceSave,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                })
+                                                .ok();
+                                        }
+                                    },
+                                );
+                            }
+
                             menu = menu.separator();
 
                             menu = menu.toggleable_entry(
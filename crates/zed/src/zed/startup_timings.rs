@@ -0,0 +1,172 @@
+use std::{
+    fmt::Write as _,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, AsyncApp, Global};
+
+/// Records how long each part of startup takes, so that the remaining startup costs can be
+/// reported with `dev: open startup report`.
+pub struct StartupTimings {
+    started_at: Instant,
+    /// When the first workspace finished opening. Spans that start later, such as those of
+    /// windows opened afterwards, aren't part of startup.
+    finished_at: Option<Instant>,
+    spans: Vec<StartupSpan>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct StartupSpan {
+    name: String,
+    /// When the span started, relative to the start of the process.
+    start: Duration,
+    duration: Duration,
+}
+
+impl Global for StartupTimings {}
+
+impl StartupTimings {
+    /// Starts recording spans, measuring them from `started_at`, which should be as close to the
+    /// start of the process as possible.
+    pub fn init(started_at: Instant, cx: &mut App) {
+        cx.set_global(Self {
+            started_at,
+            finished_at: None,
+            spans: Vec::new(),
+        });
+    }
+
+    /// Marks the end of startup, once the first workspace has opened.
+    pub fn finish(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            let timings = cx.global_mut::<Self>();
+            timings.finished_at.get_or_insert_with(Instant::now);
+        }
+    }
+
+    /// Records a span that started at `start` and has just finished.
+    pub fn record(name: impl Into<String>, start: Instant, cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.global_mut::<Self>()
+                .push(name.into(), start, Instant::now());
+        }
+    }
+
+    /// Awaits `future`, recording how long it takes.
+    pub async fn measure<R>(
+        name: &'static str,
+        cx: &AsyncApp,
+        future: impl Future<Output = R>,
+    ) -> R {
+        let start = Instant::now();
+        let result = future.await;
+        cx.update(|cx| Self::record(name, start, cx)).ok();
+        result
+    }
+
+    fn push(&mut self, name: String, start: Instant, end: Instant) {
+        if self
+            .finished_at
+            .is_some_and(|finished_at| start > finished_at)
+        {
+            return;
+        }
+        let span = StartupSpan {
+            name,
+            start: start.saturating_duration_since(self.started_at),
+            duration: end.saturating_duration_since(start),
+        };
+        log::debug!(
+            "startup: {} took {:?}, starting at {:?}",
+            span.name,
+            span.duration,
+            span.start
+        );
+        self.spans.push(span);
+    }
+
+    /// Returns a Markdown report of the recorded spans, slowest first.
+    pub fn report(cx: &App) -> String {
+        cx.try_global::<Self>()
+            .map(|timings| timings.render_report())
+            .unwrap_or_default()
+    }
+
+    fn render_report(&self) -> String {
+        let mut spans = self.spans.clone();
+        spans.sort_by(|a, b| b.duration.cmp(&a.duration));
+        let end = self
+            .spans
+            .iter()
+            .map(|span| span.start + span.duration)
+            .max()
+            .unwrap_or_default();
+
+        let mut report = String::from("# Startup Report\n\n");
+        writeln!(
+            report,
+            "The recorded parts of startup finished {} after the process started.\n",
+            format_duration(end)
+        )
+        .ok();
+        report.push_str("| Span | Duration | Started at |\n");
+        report.push_str("| --- | ---: | ---: |\n");
+        for span in spans {
+            writeln!(
+                report,
+                "| {} | {} | {} |",
+                span.name,
+                format_duration(span.duration),
+                format_duration(span.start)
+            )
+            .ok();
+        }
+        report
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_report() {
+        let started_at = Instant::now();
+        let mut timings = StartupTimings {
+            started_at,
+            finished_at: None,
+            spans: Vec::new(),
+        };
+        timings.push(
+            "settings".into(),
+            started_at + Duration::from_millis(5),
+            started_at + Duration::from_millis(7),
+        );
+        timings.push(
+            "languages".into(),
+            started_at + Duration::from_millis(7),
+            started_at + Duration::from_millis(20),
+        );
+        // Spans that start after startup has finished are left out.
+        timings.finished_at = Some(started_at + Duration::from_millis(30));
+        timings.push(
+            "second window".into(),
+            started_at + Duration::from_millis(40),
+            started_at + Duration::from_millis(50),
+        );
+
+        assert_eq!(
+            timings.render_report(),
+            "# Startup Report\n\n\
+             The recorded parts of startup finished 20.0 ms after the process started.\n\n\
+             | Span | Duration | Started at |\n\
+             | --- | ---: | ---: |\n\
+             | languages | 13.0 ms | 7.0 ms |\n\
+             | settings | 2.0 ms | 5.0 ms |\n"
+        );
+    }
+}
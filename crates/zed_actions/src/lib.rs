@@ -52,6 +52,12 @@ actions!(
         OpenLicenses,
         /// Opens the telemetry log.
         OpenTelemetryLog,
+        /// Enlarges the whole interface, including the editor and agent panel fonts.
+        ZoomIn,
+        /// Shrinks the whole interface, including the editor and agent panel fonts.
+        ZoomOut,
+        /// Resets the size of the whole interface to the one in the settings.
+        ResetZoom,
     ]
 );
 
@@ -144,7 +150,14 @@ pub mod dev {
         dev,
         [
             /// Toggles the developer inspector for debugging UI elements.
-            ToggleInspector
+            ToggleInspector,
+            /// Toggles an overlay that shows frame times and the slowest views.
+            TogglePerformanceHud,
+            /// Exports the frames recorded while the performance HUD is shown as a trace for
+            /// `chrome://tracing`.
+            ExportFrameTrace,
+            /// Opens a report of how long each part of startup took.
+            OpenStartupReport,
         ]
     );
 }
@@ -166,6 +179,18 @@ pub mod workspace {
     );
 }
 
+pub mod hex_editor {
+    use gpui::actions;
+
+    actions!(
+        hex_editor,
+        [
+            /// Reopens the active file in the hex editor.
+            ReopenAsHex
+        ]
+    );
+}
+
 pub mod git {
     use gpui::actions;
 
@@ -311,7 +336,9 @@ pub mod assistant {
         assistant,
         [
             /// Shows the assistant configuration panel.
-            ShowConfiguration
+            ShowConfiguration,
+            /// Explains the selected terminal text, or the command on the terminal's prompt line.
+            ExplainTerminalCommand
         ]
     );
 
@@ -331,6 +358,16 @@ pub mod assistant {
     pub struct InlineAssist {
         pub prompt: Option<String>,
     }
+
+    /// Runs a rule from the rules library with the inline assistant, expanding its
+    /// variables for the active editor.
+    #[derive(Clone, Debug, Deserialize, PartialEq, JsonSchema, Action)]
+    #[action(namespace = agent)]
+    #[serde(deny_unknown_fields)]
+    pub struct RunRule {
+        /// The title of the rule to run.
+        pub title: String,
+    }
 }
 
 pub mod debugger {
@@ -436,6 +473,16 @@ pub struct Rerun {
     pub task_id: Option<String>,
 }
 
+/// Reveals the terminal with the output of a task.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = task)]
+#[serde(deny_unknown_fields)]
+pub struct RevealOutput {
+    /// If present, reveal the output of the task with this ID, otherwise of the last task.
+    #[serde(skip)]
+    pub task_id: Option<String>,
+}
+
 pub mod outline {
     use std::sync::OnceLock;
 